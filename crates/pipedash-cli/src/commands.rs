@@ -0,0 +1,469 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{
+    anyhow,
+    bail,
+    Context,
+};
+use pipedash_core::application::ProvisioningService;
+use pipedash_core::domain::{
+    BackupInfo,
+    PlanAction,
+    ProvisioningPlan,
+};
+use pipedash_core::infrastructure::config::{
+    ConfigLoader,
+    KeyringLayout,
+    PipedashConfig,
+    SetupDocument,
+};
+use pipedash_core::infrastructure::secrets::KeyringTokenStore;
+use pipedash_core::{
+    CoreContext,
+    PipelineRun,
+    PipelineStatus,
+    TriggerParams,
+};
+use serde::Serialize;
+
+use crate::output::{
+    format_duration,
+    print_json,
+    print_json_line,
+    print_table,
+    OutputFormat,
+};
+
+#[derive(Debug, Serialize)]
+struct SetupResult<'a> {
+    config_path: String,
+    config_changed: bool,
+    plan: &'a ProvisioningPlan,
+}
+
+#[derive(Debug, Serialize)]
+struct TriggerResult {
+    pipeline_id: String,
+    result: String,
+}
+
+pub fn parse_param(raw: &str) -> Result<(String, String), String> {
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("invalid parameter '{}': expected KEY=VALUE", raw))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(format!("invalid parameter '{}': empty key", raw));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+pub async fn list_pipelines(
+    core: &CoreContext, provider_id: Option<i64>, cached: bool, format: OutputFormat,
+) -> anyhow::Result<()> {
+    let mut pipelines = if cached {
+        core.pipeline_service
+            .get_cached_pipelines(provider_id)
+            .await?
+    } else {
+        core.pipeline_service.fetch_pipelines(provider_id).await?
+    };
+    pipelines.sort_by(|a, b| a.name.cmp(&b.name));
+
+    match format {
+        OutputFormat::Json => print_json(&pipelines),
+        OutputFormat::Table => {
+            let rows: Vec<Vec<String>> = pipelines
+                .iter()
+                .map(|p| {
+                    vec![
+                        p.id.clone(),
+                        p.name.clone(),
+                        p.status.as_str().to_string(),
+                        p.repository.clone(),
+                        p.last_run
+                            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_else(|| "-".to_string()),
+                    ]
+                })
+                .collect();
+            print_table(&["ID", "NAME", "STATUS", "REPOSITORY", "LAST RUN"], &rows);
+            Ok(())
+        }
+    }
+}
+
+pub async fn list_runs(
+    core: &CoreContext, pipeline_id: &str, limit: usize, format: OutputFormat,
+) -> anyhow::Result<()> {
+    let runs = core
+        .pipeline_service
+        .fetch_run_history(pipeline_id, limit)
+        .await?;
+
+    match format {
+        OutputFormat::Json => print_json(&runs),
+        OutputFormat::Table => {
+            let rows: Vec<Vec<String>> = runs.iter().map(run_row).collect();
+            print_table(
+                &["RUN", "STATUS", "BRANCH", "COMMIT", "STARTED", "DURATION"],
+                &rows,
+            );
+            Ok(())
+        }
+    }
+}
+
+pub async fn trigger(
+    core: &CoreContext, pipeline_id: &str, params: Vec<(String, String)>, format: OutputFormat,
+) -> anyhow::Result<()> {
+    let inputs = if params.is_empty() {
+        None
+    } else {
+        Some(serde_json::Value::Object(
+            params
+                .into_iter()
+                .map(|(key, value)| (key, serde_json::Value::String(value)))
+                .collect(),
+        ))
+    };
+
+    let result = core
+        .pipeline_service
+        .trigger_pipeline(TriggerParams {
+            workflow_id: pipeline_id.to_string(),
+            inputs,
+        })
+        .await?;
+
+    match format {
+        OutputFormat::Json => print_json(&TriggerResult {
+            pipeline_id: pipeline_id.to_string(),
+            result,
+        }),
+        OutputFormat::Table => {
+            println!("Triggered {}: {}", pipeline_id, result);
+            Ok(())
+        }
+    }
+}
+
+pub async fn watch_run(
+    core: &CoreContext, pipeline_id: &str, run_number: Option<i64>, interval: Duration,
+    format: OutputFormat,
+) -> anyhow::Result<PipelineStatus> {
+    let run_number = match run_number {
+        Some(n) => n,
+        None => core
+            .pipeline_service
+            .fetch_run_history(pipeline_id, 1)
+            .await?
+            .first()
+            .map(|run| run.run_number)
+            .ok_or_else(|| anyhow!("No runs found for {}", pipeline_id))?,
+    };
+
+    let mut last_status = None;
+    loop {
+        let run = core
+            .pipeline_service
+            .fetch_run_details(pipeline_id, run_number)
+            .await?;
+
+        if last_status.as_ref() != Some(&run.status) {
+            match format {
+                OutputFormat::Json => print_json_line(&run)?,
+                OutputFormat::Table => println!(
+                    "[{}] #{} {} ({})",
+                    chrono::Local::now().format("%H:%M:%S"),
+                    run.run_number,
+                    run.status.as_str(),
+                    format_duration(run.duration_seconds)
+                ),
+            }
+            last_status = Some(run.status.clone());
+        }
+
+        if run.status.is_terminal() {
+            return Ok(run.status);
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+pub fn exit_status(status: &PipelineStatus) -> anyhow::Result<()> {
+    match status {
+        PipelineStatus::Failed => bail!("Run failed"),
+        PipelineStatus::Cancelled => bail!("Run was cancelled"),
+        _ => Ok(()),
+    }
+}
+
+fn run_row(run: &PipelineRun) -> Vec<String> {
+    vec![
+        format!("#{}", run.run_number),
+        run.status.as_str().to_string(),
+        run.branch.clone().unwrap_or_else(|| "-".to_string()),
+        run.commit_sha
+            .as_deref()
+            .map(|sha| sha.chars().take(7).collect())
+            .unwrap_or_else(|| "-".to_string()),
+        run.started_at.format("%Y-%m-%d %H:%M").to_string(),
+        format_duration(run.duration_seconds),
+    ]
+}
+
+fn backup_row(info: &BackupInfo) -> Vec<String> {
+    vec![
+        info.id.clone(),
+        info.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        format!("{:?}", info.format).to_lowercase(),
+        info.size_bytes.to_string(),
+    ]
+}
+
+const BACKUP_HEADERS: [&str; 4] = ["ID", "CREATED", "FORMAT", "BYTES"];
+
+pub async fn create_backup(core: &CoreContext, format: OutputFormat) -> anyhow::Result<()> {
+    let info = core.backup_service.create_backup().await?;
+
+    match format {
+        OutputFormat::Json => print_json(&info),
+        OutputFormat::Table => {
+            print_table(&BACKUP_HEADERS, &[backup_row(&info)]);
+            Ok(())
+        }
+    }
+}
+
+pub async fn list_backups(core: &CoreContext, format: OutputFormat) -> anyhow::Result<()> {
+    let backups = core.backup_service.list_backups().await?;
+
+    match format {
+        OutputFormat::Json => print_json(&backups),
+        OutputFormat::Table => {
+            let rows: Vec<Vec<String>> = backups.iter().map(backup_row).collect();
+            print_table(&BACKUP_HEADERS, &rows);
+            Ok(())
+        }
+    }
+}
+
+pub async fn verify_backup(
+    core: &CoreContext, id: &str, format: OutputFormat,
+) -> anyhow::Result<()> {
+    let verification = core.backup_service.verify_backup(id).await?;
+
+    match format {
+        OutputFormat::Json => print_json(&verification)?,
+        OutputFormat::Table => match &verification.error {
+            None => println!("Backup {} is valid", verification.id),
+            Some(error) => println!("Backup {} is invalid: {}", verification.id, error),
+        },
+    }
+
+    if verification.valid {
+        Ok(())
+    } else {
+        bail!("Backup {} failed verification", verification.id)
+    }
+}
+
+pub async fn restore_backup(
+    core: &CoreContext, id: &str, format: OutputFormat,
+) -> anyhow::Result<()> {
+    let info = core.backup_service.restore_backup(id).await?;
+
+    match format {
+        OutputFormat::Json => print_json(&info),
+        OutputFormat::Table => {
+            println!("Restored {}", info.id);
+            Ok(())
+        }
+    }
+}
+
+const PLAN_HEADERS: [&str; 4] = ["ACTION", "RESOURCE", "NAME", "DETAIL"];
+
+pub async fn plan_provisioning(
+    config: &PipedashConfig, service: &ProvisioningService, format: OutputFormat,
+) -> anyhow::Result<()> {
+    let plan = service.plan(config).await?;
+    print_plan(&plan, format)
+}
+
+pub async fn apply_provisioning(
+    config: &PipedashConfig, service: &ProvisioningService, format: OutputFormat,
+) -> anyhow::Result<()> {
+    let plan = service.apply(config).await?;
+    print_plan(&plan, format)
+}
+
+// Writes the document's config.toml and exports its vault password for the
+// storage opened next. Returns whether config.toml changed.
+pub fn install_setup(config_path: &Path, document: &Path) -> anyhow::Result<bool> {
+    let content = if document == Path::new("-") {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(document)
+            .with_context(|| format!("Failed to read {}", document.display()))?
+    };
+    let document = SetupDocument::parse_file(&content)?;
+    document.validate()?;
+
+    if let Some(password) = document.vault_password() {
+        std::env::set_var("PIPEDASH_VAULT_PASSWORD", password);
+    }
+    let changed = document.install(config_path)?;
+    std::fs::create_dir_all(document.config.data_dir())?;
+    Ok(changed)
+}
+
+pub async fn apply_setup(
+    config_path: &Path, config_changed: bool, config: &PipedashConfig,
+    service: &ProvisioningService, format: OutputFormat,
+) -> anyhow::Result<()> {
+    let plan = service.apply(config).await?;
+    match format {
+        OutputFormat::Json => {
+            for warning in &plan.warnings {
+                eprintln!("Warning: {}", warning);
+            }
+            print_json(&SetupResult {
+                config_path: config_path.display().to_string(),
+                config_changed,
+                plan: &plan,
+            })
+        }
+        OutputFormat::Table => {
+            if config_changed {
+                println!("Wrote {}", config_path.display());
+            } else {
+                println!("{} is up to date", config_path.display());
+            }
+            print_plan(&plan, format)
+        }
+    }
+}
+
+fn print_plan(plan: &ProvisioningPlan, format: OutputFormat) -> anyhow::Result<()> {
+    for warning in &plan.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+
+    match format {
+        OutputFormat::Json => print_json(plan),
+        OutputFormat::Table => {
+            if plan.changes.is_empty() {
+                println!("No changes: the database matches config.toml");
+                return Ok(());
+            }
+
+            let rows: Vec<Vec<String>> = plan
+                .changes
+                .iter()
+                .map(|change| {
+                    vec![
+                        change.action.as_str().to_string(),
+                        change.resource.as_str().to_string(),
+                        change.name.clone(),
+                        change.detail.clone().unwrap_or_else(|| "-".to_string()),
+                    ]
+                })
+                .collect();
+            print_table(&PLAN_HEADERS, &rows);
+
+            println!(
+                "\n{}: {} to create, {} to update, {} to delete",
+                if plan.applied { "Applied" } else { "Plan" },
+                plan.count(PlanAction::Create),
+                plan.count(PlanAction::Update),
+                plan.count(PlanAction::Delete)
+            );
+            let unmanaged = plan.count(PlanAction::Unmanaged);
+            if unmanaged > 0 {
+                println!(
+                    "{} unmanaged resources are kept; set [provisioning] declarative = true to \
+                     delete them",
+                    unmanaged
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+pub async fn migrate_keyring(
+    config_path: &Path, mut config: PipedashConfig, provider_ids: &[i64],
+    layout: Option<KeyringLayout>, dry_run: bool, format: OutputFormat,
+) -> anyhow::Result<()> {
+    let current = config.storage.keyring_layout;
+    let target = layout.unwrap_or(current);
+
+    let report = KeyringTokenStore::new()
+        .with_layout(current)
+        .migrate_layout(target, provider_ids, dry_run)
+        .await?;
+
+    // The app reads tokens from the layout in config.toml, so it follows the
+    // migration.
+    if !dry_run && target != current {
+        config.storage.keyring_layout = target;
+        ConfigLoader::save(&config, config_path)?;
+    }
+
+    match format {
+        OutputFormat::Json => print_json(&report),
+        OutputFormat::Table => {
+            let ids = |ids: &[i64]| {
+                if ids.is_empty() {
+                    "-".to_string()
+                } else {
+                    ids.iter()
+                        .map(i64::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+            };
+            print_table(
+                &["CHANGE", "PROVIDERS"],
+                &[
+                    vec!["moved".to_string(), ids(&report.moved)],
+                    vec!["orphaned".to_string(), ids(&report.orphaned)],
+                    vec!["duplicates".to_string(), ids(&report.duplicates)],
+                ],
+            );
+            println!(
+                "\n{} keyring layout: {}",
+                if dry_run { "Plan for" } else { "Migrated to" },
+                target
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_param() {
+        assert_eq!(
+            parse_param("env=production"),
+            Ok(("env".to_string(), "production".to_string()))
+        );
+        assert_eq!(
+            parse_param("query=a=b"),
+            Ok(("query".to_string(), "a=b".to_string()))
+        );
+        assert_eq!(
+            parse_param("empty="),
+            Ok(("empty".to_string(), String::new()))
+        );
+        assert!(parse_param("novalue").is_err());
+        assert!(parse_param("=value").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::sync::Arc;
+
+use anyhow::{
+    bail,
+    Context,
+};
+use pipedash_core::application::ProvisioningService;
+use pipedash_core::infrastructure::config::{
+    ConfigLoader,
+    PipedashConfig,
+};
+use pipedash_core::infrastructure::database::{
+    has_encrypted_tokens,
+    init_database,
+    Repository,
+};
+use pipedash_core::infrastructure::secrets::{
+    FallbackTokenStore,
+    KeyringTokenStore,
+    MemoryTokenStore,
+    SqliteTokenStore,
+};
+use pipedash_core::infrastructure::StorageManager;
+use pipedash_core::{
+    CoreContext,
+    NoOpEventBus,
+    TokenStore,
+};
+
+// Tauri bundle identifier; the desktop app keeps its config under this dir.
+const DESKTOP_APP_IDENTIFIER: &str = "com.henrique.pipedash";
+
+pub fn resolve_config_path(explicit: Option<PathBuf>) -> PathBuf {
+    if let Some(path) = explicit {
+        return path;
+    }
+
+    if std::env::var_os("PIPEDASH_CONFIG_PATH").is_none() {
+        if let Some(path) = dirs::data_dir()
+            .map(|dir| dir.join(DESKTOP_APP_IDENTIFIER).join("config.toml"))
+            .filter(|path| path.exists())
+        {
+            return path;
+        }
+    }
+
+    ConfigLoader::discover_config_path()
+}
+
+pub async fn bootstrap(config_path: &Path) -> anyhow::Result<CoreContext> {
+    let storage_manager = open_storage(load_config(config_path)?).await?;
+
+    let core = CoreContext::with_storage_manager(&storage_manager, Arc::new(NoOpEventBus))
+        .await
+        .context("Failed to initialize core")?;
+    core.warmup_token_store()
+        .await
+        .context("Failed to load provider tokens")?;
+
+    Ok(core)
+}
+
+// Skips the core context on purpose: building it syncs providers from the
+// file, which would leave `plan` with nothing to report.
+pub async fn provisioning(
+    config_path: &Path,
+) -> anyhow::Result<(PipedashConfig, ProvisioningService)> {
+    let config = load_config(config_path)?;
+    let storage_manager = open_storage(config.clone()).await?;
+    let token_store = storage_manager.token_store().await;
+    let repository = Arc::new(Repository::new(
+        storage_manager.config_backend(),
+        storage_manager.database_pool(),
+        Arc::clone(&token_store),
+    ));
+
+    Ok((config, ProvisioningService::new(repository, token_store)))
+}
+
+// Keyring entries aren't scoped to a data dir: run against one config, a
+// migration treats tokens of providers from any other as orphaned.
+pub async fn keyring(config_path: &Path) -> anyhow::Result<(PipedashConfig, Vec<i64>)> {
+    let config = load_config(config_path)?;
+    if !config.storage.backend.is_sqlite() {
+        bail!("Tokens are only kept in the system keyring with the SQLite backend");
+    }
+
+    let storage_manager =
+        StorageManager::with_token_store(config.clone(), Arc::new(MemoryTokenStore::new()), true)
+            .await?;
+    let provider_ids = storage_manager
+        .config_backend()
+        .list_providers()
+        .await
+        .context("Failed to list providers")?
+        .into_iter()
+        .filter_map(|provider| provider.id)
+        .collect();
+
+    Ok((config, provider_ids))
+}
+
+fn load_config(config_path: &Path) -> anyhow::Result<PipedashConfig> {
+    ConfigLoader::load(config_path)
+        .with_context(|| format!("Failed to load config from {}", config_path.display()))
+}
+
+async fn open_storage(config: PipedashConfig) -> anyhow::Result<StorageManager> {
+    let vault_password_available = std::env::var("PIPEDASH_VAULT_PASSWORD").is_ok();
+
+    // Same token store selection as the desktop app, minus the locked mode: a
+    // CLI invocation cannot prompt for an unlock later.
+    let storage_manager = if config.storage.backend.is_sqlite() {
+        let db_path = config.db_path();
+        let encrypted = has_encrypted_tokens(&db_path).await;
+
+        if vault_password_available {
+            let pool = init_database(db_path)
+                .await
+                .context("Failed to initialize SQLite database")?;
+            let primary: Arc<dyn TokenStore> = Arc::new(SqliteTokenStore::new(pool, None).await?);
+            let fallback: Arc<dyn TokenStore> =
+                Arc::new(KeyringTokenStore::new().with_layout(config.storage.keyring_layout));
+            let token_store: Arc<dyn TokenStore> =
+                Arc::new(FallbackTokenStore::new(primary, fallback));
+
+            StorageManager::with_token_store(config, token_store, true).await?
+        } else if encrypted {
+            bail!("Vault is locked: set PIPEDASH_VAULT_PASSWORD to access encrypted tokens");
+        } else {
+            let token_store: Arc<dyn TokenStore> =
+                Arc::new(KeyringTokenStore::new().with_layout(config.storage.keyring_layout));
+            StorageManager::with_token_store(config, token_store, true).await?
+        }
+    } else {
+        if !vault_password_available {
+            bail!("Vault is locked: set PIPEDASH_VAULT_PASSWORD to access encrypted tokens");
+        }
+        StorageManager::from_config(config, false).await?
+    };
+
+    Ok(storage_manager)
+}
@@ -0,0 +1,232 @@
+mod commands;
+mod context;
+mod output;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{
+    Parser,
+    Subcommand,
+};
+use pipedash_core::infrastructure::config::KeyringLayout;
+use pipedash_core::logging::RedactingWriter;
+use tracing_subscriber::EnvFilter;
+
+use crate::output::OutputFormat;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "pipedash",
+    version,
+    about = "Monitor and trigger CI/CD pipelines"
+)]
+struct Cli {
+    /// Path to config.toml (defaults to the desktop/server config location)
+    #[arg(long, global = true, env = "PIPEDASH_CONFIG_PATH")]
+    config: Option<PathBuf>,
+
+    #[arg(long, short, global = true, value_enum, default_value = "table")]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Work with monitored pipelines
+    Pipelines {
+        #[command(subcommand)]
+        command: PipelinesCommand,
+    },
+    /// Inspect pipeline runs
+    Runs {
+        #[command(subcommand)]
+        command: RunsCommand,
+    },
+    /// Trigger a pipeline run
+    Trigger {
+        pipeline: String,
+        /// Workflow input as KEY=VALUE (repeatable)
+        #[arg(long = "param", short = 'p', value_parser = commands::parse_param)]
+        params: Vec<(String, String)>,
+    },
+    /// Back up or restore the database
+    Backup {
+        #[command(subcommand)]
+        command: BackupCommand,
+    },
+    /// Show how the database differs from the resources declared in
+    /// config.toml
+    Plan,
+    /// Make the database match the resources declared in config.toml
+    Apply,
+    /// Install a setup document (config.toml contents plus `vault_password`,
+    /// as TOML or JSON; `-` reads stdin) and apply it. Running it again with
+    /// the same document changes nothing
+    Setup { document: PathBuf },
+    /// Manage the tokens kept in the system keyring
+    Keyring {
+        #[command(subcommand)]
+        command: KeyringCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum PipelinesCommand {
+    /// List pipelines across all providers
+    List {
+        #[arg(long)]
+        provider: Option<i64>,
+        /// Read from the local cache instead of querying providers
+        #[arg(long)]
+        cached: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum RunsCommand {
+    /// List recent runs of a pipeline
+    List {
+        pipeline: String,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Follow a run until it finishes (defaults to the latest run)
+    Watch {
+        pipeline: String,
+        #[arg(long)]
+        run: Option<i64>,
+        /// Poll interval in seconds
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum BackupCommand {
+    /// Take a backup now
+    Create,
+    /// List stored backups, newest first
+    List,
+    /// Check a backup's checksum and integrity
+    Verify { id: String },
+    /// Replace the database contents with a backup
+    Restore { id: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum KeyringCommand {
+    /// Move tokens to a keyring layout and remove those of deleted providers
+    Migrate {
+        /// `single` or `per_provider` (defaults to the configured layout)
+        #[arg(long)]
+        layout: Option<KeyringLayout>,
+        /// Report what would change without touching the keyring
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    rustls::crypto::ring::default_provider()
+        .install_default()
+        .expect("Failed to install rustls crypto provider");
+
+    // Logs go to stderr so JSON output on stdout stays machine readable.
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        )
+        .with_writer(RedactingWriter::new(std::io::stderr))
+        .init();
+
+    let cli = Cli::parse();
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> anyhow::Result<()> {
+    let config_path = context::resolve_config_path(cli.config);
+    let format = cli.output;
+
+    match cli.command {
+        Command::Plan => {
+            let (config, service) = context::provisioning(&config_path).await?;
+            return commands::plan_provisioning(&config, &service, format).await;
+        }
+        Command::Apply => {
+            let (config, service) = context::provisioning(&config_path).await?;
+            return commands::apply_provisioning(&config, &service, format).await;
+        }
+        Command::Setup { document } => {
+            let config_changed = commands::install_setup(&config_path, &document)?;
+            let (config, service) = context::provisioning(&config_path).await?;
+            return commands::apply_setup(&config_path, config_changed, &config, &service, format)
+                .await;
+        }
+        Command::Keyring {
+            command: KeyringCommand::Migrate { layout, dry_run },
+        } => {
+            let (config, provider_ids) = context::keyring(&config_path).await?;
+            return commands::migrate_keyring(
+                &config_path,
+                config,
+                &provider_ids,
+                layout,
+                dry_run,
+                format,
+            )
+            .await;
+        }
+        _ => {}
+    }
+
+    let core = context::bootstrap(&config_path).await?;
+
+    let result = match cli.command {
+        Command::Pipelines {
+            command: PipelinesCommand::List { provider, cached },
+        } => commands::list_pipelines(&core, provider, cached, format).await,
+        Command::Runs {
+            command: RunsCommand::List { pipeline, limit },
+        } => commands::list_runs(&core, &pipeline, limit, format).await,
+        Command::Runs {
+            command:
+                RunsCommand::Watch {
+                    pipeline,
+                    run,
+                    interval,
+                },
+        } => {
+            let interval = Duration::from_secs(interval.max(1));
+            commands::watch_run(&core, &pipeline, run, interval, format)
+                .await
+                .and_then(|status| commands::exit_status(&status))
+        }
+        Command::Trigger { pipeline, params } => {
+            commands::trigger(&core, &pipeline, params, format).await
+        }
+        Command::Backup { command } => match command {
+            BackupCommand::Create => commands::create_backup(&core, format).await,
+            BackupCommand::List => commands::list_backups(&core, format).await,
+            BackupCommand::Verify { id } => commands::verify_backup(&core, &id, format).await,
+            BackupCommand::Restore { id } => commands::restore_backup(&core, &id, format).await,
+        },
+        Command::Plan | Command::Apply | Command::Setup { .. } | Command::Keyring { .. } => {
+            unreachable!("handled before bootstrapping")
+        }
+    };
+
+    core.shutdown().await;
+    result
+}
@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS pipeline_groups (
+    id BIGSERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS pipeline_group_members (
+    group_id BIGINT NOT NULL REFERENCES pipeline_groups(id) ON DELETE CASCADE,
+    pipeline_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (group_id, pipeline_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_group_members_pipeline
+    ON pipeline_group_members(pipeline_id);
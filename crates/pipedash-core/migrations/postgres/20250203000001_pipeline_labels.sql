@@ -0,0 +1,10 @@
+-- Labels are kept apart from pipelines_cache so that refreshing or clearing the
+-- cache does not drop what the user assigned.
+CREATE TABLE IF NOT EXISTS pipeline_labels (
+    pipeline_id TEXT NOT NULL,
+    label TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (pipeline_id, label)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_labels_label ON pipeline_labels(label);
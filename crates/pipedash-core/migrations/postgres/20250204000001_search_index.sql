@@ -0,0 +1,10 @@
+-- Expression indexes for full-text search over cached pipelines and runs. The
+-- search queries repeat these expressions verbatim so the planner uses them.
+CREATE INDEX IF NOT EXISTS idx_pipelines_cache_search ON pipelines_cache
+    USING GIN (to_tsvector('simple', name || ' ' || repository || ' ' || COALESCE(branch, '')));
+
+CREATE INDEX IF NOT EXISTS idx_run_history_search ON run_history_cache
+    USING GIN (to_tsvector('simple',
+        COALESCE(run_data::jsonb ->> 'commit_message', '') || ' ' ||
+        COALESCE(run_data::jsonb ->> 'branch', '') || ' ' ||
+        COALESCE(run_data::jsonb ->> 'actor', '')));
@@ -0,0 +1,23 @@
+-- Global run history retention. NULL limits mean "keep everything".
+CREATE TABLE IF NOT EXISTS run_retention_global_config (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    enabled BOOLEAN NOT NULL DEFAULT true,
+    max_age_days INTEGER,
+    max_runs_per_pipeline INTEGER,
+    last_pruned_at TIMESTAMPTZ,
+    last_pruned_count BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO run_retention_global_config (id, enabled, max_age_days, max_runs_per_pipeline, updated_at)
+VALUES (1, true, 90, 500, NOW())
+ON CONFLICT (id) DO NOTHING;
+
+-- Per-pipeline overrides; a NULL column falls back to the global limit.
+CREATE TABLE IF NOT EXISTS run_retention_config (
+    pipeline_id TEXT PRIMARY KEY,
+    max_age_days INTEGER,
+    max_runs INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
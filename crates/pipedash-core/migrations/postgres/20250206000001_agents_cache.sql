@@ -0,0 +1,10 @@
+-- Last known state of each provider's build agents and runners. The full
+-- agent is stored as JSON in agent_data.
+CREATE TABLE IF NOT EXISTS agents_cache (
+    provider_id BIGINT NOT NULL,
+    agent_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    agent_data TEXT NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (provider_id, agent_id)
+);
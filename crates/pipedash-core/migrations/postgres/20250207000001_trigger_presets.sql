@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS trigger_presets (
+    id BIGSERIAL PRIMARY KEY,
+    pipeline_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    branch TEXT,
+    parameters_json TEXT NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (pipeline_id, name)
+);
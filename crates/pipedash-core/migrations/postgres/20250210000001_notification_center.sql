@@ -0,0 +1,13 @@
+-- In-app notification center. The notification itself is stored as JSON in
+-- notification_data; read state is kept in its own column so it can be
+-- updated in place.
+CREATE TABLE IF NOT EXISTS notifications (
+    id BIGSERIAL PRIMARY KEY,
+    kind TEXT NOT NULL,
+    notification_data TEXT NOT NULL,
+    is_read BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_unread
+    ON notifications(is_read);
@@ -0,0 +1,3 @@
+-- Per-channel quiet hours, deduplication window and transition-only delivery,
+-- stored as JSON like rules_json.
+ALTER TABLE notification_channels ADD COLUMN IF NOT EXISTS suppression_json TEXT NOT NULL DEFAULT '{}';
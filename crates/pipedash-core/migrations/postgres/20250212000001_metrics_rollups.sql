@@ -0,0 +1,26 @@
+-- Hourly and daily aggregates of pipeline_metrics, so long ranges can be
+-- charted without scanning every raw row.
+CREATE TABLE IF NOT EXISTS pipeline_metrics_rollups (
+    pipeline_id TEXT NOT NULL,
+    metric_type TEXT NOT NULL,
+    granularity TEXT NOT NULL,
+    bucket_start TIMESTAMPTZ NOT NULL,
+    count BIGINT NOT NULL,
+    sum DOUBLE PRECISION NOT NULL,
+    min DOUBLE PRECISION NOT NULL,
+    max DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (granularity, metric_type, pipeline_id, bucket_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_metrics_rollups_bucket
+    ON pipeline_metrics_rollups(granularity, bucket_start);
+
+-- Highest pipeline_metrics id already folded into the rollups.
+CREATE TABLE IF NOT EXISTS metrics_rollup_state (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    last_metric_id BIGINT NOT NULL DEFAULT 0,
+    compacted_at TIMESTAMPTZ
+);
+
+INSERT INTO metrics_rollup_state (id, last_metric_id) VALUES (1, 0)
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,17 @@
+-- Duration and failure-rate deviations found by the metrics anomaly detector.
+-- One row per pipeline, kind and triggering run, so re-running detection over
+-- the same runs never duplicates a finding.
+CREATE TABLE IF NOT EXISTS metric_anomalies (
+    id BIGSERIAL PRIMARY KEY,
+    pipeline_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    baseline DOUBLE PRECISION NOT NULL,
+    observed DOUBLE PRECISION NOT NULL,
+    z_score DOUBLE PRECISION NOT NULL,
+    run_number BIGINT NOT NULL,
+    detected_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (pipeline_id, kind, run_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_metric_anomalies_detected
+    ON metric_anomalies(detected_at);
@@ -0,0 +1,12 @@
+-- One row per pipeline fetch against a provider, used for health scoring and
+-- outage detection. Rows older than a week are pruned as new ones arrive.
+CREATE TABLE IF NOT EXISTS provider_health_checks (
+    id BIGSERIAL PRIMARY KEY,
+    provider_id BIGINT NOT NULL,
+    success BOOLEAN NOT NULL,
+    error TEXT,
+    checked_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_health_checks_provider
+    ON provider_health_checks(provider_id, checked_at);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS pipeline_refresh_settings (
+    pipeline_id TEXT PRIMARY KEY,
+    refresh_interval_secs BIGINT,
+    snoozed BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
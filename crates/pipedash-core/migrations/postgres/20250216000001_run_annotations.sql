@@ -0,0 +1,9 @@
+-- Annotations of finished runs, fetched when a run is opened. Kept apart from
+-- run_history_cache so history refreshes do not drop them.
+CREATE TABLE IF NOT EXISTS run_annotations_cache (
+    pipeline_id TEXT NOT NULL,
+    run_number BIGINT NOT NULL,
+    annotations_json TEXT NOT NULL DEFAULT '[]',
+    cached_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (pipeline_id, run_number)
+);
@@ -0,0 +1,7 @@
+-- Latest run per branch of a pipeline, for the branch board. Refetched once
+-- the cached board is older than a minute.
+CREATE TABLE IF NOT EXISTS branch_status_cache (
+    pipeline_id TEXT PRIMARY KEY NOT NULL,
+    statuses_json TEXT NOT NULL DEFAULT '[]',
+    cached_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS pinned_pipelines (
+    pipeline_id TEXT PRIMARY KEY,
+    position BIGINT NOT NULL DEFAULT 0,
+    unpinned BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,11 @@
+-- Security audit log (denied and failed authentication attempts). The entry
+-- is stored as JSON in entry_data, like notifications.
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    action TEXT NOT NULL,
+    entry_data TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_action
+    ON audit_log(action);
@@ -0,0 +1,5 @@
+-- Commit-centric index over cached runs, so every run of a SHA can be found
+-- across providers. text_pattern_ops lets abbreviated SHAs use it as a LIKE
+-- prefix.
+CREATE INDEX IF NOT EXISTS runs_by_commit
+    ON run_history_cache((run_data::jsonb ->> 'commit_sha') text_pattern_ops);
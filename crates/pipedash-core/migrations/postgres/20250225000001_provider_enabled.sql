@@ -0,0 +1,2 @@
+-- Disabled providers keep their config and history but are not fetched
+ALTER TABLE providers ADD COLUMN IF NOT EXISTS enabled BOOLEAN NOT NULL DEFAULT TRUE;
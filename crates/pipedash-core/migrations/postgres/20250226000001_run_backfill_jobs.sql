@@ -0,0 +1,8 @@
+-- One history import per provider. Progress is stored as JSON in job_data so
+-- an interrupted job can resume on the next start.
+CREATE TABLE IF NOT EXISTS run_backfill_jobs (
+    provider_id BIGINT PRIMARY KEY,
+    status TEXT NOT NULL,
+    job_data TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
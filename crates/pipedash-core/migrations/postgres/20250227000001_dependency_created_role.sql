@@ -0,0 +1,3 @@
+-- Dependent pipelines are triggered with the role of whoever saved the
+-- dependency. Rows saved before this was recorded fall back to the default role.
+ALTER TABLE pipeline_dependencies ADD COLUMN IF NOT EXISTS created_by_role TEXT;
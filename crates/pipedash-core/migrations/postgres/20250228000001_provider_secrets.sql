@@ -0,0 +1,11 @@
+-- Credentials from a provider's config besides its token (webhook secrets,
+-- client keys), encrypted with the vault key. The config only keeps a
+-- `storage:<name>` reference to the row.
+CREATE TABLE IF NOT EXISTS provider_secrets (
+    provider_id BIGINT NOT NULL,
+    name TEXT NOT NULL,
+    encrypted_value BYTEA NOT NULL,
+    value_nonce BYTEA NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (provider_id, name)
+);
@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS pipeline_groups (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS pipeline_group_members (
+    group_id INTEGER NOT NULL,
+    pipeline_id TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (group_id, pipeline_id),
+    FOREIGN KEY (group_id) REFERENCES pipeline_groups(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_group_members_pipeline
+    ON pipeline_group_members(pipeline_id);
@@ -0,0 +1,75 @@
+-- Full-text index over cached pipelines and runs. Each FTS row shares the rowid
+-- of the cache row it was built from, and triggers keep both in sync.
+CREATE VIRTUAL TABLE IF NOT EXISTS pipelines_fts USING fts5(
+    name,
+    repository,
+    branch,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS runs_fts USING fts5(
+    commit_message,
+    branch,
+    actor,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO pipelines_fts (rowid, name, repository, branch)
+    SELECT rowid, name, repository, COALESCE(branch, '') FROM pipelines_cache;
+
+INSERT INTO runs_fts (rowid, commit_message, branch, actor)
+    SELECT rowid,
+           COALESCE(json_extract(run_data, '$.commit_message'), ''),
+           COALESCE(json_extract(run_data, '$.branch'), ''),
+           COALESCE(json_extract(run_data, '$.actor'), '')
+    FROM run_history_cache
+    WHERE json_valid(run_data);
+
+CREATE TRIGGER IF NOT EXISTS pipelines_fts_insert AFTER INSERT ON pipelines_cache BEGIN
+    INSERT INTO pipelines_fts (rowid, name, repository, branch)
+        VALUES (new.rowid, new.name, new.repository, COALESCE(new.branch, ''));
+END;
+
+CREATE TRIGGER IF NOT EXISTS pipelines_fts_update AFTER UPDATE ON pipelines_cache BEGIN
+    DELETE FROM pipelines_fts WHERE rowid = old.rowid;
+    INSERT INTO pipelines_fts (rowid, name, repository, branch)
+        VALUES (new.rowid, new.name, new.repository, COALESCE(new.branch, ''));
+END;
+
+CREATE TRIGGER IF NOT EXISTS pipelines_fts_delete AFTER DELETE ON pipelines_cache BEGIN
+    DELETE FROM pipelines_fts WHERE rowid = old.rowid;
+END;
+
+-- Run history is written with INSERT OR REPLACE, which removes the old row
+-- without firing delete triggers, so stale entries are dropped up front.
+CREATE TRIGGER IF NOT EXISTS runs_fts_replace BEFORE INSERT ON run_history_cache BEGIN
+    DELETE FROM runs_fts WHERE rowid = (
+        SELECT rowid FROM run_history_cache
+        WHERE pipeline_id = new.pipeline_id AND run_number = new.run_number
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS runs_fts_insert AFTER INSERT ON run_history_cache
+WHEN json_valid(new.run_data) BEGIN
+    INSERT INTO runs_fts (rowid, commit_message, branch, actor)
+        VALUES (
+            new.rowid,
+            COALESCE(json_extract(new.run_data, '$.commit_message'), ''),
+            COALESCE(json_extract(new.run_data, '$.branch'), ''),
+            COALESCE(json_extract(new.run_data, '$.actor'), '')
+        );
+END;
+
+CREATE TRIGGER IF NOT EXISTS runs_fts_update AFTER UPDATE ON run_history_cache BEGIN
+    DELETE FROM runs_fts WHERE rowid = old.rowid;
+    INSERT INTO runs_fts (rowid, commit_message, branch, actor)
+        SELECT new.rowid,
+               COALESCE(json_extract(new.run_data, '$.commit_message'), ''),
+               COALESCE(json_extract(new.run_data, '$.branch'), ''),
+               COALESCE(json_extract(new.run_data, '$.actor'), '')
+        WHERE json_valid(new.run_data);
+END;
+
+CREATE TRIGGER IF NOT EXISTS runs_fts_delete AFTER DELETE ON run_history_cache BEGIN
+    DELETE FROM runs_fts WHERE rowid = old.rowid;
+END;
@@ -0,0 +1,22 @@
+-- Global run history retention. NULL limits mean "keep everything".
+CREATE TABLE IF NOT EXISTS run_retention_global_config (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    enabled INTEGER NOT NULL DEFAULT 1,
+    max_age_days INTEGER,
+    max_runs_per_pipeline INTEGER,
+    last_pruned_at TEXT,
+    last_pruned_count INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+INSERT OR IGNORE INTO run_retention_global_config (id, enabled, max_age_days, max_runs_per_pipeline, updated_at)
+VALUES (1, 1, 90, 500, datetime('now'));
+
+-- Per-pipeline overrides; a NULL column falls back to the global limit.
+CREATE TABLE IF NOT EXISTS run_retention_config (
+    pipeline_id TEXT PRIMARY KEY,
+    max_age_days INTEGER,
+    max_runs INTEGER,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
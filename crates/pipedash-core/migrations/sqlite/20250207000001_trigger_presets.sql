@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS trigger_presets (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    pipeline_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    branch TEXT,
+    parameters_json TEXT NOT NULL DEFAULT '{}',
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (pipeline_id, name)
+);
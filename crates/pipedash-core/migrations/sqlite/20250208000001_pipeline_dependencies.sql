@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS pipeline_dependencies (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    upstream_id TEXT NOT NULL,
+    downstream_id TEXT NOT NULL,
+    trigger_on TEXT NOT NULL DEFAULT 'success',
+    inputs_json TEXT NOT NULL DEFAULT '{}',
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (upstream_id, downstream_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_dependencies_upstream
+    ON pipeline_dependencies(upstream_id);
@@ -0,0 +1,13 @@
+-- In-app notification center. The notification itself is stored as JSON in
+-- notification_data; read state is kept in its own column so it can be
+-- updated in place.
+CREATE TABLE IF NOT EXISTS notifications (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    notification_data TEXT NOT NULL,
+    is_read INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_unread
+    ON notifications(is_read);
@@ -0,0 +1,26 @@
+-- Hourly and daily aggregates of pipeline_metrics, so long ranges can be
+-- charted without scanning every raw row. bucket_start is stored in the same
+-- RFC 3339 form as pipeline_metrics.timestamp.
+CREATE TABLE IF NOT EXISTS pipeline_metrics_rollups (
+    pipeline_id TEXT NOT NULL,
+    metric_type TEXT NOT NULL,
+    granularity TEXT NOT NULL,
+    bucket_start TEXT NOT NULL,
+    count INTEGER NOT NULL,
+    sum REAL NOT NULL,
+    min REAL NOT NULL,
+    max REAL NOT NULL,
+    PRIMARY KEY (granularity, metric_type, pipeline_id, bucket_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_metrics_rollups_bucket
+    ON pipeline_metrics_rollups(granularity, bucket_start);
+
+-- Highest pipeline_metrics id already folded into the rollups.
+CREATE TABLE IF NOT EXISTS metrics_rollup_state (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    last_metric_id INTEGER NOT NULL DEFAULT 0,
+    compacted_at TEXT
+);
+
+INSERT OR IGNORE INTO metrics_rollup_state (id, last_metric_id) VALUES (1, 0);
@@ -0,0 +1,17 @@
+-- Duration and failure-rate deviations found by the metrics anomaly detector.
+-- One row per pipeline, kind and triggering run, so re-running detection over
+-- the same runs never duplicates a finding.
+CREATE TABLE IF NOT EXISTS metric_anomalies (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    pipeline_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    baseline REAL NOT NULL,
+    observed REAL NOT NULL,
+    z_score REAL NOT NULL,
+    run_number INTEGER NOT NULL,
+    detected_at TEXT NOT NULL,
+    UNIQUE (pipeline_id, kind, run_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_metric_anomalies_detected
+    ON metric_anomalies(detected_at);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS pipeline_refresh_settings (
+    pipeline_id TEXT PRIMARY KEY,
+    refresh_interval_secs INTEGER,
+    snoozed INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
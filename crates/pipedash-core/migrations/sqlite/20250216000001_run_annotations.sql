@@ -0,0 +1,9 @@
+-- Annotations of finished runs, fetched when a run is opened. Kept apart from
+-- run_history_cache so history refreshes do not drop them.
+CREATE TABLE IF NOT EXISTS run_annotations_cache (
+    pipeline_id TEXT NOT NULL,
+    run_number INTEGER NOT NULL,
+    annotations_json TEXT NOT NULL DEFAULT '[]',
+    cached_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (pipeline_id, run_number)
+);
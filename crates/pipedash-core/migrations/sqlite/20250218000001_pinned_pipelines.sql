@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS pinned_pipelines (
+    pipeline_id TEXT PRIMARY KEY,
+    position INTEGER NOT NULL DEFAULT 0,
+    unpinned INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,7 @@
+-- When pipelines were last opened or triggered, for the command palette's
+-- recently used list.
+CREATE TABLE IF NOT EXISTS pipeline_usage (
+    pipeline_id TEXT PRIMARY KEY NOT NULL,
+    use_count INTEGER NOT NULL DEFAULT 0,
+    last_used_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,2 @@
+-- Server version detected when a provider's credentials are validated (JSON)
+ALTER TABLE providers ADD COLUMN server_version TEXT;
@@ -0,0 +1,18 @@
+-- Pipelines whose triggers need approval before the provider is called
+CREATE TABLE IF NOT EXISTS pipeline_approval_rules (
+    pipeline_id TEXT PRIMARY KEY,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Held triggers. The request is stored as JSON in approval_data; status is
+-- kept in its own column so a decision can be claimed atomically.
+CREATE TABLE IF NOT EXISTS trigger_approvals (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    pipeline_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    approval_data TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_trigger_approvals_status
+    ON trigger_approvals(status);
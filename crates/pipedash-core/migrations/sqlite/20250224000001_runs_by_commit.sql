@@ -0,0 +1,6 @@
+-- Commit-centric index over cached runs, so every run of a SHA can be found
+-- across providers. Commit lookups repeat the expression and the json_valid
+-- guard verbatim so the planner picks this index.
+CREATE INDEX IF NOT EXISTS runs_by_commit
+    ON run_history_cache(json_extract(run_data, '$.commit_sha'))
+    WHERE json_valid(run_data);
@@ -0,0 +1,119 @@
+use std::future::Future;
+
+use crate::domain::{
+    Action,
+    DomainError,
+    DomainResult,
+    Role,
+};
+
+tokio::task_local! {
+    static REQUEST_ROLE: Role;
+}
+
+// Resolves the role of whoever is calling into the services. Frontends that
+// know the caller (e.g. the web server after checking the API token) run the
+// request inside `with_role`; everything else falls back to the configured
+// default.
+pub struct AccessControl {
+    default_role: Role,
+    allow_self_approval: bool,
+}
+
+// The actor recorded for callers outside a `with_role` scope.
+pub const LOCAL_ACTOR: &str = "local";
+
+impl AccessControl {
+    pub fn new(default_role: Role) -> Self {
+        Self {
+            default_role,
+            allow_self_approval: false,
+        }
+    }
+
+    pub fn with_self_approval(mut self, allow: bool) -> Self {
+        self.allow_self_approval = allow;
+        self
+    }
+
+    // Outside a `with_role` scope the caller is the single local user, so
+    // there is no second person and self-approval (with a confirmation) is
+    // the only way through.
+    pub fn allows_self_approval(&self) -> bool {
+        self.allow_self_approval || REQUEST_ROLE.try_with(|_| ()).is_err()
+    }
+
+    // Role tokens are the only identity the web server knows, so a scoped
+    // request is identified by its role and callers sharing a token are the
+    // same actor; the desktop app and the CLI are always the local user.
+    pub fn current_actor(&self) -> String {
+        REQUEST_ROLE
+            .try_with(|role| role.as_str().to_string())
+            .unwrap_or_else(|_| LOCAL_ACTOR.to_string())
+    }
+
+    pub fn default_role(&self) -> Role {
+        self.default_role
+    }
+
+    pub fn current_role(&self) -> Role {
+        REQUEST_ROLE
+            .try_with(|role| *role)
+            .unwrap_or(self.default_role)
+    }
+
+    pub fn ensure(&self, action: Action) -> DomainResult<()> {
+        let role = self.current_role();
+        if role.allows(action) {
+            Ok(())
+        } else {
+            Err(DomainError::PermissionDenied(format!(
+                "role '{}' is not allowed to {}",
+                role,
+                action.as_str()
+            )))
+        }
+    }
+}
+
+impl Default for AccessControl {
+    fn default() -> Self {
+        Self::new(Role::default())
+    }
+}
+
+pub async fn with_role<F: Future>(role: Role, future: F) -> F::Output {
+    REQUEST_ROLE.scope(role, future).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_role_overrides_default() {
+        let access = AccessControl::new(Role::Admin);
+        assert!(access.ensure(Action::ManageProviders).is_ok());
+
+        let result = with_role(Role::Viewer, async {
+            access.ensure(Action::TriggerPipeline)
+        })
+        .await;
+        assert!(matches!(result, Err(DomainError::PermissionDenied(_))));
+
+        assert_eq!(access.current_role(), Role::Admin);
+        assert_eq!(access.current_actor(), LOCAL_ACTOR);
+        let actor = with_role(Role::Operator, async { access.current_actor() }).await;
+        assert_eq!(actor, "operator");
+    }
+
+    #[tokio::test]
+    async fn test_self_approval_outside_request_scope() {
+        let access = AccessControl::new(Role::Admin);
+        assert!(access.allows_self_approval());
+        assert!(!with_role(Role::Admin, async { access.allows_self_approval() }).await);
+
+        let access = AccessControl::new(Role::Admin).with_self_approval(true);
+        assert!(with_role(Role::Admin, async { access.allows_self_approval() }).await);
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+use std::sync::Arc;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use dashmap::DashMap;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+
+use super::services::pipeline_service::PipelineService;
+use crate::domain::{
+    DomainError,
+    DomainResult,
+    LogQuery,
+    RunLogs,
+};
+use crate::event::{
+    CoreEvent,
+    EventBus,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+const MAX_TAIL_DURATION: Duration = Duration::from_secs(6 * 60 * 60);
+const MAX_CONSECUTIVE_ERRORS: u32 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogTailInfo {
+    pub pipeline_id: String,
+    pub run_number: i64,
+    pub started_at: i64,
+}
+
+struct TailSession {
+    info: LogTailInfo,
+    handle: JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct TailState {
+    offsets: HashMap<String, usize>,
+    drained: HashSet<String>,
+    last_snapshot: Option<RunLogs>,
+}
+
+impl TailState {
+    // Prefer a finished job whose log has not been fully emitted yet, so
+    // parallel jobs are not held back by an earlier job still running.
+    fn next_focus(&self) -> Option<String> {
+        let snapshot = self.last_snapshot.as_ref()?;
+        let pending: Vec<_> = snapshot
+            .jobs
+            .iter()
+            .filter(|job| !self.drained.contains(&job.id))
+            .collect();
+
+        pending
+            .iter()
+            .find(|job| job.is_complete)
+            .or_else(|| pending.first())
+            .map(|job| job.id.clone())
+    }
+
+    fn is_finished(&self) -> bool {
+        self.last_snapshot.as_ref().is_some_and(|snapshot| {
+            snapshot.is_complete
+                && snapshot
+                    .jobs
+                    .iter()
+                    .all(|job| self.drained.contains(&job.id))
+        })
+    }
+}
+
+pub struct LogTailer {
+    pipeline_service: Arc<PipelineService>,
+    event_bus: Arc<dyn EventBus>,
+    sessions: Arc<DashMap<String, TailSession>>,
+}
+
+impl LogTailer {
+    pub fn new(pipeline_service: Arc<PipelineService>, event_bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            pipeline_service,
+            event_bus,
+            sessions: Arc::new(DashMap::new()),
+        }
+    }
+
+    fn session_key(pipeline_id: &str, run_number: i64) -> String {
+        format!("{}#{}", pipeline_id, run_number)
+    }
+
+    pub fn start(&self, pipeline_id: &str, run_number: i64) -> bool {
+        let key = Self::session_key(pipeline_id, run_number);
+        if self
+            .sessions
+            .get(&key)
+            .is_some_and(|session| !session.handle.is_finished())
+        {
+            return false;
+        }
+
+        let pipeline_service = Arc::clone(&self.pipeline_service);
+        let event_bus = Arc::clone(&self.event_bus);
+        let sessions = Arc::clone(&self.sessions);
+        let task_pipeline_id = pipeline_id.to_string();
+        let task_key = key.clone();
+
+        let handle = tokio::spawn(async move {
+            let error = Self::run_tail(
+                pipeline_service,
+                Arc::clone(&event_bus),
+                &task_pipeline_id,
+                run_number,
+            )
+            .await
+            .err()
+            .map(|e| e.to_string());
+
+            sessions.remove(&task_key);
+            event_bus
+                .emit(CoreEvent::RunLogTailStopped {
+                    pipeline_id: task_pipeline_id,
+                    run_number,
+                    error,
+                })
+                .await;
+        });
+
+        self.sessions.insert(
+            key,
+            TailSession {
+                info: LogTailInfo {
+                    pipeline_id: pipeline_id.to_string(),
+                    run_number,
+                    started_at: chrono::Utc::now().timestamp(),
+                },
+                handle,
+            },
+        );
+
+        tracing::debug!(
+            pipeline_id = pipeline_id,
+            run_number = run_number,
+            "Started log tail"
+        );
+        true
+    }
+
+    pub async fn stop(&self, pipeline_id: &str, run_number: i64) -> bool {
+        let key = Self::session_key(pipeline_id, run_number);
+        let Some((_, session)) = self.sessions.remove(&key) else {
+            return false;
+        };
+
+        session.handle.abort();
+        self.event_bus
+            .emit(CoreEvent::RunLogTailStopped {
+                pipeline_id: pipeline_id.to_string(),
+                run_number,
+                error: None,
+            })
+            .await;
+        true
+    }
+
+    pub fn stop_all(&self) {
+        for entry in self.sessions.iter() {
+            entry.handle.abort();
+        }
+        self.sessions.clear();
+    }
+
+    pub fn active_tails(&self) -> Vec<LogTailInfo> {
+        self.sessions
+            .iter()
+            .filter(|entry| !entry.handle.is_finished())
+            .map(|entry| entry.info.clone())
+            .collect()
+    }
+
+    async fn run_tail(
+        pipeline_service: Arc<PipelineService>, event_bus: Arc<dyn EventBus>, pipeline_id: &str,
+        run_number: i64,
+    ) -> DomainResult<()> {
+        let mut state = TailState::default();
+        let mut tick_interval = interval(POLL_INTERVAL);
+        let mut consecutive_errors = 0;
+        let started = Instant::now();
+
+        loop {
+            tick_interval.tick().await;
+
+            if started.elapsed() > MAX_TAIL_DURATION {
+                return Err(DomainError::InternalError(
+                    "Log tail exceeded maximum duration".to_string(),
+                ));
+            }
+
+            let focus = state.next_focus();
+            let query = LogQuery {
+                offset: focus
+                    .as_ref()
+                    .and_then(|id| state.offsets.get(id).copied())
+                    .unwrap_or(0),
+                job_id: focus.clone(),
+            };
+
+            let logs = match pipeline_service
+                .fetch_run_logs(pipeline_id, run_number, query)
+                .await
+            {
+                Ok(logs) => {
+                    consecutive_errors = 0;
+                    logs
+                }
+                Err(e) => {
+                    consecutive_errors += 1;
+                    tracing::warn!(
+                        pipeline_id = pipeline_id,
+                        run_number = run_number,
+                        error = %e,
+                        "Failed to poll run logs"
+                    );
+                    if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
+                        return Err(e);
+                    }
+                    continue;
+                }
+            };
+
+            for job in &logs.jobs {
+                let requested = focus.as_ref().is_none_or(|id| *id == job.id);
+                if !requested {
+                    continue;
+                }
+
+                if !job.content.is_empty() {
+                    event_bus
+                        .emit(CoreEvent::RunLogChunk {
+                            pipeline_id: pipeline_id.to_string(),
+                            run_number,
+                            job_id: job.id.clone(),
+                            job_name: job.name.clone(),
+                            offset: job.offset,
+                            content: job.content.clone(),
+                            is_complete: job.is_complete,
+                        })
+                        .await;
+                }
+
+                state.offsets.insert(job.id.clone(), job.next_offset);
+                // A finished job returns everything past our offset in one
+                // response, so nothing is left to emit for it afterwards.
+                if job.is_complete {
+                    state.drained.insert(job.id.clone());
+                }
+            }
+
+            state.last_snapshot = Some(logs);
+
+            if state.is_finished() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pipedash_plugin_api::{
+        JobLog,
+        PipelineStatus,
+    };
+
+    use super::*;
+
+    fn job(id: &str, is_complete: bool) -> JobLog {
+        JobLog {
+            id: id.to_string(),
+            name: id.to_string(),
+            status: if is_complete {
+                PipelineStatus::Success
+            } else {
+                PipelineStatus::Running
+            },
+            started_at: None,
+            completed_at: None,
+            steps: Vec::new(),
+            content: String::new(),
+            offset: 0,
+            next_offset: 0,
+            is_complete,
+        }
+    }
+
+    fn snapshot(jobs: Vec<JobLog>, is_complete: bool) -> RunLogs {
+        RunLogs {
+            pipeline_id: "github__1__o__r__1".to_string(),
+            run_number: 1,
+            status: PipelineStatus::Running,
+            jobs,
+            is_complete,
+        }
+    }
+
+    #[test]
+    fn test_next_focus_prefers_completed_jobs() {
+        let mut state = TailState::default();
+        assert_eq!(state.next_focus(), None);
+
+        state.last_snapshot = Some(snapshot(vec![job("a", false), job("b", true)], false));
+        assert_eq!(state.next_focus().as_deref(), Some("b"));
+
+        state.drained.insert("b".to_string());
+        assert_eq!(state.next_focus().as_deref(), Some("a"));
+        assert!(!state.is_finished());
+    }
+
+    #[test]
+    fn test_finished_when_run_complete_and_jobs_drained() {
+        let mut state = TailState {
+            last_snapshot: Some(snapshot(vec![job("a", true)], true)),
+            ..Default::default()
+        };
+        assert!(!state.is_finished());
+
+        state.drained.insert("a".to_string());
+        assert!(state.is_finished());
+    }
+}
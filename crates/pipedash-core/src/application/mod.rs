@@ -1,11 +1,42 @@
 pub mod services;
 
+pub use services::agent_service::AgentService;
+pub use services::audit_log::AuditLog;
+pub use services::backfill_service::BackfillService;
+pub use services::backup_service::BackupService;
+pub use services::config_bundle_service::ConfigBundleService;
+pub use services::discovery_service::DiscoveryService;
 pub use services::metrics_service::MetricsService;
+pub use services::notification_center::NotificationCenter;
+pub use services::notification_service::NotificationService;
+pub use services::orchestrator_service::OrchestratorService;
 pub use services::pipeline_service::PipelineService;
 pub use services::provider_service::ProviderService;
+pub use services::provisioning_service::ProvisioningService;
+pub use services::queue_service::QueueService;
+pub use services::retention_service::RunRetentionService;
 
+mod access_control;
+mod diagnostic_bundle;
+mod log_tailer;
+mod pipeline_preview;
 mod refresh_manager;
+pub use access_control::{
+    with_role,
+    AccessControl,
+    LOCAL_ACTOR,
+};
+pub use diagnostic_bundle::DiagnosticBundle;
+pub use log_tailer::{
+    LogTailInfo,
+    LogTailer,
+};
+pub use pipeline_preview::{
+    PipelinePreviewRequest,
+    PipelinePreviewer,
+};
 pub use refresh_manager::{
+    ActivityHint,
     RefreshManager,
     RefreshMode,
 };
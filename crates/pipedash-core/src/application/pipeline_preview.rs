@@ -0,0 +1,309 @@
+use std::collections::{
+    BTreeMap,
+    HashMap,
+};
+use std::hash::{
+    DefaultHasher,
+    Hash,
+    Hasher,
+};
+use std::sync::atomic::{
+    AtomicU64,
+    Ordering,
+};
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use pipedash_plugin_api::{
+    max_response_bytes,
+    ConditionalRequestCache,
+    PaginationParams,
+    Plugin as PluginTrait,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use tokio::task::JoinHandle;
+
+use super::services::provider_service::ProviderService;
+use crate::domain::{
+    DomainError,
+    DomainResult,
+    PaginatedAvailablePipelines,
+    PipelineFilter,
+};
+use crate::event::{
+    CoreEvent,
+    EventBus,
+};
+use crate::infrastructure::HttpClientManager;
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+const MAX_PREVIEW_PAGES: usize = 200;
+const MAX_PREVIEW_CACHES: usize = 16;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PipelinePreviewRequest {
+    // Chosen by the caller so it can listen for the events before starting;
+    // generated when missing. Starting an id that is still running restarts
+    // it.
+    #[serde(default)]
+    pub preview_id: Option<String>,
+    pub provider_type: String,
+    #[serde(default)]
+    pub config: HashMap<String, String>,
+    #[serde(default)]
+    pub org: Option<String>,
+    #[serde(default)]
+    pub search: Option<String>,
+    #[serde(default)]
+    pub page_size: Option<usize>,
+}
+
+impl PipelinePreviewRequest {
+    // Previews with the same credentials and settings share validators, so
+    // reopening the setup step is answered with 304s where the provider
+    // supports them.
+    fn cache_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.provider_type.hash(&mut hasher);
+        self.config
+            .iter()
+            .collect::<BTreeMap<_, _>>()
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+// Lists the pipelines a provider config can see before the provider exists,
+// either one page at a time or streamed: every page is emitted as a
+// `PreviewPage` event as soon as it arrives, followed by `PreviewFinished`.
+pub struct PipelinePreviewer {
+    provider_service: Arc<ProviderService>,
+    http_client_manager: Arc<HttpClientManager>,
+    event_bus: Arc<dyn EventBus>,
+    caches: DashMap<u64, Arc<ConditionalRequestCache>>,
+    sessions: Arc<DashMap<String, JoinHandle<()>>>,
+    next_id: AtomicU64,
+}
+
+impl PipelinePreviewer {
+    pub fn new(
+        provider_service: Arc<ProviderService>, http_client_manager: Arc<HttpClientManager>,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Self {
+        Self {
+            provider_service,
+            http_client_manager,
+            event_bus,
+            caches: DashMap::new(),
+            sessions: Arc::new(DashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub async fn fetch_page(
+        &self, request: PipelinePreviewRequest, page: usize,
+    ) -> DomainResult<PaginatedAvailablePipelines> {
+        let params = Self::pagination(&request, page)?;
+        let (plugin, filter) = self.prepare(&request).await?;
+        Self::fetch(plugin.as_ref(), &filter, &request, params).await
+    }
+
+    // Credentials are checked before returning, so a bad token fails the call
+    // instead of the stream.
+    pub async fn start(&self, request: PipelinePreviewRequest) -> DomainResult<String> {
+        let params = Self::pagination(&request, 1)?;
+        let preview_id = request.preview_id.clone().unwrap_or_else(|| {
+            format!(
+                "preview-{}-{}",
+                chrono::Utc::now().timestamp_millis(),
+                self.next_id.fetch_add(1, Ordering::Relaxed)
+            )
+        });
+        if let Some((_, handle)) = self.sessions.remove(&preview_id) {
+            handle.abort();
+        }
+
+        let (plugin, filter) = self.prepare(&request).await?;
+
+        let event_bus = Arc::clone(&self.event_bus);
+        let sessions = Arc::clone(&self.sessions);
+        let task_id = preview_id.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut total = 0;
+            let mut pages = 0;
+            let mut params = params;
+
+            let error = loop {
+                let page =
+                    match Self::fetch(plugin.as_ref(), &filter, &request, params.clone()).await {
+                        Ok(page) => page,
+                        Err(e) => break Some(e.to_string()),
+                    };
+                let has_more = page.has_more && pages + 1 < MAX_PREVIEW_PAGES;
+                total += page.items.len();
+                pages += 1;
+
+                event_bus
+                    .emit(CoreEvent::PreviewPage {
+                        preview_id: task_id.clone(),
+                        page,
+                    })
+                    .await;
+
+                if !has_more {
+                    break None;
+                }
+                params.page += 1;
+            };
+
+            sessions.remove(&task_id);
+            event_bus
+                .emit(CoreEvent::PreviewFinished {
+                    preview_id: task_id,
+                    total,
+                    pages,
+                    cancelled: false,
+                    error,
+                })
+                .await;
+        });
+
+        self.sessions.insert(preview_id.clone(), handle);
+        tracing::debug!(preview_id = %preview_id, "Started pipeline preview");
+        Ok(preview_id)
+    }
+
+    // Stops fetching further pages; pages already emitted stay valid.
+    pub async fn cancel(&self, preview_id: &str) -> bool {
+        let Some((_, handle)) = self.sessions.remove(preview_id) else {
+            return false;
+        };
+
+        handle.abort();
+        self.event_bus
+            .emit(CoreEvent::PreviewFinished {
+                preview_id: preview_id.to_string(),
+                total: 0,
+                pages: 0,
+                cancelled: true,
+                error: None,
+            })
+            .await;
+        true
+    }
+
+    pub fn stop_all(&self) {
+        for entry in self.sessions.iter() {
+            entry.abort();
+        }
+        self.sessions.clear();
+    }
+
+    fn pagination(request: &PipelinePreviewRequest, page: usize) -> DomainResult<PaginationParams> {
+        let params = PaginationParams {
+            page: page.max(1),
+            page_size: request
+                .page_size
+                .unwrap_or(DEFAULT_PAGE_SIZE)
+                .clamp(10, 200),
+        };
+        params.validate().map_err(DomainError::InvalidConfig)?;
+        Ok(params)
+    }
+
+    async fn prepare(
+        &self, request: &PipelinePreviewRequest,
+    ) -> DomainResult<(Box<dyn PluginTrait>, PipelineFilter)> {
+        let mut plugin = self
+            .provider_service
+            .create_uninitialized_plugin(&request.provider_type)?;
+        let filter = PipelineFilter::from_config(&request.config)?;
+        let http_client = self
+            .http_client_manager
+            .client_for_provider(&request.config)?;
+
+        plugin.set_conditional_cache(self.conditional_cache(request));
+        plugin
+            .initialize(0, request.config.clone(), Some(http_client))
+            .map_err(|e| DomainError::InvalidConfig(format!("Failed to initialize plugin: {e}")))?;
+        plugin.validate_credentials().await.map_err(|e| {
+            DomainError::InvalidConfig(format!("Failed to validate credentials: {e}"))
+        })?;
+
+        Ok((plugin, filter))
+    }
+
+    fn conditional_cache(&self, request: &PipelinePreviewRequest) -> Arc<ConditionalRequestCache> {
+        let key = request.cache_key();
+        if self.caches.len() >= MAX_PREVIEW_CACHES && !self.caches.contains_key(&key) {
+            self.caches.clear();
+        }
+        Arc::clone(
+            self.caches
+                .entry(key)
+                .or_insert_with(|| {
+                    Arc::new(
+                        ConditionalRequestCache::new()
+                            .with_max_response_bytes(max_response_bytes(&request.config)),
+                    )
+                })
+                .value(),
+        )
+    }
+
+    async fn fetch(
+        plugin: &dyn PluginTrait, filter: &PipelineFilter, request: &PipelinePreviewRequest,
+        params: PaginationParams,
+    ) -> DomainResult<PaginatedAvailablePipelines> {
+        let mut page = plugin
+            .fetch_available_pipelines_filtered(
+                request.org.clone(),
+                request.search.clone(),
+                Some(params),
+            )
+            .await
+            .map_err(|e| {
+                DomainError::InternalError(format!("Failed to fetch available pipelines: {e}"))
+            })?;
+        page.items.retain(|item| !filter.excludes_available(item));
+        Ok(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_ignores_config_order() {
+        let request = |pairs: &[(&str, &str)]| PipelinePreviewRequest {
+            provider_type: "github".into(),
+            config: pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        };
+
+        let a = request(&[("token", "t1"), ("base_url", "https://example.com")]);
+        let b = request(&[("base_url", "https://example.com"), ("token", "t1")]);
+        let c = request(&[("token", "t2"), ("base_url", "https://example.com")]);
+        assert_eq!(a.cache_key(), b.cache_key());
+        assert_ne!(a.cache_key(), c.cache_key());
+    }
+
+    #[test]
+    fn test_pagination_clamps_page_size() {
+        let request = PipelinePreviewRequest {
+            page_size: Some(1000),
+            ..Default::default()
+        };
+        let params = PipelinePreviewer::pagination(&request, 0).unwrap();
+        assert_eq!(params.page, 1);
+        assert_eq!(params.page_size, 200);
+    }
+}
@@ -1,3 +1,7 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 use std::sync::Arc;
 use std::time::{
     Duration,
@@ -14,13 +18,40 @@ use tokio::sync::{
 };
 use tokio::time::interval;
 
+use super::services::agent_service::AgentService;
 use super::services::metrics_service::MetricsService;
+use super::services::notification_center::NotificationCenter;
+use super::services::notification_service::NotificationService;
+use super::services::orchestrator_service::OrchestratorService;
 use super::services::pipeline_service::PipelineService;
-use crate::domain::Pipeline;
+use super::services::queue_service::QueueService;
+use super::services::retention_service::RunRetentionService;
+use crate::domain::{
+    Pipeline,
+    RefreshSchedule,
+};
 use crate::event::{
     CoreEvent,
     EventBus,
 };
+use crate::infrastructure::database::RefreshLock;
+
+// Providers that delivered a webhook within this window are left out of regular
+// polling; polling resumes on its own if webhooks stop arriving.
+const WEBHOOK_ACTIVE_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+const RUN_HISTORY_PRUNE_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+
+const AGENT_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+const QUEUE_SAMPLE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+const ANOMALY_SCAN_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+// Slowest adaptive cadence, used while no window is on screen.
+const BACKGROUND_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+const VIEWED_PIPELINE_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -49,38 +80,109 @@ impl std::str::FromStr for RefreshMode {
     }
 }
 
+// Sent by the UI as the user moves around. While the window is hidden, the
+// adaptive interval is held at the background cadence; explicit per-pipeline
+// overrides still apply so alerts keep arriving.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ActivityHint {
+    WindowVisible,
+    WindowHidden,
+    PipelineOpened { pipeline_id: String },
+    PipelineClosed,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Activity {
+    hidden: bool,
+    viewed_pipeline: Option<String>,
+}
+
 pub struct RefreshManager {
     pipeline_service: Arc<PipelineService>,
     metrics_service: Option<Arc<MetricsService>>,
+    retention_service: Arc<RunRetentionService>,
+    agent_service: Arc<AgentService>,
+    queue_service: Arc<QueueService>,
+    orchestrator_service: Arc<OrchestratorService>,
+    notification_service: Arc<NotificationService>,
+    notification_center: Arc<NotificationCenter>,
     event_bus: Arc<dyn EventBus>,
+    refresh_lock: Option<Arc<RefreshLock>>,
     mode: Arc<RwLock<RefreshMode>>,
     running: Arc<RwLock<bool>>,
     last_refresh: Arc<Mutex<Option<Instant>>>,
     last_metrics_cleanup: Arc<Mutex<Option<Instant>>>,
+    last_anomaly_scan: Arc<Mutex<Option<Instant>>>,
+    last_run_history_prune: Arc<Mutex<Option<Instant>>>,
+    last_agent_refresh: Arc<Mutex<Option<Instant>>>,
+    last_queue_sample: Arc<Mutex<Option<Instant>>>,
     no_change_count: Arc<Mutex<u32>>,
     current_interval: Arc<Mutex<Duration>>,
     priority_queue: Arc<Mutex<Vec<i64>>>,
+    webhook_activity: Arc<Mutex<HashMap<i64, Instant>>>,
+    provider_last_fetch: Arc<Mutex<HashMap<i64, Instant>>>,
+    activity: Arc<RwLock<Activity>>,
 }
 
 impl RefreshManager {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pipeline_service: Arc<PipelineService>, metrics_service: Option<Arc<MetricsService>>,
-        event_bus: Arc<dyn EventBus>,
+        retention_service: Arc<RunRetentionService>, agent_service: Arc<AgentService>,
+        queue_service: Arc<QueueService>, orchestrator_service: Arc<OrchestratorService>,
+        notification_service: Arc<NotificationService>,
+        notification_center: Arc<NotificationCenter>, event_bus: Arc<dyn EventBus>,
+        refresh_lock: Option<Arc<RefreshLock>>,
     ) -> Self {
         Self {
             pipeline_service,
             metrics_service,
+            retention_service,
+            agent_service,
+            queue_service,
+            orchestrator_service,
+            notification_service,
+            notification_center,
             event_bus,
+            refresh_lock,
             mode: Arc::new(RwLock::new(RefreshMode::Active)),
             running: Arc::new(RwLock::new(false)),
             last_refresh: Arc::new(Mutex::new(None)),
             last_metrics_cleanup: Arc::new(Mutex::new(None)),
+            last_anomaly_scan: Arc::new(Mutex::new(None)),
+            last_run_history_prune: Arc::new(Mutex::new(None)),
+            last_agent_refresh: Arc::new(Mutex::new(None)),
+            last_queue_sample: Arc::new(Mutex::new(None)),
             no_change_count: Arc::new(Mutex::new(0)),
             current_interval: Arc::new(Mutex::new(Duration::from_secs(10))),
             priority_queue: Arc::new(Mutex::new(Vec::new())),
+            webhook_activity: Arc::new(Mutex::new(HashMap::new())),
+            provider_last_fetch: Arc::new(Mutex::new(HashMap::new())),
+            activity: Arc::new(RwLock::new(Activity::default())),
         }
     }
 
+    pub async fn record_webhook(&self, provider_id: i64) {
+        let mut activity = self.webhook_activity.lock().await;
+        activity.insert(provider_id, Instant::now());
+    }
+
+    pub async fn webhook_providers(&self) -> Vec<i64> {
+        Self::active_webhook_providers(&self.webhook_activity)
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    async fn active_webhook_providers(
+        webhook_activity: &Mutex<HashMap<i64, Instant>>,
+    ) -> HashSet<i64> {
+        let mut activity = webhook_activity.lock().await;
+        activity.retain(|_, last_seen| last_seen.elapsed() < WEBHOOK_ACTIVE_WINDOW);
+        activity.keys().copied().collect()
+    }
+
     pub async fn prioritize_provider(&self, provider_id: i64) {
         let mut queue = self.priority_queue.lock().await;
         if !queue.contains(&provider_id) {
@@ -102,14 +204,28 @@ impl RefreshManager {
 
         let pipeline_service = Arc::clone(&self.pipeline_service);
         let metrics_service = self.metrics_service.clone();
+        let retention_service = Arc::clone(&self.retention_service);
+        let agent_service = Arc::clone(&self.agent_service);
+        let queue_service = Arc::clone(&self.queue_service);
+        let orchestrator_service = Arc::clone(&self.orchestrator_service);
+        let notification_service = Arc::clone(&self.notification_service);
+        let notification_center = Arc::clone(&self.notification_center);
         let event_bus = Arc::clone(&self.event_bus);
+        let refresh_lock = self.refresh_lock.clone();
         let mode = Arc::clone(&self.mode);
         let running = Arc::clone(&self.running);
         let last_refresh = Arc::clone(&self.last_refresh);
         let last_metrics_cleanup = Arc::clone(&self.last_metrics_cleanup);
+        let last_anomaly_scan = Arc::clone(&self.last_anomaly_scan);
+        let last_run_history_prune = Arc::clone(&self.last_run_history_prune);
+        let last_agent_refresh = Arc::clone(&self.last_agent_refresh);
+        let last_queue_sample = Arc::clone(&self.last_queue_sample);
         let no_change_count = Arc::clone(&self.no_change_count);
         let current_interval = Arc::clone(&self.current_interval);
         let priority_queue = Arc::clone(&self.priority_queue);
+        let webhook_activity = Arc::clone(&self.webhook_activity);
+        let provider_last_fetch = Arc::clone(&self.provider_last_fetch);
+        let activity = Arc::clone(&self.activity);
 
         tokio::spawn(async move {
             let mut tick_interval = interval(Duration::from_secs(5));
@@ -152,8 +268,51 @@ impl RefreshManager {
                 }
 
                 if current_mode == RefreshMode::Active {
-                    let refresh_interval = *current_interval.lock().await;
-                    let should_refresh = {
+                    let should_refresh_agents = {
+                        let mut last = last_agent_refresh.lock().await;
+                        let due = last.is_none_or(|t| t.elapsed() >= AGENT_REFRESH_INTERVAL);
+                        if due {
+                            *last = Some(Instant::now());
+                        }
+                        due
+                    };
+
+                    if should_refresh_agents {
+                        let service = Arc::clone(&agent_service);
+                        tokio::spawn(async move {
+                            if let Err(e) = service.fetch_agents(None).await {
+                                tracing::warn!(error = %e, "Agent refresh failed");
+                            }
+                        });
+                    }
+
+                    let should_sample_queues = {
+                        let mut last = last_queue_sample.lock().await;
+                        let due = last.is_none_or(|t| t.elapsed() >= QUEUE_SAMPLE_INTERVAL);
+                        if due {
+                            *last = Some(Instant::now());
+                        }
+                        due
+                    };
+
+                    if should_sample_queues {
+                        let service = Arc::clone(&queue_service);
+                        tokio::spawn(async move {
+                            if let Err(e) = service.sample_queues().await {
+                                tracing::warn!(error = %e, "Queue sampling failed");
+                            }
+                        });
+                    }
+                }
+
+                if current_mode == RefreshMode::Active {
+                    let activity = activity.read().await.clone();
+                    let mut refresh_interval = *current_interval.lock().await;
+                    if activity.hidden {
+                        refresh_interval = refresh_interval.max(BACKGROUND_REFRESH_INTERVAL);
+                    }
+
+                    let global_due = {
                         let last = last_refresh.lock().await;
                         match *last {
                             Some(last_time) => last_time.elapsed() >= refresh_interval,
@@ -161,18 +320,39 @@ impl RefreshManager {
                         }
                     };
 
-                    if !should_refresh {
-                        continue;
+                    let mut schedule = pipeline_service.refresh_schedule().await;
+                    if let (false, Some(pipeline_id)) = (activity.hidden, &activity.viewed_pipeline)
+                    {
+                        schedule.boost(pipeline_id, VIEWED_PIPELINE_REFRESH_INTERVAL);
                     }
+                    let old_cached = pipeline_service.get_cached_pipelines(None).await.ok();
 
-                    {
+                    let mut skipped = Self::active_webhook_providers(&webhook_activity).await;
+                    let Some(not_due) = Self::providers_not_due(
+                        &pipeline_service,
+                        &schedule,
+                        old_cached.as_deref().unwrap_or_default(),
+                        refresh_interval,
+                        &skipped,
+                        &provider_last_fetch,
+                    )
+                    .await
+                    else {
+                        continue;
+                    };
+                    skipped.extend(not_due);
+
+                    if global_due {
                         let mut last = last_refresh.lock().await;
                         *last = Some(Instant::now());
                     }
 
-                    let old_cached = pipeline_service.get_cached_pipelines(None).await.ok();
+                    if let Some(lock) = &refresh_lock {
+                        skipped
+                            .extend(Self::providers_led_elsewhere(&pipeline_service, lock).await);
+                    }
 
-                    match pipeline_service.fetch_pipelines(None).await {
+                    match pipeline_service.fetch_pipelines_skipping(&skipped).await {
                         Ok(pipelines) => {
                             let timestamp = std::time::SystemTime::now()
                                 .duration_since(std::time::UNIX_EPOCH)
@@ -181,13 +361,24 @@ impl RefreshManager {
 
                             event_bus
                                 .emit(CoreEvent::PipelinesUpdated {
-                                    pipelines: pipelines.clone(),
+                                    pipelines: pipeline_service
+                                        .annotate_maintenance(pipelines.clone())
+                                        .await,
                                     provider_id: None,
                                     timestamp,
                                 })
                                 .await;
 
                             let has_changes = if let Some(cached) = old_cached {
+                                // Snoozed pipelines stay up to date in the
+                                // cache
+                                // but never trigger notifications or chains,
+                                // nor
+                                // keep the adaptive interval short.
+                                let previous = schedule.without_snoozed(&cached);
+                                let current = schedule.without_snoozed(&pipelines);
+                                let monitored_changes = Self::has_changes(&previous, &current);
+
                                 let changes = Self::has_changes(&cached, &pipelines);
                                 if changes {
                                     event_bus
@@ -196,6 +387,27 @@ impl RefreshManager {
                                         })
                                         .await;
 
+                                    // Pipelines in a maintenance window are
+                                    // still watched, but planned work doesn't
+                                    // page anyone or set off dependent
+                                    // pipelines.
+                                    let maintenance = pipeline_service.maintenance_calendar().await;
+                                    let now = chrono::Utc::now();
+                                    let previous =
+                                        maintenance.without_in_maintenance(&previous, now);
+                                    let current = maintenance.without_in_maintenance(&current, now);
+
+                                    let orchestrator = Arc::clone(&orchestrator_service);
+                                    let notifier = Arc::clone(&notification_service);
+                                    let center = Arc::clone(&notification_center);
+                                    tokio::spawn(async move {
+                                        tokio::join!(
+                                            orchestrator.handle_status_changes(&previous, &current),
+                                            notifier.handle_status_changes(&previous, &current),
+                                            center.handle_status_changes(&previous, &current),
+                                        );
+                                    });
+
                                     for new_pipeline in &pipelines {
                                         if let Some(old_pipeline) =
                                             cached.iter().find(|p| p.id == new_pipeline.id)
@@ -214,7 +426,7 @@ impl RefreshManager {
                                         }
                                     }
                                 }
-                                changes
+                                monitored_changes
                             } else {
                                 false
                             };
@@ -231,7 +443,10 @@ impl RefreshManager {
                                         "Changes detected, decreased interval"
                                     );
                                 }
-                            } else {
+                            } else if global_due {
+                                // Early cycles for short per-pipeline overrides
+                                // do
+                                // not count towards backing off.
                                 *count += 1;
                                 if *count >= 3 && *interval < Duration::from_secs(300) {
                                     *interval = Duration::from_secs((*interval).as_secs() * 2)
@@ -277,6 +492,65 @@ impl RefreshManager {
                             }
                         }
                     }
+
+                    let should_scan = {
+                        let last = last_anomaly_scan.lock().await;
+                        match *last {
+                            Some(last_time) => last_time.elapsed() >= ANOMALY_SCAN_INTERVAL,
+                            None => true,
+                        }
+                    };
+
+                    if should_scan {
+                        {
+                            let mut last = last_anomaly_scan.lock().await;
+                            *last = Some(Instant::now());
+                        }
+
+                        match metrics_svc.detect_anomalies().await {
+                            Ok(anomalies) if !anomalies.is_empty() => {
+                                tracing::info!(
+                                    count = anomalies.len(),
+                                    "Metric anomalies detected"
+                                );
+                                let maintenance = pipeline_service.maintenance_calendar().await;
+                                let now = chrono::Utc::now();
+                                let anomalies: Vec<_> = anomalies
+                                    .into_iter()
+                                    .filter(|a| !maintenance.in_maintenance(&a.pipeline_id, now))
+                                    .collect();
+                                notification_service.handle_anomalies(&anomalies).await;
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                tracing::error!(error = %e, "Metric anomaly detection failed");
+                            }
+                        }
+                    }
+                }
+
+                let should_prune = {
+                    let last = last_run_history_prune.lock().await;
+                    match *last {
+                        Some(last_time) => last_time.elapsed() >= RUN_HISTORY_PRUNE_INTERVAL,
+                        None => true,
+                    }
+                };
+
+                if should_prune {
+                    {
+                        let mut last = last_run_history_prune.lock().await;
+                        *last = Some(Instant::now());
+                    }
+
+                    match retention_service.prune_run_history().await {
+                        Ok(deleted) => {
+                            tracing::info!(deleted = deleted, "Run history pruning complete");
+                        }
+                        Err(e) => {
+                            tracing::error!(error = %e, "Run history pruning failed");
+                        }
+                    }
                 }
             }
 
@@ -287,6 +561,124 @@ impl RefreshManager {
     pub async fn stop(&self) {
         let mut running = self.running.write().await;
         *running = false;
+        drop(running);
+
+        if let Some(lock) = &self.refresh_lock {
+            lock.release().await;
+        }
+    }
+
+    // Providers whose own schedule has not come up yet, or None when no
+    // provider is due at all. Providers that are due are marked as fetched.
+    async fn providers_not_due(
+        pipeline_service: &PipelineService, schedule: &RefreshSchedule, cached: &[Pipeline],
+        default_interval: Duration, skipped: &HashSet<i64>,
+        provider_last_fetch: &Mutex<HashMap<i64, Instant>>,
+    ) -> Option<HashSet<i64>> {
+        let provider_ids: Vec<i64> =
+            match pipeline_service.provider_service().list_providers().await {
+                Ok(providers) => providers
+                    .iter()
+                    .filter(|p| p.enabled)
+                    .map(|p| p.id)
+                    .filter(|id| !skipped.contains(id))
+                    .collect(),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to list providers for scheduling");
+                    return None;
+                }
+            };
+
+        let mut last_fetch = provider_last_fetch.lock().await;
+        let mut not_due = HashSet::new();
+        let mut any_due = false;
+
+        for provider_id in provider_ids {
+            let interval = schedule.provider_interval(
+                cached.iter().filter(|p| p.provider_id == provider_id),
+                default_interval,
+            );
+            let due = interval.is_some_and(|interval| {
+                last_fetch
+                    .get(&provider_id)
+                    .is_none_or(|last| last.elapsed() >= interval)
+            });
+
+            if due {
+                last_fetch.insert(provider_id, Instant::now());
+                any_due = true;
+            } else {
+                not_due.insert(provider_id);
+            }
+        }
+
+        any_due.then_some(not_due)
+    }
+
+    // Providers whose refresh lock is held by another instance. Their cached
+    // pipelines are still served; the owning instance keeps them fresh.
+    async fn providers_led_elsewhere(
+        pipeline_service: &PipelineService, lock: &RefreshLock,
+    ) -> HashSet<i64> {
+        let provider_ids: Vec<i64> =
+            match pipeline_service.provider_service().list_providers().await {
+                Ok(providers) => providers
+                    .iter()
+                    .filter(|p| p.enabled)
+                    .map(|p| p.id)
+                    .collect(),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to list providers for refresh locking");
+                    return HashSet::new();
+                }
+            };
+
+        let owned = lock.claim(&provider_ids).await;
+        provider_ids
+            .into_iter()
+            .filter(|id| !owned.contains(id))
+            .collect()
+    }
+
+    pub async fn set_activity_hint(&self, hint: ActivityHint) {
+        tracing::debug!(hint = ?hint, "Refresh activity hint");
+
+        if let ActivityHint::PipelineOpened { pipeline_id } = &hint {
+            self.pipeline_service
+                .record_pipeline_usage(pipeline_id)
+                .await;
+        }
+
+        let mut activity = self.activity.write().await;
+        let opened = match hint {
+            ActivityHint::WindowVisible => {
+                let was_hidden = std::mem::replace(&mut activity.hidden, false);
+                activity.viewed_pipeline.clone().filter(|_| was_hidden)
+            }
+            ActivityHint::WindowHidden => {
+                activity.hidden = true;
+                None
+            }
+            ActivityHint::PipelineOpened { pipeline_id } => {
+                activity.viewed_pipeline = Some(pipeline_id.clone());
+                (!activity.hidden).then_some(pipeline_id)
+            }
+            ActivityHint::PipelineClosed => {
+                activity.viewed_pipeline = None;
+                None
+            }
+        };
+        drop(activity);
+
+        // Whatever just came on screen is fetched on the next tick instead of
+        // waiting out the rest of its interval.
+        if let Some(provider_id) = opened
+            .as_deref()
+            .and_then(|id| id.split("__").nth(1))
+            .and_then(|id| id.parse::<i64>().ok())
+        {
+            self.provider_last_fetch.lock().await.remove(&provider_id);
+        }
     }
 
     pub async fn get_mode(&self) -> RefreshMode {
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use pipedash_plugin_api::Capability;
+
+use super::provider_service::ProviderService;
+use crate::domain::{
+    newly_offline,
+    BuildAgent,
+    DomainResult,
+    ProviderAgent,
+};
+use crate::event::{
+    CoreEvent,
+    EventBus,
+};
+use crate::infrastructure::database::Repository;
+
+pub struct AgentService {
+    repository: Arc<Repository>,
+    provider_service: Arc<ProviderService>,
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl AgentService {
+    pub fn new(
+        repository: Arc<Repository>, provider_service: Arc<ProviderService>,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Self {
+        Self {
+            repository,
+            provider_service,
+            event_bus,
+        }
+    }
+
+    pub async fn get_cached_agents(
+        &self, provider_id: Option<i64>,
+    ) -> DomainResult<Vec<ProviderAgent>> {
+        self.repository.get_cached_agents(provider_id).await
+    }
+
+    // Without a provider, every provider whose plugin lists agents is fetched
+    // and failures are logged per provider instead of failing the whole call.
+    pub async fn fetch_agents(&self, provider_id: Option<i64>) -> DomainResult<Vec<ProviderAgent>> {
+        let provider_ids = match provider_id {
+            Some(id) => vec![id],
+            None => {
+                self.provider_service
+                    .provider_ids_with_capability(Capability::Agents)
+                    .await?
+            }
+        };
+
+        let mut all_agents = Vec::new();
+        for id in provider_ids {
+            match self.refresh_provider(id).await {
+                Ok(agents) => all_agents.extend(agents.into_iter().map(|agent| ProviderAgent {
+                    provider_id: id,
+                    agent,
+                })),
+                Err(e) if provider_id.is_some() => return Err(e),
+                Err(e) => {
+                    tracing::warn!(provider_id = id, error = %e, "Failed to fetch agents");
+                }
+            }
+        }
+
+        Ok(all_agents)
+    }
+
+    async fn refresh_provider(&self, provider_id: i64) -> DomainResult<Vec<BuildAgent>> {
+        let provider = self.provider_service.get_provider(provider_id).await?;
+        let agents = provider.fetch_agents().await?;
+
+        let previous: Vec<BuildAgent> = self
+            .repository
+            .get_cached_agents(Some(provider_id))
+            .await?
+            .into_iter()
+            .map(|cached| cached.agent)
+            .collect();
+        let went_offline = newly_offline(&previous, &agents);
+
+        self.repository
+            .replace_cached_agents(provider_id, &agents)
+            .await?;
+
+        self.event_bus
+            .emit(CoreEvent::AgentsUpdated {
+                provider_id,
+                total: agents.len(),
+                offline: agents.iter().filter(|agent| agent.is_offline()).count(),
+            })
+            .await;
+
+        if !went_offline.is_empty() {
+            tracing::warn!(
+                provider_id = provider_id,
+                count = went_offline.len(),
+                "Build agents went offline"
+            );
+            self.event_bus
+                .emit(CoreEvent::AgentsOffline {
+                    provider_id,
+                    agents: went_offline,
+                })
+                .await;
+        }
+
+        Ok(agents)
+    }
+}
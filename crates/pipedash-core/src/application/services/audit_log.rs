@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use crate::application::AccessControl;
+use crate::domain::{
+    Action,
+    AuditEntry,
+    DomainResult,
+};
+use crate::infrastructure::database::Repository;
+
+const DEFAULT_LIST_LIMIT: usize = 100;
+const MAX_LIST_LIMIT: usize = 1000;
+
+pub struct AuditLog {
+    repository: Arc<Repository>,
+    access_control: Arc<AccessControl>,
+}
+
+impl AuditLog {
+    pub fn new(repository: Arc<Repository>, access_control: Arc<AccessControl>) -> Self {
+        Self {
+            repository,
+            access_control,
+        }
+    }
+
+    // Every entry is logged under the `pipedash::audit` target as well, so it
+    // reaches log collectors even when the database write fails.
+    pub async fn record(&self, entry: AuditEntry) {
+        tracing::warn!(
+            target: "pipedash::audit",
+            action = entry.action.as_str(),
+            actor = %entry.actor,
+            target = %entry.target,
+            detail = entry.detail.as_deref().unwrap_or_default(),
+            "Audit event"
+        );
+        if let Err(e) = self.repository.insert_audit_entry(&entry).await {
+            tracing::warn!(error = %e, "Failed to store audit entry");
+        }
+    }
+
+    pub async fn list(&self, limit: Option<usize>) -> DomainResult<Vec<AuditEntry>> {
+        self.access_control.ensure(Action::ViewAuditLog)?;
+        let limit = limit.unwrap_or(DEFAULT_LIST_LIMIT).clamp(1, MAX_LIST_LIMIT);
+        self.repository.list_audit_entries(limit).await
+    }
+}
@@ -0,0 +1,319 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use dashmap::DashMap;
+use tokio::task::JoinHandle;
+
+use super::metrics_service::MetricsService;
+use super::pipeline_service::PipelineService;
+use super::provider_service::ProviderService;
+use crate::domain::{
+    Action,
+    BackfillStatus,
+    DomainError,
+    DomainResult,
+    RunBackfillJob,
+    DEFAULT_BACKFILL_DEPTH,
+};
+use crate::event::{
+    CoreEvent,
+    EventBus,
+};
+use crate::infrastructure::database::Repository;
+
+// Pause between pipelines so an import does not eat the provider's rate limit
+// ahead of regular refreshes.
+const PIPELINE_DELAY: Duration = Duration::from_millis(500);
+const DEFAULT_RETRY_WAIT: Duration = Duration::from_secs(60);
+
+pub struct BackfillService {
+    repository: Arc<Repository>,
+    provider_service: Arc<ProviderService>,
+    pipeline_service: Arc<PipelineService>,
+    metrics_service: Option<Arc<MetricsService>>,
+    event_bus: Arc<dyn EventBus>,
+    tasks: DashMap<i64, JoinHandle<()>>,
+}
+
+impl BackfillService {
+    pub fn new(
+        repository: Arc<Repository>, provider_service: Arc<ProviderService>,
+        pipeline_service: Arc<PipelineService>, metrics_service: Option<Arc<MetricsService>>,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Self {
+        Self {
+            repository,
+            provider_service,
+            pipeline_service,
+            metrics_service,
+            event_bus,
+            tasks: DashMap::new(),
+        }
+    }
+
+    pub async fn get_job(&self, provider_id: i64) -> DomainResult<Option<RunBackfillJob>> {
+        self.repository.get_run_backfill_job(provider_id).await
+    }
+
+    pub async fn list_jobs(&self) -> DomainResult<Vec<RunBackfillJob>> {
+        self.repository.list_run_backfill_jobs(None).await
+    }
+
+    // Starts importing the run history of every pipeline of the provider. A
+    // job that is already running is returned as it is, and an interrupted
+    // one with the same depth carries on from the pipeline it stopped at.
+    pub async fn start(
+        self: &Arc<Self>, provider_id: i64, depth: Option<usize>,
+    ) -> DomainResult<RunBackfillJob> {
+        self.provider_service
+            .access_control()
+            .ensure(Action::ManageProviders)?;
+
+        let depth = depth.unwrap_or(DEFAULT_BACKFILL_DEPTH);
+        RunBackfillJob::validate_depth(depth)?;
+        self.provider_service.get_provider(provider_id).await?;
+
+        let existing = self.repository.get_run_backfill_job(provider_id).await?;
+        if self.is_active(provider_id) {
+            if let Some(job) = existing {
+                return Ok(job);
+            }
+        }
+
+        let job = match existing {
+            Some(mut job)
+                if job.depth == depth
+                    && job.status != BackfillStatus::Completed
+                    && job.next_pipeline().is_some() =>
+            {
+                job.status = BackfillStatus::Running;
+                job.error = None;
+                job
+            }
+            _ => {
+                let mut pipelines = self
+                    .pipeline_service
+                    .get_cached_pipelines(Some(provider_id))
+                    .await?;
+                if pipelines.is_empty() {
+                    pipelines = self
+                        .pipeline_service
+                        .fetch_pipelines(Some(provider_id))
+                        .await?;
+                }
+                RunBackfillJob::new(
+                    provider_id,
+                    depth,
+                    pipelines.into_iter().map(|p| p.id).collect(),
+                )
+            }
+        };
+
+        self.save_and_emit(&job).await?;
+        if job.is_running() {
+            self.spawn(job.clone());
+        }
+        Ok(job)
+    }
+
+    pub async fn cancel(&self, provider_id: i64) -> DomainResult<RunBackfillJob> {
+        self.provider_service
+            .access_control()
+            .ensure(Action::ManageProviders)?;
+
+        if let Some((_, handle)) = self.tasks.remove(&provider_id) {
+            handle.abort();
+        }
+
+        let mut job = self
+            .repository
+            .get_run_backfill_job(provider_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("No backfill for provider {}", provider_id))
+            })?;
+        if job.is_running() {
+            job.finish(BackfillStatus::Cancelled, None);
+            self.save_and_emit(&job).await?;
+        }
+        Ok(job)
+    }
+
+    // Picks up jobs that were still running when the app last stopped.
+    pub async fn resume_interrupted(self: &Arc<Self>) {
+        let jobs = match self
+            .repository
+            .list_run_backfill_jobs(Some(BackfillStatus::Running))
+            .await
+        {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load interrupted run backfills");
+                return;
+            }
+        };
+
+        for job in jobs {
+            if !self.is_active(job.provider_id) {
+                tracing::info!(
+                    provider_id = job.provider_id,
+                    remaining = job.pending_pipelines.len(),
+                    "Resuming run history backfill"
+                );
+                self.spawn(job);
+            }
+        }
+    }
+
+    // Running jobs keep their stored status so they resume on the next start.
+    pub fn stop_all(&self) {
+        for entry in self.tasks.iter() {
+            entry.value().abort();
+        }
+        self.tasks.clear();
+    }
+
+    fn is_active(&self, provider_id: i64) -> bool {
+        self.tasks
+            .get(&provider_id)
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    fn spawn(self: &Arc<Self>, job: RunBackfillJob) {
+        let provider_id = job.provider_id;
+        let service = Arc::clone(self);
+        let handle = tokio::spawn(async move { service.run(job).await });
+        if let Some(previous) = self.tasks.insert(provider_id, handle) {
+            previous.abort();
+        }
+    }
+
+    async fn run(&self, mut job: RunBackfillJob) {
+        while let Some(pipeline_id) = job.next_pipeline().map(str::to_string) {
+            let provider = match self.provider_service.get_provider(job.provider_id).await {
+                Ok(provider) => provider,
+                // The provider was removed along with its job.
+                Err(DomainError::ProviderNotFound(_)) => return,
+                Err(e) => {
+                    job.finish(BackfillStatus::Failed, Some(e.to_string()));
+                    let _ = self.save_and_emit(&job).await;
+                    return;
+                }
+            };
+
+            match self
+                .pipeline_service
+                .fetch_run_history(&pipeline_id, job.depth)
+                .await
+            {
+                Ok(runs) => {
+                    if let Some(metrics_service) = &self.metrics_service {
+                        match metrics_service
+                            .backfill_metrics(&pipeline_id, &runs, Some(provider.as_ref()))
+                            .await
+                        {
+                            Ok(count) if count > 0 => {
+                                self.event_bus
+                                    .emit(CoreEvent::MetricsGenerated {
+                                        pipeline_id: pipeline_id.clone(),
+                                    })
+                                    .await;
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                tracing::warn!(
+                                    pipeline_id = %pipeline_id,
+                                    error = %e,
+                                    "Failed to backfill metrics"
+                                );
+                            }
+                        }
+                    }
+                    job.record_pipeline(&pipeline_id, runs.len());
+                }
+                // Gone from the provider since the job started.
+                Err(DomainError::PipelineNotFound(_)) => job.record_pipeline(&pipeline_id, 0),
+                Err(e) => match retry_wait(&e) {
+                    Some(wait) => {
+                        tracing::info!(
+                            provider_id = job.provider_id,
+                            wait_secs = wait.as_secs(),
+                            error = %e,
+                            "Run backfill waiting for provider"
+                        );
+                        tokio::time::sleep(wait).await;
+                        continue;
+                    }
+                    None => job.finish(BackfillStatus::Failed, Some(e.to_string())),
+                },
+            }
+
+            if let Err(e) = self.save_and_emit(&job).await {
+                tracing::warn!(provider_id = job.provider_id, error = %e, "Failed to save run backfill");
+            }
+            if !job.is_running() {
+                break;
+            }
+            tokio::time::sleep(PIPELINE_DELAY).await;
+        }
+
+        tracing::info!(
+            provider_id = job.provider_id,
+            status = job.status.as_str(),
+            runs = job.runs_fetched,
+            "Run history backfill finished"
+        );
+    }
+
+    async fn save_and_emit(&self, job: &RunBackfillJob) -> DomainResult<()> {
+        self.repository.save_run_backfill_job(job).await?;
+        self.event_bus
+            .emit(CoreEvent::RunBackfillProgress { job: job.clone() })
+            .await;
+        Ok(())
+    }
+}
+
+// How long to hold off before retrying a fetch that failed because of the
+// provider's rate limit, an open circuit or another transient error.
+fn retry_wait(error: &DomainError) -> Option<Duration> {
+    match error {
+        DomainError::CircuitOpen { retry_at, .. } => Some(
+            (*retry_at - Utc::now())
+                .to_std()
+                .unwrap_or_default()
+                .max(Duration::from_secs(1)),
+        ),
+        e if e.is_transient() => Some(e.retry_after().unwrap_or(DEFAULT_RETRY_WAIT)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_wait() {
+        let rate_limited = DomainError::RateLimited {
+            message: "slow down".to_string(),
+            retry_after: Some(Duration::from_secs(30)),
+        };
+        assert_eq!(retry_wait(&rate_limited), Some(Duration::from_secs(30)));
+
+        let network = DomainError::NetworkError("reset".to_string());
+        assert_eq!(retry_wait(&network), Some(DEFAULT_RETRY_WAIT));
+
+        let open = DomainError::CircuitOpen {
+            provider_id: 1,
+            retry_at: Utc::now() - chrono::Duration::seconds(5),
+        };
+        assert_eq!(retry_wait(&open), Some(Duration::from_secs(1)));
+
+        assert_eq!(
+            retry_wait(&DomainError::AuthenticationFailed("bad token".to_string())),
+            None
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use sha2::{
+    Digest,
+    Sha256,
+};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::provider_service::ProviderService;
+use crate::application::AccessControl;
+use crate::domain::{
+    validate_backup_id,
+    Action,
+    BackupInfo,
+    BackupVerification,
+    DomainError,
+    DomainResult,
+};
+use crate::event::{
+    CacheInvalidationReason,
+    CoreEvent,
+    EventBus,
+};
+use crate::infrastructure::database::{
+    export_snapshot,
+    import_snapshot,
+    snapshot_format,
+    verify_snapshot,
+    Repository,
+};
+use crate::infrastructure::StorageBackend;
+
+// How often the schedule checks whether the newest backup has gone stale.
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+pub struct BackupService {
+    repository: Arc<Repository>,
+    provider_service: Arc<ProviderService>,
+    storage: Arc<dyn StorageBackend>,
+    access_control: Arc<AccessControl>,
+    event_bus: Arc<dyn EventBus>,
+    retention: usize,
+    schedule: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl BackupService {
+    pub fn new(
+        repository: Arc<Repository>, provider_service: Arc<ProviderService>,
+        storage: Arc<dyn StorageBackend>, access_control: Arc<AccessControl>,
+        event_bus: Arc<dyn EventBus>, retention: usize,
+    ) -> Self {
+        Self {
+            repository,
+            provider_service,
+            storage,
+            access_control,
+            event_bus,
+            retention: retention.max(1),
+            schedule: Mutex::new(None),
+        }
+    }
+
+    pub async fn create_backup(&self) -> DomainResult<BackupInfo> {
+        self.access_control.ensure(Action::ManageProviders)?;
+        self.create_backup_unchecked().await
+    }
+
+    async fn create_backup_unchecked(&self) -> DomainResult<BackupInfo> {
+        let pool = self.repository.cache_pool();
+        let format = snapshot_format(pool);
+        let data = export_snapshot(pool).await?;
+
+        // A backup that cannot be restored is worse than none, since it would
+        // push a good one out of retention.
+        verify_snapshot(format, &data).await?;
+
+        let created_at = Utc::now();
+        let info = BackupInfo {
+            id: format!("pipedash-{}", created_at.format("%Y%m%d-%H%M%S")),
+            format,
+            created_at,
+            size_bytes: data.len() as u64,
+            sha256: sha256_hex(&data),
+        };
+        let manifest = serde_json::to_vec_pretty(&info)
+            .map_err(|e| DomainError::InternalError(e.to_string()))?;
+
+        self.storage
+            .put(&info.data_key(), &data, Some("application/octet-stream"))
+            .await?;
+        self.storage
+            .put(
+                &BackupInfo::manifest_key(&info.id),
+                &manifest,
+                Some("application/json"),
+            )
+            .await?;
+
+        tracing::info!(
+            id = %info.id,
+            size_bytes = info.size_bytes,
+            backend = self.storage.backend_type(),
+            "Database backup created"
+        );
+
+        if let Err(e) = self.prune().await {
+            tracing::warn!(error = %e, "Failed to prune old backups");
+        }
+
+        Ok(info)
+    }
+
+    // Newest first.
+    pub async fn list_backups(&self) -> DomainResult<Vec<BackupInfo>> {
+        let mut backups = Vec::new();
+
+        for object in self.storage.list(None).await? {
+            if !object.key.ends_with(".manifest.json") {
+                continue;
+            }
+
+            let data = self.storage.get(&object.key).await?;
+            match serde_json::from_slice::<BackupInfo>(&data) {
+                Ok(info) => backups.push(info),
+                Err(e) => {
+                    tracing::warn!(key = %object.key, error = %e, "Skipping unreadable backup manifest");
+                }
+            }
+        }
+
+        backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+        Ok(backups)
+    }
+
+    pub async fn verify_backup(&self, id: &str) -> DomainResult<BackupVerification> {
+        let info = self.get_backup(id).await?;
+
+        let error = match self.load_verified(&info).await {
+            Ok(_) => None,
+            Err(e) => Some(e.to_string()),
+        };
+
+        Ok(BackupVerification {
+            id: info.id,
+            valid: error.is_none(),
+            error,
+        })
+    }
+
+    // Replaces the contents of the database with the backup. Providers are
+    // reloaded and every cache listener is told to start over.
+    pub async fn restore_backup(&self, id: &str) -> DomainResult<BackupInfo> {
+        self.access_control.ensure(Action::ManageProviders)?;
+
+        let info = self.get_backup(id).await?;
+        let pool = self.repository.cache_pool();
+        if info.format != snapshot_format(pool) {
+            return Err(DomainError::InvalidConfig(format!(
+                "Backup {} was taken from a {:?} database and cannot be restored into this one",
+                info.id, info.format
+            )));
+        }
+
+        let data = self.load_verified(&info).await?;
+        import_snapshot(pool, &data).await?;
+
+        tracing::info!(id = %info.id, "Database restored from backup");
+
+        if let Err(e) = self.provider_service.load_all_providers().await {
+            tracing::warn!(error = %e, "Failed to reload providers after restore");
+        }
+        self.event_bus.emit(CoreEvent::ProvidersChanged).await;
+        self.event_bus
+            .emit(CoreEvent::PipelineCacheInvalidated {
+                provider_id: None,
+                reason: CacheInvalidationReason::ManualRefresh,
+            })
+            .await;
+
+        Ok(info)
+    }
+
+    async fn get_backup(&self, id: &str) -> DomainResult<BackupInfo> {
+        validate_backup_id(id)?;
+
+        let key = BackupInfo::manifest_key(id);
+        if !self.storage.exists(&key).await? {
+            return Err(DomainError::NotFound(format!("Backup not found: {}", id)));
+        }
+
+        let data = self.storage.get(&key).await?;
+        serde_json::from_slice(&data)
+            .map_err(|e| DomainError::InternalError(format!("Invalid backup manifest: {}", e)))
+    }
+
+    async fn load_verified(&self, info: &BackupInfo) -> DomainResult<Vec<u8>> {
+        let data = self.storage.get(&info.data_key()).await?;
+
+        let checksum = sha256_hex(&data);
+        if checksum != info.sha256 {
+            return Err(DomainError::DatabaseError(format!(
+                "Checksum mismatch for backup {}: expected {}, got {}",
+                info.id, info.sha256, checksum
+            )));
+        }
+
+        verify_snapshot(info.format, &data).await?;
+        Ok(data)
+    }
+
+    async fn prune(&self) -> DomainResult<usize> {
+        let backups = self.list_backups().await?;
+        let mut removed = 0;
+
+        for info in backups.iter().skip(self.retention) {
+            self.storage.delete(&info.data_key()).await?;
+            self.storage
+                .delete(&BackupInfo::manifest_key(&info.id))
+                .await?;
+            removed += 1;
+        }
+
+        if removed > 0 {
+            tracing::info!(removed, retention = self.retention, "Pruned old backups");
+        }
+        Ok(removed)
+    }
+
+    // Takes a backup whenever the newest one is older than `interval`, so a
+    // restart does not reset the schedule.
+    pub async fn start_schedule(self: &Arc<Self>, interval: Duration) {
+        let mut schedule = self.schedule.lock().await;
+        if schedule.is_some() {
+            return;
+        }
+
+        let service = Arc::clone(self);
+        *schedule = Some(tokio::spawn(async move {
+            loop {
+                if let Err(e) = service.run_scheduled(interval).await {
+                    tracing::error!(error = %e, "Scheduled backup failed");
+                }
+                tokio::time::sleep(SCHEDULE_CHECK_INTERVAL.min(interval)).await;
+            }
+        }));
+    }
+
+    pub async fn stop_schedule(&self) {
+        if let Some(handle) = self.schedule.lock().await.take() {
+            handle.abort();
+        }
+    }
+
+    async fn run_scheduled(&self, interval: Duration) -> DomainResult<()> {
+        let newest = self.list_backups().await?.into_iter().next();
+        let due = match newest {
+            Some(info) => (Utc::now() - info.created_at)
+                .to_std()
+                .map(|age| age >= interval)
+                .unwrap_or(false),
+            None => true,
+        };
+
+        if due {
+            self.create_backup_unchecked().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::test_services;
+    use crate::infrastructure::LocalStorage;
+
+    async fn service(dir: &std::path::Path, retention: usize) -> BackupService {
+        let services = test_services(dir).await;
+
+        BackupService::new(
+            services.repository,
+            services.provider_service,
+            Arc::new(LocalStorage::new(dir.join("backups"))),
+            services.access_control,
+            services.event_bus,
+            retention,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_verify_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path(), 3).await;
+
+        let info = service.create_backup().await.unwrap();
+        let verification = service.verify_backup(&info.id).await.unwrap();
+        assert!(verification.valid, "{:?}", verification.error);
+
+        service.restore_backup(&info.id).await.unwrap();
+        assert!(service.verify_backup("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_backup_fails_verification() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path(), 3).await;
+
+        let info = service.create_backup().await.unwrap();
+        service
+            .storage
+            .put(&info.data_key(), b"not a database", None)
+            .await
+            .unwrap();
+
+        let verification = service.verify_backup(&info.id).await.unwrap();
+        assert!(!verification.valid);
+        assert!(service.restore_backup(&info.id).await.is_err());
+    }
+}
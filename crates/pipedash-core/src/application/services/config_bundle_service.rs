@@ -0,0 +1,374 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use super::metrics_service::MetricsService;
+use super::provider_service::ProviderService;
+use crate::domain::{
+    DomainError,
+    DomainResult,
+    MetricsConfigExport,
+    PinnedPipeline,
+    ProviderConfig,
+};
+use crate::infrastructure::config::bundle::{
+    portable_token_reference,
+    remap_pipeline_id,
+    BundledProvider,
+    BundledTablePreference,
+    ConfigBundle,
+    ImportOptions,
+    ImportReport,
+    ProviderImportAction,
+    ProviderImportResult,
+    CONFIG_BUNDLE_VERSION,
+};
+use crate::infrastructure::config::TokenReference;
+use crate::infrastructure::secrets::redact_config_secrets;
+
+pub struct ConfigBundleService {
+    provider_service: Arc<ProviderService>,
+    metrics_service: Option<Arc<MetricsService>>,
+}
+
+impl ConfigBundleService {
+    pub fn new(
+        provider_service: Arc<ProviderService>, metrics_service: Option<Arc<MetricsService>>,
+    ) -> Self {
+        Self {
+            provider_service,
+            metrics_service,
+        }
+    }
+
+    pub async fn export_bundle(&self) -> DomainResult<ConfigBundle> {
+        // Read straight from the config backend so the token field only ever
+        // holds the stored reference, never a token resolved from the token
+        // store.
+        let export = self
+            .provider_service
+            .repository()
+            .config_backend()
+            .export_all()
+            .await?;
+
+        let providers = export
+            .providers
+            .iter()
+            .filter_map(|config| {
+                let source_id = config.id?;
+                // Providers saved before secrets moved to the token store can
+                // still have them inline.
+                let mut provider_config = config.config.clone();
+                redact_config_secrets(&mut provider_config);
+                Some(BundledProvider {
+                    source_id,
+                    name: config.name.clone(),
+                    provider_type: config.provider_type.clone(),
+                    token: portable_token_reference(&config.token, &config.name),
+                    config: provider_config,
+                    refresh_interval: config.refresh_interval,
+                })
+            })
+            .collect();
+
+        let mut table_preferences: Vec<BundledTablePreference> = export
+            .table_preferences
+            .into_iter()
+            .filter_map(|(key, preferences_json)| {
+                let (provider_id, table_id) = key.split_once(':')?;
+                Some(BundledTablePreference {
+                    source_provider_id: provider_id.parse().ok()?,
+                    table_id: table_id.to_string(),
+                    preferences_json,
+                })
+            })
+            .collect();
+        table_preferences.sort_by(|a, b| {
+            (a.source_provider_id, &a.table_id).cmp(&(b.source_provider_id, &b.table_id))
+        });
+
+        let pinned_pipelines = export
+            .pinned_pipelines
+            .into_iter()
+            .filter(|pin| !pin.unpinned)
+            .map(|pin| pin.pipeline_id)
+            .collect();
+
+        let metrics = match &self.metrics_service {
+            Some(service) => Some(service.repository().export_config().await?),
+            None => None,
+        };
+
+        Ok(ConfigBundle {
+            version: CONFIG_BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            providers,
+            table_preferences,
+            metrics,
+            pinned_pipelines,
+        })
+    }
+
+    pub async fn import_bundle(
+        &self, bundle: ConfigBundle, options: ImportOptions,
+    ) -> DomainResult<ImportReport> {
+        if bundle.version > CONFIG_BUNDLE_VERSION {
+            return Err(DomainError::InvalidConfig(format!(
+                "Unsupported bundle version {} (max supported: {})",
+                bundle.version, CONFIG_BUNDLE_VERSION
+            )));
+        }
+
+        let known_types: HashSet<String> = self
+            .provider_service
+            .list_available_plugins()
+            .into_iter()
+            .map(|metadata| metadata.provider_type)
+            .collect();
+
+        let existing_names: HashSet<String> = self
+            .provider_service
+            .repository()
+            .config_backend()
+            .list_providers()
+            .await?
+            .into_iter()
+            .map(|config| config.name)
+            .collect();
+
+        let mut report = ImportReport {
+            dry_run: options.dry_run,
+            ..Default::default()
+        };
+        let mut id_mapping: HashMap<i64, i64> = HashMap::new();
+
+        for provider in &bundle.providers {
+            let (action, message, token) =
+                Self::plan_provider(provider, &known_types, &existing_names, &options);
+
+            let mut result = ProviderImportResult {
+                name: provider.name.clone(),
+                provider_type: provider.provider_type.clone(),
+                action,
+                message,
+            };
+
+            if let (ProviderImportAction::Create, Some(token), false) =
+                (action, token, options.dry_run)
+            {
+                let config = ProviderConfig {
+                    id: None,
+                    name: provider.name.clone(),
+                    provider_type: provider.provider_type.clone(),
+                    token,
+                    config: provider.config.clone(),
+                    refresh_interval: provider.refresh_interval,
+                    version: None,
+                    enabled: true,
+                };
+
+                match self.provider_service.add_provider(config).await {
+                    Ok(new_id) => {
+                        id_mapping.insert(provider.source_id, new_id);
+                    }
+                    Err(e) => {
+                        result.action = ProviderImportAction::Invalid;
+                        result.message = Some(e.to_string());
+                        report
+                            .errors
+                            .push(format!("Failed to import '{}': {}", provider.name, e));
+                    }
+                }
+            }
+
+            report.providers.push(result);
+        }
+
+        // In a dry run nothing was created, so count what would be carried over
+        // for the providers that passed validation.
+        let importable: HashSet<i64> = if options.dry_run {
+            bundle
+                .providers
+                .iter()
+                .zip(&report.providers)
+                .filter(|(_, result)| result.action == ProviderImportAction::Create)
+                .map(|(provider, _)| provider.source_id)
+                .collect()
+        } else {
+            id_mapping.keys().copied().collect()
+        };
+
+        for preference in &bundle.table_preferences {
+            if !importable.contains(&preference.source_provider_id) {
+                continue;
+            }
+            if options.dry_run {
+                report.table_preferences += 1;
+                continue;
+            }
+
+            let new_id = id_mapping[&preference.source_provider_id];
+            match self
+                .provider_service
+                .repository()
+                .upsert_table_preferences(
+                    new_id,
+                    &preference.table_id,
+                    &preference.preferences_json,
+                )
+                .await
+            {
+                Ok(()) => report.table_preferences += 1,
+                Err(e) => report.errors.push(format!(
+                    "Failed to import table preferences '{}': {}",
+                    preference.table_id, e
+                )),
+            }
+        }
+
+        for pipeline_id in &bundle.pinned_pipelines {
+            let Some(source_id) = pipeline_id
+                .split("__")
+                .nth(1)
+                .and_then(|id| id.parse::<i64>().ok())
+            else {
+                continue;
+            };
+            if !importable.contains(&source_id) {
+                continue;
+            }
+            if options.dry_run {
+                report.pinned_pipelines += 1;
+                continue;
+            }
+
+            let pin = PinnedPipeline {
+                pipeline_id: remap_pipeline_id(pipeline_id, &id_mapping)
+                    .unwrap_or_else(|| pipeline_id.clone()),
+                position: report.pinned_pipelines as i64,
+                unpinned: false,
+                updated_at: Utc::now(),
+            };
+            match self
+                .provider_service
+                .repository()
+                .save_pinned_pipeline(&pin)
+                .await
+            {
+                Ok(()) => report.pinned_pipelines += 1,
+                Err(e) => report.errors.push(format!(
+                    "Failed to import pinned pipeline '{}': {}",
+                    pin.pipeline_id, e
+                )),
+            }
+        }
+
+        if let Some(metrics) = &bundle.metrics {
+            self.import_metrics(metrics, &importable, &id_mapping, &mut report)
+                .await;
+        }
+
+        Ok(report)
+    }
+
+    fn plan_provider(
+        provider: &BundledProvider, known_types: &HashSet<String>,
+        existing_names: &HashSet<String>, options: &ImportOptions,
+    ) -> (ProviderImportAction, Option<String>, Option<String>) {
+        if !known_types.contains(&provider.provider_type) {
+            return (
+                ProviderImportAction::Invalid,
+                Some(format!("Unknown provider type: {}", provider.provider_type)),
+                None,
+            );
+        }
+
+        if existing_names.contains(&provider.name) {
+            return (
+                ProviderImportAction::Existing,
+                Some("A provider with this name already exists".to_string()),
+                None,
+            );
+        }
+
+        if let Some(token) = options
+            .tokens
+            .get(&provider.name)
+            .filter(|t| !t.trim().is_empty())
+        {
+            return (ProviderImportAction::Create, None, Some(token.clone()));
+        }
+
+        match &provider.token {
+            TokenReference::EnvVar(name) => match std::env::var(name) {
+                Ok(token) if !token.trim().is_empty() => {
+                    (ProviderImportAction::Create, None, Some(token))
+                }
+                _ => (
+                    ProviderImportAction::MissingToken,
+                    Some(format!("Set {} or provide a token for this provider", name)),
+                    None,
+                ),
+            },
+            reference if reference.is_external() => (
+                ProviderImportAction::Create,
+                None,
+                Some(reference.to_toml_string()),
+            ),
+            _ => (
+                ProviderImportAction::MissingToken,
+                Some("Provide a token for this provider".to_string()),
+                None,
+            ),
+        }
+    }
+
+    async fn import_metrics(
+        &self, metrics: &MetricsConfigExport, importable: &HashSet<i64>,
+        id_mapping: &HashMap<i64, i64>, report: &mut ImportReport,
+    ) {
+        let Some(service) = &self.metrics_service else {
+            report
+                .errors
+                .push("Metrics service not available, skipped metrics configuration".to_string());
+            return;
+        };
+
+        let pipeline_configs: Vec<_> = metrics
+            .pipeline_configs
+            .iter()
+            .filter_map(|config| {
+                let source_id: i64 = config.pipeline_id.split("__").nth(1)?.parse().ok()?;
+                if !importable.contains(&source_id) {
+                    return None;
+                }
+                let mut config = config.clone();
+                if let Some(pipeline_id) = remap_pipeline_id(&config.pipeline_id, id_mapping) {
+                    config.pipeline_id = pipeline_id;
+                }
+                Some(config)
+            })
+            .collect();
+
+        if report.dry_run {
+            report.metrics_configs = pipeline_configs.len();
+            return;
+        }
+
+        let export = MetricsConfigExport {
+            global_config: metrics.global_config.clone(),
+            pipeline_configs,
+        };
+        match service.repository().import_config(&export).await {
+            Ok(()) => report.metrics_configs = export.pipeline_configs.len(),
+            Err(e) => report
+                .errors
+                .push(format!("Failed to import metrics configuration: {}", e)),
+        }
+    }
+}
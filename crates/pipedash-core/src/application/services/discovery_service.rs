@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use pipedash_plugin_api::{
+    Capability,
+    PaginationParams,
+};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::provider_service::ProviderService;
+use crate::application::RefreshManager;
+use crate::domain::{
+    Action,
+    DiscoverySettings,
+    DomainError,
+    DomainResult,
+};
+use crate::event::{
+    CoreEvent,
+    EventBus,
+};
+
+const DISCOVERY_INTERVAL: Duration = Duration::from_secs(60 * 60);
+// Providers are checked this often, so newly enabled discovery or changed
+// organizations are picked up without waiting for the interval.
+const DISCOVERY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const DISCOVERY_PAGE_SIZE: usize = 100;
+const MAX_DISCOVERY_PAGES: usize = 50;
+
+// Keeps providers with auto-discovery on in step with their organizations:
+// repositories created since the last run are added to the provider's
+// selection and fetched right away.
+pub struct DiscoveryService {
+    provider_service: Arc<ProviderService>,
+    refresh_manager: Arc<RefreshManager>,
+    event_bus: Arc<dyn EventBus>,
+    // When each provider was last discovered, and with which organizations.
+    last_runs: Mutex<HashMap<i64, (Vec<String>, Instant)>>,
+    schedule: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl DiscoveryService {
+    pub fn new(
+        provider_service: Arc<ProviderService>, refresh_manager: Arc<RefreshManager>,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Self {
+        Self {
+            provider_service,
+            refresh_manager,
+            event_bus,
+            last_runs: Mutex::new(HashMap::new()),
+            schedule: Mutex::new(None),
+        }
+    }
+
+    pub async fn start(self: &Arc<Self>) {
+        let mut schedule = self.schedule.lock().await;
+        if schedule.is_some() {
+            return;
+        }
+
+        let service = Arc::clone(self);
+        *schedule = Some(tokio::spawn(async move {
+            loop {
+                if let Err(e) = service.run_due().await {
+                    tracing::error!(error = %e, "Repository discovery failed");
+                }
+                tokio::time::sleep(DISCOVERY_CHECK_INTERVAL).await;
+            }
+        }));
+    }
+
+    pub async fn stop(&self) {
+        if let Some(handle) = self.schedule.lock().await.take() {
+            handle.abort();
+        }
+    }
+
+    // Runs discovery for one provider now. Returns the repositories added.
+    pub async fn discover(&self, provider_id: i64) -> DomainResult<Vec<String>> {
+        self.provider_service
+            .access_control()
+            .ensure(Action::ManageProviders)?;
+
+        let config = self
+            .provider_service
+            .get_provider_config(provider_id)
+            .await?;
+        self.provider_service
+            .ensure_capability(&config.provider_type, Capability::RepositoryDiscovery)?;
+        let settings = DiscoverySettings::from_config(&config.config).ok_or_else(|| {
+            DomainError::InvalidConfig(format!(
+                "Auto-discovery is not enabled for provider {}",
+                config.display_name()
+            ))
+        })?;
+
+        self.run_provider(provider_id, settings).await
+    }
+
+    async fn run_due(&self) -> DomainResult<()> {
+        let provider_ids = self
+            .provider_service
+            .provider_ids_with_capability(Capability::RepositoryDiscovery)
+            .await?;
+
+        for provider_id in provider_ids {
+            let config = self
+                .provider_service
+                .get_provider_config(provider_id)
+                .await?;
+            let Some(settings) = DiscoverySettings::from_config(&config.config) else {
+                continue;
+            };
+
+            let due = self
+                .last_runs
+                .lock()
+                .await
+                .get(&provider_id)
+                .is_none_or(|(orgs, at)| {
+                    *orgs != settings.orgs || at.elapsed() >= DISCOVERY_INTERVAL
+                });
+            if !due {
+                continue;
+            }
+
+            if let Err(e) = self.run_provider(provider_id, settings).await {
+                tracing::warn!(provider_id, error = %e, "Repository discovery failed");
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_provider(
+        &self, provider_id: i64, settings: DiscoverySettings,
+    ) -> DomainResult<Vec<String>> {
+        // Failed runs wait for the next interval too, so a broken token
+        // doesn't hit the provider every minute.
+        self.last_runs
+            .lock()
+            .await
+            .insert(provider_id, (settings.orgs.clone(), Instant::now()));
+
+        let filter = self.provider_service.pipeline_filter(provider_id).await?;
+        let mut found = Vec::new();
+        for org in &settings.orgs {
+            let mut params = PaginationParams {
+                page: 1,
+                page_size: DISCOVERY_PAGE_SIZE,
+            };
+            loop {
+                let page = self
+                    .provider_service
+                    .list_available_pipelines(provider_id, Some(org.clone()), params.clone())
+                    .await?;
+                found.extend(
+                    page.items
+                        .into_iter()
+                        .filter(|item| !filter.excludes_available(item))
+                        .map(|item| item.id),
+                );
+                if !page.has_more || params.page >= MAX_DISCOVERY_PAGES {
+                    break;
+                }
+                params.page += 1;
+            }
+        }
+
+        let added = self
+            .provider_service
+            .add_selected_items(provider_id, found)
+            .await?;
+        if added.is_empty() {
+            return Ok(added);
+        }
+
+        tracing::info!(
+            provider_id,
+            count = added.len(),
+            "Discovered new repositories"
+        );
+        self.refresh_manager.prioritize_provider(provider_id).await;
+        self.event_bus
+            .emit(CoreEvent::PipelinesDiscovered {
+                provider_id,
+                repositories: added.clone(),
+            })
+            .await;
+        Ok(added)
+    }
+}
@@ -1,12 +1,33 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
-use chrono::Utc;
+use chrono::{
+    DateTime,
+    Utc,
+};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 
 use crate::domain::{
+    estimate_run_cost,
+    export_header,
     AggregatedMetrics,
+    AnomalyFinding,
+    CostEntry,
+    CostGroupBy,
+    CostReport,
     DomainError,
     DomainResult,
+    ExportFilter,
+    ExportFormat,
+    ExportRecord,
+    FlakinessStats,
+    FlakyPipeline,
     GlobalMetricsConfig,
+    MetricAnomaly,
     MetricEntry,
     MetricMetadata,
     MetricType,
@@ -15,17 +36,44 @@ use crate::domain::{
     MetricsStats,
     PipelineRun,
     PipelineStatus,
+    Provider,
+    ProviderQueue,
+    RollupGranularity,
+    ANOMALY_BASELINE_RUNS,
+    ANOMALY_RECENT_RUNS,
+    EXPORT_PAGE_SIZE,
+};
+use crate::infrastructure::config::CostConfig;
+use crate::infrastructure::database::{
+    MetricsRepository,
+    Repository,
 };
-use crate::infrastructure::database::MetricsRepository;
 use crate::infrastructure::deduplication::hash_pipeline_run;
 
+const FLAKINESS_RUN_WINDOW: usize = 100;
+const MIN_FLAKINESS_TRANSITIONS: usize = 4;
+const ROLLUP_COMPACTION_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const ANOMALY_RETENTION_DAYS: i64 = 90;
+const MAX_ANOMALY_RESULTS: usize = 500;
+const MAX_BATCH_SIZE: usize = 200;
+
 pub struct MetricsService {
     repository: Arc<MetricsRepository>,
+    cache_repository: Arc<Repository>,
+    costs: CostConfig,
+    compaction: Mutex<Option<JoinHandle<()>>>,
 }
 
 impl MetricsService {
-    pub fn new(repository: Arc<MetricsRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<MetricsRepository>, cache_repository: Arc<Repository>, costs: CostConfig,
+    ) -> Self {
+        Self {
+            repository,
+            cache_repository,
+            costs,
+            compaction: Mutex::new(None),
+        }
     }
 
     pub fn repository(&self) -> Arc<MetricsRepository> {
@@ -108,8 +156,9 @@ impl MetricsService {
         Ok(())
     }
 
+    // `provider` contributes its own metrics for each run when given.
     pub async fn extract_and_store_metrics(
-        &self, pipeline_id: &str, runs: &[PipelineRun],
+        &self, pipeline_id: &str, runs: &[PipelineRun], provider: Option<&dyn Provider>,
     ) -> DomainResult<usize> {
         if runs.is_empty() {
             return Ok(0);
@@ -135,16 +184,7 @@ impl MetricsService {
 
         let new_runs: Vec<_> = runs
             .iter()
-            .filter(|run| {
-                run.run_number > last_processed
-                    && matches!(
-                        run.status,
-                        PipelineStatus::Success
-                            | PipelineStatus::Failed
-                            | PipelineStatus::Cancelled
-                            | PipelineStatus::Skipped
-                    )
-            })
+            .filter(|run| run.run_number > last_processed && run.status.is_terminal())
             .collect();
 
         tracing::debug!(
@@ -167,7 +207,6 @@ impl MetricsService {
             return Ok(0);
         }
 
-        const MAX_BATCH_SIZE: usize = 200;
         let runs_to_process = if new_runs.len() > MAX_BATCH_SIZE {
             tracing::debug!(
                 available = new_runs.len(),
@@ -186,12 +225,89 @@ impl MetricsService {
             &new_runs[..]
         };
 
-        let mut metrics = Vec::new();
-        let mut max_run_number = last_processed;
+        let max_run_number = runs_to_process
+            .iter()
+            .map(|run| run.run_number)
+            .fold(last_processed, i64::max);
+        let metrics = self
+            .build_run_metrics(pipeline_id, runs_to_process, provider)
+            .await;
+
+        if metrics.is_empty() {
+            return Ok(0);
+        }
+
+        let start = std::time::Instant::now();
+        let inserted = self.repository.insert_metrics_batch(metrics).await?;
+        let insert_duration = start.elapsed();
 
-        for run in runs_to_process {
-            max_run_number = max_run_number.max(run.run_number);
+        if insert_duration.as_secs() > 2 {
+            tracing::warn!(
+                duration_ms = insert_duration.as_millis(),
+                count = inserted,
+                "Slow metrics insert"
+            );
+        }
 
+        self.repository
+            .update_last_processed_run(pipeline_id, max_run_number)
+            .await?;
+
+        tracing::debug!(
+            inserted = inserted,
+            pipeline_id = %pipeline_id,
+            duration_ms = insert_duration.as_millis(),
+            last_run = max_run_number,
+            "Stored metrics"
+        );
+
+        Ok(inserted)
+    }
+
+    // Imports metrics for historical runs. Unlike `extract_and_store_metrics`
+    // this ignores the processed-run watermark, since backfilled runs are
+    // usually older than anything seen so far; runs already stored are
+    // skipped by the metrics table's unique key.
+    pub async fn backfill_metrics(
+        &self, pipeline_id: &str, runs: &[PipelineRun], provider: Option<&dyn Provider>,
+    ) -> DomainResult<usize> {
+        let config = self.get_effective_pipeline_config(pipeline_id).await?;
+        if !config.enabled {
+            return Ok(0);
+        }
+
+        let terminal: Vec<_> = runs.iter().filter(|run| run.status.is_terminal()).collect();
+        let mut inserted = 0;
+        for chunk in terminal.chunks(MAX_BATCH_SIZE) {
+            let metrics = self.build_run_metrics(pipeline_id, chunk, provider).await;
+            inserted += self.repository.insert_metrics_batch(metrics).await?;
+        }
+
+        let last_processed = self
+            .repository
+            .get_last_processed_run(pipeline_id)
+            .await?
+            .unwrap_or(0);
+        let max_run_number = terminal
+            .iter()
+            .map(|run| run.run_number)
+            .fold(last_processed, i64::max);
+        if max_run_number > last_processed {
+            self.repository
+                .update_last_processed_run(pipeline_id, max_run_number)
+                .await?;
+        }
+
+        Ok(inserted)
+    }
+
+    async fn build_run_metrics(
+        &self, pipeline_id: &str, runs: &[&PipelineRun], provider: Option<&dyn Provider>,
+    ) -> Vec<MetricEntry> {
+        let cost_rate = self.cost_rate(pipeline_id).await;
+        let mut metrics = Vec::new();
+
+        for run in runs {
             let status_str = run.status.as_str();
 
             let run_hash = hash_pipeline_run(
@@ -222,11 +338,53 @@ impl MetricsService {
                     created_at: Utc::now(),
                     run_hash: Some(run_hash.clone()),
                 });
+
+                if let Some(rate) = cost_rate {
+                    metrics.push(MetricEntry {
+                        id: 0,
+                        pipeline_id: pipeline_id.to_string(),
+                        run_number: run.run_number,
+                        timestamp: run.started_at,
+                        metric_type: MetricType::EstimatedCost,
+                        value: estimate_run_cost(duration_seconds, rate),
+                        metadata: Some(metadata.to_json()),
+                        created_at: Utc::now(),
+                        run_hash: Some(run_hash.clone()),
+                    });
+                }
+            }
+
+            if let Some(provider) = provider {
+                match provider.extract_metrics(run).await {
+                    Ok(extracted) => {
+                        metrics.extend(extracted.into_iter().map(|metric| MetricEntry {
+                            id: 0,
+                            pipeline_id: pipeline_id.to_string(),
+                            run_number: run.run_number,
+                            timestamp: run.started_at,
+                            metric_type: metric.metric_type.into(),
+                            value: metric.value,
+                            metadata: Some(metadata.to_json()),
+                            created_at: Utc::now(),
+                            run_hash: Some(run_hash.clone()),
+                        }));
+                    }
+                    Err(e) => {
+                        tracing::debug!(
+                            error = %e,
+                            pipeline_id = %pipeline_id,
+                            run_number = run.run_number,
+                            "Provider metrics extraction failed"
+                        );
+                    }
+                }
             }
 
             let success_value = match run.status {
                 PipelineStatus::Success => 100.0,
                 PipelineStatus::Failed | PipelineStatus::Cancelled => 0.0,
+                // Skipped and neutral runs neither pass nor fail, so they stay
+                // out of the success rate.
                 _ => continue,
             };
 
@@ -255,44 +413,172 @@ impl MetricsService {
             });
         }
 
-        if metrics.is_empty() {
-            return Ok(0);
+        metrics
+    }
+
+    // Pipeline ids start with `{provider_type}__{provider_id}__`, which is
+    // enough to find the rate without loading the pipeline.
+    async fn cost_rate(&self, pipeline_id: &str) -> Option<f64> {
+        if self.costs.rates.is_empty() {
+            return None;
         }
 
-        let start = std::time::Instant::now();
-        let inserted = self.repository.insert_metrics_batch(metrics).await?;
-        let insert_duration = start.elapsed();
+        let mut parts = pipeline_id.split("__");
+        let provider_type = parts.next()?;
+        let provider_name = match parts.next().and_then(|id| id.parse::<i64>().ok()) {
+            Some(provider_id) => self
+                .cache_repository
+                .config_backend()
+                .get_provider(provider_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|provider| provider.name),
+            None => None,
+        };
 
-        if insert_duration.as_secs() > 2 {
-            tracing::warn!(
-                duration_ms = insert_duration.as_millis(),
-                count = inserted,
-                "Slow metrics insert"
-            );
-        }
+        self.costs.rate_for(provider_name.as_deref(), provider_type)
+    }
 
-        self.repository
-            .update_last_processed_run(pipeline_id, max_run_number)
+    pub async fn estimate_costs(
+        &self, group_by: CostGroupBy, start_date: Option<DateTime<Utc>>,
+        end_date: Option<DateTime<Utc>>,
+    ) -> DomainResult<CostReport> {
+        let totals = self
+            .repository
+            .sum_metric_by_pipeline(MetricType::EstimatedCost, start_date, end_date)
             .await?;
+        let pipelines = self.cache_repository.get_cached_pipelines(None).await?;
+        let pipeline_name = |id: &str| {
+            pipelines
+                .iter()
+                .find(|p| p.id == id)
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| id.to_string())
+        };
 
-        tracing::debug!(
-            inserted = inserted,
-            pipeline_id = %pipeline_id,
-            duration_ms = insert_duration.as_millis(),
-            last_run = max_run_number,
-            "Stored metrics"
-        );
+        let mut entries: HashMap<String, CostEntry> = HashMap::new();
+        let mut add = |key: String, name: String, runs: i64, cost: f64| {
+            let entry = entries.entry(key.clone()).or_insert(CostEntry {
+                key,
+                name,
+                runs: 0,
+                cost: 0.0,
+            });
+            entry.runs += runs;
+            entry.cost += cost;
+        };
 
-        Ok(inserted)
+        match group_by {
+            CostGroupBy::Pipeline => {
+                for (pipeline_id, runs, cost) in &totals {
+                    add(
+                        pipeline_id.clone(),
+                        pipeline_name(pipeline_id),
+                        *runs,
+                        *cost,
+                    );
+                }
+            }
+            CostGroupBy::Provider => {
+                let providers = self
+                    .cache_repository
+                    .config_backend()
+                    .list_providers()
+                    .await?;
+                for (pipeline_id, runs, cost) in &totals {
+                    let provider_id = pipeline_id.split("__").nth(1).unwrap_or_default();
+                    let name = providers
+                        .iter()
+                        .find(|p| p.id.map(|id| id.to_string()).as_deref() == Some(provider_id))
+                        .map(|p| p.name.clone())
+                        .unwrap_or_else(|| provider_id.to_string());
+                    add(provider_id.to_string(), name, *runs, *cost);
+                }
+            }
+            CostGroupBy::Group => {
+                let groups = self.cache_repository.list_pipeline_groups().await?;
+                for (pipeline_id, runs, cost) in &totals {
+                    let mut grouped = false;
+                    for group in groups
+                        .iter()
+                        .filter(|g| g.pipeline_ids.contains(pipeline_id))
+                    {
+                        add(group.id.to_string(), group.name.clone(), *runs, *cost);
+                        grouped = true;
+                    }
+                    if !grouped {
+                        add(String::new(), "Ungrouped".to_string(), *runs, *cost);
+                    }
+                }
+            }
+        }
+
+        let mut entries: Vec<CostEntry> = entries
+            .into_values()
+            .map(|mut entry| {
+                entry.cost = round_cost(entry.cost);
+                entry
+            })
+            .collect();
+        entries.sort_by(|a, b| b.cost.total_cmp(&a.cost).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(CostReport {
+            currency: self.costs.currency.clone(),
+            group_by,
+            start_date,
+            end_date,
+            total: round_cost(totals.iter().map(|(_, _, cost)| cost).sum()),
+            entries,
+        })
+    }
+
+    // Queue samples aren't tied to a run, so they are stored with run number 0
+    // and no hash, which keeps them out of run deduplication.
+    pub async fn record_queue_metrics(&self, queues: &[ProviderQueue]) -> DomainResult<usize> {
+        let timestamp = Utc::now();
+        let mut metrics = Vec::new();
+
+        for queue in queues {
+            let config = self
+                .get_effective_pipeline_config(&queue.metrics_id)
+                .await?;
+            if !config.enabled {
+                continue;
+            }
+
+            let mut sample = |metric_type: MetricType, value: f64| {
+                metrics.push(MetricEntry {
+                    id: 0,
+                    pipeline_id: queue.metrics_id.clone(),
+                    run_number: 0,
+                    timestamp,
+                    metric_type,
+                    value,
+                    metadata: None,
+                    created_at: timestamp,
+                    run_hash: None,
+                });
+            };
+
+            sample(MetricType::QueueDepth, queue.queue.waiting as f64);
+            if let Some(wait_seconds) = queue.queue.avg_wait_time {
+                sample(MetricType::QueueWaitTime, wait_seconds as f64);
+            }
+        }
+
+        self.repository.insert_metrics_batch(metrics).await
     }
 
     pub async fn query_metrics(&self, query: MetricsQuery) -> DomainResult<Vec<MetricEntry>> {
+        let query = self.resolve_label_filter(query).await?;
         self.repository.query_metrics(query).await
     }
 
     pub async fn query_aggregated_metrics(
         &self, query: MetricsQuery,
     ) -> DomainResult<AggregatedMetrics> {
+        let query = self.resolve_label_filter(query).await?;
         if let Some(pipeline_id) = &query.pipeline_id {
             let config = self.get_effective_pipeline_config(pipeline_id).await?;
             if config.enabled {
@@ -318,9 +604,214 @@ impl MetricsService {
             }
         }
 
+        // Catch up first so a long-range chart includes the latest runs
+        // instead of whatever the last scheduled compaction saw.
+        if RollupGranularity::for_query(&query).is_some() {
+            if let Err(e) = self.repository.compact_rollups().await {
+                tracing::warn!(error = %e, "Failed to compact metrics rollups before query");
+            }
+        }
+
         self.repository.query_aggregated_metrics(query).await
     }
 
+    // Streams raw metrics in id order, one page per chunk.
+    pub fn export_metrics(
+        &self, filter: ExportFilter, metric_type: Option<MetricType>, format: ExportFormat,
+    ) -> BoxStream<'static, DomainResult<String>> {
+        let repository = Arc::clone(&self.repository);
+        let query = MetricsQuery {
+            pipeline_ids: (!filter.pipeline_ids.is_empty()).then_some(filter.pipeline_ids),
+            metric_type,
+            start_date: filter.start_date,
+            end_date: filter.end_date,
+            ..Default::default()
+        };
+        let header = futures::stream::iter(export_header::<MetricEntry>(format).map(Ok));
+
+        let rows = futures::stream::try_unfold(Some(0), move |after| {
+            let repository = Arc::clone(&repository);
+            let query = query.clone();
+            async move {
+                let Some(after) = after else {
+                    return Ok(None);
+                };
+
+                let page = repository
+                    .get_metrics_after(query, after, EXPORT_PAGE_SIZE)
+                    .await?;
+                let Some(last) = page.last() else {
+                    return Ok(None);
+                };
+
+                let next = (page.len() == EXPORT_PAGE_SIZE).then_some(last.id);
+                let chunk: String = page.iter().map(|entry| entry.encode(format)).collect();
+                Ok(Some((chunk, next)))
+            }
+        });
+
+        header.chain(rows).boxed()
+    }
+
+    pub async fn compact_rollups(&self) -> DomainResult<usize> {
+        self.repository.compact_rollups().await
+    }
+
+    pub async fn start_compaction(self: &Arc<Self>) {
+        let mut compaction = self.compaction.lock().await;
+        if compaction.is_some() {
+            return;
+        }
+
+        let service = Arc::clone(self);
+        *compaction = Some(tokio::spawn(async move {
+            loop {
+                if let Err(e) = service.compact_rollups().await {
+                    tracing::error!(error = %e, "Metrics rollup compaction failed");
+                }
+                tokio::time::sleep(ROLLUP_COMPACTION_INTERVAL).await;
+            }
+        }));
+    }
+
+    pub async fn stop_compaction(&self) {
+        if let Some(handle) = self.compaction.lock().await.take() {
+            handle.abort();
+        }
+    }
+
+    // Labels live in the pipeline cache, so they are turned into an explicit
+    // pipeline id list before the metrics tables are queried.
+    async fn resolve_label_filter(&self, mut query: MetricsQuery) -> DomainResult<MetricsQuery> {
+        if query.labels.is_empty() {
+            return Ok(query);
+        }
+
+        let pipeline_ids = self
+            .cache_repository
+            .get_cached_pipelines(None)
+            .await?
+            .into_iter()
+            .filter(|pipeline| pipeline.has_labels(&query.labels))
+            .map(|pipeline| pipeline.id)
+            .collect();
+        query.pipeline_ids = Some(pipeline_ids);
+        Ok(query)
+    }
+
+    pub async fn analyze_flakiness(&self) -> DomainResult<Vec<FlakyPipeline>> {
+        let pipelines = self.cache_repository.get_cached_pipelines(None).await?;
+        let mut flaky = Vec::new();
+
+        for pipeline in pipelines {
+            let runs = self
+                .cache_repository
+                .get_cached_run_history(&pipeline.id, FLAKINESS_RUN_WINDOW)
+                .await?;
+
+            let stats = FlakinessStats::from_runs(&runs);
+            if stats.transitions < MIN_FLAKINESS_TRANSITIONS {
+                continue;
+            }
+
+            let score = stats.score();
+            if score > 0.0 {
+                flaky.push(FlakyPipeline {
+                    pipeline_id: pipeline.id,
+                    pipeline_name: pipeline.name,
+                    provider_id: pipeline.provider_id,
+                    score,
+                    flips: stats.flips,
+                    transitions: stats.transitions,
+                    wasted_reruns: stats.wasted_reruns,
+                    runs_analyzed: stats.runs_analyzed,
+                    last_flip_at: stats.last_flip_at,
+                });
+            }
+        }
+
+        flaky.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.wasted_reruns.cmp(&a.wasted_reruns))
+        });
+
+        Ok(flaky)
+    }
+
+    pub async fn query_flaky_pipelines(
+        &self, min_score: Option<f64>, limit: Option<usize>,
+    ) -> DomainResult<Vec<FlakyPipeline>> {
+        let min_score = min_score.unwrap_or(0.0);
+        let flaky = self.analyze_flakiness().await?;
+
+        Ok(flaky
+            .into_iter()
+            .filter(|p| p.score >= min_score)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+
+    // Compares each pipeline's latest runs against its rolling baseline and
+    // stores what deviates. Only findings that were not reported before are
+    // returned, so the caller can notify about them.
+    pub async fn detect_anomalies(&self) -> DomainResult<Vec<MetricAnomaly>> {
+        let pipelines = self.cache_repository.get_cached_pipelines(None).await?;
+        let mut detected = Vec::new();
+
+        for pipeline in pipelines {
+            if !self
+                .get_effective_pipeline_config(&pipeline.id)
+                .await?
+                .enabled
+            {
+                continue;
+            }
+
+            let runs = self
+                .cache_repository
+                .get_cached_run_history(&pipeline.id, ANOMALY_RECENT_RUNS + ANOMALY_BASELINE_RUNS)
+                .await?;
+
+            for finding in AnomalyFinding::detect(&runs) {
+                // A finding overlapping the last reported window is the same
+                // episode, not a new one.
+                let reported = self
+                    .repository
+                    .get_latest_anomaly_run(&pipeline.id, finding.kind)
+                    .await?;
+                if reported.is_some_and(|run| run >= finding.first_run) {
+                    continue;
+                }
+
+                let anomaly = MetricAnomaly {
+                    pipeline_id: pipeline.id.clone(),
+                    kind: finding.kind,
+                    baseline: finding.baseline,
+                    observed: finding.observed,
+                    z_score: finding.z_score,
+                    run_number: finding.last_run,
+                    detected_at: Utc::now(),
+                };
+                if self.repository.insert_anomaly(&anomaly).await? {
+                    detected.push(anomaly);
+                }
+            }
+        }
+
+        let cutoff = Utc::now() - chrono::Duration::days(ANOMALY_RETENTION_DAYS);
+        self.repository.delete_anomalies_before(cutoff).await?;
+
+        Ok(detected)
+    }
+
+    pub async fn list_metric_anomalies(
+        &self, pipeline_id: Option<&str>, limit: Option<usize>,
+    ) -> DomainResult<Vec<MetricAnomaly>> {
+        let limit = limit.unwrap_or(100).clamp(1, MAX_ANOMALY_RESULTS);
+        self.repository.list_anomalies(pipeline_id, limit).await
+    }
+
     pub async fn cleanup_old_metrics(&self) -> DomainResult<usize> {
         self.repository.delete_old_metrics(None).await
     }
@@ -360,9 +851,42 @@ impl MetricsService {
         Ok(repaired)
     }
 
+    // Feeds a pipeline's whole cached run history through `backfill_metrics`,
+    // a page at a time. Returns the runs read and the metrics stored.
+    pub async fn reprocess_cached_runs(
+        &self, pipeline_id: &str, provider: Option<&dyn Provider>,
+    ) -> DomainResult<(usize, usize)> {
+        let mut after = 0;
+        let mut runs = 0;
+        let mut inserted = 0;
+
+        loop {
+            let page = self
+                .cache_repository
+                .get_cached_runs_after(pipeline_id, after, EXPORT_PAGE_SIZE)
+                .await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = last.run_number;
+            runs += page.len();
+            inserted += self.backfill_metrics(pipeline_id, &page, provider).await?;
+
+            if page.len() < EXPORT_PAGE_SIZE {
+                break;
+            }
+        }
+
+        Ok((runs, inserted))
+    }
+
     pub async fn reset_pipeline_processing(&self, pipeline_id: &str) -> DomainResult<()> {
         tracing::info!(pipeline_id = %pipeline_id, "Resetting metrics processing state");
         self.repository.reset_processing_state(pipeline_id).await?;
         Ok(())
     }
 }
+
+fn round_cost(cost: f64) -> f64 {
+    (cost * 10_000.0).round() / 10_000.0
+}
@@ -1,3 +1,18 @@
+pub mod agent_service;
+pub mod audit_log;
+pub mod backfill_service;
+pub mod backup_service;
+pub mod config_bundle_service;
+pub mod discovery_service;
 pub mod metrics_service;
+pub mod notification_center;
+pub mod notification_service;
+pub mod orchestrator_service;
 pub mod pipeline_service;
 pub mod provider_service;
+pub mod provisioning_service;
+pub mod queue_service;
+pub mod retention_service;
+
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+
+use crate::domain::{
+    finished_pipelines,
+    outage_started,
+    AppNotification,
+    AppNotificationKind,
+    DomainError,
+    DomainResult,
+    FindingSeverity,
+    Pipeline,
+    PipelineStatus,
+    ProviderDiagnosis,
+    PROVIDER_OUTAGE_THRESHOLD,
+};
+use crate::event::{
+    CoreEvent,
+    EventBus,
+};
+use crate::infrastructure::database::Repository;
+
+const DEFAULT_LIST_LIMIT: usize = 100;
+const MAX_LIST_LIMIT: usize = 500;
+
+pub struct NotificationCenter {
+    repository: Arc<Repository>,
+    event_bus: Arc<dyn EventBus>,
+    // Last finished status per pipeline, to tell a recovery from an ordinary
+    // success.
+    last_finished: Mutex<HashMap<String, PipelineStatus>>,
+}
+
+impl NotificationCenter {
+    pub fn new(repository: Arc<Repository>, event_bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            repository,
+            event_bus,
+            last_finished: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn list_notifications(
+        &self, unread_only: bool, limit: Option<usize>,
+    ) -> DomainResult<Vec<AppNotification>> {
+        let limit = limit.unwrap_or(DEFAULT_LIST_LIMIT).clamp(1, MAX_LIST_LIMIT);
+        self.repository.list_notifications(unread_only, limit).await
+    }
+
+    pub async fn unread_count(&self) -> DomainResult<usize> {
+        self.repository.count_unread_notifications().await
+    }
+
+    // Marks one notification as read, or all of them when `id` is None.
+    pub async fn mark_read(&self, id: Option<i64>) -> DomainResult<()> {
+        self.repository.mark_notification_read(id).await?;
+        self.emit_changed().await;
+        Ok(())
+    }
+
+    pub async fn clear(&self) -> DomainResult<()> {
+        self.repository.clear_notifications().await?;
+        self.emit_changed().await;
+        Ok(())
+    }
+
+    // Stores the notification and pushes it to connected clients. Failures are
+    // logged, since callers are reporting on something else that went wrong.
+    pub async fn notify(&self, mut notification: AppNotification) {
+        match self.repository.insert_notification(&notification).await {
+            Ok(id) => notification.id = id,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to store notification");
+                return;
+            }
+        }
+
+        let unread = self.unread_count().await.unwrap_or_default();
+        self.event_bus
+            .emit(CoreEvent::NotificationCreated {
+                notification,
+                unread,
+            })
+            .await;
+    }
+
+    // Called by the refresh loop with the cache before and after a fetch.
+    pub async fn handle_status_changes(&self, previous: &[Pipeline], current: &[Pipeline]) {
+        let notifications: Vec<AppNotification> = {
+            let mut last_finished = self.last_finished.lock().unwrap();
+            for pipeline in previous.iter().filter(|p| p.status.is_terminal()) {
+                last_finished
+                    .entry(pipeline.id.clone())
+                    .or_insert_with(|| pipeline.status.clone());
+            }
+
+            finished_pipelines(previous, current)
+                .into_iter()
+                .filter_map(|pipeline| {
+                    let last = last_finished.insert(pipeline.id.clone(), pipeline.status.clone());
+                    AppNotification::for_status_change(pipeline, last.as_ref())
+                })
+                .collect()
+        };
+
+        for notification in notifications {
+            if notification.kind == AppNotificationKind::Failure
+                && self.provider_down(notification.provider_id).await
+            {
+                continue;
+            }
+            self.notify(notification).await;
+        }
+    }
+
+    // Called after a failed fetch. Reports the outage once, when the provider
+    // reaches the threshold of consecutive failures.
+    pub async fn provider_fetch_recorded(&self, provider_id: i64) {
+        let checks = match self
+            .repository
+            .get_provider_health_checks(provider_id, PROVIDER_OUTAGE_THRESHOLD + 1)
+            .await
+        {
+            Ok(checks) => checks,
+            Err(e) => {
+                tracing::warn!(error = %e, provider_id, "Failed to load provider health");
+                return;
+            }
+        };
+        if !outage_started(&checks) {
+            return;
+        }
+
+        let name = self.provider_name(provider_id).await;
+        tracing::warn!(provider_id, provider = %name, "Provider outage detected");
+
+        let mut notification = AppNotification::new(
+            AppNotificationKind::ProviderOutage,
+            format!("{} appears to be down", name),
+            format!(
+                "The last {} fetches failed{}. Failure notifications for its pipelines are \
+                 paused until it recovers.",
+                PROVIDER_OUTAGE_THRESHOLD,
+                checks[0]
+                    .error
+                    .as_deref()
+                    .map(|e| format!(": {}", e))
+                    .unwrap_or_default()
+            ),
+        );
+        notification.provider_id = Some(provider_id);
+        self.notify(notification).await;
+    }
+
+    async fn provider_down(&self, provider_id: Option<i64>) -> bool {
+        match provider_id {
+            Some(id) => self
+                .repository
+                .is_provider_in_outage(id)
+                .await
+                .unwrap_or(false),
+            None => false,
+        }
+    }
+
+    // Rejected credentials usually mean an expired or revoked token. This also
+    // covers OAuth tokens whose refresh failed.
+    pub async fn provider_fetch_failed(&self, provider_id: i64, error: &DomainError) {
+        if !matches!(error, DomainError::AuthenticationFailed(_)) {
+            return;
+        }
+
+        let name = self.provider_name(provider_id).await;
+        let mut notification = AppNotification::new(
+            AppNotificationKind::TokenExpiry,
+            format!("{} credentials rejected", name),
+            format!("{}. Update the token or sign in again.", error),
+        );
+        notification.provider_id = Some(provider_id);
+        self.notify(notification).await;
+    }
+
+    pub async fn provider_conflict(&self, provider_id: i64) {
+        let name = self.provider_name(provider_id).await;
+        let mut notification = AppNotification::new(
+            AppNotificationKind::SyncConflict,
+            format!("{} was changed elsewhere", name),
+            "Another client updated this provider first, so your changes were not saved. \
+             Reload it and apply them again."
+                .to_string(),
+        );
+        notification.provider_id = Some(provider_id);
+        self.notify(notification).await;
+    }
+
+    pub async fn provider_diagnosis_failed(&self, diagnosis: &ProviderDiagnosis) {
+        let problems: Vec<&str> = diagnosis
+            .findings
+            .iter()
+            .filter(|f| f.severity == FindingSeverity::Error)
+            .map(|f| f.message.as_str())
+            .collect();
+
+        let mut notification = AppNotification::new(
+            AppNotificationKind::ProviderDiagnostics,
+            format!("{} needs attention", diagnosis.provider_name),
+            format!(
+                "{}. Run the provider diagnosis for suggested fixes.",
+                problems.join("; ")
+            ),
+        );
+        notification.provider_id = Some(diagnosis.provider_id);
+        self.notify(notification).await;
+    }
+
+    async fn provider_name(&self, provider_id: i64) -> String {
+        self.repository
+            .get_provider(provider_id)
+            .await
+            .map(|p| p.display_name().to_string())
+            .unwrap_or_else(|_| format!("Provider {}", provider_id))
+    }
+
+    async fn emit_changed(&self) {
+        let unread = self.unread_count().await.unwrap_or_default();
+        self.event_bus
+            .emit(CoreEvent::NotificationsChanged { unread })
+            .await;
+    }
+}
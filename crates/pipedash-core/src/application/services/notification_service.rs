@@ -0,0 +1,549 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{
+    Duration as ChronoDuration,
+    Utc,
+};
+
+use crate::application::AccessControl;
+use crate::domain::{
+    finished_pipelines,
+    validate_channel_url,
+    Action,
+    ChannelKind,
+    DomainError,
+    DomainResult,
+    MetricAnomaly,
+    NotificationChannel,
+    NotificationDelivery,
+    NotificationMessage,
+    NotificationRule,
+    NotificationSuppression,
+    Pipeline,
+    PipelineLink,
+    PipelineRun,
+    PipelineStatus,
+};
+use crate::infrastructure::config::NotificationsConfig;
+use crate::infrastructure::database::Repository;
+use crate::infrastructure::{
+    HttpClientManager,
+    SmtpMailer,
+};
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_DELIVERY_HISTORY: usize = 200;
+// Cached runs scanned for the status of the run before the one that finished.
+const TRANSITION_LOOKBACK: usize = 20;
+
+pub struct NotificationService {
+    repository: Arc<Repository>,
+    http_client_manager: Arc<HttpClientManager>,
+    access_control: Arc<AccessControl>,
+    mailer: Option<SmtpMailer>,
+    public_url: Option<String>,
+    url_env: Vec<String>,
+}
+
+impl NotificationService {
+    pub fn new(
+        repository: Arc<Repository>, http_client_manager: Arc<HttpClientManager>,
+        access_control: Arc<AccessControl>, config: &NotificationsConfig,
+        public_url: Option<String>,
+    ) -> Self {
+        let mailer = config.smtp.as_ref().and_then(|config| {
+            SmtpMailer::new(config)
+                .map_err(|e| tracing::warn!(error = %e, "Email notifications disabled"))
+                .ok()
+        });
+
+        Self {
+            repository,
+            http_client_manager,
+            access_control,
+            mailer,
+            public_url,
+            url_env: config.channel_url_env.clone(),
+        }
+    }
+
+    fn link(&self, pipeline: &Pipeline, run_number: Option<i64>) -> String {
+        PipelineLink::new(pipeline.provider_id, &pipeline.id, run_number)
+            .url(self.public_url.as_deref())
+    }
+
+    pub async fn list_channels(&self) -> DomainResult<Vec<NotificationChannel>> {
+        self.repository.list_notification_channels().await
+    }
+
+    // Saving a channel with an existing name replaces it.
+    pub async fn save_channel(
+        &self, name: &str, kind: ChannelKind, url: &str, enabled: bool,
+        rules: Vec<NotificationRule>, suppression: NotificationSuppression,
+    ) -> DomainResult<NotificationChannel> {
+        self.access_control.ensure(Action::ManageNotifications)?;
+
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(DomainError::InvalidConfig(
+                "Channel name cannot be empty".to_string(),
+            ));
+        }
+        // The URL is stored as entered, so `${env:SLACK_WEBHOOK}` keeps the
+        // secret out of the database; it is resolved again on every delivery.
+        let url = url.trim();
+        let resolved_url = self.resolve_url(url)?;
+        validate_channel_url(kind, &resolved_url)?;
+        if kind == ChannelKind::Email && self.mailer.is_none() {
+            return Err(DomainError::InvalidConfig(
+                "Configure [notifications.smtp] in config.toml to use email channels".to_string(),
+            ));
+        }
+
+        suppression.validate()?;
+
+        let rules_json = serde_json::to_string(&rules).map_err(|e| {
+            DomainError::InternalError(format!("Failed to serialize channel rules: {}", e))
+        })?;
+        let suppression_json = serde_json::to_string(&suppression).map_err(|e| {
+            DomainError::InternalError(format!("Failed to serialize channel suppression: {}", e))
+        })?;
+        let id = self
+            .repository
+            .save_notification_channel(
+                name,
+                kind.as_str(),
+                url,
+                enabled,
+                &rules_json,
+                &suppression_json,
+            )
+            .await?;
+        self.get_channel(id).await
+    }
+
+    pub async fn delete_channel(&self, channel_id: i64) -> DomainResult<()> {
+        self.access_control.ensure(Action::ManageNotifications)?;
+
+        self.repository
+            .delete_notification_channel(channel_id)
+            .await?;
+        self.repository
+            .delete_notification_deliveries(channel_id)
+            .await
+    }
+
+    pub async fn get_deliveries(
+        &self, channel_id: Option<i64>, limit: Option<usize>,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        let limit = limit.unwrap_or(50).clamp(1, MAX_DELIVERY_HISTORY);
+        self.repository
+            .get_notification_deliveries(channel_id, limit)
+            .await
+    }
+
+    // Sends a sample message regardless of the channel's rules, suppression or
+    // enabled flag.
+    pub async fn test_channel(&self, channel_id: i64) -> DomainResult<NotificationDelivery> {
+        self.access_control.ensure(Action::ManageNotifications)?;
+
+        let channel = self.get_channel(channel_id).await?;
+        let message = NotificationMessage {
+            pipeline_id: "pipedash-test".to_string(),
+            pipeline_name: "Pipedash test notification".to_string(),
+            repository: "pipedash".to_string(),
+            provider_type: "pipedash".to_string(),
+            status: PipelineStatus::Success,
+            branch: None,
+            summary: Some("This is a test message sent from Pipedash.".to_string()),
+            link: None,
+        };
+
+        let mut delivery = self.deliver(&channel, &message).await;
+        delivery.pipeline_id = None;
+        delivery.status = None;
+        self.record(&delivery).await;
+        Ok(delivery)
+    }
+
+    // Called by the refresh loop with the cache before and after a fetch.
+    pub async fn handle_status_changes(&self, previous: &[Pipeline], current: &[Pipeline]) {
+        let finished = finished_pipelines(previous, current);
+        if finished.is_empty() {
+            return;
+        }
+
+        let channels = match self.repository.list_notification_channels().await {
+            Ok(channels) => channels,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load notification channels");
+                return;
+            }
+        };
+        if !channels.iter().any(|c| c.enabled) {
+            return;
+        }
+
+        let groups = self
+            .repository
+            .list_pipeline_groups()
+            .await
+            .unwrap_or_default();
+
+        for pipeline in finished {
+            let group_ids: Vec<i64> = groups
+                .iter()
+                .filter(|g| g.pipeline_ids.contains(&pipeline.id))
+                .map(|g| g.id)
+                .collect();
+            let runs = self
+                .repository
+                .get_cached_run_history(&pipeline.id, TRANSITION_LOOKBACK)
+                .await
+                .unwrap_or_default();
+            let mut message = NotificationMessage::from_pipeline(pipeline);
+            let run = latest_run(pipeline, &runs);
+            if let Some(run) = run {
+                message = message.with_run(run);
+            }
+            message.link = Some(self.link(pipeline, run.map(|r| r.run_number)));
+            let previous_status = previous_status(pipeline, &runs);
+
+            // A provider outage tends to fail everything at once; the outage
+            // itself is reported through the notification center instead.
+            if pipeline.status == PipelineStatus::Failed
+                && self
+                    .repository
+                    .is_provider_in_outage(pipeline.provider_id)
+                    .await
+                    .unwrap_or(false)
+            {
+                tracing::debug!(
+                    pipeline_id = %pipeline.id,
+                    provider_id = pipeline.provider_id,
+                    "Failure notification suppressed during provider outage"
+                );
+                continue;
+            }
+
+            for channel in channels.iter().filter(|c| c.routes(pipeline, &group_ids)) {
+                if let Some(reason) = self
+                    .suppression_reason(channel, pipeline, previous_status)
+                    .await
+                {
+                    tracing::debug!(
+                        channel = %channel.name,
+                        pipeline_id = %pipeline.id,
+                        reason,
+                        "Notification suppressed"
+                    );
+                    continue;
+                }
+
+                let delivery = self.deliver(channel, &message).await;
+                if let Some(error) = &delivery.error {
+                    tracing::warn!(
+                        channel = %channel.name,
+                        pipeline_id = %pipeline.id,
+                        error = %error,
+                        "Notification delivery failed"
+                    );
+                }
+                self.record(&delivery).await;
+            }
+        }
+    }
+
+    // Only channels with a rule that opted into anomalies receive these. Quiet
+    // hours still apply; the detector already reports each episode once.
+    pub async fn handle_anomalies(&self, anomalies: &[MetricAnomaly]) {
+        let channels = match self.repository.list_notification_channels().await {
+            Ok(channels) => channels,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load notification channels");
+                return;
+            }
+        };
+        if !channels
+            .iter()
+            .any(|c| c.enabled && c.rules.iter().any(|rule| rule.anomalies))
+        {
+            return;
+        }
+
+        let pipelines = self
+            .repository
+            .get_cached_pipelines(None)
+            .await
+            .unwrap_or_default();
+        let groups = self
+            .repository
+            .list_pipeline_groups()
+            .await
+            .unwrap_or_default();
+
+        for anomaly in anomalies {
+            let Some(pipeline) = pipelines.iter().find(|p| p.id == anomaly.pipeline_id) else {
+                continue;
+            };
+            let group_ids: Vec<i64> = groups
+                .iter()
+                .filter(|g| g.pipeline_ids.contains(&pipeline.id))
+                .map(|g| g.id)
+                .collect();
+            let message = NotificationMessage {
+                summary: Some(anomaly.describe()),
+                link: Some(self.link(pipeline, None)),
+                ..NotificationMessage::from_pipeline(pipeline)
+            };
+
+            for channel in channels
+                .iter()
+                .filter(|c| c.routes_anomaly(pipeline, &group_ids))
+            {
+                if channel.suppression.is_quiet(Utc::now()) {
+                    continue;
+                }
+
+                // Without a status the delivery does not count towards the
+                // dedup window of regular status notifications.
+                let mut delivery = self.deliver(channel, &message).await;
+                delivery.status = None;
+                if let Some(error) = &delivery.error {
+                    tracing::warn!(
+                        channel = %channel.name,
+                        pipeline_id = %pipeline.id,
+                        error = %error,
+                        "Anomaly notification delivery failed"
+                    );
+                }
+                self.record(&delivery).await;
+            }
+        }
+    }
+
+    async fn deliver(
+        &self, channel: &NotificationChannel, message: &NotificationMessage,
+    ) -> NotificationDelivery {
+        let result = match channel.kind {
+            ChannelKind::Email => match &self.mailer {
+                Some(mailer) => mailer
+                    .send(&channel.recipients(), message)
+                    .await
+                    .map_err(|e| e.to_string()),
+                None => Err("SMTP is not configured".to_string()),
+            },
+            _ => match self.resolve_url(&channel.url) {
+                Ok(url) => self
+                    .http_client_manager
+                    .default_client()
+                    .post(url)
+                    .timeout(DELIVERY_TIMEOUT)
+                    .json(&channel.kind.payload(message))
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        };
+
+        NotificationDelivery {
+            channel_id: channel.id,
+            pipeline_id: Some(message.pipeline_id.clone()),
+            status: Some(message.status.clone()),
+            success: result.is_ok(),
+            error: result.err(),
+            delivered_at: Utc::now(),
+        }
+    }
+
+    // Channel URLs come from API callers, so they are not interpolated: the
+    // only reference allowed is a whole `${env:NAME}` naming a variable the
+    // operator listed in `[notifications] channel_url_env`.
+    fn resolve_url(&self, url: &str) -> DomainResult<String> {
+        let Some(name) = url
+            .strip_prefix("${env:")
+            .and_then(|rest| rest.strip_suffix('}'))
+        else {
+            if url.contains("${") {
+                return Err(DomainError::InvalidConfig(
+                    "Channel URL: only a whole ${env:NAME} reference is supported".to_string(),
+                ));
+            }
+            return Ok(url.to_string());
+        };
+
+        if !self.url_env.iter().any(|allowed| allowed == name) {
+            return Err(DomainError::InvalidConfig(format!(
+                "Channel URL: {} is not listed in [notifications] channel_url_env",
+                name
+            )));
+        }
+        std::env::var(name).map_err(|_| {
+            DomainError::InvalidConfig(format!(
+                "Channel URL: environment variable {} is not set",
+                name
+            ))
+        })
+    }
+
+    async fn record(&self, delivery: &NotificationDelivery) {
+        if let Err(e) = self.repository.record_notification_delivery(delivery).await {
+            tracing::warn!(error = %e, "Failed to record notification delivery");
+        }
+    }
+
+    async fn suppression_reason(
+        &self, channel: &NotificationChannel, pipeline: &Pipeline,
+        previous_status: Option<&PipelineStatus>,
+    ) -> Option<&'static str> {
+        let suppression = &channel.suppression;
+
+        if suppression.transitions_only && previous_status == Some(&pipeline.status) {
+            return Some("status unchanged");
+        }
+
+        if suppression.dedup_window_minutes > 0 {
+            let since =
+                Utc::now() - ChronoDuration::minutes(suppression.dedup_window_minutes as i64);
+            let recent = self
+                .repository
+                .get_notification_deliveries(Some(channel.id), MAX_DELIVERY_HISTORY)
+                .await
+                .unwrap_or_default();
+            let duplicate = recent.iter().any(|delivery| {
+                delivery.success
+                    && delivery.delivered_at >= since
+                    && delivery.pipeline_id.as_deref() == Some(pipeline.id.as_str())
+                    && delivery.status.as_ref() == Some(&pipeline.status)
+            });
+            if duplicate {
+                return Some("duplicate within dedup window");
+            }
+        }
+
+        if suppression.is_quiet(Utc::now()) {
+            return Some("quiet hours");
+        }
+
+        None
+    }
+
+    async fn get_channel(&self, channel_id: i64) -> DomainResult<NotificationChannel> {
+        self.repository
+            .list_notification_channels()
+            .await?
+            .into_iter()
+            .find(|c| c.id == channel_id)
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Notification channel {} not found", channel_id))
+            })
+    }
+}
+
+// The run that just finished, if the cache already has it.
+fn latest_run<'a>(pipeline: &Pipeline, runs: &'a [PipelineRun]) -> Option<&'a PipelineRun> {
+    runs.first().filter(|run| run.status == pipeline.status)
+}
+
+// Status of the finished run before the current one. Providers stamp
+// `last_run` with either the start or the last update of the run, so a cached
+// run that ended within a minute of it (or later) is taken to be the current
+// one.
+fn previous_status<'a>(pipeline: &Pipeline, runs: &'a [PipelineRun]) -> Option<&'a PipelineStatus> {
+    runs.iter()
+        .filter(|run| {
+            pipeline.last_run.is_none_or(|last_run| {
+                run.concluded_at.unwrap_or(run.started_at) + ChronoDuration::minutes(1) < last_run
+            })
+        })
+        .map(|run| &run.status)
+        .find(|status| status.is_terminal())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::test_services;
+    use crate::application::with_role;
+    use crate::domain::Role;
+
+    async fn service(dir: &std::path::Path) -> NotificationService {
+        let services = test_services(dir).await;
+        let config = NotificationsConfig {
+            channel_url_env: vec!["TEST_CHANNEL_URL".to_string()],
+            ..Default::default()
+        };
+
+        NotificationService::new(
+            services.repository,
+            services.http_client_manager,
+            services.access_control,
+            &config,
+            None,
+        )
+    }
+
+    async fn save(service: &NotificationService, url: &str) -> DomainResult<NotificationChannel> {
+        service
+            .save_channel(
+                "alerts",
+                ChannelKind::Webhook,
+                url,
+                true,
+                Vec::new(),
+                NotificationSuppression::default(),
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_channel_url_references_are_not_interpolated() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path()).await;
+        std::env::set_var("TEST_CHANNEL_URL", "https://hooks.example.com/abc");
+
+        for url in [
+            "https://attacker.example/?k=${env:TEST_CHANNEL_URL}",
+            "${file:/etc/hostname}",
+            "${env:PIPEDASH_VAULT_PASSWORD}",
+            "${TEST_CHANNEL_URL}",
+        ] {
+            assert!(
+                matches!(
+                    save(&service, url).await,
+                    Err(DomainError::InvalidConfig(_))
+                ),
+                "{url} was accepted"
+            );
+        }
+        assert!(service.list_channels().await.unwrap().is_empty());
+
+        let channel = save(&service, "${env:TEST_CHANNEL_URL}").await.unwrap();
+        assert_eq!(channel.url, "${env:TEST_CHANNEL_URL}");
+        std::env::remove_var("TEST_CHANNEL_URL");
+    }
+
+    #[tokio::test]
+    async fn test_viewer_cannot_manage_channels() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path()).await;
+
+        let saved = with_role(Role::Viewer, save(&service, "https://hooks.example.com")).await;
+        assert!(matches!(saved, Err(DomainError::PermissionDenied(_))));
+        assert!(service.list_channels().await.unwrap().is_empty());
+
+        let channel = save(&service, "https://hooks.example.com").await.unwrap();
+
+        let tested = with_role(Role::Viewer, service.test_channel(channel.id)).await;
+        assert!(matches!(tested, Err(DomainError::PermissionDenied(_))));
+        assert!(service.get_deliveries(None, None).await.unwrap().is_empty());
+
+        let deleted = with_role(Role::Viewer, service.delete_channel(channel.id)).await;
+        assert!(matches!(deleted, Err(DomainError::PermissionDenied(_))));
+        assert_eq!(service.list_channels().await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::pipeline_service::PipelineService;
+use crate::application::with_role;
+use crate::domain::{
+    finished_pipelines,
+    validate_dependency,
+    validate_pipeline_id,
+    Action,
+    DomainError,
+    DomainResult,
+    Pipeline,
+    PipelineDependency,
+    PipelineGraph,
+    PipelineStatus,
+    TriggerParams,
+};
+use crate::event::{
+    CoreEvent,
+    EventBus,
+};
+use crate::infrastructure::database::Repository;
+
+pub struct OrchestratorService {
+    repository: Arc<Repository>,
+    pipeline_service: Arc<PipelineService>,
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl OrchestratorService {
+    pub fn new(
+        repository: Arc<Repository>, pipeline_service: Arc<PipelineService>,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Self {
+        Self {
+            repository,
+            pipeline_service,
+            event_bus,
+        }
+    }
+
+    pub async fn list_dependencies(&self) -> DomainResult<Vec<PipelineDependency>> {
+        self.repository.list_pipeline_dependencies().await
+    }
+
+    // Saving an existing upstream/downstream pair replaces its status and
+    // inputs.
+    pub async fn save_dependency(
+        &self, upstream_id: &str, downstream_id: &str, trigger_on: PipelineStatus,
+        inputs: HashMap<String, serde_json::Value>,
+    ) -> DomainResult<PipelineDependency> {
+        let access_control = self.pipeline_service.provider_service().access_control();
+        access_control.ensure(Action::TriggerPipeline)?;
+        validate_pipeline_id(upstream_id)?;
+        validate_pipeline_id(downstream_id)?;
+
+        let edges: Vec<PipelineDependency> = self
+            .repository
+            .list_pipeline_dependencies()
+            .await?
+            .into_iter()
+            .filter(|e| !(e.upstream_id == upstream_id && e.downstream_id == downstream_id))
+            .collect();
+        validate_dependency(&edges, upstream_id, downstream_id, &trigger_on)?;
+
+        let inputs_json = serde_json::to_string(&inputs).map_err(|e| {
+            DomainError::InternalError(format!("Failed to serialize dependency: {}", e))
+        })?;
+        let id = self
+            .repository
+            .save_pipeline_dependency(
+                upstream_id,
+                downstream_id,
+                trigger_on.as_str(),
+                &inputs_json,
+                access_control.current_role().as_str(),
+            )
+            .await?;
+
+        self.repository
+            .list_pipeline_dependencies()
+            .await?
+            .into_iter()
+            .find(|e| e.id == id)
+            .ok_or_else(|| DomainError::NotFound(format!("Dependency {} not found", id)))
+    }
+
+    pub async fn delete_dependency(&self, id: i64) -> DomainResult<()> {
+        self.pipeline_service
+            .provider_service()
+            .access_control()
+            .ensure(Action::TriggerPipeline)?;
+        self.repository.delete_pipeline_dependency(id).await
+    }
+
+    pub async fn get_pipeline_graph(&self) -> DomainResult<PipelineGraph> {
+        let edges = self.repository.list_pipeline_dependencies().await?;
+        let pipelines = self.repository.get_cached_pipelines(None).await?;
+        Ok(PipelineGraph::build(edges, &pipelines))
+    }
+
+    // Called by the refresh loop with the cache before and after a fetch.
+    // Downstream pipelines are triggered once per upstream run that finished
+    // with the dependency's status.
+    pub async fn handle_status_changes(&self, previous: &[Pipeline], current: &[Pipeline]) {
+        let finished = finished_pipelines(previous, current);
+        if finished.is_empty() {
+            return;
+        }
+
+        let edges = match self.repository.list_pipeline_dependencies().await {
+            Ok(edges) => edges,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load pipeline dependencies");
+                return;
+            }
+        };
+
+        let default_role = self
+            .pipeline_service
+            .provider_service()
+            .access_control()
+            .default_role();
+        let maintenance = self.pipeline_service.maintenance_calendar().await;
+        let now = chrono::Utc::now();
+
+        for pipeline in finished {
+            for edge in edges
+                .iter()
+                .filter(|e| e.upstream_id == pipeline.id && e.trigger_on == pipeline.status)
+            {
+                if let Some(active) = maintenance.active(&edge.downstream_id, now) {
+                    tracing::info!(
+                        upstream = %edge.upstream_id,
+                        downstream = %edge.downstream_id,
+                        window = %active.name,
+                        "Skipped dependent pipeline in maintenance"
+                    );
+                    continue;
+                }
+
+                let params = TriggerParams {
+                    workflow_id: edge.downstream_id.clone(),
+                    inputs: (!edge.inputs.is_empty()).then(|| {
+                        serde_json::Value::Object(edge.inputs.clone().into_iter().collect())
+                    }),
+                };
+
+                // The refresh loop has no caller of its own, so the trigger
+                // runs with the role that saved the dependency.
+                let role = edge.created_by_role.unwrap_or(default_role);
+                let error =
+                    match with_role(role, self.pipeline_service.trigger_pipeline(params)).await {
+                        Ok(_) => {
+                            tracing::info!(
+                                upstream = %edge.upstream_id,
+                                downstream = %edge.downstream_id,
+                                "Triggered dependent pipeline"
+                            );
+                            None
+                        }
+                        Err(e) => {
+                            tracing::warn!(
+                                upstream = %edge.upstream_id,
+                                downstream = %edge.downstream_id,
+                                error = %e,
+                                "Failed to trigger dependent pipeline"
+                            );
+                            Some(e.to_string())
+                        }
+                    };
+
+                self.event_bus
+                    .emit(CoreEvent::DependencyTriggered {
+                        upstream_id: edge.upstream_id.clone(),
+                        downstream_id: edge.downstream_id.clone(),
+                        error,
+                    })
+                    .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::test_services;
+    use crate::application::AuditLog;
+    use crate::domain::Role;
+
+    async fn service(dir: &std::path::Path) -> OrchestratorService {
+        let services = test_services(dir).await;
+        let pipeline_service = Arc::new(PipelineService::new(
+            Arc::clone(&services.repository),
+            services.provider_service,
+            None,
+            Arc::clone(&services.event_bus),
+            services.notification_center,
+            Arc::new(AuditLog::new(
+                Arc::clone(&services.repository),
+                services.access_control,
+            )),
+        ));
+
+        OrchestratorService::new(services.repository, pipeline_service, services.event_bus)
+    }
+
+    #[tokio::test]
+    async fn test_viewer_cannot_change_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path()).await;
+
+        let saved = with_role(
+            Role::Viewer,
+            service.save_dependency("1__a", "1__b", PipelineStatus::Success, HashMap::new()),
+        )
+        .await;
+        assert!(matches!(saved, Err(DomainError::PermissionDenied(_))));
+        assert!(service.list_dependencies().await.unwrap().is_empty());
+
+        let dependency = with_role(
+            Role::Operator,
+            service.save_dependency("1__a", "1__b", PipelineStatus::Success, HashMap::new()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(dependency.created_by_role, Some(Role::Operator));
+
+        let deleted = with_role(Role::Viewer, service.delete_dependency(dependency.id)).await;
+        assert!(matches!(deleted, Err(DomainError::PermissionDenied(_))));
+        assert_eq!(service.list_dependencies().await.unwrap().len(), 1);
+    }
+}
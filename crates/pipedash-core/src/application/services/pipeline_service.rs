@@ -1,22 +1,90 @@
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 use std::sync::Arc;
 use std::time::Duration;
 
 use dashmap::DashSet;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use pipedash_plugin_api::Capability;
 use tokio::sync::Semaphore;
 use tokio::time::timeout;
 
 const MAX_CONCURRENT_PROVIDER_FETCHES: usize = 10;
-
+const MAX_SEARCH_RESULTS: usize = 100;
+// Upper bound on cached runs read for one commit lookup, retries included.
+const MAX_COMMIT_RUNS: usize = 1000;
+const MAX_QUICK_ACTIONS: usize = 50;
+const MAX_CONCURRENT_BULK_ACTIONS: usize = 5;
+const MAX_BULK_ITEMS: usize = 100;
+const DEFAULT_APPROVAL_LIST_LIMIT: usize = 100;
+const MAX_APPROVAL_LIST_LIMIT: usize = 500;
+// Branch boards older than this are refetched from the provider.
+const BRANCH_STATUS_CACHE_TTL: Duration = Duration::from_secs(60);
+
+use super::audit_log::AuditLog;
 use super::metrics_service::MetricsService;
+use super::notification_center::NotificationCenter;
 use super::provider_service::ProviderService;
 use crate::domain::{
+    branch_parameter,
+    export_header,
+    normalize_commit_prefix,
+    normalize_labels,
+    pipeline_actions,
+    rank_pipelines,
+    validate_pagination,
+    validate_pipeline_id,
+    validate_preset_parameters,
+    Action,
+    ApprovalStatus,
+    ArtifactDownload,
+    AuditAction,
+    AuditEntry,
+    BranchBoard,
+    BuildArtifact,
+    BulkItemResult,
+    BulkOperation,
+    CommitStatus,
     DomainError,
     DomainResult,
+    ExportFilter,
+    ExportFormat,
+    ExportRecord,
+    GatedOperation,
+    LogQuery,
+    MaintenanceCalendar,
+    MaintenanceWindow,
+    MetricsRebuild,
     PaginatedRunHistory,
+    PendingApproval,
+    PinnedPipeline,
     Pipeline,
+    PipelineGroup,
+    PipelineGroupStatus,
+    PipelineListQuery,
+    PipelineRefreshSetting,
     PipelineRun,
+    Provider,
+    QuickAction,
+    RefreshSchedule,
+    ReleaseReport,
+    RunAnnotation,
+    RunComparison,
+    RunLogs,
+    RunRef,
+    SavedView,
+    SearchQuery,
+    SearchResult,
+    StatusSummary,
+    TriggerApproval,
     TriggerParams,
+    TriggerPreset,
+    ViewQuery,
+    EXPORT_PAGE_SIZE,
 };
 use crate::event::{
     CacheInvalidationReason,
@@ -29,21 +97,27 @@ use crate::infrastructure::deduplication::{
     hash_request,
     RequestDeduplicator,
 };
+use crate::infrastructure::sync::SyncManager;
+use crate::infrastructure::webhooks::WebhookEvent;
 
 pub struct PipelineService {
     repository: Arc<Repository>,
     provider_service: Arc<ProviderService>,
     metrics_service: Option<Arc<MetricsService>>,
     event_bus: Arc<dyn EventBus>,
-    deduplicator: Arc<RequestDeduplicator<Vec<Pipeline>>>,
+    deduplicator: Arc<RequestDeduplicator<Option<Vec<Pipeline>>>>,
     run_deduplicator: Arc<RequestDeduplicator<Vec<PipelineRun>>>,
+    run_details_deduplicator: Arc<RequestDeduplicator<PipelineRun>>,
     cache_write_tracker: Arc<DashSet<String>>,
+    notification_center: Arc<NotificationCenter>,
+    audit_log: Arc<AuditLog>,
 }
 
 impl PipelineService {
     pub fn new(
         repository: Arc<Repository>, provider_service: Arc<ProviderService>,
         metrics_service: Option<Arc<MetricsService>>, event_bus: Arc<dyn EventBus>,
+        notification_center: Arc<NotificationCenter>, audit_log: Arc<AuditLog>,
     ) -> Self {
         Self {
             repository,
@@ -52,13 +126,21 @@ impl PipelineService {
             event_bus,
             deduplicator: Arc::new(RequestDeduplicator::new()),
             run_deduplicator: Arc::new(RequestDeduplicator::new()),
+            run_details_deduplicator: Arc::new(RequestDeduplicator::new()),
             cache_write_tracker: Arc::new(DashSet::new()),
+            notification_center,
+            audit_log,
         }
     }
 
+    pub fn provider_service(&self) -> &Arc<ProviderService> {
+        &self.provider_service
+    }
+
+    // Returns whether the provider's fetch status changed.
     async fn update_provider_status_and_emit(
         &self, provider_id: i64, success: bool, error: Option<String>,
-    ) {
+    ) -> bool {
         if let Ok(changed) = self
             .repository
             .update_provider_fetch_status(provider_id, success, error)
@@ -67,7 +149,21 @@ impl PipelineService {
             if changed {
                 self.event_bus.emit(CoreEvent::ProvidersChanged).await;
             }
+            if !success {
+                self.notification_center
+                    .provider_fetch_recorded(provider_id)
+                    .await;
+            }
+            return changed;
         }
+        false
+    }
+
+    // The provider behind a pipeline, for plugin-contributed metrics. Metrics
+    // are still extracted without it, e.g. once the provider has been removed.
+    async fn metrics_provider(&self, pipeline_id: &str) -> Option<Arc<dyn Provider>> {
+        let provider_id = pipeline_id.split("__").nth(1)?.parse::<i64>().ok()?;
+        self.provider_service.get_provider(provider_id).await.ok()
     }
 
     pub async fn fetch_pipelines(&self, provider_id: Option<i64>) -> DomainResult<Vec<Pipeline>> {
@@ -75,23 +171,56 @@ impl PipelineService {
 
         if let Some(pid) = provider_id {
             let provider = self.provider_service.get_provider(pid).await?;
-
-            let result = timeout(Duration::from_secs(30), provider.fetch_pipelines()).await;
+            let provider_service = Arc::clone(&self.provider_service);
+
+            // Shares the request with a background refresh of the same
+            // provider.
+            let request_id = hash_request(pid, "fetch_pipelines");
+            let result = timeout(
+                Duration::from_secs(30),
+                self.deduplicator.deduplicate(request_id, || async move {
+                    let mut pipelines = provider_service
+                        .guarded_fetch(pid, || provider.fetch_pipelines_if_modified())
+                        .await?;
+                    if let Some(pipelines) = &mut pipelines {
+                        provider_service
+                            .pipeline_filter(pid)
+                            .await?
+                            .apply(pipelines);
+                    }
+                    Ok(pipelines)
+                }),
+            )
+            .await;
 
             match result {
-                Ok(Ok(pipelines)) => {
-                    self.repository
-                        .update_pipelines_cache(pid, &pipelines)
-                        .await?;
+                Ok(Ok(fetched)) => {
+                    // `None` means the provider reported no change, so the
+                    // cached pipelines are current and nothing is written.
+                    let (pipelines, changed) = match fetched {
+                        Some(pipelines) => {
+                            let changed = self
+                                .repository
+                                .update_pipelines_cache(pid, &pipelines)
+                                .await?;
+                            (pipelines, changed)
+                        }
+                        None => (
+                            self.repository.get_cached_pipelines(Some(pid)).await?,
+                            false,
+                        ),
+                    };
 
                     self.update_provider_status_and_emit(pid, true, None).await;
 
-                    self.event_bus
-                        .emit(CoreEvent::PipelineCacheInvalidated {
-                            provider_id: Some(pid),
-                            reason: CacheInvalidationReason::Fetch,
-                        })
-                        .await;
+                    if changed {
+                        self.event_bus
+                            .emit(CoreEvent::PipelineCacheInvalidated {
+                                provider_id: Some(pid),
+                                reason: CacheInvalidationReason::Fetch,
+                            })
+                            .await;
+                    }
 
                     let timestamp = std::time::SystemTime::now()
                         .duration_since(std::time::UNIX_EPOCH)
@@ -100,7 +229,7 @@ impl PipelineService {
 
                     self.event_bus
                         .emit(CoreEvent::PipelinesUpdated {
-                            pipelines: pipelines.clone(),
+                            pipelines: self.annotate_maintenance(pipelines.clone()).await,
                             provider_id: Some(pid),
                             timestamp,
                         })
@@ -108,14 +237,23 @@ impl PipelineService {
 
                     Ok(pipelines)
                 }
+                // Nothing was fetched, so there is nothing to record.
+                Ok(Err(e @ DomainError::CircuitOpen { .. })) => Err(e),
                 Ok(Err(e)) => {
                     let error_msg = format!("{e}");
-                    self.update_provider_status_and_emit(pid, false, Some(error_msg))
-                        .await;
+                    if self
+                        .update_provider_status_and_emit(pid, false, Some(error_msg))
+                        .await
+                    {
+                        self.notification_center
+                            .provider_fetch_failed(pid, &e)
+                            .await;
+                    }
 
                     Err(e)
                 }
                 Err(_elapsed) => {
+                    self.provider_service.record_fetch_outcome(pid, true);
                     let error_msg = "Connection timeout - provider did not respond".to_string();
                     self.update_provider_status_and_emit(pid, false, Some(error_msg.clone()))
                         .await;
@@ -124,134 +262,187 @@ impl PipelineService {
                 }
             }
         } else {
-            let provider_summaries = self.provider_service.list_providers().await?;
-
-            let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_PROVIDER_FETCHES));
-
-            let futures: Vec<_> = provider_summaries
-                .into_iter()
-                .map(|summary| {
-                    let semaphore = semaphore.clone();
-                    let provider_service = self.provider_service.clone();
-                    let deduplicator = self.deduplicator.clone();
-                    let repository = self.repository.clone();
-                    let event_bus = self.event_bus.clone();
-                    let provider_id = summary.id;
-                    async move {
-                        let _permit = semaphore.acquire().await.expect("semaphore closed");
-                        let request_id = hash_request(provider_id, "fetch_pipelines");
-
-                        let result = timeout(
-                            Duration::from_secs(30),
-                            deduplicator.deduplicate(request_id, || async {
-                                let provider = provider_service.get_provider(provider_id).await?;
-                                provider.fetch_pipelines().await
-                            }),
-                        )
-                        .await;
-
-                        match result {
-                            Ok(Ok(pipelines)) => {
-                                if let Ok(changed) = repository
-                                    .update_provider_fetch_status(provider_id, true, None)
-                                    .await
-                                {
-                                    if changed {
-                                        event_bus.emit(CoreEvent::ProvidersChanged).await;
-                                    }
-                                }
+            self.fetch_pipelines_skipping(&HashSet::new()).await
+        }
+    }
 
-                                Ok((provider_id, pipelines))
+    pub async fn fetch_pipelines_skipping(
+        &self, skip_providers: &HashSet<i64>,
+    ) -> DomainResult<Vec<Pipeline>> {
+        let provider_summaries: Vec<_> = self
+            .provider_service
+            .list_providers()
+            .await?
+            .into_iter()
+            .filter(|summary| summary.enabled && !skip_providers.contains(&summary.id))
+            .collect();
+
+        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_PROVIDER_FETCHES));
+
+        let futures: Vec<_> = provider_summaries
+            .into_iter()
+            .map(|summary| {
+                let semaphore = semaphore.clone();
+                let provider_service = self.provider_service.clone();
+                let deduplicator = self.deduplicator.clone();
+                let repository = self.repository.clone();
+                let event_bus = self.event_bus.clone();
+                let notification_center = self.notification_center.clone();
+                let provider_id = summary.id;
+                async move {
+                    let _permit = semaphore.acquire().await.expect("semaphore closed");
+                    let request_id = hash_request(provider_id, "fetch_pipelines");
+                    let fetch_service = Arc::clone(&provider_service);
+
+                    let result = timeout(
+                        Duration::from_secs(30),
+                        deduplicator.deduplicate(request_id, || async move {
+                            let provider = fetch_service.get_provider(provider_id).await?;
+                            let mut pipelines = fetch_service
+                                .guarded_fetch(provider_id, || {
+                                    provider.fetch_pipelines_if_modified()
+                                })
+                                .await?;
+                            if let Some(pipelines) = &mut pipelines {
+                                fetch_service
+                                    .pipeline_filter(provider_id)
+                                    .await?
+                                    .apply(pipelines);
                             }
-                            Ok(Err(e)) => {
-                                let error_msg = format!("{e}");
-                                if let Ok(changed) = repository
-                                    .update_provider_fetch_status(
-                                        provider_id,
-                                        false,
-                                        Some(error_msg),
-                                    )
-                                    .await
-                                {
-                                    if changed {
-                                        event_bus.emit(CoreEvent::ProvidersChanged).await;
-                                    }
+                            Ok(pipelines)
+                        }),
+                    )
+                    .await;
+
+                    match result {
+                        Ok(Ok(pipelines)) => {
+                            if let Ok(changed) = repository
+                                .update_provider_fetch_status(provider_id, true, None)
+                                .await
+                            {
+                                if changed {
+                                    event_bus.emit(CoreEvent::ProvidersChanged).await;
                                 }
-
-                                Err(e)
                             }
-                            Err(_elapsed) => {
-                                let error_msg =
-                                    "Connection timeout - provider did not respond".to_string();
-                                if let Ok(changed) = repository
-                                    .update_provider_fetch_status(
-                                        provider_id,
-                                        false,
-                                        Some(error_msg.clone()),
-                                    )
-                                    .await
-                                {
-                                    if changed {
-                                        event_bus.emit(CoreEvent::ProvidersChanged).await;
-                                    }
+
+                            Ok((provider_id, pipelines))
+                        }
+                        Ok(Err(e @ DomainError::CircuitOpen { .. })) => Err(e),
+                        Ok(Err(e)) => {
+                            let error_msg = format!("{e}");
+                            if let Ok(changed) = repository
+                                .update_provider_fetch_status(provider_id, false, Some(error_msg))
+                                .await
+                            {
+                                if changed {
+                                    event_bus.emit(CoreEvent::ProvidersChanged).await;
+                                    notification_center
+                                        .provider_fetch_failed(provider_id, &e)
+                                        .await;
                                 }
+                                notification_center
+                                    .provider_fetch_recorded(provider_id)
+                                    .await;
+                            }
 
-                                Err(DomainError::ProviderError(error_msg))
+                            Err(e)
+                        }
+                        Err(_elapsed) => {
+                            provider_service.record_fetch_outcome(provider_id, true);
+                            let error_msg =
+                                "Connection timeout - provider did not respond".to_string();
+                            if let Ok(changed) = repository
+                                .update_provider_fetch_status(
+                                    provider_id,
+                                    false,
+                                    Some(error_msg.clone()),
+                                )
+                                .await
+                            {
+                                if changed {
+                                    event_bus.emit(CoreEvent::ProvidersChanged).await;
+                                }
+                                notification_center
+                                    .provider_fetch_recorded(provider_id)
+                                    .await;
                             }
+
+                            Err(DomainError::ProviderError(error_msg))
                         }
                     }
-                })
-                .collect();
+                }
+            })
+            .collect();
 
-            let results =
-                timeout(Duration::from_secs(60), futures::future::join_all(futures)).await;
+        let results = timeout(Duration::from_secs(60), futures::future::join_all(futures)).await;
 
-            let results = match results {
-                Ok(results) => results,
-                Err(_) => {
-                    tracing::warn!("Overall provider fetch timeout (60s exceeded)");
-                    return Err(DomainError::ProviderError(
-                        "Overall provider fetch timeout (60s exceeded)".into(),
-                    ));
-                }
-            };
+        let results = match results {
+            Ok(results) => results,
+            Err(_) => {
+                tracing::warn!("Overall provider fetch timeout (60s exceeded)");
+                return Err(DomainError::ProviderError(
+                    "Overall provider fetch timeout (60s exceeded)".into(),
+                ));
+            }
+        };
 
-            let mut all_pipelines = Vec::new();
-            for result in results {
-                match result {
-                    Ok((provider_id, pipelines)) => {
-                        self.repository
-                            .update_pipelines_cache(provider_id, &pipelines)
-                            .await?;
+        let mut all_pipelines = Vec::new();
+        for provider_id in skip_providers {
+            if let Ok(cached) = self
+                .repository
+                .get_cached_pipelines(Some(*provider_id))
+                .await
+            {
+                all_pipelines.extend(cached);
+            }
+        }
 
+        for result in results {
+            match result {
+                Ok((provider_id, Some(pipelines))) => {
+                    let changed = self
+                        .repository
+                        .update_pipelines_cache(provider_id, &pipelines)
+                        .await?;
+
+                    if changed {
                         self.event_bus
                             .emit(CoreEvent::PipelineCacheInvalidated {
                                 provider_id: Some(provider_id),
                                 reason: CacheInvalidationReason::Fetch,
                             })
                             .await;
+                    }
 
-                        all_pipelines.extend(pipelines);
+                    all_pipelines.extend(pipelines);
+                }
+                Ok((provider_id, None)) => {
+                    if let Ok(cached) = self
+                        .repository
+                        .get_cached_pipelines(Some(provider_id))
+                        .await
+                    {
+                        all_pipelines.extend(cached);
                     }
-                    Err(_e) => {}
                 }
+                Err(_e) => {}
             }
+        }
 
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as i64;
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
 
-            self.event_bus
-                .emit(CoreEvent::PipelinesUpdated {
-                    pipelines: all_pipelines.clone(),
-                    provider_id: None,
-                    timestamp,
-                })
-                .await;
+        self.event_bus
+            .emit(CoreEvent::PipelinesUpdated {
+                pipelines: self.annotate_maintenance(all_pipelines.clone()).await,
+                provider_id: None,
+                timestamp,
+            })
+            .await;
 
-            Ok(all_pipelines)
-        }
+        Ok(all_pipelines)
     }
 
     pub async fn get_cached_pipelines(
@@ -260,6 +451,41 @@ impl PipelineService {
         self.repository.get_cached_pipelines(provider_id).await
     }
 
+    pub async fn get_cached_pipelines_with_labels(
+        &self, provider_id: Option<i64>, labels: &[String],
+    ) -> DomainResult<Vec<Pipeline>> {
+        let pipelines = self.repository.get_cached_pipelines(provider_id).await?;
+        Ok(pipelines
+            .into_iter()
+            .filter(|pipeline| pipeline.has_labels(labels))
+            .collect())
+    }
+
+    pub async fn list_cached_pipelines(
+        &self, query: &PipelineListQuery,
+    ) -> DomainResult<pipedash_plugin_api::PaginatedResponse<Pipeline>> {
+        validate_pagination(query.page, query.page_size)?;
+        let mut page = self.repository.query_pipelines_page(query).await?;
+        page.items = self.annotate_maintenance(page.items).await;
+        Ok(page)
+    }
+
+    pub async fn set_pipeline_labels(
+        &self, pipeline_id: &str, labels: Vec<String>,
+    ) -> DomainResult<Vec<String>> {
+        let labels = normalize_labels(labels)?;
+
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        if !cached_pipelines.iter().any(|p| p.id == pipeline_id) {
+            return Err(DomainError::PipelineNotFound(pipeline_id.to_string()));
+        }
+
+        self.repository
+            .set_pipeline_labels(pipeline_id, &labels)
+            .await?;
+        Ok(labels)
+    }
+
     pub async fn fetch_pipelines_lazy(
         &self, provider_id: Option<i64>, page: usize, page_size: usize,
     ) -> DomainResult<pipedash_plugin_api::PaginatedResponse<Pipeline>> {
@@ -267,7 +493,12 @@ impl PipelineService {
 
         if let Some(pid) = provider_id {
             let provider = self.provider_service.get_provider(pid).await?;
-            let result = provider.fetch_pipelines_paginated(page, page_size).await?;
+            let mut result = provider.fetch_pipelines_paginated(page, page_size).await?;
+            // Counts stay the provider's; only the page's items are filtered.
+            self.provider_service
+                .pipeline_filter(pid)
+                .await?
+                .apply(&mut result.items);
 
             if page == 1 {
                 self.repository
@@ -320,7 +551,11 @@ impl PipelineService {
         let api_runs = run_deduplicator
             .deduplicate(request_id, || async move {
                 let provider = provider_service.get_provider(provider_id).await?;
-                provider.fetch_run_history(&pipeline_id_owned, limit).await
+                provider_service
+                    .guarded_fetch(provider_id, || {
+                        provider.fetch_run_history(&pipeline_id_owned, limit)
+                    })
+                    .await
             })
             .await?;
 
@@ -383,6 +618,111 @@ impl PipelineService {
         Ok(api_runs)
     }
 
+    // Streams cached run history one page at a time, pipeline by pipeline,
+    // so large exports never sit in memory as a whole. Only runs already in
+    // the run cache are exported; nothing is fetched from providers.
+    pub fn export_run_history(
+        &self, filter: ExportFilter, format: ExportFormat,
+    ) -> BoxStream<'static, DomainResult<String>> {
+        let repository = Arc::clone(&self.repository);
+        let header = futures::stream::iter(export_header::<PipelineRun>(format).map(Ok));
+
+        // (pipelines to export, index of the current one, last run number seen)
+        let initial: (Option<Vec<String>>, usize, i64) = (None, 0, 0);
+        let rows =
+            futures::stream::try_unfold(initial, move |(pipelines, mut index, mut after)| {
+                let repository = Arc::clone(&repository);
+                let filter = filter.clone();
+                async move {
+                    let pipelines = match pipelines {
+                        Some(pipelines) => pipelines,
+                        None if filter.pipeline_ids.is_empty() => {
+                            let mut ids = repository.list_run_history_pipeline_ids().await?;
+                            ids.sort();
+                            ids
+                        }
+                        None => filter.pipeline_ids.clone(),
+                    };
+
+                    while let Some(pipeline_id) = pipelines.get(index) {
+                        let page = repository
+                            .get_cached_runs_after(pipeline_id, after, EXPORT_PAGE_SIZE)
+                            .await?;
+                        match page.last() {
+                            Some(last) if page.len() == EXPORT_PAGE_SIZE => after = last.run_number,
+                            _ => {
+                                index += 1;
+                                after = 0;
+                            }
+                        }
+
+                        let chunk: String = page
+                            .iter()
+                            .filter(|run| filter.includes(&run.pipeline_id, run.started_at))
+                            .map(|run| run.encode(format))
+                            .collect();
+                        if !chunk.is_empty() {
+                            return Ok(Some((chunk, (Some(pipelines), index, after))));
+                        }
+                    }
+
+                    Ok(None)
+                }
+            });
+
+        header.chain(rows).boxed()
+    }
+
+    // Clears the stored metrics of one pipeline, or all of them, and
+    // recomputes them from the cached run history. Used after metric
+    // definitions change or the processing state went out of step with the
+    // stored metrics.
+    pub async fn rebuild_metrics(&self, pipeline_id: Option<&str>) -> DomainResult<MetricsRebuild> {
+        let metrics_service = self
+            .metrics_service
+            .as_ref()
+            .ok_or_else(|| DomainError::NotSupported("Metrics are disabled".to_string()))?;
+
+        let pipeline_ids = match pipeline_id {
+            Some(id) => vec![id.to_string()],
+            None => self.repository.list_run_history_pipeline_ids().await?,
+        };
+
+        metrics_service.flush_metrics(pipeline_id, true).await?;
+
+        let mut rebuild = MetricsRebuild::default();
+        for id in &pipeline_ids {
+            let provider = self.metrics_provider(id).await;
+            match metrics_service
+                .reprocess_cached_runs(id, provider.as_deref())
+                .await
+            {
+                Ok((runs, metrics)) => {
+                    rebuild.pipelines += 1;
+                    rebuild.runs += runs;
+                    rebuild.metrics += metrics;
+                }
+                Err(e) => {
+                    tracing::warn!(pipeline_id = %id, error = %e, "Failed to rebuild metrics");
+                }
+            }
+        }
+
+        tracing::info!(
+            pipelines = rebuild.pipelines,
+            runs = rebuild.runs,
+            metrics = rebuild.metrics,
+            "Rebuilt metrics from cached run history"
+        );
+        self.event_bus
+            .emit(CoreEvent::MetricsFlushed {
+                pipeline_id: pipeline_id.map(str::to_string),
+            })
+            .await;
+
+        Ok(rebuild)
+    }
+
     pub async fn fetch_run_history_paginated(
         &self, pipeline_id: &str, page: usize, page_size: usize,
     ) -> DomainResult<PaginatedRunHistory> {
@@ -406,6 +746,7 @@ impl PipelineService {
                     let metrics_service_clone = metrics_service.clone();
                     let event_bus = self.event_bus.clone();
                     let repository = self.repository.clone();
+                    let provider = self.metrics_provider(pipeline_id).await;
 
                     tokio::spawn(async move {
                         match repository
@@ -414,7 +755,11 @@ impl PipelineService {
                         {
                             Ok(all_cached_runs) => {
                                 match metrics_service_clone
-                                    .extract_and_store_metrics(&pipeline_id_clone, &all_cached_runs)
+                                    .extract_and_store_metrics(
+                                        &pipeline_id_clone,
+                                        &all_cached_runs,
+                                        provider.as_deref(),
+                                    )
                                     .await
                                 {
                                     Ok(count) => {
@@ -498,10 +843,11 @@ impl PipelineService {
             let runs_clone = all_runs.clone();
             let metrics_service_clone = metrics_service.clone();
             let event_bus = self.event_bus.clone();
+            let provider = self.metrics_provider(pipeline_id).await;
 
             tokio::spawn(async move {
                 match metrics_service_clone
-                    .extract_and_store_metrics(&pipeline_id_clone, &runs_clone)
+                    .extract_and_store_metrics(&pipeline_id_clone, &runs_clone, provider.as_deref())
                     .await
                 {
                     Ok(count) => {
@@ -557,51 +903,1324 @@ impl PipelineService {
             .provider_service
             .get_provider(pipeline.provider_id)
             .await?;
-        provider.fetch_run_details(pipeline_id, run_number).await
+
+        let request_id = hash_request(
+            pipeline.provider_id,
+            &format!("fetch_run_details_{}_{}", pipeline_id, run_number),
+        );
+        let details_provider = Arc::clone(&provider);
+        let details_pipeline_id = pipeline_id.to_string();
+        let provider_service = Arc::clone(&self.provider_service);
+        let provider_id = pipeline.provider_id;
+        let mut run = self
+            .run_details_deduplicator
+            .deduplicate(request_id, || async move {
+                provider_service
+                    .guarded_fetch(provider_id, || {
+                        details_provider.fetch_run_details(&details_pipeline_id, run_number)
+                    })
+                    .await
+            })
+            .await?;
+
+        if run.annotations.is_empty() {
+            run.annotations = self
+                .run_annotations(provider.as_ref(), pipeline_id, &run)
+                .await;
+        }
+        Ok(run)
     }
 
-    pub async fn trigger_pipeline(&self, params: TriggerParams) -> DomainResult<String> {
+    // Latest run per branch. A cached board younger than
+    // `BRANCH_STATUS_CACHE_TTL` is returned as is unless `refresh` is set.
+    pub async fn fetch_branch_statuses(
+        &self, pipeline_id: &str, refresh: bool,
+    ) -> DomainResult<BranchBoard> {
         let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
         let pipeline = cached_pipelines
             .iter()
-            .find(|p| p.id == params.workflow_id)
-            .ok_or_else(|| DomainError::PipelineNotFound(params.workflow_id.clone()))?;
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
+        self.provider_service
+            .ensure_capability(&pipeline.provider_type, Capability::BranchStatuses)?;
+
+        if !refresh {
+            match self
+                .repository
+                .get_cached_branch_statuses(pipeline_id)
+                .await
+            {
+                Ok(Some((branches, cached_at)))
+                    if (chrono::Utc::now() - cached_at)
+                        .to_std()
+                        .is_ok_and(|age| age < BRANCH_STATUS_CACHE_TTL) =>
+                {
+                    return Ok(BranchBoard {
+                        pipeline_id: pipeline_id.to_string(),
+                        branches,
+                        fetched_at: cached_at,
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::debug!(error = %e, "Failed to read cached branch statuses");
+                }
+            }
+        }
 
         let provider = self
             .provider_service
             .get_provider(pipeline.provider_id)
             .await?;
-        let result = provider.trigger_pipeline(params.clone()).await?;
+        let branches = provider.list_branch_statuses(pipeline_id).await?;
 
-        self.event_bus
-            .emit(CoreEvent::RunTriggered {
-                workflow_id: params.workflow_id,
-            })
-            .await;
+        if let Err(e) = self
+            .repository
+            .cache_branch_statuses(pipeline_id, &branches)
+            .await
+        {
+            tracing::warn!(error = %e, pipeline_id = %pipeline_id, "Failed to cache branch statuses");
+        }
 
-        Ok(result)
+        Ok(BranchBoard {
+            pipeline_id: pipeline_id.to_string(),
+            branches,
+            fetched_at: chrono::Utc::now(),
+        })
     }
 
-    pub async fn cancel_run(&self, pipeline_id: &str, run_number: i64) -> DomainResult<()> {
+    // Annotations stop changing once a run has finished, so those are served
+    // from the cache. A failed lookup only drops the annotations.
+    async fn run_annotations(
+        &self, provider: &dyn Provider, pipeline_id: &str, run: &PipelineRun,
+    ) -> Vec<RunAnnotation> {
+        let finished = run.status.is_terminal();
+        if finished {
+            match self
+                .repository
+                .get_cached_run_annotations(pipeline_id, run.run_number)
+                .await
+            {
+                Ok(Some(annotations)) => return annotations,
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::debug!(error = %e, "Failed to read cached run annotations");
+                }
+            }
+        }
+
+        match provider.fetch_run_annotations(run).await {
+            Ok(annotations) => {
+                if finished {
+                    if let Err(e) = self
+                        .repository
+                        .cache_run_annotations(pipeline_id, run.run_number, &annotations)
+                        .await
+                    {
+                        tracing::debug!(error = %e, "Failed to cache run annotations");
+                    }
+                }
+                annotations
+            }
+            Err(e) => {
+                tracing::debug!(
+                    pipeline_id = %pipeline_id,
+                    run_number = run.run_number,
+                    error = %e,
+                    "Failed to fetch run annotations"
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    pub async fn fetch_run_logs(
+        &self, pipeline_id: &str, run_number: i64, query: LogQuery,
+    ) -> DomainResult<RunLogs> {
         let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
         let pipeline = cached_pipelines
             .iter()
             .find(|p| p.id == pipeline_id)
             .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
 
+        self.provider_service
+            .ensure_capability(&pipeline.provider_type, Capability::Logs)?;
+
         let provider = self
             .provider_service
             .get_provider(pipeline.provider_id)
             .await?;
-        provider.cancel_run(pipeline_id, run_number).await?;
+        provider
+            .fetch_run_logs(pipeline_id, run_number, query)
+            .await
+    }
 
-        self.event_bus
-            .emit(CoreEvent::RunCancelled {
-                pipeline_id: pipeline_id.to_string(),
-            })
-            .await;
+    pub async fn compare_runs(
+        &self, pipeline_id: &str, run_a: i64, run_b: i64,
+    ) -> DomainResult<RunComparison> {
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        let pipeline = cached_pipelines
+            .iter()
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
 
-        Ok(())
+        let provider = self
+            .provider_service
+            .get_provider(pipeline.provider_id)
+            .await?;
+        let (base, head) = tokio::try_join!(
+            provider.fetch_run_details(pipeline_id, run_a),
+            provider.fetch_run_details(pipeline_id, run_b)
+        )?;
+
+        let mut comparison = RunComparison::new(base, head);
+
+        if let (Some(base_sha), Some(head_sha)) = (
+            comparison.base.commit_sha.clone(),
+            comparison.head.commit_sha.clone(),
+        ) {
+            if base_sha == head_sha {
+                comparison.commits_available = true;
+            } else {
+                // Commit history is best effort; the rest of the diff is still
+                // useful without it.
+                match provider
+                    .fetch_commits_between(pipeline_id, &base_sha, &head_sha)
+                    .await
+                {
+                    Ok(commits) => {
+                        comparison.commits = commits;
+                        comparison.commits_available = true;
+                    }
+                    Err(DomainError::NotSupported(_)) => {}
+                    Err(e) => {
+                        tracing::warn!(
+                            pipeline_id = pipeline_id,
+                            error = %e,
+                            "Failed to fetch commits between runs"
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(comparison)
+    }
+
+    // Commits, pull requests, contributors and linked issues that shipped
+    // between two runs of a deployment pipeline. Like `compare_runs`, the
+    // commit list is best effort.
+    pub async fn generate_release_report(
+        &self, pipeline_id: &str, from_run: i64, to_run: i64,
+    ) -> DomainResult<ReleaseReport> {
+        if from_run >= to_run {
+            return Err(DomainError::InvalidConfig(format!(
+                "from_run ({}) must be older than to_run ({})",
+                from_run, to_run
+            )));
+        }
+
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        let pipeline = cached_pipelines
+            .iter()
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
+
+        let provider = self
+            .provider_service
+            .get_provider(pipeline.provider_id)
+            .await?;
+        let (from, to) = tokio::try_join!(
+            provider.fetch_run_details(pipeline_id, from_run),
+            provider.fetch_run_details(pipeline_id, to_run)
+        )?;
+
+        let commits = match (from.commit_sha.as_deref(), to.commit_sha.as_deref()) {
+            (Some(base), Some(head)) if base == head => Some(Vec::new()),
+            (Some(base), Some(head)) => {
+                match provider
+                    .fetch_commits_between(pipeline_id, base, head)
+                    .await
+                {
+                    Ok(commits) => Some(commits),
+                    Err(DomainError::NotSupported(_)) => None,
+                    Err(e) => {
+                        tracing::warn!(
+                            pipeline_id = pipeline_id,
+                            error = %e,
+                            "Failed to fetch commits for release report"
+                        );
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        Ok(ReleaseReport::new(pipeline, from, to, commits))
+    }
+
+    pub async fn list_run_artifacts(
+        &self, pipeline_id: &str, run_number: i64,
+    ) -> DomainResult<Vec<BuildArtifact>> {
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        let pipeline = cached_pipelines
+            .iter()
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
+        // Runs of plugins without artifacts simply have none.
+        if !self
+            .provider_service
+            .supports(&pipeline.provider_type, Capability::Artifacts)
+        {
+            return Ok(Vec::new());
+        }
+
+        let provider = self
+            .provider_service
+            .get_provider(pipeline.provider_id)
+            .await?;
+        self.gated(pipeline.provider_id, GatedOperation::Artifacts, async {
+            let run = provider.fetch_run_details(pipeline_id, run_number).await?;
+            provider.fetch_artifacts(&run.id).await
+        })
+        .await
+    }
+
+    pub async fn resolve_artifact_download(
+        &self, pipeline_id: &str, run_number: i64, artifact_id: &str,
+    ) -> DomainResult<(BuildArtifact, ArtifactDownload)> {
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        let pipeline = cached_pipelines
+            .iter()
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
+        self.provider_service
+            .ensure_capability(&pipeline.provider_type, Capability::Artifacts)?;
+
+        let provider = self
+            .provider_service
+            .get_provider(pipeline.provider_id)
+            .await?;
+        self.gated(pipeline.provider_id, GatedOperation::Artifacts, async {
+            let run = provider.fetch_run_details(pipeline_id, run_number).await?;
+            let artifact = provider
+                .fetch_artifacts(&run.id)
+                .await?
+                .into_iter()
+                .find(|a| a.id == artifact_id)
+                .ok_or_else(|| DomainError::NotFound(format!("Artifact {}", artifact_id)))?;
+
+            let download = provider.artifact_download(&artifact).await?;
+            Ok((artifact, download))
+        })
+        .await
+    }
+
+    // On pipelines that need approval this holds the trigger instead and
+    // returns `TriggerApproval::pending_response`.
+    pub async fn trigger_pipeline(&self, params: TriggerParams) -> DomainResult<String> {
+        self.provider_service
+            .access_control()
+            .ensure(Action::TriggerPipeline)?;
+
+        let pipeline = self.trigger_target(&params.workflow_id).await?;
+        if self.requires_approval(&pipeline.id).await? {
+            return self.request_approval(&pipeline, params).await;
+        }
+        self.trigger_now(&pipeline, params).await
+    }
+
+    async fn trigger_target(&self, pipeline_id: &str) -> DomainResult<Pipeline> {
+        let pipeline = self
+            .repository
+            .get_cached_pipelines(None)
+            .await?
+            .into_iter()
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
+        self.provider_service
+            .ensure_capability(&pipeline.provider_type, Capability::Trigger)?;
+        Ok(pipeline)
+    }
+
+    async fn trigger_now(
+        &self, pipeline: &Pipeline, params: TriggerParams,
+    ) -> DomainResult<String> {
+        let provider = self
+            .provider_service
+            .get_provider(pipeline.provider_id)
+            .await?;
+        let result = self
+            .gated(
+                pipeline.provider_id,
+                GatedOperation::Trigger,
+                provider.trigger_pipeline(params.clone()),
+            )
+            .await?;
+        self.record_pipeline_usage(&params.workflow_id).await;
+
+        self.event_bus
+            .emit(CoreEvent::RunTriggered {
+                workflow_id: params.workflow_id,
+            })
+            .await;
+
+        Ok(result)
+    }
+
+    async fn requires_approval(&self, pipeline_id: &str) -> DomainResult<bool> {
+        Ok(self
+            .repository
+            .list_approval_rules()
+            .await?
+            .iter()
+            .any(|id| id == pipeline_id))
+    }
+
+    async fn request_approval(
+        &self, pipeline: &Pipeline, params: TriggerParams,
+    ) -> DomainResult<String> {
+        let actor = self.provider_service.access_control().current_actor();
+        let mut approval = TriggerApproval::new(&pipeline.name, params, &actor);
+        approval.id = self.repository.insert_trigger_approval(&approval).await?;
+
+        self.audit_log
+            .record(AuditEntry::new(
+                AuditAction::TriggerRequested,
+                &actor,
+                &pipeline.id,
+                Some(format!("Request {}", approval.id)),
+            ))
+            .await;
+        let response = approval.pending_response();
+        self.event_bus
+            .emit(CoreEvent::TriggerApprovalsChanged { approval })
+            .await;
+
+        Ok(response)
+    }
+
+    pub async fn list_trigger_approvals(
+        &self, pending_only: bool, limit: Option<usize>,
+    ) -> DomainResult<Vec<TriggerApproval>> {
+        let limit = limit
+            .unwrap_or(DEFAULT_APPROVAL_LIST_LIMIT)
+            .clamp(1, MAX_APPROVAL_LIST_LIMIT);
+        self.repository
+            .list_trigger_approvals(pending_only, limit)
+            .await
+    }
+
+    pub async fn list_approval_rules(&self) -> DomainResult<Vec<String>> {
+        self.repository.list_approval_rules().await
+    }
+
+    pub async fn set_approval_required(
+        &self, pipeline_id: &str, required: bool,
+    ) -> DomainResult<()> {
+        let access_control = self.provider_service.access_control();
+        access_control.ensure(Action::ApproveTrigger)?;
+        validate_pipeline_id(pipeline_id)?;
+
+        self.repository
+            .set_approval_rule(pipeline_id, required)
+            .await?;
+        self.audit_log
+            .record(AuditEntry::new(
+                AuditAction::ApprovalRuleChanged,
+                &access_control.current_actor(),
+                pipeline_id,
+                Some(if required { "required" } else { "not required" }.to_string()),
+            ))
+            .await;
+        Ok(())
+    }
+
+    // Approving calls the provider with the parameters captured in the
+    // request. Requesters may always withdraw (reject) their own request;
+    // anything else needs permission to approve.
+    pub async fn decide_trigger_approval(
+        &self, id: i64, approve: bool, confirmed: bool, comment: Option<String>,
+    ) -> DomainResult<TriggerApproval> {
+        let access_control = self.provider_service.access_control();
+        let actor = access_control.current_actor();
+        let mut approval = self.repository.get_trigger_approval(id).await?;
+        if approve || actor != approval.requested_by {
+            access_control.ensure(Action::ApproveTrigger)?;
+        }
+        approval.ensure_decidable(
+            &actor,
+            approve,
+            confirmed,
+            access_control.allows_self_approval(),
+        )?;
+
+        let status = if approve {
+            ApprovalStatus::Approved
+        } else {
+            ApprovalStatus::Rejected
+        };
+        approval.decide(status, &actor, comment);
+        if !self
+            .repository
+            .update_trigger_approval(&approval, ApprovalStatus::Pending)
+            .await?
+        {
+            return Err(DomainError::InvalidConfig(format!(
+                "Trigger request {} was already decided",
+                id
+            )));
+        }
+        self.audit_log
+            .record(AuditEntry::new(
+                if approve {
+                    AuditAction::TriggerApproved
+                } else {
+                    AuditAction::TriggerRejected
+                },
+                &actor,
+                &approval.pipeline_id,
+                Some(match &approval.comment {
+                    Some(comment) => format!("Request {}: {}", id, comment),
+                    None => format!("Request {}", id),
+                }),
+            ))
+            .await;
+
+        if approve {
+            let result = match self.trigger_target(&approval.pipeline_id).await {
+                Ok(pipeline) => self.trigger_now(&pipeline, approval.params.clone()).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(result) => approval.result = Some(result),
+                Err(e) => {
+                    tracing::warn!(approval_id = id, error = %e, "Approved trigger failed");
+                    approval.status = ApprovalStatus::Failed;
+                    approval.comment = Some(e.to_string());
+                }
+            }
+            self.repository
+                .update_trigger_approval(&approval, ApprovalStatus::Approved)
+                .await?;
+        }
+
+        self.event_bus
+            .emit(CoreEvent::TriggerApprovalsChanged {
+                approval: approval.clone(),
+            })
+            .await;
+        Ok(approval)
+    }
+
+    pub async fn cancel_run(&self, pipeline_id: &str, run_number: i64) -> DomainResult<()> {
+        self.provider_service
+            .access_control()
+            .ensure(Action::CancelRun)?;
+
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        let pipeline = cached_pipelines
+            .iter()
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
+        self.provider_service
+            .ensure_capability(&pipeline.provider_type, Capability::Cancel)?;
+
+        let provider = self
+            .provider_service
+            .get_provider(pipeline.provider_id)
+            .await?;
+        self.gated(
+            pipeline.provider_id,
+            GatedOperation::Cancel,
+            provider.cancel_run(pipeline_id, run_number),
+        )
+        .await?;
+
+        self.event_bus
+            .emit(CoreEvent::RunCancelled {
+                pipeline_id: pipeline_id.to_string(),
+            })
+            .await;
+
+        Ok(())
+    }
+
+    pub async fn retry_run(
+        &self, pipeline_id: &str, run_number: i64, failed_only: bool,
+    ) -> DomainResult<String> {
+        self.provider_service
+            .access_control()
+            .ensure(Action::TriggerPipeline)?;
+
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        let pipeline = cached_pipelines
+            .iter()
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
+        // A retry can't be held for approval, so these go through a trigger.
+        if self.requires_approval(pipeline_id).await? {
+            return Err(DomainError::PermissionDenied(format!(
+                "{} needs approval; trigger it to request a run",
+                pipeline.name
+            )));
+        }
+        self.provider_service.ensure_capability(
+            &pipeline.provider_type,
+            if failed_only {
+                Capability::RetryFailedJobs
+            } else {
+                Capability::Trigger
+            },
+        )?;
+
+        let provider = self
+            .provider_service
+            .get_provider(pipeline.provider_id)
+            .await?;
+        let result = self
+            .gated(
+                pipeline.provider_id,
+                GatedOperation::Trigger,
+                provider.retry_run(pipeline_id, run_number, failed_only),
+            )
+            .await?;
+
+        self.event_bus
+            .emit(CoreEvent::RunTriggered {
+                workflow_id: pipeline_id.to_string(),
+            })
+            .await;
+
+        Ok(result)
+    }
+
+    pub async fn list_pending_approvals(
+        &self, pipeline_id: &str, run_number: i64,
+    ) -> DomainResult<Vec<PendingApproval>> {
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        let pipeline = cached_pipelines
+            .iter()
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
+        self.provider_service
+            .ensure_capability(&pipeline.provider_type, Capability::UnblockJobs)?;
+
+        let provider = self
+            .provider_service
+            .get_provider(pipeline.provider_id)
+            .await?;
+        self.gated(
+            pipeline.provider_id,
+            GatedOperation::View,
+            provider.fetch_pending_approvals(pipeline_id, run_number),
+        )
+        .await
+    }
+
+    // Releasing a block step continues a run that was already started, so it
+    // needs trigger access but isn't held by the pipeline's approval rule.
+    pub async fn unblock_job(
+        &self, pipeline_id: &str, run_number: i64, job_id: &str, fields: HashMap<String, String>,
+    ) -> DomainResult<()> {
+        let access_control = self.provider_service.access_control();
+        access_control.ensure(Action::TriggerPipeline)?;
+
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        let pipeline = cached_pipelines
+            .iter()
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
+        self.provider_service
+            .ensure_capability(&pipeline.provider_type, Capability::UnblockJobs)?;
+
+        let provider = self
+            .provider_service
+            .get_provider(pipeline.provider_id)
+            .await?;
+        self.gated(
+            pipeline.provider_id,
+            GatedOperation::Trigger,
+            provider.unblock_job(pipeline_id, run_number, job_id, fields),
+        )
+        .await?;
+
+        self.audit_log
+            .record(AuditEntry::new(
+                AuditAction::JobUnblocked,
+                &access_control.current_actor(),
+                pipeline_id,
+                Some(format!("Run #{} job {}", run_number, job_id)),
+            ))
+            .await;
+        self.event_bus
+            .emit(CoreEvent::RunTriggered {
+                workflow_id: pipeline_id.to_string(),
+            })
+            .await;
+
+        Ok(())
+    }
+
+    pub async fn trigger_pipelines_bulk(
+        &self, items: Vec<TriggerParams>,
+    ) -> DomainResult<Vec<BulkItemResult>> {
+        self.provider_service
+            .access_control()
+            .ensure(Action::TriggerPipeline)?;
+
+        self.run_bulk(BulkOperation::Trigger, items, |params| async move {
+            let pipeline_id = params.workflow_id.clone();
+            let result = self.trigger_pipeline(params).await.map(Some);
+            BulkItemResult::from_result(pipeline_id, None, result)
+        })
+        .await
+    }
+
+    pub async fn cancel_runs_bulk(&self, runs: Vec<RunRef>) -> DomainResult<Vec<BulkItemResult>> {
+        self.provider_service
+            .access_control()
+            .ensure(Action::CancelRun)?;
+
+        self.run_bulk(BulkOperation::Cancel, runs, |run| async move {
+            let result = self
+                .cancel_run(&run.pipeline_id, run.run_number)
+                .await
+                .map(|_| None);
+            BulkItemResult::from_result(run.pipeline_id, Some(run.run_number), result)
+        })
+        .await
+    }
+
+    pub async fn retry_runs_bulk(
+        &self, runs: Vec<RunRef>, failed_only: bool,
+    ) -> DomainResult<Vec<BulkItemResult>> {
+        self.provider_service
+            .access_control()
+            .ensure(Action::TriggerPipeline)?;
+
+        self.run_bulk(BulkOperation::Retry, runs, |run| async move {
+            let result = self
+                .retry_run(&run.pipeline_id, run.run_number, failed_only)
+                .await
+                .map(Some);
+            BulkItemResult::from_result(run.pipeline_id, Some(run.run_number), result)
+        })
+        .await
+    }
+
+    // Runs a provider call once the stored permissions allow the operation. A
+    // 403 means those permissions are stale, so they are refreshed, and the
+    // missing permission is reported when the fresh status names it.
+    async fn gated<T>(
+        &self, provider_id: i64, operation: GatedOperation,
+        call: impl std::future::Future<Output = DomainResult<T>>,
+    ) -> DomainResult<T> {
+        self.provider_service
+            .ensure_feature(provider_id, operation)
+            .await?;
+
+        let error = match call.await {
+            Err(error @ DomainError::PermissionDenied(_)) => error,
+            result => return result,
+        };
+        if let Err(e) = self
+            .provider_service
+            .recheck_provider_permissions(provider_id)
+            .await
+        {
+            tracing::warn!(error = %e, provider_id, "Failed to refresh provider permissions");
+            return Err(error);
+        }
+        match self
+            .provider_service
+            .ensure_feature(provider_id, operation)
+            .await
+        {
+            Err(unavailable @ DomainError::FeatureUnavailable { .. }) => Err(unavailable),
+            _ => Err(error),
+        }
+    }
+
+    // Runs at most MAX_CONCURRENT_BULK_ACTIONS at a time and emits a progress
+    // event per finished item. Results keep the order of the input.
+    async fn run_bulk<T, F, Fut>(
+        &self, operation: BulkOperation, items: Vec<T>, action: F,
+    ) -> DomainResult<Vec<BulkItemResult>>
+    where
+        F: Fn(T) -> Fut,
+        Fut: std::future::Future<Output = BulkItemResult>,
+    {
+        if items.len() > MAX_BULK_ITEMS {
+            return Err(DomainError::InvalidConfig(format!(
+                "Bulk operations are limited to {} items",
+                MAX_BULK_ITEMS
+            )));
+        }
+
+        let total = items.len();
+        let mut results: Vec<Option<BulkItemResult>> = vec![None; total];
+        let mut completed = 0;
+
+        let mut pending = futures::stream::iter(items.into_iter().enumerate())
+            .map(|(index, item)| {
+                let fut = action(item);
+                async move { (index, fut.await) }
+            })
+            .buffer_unordered(MAX_CONCURRENT_BULK_ACTIONS);
+
+        while let Some((index, item)) = pending.next().await {
+            completed += 1;
+            if item.success {
+                self.invalidate_run_cache(&item.pipeline_id).await;
+            }
+
+            self.event_bus
+                .emit(CoreEvent::BulkProgress {
+                    operation,
+                    completed,
+                    total,
+                    item: item.clone(),
+                })
+                .await;
+            results[index] = Some(item);
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    pub async fn apply_webhook_event(&self, event: &WebhookEvent) -> DomainResult<()> {
+        if let Some(pipeline_id) = &event.pipeline_id {
+            self.invalidate_run_cache(pipeline_id).await;
+        }
+        self.fetch_pipelines(Some(event.provider_id)).await?;
+        Ok(())
+    }
+
+    pub async fn list_groups(&self) -> DomainResult<Vec<PipelineGroupStatus>> {
+        let groups = self.repository.list_pipeline_groups().await?;
+        let pipelines: HashMap<String, Pipeline> = self
+            .repository
+            .get_cached_pipelines(None)
+            .await?
+            .into_iter()
+            .map(|p| (p.id.clone(), p))
+            .collect();
+
+        Ok(groups
+            .into_iter()
+            .map(|group| {
+                let members: Vec<&Pipeline> = group
+                    .pipeline_ids
+                    .iter()
+                    .filter_map(|id| pipelines.get(id))
+                    .collect();
+                PipelineGroupStatus::from_members(group, &members)
+            })
+            .collect())
+    }
+
+    pub async fn create_group(&self, name: &str) -> DomainResult<PipelineGroup> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(DomainError::InvalidConfig(
+                "Group name cannot be empty".to_string(),
+            ));
+        }
+
+        let groups = self.repository.list_pipeline_groups().await?;
+        if groups.iter().any(|g| g.name.eq_ignore_ascii_case(name)) {
+            return Err(DomainError::InvalidConfig(format!(
+                "A group named '{}' already exists",
+                name
+            )));
+        }
+
+        let id = self.repository.create_pipeline_group(name).await?;
+        self.get_group(id).await
+    }
+
+    pub async fn delete_group(&self, group_id: i64) -> DomainResult<()> {
+        self.repository.delete_pipeline_group(group_id).await
+    }
+
+    pub async fn assign_pipeline_to_group(
+        &self, group_id: i64, pipeline_id: &str,
+    ) -> DomainResult<PipelineGroup> {
+        self.get_group(group_id).await?;
+
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        if !cached_pipelines.iter().any(|p| p.id == pipeline_id) {
+            return Err(DomainError::PipelineNotFound(pipeline_id.to_string()));
+        }
+
+        self.repository
+            .add_pipeline_to_group(group_id, pipeline_id)
+            .await?;
+        self.get_group(group_id).await
+    }
+
+    pub async fn remove_pipeline_from_group(
+        &self, group_id: i64, pipeline_id: &str,
+    ) -> DomainResult<PipelineGroup> {
+        self.repository
+            .remove_pipeline_from_group(group_id, pipeline_id)
+            .await?;
+        self.get_group(group_id).await
+    }
+
+    async fn get_group(&self, group_id: i64) -> DomainResult<PipelineGroup> {
+        self.repository
+            .list_pipeline_groups()
+            .await?
+            .into_iter()
+            .find(|g| g.id == group_id)
+            .ok_or_else(|| DomainError::NotFound(format!("Group {} not found", group_id)))
+    }
+
+    pub async fn list_views(&self) -> DomainResult<Vec<SavedView>> {
+        self.repository.list_saved_views().await
+    }
+
+    pub async fn save_view(&self, name: &str, query: ViewQuery) -> DomainResult<SavedView> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(DomainError::InvalidConfig(
+                "View name cannot be empty".to_string(),
+            ));
+        }
+        query.validate()?;
+
+        let query_json = serde_json::to_string(&query)
+            .map_err(|e| DomainError::InternalError(format!("Failed to serialize view: {}", e)))?;
+        let id = self.repository.save_view(name, &query_json).await?;
+        self.get_view(id).await
+    }
+
+    pub async fn delete_view(&self, view_id: i64) -> DomainResult<()> {
+        self.repository.delete_saved_view(view_id).await
+    }
+
+    pub async fn query_view(&self, view_id: i64) -> DomainResult<Vec<Pipeline>> {
+        let view = self.get_view(view_id).await?;
+        let pipelines = self.repository.get_cached_pipelines(None).await?;
+        view.query.filter(pipelines)
+    }
+
+    async fn get_view(&self, view_id: i64) -> DomainResult<SavedView> {
+        self.repository
+            .list_saved_views()
+            .await?
+            .into_iter()
+            .find(|v| v.id == view_id)
+            .ok_or_else(|| DomainError::NotFound(format!("View {} not found", view_id)))
+    }
+
+    pub async fn list_refresh_settings(&self) -> DomainResult<Vec<PipelineRefreshSetting>> {
+        self.repository.list_pipeline_refresh_settings().await
+    }
+
+    // Falls back to the default schedule so a broken settings table never
+    // stops the refresh loop.
+    pub async fn refresh_schedule(&self) -> RefreshSchedule {
+        match self.repository.list_pipeline_refresh_settings().await {
+            Ok(settings) => RefreshSchedule::new(settings),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load pipeline refresh settings");
+                RefreshSchedule::default()
+            }
+        }
+    }
+
+    pub async fn list_maintenance_windows(&self) -> DomainResult<Vec<MaintenanceWindow>> {
+        self.repository.list_maintenance_windows().await
+    }
+
+    // Saving under an existing name replaces that window.
+    pub async fn save_maintenance_window(
+        &self, mut window: MaintenanceWindow,
+    ) -> DomainResult<MaintenanceWindow> {
+        let access_control = self.provider_service.access_control();
+        access_control.ensure(Action::ManageMaintenance)?;
+        window.name = window.name.trim().to_string();
+        window.validate()?;
+        for pipeline_id in &window.pipeline_ids {
+            validate_pipeline_id(pipeline_id)?;
+        }
+
+        let window_json = serde_json::to_string(&window).map_err(|e| {
+            DomainError::InternalError(format!("Failed to serialize maintenance window: {}", e))
+        })?;
+        let id = self
+            .repository
+            .save_maintenance_window(&window.name, &window_json)
+            .await?;
+        self.audit_log
+            .record(AuditEntry::new(
+                AuditAction::MaintenanceWindowChanged,
+                &access_control.current_actor(),
+                &window.name,
+                Some("saved".to_string()),
+            ))
+            .await;
+
+        self.repository
+            .list_maintenance_windows()
+            .await?
+            .into_iter()
+            .find(|w| w.id == id)
+            .ok_or_else(|| DomainError::NotFound(format!("Maintenance window {} not found", id)))
+    }
+
+    pub async fn delete_maintenance_window(&self, id: i64) -> DomainResult<()> {
+        let access_control = self.provider_service.access_control();
+        access_control.ensure(Action::ManageMaintenance)?;
+
+        let name = self
+            .repository
+            .list_maintenance_windows()
+            .await?
+            .into_iter()
+            .find(|w| w.id == id)
+            .map(|w| w.name)
+            .ok_or_else(|| DomainError::NotFound(format!("Maintenance window {} not found", id)))?;
+        self.repository.delete_maintenance_window(id).await?;
+        self.audit_log
+            .record(AuditEntry::new(
+                AuditAction::MaintenanceWindowChanged,
+                &access_control.current_actor(),
+                &name,
+                Some("deleted".to_string()),
+            ))
+            .await;
+        Ok(())
+    }
+
+    // Like the refresh schedule, an unreadable table means no maintenance
+    // rather than a stalled refresh loop.
+    pub async fn maintenance_calendar(&self) -> MaintenanceCalendar {
+        let windows = match self.repository.list_maintenance_windows().await {
+            Ok(windows) => windows,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load maintenance windows");
+                return MaintenanceCalendar::default();
+            }
+        };
+        if windows.is_empty() {
+            return MaintenanceCalendar::default();
+        }
+
+        let groups = self
+            .repository
+            .list_pipeline_groups()
+            .await
+            .unwrap_or_default();
+        MaintenanceCalendar::new(windows, groups)
+    }
+
+    // Adds the active window to the metadata of pipelines in maintenance.
+    pub async fn annotate_maintenance(&self, mut pipelines: Vec<Pipeline>) -> Vec<Pipeline> {
+        self.maintenance_calendar()
+            .await
+            .annotate(&mut pipelines, chrono::Utc::now());
+        pipelines
+    }
+
+    // Clearing both the interval and the snooze drops the override.
+    pub async fn set_pipeline_refresh(
+        &self, pipeline_id: &str, refresh_interval_secs: Option<u64>, snoozed: bool,
+    ) -> DomainResult<Option<PipelineRefreshSetting>> {
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        if !cached_pipelines.iter().any(|p| p.id == pipeline_id) {
+            return Err(DomainError::PipelineNotFound(pipeline_id.to_string()));
+        }
+
+        if refresh_interval_secs.is_none() && !snoozed {
+            self.repository
+                .delete_pipeline_refresh_setting(pipeline_id)
+                .await?;
+            return Ok(None);
+        }
+
+        let setting = PipelineRefreshSetting {
+            pipeline_id: pipeline_id.to_string(),
+            refresh_interval_secs,
+            snoozed,
+            updated_at: chrono::Utc::now(),
+        };
+        setting.validate()?;
+
+        self.repository
+            .save_pipeline_refresh_setting(
+                pipeline_id,
+                refresh_interval_secs.map(|secs| secs as i64),
+                snoozed,
+            )
+            .await?;
+        Ok(Some(setting))
+    }
+
+    pub async fn list_pinned_pipelines(&self) -> DomainResult<Vec<PinnedPipeline>> {
+        let pins = self.repository.list_pinned_pipelines().await?;
+        Ok(pins.into_iter().filter(|pin| !pin.unpinned).collect())
+    }
+
+    // New pins go to the end of the list; pinning twice is a no-op.
+    pub async fn pin_pipeline(&self, pipeline_id: &str) -> DomainResult<PinnedPipeline> {
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        if !cached_pipelines.iter().any(|p| p.id == pipeline_id) {
+            return Err(DomainError::PipelineNotFound(pipeline_id.to_string()));
+        }
+
+        let pins = self.repository.list_pinned_pipelines().await?;
+        if let Some(pin) = pins
+            .iter()
+            .find(|pin| pin.pipeline_id == pipeline_id && !pin.unpinned)
+        {
+            return Ok(pin.clone());
+        }
+
+        let pin = PinnedPipeline {
+            pipeline_id: pipeline_id.to_string(),
+            position: pins.iter().map(|pin| pin.position + 1).max().unwrap_or(0),
+            unpinned: false,
+            updated_at: chrono::Utc::now(),
+        };
+        self.repository.save_pinned_pipeline(&pin).await?;
+        Ok(pin)
+    }
+
+    pub async fn unpin_pipeline(&self, pipeline_id: &str) -> DomainResult<()> {
+        let pins = self.repository.list_pinned_pipelines().await?;
+        let Some(pin) = pins
+            .into_iter()
+            .find(|pin| pin.pipeline_id == pipeline_id && !pin.unpinned)
+        else {
+            return Ok(());
+        };
+
+        self.repository
+            .save_pinned_pipeline(&PinnedPipeline {
+                unpinned: true,
+                updated_at: chrono::Utc::now(),
+                ..pin
+            })
+            .await
+    }
+
+    // Summarizes the given pipelines, or the pinned ones when none are given.
+    pub async fn status_summary(
+        &self, pipeline_ids: Option<Vec<String>>,
+    ) -> DomainResult<StatusSummary> {
+        let pipeline_ids = match pipeline_ids {
+            Some(ids) => ids,
+            None => self
+                .list_pinned_pipelines()
+                .await?
+                .into_iter()
+                .map(|pin| pin.pipeline_id)
+                .collect(),
+        };
+        let cached = self.repository.get_cached_pipelines(None).await?;
+        let cached = self.annotate_maintenance(cached).await;
+        Ok(StatusSummary::new(&pipeline_ids, &cached))
+    }
+
+    // Pulls pins changed on other devices through the sync remote and
+    // pushes ours; returns the resulting pinned set.
+    pub async fn sync_pinned_pipelines(
+        &self, sync_manager: &SyncManager,
+    ) -> DomainResult<Vec<PinnedPipeline>> {
+        let local = self.repository.list_pinned_pipelines().await?;
+        let merged = sync_manager.sync_pinned_pipelines(local.clone()).await?;
+
+        for pin in merged.iter().filter(|pin| !local.contains(pin)) {
+            self.repository.save_pinned_pipeline(pin).await?;
+        }
+
+        Ok(merged.into_iter().filter(|pin| !pin.unpinned).collect())
+    }
+
+    pub async fn list_trigger_presets(
+        &self, pipeline_id: &str,
+    ) -> DomainResult<Vec<TriggerPreset>> {
+        self.repository.list_trigger_presets(pipeline_id).await
+    }
+
+    pub async fn save_trigger_preset(
+        &self, pipeline_id: &str, name: &str, branch: Option<String>,
+        parameters: HashMap<String, serde_json::Value>,
+    ) -> DomainResult<TriggerPreset> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(DomainError::InvalidConfig(
+                "Preset name cannot be empty".to_string(),
+            ));
+        }
+        let branch = branch
+            .map(|b| b.trim().to_string())
+            .filter(|b| !b.is_empty());
+
+        let schema = self.workflow_schema(pipeline_id).await?;
+        validate_preset_parameters(&schema, &parameters)?;
+        if branch.is_some() {
+            branch_parameter(&schema)?;
+        }
+
+        let parameters_json = serde_json::to_string(&parameters).map_err(|e| {
+            DomainError::InternalError(format!("Failed to serialize preset: {}", e))
+        })?;
+        let id = self
+            .repository
+            .save_trigger_preset(pipeline_id, name, branch.as_deref(), &parameters_json)
+            .await?;
+        self.get_trigger_preset(pipeline_id, id).await
+    }
+
+    pub async fn delete_trigger_preset(&self, preset_id: i64) -> DomainResult<()> {
+        self.repository.delete_trigger_preset(preset_id).await
+    }
+
+    pub async fn trigger_with_preset(
+        &self, pipeline_id: &str, preset_id: i64, overrides: HashMap<String, serde_json::Value>,
+    ) -> DomainResult<String> {
+        let preset = self.get_trigger_preset(pipeline_id, preset_id).await?;
+        let schema = self.workflow_schema(pipeline_id).await?;
+        let inputs = preset.resolve_inputs(&schema, overrides)?;
+
+        self.trigger_pipeline(TriggerParams {
+            workflow_id: pipeline_id.to_string(),
+            inputs: Some(serde_json::Value::Object(inputs.into_iter().collect())),
+        })
+        .await
+    }
+
+    async fn get_trigger_preset(
+        &self, pipeline_id: &str, preset_id: i64,
+    ) -> DomainResult<TriggerPreset> {
+        self.repository
+            .list_trigger_presets(pipeline_id)
+            .await?
+            .into_iter()
+            .find(|p| p.id == preset_id)
+            .ok_or_else(|| DomainError::NotFound(format!("Trigger preset {} not found", preset_id)))
+    }
+
+    // Served from the workflow parameter cache when available.
+    async fn workflow_schema(
+        &self, pipeline_id: &str,
+    ) -> DomainResult<Vec<pipedash_plugin_api::WorkflowParameter>> {
+        let cached_pipelines = self.repository.get_cached_pipelines(None).await?;
+        let pipeline = cached_pipelines
+            .iter()
+            .find(|p| p.id == pipeline_id)
+            .ok_or_else(|| DomainError::PipelineNotFound(pipeline_id.to_string()))?;
+
+        self.provider_service
+            .get_workflow_parameters(pipeline.provider_id, pipeline_id)
+            .await
+    }
+
+    // Pipelines come first, then runs; `limit` applies to each kind separately.
+    pub async fn search_everything(
+        &self, query: &str, limit: usize,
+    ) -> DomainResult<Vec<SearchResult>> {
+        let Some(query) = SearchQuery::parse(query) else {
+            return Ok(Vec::new());
+        };
+        let limit = limit.clamp(1, MAX_SEARCH_RESULTS);
+
+        let (pipelines, runs) = tokio::try_join!(
+            self.repository.search_pipelines(&query, limit),
+            self.repository.search_runs(&query, limit)
+        )?;
+
+        let mut results: Vec<SearchResult> =
+            pipelines.iter().map(SearchResult::for_pipeline).collect();
+
+        if !runs.is_empty() {
+            let names: HashMap<String, String> = self
+                .repository
+                .get_cached_pipelines(None)
+                .await?
+                .into_iter()
+                .map(|p| (p.id, p.name))
+                .collect();
+
+            // Runs whose pipeline has since dropped out of the cache can't be
+            // opened, so they are left out.
+            results.extend(runs.iter().filter_map(|run| {
+                names
+                    .get(&run.pipeline_id)
+                    .map(|name| SearchResult::for_run(run, name))
+            }));
+        }
+
+        Ok(results)
+    }
+
+    // Every cached run of a commit across providers, reduced to the latest one
+    // per pipeline. Only runs already in the run history cache are seen.
+    pub async fn get_commit_status(&self, sha: &str) -> DomainResult<CommitStatus> {
+        let prefix = normalize_commit_prefix(sha)?;
+        let (runs, pipelines) = tokio::try_join!(
+            self.repository.get_runs_by_commit(&prefix, MAX_COMMIT_RUNS),
+            self.repository.get_cached_pipelines(None)
+        )?;
+
+        CommitStatus::new(&prefix, runs, &pipelines)
+    }
+
+    // Command palette entries: recently used pipelines without a query, fuzzy
+    // matches over cached pipeline names with one.
+    pub async fn quick_actions(
+        &self, query: Option<&str>, limit: usize,
+    ) -> DomainResult<Vec<QuickAction>> {
+        let limit = limit.clamp(1, MAX_QUICK_ACTIONS);
+        let (pipelines, usage, pins) = tokio::try_join!(
+            self.repository.get_cached_pipelines(None),
+            self.repository.list_pipeline_usage(),
+            self.list_pinned_pipelines()
+        )?;
+        let pinned: HashSet<&str> = pins.iter().map(|pin| pin.pipeline_id.as_str()).collect();
+
+        let mut features = HashMap::new();
+        let mut entries = Vec::new();
+        for (pipeline, score) in rank_pipelines(query, &pipelines, &usage, limit) {
+            let Some(capabilities) = self
+                .provider_service
+                .plugin_capabilities(&pipeline.provider_type)
+            else {
+                continue;
+            };
+            if let Entry::Vacant(entry) = features.entry(pipeline.provider_id) {
+                entry.insert(
+                    self.provider_service
+                        .get_provider_features(pipeline.provider_id)
+                        .await
+                        .unwrap_or_default(),
+                );
+            }
+
+            let actions = pipeline_actions(
+                pipeline,
+                &capabilities,
+                &features[&pipeline.provider_id],
+                pinned.contains(pipeline.id.as_str()),
+            );
+            let usage = usage.iter().find(|u| u.pipeline_id == pipeline.id);
+            entries.push(QuickAction::new(pipeline, score, usage, actions));
+        }
+
+        Ok(entries)
+    }
+
+    // Best effort; feeds the palette's recently used list.
+    pub async fn record_pipeline_usage(&self, pipeline_id: &str) {
+        if let Err(e) = self.repository.record_pipeline_usage(pipeline_id).await {
+            tracing::debug!(pipeline_id = %pipeline_id, error = %e, "Failed to record pipeline usage");
+        }
     }
 
     pub async fn refresh_all(&self) -> DomainResult<()> {
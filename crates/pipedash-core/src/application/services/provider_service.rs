@@ -1,32 +1,90 @@
-use std::collections::HashMap;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 use std::sync::Arc;
+use std::time::{
+    Duration,
+    Instant,
+};
 
+use chrono::{
+    DateTime,
+    Utc,
+};
+use pipedash_plugin_api::schema::TableSchema;
 use pipedash_plugin_api::{
+    max_response_bytes,
+    Capability,
+    FeatureAvailability,
+    PaginationParams,
     Plugin as PluginTrait,
+    PluginCapabilities,
     PluginRegistry,
+    ServerVersion,
 };
 use tokio::sync::{
     Mutex,
     RwLock,
 };
+use tokio::task::JoinHandle;
 
+use super::notification_center::NotificationCenter;
+use crate::application::AccessControl;
 use crate::domain::{
+    add_selected_items,
+    auth_finding,
+    clock_skew_finding,
+    permission_findings,
+    plugin_token,
+    rate_limit_finding,
+    reachability_finding,
+    server_version_finding,
+    Action,
+    CircuitBreaker,
+    DeviceAuthorization,
+    DiagnosticCheck,
+    DiagnosticFinding,
     DomainError,
     DomainResult,
+    EndpointProbe,
     FetchStatus,
+    FindingSeverity,
+    GatedOperation,
+    OAuthCredentials,
+    OAuthPollStatus,
+    PaginatedAvailablePipelines,
+    PipelineFilter,
     Provider,
     ProviderConfig,
+    ProviderDiagnosis,
+    ProviderHealth,
     ProviderSummary,
+    ResilienceConfig,
+    PROVIDER_HEALTH_WINDOW,
 };
 use crate::event::{
     CoreEvent,
     EventBus,
 };
 use crate::infrastructure::config::token_ref::TokenReference;
+use crate::infrastructure::config::OAuthConfig;
 use crate::infrastructure::database::Repository;
+use crate::infrastructure::oauth::{
+    self,
+    DeviceFlowClient,
+    DeviceFlowEndpoints,
+};
 use crate::infrastructure::providers::PluginAdapter;
 use crate::plugins;
 
+const DIAGNOSTICS_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+// Config keys plugins take their server URL from, tried in order when
+// pointing a provider at a recording proxy.
+const RECORDABLE_URL_KEYS: &[&str] = &["base_url", "server_url", "gate_url"];
+const RECORDING_SENTINEL_URL: &str = "http://recording.invalid";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub struct ProviderService {
     repository: Arc<Repository>,
     http_client_manager: Arc<crate::infrastructure::HttpClientManager>,
@@ -34,13 +92,30 @@ pub struct ProviderService {
     plugin_registry: Arc<PluginRegistry>,
     parameter_fetches: Arc<Mutex<HashMap<String, Arc<Mutex<()>>>>>,
     event_bus: Arc<dyn EventBus>,
+    access_control: Arc<AccessControl>,
+    oauth_config: OAuthConfig,
+    device_flow: DeviceFlowClient,
+    // When each OAuth-backed provider's access token should be refreshed.
+    oauth_refresh_at: std::sync::Mutex<HashMap<i64, DateTime<Utc>>>,
+    // Refresh tokens may be single-use, so refreshes never run concurrently.
+    oauth_refresh: Mutex<()>,
+    notification_center: Arc<NotificationCenter>,
+    // Latest diagnosis per provider, from the periodic run or on demand.
+    diagnoses: RwLock<HashMap<i64, ProviderDiagnosis>>,
+    diagnostics: Mutex<Option<JoinHandle<()>>>,
+    // Detected server version per provider, handed to plugins on creation so
+    // they can pick compatibility shims.
+    server_versions: std::sync::Mutex<HashMap<i64, ServerVersion>>,
+    resilience: ResilienceConfig,
+    circuit_breakers: std::sync::Mutex<HashMap<i64, CircuitBreaker>>,
 }
 
 impl ProviderService {
     pub fn new(
         repository: Arc<Repository>,
         http_client_manager: Arc<crate::infrastructure::HttpClientManager>,
-        event_bus: Arc<dyn EventBus>,
+        event_bus: Arc<dyn EventBus>, access_control: Arc<AccessControl>,
+        oauth_config: OAuthConfig, notification_center: Arc<NotificationCenter>,
     ) -> Self {
         let plugin_registry = plugins::create_plugin_registry();
 
@@ -51,13 +126,33 @@ impl ProviderService {
             plugin_registry: Arc::new(plugin_registry),
             parameter_fetches: Arc::new(Mutex::new(HashMap::new())),
             event_bus,
+            access_control,
+            oauth_config,
+            device_flow: DeviceFlowClient::new(),
+            oauth_refresh_at: std::sync::Mutex::new(HashMap::new()),
+            oauth_refresh: Mutex::new(()),
+            notification_center,
+            diagnoses: RwLock::new(HashMap::new()),
+            diagnostics: Mutex::new(None),
+            server_versions: std::sync::Mutex::new(HashMap::new()),
+            resilience: ResilienceConfig::default(),
+            circuit_breakers: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
+    pub fn with_resilience(mut self, resilience: ResilienceConfig) -> Self {
+        self.resilience = resilience;
+        self
+    }
+
     pub fn repository(&self) -> &Arc<Repository> {
         &self.repository
     }
 
+    pub fn access_control(&self) -> &Arc<AccessControl> {
+        &self.access_control
+    }
+
     pub fn list_available_plugins(&self) -> Vec<pipedash_plugin_api::PluginMetadata> {
         let mut metadata_list = Vec::new();
         for provider_type in self.plugin_registry.provider_types() {
@@ -68,6 +163,25 @@ impl ProviderService {
         metadata_list
     }
 
+    // IDs of configured providers whose plugin reports the given capability.
+    pub async fn provider_ids_with_capability(
+        &self, capability: Capability,
+    ) -> DomainResult<Vec<i64>> {
+        Ok(self
+            .repository
+            .list_providers()
+            .await?
+            .into_iter()
+            .filter(|config| config.enabled)
+            .filter(|config| {
+                self.plugin_registry
+                    .get(&config.provider_type)
+                    .is_some_and(|plugin| plugin.metadata().capabilities.supports(capability))
+            })
+            .filter_map(|config| config.id)
+            .collect())
+    }
+
     pub fn create_uninitialized_plugin(
         &self, provider_type: &str,
     ) -> DomainResult<Box<dyn PluginTrait>> {
@@ -77,23 +191,29 @@ impl ProviderService {
     }
 
     pub async fn add_provider(&self, config: ProviderConfig) -> DomainResult<i64> {
+        self.access_control.ensure(Action::ManageProviders)?;
+        PipelineFilter::from_config(&config.config)?;
+        reject_token_file(&config.token)?;
+
         let mut plugin = self.create_uninitialized_plugin(&config.provider_type)?;
 
+        let token = self.repository.resolve_token(&config.token).await?;
         let mut plugin_config = config.config.clone();
-        plugin_config.insert("token".to_string(), config.token.clone());
+        plugin_config.insert("token".to_string(), plugin_token(&token));
 
-        let http_client = if let Some(base_url) = plugin_config.get("base_url") {
-            self.http_client_manager.client_for_url(base_url)?
-        } else {
-            self.http_client_manager.default_client()
-        };
+        let http_client = self
+            .http_client_manager
+            .client_for_provider(&plugin_config)?;
 
         plugin
             .initialize(0, plugin_config.clone(), Some(http_client))
             .map_err(|e| DomainError::InvalidConfig(format!("Failed to initialize plugin: {e}")))?;
 
-        let (validation_result, permission_status) =
-            tokio::join!(plugin.validate_credentials(), plugin.check_permissions());
+        let (validation_result, permission_status, server_version) = tokio::join!(
+            plugin.validate_credentials(),
+            plugin.check_permissions(),
+            self.detect_server_version(plugin.as_ref())
+        );
 
         validation_result.map_err(|e| {
             DomainError::InvalidConfig(format!("Credential validation failed: {e}"))
@@ -116,9 +236,13 @@ impl ProviderService {
                 tracing::warn!(provider_id = id, error = %e, "Failed to store permissions");
             }
         }
+        if let Some(version) = server_version {
+            self.store_server_version(id, version).await;
+        }
 
         let mut config_with_id = config.clone();
         config_with_id.id = Some(id);
+        config_with_id.token = token;
         let provider = self.create_provider(&config_with_id)?;
 
         let mut providers = self.providers.write().await;
@@ -195,6 +319,8 @@ impl ProviderService {
             last_fetch_error,
             last_fetch_at: last_fetch_at_parsed,
             version: fresh_config.version.unwrap_or(1),
+            server_version: self.server_version(id),
+            enabled: fresh_config.enabled,
         };
 
         let timestamp = std::time::SystemTime::now()
@@ -216,6 +342,13 @@ impl ProviderService {
         self.repository.get_provider(id).await
     }
 
+    // Include/exclude patterns from the provider's config, applied to every
+    // pipeline fetch before anything is cached.
+    pub async fn pipeline_filter(&self, id: i64) -> DomainResult<PipelineFilter> {
+        let config = self.repository.get_provider(id).await?;
+        PipelineFilter::from_config(&config.config)
+    }
+
     pub async fn list_providers(&self) -> DomainResult<Vec<ProviderSummary>> {
         let configs = self.repository.list_providers().await?;
         let mut summaries = Vec::new();
@@ -291,6 +424,8 @@ impl ProviderService {
                 last_fetch_error,
                 last_fetch_at: last_fetch_at_parsed,
                 version: config.version.unwrap_or(1),
+                server_version: self.server_version(provider_id),
+                enabled: config.enabled,
             });
         }
 
@@ -298,16 +433,19 @@ impl ProviderService {
     }
 
     pub async fn update_provider(&self, id: i64, config: ProviderConfig) -> DomainResult<()> {
+        self.access_control.ensure(Action::ManageProviders)?;
+        PipelineFilter::from_config(&config.config)?;
+        reject_token_file(&config.token)?;
+
         let mut plugin = self.create_uninitialized_plugin(&config.provider_type)?;
 
+        let token = self.repository.resolve_token(&config.token).await?;
         let mut plugin_config = config.config.clone();
-        plugin_config.insert("token".to_string(), config.token.clone());
+        plugin_config.insert("token".to_string(), plugin_token(&token));
 
-        let http_client = if let Some(base_url) = plugin_config.get("base_url") {
-            self.http_client_manager.client_for_url(base_url)?
-        } else {
-            self.http_client_manager.default_client()
-        };
+        let http_client = self
+            .http_client_manager
+            .client_for_provider(&plugin_config)?;
 
         plugin
             .initialize(id, plugin_config.clone(), Some(http_client))
@@ -318,6 +456,7 @@ impl ProviderService {
         })?;
 
         let permission_status = plugin.check_permissions().await.ok();
+        let server_version = self.detect_server_version(plugin.as_ref()).await;
 
         let current = self.repository.get_provider(id).await?;
         let current_version = current.version.unwrap_or(1);
@@ -328,6 +467,7 @@ impl ProviderService {
             .await?;
 
         if !success {
+            self.notification_center.provider_conflict(id).await;
             return Err(DomainError::ConcurrentModification(format!(
                 "Provider {} was modified by another client",
                 id
@@ -339,15 +479,28 @@ impl ProviderService {
                 tracing::warn!(provider_id = id, error = %e, "Failed to store permissions");
             }
         }
+        if let Some(version) = server_version {
+            self.store_server_version(id, version).await;
+        }
 
         let mut config_with_id = config.clone();
         config_with_id.id = Some(id);
+        config_with_id.token = token;
+        // Recreated with the new config's size limit, and so bodies stored
+        // under the old credentials aren't replayed.
+        self.http_client_manager.remove_conditional_cache(id);
         let new_provider = self.create_provider(&config_with_id)?;
+        // New credentials or URL deserve a fresh start.
+        self.circuit_breakers.lock().unwrap().remove(&id);
 
         let providers = Arc::clone(&self.providers);
         let old_provider = {
             let mut map = providers.write().await;
-            map.insert(id, new_provider)
+            if current.enabled {
+                map.insert(id, new_provider)
+            } else {
+                map.remove(&id)
+            }
         };
 
         if old_provider.is_some() {
@@ -430,6 +583,8 @@ impl ProviderService {
             last_fetch_error,
             last_fetch_at: last_fetch_at_parsed,
             version: fresh_config.version.unwrap_or(1),
+            server_version: self.server_version(id),
+            enabled: fresh_config.enabled,
         };
 
         let timestamp = std::time::SystemTime::now()
@@ -450,13 +605,136 @@ impl ProviderService {
     pub async fn update_provider_refresh_interval(
         &self, id: i64, refresh_interval: i64,
     ) -> DomainResult<()> {
+        self.access_control.ensure(Action::ManageProviders)?;
+
         let mut config = self.repository.get_provider(id).await?;
         config.refresh_interval = refresh_interval;
         self.repository.update_provider(id, &config).await?;
         Ok(())
     }
 
+    // One page of what a configured provider can see, e.g. an organization's
+    // repositories. Uses the provider's conditional cache, so polling an
+    // unchanged organization is cheap.
+    pub async fn list_available_pipelines(
+        &self, id: i64, org: Option<String>, params: PaginationParams,
+    ) -> DomainResult<PaginatedAvailablePipelines> {
+        if self.oauth_refresh_due(id) {
+            if let Err(e) = self.refresh_oauth_token(id).await {
+                tracing::warn!(provider_id = id, error = %e, "Failed to refresh OAuth token");
+            }
+        }
+
+        let config = self.repository.get_provider(id).await?;
+        let mut plugin = self.create_uninitialized_plugin(&config.provider_type)?;
+
+        let mut plugin_config = config.config.clone();
+        plugin_config.insert("token".to_string(), plugin_token(&config.token));
+        let http_client = self
+            .http_client_manager
+            .client_for_provider(&plugin_config)?;
+
+        plugin.set_conditional_cache(
+            self.http_client_manager
+                .conditional_cache(id, max_response_bytes(&plugin_config)),
+        );
+        plugin
+            .initialize(id, plugin_config, Some(http_client))
+            .map_err(|e| DomainError::InvalidConfig(format!("Failed to initialize plugin: {e}")))?;
+
+        plugin
+            .fetch_available_pipelines_filtered(org, None, Some(params))
+            .await
+            .map_err(|e| {
+                DomainError::InternalError(format!("Failed to fetch available pipelines: {e}"))
+            })
+    }
+
+    // Appends to the provider's selected items, e.g. repositories found by
+    // discovery. Nothing is revalidated; the plugin is rebuilt on next use.
+    // Returns the items added, none when another client changed the provider
+    // in the meantime.
+    pub async fn add_selected_items(
+        &self, id: i64, items: Vec<String>,
+    ) -> DomainResult<Vec<String>> {
+        let mut config = self.repository.get_provider(id).await?;
+        let added = add_selected_items(&mut config.config, items);
+        if added.is_empty() {
+            return Ok(added);
+        }
+
+        let version = config.version.unwrap_or(1);
+        if !self
+            .repository
+            .update_provider_with_version(id, &config, version)
+            .await?
+        {
+            return Ok(Vec::new());
+        }
+        self.providers.write().await.remove(&id);
+
+        if let Some(provider) = self
+            .list_providers()
+            .await?
+            .into_iter()
+            .find(|summary| summary.id == id)
+        {
+            self.event_bus
+                .emit(CoreEvent::ProviderUpdated {
+                    provider,
+                    timestamp: Utc::now().timestamp_millis(),
+                })
+                .await;
+        }
+        Ok(added)
+    }
+
+    // Pauses a provider without losing its config, cached pipelines or run
+    // history. It isn't fetched or refreshed until enabled again.
+    pub async fn disable_provider(&self, id: i64) -> DomainResult<()> {
+        self.set_provider_enabled(id, false).await
+    }
+
+    pub async fn enable_provider(&self, id: i64) -> DomainResult<()> {
+        self.set_provider_enabled(id, true).await
+    }
+
+    async fn set_provider_enabled(&self, id: i64, enabled: bool) -> DomainResult<()> {
+        self.access_control.ensure(Action::ManageProviders)?;
+
+        self.repository.set_provider_enabled(id, enabled).await?;
+        // A disabled provider's instance is dropped; an enabled one is
+        // created on first use, with a closed circuit either way.
+        self.providers.write().await.remove(&id);
+        self.circuit_breakers.lock().unwrap().remove(&id);
+
+        let Some(provider_summary) = self
+            .list_providers()
+            .await?
+            .into_iter()
+            .find(|summary| summary.id == id)
+        else {
+            return Ok(());
+        };
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+
+        self.event_bus
+            .emit(CoreEvent::ProviderUpdated {
+                provider: provider_summary,
+                timestamp,
+            })
+            .await;
+
+        Ok(())
+    }
+
     pub async fn remove_provider(&self, id: i64) -> DomainResult<()> {
+        self.access_control.ensure(Action::ManageProviders)?;
+
         let fresh_config = self.repository.get_provider(id).await?;
         let cached_pipelines = self.repository.get_cached_pipelines(Some(id)).await?;
         let pipeline_count = cached_pipelines.len();
@@ -527,11 +805,19 @@ impl ProviderService {
             last_fetch_error,
             last_fetch_at: last_fetch_at_parsed,
             version: fresh_config.version.unwrap_or(1),
+            server_version: self.server_version(id),
+            enabled: fresh_config.enabled,
         };
 
         let pipelines = cached_pipelines;
 
         self.repository.remove_provider(id).await?;
+        self.http_client_manager.remove_conditional_cache(id);
+        self.http_client_manager.stop_recording(id);
+        self.oauth_refresh_at.lock().unwrap().remove(&id);
+        self.server_versions.lock().unwrap().remove(&id);
+        self.circuit_breakers.lock().unwrap().remove(&id);
+        self.diagnoses.write().await.remove(&id);
 
         let providers = Arc::clone(&self.providers);
         tokio::spawn(async move {
@@ -561,6 +847,12 @@ impl ProviderService {
     }
 
     pub async fn get_provider(&self, id: i64) -> DomainResult<Arc<dyn Provider>> {
+        if self.oauth_refresh_due(id) {
+            if let Err(e) = self.refresh_oauth_token(id).await {
+                tracing::warn!(provider_id = id, error = %e, "Failed to refresh OAuth token");
+            }
+        }
+
         let providers = self.providers.read().await;
 
         if let Some(provider) = providers.get(&id) {
@@ -570,6 +862,12 @@ impl ProviderService {
         drop(providers);
 
         let config = self.repository.get_provider(id).await?;
+        if !config.enabled {
+            return Err(DomainError::InvalidConfig(format!(
+                "Provider {} is disabled",
+                config.display_name()
+            )));
+        }
         let provider = self.create_provider(&config)?;
 
         let mut providers = self.providers.write().await;
@@ -582,11 +880,106 @@ impl ProviderService {
         self.get_provider(id).await
     }
 
+    pub async fn start_oauth(
+        &self, provider_type: &str, base_url: Option<&str>,
+    ) -> DomainResult<DeviceAuthorization> {
+        self.access_control.ensure(Action::ManageProviders)?;
+
+        let client_id = self.oauth_config.client_id(provider_type).ok_or_else(|| {
+            DomainError::InvalidConfig(format!(
+                "OAuth sign-in for {} requires [oauth] {}_client_id in the config",
+                provider_type, provider_type
+            ))
+        })?;
+        let endpoints = DeviceFlowEndpoints::for_provider(provider_type, base_url)?;
+        let http_client = match base_url {
+            Some(url) if !url.trim().is_empty() => self.http_client_manager.client_for_url(url)?,
+            _ => self.http_client_manager.default_client(),
+        };
+
+        self.device_flow
+            .start(&http_client, &endpoints, client_id)
+            .await
+    }
+
+    pub async fn poll_oauth(&self, session_id: &str) -> DomainResult<OAuthPollStatus> {
+        self.access_control.ensure(Action::ManageProviders)?;
+
+        let http_client = self.http_client_manager.default_client();
+        self.device_flow.poll(&http_client, session_id).await
+    }
+
+    fn oauth_refresh_due(&self, id: i64) -> bool {
+        self.oauth_refresh_at
+            .lock()
+            .unwrap()
+            .get(&id)
+            .is_some_and(|refresh_at| *refresh_at <= Utc::now())
+    }
+
+    async fn refresh_oauth_token(&self, id: i64) -> DomainResult<()> {
+        let _guard = self.oauth_refresh.lock().await;
+        // Another caller may have refreshed while we waited for the lock.
+        if !self.oauth_refresh_due(id) {
+            return Ok(());
+        }
+
+        let mut config = self.repository.get_provider(id).await?;
+        let Some(credentials) = OAuthCredentials::parse(&config.token) else {
+            self.oauth_refresh_at.lock().unwrap().remove(&id);
+            return Ok(());
+        };
+        if !credentials.needs_refresh(Utc::now()) {
+            if let Some(refresh_at) = credentials.refresh_at() {
+                self.oauth_refresh_at.lock().unwrap().insert(id, refresh_at);
+            }
+            return Ok(());
+        }
+
+        let http_client = self.http_client_manager.default_client();
+        let refreshed = oauth::refresh_credentials(&http_client, &credentials).await?;
+        config.token = refreshed.to_token_string();
+        self.repository
+            .store_provider_token(id, &config.token)
+            .await?;
+
+        let provider = self.create_provider(&config)?;
+        self.providers.write().await.insert(id, provider);
+
+        tracing::debug!(provider_id = id, "Refreshed OAuth token");
+        Ok(())
+    }
+
     pub async fn load_all_providers(&self) -> DomainResult<()> {
-        let configs = self.repository.list_providers().await?;
+        let configs = self.repository.list_providers_with_tokens().await?;
+        match self.repository.get_provider_server_versions().await {
+            Ok(versions) => *self.server_versions.lock().unwrap() = versions,
+            Err(e) => tracing::warn!(error = %e, "Failed to load provider server versions"),
+        }
         let mut old_providers_to_cleanup = Vec::new();
 
-        for config in configs {
+        // Providers deleted behind our back, e.g. pruned by provisioning, or
+        // disabled since the last load.
+        {
+            let ids: HashSet<i64> = configs
+                .iter()
+                .filter(|c| c.enabled)
+                .filter_map(|c| c.id)
+                .collect();
+            let mut providers = self.providers.write().await;
+            let removed: Vec<i64> = providers
+                .keys()
+                .filter(|id| !ids.contains(id))
+                .copied()
+                .collect();
+            for id in removed {
+                if let Some(old) = providers.remove(&id) {
+                    old_providers_to_cleanup.push((id, old));
+                }
+            }
+        }
+
+        for config in configs.into_iter().filter(|c| c.enabled) {
             if let Some(id) = config.id {
                 if config.token.is_empty() {
                     tracing::debug!(
@@ -639,32 +1032,71 @@ impl ProviderService {
         let provider_id = config.id.unwrap_or(0);
         let mut plugin_config = config.config.clone();
 
-        let resolved_token = if config.token.is_empty() {
-            String::new()
-        } else {
-            match TokenReference::parse(&config.token) {
-                Ok(TokenReference::EnvVar(var_name)) => {
-                    std::env::var(&var_name).unwrap_or_else(|_| {
-                        tracing::warn!(
-                            provider_id = provider_id,
-                            var_name = %var_name,
-                            "Environment variable not found, using empty token"
-                        );
-                        String::new()
-                    })
+        let resolved_token =
+            if config.token.is_empty() {
+                String::new()
+            } else {
+                match TokenReference::parse(&config.token) {
+                    Ok(TokenReference::EnvVar(var_name)) => std::env::var(&var_name)
+                        .unwrap_or_else(|_| {
+                            tracing::warn!(
+                                provider_id = provider_id,
+                                var_name = %var_name,
+                                "Environment variable not found, using empty token"
+                            );
+                            String::new()
+                        }),
+                    Ok(TokenReference::File(path)) => TokenReference::read_file(&path)
+                        .unwrap_or_else(|e| {
+                            tracing::warn!(
+                                provider_id = provider_id,
+                                error = %e,
+                                "Token file not readable, using empty token"
+                            );
+                            String::new()
+                        }),
+                    Ok(TokenReference::None) => String::new(),
+                    Ok(_) => config.token.clone(),
+                    Err(_) => config.token.clone(),
                 }
-                Ok(TokenReference::None) => String::new(),
-                Ok(_) => config.token.clone(),
-                Err(_) => config.token.clone(),
+            };
+        if let Some(refresh_at) =
+            OAuthCredentials::parse(&resolved_token).and_then(|c| c.refresh_at())
+        {
+            self.oauth_refresh_at
+                .lock()
+                .unwrap()
+                .insert(provider_id, refresh_at);
+        }
+        plugin_config.insert("token".to_string(), plugin_token(&resolved_token));
+
+        let mut http_client = self
+            .http_client_manager
+            .client_for_provider(&plugin_config)?;
+
+        if provider_id != 0 && self.http_client_manager.is_recording() {
+            if let Some(recording_config) = self.recording_config(
+                provider_id,
+                &config.provider_type,
+                &plugin_config,
+                &http_client,
+            ) {
+                // The proxy talks to the provider with the configured client;
+                // the plugin only needs to reach localhost.
+                plugin_config = recording_config;
+                http_client = self.http_client_manager.default_client();
             }
-        };
-        plugin_config.insert("token".to_string(), resolved_token);
+        }
 
-        let http_client = if let Some(base_url) = plugin_config.get("base_url") {
-            self.http_client_manager.client_for_url(base_url)?
-        } else {
-            self.http_client_manager.default_client()
-        };
+        if provider_id != 0 {
+            plugin.set_conditional_cache(
+                self.http_client_manager
+                    .conditional_cache(provider_id, max_response_bytes(&plugin_config)),
+            );
+        }
+        if let Some(version) = self.server_version(provider_id) {
+            plugin.set_server_version(&version);
+        }
 
         plugin
             .initialize(provider_id, plugin_config, Some(http_client))
@@ -674,6 +1106,113 @@ impl ProviderService {
         Ok(Arc::new(adapter))
     }
 
+    pub fn server_version(&self, provider_id: i64) -> Option<ServerVersion> {
+        self.server_versions
+            .lock()
+            .unwrap()
+            .get(&provider_id)
+            .cloned()
+    }
+
+    // A failed detection only means no shims are applied, so it is logged
+    // rather than failing validation.
+    pub async fn detect_server_version(&self, plugin: &dyn PluginTrait) -> Option<ServerVersion> {
+        match plugin.detect_server_version().await {
+            Ok(version) => version,
+            Err(e) => {
+                tracing::debug!(
+                    provider_type = plugin.provider_type(),
+                    error = %e,
+                    "Could not detect the provider server version"
+                );
+                None
+            }
+        }
+    }
+
+    // Returns whether the version differs from the one stored before.
+    async fn store_server_version(&self, provider_id: i64, version: ServerVersion) -> bool {
+        if let Some(warning) = &version.warning {
+            tracing::warn!(
+                provider_id,
+                version = %version.version,
+                supported = version.supported,
+                "{}",
+                warning
+            );
+        }
+        if let Err(e) = self
+            .repository
+            .store_provider_server_version(provider_id, &version)
+            .await
+        {
+            tracing::warn!(provider_id, error = %e, "Failed to store provider server version");
+        }
+
+        let previous = self
+            .server_versions
+            .lock()
+            .unwrap()
+            .insert(provider_id, version.clone());
+        previous.is_none_or(|previous| previous.version != version.version)
+    }
+
+    // The plugin config with its server URL pointed at a recording proxy. The
+    // URL key is found by initializing throwaway plugins with a sentinel URL
+    // and comparing API endpoints, which also gives the path prefix the
+    // plugin adds (`/api/v4` for GitLab). Plugins on a fixed host can't be
+    // redirected and are left alone, as are any failures: recording never
+    // keeps a provider from loading.
+    fn recording_config(
+        &self, provider_id: i64, provider_type: &str, plugin_config: &HashMap<String, String>,
+        http_client: &Arc<reqwest::Client>,
+    ) -> Option<HashMap<String, String>> {
+        let api_endpoint = |config: &HashMap<String, String>| {
+            let mut plugin = self.create_uninitialized_plugin(provider_type).ok()?;
+            plugin
+                .initialize(provider_id, config.clone(), Some(Arc::clone(http_client)))
+                .ok()?;
+            plugin.api_endpoint()
+        };
+        let upstream = api_endpoint(plugin_config)?;
+
+        for key in RECORDABLE_URL_KEYS {
+            let mut redirected = plugin_config.clone();
+            redirected.insert(key.to_string(), RECORDING_SENTINEL_URL.to_string());
+            let Some(prefix) = api_endpoint(&redirected).and_then(|endpoint| {
+                endpoint
+                    .strip_prefix(RECORDING_SENTINEL_URL)
+                    .map(str::to_string)
+            }) else {
+                continue;
+            };
+
+            return match self.http_client_manager.start_recording(
+                provider_id,
+                provider_type,
+                &upstream,
+                &prefix,
+                http_client,
+            ) {
+                Ok(url) => {
+                    redirected.insert(key.to_string(), url);
+                    Some(redirected)
+                }
+                Err(e) => {
+                    tracing::warn!(provider_id, error = %e, "Failed to record provider traffic");
+                    None
+                }
+            };
+        }
+
+        tracing::debug!(
+            provider_id,
+            provider_type,
+            "Provider API host is not configurable, not recording"
+        );
+        None
+    }
+
     pub async fn get_workflow_parameters(
         &self, provider_id: i64, workflow_id: &str,
     ) -> DomainResult<Vec<pipedash_plugin_api::WorkflowParameter>> {
@@ -707,6 +1246,9 @@ impl ProviderService {
         let provider = providers.get(&provider_id).ok_or_else(|| {
             DomainError::ProviderNotFound(format!("Provider {provider_id} not found"))
         })?;
+        if !self.supports(provider.provider_type(), Capability::WorkflowParameters) {
+            return Ok(Vec::new());
+        }
 
         let parameters = provider
             .get_workflow_parameters(workflow_id)
@@ -728,30 +1270,236 @@ impl ProviderService {
         Ok(parameters)
     }
 
+    // Health of one provider, or of all of them when `provider_id` is None.
+    pub async fn get_provider_health(
+        &self, provider_id: Option<i64>,
+    ) -> DomainResult<Vec<ProviderHealth>> {
+        let providers = self.list_providers().await?;
+        if let Some(id) = provider_id {
+            if !providers.iter().any(|p| p.id == id) {
+                return Err(DomainError::ProviderNotFound(id.to_string()));
+            }
+        }
+
+        let mut health = Vec::new();
+        for provider in providers
+            .into_iter()
+            .filter(|p| provider_id.is_none_or(|id| p.id == id))
+        {
+            let checks = self
+                .repository
+                .get_provider_health_checks(provider.id, PROVIDER_HEALTH_WINDOW)
+                .await?;
+            let mut provider_health = ProviderHealth::from_checks(
+                provider.id,
+                provider.name,
+                provider.provider_type,
+                checks,
+            );
+            provider_health.circuit_breaker = self.circuit_breaker_status(provider.id);
+            health.push(provider_health);
+        }
+
+        Ok(health)
+    }
+
+    pub fn circuit_breaker_status(&self, provider_id: i64) -> crate::domain::CircuitBreakerStatus {
+        self.circuit_breakers
+            .lock()
+            .unwrap()
+            .get(&provider_id)
+            .map(|breaker| breaker.status(&self.resilience))
+            .unwrap_or_default()
+    }
+
+    // Fails with `CircuitOpen` while the provider's breaker is open.
+    pub fn acquire_circuit(&self, provider_id: i64) -> DomainResult<()> {
+        self.circuit_breakers
+            .lock()
+            .unwrap()
+            .entry(provider_id)
+            .or_default()
+            .try_acquire(&self.resilience, Utc::now())
+            .map_err(|retry_at| DomainError::CircuitOpen {
+                provider_id,
+                retry_at,
+            })
+    }
+
+    // Only transient failures count against the breaker, but any other
+    // outcome still ends a half-open trial.
+    pub fn record_fetch_outcome(&self, provider_id: i64, transient_failure: bool) {
+        let mut breakers = self.circuit_breakers.lock().unwrap();
+        let breaker = breakers.entry(provider_id).or_default();
+        if transient_failure {
+            let was_open = breaker.status(&self.resilience).retry_at.is_some();
+            breaker.record_failure(&self.resilience, Utc::now());
+            if !was_open {
+                if let Some(retry_at) = breaker.status(&self.resilience).retry_at {
+                    tracing::warn!(
+                        provider_id,
+                        retry_at = %retry_at,
+                        "Circuit breaker opened after repeated fetch failures"
+                    );
+                }
+            }
+        } else {
+            breaker.record_success();
+        }
+    }
+
+    // Runs a read-only provider call behind the provider's circuit breaker,
+    // retrying transient failures with backoff and honouring Retry-After.
+    // Only meant for requests that are safe to repeat.
+    pub async fn guarded_fetch<T, F, Fut>(&self, provider_id: i64, operation: F) -> DomainResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = DomainResult<T>>,
+    {
+        self.acquire_circuit(provider_id)?;
+
+        let policy = self.resilience.retry_policy();
+        let mut attempt = 0;
+        let result = loop {
+            match operation().await {
+                Err(e) if e.is_transient() => match policy.delay(attempt, e.retry_after()) {
+                    Some(delay) => {
+                        tracing::debug!(
+                            provider_id,
+                            attempt = attempt + 1,
+                            delay_ms = delay.as_millis() as u64,
+                            error = %e,
+                            "Retrying provider fetch"
+                        );
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                    None => break Err(e),
+                },
+                result => break result,
+            }
+        };
+
+        self.record_fetch_outcome(
+            provider_id,
+            result.as_ref().is_err_and(DomainError::is_transient),
+        );
+        result
+    }
+
     pub async fn get_provider_permissions(
         &self, provider_id: i64,
     ) -> DomainResult<Option<pipedash_plugin_api::PermissionStatus>> {
         self.repository.get_provider_permissions(provider_id).await
     }
 
-    pub async fn recheck_provider_permissions(
-        &self, provider_id: i64,
-    ) -> DomainResult<pipedash_plugin_api::PermissionStatus> {
-        let config = self.repository.get_provider(provider_id).await?;
-        let mut plugin = self.create_uninitialized_plugin(&config.provider_type)?;
+    pub fn plugin_capabilities(&self, provider_type: &str) -> Option<PluginCapabilities> {
+        self.plugin_registry
+            .get(provider_type)
+            .map(|plugin| plugin.metadata().capabilities.clone())
+    }
 
-        let mut plugin_config = config.config.clone();
-        plugin_config.insert("token".to_string(), config.token.clone());
+    pub fn supports(&self, provider_type: &str, capability: Capability) -> bool {
+        self.plugin_registry
+            .get(provider_type)
+            .is_some_and(|plugin| plugin.metadata().capabilities.supports(capability))
+    }
 
-        let http_client = if let Some(base_url) = plugin_config.get("base_url") {
-            self.http_client_manager.client_for_url(base_url)?
+    // Fails fast for operations the plugin does not implement, instead of
+    // sending them to the provider.
+    pub fn ensure_capability(
+        &self, provider_type: &str, capability: Capability,
+    ) -> DomainResult<()> {
+        let Some(plugin) = self.plugin_registry.get(provider_type) else {
+            return Err(DomainError::InvalidProviderType(format!(
+                "Unknown provider type: {}",
+                provider_type
+            )));
+        };
+        let metadata = plugin.metadata();
+        if metadata.capabilities.supports(capability) {
+            Ok(())
         } else {
-            self.http_client_manager.default_client()
+            Err(DomainError::NotSupported(format!(
+                "{} does not support {}",
+                metadata.name,
+                capability.description()
+            )))
+        }
+    }
+
+    pub async fn get_provider_table_schema(&self, provider_id: i64) -> DomainResult<TableSchema> {
+        let config = self.get_provider_config(provider_id).await?;
+        let plugin = self
+            .plugin_registry
+            .get(&config.provider_type)
+            .ok_or_else(|| {
+                DomainError::NotFound(format!(
+                    "Plugin not found for provider type: {}",
+                    config.provider_type
+                ))
+            })?;
+
+        let metadata = plugin.metadata();
+        let mut schema = metadata.table_schema.clone();
+        schema.capabilities = Some(metadata.capabilities.clone());
+        Ok(schema)
+    }
+
+    // Features of the provider's plugin and whether the stored permission
+    // check grants them. Before the first check every feature is unavailable.
+    pub async fn get_provider_features(
+        &self, provider_id: i64,
+    ) -> DomainResult<Vec<FeatureAvailability>> {
+        let config = self.get_provider_config(provider_id).await?;
+        let plugin = self
+            .plugin_registry
+            .get(&config.provider_type)
+            .ok_or_else(|| {
+                DomainError::NotFound(format!(
+                    "Plugin not found for provider type: {}",
+                    config.provider_type
+                ))
+            })?;
+
+        // Plugins may map permission names per token type, so they decide
+        // availability.
+        Ok(match self.get_provider_permissions(provider_id).await? {
+            Some(status) => plugin.get_feature_availability(&status),
+            None => plugin
+                .metadata()
+                .features
+                .iter()
+                .map(|feature| FeatureAvailability {
+                    feature: feature.clone(),
+                    available: feature.required_permissions.is_empty(),
+                    missing_permissions: feature.required_permissions.clone(),
+                })
+                .collect(),
+        })
+    }
+
+    // Enforces the stored permissions for an operation. Providers whose
+    // permissions were never checked are not restricted, since a check that
+    // never ran says nothing about the token.
+    pub async fn ensure_feature(
+        &self, provider_id: i64, operation: GatedOperation,
+    ) -> DomainResult<()> {
+        let config = self.get_provider_config(provider_id).await?;
+        let Some(plugin) = self.plugin_registry.get(&config.provider_type) else {
+            return Ok(());
         };
+        let Some(status) = self.get_provider_permissions(provider_id).await? else {
+            return Ok(());
+        };
+        operation.ensure_available(&plugin.get_feature_availability(&status))
+    }
 
-        plugin
-            .initialize(provider_id, plugin_config, Some(http_client))
-            .map_err(|e| DomainError::InvalidConfig(format!("Failed to initialize plugin: {e}")))?;
+    pub async fn recheck_provider_permissions(
+        &self, provider_id: i64,
+    ) -> DomainResult<pipedash_plugin_api::PermissionStatus> {
+        let config = self.repository.get_provider(provider_id).await?;
+        let (plugin, _) = self.initialize_plugin(provider_id, &config)?;
 
         let status = plugin
             .check_permissions()
@@ -764,4 +1512,230 @@ impl ProviderService {
 
         Ok(status)
     }
+
+    fn initialize_plugin(
+        &self, provider_id: i64, config: &ProviderConfig,
+    ) -> DomainResult<(Box<dyn PluginTrait>, Arc<reqwest::Client>)> {
+        let mut plugin = self.create_uninitialized_plugin(&config.provider_type)?;
+
+        let mut plugin_config = config.config.clone();
+        plugin_config.insert("token".to_string(), plugin_token(&config.token));
+
+        let http_client = self
+            .http_client_manager
+            .client_for_provider(&plugin_config)?;
+
+        plugin
+            .initialize(provider_id, plugin_config, Some(Arc::clone(&http_client)))
+            .map_err(|e| DomainError::InvalidConfig(format!("Failed to initialize plugin: {e}")))?;
+
+        Ok((plugin, http_client))
+    }
+
+    // Runs the credential, permission, reachability, clock and rate limit
+    // checks against a provider. Failed checks become findings rather than
+    // errors, so only an unknown provider or plugin fails the call.
+    pub async fn diagnose_provider(&self, provider_id: i64) -> DomainResult<ProviderDiagnosis> {
+        let config = self.repository.get_provider(provider_id).await?;
+        let mut findings = Vec::new();
+
+        match self.initialize_plugin(provider_id, &config) {
+            Ok((plugin, http_client)) => {
+                let authenticated = plugin
+                    .validate_credentials()
+                    .await
+                    .map_err(|e| e.to_string());
+                let auth = auth_finding(authenticated);
+                let auth_failed = auth.severity == FindingSeverity::Error;
+                findings.push(auth);
+
+                if !auth_failed {
+                    findings.extend(
+                        self.diagnose_permissions(provider_id, plugin.as_ref())
+                            .await,
+                    );
+
+                    if let Some(version) = self.detect_server_version(plugin.as_ref()).await {
+                        findings.push(server_version_finding(&version));
+                        // An upgraded or downgraded server may need other
+                        // shims, so the loaded provider is rebuilt.
+                        if self.store_server_version(provider_id, version).await
+                            && self.providers.read().await.contains_key(&provider_id)
+                        {
+                            match self.create_provider(&config) {
+                                Ok(provider) => {
+                                    self.providers.write().await.insert(provider_id, provider);
+                                }
+                                Err(e) => tracing::warn!(
+                                    provider_id,
+                                    error = %e,
+                                    "Failed to reload provider after a server version change"
+                                ),
+                            }
+                        }
+                    }
+                }
+
+                if let Some(endpoint) = plugin.api_endpoint() {
+                    let probe = probe_endpoint(&http_client, &endpoint).await;
+                    findings.push(reachability_finding(&endpoint, &probe));
+                    if let Some(server_time) = probe.ok().and_then(|p| p.server_time) {
+                        findings.push(clock_skew_finding(server_time, Utc::now()));
+                    }
+                }
+
+                if !auth_failed {
+                    match plugin.rate_limit_status().await {
+                        Ok(Some(status)) => findings.push(rate_limit_finding(&status)),
+                        Ok(None) => {}
+                        Err(e) => findings.push(DiagnosticFinding::new(
+                            DiagnosticCheck::RateLimit,
+                            FindingSeverity::Info,
+                            format!("Could not read the rate limit: {}", e),
+                            "Run the diagnosis again later.",
+                        )),
+                    }
+                }
+            }
+            Err(DomainError::InvalidProviderType(e)) => {
+                return Err(DomainError::InvalidProviderType(e));
+            }
+            Err(e) => findings.push(DiagnosticFinding::new(
+                DiagnosticCheck::Auth,
+                FindingSeverity::Error,
+                e.to_string(),
+                "Edit the provider and fix its configuration.",
+            )),
+        }
+
+        let diagnosis = ProviderDiagnosis::new(
+            provider_id,
+            config.display_name().to_string(),
+            config.provider_type.clone(),
+            findings,
+        );
+        self.diagnoses
+            .write()
+            .await
+            .insert(provider_id, diagnosis.clone());
+        Ok(diagnosis)
+    }
+
+    async fn diagnose_permissions(
+        &self, provider_id: i64, plugin: &dyn PluginTrait,
+    ) -> Vec<DiagnosticFinding> {
+        let status = match plugin.check_permissions().await {
+            Ok(status) => status,
+            Err(e) => {
+                return vec![DiagnosticFinding::new(
+                    DiagnosticCheck::Permissions,
+                    FindingSeverity::Warning,
+                    format!("Could not check permissions: {}", e),
+                    "Run the diagnosis again later.",
+                )]
+            }
+        };
+
+        if let Err(e) = self
+            .repository
+            .store_provider_permissions(provider_id, &status)
+            .await
+        {
+            tracing::warn!(error = %e, provider_id, "Failed to store provider permissions");
+        }
+
+        permission_findings(&status, &plugin.get_feature_availability(&status))
+    }
+
+    pub async fn get_provider_diagnoses(&self) -> Vec<ProviderDiagnosis> {
+        let mut diagnoses: Vec<ProviderDiagnosis> =
+            self.diagnoses.read().await.values().cloned().collect();
+        diagnoses.sort_by_key(|d| d.provider_id);
+        diagnoses
+    }
+
+    // Diagnoses every provider, notifying about those that newly have errors.
+    pub async fn run_diagnostics(&self) -> DomainResult<()> {
+        for config in self.repository.list_providers().await? {
+            let Some(provider_id) = config.id.filter(|_| config.enabled) else {
+                continue;
+            };
+            let had_errors = self
+                .diagnoses
+                .read()
+                .await
+                .get(&provider_id)
+                .is_some_and(ProviderDiagnosis::has_errors);
+
+            match self.diagnose_provider(provider_id).await {
+                Ok(diagnosis) if diagnosis.has_errors() && !had_errors => {
+                    self.notification_center
+                        .provider_diagnosis_failed(&diagnosis)
+                        .await;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(error = %e, provider_id, "Provider diagnosis failed");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn start_diagnostics(self: &Arc<Self>) {
+        let mut diagnostics = self.diagnostics.lock().await;
+        if diagnostics.is_some() {
+            return;
+        }
+
+        let service = Arc::clone(self);
+        *diagnostics = Some(tokio::spawn(async move {
+            loop {
+                if let Err(e) = service.run_diagnostics().await {
+                    tracing::error!(error = %e, "Provider diagnostics failed");
+                }
+                tokio::time::sleep(DIAGNOSTICS_INTERVAL).await;
+            }
+        }));
+    }
+
+    pub async fn stop_diagnostics(&self) {
+        if let Some(handle) = self.diagnostics.lock().await.take() {
+            handle.abort();
+        }
+    }
+}
+
+async fn probe_endpoint(client: &reqwest::Client, endpoint: &str) -> Result<EndpointProbe, String> {
+    let started = Instant::now();
+    let response = client
+        .get(endpoint)
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let server_time = response
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .map(|time| time.with_timezone(&Utc));
+
+    Ok(EndpointProbe {
+        status: response.status().as_u16(),
+        latency_ms: started.elapsed().as_millis() as u64,
+        server_time,
+    })
+}
+
+// Token files are for config.toml only: through the API they would let any
+// caller read a file on the server and send it to a provider host they chose.
+fn reject_token_file(token: &str) -> DomainResult<()> {
+    if matches!(TokenReference::parse(token), Ok(TokenReference::File(_))) {
+        return Err(DomainError::InvalidConfig(
+            "File token references are only supported in config.toml".to_string(),
+        ));
+    }
+    Ok(())
 }
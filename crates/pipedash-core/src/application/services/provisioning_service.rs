@@ -0,0 +1,616 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::domain::{
+    resolve_pipeline_reference,
+    validate_channel_url,
+    ChannelKind,
+    DomainError,
+    DomainResult,
+    NotificationRule,
+    Pipeline,
+    PipelineRefreshSetting,
+    PipelineStatus,
+    PlanAction,
+    ProvisionedResource,
+    ProvisioningPlan,
+};
+use crate::infrastructure::config::{
+    ChannelFileConfig,
+    PipedashConfig,
+    ScheduleFileConfig,
+};
+use crate::infrastructure::database::Repository;
+use crate::infrastructure::{
+    ProviderSyncService,
+    TokenStore,
+};
+
+// Reconciles the database with the providers, groups, schedules and
+// notification channels declared in config.toml. Nothing is removed unless
+// `[provisioning] declarative` is on.
+pub struct ProvisioningService {
+    repository: Arc<Repository>,
+    token_store: Arc<dyn TokenStore>,
+}
+
+// A channel rule in comparable form: group names instead of IDs, lowercased,
+// and everything sorted.
+type RuleKey = (Vec<String>, Vec<String>, Vec<String>, bool);
+
+struct PipelineResolver {
+    pipelines: Vec<Pipeline>,
+    provider_ids: HashMap<String, i64>,
+}
+
+impl PipelineResolver {
+    // Resolved IDs and the references that matched nothing.
+    fn resolve(&self, references: &[String]) -> (Vec<String>, Vec<String>) {
+        let mut ids = Vec::new();
+        let mut unresolved = Vec::new();
+        for reference in references {
+            let matched =
+                resolve_pipeline_reference(reference, &self.pipelines, &self.provider_ids);
+            if matched.is_empty() {
+                unresolved.push(reference.clone());
+            }
+            for id in matched {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+        (ids, unresolved)
+    }
+}
+
+impl ProvisioningService {
+    pub fn new(repository: Arc<Repository>, token_store: Arc<dyn TokenStore>) -> Self {
+        Self {
+            repository,
+            token_store,
+        }
+    }
+
+    pub async fn plan(&self, config: &PipedashConfig) -> DomainResult<ProvisioningPlan> {
+        self.reconcile(config, false).await
+    }
+
+    pub async fn apply(&self, config: &PipedashConfig) -> DomainResult<ProvisioningPlan> {
+        self.reconcile(config, true).await
+    }
+
+    async fn reconcile(
+        &self, config: &PipedashConfig, apply: bool,
+    ) -> DomainResult<ProvisioningPlan> {
+        let mut plan = ProvisioningPlan {
+            declarative: config.provisioning.declarative,
+            applied: apply,
+            ..Default::default()
+        };
+        let group_names: Vec<String> = self
+            .repository
+            .list_pipeline_groups()
+            .await?
+            .into_iter()
+            .map(|g| g.name)
+            .collect();
+        Self::validate_channels(config, &group_names)?;
+
+        self.reconcile_providers(config, apply, &mut plan).await?;
+
+        let provider_ids = self
+            .repository
+            .list_providers()
+            .await?
+            .into_iter()
+            .filter_map(|p| Some((p.name, p.id?)))
+            .collect();
+        let resolver = PipelineResolver {
+            pipelines: self.repository.get_cached_pipelines(None).await?,
+            provider_ids,
+        };
+
+        self.reconcile_groups(config, &resolver, apply, &mut plan)
+            .await?;
+        self.reconcile_schedules(config, &resolver, apply, &mut plan)
+            .await?;
+        self.reconcile_channels(config, &resolver, apply, &mut plan)
+            .await?;
+
+        if apply && plan.has_changes() {
+            tracing::info!(
+                created = plan.count(PlanAction::Create),
+                updated = plan.count(PlanAction::Update),
+                deleted = plan.count(PlanAction::Delete),
+                "Applied provisioning from config.toml"
+            );
+        }
+        Ok(plan)
+    }
+
+    async fn reconcile_providers(
+        &self, config: &PipedashConfig, apply: bool, plan: &mut ProvisioningPlan,
+    ) -> DomainResult<()> {
+        let sync_service = ProviderSyncService::new();
+        let config_backend = self.repository.config_backend();
+        let result = if apply {
+            sync_service
+                .sync_toml_to_db(
+                    config.providers.clone(),
+                    config_backend.as_ref(),
+                    self.token_store.as_ref(),
+                    false,
+                )
+                .await?
+        } else {
+            sync_service
+                .plan_toml_to_db(config.providers.clone(), config_backend.as_ref(), false)
+                .await?
+        };
+
+        for name in &result.added {
+            plan.push(
+                ProvisionedResource::Provider,
+                name,
+                PlanAction::Create,
+                None,
+            );
+        }
+        for name in &result.updated {
+            plan.push(
+                ProvisionedResource::Provider,
+                name,
+                PlanAction::Update,
+                None,
+            );
+        }
+        if result.unmanaged.is_empty() {
+            return Ok(());
+        }
+
+        // Orphans go through the repository rather than the sync so their
+        // cached pipelines and run history are removed with them.
+        let providers = self.repository.list_providers().await?;
+        for name in &result.unmanaged {
+            if !plan.declarative {
+                plan.push(
+                    ProvisionedResource::Provider,
+                    name,
+                    PlanAction::Unmanaged,
+                    None,
+                );
+                continue;
+            }
+            if apply {
+                if let Some(id) = providers
+                    .iter()
+                    .find(|p| &p.name == name)
+                    .and_then(|p| p.id)
+                {
+                    self.repository.remove_provider(id).await?;
+                }
+            }
+            plan.push(
+                ProvisionedResource::Provider,
+                name,
+                PlanAction::Delete,
+                None,
+            );
+        }
+        Ok(())
+    }
+
+    async fn reconcile_groups(
+        &self, config: &PipedashConfig, resolver: &PipelineResolver, apply: bool,
+        plan: &mut ProvisioningPlan,
+    ) -> DomainResult<()> {
+        let existing = self.repository.list_pipeline_groups().await?;
+
+        for (name, group) in &config.groups {
+            let (ids, unresolved) = resolver.resolve(&group.pipelines);
+            Self::warn_unresolved(plan, &format!("groups.{}", name), &unresolved);
+
+            let Some(current) = existing.iter().find(|g| g.name.eq_ignore_ascii_case(name)) else {
+                if apply {
+                    let group_id = self.repository.create_pipeline_group(name).await?;
+                    for pipeline_id in &ids {
+                        self.repository
+                            .add_pipeline_to_group(group_id, pipeline_id)
+                            .await?;
+                    }
+                }
+                plan.push(
+                    ProvisionedResource::Group,
+                    name,
+                    PlanAction::Create,
+                    Some(format!("{} pipelines", ids.len())),
+                );
+                continue;
+            };
+
+            // Members are only removed once every reference resolves, so a
+            // provider that hasn't been fetched yet doesn't empty the group.
+            let added: Vec<&String> = ids
+                .iter()
+                .filter(|id| !current.pipeline_ids.contains(id))
+                .collect();
+            let removed: Vec<&String> = if unresolved.is_empty() {
+                current
+                    .pipeline_ids
+                    .iter()
+                    .filter(|id| !ids.contains(id))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            if added.is_empty() && removed.is_empty() {
+                continue;
+            }
+
+            if apply {
+                for pipeline_id in &added {
+                    self.repository
+                        .add_pipeline_to_group(current.id, pipeline_id)
+                        .await?;
+                }
+                for pipeline_id in &removed {
+                    self.repository
+                        .remove_pipeline_from_group(current.id, pipeline_id)
+                        .await?;
+                }
+            }
+            plan.push(
+                ProvisionedResource::Group,
+                &current.name,
+                PlanAction::Update,
+                Some(format!("+{} -{} pipelines", added.len(), removed.len())),
+            );
+        }
+
+        for group in &existing {
+            if config
+                .groups
+                .keys()
+                .any(|name| name.eq_ignore_ascii_case(&group.name))
+            {
+                continue;
+            }
+            if !plan.declarative {
+                plan.push(
+                    ProvisionedResource::Group,
+                    &group.name,
+                    PlanAction::Unmanaged,
+                    None,
+                );
+                continue;
+            }
+            if apply {
+                self.repository.delete_pipeline_group(group.id).await?;
+            }
+            plan.push(
+                ProvisionedResource::Group,
+                &group.name,
+                PlanAction::Delete,
+                None,
+            );
+        }
+        Ok(())
+    }
+
+    async fn reconcile_schedules(
+        &self, config: &PipedashConfig, resolver: &PipelineResolver, apply: bool,
+        plan: &mut ProvisioningPlan,
+    ) -> DomainResult<()> {
+        let existing = self.repository.list_pipeline_refresh_settings().await?;
+        let mut declared = HashSet::new();
+        let mut complete = true;
+
+        for (reference, schedule) in &config.schedules {
+            let key = format!("schedules.\"{}\"", reference);
+            let (ids, unresolved) = resolver.resolve(std::slice::from_ref(reference));
+            if !unresolved.is_empty() {
+                complete = false;
+                Self::warn_unresolved(plan, &key, &unresolved);
+            }
+
+            for pipeline_id in ids {
+                let setting = PipelineRefreshSetting {
+                    pipeline_id: pipeline_id.clone(),
+                    refresh_interval_secs: schedule.refresh_interval,
+                    snoozed: schedule.snoozed,
+                    updated_at: Utc::now(),
+                };
+                setting.validate().map_err(|e| Self::in_key(&key, e))?;
+
+                let action = match existing.iter().find(|s| s.pipeline_id == pipeline_id) {
+                    None => PlanAction::Create,
+                    Some(current)
+                        if current.refresh_interval_secs != schedule.refresh_interval
+                            || current.snoozed != schedule.snoozed =>
+                    {
+                        PlanAction::Update
+                    }
+                    Some(_) => {
+                        declared.insert(pipeline_id);
+                        continue;
+                    }
+                };
+
+                if apply {
+                    self.repository
+                        .save_pipeline_refresh_setting(
+                            &pipeline_id,
+                            schedule.refresh_interval.map(|secs| secs as i64),
+                            schedule.snoozed,
+                        )
+                        .await?;
+                }
+                plan.push(
+                    ProvisionedResource::Schedule,
+                    &pipeline_id,
+                    action,
+                    Some(Self::describe_schedule(schedule)),
+                );
+                declared.insert(pipeline_id);
+            }
+        }
+
+        // With references still unresolved there is no telling which
+        // settings they would have covered.
+        if !complete {
+            return Ok(());
+        }
+        for setting in existing
+            .iter()
+            .filter(|s| !declared.contains(&s.pipeline_id))
+        {
+            if !plan.declarative {
+                plan.push(
+                    ProvisionedResource::Schedule,
+                    &setting.pipeline_id,
+                    PlanAction::Unmanaged,
+                    None,
+                );
+                continue;
+            }
+            if apply {
+                self.repository
+                    .delete_pipeline_refresh_setting(&setting.pipeline_id)
+                    .await?;
+            }
+            plan.push(
+                ProvisionedResource::Schedule,
+                &setting.pipeline_id,
+                PlanAction::Delete,
+                None,
+            );
+        }
+        Ok(())
+    }
+
+    async fn reconcile_channels(
+        &self, config: &PipedashConfig, resolver: &PipelineResolver, apply: bool,
+        plan: &mut ProvisioningPlan,
+    ) -> DomainResult<()> {
+        let existing = self.repository.list_notification_channels().await?;
+        // Groups are reconciled first, so when applying every group a rule
+        // names exists by now.
+        let groups = self.repository.list_pipeline_groups().await?;
+        let group_names: HashMap<i64, String> = groups
+            .iter()
+            .map(|g| (g.id, g.name.to_lowercase()))
+            .collect();
+        let group_ids: HashMap<String, i64> = groups
+            .iter()
+            .map(|g| (g.name.to_lowercase(), g.id))
+            .collect();
+
+        for (name, channel) in &config.notifications.channels {
+            let key = format!("notifications.channels.{}", name);
+            let mut rules = Vec::new();
+            let mut desired = Vec::new();
+            for rule in &channel.rules {
+                let (mut pipeline_ids, unresolved) = resolver.resolve(&rule.pipelines);
+                Self::warn_unresolved(plan, &key, &unresolved);
+                // Kept as written so the rule matches nothing for them rather
+                // than every pipeline.
+                pipeline_ids.extend(unresolved);
+
+                let group_names: Vec<String> =
+                    rule.groups.iter().map(|g| g.to_lowercase()).collect();
+                desired.push(Self::rule_key(
+                    &pipeline_ids,
+                    group_names.clone(),
+                    &rule.statuses,
+                    rule.anomalies,
+                ));
+                rules.push(NotificationRule {
+                    pipeline_ids,
+                    group_ids: group_names
+                        .iter()
+                        .filter_map(|g| group_ids.get(g).copied())
+                        .collect(),
+                    statuses: rule.statuses.clone(),
+                    anomalies: rule.anomalies,
+                });
+            }
+            desired.sort();
+
+            let current = existing.iter().find(|c| &c.name == name);
+            let action = match current {
+                None => PlanAction::Create,
+                Some(current) => {
+                    let mut current_rules: Vec<RuleKey> = current
+                        .rules
+                        .iter()
+                        .map(|rule| {
+                            Self::rule_key(
+                                &rule.pipeline_ids,
+                                rule.group_ids
+                                    .iter()
+                                    .filter_map(|id| group_names.get(id).cloned())
+                                    .collect(),
+                                &rule.statuses,
+                                rule.anomalies,
+                            )
+                        })
+                        .collect();
+                    current_rules.sort();
+
+                    if current.kind == channel.kind
+                        && current.url == channel.url.trim()
+                        && current.enabled == channel.enabled
+                        && current.suppression == channel.suppression
+                        && current_rules == desired
+                    {
+                        continue;
+                    }
+                    PlanAction::Update
+                }
+            };
+
+            if apply {
+                let rules_json = serde_json::to_string(&rules).map_err(|e| {
+                    DomainError::InternalError(format!("Failed to serialize channel rules: {}", e))
+                })?;
+                let suppression_json =
+                    serde_json::to_string(&channel.suppression).map_err(|e| {
+                        DomainError::InternalError(format!(
+                            "Failed to serialize channel suppression: {}",
+                            e
+                        ))
+                    })?;
+                self.repository
+                    .save_notification_channel(
+                        name,
+                        channel.kind.as_str(),
+                        channel.url.trim(),
+                        channel.enabled,
+                        &rules_json,
+                        &suppression_json,
+                    )
+                    .await?;
+            }
+            plan.push(
+                ProvisionedResource::Channel,
+                name,
+                action,
+                Some(format!("{}, {} rules", channel.kind.as_str(), rules.len())),
+            );
+        }
+
+        for channel in &existing {
+            if config.notifications.channels.contains_key(&channel.name) {
+                continue;
+            }
+            if !plan.declarative {
+                plan.push(
+                    ProvisionedResource::Channel,
+                    &channel.name,
+                    PlanAction::Unmanaged,
+                    None,
+                );
+                continue;
+            }
+            if apply {
+                self.repository
+                    .delete_notification_channel(channel.id)
+                    .await?;
+                self.repository
+                    .delete_notification_deliveries(channel.id)
+                    .await?;
+            }
+            plan.push(
+                ProvisionedResource::Channel,
+                &channel.name,
+                PlanAction::Delete,
+                None,
+            );
+        }
+        Ok(())
+    }
+
+    // Checked up front so an invalid channel fails the whole apply before
+    // anything has been written. Rules may name groups from the file, or
+    // existing ones as long as they aren't about to be pruned.
+    fn validate_channels(config: &PipedashConfig, existing_groups: &[String]) -> DomainResult<()> {
+        let mut known_groups: Vec<&String> = config.groups.keys().collect();
+        if !config.provisioning.declarative {
+            known_groups.extend(existing_groups);
+        }
+
+        for (name, channel) in &config.notifications.channels {
+            let key = format!("notifications.channels.{}", name);
+            Self::validate_channel(config, channel).map_err(|e| Self::in_key(&key, e))?;
+
+            for group in channel.rules.iter().flat_map(|rule| &rule.groups) {
+                if !known_groups
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(group))
+                {
+                    return Err(DomainError::InvalidConfig(format!(
+                        "{}: unknown group '{}'",
+                        key, group
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_channel(config: &PipedashConfig, channel: &ChannelFileConfig) -> DomainResult<()> {
+        // Already interpolated when config.toml was loaded.
+        validate_channel_url(channel.kind, channel.url.trim())?;
+        if channel.kind == ChannelKind::Email && config.notifications.smtp.is_none() {
+            return Err(DomainError::InvalidConfig(
+                "Configure [notifications.smtp] in config.toml to use email channels".to_string(),
+            ));
+        }
+        channel.suppression.validate()
+    }
+
+    fn rule_key(
+        pipeline_ids: &[String], mut groups: Vec<String>, statuses: &[PipelineStatus],
+        anomalies: bool,
+    ) -> RuleKey {
+        let mut pipeline_ids = pipeline_ids.to_vec();
+        pipeline_ids.sort();
+        groups.sort();
+        let mut statuses: Vec<String> = statuses.iter().map(|s| s.as_str().to_string()).collect();
+        statuses.sort();
+        (pipeline_ids, groups, statuses, anomalies)
+    }
+
+    fn describe_schedule(schedule: &ScheduleFileConfig) -> String {
+        match (schedule.refresh_interval, schedule.snoozed) {
+            (_, true) => "snoozed".to_string(),
+            (Some(secs), false) => format!("every {}s", secs),
+            (None, false) => "default interval".to_string(),
+        }
+    }
+
+    fn warn_unresolved(plan: &mut ProvisioningPlan, key: &str, unresolved: &[String]) {
+        for reference in unresolved {
+            plan.warnings.push(format!(
+                "{}: '{}' matches no cached pipeline yet; it is picked up on the next apply \
+                 after the provider has been refreshed",
+                key, reference
+            ));
+        }
+    }
+
+    fn in_key(key: &str, error: DomainError) -> DomainError {
+        match error {
+            DomainError::InvalidConfig(message) => {
+                DomainError::InvalidConfig(format!("{}: {}", key, message))
+            }
+            other => other,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use pipedash_plugin_api::Capability;
+
+use super::metrics_service::MetricsService;
+use super::provider_service::ProviderService;
+use crate::domain::{
+    DomainResult,
+    ProviderQueue,
+};
+
+pub struct QueueService {
+    provider_service: Arc<ProviderService>,
+    metrics_service: Option<Arc<MetricsService>>,
+}
+
+impl QueueService {
+    pub fn new(
+        provider_service: Arc<ProviderService>, metrics_service: Option<Arc<MetricsService>>,
+    ) -> Self {
+        Self {
+            provider_service,
+            metrics_service,
+        }
+    }
+
+    pub async fn fetch_queues(&self, provider_id: Option<i64>) -> DomainResult<Vec<ProviderQueue>> {
+        let provider_ids = match provider_id {
+            Some(id) => vec![id],
+            None => {
+                self.provider_service
+                    .provider_ids_with_capability(Capability::Queues)
+                    .await?
+            }
+        };
+
+        let mut all_queues = Vec::new();
+        for id in provider_ids {
+            let provider = self.provider_service.get_provider(id).await?;
+            match provider.fetch_queues().await {
+                Ok(queues) => all_queues.extend(
+                    queues
+                        .into_iter()
+                        .map(|queue| ProviderQueue::new(id, queue)),
+                ),
+                Err(e) if provider_id.is_some() => return Err(e),
+                Err(e) => {
+                    tracing::warn!(provider_id = id, error = %e, "Failed to fetch build queues");
+                }
+            }
+        }
+
+        Ok(all_queues)
+    }
+
+    // Fetches every queue and records depth and wait time samples. Does
+    // nothing when metrics are disabled.
+    pub async fn sample_queues(&self) -> DomainResult<usize> {
+        let Some(metrics_service) = &self.metrics_service else {
+            return Ok(0);
+        };
+
+        let queues = self.fetch_queues(None).await?;
+        metrics_service.record_queue_metrics(&queues).await
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::{
+    DomainResult,
+    GlobalRunRetentionConfig,
+    PipelineRunRetention,
+    RunRetentionPolicy,
+};
+use crate::infrastructure::database::Repository;
+
+pub struct RunRetentionService {
+    repository: Arc<Repository>,
+}
+
+impl RunRetentionService {
+    pub fn new(repository: Arc<Repository>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn get_config(&self) -> DomainResult<GlobalRunRetentionConfig> {
+        self.repository.get_run_retention_config().await
+    }
+
+    pub async fn update_config(
+        &self, enabled: bool, policy: RunRetentionPolicy,
+    ) -> DomainResult<GlobalRunRetentionConfig> {
+        policy.validate()?;
+        self.repository
+            .update_run_retention_config(enabled, policy)
+            .await?;
+        self.get_config().await
+    }
+
+    pub async fn list_pipeline_overrides(&self) -> DomainResult<Vec<PipelineRunRetention>> {
+        self.repository.list_pipeline_run_retention().await
+    }
+
+    // An override without any limit would just mirror the global policy, so it
+    // is removed instead of stored.
+    pub async fn set_pipeline_override(
+        &self, pipeline_id: &str, policy: RunRetentionPolicy,
+    ) -> DomainResult<()> {
+        policy.validate()?;
+        if policy.is_unlimited() {
+            return self.remove_pipeline_override(pipeline_id).await;
+        }
+        self.repository
+            .set_pipeline_run_retention(pipeline_id, policy)
+            .await
+    }
+
+    pub async fn remove_pipeline_override(&self, pipeline_id: &str) -> DomainResult<()> {
+        self.repository
+            .delete_pipeline_run_retention(pipeline_id)
+            .await
+    }
+
+    pub async fn prune_run_history(&self) -> DomainResult<usize> {
+        let config = self.get_config().await?;
+        if !config.enabled {
+            return Ok(0);
+        }
+
+        let overrides: HashMap<String, PipelineRunRetention> = self
+            .list_pipeline_overrides()
+            .await?
+            .into_iter()
+            .map(|o| (o.pipeline_id.clone(), o))
+            .collect();
+
+        let start = std::time::Instant::now();
+        let mut deleted = 0;
+
+        for pipeline_id in self.repository.list_run_history_pipeline_ids().await? {
+            let policy = config.policy_for(overrides.get(&pipeline_id));
+            if policy.is_unlimited() {
+                continue;
+            }
+
+            match self
+                .repository
+                .prune_run_history(&pipeline_id, policy)
+                .await
+            {
+                Ok(count) => deleted += count,
+                Err(e) => {
+                    tracing::warn!(
+                        pipeline_id = %pipeline_id,
+                        error = %e,
+                        "Failed to prune run history"
+                    );
+                }
+            }
+        }
+
+        self.repository.record_run_history_prune(deleted).await?;
+
+        tracing::debug!(
+            deleted = deleted,
+            duration_ms = start.elapsed().as_millis(),
+            "Pruned run history cache"
+        );
+
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use super::notification_center::NotificationCenter;
+use super::provider_service::ProviderService;
+use crate::application::AccessControl;
+use crate::domain::Role;
+use crate::event::EventBus;
+use crate::infrastructure::database::{
+    init_database,
+    DatabasePool,
+    Repository,
+};
+use crate::infrastructure::{
+    HttpClientManager,
+    MemoryTokenStore,
+    SqliteConfigBackend,
+};
+use crate::NoOpEventBus;
+
+// The pieces most service tests are built from: a repository on a fresh
+// SQLite database in `dir` and an admin provider service on top of it.
+pub(crate) struct TestServices {
+    pub repository: Arc<Repository>,
+    pub event_bus: Arc<dyn EventBus>,
+    pub access_control: Arc<AccessControl>,
+    pub http_client_manager: Arc<HttpClientManager>,
+    pub notification_center: Arc<NotificationCenter>,
+    pub provider_service: Arc<ProviderService>,
+}
+
+pub(crate) async fn test_services(dir: &Path) -> TestServices {
+    let pool = init_database(dir.join("test.db")).await.unwrap();
+    let repository = Arc::new(Repository::new(
+        Arc::new(SqliteConfigBackend::new(pool.clone())),
+        DatabasePool::Sqlite(pool),
+        Arc::new(MemoryTokenStore::new()),
+    ));
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    let event_bus: Arc<dyn EventBus> = Arc::new(NoOpEventBus);
+    let access_control = Arc::new(AccessControl::new(Role::Admin));
+    let http_client_manager = Arc::new(HttpClientManager::new().unwrap());
+    let notification_center = Arc::new(NotificationCenter::new(
+        Arc::clone(&repository),
+        Arc::clone(&event_bus),
+    ));
+    let provider_service = Arc::new(ProviderService::new(
+        Arc::clone(&repository),
+        Arc::clone(&http_client_manager),
+        Arc::clone(&event_bus),
+        Arc::clone(&access_control),
+        Default::default(),
+        Arc::clone(&notification_center),
+    ));
+
+    TestServices {
+        repository,
+        event_bus,
+        access_control,
+        http_client_manager,
+        notification_center,
+        provider_service,
+    }
+}
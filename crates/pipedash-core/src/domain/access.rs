@@ -0,0 +1,109 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Viewer,
+    Operator,
+    // Single-user installs have no notion of identity, so everything stays
+    // allowed unless a role is configured explicitly.
+    #[default]
+    Admin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    TriggerPipeline,
+    CancelRun,
+    ManageProviders,
+    ApproveTrigger,
+    ViewAuditLog,
+    ManageMaintenance,
+    ManageNotifications,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Viewer => "viewer",
+            Self::Operator => "operator",
+            Self::Admin => "admin",
+        }
+    }
+
+    pub fn allows(&self, action: Action) -> bool {
+        match self {
+            Self::Viewer => false,
+            Self::Operator => matches!(action, Action::TriggerPipeline | Action::CancelRun),
+            Self::Admin => true,
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "viewer" => Ok(Self::Viewer),
+            "operator" => Ok(Self::Operator),
+            "admin" => Ok(Self::Admin),
+            _ => Err(format!(
+                "Unknown role: {}. Valid options: viewer, operator, admin",
+                s
+            )),
+        }
+    }
+}
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TriggerPipeline => "trigger pipelines",
+            Self::CancelRun => "cancel runs",
+            Self::ManageProviders => "manage providers",
+            Self::ApproveTrigger => "approve triggers",
+            Self::ViewAuditLog => "view the audit log",
+            Self::ManageMaintenance => "manage maintenance windows",
+            Self::ManageNotifications => "manage notification channels",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_permissions() {
+        assert!(!Role::Viewer.allows(Action::TriggerPipeline));
+        assert!(!Role::Viewer.allows(Action::ManageProviders));
+
+        assert!(Role::Operator.allows(Action::TriggerPipeline));
+        assert!(Role::Operator.allows(Action::CancelRun));
+        assert!(!Role::Operator.allows(Action::ManageProviders));
+        assert!(!Role::Operator.allows(Action::ApproveTrigger));
+        assert!(!Role::Operator.allows(Action::ViewAuditLog));
+        assert!(!Role::Operator.allows(Action::ManageMaintenance));
+        assert!(!Role::Operator.allows(Action::ManageNotifications));
+
+        assert!(Role::Admin.allows(Action::ManageProviders));
+        assert!(Role::Admin.allows(Action::ViewAuditLog));
+    }
+
+    #[test]
+    fn test_role_from_str() {
+        assert_eq!("Operator".parse::<Role>(), Ok(Role::Operator));
+        assert!("owner".parse::<Role>().is_err());
+    }
+}
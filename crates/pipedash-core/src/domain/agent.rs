@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+pub use pipedash_plugin_api::BuildAgent;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderAgent {
+    pub provider_id: i64,
+    #[serde(flatten)]
+    pub agent: BuildAgent,
+}
+
+// Agents that were online in `previous` and are offline in `current`. Agents
+// seen for the first time are skipped, so the first fetch after startup or
+// after adding a provider doesn't report every offline agent at once.
+pub fn newly_offline(previous: &[BuildAgent], current: &[BuildAgent]) -> Vec<BuildAgent> {
+    let previous: HashMap<&str, &BuildAgent> = previous
+        .iter()
+        .map(|agent| (agent.id.as_str(), agent))
+        .collect();
+
+    current
+        .iter()
+        .filter(|agent| agent.is_offline())
+        .filter(|agent| {
+            previous
+                .get(agent.id.as_str())
+                .is_some_and(|before| !before.is_offline())
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn agent(id: &str, status: &str) -> BuildAgent {
+        BuildAgent {
+            id: id.to_string(),
+            name: id.to_string(),
+            hostname: id.to_string(),
+            status: status.to_string(),
+            job_id: None,
+            last_seen: Utc::now(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_newly_offline() {
+        let previous = vec![
+            agent("a", "busy"),
+            agent("b", "offline"),
+            agent("c", "idle"),
+        ];
+        let current = vec![
+            agent("a", "offline"),
+            agent("b", "offline"),
+            agent("c", "paused"),
+            agent("d", "offline"),
+        ];
+
+        let offline: Vec<String> = newly_offline(&previous, &current)
+            .into_iter()
+            .map(|agent| agent.id)
+            .collect();
+
+        assert_eq!(offline, vec!["a".to_string()]);
+    }
+}
@@ -0,0 +1,186 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+use super::pipeline::TriggerParams;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    // Approved, but the provider refused the trigger.
+    Failed,
+}
+
+impl ApprovalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalStatus::Pending => "pending",
+            ApprovalStatus::Approved => "approved",
+            ApprovalStatus::Rejected => "rejected",
+            ApprovalStatus::Failed => "failed",
+        }
+    }
+}
+
+// A trigger held back until someone approves it. The provider is only called
+// once the request is approved, with the parameters captured here.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerApproval {
+    #[serde(default)]
+    pub id: i64,
+    pub pipeline_id: String,
+    pub pipeline_name: String,
+    pub params: TriggerParams,
+    pub status: ApprovalStatus,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<DateTime<Utc>>,
+    // Rejection reason, or the provider's error when the trigger failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    // What the provider returned for the trigger.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+}
+
+impl TriggerApproval {
+    pub fn new(pipeline_name: &str, params: TriggerParams, requested_by: &str) -> Self {
+        Self {
+            id: 0,
+            pipeline_id: params.workflow_id.clone(),
+            pipeline_name: pipeline_name.to_string(),
+            params,
+            status: ApprovalStatus::Pending,
+            requested_by: requested_by.to_string(),
+            requested_at: Utc::now(),
+            decided_by: None,
+            decided_at: None,
+            comment: None,
+            result: None,
+        }
+    }
+
+    // Two-person rule: the requester can only decide on their own request
+    // when self-approval is allowed, and then only with an explicit confirm.
+    // Withdrawing (rejecting) your own request is always fine.
+    pub fn ensure_decidable(
+        &self, actor: &str, approve: bool, confirmed: bool, allow_self_approval: bool,
+    ) -> DomainResult<()> {
+        if self.status != ApprovalStatus::Pending {
+            return Err(DomainError::InvalidConfig(format!(
+                "Trigger request {} is already {}",
+                self.id,
+                self.status.as_str()
+            )));
+        }
+        if !approve || actor != self.requested_by {
+            return Ok(());
+        }
+        if !allow_self_approval {
+            return Err(DomainError::PermissionDenied(format!(
+                "trigger request {} must be approved by someone other than '{}'",
+                self.id, self.requested_by
+            )));
+        }
+        if !confirmed {
+            return Err(DomainError::InvalidConfig(
+                "Approving your own trigger request needs an explicit confirmation".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn decide(&mut self, status: ApprovalStatus, actor: &str, comment: Option<String>) {
+        self.status = status;
+        self.decided_by = Some(actor.to_string());
+        self.decided_at = Some(Utc::now());
+        self.comment = comment;
+    }
+
+    // Stands in for the provider's response when a trigger is held, so
+    // callers that parse the trigger result can tell it apart from a run.
+    pub fn pending_response(&self) -> String {
+        serde_json::json!({
+            "approval_id": self.id,
+            "status": self.status.as_str(),
+            "message": format!("{} needs approval before it runs", self.pipeline_name),
+        })
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approval() -> TriggerApproval {
+        TriggerApproval::new(
+            "Deploy",
+            TriggerParams {
+                workflow_id: "github__1__acme/api__deploy".to_string(),
+                inputs: None,
+            },
+            "operator",
+        )
+    }
+
+    #[test]
+    fn test_two_person_rule() {
+        let request = approval();
+
+        assert!(request
+            .ensure_decidable("admin", true, false, false)
+            .is_ok());
+        assert!(matches!(
+            request.ensure_decidable("operator", true, true, false),
+            Err(DomainError::PermissionDenied(_))
+        ));
+        assert!(request
+            .ensure_decidable("operator", true, false, true)
+            .is_err());
+        assert!(request
+            .ensure_decidable("operator", true, true, true)
+            .is_ok());
+        assert!(request
+            .ensure_decidable("operator", false, false, false)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_decided_requests_are_final() {
+        let mut request = approval();
+        request.decide(
+            ApprovalStatus::Rejected,
+            "admin",
+            Some("Freeze".to_string()),
+        );
+
+        assert_eq!(request.decided_by.as_deref(), Some("admin"));
+        assert!(request.ensure_decidable("admin", true, true, true).is_err());
+    }
+
+    #[test]
+    fn test_pending_response() {
+        let mut request = approval();
+        request.id = 12;
+        let response: serde_json::Value =
+            serde_json::from_str(&request.pending_response()).unwrap();
+        assert_eq!(response["approval_id"], 12);
+        assert_eq!(response["status"], "pending");
+    }
+}
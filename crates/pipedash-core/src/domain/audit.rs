@@ -0,0 +1,73 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    // A wrong vault password or bearer token.
+    AuthFailed,
+    // A client hit the rate limit on an authentication endpoint.
+    AuthRateLimited,
+    // A client was locked out after repeated authentication failures.
+    AuthLockout,
+    // A trigger on a pipeline that needs approval was held for review.
+    TriggerRequested,
+    TriggerApproved,
+    TriggerRejected,
+    // A pipeline was marked as needing approval, or no longer needing it.
+    ApprovalRuleChanged,
+    // A maintenance window was saved or deleted.
+    MaintenanceWindowChanged,
+    // A run waiting on a block step was let through.
+    JobUnblocked,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::AuthFailed => "auth_failed",
+            AuditAction::AuthRateLimited => "auth_rate_limited",
+            AuditAction::AuthLockout => "auth_lockout",
+            AuditAction::TriggerRequested => "trigger_requested",
+            AuditAction::TriggerApproved => "trigger_approved",
+            AuditAction::TriggerRejected => "trigger_rejected",
+            AuditAction::ApprovalRuleChanged => "approval_rule_changed",
+            AuditAction::MaintenanceWindowChanged => "maintenance_window_changed",
+            AuditAction::JobUnblocked => "job_unblocked",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    #[serde(default)]
+    pub id: i64,
+    pub action: AuditAction,
+    // Who did it: a client address for authentication events, the role or
+    // local user for everything else.
+    pub actor: String,
+    // What it was done to: a request path or a pipeline ID.
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    pub fn new(action: AuditAction, actor: &str, target: &str, detail: Option<String>) -> Self {
+        Self {
+            id: 0,
+            action,
+            actor: actor.to_string(),
+            target: target.to_string(),
+            detail,
+            created_at: Utc::now(),
+        }
+    }
+}
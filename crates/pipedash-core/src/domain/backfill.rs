@@ -0,0 +1,158 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+
+pub const DEFAULT_BACKFILL_DEPTH: usize = 1000;
+pub const MAX_BACKFILL_DEPTH: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackfillStatus {
+    Running,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+impl BackfillStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackfillStatus::Running => "running",
+            BackfillStatus::Completed => "completed",
+            BackfillStatus::Cancelled => "cancelled",
+            BackfillStatus::Failed => "failed",
+        }
+    }
+}
+
+// Imports up to `depth` runs of history for every pipeline of a provider.
+// Pipelines are done one at a time and dropped from `pending_pipelines` once
+// their runs are stored, so an interrupted job picks up where it stopped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunBackfillJob {
+    pub provider_id: i64,
+    pub depth: usize,
+    pub status: BackfillStatus,
+    pub pending_pipelines: Vec<String>,
+    pub pipelines_total: usize,
+    pub runs_fetched: usize,
+    // Starts at `depth` runs per pipeline and shrinks as pipelines turn out
+    // to have less history than that.
+    pub runs_target: usize,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RunBackfillJob {
+    pub fn new(provider_id: i64, depth: usize, pipelines: Vec<String>) -> Self {
+        let now = Utc::now();
+        let mut job = Self {
+            provider_id,
+            depth,
+            status: BackfillStatus::Running,
+            pipelines_total: pipelines.len(),
+            runs_target: pipelines.len() * depth,
+            pending_pipelines: pipelines,
+            runs_fetched: 0,
+            started_at: now,
+            updated_at: now,
+            error: None,
+        };
+        if job.pending_pipelines.is_empty() {
+            job.finish(BackfillStatus::Completed, None);
+        }
+        job
+    }
+
+    pub fn validate_depth(depth: usize) -> DomainResult<()> {
+        if depth == 0 || depth > MAX_BACKFILL_DEPTH {
+            return Err(DomainError::InvalidConfig(format!(
+                "Backfill depth must be between 1 and {}",
+                MAX_BACKFILL_DEPTH
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.status == BackfillStatus::Running
+    }
+
+    pub fn next_pipeline(&self) -> Option<&str> {
+        self.pending_pipelines.first().map(String::as_str)
+    }
+
+    // Marks a pipeline as done after `runs` of its runs were imported. The
+    // job completes with the last pipeline.
+    pub fn record_pipeline(&mut self, pipeline_id: &str, runs: usize) {
+        let before = self.pending_pipelines.len();
+        self.pending_pipelines.retain(|id| id != pipeline_id);
+        if self.pending_pipelines.len() == before {
+            return;
+        }
+
+        let runs = runs.min(self.depth);
+        self.runs_fetched += runs;
+        self.runs_target -= self.depth - runs;
+        self.updated_at = Utc::now();
+
+        if self.pending_pipelines.is_empty() {
+            self.finish(BackfillStatus::Completed, None);
+        }
+    }
+
+    pub fn finish(&mut self, status: BackfillStatus, error: Option<String>) {
+        self.status = status;
+        self.error = error;
+        self.updated_at = Utc::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_shrinks_to_available_history() {
+        let mut job = RunBackfillJob::new(1, 100, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(job.runs_target, 200);
+
+        job.record_pipeline("a", 40);
+        assert_eq!((job.runs_fetched, job.runs_target), (40, 140));
+        assert_eq!(job.next_pipeline(), Some("b"));
+        assert!(job.is_running());
+
+        job.record_pipeline("a", 40);
+        assert_eq!(job.runs_fetched, 40);
+
+        job.record_pipeline("b", 250);
+        assert_eq!((job.runs_fetched, job.runs_target), (140, 140));
+        assert_eq!(job.status, BackfillStatus::Completed);
+    }
+
+    #[test]
+    fn test_empty_job_is_complete() {
+        let job = RunBackfillJob::new(1, 100, Vec::new());
+        assert_eq!(job.status, BackfillStatus::Completed);
+        assert_eq!(job.runs_target, 0);
+    }
+
+    #[test]
+    fn test_validate_depth() {
+        assert!(RunBackfillJob::validate_depth(0).is_err());
+        assert!(RunBackfillJob::validate_depth(DEFAULT_BACKFILL_DEPTH).is_ok());
+        assert!(RunBackfillJob::validate_depth(MAX_BACKFILL_DEPTH + 1).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    DomainError,
+    DomainResult,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupFormat {
+    // Standalone database file written by `VACUUM INTO`.
+    Sqlite,
+    // JSON document holding the rows of every table.
+    Postgres,
+}
+
+impl BackupFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            BackupFormat::Sqlite => "db",
+            BackupFormat::Postgres => "json",
+        }
+    }
+}
+
+// Stored next to each backup so backups can be listed and checked without
+// downloading them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub id: String,
+    pub format: BackupFormat,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+    pub sha256: String,
+}
+
+impl BackupInfo {
+    pub fn data_key(&self) -> String {
+        format!("{}.{}", self.id, self.format.extension())
+    }
+
+    pub fn manifest_key(id: &str) -> String {
+        format!("{}.manifest.json", id)
+    }
+}
+
+// Ids become storage keys, so anything that could escape the backup location
+// is rejected.
+pub fn validate_backup_id(id: &str) -> DomainResult<()> {
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(DomainError::InvalidConfig(format!(
+            "Invalid backup id: {}",
+            id
+        )))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupVerification {
+    pub id: String,
+    pub valid: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_keys() {
+        let info = BackupInfo {
+            id: "pipedash-20250101-000000".to_string(),
+            format: BackupFormat::Sqlite,
+            created_at: Utc::now(),
+            size_bytes: 0,
+            sha256: String::new(),
+        };
+
+        assert_eq!(info.data_key(), "pipedash-20250101-000000.db");
+        assert_eq!(
+            BackupInfo::manifest_key(&info.id),
+            "pipedash-20250101-000000.manifest.json"
+        );
+    }
+
+    #[test]
+    fn test_validate_backup_id() {
+        assert!(validate_backup_id("pipedash-20250101-000000").is_ok());
+        assert!(validate_backup_id("").is_err());
+        assert!(validate_backup_id("../pipedash").is_err());
+        assert!(validate_backup_id("a/b").is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+use super::group::aggregate_status;
+use super::pipeline::{
+    Pipeline,
+    PipelineRun,
+    PipelineStatus,
+};
+
+// Shorter prefixes match too many unrelated commits to be useful.
+pub const MIN_COMMIT_PREFIX_LEN: usize = 7;
+
+// Lowercases a full or abbreviated SHA so it can be matched against the run
+// cache as a prefix.
+pub fn normalize_commit_prefix(sha: &str) -> DomainResult<String> {
+    let sha = sha.trim().to_ascii_lowercase();
+
+    if sha.len() < MIN_COMMIT_PREFIX_LEN
+        || sha.len() > 64
+        || !sha.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Err(DomainError::InvalidConfig(format!(
+            "Invalid commit SHA '{}': expected {} to 64 hex characters",
+            sha, MIN_COMMIT_PREFIX_LEN
+        )));
+    }
+
+    Ok(sha)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitRun {
+    pub pipeline_id: String,
+    pub pipeline_name: String,
+    pub provider_id: i64,
+    pub provider_type: String,
+    pub run: PipelineRun,
+}
+
+// Every cached pipeline that built a commit, with its latest run of it.
+// `green` means all of them succeeded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitStatus {
+    pub sha: String,
+    pub status: PipelineStatus,
+    pub green: bool,
+    pub total: usize,
+    pub failed: usize,
+    pub running: usize,
+    pub runs: Vec<CommitRun>,
+}
+
+impl CommitStatus {
+    // Runs of pipelines no longer in the cache are left out. A prefix that
+    // matches more than one commit is rejected rather than mixing them.
+    pub fn new(prefix: &str, runs: Vec<PipelineRun>, pipelines: &[Pipeline]) -> DomainResult<Self> {
+        let mut shas: Vec<&str> = runs
+            .iter()
+            .filter_map(|run| run.commit_sha.as_deref())
+            .collect();
+        shas.sort_unstable();
+        shas.dedup();
+
+        if shas.len() > 1 {
+            return Err(DomainError::InvalidConfig(format!(
+                "Commit prefix '{}' is ambiguous: matches {}",
+                prefix,
+                shas.join(", ")
+            )));
+        }
+        let sha = shas
+            .first()
+            .map(|sha| sha.to_string())
+            .unwrap_or_else(|| prefix.to_string());
+
+        let by_id: HashMap<&str, &Pipeline> =
+            pipelines.iter().map(|p| (p.id.as_str(), p)).collect();
+        let mut latest: HashMap<String, CommitRun> = HashMap::new();
+
+        for run in runs {
+            let Some(pipeline) = by_id.get(run.pipeline_id.as_str()) else {
+                continue;
+            };
+            if latest
+                .get(&run.pipeline_id)
+                .is_some_and(|existing| existing.run.run_number >= run.run_number)
+            {
+                continue;
+            }
+            latest.insert(
+                run.pipeline_id.clone(),
+                CommitRun {
+                    pipeline_id: pipeline.id.clone(),
+                    pipeline_name: pipeline.name.clone(),
+                    provider_id: pipeline.provider_id,
+                    provider_type: pipeline.provider_type.clone(),
+                    run,
+                },
+            );
+        }
+
+        let mut runs: Vec<CommitRun> = latest.into_values().collect();
+        runs.sort_by(|a, b| {
+            (a.provider_id, &a.pipeline_name).cmp(&(b.provider_id, &b.pipeline_name))
+        });
+
+        let statuses: Vec<&PipelineStatus> = runs.iter().map(|r| &r.run.status).collect();
+        let count = |status: PipelineStatus| statuses.iter().filter(|s| ***s == status).count();
+        let status = aggregate_status(&statuses);
+
+        Ok(Self {
+            sha,
+            green: !runs.is_empty() && status == PipelineStatus::Success,
+            status,
+            total: runs.len(),
+            failed: count(PipelineStatus::Failed),
+            running: count(PipelineStatus::Running),
+            runs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn pipeline(id: &str, provider_id: i64, provider_type: &str) -> Pipeline {
+        Pipeline {
+            id: id.to_string(),
+            provider_id,
+            provider_type: provider_type.to_string(),
+            name: id.to_string(),
+            status: PipelineStatus::Success,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "acme/api".to_string(),
+            branch: None,
+            workflow_file: None,
+            metadata: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    fn run(pipeline_id: &str, run_number: i64, sha: &str, status: PipelineStatus) -> PipelineRun {
+        PipelineRun {
+            id: format!("{}-{}", pipeline_id, run_number),
+            pipeline_id: pipeline_id.to_string(),
+            run_number,
+            status,
+            started_at: Utc::now(),
+            concluded_at: None,
+            duration_seconds: None,
+            logs_url: String::new(),
+            commit_sha: Some(sha.to_string()),
+            commit_message: None,
+            branch: None,
+            actor: None,
+            commit_author: None,
+            author_avatar_url: None,
+            pull_request: None,
+            inputs: None,
+            metadata: HashMap::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_commit_prefix() {
+        assert_eq!(normalize_commit_prefix(" ABC1234 ").unwrap(), "abc1234");
+        assert!(normalize_commit_prefix("abc12").is_err());
+        assert!(normalize_commit_prefix("abc123z").is_err());
+    }
+
+    #[test]
+    fn test_commit_status_uses_latest_run_per_pipeline() {
+        let sha = "abc1234def5678";
+        let pipelines = vec![pipeline("ci", 1, "github"), pipeline("cd", 2, "argocd")];
+        let runs = vec![
+            run("ci", 4, sha, PipelineStatus::Failed),
+            run("ci", 5, sha, PipelineStatus::Success),
+            run("cd", 9, sha, PipelineStatus::Success),
+            run("gone", 1, sha, PipelineStatus::Failed),
+        ];
+
+        let status = CommitStatus::new("abc1234", runs, &pipelines).unwrap();
+        assert_eq!(status.sha, sha);
+        assert!(status.green);
+        assert_eq!((status.total, status.failed), (2, 0));
+        assert_eq!(status.runs[0].pipeline_id, "ci");
+        assert_eq!(status.runs[0].run.run_number, 5);
+
+        let runs = vec![
+            run("ci", 5, sha, PipelineStatus::Success),
+            run("cd", 10, sha, PipelineStatus::Running),
+        ];
+        let status = CommitStatus::new(sha, runs, &pipelines).unwrap();
+        assert_eq!(status.status, PipelineStatus::Running);
+        assert!(!status.green);
+    }
+
+    #[test]
+    fn test_commit_status_rejects_ambiguous_prefix() {
+        let pipelines = vec![pipeline("ci", 1, "github")];
+        let runs = vec![
+            run("ci", 1, "abc1234aaaa", PipelineStatus::Success),
+            run("ci", 2, "abc1234bbbb", PipelineStatus::Success),
+        ];
+
+        assert!(CommitStatus::new("abc1234", runs, &pipelines).is_err());
+
+        let empty = CommitStatus::new("abc1234", Vec::new(), &pipelines).unwrap();
+        assert!(!empty.green);
+        assert_eq!(empty.status, PipelineStatus::Pending);
+    }
+}
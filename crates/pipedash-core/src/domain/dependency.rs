@@ -0,0 +1,204 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+    VecDeque,
+};
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::access::Role;
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+use super::pipeline::{
+    Pipeline,
+    PipelineStatus,
+};
+
+// When `upstream_id` finishes with `trigger_on`, `downstream_id` is triggered
+// with `inputs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineDependency {
+    pub id: i64,
+    pub upstream_id: String,
+    pub downstream_id: String,
+    pub trigger_on: PipelineStatus,
+    #[serde(default)]
+    pub inputs: HashMap<String, serde_json::Value>,
+    // Role of whoever saved the dependency; downstream triggers run with it.
+    // Unset on dependencies saved before roles were recorded.
+    #[serde(default)]
+    pub created_by_role: Option<Role>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineGraphNode {
+    pub pipeline_id: String,
+    // Unset when the pipeline is no longer in the cache.
+    pub name: Option<String>,
+    pub status: Option<PipelineStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineGraph {
+    pub nodes: Vec<PipelineGraphNode>,
+    pub edges: Vec<PipelineDependency>,
+}
+
+impl PipelineGraph {
+    pub fn build(edges: Vec<PipelineDependency>, pipelines: &[Pipeline]) -> Self {
+        let mut seen = HashSet::new();
+        let nodes = edges
+            .iter()
+            .flat_map(|edge| [&edge.upstream_id, &edge.downstream_id])
+            .filter(|id| seen.insert(id.as_str()))
+            .map(|id| {
+                let pipeline = pipelines.iter().find(|p| &p.id == id);
+                PipelineGraphNode {
+                    pipeline_id: id.clone(),
+                    name: pipeline.map(|p| p.name.clone()),
+                    status: pipeline.map(|p| p.status.clone()),
+                }
+            })
+            .collect();
+
+        Self { nodes, edges }
+    }
+}
+
+// Adding `upstream -> downstream` closes a cycle if `upstream` is already
+// reachable from `downstream`.
+pub fn validate_dependency(
+    edges: &[PipelineDependency], upstream_id: &str, downstream_id: &str,
+    trigger_on: &PipelineStatus,
+) -> DomainResult<()> {
+    if !trigger_on.is_terminal() {
+        return Err(DomainError::InvalidConfig(format!(
+            "Dependencies can only trigger on a finished status, not '{}'",
+            trigger_on.as_str()
+        )));
+    }
+
+    let mut queue = VecDeque::from([downstream_id]);
+    let mut visited = HashSet::new();
+    while let Some(current) = queue.pop_front() {
+        if current == upstream_id {
+            return Err(DomainError::InvalidConfig(format!(
+                "Triggering '{}' from '{}' would create a cycle",
+                downstream_id, upstream_id
+            )));
+        }
+        if !visited.insert(current) {
+            continue;
+        }
+        queue.extend(
+            edges
+                .iter()
+                .filter(|edge| edge.upstream_id == current)
+                .map(|edge| edge.downstream_id.as_str()),
+        );
+    }
+
+    Ok(())
+}
+
+// Pipelines that reached a new run or status since the previous snapshot.
+// Pipelines not present before are skipped so a first fetch triggers nothing.
+pub fn finished_pipelines<'a>(previous: &[Pipeline], current: &'a [Pipeline]) -> Vec<&'a Pipeline> {
+    current
+        .iter()
+        .filter(|pipeline| pipeline.status.is_terminal())
+        .filter(|pipeline| {
+            previous.iter().any(|old| {
+                old.id == pipeline.id
+                    && (old.status != pipeline.status || old.last_run != pipeline.last_run)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(upstream: &str, downstream: &str) -> PipelineDependency {
+        PipelineDependency {
+            id: 0,
+            upstream_id: upstream.to_string(),
+            downstream_id: downstream.to_string(),
+            trigger_on: PipelineStatus::Success,
+            inputs: HashMap::new(),
+            created_by_role: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn pipeline(id: &str, status: PipelineStatus) -> Pipeline {
+        Pipeline {
+            id: id.to_string(),
+            provider_id: 1,
+            provider_type: "github".to_string(),
+            name: id.to_string(),
+            status,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "org/repo".to_string(),
+            branch: None,
+            workflow_file: None,
+            metadata: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_validate_dependency_detects_cycles() {
+        let edges = vec![dependency("a", "b"), dependency("b", "c")];
+
+        assert!(validate_dependency(&edges, "c", "d", &PipelineStatus::Success).is_ok());
+        assert!(validate_dependency(&edges, "c", "a", &PipelineStatus::Success).is_err());
+        assert!(validate_dependency(&edges, "a", "a", &PipelineStatus::Success).is_err());
+        assert!(validate_dependency(&edges, "c", "d", &PipelineStatus::Running).is_err());
+    }
+
+    #[test]
+    fn test_finished_pipelines() {
+        let previous = vec![
+            pipeline("a", PipelineStatus::Running),
+            pipeline("b", PipelineStatus::Running),
+            pipeline("c", PipelineStatus::Success),
+        ];
+        let current = vec![
+            pipeline("a", PipelineStatus::Success),
+            pipeline("b", PipelineStatus::Running),
+            pipeline("c", PipelineStatus::Success),
+            pipeline("d", PipelineStatus::Failed),
+        ];
+
+        let finished: Vec<&str> = finished_pipelines(&previous, &current)
+            .iter()
+            .map(|p| p.id.as_str())
+            .collect();
+        assert_eq!(finished, vec!["a"]);
+    }
+
+    #[test]
+    fn test_graph_nodes_are_unique() {
+        let graph = PipelineGraph::build(
+            vec![dependency("a", "b"), dependency("a", "c")],
+            &[pipeline("a", PipelineStatus::Success)],
+        );
+
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.nodes[0].name.as_deref(), Some("a"));
+        assert!(graph.nodes[1].status.is_none());
+    }
+}
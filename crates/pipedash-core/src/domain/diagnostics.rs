@@ -0,0 +1,469 @@
+use std::collections::BTreeMap;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use pipedash_plugin_api::{
+    FeatureAvailability,
+    PermissionStatus,
+    RateLimitStatus,
+    ServerVersion,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+pub const CLOCK_SKEW_WARNING_SECS: i64 = 30;
+pub const CLOCK_SKEW_ERROR_SECS: i64 = 300;
+pub const SLOW_RESPONSE_MS: u64 = 3_000;
+// Warn once less than a tenth of the rate limit is left.
+const RATE_LIMIT_LOW_DIVISOR: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticCheck {
+    Auth,
+    Permissions,
+    Reachability,
+    ClockSkew,
+    RateLimit,
+    ServerVersion,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSeverity {
+    Ok,
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticFinding {
+    pub check: DiagnosticCheck,
+    pub severity: FindingSeverity,
+    pub message: String,
+    // What the user can do about it, for anything worse than `Ok`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl DiagnosticFinding {
+    pub fn ok(check: DiagnosticCheck, message: String) -> Self {
+        Self {
+            check,
+            severity: FindingSeverity::Ok,
+            message,
+            suggestion: None,
+        }
+    }
+
+    pub fn new(
+        check: DiagnosticCheck, severity: FindingSeverity, message: String, suggestion: &str,
+    ) -> Self {
+        Self {
+            check,
+            severity,
+            message,
+            suggestion: Some(suggestion.to_string()),
+        }
+    }
+}
+
+// Response of an unauthenticated request to the provider's API endpoint.
+#[derive(Debug, Clone)]
+pub struct EndpointProbe {
+    pub status: u16,
+    pub latency_ms: u64,
+    // From the `Date` response header.
+    pub server_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderDiagnosis {
+    pub provider_id: i64,
+    pub provider_name: String,
+    pub provider_type: String,
+    // The worst severity among the findings.
+    pub severity: FindingSeverity,
+    pub findings: Vec<DiagnosticFinding>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl ProviderDiagnosis {
+    pub fn new(
+        provider_id: i64, provider_name: String, provider_type: String,
+        findings: Vec<DiagnosticFinding>,
+    ) -> Self {
+        let severity = findings
+            .iter()
+            .map(|f| f.severity)
+            .max()
+            .unwrap_or(FindingSeverity::Ok);
+        Self {
+            provider_id,
+            provider_name,
+            provider_type,
+            severity,
+            findings,
+            checked_at: Utc::now(),
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.severity == FindingSeverity::Error
+    }
+}
+
+// A row of the database's migrations table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaMigration {
+    pub version: i64,
+    pub description: String,
+    pub success: bool,
+}
+
+// Which migrations ran against the cache database, next to the newest one this
+// build ships. A gap between the two usually means a failed startup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaStatus {
+    pub backend: String,
+    pub latest_known: Option<i64>,
+    pub applied: Vec<SchemaMigration>,
+}
+
+pub fn auth_finding(result: Result<bool, String>) -> DiagnosticFinding {
+    match result {
+        Ok(true) => {
+            DiagnosticFinding::ok(DiagnosticCheck::Auth, "Credentials accepted".to_string())
+        }
+        Ok(false) => DiagnosticFinding::new(
+            DiagnosticCheck::Auth,
+            FindingSeverity::Error,
+            "Credentials were rejected".to_string(),
+            "Update the token or sign in again.",
+        ),
+        Err(e) => DiagnosticFinding::new(
+            DiagnosticCheck::Auth,
+            FindingSeverity::Error,
+            format!("Could not validate credentials: {}", e),
+            "Check the token and the server URL.",
+        ),
+    }
+}
+
+// One finding per missing permission, naming the features it disables.
+// Missing permissions the plugin marks as required are errors.
+pub fn permission_findings(
+    status: &PermissionStatus, features: &[FeatureAvailability],
+) -> Vec<DiagnosticFinding> {
+    let mut disabled: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for feature in features.iter().filter(|f| !f.available) {
+        for permission in &feature.missing_permissions {
+            disabled
+                .entry(permission.as_str())
+                .or_default()
+                .push(feature.feature.name.as_str());
+        }
+    }
+
+    let findings: Vec<DiagnosticFinding> = status
+        .permissions
+        .iter()
+        .filter(|check| !check.granted)
+        .map(|check| {
+            let name = &check.permission.name;
+            let (severity, effect) = match disabled.get(name.as_str()) {
+                Some(features) => (
+                    FindingSeverity::Warning,
+                    format!("{} disabled", features.join(", ").to_lowercase()),
+                ),
+                None => (FindingSeverity::Info, "no features affected".to_string()),
+            };
+            let severity = if check.permission.required {
+                FindingSeverity::Error
+            } else {
+                severity
+            };
+            DiagnosticFinding::new(
+                DiagnosticCheck::Permissions,
+                severity,
+                format!("token missing `{}` scope: {}", name, effect),
+                &format!(
+                    "Grant the `{}` scope to the token, then run the diagnosis again.",
+                    name
+                ),
+            )
+        })
+        .collect();
+
+    if findings.is_empty() {
+        return vec![DiagnosticFinding::ok(
+            DiagnosticCheck::Permissions,
+            format!("All {} permissions granted", status.permissions.len()),
+        )];
+    }
+    findings
+}
+
+// Client errors are expected since the probe is unauthenticated; only
+// failing to connect, server errors and slow responses are reported.
+pub fn reachability_finding(
+    endpoint: &str, probe: &Result<EndpointProbe, String>,
+) -> DiagnosticFinding {
+    match probe {
+        Err(e) => DiagnosticFinding::new(
+            DiagnosticCheck::Reachability,
+            FindingSeverity::Error,
+            format!("{} is unreachable: {}", endpoint, e),
+            "Check the server URL, network and proxy settings.",
+        ),
+        Ok(probe) if probe.status >= 500 => DiagnosticFinding::new(
+            DiagnosticCheck::Reachability,
+            FindingSeverity::Error,
+            format!("{} responded with HTTP {}", endpoint, probe.status),
+            "The provider may be having an outage. Try again later.",
+        ),
+        Ok(probe) if probe.latency_ms >= SLOW_RESPONSE_MS => DiagnosticFinding::new(
+            DiagnosticCheck::Reachability,
+            FindingSeverity::Warning,
+            format!("{} took {} ms to respond", endpoint, probe.latency_ms),
+            "Check the network and proxy settings, or raise the refresh interval.",
+        ),
+        Ok(probe) => DiagnosticFinding::ok(
+            DiagnosticCheck::Reachability,
+            format!("{} responded in {} ms", endpoint, probe.latency_ms),
+        ),
+    }
+}
+
+pub fn clock_skew_finding(
+    server_time: DateTime<Utc>, local_time: DateTime<Utc>,
+) -> DiagnosticFinding {
+    let skew = (local_time - server_time).num_seconds();
+    let direction = if skew > 0 { "ahead of" } else { "behind" };
+    let message = format!("Local clock is {}s {} the provider", skew.abs(), direction);
+    let suggestion = "Sync the system clock. Signed requests and short-lived tokens are \
+                      rejected when the clock is off.";
+
+    match skew.abs() {
+        s if s >= CLOCK_SKEW_ERROR_SECS => DiagnosticFinding::new(
+            DiagnosticCheck::ClockSkew,
+            FindingSeverity::Error,
+            message,
+            suggestion,
+        ),
+        s if s >= CLOCK_SKEW_WARNING_SECS => DiagnosticFinding::new(
+            DiagnosticCheck::ClockSkew,
+            FindingSeverity::Warning,
+            message,
+            suggestion,
+        ),
+        _ => DiagnosticFinding::ok(
+            DiagnosticCheck::ClockSkew,
+            "Local clock matches the provider".to_string(),
+        ),
+    }
+}
+
+pub fn rate_limit_finding(status: &RateLimitStatus) -> DiagnosticFinding {
+    let resets = status
+        .reset_at
+        .map(|at| format!(", resets at {}", at.format("%H:%M UTC")))
+        .unwrap_or_default();
+    let message = format!(
+        "{} of {} API requests left{}",
+        status.remaining, status.limit, resets
+    );
+
+    if status.remaining == 0 {
+        DiagnosticFinding::new(
+            DiagnosticCheck::RateLimit,
+            FindingSeverity::Error,
+            message,
+            "Refreshes fail until the limit resets. Raise the refresh interval or monitor fewer \
+             pipelines.",
+        )
+    } else if status.remaining.saturating_mul(RATE_LIMIT_LOW_DIVISOR) < status.limit {
+        DiagnosticFinding::new(
+            DiagnosticCheck::RateLimit,
+            FindingSeverity::Warning,
+            message,
+            "Raise the refresh interval or monitor fewer pipelines.",
+        )
+    } else {
+        DiagnosticFinding::ok(DiagnosticCheck::RateLimit, message)
+    }
+}
+
+// Unsupported versions are warnings since the provider usually still works;
+// supported ones that run on a compatibility shim are informational.
+pub fn server_version_finding(version: &ServerVersion) -> DiagnosticFinding {
+    match (&version.warning, version.supported) {
+        (Some(warning), false) => DiagnosticFinding::new(
+            DiagnosticCheck::ServerVersion,
+            FindingSeverity::Warning,
+            warning.clone(),
+            "Upgrade the server, or expect missing or wrong data for this provider.",
+        ),
+        (Some(warning), true) => DiagnosticFinding::new(
+            DiagnosticCheck::ServerVersion,
+            FindingSeverity::Info,
+            warning.clone(),
+            "Upgrade the server to lift the restriction.",
+        ),
+        (None, _) => DiagnosticFinding::ok(
+            DiagnosticCheck::ServerVersion,
+            format!("Server version {} is supported", version.version),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::Duration;
+    use pipedash_plugin_api::{
+        Feature,
+        Permission,
+        PermissionCheck,
+    };
+
+    use super::*;
+
+    fn check(name: &str, required: bool, granted: bool) -> PermissionCheck {
+        PermissionCheck {
+            permission: Permission {
+                name: name.to_string(),
+                description: String::new(),
+                required,
+            },
+            granted,
+        }
+    }
+
+    #[test]
+    fn test_permission_findings_name_disabled_features() {
+        let status = PermissionStatus {
+            permissions: vec![
+                check("repo", true, true),
+                check("workflow", false, false),
+                check("read:org", false, false),
+            ],
+            all_granted: false,
+            checked_at: Utc::now(),
+            metadata: HashMap::new(),
+        };
+        let features = vec![FeatureAvailability {
+            feature: Feature {
+                id: "trigger_dispatch".to_string(),
+                name: "Triggering".to_string(),
+                description: String::new(),
+                required_permissions: vec!["workflow".to_string()],
+            },
+            available: false,
+            missing_permissions: vec!["workflow".to_string()],
+        }];
+
+        let findings = permission_findings(&status, &features);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(
+            findings[0].message,
+            "token missing `workflow` scope: triggering disabled"
+        );
+        assert_eq!(findings[0].severity, FindingSeverity::Warning);
+        assert_eq!(findings[1].severity, FindingSeverity::Info);
+
+        let status = PermissionStatus {
+            permissions: vec![check("repo", true, false)],
+            ..status
+        };
+        assert_eq!(
+            permission_findings(&status, &[])[0].severity,
+            FindingSeverity::Error
+        );
+    }
+
+    #[test]
+    fn test_clock_skew_thresholds() {
+        let now = Utc::now();
+        let severity = |skew: i64| clock_skew_finding(now - Duration::seconds(skew), now).severity;
+
+        assert_eq!(severity(5), FindingSeverity::Ok);
+        assert_eq!(severity(-45), FindingSeverity::Warning);
+        assert_eq!(severity(600), FindingSeverity::Error);
+        assert_eq!(
+            clock_skew_finding(now + Duration::seconds(45), now).message,
+            "Local clock is 45s behind the provider"
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_and_reachability_findings() {
+        let rate_limit = |remaining| RateLimitStatus {
+            limit: 5000,
+            remaining,
+            reset_at: None,
+        };
+        assert_eq!(
+            rate_limit_finding(&rate_limit(4000)).severity,
+            FindingSeverity::Ok
+        );
+        assert_eq!(
+            rate_limit_finding(&rate_limit(120)).severity,
+            FindingSeverity::Warning
+        );
+        assert_eq!(
+            rate_limit_finding(&rate_limit(0)).severity,
+            FindingSeverity::Error
+        );
+
+        let probe = |status, latency_ms| {
+            Ok(EndpointProbe {
+                status,
+                latency_ms,
+                server_time: None,
+            })
+        };
+        let severity = |probe| reachability_finding("https://api.example.com", &probe).severity;
+        assert_eq!(severity(probe(401, 80)), FindingSeverity::Ok);
+        assert_eq!(severity(probe(200, 5_000)), FindingSeverity::Warning);
+        assert_eq!(severity(probe(503, 80)), FindingSeverity::Error);
+        assert_eq!(
+            severity(Err("timed out".to_string())),
+            FindingSeverity::Error
+        );
+
+        let diagnosis = ProviderDiagnosis::new(
+            1,
+            "GitHub".to_string(),
+            "github".to_string(),
+            vec![
+                rate_limit_finding(&rate_limit(4000)),
+                rate_limit_finding(&rate_limit(120)),
+            ],
+        );
+        assert_eq!(diagnosis.severity, FindingSeverity::Warning);
+        assert!(!diagnosis.has_errors());
+    }
+
+    #[test]
+    fn test_server_version_finding() {
+        let severity = |version: &str| {
+            server_version_finding(&ServerVersion::new("GitLab", version, (11, 0))).severity
+        };
+        assert_eq!(severity("16.4.1-ee"), FindingSeverity::Ok);
+        assert_eq!(severity("10.8.7"), FindingSeverity::Warning);
+        assert_eq!(severity("nightly"), FindingSeverity::Warning);
+
+        let shimmed = ServerVersion::new("GitLab", "11.11.8", (11, 0)).with_warning("slower");
+        assert_eq!(
+            server_version_finding(&shimmed).severity,
+            FindingSeverity::Info
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+// Provider config keys. With `auto_discover = "true"` the organizations (or
+// GitLab groups) in `discovery_orgs` are listed periodically and repositories
+// not selected yet are appended to `selected_items`, unless an exclude
+// pattern matches them.
+pub const AUTO_DISCOVER_KEY: &str = "auto_discover";
+pub const DISCOVERY_ORGS_KEY: &str = "discovery_orgs";
+pub const SELECTED_ITEMS_KEY: &str = "selected_items";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoverySettings {
+    pub orgs: Vec<String>,
+}
+
+impl DiscoverySettings {
+    // `None` unless discovery is on and at least one organization is set.
+    pub fn from_config(config: &HashMap<String, String>) -> Option<Self> {
+        let enabled = config
+            .get(AUTO_DISCOVER_KEY)
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"));
+        if !enabled {
+            return None;
+        }
+
+        let orgs = split_list(config.get(DISCOVERY_ORGS_KEY));
+        (!orgs.is_empty()).then_some(Self { orgs })
+    }
+}
+
+pub fn selected_items(config: &HashMap<String, String>) -> Vec<String> {
+    split_list(config.get(SELECTED_ITEMS_KEY))
+}
+
+// Appends the items not selected yet, keeping the existing order. Returns the
+// ones added.
+pub fn add_selected_items(
+    config: &mut HashMap<String, String>, items: impl IntoIterator<Item = String>,
+) -> Vec<String> {
+    let mut selected = selected_items(config);
+    let mut added = Vec::new();
+    for item in items {
+        if !selected.contains(&item) {
+            selected.push(item.clone());
+            added.push(item);
+        }
+    }
+
+    if !added.is_empty() {
+        config.insert(SELECTED_ITEMS_KEY.to_string(), selected.join(","));
+    }
+    added
+}
+
+// The config to store for a provider declared in config.toml: the declared
+// one, plus whatever discovery already added, so reapplying the file doesn't
+// drop discovered repositories.
+pub fn keep_discovered_items(
+    declared: &HashMap<String, String>, stored: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut config = declared.clone();
+    if DiscoverySettings::from_config(declared).is_some() {
+        add_selected_items(&mut config, selected_items(stored));
+    }
+    config
+}
+
+fn split_list(value: Option<&String>) -> Vec<String> {
+    value
+        .map(|items| {
+            items
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_settings_need_orgs_and_flag() {
+        assert_eq!(
+            DiscoverySettings::from_config(&config(&[
+                ("auto_discover", "true"),
+                ("discovery_orgs", "acme, widgets,"),
+            ])),
+            Some(DiscoverySettings {
+                orgs: vec!["acme".into(), "widgets".into()]
+            })
+        );
+        assert!(DiscoverySettings::from_config(&config(&[("discovery_orgs", "acme")])).is_none());
+        assert!(DiscoverySettings::from_config(&config(&[("auto_discover", "true")])).is_none());
+    }
+
+    #[test]
+    fn test_add_selected_items_skips_known() {
+        let mut cfg = config(&[("selected_items", "acme/api, acme/web")]);
+        let added = add_selected_items(&mut cfg, ["acme/web".to_string(), "acme/cli".to_string()]);
+        assert_eq!(added, vec!["acme/cli".to_string()]);
+        assert_eq!(cfg["selected_items"], "acme/api,acme/web,acme/cli");
+
+        assert!(add_selected_items(&mut cfg, ["acme/api".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_keep_discovered_items() {
+        let declared = config(&[
+            ("auto_discover", "true"),
+            ("discovery_orgs", "acme"),
+            ("selected_items", "acme/api"),
+        ]);
+        let stored = config(&[("selected_items", "acme/api,acme/new")]);
+        assert_eq!(
+            keep_discovered_items(&declared, &stored)["selected_items"],
+            "acme/api,acme/new"
+        );
+
+        let fixed = config(&[("selected_items", "acme/api")]);
+        assert_eq!(keep_discovered_items(&fixed, &stored), fixed);
+    }
+}
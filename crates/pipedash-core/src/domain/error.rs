@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum DomainError {
     #[error("Provider not found: {0}")]
     ProviderNotFound(String),
@@ -17,6 +17,17 @@ pub enum DomainError {
     #[error("Authentication failed: {0}")]
     AuthenticationFailed(String),
 
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error(
+        "{feature} is unavailable: the token is missing the `{missing_permission}` permission"
+    )]
+    FeatureUnavailable {
+        feature: String,
+        missing_permission: String,
+    },
+
     #[error("API error: {0}")]
     ApiError(String),
 
@@ -35,9 +46,18 @@ pub enum DomainError {
     #[error("Internal error: {0}")]
     InternalError(String),
 
-    #[allow(dead_code)]
-    #[error("Rate limited: {0}")]
-    RateLimited(String),
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        retry_after: Option<std::time::Duration>,
+    },
+
+    // Fetches are paused after repeated failures until `retry_at`.
+    #[error("Provider {provider_id} is paused after repeated failures, retrying at {retry_at}")]
+    CircuitOpen {
+        provider_id: i64,
+        retry_at: chrono::DateTime<chrono::Utc>,
+    },
 
     #[error("Network error: {0}")]
     NetworkError(String),
@@ -71,3 +91,23 @@ pub enum DomainError {
 }
 
 pub type DomainResult<T> = Result<T, DomainError>;
+
+impl DomainError {
+    // Provider failures that may clear up by themselves. These are retried
+    // and count towards opening a provider's circuit breaker.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            DomainError::NetworkError(_)
+                | DomainError::ApiError(_)
+                | DomainError::RateLimited { .. }
+        )
+    }
+
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            DomainError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
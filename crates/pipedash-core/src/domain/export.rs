@@ -0,0 +1,226 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::metrics::MetricEntry;
+use super::pipeline::PipelineRun;
+
+// Rows fetched per database round trip while exporting.
+pub const EXPORT_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    // One JSON object per line.
+    Jsonl,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" | "ndjson" => Ok(ExportFormat::Jsonl),
+            _ => Err(format!("Invalid export format: {}", s)),
+        }
+    }
+}
+
+// An empty pipeline list exports every pipeline.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportFilter {
+    #[serde(default)]
+    pub pipeline_ids: Vec<String>,
+    #[serde(default)]
+    pub start_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub end_date: Option<DateTime<Utc>>,
+}
+
+impl ExportFilter {
+    pub fn includes(&self, pipeline_id: &str, timestamp: DateTime<Utc>) -> bool {
+        (self.pipeline_ids.is_empty() || self.pipeline_ids.iter().any(|id| id == pipeline_id))
+            && self.start_date.is_none_or(|start| timestamp >= start)
+            && self.end_date.is_none_or(|end| timestamp <= end)
+    }
+}
+
+pub trait ExportRecord: Serialize {
+    const CSV_HEADER: &'static [&'static str];
+
+    fn csv_fields(&self) -> Vec<String>;
+
+    fn encode(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Csv => csv_line(self.csv_fields()),
+            ExportFormat::Jsonl => {
+                let mut line = serde_json::to_string(self).unwrap_or_default();
+                line.push('\n');
+                line
+            }
+        }
+    }
+}
+
+// CSV needs a header before the first row; JSON Lines is self-describing.
+pub fn export_header<T: ExportRecord>(format: ExportFormat) -> Option<String> {
+    match format {
+        ExportFormat::Csv => Some(csv_line(T::CSV_HEADER.iter().map(|h| h.to_string()))),
+        ExportFormat::Jsonl => None,
+    }
+}
+
+fn csv_line(fields: impl IntoIterator<Item = String>) -> String {
+    let mut line = fields
+        .into_iter()
+        .map(|field| csv_escape(&field))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl ExportRecord for MetricEntry {
+    const CSV_HEADER: &'static [&'static str] = &[
+        "pipeline_id",
+        "run_number",
+        "timestamp",
+        "metric_type",
+        "value",
+        "metadata",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.pipeline_id.clone(),
+            self.run_number.to_string(),
+            self.timestamp.to_rfc3339(),
+            self.metric_type.as_str().to_string(),
+            self.value.to_string(),
+            self.metadata
+                .as_ref()
+                .map(|m| m.to_string())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+impl ExportRecord for PipelineRun {
+    const CSV_HEADER: &'static [&'static str] = &[
+        "pipeline_id",
+        "run_number",
+        "status",
+        "started_at",
+        "concluded_at",
+        "duration_seconds",
+        "branch",
+        "commit_sha",
+        "commit_message",
+        "actor",
+        "logs_url",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.pipeline_id.clone(),
+            self.run_number.to_string(),
+            self.status.as_str().to_string(),
+            self.started_at.to_rfc3339(),
+            self.concluded_at
+                .map(|at| at.to_rfc3339())
+                .unwrap_or_default(),
+            self.duration_seconds
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+            self.branch.clone().unwrap_or_default(),
+            self.commit_sha.clone().unwrap_or_default(),
+            self.commit_message.clone().unwrap_or_default(),
+            self.actor.clone().unwrap_or_default(),
+            self.logs_url.clone(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::MetricType;
+
+    #[test]
+    fn test_csv_escaping() {
+        let entry = MetricEntry {
+            id: 1,
+            pipeline_id: "github__1__o__r__1".to_string(),
+            run_number: 7,
+            timestamp: DateTime::from_timestamp(0, 0).unwrap(),
+            metric_type: MetricType::RunDuration,
+            value: 42.5,
+            metadata: Some(serde_json::json!({ "branch": "main" })),
+            created_at: DateTime::from_timestamp(0, 0).unwrap(),
+            run_hash: None,
+        };
+
+        assert_eq!(
+            export_header::<MetricEntry>(ExportFormat::Csv).unwrap(),
+            "pipeline_id,run_number,timestamp,metric_type,value,metadata\r\n"
+        );
+        assert_eq!(
+            entry.encode(ExportFormat::Csv),
+            "github__1__o__r__1,7,1970-01-01T00:00:00+00:00,run_duration,42.5,\"{\"\"branch\"\":\"\"main\"\"}\"\r\n"
+        );
+
+        let line = entry.encode(ExportFormat::Jsonl);
+        assert!(line.ends_with('\n'));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&line).unwrap()["value"],
+            42.5
+        );
+    }
+
+    #[test]
+    fn test_filter_includes() {
+        let at = DateTime::from_timestamp(1_000, 0).unwrap();
+        let filter = ExportFilter {
+            pipeline_ids: vec!["a".to_string()],
+            start_date: Some(at),
+            end_date: None,
+        };
+
+        assert!(filter.includes("a", at));
+        assert!(!filter.includes("b", at));
+        assert!(!filter.includes("a", DateTime::from_timestamp(999, 0).unwrap()));
+        assert!(ExportFilter::default().includes("b", at));
+    }
+}
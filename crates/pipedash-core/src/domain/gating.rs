@@ -0,0 +1,99 @@
+use pipedash_plugin_api::FeatureAvailability;
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+
+// Operations that depend on a plugin feature, keyed by the feature ids the
+// plugins declare for them. Plugins that declare none of the ids are not
+// restricted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GatedOperation {
+    View,
+    Trigger,
+    Cancel,
+    Artifacts,
+}
+
+impl GatedOperation {
+    pub fn feature_ids(&self) -> &'static [&'static str] {
+        match self {
+            GatedOperation::View | GatedOperation::Artifacts => {
+                &["view_run_history", "view_pipelines"]
+            }
+            GatedOperation::Trigger => &["trigger_dispatch", "trigger_pipelines"],
+            GatedOperation::Cancel => &["cancel_workflows", "cancel_pipelines"],
+        }
+    }
+
+    fn unavailable<'a>(
+        &self, features: &'a [FeatureAvailability],
+    ) -> impl Iterator<Item = &'a FeatureAvailability> {
+        let ids = self.feature_ids();
+        features
+            .iter()
+            .filter(move |f| !f.available && ids.contains(&f.feature.id.as_str()))
+    }
+
+    pub fn missing_permissions(&self, features: &[FeatureAvailability]) -> Vec<String> {
+        self.unavailable(features)
+            .flat_map(|f| f.missing_permissions.iter().cloned())
+            .collect()
+    }
+
+    pub fn ensure_available(&self, features: &[FeatureAvailability]) -> DomainResult<()> {
+        match self.unavailable(features).next() {
+            Some(f) => Err(DomainError::FeatureUnavailable {
+                feature: f.feature.name.clone(),
+                missing_permission: f.missing_permissions.first().cloned().unwrap_or_default(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pipedash_plugin_api::Feature;
+
+    use super::*;
+
+    fn feature(id: &str, missing: &[&str]) -> FeatureAvailability {
+        FeatureAvailability {
+            feature: Feature {
+                id: id.to_string(),
+                name: "Trigger workflow dispatch".to_string(),
+                description: String::new(),
+                required_permissions: vec!["workflow".to_string()],
+            },
+            available: missing.is_empty(),
+            missing_permissions: missing.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_ensure_available() {
+        let features = vec![
+            feature("view_run_history", &[]),
+            feature("trigger_dispatch", &["workflow"]),
+        ];
+
+        assert!(GatedOperation::View.ensure_available(&features).is_ok());
+        assert!(GatedOperation::Cancel.ensure_available(&[]).is_ok());
+        match GatedOperation::Trigger.ensure_available(&features) {
+            Err(DomainError::FeatureUnavailable {
+                feature,
+                missing_permission,
+            }) => {
+                assert_eq!(feature, "Trigger workflow dispatch");
+                assert_eq!(missing_permission, "workflow");
+            }
+            other => panic!("expected FeatureUnavailable, got {:?}", other),
+        }
+        assert_eq!(
+            GatedOperation::Trigger.missing_permissions(&features),
+            vec!["workflow".to_string()]
+        );
+    }
+}
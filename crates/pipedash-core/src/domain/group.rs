@@ -0,0 +1,117 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::pipeline::{
+    Pipeline,
+    PipelineStatus,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineGroup {
+    pub id: i64,
+    pub name: String,
+    pub pipeline_ids: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineGroupStatus {
+    pub group: PipelineGroup,
+    pub status: PipelineStatus,
+    pub total: usize,
+    pub failed: usize,
+    pub running: usize,
+    // Members that are assigned but not (or no longer) in the pipeline cache.
+    pub missing: usize,
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+impl PipelineGroupStatus {
+    pub fn from_members(group: PipelineGroup, members: &[&Pipeline]) -> Self {
+        let statuses: Vec<&PipelineStatus> = members.iter().map(|p| &p.status).collect();
+
+        Self {
+            status: aggregate_status(&statuses),
+            total: group.pipeline_ids.len(),
+            failed: statuses
+                .iter()
+                .filter(|s| ***s == PipelineStatus::Failed)
+                .count(),
+            running: statuses
+                .iter()
+                .filter(|s| ***s == PipelineStatus::Running)
+                .count(),
+            missing: group.pipeline_ids.len().saturating_sub(members.len()),
+            last_run: members.iter().filter_map(|p| p.last_run).max(),
+            group,
+        }
+    }
+}
+
+// Worst status wins: a single failed member marks the whole group failed, and
+// an in-flight member keeps it running until everything has settled.
+pub fn aggregate_status(statuses: &[&PipelineStatus]) -> PipelineStatus {
+    const PRECEDENCE: [PipelineStatus; 8] = [
+        PipelineStatus::Failed,
+        PipelineStatus::Waiting,
+        PipelineStatus::Running,
+        PipelineStatus::Queued,
+        PipelineStatus::Pending,
+        PipelineStatus::Cancelled,
+        PipelineStatus::Success,
+        PipelineStatus::Neutral,
+    ];
+
+    if statuses.is_empty() {
+        return PipelineStatus::Pending;
+    }
+
+    PRECEDENCE
+        .into_iter()
+        .find(|candidate| statuses.contains(&candidate))
+        .unwrap_or(PipelineStatus::Skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_status_failed_wins() {
+        assert_eq!(
+            aggregate_status(&[
+                &PipelineStatus::Success,
+                &PipelineStatus::Running,
+                &PipelineStatus::Failed,
+            ]),
+            PipelineStatus::Failed
+        );
+        assert_eq!(
+            aggregate_status(&[&PipelineStatus::Success, &PipelineStatus::Running]),
+            PipelineStatus::Running
+        );
+        assert_eq!(
+            aggregate_status(&[&PipelineStatus::Success, &PipelineStatus::Skipped]),
+            PipelineStatus::Success
+        );
+        assert_eq!(
+            aggregate_status(&[&PipelineStatus::Skipped]),
+            PipelineStatus::Skipped
+        );
+        assert_eq!(
+            aggregate_status(&[&PipelineStatus::Queued, &PipelineStatus::Neutral]),
+            PipelineStatus::Queued
+        );
+        assert_eq!(
+            aggregate_status(&[&PipelineStatus::Neutral, &PipelineStatus::Skipped]),
+            PipelineStatus::Neutral
+        );
+        assert_eq!(aggregate_status(&[]), PipelineStatus::Pending);
+    }
+}
@@ -0,0 +1,153 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+
+pub const DEEP_LINK_SCHEME: &str = "pipedash";
+
+// Canonical link to a pipeline or one of its runs. The same path is served by
+// the web server (`/p/...`) and opened by the desktop app (`pipedash://p/...`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineLink {
+    pub provider_id: i64,
+    pub pipeline_id: String,
+    pub run_number: Option<i64>,
+}
+
+impl PipelineLink {
+    pub fn new(provider_id: i64, pipeline_id: &str, run_number: Option<i64>) -> Self {
+        Self {
+            provider_id,
+            pipeline_id: pipeline_id.to_string(),
+            run_number,
+        }
+    }
+
+    // `/p/{provider}/{pipeline}[/{run}]`
+    pub fn path(&self) -> String {
+        let mut path = format!(
+            "/p/{}/{}",
+            self.provider_id,
+            urlencoding::encode(&self.pipeline_id)
+        );
+        if let Some(run_number) = self.run_number {
+            path.push_str(&format!("/{}", run_number));
+        }
+        path
+    }
+
+    pub fn deep_link(&self) -> String {
+        format!("{}:/{}", DEEP_LINK_SCHEME, self.path())
+    }
+
+    // A web link when the server's public URL is known, the desktop deep link
+    // otherwise.
+    pub fn url(&self, public_url: Option<&str>) -> String {
+        match public_url.map(|url| url.trim_end_matches('/')) {
+            Some(base) if !base.is_empty() => format!("{}{}", base, self.path()),
+            _ => self.deep_link(),
+        }
+    }
+
+    // The frontend route that shows the pipeline and opens the run's logs.
+    pub fn app_path(&self) -> String {
+        let mut path = format!("/pipelines/{}", urlencoding::encode(&self.pipeline_id));
+        if let Some(run_number) = self.run_number {
+            path.push_str(&format!("?run={}", run_number));
+        }
+        path
+    }
+
+    // Accepts deep links, web links and bare `/p/...` paths.
+    pub fn parse(link: &str) -> DomainResult<Self> {
+        let invalid = || DomainError::InvalidConfig(format!("Invalid pipeline link '{}'", link));
+
+        let link = link.trim();
+        let path = match link.split_once("://") {
+            Some((scheme, rest)) if scheme.eq_ignore_ascii_case(DEEP_LINK_SCHEME) => rest,
+            // Drop the host of a web link.
+            Some((_, rest)) => rest.find('/').map(|i| &rest[i..]).unwrap_or_default(),
+            None => link,
+        };
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+
+        let segments: Vec<&str> = path
+            .trim_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+        let (provider, pipeline, run) = match segments.as_slice() {
+            ["p", provider, pipeline] => (*provider, *pipeline, None),
+            ["p", provider, pipeline, run] => (*provider, *pipeline, Some(*run)),
+            _ => return Err(invalid()),
+        };
+
+        let provider_id = provider.parse::<i64>().map_err(|_| invalid())?;
+        let pipeline_id = urlencoding::decode(pipeline)
+            .map_err(|_| invalid())?
+            .into_owned();
+        let run_number = run
+            .map(|run| run.parse::<i64>().map_err(|_| invalid()))
+            .transpose()?;
+
+        Ok(Self {
+            provider_id,
+            pipeline_id,
+            run_number,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_round_trip() {
+        let link = PipelineLink::new(3, "github__3__acme__api__ci.yml", Some(42));
+
+        assert_eq!(link.path(), "/p/3/github__3__acme__api__ci.yml/42");
+        assert_eq!(
+            link.deep_link(),
+            "pipedash://p/3/github__3__acme__api__ci.yml/42"
+        );
+        assert_eq!(
+            link.url(Some("https://ci.example.com/")),
+            "https://ci.example.com/p/3/github__3__acme__api__ci.yml/42"
+        );
+        assert_eq!(link.url(None), link.deep_link());
+
+        for text in [
+            link.path(),
+            link.deep_link(),
+            link.url(Some("https://ci.example.com")),
+        ] {
+            assert_eq!(PipelineLink::parse(&text).unwrap(), link);
+        }
+    }
+
+    #[test]
+    fn test_link_encodes_pipeline_id() {
+        let link = PipelineLink::new(1, "jenkins__1__team/deploy job", None);
+
+        assert_eq!(link.path(), "/p/1/jenkins__1__team%2Fdeploy%20job");
+        assert_eq!(
+            link.app_path(),
+            "/pipelines/jenkins__1__team%2Fdeploy%20job"
+        );
+        assert_eq!(PipelineLink::parse(&link.deep_link()).unwrap(), link);
+    }
+
+    #[test]
+    fn test_parse_rejects_other_paths() {
+        assert!(PipelineLink::parse("pipedash://settings").is_err());
+        assert!(PipelineLink::parse("/p/abc/pipeline").is_err());
+        assert!(PipelineLink::parse("/p/1/pipeline/latest").is_err());
+        assert!(PipelineLink::parse("/p/1").is_err());
+    }
+}
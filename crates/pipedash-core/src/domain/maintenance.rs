@@ -0,0 +1,336 @@
+use chrono::{
+    DateTime,
+    Datelike,
+    Duration,
+    NaiveDateTime,
+    NaiveTime,
+    Utc,
+    Weekday,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+use super::group::PipelineGroup;
+use super::pipeline::Pipeline;
+
+// Key under which an active window is added to a pipeline's metadata.
+pub const MAINTENANCE_METADATA_KEY: &str = "maintenance";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MaintenanceSchedule {
+    Once {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    // A wall-clock range in the user's timezone on the given days (every day
+    // when empty); `start` after `end` wraps past midnight into the next day.
+    Weekly {
+        #[serde(default)]
+        days: Vec<Weekday>,
+        start: NaiveTime,
+        end: NaiveTime,
+        #[serde(default)]
+        utc_offset_minutes: i32,
+    },
+}
+
+impl MaintenanceSchedule {
+    pub fn validate(&self) -> DomainResult<()> {
+        match self {
+            MaintenanceSchedule::Once { start, end } => {
+                if end <= start {
+                    return Err(DomainError::InvalidConfig(
+                        "Maintenance window must end after it starts".to_string(),
+                    ));
+                }
+            }
+            MaintenanceSchedule::Weekly {
+                start,
+                end,
+                utc_offset_minutes,
+                ..
+            } => {
+                if start == end {
+                    return Err(DomainError::InvalidConfig(
+                        "Maintenance window must start and end at different times".to_string(),
+                    ));
+                }
+                if utc_offset_minutes.abs() > 14 * 60 {
+                    return Err(DomainError::InvalidConfig(format!(
+                        "Invalid UTC offset: {} minutes",
+                        utc_offset_minutes
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // When the occurrence covering `at` ends, or None outside the window.
+    pub fn active_until(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            MaintenanceSchedule::Once { start, end } => (*start <= at && at < *end).then_some(*end),
+            MaintenanceSchedule::Weekly {
+                days,
+                start,
+                end,
+                utc_offset_minutes,
+            } => {
+                let offset = Duration::minutes(*utc_offset_minutes as i64);
+                let local = (at + offset).naive_utc();
+                let today = local.date();
+                let time = local.time();
+                let on = |day: Weekday| days.is_empty() || days.contains(&day);
+
+                let local_end = if start < end {
+                    (on(today.weekday()) && *start <= time && time < *end)
+                        .then(|| today.and_time(*end))
+                } else if time >= *start && on(today.weekday()) {
+                    today.succ_opt().map(|tomorrow| tomorrow.and_time(*end))
+                } else if time < *end && on(today.weekday().pred()) {
+                    Some(today.and_time(*end))
+                } else {
+                    None
+                };
+                local_end.map(|local_end: NaiveDateTime| local_end.and_utc() - offset)
+            }
+        }
+    }
+}
+
+// Planned work on a set of pipelines. While a window is active, dependency
+// triggers into those pipelines are skipped and their status changes don't
+// notify anyone. Without pipelines or groups it covers every pipeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    #[serde(default)]
+    pub id: i64,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pipeline_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub group_ids: Vec<i64>,
+    pub schedule: MaintenanceSchedule,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MaintenanceWindow {
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::InvalidConfig(
+                "Maintenance window name cannot be empty".to_string(),
+            ));
+        }
+        self.schedule.validate()
+    }
+
+    pub fn covers(&self, pipeline_id: &str, pipeline_group_ids: &[i64]) -> bool {
+        if self.pipeline_ids.is_empty() && self.group_ids.is_empty() {
+            return true;
+        }
+
+        self.pipeline_ids.iter().any(|id| id == pipeline_id)
+            || self
+                .group_ids
+                .iter()
+                .any(|id| pipeline_group_ids.contains(id))
+    }
+}
+
+// What the status API reports for a pipeline inside a window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveMaintenance {
+    pub window_id: i64,
+    pub name: String,
+    pub until: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+// The windows together with group membership, loaded once per refresh.
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceCalendar {
+    windows: Vec<MaintenanceWindow>,
+    groups: Vec<PipelineGroup>,
+}
+
+impl MaintenanceCalendar {
+    pub fn new(windows: Vec<MaintenanceWindow>, groups: Vec<PipelineGroup>) -> Self {
+        Self { windows, groups }
+    }
+
+    // With overlapping windows, the one that lasts longest is reported.
+    pub fn active(&self, pipeline_id: &str, at: DateTime<Utc>) -> Option<ActiveMaintenance> {
+        if self.windows.is_empty() {
+            return None;
+        }
+
+        let group_ids: Vec<i64> = self
+            .groups
+            .iter()
+            .filter(|g| g.pipeline_ids.iter().any(|id| id == pipeline_id))
+            .map(|g| g.id)
+            .collect();
+
+        self.windows
+            .iter()
+            .filter(|w| w.covers(pipeline_id, &group_ids))
+            .filter_map(|w| w.schedule.active_until(at).map(|until| (w, until)))
+            .max_by_key(|(_, until)| *until)
+            .map(|(w, until)| ActiveMaintenance {
+                window_id: w.id,
+                name: w.name.clone(),
+                until,
+                reason: w.reason.clone(),
+            })
+    }
+
+    pub fn in_maintenance(&self, pipeline_id: &str, at: DateTime<Utc>) -> bool {
+        self.active(pipeline_id, at).is_some()
+    }
+
+    pub fn without_in_maintenance(
+        &self, pipelines: &[Pipeline], at: DateTime<Utc>,
+    ) -> Vec<Pipeline> {
+        pipelines
+            .iter()
+            .filter(|p| !self.in_maintenance(&p.id, at))
+            .cloned()
+            .collect()
+    }
+
+    pub fn annotate(&self, pipelines: &mut [Pipeline], at: DateTime<Utc>) {
+        for pipeline in pipelines {
+            if let Some(active) = self.active(&pipeline.id, at) {
+                if let Ok(value) = serde_json::to_value(active) {
+                    pipeline
+                        .metadata
+                        .insert(MAINTENANCE_METADATA_KEY.to_string(), value);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // 2025-03-03 is a Monday.
+        Utc.with_ymd_and_hms(2025, 3, day, hour, minute, 0).unwrap()
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn window(
+        id: i64, pipeline_ids: &[&str], group_ids: &[i64], schedule: MaintenanceSchedule,
+    ) -> MaintenanceWindow {
+        MaintenanceWindow {
+            id,
+            name: format!("window {}", id),
+            pipeline_ids: pipeline_ids.iter().map(|id| id.to_string()).collect(),
+            group_ids: group_ids.to_vec(),
+            schedule,
+            reason: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_one_off_window() {
+        let schedule = MaintenanceSchedule::Once {
+            start: at(3, 10, 0),
+            end: at(3, 12, 0),
+        };
+
+        assert_eq!(schedule.active_until(at(3, 9, 59)), None);
+        assert_eq!(schedule.active_until(at(3, 10, 0)), Some(at(3, 12, 0)));
+        assert_eq!(schedule.active_until(at(3, 12, 0)), None);
+        assert!(MaintenanceSchedule::Once {
+            start: at(3, 12, 0),
+            end: at(3, 10, 0),
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_weekly_window_wraps_past_midnight() {
+        // Saturday 22:00 to Sunday 02:00, in UTC+1.
+        let schedule = MaintenanceSchedule::Weekly {
+            days: vec![Weekday::Sat],
+            start: time(22, 0),
+            end: time(2, 0),
+            utc_offset_minutes: 60,
+        };
+
+        assert_eq!(schedule.active_until(at(8, 20, 59)), None);
+        assert_eq!(schedule.active_until(at(8, 21, 0)), Some(at(9, 1, 0)));
+        assert_eq!(schedule.active_until(at(9, 0, 30)), Some(at(9, 1, 0)));
+        assert_eq!(schedule.active_until(at(9, 1, 0)), None);
+        // Friday night is not in the window.
+        assert_eq!(schedule.active_until(at(7, 22, 0)), None);
+    }
+
+    #[test]
+    fn test_calendar_covers_pipelines_and_groups() {
+        let every_day = MaintenanceSchedule::Weekly {
+            days: Vec::new(),
+            start: time(9, 0),
+            end: time(10, 0),
+            utc_offset_minutes: 0,
+        };
+        let groups = vec![PipelineGroup {
+            id: 7,
+            name: "Deploys".to_string(),
+            pipeline_ids: vec!["deploy".to_string()],
+            created_at: Utc::now(),
+        }];
+        let calendar = MaintenanceCalendar::new(
+            vec![
+                window(1, &["api"], &[], every_day.clone()),
+                window(
+                    2,
+                    &[],
+                    &[7],
+                    MaintenanceSchedule::Once {
+                        start: at(3, 9, 0),
+                        end: at(3, 11, 0),
+                    },
+                ),
+            ],
+            groups,
+        );
+
+        assert!(calendar.in_maintenance("api", at(4, 9, 30)));
+        assert!(!calendar.in_maintenance("api", at(4, 10, 30)));
+        assert_eq!(
+            calendar
+                .active("deploy", at(3, 10, 30))
+                .map(|a| a.window_id),
+            Some(2)
+        );
+        assert!(!calendar.in_maintenance("web", at(3, 9, 30)));
+
+        let everything = MaintenanceCalendar::new(vec![window(3, &[], &[], every_day)], Vec::new());
+        assert!(everything.in_maintenance("web", at(3, 9, 30)));
+    }
+}
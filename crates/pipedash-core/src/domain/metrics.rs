@@ -1,18 +1,36 @@
+use std::collections::HashMap;
+
 use chrono::{
     DateTime,
+    Timelike,
     Utc,
 };
+pub use pipedash_plugin_api::{
+    RunMetric,
+    RunMetricType,
+};
 use serde::{
     Deserialize,
     Serialize,
 };
 
+use super::pipeline::{
+    PipelineRun,
+    PipelineStatus,
+};
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum MetricType {
     RunDuration,
     SuccessRate,
     RunFrequency,
+    QueueWaitTime,
+    QueueDepth,
+    EstimatedCost,
+    // Recorded by plugins through `extract_metrics`.
+    AgentWaitTime,
+    BillableMinutes,
 }
 
 impl MetricType {
@@ -21,6 +39,20 @@ impl MetricType {
             MetricType::RunDuration => "run_duration",
             MetricType::SuccessRate => "success_rate",
             MetricType::RunFrequency => "run_frequency",
+            MetricType::QueueWaitTime => "queue_wait_time",
+            MetricType::QueueDepth => "queue_depth",
+            MetricType::EstimatedCost => "estimated_cost",
+            MetricType::AgentWaitTime => "agent_wait_time",
+            MetricType::BillableMinutes => "billable_minutes",
+        }
+    }
+}
+
+impl From<RunMetricType> for MetricType {
+    fn from(metric_type: RunMetricType) -> Self {
+        match metric_type {
+            RunMetricType::AgentWaitTime => MetricType::AgentWaitTime,
+            RunMetricType::BillableMinutes => MetricType::BillableMinutes,
         }
     }
 }
@@ -33,6 +65,11 @@ impl std::str::FromStr for MetricType {
             "run_duration" => Ok(MetricType::RunDuration),
             "success_rate" => Ok(MetricType::SuccessRate),
             "run_frequency" => Ok(MetricType::RunFrequency),
+            "queue_wait_time" => Ok(MetricType::QueueWaitTime),
+            "queue_depth" => Ok(MetricType::QueueDepth),
+            "estimated_cost" => Ok(MetricType::EstimatedCost),
+            "agent_wait_time" => Ok(MetricType::AgentWaitTime),
+            "billable_minutes" => Ok(MetricType::BillableMinutes),
             _ => Err(format!("Unknown metric type: {}", s)),
         }
     }
@@ -106,6 +143,10 @@ pub struct MetricEntry {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricsQuery {
     pub pipeline_id: Option<String>,
+    #[serde(default)]
+    pub pipeline_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub labels: Vec<String>,
     pub metric_type: Option<MetricType>,
     pub start_date: Option<DateTime<Utc>>,
     pub end_date: Option<DateTime<Utc>>,
@@ -118,6 +159,8 @@ impl Default for MetricsQuery {
     fn default() -> Self {
         Self {
             pipeline_id: None,
+            pipeline_ids: None,
+            labels: Vec::new(),
             metric_type: None,
             start_date: None,
             end_date: None,
@@ -128,6 +171,71 @@ impl Default for MetricsQuery {
     }
 }
 
+// Queries spanning at least this long are answered from rollups.
+pub const ROLLUP_MIN_RANGE_DAYS: i64 = 7;
+
+// Buckets of pre-aggregated `pipeline_metrics` rows, maintained by the metrics
+// compaction task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollupGranularity {
+    Hourly,
+    Daily,
+}
+
+impl RollupGranularity {
+    pub const ALL: [RollupGranularity; 2] = [RollupGranularity::Hourly, RollupGranularity::Daily];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RollupGranularity::Hourly => "hour",
+            RollupGranularity::Daily => "day",
+        }
+    }
+
+    // Rollups outlive the raw rows, which is what makes long ranges possible.
+    pub fn retention_days(&self) -> i64 {
+        match self {
+            RollupGranularity::Hourly => 90,
+            RollupGranularity::Daily => 730,
+        }
+    }
+
+    pub fn bucket_start(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let date = timestamp.date_naive();
+        let hour = match self {
+            RollupGranularity::Hourly => timestamp.hour(),
+            RollupGranularity::Daily => 0,
+        };
+        date.and_hms_opt(hour, 0, 0)
+            .map(|dt| dt.and_utc())
+            .unwrap_or(timestamp)
+    }
+
+    // Percentiles need the raw values, and short ranges are cheap enough to
+    // scan, so only long ranges with a reproducible aggregation qualify.
+    pub fn for_query(query: &MetricsQuery) -> Option<Self> {
+        let period = query.aggregation_period?;
+        if matches!(
+            query.aggregation_type,
+            Some(AggregationType::P95 | AggregationType::P99)
+        ) {
+            return None;
+        }
+
+        if let Some(start) = query.start_date {
+            let end = query.end_date.unwrap_or_else(Utc::now);
+            if end - start < chrono::Duration::days(ROLLUP_MIN_RANGE_DAYS) {
+                return None;
+            }
+        }
+
+        Some(match period {
+            AggregationPeriod::Hourly => RollupGranularity::Hourly,
+            _ => RollupGranularity::Daily,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AggregatedMetric {
     pub timestamp: DateTime<Utc>,
@@ -165,6 +273,14 @@ pub struct PipelineMetricsStats {
     pub newest_metric: Option<DateTime<Utc>>,
 }
 
+// Result of recomputing metrics from the cached run history.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetricsRebuild {
+    pub pipelines: usize,
+    pub runs: usize,
+    pub metrics: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricMetadata {
     pub status: Option<String>,
@@ -178,3 +294,495 @@ impl MetricMetadata {
         serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlakyPipeline {
+    pub pipeline_id: String,
+    pub pipeline_name: String,
+    pub provider_id: i64,
+    pub score: f64,
+    pub flips: usize,
+    pub transitions: usize,
+    pub wasted_reruns: usize,
+    pub runs_analyzed: usize,
+    pub last_flip_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlakinessStats {
+    pub flips: usize,
+    pub transitions: usize,
+    pub wasted_reruns: usize,
+    pub runs_analyzed: usize,
+    pub last_flip_at: Option<DateTime<Utc>>,
+}
+
+impl FlakinessStats {
+    // Only success/failure outcomes count; cancelled, skipped and in-flight
+    // runs say nothing about whether the pipeline is deterministic.
+    pub fn from_runs(runs: &[PipelineRun]) -> Self {
+        let mut finished: Vec<&PipelineRun> = runs
+            .iter()
+            .filter(|run| matches!(run.status, PipelineStatus::Success | PipelineStatus::Failed))
+            .collect();
+        finished.sort_by_key(|run| (run.started_at, run.run_number));
+
+        let mut stats = Self {
+            runs_analyzed: finished.len(),
+            ..Self::default()
+        };
+
+        let mut last_by_branch: HashMap<Option<&str>, &PipelineRun> = HashMap::new();
+        for run in &finished {
+            if let Some(previous) = last_by_branch.insert(run.branch.as_deref(), run) {
+                stats.transitions += 1;
+                if previous.status != run.status {
+                    stats.flips += 1;
+                    stats.last_flip_at = Some(run.started_at);
+                }
+            }
+        }
+
+        // A failure followed by a success on the same commit means the failure
+        // was resolved by re-running rather than by a code change.
+        let mut failed_commits: HashMap<&str, usize> = HashMap::new();
+        for run in &finished {
+            let Some(sha) = run.commit_sha.as_deref() else {
+                continue;
+            };
+            match run.status {
+                PipelineStatus::Failed => *failed_commits.entry(sha).or_default() += 1,
+                _ => {
+                    if let Some(failures) = failed_commits.remove(sha) {
+                        stats.wasted_reruns += failures;
+                    }
+                }
+            }
+        }
+
+        stats
+    }
+
+    pub fn score(&self) -> f64 {
+        if self.transitions == 0 {
+            return 0.0;
+        }
+
+        let flip_rate = self.flips as f64 / self.transitions as f64;
+        let rerun_rate = (self.wasted_reruns as f64 / self.runs_analyzed as f64).min(1.0);
+        ((flip_rate + rerun_rate) / 2.0 * 1000.0).round() / 1000.0
+    }
+}
+
+// CI services bill every started minute, so partial minutes round up.
+pub fn estimate_run_cost(duration_seconds: i64, rate_per_minute: f64) -> f64 {
+    let minutes = (duration_seconds.max(0) as f64 / 60.0).ceil();
+    (minutes * rate_per_minute * 10_000.0).round() / 10_000.0
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CostGroupBy {
+    #[default]
+    Pipeline,
+    Group,
+    Provider,
+}
+
+impl std::str::FromStr for CostGroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pipeline" => Ok(CostGroupBy::Pipeline),
+            "group" => Ok(CostGroupBy::Group),
+            "provider" => Ok(CostGroupBy::Provider),
+            _ => Err(format!("Invalid cost grouping: {}", s)),
+        }
+    }
+}
+
+// `key` is the pipeline id, group id or provider id, depending on the
+// grouping. Pipelines outside any group are reported under an empty key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostEntry {
+    pub key: String,
+    pub name: String,
+    pub runs: i64,
+    pub cost: f64,
+}
+
+// A pipeline in several groups counts towards each of them, so group entries
+// can add up to more than `total`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostReport {
+    pub currency: String,
+    pub group_by: CostGroupBy,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub total: f64,
+    pub entries: Vec<CostEntry>,
+}
+
+// The most recent finished runs are compared against the ones before them.
+pub const ANOMALY_RECENT_RUNS: usize = 5;
+pub const ANOMALY_BASELINE_RUNS: usize = 45;
+const ANOMALY_MIN_BASELINE_RUNS: usize = 10;
+const ANOMALY_Z_THRESHOLD: f64 = 3.0;
+// Keeps a near-constant baseline from turning a few seconds into a huge z.
+const ANOMALY_MIN_DURATION_SPREAD: f64 = 0.05;
+const ANOMALY_MIN_SLOWDOWN: f64 = 1.2;
+const ANOMALY_MIN_RECENT_FAILURES: usize = 2;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    DurationRegression,
+    FailureRate,
+}
+
+impl AnomalyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnomalyKind::DurationRegression => "duration_regression",
+            AnomalyKind::FailureRate => "failure_rate",
+        }
+    }
+}
+
+impl std::str::FromStr for AnomalyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "duration_regression" => Ok(AnomalyKind::DurationRegression),
+            "failure_rate" => Ok(AnomalyKind::FailureRate),
+            _ => Err(format!("Invalid anomaly kind: {}", s)),
+        }
+    }
+}
+
+// `baseline` and `observed` are mean seconds for duration regressions and
+// failure ratios for failure rates. `run_number` is the newest run that was
+// part of the deviating window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricAnomaly {
+    pub pipeline_id: String,
+    pub kind: AnomalyKind,
+    pub baseline: f64,
+    pub observed: f64,
+    pub z_score: f64,
+    pub run_number: i64,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl MetricAnomaly {
+    pub fn describe(&self) -> String {
+        match self.kind {
+            AnomalyKind::DurationRegression => format!(
+                "Runs are taking {:.0}s on average, up from {:.0}s (z = {:.1})",
+                self.observed, self.baseline, self.z_score
+            ),
+            AnomalyKind::FailureRate => format!(
+                "Failure rate rose to {:.0}% from {:.0}% (z = {:.1})",
+                self.observed * 100.0,
+                self.baseline * 100.0,
+                self.z_score
+            ),
+        }
+    }
+}
+
+// A deviation of the recent window from the baseline. `first_run` lets the
+// caller tell a new episode from one that was already reported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnomalyFinding {
+    pub kind: AnomalyKind,
+    pub baseline: f64,
+    pub observed: f64,
+    pub z_score: f64,
+    pub first_run: i64,
+    pub last_run: i64,
+}
+
+impl AnomalyFinding {
+    // Only slowdowns and rising failure rates are reported; getting faster or
+    // more reliable is not something anyone needs to be paged about.
+    pub fn detect(runs: &[PipelineRun]) -> Vec<AnomalyFinding> {
+        let mut finished: Vec<&PipelineRun> = runs
+            .iter()
+            .filter(|run| matches!(run.status, PipelineStatus::Success | PipelineStatus::Failed))
+            .collect();
+        finished.sort_by_key(|run| (run.started_at, run.run_number));
+
+        if finished.len() < ANOMALY_RECENT_RUNS + ANOMALY_MIN_BASELINE_RUNS {
+            return Vec::new();
+        }
+        let split = finished.len() - ANOMALY_RECENT_RUNS;
+        let baseline = &finished[split.saturating_sub(ANOMALY_BASELINE_RUNS)..split];
+        let recent = &finished[split..];
+
+        [
+            Self::duration_regression(baseline, recent),
+            Self::failure_rate(baseline, recent),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    // Failed runs often stop early, so only successful runs are timed.
+    fn duration_regression(
+        baseline: &[&PipelineRun], recent: &[&PipelineRun],
+    ) -> Option<AnomalyFinding> {
+        let durations = |runs: &[&PipelineRun]| -> Vec<f64> {
+            runs.iter()
+                .filter(|run| run.status == PipelineStatus::Success)
+                .filter_map(|run| run.duration_seconds)
+                .map(|seconds| seconds as f64)
+                .collect()
+        };
+        let base = durations(baseline);
+        let recent_durations = durations(recent);
+        if base.len() < ANOMALY_MIN_BASELINE_RUNS || recent_durations.len() < 3 {
+            return None;
+        }
+
+        let base_mean = mean(&base);
+        let observed = mean(&recent_durations);
+        let variance =
+            base.iter().map(|d| (d - base_mean).powi(2)).sum::<f64>() / base.len() as f64;
+        let spread = variance
+            .sqrt()
+            .max(base_mean * ANOMALY_MIN_DURATION_SPREAD)
+            .max(1.0);
+        let z_score = (observed - base_mean) / (spread / (recent_durations.len() as f64).sqrt());
+
+        if z_score < ANOMALY_Z_THRESHOLD || observed < base_mean * ANOMALY_MIN_SLOWDOWN {
+            return None;
+        }
+
+        let timed: Vec<&&PipelineRun> = recent
+            .iter()
+            .filter(|run| run.status == PipelineStatus::Success && run.duration_seconds.is_some())
+            .collect();
+        Some(AnomalyFinding {
+            kind: AnomalyKind::DurationRegression,
+            baseline: round(base_mean),
+            observed: round(observed),
+            z_score: round(z_score),
+            first_run: timed.first()?.run_number,
+            last_run: timed.last()?.run_number,
+        })
+    }
+
+    fn failure_rate(baseline: &[&PipelineRun], recent: &[&PipelineRun]) -> Option<AnomalyFinding> {
+        let failures = |runs: &[&PipelineRun]| {
+            runs.iter()
+                .filter(|run| run.status == PipelineStatus::Failed)
+                .count()
+        };
+        let recent_failures = failures(recent);
+        if recent_failures < ANOMALY_MIN_RECENT_FAILURES {
+            return None;
+        }
+
+        let base_rate = failures(baseline) as f64 / baseline.len() as f64;
+        let observed = recent_failures as f64 / recent.len() as f64;
+        // A spotless baseline would otherwise make any failure infinitely
+        // surprising.
+        let p = base_rate.clamp(0.05, 0.95);
+        let z_score = (observed - base_rate) / (p * (1.0 - p) / recent.len() as f64).sqrt();
+
+        if z_score < ANOMALY_Z_THRESHOLD {
+            return None;
+        }
+
+        Some(AnomalyFinding {
+            kind: AnomalyKind::FailureRate,
+            baseline: round(base_rate),
+            observed: round(observed),
+            z_score: round(z_score),
+            first_run: recent.first()?.run_number,
+            last_run: recent.last()?.run_number,
+        })
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(run_number: i64, status: PipelineStatus, branch: &str, sha: &str) -> PipelineRun {
+        PipelineRun {
+            id: run_number.to_string(),
+            pipeline_id: "github__1__o__r__1".to_string(),
+            run_number,
+            status,
+            started_at: DateTime::from_timestamp(run_number * 60, 0).unwrap(),
+            concluded_at: None,
+            duration_seconds: None,
+            logs_url: String::new(),
+            commit_sha: Some(sha.to_string()),
+            commit_message: None,
+            branch: Some(branch.to_string()),
+            actor: None,
+            commit_author: None,
+            author_avatar_url: None,
+            pull_request: None,
+            inputs: None,
+            metadata: HashMap::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_stable_pipeline_scores_zero() {
+        let runs = vec![
+            run(1, PipelineStatus::Success, "main", "a"),
+            run(2, PipelineStatus::Success, "main", "b"),
+            run(3, PipelineStatus::Running, "main", "c"),
+        ];
+
+        let stats = FlakinessStats::from_runs(&runs);
+        assert_eq!(stats.runs_analyzed, 2);
+        assert_eq!(stats.transitions, 1);
+        assert_eq!(stats.flips, 0);
+        assert_eq!(stats.score(), 0.0);
+    }
+
+    #[test]
+    fn test_rerun_on_same_commit_counts_as_wasted() {
+        let runs = vec![
+            run(1, PipelineStatus::Failed, "main", "a"),
+            run(2, PipelineStatus::Success, "main", "a"),
+            run(3, PipelineStatus::Failed, "feature", "b"),
+            run(4, PipelineStatus::Failed, "main", "c"),
+            run(5, PipelineStatus::Success, "main", "c"),
+        ];
+
+        let stats = FlakinessStats::from_runs(&runs);
+        assert_eq!(stats.transitions, 3);
+        assert_eq!(stats.flips, 3);
+        assert_eq!(stats.wasted_reruns, 2);
+        assert_eq!(stats.score(), 0.7);
+        assert_eq!(stats.last_flip_at, Some(runs[4].started_at));
+    }
+
+    fn timed_run(run_number: i64, status: PipelineStatus, seconds: i64) -> PipelineRun {
+        PipelineRun {
+            duration_seconds: Some(seconds),
+            ..run(run_number, status, "main", &run_number.to_string())
+        }
+    }
+
+    #[test]
+    fn test_detects_duration_regression() {
+        let mut runs: Vec<PipelineRun> = (1..=20)
+            .map(|n| timed_run(n, PipelineStatus::Success, 100 + n % 3))
+            .collect();
+        assert!(AnomalyFinding::detect(&runs).is_empty());
+
+        runs.extend((21..=25).map(|n| timed_run(n, PipelineStatus::Success, 180)));
+        let findings = AnomalyFinding::detect(&runs);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, AnomalyKind::DurationRegression);
+        assert_eq!((findings[0].first_run, findings[0].last_run), (21, 25));
+        assert_eq!(findings[0].observed, 180.0);
+        assert!(findings[0].z_score >= 3.0);
+    }
+
+    #[test]
+    fn test_detects_failure_rate_spike() {
+        let mut runs: Vec<PipelineRun> = (1..=20)
+            .map(|n| {
+                let status = if n == 7 {
+                    PipelineStatus::Failed
+                } else {
+                    PipelineStatus::Success
+                };
+                timed_run(n, status, 100)
+            })
+            .collect();
+        runs.extend((21..=25).map(|n| {
+            let status = if n % 2 == 1 {
+                PipelineStatus::Failed
+            } else {
+                PipelineStatus::Success
+            };
+            timed_run(n, status, 100)
+        }));
+
+        let findings = AnomalyFinding::detect(&runs);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, AnomalyKind::FailureRate);
+        assert_eq!(findings[0].baseline, 0.05);
+        assert_eq!(findings[0].observed, 0.6);
+
+        // One failure in the window is within normal variation.
+        runs[22].status = PipelineStatus::Success;
+        runs[24].status = PipelineStatus::Success;
+        assert!(AnomalyFinding::detect(&runs).is_empty());
+    }
+
+    #[test]
+    fn test_estimate_run_cost_rounds_up_minutes() {
+        assert_eq!(estimate_run_cost(0, 0.008), 0.0);
+        assert_eq!(estimate_run_cost(1, 0.008), 0.008);
+        assert_eq!(estimate_run_cost(60, 0.008), 0.008);
+        assert_eq!(estimate_run_cost(61, 0.008), 0.016);
+    }
+
+    #[test]
+    fn test_rollup_granularity_for_query() {
+        let now = Utc::now();
+        let mut query = MetricsQuery {
+            metric_type: Some(MetricType::RunDuration),
+            aggregation_period: Some(AggregationPeriod::Daily),
+            start_date: Some(now - chrono::Duration::days(365)),
+            end_date: Some(now),
+            ..Default::default()
+        };
+        assert_eq!(
+            RollupGranularity::for_query(&query),
+            Some(RollupGranularity::Daily)
+        );
+
+        query.aggregation_type = Some(AggregationType::P95);
+        assert_eq!(RollupGranularity::for_query(&query), None);
+
+        query.aggregation_type = Some(AggregationType::Max);
+        query.start_date = Some(now - chrono::Duration::days(1));
+        assert_eq!(RollupGranularity::for_query(&query), None);
+
+        query.start_date = None;
+        query.aggregation_period = Some(AggregationPeriod::Hourly);
+        assert_eq!(
+            RollupGranularity::for_query(&query),
+            Some(RollupGranularity::Hourly)
+        );
+    }
+
+    #[test]
+    fn test_rollup_bucket_start() {
+        let ts = DateTime::parse_from_rfc3339("2025-03-04T15:42:10Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            RollupGranularity::Hourly.bucket_start(ts).to_rfc3339(),
+            "2025-03-04T15:00:00+00:00"
+        );
+        assert_eq!(
+            RollupGranularity::Daily.bucket_start(ts).to_rfc3339(),
+            "2025-03-04T00:00:00+00:00"
+        );
+    }
+}
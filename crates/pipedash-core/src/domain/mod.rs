@@ -1,44 +1,297 @@
+pub mod access;
+pub mod agent;
+pub mod approval;
+pub mod audit;
+pub mod backfill;
+pub mod backup;
+pub mod commit;
+pub mod dependency;
+pub mod diagnostics;
+pub mod discovery;
 pub mod error;
+pub mod export;
+pub mod gating;
+pub mod group;
+pub mod link;
+pub mod maintenance;
 pub mod metrics;
+pub mod notification;
+pub mod oauth;
+pub mod palette;
+pub mod pin;
 pub mod pipeline;
+pub mod pipeline_filter;
+pub mod preset;
 pub mod provider;
+pub mod provisioning;
+pub mod queue;
+pub mod redaction;
+pub mod refresh;
+pub mod release;
+pub mod resilience;
+pub mod retention;
+pub mod search;
 pub mod validation;
+pub mod view;
 
+pub use access::{
+    Action,
+    Role,
+};
+pub use agent::{
+    newly_offline,
+    BuildAgent,
+    ProviderAgent,
+};
+pub use approval::{
+    ApprovalStatus,
+    TriggerApproval,
+};
+pub use audit::{
+    AuditAction,
+    AuditEntry,
+};
+pub use backfill::{
+    BackfillStatus,
+    RunBackfillJob,
+    DEFAULT_BACKFILL_DEPTH,
+    MAX_BACKFILL_DEPTH,
+};
+pub use backup::{
+    validate_backup_id,
+    BackupFormat,
+    BackupInfo,
+    BackupVerification,
+};
+pub use commit::{
+    normalize_commit_prefix,
+    CommitRun,
+    CommitStatus,
+};
+pub use dependency::{
+    finished_pipelines,
+    validate_dependency,
+    PipelineDependency,
+    PipelineGraph,
+    PipelineGraphNode,
+};
+pub use diagnostics::{
+    auth_finding,
+    clock_skew_finding,
+    permission_findings,
+    rate_limit_finding,
+    reachability_finding,
+    server_version_finding,
+    DiagnosticCheck,
+    DiagnosticFinding,
+    EndpointProbe,
+    FindingSeverity,
+    ProviderDiagnosis,
+    SchemaMigration,
+    SchemaStatus,
+};
+pub use discovery::{
+    add_selected_items,
+    keep_discovered_items,
+    selected_items,
+    DiscoverySettings,
+    AUTO_DISCOVER_KEY,
+    DISCOVERY_ORGS_KEY,
+    SELECTED_ITEMS_KEY,
+};
 pub use error::{
     DomainError,
     DomainResult,
 };
+pub use export::{
+    export_header,
+    ExportFilter,
+    ExportFormat,
+    ExportRecord,
+    EXPORT_PAGE_SIZE,
+};
+pub use gating::GatedOperation;
+pub use group::{
+    aggregate_status,
+    PipelineGroup,
+    PipelineGroupStatus,
+};
+pub use link::{
+    PipelineLink,
+    DEEP_LINK_SCHEME,
+};
+pub use maintenance::{
+    ActiveMaintenance,
+    MaintenanceCalendar,
+    MaintenanceSchedule,
+    MaintenanceWindow,
+    MAINTENANCE_METADATA_KEY,
+};
 pub use metrics::{
+    estimate_run_cost,
     AggregatedMetric,
     AggregatedMetrics,
     AggregationPeriod,
     AggregationType,
+    AnomalyFinding,
+    AnomalyKind,
+    CostEntry,
+    CostGroupBy,
+    CostReport,
+    FlakinessStats,
+    FlakyPipeline,
     GlobalMetricsConfig,
+    MetricAnomaly,
     MetricEntry,
     MetricMetadata,
     MetricType,
     MetricsConfig,
     MetricsConfigExport,
     MetricsQuery,
+    MetricsRebuild,
     MetricsStats,
     PipelineMetricsStats,
+    RollupGranularity,
+    RunMetric,
+    RunMetricType,
+    ANOMALY_BASELINE_RUNS,
+    ANOMALY_RECENT_RUNS,
+    ROLLUP_MIN_RANGE_DAYS,
+};
+pub use notification::{
+    validate_channel_url,
+    AppNotification,
+    AppNotificationKind,
+    ChannelKind,
+    NotificationChannel,
+    NotificationDelivery,
+    NotificationMessage,
+    NotificationRule,
+    NotificationSuppression,
+    QuietHours,
+    DEFAULT_EMAIL_BODY,
+    DEFAULT_EMAIL_SUBJECT,
+};
+pub use oauth::{
+    plugin_token,
+    DeviceAuthorization,
+    OAuthCredentials,
+    OAuthPollStatus,
+};
+pub use palette::{
+    fuzzy_score,
+    pipeline_actions,
+    rank_pipelines,
+    ActionAvailability,
+    PaletteAction,
+    PipelineUsage,
+    QuickAction,
+};
+pub use pin::{
+    merge_pins,
+    PinnedPipeline,
+    StatusSummary,
 };
 pub use pipeline::{
+    AnnotationLevel,
+    ArtifactDownload,
+    BranchBoard,
+    BranchStatus,
+    BuildArtifact,
+    BulkItemResult,
+    BulkOperation,
+    CommitInfo,
+    JobLog,
+    LogQuery,
+    LogStep,
     PaginatedAvailablePipelines,
     PaginatedRunHistory,
     PaginationParams,
+    ParameterChange,
+    PendingApproval,
     Pipeline,
+    PipelineListQuery,
     PipelineRun,
+    PipelineSortField,
     PipelineStatus,
+    PullRequestRef,
+    RunAnnotation,
+    RunComparison,
+    RunLogs,
+    RunRef,
+    SortOrder,
     TriggerParams,
 };
+pub use pipeline_filter::{
+    PipelineFilter,
+    EXCLUDE_PIPELINES_KEY,
+    INCLUDE_PIPELINES_KEY,
+};
+pub use preset::{
+    branch_parameter,
+    validate_preset_parameters,
+    TriggerPreset,
+};
 pub use provider::{
+    outage_started,
     FetchStatus,
     Provider,
     ProviderConfig,
+    ProviderHealth,
+    ProviderHealthCheck,
     ProviderSummary,
+    PROVIDER_HEALTH_WINDOW,
+    PROVIDER_OUTAGE_THRESHOLD,
+};
+pub use provisioning::{
+    resolve_pipeline_reference,
+    PlanAction,
+    PlannedChange,
+    ProvisionedResource,
+    ProvisioningPlan,
+};
+pub use queue::{
+    queue_metrics_id,
+    BuildQueue,
+    ProviderQueue,
+};
+pub use redaction::{
+    redactor,
+    set_redactor,
+    RedactionConfig,
+    Redactor,
+    REDACTION_MASK,
+};
+pub use refresh::{
+    PipelineRefreshSetting,
+    RefreshSchedule,
+    MAX_PIPELINE_REFRESH_SECS,
+    MIN_PIPELINE_REFRESH_SECS,
+};
+pub use release::{
+    ReleaseCommit,
+    ReleaseContributor,
+    ReleasePullRequest,
+    ReleaseReport,
+};
+pub use resilience::{
+    CircuitBreaker,
+    CircuitBreakerStatus,
+    CircuitState,
+    ResilienceConfig,
+};
+pub use retention::{
+    GlobalRunRetentionConfig,
+    PipelineRunRetention,
+    RunRetentionPolicy,
+};
+pub use search::{
+    SearchQuery,
+    SearchResult,
+    SearchResultKind,
 };
 pub use validation::{
+    normalize_labels,
     validate_config,
     validate_pagination,
     validate_pipeline_id,
@@ -46,3 +299,7 @@ pub use validation::{
     validate_provider_type,
     validate_trigger_params,
 };
+pub use view::{
+    SavedView,
+    ViewQuery,
+};
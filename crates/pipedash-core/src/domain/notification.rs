@@ -0,0 +1,695 @@
+use chrono::{
+    DateTime,
+    Duration,
+    NaiveTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+use super::pipeline::{
+    Pipeline,
+    PipelineRun,
+    PipelineStatus,
+};
+
+pub const DEFAULT_EMAIL_SUBJECT: &str = "[Pipedash] {pipeline} {status} on {branch}";
+
+pub const DEFAULT_EMAIL_BODY: &str = "{pipeline} ({repository}) finished with status {status} \
+                                      on {branch}.\n\n{summary}";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelKind {
+    Slack,
+    Teams,
+    Discord,
+    Webhook,
+    Email,
+}
+
+impl ChannelKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChannelKind::Slack => "slack",
+            ChannelKind::Teams => "teams",
+            ChannelKind::Discord => "discord",
+            ChannelKind::Webhook => "webhook",
+            ChannelKind::Email => "email",
+        }
+    }
+
+    // Slack, Teams and Discord get their own incoming-webhook format; generic
+    // webhooks and email receive the message as JSON.
+    pub fn payload(&self, message: &NotificationMessage) -> serde_json::Value {
+        let text = match &message.link {
+            Some(link) => format!("{}\n{}", message.text(), link),
+            None => message.text(),
+        };
+        match self {
+            ChannelKind::Slack => serde_json::json!({ "text": text }),
+            ChannelKind::Teams => serde_json::json!({
+                "@type": "MessageCard",
+                "@context": "https://schema.org/extensions",
+                "summary": text,
+                "themeColor": message.color(),
+                "text": text,
+            }),
+            ChannelKind::Discord => serde_json::json!({ "content": text }),
+            ChannelKind::Webhook | ChannelKind::Email => serde_json::json!(message),
+        }
+    }
+}
+
+impl std::str::FromStr for ChannelKind {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "slack" => Ok(ChannelKind::Slack),
+            "teams" => Ok(ChannelKind::Teams),
+            "discord" => Ok(ChannelKind::Discord),
+            "webhook" => Ok(ChannelKind::Webhook),
+            "email" => Ok(ChannelKind::Email),
+            other => Err(DomainError::InvalidConfig(format!(
+                "Unknown notification channel type '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+// Every set field narrows the rule; a rule with no filters matches every
+// finished pipeline.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotificationRule {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pipeline_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub group_ids: Vec<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<PipelineStatus>,
+    // Also send metric anomalies for the pipelines this rule covers. Status
+    // filters do not apply to them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub anomalies: bool,
+}
+
+impl NotificationRule {
+    pub fn matches(&self, pipeline: &Pipeline, pipeline_group_ids: &[i64]) -> bool {
+        self.covers(pipeline, pipeline_group_ids)
+            && (self.statuses.is_empty() || self.statuses.contains(&pipeline.status))
+    }
+
+    pub fn matches_anomaly(&self, pipeline: &Pipeline, pipeline_group_ids: &[i64]) -> bool {
+        self.anomalies && self.covers(pipeline, pipeline_group_ids)
+    }
+
+    fn covers(&self, pipeline: &Pipeline, pipeline_group_ids: &[i64]) -> bool {
+        if !self.pipeline_ids.is_empty() && !self.pipeline_ids.contains(&pipeline.id) {
+            return false;
+        }
+
+        self.group_ids.is_empty()
+            || self
+                .group_ids
+                .iter()
+                .any(|id| pipeline_group_ids.contains(id))
+    }
+}
+
+// A wall-clock window in the user's timezone; `start` after `end` wraps past
+// midnight.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+}
+
+impl QuietHours {
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        let local = (at + Duration::minutes(self.utc_offset_minutes as i64)).time();
+        if self.start <= self.end {
+            local >= self.start && local < self.end
+        } else {
+            local >= self.start || local < self.end
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotificationSuppression {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+    // A pipeline finishing with the same status again within this many
+    // minutes is not re-sent. 0 disables deduplication.
+    #[serde(default)]
+    pub dedup_window_minutes: u32,
+    // Skip runs that finish with the same status as the run before them.
+    #[serde(default)]
+    pub transitions_only: bool,
+}
+
+impl NotificationSuppression {
+    const MAX_DEDUP_WINDOW_MINUTES: u32 = 7 * 24 * 60;
+
+    pub fn validate(&self) -> DomainResult<()> {
+        if let Some(quiet_hours) = &self.quiet_hours {
+            if quiet_hours.start == quiet_hours.end {
+                return Err(DomainError::InvalidConfig(
+                    "Quiet hours must start and end at different times".to_string(),
+                ));
+            }
+            if quiet_hours.utc_offset_minutes.abs() > 14 * 60 {
+                return Err(DomainError::InvalidConfig(format!(
+                    "Invalid UTC offset: {} minutes",
+                    quiet_hours.utc_offset_minutes
+                )));
+            }
+        }
+
+        if self.dedup_window_minutes > Self::MAX_DEDUP_WINDOW_MINUTES {
+            return Err(DomainError::InvalidConfig(
+                "Deduplication window cannot exceed 7 days".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn is_quiet(&self, at: DateTime<Utc>) -> bool {
+        self.quiet_hours
+            .as_ref()
+            .is_some_and(|quiet_hours| quiet_hours.contains(at))
+    }
+}
+
+// A channel without rules receives nothing; rules are combined with OR. For
+// email channels `url` holds a comma-separated list of recipients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationChannel {
+    pub id: i64,
+    pub name: String,
+    pub kind: ChannelKind,
+    pub url: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub rules: Vec<NotificationRule>,
+    #[serde(default)]
+    pub suppression: NotificationSuppression,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl NotificationChannel {
+    pub fn routes(&self, pipeline: &Pipeline, pipeline_group_ids: &[i64]) -> bool {
+        self.enabled
+            && self
+                .rules
+                .iter()
+                .any(|rule| rule.matches(pipeline, pipeline_group_ids))
+    }
+
+    pub fn routes_anomaly(&self, pipeline: &Pipeline, pipeline_group_ids: &[i64]) -> bool {
+        self.enabled
+            && self
+                .rules
+                .iter()
+                .any(|rule| rule.matches_anomaly(pipeline, pipeline_group_ids))
+    }
+
+    pub fn recipients(&self) -> Vec<&str> {
+        self.url
+            .split(',')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .collect()
+    }
+}
+
+pub fn validate_channel_url(kind: ChannelKind, url: &str) -> DomainResult<()> {
+    if kind == ChannelKind::Email {
+        return validate_recipients(url);
+    }
+
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err(DomainError::InvalidConfig(
+            "Notification channel URL must start with http:// or https://".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_recipients(recipients: &str) -> DomainResult<()> {
+    let mut count = 0;
+    for recipient in recipients
+        .split(',')
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+    {
+        let valid = recipient
+            .split_once('@')
+            .is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.'));
+        if !valid || recipient.contains(char::is_whitespace) {
+            return Err(DomainError::InvalidConfig(format!(
+                "Invalid email address '{}'",
+                recipient
+            )));
+        }
+        count += 1;
+    }
+
+    if count == 0 {
+        return Err(DomainError::InvalidConfig(
+            "Email channels need at least one recipient".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationMessage {
+    pub pipeline_id: String,
+    pub pipeline_name: String,
+    pub repository: String,
+    pub provider_type: String,
+    pub status: PipelineStatus,
+    pub branch: Option<String>,
+    // Details of the run that finished, when it is in the run cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    // Canonical web or deep link back to the pipeline in Pipedash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+impl NotificationMessage {
+    pub fn from_pipeline(pipeline: &Pipeline) -> Self {
+        Self {
+            pipeline_id: pipeline.id.clone(),
+            pipeline_name: pipeline.name.clone(),
+            repository: pipeline.repository.clone(),
+            provider_type: pipeline.provider_type.clone(),
+            status: pipeline.status.clone(),
+            branch: pipeline.branch.clone(),
+            summary: None,
+            link: None,
+        }
+    }
+
+    pub fn with_run(mut self, run: &PipelineRun) -> Self {
+        let mut lines = vec![format!("Run #{}: {}", run.run_number, run.status.as_str())];
+        if let Some(actor) = &run.actor {
+            lines.push(format!("Triggered by: {}", actor));
+        }
+        if let Some(duration) = run.duration_seconds {
+            lines.push(format!("Duration: {}m {}s", duration / 60, duration % 60));
+        }
+        if let Some(sha) = &run.commit_sha {
+            let short = sha.get(..7).unwrap_or(sha);
+            let message = run
+                .commit_message
+                .as_deref()
+                .and_then(|m| m.lines().next())
+                .unwrap_or_default();
+            lines.push(
+                format!("Commit: {} {}", short, message)
+                    .trim_end()
+                    .to_string(),
+            );
+        }
+        if !run.logs_url.is_empty() {
+            lines.push(format!("Logs: {}", run.logs_url));
+        }
+
+        if self.branch.is_none() {
+            self.branch = run.branch.clone();
+        }
+        self.summary = Some(lines.join("\n"));
+        self
+    }
+
+    // Replaces {pipeline}, {repository}, {provider}, {status}, {branch},
+    // {summary} and {link} in a subject or body template.
+    pub fn render(&self, template: &str) -> String {
+        template
+            .replace("{pipeline}", &self.pipeline_name)
+            .replace("{repository}", &self.repository)
+            .replace("{provider}", &self.provider_type)
+            .replace("{status}", self.status.as_str())
+            .replace("{branch}", self.branch.as_deref().unwrap_or("-"))
+            .replace("{summary}", self.summary.as_deref().unwrap_or_default())
+            .replace("{link}", self.link.as_deref().unwrap_or_default())
+    }
+
+    pub fn text(&self) -> String {
+        let branch = self
+            .branch
+            .as_deref()
+            .map(|b| format!(" on {}", b))
+            .unwrap_or_default();
+        format!(
+            "{} ({}){}: {}",
+            self.pipeline_name,
+            self.repository,
+            branch,
+            self.status.as_str()
+        )
+    }
+
+    fn color(&self) -> &'static str {
+        match self.status {
+            PipelineStatus::Success => "2EB67D",
+            PipelineStatus::Failed => "E01E5A",
+            _ => "808080",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationDelivery {
+    pub channel_id: i64,
+    pub pipeline_id: Option<String>,
+    pub status: Option<PipelineStatus>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub delivered_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppNotificationKind {
+    Failure,
+    Recovery,
+    TokenExpiry,
+    SyncConflict,
+    ProviderOutage,
+    ProviderDiagnostics,
+}
+
+impl AppNotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppNotificationKind::Failure => "failure",
+            AppNotificationKind::Recovery => "recovery",
+            AppNotificationKind::TokenExpiry => "token_expiry",
+            AppNotificationKind::SyncConflict => "sync_conflict",
+            AppNotificationKind::ProviderOutage => "provider_outage",
+            AppNotificationKind::ProviderDiagnostics => "provider_diagnostics",
+        }
+    }
+}
+
+// An entry in the in-app notification center.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppNotification {
+    pub id: i64,
+    pub kind: AppNotificationKind,
+    pub title: String,
+    pub message: String,
+    pub pipeline_id: Option<String>,
+    pub provider_id: Option<i64>,
+    pub read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AppNotification {
+    pub fn new(kind: AppNotificationKind, title: String, message: String) -> Self {
+        Self {
+            id: 0,
+            kind,
+            title,
+            message,
+            pipeline_id: None,
+            provider_id: None,
+            read: false,
+            created_at: Utc::now(),
+        }
+    }
+
+    // Failed runs always notify; a success only counts as a recovery when the
+    // pipeline's previous finished run failed.
+    pub fn for_status_change(
+        pipeline: &Pipeline, last_finished: Option<&PipelineStatus>,
+    ) -> Option<Self> {
+        let kind = match (&pipeline.status, last_finished) {
+            (PipelineStatus::Failed, _) => AppNotificationKind::Failure,
+            (PipelineStatus::Success, Some(PipelineStatus::Failed)) => {
+                AppNotificationKind::Recovery
+            }
+            _ => return None,
+        };
+        let title = match kind {
+            AppNotificationKind::Failure => format!("{} failed", pipeline.name),
+            _ => format!("{} recovered", pipeline.name),
+        };
+
+        let mut notification = Self::new(
+            kind,
+            title,
+            NotificationMessage::from_pipeline(pipeline).text(),
+        );
+        notification.pipeline_id = Some(pipeline.id.clone());
+        notification.provider_id = Some(pipeline.provider_id);
+        Some(notification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn pipeline(status: PipelineStatus) -> Pipeline {
+        Pipeline {
+            id: "github__1__org__deploy".to_string(),
+            provider_id: 1,
+            provider_type: "github".to_string(),
+            name: "deploy".to_string(),
+            status,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "org/app".to_string(),
+            branch: Some("main".to_string()),
+            workflow_file: None,
+            metadata: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    fn channel(rules: Vec<NotificationRule>) -> NotificationChannel {
+        NotificationChannel {
+            id: 1,
+            name: "alerts".to_string(),
+            kind: ChannelKind::Slack,
+            url: "https://hooks.slack.com/services/x".to_string(),
+            enabled: true,
+            rules,
+            suppression: NotificationSuppression::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_channel_routing() {
+        let failures = NotificationRule {
+            statuses: vec![PipelineStatus::Failed],
+            ..Default::default()
+        };
+        let release_group = NotificationRule {
+            group_ids: vec![7],
+            ..Default::default()
+        };
+        let routed = channel(vec![failures, release_group]);
+
+        assert!(routed.routes(&pipeline(PipelineStatus::Failed), &[]));
+        assert!(!routed.routes(&pipeline(PipelineStatus::Success), &[]));
+        assert!(routed.routes(&pipeline(PipelineStatus::Success), &[7]));
+
+        let mut disabled = routed.clone();
+        disabled.enabled = false;
+        assert!(!disabled.routes(&pipeline(PipelineStatus::Failed), &[]));
+
+        assert!(!channel(Vec::new()).routes(&pipeline(PipelineStatus::Failed), &[]));
+    }
+
+    #[test]
+    fn test_anomaly_routing_ignores_statuses() {
+        let failures = NotificationRule {
+            statuses: vec![PipelineStatus::Failed],
+            ..Default::default()
+        };
+        assert!(!channel(vec![failures.clone()])
+            .routes_anomaly(&pipeline(PipelineStatus::Success), &[]));
+
+        let anomalies = NotificationRule {
+            anomalies: true,
+            ..failures
+        };
+        let routed = channel(vec![anomalies]);
+        assert!(routed.routes_anomaly(&pipeline(PipelineStatus::Success), &[]));
+        assert!(!routed.routes(&pipeline(PipelineStatus::Success), &[]));
+    }
+
+    #[test]
+    fn test_quiet_hours() {
+        let at = |time: &str| {
+            DateTime::parse_from_rfc3339(&format!("2025-02-10T{}:00Z", time))
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let overnight = QuietHours {
+            start: "22:00".parse().unwrap(),
+            end: "07:00".parse().unwrap(),
+            utc_offset_minutes: 0,
+        };
+        assert!(overnight.contains(at("23:30")));
+        assert!(overnight.contains(at("06:59")));
+        assert!(!overnight.contains(at("07:00")));
+        assert!(!overnight.contains(at("12:00")));
+
+        // 20:30 UTC is 22:30 at UTC+2.
+        let shifted = QuietHours {
+            utc_offset_minutes: 120,
+            ..overnight.clone()
+        };
+        assert!(shifted.contains(at("20:30")));
+        assert!(!overnight.contains(at("20:30")));
+
+        let suppression = NotificationSuppression {
+            quiet_hours: Some(overnight),
+            ..Default::default()
+        };
+        assert!(suppression.is_quiet(at("01:00")));
+        assert!(!NotificationSuppression::default().is_quiet(at("01:00")));
+    }
+
+    #[test]
+    fn test_suppression_validation() {
+        assert!(NotificationSuppression::default().validate().is_ok());
+
+        let empty_window = NotificationSuppression {
+            quiet_hours: Some(QuietHours {
+                start: "09:00".parse().unwrap(),
+                end: "09:00".parse().unwrap(),
+                utc_offset_minutes: 0,
+            }),
+            ..Default::default()
+        };
+        assert!(empty_window.validate().is_err());
+
+        let long_window = NotificationSuppression {
+            dedup_window_minutes: 8 * 24 * 60,
+            ..Default::default()
+        };
+        assert!(long_window.validate().is_err());
+    }
+
+    #[test]
+    fn test_status_change_notifications() {
+        let failed = pipeline(PipelineStatus::Failed);
+        let succeeded = pipeline(PipelineStatus::Success);
+
+        let failure = AppNotification::for_status_change(&failed, None).unwrap();
+        assert_eq!(failure.kind, AppNotificationKind::Failure);
+        assert_eq!(failure.title, "deploy failed");
+        assert_eq!(
+            failure.pipeline_id.as_deref(),
+            Some("github__1__org__deploy")
+        );
+
+        let recovery =
+            AppNotification::for_status_change(&succeeded, Some(&PipelineStatus::Failed)).unwrap();
+        assert_eq!(recovery.kind, AppNotificationKind::Recovery);
+
+        assert!(
+            AppNotification::for_status_change(&succeeded, Some(&PipelineStatus::Success))
+                .is_none()
+        );
+        assert!(AppNotification::for_status_change(&succeeded, None).is_none());
+        assert!(
+            AppNotification::for_status_change(&pipeline(PipelineStatus::Running), None).is_none()
+        );
+    }
+
+    #[test]
+    fn test_email_recipients() {
+        assert!(
+            validate_channel_url(ChannelKind::Email, "ops@example.com, dev@example.com").is_ok()
+        );
+        assert!(validate_channel_url(ChannelKind::Email, "not-an-address").is_err());
+        assert!(validate_channel_url(ChannelKind::Email, " , ").is_err());
+        assert!(validate_channel_url(ChannelKind::Slack, "ops@example.com").is_err());
+
+        let mut email = channel(Vec::new());
+        email.kind = ChannelKind::Email;
+        email.url = "ops@example.com, dev@example.com,".to_string();
+        assert_eq!(
+            email.recipients(),
+            vec!["ops@example.com", "dev@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let run = PipelineRun {
+            id: "1".to_string(),
+            pipeline_id: "github__1__org__deploy".to_string(),
+            run_number: 42,
+            status: PipelineStatus::Failed,
+            started_at: Utc::now(),
+            concluded_at: None,
+            duration_seconds: Some(125),
+            logs_url: "https://example.com/runs/42".to_string(),
+            commit_sha: Some("0123456789abcdef".to_string()),
+            commit_message: Some("Fix deploy\n\nDetails".to_string()),
+            branch: Some("main".to_string()),
+            actor: Some("octocat".to_string()),
+            commit_author: None,
+            author_avatar_url: None,
+            pull_request: None,
+            inputs: None,
+            metadata: HashMap::new(),
+            annotations: Vec::new(),
+        };
+        let message =
+            NotificationMessage::from_pipeline(&pipeline(PipelineStatus::Failed)).with_run(&run);
+
+        assert_eq!(
+            message.render(DEFAULT_EMAIL_SUBJECT),
+            "[Pipedash] deploy failed on main"
+        );
+        let summary = message.summary.as_deref().unwrap();
+        assert!(summary.contains("Duration: 2m 5s"));
+        assert!(summary.contains("Commit: 0123456 Fix deploy"));
+        assert!(message
+            .render(DEFAULT_EMAIL_BODY)
+            .ends_with("Logs: https://example.com/runs/42"));
+    }
+
+    #[test]
+    fn test_payload_formats() {
+        let message = NotificationMessage::from_pipeline(&pipeline(PipelineStatus::Failed));
+        assert_eq!(message.text(), "deploy (org/app) on main: failed");
+
+        assert_eq!(
+            ChannelKind::Slack.payload(&message)["text"],
+            "deploy (org/app) on main: failed"
+        );
+        assert_eq!(ChannelKind::Teams.payload(&message)["themeColor"], "E01E5A");
+        assert!(ChannelKind::Discord.payload(&message)["content"].is_string());
+        assert_eq!(
+            ChannelKind::Webhook.payload(&message)["pipeline_id"],
+            "github__1__org__deploy"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use chrono::{
+    DateTime,
+    Duration,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+const CREDENTIALS_PREFIX: &str = "oauth:";
+
+// Refresh this long before the access token expires, so a fetch never goes out
+// with a token that dies mid-request.
+const REFRESH_MARGIN_SECS: i64 = 300;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceAuthorization {
+    pub session_id: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub interval_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum OAuthPollStatus {
+    Pending { interval_secs: u64 },
+    // `token` goes into the provider config like a pasted token would.
+    Authorized { token: String },
+    Denied,
+    Expired,
+}
+
+// Tokens that expire come with a refresh token. Both are kept in the provider's
+// token slot, serialized with a prefix, so they go through the same TokenStore
+// as any other token. Plugins only ever see `access_token`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuthCredentials {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub token_url: String,
+    pub client_id: String,
+}
+
+impl OAuthCredentials {
+    pub fn parse(stored: &str) -> Option<Self> {
+        serde_json::from_str(stored.strip_prefix(CREDENTIALS_PREFIX)?).ok()
+    }
+
+    pub fn to_token_string(&self) -> String {
+        format!(
+            "{}{}",
+            CREDENTIALS_PREFIX,
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+
+    pub fn is_refreshable(&self) -> bool {
+        self.refresh_token.is_some() && self.expires_at.is_some()
+    }
+
+    pub fn refresh_at(&self) -> Option<DateTime<Utc>> {
+        self.refresh_token.as_ref()?;
+        self.expires_at
+            .map(|at| at - Duration::seconds(REFRESH_MARGIN_SECS))
+    }
+
+    pub fn needs_refresh(&self, now: DateTime<Utc>) -> bool {
+        self.refresh_at().is_some_and(|at| at <= now)
+    }
+}
+
+// The token a plugin should be initialized with for a stored token value.
+pub fn plugin_token(stored: &str) -> String {
+    match OAuthCredentials::parse(stored) {
+        Some(credentials) => credentials.access_token,
+        None => stored.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credentials(expires_at: Option<DateTime<Utc>>) -> OAuthCredentials {
+        OAuthCredentials {
+            access_token: "access".to_string(),
+            refresh_token: Some("refresh".to_string()),
+            expires_at,
+            token_url: "https://gitlab.com/oauth/token".to_string(),
+            client_id: "client".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_credentials_round_trip() {
+        let credentials = credentials(Some(Utc::now()));
+        let stored = credentials.to_token_string();
+
+        assert_eq!(OAuthCredentials::parse(&stored), Some(credentials));
+        assert_eq!(plugin_token(&stored), "access");
+        assert_eq!(plugin_token("glpat-xxxx"), "glpat-xxxx");
+    }
+
+    #[test]
+    fn test_needs_refresh() {
+        let now = Utc::now();
+
+        assert!(credentials(Some(now + Duration::seconds(60))).needs_refresh(now));
+        assert!(!credentials(Some(now + Duration::hours(1))).needs_refresh(now));
+        assert!(!credentials(None).needs_refresh(now));
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use pipedash_plugin_api::{
+    FeatureAvailability,
+    PluginCapabilities,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::gating::GatedOperation;
+use super::pipeline::{
+    Pipeline,
+    PipelineStatus,
+};
+
+const MATCH_SCORE: u32 = 1;
+const BOUNDARY_BONUS: u32 = 8;
+const CONSECUTIVE_BONUS: u32 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineUsage {
+    pub pipeline_id: String,
+    pub use_count: i64,
+    pub last_used_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaletteAction {
+    Open,
+    ViewMetrics,
+    ViewBranches,
+    Trigger,
+    Cancel,
+    RetryFailedJobs,
+    Pin,
+    Unpin,
+}
+
+impl PaletteAction {
+    fn operation(&self) -> Option<GatedOperation> {
+        match self {
+            PaletteAction::Open | PaletteAction::ViewMetrics | PaletteAction::ViewBranches => {
+                Some(GatedOperation::View)
+            }
+            PaletteAction::Trigger | PaletteAction::RetryFailedJobs => {
+                Some(GatedOperation::Trigger)
+            }
+            PaletteAction::Cancel => Some(GatedOperation::Cancel),
+            PaletteAction::Pin | PaletteAction::Unpin => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionAvailability {
+    pub action: PaletteAction,
+    pub available: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_permissions: Vec<String>,
+}
+
+// A pipeline offered by the command palette with what can be done to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickAction {
+    pub pipeline_id: String,
+    pub pipeline_name: String,
+    pub repository: String,
+    pub provider_id: i64,
+    pub provider_type: String,
+    pub status: PipelineStatus,
+    // Fuzzy match score, when a query was given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub actions: Vec<ActionAvailability>,
+}
+
+impl QuickAction {
+    pub fn new(
+        pipeline: &Pipeline, score: Option<u32>, usage: Option<&PipelineUsage>,
+        actions: Vec<ActionAvailability>,
+    ) -> Self {
+        Self {
+            pipeline_id: pipeline.id.clone(),
+            pipeline_name: pipeline.name.clone(),
+            repository: pipeline.repository.clone(),
+            provider_id: pipeline.provider_id,
+            provider_type: pipeline.provider_type.clone(),
+            status: pipeline.status.clone(),
+            score,
+            last_used_at: usage.map(|u| u.last_used_at),
+            actions,
+        }
+    }
+}
+
+// Subsequence match of `query` in `text`, ignoring case and whitespace in the
+// query. Matches at word starts and runs of consecutive characters score
+// higher, so "dpr" ranks "deploy-prod" above "dependency-report". The best
+// alignment is picked, not just the leftmost one.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let Some((&first, rest)) = query.split_first() else {
+        return Some(0);
+    };
+
+    let text: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = text
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let bonus: Vec<u32> = (0..text.len())
+        .map(|i| {
+            let boundary = match i.checked_sub(1).map(|prev| text[prev]) {
+                None => true,
+                Some(prev) => {
+                    !prev.is_alphanumeric() || (prev.is_lowercase() && text[i].is_uppercase())
+                }
+            };
+            MATCH_SCORE + if boundary { BOUNDARY_BONUS } else { 0 }
+        })
+        .collect();
+
+    // Best score with the current query character matched at each position.
+    let mut best: Vec<Option<u32>> = lower
+        .iter()
+        .zip(&bonus)
+        .map(|(c, bonus)| (*c == first).then_some(*bonus))
+        .collect();
+    for &qc in rest {
+        let mut next = vec![None; text.len()];
+        let mut best_before = None;
+        for i in 0..text.len() {
+            if i >= 2 {
+                best_before = best_before.max(best[i - 2]);
+            }
+            if lower[i] != qc {
+                continue;
+            }
+            let adjacent = i
+                .checked_sub(1)
+                .and_then(|prev| best[prev])
+                .map(|score| score + CONSECUTIVE_BONUS);
+            next[i] = best_before.max(adjacent).map(|score| score + bonus[i]);
+        }
+        best = next;
+    }
+
+    best.into_iter().flatten().max()
+}
+
+// Without a query the recently used pipelines come back, most recent first.
+// With one, cached pipeline names are fuzzy matched and ranked by score, then
+// by recent use and name length.
+pub fn rank_pipelines<'a>(
+    query: Option<&str>, pipelines: &'a [Pipeline], usage: &[PipelineUsage], limit: usize,
+) -> Vec<(&'a Pipeline, Option<u32>)> {
+    let query = query.map(str::trim).filter(|q| !q.is_empty());
+    let by_id: HashMap<&str, &Pipeline> = pipelines.iter().map(|p| (p.id.as_str(), p)).collect();
+
+    let Some(query) = query else {
+        return usage
+            .iter()
+            .filter_map(|u| by_id.get(u.pipeline_id.as_str()))
+            .take(limit)
+            .map(|pipeline| (*pipeline, None))
+            .collect();
+    };
+
+    let last_used: HashMap<&str, DateTime<Utc>> = usage
+        .iter()
+        .map(|u| (u.pipeline_id.as_str(), u.last_used_at))
+        .collect();
+    let mut matches: Vec<(&Pipeline, u32)> = pipelines
+        .iter()
+        .filter_map(|p| fuzzy_score(query, &p.name).map(|score| (p, score)))
+        .collect();
+    matches.sort_by(|(a, a_score), (b, b_score)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| {
+                last_used
+                    .get(b.id.as_str())
+                    .cmp(&last_used.get(a.id.as_str()))
+            })
+            .then_with(|| a.name.len().cmp(&b.name.len()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|(pipeline, score)| (pipeline, Some(score)))
+        .collect()
+}
+
+pub fn pipeline_actions(
+    pipeline: &Pipeline, capabilities: &PluginCapabilities, features: &[FeatureAvailability],
+    pinned: bool,
+) -> Vec<ActionAvailability> {
+    let running = matches!(
+        pipeline.status,
+        PipelineStatus::Running
+            | PipelineStatus::Pending
+            | PipelineStatus::Queued
+            | PipelineStatus::Waiting
+    );
+
+    let mut actions = vec![PaletteAction::Open, PaletteAction::ViewMetrics];
+    if capabilities.branch_statuses {
+        actions.push(PaletteAction::ViewBranches);
+    }
+    if capabilities.trigger {
+        actions.push(PaletteAction::Trigger);
+    }
+    if running && capabilities.cancel {
+        actions.push(PaletteAction::Cancel);
+    }
+    if capabilities.retry_failed_jobs && pipeline.status == PipelineStatus::Failed {
+        actions.push(PaletteAction::RetryFailedJobs);
+    }
+    actions.push(if pinned {
+        PaletteAction::Unpin
+    } else {
+        PaletteAction::Pin
+    });
+
+    actions
+        .into_iter()
+        .map(|action| {
+            let missing_permissions = action
+                .operation()
+                .map(|operation| operation.missing_permissions(features))
+                .unwrap_or_default();
+            ActionAvailability {
+                action,
+                available: missing_permissions.is_empty(),
+                missing_permissions,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use pipedash_plugin_api::Feature;
+
+    use super::*;
+
+    fn pipeline(id: &str, name: &str, status: PipelineStatus) -> Pipeline {
+        Pipeline {
+            id: id.to_string(),
+            provider_id: 1,
+            provider_type: "github".to_string(),
+            name: name.to_string(),
+            status,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "acme/api".to_string(),
+            branch: None,
+            workflow_file: None,
+            metadata: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_word_starts() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        assert_eq!(fuzzy_score("xyz", "deploy-prod"), None);
+
+        let deploy = fuzzy_score("dpr", "deploy-prod").unwrap();
+        let report = fuzzy_score("dpr", "dependency-report").unwrap();
+        assert!(deploy > report);
+
+        let exact = fuzzy_score("B uild", "build").unwrap();
+        let inner = fuzzy_score("build", "rebuild-cache").unwrap();
+        assert!(exact > inner);
+    }
+
+    #[test]
+    fn test_rank_pipelines() {
+        let pipelines = vec![
+            pipeline("a", "deploy-prod", PipelineStatus::Success),
+            pipeline("b", "deploy-production-eu", PipelineStatus::Success),
+            pipeline("c", "lint", PipelineStatus::Success),
+        ];
+        let now = Utc::now();
+        let usage = vec![
+            PipelineUsage {
+                pipeline_id: "c".to_string(),
+                use_count: 1,
+                last_used_at: now,
+            },
+            PipelineUsage {
+                pipeline_id: "gone".to_string(),
+                use_count: 4,
+                last_used_at: now - Duration::minutes(1),
+            },
+            PipelineUsage {
+                pipeline_id: "b".to_string(),
+                use_count: 2,
+                last_used_at: now - Duration::minutes(2),
+            },
+        ];
+
+        let recent: Vec<&str> = rank_pipelines(None, &pipelines, &usage, 10)
+            .iter()
+            .map(|(p, _)| p.id.as_str())
+            .collect();
+        assert_eq!(recent, vec!["c", "b"]);
+
+        // Equal scores fall back to recent use before name length.
+        let matched: Vec<&str> = rank_pipelines(Some("deploy"), &pipelines, &usage, 10)
+            .iter()
+            .map(|(p, _)| p.id.as_str())
+            .collect();
+        assert_eq!(matched, vec!["b", "a"]);
+        assert_eq!(
+            rank_pipelines(Some("deploy"), &pipelines, &[], 1)[0].0.id,
+            "a"
+        );
+    }
+
+    #[test]
+    fn test_pipeline_actions_follow_features() {
+        let capabilities = PluginCapabilities {
+            pipelines: true,
+            pipeline_runs: true,
+            trigger: true,
+            logs: true,
+            retry_failed_jobs: true,
+            ..PluginCapabilities::default()
+        };
+        let features = vec![FeatureAvailability {
+            feature: Feature {
+                id: "trigger_dispatch".to_string(),
+                name: "Trigger workflow dispatch".to_string(),
+                description: String::new(),
+                required_permissions: vec!["workflow".to_string()],
+            },
+            available: false,
+            missing_permissions: vec!["workflow".to_string()],
+        }];
+
+        let actions = pipeline_actions(
+            &pipeline("a", "ci", PipelineStatus::Failed),
+            &capabilities,
+            &features,
+            true,
+        );
+        let kinds: Vec<PaletteAction> = actions.iter().map(|a| a.action).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                PaletteAction::Open,
+                PaletteAction::ViewMetrics,
+                PaletteAction::Trigger,
+                PaletteAction::RetryFailedJobs,
+                PaletteAction::Unpin,
+            ]
+        );
+
+        let trigger = &actions[2];
+        assert!(!trigger.available);
+        assert_eq!(trigger.missing_permissions, vec!["workflow".to_string()]);
+        assert!(actions[0].available);
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::group::aggregate_status;
+use super::pipeline::{
+    Pipeline,
+    PipelineStatus,
+};
+
+// A pinned (favorite) pipeline. Unpinning keeps the row as a tombstone so an
+// unpin on one device wins over an older pin on another when pins are merged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedPipeline {
+    pub pipeline_id: String,
+    pub position: i64,
+    #[serde(default)]
+    pub unpinned: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Aggregate status of a handful of pipelines (the pinned ones unless chosen
+// otherwise), for compact indicators such as the desktop tray.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusSummary {
+    pub status: PipelineStatus,
+    pub total: usize,
+    pub failed: usize,
+    pub running: usize,
+    // Pipelines in a maintenance window. They are left out of the aggregate
+    // status and the failed count.
+    #[serde(default)]
+    pub maintenance: usize,
+    // Selected pipelines that are not (or no longer) in the pipeline cache.
+    pub missing: usize,
+    pub last_run: Option<DateTime<Utc>>,
+    pub pipelines: Vec<Pipeline>,
+}
+
+impl StatusSummary {
+    // Keeps the order of `pipeline_ids`.
+    pub fn new(pipeline_ids: &[String], cached: &[Pipeline]) -> Self {
+        let pipelines: Vec<Pipeline> = pipeline_ids
+            .iter()
+            .filter_map(|id| cached.iter().find(|p| &p.id == id).cloned())
+            .collect();
+        let statuses: Vec<&PipelineStatus> = pipelines
+            .iter()
+            .filter(|p| !p.in_maintenance())
+            .map(|p| &p.status)
+            .collect();
+        let count = |status: PipelineStatus| statuses.iter().filter(|s| ***s == status).count();
+
+        Self {
+            status: aggregate_status(&statuses),
+            total: pipeline_ids.len(),
+            failed: count(PipelineStatus::Failed),
+            running: count(PipelineStatus::Running),
+            maintenance: pipelines.iter().filter(|p| p.in_maintenance()).count(),
+            missing: pipeline_ids.len().saturating_sub(pipelines.len()),
+            last_run: pipelines.iter().filter_map(|p| p.last_run).max(),
+            pipelines,
+        }
+    }
+}
+
+// Last write wins per pipeline; ties keep the local entry.
+pub fn merge_pins(local: Vec<PinnedPipeline>, remote: Vec<PinnedPipeline>) -> Vec<PinnedPipeline> {
+    let mut merged: HashMap<String, PinnedPipeline> = local
+        .into_iter()
+        .map(|pin| (pin.pipeline_id.clone(), pin))
+        .collect();
+
+    for pin in remote {
+        match merged.get(&pin.pipeline_id) {
+            Some(existing) if existing.updated_at >= pin.updated_at => {}
+            _ => {
+                merged.insert(pin.pipeline_id.clone(), pin);
+            }
+        }
+    }
+
+    let mut pins: Vec<PinnedPipeline> = merged.into_values().collect();
+    pins.sort_by(|a, b| (a.position, &a.pipeline_id).cmp(&(b.position, &b.pipeline_id)));
+    pins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pin(id: &str, position: i64, unpinned: bool, at: i64) -> PinnedPipeline {
+        PinnedPipeline {
+            pipeline_id: id.to_string(),
+            position,
+            unpinned,
+            updated_at: DateTime::from_timestamp(at, 0).unwrap(),
+        }
+    }
+
+    fn pipeline(id: &str, status: PipelineStatus) -> Pipeline {
+        Pipeline {
+            id: id.to_string(),
+            provider_id: 1,
+            provider_type: "github".to_string(),
+            name: id.to_string(),
+            status,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "o/r".to_string(),
+            branch: None,
+            workflow_file: None,
+            metadata: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_status_summary() {
+        let cached = vec![
+            pipeline("deploy", PipelineStatus::Success),
+            pipeline("lint", PipelineStatus::Failed),
+            pipeline("e2e", PipelineStatus::Running),
+        ];
+        let ids = ["e2e", "lint", "gone"].map(String::from);
+
+        let summary = StatusSummary::new(&ids, &cached);
+        assert_eq!(summary.status, PipelineStatus::Failed);
+        assert_eq!((summary.total, summary.failed, summary.running), (3, 1, 1));
+        assert_eq!(summary.missing, 1);
+        assert_eq!(summary.pipelines[0].id, "e2e");
+
+        // A failure during planned maintenance doesn't turn the summary red.
+        let mut cached = cached;
+        cached[1]
+            .metadata
+            .insert("maintenance".to_string(), serde_json::json!({}));
+        let summary = StatusSummary::new(&ids, &cached);
+        assert_eq!(summary.status, PipelineStatus::Running);
+        assert_eq!((summary.failed, summary.maintenance), (0, 1));
+    }
+
+    #[test]
+    fn test_merge_pins_last_write_wins() {
+        let local = vec![pin("deploy", 0, false, 10), pin("nightly", 1, true, 30)];
+        let remote = vec![
+            pin("deploy", 0, true, 20),
+            pin("nightly", 1, false, 15),
+            pin("lint", 2, false, 5),
+        ];
+
+        let merged = merge_pins(local, remote);
+        assert_eq!(
+            merged,
+            vec![
+                pin("deploy", 0, true, 20),
+                pin("nightly", 1, true, 30),
+                pin("lint", 2, false, 5),
+            ]
+        );
+    }
+}
@@ -5,14 +5,28 @@ use chrono::{
     Utc,
 };
 pub use pipedash_plugin_api::{
+    AnnotationLevel,
+    ArtifactDownload,
+    BuildArtifact,
+    CommitInfo,
+    JobLog,
+    LogQuery,
+    LogStep,
     PaginatedAvailablePipelines,
     PaginationParams,
+    PendingApproval,
+    PullRequestRef,
+    RunAnnotation,
+    RunLogs,
 };
 use serde::{
     Deserialize,
     Serialize,
 };
 
+use super::error::DomainResult;
+use super::maintenance::MAINTENANCE_METADATA_KEY;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum PipelineStatus {
@@ -20,12 +34,19 @@ pub enum PipelineStatus {
     Failed,
     Running,
     Pending,
+    // Accepted by the provider but still waiting for a runner or agent.
+    Queued,
     Cancelled,
     Skipped,
+    // Held by an approval gate, manual step or protection rule until someone
+    // lets it through.
+    #[serde(alias = "blocked", alias = "waiting_approval")]
+    Waiting,
+    // Finished without passing or failing, e.g. a GitHub neutral conclusion.
+    Neutral,
 }
 
 impl PipelineStatus {
-    #[allow(dead_code)]
     pub fn is_terminal(&self) -> bool {
         matches!(
             self,
@@ -33,17 +54,35 @@ impl PipelineStatus {
                 | PipelineStatus::Failed
                 | PipelineStatus::Cancelled
                 | PipelineStatus::Skipped
+                | PipelineStatus::Neutral
         )
     }
 
+    // Reads a status column written by any version of the app: JSON quoted or
+    // bare, including the older aliases. Anything unrecognised, such as a
+    // status added by a newer build, falls back to pending.
+    pub fn from_stored(value: &str) -> Self {
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+
+        serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+            .unwrap_or(PipelineStatus::Pending)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             PipelineStatus::Success => "success",
             PipelineStatus::Failed => "failed",
             PipelineStatus::Running => "running",
             PipelineStatus::Pending => "pending",
+            PipelineStatus::Queued => "queued",
             PipelineStatus::Cancelled => "cancelled",
             PipelineStatus::Skipped => "skipped",
+            PipelineStatus::Waiting => "waiting",
+            PipelineStatus::Neutral => "neutral",
         }
     }
 }
@@ -62,6 +101,30 @@ pub struct Pipeline {
     pub workflow_file: Option<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+impl Pipeline {
+    // Set when the status API annotated the pipeline with an active window.
+    pub fn in_maintenance(&self) -> bool {
+        self.metadata.contains_key(MAINTENANCE_METADATA_KEY)
+    }
+
+    // A filter matches a label exactly (`team:payments`), or any label with
+    // that key when given without a value (`team`).
+    pub fn has_label(&self, filter: &str) -> bool {
+        let filter = filter.trim();
+        self.labels.iter().any(|label| {
+            label == filter
+                || (!filter.contains(':')
+                    && label.split_once(':').is_some_and(|(key, _)| key == filter))
+        })
+    }
+
+    pub fn has_labels(&self, filters: &[String]) -> bool {
+        filters.iter().all(|filter| self.has_label(filter))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,18 +141,192 @@ pub struct PipelineRun {
     pub commit_message: Option<String>,
     pub branch: Option<String>,
     pub actor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_avatar_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<PullRequestRef>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inputs: Option<serde_json::Value>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<RunAnnotation>,
+}
+
+// Latest run on a branch; see `Plugin::list_branch_statuses`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchStatus {
+    pub branch: String,
+    pub status: PipelineStatus,
+    pub run_number: i64,
+    pub started_at: DateTime<Utc>,
+    pub logs_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<PullRequestRef>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchBoard {
+    pub pipeline_id: String,
+    pub branches: Vec<BranchStatus>,
+    // When the statuses were fetched from the provider.
+    pub fetched_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TriggerParams {
     pub workflow_id: String,
     pub inputs: Option<serde_json::Value>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRef {
+    pub pipeline_id: String,
+    pub run_number: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkOperation {
+    Trigger,
+    Cancel,
+    Retry,
+}
+
+// `run_id` is the provider's identifier for the started run; cancels leave it
+// empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkItemResult {
+    pub pipeline_id: String,
+    pub run_number: Option<i64>,
+    pub success: bool,
+    pub run_id: Option<String>,
+    pub error: Option<String>,
+}
+
+impl BulkItemResult {
+    pub fn from_result(
+        pipeline_id: String, run_number: Option<i64>, result: DomainResult<Option<String>>,
+    ) -> Self {
+        match result {
+            Ok(run_id) => Self {
+                pipeline_id,
+                run_number,
+                success: true,
+                run_id,
+                error: None,
+            },
+            Err(e) => Self {
+                pipeline_id,
+                run_number,
+                success: false,
+                run_id: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineSortField {
+    Name,
+    Status,
+    Repository,
+    Provider,
+    LastRun,
+    #[default]
+    LastUpdated,
+}
+
+impl PipelineSortField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            PipelineSortField::Name => "name",
+            PipelineSortField::Status => "status",
+            PipelineSortField::Repository => "repository",
+            PipelineSortField::Provider => "provider_id",
+            PipelineSortField::LastRun => "last_run",
+            PipelineSortField::LastUpdated => "last_updated",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+// Filters for the cached pipeline list. Every set field narrows the result;
+// `statuses` matches any entry while `labels` must all match (see
+// `Pipeline::has_label`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineListQuery {
+    #[serde(default)]
+    pub provider_id: Option<i64>,
+    #[serde(default)]
+    pub statuses: Vec<PipelineStatus>,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub sort_by: PipelineSortField,
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    #[serde(default = "default_list_page")]
+    pub page: usize,
+    #[serde(default = "default_list_page_size")]
+    pub page_size: usize,
+}
+
+fn default_list_page() -> usize {
+    1
+}
+
+fn default_list_page_size() -> usize {
+    100
+}
+
+impl Default for PipelineListQuery {
+    fn default() -> Self {
+        Self {
+            provider_id: None,
+            statuses: Vec::new(),
+            branch: None,
+            labels: Vec::new(),
+            sort_by: PipelineSortField::default(),
+            sort_order: SortOrder::default(),
+            page: default_list_page(),
+            page_size: default_list_page_size(),
+        }
+    }
+}
+
+impl PipelineListQuery {
+    pub fn offset(&self) -> usize {
+        self.page.saturating_sub(1) * self.page_size
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaginatedRunHistory {
     pub runs: Vec<PipelineRun>,
@@ -100,3 +337,192 @@ pub struct PaginatedRunHistory {
     pub page_size: usize,
     pub total_pages: usize,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterChange {
+    pub name: String,
+    pub base: Option<serde_json::Value>,
+    pub head: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunComparison {
+    pub pipeline_id: String,
+    pub base: PipelineRun,
+    pub head: PipelineRun,
+    pub duration_delta_seconds: Option<i64>,
+    pub status_changed: bool,
+    pub commits: Vec<CommitInfo>,
+    pub commits_available: bool,
+    pub parameter_changes: Vec<ParameterChange>,
+}
+
+impl RunComparison {
+    pub fn new(base: PipelineRun, head: PipelineRun) -> Self {
+        let duration_delta_seconds = match (base.duration_seconds, head.duration_seconds) {
+            (Some(base), Some(head)) => Some(head - base),
+            _ => None,
+        };
+
+        Self {
+            pipeline_id: head.pipeline_id.clone(),
+            duration_delta_seconds,
+            status_changed: base.status != head.status,
+            commits: Vec::new(),
+            commits_available: false,
+            parameter_changes: diff_inputs(base.inputs.as_ref(), head.inputs.as_ref()),
+            base,
+            head,
+        }
+    }
+}
+
+fn diff_inputs(
+    base: Option<&serde_json::Value>, head: Option<&serde_json::Value>,
+) -> Vec<ParameterChange> {
+    let empty = serde_json::Map::new();
+    let base = base.and_then(|v| v.as_object()).unwrap_or(&empty);
+    let head = head.and_then(|v| v.as_object()).unwrap_or(&empty);
+
+    let mut names: Vec<&String> = base.keys().chain(head.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter(|name| base.get(*name) != head.get(*name))
+        .map(|name| ParameterChange {
+            name: name.clone(),
+            base: base.get(name).cloned(),
+            head: head.get(name).cloned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn run(run_number: i64, status: PipelineStatus, duration: Option<i64>) -> PipelineRun {
+        PipelineRun {
+            id: run_number.to_string(),
+            pipeline_id: "github__1__o__r__1".to_string(),
+            run_number,
+            status,
+            started_at: Utc::now(),
+            concluded_at: None,
+            duration_seconds: duration,
+            logs_url: String::new(),
+            commit_sha: None,
+            commit_message: None,
+            branch: None,
+            actor: None,
+            commit_author: None,
+            author_avatar_url: None,
+            pull_request: None,
+            inputs: None,
+            metadata: HashMap::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_run_comparison_deltas() {
+        let base = run(1, PipelineStatus::Success, Some(120));
+        let head = run(2, PipelineStatus::Failed, Some(300));
+
+        let comparison = RunComparison::new(base, head);
+        assert_eq!(comparison.duration_delta_seconds, Some(180));
+        assert!(comparison.status_changed);
+
+        let comparison = RunComparison::new(
+            run(1, PipelineStatus::Success, None),
+            run(2, PipelineStatus::Success, Some(60)),
+        );
+        assert_eq!(comparison.duration_delta_seconds, None);
+        assert!(!comparison.status_changed);
+    }
+
+    #[test]
+    fn test_diff_inputs() {
+        let base = json!({"env": "staging", "debug": false, "removed": 1});
+        let head = json!({"env": "production", "debug": false, "added": "x"});
+
+        let changes = diff_inputs(Some(&base), Some(&head));
+        let names: Vec<_> = changes.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["added", "env", "removed"]);
+        assert_eq!(changes[1].base, Some(json!("staging")));
+        assert_eq!(changes[1].head, Some(json!("production")));
+        assert_eq!(changes[2].head, None);
+
+        assert!(diff_inputs(None, None).is_empty());
+    }
+
+    #[test]
+    fn test_pipeline_list_query_defaults() {
+        let query: PipelineListQuery =
+            serde_json::from_value(json!({"page": 3, "sort_by": "name"})).unwrap();
+        assert_eq!(query.page_size, 100);
+        assert_eq!(query.sort_by, PipelineSortField::Name);
+        assert_eq!(query.sort_order, SortOrder::Desc);
+        assert_eq!(query.offset(), 200);
+
+        assert_eq!(PipelineListQuery::default().offset(), 0);
+    }
+
+    #[test]
+    fn test_pipeline_label_matching() {
+        let pipeline = Pipeline {
+            id: "github__1__o__r__1".to_string(),
+            provider_id: 1,
+            provider_type: "github".to_string(),
+            name: "ci".to_string(),
+            status: PipelineStatus::Success,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "o/r".to_string(),
+            branch: None,
+            workflow_file: None,
+            metadata: HashMap::new(),
+            labels: vec!["team:payments".to_string(), "tier:prod".to_string()],
+        };
+
+        assert!(pipeline.has_label("team:payments"));
+        assert!(pipeline.has_label("tier"));
+        assert!(!pipeline.has_label("team:mobile"));
+        assert!(!pipeline.has_label("team:pay"));
+        assert!(pipeline.has_labels(&["team".to_string(), "tier:prod".to_string()]));
+        assert!(!pipeline.has_labels(&["team".to_string(), "region".to_string()]));
+    }
+
+    #[test]
+    fn test_status_from_stored() {
+        assert_eq!(
+            PipelineStatus::from_stored("\"queued\""),
+            PipelineStatus::Queued
+        );
+        assert_eq!(
+            PipelineStatus::from_stored("neutral"),
+            PipelineStatus::Neutral
+        );
+        assert_eq!(
+            PipelineStatus::from_stored("Success"),
+            PipelineStatus::Success
+        );
+        assert_eq!(
+            PipelineStatus::from_stored("blocked"),
+            PipelineStatus::Waiting
+        );
+        assert_eq!(
+            PipelineStatus::from_stored("\"waiting_approval\""),
+            PipelineStatus::Waiting
+        );
+        assert_eq!(PipelineStatus::from_stored(""), PipelineStatus::Pending);
+        assert_eq!(
+            PipelineStatus::from_stored("paused"),
+            PipelineStatus::Pending
+        );
+    }
+}
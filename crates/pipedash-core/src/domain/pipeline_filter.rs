@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use glob::Pattern;
+use pipedash_plugin_api::AvailablePipeline;
+use regex::Regex;
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+use super::pipeline::Pipeline;
+
+// Provider config keys holding comma- or newline-separated patterns.
+pub const INCLUDE_PIPELINES_KEY: &str = "include_pipelines";
+pub const EXCLUDE_PIPELINES_KEY: &str = "exclude_pipelines";
+
+#[derive(Debug, Clone)]
+enum NamePattern {
+    Glob(Pattern),
+    Regex(Regex),
+}
+
+impl NamePattern {
+    // `re:` selects a regular expression; anything else is a glob.
+    fn parse(pattern: &str) -> DomainResult<Self> {
+        match pattern.strip_prefix("re:") {
+            Some(regex) => Regex::new(regex).map(NamePattern::Regex).map_err(|e| {
+                DomainError::InvalidConfig(format!("Invalid pipeline pattern '{}': {}", pattern, e))
+            }),
+            None => Pattern::new(pattern).map(NamePattern::Glob).map_err(|e| {
+                DomainError::InvalidConfig(format!("Invalid pipeline pattern '{}': {}", pattern, e))
+            }),
+        }
+    }
+
+    fn is_match(&self, value: &str) -> bool {
+        match self {
+            NamePattern::Glob(pattern) => pattern.matches(value),
+            NamePattern::Regex(regex) => regex.is_match(value),
+        }
+    }
+}
+
+// Include/exclude patterns matched against a pipeline's name and workflow
+// path. A pipeline is kept when it matches an include pattern (or none are
+// set) and no exclude pattern.
+#[derive(Debug, Clone, Default)]
+pub struct PipelineFilter {
+    include: Vec<NamePattern>,
+    exclude: Vec<NamePattern>,
+}
+
+impl PipelineFilter {
+    pub fn from_config(config: &HashMap<String, String>) -> DomainResult<Self> {
+        let parse = |key: &str| -> DomainResult<Vec<NamePattern>> {
+            config
+                .get(key)
+                .map(|value| {
+                    value
+                        .split([',', '\n'])
+                        .map(str::trim)
+                        .filter(|p| !p.is_empty())
+                        .map(NamePattern::parse)
+                        .collect()
+                })
+                .unwrap_or_else(|| Ok(Vec::new()))
+        };
+
+        Ok(Self {
+            include: parse(INCLUDE_PIPELINES_KEY)?,
+            exclude: parse(EXCLUDE_PIPELINES_KEY)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn matches(&self, pipeline: &Pipeline) -> bool {
+        let candidates: Vec<&str> = std::iter::once(pipeline.name.as_str())
+            .chain(pipeline.workflow_file.as_deref())
+            .collect();
+        let matched = |patterns: &[NamePattern]| {
+            patterns
+                .iter()
+                .any(|p| candidates.iter().any(|c| p.is_match(c)))
+        };
+
+        (self.include.is_empty() || matched(&self.include)) && !matched(&self.exclude)
+    }
+
+    // Entries offered when adding a provider are often repositories rather
+    // than single pipelines, so only exclude patterns are applied to them.
+    pub fn excludes_available(&self, available: &AvailablePipeline) -> bool {
+        self.exclude
+            .iter()
+            .any(|p| p.is_match(&available.name) || p.is_match(&available.id))
+    }
+
+    pub fn apply(&self, pipelines: &mut Vec<Pipeline>) {
+        if !self.is_empty() {
+            pipelines.retain(|p| self.matches(p));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::domain::PipelineStatus;
+
+    fn pipeline(name: &str, workflow_file: Option<&str>) -> Pipeline {
+        Pipeline {
+            id: format!("github__1__o__r__{}", name),
+            provider_id: 1,
+            provider_type: "github".to_string(),
+            name: name.to_string(),
+            status: PipelineStatus::Success,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "o/r".to_string(),
+            branch: None,
+            workflow_file: workflow_file.map(String::from),
+            metadata: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_include_and_exclude() {
+        let config = HashMap::from([
+            (
+                INCLUDE_PIPELINES_KEY.to_string(),
+                "deploy-*, .github/workflows/ci.yml".to_string(),
+            ),
+            (
+                EXCLUDE_PIPELINES_KEY.to_string(),
+                "re:-staging$".to_string(),
+            ),
+        ]);
+        let filter = PipelineFilter::from_config(&config).unwrap();
+
+        assert!(filter.matches(&pipeline("deploy-api", None)));
+        assert!(filter.matches(&pipeline("CI", Some(".github/workflows/ci.yml"))));
+        assert!(!filter.matches(&pipeline("deploy-api-staging", None)));
+        assert!(!filter.matches(&pipeline("lint", Some(".github/workflows/lint.yml"))));
+        assert!(PipelineFilter::from_config(&HashMap::new())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let config = HashMap::from([(EXCLUDE_PIPELINES_KEY.to_string(), "re:(".to_string())]);
+        assert!(PipelineFilter::from_config(&config).is_err());
+    }
+}
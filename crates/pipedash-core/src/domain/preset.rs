@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use pipedash_plugin_api::{
+    WorkflowParameter,
+    WorkflowParameterType,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+
+// Parameter names plugins declare for the branch or ref to build.
+const BRANCH_PARAMETERS: [&str; 2] = ["ref", "branch"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerPreset {
+    pub id: i64,
+    pub pipeline_id: String,
+    pub name: String,
+    pub branch: Option<String>,
+    pub parameters: HashMap<String, Value>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TriggerPreset {
+    // Overrides win over the preset's own values, and both win over the branch.
+    // The result is checked against the schema, including required parameters.
+    pub fn resolve_inputs(
+        &self, schema: &[WorkflowParameter], overrides: HashMap<String, Value>,
+    ) -> DomainResult<HashMap<String, Value>> {
+        let mut inputs = HashMap::new();
+        if let Some(branch) = self.branch.as_deref().filter(|b| !b.is_empty()) {
+            inputs.insert(
+                branch_parameter(schema)?.to_string(),
+                Value::String(branch.to_string()),
+            );
+        }
+        inputs.extend(self.parameters.clone());
+        inputs.extend(overrides);
+
+        validate_preset_parameters(schema, &inputs)?;
+
+        if let Some(missing) = schema
+            .iter()
+            .find(|p| p.required && !inputs.contains_key(&p.name) && !has_default(&p.param_type))
+        {
+            return Err(DomainError::InvalidConfig(format!(
+                "Missing required parameter '{}'",
+                missing.name
+            )));
+        }
+
+        Ok(inputs)
+    }
+}
+
+pub fn branch_parameter(schema: &[WorkflowParameter]) -> DomainResult<&str> {
+    schema
+        .iter()
+        .map(|p| p.name.as_str())
+        .find(|name| BRANCH_PARAMETERS.contains(name))
+        .ok_or_else(|| {
+            DomainError::InvalidConfig("This pipeline does not accept a branch".to_string())
+        })
+}
+
+// Checks that every value belongs to a declared parameter and has the right
+// type. Missing required parameters are only an error at trigger time.
+pub fn validate_preset_parameters(
+    schema: &[WorkflowParameter], parameters: &HashMap<String, Value>,
+) -> DomainResult<()> {
+    for (name, value) in parameters {
+        let parameter = schema
+            .iter()
+            .find(|p| &p.name == name)
+            .ok_or_else(|| DomainError::InvalidConfig(format!("Unknown parameter '{}'", name)))?;
+
+        if !value_matches(&parameter.param_type, value) {
+            return Err(DomainError::InvalidConfig(format!(
+                "Invalid value {} for parameter '{}'",
+                value, name
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn value_matches(param_type: &WorkflowParameterType, value: &Value) -> bool {
+    match (param_type, value) {
+        (WorkflowParameterType::String { .. }, Value::String(_)) => true,
+        (WorkflowParameterType::Boolean { .. }, Value::Bool(_)) => true,
+        (WorkflowParameterType::Boolean { .. }, Value::String(s)) => s.parse::<bool>().is_ok(),
+        (WorkflowParameterType::Choice { options, .. }, Value::String(s)) => options.contains(s),
+        (WorkflowParameterType::Number { .. }, Value::Number(_)) => true,
+        (WorkflowParameterType::Number { .. }, Value::String(s)) => s.parse::<f64>().is_ok(),
+        _ => false,
+    }
+}
+
+fn has_default(param_type: &WorkflowParameterType) -> bool {
+    match param_type {
+        WorkflowParameterType::String { default } => default.is_some(),
+        WorkflowParameterType::Boolean { .. } => true,
+        WorkflowParameterType::Choice { default, .. } => default.is_some(),
+        WorkflowParameterType::Number { default } => default.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn parameter(
+        name: &str, param_type: WorkflowParameterType, required: bool,
+    ) -> WorkflowParameter {
+        WorkflowParameter {
+            name: name.to_string(),
+            label: None,
+            description: None,
+            param_type,
+            required,
+        }
+    }
+
+    fn schema() -> Vec<WorkflowParameter> {
+        vec![
+            parameter("ref", WorkflowParameterType::String { default: None }, true),
+            parameter(
+                "environment",
+                WorkflowParameterType::Choice {
+                    options: vec!["staging".to_string(), "production".to_string()],
+                    default: None,
+                },
+                true,
+            ),
+            parameter(
+                "dry_run",
+                WorkflowParameterType::Boolean { default: true },
+                true,
+            ),
+            parameter(
+                "replicas",
+                WorkflowParameterType::Number { default: None },
+                false,
+            ),
+        ]
+    }
+
+    fn preset() -> TriggerPreset {
+        TriggerPreset {
+            id: 1,
+            pipeline_id: "github__1__org__repo__release".to_string(),
+            name: "Production release".to_string(),
+            branch: Some("main".to_string()),
+            parameters: HashMap::from([
+                ("environment".to_string(), json!("production")),
+                ("dry_run".to_string(), json!(false)),
+            ]),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_inputs_merges_branch_and_overrides() {
+        let inputs = preset()
+            .resolve_inputs(
+                &schema(),
+                HashMap::from([("replicas".to_string(), json!(3))]),
+            )
+            .unwrap();
+
+        assert_eq!(inputs["ref"], json!("main"));
+        assert_eq!(inputs["environment"], json!("production"));
+        assert_eq!(inputs["replicas"], json!(3));
+
+        let inputs = preset()
+            .resolve_inputs(
+                &schema(),
+                HashMap::from([("ref".to_string(), json!("release/1.2"))]),
+            )
+            .unwrap();
+        assert_eq!(inputs["ref"], json!("release/1.2"));
+    }
+
+    #[test]
+    fn test_resolve_inputs_rejects_invalid_values() {
+        let overrides = HashMap::from([("environment".to_string(), json!("qa"))]);
+        assert!(preset().resolve_inputs(&schema(), overrides).is_err());
+
+        let overrides = HashMap::from([("unknown".to_string(), json!("x"))]);
+        assert!(preset().resolve_inputs(&schema(), overrides).is_err());
+
+        let mut missing = preset();
+        missing.parameters.remove("environment");
+        assert!(missing.resolve_inputs(&schema(), HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_branch_requires_ref_parameter() {
+        let schema = vec![parameter(
+            "environment",
+            WorkflowParameterType::String { default: None },
+            false,
+        )];
+        let mut preset = preset();
+        preset.parameters.clear();
+
+        assert!(preset.resolve_inputs(&schema, HashMap::new()).is_err());
+
+        preset.branch = None;
+        assert!(preset.resolve_inputs(&schema, HashMap::new()).is_ok());
+    }
+}
@@ -6,12 +6,24 @@ use serde::{
     Serialize,
 };
 
+use super::agent::BuildAgent;
 use super::error::DomainResult;
+use super::metrics::RunMetric;
 use super::pipeline::{
+    ArtifactDownload,
+    BranchStatus,
+    BuildArtifact,
+    CommitInfo,
+    LogQuery,
+    PendingApproval,
     Pipeline,
     PipelineRun,
+    RunAnnotation,
+    RunLogs,
     TriggerParams,
 };
+use super::queue::BuildQueue;
+use super::resilience::CircuitBreakerStatus;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderConfig {
@@ -24,12 +36,20 @@ pub struct ProviderConfig {
     pub refresh_interval: i64,
     #[serde(default)]
     pub version: Option<i64>,
+    // Disabled providers are kept, with their cached pipelines and history,
+    // but never fetched. Only changed through enable/disable, not updates.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
 }
 
 fn default_refresh_interval() -> i64 {
     30
 }
 
+fn default_enabled() -> bool {
+    true
+}
+
 impl ProviderConfig {
     pub fn display_name(&self) -> &str {
         self.config
@@ -61,12 +81,97 @@ pub struct ProviderSummary {
     pub last_fetch_error: Option<String>,
     pub last_fetch_at: Option<chrono::DateTime<chrono::Utc>>,
     pub version: i64,
+    // Detected when the credentials were last validated, for self-hosted
+    // providers that report one.
+    pub server_version: Option<pipedash_plugin_api::ServerVersion>,
+    pub enabled: bool,
+}
+
+// Consecutive failed fetches after which a provider is treated as down.
+pub const PROVIDER_OUTAGE_THRESHOLD: usize = 3;
+// Most recent fetches the health score is computed over.
+pub const PROVIDER_HEALTH_WINDOW: usize = 100;
+
+// One pipeline fetch against a provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderHealthCheck {
+    pub success: bool,
+    pub error: Option<String>,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderHealth {
+    pub provider_id: i64,
+    pub name: String,
+    pub provider_type: String,
+    // Percentage of successful fetches in the window, None before the first
+    // fetch.
+    pub score: Option<f64>,
+    pub consecutive_failures: usize,
+    pub outage: bool,
+    // First failed fetch of the current outage.
+    pub down_since: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_success_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_error: Option<String>,
+    // Newest first.
+    pub checks: Vec<ProviderHealthCheck>,
+    // Kept in memory, so it starts closed again after a restart.
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerStatus,
+}
+
+impl ProviderHealth {
+    // `checks` must be ordered newest first.
+    pub fn from_checks(
+        provider_id: i64, name: String, provider_type: String, checks: Vec<ProviderHealthCheck>,
+    ) -> Self {
+        let consecutive_failures = consecutive_failures(&checks);
+        let outage = consecutive_failures >= PROVIDER_OUTAGE_THRESHOLD;
+        let score = (!checks.is_empty()).then(|| {
+            let successes = checks.iter().filter(|c| c.success).count();
+            (successes as f64 * 1000.0 / checks.len() as f64).round() / 10.0
+        });
+
+        Self {
+            provider_id,
+            name,
+            provider_type,
+            score,
+            consecutive_failures,
+            outage,
+            down_since: outage.then(|| checks[consecutive_failures - 1].checked_at),
+            last_success_at: checks.iter().find(|c| c.success).map(|c| c.checked_at),
+            last_error: checks
+                .first()
+                .filter(|c| !c.success)
+                .and_then(|c| c.error.clone()),
+            checks,
+            circuit_breaker: CircuitBreakerStatus::default(),
+        }
+    }
+}
+
+fn consecutive_failures(checks: &[ProviderHealthCheck]) -> usize {
+    checks.iter().take_while(|c| !c.success).count()
+}
+
+// True only for the fetch that tipped the provider into an outage, so the
+// outage is reported once. Needs at least `PROVIDER_OUTAGE_THRESHOLD + 1`
+// checks, newest first, when that many exist.
+pub fn outage_started(checks: &[ProviderHealthCheck]) -> bool {
+    consecutive_failures(checks) == PROVIDER_OUTAGE_THRESHOLD
 }
 
 #[async_trait]
 pub trait Provider: Send + Sync {
     async fn fetch_pipelines(&self) -> DomainResult<Vec<Pipeline>>;
 
+    // `None` when the provider reported no change since the previous fetch.
+    async fn fetch_pipelines_if_modified(&self) -> DomainResult<Option<Vec<Pipeline>>> {
+        self.fetch_pipelines().await.map(Some)
+    }
+
     async fn fetch_pipelines_paginated(
         &self, page: usize, page_size: usize,
     ) -> DomainResult<pipedash_plugin_api::PaginatedResponse<Pipeline>> {
@@ -99,17 +204,95 @@ pub trait Provider: Send + Sync {
         &self, pipeline_id: &str, run_number: i64,
     ) -> DomainResult<PipelineRun>;
 
+    async fn fetch_run_logs(
+        &self, pipeline_id: &str, run_number: i64, query: LogQuery,
+    ) -> DomainResult<RunLogs>;
+
+    async fn fetch_artifacts(&self, run_id: &str) -> DomainResult<Vec<BuildArtifact>>;
+
+    async fn fetch_agents(&self) -> DomainResult<Vec<BuildAgent>>;
+
+    async fn fetch_queues(&self) -> DomainResult<Vec<BuildQueue>>;
+
+    async fn artifact_download(&self, artifact: &BuildArtifact) -> DomainResult<ArtifactDownload>;
+
+    async fn fetch_commits_between(
+        &self, pipeline_id: &str, base_sha: &str, head_sha: &str,
+    ) -> DomainResult<Vec<CommitInfo>>;
+
+    async fn list_branch_statuses(&self, pipeline_id: &str) -> DomainResult<Vec<BranchStatus>>;
+
     async fn trigger_pipeline(&self, params: TriggerParams) -> DomainResult<String>;
 
     async fn cancel_run(&self, pipeline_id: &str, run_number: i64) -> DomainResult<()>;
 
+    async fn retry_run(
+        &self, pipeline_id: &str, run_number: i64, failed_only: bool,
+    ) -> DomainResult<String>;
+
     async fn get_workflow_parameters(
         &self, workflow_id: &str,
     ) -> DomainResult<Vec<pipedash_plugin_api::WorkflowParameter>>;
 
+    async fn fetch_pending_approvals(
+        &self, pipeline_id: &str, run_number: i64,
+    ) -> DomainResult<Vec<PendingApproval>>;
+
+    async fn unblock_job(
+        &self, pipeline_id: &str, run_number: i64, job_id: &str, fields: HashMap<String, String>,
+    ) -> DomainResult<()>;
+
+    // Provider-specific metrics for a finished run; see
+    // `Plugin::extract_metrics`.
+    async fn extract_metrics(&self, _run: &PipelineRun) -> DomainResult<Vec<RunMetric>> {
+        Ok(Vec::new())
+    }
+
+    // See `Plugin::fetch_run_annotations`.
+    async fn fetch_run_annotations(&self, _run: &PipelineRun) -> DomainResult<Vec<RunAnnotation>> {
+        Ok(Vec::new())
+    }
+
     #[allow(dead_code)]
     async fn validate_credentials(&self) -> DomainResult<bool>;
 
     #[allow(dead_code)]
     fn provider_type(&self) -> &str;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(success: bool, minutes_ago: i64) -> ProviderHealthCheck {
+        ProviderHealthCheck {
+            success,
+            error: (!success).then(|| "timeout".to_string()),
+            checked_at: chrono::Utc::now() - chrono::Duration::minutes(minutes_ago),
+        }
+    }
+
+    #[test]
+    fn test_provider_health_outage() {
+        let checks = vec![
+            check(false, 1),
+            check(false, 2),
+            check(false, 3),
+            check(true, 4),
+        ];
+        assert!(outage_started(&checks));
+        assert!(!outage_started(&checks[1..]));
+
+        let health = ProviderHealth::from_checks(1, "gh".into(), "github".into(), checks.clone());
+        assert!(health.outage);
+        assert_eq!(health.consecutive_failures, 3);
+        assert_eq!(health.score, Some(25.0));
+        assert_eq!(health.down_since, Some(checks[2].checked_at));
+        assert_eq!(health.last_success_at, Some(checks[3].checked_at));
+        assert_eq!(health.last_error.as_deref(), Some("timeout"));
+
+        let health = ProviderHealth::from_checks(1, "gh".into(), "github".into(), Vec::new());
+        assert!(!health.outage);
+        assert_eq!(health.score, None);
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::pipeline::Pipeline;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvisionedResource {
+    Provider,
+    Group,
+    Schedule,
+    Channel,
+}
+
+impl ProvisionedResource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProvisionedResource::Provider => "provider",
+            ProvisionedResource::Group => "group",
+            ProvisionedResource::Schedule => "schedule",
+            ProvisionedResource::Channel => "channel",
+        }
+    }
+}
+
+// `Unmanaged` marks a resource that only exists in the database. It is left
+// alone unless provisioning is declarative, in which case it shows up as
+// `Delete` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanAction {
+    Create,
+    Update,
+    Delete,
+    Unmanaged,
+}
+
+impl PlanAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlanAction::Create => "create",
+            PlanAction::Update => "update",
+            PlanAction::Delete => "delete",
+            PlanAction::Unmanaged => "unmanaged",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedChange {
+    pub resource: ProvisionedResource,
+    pub name: String,
+    pub action: PlanAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+// The drift between config.toml and the database. `applied` tells whether
+// the changes were made or only computed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProvisioningPlan {
+    pub declarative: bool,
+    pub changes: Vec<PlannedChange>,
+    pub warnings: Vec<String>,
+    pub applied: bool,
+}
+
+impl ProvisioningPlan {
+    pub fn push(
+        &mut self, resource: ProvisionedResource, name: &str, action: PlanAction,
+        detail: Option<String>,
+    ) {
+        self.changes.push(PlannedChange {
+            resource,
+            name: name.to_string(),
+            action,
+            detail,
+        });
+    }
+
+    pub fn count(&self, action: PlanAction) -> usize {
+        self.changes.iter().filter(|c| c.action == action).count()
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| c.action != PlanAction::Unmanaged)
+    }
+}
+
+// Pipeline IDs embed database IDs, so config.toml refers to pipelines as
+// `<provider>:<repository>` or `<provider>:<pipeline name>`, with `<provider>`
+// being the key under `[providers]`. A full pipeline ID is accepted too. A
+// repository can hold several pipelines, so this returns every match.
+pub fn resolve_pipeline_reference(
+    reference: &str, pipelines: &[Pipeline], provider_ids: &HashMap<String, i64>,
+) -> Vec<String> {
+    let reference = reference.trim();
+    if pipelines.iter().any(|p| p.id == reference) {
+        return vec![reference.to_string()];
+    }
+
+    let Some((provider, target)) = reference.split_once(':') else {
+        return Vec::new();
+    };
+    let Some(provider_id) = provider_ids.get(provider.trim()) else {
+        return Vec::new();
+    };
+    let target = target.trim();
+
+    pipelines
+        .iter()
+        .filter(|p| p.provider_id == *provider_id && (p.repository == target || p.name == target))
+        .map(|p| p.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::domain::PipelineStatus;
+
+    fn pipeline(id: &str, provider_id: i64, repository: &str, name: &str) -> Pipeline {
+        Pipeline {
+            id: id.to_string(),
+            provider_id,
+            provider_type: "github".to_string(),
+            name: name.to_string(),
+            status: PipelineStatus::Success,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: repository.to_string(),
+            branch: None,
+            workflow_file: None,
+            metadata: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_pipeline_reference() {
+        let pipelines = vec![
+            pipeline("github__1__acme/api__ci", 1, "acme/api", "CI"),
+            pipeline("github__1__acme/api__release", 1, "acme/api", "Release"),
+            pipeline("github__2__acme/api__ci", 2, "acme/api", "CI"),
+            pipeline("github__2__acme/web__ci", 2, "acme/web", "Web CI"),
+        ];
+        let provider_ids = HashMap::from([("work".to_string(), 1), ("oss".to_string(), 2)]);
+
+        assert_eq!(
+            resolve_pipeline_reference("work:acme/api", &pipelines, &provider_ids),
+            vec!["github__1__acme/api__ci", "github__1__acme/api__release"]
+        );
+        assert_eq!(
+            resolve_pipeline_reference("oss:Web CI", &pipelines, &provider_ids),
+            vec!["github__2__acme/web__ci"]
+        );
+        assert_eq!(
+            resolve_pipeline_reference("github__2__acme/api__ci", &pipelines, &provider_ids),
+            vec!["github__2__acme/api__ci"]
+        );
+        assert!(resolve_pipeline_reference("work:acme/web", &pipelines, &provider_ids).is_empty());
+        assert!(resolve_pipeline_reference("other:acme/api", &pipelines, &provider_ids).is_empty());
+        assert!(resolve_pipeline_reference("acme/api", &pipelines, &provider_ids).is_empty());
+    }
+
+    #[test]
+    fn test_plan_has_changes() {
+        let mut plan = ProvisioningPlan::default();
+        plan.push(
+            ProvisionedResource::Provider,
+            "legacy",
+            PlanAction::Unmanaged,
+            None,
+        );
+        assert!(!plan.has_changes());
+
+        plan.push(
+            ProvisionedResource::Group,
+            "Backend",
+            PlanAction::Create,
+            None,
+        );
+        assert!(plan.has_changes());
+        assert_eq!(plan.count(PlanAction::Create), 1);
+        assert_eq!(plan.count(PlanAction::Delete), 0);
+    }
+}
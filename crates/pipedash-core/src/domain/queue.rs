@@ -0,0 +1,29 @@
+pub use pipedash_plugin_api::BuildQueue;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderQueue {
+    pub provider_id: i64,
+    // Key the queue's metrics are stored under, usable as `pipeline_id` in
+    // metrics queries.
+    pub metrics_id: String,
+    #[serde(flatten)]
+    pub queue: BuildQueue,
+}
+
+impl ProviderQueue {
+    pub fn new(provider_id: i64, queue: BuildQueue) -> Self {
+        Self {
+            provider_id,
+            metrics_id: queue_metrics_id(provider_id, &queue.id),
+            queue,
+        }
+    }
+}
+
+pub fn queue_metrics_id(provider_id: i64, queue_id: &str) -> String {
+    format!("queue__{}__{}", provider_id, queue_id)
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+use super::pipeline::Pipeline;
+
+// The refresh loop ticks every five seconds, so shorter overrides would not be
+// honoured anyway.
+pub const MIN_PIPELINE_REFRESH_SECS: u64 = 5;
+pub const MAX_PIPELINE_REFRESH_SECS: u64 = 24 * 3600;
+
+// Per-pipeline override of the adaptive refresh interval. A snoozed pipeline
+// is neither polled for nor notified about until it is unsnoozed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineRefreshSetting {
+    pub pipeline_id: String,
+    #[serde(default)]
+    pub refresh_interval_secs: Option<u64>,
+    #[serde(default)]
+    pub snoozed: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PipelineRefreshSetting {
+    pub fn validate(&self) -> DomainResult<()> {
+        if let Some(secs) = self.refresh_interval_secs {
+            if !(MIN_PIPELINE_REFRESH_SECS..=MAX_PIPELINE_REFRESH_SECS).contains(&secs) {
+                return Err(DomainError::InvalidConfig(format!(
+                    "Refresh interval must be between {} and {} seconds",
+                    MIN_PIPELINE_REFRESH_SECS, MAX_PIPELINE_REFRESH_SECS
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RefreshSchedule {
+    settings: HashMap<String, PipelineRefreshSetting>,
+}
+
+impl RefreshSchedule {
+    pub fn new(settings: Vec<PipelineRefreshSetting>) -> Self {
+        Self {
+            settings: settings
+                .into_iter()
+                .map(|s| (s.pipeline_id.clone(), s))
+                .collect(),
+        }
+    }
+
+    // Refreshes the pipeline at least every `interval` for as long as this
+    // schedule is in use. Snoozed pipelines are left alone.
+    pub fn boost(&mut self, pipeline_id: &str, interval: Duration) {
+        let setting = self
+            .settings
+            .entry(pipeline_id.to_string())
+            .or_insert_with(|| PipelineRefreshSetting {
+                pipeline_id: pipeline_id.to_string(),
+                refresh_interval_secs: None,
+                snoozed: false,
+                updated_at: Utc::now(),
+            });
+        if setting.snoozed {
+            return;
+        }
+
+        let secs = interval.as_secs();
+        setting.refresh_interval_secs = Some(
+            setting
+                .refresh_interval_secs
+                .map_or(secs, |current| current.min(secs)),
+        );
+    }
+
+    pub fn is_snoozed(&self, pipeline_id: &str) -> bool {
+        self.settings.get(pipeline_id).is_some_and(|s| s.snoozed)
+    }
+
+    // Unset for snoozed pipelines.
+    pub fn pipeline_interval(&self, pipeline_id: &str, default: Duration) -> Option<Duration> {
+        match self.settings.get(pipeline_id) {
+            Some(setting) if setting.snoozed => None,
+            Some(setting) => Some(
+                setting
+                    .refresh_interval_secs
+                    .map(Duration::from_secs)
+                    .unwrap_or(default),
+            ),
+            None => Some(default),
+        }
+    }
+
+    // Providers are fetched as a whole, so a provider is due as soon as its
+    // most frequently refreshed pipeline is. Unset when every pipeline of the
+    // provider is snoozed; providers with nothing cached yet use the default.
+    pub fn provider_interval<'a>(
+        &self, pipelines: impl IntoIterator<Item = &'a Pipeline>, default: Duration,
+    ) -> Option<Duration> {
+        let mut any = false;
+        let mut shortest = None;
+        for pipeline in pipelines {
+            any = true;
+            if let Some(interval) = self.pipeline_interval(&pipeline.id, default) {
+                shortest = Some(shortest.map_or(interval, |s: Duration| s.min(interval)));
+            }
+        }
+
+        if any {
+            shortest
+        } else {
+            Some(default)
+        }
+    }
+
+    pub fn without_snoozed(&self, pipelines: &[Pipeline]) -> Vec<Pipeline> {
+        pipelines
+            .iter()
+            .filter(|p| !self.is_snoozed(&p.id))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::PipelineStatus;
+
+    fn pipeline(id: &str) -> Pipeline {
+        Pipeline {
+            id: id.to_string(),
+            provider_id: 1,
+            provider_type: "github".to_string(),
+            name: id.to_string(),
+            status: PipelineStatus::Success,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "o/r".to_string(),
+            branch: None,
+            workflow_file: None,
+            metadata: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    fn setting(id: &str, secs: Option<u64>, snoozed: bool) -> PipelineRefreshSetting {
+        PipelineRefreshSetting {
+            pipeline_id: id.to_string(),
+            refresh_interval_secs: secs,
+            snoozed,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_provider_interval_uses_shortest_unsnoozed() {
+        let default = Duration::from_secs(60);
+        let schedule = RefreshSchedule::new(vec![
+            setting("deploy", Some(15), false),
+            setting("nightly", Some(1800), false),
+            setting("muted", Some(5), true),
+        ]);
+
+        let deploy = pipeline("deploy");
+        let nightly = pipeline("nightly");
+        let muted = pipeline("muted");
+
+        assert_eq!(
+            schedule.provider_interval([&deploy, &nightly, &muted], default),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(
+            schedule.provider_interval([&nightly], default),
+            Some(Duration::from_secs(1800))
+        );
+        assert_eq!(schedule.provider_interval([&muted], default), None);
+        assert_eq!(schedule.provider_interval([], default), Some(default));
+        assert_eq!(
+            schedule.provider_interval([&pipeline("other")], default),
+            Some(default)
+        );
+    }
+
+    #[test]
+    fn test_boost_shortens_but_never_unsnoozes() {
+        let default = Duration::from_secs(300);
+        let mut schedule = RefreshSchedule::new(vec![
+            setting("fast", Some(5), false),
+            setting("muted", None, true),
+        ]);
+        schedule.boost("fast", Duration::from_secs(10));
+        schedule.boost("muted", Duration::from_secs(10));
+        schedule.boost("viewed", Duration::from_secs(10));
+
+        assert_eq!(
+            schedule.pipeline_interval("fast", default),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(schedule.pipeline_interval("muted", default), None);
+        assert_eq!(
+            schedule.pipeline_interval("viewed", default),
+            Some(Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn test_validate_bounds() {
+        assert!(setting("p", Some(1), false).validate().is_err());
+        assert!(setting("p", Some(15), false).validate().is_ok());
+        assert!(setting("p", None, true).validate().is_ok());
+    }
+}
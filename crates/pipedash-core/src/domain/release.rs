@@ -0,0 +1,429 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::LazyLock;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use regex::Regex;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::pipeline::{
+    CommitInfo,
+    Pipeline,
+    PipelineRun,
+};
+
+// Merge and squash commit conventions of GitHub ("Merge pull request #12",
+// "Title (#12)"), GitLab ("See merge request group/project!12") and Bitbucket
+// ("Merged in branch (pull request #12)").
+static PULL_REQUEST_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)Merge pull request #(?P<gh>\d+)|\(#(?P<squash>\d+)\)\s*$|See merge request \S*!(?P<mr>\d+)|\(pull request #(?P<bb>\d+)\)",
+    )
+    .expect("Invalid regex pattern")
+});
+
+// Closing keywords ("Fixes #12", "closes acme/api#3") and issue keys leading
+// the title ("PROJ-123: ..." or "[PROJ-123] ...").
+static ISSUE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:close[sd]?|fix(?:e[sd])?|resolve[sd]?):?\s+(?P<issue>(?:[\w.-]+/[\w.-]+)?#\d+)",
+    )
+    .expect("Invalid regex pattern")
+});
+
+static ISSUE_KEY_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\[?(?P<key>[A-Z][A-Z0-9]+-\d+)\]?[:\s]").expect("Invalid regex pattern")
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseCommit {
+    pub sha: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub committed_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<String>,
+}
+
+impl ReleaseCommit {
+    fn from_commit(commit: CommitInfo) -> Self {
+        let mut paragraphs = commit
+            .message
+            .split("\n\n")
+            .map(str::trim)
+            .filter(|p| !p.is_empty());
+        let subject = paragraphs
+            .next()
+            .unwrap_or_default()
+            .lines()
+            .next()
+            .unwrap_or_default();
+
+        let pull_request = PULL_REQUEST_PATTERN
+            .captures(&commit.message)
+            .and_then(|caps| {
+                ["gh", "squash", "mr", "bb"]
+                    .iter()
+                    .find_map(|name| caps.name(name))
+                    .and_then(|m| m.as_str().parse().ok())
+            });
+
+        // Merge commits carry the pull request title in the body.
+        let title = if subject.starts_with("Merge pull request")
+            || subject.starts_with("Merge branch")
+            || subject.starts_with("Merged in")
+        {
+            paragraphs
+                .next()
+                .and_then(|p| p.lines().next())
+                .unwrap_or(subject)
+        } else {
+            subject
+        };
+
+        let mut issues: Vec<String> = ISSUE_PATTERN
+            .captures_iter(&commit.message)
+            .filter_map(|caps| caps.name("issue"))
+            .map(|m| m.as_str().to_string())
+            .chain(
+                ISSUE_KEY_PATTERN
+                    .captures(title)
+                    .and_then(|caps| caps.name("key"))
+                    .map(|m| m.as_str().to_string()),
+            )
+            .collect();
+        issues.dedup();
+
+        Self {
+            sha: commit.sha,
+            title: title.to_string(),
+            author: commit.author,
+            committed_at: commit.committed_at,
+            url: commit.url,
+            pull_request,
+            issues,
+        }
+    }
+
+    fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(7)]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleasePullRequest {
+    pub number: u64,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseContributor {
+    pub name: String,
+    pub commits: usize,
+}
+
+// What shipped between two deployment runs of a pipeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseReport {
+    pub pipeline_id: String,
+    pub pipeline_name: String,
+    pub repository: String,
+    pub from_run: PipelineRun,
+    pub to_run: PipelineRun,
+    // False when the provider can't list commits between the two runs.
+    pub commits_available: bool,
+    pub commits: Vec<ReleaseCommit>,
+    pub pull_requests: Vec<ReleasePullRequest>,
+    pub contributors: Vec<ReleaseContributor>,
+    pub issues: Vec<String>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl ReleaseReport {
+    pub fn new(
+        pipeline: &Pipeline, from_run: PipelineRun, to_run: PipelineRun,
+        commits: Option<Vec<CommitInfo>>,
+    ) -> Self {
+        let commits_available = commits.is_some();
+        // Providers list commits oldest first; the report reads newest first.
+        let commits: Vec<ReleaseCommit> = commits
+            .unwrap_or_default()
+            .into_iter()
+            .rev()
+            .map(ReleaseCommit::from_commit)
+            .collect();
+
+        let mut pull_requests: Vec<ReleasePullRequest> = Vec::new();
+        let mut contributors: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut issues: Vec<String> = Vec::new();
+
+        for commit in &commits {
+            if let Some(number) = commit.pull_request {
+                if !pull_requests.iter().any(|pr| pr.number == number) {
+                    pull_requests.push(ReleasePullRequest {
+                        number,
+                        title: commit.title.clone(),
+                        url: commit
+                            .url
+                            .as_deref()
+                            .and_then(|url| pull_request_url(url, number)),
+                    });
+                }
+            }
+            if let Some(author) = commit.author.as_deref() {
+                *contributors.entry(author).or_default() += 1;
+            }
+            for issue in &commit.issues {
+                if !issues.contains(issue) {
+                    issues.push(issue.clone());
+                }
+            }
+        }
+
+        let mut contributors: Vec<ReleaseContributor> = contributors
+            .into_iter()
+            .map(|(name, commits)| ReleaseContributor {
+                name: name.to_string(),
+                commits,
+            })
+            .collect();
+        contributors.sort_by_key(|c| std::cmp::Reverse(c.commits));
+
+        Self {
+            pipeline_id: pipeline.id.clone(),
+            pipeline_name: pipeline.name.clone(),
+            repository: pipeline.repository.clone(),
+            from_run,
+            to_run,
+            commits_available,
+            commits,
+            pull_requests,
+            contributors,
+            issues,
+            generated_at: Utc::now(),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let sha = |run: &PipelineRun| {
+            run.commit_sha
+                .as_deref()
+                .map(|sha| format!(" (`{}`)", &sha[..sha.len().min(7)]))
+                .unwrap_or_default()
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}: release report\n", self.pipeline_name);
+        let _ = writeln!(
+            out,
+            "{} run #{}{} → #{}{}, deployed {}\n",
+            self.repository,
+            self.from_run.run_number,
+            sha(&self.from_run),
+            self.to_run.run_number,
+            sha(&self.to_run),
+            self.to_run.started_at.format("%Y-%m-%d %H:%M UTC")
+        );
+
+        if !self.commits_available {
+            out.push_str("_The provider doesn't list commits between runs._\n");
+            return out;
+        }
+        if self.commits.is_empty() {
+            out.push_str("_No new commits._\n");
+            return out;
+        }
+
+        if !self.pull_requests.is_empty() {
+            out.push_str("## Pull requests\n\n");
+            for pr in &self.pull_requests {
+                let _ = match &pr.url {
+                    Some(url) => writeln!(out, "- [#{}]({}) {}", pr.number, url, pr.title),
+                    None => writeln!(out, "- #{} {}", pr.number, pr.title),
+                };
+            }
+            out.push('\n');
+        }
+
+        let _ = writeln!(out, "## Commits ({})\n", self.commits.len());
+        for commit in &self.commits {
+            let sha = match &commit.url {
+                Some(url) => format!("[`{}`]({})", commit.short_sha(), url),
+                None => format!("`{}`", commit.short_sha()),
+            };
+            let _ = match &commit.author {
+                Some(author) => writeln!(out, "- {} {} ({})", sha, commit.title, author),
+                None => writeln!(out, "- {} {}", sha, commit.title),
+            };
+        }
+
+        if !self.contributors.is_empty() {
+            out.push_str("\n## Contributors\n\n");
+            for contributor in &self.contributors {
+                let _ = writeln!(
+                    out,
+                    "- {} ({} commit{})",
+                    contributor.name,
+                    contributor.commits,
+                    if contributor.commits == 1 { "" } else { "s" }
+                );
+            }
+        }
+
+        if !self.issues.is_empty() {
+            out.push_str("\n## Linked issues\n\n");
+            for issue in &self.issues {
+                let _ = writeln!(out, "- {}", issue);
+            }
+        }
+
+        out
+    }
+}
+
+// Derived from the commit URL for GitHub and GitLab, the providers that can
+// list commits between runs.
+fn pull_request_url(commit_url: &str, number: u64) -> Option<String> {
+    if let Some((project, _)) = commit_url.split_once("/-/commit/") {
+        return Some(format!("{}/-/merge_requests/{}", project, number));
+    }
+    commit_url
+        .split_once("/commit/")
+        .map(|(repo, _)| format!("{}/pull/{}", repo, number))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::domain::PipelineStatus;
+
+    fn commit(sha: &str, message: &str, author: &str) -> CommitInfo {
+        CommitInfo {
+            sha: sha.to_string(),
+            message: message.to_string(),
+            author: Some(author.to_string()),
+            committed_at: None,
+            url: Some(format!("https://github.com/acme/api/commit/{}", sha)),
+        }
+    }
+
+    fn run(run_number: i64, sha: &str) -> PipelineRun {
+        PipelineRun {
+            id: run_number.to_string(),
+            pipeline_id: "deploy".to_string(),
+            run_number,
+            status: PipelineStatus::Success,
+            started_at: DateTime::from_timestamp(1_740_000_000, 0).unwrap(),
+            concluded_at: None,
+            duration_seconds: None,
+            logs_url: String::new(),
+            commit_sha: Some(sha.to_string()),
+            commit_message: None,
+            branch: None,
+            actor: None,
+            commit_author: None,
+            author_avatar_url: None,
+            pull_request: None,
+            inputs: None,
+            metadata: HashMap::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    fn pipeline() -> Pipeline {
+        Pipeline {
+            id: "deploy".to_string(),
+            provider_id: 1,
+            provider_type: "github".to_string(),
+            name: "Deploy".to_string(),
+            status: PipelineStatus::Success,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "acme/api".to_string(),
+            branch: None,
+            workflow_file: None,
+            metadata: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_commit_parsing() {
+        let merge = ReleaseCommit::from_commit(commit(
+            "aaa",
+            "Merge pull request #42 from acme/retry\n\nRetry flaky uploads\n\nFixes #7",
+            "alice",
+        ));
+        assert_eq!(merge.pull_request, Some(42));
+        assert_eq!(merge.title, "Retry flaky uploads");
+        assert_eq!(merge.issues, vec!["#7"]);
+
+        let squash =
+            ReleaseCommit::from_commit(commit("bbb", "PROJ-12: Add health checks (#43)", "bob"));
+        assert_eq!(squash.pull_request, Some(43));
+        assert_eq!(squash.issues, vec!["PROJ-12"]);
+
+        let gitlab = ReleaseCommit::from_commit(commit(
+            "ccc",
+            "Merge branch 'cache' into 'main'\n\nCache tokens\n\nCloses acme/api#9\n\nSee merge request acme/api!5",
+            "carol",
+        ));
+        assert_eq!(gitlab.pull_request, Some(5));
+        assert_eq!(gitlab.title, "Cache tokens");
+        assert_eq!(gitlab.issues, vec!["acme/api#9"]);
+
+        let plain = ReleaseCommit::from_commit(commit("ddd", "Bump UTF-8 handling", "alice"));
+        assert_eq!(plain.pull_request, None);
+        assert!(plain.issues.is_empty());
+    }
+
+    #[test]
+    fn test_release_report() {
+        let commits = vec![
+            commit("1111111aaa", "Fix typo", "alice"),
+            commit("2222222bbb", "Add retries (#42)\n\nResolves #7", "bob"),
+            commit("3333333ccc", "Tune timeouts", "alice"),
+        ];
+        let report = ReleaseReport::new(
+            &pipeline(),
+            run(10, "1111111aaa"),
+            run(11, "3333333ccc"),
+            Some(commits),
+        );
+
+        assert_eq!(report.commits[0].sha, "3333333ccc");
+        assert_eq!(report.pull_requests.len(), 1);
+        assert_eq!(
+            report.pull_requests[0].url.as_deref(),
+            Some("https://github.com/acme/api/pull/42")
+        );
+        assert_eq!(report.contributors[0].name, "alice");
+        assert_eq!(report.contributors[0].commits, 2);
+        assert_eq!(report.issues, vec!["#7"]);
+
+        let markdown = report.to_markdown();
+        assert!(markdown.starts_with("# Deploy: release report"));
+        assert!(markdown.contains("run #10 (`1111111`) → #11 (`3333333`)"));
+        assert!(markdown.contains("- [#42](https://github.com/acme/api/pull/42) Add retries"));
+        assert!(markdown.contains("- alice (2 commits)"));
+
+        let unavailable = ReleaseReport::new(&pipeline(), run(10, "a"), run(11, "b"), None);
+        assert!(unavailable.to_markdown().contains("doesn't list commits"));
+    }
+}
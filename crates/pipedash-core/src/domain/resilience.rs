@@ -0,0 +1,229 @@
+use std::time::Duration;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use pipedash_plugin_api::RetryPolicy;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+// How provider fetches are retried, and when a provider's circuit breaker
+// stops fetching from it. Read from the `[resilience]` config section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResilienceConfig {
+    // Attempts per fetch, the first one included. 1 disables retries.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: usize,
+
+    #[serde(default = "default_initial_delay_ms")]
+    pub initial_delay_ms: u64,
+
+    // Longest wait between attempts. A longer Retry-After fails the fetch
+    // instead.
+    #[serde(default = "default_max_delay_secs")]
+    pub max_delay_secs: u64,
+
+    // Consecutive failed fetches that open the breaker. 0 disables it.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: usize,
+
+    // How long an open breaker waits before letting a trial fetch through.
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+impl Default for ResilienceConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            initial_delay_ms: default_initial_delay_ms(),
+            max_delay_secs: default_max_delay_secs(),
+            failure_threshold: default_failure_threshold(),
+            cooldown_secs: default_cooldown_secs(),
+        }
+    }
+}
+
+fn default_max_attempts() -> usize {
+    2
+}
+
+fn default_initial_delay_ms() -> u64 {
+    500
+}
+
+fn default_max_delay_secs() -> u64 {
+    10
+}
+
+fn default_failure_threshold() -> usize {
+    5
+}
+
+fn default_cooldown_secs() -> u64 {
+    60
+}
+
+impl ResilienceConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(
+            self.max_attempts,
+            Duration::from_millis(self.initial_delay_ms),
+            true,
+        )
+        .with_max_delay(Duration::from_secs(self.max_delay_secs))
+    }
+
+    fn cooldown(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.cooldown_secs as i64)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    #[default]
+    Closed,
+    // Fetches fail right away until the cooldown is over.
+    Open,
+    // The cooldown is over and one trial fetch decides whether to close.
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CircuitBreakerStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: usize,
+    pub opened_at: Option<DateTime<Utc>>,
+    // When the next trial fetch is let through, while open.
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+// Per-provider breaker. Only transient failures count, so a revoked token
+// keeps reporting its own error instead of tripping the breaker.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBreaker {
+    state: CircuitState,
+    consecutive_failures: usize,
+    opened_at: Option<DateTime<Utc>>,
+    trial_in_flight: bool,
+}
+
+impl CircuitBreaker {
+    // Whether a fetch may go out now. Err carries when it may be tried again.
+    pub fn try_acquire(
+        &mut self, config: &ResilienceConfig, now: DateTime<Utc>,
+    ) -> Result<(), DateTime<Utc>> {
+        match self.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open => {
+                let retry_at = self.retry_at(config).unwrap_or(now);
+                if now < retry_at {
+                    return Err(retry_at);
+                }
+                self.state = CircuitState::HalfOpen;
+                self.trial_in_flight = true;
+                Ok(())
+            }
+            CircuitState::HalfOpen if self.trial_in_flight => {
+                Err(now + chrono::Duration::seconds(1))
+            }
+            CircuitState::HalfOpen => {
+                self.trial_in_flight = true;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn record_success(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn record_failure(&mut self, config: &ResilienceConfig, now: DateTime<Utc>) {
+        self.consecutive_failures += 1;
+        self.trial_in_flight = false;
+
+        let tripped =
+            config.failure_threshold > 0 && self.consecutive_failures >= config.failure_threshold;
+        if self.state == CircuitState::HalfOpen || tripped {
+            self.state = CircuitState::Open;
+            self.opened_at = Some(now);
+        }
+    }
+
+    fn retry_at(&self, config: &ResilienceConfig) -> Option<DateTime<Utc>> {
+        self.opened_at
+            .map(|opened_at| opened_at + config.cooldown())
+    }
+
+    pub fn status(&self, config: &ResilienceConfig) -> CircuitBreakerStatus {
+        CircuitBreakerStatus {
+            state: self.state,
+            consecutive_failures: self.consecutive_failures,
+            opened_at: self.opened_at,
+            retry_at: (self.state == CircuitState::Open)
+                .then(|| self.retry_at(config))
+                .flatten(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_breaker_opens_and_recovers() {
+        let config = ResilienceConfig {
+            failure_threshold: 2,
+            cooldown_secs: 60,
+            ..ResilienceConfig::default()
+        };
+        let now = Utc::now();
+        let mut breaker = CircuitBreaker::default();
+
+        breaker.record_failure(&config, now);
+        assert!(breaker.try_acquire(&config, now).is_ok());
+        breaker.record_failure(&config, now);
+
+        let status = breaker.status(&config);
+        assert_eq!(status.state, CircuitState::Open);
+        assert_eq!(status.retry_at, Some(now + chrono::Duration::seconds(60)));
+        assert_eq!(
+            breaker.try_acquire(&config, now + chrono::Duration::seconds(30)),
+            Err(now + chrono::Duration::seconds(60))
+        );
+
+        // One trial fetch after the cooldown; a failure reopens right away.
+        let later = now + chrono::Duration::seconds(61);
+        assert!(breaker.try_acquire(&config, later).is_ok());
+        assert!(breaker.try_acquire(&config, later).is_err());
+        breaker.record_failure(&config, later);
+        assert_eq!(breaker.status(&config).state, CircuitState::Open);
+
+        let later = later + chrono::Duration::seconds(61);
+        assert!(breaker.try_acquire(&config, later).is_ok());
+        breaker.record_success();
+        assert_eq!(breaker.status(&config), CircuitBreakerStatus::default());
+    }
+
+    #[test]
+    fn test_circuit_breaker_disabled() {
+        let config = ResilienceConfig {
+            failure_threshold: 0,
+            ..ResilienceConfig::default()
+        };
+        let mut breaker = CircuitBreaker::default();
+        for _ in 0..10 {
+            breaker.record_failure(&config, Utc::now());
+        }
+        assert!(breaker.try_acquire(&config, Utc::now()).is_ok());
+    }
+}
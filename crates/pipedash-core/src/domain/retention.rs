@@ -0,0 +1,133 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+
+// Limits applied to the cached run history of a single pipeline. `None` keeps
+// everything along that dimension.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunRetentionPolicy {
+    pub max_age_days: Option<i64>,
+    pub max_runs: Option<i64>,
+}
+
+impl RunRetentionPolicy {
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.max_age_days.is_some_and(|days| days < 1) {
+            return Err(DomainError::InvalidConfig(
+                "Run history max age must be at least 1 day".to_string(),
+            ));
+        }
+        if self.max_runs.is_some_and(|runs| runs < 1) {
+            return Err(DomainError::InvalidConfig(
+                "Run history max runs must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.max_age_days.is_none() && self.max_runs.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalRunRetentionConfig {
+    pub enabled: bool,
+    pub max_age_days: Option<i64>,
+    pub max_runs_per_pipeline: Option<i64>,
+    pub last_pruned_at: Option<DateTime<Utc>>,
+    pub last_pruned_count: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Default for GlobalRunRetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_age_days: Some(90),
+            max_runs_per_pipeline: Some(500),
+            last_pruned_at: None,
+            last_pruned_count: 0,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+impl GlobalRunRetentionConfig {
+    pub fn policy(&self) -> RunRetentionPolicy {
+        RunRetentionPolicy {
+            max_age_days: self.max_age_days,
+            max_runs: self.max_runs_per_pipeline,
+        }
+    }
+
+    // Each limit set on the pipeline override wins over the global one.
+    pub fn policy_for(&self, pipeline: Option<&PipelineRunRetention>) -> RunRetentionPolicy {
+        let global = self.policy();
+        match pipeline {
+            Some(pipeline) => RunRetentionPolicy {
+                max_age_days: pipeline.max_age_days.or(global.max_age_days),
+                max_runs: pipeline.max_runs.or(global.max_runs),
+            },
+            None => global,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineRunRetention {
+    pub pipeline_id: String,
+    pub max_age_days: Option<i64>,
+    pub max_runs: Option<i64>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_override_falls_back_per_field() {
+        let global = GlobalRunRetentionConfig {
+            max_age_days: Some(30),
+            max_runs_per_pipeline: Some(200),
+            ..Default::default()
+        };
+        let pipeline = PipelineRunRetention {
+            pipeline_id: "github__1__o__r__1".to_string(),
+            max_age_days: None,
+            max_runs: Some(1000),
+            updated_at: Utc::now(),
+        };
+
+        assert_eq!(
+            global.policy_for(Some(&pipeline)),
+            RunRetentionPolicy {
+                max_age_days: Some(30),
+                max_runs: Some(1000),
+            }
+        );
+        assert_eq!(global.policy_for(None), global.policy());
+    }
+
+    #[test]
+    fn test_validate_policy() {
+        assert!(RunRetentionPolicy::default().validate().is_ok());
+        assert!(RunRetentionPolicy {
+            max_age_days: Some(0),
+            max_runs: None,
+        }
+        .validate()
+        .is_err());
+    }
+}
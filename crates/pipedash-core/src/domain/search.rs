@@ -0,0 +1,134 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::pipeline::{
+    Pipeline,
+    PipelineRun,
+    PipelineStatus,
+};
+
+const MAX_SEARCH_TERMS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchResultKind {
+    Pipeline,
+    Run,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub kind: SearchResultKind,
+    pub pipeline_id: String,
+    pub pipeline_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_number: Option<i64>,
+    pub status: PipelineStatus,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+}
+
+impl SearchResult {
+    pub fn for_pipeline(pipeline: &Pipeline) -> Self {
+        Self {
+            kind: SearchResultKind::Pipeline,
+            pipeline_id: pipeline.id.clone(),
+            pipeline_name: pipeline.name.clone(),
+            run_number: None,
+            status: pipeline.status.clone(),
+            title: pipeline.name.clone(),
+            subtitle: Some(match &pipeline.branch {
+                Some(branch) => format!("{} ({})", pipeline.repository, branch),
+                None => pipeline.repository.clone(),
+            }),
+        }
+    }
+
+    pub fn for_run(run: &PipelineRun, pipeline_name: &str) -> Self {
+        let subtitle = run
+            .commit_message
+            .as_deref()
+            .and_then(|message| message.lines().next())
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .or_else(|| run.branch.clone());
+
+        Self {
+            kind: SearchResultKind::Run,
+            pipeline_id: run.pipeline_id.clone(),
+            pipeline_name: pipeline_name.to_string(),
+            run_number: Some(run.run_number),
+            status: run.status.clone(),
+            title: format!("{} #{}", pipeline_name, run.run_number),
+            subtitle,
+        }
+    }
+}
+
+// Free text typed by the user, split into terms that every hit must contain.
+// Terms are matched as prefixes so results show up while typing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    terms: Vec<String>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Option<Self> {
+        let terms: Vec<String> = input
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|term| !term.is_empty())
+            .map(str::to_lowercase)
+            .take(MAX_SEARCH_TERMS)
+            .collect();
+
+        if terms.is_empty() {
+            None
+        } else {
+            Some(Self { terms })
+        }
+    }
+
+    pub fn terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    // SQLite FTS5 MATCH expression; terms only contain alphanumerics so quoting
+    // them is enough to keep FTS5 operators out.
+    pub fn fts5_expression(&self) -> String {
+        self.terms
+            .iter()
+            .map(|term| format!("\"{}\"*", term))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // Postgres to_tsquery expression.
+    pub fn tsquery_expression(&self) -> String {
+        self.terms
+            .iter()
+            .map(|term| format!("{}:*", term))
+            .collect::<Vec<_>>()
+            .join(" & ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_query() {
+        let query = SearchQuery::parse("Payments  deploy-prod").unwrap();
+        assert_eq!(query.terms(), ["payments", "deploy", "prod"]);
+        assert_eq!(
+            query.fts5_expression(),
+            "\"payments\"* \"deploy\"* \"prod\"*"
+        );
+        assert_eq!(query.tsquery_expression(), "payments:* & deploy:* & prod:*");
+
+        assert!(SearchQuery::parse("  \"* ").is_none());
+    }
+}
@@ -13,6 +13,10 @@ const MAX_NAME_LENGTH: usize = 128;
 
 const MAX_PIPELINE_ID_LENGTH: usize = 512;
 
+const MAX_LABEL_LENGTH: usize = 128;
+
+const MAX_LABELS_PER_PIPELINE: usize = 32;
+
 pub fn validate_config(config: &HashMap<String, String>) -> DomainResult<()> {
     for (key, value) in config {
         validate_config_key(key)?;
@@ -127,6 +131,42 @@ pub fn validate_trigger_params(
     Ok(())
 }
 
+pub fn normalize_labels(labels: Vec<String>) -> DomainResult<Vec<String>> {
+    let mut normalized: Vec<String> = labels
+        .into_iter()
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty())
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+
+    if normalized.len() > MAX_LABELS_PER_PIPELINE {
+        return Err(DomainError::InvalidConfig(format!(
+            "A pipeline cannot have more than {} labels",
+            MAX_LABELS_PER_PIPELINE
+        )));
+    }
+
+    for label in &normalized {
+        if label.len() > MAX_LABEL_LENGTH {
+            return Err(DomainError::InvalidConfig(format!(
+                "Label '{}...' exceeds maximum length of {} characters",
+                &label[..32.min(label.len())],
+                MAX_LABEL_LENGTH
+            )));
+        }
+
+        if label.chars().any(|c| c.is_whitespace() || c == ',') {
+            return Err(DomainError::InvalidConfig(format!(
+                "Label '{}' cannot contain whitespace or commas",
+                label
+            )));
+        }
+    }
+
+    Ok(normalized)
+}
+
 pub fn validate_pagination(page: usize, page_size: usize) -> DomainResult<()> {
     if page == 0 {
         return Err(DomainError::InvalidConfig(
@@ -233,4 +273,18 @@ mod tests {
         assert!(validate_pagination(1, 0).is_err());
         assert!(validate_pagination(1, 2000).is_err());
     }
+
+    #[test]
+    fn test_normalize_labels() {
+        let labels = normalize_labels(vec![
+            " tier:prod ".to_string(),
+            "team:payments".to_string(),
+            "".to_string(),
+            "tier:prod".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(labels, vec!["team:payments", "tier:prod"]);
+
+        assert!(normalize_labels(vec!["team payments".to_string()]).is_err());
+    }
 }
@@ -0,0 +1,230 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use regex::Regex;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::error::{
+    DomainError,
+    DomainResult,
+};
+use super::pipeline::{
+    Pipeline,
+    PipelineStatus,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedView {
+    pub id: i64,
+    pub name: String,
+    pub query: ViewQuery,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Every set field narrows the result; list fields match if any entry matches.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ViewQuery {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provider_ids: Vec<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provider_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<PipelineStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_pattern: Option<String>,
+    // Matched against the pipeline's labels (`team:payments`, or `team` for any
+    // value) and, for provider-reported data, its metadata (`key`, `key=value`).
+    // All labels must match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+impl ViewQuery {
+    pub fn validate(&self) -> DomainResult<()> {
+        self.compile_name_pattern().map(|_| ())
+    }
+
+    pub fn filter(&self, pipelines: Vec<Pipeline>) -> DomainResult<Vec<Pipeline>> {
+        let name_regex = self.compile_name_pattern()?;
+        Ok(pipelines
+            .into_iter()
+            .filter(|p| self.matches(p, name_regex.as_ref()))
+            .collect())
+    }
+
+    fn compile_name_pattern(&self) -> DomainResult<Option<Regex>> {
+        self.name_pattern
+            .as_deref()
+            .filter(|pattern| !pattern.is_empty())
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|e| {
+                    DomainError::InvalidConfig(format!("Invalid name pattern '{}': {}", pattern, e))
+                })
+            })
+            .transpose()
+    }
+
+    fn matches(&self, pipeline: &Pipeline, name_regex: Option<&Regex>) -> bool {
+        if !self.provider_ids.is_empty() && !self.provider_ids.contains(&pipeline.provider_id) {
+            return false;
+        }
+
+        if !self.provider_types.is_empty()
+            && !self
+                .provider_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(&pipeline.provider_type))
+        {
+            return false;
+        }
+
+        if !self.statuses.is_empty() && !self.statuses.contains(&pipeline.status) {
+            return false;
+        }
+
+        if let Some(branch) = self.branch.as_deref().filter(|b| !b.is_empty()) {
+            if pipeline.branch.as_deref() != Some(branch) {
+                return false;
+            }
+        }
+
+        if let Some(regex) = name_regex {
+            if !regex.is_match(&pipeline.name) {
+                return false;
+            }
+        }
+
+        self.labels
+            .iter()
+            .all(|label| label_matches(pipeline, label))
+    }
+}
+
+fn label_matches(pipeline: &Pipeline, label: &str) -> bool {
+    if pipeline.has_label(label) {
+        return true;
+    }
+
+    let (key, expected) = match label.split_once('=') {
+        Some((key, value)) => (key.trim(), Some(value.trim())),
+        None => (label.trim(), None),
+    };
+
+    let Some(value) = pipeline.metadata.get(key) else {
+        return false;
+    };
+
+    match expected {
+        None => true,
+        Some(expected) => match value {
+            serde_json::Value::String(s) => s == expected,
+            serde_json::Value::Array(items) => {
+                items.iter().any(|item| item.as_str() == Some(expected))
+            }
+            // Numbers and booleans match their JSON text, e.g. `replicas=3`.
+            #[allow(clippy::cmp_owned)]
+            other => other.to_string() == expected,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn pipeline(name: &str, status: PipelineStatus, branch: &str) -> Pipeline {
+        Pipeline {
+            id: format!("github__1__org__{}", name),
+            provider_id: 1,
+            provider_type: "github".to_string(),
+            name: name.to_string(),
+            status,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "org/repo".to_string(),
+            branch: Some(branch.to_string()),
+            workflow_file: None,
+            metadata: HashMap::from([(
+                "team".to_string(),
+                serde_json::Value::String("platform".to_string()),
+            )]),
+            labels: vec!["tier:prod".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_filter_failing_on_main() {
+        let query = ViewQuery {
+            statuses: vec![PipelineStatus::Failed],
+            branch: Some("main".to_string()),
+            ..Default::default()
+        };
+
+        let result = query
+            .filter(vec![
+                pipeline("ci", PipelineStatus::Failed, "main"),
+                pipeline("lint", PipelineStatus::Success, "main"),
+                pipeline("nightly", PipelineStatus::Failed, "develop"),
+            ])
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "ci");
+    }
+
+    #[test]
+    fn test_filter_name_pattern_and_labels() {
+        let query = ViewQuery {
+            name_pattern: Some("^deploy-".to_string()),
+            labels: vec!["team=platform".to_string()],
+            ..Default::default()
+        };
+
+        let result = query
+            .filter(vec![
+                pipeline("deploy-prod", PipelineStatus::Success, "main"),
+                pipeline("build", PipelineStatus::Success, "main"),
+            ])
+            .unwrap();
+        assert_eq!(result.len(), 1);
+
+        let query = ViewQuery {
+            labels: vec!["team=mobile".to_string()],
+            ..Default::default()
+        };
+        assert!(query
+            .filter(vec![pipeline("ci", PipelineStatus::Success, "main")])
+            .unwrap()
+            .is_empty());
+
+        let query = ViewQuery {
+            labels: vec!["tier:prod".to_string(), "team".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            query
+                .filter(vec![pipeline("ci", PipelineStatus::Success, "main")])
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_invalid_name_pattern() {
+        let query = ViewQuery {
+            name_pattern: Some("(".to_string()),
+            ..Default::default()
+        };
+        assert!(query.validate().is_err());
+    }
+}
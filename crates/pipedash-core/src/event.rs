@@ -6,8 +6,16 @@ use serde::{
 
 use crate::domain::{
     provider::ProviderSummary,
+    AppNotification,
+    BuildAgent,
+    BulkItemResult,
+    BulkOperation,
+    PaginatedAvailablePipelines,
     Pipeline,
+    RunBackfillJob,
+    TriggerApproval,
 };
+use crate::infrastructure::ConfigChangeEvent;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -15,6 +23,8 @@ pub enum CacheInvalidationReason {
     Fetch,
     ProviderChange,
     ManualRefresh,
+    // Published by another instance sharing the same database.
+    Remote,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +58,35 @@ pub enum CoreEvent {
         pipeline_id: String,
     },
 
+    BulkProgress {
+        operation: BulkOperation,
+        completed: usize,
+        total: usize,
+        item: BulkItemResult,
+    },
+
+    DependencyTriggered {
+        upstream_id: String,
+        downstream_id: String,
+        error: Option<String>,
+    },
+
+    RunLogChunk {
+        pipeline_id: String,
+        run_number: i64,
+        job_id: String,
+        job_name: String,
+        offset: usize,
+        content: String,
+        is_complete: bool,
+    },
+
+    RunLogTailStopped {
+        pipeline_id: String,
+        run_number: i64,
+        error: Option<String>,
+    },
+
     RefreshError {
         error: String,
     },
@@ -106,7 +145,69 @@ pub enum CoreEvent {
         pipeline_id: Option<String>,
     },
 
+    AgentsUpdated {
+        provider_id: i64,
+        total: usize,
+        offline: usize,
+    },
+
+    AgentsOffline {
+        provider_id: i64,
+        agents: Vec<BuildAgent>,
+    },
+
     VaultUnlocked,
+
+    VaultRotationProgress {
+        rotated: usize,
+        total: usize,
+    },
+
+    NotificationCreated {
+        notification: AppNotification,
+        unread: usize,
+    },
+
+    // Read state changed or notifications were cleared.
+    NotificationsChanged {
+        unread: usize,
+    },
+
+    // config.toml was edited on disk and reloaded, or the edit was rejected.
+    ConfigChanged {
+        change: ConfigChangeEvent,
+    },
+
+    // A trigger request was created, approved or rejected.
+    TriggerApprovalsChanged {
+        approval: TriggerApproval,
+    },
+
+    // A run history import moved on to the next pipeline or stopped.
+    RunBackfillProgress {
+        job: RunBackfillJob,
+    },
+
+    // One page of a streamed pipelines preview, already filtered.
+    PreviewPage {
+        preview_id: String,
+        page: PaginatedAvailablePipelines,
+    },
+
+    // A streamed preview ran out of pages, failed or was cancelled.
+    PreviewFinished {
+        preview_id: String,
+        total: usize,
+        pages: usize,
+        cancelled: bool,
+        error: Option<String>,
+    },
+
+    // Auto-discovery added repositories to a provider's selection.
+    PipelinesDiscovered {
+        provider_id: i64,
+        repositories: Vec<String>,
+    },
 }
 
 impl CoreEvent {
@@ -119,6 +220,10 @@ impl CoreEvent {
             CoreEvent::PipelineStatusChanged { .. } => "pipeline-status-changed",
             CoreEvent::RunTriggered { .. } => "run-triggered",
             CoreEvent::RunCancelled { .. } => "run-cancelled",
+            CoreEvent::BulkProgress { .. } => "bulk-progress",
+            CoreEvent::DependencyTriggered { .. } => "dependency-triggered",
+            CoreEvent::RunLogChunk { .. } => "run-log-chunk",
+            CoreEvent::RunLogTailStopped { .. } => "run-log-tail-stopped",
             CoreEvent::RefreshError { .. } => "refresh-error",
             CoreEvent::MetricsGenerated { .. } => "metrics-generated",
             CoreEvent::MetricsGlobalConfigChanged => "metrics-global-config-changed",
@@ -132,7 +237,18 @@ impl CoreEvent {
             CoreEvent::ProviderRemoved { .. } => "provider-removed",
             CoreEvent::PipelineCacheInvalidated { .. } => "pipeline-cache-invalidated",
             CoreEvent::RunHistoryCacheInvalidated { .. } => "run-history-cache-invalidated",
+            CoreEvent::AgentsUpdated { .. } => "agents-updated",
+            CoreEvent::AgentsOffline { .. } => "agents-offline",
             CoreEvent::VaultUnlocked => "vault-unlocked",
+            CoreEvent::VaultRotationProgress { .. } => "vault-rotation-progress",
+            CoreEvent::NotificationCreated { .. } => "notification-created",
+            CoreEvent::NotificationsChanged { .. } => "notifications-changed",
+            CoreEvent::ConfigChanged { .. } => "config-changed",
+            CoreEvent::TriggerApprovalsChanged { .. } => "trigger-approvals-changed",
+            CoreEvent::RunBackfillProgress { .. } => "run-backfill-progress",
+            CoreEvent::PreviewPage { .. } => "preview-page",
+            CoreEvent::PreviewFinished { .. } => "preview-finished",
+            CoreEvent::PipelinesDiscovered { .. } => "pipelines-discovered",
         }
     }
 
@@ -160,6 +276,52 @@ impl CoreEvent {
             }
             CoreEvent::RunTriggered { workflow_id } => serde_json::json!(workflow_id),
             CoreEvent::RunCancelled { pipeline_id } => serde_json::json!(pipeline_id),
+            CoreEvent::BulkProgress {
+                operation,
+                completed,
+                total,
+                item,
+            } => serde_json::json!({
+                "operation": operation,
+                "completed": completed,
+                "total": total,
+                "item": item,
+            }),
+            CoreEvent::DependencyTriggered {
+                upstream_id,
+                downstream_id,
+                error,
+            } => serde_json::json!({
+                "upstream_id": upstream_id,
+                "downstream_id": downstream_id,
+                "error": error,
+            }),
+            CoreEvent::RunLogChunk {
+                pipeline_id,
+                run_number,
+                job_id,
+                job_name,
+                offset,
+                content,
+                is_complete,
+            } => serde_json::json!({
+                "pipeline_id": pipeline_id,
+                "run_number": run_number,
+                "job_id": job_id,
+                "job_name": job_name,
+                "offset": offset,
+                "content": content,
+                "is_complete": is_complete,
+            }),
+            CoreEvent::RunLogTailStopped {
+                pipeline_id,
+                run_number,
+                error,
+            } => serde_json::json!({
+                "pipeline_id": pipeline_id,
+                "run_number": run_number,
+                "error": error,
+            }),
             CoreEvent::RefreshError { error } => serde_json::json!(error),
             CoreEvent::MetricsGenerated { pipeline_id } => serde_json::json!(pipeline_id),
             CoreEvent::MetricsGlobalConfigChanged => serde_json::json!({}),
@@ -230,7 +392,68 @@ impl CoreEvent {
                 }
                 json
             }
+            CoreEvent::AgentsUpdated {
+                provider_id,
+                total,
+                offline,
+            } => serde_json::json!({
+                "provider_id": provider_id,
+                "total": total,
+                "offline": offline,
+            }),
+            CoreEvent::AgentsOffline {
+                provider_id,
+                agents,
+            } => serde_json::json!({
+                "provider_id": provider_id,
+                "agents": agents,
+            }),
             CoreEvent::VaultUnlocked => serde_json::json!({}),
+            CoreEvent::VaultRotationProgress { rotated, total } => serde_json::json!({
+                "rotated": rotated,
+                "total": total,
+            }),
+            CoreEvent::NotificationCreated {
+                notification,
+                unread,
+            } => serde_json::json!({
+                "notification": notification,
+                "unread": unread,
+            }),
+            CoreEvent::NotificationsChanged { unread } => serde_json::json!({
+                "unread": unread,
+            }),
+            CoreEvent::ConfigChanged { change } => {
+                let mut json = serde_json::to_value(change).unwrap_or_default();
+                json["summary"] = serde_json::json!(change.summary());
+                json
+            }
+            CoreEvent::TriggerApprovalsChanged { approval } => serde_json::json!(approval),
+            CoreEvent::RunBackfillProgress { job } => serde_json::json!(job),
+            CoreEvent::PreviewPage { preview_id, page } => serde_json::json!({
+                "preview_id": preview_id,
+                "page": page,
+            }),
+            CoreEvent::PreviewFinished {
+                preview_id,
+                total,
+                pages,
+                cancelled,
+                error,
+            } => serde_json::json!({
+                "preview_id": preview_id,
+                "total": total,
+                "pages": pages,
+                "cancelled": cancelled,
+                "error": error,
+            }),
+            CoreEvent::PipelinesDiscovered {
+                provider_id,
+                repositories,
+            } => serde_json::json!({
+                "provider_id": provider_id,
+                "repositories": repositories,
+            }),
         }
     }
 }
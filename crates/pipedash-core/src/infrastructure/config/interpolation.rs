@@ -5,9 +5,18 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum InterpolationError {
-    #[error("Required environment variable not found: {0}")]
+    #[error("Required environment variable not found: {0} (set it or add a default with ${{{0}:-default}})")]
     RequiredVarNotFound(String),
 
+    #[error("Failed to read {path}: {reason}")]
+    FileNotReadable { path: String, reason: String },
+
+    #[error("{key}: {source}")]
+    InKey {
+        key: String,
+        source: Box<InterpolationError>,
+    },
+
     #[error("Invalid variable syntax: {0}")]
     InvalidSyntax(String),
 
@@ -19,73 +28,124 @@ pub type InterpolationResult<T> = Result<T, InterpolationError>;
 
 const MAX_RECURSION_DEPTH: usize = 10;
 
+// `${VAR}` or `${env:VAR}` reads an environment variable, `${file:/path}` the
+// contents of a file (a trailing newline is dropped, as with Docker and
+// Kubernetes secret files). Any of them can fall back with `:-default`, and a
+// default can hold one more reference.
 static VAR_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\}").expect("Invalid regex pattern")
+    Regex::new(
+        r"\$\{(?:(?:env:)?(?P<var>[A-Za-z_][A-Za-z0-9_]*)|file:(?P<file>[^}]+?))(?::-(?P<default>(?:\$\{[^}]*\}|[^}])*))?\}",
+    )
+    .expect("Invalid regex pattern")
 });
 
+enum Source {
+    Env(String),
+    File(String),
+}
+
+impl Source {
+    fn from_captures(cap: &regex::Captures<'_>) -> Self {
+        match (cap.name("var"), cap.name("file")) {
+            (Some(var), _) => Self::Env(var.as_str().to_string()),
+            (None, Some(file)) => Self::File(file.as_str().trim().to_string()),
+            (None, None) => unreachable!("pattern always captures a source"),
+        }
+    }
+
+    // `None` when the variable is unset or the file doesn't exist, so a
+    // default can apply.
+    fn read(&self) -> InterpolationResult<Option<String>> {
+        match self {
+            Self::Env(name) => Ok(std::env::var(name).ok()),
+            Self::File(path) => match std::fs::read_to_string(path) {
+                Ok(content) => Ok(Some(content.trim_end_matches(['\n', '\r']).to_string())),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(InterpolationError::FileNotReadable {
+                    path: path.clone(),
+                    reason: e.to_string(),
+                }),
+            },
+        }
+    }
+
+    fn missing(self) -> InterpolationError {
+        match self {
+            Self::Env(name) => InterpolationError::RequiredVarNotFound(name),
+            Self::File(path) => InterpolationError::FileNotReadable {
+                path,
+                reason: "file not found".to_string(),
+            },
+        }
+    }
+}
+
 pub fn interpolate(input: &str) -> InterpolationResult<String> {
     interpolate_with_depth(input, 0)
 }
 
+// One pass: a resolved value is used as is, even if it contains `${...}`
+// itself. Only a default is interpolated again.
 fn interpolate_with_depth(input: &str, depth: usize) -> InterpolationResult<String> {
     if depth > MAX_RECURSION_DEPTH {
         return Err(InterpolationError::RecursionLimit);
     }
 
-    let mut result = input.to_string();
-    let mut changed = true;
-
-    while changed {
-        changed = false;
-
-        let matches: Vec<_> = VAR_PATTERN
-            .captures_iter(&result)
-            .map(|cap| {
-                let full_match = cap.get(0).unwrap();
-                let var_name = cap.get(1).unwrap().as_str().to_string();
-                let default = cap.get(2).map(|m| m.as_str().to_string());
-                (
-                    full_match.start(),
-                    full_match.end(),
-                    full_match.as_str().to_string(),
-                    var_name,
-                    default,
-                )
-            })
-            .collect();
-
-        for (_, _, full_match, var_name, default) in matches.into_iter().rev() {
-            let replacement = match std::env::var(&var_name) {
-                Ok(value) => value,
-                Err(_) => match default {
-                    Some(def) => interpolate_with_depth(&def, depth + 1)?,
-                    None => {
-                        return Err(InterpolationError::RequiredVarNotFound(var_name));
-                    }
-                },
-            };
-
-            result = result.replace(&full_match, &replacement);
-            changed = true;
+    let mut error = None;
+    let result = VAR_PATTERN.replace_all(input, |cap: &regex::Captures<'_>| {
+        if error.is_some() {
+            return String::new();
         }
+        resolve(cap, depth).unwrap_or_else(|e| {
+            error = Some(e);
+            String::new()
+        })
+    });
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(result.into_owned()),
     }
+}
 
-    Ok(result)
+fn resolve(cap: &regex::Captures<'_>, depth: usize) -> InterpolationResult<String> {
+    let source = Source::from_captures(cap);
+    match source.read()? {
+        Some(value) => Ok(value),
+        None => match cap.name("default") {
+            Some(default) => interpolate_with_depth(default.as_str(), depth + 1),
+            None => Err(source.missing()),
+        },
+    }
 }
 
+// Interpolates every string in the document. Errors name the key they came
+// from, e.g. `providers.github.token`.
 pub fn interpolate_toml(value: &mut toml::Value) -> InterpolationResult<()> {
+    interpolate_toml_at(value, "")
+}
+
+fn interpolate_toml_at(value: &mut toml::Value, key: &str) -> InterpolationResult<()> {
     match value {
         toml::Value::String(s) => {
-            *s = interpolate(s)?;
+            *s = interpolate(s).map_err(|e| InterpolationError::InKey {
+                key: key.to_string(),
+                source: Box::new(e),
+            })?;
         }
         toml::Value::Array(arr) => {
-            for item in arr {
-                interpolate_toml(item)?;
+            for (i, item) in arr.iter_mut().enumerate() {
+                interpolate_toml_at(item, &format!("{}[{}]", key, i))?;
             }
         }
         toml::Value::Table(table) => {
-            for (_, v) in table.iter_mut() {
-                interpolate_toml(v)?;
+            for (k, v) in table.iter_mut() {
+                let child = if key.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", key, k)
+                };
+                interpolate_toml_at(v, &child)?;
             }
         }
         _ => {}
@@ -100,7 +160,7 @@ pub fn has_variables(input: &str) -> bool {
 pub fn extract_variable_names(input: &str) -> Vec<String> {
     VAR_PATTERN
         .captures_iter(input)
-        .map(|cap| cap.get(1).unwrap().as_str().to_string())
+        .filter_map(|cap| cap.name("var").map(|m| m.as_str().to_string()))
         .collect()
 }
 
@@ -123,8 +183,10 @@ pub fn interpolate_with_tracking(input: &str) -> InterpolationResult<Interpolate
     let mut defaulted_vars = Vec::new();
 
     for cap in VAR_PATTERN.captures_iter(input) {
-        let var_name = cap.get(1).unwrap().as_str().to_string();
-        let has_default = cap.get(2).is_some();
+        let Some(var_name) = cap.name("var").map(|m| m.as_str().to_string()) else {
+            continue;
+        };
+        let has_default = cap.name("default").is_some();
 
         if std::env::var(&var_name).is_ok() {
             resolved_vars.push(var_name);
@@ -215,6 +277,22 @@ mod tests {
         std::env::remove_var("TEST_NESTED_INNER");
     }
 
+    #[test]
+    fn test_resolved_value_is_not_expanded_again() {
+        std::env::set_var("TEST_SELF_REF", "${env:TEST_SELF_REF}");
+        assert_eq!(
+            interpolate("${env:TEST_SELF_REF}").unwrap(),
+            "${env:TEST_SELF_REF}"
+        );
+        std::env::remove_var("TEST_SELF_REF");
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("value");
+        std::fs::write(&path, format!("${{file:{}}}", path.display())).unwrap();
+        let input = format!("${{file:{}}}", path.display());
+        assert_eq!(interpolate(&input).unwrap(), input);
+    }
+
     #[test]
     fn test_no_interpolation() {
         let result = interpolate("plain text").unwrap();
@@ -269,6 +347,54 @@ mod tests {
         std::env::remove_var("TEST_TOML_VAR");
     }
 
+    #[test]
+    fn test_env_prefix() {
+        std::env::set_var("TEST_ENV_PREFIX", "prefixed");
+        assert_eq!(interpolate("${env:TEST_ENV_PREFIX}").unwrap(), "prefixed");
+        assert_eq!(
+            interpolate("${env:NONEXISTENT_PREFIXED:-fallback}").unwrap(),
+            "fallback"
+        );
+        std::env::remove_var("TEST_ENV_PREFIX");
+    }
+
+    #[test]
+    fn test_file_source() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("token");
+        std::fs::write(&path, "s3cret\n").unwrap();
+
+        let input = format!("Bearer ${{file:{}}}", path.display());
+        assert_eq!(interpolate(&input).unwrap(), "Bearer s3cret");
+        assert!(has_variables(&input));
+        assert!(extract_variable_names(&input).is_empty());
+
+        let missing = dir.path().join("missing");
+        assert_eq!(
+            interpolate(&format!("${{file:{}:-none}}", missing.display())).unwrap(),
+            "none"
+        );
+        assert!(matches!(
+            interpolate(&format!("${{file:{}}}", missing.display())),
+            Err(InterpolationError::FileNotReadable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_toml_error_names_key() {
+        let mut value: toml::Value = toml::from_str(
+            r#"
+            [providers.github.config]
+            base_url = "${THIS_VAR_DOES_NOT_EXIST_67890}"
+        "#,
+        )
+        .unwrap();
+
+        let error = interpolate_toml(&mut value).unwrap_err().to_string();
+        assert!(error.starts_with("providers.github.config.base_url: "));
+        assert!(error.contains("THIS_VAR_DOES_NOT_EXIST_67890"));
+    }
+
     #[test]
     fn test_complex_address() {
         std::env::set_var("TEST_HOST", "localhost");
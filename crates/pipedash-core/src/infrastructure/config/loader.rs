@@ -17,7 +17,11 @@ use super::interpolation::{
     interpolate_toml,
     InterpolationError,
 };
-use super::schema::PipedashConfig;
+use super::schema::{
+    KeyringLayout,
+    PipedashConfig,
+};
+use super::versions::write_config;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SetupStatus {
@@ -43,7 +47,7 @@ pub enum ConfigLoadError {
     #[error("Failed to parse TOML: {0}")]
     ParseError(#[from] toml::de::Error),
 
-    #[error("Environment variable interpolation failed: {0}")]
+    #[error("Config interpolation failed: {0}")]
     InterpolationError(#[from] InterpolationError),
 
     #[error("Invalid configuration: {0}")]
@@ -261,17 +265,13 @@ impl ConfigLoader {
 
             Self::update_document(&mut doc, config)?;
 
-            std::fs::write(path, doc.to_string())?;
+            write_config(path, &doc.to_string())?;
         } else {
             let toml_str = Self::to_toml(config).map_err(|e| {
                 ConfigLoadError::InvalidConfig(format!("Failed to serialize config: {}", e))
             })?;
 
-            if let Some(parent) = path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-
-            std::fs::write(path, toml_str)?;
+            write_config(path, &toml_str)?;
         }
 
         Ok(())
@@ -298,6 +298,14 @@ impl ConfigLoader {
                 "cors_allow_all",
                 Item::Value(config.server.cors_allow_all.into()),
             );
+            match &config.server.public_url {
+                Some(url) => {
+                    server.insert("public_url", Item::Value(url.clone().into()));
+                }
+                None => {
+                    server.remove("public_url");
+                }
+            }
         }
 
         if doc.get("storage").is_none() {
@@ -317,7 +325,24 @@ impl ConfigLoader {
                 );
             }
 
-            if !config.storage.postgres.connection_string.is_empty() {
+            if config.storage.keyring_layout == KeyringLayout::Single {
+                storage.remove("keyring_layout");
+            } else {
+                storage.insert(
+                    "keyring_layout",
+                    Item::Value(config.storage.keyring_layout.to_string().into()),
+                );
+            }
+
+            if config.storage.encrypt_cache {
+                storage.insert("encrypt_cache", Item::Value(true.into()));
+            } else {
+                storage.remove("encrypt_cache");
+            }
+
+            if !config.storage.postgres.connection_string.is_empty()
+                || config.storage.postgres.refresh_lock
+            {
                 if storage.get("postgres").is_none() {
                     storage.insert("postgres", Item::Table(toml_edit::Table::new()));
                 }
@@ -325,14 +350,33 @@ impl ConfigLoader {
                     .get_mut("postgres")
                     .and_then(|v| v.as_table_like_mut())
                 {
-                    postgres.insert(
-                        "connection_string",
-                        Item::Value(config.storage.postgres.connection_string.clone().into()),
-                    );
+                    if !config.storage.postgres.connection_string.is_empty() {
+                        postgres.insert(
+                            "connection_string",
+                            Item::Value(config.storage.postgres.connection_string.clone().into()),
+                        );
+                    }
+                    if config.storage.postgres.refresh_lock {
+                        postgres.insert("refresh_lock", Item::Value(true.into()));
+                    } else {
+                        postgres.remove("refresh_lock");
+                    }
                 }
             }
         }
 
+        if config.desktop.run_in_background || doc.get("desktop").is_some() {
+            if doc.get("desktop").is_none() {
+                doc.insert("desktop", Item::Table(toml_edit::Table::new()));
+            }
+            if let Some(desktop) = doc.get_mut("desktop").and_then(|v| v.as_table_like_mut()) {
+                desktop.insert(
+                    "run_in_background",
+                    Item::Value(config.desktop.run_in_background.into()),
+                );
+            }
+        }
+
         Ok(())
     }
 
@@ -379,6 +423,7 @@ impl ConfigSources {
             "storage.data_dir" => Some("PIPEDASH_DATA_DIR"),
             "storage.backend" => Some("PIPEDASH_STORAGE_BACKEND"),
             "storage.postgres.connection_string" => Some("PIPEDASH_POSTGRES_URL"),
+            "general.demo_mode" => Some("PIPEDASH_DEMO_MODE"),
             _ => None,
         };
 
@@ -512,6 +557,21 @@ bind_addr = "${NONEXISTENT_VAR:-127.0.0.1:8080}"
         assert!(toml_str.contains("metrics_enabled"));
     }
 
+    #[test]
+    fn test_save_desktop_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(&config_path, "[general]\nmetrics_enabled = false\n").unwrap();
+
+        let mut config = ConfigLoader::load(&config_path).unwrap();
+        config.desktop.run_in_background = true;
+        ConfigLoader::save(&config, &config_path).unwrap();
+
+        let saved = ConfigLoader::load(&config_path).unwrap();
+        assert!(saved.desktop.run_in_background);
+        assert!(!saved.general.metrics_enabled);
+    }
+
     #[test]
     fn test_discover_config_path_env_override() {
         std::env::set_var("PIPEDASH_CONFIG_PATH", "/custom/path/config.toml");
@@ -97,6 +97,7 @@ impl StorageManager {
 
         let (config_backend, database_pool) =
             Self::create_config_backend(&config, backend, &data_dir).await?;
+        Self::share_sqlite_writer(token_store.as_ref(), config_backend.as_ref());
         let cache_backend = Self::create_cache_backend(&config, backend, &data_dir).await?;
 
         Ok(Self {
@@ -111,6 +112,14 @@ impl StorageManager {
         })
     }
 
+    // A SQLite token store writes to the same database as the config backend,
+    // so it queues its writes on the backend's writer.
+    fn share_sqlite_writer(token_store: &dyn TokenStore, config_backend: &dyn ConfigBackend) {
+        if let Some(writer) = config_backend.sqlite_writer() {
+            token_store.use_sqlite_writer(writer);
+        }
+    }
+
     fn is_vault_password_error(error: &DomainError) -> bool {
         match error {
             DomainError::InvalidConfig(msg) => {
@@ -155,6 +164,7 @@ impl StorageManager {
             password,
         )
         .await?;
+        Self::share_sqlite_writer(new_store.as_ref(), self.config_backend.as_ref());
 
         {
             let mut store = self.token_store.write().await;
@@ -382,6 +392,7 @@ impl StorageManager {
 
         let (config_backend, database_pool) =
             Self::create_config_backend(&config, backend, &data_dir).await?;
+        Self::share_sqlite_writer(token_store.as_ref(), config_backend.as_ref());
         let cache_backend = Self::create_cache_backend(&config, backend, &data_dir).await?;
 
         Ok(Self {
@@ -408,6 +419,7 @@ impl StorageManager {
 
         let (config_backend, database_pool) =
             Self::create_config_backend(&config, backend, &data_dir).await?;
+        Self::share_sqlite_writer(token_store.as_ref(), config_backend.as_ref());
         let cache_backend = Self::create_cache_backend(&config, backend, &data_dir).await?;
 
         tracing::info!("StorageManager created in locked vault mode");
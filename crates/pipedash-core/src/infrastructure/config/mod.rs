@@ -1,14 +1,27 @@
+pub mod bundle;
 pub mod encrypted_config;
 pub mod interpolation;
 pub mod loader;
 pub mod manager;
 pub mod migration;
 pub mod schema;
+pub mod setup;
 pub mod state;
 pub mod sync;
 pub mod token_ref;
 pub mod validation;
+pub mod versions;
 
+pub use bundle::{
+    BundledProvider,
+    BundledTablePreference,
+    ConfigBundle,
+    ImportOptions,
+    ImportReport,
+    ProviderImportAction,
+    ProviderImportResult,
+    CONFIG_BUNDLE_VERSION,
+};
 pub use encrypted_config::{
     is_encrypted_format,
     EncryptedValue,
@@ -22,15 +35,30 @@ pub use loader::{
 pub use manager::StorageManager;
 pub use migration::ConfigMigrator;
 pub use schema::{
+    AccessConfig,
+    BackupConfig,
+    ChannelFileConfig,
+    ChannelRuleFileConfig,
     ConfigKey,
+    CostConfig,
+    DesktopConfig,
     GeneralConfig,
+    GroupFileConfig,
+    KeyringLayout,
+    NotificationsConfig,
+    OAuthConfig,
     PipedashConfig,
     PostgresConfig,
     ProviderFileConfig,
+    ProvisioningConfig,
+    ScheduleFileConfig,
     ServerConfig,
+    SmtpConfig,
+    SmtpSecurity,
     StorageBackend,
     StorageConfig,
 };
+pub use setup::SetupDocument;
 pub use state::{
     ConfigChangeEvent,
     ConfigState,
@@ -44,3 +72,10 @@ pub use token_ref::{
     TokenReference,
 };
 pub use validation::ValidationResult;
+pub use versions::{
+    list_config_versions,
+    read_config_version,
+    restore_config_version,
+    write_config,
+    ConfigVersion,
+};
@@ -8,6 +8,14 @@ use serde::{
 };
 
 use super::token_ref::TokenReference;
+use crate::domain::{
+    ChannelKind,
+    NotificationSuppression,
+    PipelineStatus,
+    RedactionConfig,
+    ResilienceConfig,
+    Role,
+};
 
 pub(super) const DEFAULT_REFRESH_INTERVAL_SECS: u32 = 30;
 
@@ -21,6 +29,14 @@ pub(super) const DEFAULT_METRICS_ENABLED: bool = true;
 
 pub(super) const DEFAULT_DATA_DIR_SERVER: &str = "./data";
 
+pub(super) const DEFAULT_SMTP_PORT: u16 = 587;
+
+pub(super) const DEFAULT_BACKUP_INTERVAL_HOURS: u32 = 24;
+
+pub(super) const DEFAULT_BACKUP_RETENTION: usize = 7;
+
+pub(super) const DEFAULT_COST_CURRENCY: &str = "USD";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum StorageBackend {
@@ -63,6 +79,41 @@ impl std::str::FromStr for StorageBackend {
     }
 }
 
+// How tokens are laid out in the system keyring. `Single` keeps them all in
+// one entry, so the keychain is unlocked once per session; `PerProvider`
+// gives each provider its own `provider_{id}` entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyringLayout {
+    #[default]
+    Single,
+    PerProvider,
+}
+
+impl std::fmt::Display for KeyringLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Single => write!(f, "single"),
+            Self::PerProvider => write!(f, "per_provider"),
+        }
+    }
+}
+
+impl std::str::FromStr for KeyringLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "single" => Ok(Self::Single),
+            "per_provider" | "per-provider" => Ok(Self::PerProvider),
+            _ => Err(format!(
+                "Unknown keyring layout: {}. Valid options: single, per_provider",
+                s
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PipedashConfig {
     #[serde(default)]
@@ -74,8 +125,45 @@ pub struct PipedashConfig {
     #[serde(default)]
     pub storage: StorageConfig,
 
+    #[serde(default)]
+    pub access: AccessConfig,
+
+    #[serde(default)]
+    pub oauth: OAuthConfig,
+
+    #[serde(default, skip_serializing_if = "NotificationsConfig::is_empty")]
+    pub notifications: NotificationsConfig,
+
+    #[serde(default, skip_serializing_if = "BackupConfig::is_default")]
+    pub backup: BackupConfig,
+
+    #[serde(default, skip_serializing_if = "CostConfig::is_default")]
+    pub costs: CostConfig,
+
+    #[serde(default, skip_serializing_if = "DesktopConfig::is_default")]
+    pub desktop: DesktopConfig,
+
+    #[serde(default, skip_serializing_if = "ProvisioningConfig::is_default")]
+    pub provisioning: ProvisioningConfig,
+
+    // Retries and per-provider circuit breakers for provider fetches.
+    #[serde(default, skip_serializing_if = "ResilienceConfig::is_default")]
+    pub resilience: ResilienceConfig,
+
+    // Extra patterns and parameter names to mask in logs, run data and
+    // error messages.
+    #[serde(default, skip_serializing_if = "RedactionConfig::is_default")]
+    pub redaction: RedactionConfig,
+
     #[serde(default)]
     pub providers: IndexMap<String, ProviderFileConfig>,
+
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub groups: IndexMap<String, GroupFileConfig>,
+
+    // Keyed by pipeline reference, see `GroupFileConfig::pipelines`.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub schedules: IndexMap<String, ScheduleFileConfig>,
 }
 
 impl PipedashConfig {
@@ -116,6 +204,13 @@ impl PipedashConfig {
     pub fn config_path(&self) -> PathBuf {
         self.data_dir().join("config.toml")
     }
+
+    pub fn backup_dir(&self) -> PathBuf {
+        match &self.backup.directory {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => self.data_dir().join("backups"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -125,6 +220,11 @@ pub struct GeneralConfig {
 
     #[serde(default = "default_refresh_interval")]
     pub default_refresh_interval: u32,
+
+    // Offers the `demo` provider, which serves simulated pipelines without
+    // credentials.
+    #[serde(default)]
+    pub demo_mode: bool,
 }
 
 impl Default for GeneralConfig {
@@ -132,6 +232,7 @@ impl Default for GeneralConfig {
         Self {
             metrics_enabled: default_metrics_enabled(),
             default_refresh_interval: default_refresh_interval(),
+            demo_mode: false,
         }
     }
 }
@@ -151,6 +252,11 @@ pub struct ServerConfig {
 
     #[serde(default = "default_cors_allow_all")]
     pub cors_allow_all: bool,
+
+    // Base URL of the web UI, used for links in notifications. Without it
+    // links open the desktop app through `pipedash://`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_url: Option<String>,
 }
 
 impl Default for ServerConfig {
@@ -158,6 +264,7 @@ impl Default for ServerConfig {
         Self {
             bind_addr: default_bind_addr(),
             cors_allow_all: default_cors_allow_all(),
+            public_url: None,
         }
     }
 }
@@ -170,6 +277,217 @@ fn default_cors_allow_all() -> bool {
     DEFAULT_CORS_ALLOW_ALL
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessConfig {
+    // Role applied when the caller is not identified by a role token: the desktop
+    // app, the CLI, and the API server when no auth token is configured.
+    #[serde(default)]
+    pub default_role: Role,
+
+    // Lets whoever requested a trigger that needs approval approve it with an
+    // explicit confirmation. Off by default, so a second person has to
+    // approve. The web server only knows which role token a caller used, so
+    // requester and approver are compared by role: everyone sharing the admin
+    // token counts as one person. The desktop app and the CLI have a single
+    // local user, who can always approve with an explicit confirmation.
+    #[serde(default = "default_allow_self_approval")]
+    pub allow_self_approval: bool,
+}
+
+impl Default for AccessConfig {
+    fn default() -> Self {
+        Self {
+            default_role: Role::default(),
+            allow_self_approval: default_allow_self_approval(),
+        }
+    }
+}
+
+fn default_allow_self_approval() -> bool {
+    false
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OAuthConfig {
+    // Client IDs of OAuth apps with the device flow enabled. Device flow
+    // clients have no secret, so these are fine to keep in the config file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_client_id: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gitlab_client_id: Option<String>,
+}
+
+impl OAuthConfig {
+    pub fn client_id(&self, provider_type: &str) -> Option<&str> {
+        match provider_type {
+            "github" => self.github_client_id.as_deref(),
+            "gitlab" => self.gitlab_client_id.as_deref(),
+            _ => None,
+        }
+        .filter(|id| !id.trim().is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NotificationsConfig {
+    // Outgoing mail server used by email notification channels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smtp: Option<SmtpConfig>,
+
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub channels: IndexMap<String, ChannelFileConfig>,
+
+    // Environment variables a channel URL saved in the app may name, as the
+    // whole URL (`${env:SLACK_WEBHOOK}`). Anything else is stored literally.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channel_url_env: Vec<String>,
+}
+
+impl NotificationsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.smtp.is_none() && self.channels.is_empty() && self.channel_url_env.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    // Plain connection upgraded with STARTTLS, usually on port 587.
+    #[default]
+    Starttls,
+    // Implicit TLS, usually on port 465.
+    Tls,
+    None,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+
+    #[serde(default)]
+    pub security: SmtpSecurity,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    // Supports ${ENV_VAR} interpolation like provider tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    pub from: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_template: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_template: Option<String>,
+}
+
+fn default_smtp_port() -> u16 {
+    DEFAULT_SMTP_PORT
+}
+
+// Only read by the desktop app.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DesktopConfig {
+    // Closing the window hides it to the tray; the refresh loop and
+    // notifications keep running until Quit is chosen from the tray.
+    #[serde(default)]
+    pub run_in_background: bool,
+}
+
+impl DesktopConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupConfig {
+    // Scheduled backups only; manual backups work either way.
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "default_backup_interval_hours")]
+    pub interval_hours: u32,
+
+    // Number of backups kept, oldest removed first.
+    #[serde(default = "default_backup_retention")]
+    pub retention: usize,
+
+    // Defaults to a `backups` folder in the data directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory: Option<String>,
+}
+
+impl BackupConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: default_backup_interval_hours(),
+            retention: default_backup_retention(),
+            directory: None,
+        }
+    }
+}
+
+fn default_backup_interval_hours() -> u32 {
+    DEFAULT_BACKUP_INTERVAL_HOURS
+}
+
+fn default_backup_retention() -> usize {
+    DEFAULT_BACKUP_RETENTION
+}
+
+// Rates for estimating what CI minutes cost. Keys are provider names or
+// provider types (`github`, `buildkite`, ...); a provider name wins over its
+// type. Pipelines without a rate get no cost metric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostConfig {
+    #[serde(default = "default_cost_currency")]
+    pub currency: String,
+
+    // Price of one billable minute.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rates: HashMap<String, f64>,
+}
+
+impl CostConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn rate_for(&self, provider_name: Option<&str>, provider_type: &str) -> Option<f64> {
+        provider_name
+            .and_then(|name| self.rates.get(name))
+            .or_else(|| self.rates.get(provider_type))
+            .copied()
+    }
+}
+
+impl Default for CostConfig {
+    fn default() -> Self {
+        Self {
+            currency: default_cost_currency(),
+            rates: HashMap::new(),
+        }
+    }
+}
+
+fn default_cost_currency() -> String {
+    DEFAULT_COST_CURRENCY.to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StorageConfig {
     #[serde(default, skip_serializing_if = "String::is_empty")]
@@ -181,12 +499,25 @@ pub struct StorageConfig {
     #[serde(default, skip_serializing_if = "is_default_postgres_config")]
     pub postgres: PostgresConfig,
 
+    // Only used while tokens are kept in the system keyring.
+    #[serde(default, skip_serializing_if = "is_default_keyring_layout")]
+    pub keyring_layout: KeyringLayout,
+
+    // Encrypts cached run history and pipeline payloads with a key derived
+    // from the vault password.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub encrypt_cache: bool,
+
     #[serde(default, skip_serializing)]
     pub vault_password: Option<String>,
 }
 
 fn is_default_postgres_config(c: &PostgresConfig) -> bool {
-    c.connection_string.is_empty()
+    c.connection_string.is_empty() && !c.refresh_lock
+}
+
+fn is_default_keyring_layout(layout: &KeyringLayout) -> bool {
+    *layout == KeyringLayout::Single
 }
 
 impl StorageConfig {
@@ -207,6 +538,13 @@ impl StorageConfig {
 pub struct PostgresConfig {
     #[serde(default)]
     pub connection_string: String,
+
+    // Coordinates background refreshes through advisory locks so that only
+    // one of several instances sharing this database polls each provider.
+    // This is failover, not sharding: the first instance to refresh keeps
+    // every lock it takes, and another takes over when it stops.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub refresh_lock: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -260,6 +598,77 @@ impl ProviderFileConfig {
     }
 }
 
+// With `declarative` on, config.toml is the source of truth: providers,
+// groups, schedules and notification channels missing from the file are
+// deleted when it is applied. Otherwise declared resources are created and
+// updated but nothing is removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvisioningConfig {
+    #[serde(default)]
+    pub declarative: bool,
+}
+
+impl ProvisioningConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupFileConfig {
+    // Pipeline references: `<provider>:<repository or pipeline name>`, where
+    // `<provider>` is the key under `[providers]`, or a full pipeline ID.
+    #[serde(default)]
+    pub pipelines: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleFileConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval: Option<u64>,
+
+    #[serde(default)]
+    pub snoozed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelFileConfig {
+    #[serde(rename = "type")]
+    pub kind: ChannelKind,
+
+    pub url: String,
+
+    #[serde(default = "default_channel_enabled")]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub rules: Vec<ChannelRuleFileConfig>,
+
+    #[serde(default)]
+    pub suppression: NotificationSuppression,
+}
+
+fn default_channel_enabled() -> bool {
+    true
+}
+
+// Like `NotificationRule`, with pipeline references and group names in place
+// of database IDs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChannelRuleFileConfig {
+    #[serde(default)]
+    pub pipelines: Vec<String>,
+
+    #[serde(default)]
+    pub groups: Vec<String>,
+
+    #[serde(default)]
+    pub statuses: Vec<PipelineStatus>,
+
+    #[serde(default)]
+    pub anomalies: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ConfigKey {
     MetricsEnabled,
@@ -269,6 +678,7 @@ pub enum ConfigKey {
     DataDir,
     StorageBackend,
     PostgresConnectionString,
+    DemoMode,
 }
 
 impl ConfigKey {
@@ -281,6 +691,7 @@ impl ConfigKey {
             Self::DataDir => "PIPEDASH_DATA_DIR",
             Self::StorageBackend => "PIPEDASH_STORAGE_BACKEND",
             Self::PostgresConnectionString => "PIPEDASH_POSTGRES_URL",
+            Self::DemoMode => "PIPEDASH_DEMO_MODE",
         }
     }
 
@@ -289,7 +700,10 @@ impl ConfigKey {
     }
 
     pub fn requires_restart(&self) -> bool {
-        matches!(self, Self::StorageBackend | Self::BindAddr | Self::DataDir)
+        matches!(
+            self,
+            Self::StorageBackend | Self::BindAddr | Self::DataDir | Self::DemoMode
+        )
     }
 }
 
@@ -347,12 +761,63 @@ mod tests {
             backend: StorageBackend::Postgres,
             postgres: PostgresConfig {
                 connection_string: "postgres://localhost/test".to_string(),
+                ..Default::default()
             },
             ..Default::default()
         };
         assert!(pg_config_with_conn.validate().is_ok());
     }
 
+    #[test]
+    fn test_smtp_config_defaults() {
+        let config: PipedashConfig = toml::from_str(
+            r#"
+            [notifications.smtp]
+            host = "smtp.example.com"
+            from = "pipedash@example.com"
+            "#,
+        )
+        .unwrap();
+
+        let smtp = config.notifications.smtp.unwrap();
+        assert_eq!(smtp.port, 587);
+        assert_eq!(smtp.security, SmtpSecurity::Starttls);
+        assert!(PipedashConfig::default().notifications.is_empty());
+    }
+
+    #[test]
+    fn test_keyring_layout() {
+        let toml = r#"
+            [storage]
+            keyring_layout = "per_provider"
+        "#;
+        let config: PipedashConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.storage.keyring_layout, KeyringLayout::PerProvider);
+        assert_eq!(
+            "per-provider".parse::<KeyringLayout>().unwrap(),
+            KeyringLayout::PerProvider
+        );
+        assert!("split".parse::<KeyringLayout>().is_err());
+        assert_eq!(
+            StorageConfig::default().keyring_layout,
+            KeyringLayout::Single
+        );
+    }
+
+    #[test]
+    fn test_postgres_refresh_lock() {
+        let config: PipedashConfig = toml::from_str(
+            r#"
+            [storage.postgres]
+            refresh_lock = true
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.storage.postgres.refresh_lock);
+        assert!(!PostgresConfig::default().refresh_lock);
+    }
+
     #[test]
     fn test_provider_token_reference() {
         let provider = ProviderFileConfig {
@@ -414,4 +879,40 @@ mod tests {
         assert!(config.cache_dir().starts_with(&data_dir));
         assert!(config.vault_path().starts_with(&data_dir));
     }
+
+    #[test]
+    fn test_provisioned_sections() {
+        let config: PipedashConfig = toml::from_str(
+            r#"
+            [provisioning]
+            declarative = true
+
+            [groups.backend]
+            pipelines = ["work:acme/api"]
+
+            [schedules."work:acme/api"]
+            refresh_interval = 60
+
+            [notifications.channels.alerts]
+            type = "slack"
+            url = "https://hooks.slack.com/services/T000/B000/XXX"
+
+            [[notifications.channels.alerts.rules]]
+            groups = ["backend"]
+            statuses = ["failed"]
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.provisioning.declarative);
+        assert_eq!(config.groups["backend"].pipelines, vec!["work:acme/api"]);
+        assert_eq!(config.schedules["work:acme/api"].refresh_interval, Some(60));
+        assert!(!config.schedules["work:acme/api"].snoozed);
+
+        let channel = &config.notifications.channels["alerts"];
+        assert_eq!(channel.kind, ChannelKind::Slack);
+        assert!(channel.enabled);
+        assert_eq!(channel.rules[0].statuses, vec![PipelineStatus::Failed]);
+        assert!(!config.notifications.is_empty());
+    }
 }
@@ -0,0 +1,164 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::interpolation::interpolate;
+use super::loader::ConfigLoader;
+use super::schema::PipedashConfig;
+use super::versions::write_config;
+use crate::domain::{
+    DomainError,
+    DomainResult,
+};
+
+// Everything a first run needs in one document, so headless deployments can
+// be set up by provisioning tools instead of the setup wizard: the
+// config.toml to install (storage backend, providers, groups...) plus the
+// vault password. Applying the same document twice changes nothing.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetupDocument {
+    #[serde(flatten)]
+    pub config: PipedashConfig,
+
+    // Never written to config.toml. Falls back to `storage.vault_password`
+    // and then `PIPEDASH_VAULT_PASSWORD`.
+    #[serde(default)]
+    pub vault_password: Option<String>,
+}
+
+impl SetupDocument {
+    // JSON or TOML. `${VAR}` references in the config are kept as-is and
+    // resolved when the installed file is loaded. The vault password is taken
+    // literally: a document sent to the API must not make the server read
+    // its environment or files.
+    pub fn parse(content: &str) -> DomainResult<Self> {
+        let invalid = |e: &dyn std::fmt::Display| {
+            DomainError::InvalidConfig(format!("Invalid setup document: {}", e))
+        };
+        if content.trim_start().starts_with('{') {
+            serde_json::from_str(content).map_err(|e| invalid(&e))
+        } else {
+            toml::from_str(content).map_err(|e| invalid(&e))
+        }
+    }
+
+    // A document read from a local file, like config.toml itself. The vault
+    // password is resolved right away since it is never written.
+    pub fn parse_file(content: &str) -> DomainResult<Self> {
+        let mut document = Self::parse(content)?;
+        if let Some(password) = &document.vault_password {
+            document.vault_password = Some(interpolate(password).map_err(|e| {
+                DomainError::InvalidConfig(format!("Invalid setup document: {}", e))
+            })?);
+        }
+        Ok(document)
+    }
+
+    pub fn vault_password(&self) -> Option<String> {
+        self.vault_password
+            .clone()
+            .or_else(|| self.config.storage.vault_password.clone())
+            .or_else(|| std::env::var("PIPEDASH_VAULT_PASSWORD").ok())
+            .filter(|password| !password.is_empty())
+    }
+
+    // Provider tokens are encrypted with the vault password, so a headless
+    // setup can't go ahead without one.
+    pub fn validate(&self) -> DomainResult<()> {
+        let validation = self.config.validate();
+        if !validation.is_ok() {
+            let errors: Vec<String> = validation.errors.iter().map(|e| e.to_string()).collect();
+            return Err(DomainError::InvalidConfig(format!(
+                "Config validation failed: {}",
+                errors.join(", ")
+            )));
+        }
+        if self.vault_password().is_none() {
+            return Err(DomainError::InvalidConfig(
+                "A vault password is required: set vault_password or PIPEDASH_VAULT_PASSWORD"
+                    .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    // Writes the config unless the installed file already matches. Returns
+    // whether it changed.
+    pub fn install(&self, config_path: &Path) -> DomainResult<bool> {
+        let content = ConfigLoader::to_toml(&self.config).map_err(|e| {
+            DomainError::InvalidConfig(format!("Failed to serialize config: {}", e))
+        })?;
+        if std::fs::read_to_string(config_path).is_ok_and(|existing| existing == content) {
+            return Ok(false);
+        }
+
+        write_config(config_path, &content).map_err(|e| {
+            DomainError::InternalError(format!("Failed to write {}: {}", config_path.display(), e))
+        })?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"
+vault_password = "${SETUP_TEST_VAULT_PASSWORD:-correct horse battery}"
+
+[storage]
+backend = "sqlite"
+
+[providers.github]
+type = "github"
+token = "${GITHUB_TOKEN}"
+"#;
+
+    #[test]
+    fn test_parse_toml_and_json() {
+        let document = SetupDocument::parse_file(DOCUMENT).unwrap();
+        assert_eq!(
+            document.vault_password().as_deref(),
+            Some("correct horse battery")
+        );
+        assert_eq!(document.config.providers["github"].token, "${GITHUB_TOKEN}");
+
+        let document = SetupDocument::parse(
+            r#"{"vault_password": "pw", "providers": {"gl": {"type": "gitlab"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(document.vault_password.as_deref(), Some("pw"));
+        assert!(document.config.providers.contains_key("gl"));
+    }
+
+    #[test]
+    fn test_api_vault_password_is_literal() {
+        let document = SetupDocument::parse(DOCUMENT).unwrap();
+        assert_eq!(
+            document.vault_password().as_deref(),
+            Some("${SETUP_TEST_VAULT_PASSWORD:-correct horse battery}")
+        );
+
+        let document =
+            SetupDocument::parse(r#"{"vault_password": "${file:/nonexistent/pipedash}"}"#).unwrap();
+        assert_eq!(
+            document.vault_password().as_deref(),
+            Some("${file:/nonexistent/pipedash}")
+        );
+    }
+
+    #[test]
+    fn test_install_is_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        let document = SetupDocument::parse(DOCUMENT).unwrap();
+        document.validate().unwrap();
+
+        assert!(document.install(&config_path).unwrap());
+        assert!(!document.install(&config_path).unwrap());
+
+        let installed = std::fs::read_to_string(&config_path).unwrap();
+        assert!(installed.contains("${GITHUB_TOKEN}"));
+        assert!(!installed.contains("correct horse battery"));
+    }
+}
@@ -1,10 +1,24 @@
+use std::hash::{
+    DefaultHasher,
+    Hash,
+    Hasher,
+};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::time::Duration;
 
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use tokio::sync::{
     broadcast,
     RwLock,
 };
+use tokio::task::JoinHandle;
 
 use super::loader::{
     ConfigLoader,
@@ -21,6 +35,7 @@ use super::sync::{
 };
 use super::token_ref::TokenReference;
 use super::validation::ValidationResult;
+use super::versions::write_config;
 use crate::domain::{
     DomainError,
     DomainResult,
@@ -30,10 +45,20 @@ use crate::infrastructure::{
     TokenStore,
 };
 
-#[derive(Debug, Clone)]
+// Sections only read at startup; edits are picked up but need a restart.
+const RESTART_REQUIRED_SECTIONS: &[&str] = &["server", "storage", "oauth", "access"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum ConfigChangeEvent {
     Reloaded {
         changed_keys: Vec<String>,
+        restart_required: Vec<String>,
+    },
+    // An edit on disk that failed to parse or validate; the previous config
+    // stays active.
+    Rejected {
+        errors: Vec<String>,
     },
     ValueChanged {
         key: String,
@@ -54,9 +79,12 @@ pub enum ConfigChangeEvent {
 impl ConfigChangeEvent {
     pub fn summary(&self) -> String {
         match self {
-            Self::Reloaded { changed_keys } => {
+            Self::Reloaded { changed_keys, .. } => {
                 format!("Config reloaded, {} keys changed", changed_keys.len())
             }
+            Self::Rejected { errors } => {
+                format!("Config change rejected: {}", errors.join("; "))
+            }
             Self::ValueChanged { key, .. } => format!("Value changed: {}", key),
             Self::ProviderAdded { name } => format!("Provider added: {}", name),
             Self::ProviderUpdated { name } => format!("Provider updated: {}", name),
@@ -77,6 +105,12 @@ pub struct ConfigState {
     platform: Platform,
 
     sync_service: ProviderSyncService,
+
+    // Hash of the file content last loaded or written, so the watcher only
+    // reacts to edits made outside this process.
+    fingerprint: Mutex<Option<u64>>,
+
+    watcher: Mutex<Option<JoinHandle<()>>>,
 }
 
 impl ConfigState {
@@ -109,11 +143,13 @@ impl ConfigState {
 
         let state = Arc::new(Self {
             config: RwLock::new(Arc::new(config)),
+            fingerprint: Mutex::new(Self::file_fingerprint(&config_path)),
             config_path,
             change_tx,
             token_store,
             platform,
             sync_service: ProviderSyncService::new(),
+            watcher: Mutex::new(None),
         });
 
         Ok(state)
@@ -126,11 +162,13 @@ impl ConfigState {
 
         Arc::new(Self {
             config: RwLock::new(Arc::new(config)),
+            fingerprint: Mutex::new(Self::file_fingerprint(&config_path)),
             config_path,
             change_tx,
             token_store,
             platform: Platform::detect(),
             sync_service: ProviderSyncService::new(),
+            watcher: Mutex::new(None),
         })
     }
 
@@ -170,7 +208,9 @@ impl ConfigState {
     }
 
     pub async fn reload(&self) -> DomainResult<ConfigChangeEvent> {
-        let new_config = ConfigLoader::load(&self.config_path)
+        let content = std::fs::read_to_string(&self.config_path)
+            .map_err(|e| DomainError::InvalidConfig(format!("Failed to reload config: {}", e)))?;
+        let new_config = ConfigLoader::parse(&content)
             .map_err(|e| DomainError::InvalidConfig(format!("Failed to reload config: {}", e)))?;
 
         let validation = new_config.validate();
@@ -181,14 +221,154 @@ impl ConfigState {
             )));
         }
 
-        let event = ConfigChangeEvent::Reloaded {
-            changed_keys: vec![], // TODO: Calculate diff
+        *self.fingerprint.lock().unwrap() = Some(Self::fingerprint_of(&content));
+        Ok(self.apply_reloaded(new_config).await)
+    }
+
+    // Compares the file on disk against what was last loaded and reloads it
+    // when it changed. An edit that doesn't parse or validate is reported
+    // once as `Rejected` and the running config is kept.
+    pub async fn check_for_changes(&self) -> Option<ConfigChangeEvent> {
+        let content = std::fs::read_to_string(&self.config_path).ok()?;
+        let fingerprint = Self::fingerprint_of(&content);
+        {
+            let mut last = self.fingerprint.lock().unwrap();
+            if *last == Some(fingerprint) {
+                return None;
+            }
+            *last = Some(fingerprint);
+        }
+
+        let errors = match ConfigLoader::parse(&content) {
+            Ok(new_config) => {
+                let validation = new_config.validate();
+                if validation.errors.is_empty() {
+                    return Some(self.apply_reloaded(new_config).await);
+                }
+                validation.errors.iter().map(ToString::to_string).collect()
+            }
+            Err(e) => vec![e.to_string()],
         };
 
+        tracing::error!(
+            path = ?self.config_path,
+            errors = ?errors,
+            "Rejected config change, keeping the previous config"
+        );
+        let event = ConfigChangeEvent::Rejected { errors };
+        let _ = self.change_tx.send(event.clone());
+        Some(event)
+    }
+
+    pub fn start_watching(self: &Arc<Self>, interval: Duration) {
+        let mut watcher = self.watcher.lock().unwrap();
+        if watcher.is_some() {
+            return;
+        }
+
+        let state = Arc::clone(self);
+        *watcher = Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                state.check_for_changes().await;
+            }
+        }));
+        tracing::debug!(path = ?self.config_path, "Watching config file for changes");
+    }
+
+    pub fn stop_watching(&self) {
+        if let Some(handle) = self.watcher.lock().unwrap().take() {
+            handle.abort();
+        }
+    }
+
+    async fn apply_reloaded(&self, new_config: PipedashConfig) -> ConfigChangeEvent {
+        let mut current = self.config.write().await;
+        let changed_keys = Self::changed_keys(&current, &new_config);
+        *current = Arc::new(new_config);
+        drop(current);
+
+        let restart_required: Vec<String> = changed_keys
+            .iter()
+            .filter(|key| {
+                RESTART_REQUIRED_SECTIONS
+                    .iter()
+                    .any(|section| key.split('.').next() == Some(*section))
+            })
+            .cloned()
+            .collect();
+        if !restart_required.is_empty() {
+            tracing::warn!(
+                keys = ?restart_required,
+                "Config changes will take effect after a restart"
+            );
+        }
+
+        let event = ConfigChangeEvent::Reloaded {
+            changed_keys,
+            restart_required,
+        };
         let _ = self.change_tx.send(event.clone());
 
         tracing::info!("Config reloaded from {:?}", self.config_path);
-        Ok(event)
+        event
+    }
+
+    // Dotted keys two levels deep, e.g. `general.default_refresh_interval` or
+    // `providers.github-work`.
+    fn changed_keys(old: &PipedashConfig, new: &PipedashConfig) -> Vec<String> {
+        let old = serde_json::to_value(old).unwrap_or_default();
+        let new = serde_json::to_value(new).unwrap_or_default();
+        let empty = serde_json::Map::new();
+
+        let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+            return Vec::new();
+        };
+
+        let mut sections: Vec<&String> = old.keys().chain(new.keys()).collect();
+        sections.sort();
+        sections.dedup();
+
+        let mut changed = Vec::new();
+        for section in sections {
+            let (old_value, new_value) = (old.get(section), new.get(section));
+            if old_value == new_value {
+                continue;
+            }
+
+            let old_fields = old_value.and_then(|v| v.as_object());
+            let new_fields = new_value.and_then(|v| v.as_object());
+            if old_fields.is_none() && new_fields.is_none() {
+                changed.push(section.clone());
+                continue;
+            }
+
+            let old_fields = old_fields.unwrap_or(&empty);
+            let new_fields = new_fields.unwrap_or(&empty);
+            let mut keys: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            changed.extend(
+                keys.into_iter()
+                    .filter(|key| old_fields.get(*key) != new_fields.get(*key))
+                    .map(|key| format!("{}.{}", section, key)),
+            );
+        }
+        changed
+    }
+
+    fn fingerprint_of(content: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn file_fingerprint(path: &std::path::Path) -> Option<u64> {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|content| Self::fingerprint_of(&content))
     }
 
     pub async fn validate(&self) -> ValidationResult {
@@ -321,9 +501,12 @@ impl ConfigState {
             })?
         };
 
-        std::fs::write(&self.config_path, &new_content).map_err(|e| {
+        write_config(&self.config_path, &new_content).map_err(|e| {
             DomainError::DatabaseError(format!("Failed to write config file: {}", e))
         })?;
+        *self.fingerprint.lock().unwrap() = Some(Self::fingerprint_of(&new_content));
+
+        *self.config.write().await = Arc::new(config.clone());
 
         tracing::debug!("Config persisted to {:?}", self.config_path);
         Ok(())
@@ -416,6 +599,7 @@ impl ConfigState {
             ConfigKey::PostgresConnectionString => {
                 config.storage.postgres.connection_string.clone()
             }
+            ConfigKey::DemoMode => config.general.demo_mode.to_string(),
         }
     }
 
@@ -540,6 +724,74 @@ mod tests {
         assert_eq!(loaded_config.providers["persist-test"].refresh_interval, 60);
     }
 
+    #[tokio::test]
+    async fn test_check_for_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let token_store = Arc::new(MemoryTokenStore::new());
+        let config_path = temp_dir.path().join("config.toml");
+        ConfigLoader::save(&PipedashConfig::default(), &config_path).unwrap();
+
+        let state =
+            ConfigState::from_config(PipedashConfig::default(), config_path.clone(), token_store);
+        let mut rx = state.subscribe();
+        assert!(state.check_for_changes().await.is_none());
+
+        // Writes made through the state itself are not picked up as edits.
+        state.update_general_settings(true, 45).await.unwrap();
+        rx.try_recv().unwrap();
+        assert!(state.check_for_changes().await.is_none());
+
+        let mut edited = (*state.get().await).clone();
+        edited.general.default_refresh_interval = 90;
+        edited.providers.insert(
+            "gitlab-work".to_string(),
+            ProviderFileConfig {
+                name: None,
+                provider_type: "gitlab".to_string(),
+                token: "${GITLAB_TOKEN:-}".to_string(),
+                refresh_interval: 60,
+                config: std::collections::HashMap::new(),
+            },
+        );
+        edited.server.bind_addr = "0.0.0.0:9000".to_string();
+        std::fs::write(&config_path, ConfigLoader::to_toml(&edited).unwrap()).unwrap();
+
+        let Some(ConfigChangeEvent::Reloaded {
+            changed_keys,
+            restart_required,
+        }) = state.check_for_changes().await
+        else {
+            panic!("expected a reload");
+        };
+        assert_eq!(
+            changed_keys,
+            vec![
+                "general.default_refresh_interval",
+                "providers.gitlab-work",
+                "server.bind_addr"
+            ]
+        );
+        assert_eq!(restart_required, vec!["server.bind_addr"]);
+        assert_eq!(state.get().await.general.default_refresh_interval, 90);
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            ConfigChangeEvent::Reloaded { .. }
+        ));
+
+        std::fs::write(&config_path, "[general\nmetrics_enabled = ").unwrap();
+        assert!(matches!(
+            state.check_for_changes().await,
+            Some(ConfigChangeEvent::Rejected { .. })
+        ));
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            ConfigChangeEvent::Rejected { .. }
+        ));
+        assert_eq!(state.get().await.providers.len(), 1);
+        // Reported once, not on every poll.
+        assert!(state.check_for_changes().await.is_none());
+    }
+
     #[tokio::test]
     async fn test_get_config_values() {
         let old_metrics = std::env::var("PIPEDASH_METRICS_ENABLED").ok();
@@ -5,9 +5,14 @@ use indexmap::IndexMap;
 use super::schema::ProviderFileConfig;
 use super::token_ref::TokenReference;
 use crate::domain::{
+    keep_discovered_items,
     DomainResult,
     ProviderConfig,
 };
+use crate::infrastructure::secrets::{
+    redact_config_secrets,
+    store_config_secrets,
+};
 use crate::infrastructure::{
     ConfigBackend,
     TokenStore,
@@ -18,6 +23,9 @@ pub struct SyncResult {
     pub added: Vec<String>,
     pub updated: Vec<String>,
     pub removed: Vec<String>,
+    // In the database but not in the TOML, kept because orphans weren't
+    // deleted.
+    pub unmanaged: Vec<String>,
 }
 
 pub struct ProviderSyncService;
@@ -30,6 +38,30 @@ impl ProviderSyncService {
     pub async fn sync_toml_to_db(
         &self, toml_providers: IndexMap<String, ProviderFileConfig>,
         config_backend: &dyn ConfigBackend, token_store: &dyn TokenStore, delete_orphans: bool,
+    ) -> DomainResult<SyncResult> {
+        self.reconcile(
+            toml_providers,
+            config_backend,
+            Some(token_store),
+            delete_orphans,
+        )
+        .await
+    }
+
+    // What `sync_toml_to_db` would do, without writing anything.
+    pub async fn plan_toml_to_db(
+        &self, toml_providers: IndexMap<String, ProviderFileConfig>,
+        config_backend: &dyn ConfigBackend, delete_orphans: bool,
+    ) -> DomainResult<SyncResult> {
+        self.reconcile(toml_providers, config_backend, None, delete_orphans)
+            .await
+    }
+
+    // Writes only when given a token store.
+    async fn reconcile(
+        &self, toml_providers: IndexMap<String, ProviderFileConfig>,
+        config_backend: &dyn ConfigBackend, token_store: Option<&dyn TokenStore>,
+        delete_orphans: bool,
     ) -> DomainResult<SyncResult> {
         let mut result = SyncResult::default();
 
@@ -41,8 +73,10 @@ impl ProviderSyncService {
             let existing_db = db_providers.iter().find(|p| p.name == id);
 
             match existing_db {
+                None if token_store.is_none() => result.added.push(id.clone()),
                 None => {
                     let mut config = toml_provider.config.clone();
+                    redact_config_secrets(&mut config);
                     if let Some(display_name) = &toml_provider.name {
                         config.insert("display_name".to_string(), display_name.clone());
                     }
@@ -55,11 +89,18 @@ impl ProviderSyncService {
                         config,
                         refresh_interval: toml_provider.refresh_interval as i64,
                         version: None,
+                        enabled: true,
                     };
 
                     let new_provider_id = config_backend.create_provider(&provider_config).await?;
+                    if let Some(token_store) = token_store {
+                        Self::store_secrets(token_store, new_provider_id, &id, &toml_provider)
+                            .await;
+                    }
 
-                    if let Some(resolved_token) = Self::resolve_token_ref(&toml_provider.token) {
+                    if let (Some(resolved_token), Some(token_store)) =
+                        (Self::resolve_token_ref(&toml_provider.token), token_store)
+                    {
                         if let Err(e) = token_store
                             .store_token(new_provider_id, &resolved_token)
                             .await
@@ -79,9 +120,16 @@ impl ProviderSyncService {
                         "Synced provider from TOML to database (new)"
                     );
                 }
+                Some(db_provider) if token_store.is_none() => {
+                    if self.needs_update(&toml_provider, db_provider) {
+                        result.updated.push(id.clone());
+                    }
+                }
                 Some(db_provider) => {
                     if self.needs_update(&toml_provider, db_provider) {
-                        let mut config = toml_provider.config.clone();
+                        let mut config =
+                            keep_discovered_items(&toml_provider.config, &db_provider.config);
+                        redact_config_secrets(&mut config);
                         if let Some(display_name) = &toml_provider.name {
                             config.insert("display_name".to_string(), display_name.clone());
                         }
@@ -94,6 +142,7 @@ impl ProviderSyncService {
                             config,
                             refresh_interval: toml_provider.refresh_interval as i64,
                             version: db_provider.version,
+                            enabled: db_provider.enabled,
                         };
 
                         if let Some(db_id) = db_provider.id {
@@ -101,8 +150,8 @@ impl ProviderSyncService {
                                 .update_provider(db_id, &provider_config)
                                 .await?;
 
-                            if let Some(resolved_token) =
-                                Self::resolve_token_ref(&toml_provider.token)
+                            if let (Some(resolved_token), Some(token_store)) =
+                                (Self::resolve_token_ref(&toml_provider.token), token_store)
                             {
                                 if let Err(e) =
                                     token_store.store_token(db_id, &resolved_token).await
@@ -123,29 +172,51 @@ impl ProviderSyncService {
                             );
                         }
                     }
+
+                    // Secrets aren't in the database config, so a changed one
+                    // doesn't show up as drift; write them on every sync.
+                    if let (Some(db_id), Some(token_store)) = (db_provider.id, token_store) {
+                        Self::store_secrets(token_store, db_id, &id, &toml_provider).await;
+                    }
                 }
             }
         }
 
-        if delete_orphans {
-            for db_provider in db_providers {
-                if !toml_ids.contains(&db_provider.name) {
-                    if let Some(id) = db_provider.id {
-                        config_backend.delete_provider(id).await?;
-                        result.removed.push(db_provider.name.clone());
-
-                        tracing::info!(
-                            provider = %db_provider.name,
-                            "Removed provider from database (not in TOML)"
-                        );
-                    }
-                }
+        for db_provider in db_providers {
+            if toml_ids.contains(&db_provider.name) {
+                continue;
+            }
+            let Some(id) = db_provider.id.filter(|_| delete_orphans) else {
+                result.unmanaged.push(db_provider.name.clone());
+                continue;
+            };
+
+            if token_store.is_some() {
+                config_backend.delete_provider(id).await?;
+                tracing::info!(
+                    provider = %db_provider.name,
+                    "Removed provider from database (not in TOML)"
+                );
             }
+            result.removed.push(db_provider.name.clone());
         }
 
         Ok(result)
     }
 
+    async fn store_secrets(
+        token_store: &dyn TokenStore, provider_id: i64, name: &str, toml: &ProviderFileConfig,
+    ) {
+        let mut config = toml.config.clone();
+        if let Err(e) = store_config_secrets(token_store, provider_id, &mut config).await {
+            tracing::warn!(
+                provider = %name,
+                error = %e,
+                "Failed to store provider secrets from TOML"
+            );
+        }
+    }
+
     fn needs_update(&self, toml: &ProviderFileConfig, db: &ProviderConfig) -> bool {
         if toml.provider_type != db.provider_type
             || toml.token != db.token
@@ -167,7 +238,11 @@ impl ProviderSyncService {
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
 
-        toml.config != db_config_without_display
+        // Repositories added by auto-discovery aren't in the file; they don't
+        // count as drift.
+        let mut toml_config = keep_discovered_items(&toml.config, &db.config);
+        redact_config_secrets(&mut toml_config);
+        toml_config != db_config_without_display
     }
 
     fn resolve_token_ref(token_ref: &str) -> Option<String> {
@@ -190,6 +265,11 @@ impl ProviderSyncService {
                 }
             },
             Ok(TokenReference::None) => None,
+            // Stored as-is and resolved from the secrets manager on read.
+            Ok(reference) if reference.is_external() => Some(reference.to_toml_string()),
+            // Kept as a reference so a rotated secret file is picked up the
+            // next time the provider is built.
+            Ok(reference @ TokenReference::File(_)) => Some(reference.to_toml_string()),
             Ok(_) => {
                 tracing::warn!(
                     token_ref = %token_ref,
@@ -277,6 +357,7 @@ mod tests {
             config: Default::default(),
             refresh_interval: 30,
             version: None,
+            enabled: true,
         };
         backend.create_provider(&initial_provider).await.unwrap();
 
@@ -307,6 +388,120 @@ mod tests {
         assert_eq!(db_providers[0].refresh_interval, 60);
     }
 
+    #[tokio::test]
+    async fn test_sync_toml_to_db_keeps_discovered_items() {
+        let (backend, token_store, _temp) = create_test_backend().await;
+        let sync_service = ProviderSyncService::new();
+
+        let declared: std::collections::HashMap<String, String> = [
+            ("auto_discover", "true"),
+            ("discovery_orgs", "acme"),
+            ("selected_items", "acme/api"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let mut stored = declared.clone();
+        stored.insert(
+            "selected_items".to_string(),
+            "acme/api,acme/new".to_string(),
+        );
+
+        backend
+            .create_provider(&ProviderConfig {
+                id: None,
+                name: "test-github".to_string(),
+                provider_type: "github".to_string(),
+                token: "${GITHUB_TOKEN}".to_string(),
+                config: stored,
+                refresh_interval: 30,
+                version: None,
+                enabled: true,
+            })
+            .await
+            .unwrap();
+
+        let provider = |refresh_interval| ProviderFileConfig {
+            name: None,
+            provider_type: "github".to_string(),
+            token: "${GITHUB_TOKEN}".to_string(),
+            refresh_interval,
+            config: declared.clone(),
+        };
+
+        let unchanged = sync_service
+            .sync_toml_to_db(
+                IndexMap::from([("test-github".to_string(), provider(30))]),
+                &backend,
+                token_store.as_ref(),
+                true,
+            )
+            .await
+            .unwrap();
+        assert!(unchanged.updated.is_empty());
+
+        let changed = sync_service
+            .sync_toml_to_db(
+                IndexMap::from([("test-github".to_string(), provider(60))]),
+                &backend,
+                token_store.as_ref(),
+                true,
+            )
+            .await
+            .unwrap();
+        assert_eq!(changed.updated, vec!["test-github".to_string()]);
+
+        let db_providers = backend.list_providers().await.unwrap();
+        assert_eq!(
+            db_providers[0].config["selected_items"],
+            "acme/api,acme/new"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_toml_to_db_keeps_secrets_out_of_config() {
+        let (backend, token_store, _temp) = create_test_backend().await;
+        let sync_service = ProviderSyncService::new();
+
+        let toml = |secret: &str| {
+            IndexMap::from([(
+                "test-github".to_string(),
+                ProviderFileConfig {
+                    name: None,
+                    provider_type: "github".to_string(),
+                    token: "${GITHUB_TOKEN}".to_string(),
+                    refresh_interval: 30,
+                    config: [("webhook_secret".to_string(), secret.to_string())].into(),
+                },
+            )])
+        };
+
+        sync_service
+            .sync_toml_to_db(toml("first"), &backend, token_store.as_ref(), true)
+            .await
+            .unwrap();
+        let db_providers = backend.list_providers().await.unwrap();
+        let id = db_providers[0].id.unwrap();
+        assert_eq!(
+            db_providers[0].config["webhook_secret"],
+            "storage:webhook_secret"
+        );
+        assert_eq!(
+            token_store.get_secret(id, "webhook_secret").await.unwrap(),
+            Some("first".to_string())
+        );
+
+        let result = sync_service
+            .sync_toml_to_db(toml("second"), &backend, token_store.as_ref(), true)
+            .await
+            .unwrap();
+        assert!(result.updated.is_empty());
+        assert_eq!(
+            token_store.get_secret(id, "webhook_secret").await.unwrap(),
+            Some("second".to_string())
+        );
+    }
+
     #[tokio::test]
     async fn test_sync_toml_to_db_remove_provider() {
         let (backend, token_store, _temp) = create_test_backend().await;
@@ -320,6 +515,7 @@ mod tests {
             config: Default::default(),
             refresh_interval: 30,
             version: None,
+            enabled: true,
         };
         backend.create_provider(&initial_provider).await.unwrap();
 
@@ -350,6 +546,7 @@ mod tests {
             config: Default::default(),
             refresh_interval: 30,
             version: None,
+            enabled: true,
         };
         backend.create_provider(&db_only_provider).await.unwrap();
 
@@ -361,6 +558,7 @@ mod tests {
         assert!(result.added.is_empty());
         assert!(result.updated.is_empty());
         assert!(result.removed.is_empty());
+        assert_eq!(result.unmanaged, vec!["db-only-provider"]);
 
         let db_providers = backend.list_providers().await.unwrap();
         assert_eq!(db_providers.len(), 1);
@@ -380,6 +578,7 @@ mod tests {
             config: Default::default(),
             refresh_interval: 30,
             version: None,
+            enabled: true,
         };
         backend.create_provider(&db_only_provider).await.unwrap();
 
@@ -408,4 +607,47 @@ mod tests {
         let db_providers = backend.list_providers().await.unwrap();
         assert_eq!(db_providers.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_plan_toml_to_db_writes_nothing() {
+        let (backend, _token_store, _temp) = create_test_backend().await;
+        let sync_service = ProviderSyncService::new();
+
+        let db_only_provider = ProviderConfig {
+            id: None,
+            name: "db-only-provider".to_string(),
+            provider_type: "github".to_string(),
+            token: "${DB_TOKEN}".to_string(),
+            config: Default::default(),
+            refresh_interval: 30,
+            version: None,
+            enabled: true,
+        };
+        backend.create_provider(&db_only_provider).await.unwrap();
+
+        let mut toml_providers = IndexMap::new();
+        toml_providers.insert(
+            "toml-provider".to_string(),
+            ProviderFileConfig {
+                name: None,
+                provider_type: "gitlab".to_string(),
+                token: "${TOML_TOKEN}".to_string(),
+                refresh_interval: 60,
+                config: Default::default(),
+            },
+        );
+
+        let result = sync_service
+            .plan_toml_to_db(toml_providers, &backend, true)
+            .await
+            .unwrap();
+
+        assert_eq!(result.added, vec!["toml-provider"]);
+        assert_eq!(result.removed, vec!["db-only-provider"]);
+        assert!(result.unmanaged.is_empty());
+
+        let db_providers = backend.list_providers().await.unwrap();
+        assert_eq!(db_providers.len(), 1);
+        assert_eq!(db_providers[0].name, "db-only-provider");
+    }
 }
@@ -22,6 +22,9 @@ pub enum TokenRefError {
     #[error("Environment variable not found: {0}")]
     EnvVarNotFound(String),
 
+    #[error("Failed to read token file {0}: {1}")]
+    FileNotReadable(String, String),
+
     #[error("Token not found in secure storage for provider: {0}")]
     NotFoundInStorage(i64),
 
@@ -30,14 +33,33 @@ pub enum TokenRefError {
 
     #[error("Keyring lookup failed: {0}")]
     KeyringError(String),
+
+    #[error("External secret lookup failed: {0}")]
+    ExternalSecretError(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(tag = "type", content = "value")]
 pub enum TokenReference {
     EnvVar(String),
+    // A file holding just the token, like a mounted Docker or Kubernetes
+    // secret.
+    File(String),
     SecureStorage(i64),
     Keyring(String),
+    Vault {
+        path: String,
+        key: String,
+    },
+    AwsSecret {
+        secret_id: String,
+        key: Option<String>,
+    },
+    OnePassword {
+        vault: String,
+        item: String,
+        field: String,
+    },
     #[default]
     None,
 }
@@ -46,8 +68,12 @@ impl fmt::Display for TokenReference {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::EnvVar(name) => write!(f, "${{{}}} ", name),
+            Self::File(path) => write!(f, "file:{}", path),
             Self::SecureStorage(id) => write!(f, "storage:{}", id),
             Self::Keyring(name) => write!(f, "keyring:{}", name),
+            Self::Vault { .. } | Self::AwsSecret { .. } | Self::OnePassword { .. } => {
+                write!(f, "{}", self.to_toml_string())
+            }
             Self::None => write!(f, "(not configured)"),
         }
     }
@@ -63,7 +89,11 @@ impl TokenReference {
 
         if value.starts_with("${") && value.ends_with('}') {
             let inner = &value[2..value.len() - 1];
-            let var_name = inner.split(":-").next().unwrap_or(inner);
+            let inner = inner.split(":-").next().unwrap_or(inner);
+            if let Some(path) = inner.strip_prefix("file:") {
+                return Self::parse_file(path);
+            }
+            let var_name = inner.strip_prefix("env:").unwrap_or(inner);
             if var_name.is_empty() {
                 return Err(TokenRefError::InvalidFormat(
                     "Empty variable name in ${}".to_string(),
@@ -81,6 +111,10 @@ impl TokenReference {
             return Ok(Self::EnvVar(var_name.to_string()));
         }
 
+        if let Some(path) = value.strip_prefix("file:") {
+            return Self::parse_file(path);
+        }
+
         if let Some(name) = value.strip_prefix("keyring:") {
             if name.is_empty() {
                 return Err(TokenRefError::InvalidFormat(
@@ -95,6 +129,49 @@ impl TokenReference {
             return Ok(Self::SecureStorage(id));
         }
 
+        if let Some(reference) = value.strip_prefix("vault:") {
+            let (path, key) = reference.split_once('#').unwrap_or((reference, "token"));
+            if !path.contains('/') || path.starts_with('/') || key.is_empty() {
+                return Err(TokenRefError::InvalidFormat(
+                    "Expected vault:<mount>/<path>#<key>".to_string(),
+                ));
+            }
+            return Ok(Self::Vault {
+                path: path.to_string(),
+                key: key.to_string(),
+            });
+        }
+
+        if let Some(reference) = value.strip_prefix("aws-sm:") {
+            let (secret_id, key) = match reference.split_once('#') {
+                Some((id, key)) => (id, Some(key)),
+                None => (reference, None),
+            };
+            if secret_id.is_empty() || key.is_some_and(str::is_empty) {
+                return Err(TokenRefError::InvalidFormat(
+                    "Expected aws-sm:<secret-id> or aws-sm:<secret-id>#<json-key>".to_string(),
+                ));
+            }
+            return Ok(Self::AwsSecret {
+                secret_id: secret_id.to_string(),
+                key: key.map(str::to_string),
+            });
+        }
+
+        if let Some(reference) = value.strip_prefix("op://") {
+            let parts: Vec<&str> = reference.split('/').collect();
+            if parts.len() != 3 || parts.iter().any(|p| p.is_empty()) {
+                return Err(TokenRefError::InvalidFormat(
+                    "Expected op://<vault>/<item>/<field>".to_string(),
+                ));
+            }
+            return Ok(Self::OnePassword {
+                vault: parts[0].to_string(),
+                item: parts[1].to_string(),
+                field: parts[2].to_string(),
+            });
+        }
+
         if Self::looks_like_token(value) {
             return Err(TokenRefError::PlainTextToken(
                 "Plain-text tokens are not allowed in config. Use ${ENV_VAR} or leave empty for keyring.".to_string(),
@@ -106,20 +183,60 @@ impl TokenReference {
         }
 
         Err(TokenRefError::InvalidFormat(format!(
-            "Unknown token reference format: '{}'. Use ${{ENV_VAR}}, env:VAR, keyring:name, or storage:id",
+            "Unknown token reference format: '{}'. Use ${{ENV_VAR}}, env:VAR, file:/path, keyring:name, storage:id, vault:path#key, aws-sm:id, or op://vault/item/field",
             value
         )))
     }
 
+    fn parse_file(path: &str) -> Result<Self, TokenRefError> {
+        let path = path.trim();
+        if path.is_empty() {
+            return Err(TokenRefError::InvalidFormat(
+                "Empty path after file:".to_string(),
+            ));
+        }
+        Ok(Self::File(path.to_string()))
+    }
+
+    // Reads a token file, dropping the trailing newline most editors and
+    // secret mounts add.
+    pub fn read_file(path: &str) -> Result<String, TokenRefError> {
+        std::fs::read_to_string(path)
+            .map(|content| content.trim_end_matches(['\n', '\r']).to_string())
+            .map_err(|e| TokenRefError::FileNotReadable(path.to_string(), e.to_string()))
+    }
+
     pub fn to_toml_string(&self) -> String {
         match self {
             Self::EnvVar(name) => format!("${{{}}}", name),
+            Self::File(path) => format!("${{file:{}}}", path),
             Self::SecureStorage(id) => format!("storage:{}", id),
             Self::Keyring(name) => format!("keyring:{}", name),
+            Self::Vault { path, key } => format!("vault:{}#{}", path, key),
+            Self::AwsSecret {
+                secret_id,
+                key: Some(key),
+            } => format!("aws-sm:{}#{}", secret_id, key),
+            Self::AwsSecret {
+                secret_id,
+                key: None,
+            } => format!("aws-sm:{}", secret_id),
+            Self::OnePassword { vault, item, field } => {
+                format!("op://{}/{}/{}", vault, item, field)
+            }
             Self::None => String::new(),
         }
     }
 
+    // References into a secrets manager; the token itself never reaches
+    // pipedash's own storage.
+    pub fn is_external(&self) -> bool {
+        matches!(
+            self,
+            Self::Vault { .. } | Self::AwsSecret { .. } | Self::OnePassword { .. }
+        )
+    }
+
     pub fn is_plain_text(&self) -> bool {
         false
     }
@@ -135,6 +252,7 @@ impl TokenReference {
             Self::EnvVar(name) => {
                 std::env::var(name).map_err(|_| TokenRefError::EnvVarNotFound(name.clone()))
             }
+            Self::File(path) => Self::read_file(path),
             Self::SecureStorage(id) => token_store
                 .get_token(*id)
                 .await
@@ -143,6 +261,10 @@ impl TokenReference {
                 .get_token_by_name(name)
                 .await
                 .map_err(|e| TokenRefError::KeyringError(e.to_string())),
+            Self::Vault { .. } | Self::AwsSecret { .. } | Self::OnePassword { .. } => token_store
+                .get_token_by_name(&self.to_toml_string())
+                .await
+                .map_err(|e| TokenRefError::ExternalSecretError(e.to_string())),
             Self::None => {
                 if let Some(id) = provider_id {
                     token_store
@@ -232,6 +354,27 @@ mod tests {
         assert_eq!(result, TokenReference::EnvVar("MY_TOKEN".to_string()));
     }
 
+    #[test]
+    fn test_parse_env_and_file_sources() {
+        assert_eq!(
+            TokenReference::parse("${env:GITHUB_TOKEN}").unwrap(),
+            TokenReference::EnvVar("GITHUB_TOKEN".to_string())
+        );
+        assert_eq!(
+            TokenReference::parse("${file:/run/secrets/github}").unwrap(),
+            TokenReference::File("/run/secrets/github".to_string())
+        );
+        assert_eq!(
+            TokenReference::parse("file:/run/secrets/github").unwrap(),
+            TokenReference::File("/run/secrets/github".to_string())
+        );
+        assert_eq!(
+            TokenReference::File("/run/secrets/github".to_string()).to_toml_string(),
+            "${file:/run/secrets/github}"
+        );
+        assert!(TokenReference::parse("${file:}").is_err());
+    }
+
     #[test]
     fn test_parse_keyring() {
         let result = TokenReference::parse("keyring:my-provider").unwrap();
@@ -284,4 +427,43 @@ mod tests {
         );
         assert_eq!(TokenReference::None.to_toml_string(), "");
     }
+
+    #[test]
+    fn test_parse_external_references() {
+        assert_eq!(
+            TokenReference::parse("vault:secret/ci/github").unwrap(),
+            TokenReference::Vault {
+                path: "secret/ci/github".to_string(),
+                key: "token".to_string(),
+            }
+        );
+        assert_eq!(
+            TokenReference::parse("aws-sm:arn:aws:secretsmanager:us-east-1:1:secret:ci#gitlab")
+                .unwrap(),
+            TokenReference::AwsSecret {
+                secret_id: "arn:aws:secretsmanager:us-east-1:1:secret:ci".to_string(),
+                key: Some("gitlab".to_string()),
+            }
+        );
+        assert_eq!(
+            TokenReference::parse("op://CI/GitHub/credential").unwrap(),
+            TokenReference::OnePassword {
+                vault: "CI".to_string(),
+                item: "GitHub".to_string(),
+                field: "credential".to_string(),
+            }
+        );
+        assert!(TokenReference::parse("vault:secret").is_err());
+        assert!(TokenReference::parse("op://CI/GitHub").is_err());
+
+        for raw in [
+            "vault:secret/ci/github#pat",
+            "aws-sm:ci/tokens",
+            "op://CI/GitHub/credential",
+        ] {
+            let reference = TokenReference::parse(raw).unwrap();
+            assert!(reference.is_external());
+            assert_eq!(reference.to_toml_string(), raw);
+        }
+    }
 }
@@ -1,10 +1,18 @@
 use indexmap::IndexMap;
 
 use super::schema::{
+    BackupConfig,
+    CostConfig,
+    NotificationsConfig,
     PipedashConfig,
     ProviderFileConfig,
+    SmtpSecurity,
     StorageBackend,
 };
+use crate::domain::{
+    RedactionConfig,
+    ResilienceConfig,
+};
 
 #[derive(Debug, Default)]
 pub struct ValidationResult {
@@ -120,6 +128,11 @@ impl ConfigValidator {
 
         Self::validate_storage(&config.storage, &mut result);
         Self::validate_providers(&config.providers, &mut result);
+        Self::validate_notifications(&config.notifications, &mut result);
+        Self::validate_backup(&config.backup, &mut result);
+        Self::validate_costs(&config.costs, &mut result);
+        Self::validate_resilience(&config.resilience, &mut result);
+        Self::validate_redaction(&config.redaction, &mut result);
 
         result
     }
@@ -168,21 +181,13 @@ impl ConfigValidator {
                     code: ConfigErrorCode::MissingRequired,
                 });
             } else {
-                let valid_types = [
-                    "github",
-                    "gitlab",
-                    "bitbucket",
-                    "buildkite",
-                    "jenkins",
-                    "tekton",
-                    "argocd",
-                ];
-                if !valid_types.contains(&provider.provider_type.as_str()) {
+                if !crate::plugins::is_known_provider(&provider.provider_type) {
                     result.add_warning(ConfigWarning {
                         field: format!("{}.type", prefix),
                         message: format!(
                             "Unknown provider type: '{}'. Valid types: {:?}",
-                            provider.provider_type, valid_types
+                            provider.provider_type,
+                            crate::plugins::create_plugin_registry().provider_types()
                         ),
                         code: ConfigWarningCode::UnusedSetting,
                     });
@@ -209,6 +214,112 @@ impl ConfigValidator {
             }
         }
     }
+
+    fn validate_notifications(notifications: &NotificationsConfig, result: &mut ValidationResult) {
+        let Some(smtp) = &notifications.smtp else {
+            return;
+        };
+
+        if smtp.host.trim().is_empty() {
+            result.add_error(ConfigError {
+                field: "notifications.smtp.host".to_string(),
+                message: "SMTP host is required".to_string(),
+                code: ConfigErrorCode::MissingRequired,
+            });
+        }
+
+        if !smtp.from.contains('@') {
+            result.add_error(ConfigError {
+                field: "notifications.smtp.from".to_string(),
+                message: format!("Invalid sender address: '{}'", smtp.from),
+                code: ConfigErrorCode::InvalidValue,
+            });
+        }
+
+        if smtp.password.is_some() && smtp.username.is_none() {
+            result.add_error(ConfigError {
+                field: "notifications.smtp.username".to_string(),
+                message: "SMTP username is required when a password is set".to_string(),
+                code: ConfigErrorCode::MissingRequired,
+            });
+        }
+
+        if smtp.security == SmtpSecurity::None && smtp.password.is_some() {
+            result.add_warning(ConfigWarning {
+                field: "notifications.smtp.security".to_string(),
+                message: "SMTP credentials will be sent without encryption".to_string(),
+                code: ConfigWarningCode::InsecureDefault,
+            });
+        }
+    }
+
+    fn validate_backup(backup: &BackupConfig, result: &mut ValidationResult) {
+        if backup.interval_hours == 0 {
+            result.add_error(ConfigError {
+                field: "backup.interval_hours".to_string(),
+                message: "Backup interval must be at least 1 hour".to_string(),
+                code: ConfigErrorCode::InvalidValue,
+            });
+        }
+
+        if backup.retention == 0 {
+            result.add_error(ConfigError {
+                field: "backup.retention".to_string(),
+                message: "At least one backup must be kept".to_string(),
+                code: ConfigErrorCode::InvalidValue,
+            });
+        }
+    }
+
+    fn validate_costs(costs: &CostConfig, result: &mut ValidationResult) {
+        if costs.currency.trim().is_empty() {
+            result.add_error(ConfigError {
+                field: "costs.currency".to_string(),
+                message: "Currency cannot be empty".to_string(),
+                code: ConfigErrorCode::InvalidValue,
+            });
+        }
+
+        for (key, rate) in &costs.rates {
+            if !rate.is_finite() || *rate < 0.0 {
+                result.add_error(ConfigError {
+                    field: format!("costs.rates.{}", key),
+                    message: "Rate per minute must be a non-negative number".to_string(),
+                    code: ConfigErrorCode::InvalidValue,
+                });
+            }
+        }
+    }
+
+    fn validate_resilience(resilience: &ResilienceConfig, result: &mut ValidationResult) {
+        if resilience.max_attempts == 0 {
+            result.add_error(ConfigError {
+                field: "resilience.max_attempts".to_string(),
+                message: "At least one attempt is needed; use 1 to disable retries".to_string(),
+                code: ConfigErrorCode::InvalidValue,
+            });
+        }
+
+        if resilience.failure_threshold > 0 && resilience.cooldown_secs == 0 {
+            result.add_error(ConfigError {
+                field: "resilience.cooldown_secs".to_string(),
+                message: "Circuit breaker cooldown must be at least 1 second".to_string(),
+                code: ConfigErrorCode::InvalidValue,
+            });
+        }
+    }
+
+    fn validate_redaction(redaction: &RedactionConfig, result: &mut ValidationResult) {
+        for (index, pattern) in redaction.patterns.iter().enumerate() {
+            if let Err(e) = regex::Regex::new(pattern) {
+                result.add_error(ConfigError {
+                    field: format!("redaction.patterns[{}]", index),
+                    message: format!("Invalid regular expression: {}", e),
+                    code: ConfigErrorCode::InvalidValue,
+                });
+            }
+        }
+    }
 }
 
 impl PipedashConfig {
@@ -221,6 +332,7 @@ impl PipedashConfig {
 mod tests {
     use std::collections::HashMap;
 
+    use super::super::schema::SmtpConfig;
     use super::*;
 
     #[test]
@@ -243,6 +355,35 @@ mod tests {
             .any(|e| e.field.contains("postgres.connection_string")));
     }
 
+    #[test]
+    fn test_smtp_validation() {
+        let mut config = PipedashConfig::default();
+        config.notifications.smtp = Some(SmtpConfig {
+            host: "smtp.example.com".to_string(),
+            port: 587,
+            security: SmtpSecurity::None,
+            username: None,
+            password: Some("${SMTP_PASSWORD}".to_string()),
+            from: "pipedash".to_string(),
+            subject_template: None,
+            body_template: None,
+        });
+
+        let result = config.validate();
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.field == "notifications.smtp.from"));
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.field == "notifications.smtp.username"));
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.code == ConfigWarningCode::InsecureDefault));
+    }
+
     #[test]
     fn test_provider_missing_type() {
         let mut config = PipedashConfig::default();
@@ -306,6 +447,51 @@ mod tests {
             .any(|w| w.code == ConfigWarningCode::NoTokenConfigured));
     }
 
+    #[test]
+    fn test_backup_validation() {
+        let mut config = PipedashConfig::default();
+        config.backup.retention = 0;
+
+        let result = config.validate();
+        assert!(result.errors.iter().any(|e| e.field == "backup.retention"));
+    }
+
+    #[test]
+    fn test_cost_validation() {
+        let mut config = PipedashConfig::default();
+        config.costs.rates.insert("github".to_string(), 0.008);
+        assert!(config.validate().is_ok());
+
+        config.costs.rates.insert("buildkite".to_string(), -1.0);
+        let result = config.validate();
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.field == "costs.rates.buildkite"));
+    }
+
+    #[test]
+    fn test_resilience_validation() {
+        let mut config = PipedashConfig::default();
+        config.resilience.max_attempts = 0;
+
+        let result = config.validate();
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.field == "resilience.max_attempts"));
+    }
+
+    #[test]
+    fn test_redaction_validation() {
+        let mut config = PipedashConfig::default();
+        config.redaction.patterns = vec!["acme-[0-9]+".to_string(), "(unclosed".to_string()];
+
+        let result = config.validate();
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].field, "redaction.patterns[1]");
+    }
+
     #[test]
     fn test_validation_summary() {
         let result = ValidationResult::new();
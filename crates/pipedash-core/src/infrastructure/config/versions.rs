@@ -0,0 +1,273 @@
+use std::io::Write;
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use chrono::{
+    DateTime,
+    NaiveDateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::schema::PipedashConfig;
+use crate::domain::{
+    validate_backup_id,
+    DomainError,
+    DomainResult,
+};
+
+// Previous versions of config.toml live in this directory next to it.
+pub const CONFIG_VERSIONS_DIR: &str = "config_versions";
+
+// How many previous versions are kept; the oldest is dropped on each write
+// past this.
+pub const MAX_CONFIG_VERSIONS: usize = 20;
+
+const VERSION_ID_FORMAT: &str = "%Y%m%d-%H%M%S-%3f";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigVersion {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+}
+
+// Replaces config.toml without ever leaving a half-written file behind: the
+// content goes to a temp file in the same directory, is synced, then renamed
+// over the original. The content being replaced is kept as a version first.
+// The new file takes over the original's permissions.
+pub fn write_config(path: &Path, content: &str) -> std::io::Result<()> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    std::fs::create_dir_all(dir)?;
+
+    if let Ok(previous) = std::fs::read_to_string(path) {
+        if previous == content {
+            return Ok(());
+        }
+        save_version(path, &previous)?;
+    }
+
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("config.toml");
+    let temp_path = dir.join(format!(".{}.tmp", file_name));
+    {
+        let mut file = create_private(&temp_path)?;
+        if let Ok(metadata) = std::fs::metadata(path) {
+            file.set_permissions(metadata.permissions())?;
+        }
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+    }
+    std::fs::rename(&temp_path, path)?;
+
+    // Makes the rename itself durable.
+    #[cfg(unix)]
+    if let Ok(dir) = std::fs::File::open(dir) {
+        let _ = dir.sync_all();
+    }
+
+    Ok(())
+}
+
+// Newest first.
+pub fn list_config_versions(path: &Path) -> DomainResult<Vec<ConfigVersion>> {
+    let dir = versions_dir(path);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(DomainError::InternalError(format!(
+                "Failed to read {}: {}",
+                dir.display(),
+                e
+            )))
+        }
+    };
+
+    let mut versions: Vec<ConfigVersion> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let id = name.strip_prefix("config-")?.strip_suffix(".toml")?;
+            let created_at = NaiveDateTime::parse_from_str(id, VERSION_ID_FORMAT)
+                .ok()?
+                .and_utc();
+            Some(ConfigVersion {
+                id: id.to_string(),
+                created_at,
+                size_bytes: entry.metadata().ok()?.len(),
+            })
+        })
+        .collect();
+    versions.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(versions)
+}
+
+pub fn read_config_version(path: &Path, id: &str) -> DomainResult<String> {
+    validate_backup_id(id)?;
+    std::fs::read_to_string(version_path(path, id)).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            DomainError::NotFound(format!("Config version {} not found", id))
+        } else {
+            DomainError::InternalError(format!("Failed to read config version {}: {}", id, e))
+        }
+    })
+}
+
+// The current file becomes a version itself, so a restore can be undone.
+pub fn restore_config_version(path: &Path, id: &str) -> DomainResult<()> {
+    let content = read_config_version(path, id)?;
+    toml::from_str::<PipedashConfig>(&content).map_err(|e| {
+        DomainError::InvalidConfig(format!("Config version {} doesn't parse: {}", id, e))
+    })?;
+
+    write_config(path, &content).map_err(|e| {
+        DomainError::InternalError(format!("Failed to restore config version {}: {}", id, e))
+    })?;
+    tracing::info!(version = %id, "Restored config.toml");
+    Ok(())
+}
+
+fn versions_dir(path: &Path) -> PathBuf {
+    path.parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(CONFIG_VERSIONS_DIR)
+}
+
+fn version_path(path: &Path, id: &str) -> PathBuf {
+    versions_dir(path).join(format!("config-{}.toml", id))
+}
+
+// config.toml can hold literal tokens and the vault password, so files
+// written from it are readable by the owner only.
+fn create_private(path: &Path) -> std::io::Result<std::fs::File> {
+    // The mode only applies to a new file, so a temp file left by a crashed
+    // write is replaced rather than reused.
+    if let Err(e) = std::fs::remove_file(path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            return Err(e);
+        }
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+fn save_version(path: &Path, content: &str) -> std::io::Result<()> {
+    std::fs::create_dir_all(versions_dir(path))?;
+
+    let mut now = Utc::now();
+    let mut version = version_path(path, &now.format(VERSION_ID_FORMAT).to_string());
+    // Two writes within the same millisecond.
+    while version.exists() {
+        now += chrono::Duration::milliseconds(1);
+        version = version_path(path, &now.format(VERSION_ID_FORMAT).to_string());
+    }
+    create_private(&version)?.write_all(content.as_bytes())?;
+
+    if let Ok(versions) = list_config_versions(path) {
+        for stale in versions.iter().skip(MAX_CONFIG_VERSIONS) {
+            let _ = std::fs::remove_file(version_path(path, &stale.id));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_keeps_previous_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        write_config(&path, "[general]\nmetrics_enabled = true\n").unwrap();
+        assert!(list_config_versions(&path).unwrap().is_empty());
+
+        write_config(&path, "[general]\nmetrics_enabled = false\n").unwrap();
+        write_config(&path, "[general]\nmetrics_enabled = false\n").unwrap();
+        let versions = list_config_versions(&path).unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(
+            read_config_version(&path, &versions[0].id).unwrap(),
+            "[general]\nmetrics_enabled = true\n"
+        );
+        assert!(!dir.path().join(".config.toml.tmp").exists());
+
+        restore_config_version(&path, &versions[0].id).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "[general]\nmetrics_enabled = true\n"
+        );
+        assert_eq!(list_config_versions(&path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_versions_are_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        for i in 0..MAX_CONFIG_VERSIONS + 3 {
+            write_config(&path, &format!("# edit {}\n", i)).unwrap();
+        }
+
+        let versions = list_config_versions(&path).unwrap();
+        assert_eq!(versions.len(), MAX_CONFIG_VERSIONS);
+        assert_eq!(
+            read_config_version(&path, &versions[0].id).unwrap(),
+            format!("# edit {}\n", MAX_CONFIG_VERSIONS + 1)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_keeps_file_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        write_config(&path, "# first\n").unwrap();
+        assert_eq!(mode(&path), 0o600);
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+        write_config(&path, "# second\n").unwrap();
+        assert_eq!(mode(&path), 0o640);
+
+        let versions = list_config_versions(&path).unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(mode(&version_path(&path, &versions[0].id)), 0o600);
+    }
+
+    #[test]
+    fn test_restore_rejects_bad_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        assert!(matches!(
+            restore_config_version(&path, "../config"),
+            Err(DomainError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            restore_config_version(&path, "20240101-000000-000"),
+            Err(DomainError::NotFound(_))
+        ));
+    }
+}
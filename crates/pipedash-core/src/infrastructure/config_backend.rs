@@ -8,8 +8,17 @@ use serde::{
 
 use crate::domain::{
     DomainResult,
+    MaintenanceWindow,
+    NotificationChannel,
+    PinnedPipeline,
+    PipelineDependency,
+    PipelineGroup,
+    PipelineRefreshSetting,
     ProviderConfig,
+    SavedView,
+    TriggerPreset,
 };
+use crate::infrastructure::database::SqliteWriter;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredPermissions {
@@ -23,6 +32,8 @@ pub struct ConfigExport {
     pub providers: Vec<ProviderConfig>,
     pub table_preferences: HashMap<String, String>,
     pub permissions: HashMap<i64, StoredPermissions>,
+    #[serde(default)]
+    pub pinned_pipelines: Vec<PinnedPipeline>,
 }
 
 impl Default for ConfigExport {
@@ -32,12 +43,19 @@ impl Default for ConfigExport {
             providers: Vec::new(),
             table_preferences: HashMap::new(),
             permissions: HashMap::new(),
+            pinned_pipelines: Vec::new(),
         }
     }
 }
 
 #[async_trait]
 pub trait ConfigBackend: Send + Sync {
+    // The writer a SQLite backend queues its writes on. A `Repository` over
+    // the same database uses it too, so there is one writer per database.
+    fn sqlite_writer(&self) -> Option<SqliteWriter> {
+        None
+    }
+
     async fn list_providers(&self) -> DomainResult<Vec<ProviderConfig>>;
 
     async fn get_provider(&self, id: i64) -> DomainResult<Option<ProviderConfig>>;
@@ -56,6 +74,75 @@ pub trait ConfigBackend: Send + Sync {
         &self, provider_id: i64, table_id: &str, preferences_json: &str,
     ) -> DomainResult<()>;
 
+    async fn list_pipeline_groups(&self) -> DomainResult<Vec<PipelineGroup>>;
+
+    async fn create_pipeline_group(&self, name: &str) -> DomainResult<i64>;
+
+    async fn delete_pipeline_group(&self, id: i64) -> DomainResult<()>;
+
+    async fn add_pipeline_to_group(&self, group_id: i64, pipeline_id: &str) -> DomainResult<()>;
+
+    async fn remove_pipeline_from_group(
+        &self, group_id: i64, pipeline_id: &str,
+    ) -> DomainResult<()>;
+
+    async fn list_saved_views(&self) -> DomainResult<Vec<SavedView>>;
+
+    async fn save_view(&self, name: &str, query_json: &str) -> DomainResult<i64>;
+
+    async fn delete_saved_view(&self, id: i64) -> DomainResult<()>;
+
+    async fn list_trigger_presets(&self, pipeline_id: &str) -> DomainResult<Vec<TriggerPreset>>;
+
+    async fn save_trigger_preset(
+        &self, pipeline_id: &str, name: &str, branch: Option<&str>, parameters_json: &str,
+    ) -> DomainResult<i64>;
+
+    async fn delete_trigger_preset(&self, id: i64) -> DomainResult<()>;
+
+    async fn list_pipeline_dependencies(&self) -> DomainResult<Vec<PipelineDependency>>;
+
+    async fn save_pipeline_dependency(
+        &self, upstream_id: &str, downstream_id: &str, trigger_on: &str, inputs_json: &str,
+        created_by_role: &str,
+    ) -> DomainResult<i64>;
+
+    async fn delete_pipeline_dependency(&self, id: i64) -> DomainResult<()>;
+
+    async fn list_notification_channels(&self) -> DomainResult<Vec<NotificationChannel>>;
+
+    async fn save_notification_channel(
+        &self, name: &str, kind: &str, url: &str, enabled: bool, rules_json: &str,
+        suppression_json: &str,
+    ) -> DomainResult<i64>;
+
+    async fn delete_notification_channel(&self, id: i64) -> DomainResult<()>;
+
+    async fn list_pipeline_refresh_settings(&self) -> DomainResult<Vec<PipelineRefreshSetting>>;
+
+    async fn save_pipeline_refresh_setting(
+        &self, pipeline_id: &str, refresh_interval_secs: Option<i64>, snoozed: bool,
+    ) -> DomainResult<()>;
+
+    async fn delete_pipeline_refresh_setting(&self, pipeline_id: &str) -> DomainResult<()>;
+
+    // Pipelines whose triggers need approval.
+    async fn list_approval_rules(&self) -> DomainResult<Vec<String>>;
+
+    async fn set_approval_rule(&self, pipeline_id: &str, required: bool) -> DomainResult<()>;
+
+    async fn list_maintenance_windows(&self) -> DomainResult<Vec<MaintenanceWindow>>;
+
+    // Saving under an existing name replaces that window.
+    async fn save_maintenance_window(&self, name: &str, window_json: &str) -> DomainResult<i64>;
+
+    async fn delete_maintenance_window(&self, id: i64) -> DomainResult<()>;
+
+    // Includes unpinned tombstones.
+    async fn list_pinned_pipelines(&self) -> DomainResult<Vec<PinnedPipeline>>;
+
+    async fn save_pinned_pipeline(&self, pin: &PinnedPipeline) -> DomainResult<()>;
+
     async fn store_permissions(
         &self, provider_id: i64, permissions: &StoredPermissions,
     ) -> DomainResult<()>;
@@ -0,0 +1,72 @@
+use hmac::{
+    Hmac,
+    Mac,
+};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hmac(key: &[u8], message: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac
+}
+
+pub(crate) fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    hmac(key, message).finalize().into_bytes().into()
+}
+
+// Checks a signature over `message` in constant time.
+pub(crate) fn verify_hmac_sha256(key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    hmac(key, message).verify_slice(signature).is_ok()
+}
+
+// For shared secrets sent as-is, like GitLab's webhook token. Only the
+// length is compared in variable time.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc4231() {
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            to_hex(&mac),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let long_key = [0xaa; 131];
+        let mac = hmac_sha256(
+            &long_key,
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        assert_eq!(
+            to_hex(&mac),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_verify_hmac_sha256() {
+        let mac = hmac_sha256(b"key", b"message");
+        assert!(verify_hmac_sha256(b"key", b"message", &mac));
+        assert!(!verify_hmac_sha256(b"key", b"other message", &mac));
+        assert!(!verify_hmac_sha256(b"key", b"message", &mac[..16]));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"token2"));
+    }
+}
@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+
+use aes_gcm::{
+    aead::{
+        Aead,
+        KeyInit,
+    },
+    Aes256Gcm,
+    Nonce,
+};
+use base64::{
+    engine::general_purpose::STANDARD as BASE64,
+    Engine as _,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::domain::{
+    DomainError,
+    DomainResult,
+};
+
+const CACHE_KDF_SALT: &[u8] = b"pipedash-cache-encrypt-v1";
+const NONCE_LEN: usize = 12;
+
+// Every sealed payload starts with this; plain run and pipeline JSON never
+// does.
+pub(crate) const SEALED_PREFIX: &str = "{\"$sealed\":";
+
+// Sealed payloads are still JSON, so the SQL over these columns (the FTS
+// triggers, the postgres `::jsonb` indexes) keeps working and just finds no
+// fields. The commit SHA stays in the clear so commit lookups still match.
+#[derive(Serialize, Deserialize)]
+struct SealedPayload {
+    #[serde(rename = "$sealed")]
+    sealed: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    commit_sha: Option<String>,
+}
+
+// Encrypts the payload columns of the pipeline and run history caches with a
+// key derived from the vault password.
+pub struct CacheCipher {
+    cipher: Aes256Gcm,
+}
+
+impl CacheCipher {
+    // The key derivation is deliberately slow, so async callers run it on the
+    // blocking pool instead of stalling a runtime worker.
+    pub async fn derive(password: &str) -> DomainResult<Self> {
+        let password = password.to_string();
+        tokio::task::spawn_blocking(move || Self::from_vault_password(&password))
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Failed to derive cache key: {}", e)))
+    }
+
+    pub fn from_vault_password(password: &str) -> Self {
+        use argon2::{
+            Argon2,
+            ParamsBuilder,
+        };
+
+        let mut key = [0u8; 32];
+
+        let params = ParamsBuilder::new()
+            .m_cost(65536)
+            .t_cost(3)
+            .p_cost(1)
+            .output_len(32)
+            .build()
+            .expect("Invalid Argon2 parameters");
+
+        let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+        argon2
+            .hash_password_into(password.as_bytes(), CACHE_KDF_SALT, &mut key)
+            .expect("Failed to derive cache encryption key");
+
+        Self::from_key(key)
+    }
+
+    fn from_key(key: [u8; 32]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(&key.into()),
+        }
+    }
+
+    pub fn is_sealed(stored: &str) -> bool {
+        stored.starts_with(SEALED_PREFIX)
+    }
+
+    pub fn seal(&self, payload: &str, commit_sha: Option<&str>) -> DomainResult<String> {
+        let nonce_bytes: [u8; NONCE_LEN] = rand::random();
+        let ciphertext = self
+            .cipher
+            .encrypt(&Nonce::from(nonce_bytes), payload.as_bytes())
+            .map_err(|e| DomainError::InternalError(format!("Encryption failed: {}", e)))?;
+
+        let mut sealed = nonce_bytes.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+
+        serde_json::to_string(&SealedPayload {
+            sealed: BASE64.encode(sealed),
+            commit_sha: commit_sha.map(str::to_string),
+        })
+        .map_err(|e| DomainError::InternalError(e.to_string()))
+    }
+
+    // Payloads written before encryption was turned on pass through as-is.
+    pub fn open<'a>(&self, stored: &'a str) -> DomainResult<Cow<'a, str>> {
+        if !Self::is_sealed(stored) {
+            return Ok(Cow::Borrowed(stored));
+        }
+
+        let payload: SealedPayload = serde_json::from_str(stored)
+            .map_err(|e| DomainError::DatabaseError(format!("Invalid sealed payload: {}", e)))?;
+        let bytes = BASE64
+            .decode(&payload.sealed)
+            .map_err(|e| DomainError::DatabaseError(format!("Invalid sealed payload: {}", e)))?;
+        if bytes.len() < NONCE_LEN {
+            return Err(DomainError::DatabaseError(
+                "Invalid sealed payload: too short".into(),
+            ));
+        }
+
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let nonce: [u8; NONCE_LEN] = nonce
+            .try_into()
+            .map_err(|_| DomainError::DatabaseError("Invalid nonce".into()))?;
+        let plaintext = self
+            .cipher
+            .decrypt(&Nonce::from(nonce), ciphertext)
+            .map_err(|_| {
+                DomainError::AuthenticationFailed(
+                    "Cache decryption failed - wrong vault password?".into(),
+                )
+            })?;
+
+        String::from_utf8(plaintext)
+            .map(Cow::Owned)
+            .map_err(|e| DomainError::DatabaseError(format!("Invalid UTF-8 in payload: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_round_trip() {
+        let cipher = CacheCipher::from_key([7u8; 32]);
+        let payload = r#"{"id":"run-1","commit_message":"rotate the prod secret"}"#;
+
+        let sealed = cipher.seal(payload, Some("abc123")).unwrap();
+        assert!(CacheCipher::is_sealed(&sealed));
+        assert!(!sealed.contains("prod secret"));
+
+        let value: serde_json::Value = serde_json::from_str(&sealed).unwrap();
+        assert_eq!(value["commit_sha"], "abc123");
+
+        assert_eq!(cipher.open(&sealed).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_plain_payload_passes_through() {
+        let cipher = CacheCipher::from_key([7u8; 32]);
+        let payload = r#"{"id":"run-1"}"#;
+
+        assert!(!CacheCipher::is_sealed(payload));
+        assert!(matches!(cipher.open(payload).unwrap(), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_wrong_key_fails() {
+        let sealed = CacheCipher::from_key([7u8; 32]).seal("{}", None).unwrap();
+
+        assert!(CacheCipher::from_key([8u8; 32]).open(&sealed).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use sqlx::postgres::{
+    PgListener,
+    PgPool,
+};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::event::{
+    CacheInvalidationReason,
+    CoreEvent,
+    EventBus,
+};
+
+pub const CACHE_INVALIDATION_CHANNEL: &str = "pipedash_cache_invalidation";
+
+const LISTENER_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CacheInvalidation {
+    Pipelines { provider_id: Option<i64> },
+    RunHistory { pipeline_id: Option<String> },
+}
+
+impl CacheInvalidation {
+    fn into_event(self) -> CoreEvent {
+        match self {
+            CacheInvalidation::Pipelines { provider_id } => CoreEvent::PipelineCacheInvalidated {
+                provider_id,
+                reason: CacheInvalidationReason::Remote,
+            },
+            CacheInvalidation::RunHistory { pipeline_id } => {
+                CoreEvent::RunHistoryCacheInvalidated { pipeline_id }
+            }
+        }
+    }
+}
+
+// Payload sent over NOTIFY. `origin` identifies the publishing instance so it
+// can skip its own notifications; it already emitted the local events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheInvalidationMessage {
+    pub origin: String,
+    #[serde(flatten)]
+    pub invalidation: CacheInvalidation,
+}
+
+pub fn new_instance_id() -> String {
+    format!("{:016x}", rand::random::<u64>())
+}
+
+// Forwards cache invalidations published by other instances sharing the same
+// Postgres database to the local event bus.
+pub struct CacheInvalidationListener {
+    pool: PgPool,
+    instance_id: String,
+    event_bus: Arc<dyn EventBus>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl CacheInvalidationListener {
+    pub fn new(pool: PgPool, instance_id: String, event_bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            pool,
+            instance_id,
+            event_bus,
+            handle: Mutex::new(None),
+        }
+    }
+
+    pub async fn start(&self) {
+        let mut handle = self.handle.lock().await;
+        if handle.is_some() {
+            return;
+        }
+
+        let pool = self.pool.clone();
+        let instance_id = self.instance_id.clone();
+        let event_bus = Arc::clone(&self.event_bus);
+
+        *handle = Some(tokio::spawn(async move {
+            loop {
+                if let Err(e) = listen(&pool, &instance_id, event_bus.as_ref()).await {
+                    tracing::warn!(error = %e, "Cache invalidation listener failed, retrying");
+                }
+                tokio::time::sleep(LISTENER_RETRY_DELAY).await;
+            }
+        }));
+    }
+
+    pub async fn stop(&self) {
+        if let Some(handle) = self.handle.lock().await.take() {
+            handle.abort();
+        }
+    }
+}
+
+async fn listen(
+    pool: &PgPool, instance_id: &str, event_bus: &dyn EventBus,
+) -> Result<(), sqlx::Error> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(CACHE_INVALIDATION_CHANNEL).await?;
+    tracing::info!(
+        channel = CACHE_INVALIDATION_CHANNEL,
+        "Listening for cache invalidations"
+    );
+
+    loop {
+        // `None` means the connection dropped and was re-established, so any
+        // notifications sent in between are lost. Invalidate everything.
+        let Some(notification) = listener.try_recv().await? else {
+            tracing::debug!("Cache invalidation listener reconnected");
+            event_bus
+                .emit(CacheInvalidation::Pipelines { provider_id: None }.into_event())
+                .await;
+            event_bus
+                .emit(CacheInvalidation::RunHistory { pipeline_id: None }.into_event())
+                .await;
+            continue;
+        };
+
+        let message: CacheInvalidationMessage = match serde_json::from_str(notification.payload()) {
+            Ok(message) => message,
+            Err(e) => {
+                tracing::warn!(error = %e, "Ignoring malformed cache invalidation");
+                continue;
+            }
+        };
+
+        if message.origin == instance_id {
+            continue;
+        }
+
+        event_bus.emit(message.invalidation.into_event()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_round_trip() {
+        let message = CacheInvalidationMessage {
+            origin: "abc".to_string(),
+            invalidation: CacheInvalidation::Pipelines {
+                provider_id: Some(3),
+            },
+        };
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(
+            json,
+            r#"{"origin":"abc","kind":"pipelines","provider_id":3}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<CacheInvalidationMessage>(&json).unwrap(),
+            message
+        );
+    }
+
+    #[test]
+    fn test_invalidation_events() {
+        let event = CacheInvalidation::Pipelines { provider_id: None }.into_event();
+        assert_eq!(event.event_name(), "pipeline-cache-invalidated");
+        assert_eq!(event.to_json_payload()["reason"], "remote");
+
+        let event = CacheInvalidation::RunHistory {
+            pipeline_id: Some("p".to_string()),
+        }
+        .into_event();
+        assert_eq!(event.event_name(), "run-history-cache-invalidated");
+    }
+}
@@ -0,0 +1,90 @@
+// Repository SQL is written once, with `?` placeholders and `NOW()`, and
+// rewritten here for the backend it runs on. Only dialect differences that
+// the shared statements need are covered; anything backend-specific (full
+// text search, casts) stays in its own match arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Sqlite,
+    Postgres,
+}
+
+impl Dialect {
+    pub fn placeholder(self, index: usize) -> String {
+        match self {
+            Dialect::Sqlite => "?".to_string(),
+            Dialect::Postgres => format!("${}", index),
+        }
+    }
+
+    pub fn now(self) -> &'static str {
+        match self {
+            Dialect::Sqlite => "datetime('now')",
+            Dialect::Postgres => "NOW()",
+        }
+    }
+
+    // Numbers `?` placeholders left to right for Postgres and swaps `NOW()`
+    // for SQLite. Quoted literals and identifiers are copied as they are.
+    pub fn sql(self, sql: &str) -> String {
+        let mut out = String::with_capacity(sql.len() + 16);
+        let mut index = 0;
+        let mut quote: Option<char> = None;
+        let mut prev: Option<char> = None;
+        let mut rest = sql;
+
+        while let Some(c) = rest.chars().next() {
+            let len = c.len_utf8();
+
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '\'' || c == '"' => quote = Some(c),
+                None if c == '?' => {
+                    index += 1;
+                    out.push_str(&self.placeholder(index));
+                    prev = Some(c);
+                    rest = &rest[len..];
+                    continue;
+                }
+                None if rest.starts_with("NOW()")
+                    && !prev.is_some_and(|p| p.is_alphanumeric() || p == '_') =>
+                {
+                    out.push_str(self.now());
+                    prev = Some(')');
+                    rest = &rest["NOW()".len()..];
+                    continue;
+                }
+                None => {}
+            }
+
+            out.push(c);
+            prev = Some(c);
+            rest = &rest[len..];
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sql_rewrites_placeholders_and_now() {
+        let sql = "UPDATE t SET a = ?, b = NOW(), c = 'why?' WHERE id = ? AND x = KNOW()";
+
+        assert_eq!(
+            Dialect::Postgres.sql(sql),
+            "UPDATE t SET a = $1, b = NOW(), c = 'why?' WHERE id = $2 AND x = KNOW()"
+        );
+        assert_eq!(
+            Dialect::Sqlite.sql(sql),
+            "UPDATE t SET a = ?, b = datetime('now'), c = 'why?' WHERE id = ? AND x = KNOW()"
+        );
+        assert_eq!(
+            Dialect::Sqlite.sql("SELECT 'NOW()', \"?\""),
+            "SELECT 'NOW()', \"?\""
+        );
+    }
+}
@@ -11,9 +11,11 @@ use crate::domain::{
     AggregatedMetrics,
     AggregationPeriod,
     AggregationType,
+    AnomalyKind,
     DomainError,
     DomainResult,
     GlobalMetricsConfig,
+    MetricAnomaly,
     MetricEntry,
     MetricType,
     MetricsConfig,
@@ -21,8 +23,12 @@ use crate::domain::{
     MetricsQuery,
     MetricsStats,
     PipelineMetricsStats,
+    RollupGranularity,
 };
 
+// pipeline_id, kind, baseline, observed, z_score, run_number, detected_at
+type AnomalyRow<T> = (String, String, f64, f64, f64, i64, T);
+
 pub struct MetricsRepository {
     pool: DatabasePool,
 }
@@ -39,35 +45,50 @@ impl MetricsRepository {
     }
 
     fn placeholder(&self, index: usize) -> String {
-        match &self.pool {
-            DatabasePool::Sqlite(_) => "?".to_string(),
-            DatabasePool::Postgres(_) => format!("${}", index),
-        }
+        self.pool.dialect().placeholder(index)
     }
 
-    fn datetime_now(&self) -> &'static str {
-        match &self.pool {
-            DatabasePool::Sqlite(_) => "datetime('now')",
-            DatabasePool::Postgres(_) => "NOW()",
+    // Shared SQL rewritten for the metrics database, see `Dialect::sql`.
+    fn sql(&self, sql: &str) -> String {
+        self.pool.dialect().sql(sql)
+    }
+
+    // An empty list means the label filter matched no pipelines, so nothing may
+    // match rather than falling back to all pipelines.
+    fn push_pipeline_ids_filter(
+        &self, sql: &mut String, pipeline_ids: &[String], param_idx: &mut usize,
+        string_params: &mut Vec<(usize, String)>,
+    ) {
+        if pipeline_ids.is_empty() {
+            sql.push_str(" AND 1 = 0");
+            return;
+        }
+
+        let mut placeholders = Vec::with_capacity(pipeline_ids.len());
+        for pipeline_id in pipeline_ids {
+            *param_idx += 1;
+            placeholders.push(self.placeholder(*param_idx));
+            string_params.push((*param_idx, pipeline_id.clone()));
         }
+        sql.push_str(&format!(
+            " AND pipeline_id IN ({})",
+            placeholders.join(", ")
+        ));
     }
 
     pub async fn count_metrics_for_pipeline(&self, pipeline_id: &str) -> DomainResult<i64> {
+        let sql = self.sql("SELECT COUNT(*) FROM pipeline_metrics WHERE pipeline_id = ?");
         match &self.pool {
-            DatabasePool::Sqlite(p) => sqlx::query_scalar::<_, i64>(
-                "SELECT COUNT(*) FROM pipeline_metrics WHERE pipeline_id = ?",
-            )
-            .bind(pipeline_id)
-            .fetch_one(p)
-            .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string())),
-            DatabasePool::Postgres(p) => sqlx::query_scalar::<_, i64>(
-                "SELECT COUNT(*) FROM pipeline_metrics WHERE pipeline_id = $1",
-            )
-            .bind(pipeline_id)
-            .fetch_one(p)
-            .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string())),
+            DatabasePool::Sqlite(p) => sqlx::query_scalar::<_, i64>(&sql)
+                .bind(pipeline_id)
+                .fetch_one(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string())),
+            DatabasePool::Postgres(p) => sqlx::query_scalar::<_, i64>(&sql)
+                .bind(pipeline_id)
+                .fetch_one(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string())),
         }
     }
 
@@ -143,11 +164,8 @@ impl MetricsRepository {
     pub async fn update_global_config(
         &self, enabled: bool, default_retention_days: i64,
     ) -> DomainResult<()> {
-        let sql = format!(
-            "UPDATE metrics_global_config SET enabled = {}, default_retention_days = {}, updated_at = {} WHERE id = 1",
-            self.placeholder(1),
-            self.placeholder(2),
-            self.datetime_now()
+        let sql = self.sql(
+            "UPDATE metrics_global_config SET enabled = ?, default_retention_days = ?, updated_at = NOW() WHERE id = 1",
         );
 
         match &self.pool {
@@ -210,44 +228,38 @@ impl MetricsRepository {
     pub async fn upsert_pipeline_config(
         &self, pipeline_id: &str, enabled: bool, retention_days: i64,
     ) -> DomainResult<()> {
+        let sql = self.sql(
+            "INSERT INTO metrics_config (pipeline_id, enabled, retention_days, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(pipeline_id) DO UPDATE SET
+                 enabled = excluded.enabled,
+                 retention_days = excluded.retention_days,
+                 updated_at = excluded.updated_at",
+        );
         match &self.pool {
             DatabasePool::Sqlite(p) => {
                 let now = Utc::now().to_rfc3339();
-                sqlx::query(
-                    "INSERT INTO metrics_config (pipeline_id, enabled, retention_days, created_at, updated_at)
-                     VALUES (?, ?, ?, ?, ?)
-                     ON CONFLICT(pipeline_id) DO UPDATE SET
-                         enabled = excluded.enabled,
-                         retention_days = excluded.retention_days,
-                         updated_at = excluded.updated_at",
-                )
-                .bind(pipeline_id)
-                .bind(enabled as i64)
-                .bind(retention_days)
-                .bind(&now)
-                .bind(&now)
-                .execute(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                sqlx::query(&sql)
+                    .bind(pipeline_id)
+                    .bind(enabled as i64)
+                    .bind(retention_days)
+                    .bind(&now)
+                    .bind(&now)
+                    .execute(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
             }
             DatabasePool::Postgres(p) => {
                 let now = Utc::now();
-                sqlx::query(
-                    "INSERT INTO metrics_config (pipeline_id, enabled, retention_days, created_at, updated_at)
-                     VALUES ($1, $2, $3, $4, $5)
-                     ON CONFLICT(pipeline_id) DO UPDATE SET
-                         enabled = excluded.enabled,
-                         retention_days = excluded.retention_days,
-                         updated_at = excluded.updated_at",
-                )
-                .bind(pipeline_id)
-                .bind(enabled)
-                .bind(retention_days)
-                .bind(now)
-                .bind(now)
-                .execute(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                sqlx::query(&sql)
+                    .bind(pipeline_id)
+                    .bind(enabled)
+                    .bind(retention_days)
+                    .bind(now)
+                    .bind(now)
+                    .execute(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
             }
         }
 
@@ -282,42 +294,36 @@ impl MetricsRepository {
     pub async fn update_last_processed_run(
         &self, pipeline_id: &str, run_number: i64,
     ) -> DomainResult<()> {
+        let sql = self.sql(
+            "INSERT INTO metrics_processing_state (pipeline_id, last_processed_run_number, last_processed_at, updated_at)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(pipeline_id) DO UPDATE SET
+                 last_processed_run_number = excluded.last_processed_run_number,
+                 last_processed_at = excluded.last_processed_at,
+                 updated_at = excluded.updated_at",
+        );
         match &self.pool {
             DatabasePool::Sqlite(p) => {
                 let now = Utc::now().to_rfc3339();
-                sqlx::query(
-                    "INSERT INTO metrics_processing_state (pipeline_id, last_processed_run_number, last_processed_at, updated_at)
-                     VALUES (?, ?, ?, ?)
-                     ON CONFLICT(pipeline_id) DO UPDATE SET
-                         last_processed_run_number = excluded.last_processed_run_number,
-                         last_processed_at = excluded.last_processed_at,
-                         updated_at = excluded.updated_at"
-                )
-                .bind(pipeline_id)
-                .bind(run_number)
-                .bind(&now)
-                .bind(&now)
-                .execute(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                sqlx::query(&sql)
+                    .bind(pipeline_id)
+                    .bind(run_number)
+                    .bind(&now)
+                    .bind(&now)
+                    .execute(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
             }
             DatabasePool::Postgres(p) => {
                 let now = Utc::now();
-                sqlx::query(
-                    "INSERT INTO metrics_processing_state (pipeline_id, last_processed_run_number, last_processed_at, updated_at)
-                     VALUES ($1, $2, $3, $4)
-                     ON CONFLICT(pipeline_id) DO UPDATE SET
-                         last_processed_run_number = excluded.last_processed_run_number,
-                         last_processed_at = excluded.last_processed_at,
-                         updated_at = excluded.updated_at"
-                )
-                .bind(pipeline_id)
-                .bind(run_number)
-                .bind(now)
-                .bind(now)
-                .execute(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                sqlx::query(&sql)
+                    .bind(pipeline_id)
+                    .bind(run_number)
+                    .bind(now)
+                    .bind(now)
+                    .execute(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
             }
         }
 
@@ -325,10 +331,7 @@ impl MetricsRepository {
     }
 
     pub async fn reset_processing_state(&self, pipeline_id: &str) -> DomainResult<()> {
-        let sql = format!(
-            "DELETE FROM metrics_processing_state WHERE pipeline_id = {}",
-            self.placeholder(1)
-        );
+        let sql = self.sql("DELETE FROM metrics_processing_state WHERE pipeline_id = ?");
 
         match &self.pool {
             DatabasePool::Sqlite(p) => {
@@ -570,6 +573,20 @@ impl MetricsRepository {
     }
 
     pub async fn query_metrics(&self, query: MetricsQuery) -> DomainResult<Vec<MetricEntry>> {
+        self.select_metrics(query, None).await
+    }
+
+    // Oldest id first, for paging through every matching row.
+    pub async fn get_metrics_after(
+        &self, mut query: MetricsQuery, after_id: i64, limit: usize,
+    ) -> DomainResult<Vec<MetricEntry>> {
+        query.limit = Some(limit);
+        self.select_metrics(query, Some(after_id)).await
+    }
+
+    async fn select_metrics(
+        &self, query: MetricsQuery, after_id: Option<i64>,
+    ) -> DomainResult<Vec<MetricEntry>> {
         let mut param_idx = 0;
         let base_select = match &self.pool {
             DatabasePool::Sqlite(_) => "SELECT id, pipeline_id, run_number, timestamp, metric_type, value, metadata_json, created_at, run_hash FROM pipeline_metrics WHERE 1=1",
@@ -589,6 +606,15 @@ impl MetricsRepository {
             string_params.push((param_idx, pipeline_id.clone()));
         }
 
+        if let Some(pipeline_ids) = &query.pipeline_ids {
+            self.push_pipeline_ids_filter(
+                &mut sql,
+                pipeline_ids,
+                &mut param_idx,
+                &mut string_params,
+            );
+        }
+
         if let Some(metric_type) = &query.metric_type {
             param_idx += 1;
             sql.push_str(&format!(
@@ -616,7 +642,10 @@ impl MetricsRepository {
             timestamp_params.push((param_idx, *end_date));
         }
 
-        sql.push_str(" ORDER BY timestamp DESC");
+        match after_id {
+            Some(after_id) => sql.push_str(&format!(" AND id > {} ORDER BY id ASC", after_id)),
+            None => sql.push_str(" ORDER BY timestamp DESC"),
+        }
 
         if let Some(limit) = query.limit {
             sql.push_str(&format!(" LIMIT {}", limit));
@@ -677,39 +706,64 @@ impl MetricsRepository {
         }
     }
 
-    pub async fn query_aggregated_metrics(
-        &self, query: MetricsQuery,
-    ) -> DomainResult<AggregatedMetrics> {
-        let metric_type = query
-            .metric_type
-            .ok_or_else(|| DomainError::InvalidConfig("metric_type is required".to_string()))?;
-        let aggregation_period = query.aggregation_period.ok_or_else(|| {
-            DomainError::InvalidConfig("aggregation_period is required".to_string())
-        })?;
-        let aggregation_type = query.aggregation_type.unwrap_or(AggregationType::Avg);
-
-        let period_select = match (&self.pool, aggregation_period) {
+    fn period_select(&self, aggregation_period: AggregationPeriod, column: &str) -> String {
+        match (&self.pool, aggregation_period) {
             (DatabasePool::Sqlite(_), AggregationPeriod::Hourly) => {
-                "datetime(strftime('%Y-%m-%d %H:00:00', timestamp))"
+                format!("datetime(strftime('%Y-%m-%d %H:00:00', {}))", column)
             }
-            (DatabasePool::Sqlite(_), AggregationPeriod::Daily) => "date(timestamp)",
+            (DatabasePool::Sqlite(_), AggregationPeriod::Daily) => format!("date({})", column),
             (DatabasePool::Sqlite(_), AggregationPeriod::Weekly) => {
-                "date(timestamp, 'weekday 0', '-6 days')"
+                format!("date({}, 'weekday 0', '-6 days')", column)
             }
             (DatabasePool::Sqlite(_), AggregationPeriod::Monthly) => {
-                "date(timestamp, 'start of month')"
+                format!("date({}, 'start of month')", column)
             }
             (DatabasePool::Postgres(_), AggregationPeriod::Hourly) => {
-                "date_trunc('hour', timestamp)"
+                format!("date_trunc('hour', {})", column)
+            }
+            (DatabasePool::Postgres(_), AggregationPeriod::Daily) => {
+                format!("date_trunc('day', {})", column)
             }
-            (DatabasePool::Postgres(_), AggregationPeriod::Daily) => "date_trunc('day', timestamp)",
             (DatabasePool::Postgres(_), AggregationPeriod::Weekly) => {
-                "date_trunc('week', timestamp)"
+                format!("date_trunc('week', {})", column)
             }
             (DatabasePool::Postgres(_), AggregationPeriod::Monthly) => {
-                "date_trunc('month', timestamp)"
+                format!("date_trunc('month', {})", column)
             }
-        };
+        }
+    }
+
+    pub async fn query_aggregated_metrics(
+        &self, query: MetricsQuery,
+    ) -> DomainResult<AggregatedMetrics> {
+        let metric_type = query
+            .metric_type
+            .ok_or_else(|| DomainError::InvalidConfig("metric_type is required".to_string()))?;
+        let aggregation_period = query.aggregation_period.ok_or_else(|| {
+            DomainError::InvalidConfig("aggregation_period is required".to_string())
+        })?;
+        let aggregation_type = query.aggregation_type.unwrap_or(AggregationType::Avg);
+
+        if let Some(granularity) = RollupGranularity::for_query(&query) {
+            let metrics = self
+                .query_rollup_metrics(
+                    &query,
+                    metric_type,
+                    aggregation_period,
+                    aggregation_type,
+                    granularity,
+                )
+                .await?;
+
+            return Ok(AggregatedMetrics {
+                total_count: metrics.len(),
+                metrics,
+                metric_type,
+                aggregation_period,
+            });
+        }
+
+        let period_select = self.period_select(aggregation_period, "timestamp");
 
         let use_percentile = matches!(
             aggregation_type,
@@ -722,7 +776,7 @@ impl MetricsRepository {
                 metric_type,
                 aggregation_period,
                 aggregation_type,
-                period_select,
+                &period_select,
             )
             .await?
         } else {
@@ -766,6 +820,15 @@ impl MetricsRepository {
                 string_params.push((param_idx, pipeline_id.clone()));
             }
 
+            if let Some(pipeline_ids) = &query.pipeline_ids {
+                self.push_pipeline_ids_filter(
+                    &mut sql,
+                    pipeline_ids,
+                    &mut param_idx,
+                    &mut string_params,
+                );
+            }
+
             if let Some(start_date) = &query.start_date {
                 param_idx += 1;
                 sql.push_str(&format!(
@@ -790,109 +853,427 @@ impl MetricsRepository {
                 sql.push_str(&format!(" LIMIT {}", limit));
             }
 
-            match &self.pool {
-                DatabasePool::Sqlite(p) => {
-                    let mut query_builder = sqlx::query(&sql);
-                    let mut all_params: Vec<(usize, String)> = string_params;
-                    for (idx, ts) in timestamp_params {
-                        all_params.push((idx, ts.to_rfc3339()));
-                    }
-                    all_params.sort_by_key(|(idx, _)| *idx);
-                    for (_, value) in all_params {
-                        query_builder = query_builder.bind(value);
-                    }
+            self.fetch_aggregated(&sql, string_params, timestamp_params, aggregation_period)
+                .await?
+        };
 
-                    let rows = query_builder
-                        .fetch_all(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let total_count = metrics.len();
 
-                    rows.iter()
-                        .map(|row| {
-                            let period_str: String = row
-                                .try_get(0)
-                                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            let timestamp =
-                                self.parse_period_timestamp(&period_str, aggregation_period);
-
-                            Ok(AggregatedMetric {
-                                timestamp,
-                                value: row
-                                    .try_get::<f64, _>(1)
-                                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                                count: row
-                                    .try_get(2)
-                                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                                min: row.try_get(3).ok(),
-                                max: row.try_get(4).ok(),
-                                avg: row
-                                    .try_get(5)
-                                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                            })
+        Ok(AggregatedMetrics {
+            metrics,
+            total_count,
+            metric_type,
+            aggregation_period,
+        })
+    }
+
+    async fn fetch_aggregated(
+        &self, sql: &str, string_params: Vec<(usize, String)>,
+        timestamp_params: Vec<(usize, DateTime<Utc>)>, aggregation_period: AggregationPeriod,
+    ) -> DomainResult<Vec<AggregatedMetric>> {
+        match &self.pool {
+            DatabasePool::Sqlite(p) => {
+                let mut query_builder = sqlx::query(sql);
+                let mut all_params: Vec<(usize, String)> = string_params;
+                for (idx, ts) in timestamp_params {
+                    all_params.push((idx, ts.to_rfc3339()));
+                }
+                all_params.sort_by_key(|(idx, _)| *idx);
+                for (_, value) in all_params {
+                    query_builder = query_builder.bind(value);
+                }
+
+                let rows = query_builder
+                    .fetch_all(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                rows.iter()
+                    .map(|row| {
+                        let period_str: String = row
+                            .try_get(0)
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                        let timestamp =
+                            self.parse_period_timestamp(&period_str, aggregation_period);
+
+                        Ok(AggregatedMetric {
+                            timestamp,
+                            value: row
+                                .try_get::<f64, _>(1)
+                                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                            count: row
+                                .try_get(2)
+                                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                            min: row.try_get(3).ok(),
+                            max: row.try_get(4).ok(),
+                            avg: row
+                                .try_get(5)
+                                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
                         })
-                        .collect::<DomainResult<Vec<_>>>()?
+                    })
+                    .collect::<DomainResult<Vec<_>>>()
+            }
+            DatabasePool::Postgres(p) => {
+                enum BindValue {
+                    Str(String),
+                    Timestamp(DateTime<Utc>),
                 }
-                DatabasePool::Postgres(p) => {
-                    enum BindValue {
-                        Str(String),
-                        Timestamp(DateTime<Utc>),
-                    }
-                    let mut all_params: Vec<(usize, BindValue)> = Vec::new();
-                    for (idx, s) in string_params {
-                        all_params.push((idx, BindValue::Str(s)));
-                    }
-                    for (idx, ts) in timestamp_params {
-                        all_params.push((idx, BindValue::Timestamp(ts)));
-                    }
-                    all_params.sort_by_key(|(idx, _)| *idx);
+                let mut all_params: Vec<(usize, BindValue)> = Vec::new();
+                for (idx, s) in string_params {
+                    all_params.push((idx, BindValue::Str(s)));
+                }
+                for (idx, ts) in timestamp_params {
+                    all_params.push((idx, BindValue::Timestamp(ts)));
+                }
+                all_params.sort_by_key(|(idx, _)| *idx);
 
-                    let mut query_builder = sqlx::query(&sql);
-                    for (_, value) in all_params {
-                        match value {
-                            BindValue::Str(s) => query_builder = query_builder.bind(s),
-                            BindValue::Timestamp(ts) => query_builder = query_builder.bind(ts),
-                        }
+                let mut query_builder = sqlx::query(sql);
+                for (_, value) in all_params {
+                    match value {
+                        BindValue::Str(s) => query_builder = query_builder.bind(s),
+                        BindValue::Timestamp(ts) => query_builder = query_builder.bind(ts),
                     }
+                }
 
-                    let rows = query_builder
-                        .fetch_all(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                let rows = query_builder
+                    .fetch_all(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-                    rows.iter()
-                        .map(|row| {
-                            let timestamp: DateTime<Utc> = row
-                                .try_get(0)
-                                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-
-                            Ok(AggregatedMetric {
-                                timestamp,
-                                value: row
-                                    .try_get::<f64, _>(1)
-                                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                                count: row
-                                    .try_get(2)
-                                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                                min: row.try_get(3).ok(),
-                                max: row.try_get(4).ok(),
-                                avg: row
-                                    .try_get(5)
-                                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                            })
+                rows.iter()
+                    .map(|row| {
+                        let timestamp: DateTime<Utc> = row
+                            .try_get(0)
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                        Ok(AggregatedMetric {
+                            timestamp,
+                            value: row
+                                .try_get::<f64, _>(1)
+                                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                            count: row
+                                .try_get(2)
+                                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                            min: row.try_get(3).ok(),
+                            max: row.try_get(4).ok(),
+                            avg: row
+                                .try_get(5)
+                                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
                         })
-                        .collect::<DomainResult<Vec<_>>>()?
+                    })
+                    .collect::<DomainResult<Vec<_>>>()
+            }
+        }
+    }
+
+    // Same shape as the raw aggregation, computed from rollup buckets. The
+    // date range is widened to whole buckets.
+    async fn query_rollup_metrics(
+        &self, query: &MetricsQuery, metric_type: MetricType,
+        aggregation_period: AggregationPeriod, aggregation_type: AggregationType,
+        granularity: RollupGranularity,
+    ) -> DomainResult<Vec<AggregatedMetric>> {
+        let (count_select, avg_select) = match &self.pool {
+            DatabasePool::Sqlite(_) => ("SUM(count)", "SUM(sum) / SUM(count)"),
+            DatabasePool::Postgres(_) => (
+                "SUM(count)::BIGINT",
+                "SUM(sum) / SUM(count)::DOUBLE PRECISION",
+            ),
+        };
+        let aggregation_select = match aggregation_type {
+            AggregationType::Avg => avg_select,
+            AggregationType::Sum => "SUM(sum)",
+            AggregationType::Min => "MIN(min)",
+            AggregationType::Max => "MAX(max)",
+            AggregationType::P95 | AggregationType::P99 => {
+                return Err(DomainError::InvalidConfig(
+                    "Percentiles cannot be computed from rollups".to_string(),
+                ))
+            }
+        };
+
+        let mut sql = format!(
+            "SELECT
+                {} as period,
+                {} as agg_value,
+                {} as count,
+                MIN(min) as min_value,
+                MAX(max) as max_value,
+                {} as avg_value
+             FROM pipeline_metrics_rollups
+             WHERE granularity = {} AND metric_type = {}",
+            self.period_select(aggregation_period, "bucket_start"),
+            aggregation_select,
+            count_select,
+            avg_select,
+            self.placeholder(1),
+            self.placeholder(2)
+        );
+        let mut param_idx = 2;
+        let mut string_params: Vec<(usize, String)> = vec![
+            (1, granularity.as_str().to_string()),
+            (2, metric_type.as_str().to_string()),
+        ];
+        let mut timestamp_params: Vec<(usize, DateTime<Utc>)> = Vec::new();
+
+        if let Some(pipeline_id) = &query.pipeline_id {
+            param_idx += 1;
+            sql.push_str(&format!(
+                " AND pipeline_id = {}",
+                self.placeholder(param_idx)
+            ));
+            string_params.push((param_idx, pipeline_id.clone()));
+        }
+
+        if let Some(pipeline_ids) = &query.pipeline_ids {
+            self.push_pipeline_ids_filter(
+                &mut sql,
+                pipeline_ids,
+                &mut param_idx,
+                &mut string_params,
+            );
+        }
+
+        if let Some(start_date) = &query.start_date {
+            param_idx += 1;
+            sql.push_str(&format!(
+                " AND bucket_start >= {}",
+                self.placeholder(param_idx)
+            ));
+            timestamp_params.push((param_idx, granularity.bucket_start(*start_date)));
+        }
+
+        if let Some(end_date) = &query.end_date {
+            param_idx += 1;
+            sql.push_str(&format!(
+                " AND bucket_start <= {}",
+                self.placeholder(param_idx)
+            ));
+            timestamp_params.push((param_idx, *end_date));
+        }
+
+        sql.push_str(" GROUP BY period ORDER BY period ASC");
+
+        if let Some(limit) = query.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        self.fetch_aggregated(&sql, string_params, timestamp_params, aggregation_period)
+            .await
+    }
+
+    // Folds raw rows inserted since the last run into the rollup tables. Every
+    // bucket from the day of the oldest new row onwards is rebuilt, so rows
+    // that arrive late or out of order are still counted exactly once.
+    pub async fn compact_rollups(&self) -> DomainResult<usize> {
+        let last_metric_id = self.get_rollup_watermark().await?;
+
+        let sql = self.sql("SELECT MAX(id), MIN(timestamp) FROM pipeline_metrics WHERE id > ?");
+        let pending: Option<(i64, DateTime<Utc>)> = match &self.pool {
+            DatabasePool::Sqlite(p) => {
+                let row: (Option<i64>, Option<String>) = sqlx::query_as(&sql)
+                    .bind(last_metric_id)
+                    .fetch_one(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                match row {
+                    (Some(max_id), Some(min_ts)) => {
+                        let min_ts = DateTime::parse_from_rfc3339(&min_ts)
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                        Some((max_id, min_ts))
+                    }
+                    _ => None,
+                }
+            }
+            DatabasePool::Postgres(p) => {
+                let row: (Option<i64>, Option<DateTime<Utc>>) = sqlx::query_as(&sql)
+                    .bind(last_metric_id)
+                    .fetch_one(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                match row {
+                    (Some(max_id), Some(min_ts)) => Some((max_id, min_ts)),
+                    _ => None,
                 }
             }
         };
 
-        let total_count = metrics.len();
+        let mut written = 0;
+        if let Some((max_id, min_ts)) = pending {
+            let since = RollupGranularity::Daily.bucket_start(min_ts);
+            written = match &self.pool {
+                DatabasePool::Sqlite(p) => Self::rebuild_rollups_sqlite(p, since, max_id).await?,
+                DatabasePool::Postgres(p) => {
+                    Self::rebuild_rollups_postgres(p, since, max_id).await?
+                }
+            };
+        }
 
-        Ok(AggregatedMetrics {
-            metrics,
-            total_count,
-            metric_type,
-            aggregation_period,
-        })
+        self.prune_rollups().await?;
+
+        if written > 0 {
+            tracing::debug!(rows = written, "Compacted metrics rollups");
+        }
+        Ok(written)
+    }
+
+    async fn get_rollup_watermark(&self) -> DomainResult<i64> {
+        let watermark = match &self.pool {
+            DatabasePool::Sqlite(p) => {
+                sqlx::query_scalar::<_, i64>(
+                    "SELECT last_metric_id FROM metrics_rollup_state WHERE id = 1",
+                )
+                .fetch_optional(p)
+                .await
+            }
+            DatabasePool::Postgres(p) => {
+                sqlx::query_scalar::<_, i64>(
+                    "SELECT last_metric_id FROM metrics_rollup_state WHERE id = 1",
+                )
+                .fetch_optional(p)
+                .await
+            }
+        }
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(watermark.unwrap_or(0))
+    }
+
+    async fn rebuild_rollups_sqlite(
+        pool: &sqlx::SqlitePool, since: DateTime<Utc>, max_id: i64,
+    ) -> DomainResult<usize> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let mut written = 0;
+
+        for granularity in RollupGranularity::ALL {
+            // Stored in the same RFC 3339 form as pipeline_metrics.timestamp.
+            let bucket_format = match granularity {
+                RollupGranularity::Hourly => "%Y-%m-%dT%H:00:00+00:00",
+                RollupGranularity::Daily => "%Y-%m-%dT00:00:00+00:00",
+            };
+
+            sqlx::query(
+                "DELETE FROM pipeline_metrics_rollups WHERE granularity = ? AND bucket_start >= ?",
+            )
+            .bind(granularity.as_str())
+            .bind(since.to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+            let result = sqlx::query(&format!(
+                "INSERT INTO pipeline_metrics_rollups
+                    (pipeline_id, metric_type, granularity, bucket_start, count, sum, min, max)
+                 SELECT pipeline_id, metric_type, ?, strftime('{}', timestamp) AS bucket,
+                    COUNT(*), SUM(value), MIN(value), MAX(value)
+                 FROM pipeline_metrics
+                 WHERE datetime(timestamp) >= datetime(?) AND id <= ?
+                 GROUP BY pipeline_id, metric_type, bucket",
+                bucket_format
+            ))
+            .bind(granularity.as_str())
+            .bind(since.to_rfc3339())
+            .bind(max_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            written += result.rows_affected() as usize;
+        }
+
+        sqlx::query(
+            "UPDATE metrics_rollup_state SET last_metric_id = ?, compacted_at = ? WHERE id = 1",
+        )
+        .bind(max_id)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        Ok(written)
+    }
+
+    async fn rebuild_rollups_postgres(
+        pool: &sqlx::PgPool, since: DateTime<Utc>, max_id: i64,
+    ) -> DomainResult<usize> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let mut written = 0;
+
+        for granularity in RollupGranularity::ALL {
+            sqlx::query(
+                "DELETE FROM pipeline_metrics_rollups WHERE granularity = $1 AND bucket_start >= $2",
+            )
+            .bind(granularity.as_str())
+            .bind(since)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+            let result = sqlx::query(
+                "INSERT INTO pipeline_metrics_rollups
+                    (pipeline_id, metric_type, granularity, bucket_start, count, sum, min, max)
+                 SELECT pipeline_id, metric_type, $1,
+                    date_trunc($1, timestamp AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' AS bucket,
+                    COUNT(*), SUM(value), MIN(value), MAX(value)
+                 FROM pipeline_metrics
+                 WHERE timestamp >= $2 AND id <= $3
+                 GROUP BY pipeline_id, metric_type, bucket",
+            )
+            .bind(granularity.as_str())
+            .bind(since)
+            .bind(max_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            written += result.rows_affected() as usize;
+        }
+
+        sqlx::query(
+            "UPDATE metrics_rollup_state SET last_metric_id = $1, compacted_at = NOW() WHERE id = 1",
+        )
+        .bind(max_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        Ok(written)
+    }
+
+    async fn prune_rollups(&self) -> DomainResult<()> {
+        for granularity in RollupGranularity::ALL {
+            let cutoff = Utc::now() - Duration::days(granularity.retention_days());
+            let sql = self.sql(
+                "DELETE FROM pipeline_metrics_rollups WHERE granularity = ? AND bucket_start < ?",
+            );
+            match &self.pool {
+                DatabasePool::Sqlite(p) => sqlx::query(&sql)
+                    .bind(granularity.as_str())
+                    .bind(cutoff.to_rfc3339())
+                    .execute(p)
+                    .await
+                    .map(|_| ()),
+                DatabasePool::Postgres(p) => sqlx::query(&sql)
+                    .bind(granularity.as_str())
+                    .bind(cutoff)
+                    .execute(p)
+                    .await
+                    .map(|_| ()),
+            }
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        }
+        Ok(())
     }
 
     async fn calculate_percentile_metrics(
@@ -928,6 +1309,15 @@ impl MetricsRepository {
             string_params.push((param_idx, pipeline_id.clone()));
         }
 
+        if let Some(pipeline_ids) = &query.pipeline_ids {
+            self.push_pipeline_ids_filter(
+                &mut sql,
+                pipeline_ids,
+                &mut param_idx,
+                &mut string_params,
+            );
+        }
+
         if let Some(start_date) = &query.start_date {
             param_idx += 1;
             sql.push_str(&format!(
@@ -1058,7 +1448,7 @@ impl MetricsRepository {
             .collect();
 
         let mut sorted_metrics = metrics;
-        sorted_metrics.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        sorted_metrics.sort_by_key(|m| m.timestamp);
 
         if let Some(limit) = query.limit {
             sorted_metrics.truncate(limit);
@@ -1341,6 +1731,26 @@ impl MetricsRepository {
             }
         };
 
+        // Flushed metrics should not live on in the charts through rollups.
+        let sql = self.sql("DELETE FROM pipeline_metrics_rollups WHERE pipeline_id = ?");
+        match &self.pool {
+            DatabasePool::Sqlite(p) => match pipeline_id {
+                Some(pid) => sqlx::query(&sql).bind(pid).execute(p).await.map(|_| ()),
+                None => sqlx::query("DELETE FROM pipeline_metrics_rollups")
+                    .execute(p)
+                    .await
+                    .map(|_| ()),
+            },
+            DatabasePool::Postgres(p) => match pipeline_id {
+                Some(pid) => sqlx::query(&sql).bind(pid).execute(p).await.map(|_| ()),
+                None => sqlx::query("DELETE FROM pipeline_metrics_rollups")
+                    .execute(p)
+                    .await
+                    .map(|_| ()),
+            },
+        }
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
         if let DatabasePool::Sqlite(p) = &self.pool {
             if deleted_count > 0 && !skip_vacuum {
                 tracing::debug!(
@@ -1810,21 +2220,18 @@ impl MetricsRepository {
 
         if let Some((last_run,)) = state {
             if last_run > 0 {
+                let sql = self.sql("SELECT COUNT(*) FROM pipeline_metrics WHERE pipeline_id = ?");
                 let count = match &self.pool {
-                    DatabasePool::Sqlite(p) => sqlx::query_as::<_, (i64,)>(
-                        "SELECT COUNT(*) FROM pipeline_metrics WHERE pipeline_id = ?",
-                    )
-                    .bind(pipeline_id)
-                    .fetch_one(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                    DatabasePool::Postgres(p) => sqlx::query_as::<_, (i64,)>(
-                        "SELECT COUNT(*) FROM pipeline_metrics WHERE pipeline_id = $1",
-                    )
-                    .bind(pipeline_id)
-                    .fetch_one(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                    DatabasePool::Sqlite(p) => sqlx::query_as::<_, (i64,)>(&sql)
+                        .bind(pipeline_id)
+                        .fetch_one(p)
+                        .await
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                    DatabasePool::Postgres(p) => sqlx::query_as::<_, (i64,)>(&sql)
+                        .bind(pipeline_id)
+                        .fetch_one(p)
+                        .await
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
                 };
 
                 return Ok(count.0 == 0);
@@ -1871,4 +2278,368 @@ impl MetricsRepository {
 
         Ok(corrupted.into_iter().map(|(id,)| id).collect())
     }
+
+    // Per-pipeline run count and sum of one metric type over a time range.
+    pub async fn sum_metric_by_pipeline(
+        &self, metric_type: MetricType, start_date: Option<DateTime<Utc>>,
+        end_date: Option<DateTime<Utc>>,
+    ) -> DomainResult<Vec<(String, i64, f64)>> {
+        match &self.pool {
+            DatabasePool::Sqlite(p) => {
+                sqlx::query_as(
+                    r#"
+                SELECT pipeline_id, COUNT(*), COALESCE(SUM(value), 0.0)
+                FROM pipeline_metrics
+                WHERE metric_type = ?
+                  AND (? IS NULL OR timestamp >= ?)
+                  AND (? IS NULL OR timestamp <= ?)
+                GROUP BY pipeline_id
+                "#,
+                )
+                .bind(metric_type.as_str())
+                .bind(start_date.map(|d| d.to_rfc3339()))
+                .bind(start_date.map(|d| d.to_rfc3339()))
+                .bind(end_date.map(|d| d.to_rfc3339()))
+                .bind(end_date.map(|d| d.to_rfc3339()))
+                .fetch_all(p)
+                .await
+            }
+            DatabasePool::Postgres(p) => {
+                sqlx::query_as(
+                    r#"
+                SELECT pipeline_id, COUNT(*), COALESCE(SUM(value), 0.0)::DOUBLE PRECISION
+                FROM pipeline_metrics
+                WHERE metric_type = $1
+                  AND ($2::TIMESTAMPTZ IS NULL OR timestamp >= $2)
+                  AND ($3::TIMESTAMPTZ IS NULL OR timestamp <= $3)
+                GROUP BY pipeline_id
+                "#,
+                )
+                .bind(metric_type.as_str())
+                .bind(start_date)
+                .bind(end_date)
+                .fetch_all(p)
+                .await
+            }
+        }
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))
+    }
+
+    // Returns false when the finding was already stored.
+    pub async fn insert_anomaly(&self, anomaly: &MetricAnomaly) -> DomainResult<bool> {
+        let inserted = match &self.pool {
+            DatabasePool::Sqlite(p) => sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO metric_anomalies
+                    (pipeline_id, kind, baseline, observed, z_score, run_number, detected_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&anomaly.pipeline_id)
+            .bind(anomaly.kind.as_str())
+            .bind(anomaly.baseline)
+            .bind(anomaly.observed)
+            .bind(anomaly.z_score)
+            .bind(anomaly.run_number)
+            .bind(anomaly.detected_at.to_rfc3339())
+            .execute(p)
+            .await
+            .map(|r| r.rows_affected()),
+            DatabasePool::Postgres(p) => sqlx::query(
+                r#"
+                INSERT INTO metric_anomalies
+                    (pipeline_id, kind, baseline, observed, z_score, run_number, detected_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (pipeline_id, kind, run_number) DO NOTHING
+                "#,
+            )
+            .bind(&anomaly.pipeline_id)
+            .bind(anomaly.kind.as_str())
+            .bind(anomaly.baseline)
+            .bind(anomaly.observed)
+            .bind(anomaly.z_score)
+            .bind(anomaly.run_number)
+            .bind(anomaly.detected_at)
+            .execute(p)
+            .await
+            .map(|r| r.rows_affected()),
+        }
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(inserted > 0)
+    }
+
+    pub async fn get_latest_anomaly_run(
+        &self, pipeline_id: &str, kind: AnomalyKind,
+    ) -> DomainResult<Option<i64>> {
+        let sql = self
+            .sql("SELECT MAX(run_number) FROM metric_anomalies WHERE pipeline_id = ? AND kind = ?");
+        match &self.pool {
+            DatabasePool::Sqlite(p) => {
+                sqlx::query_scalar(&sql)
+                    .bind(pipeline_id)
+                    .bind(kind.as_str())
+                    .fetch_one(p)
+                    .await
+            }
+            DatabasePool::Postgres(p) => {
+                sqlx::query_scalar(&sql)
+                    .bind(pipeline_id)
+                    .bind(kind.as_str())
+                    .fetch_one(p)
+                    .await
+            }
+        }
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))
+    }
+
+    // Newest first.
+    pub async fn list_anomalies(
+        &self, pipeline_id: Option<&str>, limit: usize,
+    ) -> DomainResult<Vec<MetricAnomaly>> {
+        let rows: Vec<AnomalyRow<DateTime<Utc>>> = match &self.pool {
+            DatabasePool::Sqlite(p) => {
+                let rows: Vec<AnomalyRow<String>> = sqlx::query_as(
+                    r#"
+                    SELECT pipeline_id, kind, baseline, observed, z_score, run_number, detected_at
+                    FROM metric_anomalies
+                    WHERE ? IS NULL OR pipeline_id = ?
+                    ORDER BY detected_at DESC, id DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(pipeline_id)
+                .bind(pipeline_id)
+                .bind(limit as i64)
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                rows.into_iter()
+                    .map(|(pid, kind, baseline, observed, z, run, detected_at)| {
+                        let detected_at = DateTime::parse_from_rfc3339(&detected_at)
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .unwrap_or_else(|_| Utc::now());
+                        (pid, kind, baseline, observed, z, run, detected_at)
+                    })
+                    .collect()
+            }
+            DatabasePool::Postgres(p) => sqlx::query_as(
+                r#"
+                SELECT pipeline_id, kind, baseline, observed, z_score, run_number, detected_at
+                FROM metric_anomalies
+                WHERE $1::TEXT IS NULL OR pipeline_id = $1
+                ORDER BY detected_at DESC, id DESC
+                LIMIT $2
+                "#,
+            )
+            .bind(pipeline_id)
+            .bind(limit as i64)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+        };
+
+        Ok(rows
+            .into_iter()
+            .filter_map(
+                |(pipeline_id, kind, baseline, observed, z_score, run_number, detected_at)| {
+                    Some(MetricAnomaly {
+                        pipeline_id,
+                        kind: kind.parse().ok()?,
+                        baseline,
+                        observed,
+                        z_score,
+                        run_number,
+                        detected_at,
+                    })
+                },
+            )
+            .collect())
+    }
+
+    pub async fn delete_anomalies_before(&self, cutoff: DateTime<Utc>) -> DomainResult<usize> {
+        let sql = self.sql("DELETE FROM metric_anomalies WHERE detected_at < ?");
+        let deleted = match &self.pool {
+            DatabasePool::Sqlite(p) => sqlx::query(&sql)
+                .bind(cutoff.to_rfc3339())
+                .execute(p)
+                .await
+                .map(|r| r.rows_affected()),
+            DatabasePool::Postgres(p) => sqlx::query(&sql)
+                .bind(cutoff)
+                .execute(p)
+                .await
+                .map(|r| r.rows_affected()),
+        }
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(deleted as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_database;
+
+    fn metric(run_number: i64, days_ago: i64, value: f64) -> MetricEntry {
+        let timestamp = Utc::now() - Duration::days(days_ago);
+        MetricEntry {
+            id: 0,
+            pipeline_id: "github__1__o__r__1".to_string(),
+            run_number,
+            timestamp,
+            metric_type: MetricType::RunDuration,
+            value,
+            metadata: None,
+            created_at: timestamp,
+            run_hash: Some(format!("hash-{}", run_number)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_long_range_queries_read_rollups() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = init_database(dir.path().join("test.db")).await.unwrap();
+        let repository = MetricsRepository::new(pool);
+
+        repository
+            .insert_metrics_batch(vec![
+                metric(1, 20, 10.0),
+                metric(2, 20, 30.0),
+                metric(3, 2, 50.0),
+            ])
+            .await
+            .unwrap();
+        assert!(repository.compact_rollups().await.unwrap() > 0);
+
+        let query = MetricsQuery {
+            metric_type: Some(MetricType::RunDuration),
+            aggregation_period: Some(AggregationPeriod::Daily),
+            aggregation_type: Some(AggregationType::Avg),
+            start_date: Some(Utc::now() - Duration::days(30)),
+            ..Default::default()
+        };
+        let result = repository
+            .query_aggregated_metrics(query.clone())
+            .await
+            .unwrap();
+        let values: Vec<(i64, f64)> = result.metrics.iter().map(|m| (m.count, m.value)).collect();
+        assert_eq!(values, vec![(2, 20.0), (1, 50.0)]);
+
+        // Late rows for an already compacted day rebuild that day's bucket.
+        repository
+            .insert_metrics_batch(vec![metric(4, 20, 80.0)])
+            .await
+            .unwrap();
+        repository.compact_rollups().await.unwrap();
+
+        let result = repository.query_aggregated_metrics(query).await.unwrap();
+        assert_eq!(result.metrics[0].count, 3);
+        assert_eq!(result.metrics[0].value, 40.0);
+        assert_eq!(result.metrics[0].max, Some(80.0));
+    }
+
+    #[tokio::test]
+    async fn test_sum_metric_by_pipeline() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = init_database(dir.path().join("test.db")).await.unwrap();
+        let repository = MetricsRepository::new(pool);
+
+        let cost = |run_number: i64, days_ago: i64, value: f64| MetricEntry {
+            metric_type: MetricType::EstimatedCost,
+            ..metric(run_number, days_ago, value)
+        };
+        repository
+            .insert_metrics_batch(vec![
+                cost(1, 20, 0.5),
+                cost(2, 2, 0.25),
+                cost(3, 1, 0.25),
+                metric(4, 1, 120.0),
+            ])
+            .await
+            .unwrap();
+
+        let totals = repository
+            .sum_metric_by_pipeline(
+                MetricType::EstimatedCost,
+                Some(Utc::now() - Duration::days(7)),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(totals, vec![("github__1__o__r__1".to_string(), 2, 0.5)]);
+    }
+
+    #[tokio::test]
+    async fn test_get_metrics_after_pages_by_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = init_database(dir.path().join("test.db")).await.unwrap();
+        let repository = MetricsRepository::new(pool);
+
+        repository
+            .insert_metrics_batch((1..=5).map(|n| metric(n, 1, n as f64)).collect())
+            .await
+            .unwrap();
+
+        let mut after_id = 0;
+        let mut runs = Vec::new();
+        loop {
+            let page = repository
+                .get_metrics_after(MetricsQuery::default(), after_id, 2)
+                .await
+                .unwrap();
+            let Some(last) = page.last() else {
+                break;
+            };
+            after_id = last.id;
+            runs.extend(page.iter().map(|m| m.run_number));
+        }
+        assert_eq!(runs, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_anomalies_are_stored_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = init_database(dir.path().join("test.db")).await.unwrap();
+        let repository = MetricsRepository::new(pool);
+
+        let anomaly = MetricAnomaly {
+            pipeline_id: "github__1__o__r__1".to_string(),
+            kind: AnomalyKind::FailureRate,
+            baseline: 0.05,
+            observed: 0.6,
+            z_score: 5.6,
+            run_number: 25,
+            detected_at: Utc::now(),
+        };
+        assert!(repository.insert_anomaly(&anomaly).await.unwrap());
+        assert!(!repository.insert_anomaly(&anomaly).await.unwrap());
+
+        assert_eq!(
+            repository
+                .get_latest_anomaly_run(&anomaly.pipeline_id, AnomalyKind::FailureRate)
+                .await
+                .unwrap(),
+            Some(25)
+        );
+        let listed = repository
+            .list_anomalies(Some(&anomaly.pipeline_id), 10)
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].kind, AnomalyKind::FailureRate);
+        assert!(repository
+            .list_anomalies(Some("other"), 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let removed = repository
+            .delete_anomalies_before(Utc::now() + Duration::minutes(1))
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+    }
 }
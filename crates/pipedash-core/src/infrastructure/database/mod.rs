@@ -1,16 +1,31 @@
 use std::path::Path;
 
+mod cache_cipher;
+mod cache_notify;
+mod dialect;
 mod metrics_repository;
+mod refresh_lock;
 mod repository;
 mod schema;
+mod snapshot;
 mod sqlite_backend;
+mod sqlite_writer;
 
 #[cfg(feature = "postgres")]
 mod postgres_backend;
 
+pub use cache_cipher::CacheCipher;
+pub use cache_notify::{
+    CacheInvalidation,
+    CacheInvalidationListener,
+    CacheInvalidationMessage,
+    CACHE_INVALIDATION_CHANNEL,
+};
+pub use dialect::Dialect;
 pub use metrics_repository::MetricsRepository;
 #[cfg(feature = "postgres")]
 pub use postgres_backend::PostgresConfigBackend;
+pub use refresh_lock::RefreshLock;
 pub use repository::{
     DatabasePool,
     Repository,
@@ -18,7 +33,14 @@ pub use repository::{
 pub use schema::init_database;
 #[cfg(feature = "postgres")]
 pub use schema::init_postgres_database;
+pub use snapshot::{
+    export_snapshot,
+    import_snapshot,
+    snapshot_format,
+    verify_snapshot,
+};
 pub use sqlite_backend::SqliteConfigBackend;
+pub use sqlite_writer::SqliteWriter;
 
 pub async fn has_encrypted_tokens(db_path: &Path) -> bool {
     if !db_path.exists() {
@@ -7,8 +7,17 @@ use sqlx::Row;
 use crate::domain::{
     DomainError,
     DomainResult,
+    MaintenanceWindow,
+    NotificationChannel,
+    PinnedPipeline,
+    PipelineDependency,
+    PipelineGroup,
+    PipelineRefreshSetting,
     ProviderConfig,
+    SavedView,
+    TriggerPreset,
 };
+use crate::infrastructure::config::bundle::remap_pipeline_id;
 use crate::infrastructure::config_backend::{
     ConfigBackend,
     ConfigExport,
@@ -43,7 +52,7 @@ impl PostgresConfigBackend {
 impl ConfigBackend for PostgresConfigBackend {
     async fn list_providers(&self) -> DomainResult<Vec<ProviderConfig>> {
         let rows = sqlx::query(
-            "SELECT id, name, provider_type, config_json, refresh_interval, version FROM providers",
+            "SELECT id, name, provider_type, config_json, refresh_interval, version, enabled FROM providers",
         )
         .fetch_all(&self.pool)
         .await
@@ -69,6 +78,7 @@ impl ConfigBackend for PostgresConfigBackend {
                 token: String::new(), // Tokens stored separately in TokenStore
                 refresh_interval: row.get::<i32, _>("refresh_interval") as i64,
                 version: Some(row.get::<i32, _>("version") as i64),
+                enabled: row.get("enabled"),
             });
         }
 
@@ -77,7 +87,7 @@ impl ConfigBackend for PostgresConfigBackend {
 
     async fn get_provider(&self, id: i64) -> DomainResult<Option<ProviderConfig>> {
         let row = sqlx::query(
-            "SELECT id, name, provider_type, config_json, refresh_interval, version FROM providers WHERE id = $1",
+            "SELECT id, name, provider_type, config_json, refresh_interval, version, enabled FROM providers WHERE id = $1",
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -103,6 +113,7 @@ impl ConfigBackend for PostgresConfigBackend {
                     token: String::new(),
                     refresh_interval: row.get::<i32, _>("refresh_interval") as i64,
                     version: Some(row.get::<i32, _>("version") as i64),
+                    enabled: row.get("enabled"),
                 }))
             }
             None => Ok(None),
@@ -129,8 +140,8 @@ impl ConfigBackend for PostgresConfigBackend {
 
         let id: i64 = sqlx::query_scalar(
             r#"
-            INSERT INTO providers (name, provider_type, token_encrypted, config_json, refresh_interval)
-            VALUES ($1, $2, '', $3, $4)
+            INSERT INTO providers (name, provider_type, token_encrypted, config_json, refresh_interval, enabled)
+            VALUES ($1, $2, '', $3, $4, $5)
             RETURNING id
             "#,
         )
@@ -138,6 +149,7 @@ impl ConfigBackend for PostgresConfigBackend {
         .bind(&config.provider_type)
         .bind(&config_json)
         .bind(config.refresh_interval)
+        .bind(config.enabled)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| DomainError::DatabaseError(format!("Failed to insert provider: {}", e)))?;
@@ -230,6 +242,673 @@ impl ConfigBackend for PostgresConfigBackend {
         Ok(())
     }
 
+    async fn list_pipeline_groups(&self) -> DomainResult<Vec<PipelineGroup>> {
+        let group_rows =
+            sqlx::query("SELECT id, name, created_at FROM pipeline_groups ORDER BY name")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| DomainError::DatabaseError(format!("Failed to list groups: {}", e)))?;
+
+        let member_rows = sqlx::query(
+            "SELECT group_id, pipeline_id FROM pipeline_group_members ORDER BY pipeline_id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to list group members: {}", e)))?;
+
+        let mut members: HashMap<i64, Vec<String>> = HashMap::new();
+        for row in member_rows {
+            members
+                .entry(row.get("group_id"))
+                .or_default()
+                .push(row.get("pipeline_id"));
+        }
+
+        Ok(group_rows
+            .into_iter()
+            .map(|row| {
+                let id: i64 = row.get("id");
+                PipelineGroup {
+                    id,
+                    name: row.get("name"),
+                    pipeline_ids: members.remove(&id).unwrap_or_default(),
+                    created_at: row.get("created_at"),
+                }
+            })
+            .collect())
+    }
+
+    async fn create_pipeline_group(&self, name: &str) -> DomainResult<i64> {
+        let id: i64 =
+            sqlx::query_scalar("INSERT INTO pipeline_groups (name) VALUES ($1) RETURNING id")
+                .bind(name)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| {
+                    DomainError::DatabaseError(format!("Failed to create group: {}", e))
+                })?;
+
+        Ok(id)
+    }
+
+    async fn delete_pipeline_group(&self, id: i64) -> DomainResult<()> {
+        let result = sqlx::query("DELETE FROM pipeline_groups WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to delete group: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!("Group {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    async fn add_pipeline_to_group(&self, group_id: i64, pipeline_id: &str) -> DomainResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO pipeline_group_members (group_id, pipeline_id)
+            VALUES ($1, $2)
+            ON CONFLICT (group_id, pipeline_id) DO NOTHING
+            "#,
+        )
+        .bind(group_id)
+        .bind(pipeline_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to assign pipeline to group: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    async fn remove_pipeline_from_group(
+        &self, group_id: i64, pipeline_id: &str,
+    ) -> DomainResult<()> {
+        sqlx::query("DELETE FROM pipeline_group_members WHERE group_id = $1 AND pipeline_id = $2")
+            .bind(group_id)
+            .bind(pipeline_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to remove pipeline from group: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    async fn list_saved_views(&self) -> DomainResult<Vec<SavedView>> {
+        let rows = sqlx::query(
+            "SELECT id, name, query_json, created_at, updated_at FROM saved_views ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to list views: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.get("id");
+                let query_json: String = row.get("query_json");
+                let query = serde_json::from_str(&query_json)
+                    .map_err(|e| {
+                        tracing::warn!(view_id = id, error = %e, "Skipping unreadable saved view");
+                    })
+                    .ok()?;
+
+                Some(SavedView {
+                    id,
+                    name: row.get("name"),
+                    query,
+                    created_at: row.get("created_at"),
+                    updated_at: row.get("updated_at"),
+                })
+            })
+            .collect())
+    }
+
+    async fn save_view(&self, name: &str, query_json: &str) -> DomainResult<i64> {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO saved_views (name, query_json)
+            VALUES ($1, $2)
+            ON CONFLICT (name) DO UPDATE SET
+                query_json = EXCLUDED.query_json,
+                updated_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(name)
+        .bind(query_json)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to save view: {}", e)))?;
+
+        Ok(id)
+    }
+
+    async fn delete_saved_view(&self, id: i64) -> DomainResult<()> {
+        let result = sqlx::query("DELETE FROM saved_views WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to delete view: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!("View {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    async fn list_trigger_presets(&self, pipeline_id: &str) -> DomainResult<Vec<TriggerPreset>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, pipeline_id, name, branch, parameters_json, created_at, updated_at
+            FROM trigger_presets WHERE pipeline_id = $1 ORDER BY name
+            "#,
+        )
+        .bind(pipeline_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list trigger presets: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.get("id");
+                let parameters_json: String = row.get("parameters_json");
+                let parameters = serde_json::from_str(&parameters_json)
+                    .map_err(|e| {
+                        tracing::warn!(preset_id = id, error = %e, "Skipping unreadable preset");
+                    })
+                    .ok()?;
+
+                Some(TriggerPreset {
+                    id,
+                    pipeline_id: row.get("pipeline_id"),
+                    name: row.get("name"),
+                    branch: row.get("branch"),
+                    parameters,
+                    created_at: row.get("created_at"),
+                    updated_at: row.get("updated_at"),
+                })
+            })
+            .collect())
+    }
+
+    async fn save_trigger_preset(
+        &self, pipeline_id: &str, name: &str, branch: Option<&str>, parameters_json: &str,
+    ) -> DomainResult<i64> {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO trigger_presets (pipeline_id, name, branch, parameters_json)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (pipeline_id, name) DO UPDATE SET
+                branch = EXCLUDED.branch,
+                parameters_json = EXCLUDED.parameters_json,
+                updated_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(pipeline_id)
+        .bind(name)
+        .bind(branch)
+        .bind(parameters_json)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to save trigger preset: {}", e)))?;
+
+        Ok(id)
+    }
+
+    async fn delete_trigger_preset(&self, id: i64) -> DomainResult<()> {
+        let result = sqlx::query("DELETE FROM trigger_presets WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete trigger preset: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Trigger preset {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn list_pipeline_dependencies(&self) -> DomainResult<Vec<PipelineDependency>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, upstream_id, downstream_id, trigger_on, inputs_json, created_by_role,
+                   created_at
+            FROM pipeline_dependencies ORDER BY id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list pipeline dependencies: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.get("id");
+                let trigger_on: String = row.get("trigger_on");
+                let inputs_json: String = row.get("inputs_json");
+                let parsed = serde_json::from_value(serde_json::Value::String(trigger_on))
+                    .and_then(|trigger_on| {
+                        serde_json::from_str(&inputs_json).map(|inputs| (trigger_on, inputs))
+                    });
+                let (trigger_on, inputs) = parsed
+                    .map_err(|e| {
+                        tracing::warn!(
+                            dependency_id = id,
+                            error = %e,
+                            "Skipping unreadable dependency"
+                        );
+                    })
+                    .ok()?;
+
+                Some(PipelineDependency {
+                    id,
+                    upstream_id: row.get("upstream_id"),
+                    downstream_id: row.get("downstream_id"),
+                    trigger_on,
+                    inputs,
+                    created_by_role: row
+                        .get::<Option<String>, _>("created_by_role")
+                        .and_then(|role| role.parse().ok()),
+                    created_at: row.get("created_at"),
+                })
+            })
+            .collect())
+    }
+
+    async fn save_pipeline_dependency(
+        &self, upstream_id: &str, downstream_id: &str, trigger_on: &str, inputs_json: &str,
+        created_by_role: &str,
+    ) -> DomainResult<i64> {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO pipeline_dependencies
+                (upstream_id, downstream_id, trigger_on, inputs_json, created_by_role)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (upstream_id, downstream_id) DO UPDATE SET
+                trigger_on = EXCLUDED.trigger_on,
+                inputs_json = EXCLUDED.inputs_json,
+                created_by_role = EXCLUDED.created_by_role
+            RETURNING id
+            "#,
+        )
+        .bind(upstream_id)
+        .bind(downstream_id)
+        .bind(trigger_on)
+        .bind(inputs_json)
+        .bind(created_by_role)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save pipeline dependency: {}", e))
+        })?;
+
+        Ok(id)
+    }
+
+    async fn delete_pipeline_dependency(&self, id: i64) -> DomainResult<()> {
+        let result = sqlx::query("DELETE FROM pipeline_dependencies WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete pipeline dependency: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Dependency {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn list_notification_channels(&self) -> DomainResult<Vec<NotificationChannel>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, kind, url, enabled, rules_json, suppression_json, created_at,
+                   updated_at
+            FROM notification_channels ORDER BY name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list notification channels: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.get("id");
+                let kind: String = row.get("kind");
+                let rules_json: String = row.get("rules_json");
+                let suppression_json: String = row.get("suppression_json");
+                let parsed = kind.parse().and_then(|kind| {
+                    serde_json::from_str(&rules_json)
+                        .and_then(|rules| {
+                            serde_json::from_str(&suppression_json)
+                                .map(|suppression| (kind, rules, suppression))
+                        })
+                        .map_err(|e| DomainError::InvalidConfig(e.to_string()))
+                });
+                let (kind, rules, suppression) = parsed
+                    .map_err(|e| {
+                        tracing::warn!(
+                            channel_id = id,
+                            error = %e,
+                            "Skipping unreadable notification channel"
+                        );
+                    })
+                    .ok()?;
+
+                Some(NotificationChannel {
+                    id,
+                    name: row.get("name"),
+                    kind,
+                    url: row.get("url"),
+                    enabled: row.get("enabled"),
+                    rules,
+                    suppression,
+                    created_at: row.get("created_at"),
+                    updated_at: row.get("updated_at"),
+                })
+            })
+            .collect())
+    }
+
+    async fn save_notification_channel(
+        &self, name: &str, kind: &str, url: &str, enabled: bool, rules_json: &str,
+        suppression_json: &str,
+    ) -> DomainResult<i64> {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO notification_channels
+                (name, kind, url, enabled, rules_json, suppression_json)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (name) DO UPDATE SET
+                kind = EXCLUDED.kind,
+                url = EXCLUDED.url,
+                enabled = EXCLUDED.enabled,
+                rules_json = EXCLUDED.rules_json,
+                suppression_json = EXCLUDED.suppression_json,
+                updated_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(name)
+        .bind(kind)
+        .bind(url)
+        .bind(enabled)
+        .bind(rules_json)
+        .bind(suppression_json)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save notification channel: {}", e))
+        })?;
+
+        Ok(id)
+    }
+
+    async fn delete_notification_channel(&self, id: i64) -> DomainResult<()> {
+        let result = sqlx::query("DELETE FROM notification_channels WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete notification channel: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Notification channel {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn list_pipeline_refresh_settings(&self) -> DomainResult<Vec<PipelineRefreshSetting>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT pipeline_id, refresh_interval_secs, snoozed, updated_at
+            FROM pipeline_refresh_settings ORDER BY pipeline_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list refresh settings: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PipelineRefreshSetting {
+                pipeline_id: row.get("pipeline_id"),
+                refresh_interval_secs: row
+                    .get::<Option<i64>, _>("refresh_interval_secs")
+                    .map(|secs| secs.max(0) as u64),
+                snoozed: row.get("snoozed"),
+                updated_at: row.get("updated_at"),
+            })
+            .collect())
+    }
+
+    async fn save_pipeline_refresh_setting(
+        &self, pipeline_id: &str, refresh_interval_secs: Option<i64>, snoozed: bool,
+    ) -> DomainResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO pipeline_refresh_settings (pipeline_id, refresh_interval_secs, snoozed)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (pipeline_id) DO UPDATE SET
+                refresh_interval_secs = EXCLUDED.refresh_interval_secs,
+                snoozed = EXCLUDED.snoozed,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(pipeline_id)
+        .bind(refresh_interval_secs)
+        .bind(snoozed)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save refresh setting: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    async fn delete_pipeline_refresh_setting(&self, pipeline_id: &str) -> DomainResult<()> {
+        sqlx::query("DELETE FROM pipeline_refresh_settings WHERE pipeline_id = $1")
+            .bind(pipeline_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete refresh setting: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    async fn list_approval_rules(&self) -> DomainResult<Vec<String>> {
+        sqlx::query_scalar("SELECT pipeline_id FROM pipeline_approval_rules ORDER BY pipeline_id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to list approval rules: {}", e))
+            })
+    }
+
+    async fn set_approval_rule(&self, pipeline_id: &str, required: bool) -> DomainResult<()> {
+        let sql = if required {
+            "INSERT INTO pipeline_approval_rules (pipeline_id) VALUES ($1) \
+             ON CONFLICT (pipeline_id) DO NOTHING"
+        } else {
+            "DELETE FROM pipeline_approval_rules WHERE pipeline_id = $1"
+        };
+
+        sqlx::query(sql)
+            .bind(pipeline_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to save approval rule: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    async fn list_maintenance_windows(&self) -> DomainResult<Vec<MaintenanceWindow>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, window_data, created_at, updated_at
+            FROM maintenance_windows ORDER BY name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list maintenance windows: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.get("id");
+                let window_data: String = row.get("window_data");
+                let window: MaintenanceWindow = serde_json::from_str(&window_data)
+                    .map_err(|e| {
+                        tracing::warn!(
+                            window_id = id,
+                            error = %e,
+                            "Skipping unreadable maintenance window"
+                        );
+                    })
+                    .ok()?;
+
+                Some(MaintenanceWindow {
+                    id,
+                    name: row.get("name"),
+                    created_at: row.get("created_at"),
+                    updated_at: row.get("updated_at"),
+                    ..window
+                })
+            })
+            .collect())
+    }
+
+    async fn save_maintenance_window(&self, name: &str, window_json: &str) -> DomainResult<i64> {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO maintenance_windows (name, window_data)
+            VALUES ($1, $2)
+            ON CONFLICT (name) DO UPDATE SET
+                window_data = EXCLUDED.window_data,
+                updated_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(name)
+        .bind(window_json)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save maintenance window: {}", e))
+        })?;
+
+        Ok(id)
+    }
+
+    async fn delete_maintenance_window(&self, id: i64) -> DomainResult<()> {
+        let result = sqlx::query("DELETE FROM maintenance_windows WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete maintenance window: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Maintenance window {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn list_pinned_pipelines(&self) -> DomainResult<Vec<PinnedPipeline>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT pipeline_id, position, unpinned, updated_at
+            FROM pinned_pipelines ORDER BY position, pipeline_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list pinned pipelines: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PinnedPipeline {
+                pipeline_id: row.get("pipeline_id"),
+                position: row.get("position"),
+                unpinned: row.get("unpinned"),
+                updated_at: row.get("updated_at"),
+            })
+            .collect())
+    }
+
+    async fn save_pinned_pipeline(&self, pin: &PinnedPipeline) -> DomainResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO pinned_pipelines (pipeline_id, position, unpinned, updated_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (pipeline_id) DO UPDATE SET
+                position = EXCLUDED.position,
+                unpinned = EXCLUDED.unpinned,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(&pin.pipeline_id)
+        .bind(pin.position)
+        .bind(pin.unpinned)
+        .bind(pin.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save pinned pipeline: {}", e))
+        })?;
+
+        Ok(())
+    }
+
     async fn store_permissions(
         &self, provider_id: i64, permissions: &StoredPermissions,
     ) -> DomainResult<()> {
@@ -333,11 +1012,14 @@ impl ConfigBackend for PostgresConfigBackend {
             entry.permissions.insert(permission_name, granted);
         }
 
+        let pinned_pipelines = self.list_pinned_pipelines().await?;
+
         Ok(ConfigExport {
             version: "1.0".to_string(),
             providers,
             table_preferences,
             permissions,
+            pinned_pipelines,
         })
     }
 
@@ -394,6 +1076,14 @@ impl ConfigBackend for PostgresConfigBackend {
             }
         }
 
+        for pin in &data.pinned_pipelines {
+            let mut pin = pin.clone();
+            if let Some(pipeline_id) = remap_pipeline_id(&pin.pipeline_id, &id_mapping) {
+                pin.pipeline_id = pipeline_id;
+            }
+            self.save_pinned_pipeline(&pin).await?;
+        }
+
         Ok(id_mapping)
     }
 }
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use sqlx::postgres::{
+    PgConnection,
+    PgPool,
+};
+use sqlx::Connection;
+use tokio::sync::Mutex;
+
+// First key of every advisory lock taken here; the provider id is the second.
+// Keeps these locks apart from any other advisory locks on the same database.
+const REFRESH_LOCK_NAMESPACE: i32 = 0x5044_5246;
+
+#[derive(Default)]
+struct LockState {
+    conn: Option<PgConnection>,
+    held: HashSet<i64>,
+}
+
+// Per-provider refresh ownership for instances sharing one Postgres database,
+// built on session advisory locks. The locks live on a dedicated connection,
+// so the server drops them as soon as the owning instance dies or loses its
+// connection, and another instance picks the provider up on its next cycle.
+// Locks are kept until then, so one instance usually ends up owning every
+// provider while the others stand by.
+pub struct RefreshLock {
+    pool: PgPool,
+    state: Mutex<LockState>,
+}
+
+impl RefreshLock {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            state: Mutex::new(LockState::default()),
+        }
+    }
+
+    // Returns the providers this instance should refresh. When the database
+    // cannot be reached every provider is returned, so a lock failure never
+    // leaves pipelines unrefreshed.
+    pub async fn claim(&self, provider_ids: &[i64]) -> HashSet<i64> {
+        let mut state = self.state.lock().await;
+
+        match Self::claim_locked(&self.pool, &mut state, provider_ids).await {
+            Ok(owned) => owned,
+            Err(e) => {
+                tracing::warn!(error = %e, "Refresh lock unavailable, refreshing all providers");
+                state.conn = None;
+                state.held.clear();
+                provider_ids.iter().copied().collect()
+            }
+        }
+    }
+
+    async fn claim_locked(
+        pool: &PgPool, state: &mut LockState, provider_ids: &[i64],
+    ) -> Result<HashSet<i64>, sqlx::Error> {
+        let alive = match state.conn.as_mut() {
+            Some(conn) => conn.ping().await.is_ok(),
+            None => false,
+        };
+        if !alive {
+            // Locks held on a dead connection are already gone.
+            state.held.clear();
+            state.conn = Some(pool.acquire().await?.detach());
+        }
+        let conn = state
+            .conn
+            .as_mut()
+            .expect("connection was just established");
+
+        let wanted: HashSet<i64> = provider_ids.iter().copied().collect();
+
+        for provider_id in state.held.difference(&wanted).copied().collect::<Vec<_>>() {
+            sqlx::query("SELECT pg_advisory_unlock($1, $2)")
+                .bind(REFRESH_LOCK_NAMESPACE)
+                .bind(provider_id as i32)
+                .execute(&mut *conn)
+                .await?;
+            state.held.remove(&provider_id);
+        }
+
+        for &provider_id in &wanted {
+            if state.held.contains(&provider_id) {
+                continue;
+            }
+
+            let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1, $2)")
+                .bind(REFRESH_LOCK_NAMESPACE)
+                .bind(provider_id as i32)
+                .fetch_one(&mut *conn)
+                .await?;
+
+            if acquired {
+                tracing::info!(provider_id, "Acquired refresh lock");
+                state.held.insert(provider_id);
+            }
+        }
+
+        Ok(state.held.clone())
+    }
+
+    // Closing the connection releases every lock at once.
+    pub async fn release(&self) {
+        let mut state = self.state.lock().await;
+        state.held.clear();
+        if let Some(conn) = state.conn.take() {
+            let _ = conn.close().await;
+        }
+    }
+}
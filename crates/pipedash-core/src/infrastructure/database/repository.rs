@@ -1,32 +1,125 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{
+    Arc,
+    OnceLock,
+    RwLock,
+};
 
 use chrono::{
     DateTime,
     Utc,
 };
+use pipedash_plugin_api::PaginatedResponse;
 use sqlx::postgres::PgPool;
 use sqlx::{
+    Connection,
     Row as SqlxRow,
     SqlitePool,
 };
-use tokio::time::sleep;
 
+use super::cache_cipher::{
+    CacheCipher,
+    SEALED_PREFIX,
+};
+use super::cache_notify::{
+    new_instance_id,
+    CacheInvalidation,
+    CacheInvalidationMessage,
+    CACHE_INVALIDATION_CHANNEL,
+};
+use super::dialect::Dialect;
+use super::schema::latest_migration;
+use super::sqlite_writer::{
+    SqliteWriter,
+    WriteConnection,
+};
 use crate::domain::{
+    AppNotification,
+    ApprovalStatus,
+    AuditEntry,
+    BackfillStatus,
+    BranchStatus,
+    BuildAgent,
     DomainError,
     DomainResult,
+    GlobalRunRetentionConfig,
+    MaintenanceWindow,
+    NotificationChannel,
+    NotificationDelivery,
+    PinnedPipeline,
     Pipeline,
+    PipelineDependency,
+    PipelineGroup,
+    PipelineListQuery,
+    PipelineRefreshSetting,
     PipelineRun,
+    PipelineRunRetention,
     PipelineStatus,
+    PipelineUsage,
+    ProviderAgent,
     ProviderConfig,
+    ProviderHealthCheck,
+    RunAnnotation,
+    RunBackfillJob,
+    RunRetentionPolicy,
+    SavedView,
+    SchemaMigration,
+    SchemaStatus,
+    SearchQuery,
+    TriggerApproval,
+    TriggerPreset,
+    PROVIDER_OUTAGE_THRESHOLD,
 };
+use crate::infrastructure::config::token_ref::TokenReference;
 use crate::infrastructure::deduplication::hash_pipeline_run;
+use crate::infrastructure::secrets::{
+    delete_config_secrets,
+    redact_config_secrets,
+    resolve_config_secrets,
+    store_config_secrets,
+    CachedTokenStore,
+    ExternalSecretsTokenStore,
+};
 use crate::infrastructure::{
     ConfigBackend,
     TokenStore,
 };
 
+// Runs a query written once against whichever backend the cache database is
+// on: `$body` is expanded per backend with `$pool` bound to that pool.
+macro_rules! with_pool {
+    ($repo:expr, |$pool:ident| $body:expr) => {
+        match &$repo.cache_pool {
+            DatabasePool::Sqlite($pool) => $body,
+            DatabasePool::Postgres($pool) => $body,
+        }
+    };
+}
+
+// Like `with_pool!` for writes. `$conn` is a connection: the shared writer
+// for SQLite, so writes don't contend for the database lock, and a pooled
+// one for Postgres. Statements run on it with `&mut *conn`.
+macro_rules! with_write_connection {
+    ($repo:expr, |$conn:ident| $body:expr) => {
+        match &$repo.cache_pool {
+            DatabasePool::Sqlite(_) => {
+                let mut writer = $repo.write_connection().await?;
+                let $conn = &mut *writer;
+                $body
+            }
+            DatabasePool::Postgres(pool) => {
+                let mut pooled = pool
+                    .acquire()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                let $conn = &mut *pooled;
+                $body
+            }
+        }
+    };
+}
+
 #[derive(Clone)]
 pub enum DatabasePool {
     Sqlite(SqlitePool),
@@ -38,6 +131,13 @@ impl DatabasePool {
         matches!(self, DatabasePool::Postgres(_))
     }
 
+    pub fn dialect(&self) -> Dialect {
+        match self {
+            DatabasePool::Sqlite(_) => Dialect::Sqlite,
+            DatabasePool::Postgres(_) => Dialect::Postgres,
+        }
+    }
+
     pub fn as_sqlite(&self) -> Option<&SqlitePool> {
         match self {
             DatabasePool::Sqlite(pool) => Some(pool),
@@ -51,67 +151,114 @@ impl DatabasePool {
             _ => None,
         }
     }
+
+    // A cheap round trip, for readiness checks.
+    pub async fn ping(&self) -> DomainResult<()> {
+        match self {
+            DatabasePool::Sqlite(p) => {
+                sqlx::query("SELECT 1")
+                    .execute(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            }
+            DatabasePool::Postgres(p) => {
+                sqlx::query("SELECT 1")
+                    .execute(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
 }
 
 const FETCH_STATUS_SUCCESS: &str = "success";
 const FETCH_STATUS_ERROR: &str = "error";
 const FETCH_STATUS_NEVER: &str = "never";
+const PROVIDER_HEALTH_RETENTION_DAYS: i64 = 7;
+
+const MAX_NOTIFICATION_DELIVERIES: usize = 500;
+
+const MAX_NOTIFICATIONS: usize = 500;
+
+const MAX_AUDIT_ENTRIES: usize = 1000;
+
+// Must match the expression indexes in the postgres search_index migration.
+const PIPELINE_SEARCH_VECTOR: &str =
+    "to_tsvector('simple', name || ' ' || repository || ' ' || COALESCE(branch, ''))";
+const RUN_SEARCH_VECTOR: &str = "to_tsvector('simple',
+        COALESCE(run_data::jsonb ->> 'commit_message', '') || ' ' ||
+        COALESCE(run_data::jsonb ->> 'branch', '') || ' ' ||
+        COALESCE(run_data::jsonb ->> 'actor', ''))";
+
+// Expressions behind the `runs_by_commit` indexes; see the migration.
+const SQLITE_RUN_COMMIT_SHA: &str = "json_extract(run_data, '$.commit_sha')";
+const POSTGRES_RUN_COMMIT_SHA: &str = "(run_data::jsonb ->> 'commit_sha')";
+
+// Row shapes for `run_retention_global_config`.
+type SqliteRetentionRow = (i64, Option<i64>, Option<i64>, Option<String>, i64, String);
+type PostgresRetentionRow = (
+    bool,
+    Option<i64>,
+    Option<i64>,
+    Option<DateTime<Utc>>,
+    i64,
+    DateTime<Utc>,
+);
+
+// SQLite defaults produce `YYYY-MM-DD HH:MM:SS` while values written from Rust
+// are RFC 3339, so accept both.
+fn parse_sqlite_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|dt| dt.and_utc())
+        })
+}
 
-async fn retry_on_busy<F, Fut, T>(operation: F) -> DomainResult<T>
-where
-    F: Fn() -> Fut,
-    Fut: std::future::Future<Output = DomainResult<T>>,
-{
-    const MAX_RETRIES: u32 = 3; // Reduced from 10 to 3 to prevent 5-minute delays
-    const INITIAL_DELAY_MS: u64 = 10;
-
-    let mut attempt = 0;
-    loop {
-        match operation().await {
-            Ok(result) => {
-                if attempt > 0 {
-                    tracing::warn!(
-                        "SQLite operation succeeded after {} retries ({}ms total)",
-                        attempt,
-                        INITIAL_DELAY_MS * (2_u64.pow(attempt) - 1)
-                    );
-                }
-                return Ok(result);
-            }
-            Err(DomainError::DatabaseError(ref msg))
-                if (msg.contains("database is locked")
-                    || msg.contains("SQLITE_BUSY")
-                    || msg.contains("locked"))
-                    && attempt < MAX_RETRIES =>
-            {
-                attempt += 1;
-                let delay = INITIAL_DELAY_MS * 2_u64.pow(attempt - 1);
-
-                if attempt > 3 {
-                    tracing::warn!(
-                        "SQLite busy, retry {}/{} (waiting {}ms)",
-                        attempt,
-                        MAX_RETRIES,
-                        delay
-                    );
-                }
+#[derive(serde::Deserialize)]
+struct RunCommit {
+    commit_sha: Option<String>,
+}
 
-                sleep(Duration::from_millis(delay)).await;
-            }
-            Err(e) => {
-                if attempt > 0 {
-                    tracing::error!("SQLite operation failed after {} retries: {}", attempt, e);
-                }
-                return Err(e);
-            }
+// A stored payload sealed under `cipher`, or `None` when it needs no rewrite:
+// it is already sealed and either no `previous` key was given or it doesn't
+// open with that key, meaning it was written under the current one.
+fn reseal_payload(
+    stored: &str, cipher: &CacheCipher, previous: Option<&CacheCipher>, is_run: bool,
+) -> DomainResult<Option<String>> {
+    let payload = if CacheCipher::is_sealed(stored) {
+        match previous.map(|previous| previous.open(stored)) {
+            Some(Ok(payload)) => payload,
+            _ => return Ok(None),
         }
-    }
+    } else {
+        Cow::Borrowed(stored)
+    };
+
+    // Commit lookups need the SHA next to the ciphertext.
+    let commit_sha = if is_run {
+        serde_json::from_str::<RunCommit>(&payload)
+            .ok()
+            .and_then(|run| run.commit_sha)
+    } else {
+        None
+    };
+    cipher.seal(&payload, commit_sha.as_deref()).map(Some)
 }
 
 pub struct Repository {
     config_backend: Arc<dyn ConfigBackend>,
     cache_pool: DatabasePool,
     token_store: Arc<dyn TokenStore>,
+    instance_id: String,
+    // Started on the first SQLite write, which is always inside the runtime.
+    sqlite_writer: OnceLock<SqliteWriter>,
+    // Set when cached run and pipeline payloads are encrypted at rest.
+    cache_cipher: RwLock<Option<Arc<CacheCipher>>>,
 }
 
 impl Repository {
@@ -122,7 +269,76 @@ impl Repository {
         Self {
             config_backend,
             cache_pool,
-            token_store,
+            token_store: Arc::new(CachedTokenStore::new(Arc::new(
+                ExternalSecretsTokenStore::new(token_store),
+            ))),
+            instance_id: new_instance_id(),
+            sqlite_writer: OnceLock::new(),
+            cache_cipher: RwLock::new(None),
+        }
+    }
+
+    pub fn set_cache_cipher(&self, cipher: CacheCipher) {
+        *self.cache_cipher.write().unwrap() = Some(Arc::new(cipher));
+    }
+
+    pub fn cache_encrypted(&self) -> bool {
+        self.cache_cipher.read().unwrap().is_some()
+    }
+
+    fn cipher(&self) -> Option<Arc<CacheCipher>> {
+        self.cache_cipher.read().unwrap().clone()
+    }
+
+    fn encode_run(&self, run: &PipelineRun) -> DomainResult<String> {
+        let json =
+            serde_json::to_string(run).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        match self.cipher() {
+            Some(cipher) => cipher.seal(&json, run.commit_sha.as_deref()),
+            None => Ok(json),
+        }
+    }
+
+    fn encode_pipeline_metadata(&self, pipeline: &Pipeline) -> DomainResult<String> {
+        let json = serde_json::to_string(&pipeline.metadata)
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        match self.cipher() {
+            Some(cipher) => cipher.seal(&json, None),
+            None => Ok(json),
+        }
+    }
+
+    // Sealed payloads that can't be opened, because encryption was turned
+    // off or the key changed, are skipped like any other unreadable row.
+    fn open_payload<'a>(&self, stored: &'a str) -> Option<Cow<'a, str>> {
+        if !CacheCipher::is_sealed(stored) {
+            return Some(Cow::Borrowed(stored));
+        }
+        match self.cipher()?.open(stored) {
+            Ok(payload) => Some(payload),
+            Err(e) => {
+                tracing::debug!(error = %e, "Skipping cached payload that could not be decrypted");
+                None
+            }
+        }
+    }
+
+    // Runs cached before a redaction pattern was added are scrubbed on the
+    // way out too.
+    fn decode_run(&self, stored: &str) -> Option<PipelineRun> {
+        let mut run: PipelineRun = serde_json::from_str(&self.open_payload(stored)?).ok()?;
+        crate::domain::redactor().redact_run(&mut run);
+        Some(run)
+    }
+
+    // Turns a token as entered by the user into the token a plugin needs,
+    // resolving secrets manager references. Anything else is returned as-is.
+    pub async fn resolve_token(&self, token: &str) -> DomainResult<String> {
+        match TokenReference::parse(token) {
+            Ok(reference) if reference.is_external() => {
+                self.token_store.get_token_by_name(token).await
+            }
+            _ => Ok(token.to_string()),
         }
     }
 
@@ -130,29 +346,77 @@ impl Repository {
         &self.cache_pool
     }
 
+    // Identifies this process in cache invalidations published over Postgres
+    // NOTIFY.
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    // Tells other instances sharing the Postgres cache that rows changed. A
+    // no-op on SQLite, and failures only cost other instances a stale read
+    // until their next refresh.
+    async fn publish_invalidation(&self, invalidation: CacheInvalidation) {
+        let DatabasePool::Postgres(pool) = &self.cache_pool else {
+            return;
+        };
+
+        let message = CacheInvalidationMessage {
+            origin: self.instance_id.clone(),
+            invalidation,
+        };
+        let payload = match serde_json::to_string(&message) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to encode cache invalidation");
+                return;
+            }
+        };
+
+        if let Err(e) = sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(CACHE_INVALIDATION_CHANNEL)
+            .bind(payload)
+            .execute(pool)
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to publish cache invalidation");
+        }
+    }
+
     pub fn config_backend(&self) -> &Arc<dyn ConfigBackend> {
         &self.config_backend
     }
 
+    // The connection SQLite writes go through, after the ones queued before.
+    async fn write_connection(&self) -> DomainResult<WriteConnection> {
+        let DatabasePool::Sqlite(pool) = &self.cache_pool else {
+            return Err(DomainError::InternalError(
+                "Write connections are only used with SQLite".to_string(),
+            ));
+        };
+
+        self.sqlite_writer
+            .get_or_init(|| {
+                self.config_backend
+                    .sqlite_writer()
+                    .unwrap_or_else(|| SqliteWriter::spawn(pool.clone()))
+            })
+            .acquire()
+            .await
+    }
+
     pub fn pool(&self) -> &SqlitePool {
         self.cache_pool
             .as_sqlite()
             .expect("Expected SQLite pool but found PostgreSQL")
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn datetime_now(&self) -> &'static str {
-        match self.cache_pool {
-            DatabasePool::Sqlite(_) => "datetime('now')",
-            DatabasePool::Postgres(_) => "NOW()",
-        }
+    fn placeholder(&self, index: usize) -> String {
+        self.cache_pool.dialect().placeholder(index)
     }
 
-    fn placeholder(&self, index: usize) -> String {
-        match self.cache_pool {
-            DatabasePool::Sqlite(_) => "?".to_string(),
-            DatabasePool::Postgres(_) => format!("${}", index),
-        }
+    // Shared SQL rewritten for the cache database, see `Dialect::sql`.
+    fn sql(&self, sql: &str) -> String {
+        self.cache_pool.dialect().sql(sql)
     }
 
     #[allow(dead_code)]
@@ -164,7 +428,8 @@ impl Repository {
             "fetched_at",
             "run_hash",
         ];
-        let params_per_row = 4; // pipeline_id, run_number, run_data, run_hash (fetched_at is datetime)
+        let params_per_row = 4; // pipeline_id, run_number, run_data, run_hash
+                                // (fetched_at is datetime)
 
         match self.cache_pool {
             DatabasePool::Sqlite(_) => {
@@ -214,8 +479,16 @@ impl Repository {
         }
     }
 
+    // Secret config values (see `PROVIDER_SECRET_KEYS`) go to the token store;
+    // the saved config only references them.
     pub async fn add_provider(&self, config: &ProviderConfig) -> DomainResult<i64> {
-        let provider_id = self.config_backend.create_provider(config).await?;
+        let provider_id = self
+            .config_backend
+            .create_provider(&Self::without_secrets(config))
+            .await?;
+
+        let mut secrets = config.config.clone();
+        store_config_secrets(self.token_store.as_ref(), provider_id, &mut secrets).await?;
 
         self.token_store
             .store_token(provider_id, &config.token)
@@ -245,16 +518,57 @@ impl Repository {
             .ok_or_else(|| DomainError::ProviderNotFound(id.to_string()))?;
 
         provider.token = self.token_store.get_token(id).await?;
+        resolve_config_secrets(self.token_store.as_ref(), id, &mut provider.config).await?;
 
         Ok(provider)
     }
 
+    fn without_secrets(config: &ProviderConfig) -> Cow<'_, ProviderConfig> {
+        let mut redacted = config.config.clone();
+        redact_config_secrets(&mut redacted);
+        if redacted == config.config {
+            return Cow::Borrowed(config);
+        }
+        Cow::Owned(ProviderConfig {
+            config: redacted,
+            ..config.clone()
+        })
+    }
+
+    // Tokens are left empty, since reading them can mean a keychain prompt.
+    // Use `list_providers_with_tokens` when they are needed.
     pub async fn list_providers(&self) -> DomainResult<Vec<ProviderConfig>> {
         let mut providers = self.config_backend.list_providers().await?;
+        for provider in &mut providers {
+            provider.token.clear();
+        }
+        Ok(providers)
+    }
+
+    // Providers without a stored token get an empty one.
+    pub async fn list_providers_with_tokens(&self) -> DomainResult<Vec<ProviderConfig>> {
+        let mut providers = self.list_providers().await?;
 
+        let ids: Vec<i64> = providers.iter().filter_map(|p| p.id).collect();
+        let mut tokens = self
+            .token_store
+            .get_tokens_batch(&ids)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to load provider tokens");
+                HashMap::new()
+            });
         for provider in &mut providers {
-            if let Some(id) = provider.id {
-                provider.token = self.token_store.get_token(id).await.unwrap_or_default();
+            let Some(id) = provider.id else {
+                continue;
+            };
+            if let Some(token) = tokens.remove(&id) {
+                provider.token = token;
+            }
+            if let Err(e) =
+                resolve_config_secrets(self.token_store.as_ref(), id, &mut provider.config).await
+            {
+                tracing::warn!(provider_id = id, error = %e, "Failed to load provider secrets");
             }
         }
 
@@ -262,72 +576,79 @@ impl Repository {
     }
 
     pub async fn update_provider(&self, id: i64, config: &ProviderConfig) -> DomainResult<()> {
-        self.config_backend.update_provider(id, config).await?;
+        self.config_backend
+            .update_provider(id, &Self::without_secrets(config))
+            .await?;
 
         self.token_store.store_token(id, &config.token).await?;
+        let mut secrets = config.config.clone();
+        store_config_secrets(self.token_store.as_ref(), id, &mut secrets).await?;
 
         Ok(())
     }
 
+    pub async fn store_provider_token(&self, id: i64, token: &str) -> DomainResult<()> {
+        self.token_store.store_token(id, token).await
+    }
+
     pub async fn update_provider_with_version(
         &self, id: i64, config: &ProviderConfig, expected_version: i64,
     ) -> DomainResult<bool> {
-        let cache_pool = self.cache_pool.clone();
-        let config_clone = config.clone();
-        let token_clone = config.token.clone();
-        let token_store = self.token_store.clone();
+        let config_json =
+            serde_json::to_string(&Self::without_secrets(config).config).map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to serialize config: {}", e))
+            })?;
 
-        retry_on_busy(|| async {
-            let config_json = serde_json::to_string(&config_clone.config)
-                .map_err(|e| DomainError::DatabaseError(format!("Failed to serialize config: {}", e)))?;
+        let sql = self.sql(
+            r#"UPDATE providers
+               SET name = ?, provider_type = ?, config_json = ?, refresh_interval = ?,
+                   version = version + 1, updated_at = NOW()
+               WHERE id = ? AND version = ?"#,
+        );
 
-            let rows_affected = match &cache_pool {
-                DatabasePool::Sqlite(p) => {
-                    sqlx::query(
-                        r#"UPDATE providers
-                           SET name = ?, provider_type = ?, config_json = ?, refresh_interval = ?,
-                               version = version + 1, updated_at = datetime('now')
-                           WHERE id = ? AND version = ?"#
-                    )
-                    .bind(&config_clone.name)
-                    .bind(&config_clone.provider_type)
-                    .bind(&config_json)
-                    .bind(config_clone.refresh_interval)
-                    .bind(id)
-                    .bind(expected_version)
-                    .execute(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(format!("Failed to update provider: {}", e)))?
-                    .rows_affected()
-                }
-                DatabasePool::Postgres(p) => {
-                    sqlx::query(
-                        r#"UPDATE providers
-                           SET name = $1, provider_type = $2, config_json = $3, refresh_interval = $4,
-                               version = version + 1, updated_at = NOW()
-                           WHERE id = $5 AND version = $6"#
-                    )
-                    .bind(&config_clone.name)
-                    .bind(&config_clone.provider_type)
-                    .bind(&config_json)
-                    .bind(config_clone.refresh_interval)
-                    .bind(id)
-                    .bind(expected_version)
-                    .execute(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(format!("Failed to update provider: {}", e)))?
-                    .rows_affected()
-                }
-            };
+        let rows_affected = with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(&config.name)
+                .bind(&config.provider_type)
+                .bind(&config_json)
+                .bind(config.refresh_interval)
+                .bind(id)
+                .bind(expected_version)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| {
+                    DomainError::DatabaseError(format!("Failed to update provider: {}", e))
+                })?
+                .rows_affected()
+        });
 
-            let success = rows_affected > 0;
+        let success = rows_affected > 0;
 
-            if success {
-                token_store.store_token(id, &token_clone).await?;
-            }
+        if success {
+            self.token_store.store_token(id, &config.token).await?;
+            let mut secrets = config.config.clone();
+            store_config_secrets(self.token_store.as_ref(), id, &mut secrets).await?;
+        }
+
+        Ok(success)
+    }
+
+    pub async fn set_provider_enabled(&self, id: i64, enabled: bool) -> DomainResult<()> {
+        let sql = self.sql("UPDATE providers SET enabled = ?, updated_at = NOW() WHERE id = ?");
+        let rows_affected = with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(enabled)
+                .bind(id)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+                .rows_affected()
+        });
 
-            Ok(success)
-        }).await
+        if rows_affected == 0 {
+            return Err(DomainError::ProviderNotFound(id.to_string()));
+        }
+        Ok(())
     }
 
     pub async fn remove_provider(&self, id: i64) -> DomainResult<()> {
@@ -337,75 +658,74 @@ impl Repository {
             .unwrap_or_default();
         let pipeline_ids: Vec<String> = pipelines.iter().map(|p| p.id.clone()).collect();
 
-        let run_history_sql = format!(
-            "DELETE FROM run_history_cache WHERE pipeline_id IN (SELECT id FROM pipelines_cache WHERE provider_id = {})",
-            self.placeholder(1)
-        );
-        match &self.cache_pool {
-            DatabasePool::Sqlite(p) => {
-                let _ = sqlx::query(&run_history_sql)
-                    .bind(id)
-                    .execute(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()));
-            }
-            DatabasePool::Postgres(p) => {
-                let _ = sqlx::query(&run_history_sql)
-                    .bind(id)
-                    .execute(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()));
-            }
-        }
+        let run_history_sql = self.sql("DELETE FROM run_history_cache WHERE pipeline_id IN (SELECT id FROM pipelines_cache WHERE provider_id = ?)");
+        with_write_connection!(self, |conn| {
+            let _ = sqlx::query(&run_history_sql)
+                .bind(id)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()));
+        });
 
-        for pipeline_id in pipeline_ids {
-            let workflow_params_sql = format!(
-                "DELETE FROM workflow_parameters_cache WHERE workflow_id LIKE {}",
-                self.placeholder(1)
-            );
-            match &self.cache_pool {
-                DatabasePool::Sqlite(p) => {
-                    let _ = sqlx::query(&workflow_params_sql)
-                        .bind(format!("{}%", pipeline_id))
-                        .execute(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()));
-                }
-                DatabasePool::Postgres(p) => {
-                    let _ = sqlx::query(&workflow_params_sql)
-                        .bind(format!("{}%", pipeline_id))
-                        .execute(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()));
-                }
-            }
-        }
+        let branch_status_sql = self.sql("DELETE FROM branch_status_cache WHERE pipeline_id IN (SELECT id FROM pipelines_cache WHERE provider_id = ?)");
+        with_write_connection!(self, |conn| {
+            let _ = sqlx::query(&branch_status_sql)
+                .bind(id)
+                .execute(&mut *conn)
+                .await;
+        });
 
-        let pipelines_sql = format!(
-            "DELETE FROM pipelines_cache WHERE provider_id = {}",
-            self.placeholder(1)
-        );
-        match &self.cache_pool {
-            DatabasePool::Sqlite(p) => {
-                let _ = sqlx::query(&pipelines_sql)
-                    .bind(id)
-                    .execute(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()));
-            }
-            DatabasePool::Postgres(p) => {
-                let _ = sqlx::query(&pipelines_sql)
-                    .bind(id)
-                    .execute(p)
+        let usage_sql = self.sql("DELETE FROM pipeline_usage WHERE pipeline_id IN (SELECT id FROM pipelines_cache WHERE provider_id = ?)");
+        with_write_connection!(self, |conn| {
+            let _ = sqlx::query(&usage_sql).bind(id).execute(&mut *conn).await;
+        });
+
+        let health_sql = self.sql("DELETE FROM provider_health_checks WHERE provider_id = ?");
+        with_write_connection!(self, |conn| {
+            let _ = sqlx::query(&health_sql).bind(id).execute(&mut *conn).await;
+        });
+
+        let agents_sql = self.sql("DELETE FROM agents_cache WHERE provider_id = ?");
+        with_write_connection!(self, |conn| {
+            let _ = sqlx::query(&agents_sql).bind(id).execute(&mut *conn).await;
+        });
+
+        let backfill_sql = self.sql("DELETE FROM run_backfill_jobs WHERE provider_id = ?");
+        with_write_connection!(self, |conn| {
+            let _ = sqlx::query(&backfill_sql)
+                .bind(id)
+                .execute(&mut *conn)
+                .await;
+        });
+
+        for pipeline_id in pipeline_ids {
+            let workflow_params_sql =
+                self.sql("DELETE FROM workflow_parameters_cache WHERE workflow_id LIKE ?");
+            with_write_connection!(self, |conn| {
+                let _ = sqlx::query(&workflow_params_sql)
+                    .bind(format!("{}%", pipeline_id))
+                    .execute(&mut *conn)
                     .await
                     .map_err(|e| DomainError::DatabaseError(e.to_string()));
-            }
+            })
         }
 
+        let pipelines_sql = self.sql("DELETE FROM pipelines_cache WHERE provider_id = ?");
+        with_write_connection!(self, |conn| {
+            let _ = sqlx::query(&pipelines_sql)
+                .bind(id)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()));
+        });
+
         self.config_backend.delete_provider(id).await?;
 
         let token_store = self.token_store.clone();
         tokio::spawn(async move {
+            if let Err(e) = delete_config_secrets(token_store.as_ref(), id).await {
+                tracing::warn!(provider_id = id, error = %e, "Failed to delete provider secrets");
+            }
             if let Err(e) = token_store.delete_token(id).await {
                 tracing::warn!(
                     provider_id = id,
@@ -426,247 +746,497 @@ impl Repository {
     pub async fn update_provider_fetch_status(
         &self, provider_id: i64, success: bool, error: Option<String>,
     ) -> DomainResult<bool> {
-        let cache_pool = self.cache_pool.clone();
-        let error_clone = error.clone();
-
-        retry_on_busy(|| async {
-            let now = chrono::Utc::now();
-            let new_status = if success {
-                FETCH_STATUS_SUCCESS
-            } else {
-                FETCH_STATUS_ERROR
-            };
+        let now = chrono::Utc::now();
+        let new_status = if success {
+            FETCH_STATUS_SUCCESS
+        } else {
+            FETCH_STATUS_ERROR
+        };
 
-            let select_sql = match cache_pool {
-                DatabasePool::Sqlite(_) => {
-                    "SELECT last_fetch_status, last_fetch_error FROM providers WHERE id = ?".to_string()
-                }
-                DatabasePool::Postgres(_) => {
-                    "SELECT last_fetch_status, last_fetch_error FROM providers WHERE id = $1".to_string()
-                }
-            };
+        let select_sql =
+            self.sql("SELECT last_fetch_status, last_fetch_error FROM providers WHERE id = ?");
 
-            let status_changed = match &cache_pool {
-                DatabasePool::Sqlite(p) => {
-                    let current = sqlx::query(&select_sql)
-                        .bind(provider_id)
-                        .fetch_optional(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let status_changed = with_write_connection!(self, |conn| {
+            let current = sqlx::query(&select_sql)
+                .bind(provider_id)
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-                    if let Some(row) = current {
-                        let current_status: String =
-                            row.try_get("last_fetch_status").unwrap_or_default();
-                        let current_error: Option<String> =
-                            row.try_get("last_fetch_error").ok().flatten();
-                        current_status != new_status || current_error != error_clone
-                    } else {
-                        true
-                    }
-                }
-                DatabasePool::Postgres(p) => {
-                    let current = sqlx::query(&select_sql)
-                        .bind(provider_id)
-                        .fetch_optional(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            if let Some(row) = current {
+                let current_status: String = row.try_get("last_fetch_status").unwrap_or_default();
+                let current_error: Option<String> = row.try_get("last_fetch_error").ok().flatten();
+                current_status != new_status || current_error != error
+            } else {
+                true
+            }
+        });
 
-                    if let Some(row) = current {
-                        let current_status: String =
-                            row.try_get("last_fetch_status").unwrap_or_default();
-                        let current_error: Option<String> =
-                            row.try_get("last_fetch_error").ok().flatten();
-                        current_status != new_status || current_error != error_clone
-                    } else {
-                        true
-                    }
-                }
-            };
+        if status_changed {
+            let update_sql = self.sql(
+                "UPDATE providers SET last_fetch_at = ?, last_fetch_status = ?, last_fetch_error = ? WHERE id = ?",
+            );
+            with_write_connection!(self, |conn| {
+                sqlx::query(&update_sql)
+                    .bind(now)
+                    .bind(new_status)
+                    .bind(error.as_ref())
+                    .bind(provider_id)
+                    .execute(&mut *conn)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            })
+        }
 
-            if status_changed {
-                match &cache_pool {
-                    DatabasePool::Sqlite(p) => {
-                        let update_sql = "UPDATE providers SET last_fetch_at = ?, last_fetch_status = ?, last_fetch_error = ? WHERE id = ?";
-                        sqlx::query(update_sql)
-                            .bind(now.to_rfc3339())
-                            .bind(new_status)
-                            .bind(error_clone.as_ref())
-                            .bind(provider_id)
-                            .execute(p)
-                            .await
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    }
-                    DatabasePool::Postgres(p) => {
-                        let update_sql = "UPDATE providers SET last_fetch_at = $1, last_fetch_status = $2, last_fetch_error = $3 WHERE id = $4";
-                        sqlx::query(update_sql)
-                            .bind(now)
-                            .bind(new_status)
-                            .bind(error_clone.as_ref())
-                            .bind(provider_id)
-                            .execute(p)
-                            .await
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    }
-                }
-            }
+        // Every fetch is kept for health scoring, not just status changes.
+        let retention_cutoff = now - chrono::Duration::days(PROVIDER_HEALTH_RETENTION_DAYS);
+        let insert_sql = self.sql(
+            "INSERT INTO provider_health_checks (provider_id, success, error, checked_at) VALUES (?, ?, ?, ?)",
+        );
+        let prune_sql =
+            self.sql("DELETE FROM provider_health_checks WHERE provider_id = ? AND checked_at < ?");
+        with_write_connection!(self, |conn| {
+            sqlx::query(&insert_sql)
+                .bind(provider_id)
+                .bind(success)
+                .bind(error.as_ref())
+                .bind(now)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            sqlx::query(&prune_sql)
+                .bind(provider_id)
+                .bind(retention_cutoff)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
 
-            Ok(status_changed)
-        }).await
+        Ok(status_changed)
     }
 
-    pub async fn get_provider_fetch_status(
-        &self, provider_id: i64,
-    ) -> DomainResult<(String, Option<String>, Option<String>)> {
+    // Newest first.
+    pub async fn get_provider_health_checks(
+        &self, provider_id: i64, limit: usize,
+    ) -> DomainResult<Vec<ProviderHealthCheck>> {
         let sql = format!(
-            "SELECT last_fetch_status, last_fetch_error, last_fetch_at FROM providers WHERE id = {}",
-            self.placeholder(1)
+            "SELECT success, error, checked_at FROM provider_health_checks WHERE provider_id = {} ORDER BY checked_at DESC, id DESC LIMIT {}",
+            self.placeholder(1),
+            limit
         );
+
         match &self.cache_pool {
             DatabasePool::Sqlite(p) => {
-                let row = sqlx::query(&sql)
+                let rows: Vec<(bool, Option<String>, String)> = sqlx::query_as(&sql)
                     .bind(provider_id)
-                    .fetch_one(p)
+                    .fetch_all(p)
                     .await
-                    .map_err(|_e| DomainError::ProviderNotFound(provider_id.to_string()))?;
-
-                let status: String = row
-                    .try_get(0)
-                    .unwrap_or_else(|_| FETCH_STATUS_NEVER.to_string());
-                let error: Option<String> = row.try_get(1).ok().flatten();
-                let fetch_at: Option<String> = row.try_get(2).ok().flatten();
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-                Ok((status, error, fetch_at))
+                Ok(rows
+                    .into_iter()
+                    .filter_map(|(success, error, checked_at)| {
+                        Some(ProviderHealthCheck {
+                            success,
+                            error,
+                            checked_at: DateTime::parse_from_rfc3339(&checked_at)
+                                .ok()?
+                                .with_timezone(&Utc),
+                        })
+                    })
+                    .collect())
             }
             DatabasePool::Postgres(p) => {
-                let row = sqlx::query(&sql)
+                let rows: Vec<(bool, Option<String>, DateTime<Utc>)> = sqlx::query_as(&sql)
                     .bind(provider_id)
-                    .fetch_one(p)
+                    .fetch_all(p)
                     .await
-                    .map_err(|_e| DomainError::ProviderNotFound(provider_id.to_string()))?;
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-                let status: String = row
-                    .try_get(0)
-                    .unwrap_or_else(|_| FETCH_STATUS_NEVER.to_string());
-                let error: Option<String> = row.try_get(1).ok().flatten();
-                let fetch_at: Option<String> = row.try_get(2).ok().flatten();
-
-                Ok((status, error, fetch_at))
+                Ok(rows
+                    .into_iter()
+                    .map(|(success, error, checked_at)| ProviderHealthCheck {
+                        success,
+                        error,
+                        checked_at,
+                    })
+                    .collect())
             }
         }
     }
 
+    pub async fn is_provider_in_outage(&self, provider_id: i64) -> DomainResult<bool> {
+        let checks = self
+            .get_provider_health_checks(provider_id, PROVIDER_OUTAGE_THRESHOLD)
+            .await?;
+        Ok(checks.len() == PROVIDER_OUTAGE_THRESHOLD && checks.iter().all(|c| !c.success))
+    }
+
+    pub async fn get_provider_fetch_status(
+        &self, provider_id: i64,
+    ) -> DomainResult<(String, Option<String>, Option<String>)> {
+        let sql = self.sql(
+            "SELECT last_fetch_status, last_fetch_error, last_fetch_at FROM providers WHERE id = ?",
+        );
+        with_pool!(self, |p| {
+            let row = sqlx::query(&sql)
+                .bind(provider_id)
+                .fetch_one(p)
+                .await
+                .map_err(|_e| DomainError::ProviderNotFound(provider_id.to_string()))?;
+
+            let status: String = row
+                .try_get(0)
+                .unwrap_or_else(|_| FETCH_STATUS_NEVER.to_string());
+            let error: Option<String> = row.try_get(1).ok().flatten();
+            let fetch_at: Option<String> = row.try_get(2).ok().flatten();
+
+            Ok((status, error, fetch_at))
+        })
+    }
+
+    pub async fn store_provider_server_version(
+        &self, provider_id: i64, version: &pipedash_plugin_api::ServerVersion,
+    ) -> DomainResult<()> {
+        let json = serde_json::to_string(version)
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let sql = self.sql("UPDATE providers SET server_version = ? WHERE id = ?");
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(json)
+                .bind(provider_id)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+        Ok(())
+    }
+
+    // Stored server versions by provider; unreadable ones are skipped.
+    pub async fn get_provider_server_versions(
+        &self,
+    ) -> DomainResult<HashMap<i64, pipedash_plugin_api::ServerVersion>> {
+        let sql = "SELECT id, server_version FROM providers WHERE server_version IS NOT NULL";
+        let rows: Vec<(i64, String)> = with_pool!(self, |p| sqlx::query_as(sql)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, json)| Some((id, serde_json::from_str(&json).ok()?)))
+            .collect())
+    }
+
     pub async fn get_cached_pipelines(
         &self, provider_id: Option<i64>,
+    ) -> DomainResult<Vec<Pipeline>> {
+        let mut pipelines = self.query_cached_pipelines(provider_id).await?;
+        self.attach_labels(&mut pipelines).await?;
+        Ok(pipelines)
+    }
+
+    pub async fn query_pipelines_page(
+        &self, query: &PipelineListQuery,
+    ) -> DomainResult<PaginatedResponse<Pipeline>> {
+        let mut conditions = Vec::new();
+        let mut text_args: Vec<String> = Vec::new();
+        let mut index = 0;
+        let mut next_placeholder = || {
+            index += 1;
+            self.placeholder(index)
+        };
+
+        if query.provider_id.is_some() {
+            conditions.push(format!("provider_id = {}", next_placeholder()));
+        }
+
+        if !query.statuses.is_empty() {
+            let placeholders: Vec<String> = query
+                .statuses
+                .iter()
+                .map(|status| {
+                    text_args.push(status.as_str().to_string());
+                    next_placeholder()
+                })
+                .collect();
+            conditions.push(format!("status IN ({})", placeholders.join(", ")));
+        }
+
+        if let Some(branch) = query.branch.as_deref().filter(|b| !b.is_empty()) {
+            text_args.push(branch.to_string());
+            conditions.push(format!("branch = {}", next_placeholder()));
+        }
+
+        for label in query
+            .labels
+            .iter()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+        {
+            text_args.push(label.to_string());
+            let mut matcher = format!("l.label = {}", next_placeholder());
+            if !label.contains(':') {
+                text_args.push(format!("{}:%", label));
+                matcher = format!("({} OR l.label LIKE {})", matcher, next_placeholder());
+            }
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM pipeline_labels l WHERE l.pipeline_id = pipelines_cache.id AND {})",
+                matcher
+            ));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let count_sql = format!("SELECT COUNT(*) FROM pipelines_cache {}", where_clause);
+        let sql = format!(
+            "SELECT id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, provider_type, metadata_json
+             FROM pipelines_cache
+             {}
+             ORDER BY {} {}, id ASC
+             LIMIT {} OFFSET {}",
+            where_clause,
+            query.sort_by.column(),
+            query.sort_order.as_sql(),
+            query.page_size,
+            query.offset()
+        );
+
+        let (total_count, mut pipelines) = with_pool!(self, |p| {
+            let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql);
+            let mut rows_query = sqlx::query(&sql);
+            if let Some(pid) = query.provider_id {
+                count_query = count_query.bind(pid);
+                rows_query = rows_query.bind(pid);
+            }
+            for arg in &text_args {
+                count_query = count_query.bind(arg);
+                rows_query = rows_query.bind(arg);
+            }
+
+            let total_count = count_query
+                .fetch_one(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            let rows = rows_query
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            let pipelines = rows
+                .iter()
+                .map(|row| self.pipeline_from_row(row))
+                .collect::<DomainResult<Vec<_>>>()?;
+            (total_count, pipelines)
+        });
+
+        self.attach_labels(&mut pipelines).await?;
+        Ok(PaginatedResponse::new(
+            pipelines,
+            query.page,
+            query.page_size,
+            total_count as usize,
+        ))
+    }
+
+    async fn attach_labels(&self, pipelines: &mut [Pipeline]) -> DomainResult<()> {
+        let mut labels = self.get_pipeline_labels().await?;
+        for pipeline in pipelines {
+            if let Some(pipeline_labels) = labels.remove(&pipeline.id) {
+                pipeline.labels = pipeline_labels;
+            }
+        }
+        Ok(())
+    }
+
+    async fn query_cached_pipelines(
+        &self, provider_id: Option<i64>,
     ) -> DomainResult<Vec<Pipeline>> {
         if let Some(pid) = provider_id {
-            let sql = format!(
-                "SELECT id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, provider_type
+            let sql = self.sql("SELECT id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, provider_type, metadata_json
                 FROM pipelines_cache
-                WHERE provider_id = {}
-                ORDER BY last_updated DESC",
-                self.placeholder(1)
-            );
-            match &self.cache_pool {
-                DatabasePool::Sqlite(p) => {
-                    let rows = sqlx::query(&sql)
-                        .bind(pid)
-                        .fetch_all(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    rows.iter().map(|row| self.pipeline_from_row(row)).collect()
-                }
-                DatabasePool::Postgres(p) => {
-                    let rows = sqlx::query(&sql)
-                        .bind(pid)
-                        .fetch_all(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    rows.iter().map(|row| self.pipeline_from_row(row)).collect()
-                }
-            }
+                WHERE provider_id = ?
+                ORDER BY last_updated DESC");
+            with_pool!(self, |p| {
+                let rows = sqlx::query(&sql)
+                    .bind(pid)
+                    .fetch_all(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                rows.iter().map(|row| self.pipeline_from_row(row)).collect()
+            })
         } else {
-            let sql = "SELECT id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, provider_type
+            let sql = "SELECT id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, provider_type, metadata_json
                 FROM pipelines_cache
                 ORDER BY last_updated DESC";
-            match &self.cache_pool {
-                DatabasePool::Sqlite(p) => {
-                    let rows = sqlx::query(sql)
-                        .fetch_all(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    rows.iter().map(|row| self.pipeline_from_row(row)).collect()
-                }
-                DatabasePool::Postgres(p) => {
-                    let rows = sqlx::query(sql)
-                        .fetch_all(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    rows.iter().map(|row| self.pipeline_from_row(row)).collect()
-                }
-            }
+            with_pool!(self, |p| {
+                let rows = sqlx::query(sql)
+                    .fetch_all(p)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                rows.iter().map(|row| self.pipeline_from_row(row)).collect()
+            })
         }
     }
 
+    pub async fn search_pipelines(
+        &self, query: &SearchQuery, limit: usize,
+    ) -> DomainResult<Vec<Pipeline>> {
+        let mut pipelines = match &self.cache_pool {
+            DatabasePool::Sqlite(p) => {
+                let rows = sqlx::query(
+                    "SELECT p.id, p.provider_id, p.name, p.status, p.repository, p.branch, p.workflow_file, p.last_run, p.last_updated, p.provider_type, p.metadata_json
+                     FROM pipelines_fts
+                     JOIN pipelines_cache p ON p.rowid = pipelines_fts.rowid
+                     WHERE pipelines_fts MATCH ?
+                     ORDER BY bm25(pipelines_fts)
+                     LIMIT ?",
+                )
+                .bind(query.fts5_expression())
+                .bind(limit as i64)
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                rows.iter()
+                    .map(|row| self.pipeline_from_row(row))
+                    .collect::<DomainResult<Vec<_>>>()?
+            }
+            DatabasePool::Postgres(p) => {
+                let rows = sqlx::query(&format!(
+                    "SELECT id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, provider_type, metadata_json
+                     FROM pipelines_cache
+                     WHERE {vector} @@ to_tsquery('simple', $1)
+                     ORDER BY ts_rank({vector}, to_tsquery('simple', $1)) DESC
+                     LIMIT $2",
+                    vector = PIPELINE_SEARCH_VECTOR
+                ))
+                .bind(query.tsquery_expression())
+                .bind(limit as i64)
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                rows.iter()
+                    .map(|row| self.pipeline_from_row(row))
+                    .collect::<DomainResult<Vec<_>>>()?
+            }
+        };
+
+        self.attach_labels(&mut pipelines).await?;
+        Ok(pipelines)
+    }
+
+    pub async fn search_runs(
+        &self, query: &SearchQuery, limit: usize,
+    ) -> DomainResult<Vec<PipelineRun>> {
+        let rows = match &self.cache_pool {
+            DatabasePool::Sqlite(p) => sqlx::query_scalar::<_, String>(
+                "SELECT r.run_data
+                 FROM runs_fts
+                 JOIN run_history_cache r ON r.rowid = runs_fts.rowid
+                 WHERE runs_fts MATCH ?
+                 ORDER BY bm25(runs_fts)
+                 LIMIT ?",
+            )
+            .bind(query.fts5_expression())
+            .bind(limit as i64)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+            DatabasePool::Postgres(p) => sqlx::query_scalar::<_, String>(&format!(
+                "SELECT run_data
+                 FROM run_history_cache
+                 WHERE {vector} @@ to_tsquery('simple', $1)
+                 ORDER BY ts_rank({vector}, to_tsquery('simple', $1)) DESC
+                 LIMIT $2",
+                vector = RUN_SEARCH_VECTOR
+            ))
+            .bind(query.tsquery_expression())
+            .bind(limit as i64)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+        };
+
+        Ok(rows
+            .iter()
+            .filter_map(|json| self.decode_run(json))
+            .collect())
+    }
+
+    // Cached runs whose commit SHA starts with `prefix`, which must already be
+    // lowercase hex (see `normalize_commit_prefix`).
+    pub async fn get_runs_by_commit(
+        &self, prefix: &str, limit: usize,
+    ) -> DomainResult<Vec<PipelineRun>> {
+        let rows = match &self.cache_pool {
+            // '~' sorts after every hex digit, so this is a prefix range.
+            DatabasePool::Sqlite(p) => sqlx::query_scalar::<_, String>(&format!(
+                "SELECT run_data
+                 FROM run_history_cache
+                 WHERE json_valid(run_data) AND {sha} >= ? AND {sha} < ?
+                 LIMIT ?",
+                sha = SQLITE_RUN_COMMIT_SHA
+            ))
+            .bind(prefix)
+            .bind(format!("{}~", prefix))
+            .bind(limit as i64)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+            DatabasePool::Postgres(p) => sqlx::query_scalar::<_, String>(&format!(
+                "SELECT run_data
+                 FROM run_history_cache
+                 WHERE {sha} LIKE $1
+                 LIMIT $2",
+                sha = POSTGRES_RUN_COMMIT_SHA
+            ))
+            .bind(format!("{}%", prefix))
+            .bind(limit as i64)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+        };
+
+        Ok(rows
+            .iter()
+            .filter_map(|json| self.decode_run(json))
+            .collect())
+    }
+
     pub async fn cache_workflow_parameters(
         &self, workflow_id: &str, parameters: &[pipedash_plugin_api::WorkflowParameter],
     ) -> DomainResult<()> {
-        let workflow_id_owned = workflow_id.to_string();
-        let parameters_vec = parameters.to_vec();
-        let cache_pool = self.cache_pool.clone();
-        let is_postgres = cache_pool.is_postgres();
-
-        retry_on_busy(|| async {
-            let parameters_json = serde_json::to_string(&parameters_vec)
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let parameters_json = serde_json::to_string(parameters)
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-            let sql = if is_postgres {
-                "INSERT INTO workflow_parameters_cache (workflow_id, parameters_json, cached_at) VALUES ($1, $2, NOW()) ON CONFLICT (workflow_id) DO UPDATE SET parameters_json = EXCLUDED.parameters_json, cached_at = EXCLUDED.cached_at"
-            } else {
-                "INSERT OR REPLACE INTO workflow_parameters_cache (workflow_id, parameters_json, cached_at) VALUES (?, ?, datetime('now'))"
-            };
+        let sql = self.sql(
+            "INSERT INTO workflow_parameters_cache (workflow_id, parameters_json, cached_at) VALUES (?, ?, NOW()) ON CONFLICT (workflow_id) DO UPDATE SET parameters_json = EXCLUDED.parameters_json, cached_at = EXCLUDED.cached_at",
+        );
 
-            match &cache_pool {
-                DatabasePool::Sqlite(p) => {
-                    sqlx::query(sql)
-                        .bind(&workflow_id_owned)
-                        .bind(&parameters_json)
-                        .execute(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                }
-                DatabasePool::Postgres(p) => {
-                    sqlx::query(sql)
-                        .bind(&workflow_id_owned)
-                        .bind(&parameters_json)
-                        .execute(p)
-                        .await
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                }
-            }
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(workflow_id)
+                .bind(&parameters_json)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
 
-            Ok(())
-        }).await
+        Ok(())
     }
 
     pub async fn get_cached_workflow_parameters(
         &self, workflow_id: &str,
     ) -> DomainResult<Option<Vec<pipedash_plugin_api::WorkflowParameter>>> {
-        let sql = format!(
-            "SELECT parameters_json FROM workflow_parameters_cache WHERE workflow_id = {}",
-            self.placeholder(1)
-        );
-        let result = match &self.cache_pool {
-            DatabasePool::Sqlite(p) => sqlx::query_scalar::<_, String>(&sql)
-                .bind(workflow_id)
-                .fetch_optional(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-            DatabasePool::Postgres(p) => sqlx::query_scalar::<_, String>(&sql)
-                .bind(workflow_id)
-                .fetch_optional(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-        };
+        let sql =
+            self.sql("SELECT parameters_json FROM workflow_parameters_cache WHERE workflow_id = ?");
+        let result = with_pool!(self, |p| sqlx::query_scalar::<_, String>(&sql)
+            .bind(workflow_id)
+            .fetch_optional(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
 
         match result {
             Some(json) => {
@@ -688,22 +1258,128 @@ impl Repository {
     }
 
     pub async fn clear_workflow_parameters_cache(&self) -> DomainResult<()> {
-        match &self.cache_pool {
-            DatabasePool::Sqlite(p) => {
-                sqlx::query("DELETE FROM workflow_parameters_cache")
-                    .execute(p)
+        with_write_connection!(self, |conn| {
+            sqlx::query("DELETE FROM workflow_parameters_cache")
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+
+        Ok(())
+    }
+
+    // Seals the run and pipeline payloads still stored in the clear. With
+    // `previous`, payloads sealed under that key are re-sealed under the
+    // current one as well, which carries the cache over a vault password
+    // rotation. Returns the number of rows rewritten.
+    pub async fn seal_cached_payloads(
+        &self, previous: Option<&CacheCipher>,
+    ) -> DomainResult<usize> {
+        let Some(cipher) = self.cipher() else {
+            return Ok(0);
+        };
+        // Payloads are never empty, so an empty pattern selects every row.
+        let pattern = match previous {
+            Some(_) => String::new(),
+            None => format!("{}%", SEALED_PREFIX),
+        };
+        let mut rewritten = 0;
+
+        let sql = self
+            .sql("SELECT id, metadata_json FROM pipelines_cache WHERE metadata_json NOT LIKE ?");
+        let rows: Vec<(String, String)> = with_pool!(self, |p| sqlx::query_as(&sql)
+            .bind(&pattern)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+        let mut updates = Vec::new();
+        for (id, stored) in rows {
+            if let Some(sealed) = reseal_payload(&stored, &cipher, previous, false)? {
+                updates.push((sealed, id));
+            }
+        }
+        rewritten += updates.len();
+
+        let sql = self.sql("UPDATE pipelines_cache SET metadata_json = ? WHERE id = ?");
+        with_write_connection!(self, |conn| {
+            let mut tx = conn
+                .begin()
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            for (sealed, id) in &updates {
+                sqlx::query(&sql)
+                    .bind(sealed)
+                    .bind(id)
+                    .execute(&mut *tx)
                     .await
                     .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
             }
-            DatabasePool::Postgres(p) => {
-                sqlx::query("DELETE FROM workflow_parameters_cache")
-                    .execute(p)
+            tx.commit()
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+
+        // Run history is done a pipeline at a time to bound memory use.
+        let sql = self
+            .sql("SELECT DISTINCT pipeline_id FROM run_history_cache WHERE run_data NOT LIKE ?");
+        let pipeline_ids: Vec<String> = with_pool!(self, |p| sqlx::query_scalar(&sql)
+            .bind(&pattern)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+
+        let select_sql = self.sql(
+            "SELECT CAST(run_number AS BIGINT), run_data FROM run_history_cache
+             WHERE pipeline_id = ? AND run_data NOT LIKE ?",
+        );
+        let update_sql = self.sql(
+            "UPDATE run_history_cache SET run_data = ? WHERE pipeline_id = ? AND run_number = ?",
+        );
+        for pipeline_id in &pipeline_ids {
+            let rows: Vec<(i64, String)> = with_pool!(self, |p| sqlx::query_as(&select_sql)
+                .bind(pipeline_id)
+                .bind(&pattern)
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+            let mut updates = Vec::new();
+            for (run_number, stored) in rows {
+                if let Some(sealed) = reseal_payload(&stored, &cipher, previous, true)? {
+                    updates.push((sealed, run_number));
+                }
+            }
+            rewritten += updates.len();
+
+            with_write_connection!(self, |conn| {
+                let mut tx = conn
+                    .begin()
                     .await
                     .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-            }
+                for (sealed, run_number) in &updates {
+                    sqlx::query(&update_sql)
+                        .bind(sealed)
+                        .bind(pipeline_id)
+                        .bind(run_number)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                }
+                tx.commit()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            })
         }
 
-        Ok(())
+        Ok(rewritten)
+    }
+
+    // Switches cached payloads to a key derived from a new vault password.
+    pub async fn rotate_cache_key(&self, new_password: &str) -> DomainResult<usize> {
+        let Some(previous) = self.cipher() else {
+            return Ok(0);
+        };
+        self.set_cache_cipher(CacheCipher::derive(new_password).await?);
+        self.seal_cached_payloads(Some(&previous)).await
     }
 
     pub async fn cache_run_history(
@@ -714,126 +1390,130 @@ impl Repository {
         }
 
         let start = std::time::Instant::now();
-        let pipeline_id_str = pipeline_id.to_string();
-        let runs_vec = runs.to_vec();
-        let pool = self.cache_pool.clone();
-
-        retry_on_busy(|| {
-            let pipeline_id_clone = pipeline_id_str.clone();
-            let runs_clone = runs_vec.clone();
-            let pool_clone = pool.clone();
-            async move {
-
-                const BATCH_SIZE: usize = 100;
-
-                match &pool_clone {
-                    DatabasePool::Sqlite(p) => {
-                        let mut tx = p.begin().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-
-                        for chunk in runs_clone.chunks(BATCH_SIZE) {
-                            let prepared_data: Vec<(i64, String, String)> = chunk
-                                .iter()
-                                .map(|run| {
-                                    let run_data = serde_json::to_string(run)
-                                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                                    let status_str = run.status.as_str();
-                                    let run_hash = hash_pipeline_run(
-                                        run.run_number,
-                                        status_str,
-                                        run.branch.as_deref(),
-                                        &run.started_at.to_rfc3339(),
-                                        run.duration_seconds,
-                                        run.commit_sha.as_deref(),
-                                    );
-                                    Ok((run.run_number, run_data, run_hash))
-                                })
-                                .collect::<Result<Vec<_>, DomainError>>()?;
-
-                            let values_clause = prepared_data
-                                .iter()
-                                .map(|_| "(?, ?, ?, datetime('now'), ?)")
-                                .collect::<Vec<_>>()
-                                .join(", ");
-
-                            let sql = format!(
-                                "INSERT OR REPLACE INTO run_history_cache (pipeline_id, run_number, run_data, fetched_at, run_hash) VALUES {}",
-                                values_clause
-                            );
 
-                            let mut query = sqlx::query(&sql);
-                            for (run_number, run_data, run_hash) in &prepared_data {
-                                query = query
-                                    .bind(&pipeline_id_clone)
-                                    .bind(run_number)
-                                    .bind(run_data)
-                                    .bind(run_hash);
-                            }
+        const BATCH_SIZE: usize = 100;
 
-                            query.execute(&mut *tx).await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                        }
+        match &self.cache_pool {
+            DatabasePool::Sqlite(_) => {
+                let mut conn = self.write_connection().await?;
+                let mut tx = conn
+                    .begin()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-                        tx.commit().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    }
-                    DatabasePool::Postgres(p) => {
-                        let mut tx = p.begin().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-
-                        for chunk in runs_clone.chunks(BATCH_SIZE) {
-                            let prepared_data: Vec<(i64, String, String)> = chunk
-                                .iter()
-                                .map(|run| {
-                                    let run_data = serde_json::to_string(run)
-                                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                                    let status_str = run.status.as_str();
-                                    let run_hash = hash_pipeline_run(
-                                        run.run_number,
-                                        status_str,
-                                        run.branch.as_deref(),
-                                        &run.started_at.to_rfc3339(),
-                                        run.duration_seconds,
-                                        run.commit_sha.as_deref(),
-                                    );
-                                    Ok((run.run_number, run_data, run_hash))
-                                })
-                                .collect::<Result<Vec<_>, DomainError>>()?;
-
-                            let mut param_idx = 0;
-                            let values_clauses: Vec<String> = prepared_data
-                                .iter()
-                                .map(|_| {
-                                    let clause = format!(
-                                        "(${}, ${}, ${}, NOW(), ${})",
-                                        param_idx + 1, param_idx + 2, param_idx + 3, param_idx + 4
-                                    );
-                                    param_idx += 4;
-                                    clause
-                                })
-                                .collect();
-
-                            let sql = format!(
-                                "INSERT INTO run_history_cache (pipeline_id, run_number, run_data, fetched_at, run_hash) VALUES {} ON CONFLICT (pipeline_id, run_number) DO UPDATE SET run_data = EXCLUDED.run_data, fetched_at = EXCLUDED.fetched_at, run_hash = EXCLUDED.run_hash",
-                                values_clauses.join(", ")
+                for chunk in runs.chunks(BATCH_SIZE) {
+                    let prepared_data: Vec<(i64, String, String)> = chunk
+                        .iter()
+                        .map(|run| {
+                            let run_data = self.encode_run(run)?;
+                            let status_str = run.status.as_str();
+                            let run_hash = hash_pipeline_run(
+                                run.run_number,
+                                status_str,
+                                run.branch.as_deref(),
+                                &run.started_at.to_rfc3339(),
+                                run.duration_seconds,
+                                run.commit_sha.as_deref(),
                             );
+                            Ok((run.run_number, run_data, run_hash))
+                        })
+                        .collect::<Result<Vec<_>, DomainError>>()?;
+
+                    let values_clause = prepared_data
+                        .iter()
+                        .map(|_| "(?, ?, ?, datetime('now'), ?)")
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    let sql = format!(
+                        "INSERT OR REPLACE INTO run_history_cache (pipeline_id, run_number, run_data, fetched_at, run_hash) VALUES {}",
+                        values_clause
+                    );
 
-                            let mut query = sqlx::query(&sql);
-                            for (run_number, run_data, run_hash) in &prepared_data {
-                                query = query
-                                    .bind(&pipeline_id_clone)
-                                    .bind(run_number)
-                                    .bind(run_data)
-                                    .bind(run_hash);
-                            }
+                    let mut query = sqlx::query(&sql);
+                    for (run_number, run_data, run_hash) in &prepared_data {
+                        query = query
+                            .bind(pipeline_id)
+                            .bind(run_number)
+                            .bind(run_data)
+                            .bind(run_hash);
+                    }
 
-                            query.execute(&mut *tx).await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                        }
+                    query
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                }
 
-                        tx.commit().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                tx.commit()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            }
+            DatabasePool::Postgres(p) => {
+                let mut tx = p
+                    .begin()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                for chunk in runs.chunks(BATCH_SIZE) {
+                    let prepared_data: Vec<(i64, String, String)> = chunk
+                        .iter()
+                        .map(|run| {
+                            let run_data = self.encode_run(run)?;
+                            let status_str = run.status.as_str();
+                            let run_hash = hash_pipeline_run(
+                                run.run_number,
+                                status_str,
+                                run.branch.as_deref(),
+                                &run.started_at.to_rfc3339(),
+                                run.duration_seconds,
+                                run.commit_sha.as_deref(),
+                            );
+                            Ok((run.run_number, run_data, run_hash))
+                        })
+                        .collect::<Result<Vec<_>, DomainError>>()?;
+
+                    let mut param_idx = 0;
+                    let values_clauses: Vec<String> = prepared_data
+                        .iter()
+                        .map(|_| {
+                            let clause = format!(
+                                "(${}, ${}, ${}, NOW(), ${})",
+                                param_idx + 1,
+                                param_idx + 2,
+                                param_idx + 3,
+                                param_idx + 4
+                            );
+                            param_idx += 4;
+                            clause
+                        })
+                        .collect();
+
+                    let sql = format!(
+                        "INSERT INTO run_history_cache (pipeline_id, run_number, run_data, fetched_at, run_hash) VALUES {} ON CONFLICT (pipeline_id, run_number) DO UPDATE SET run_data = EXCLUDED.run_data, fetched_at = EXCLUDED.fetched_at, run_hash = EXCLUDED.run_hash",
+                        values_clauses.join(", ")
+                    );
+
+                    let mut query = sqlx::query(&sql);
+                    for (run_number, run_data, run_hash) in &prepared_data {
+                        query = query
+                            .bind(pipeline_id)
+                            .bind(run_number)
+                            .bind(run_data)
+                            .bind(run_hash);
                     }
+
+                    query
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
                 }
 
-                Ok(())
+                tx.commit()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
             }
-        })
-        .await?;
+        }
 
         let elapsed = start.elapsed();
         tracing::debug!(
@@ -843,145 +1523,548 @@ impl Repository {
             "Cached run history (batch insert)"
         );
 
+        self.publish_invalidation(CacheInvalidation::RunHistory {
+            pipeline_id: Some(pipeline_id.to_string()),
+        })
+        .await;
+
         Ok(())
     }
 
     pub async fn get_cached_run_history(
         &self, pipeline_id: &str, limit: usize,
     ) -> DomainResult<Vec<PipelineRun>> {
-        let sql = format!(
+        let sql = self.sql(
             "SELECT run_data FROM run_history_cache
-             WHERE pipeline_id = {}
+             WHERE pipeline_id = ?
              ORDER BY run_number DESC
-             LIMIT {}",
-            self.placeholder(1),
-            self.placeholder(2)
+             LIMIT ?",
         );
-        let rows = match &self.cache_pool {
-            DatabasePool::Sqlite(p) => sqlx::query_scalar::<_, String>(&sql)
-                .bind(pipeline_id)
-                .bind(limit as i64)
-                .fetch_all(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-            DatabasePool::Postgres(p) => sqlx::query_scalar::<_, String>(&sql)
-                .bind(pipeline_id)
-                .bind(limit as i64)
-                .fetch_all(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-        };
+        let rows = with_pool!(self, |p| sqlx::query_scalar::<_, String>(&sql)
+            .bind(pipeline_id)
+            .bind(limit as i64)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
 
         let runs: Vec<PipelineRun> = rows
             .iter()
-            .filter_map(|json| serde_json::from_str(json).ok())
+            .filter_map(|json| self.decode_run(json))
             .collect();
 
         Ok(runs)
     }
 
-    pub async fn clear_cached_run_history(&self, pipeline_id: &str) -> DomainResult<()> {
-        let sql = format!(
-            "DELETE FROM run_history_cache WHERE pipeline_id = {}",
-            self.placeholder(1)
+    // Oldest first, for paging through a pipeline's whole cached history.
+    pub async fn get_cached_runs_after(
+        &self, pipeline_id: &str, after_run_number: i64, limit: usize,
+    ) -> DomainResult<Vec<PipelineRun>> {
+        let sql = self.sql(
+            "SELECT run_data FROM run_history_cache
+             WHERE pipeline_id = ? AND run_number > ?
+             ORDER BY run_number ASC
+             LIMIT ?",
         );
-        match &self.cache_pool {
-            DatabasePool::Sqlite(p) => {
+        let rows = with_pool!(self, |p| sqlx::query_scalar::<_, String>(&sql)
+            .bind(pipeline_id)
+            .bind(after_run_number)
+            .bind(limit as i64)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+
+        Ok(rows
+            .iter()
+            .filter_map(|json| self.decode_run(json))
+            .collect())
+    }
+
+    pub async fn clear_cached_run_history(&self, pipeline_id: &str) -> DomainResult<()> {
+        for table in ["run_history_cache", "run_annotations_cache"] {
+            let sql = format!(
+                "DELETE FROM {} WHERE pipeline_id = {}",
+                table,
+                self.placeholder(1)
+            );
+            with_write_connection!(self, |conn| {
                 sqlx::query(&sql)
                     .bind(pipeline_id)
-                    .execute(p)
+                    .execute(&mut *conn)
                     .await
                     .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-            }
-            DatabasePool::Postgres(p) => {
-                sqlx::query(&sql)
-                    .bind(pipeline_id)
-                    .execute(p)
+            })
+        }
+
+        self.publish_invalidation(CacheInvalidation::RunHistory {
+            pipeline_id: Some(pipeline_id.to_string()),
+        })
+        .await;
+
+        Ok(())
+    }
+
+    pub async fn clear_all_run_history_cache(&self) -> DomainResult<()> {
+        for sql in [
+            "DELETE FROM run_history_cache",
+            "DELETE FROM run_annotations_cache",
+        ] {
+            with_write_connection!(self, |conn| {
+                sqlx::query(sql)
+                    .execute(&mut *conn)
                     .await
                     .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-            }
+            })
         }
 
+        self.publish_invalidation(CacheInvalidation::RunHistory { pipeline_id: None })
+            .await;
+
         Ok(())
     }
 
-    pub async fn clear_all_run_history_cache(&self) -> DomainResult<()> {
-        match &self.cache_pool {
+    pub async fn cache_run_annotations(
+        &self, pipeline_id: &str, run_number: i64, annotations: &[RunAnnotation],
+    ) -> DomainResult<()> {
+        let annotations_json = serde_json::to_string(annotations)
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let sql = self.sql(
+            "INSERT INTO run_annotations_cache (pipeline_id, run_number, annotations_json, cached_at) VALUES (?, ?, ?, NOW()) ON CONFLICT (pipeline_id, run_number) DO UPDATE SET annotations_json = EXCLUDED.annotations_json, cached_at = EXCLUDED.cached_at",
+        );
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(pipeline_id)
+                .bind(run_number)
+                .bind(&annotations_json)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+
+        Ok(())
+    }
+
+    pub async fn get_cached_run_annotations(
+        &self, pipeline_id: &str, run_number: i64,
+    ) -> DomainResult<Option<Vec<RunAnnotation>>> {
+        let sql = self.sql("SELECT annotations_json FROM run_annotations_cache WHERE pipeline_id = ? AND run_number = ?");
+        let result = with_pool!(self, |p| sqlx::query_scalar::<_, String>(&sql)
+            .bind(pipeline_id)
+            .bind(run_number)
+            .fetch_optional(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+
+        result
+            .map(|json| {
+                serde_json::from_str(&json).map_err(|e| {
+                    DomainError::DatabaseError(format!("Failed to parse run annotations: {}", e))
+                })
+            })
+            .transpose()
+    }
+
+    pub async fn cache_branch_statuses(
+        &self, pipeline_id: &str, statuses: &[BranchStatus],
+    ) -> DomainResult<()> {
+        let statuses_json = serde_json::to_string(statuses)
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let sql = self.sql(
+            "INSERT INTO branch_status_cache (pipeline_id, statuses_json, cached_at) VALUES (?, ?, NOW()) ON CONFLICT (pipeline_id) DO UPDATE SET statuses_json = EXCLUDED.statuses_json, cached_at = EXCLUDED.cached_at",
+        );
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(pipeline_id)
+                .bind(&statuses_json)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+
+        Ok(())
+    }
+
+    // Returns the statuses together with when they were cached.
+    pub async fn get_cached_branch_statuses(
+        &self, pipeline_id: &str,
+    ) -> DomainResult<Option<(Vec<BranchStatus>, DateTime<Utc>)>> {
+        let sql = self
+            .sql("SELECT statuses_json, cached_at FROM branch_status_cache WHERE pipeline_id = ?");
+        let result = match &self.cache_pool {
+            DatabasePool::Sqlite(p) => sqlx::query_as::<_, (String, String)>(&sql)
+                .bind(pipeline_id)
+                .fetch_optional(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+                .and_then(|(json, cached_at)| {
+                    parse_sqlite_timestamp(&cached_at).map(|cached_at| (json, cached_at))
+                }),
+            DatabasePool::Postgres(p) => sqlx::query_as::<_, (String, DateTime<Utc>)>(&sql)
+                .bind(pipeline_id)
+                .fetch_optional(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+        };
+
+        result
+            .map(|(json, cached_at)| {
+                serde_json::from_str(&json)
+                    .map(|statuses| (statuses, cached_at))
+                    .map_err(|e| {
+                        DomainError::DatabaseError(format!(
+                            "Failed to parse branch statuses: {}",
+                            e
+                        ))
+                    })
+            })
+            .transpose()
+    }
+
+    pub async fn record_pipeline_usage(&self, pipeline_id: &str) -> DomainResult<()> {
+        let sql = self.sql(
+            "INSERT INTO pipeline_usage (pipeline_id, use_count, last_used_at) VALUES (?, 1, NOW()) ON CONFLICT (pipeline_id) DO UPDATE SET use_count = pipeline_usage.use_count + 1, last_used_at = EXCLUDED.last_used_at",
+        );
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(pipeline_id)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+
+        Ok(())
+    }
+
+    // Most recently used first.
+    pub async fn list_pipeline_usage(&self) -> DomainResult<Vec<PipelineUsage>> {
+        let sql = "SELECT pipeline_id, use_count, last_used_at FROM pipeline_usage ORDER BY last_used_at DESC";
+        let usage = match &self.cache_pool {
+            DatabasePool::Sqlite(p) => sqlx::query_as::<_, (String, i64, String)>(sql)
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+                .into_iter()
+                .filter_map(|(pipeline_id, use_count, last_used_at)| {
+                    parse_sqlite_timestamp(&last_used_at).map(|last_used_at| PipelineUsage {
+                        pipeline_id,
+                        use_count,
+                        last_used_at,
+                    })
+                })
+                .collect(),
+            DatabasePool::Postgres(p) => sqlx::query_as::<_, (String, i64, DateTime<Utc>)>(sql)
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+                .into_iter()
+                .map(|(pipeline_id, use_count, last_used_at)| PipelineUsage {
+                    pipeline_id,
+                    use_count,
+                    last_used_at,
+                })
+                .collect(),
+        };
+
+        Ok(usage)
+    }
+
+    pub async fn get_run_retention_config(&self) -> DomainResult<GlobalRunRetentionConfig> {
+        let config = match &self.cache_pool {
             DatabasePool::Sqlite(p) => {
-                sqlx::query("DELETE FROM run_history_cache")
-                    .execute(p)
+                let row: Option<SqliteRetentionRow> =
+                    sqlx::query_as(
+                        "SELECT enabled, max_age_days, max_runs_per_pipeline, last_pruned_at, last_pruned_count, updated_at
+                         FROM run_retention_global_config WHERE id = 1",
+                    )
+                    .fetch_optional(p)
                     .await
                     .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                row.map(
+                    |(
+                        enabled,
+                        max_age_days,
+                        max_runs,
+                        last_pruned_at,
+                        last_pruned_count,
+                        updated_at,
+                    )| {
+                        GlobalRunRetentionConfig {
+                            enabled: enabled != 0,
+                            max_age_days,
+                            max_runs_per_pipeline: max_runs,
+                            last_pruned_at: last_pruned_at
+                                .as_deref()
+                                .and_then(parse_sqlite_timestamp),
+                            last_pruned_count,
+                            updated_at: parse_sqlite_timestamp(&updated_at)
+                                .unwrap_or_else(Utc::now),
+                        }
+                    },
+                )
             }
             DatabasePool::Postgres(p) => {
-                sqlx::query("DELETE FROM run_history_cache")
-                    .execute(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                let row: Option<PostgresRetentionRow> = sqlx::query_as(
+                    "SELECT enabled, max_age_days::BIGINT, max_runs_per_pipeline::BIGINT, last_pruned_at, last_pruned_count, updated_at
+                     FROM run_retention_global_config WHERE id = 1",
+                )
+                .fetch_optional(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                row.map(
+                    |(
+                        enabled,
+                        max_age_days,
+                        max_runs,
+                        last_pruned_at,
+                        last_pruned_count,
+                        updated_at,
+                    )| {
+                        GlobalRunRetentionConfig {
+                            enabled,
+                            max_age_days,
+                            max_runs_per_pipeline: max_runs,
+                            last_pruned_at,
+                            last_pruned_count,
+                            updated_at,
+                        }
+                    },
+                )
             }
-        }
+        };
+
+        Ok(config.unwrap_or_default())
+    }
 
+    pub async fn update_run_retention_config(
+        &self, enabled: bool, policy: RunRetentionPolicy,
+    ) -> DomainResult<()> {
+        let now = Utc::now();
+        let sql = self.sql(
+            "UPDATE run_retention_global_config
+             SET enabled = ?, max_age_days = ?, max_runs_per_pipeline = ?, updated_at = ?
+             WHERE id = 1",
+        );
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(enabled)
+                .bind(policy.max_age_days)
+                .bind(policy.max_runs)
+                .bind(now)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
         Ok(())
     }
 
-    pub async fn get_cached_runs_with_hashes(
-        &self, pipeline_id: &str,
-    ) -> DomainResult<HashMap<i64, (PipelineRun, String)>> {
-        let mut result = HashMap::new();
+    pub async fn record_run_history_prune(&self, deleted: usize) -> DomainResult<()> {
+        let now = Utc::now();
+        let sql = self.sql(
+            "UPDATE run_retention_global_config SET last_pruned_at = ?, last_pruned_count = ? WHERE id = 1",
+        );
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(now)
+                .bind(deleted as i64)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+        Ok(())
+    }
+
+    pub async fn list_pipeline_run_retention(&self) -> DomainResult<Vec<PipelineRunRetention>> {
         match &self.cache_pool {
             DatabasePool::Sqlite(p) => {
-                let sql = format!(
-                    "SELECT run_number, run_data, run_hash FROM run_history_cache WHERE pipeline_id = {}",
-                    self.placeholder(1)
-                );
-                let rows = sqlx::query(&sql)
+                let rows: Vec<(String, Option<i64>, Option<i64>, String)> = sqlx::query_as(
+                    "SELECT pipeline_id, max_age_days, max_runs, updated_at
+                     FROM run_retention_config ORDER BY pipeline_id",
+                )
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                Ok(rows
+                    .into_iter()
+                    .map(
+                        |(pipeline_id, max_age_days, max_runs, updated_at)| PipelineRunRetention {
+                            pipeline_id,
+                            max_age_days,
+                            max_runs,
+                            updated_at: parse_sqlite_timestamp(&updated_at)
+                                .unwrap_or_else(Utc::now),
+                        },
+                    )
+                    .collect())
+            }
+            DatabasePool::Postgres(p) => {
+                #[allow(clippy::type_complexity)]
+                let rows: Vec<(String, Option<i64>, Option<i64>, DateTime<Utc>)> = sqlx::query_as(
+                    "SELECT pipeline_id, max_age_days::BIGINT, max_runs::BIGINT, updated_at
+                     FROM run_retention_config ORDER BY pipeline_id",
+                )
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                Ok(rows
+                    .into_iter()
+                    .map(
+                        |(pipeline_id, max_age_days, max_runs, updated_at)| PipelineRunRetention {
+                            pipeline_id,
+                            max_age_days,
+                            max_runs,
+                            updated_at,
+                        },
+                    )
+                    .collect())
+            }
+        }
+    }
+
+    pub async fn set_pipeline_run_retention(
+        &self, pipeline_id: &str, policy: RunRetentionPolicy,
+    ) -> DomainResult<()> {
+        let now = Utc::now();
+        let sql = self.sql(
+            "INSERT INTO run_retention_config (pipeline_id, max_age_days, max_runs, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT (pipeline_id) DO UPDATE SET
+                 max_age_days = EXCLUDED.max_age_days,
+                 max_runs = EXCLUDED.max_runs,
+                 updated_at = EXCLUDED.updated_at",
+        );
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(pipeline_id)
+                .bind(policy.max_age_days)
+                .bind(policy.max_runs)
+                .bind(now)
+                .bind(now)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+        Ok(())
+    }
+
+    pub async fn delete_pipeline_run_retention(&self, pipeline_id: &str) -> DomainResult<()> {
+        let sql = self.sql("DELETE FROM run_retention_config WHERE pipeline_id = ?");
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(pipeline_id)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+        Ok(())
+    }
+
+    pub async fn list_run_history_pipeline_ids(&self) -> DomainResult<Vec<String>> {
+        let sql = "SELECT DISTINCT pipeline_id FROM run_history_cache";
+        with_pool!(self, |p| sqlx::query_scalar(sql)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string())))
+    }
+
+    // Drops runs fetched before the age cutoff, then everything beyond the
+    // newest `max_runs` run numbers.
+    pub async fn prune_run_history(
+        &self, pipeline_id: &str, policy: RunRetentionPolicy,
+    ) -> DomainResult<usize> {
+        let mut deleted = 0;
+
+        if let Some(max_age_days) = policy.max_age_days {
+            let cutoff = Utc::now() - chrono::Duration::days(max_age_days);
+            let affected = match &self.cache_pool {
+                DatabasePool::Sqlite(_) => {
+                    let mut conn = self.write_connection().await?;
+                    sqlx::query(
+                        "DELETE FROM run_history_cache WHERE pipeline_id = ? AND datetime(fetched_at) < datetime(?)",
+                    )
                     .bind(pipeline_id)
-                    .fetch_all(p)
+                    .bind(cutoff.to_rfc3339())
+                    .execute(&mut *conn)
                     .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+                    .rows_affected()
+                }
+                DatabasePool::Postgres(p) => sqlx::query(
+                    "DELETE FROM run_history_cache WHERE pipeline_id = $1 AND fetched_at < $2",
+                )
+                .bind(pipeline_id)
+                .bind(cutoff)
+                .execute(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+                .rows_affected(),
+            };
+            deleted += affected as usize;
+        }
 
-                for row in rows {
-                    let run_number: i64 = row
-                        .try_get(0)
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    let run_data: String = row
-                        .try_get(1)
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    let run_hash: String = row
-                        .try_get(2)
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        if let Some(max_runs) = policy.max_runs {
+            let sql = self.sql(
+                "DELETE FROM run_history_cache
+                 WHERE pipeline_id = ? AND run_number NOT IN (
+                     SELECT run_number FROM run_history_cache
+                     WHERE pipeline_id = ?
+                     ORDER BY run_number DESC
+                     LIMIT ?
+                 )",
+            );
+            let affected = with_write_connection!(self, |conn| {
+                sqlx::query(&sql)
+                    .bind(pipeline_id)
+                    .bind(pipeline_id)
+                    .bind(max_runs)
+                    .execute(&mut *conn)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+                    .rows_affected()
+            });
+            deleted += affected as usize;
+        }
 
-                    if let Ok(run) = serde_json::from_str::<PipelineRun>(&run_data) {
-                        result.insert(run_number, (run, run_hash));
-                    }
-                }
-            }
-            DatabasePool::Postgres(p) => {
-                let sql = format!(
-                    "SELECT run_number::BIGINT, run_data, run_hash FROM run_history_cache WHERE pipeline_id = {}",
-                    self.placeholder(1)
-                );
-                let rows = sqlx::query(&sql)
+        if deleted > 0 {
+            let sql = self.sql(
+                "DELETE FROM run_annotations_cache
+                 WHERE pipeline_id = ? AND run_number NOT IN (
+                     SELECT run_number FROM run_history_cache WHERE pipeline_id = ?
+                 )",
+            );
+            with_write_connection!(self, |conn| {
+                sqlx::query(&sql)
                     .bind(pipeline_id)
-                    .fetch_all(p)
+                    .bind(pipeline_id)
+                    .execute(&mut *conn)
                     .await
                     .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            })
+        }
 
-                for row in rows {
-                    let run_number: i64 = row
-                        .try_get(0)
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    let run_data: String = row
-                        .try_get(1)
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    let run_hash: String = row
-                        .try_get(2)
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        Ok(deleted)
+    }
 
-                    if let Ok(run) = serde_json::from_str::<PipelineRun>(&run_data) {
-                        result.insert(run_number, (run, run_hash));
-                    }
-                }
+    pub async fn get_cached_runs_with_hashes(
+        &self, pipeline_id: &str,
+    ) -> DomainResult<HashMap<i64, (PipelineRun, String)>> {
+        let sql = self.sql(
+            "SELECT CAST(run_number AS BIGINT), run_data, run_hash FROM run_history_cache WHERE pipeline_id = ?",
+        );
+        let rows: Vec<(i64, String, String)> = with_pool!(self, |p| sqlx::query_as(&sql)
+            .bind(pipeline_id)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+
+        let mut result = HashMap::new();
+        for (run_number, run_data, run_hash) in rows {
+            if let Some(run) = self.decode_run(&run_data) {
+                result.insert(run_number, (run, run_hash));
             }
         }
 
@@ -993,180 +2076,193 @@ impl Repository {
         deleted_run_numbers: Vec<i64>,
     ) -> DomainResult<()> {
         let start = std::time::Instant::now();
-        let pipeline_id_str = pipeline_id.to_string();
-        let pool = self.cache_pool.clone();
 
         let new_runs_count = new_runs.len();
         let changed_runs_count = changed_runs.len();
         let deleted_runs_count = deleted_run_numbers.len();
 
-        retry_on_busy(move || {
-            let pipeline_id_clone = pipeline_id_str.clone();
-            let new_runs_clone = new_runs.clone();
-            let changed_runs_clone = changed_runs.clone();
-            let deleted_clone = deleted_run_numbers.clone();
-            let pool_clone = pool.clone();
-
-            async move {
-                const BATCH_SIZE: usize = 100;
-                const DELETE_BATCH_SIZE: usize = 100;
-
-                let all_runs: Vec<_> = new_runs_clone.iter().chain(changed_runs_clone.iter()).collect();
-
-                match &pool_clone {
-                    DatabasePool::Sqlite(p) => {
-                        let mut tx = p.begin().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-
-                        if !all_runs.is_empty() {
-                            for chunk in all_runs.chunks(BATCH_SIZE) {
-                                let prepared_data: Vec<(i64, String, String)> = chunk
-                                    .iter()
-                                    .map(|run| {
-                                        let run_data = serde_json::to_string(run)
-                                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                                        let status_str = run.status.as_str();
-                                        let run_hash = hash_pipeline_run(
-                                            run.run_number,
-                                            status_str,
-                                            run.branch.as_deref(),
-                                            &run.started_at.to_rfc3339(),
-                                            run.duration_seconds,
-                                            run.commit_sha.as_deref(),
-                                        );
-                                        Ok((run.run_number, run_data, run_hash))
-                                    })
-                                    .collect::<Result<Vec<_>, DomainError>>()?;
-
-                                let values_clause = prepared_data
-                                    .iter()
-                                    .map(|_| "(?, ?, ?, datetime('now'), ?)")
-                                    .collect::<Vec<_>>()
-                                    .join(", ");
-
-                                let sql = format!(
-                                    "INSERT OR REPLACE INTO run_history_cache (pipeline_id, run_number, run_data, fetched_at, run_hash) VALUES {}",
-                                    values_clause
-                                );
-
-                                let mut query = sqlx::query(&sql);
-                                for (run_number, run_data, run_hash) in &prepared_data {
-                                    query = query
-                                        .bind(&pipeline_id_clone)
-                                        .bind(run_number)
-                                        .bind(run_data)
-                                        .bind(run_hash);
-                                }
-
-                                query.execute(&mut *tx).await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            }
-                        }
-
-                        if !deleted_clone.is_empty() {
-                            for chunk in deleted_clone.chunks(DELETE_BATCH_SIZE) {
-                                let placeholders = chunk.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
-                                let sql = format!(
-                                    "DELETE FROM run_history_cache WHERE pipeline_id = ? AND run_number IN ({})",
-                                    placeholders
-                                );
+        const BATCH_SIZE: usize = 100;
+        const DELETE_BATCH_SIZE: usize = 100;
 
-                                let mut query = sqlx::query(&sql).bind(&pipeline_id_clone);
-                                for run_number in chunk {
-                                    query = query.bind(run_number);
-                                }
+        let all_runs: Vec<_> = new_runs.iter().chain(changed_runs.iter()).collect();
 
-                                query.execute(&mut *tx).await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            }
-                        }
+        match &self.cache_pool {
+            DatabasePool::Sqlite(_) => {
+                let mut conn = self.write_connection().await?;
+                let mut tx = conn
+                    .begin()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-                        tx.commit().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    }
-                    DatabasePool::Postgres(p) => {
-                        let mut tx = p.begin().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-
-                        if !all_runs.is_empty() {
-                            for chunk in all_runs.chunks(BATCH_SIZE) {
-                                let prepared_data: Vec<(i64, String, String)> = chunk
-                                    .iter()
-                                    .map(|run| {
-                                        let run_data = serde_json::to_string(run)
-                                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                                        let status_str = run.status.as_str();
-                                        let run_hash = hash_pipeline_run(
-                                            run.run_number,
-                                            status_str,
-                                            run.branch.as_deref(),
-                                            &run.started_at.to_rfc3339(),
-                                            run.duration_seconds,
-                                            run.commit_sha.as_deref(),
-                                        );
-                                        Ok((run.run_number, run_data, run_hash))
-                                    })
-                                    .collect::<Result<Vec<_>, DomainError>>()?;
-
-                                let mut param_idx = 0;
-                                let values_clauses: Vec<String> = prepared_data
-                                    .iter()
-                                    .map(|_| {
-                                        let clause = format!(
-                                            "(${}, ${}, ${}, NOW(), ${})",
-                                            param_idx + 1, param_idx + 2, param_idx + 3, param_idx + 4
-                                        );
-                                        param_idx += 4;
-                                        clause
-                                    })
-                                    .collect();
-
-                                let sql = format!(
-                                    "INSERT INTO run_history_cache (pipeline_id, run_number, run_data, fetched_at, run_hash) VALUES {} ON CONFLICT (pipeline_id, run_number) DO UPDATE SET run_data = EXCLUDED.run_data, fetched_at = EXCLUDED.fetched_at, run_hash = EXCLUDED.run_hash",
-                                    values_clauses.join(", ")
+                if !all_runs.is_empty() {
+                    for chunk in all_runs.chunks(BATCH_SIZE) {
+                        let prepared_data: Vec<(i64, String, String)> = chunk
+                            .iter()
+                            .map(|run| {
+                                let run_data = self.encode_run(run)?;
+                                let status_str = run.status.as_str();
+                                let run_hash = hash_pipeline_run(
+                                    run.run_number,
+                                    status_str,
+                                    run.branch.as_deref(),
+                                    &run.started_at.to_rfc3339(),
+                                    run.duration_seconds,
+                                    run.commit_sha.as_deref(),
                                 );
+                                Ok((run.run_number, run_data, run_hash))
+                            })
+                            .collect::<Result<Vec<_>, DomainError>>()?;
+
+                        let values_clause = prepared_data
+                            .iter()
+                            .map(|_| "(?, ?, ?, datetime('now'), ?)")
+                            .collect::<Vec<_>>()
+                            .join(", ");
+
+                        let sql = format!(
+                            "INSERT OR REPLACE INTO run_history_cache (pipeline_id, run_number, run_data, fetched_at, run_hash) VALUES {}",
+                            values_clause
+                        );
 
-                                let mut query = sqlx::query(&sql);
-                                for (run_number, run_data, run_hash) in &prepared_data {
-                                    query = query
-                                        .bind(&pipeline_id_clone)
-                                        .bind(run_number)
-                                        .bind(run_data)
-                                        .bind(run_hash);
-                                }
-
-                                query.execute(&mut *tx).await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            }
+                        let mut query = sqlx::query(&sql);
+                        for (run_number, run_data, run_hash) in &prepared_data {
+                            query = query
+                                .bind(pipeline_id)
+                                .bind(run_number)
+                                .bind(run_data)
+                                .bind(run_hash);
                         }
 
-                        if !deleted_clone.is_empty() {
-                            for chunk in deleted_clone.chunks(DELETE_BATCH_SIZE) {
-                                let mut param_idx = 1;
-                                let placeholders = chunk.iter().map(|_| {
-                                    let p = format!("${}", param_idx);
-                                    param_idx += 1;
-                                    p
-                                }).collect::<Vec<_>>().join(", ");
-
-                                let sql = format!(
-                                    "DELETE FROM run_history_cache WHERE pipeline_id = ${} AND run_number IN ({})",
-                                    param_idx,
-                                    placeholders
-                                );
+                        query
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    }
+                }
 
-                                let mut query = sqlx::query(&sql);
-                                for run_number in chunk {
-                                    query = query.bind(run_number);
-                                }
-                                query = query.bind(&pipeline_id_clone);
+                if !deleted_run_numbers.is_empty() {
+                    for chunk in deleted_run_numbers.chunks(DELETE_BATCH_SIZE) {
+                        let placeholders = chunk.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+                        let sql = format!(
+                            "DELETE FROM run_history_cache WHERE pipeline_id = ? AND run_number IN ({})",
+                            placeholders
+                        );
 
-                                query.execute(&mut *tx).await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            }
+                        let mut query = sqlx::query(&sql).bind(pipeline_id);
+                        for run_number in chunk {
+                            query = query.bind(run_number);
                         }
 
-                        tx.commit().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                        query
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
                     }
                 }
 
-                Ok(())
+                tx.commit()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
             }
-        })
-        .await?;
+            DatabasePool::Postgres(p) => {
+                let mut tx = p
+                    .begin()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                if !all_runs.is_empty() {
+                    for chunk in all_runs.chunks(BATCH_SIZE) {
+                        let prepared_data: Vec<(i64, String, String)> = chunk
+                            .iter()
+                            .map(|run| {
+                                let run_data = self.encode_run(run)?;
+                                let status_str = run.status.as_str();
+                                let run_hash = hash_pipeline_run(
+                                    run.run_number,
+                                    status_str,
+                                    run.branch.as_deref(),
+                                    &run.started_at.to_rfc3339(),
+                                    run.duration_seconds,
+                                    run.commit_sha.as_deref(),
+                                );
+                                Ok((run.run_number, run_data, run_hash))
+                            })
+                            .collect::<Result<Vec<_>, DomainError>>()?;
+
+                        let mut param_idx = 0;
+                        let values_clauses: Vec<String> = prepared_data
+                            .iter()
+                            .map(|_| {
+                                let clause = format!(
+                                    "(${}, ${}, ${}, NOW(), ${})",
+                                    param_idx + 1,
+                                    param_idx + 2,
+                                    param_idx + 3,
+                                    param_idx + 4
+                                );
+                                param_idx += 4;
+                                clause
+                            })
+                            .collect();
+
+                        let sql = format!(
+                            "INSERT INTO run_history_cache (pipeline_id, run_number, run_data, fetched_at, run_hash) VALUES {} ON CONFLICT (pipeline_id, run_number) DO UPDATE SET run_data = EXCLUDED.run_data, fetched_at = EXCLUDED.fetched_at, run_hash = EXCLUDED.run_hash",
+                            values_clauses.join(", ")
+                        );
+
+                        let mut query = sqlx::query(&sql);
+                        for (run_number, run_data, run_hash) in &prepared_data {
+                            query = query
+                                .bind(pipeline_id)
+                                .bind(run_number)
+                                .bind(run_data)
+                                .bind(run_hash);
+                        }
+
+                        query
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    }
+                }
+
+                if !deleted_run_numbers.is_empty() {
+                    for chunk in deleted_run_numbers.chunks(DELETE_BATCH_SIZE) {
+                        let mut param_idx = 1;
+                        let placeholders = chunk
+                            .iter()
+                            .map(|_| {
+                                let p = format!("${}", param_idx);
+                                param_idx += 1;
+                                p
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ");
+
+                        let sql = format!(
+                            "DELETE FROM run_history_cache WHERE pipeline_id = ${} AND run_number IN ({})",
+                            param_idx,
+                            placeholders
+                        );
+
+                        let mut query = sqlx::query(&sql);
+                        for run_number in chunk {
+                            query = query.bind(run_number);
+                        }
+                        query = query.bind(pipeline_id);
+
+                        query
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    }
+                }
+
+                tx.commit()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            }
+        }
 
         let elapsed = start.elapsed();
         tracing::debug!(
@@ -1178,68 +2274,54 @@ impl Repository {
             "Merged run cache (batch operations)"
         );
 
+        self.publish_invalidation(CacheInvalidation::RunHistory {
+            pipeline_id: Some(pipeline_id.to_string()),
+        })
+        .await;
+
         Ok(())
     }
 
+    // Returns whether any row was inserted, updated or removed.
     pub async fn update_pipelines_cache(
         &self, provider_id: i64, new_pipelines: &[Pipeline],
-    ) -> DomainResult<()> {
-        let new_pipelines_vec = new_pipelines.to_vec();
-        let pool = self.cache_pool.clone();
-        let is_postgres = pool.is_postgres();
-
-        retry_on_busy(move || {
-            let new_pipelines_clone = new_pipelines_vec.clone();
-            let pool_clone = pool.clone();
-            let is_pg = is_postgres;
-            async move {
-                let select_sql = if is_pg {
-                    "SELECT id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, provider_type
-                    FROM pipelines_cache
-                    WHERE provider_id = $1"
-                } else {
-                    "SELECT id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, provider_type
-                    FROM pipelines_cache
-                    WHERE provider_id = ?"
-                };
-
-                let update_sql = if is_pg {
-                    "UPDATE pipelines_cache
-                     SET provider_id = $1, name = $2, status = $3, repository = $4, branch = $5,
-                         workflow_file = $6, last_run = $7, last_updated = $8, metadata_json = $9, provider_type = $10
-                     WHERE id = $11"
-                } else {
-                    "UPDATE pipelines_cache
-                     SET provider_id = ?, name = ?, status = ?, repository = ?, branch = ?,
-                         workflow_file = ?, last_run = ?, last_updated = ?, metadata_json = ?, provider_type = ?
-                     WHERE id = ?"
-                };
-
-                let insert_sql = if is_pg {
-                    "INSERT INTO pipelines_cache
-                     (id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, metadata_json, provider_type)
-                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"
-                } else {
-                    "INSERT INTO pipelines_cache
-                     (id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, metadata_json, provider_type)
-                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-                };
+    ) -> DomainResult<bool> {
+        let select_sql = self.sql(
+            "SELECT id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, provider_type
+            FROM pipelines_cache
+            WHERE provider_id = ?",
+        );
 
-                let delete_sql = if is_pg {
-                    "DELETE FROM pipelines_cache WHERE id = $1"
-                } else {
-                    "DELETE FROM pipelines_cache WHERE id = ?"
-                };
+        let update_sql = self.sql(
+            "UPDATE pipelines_cache
+             SET provider_id = ?, name = ?, status = ?, repository = ?, branch = ?,
+                 workflow_file = ?, last_run = ?, last_updated = ?, metadata_json = ?, provider_type = ?
+             WHERE id = ?",
+        );
 
-                match &pool_clone {
-                    DatabasePool::Sqlite(p) => {
-                        let mut tx = p.begin().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let insert_sql = self.sql(
+            "INSERT INTO pipelines_cache
+             (id, provider_id, name, status, repository, branch, workflow_file, last_run, last_updated, metadata_json, provider_type)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        );
 
-                        let existing_rows = sqlx::query(select_sql)
-                            .bind(provider_id)
-                            .fetch_all(&mut *tx)
-                            .await
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let delete_sql = self.sql("DELETE FROM pipelines_cache WHERE id = ?");
+
+        let mut changed = false;
+
+        match &self.cache_pool {
+            DatabasePool::Sqlite(_) => {
+                let mut conn = self.write_connection().await?;
+                let mut tx = conn
+                    .begin()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                let existing_rows = sqlx::query(&select_sql)
+                    .bind(provider_id)
+                    .fetch_all(&mut *tx)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
                 let mut existing: HashMap<String, Pipeline> = HashMap::new();
                 for row in existing_rows.iter() {
@@ -1274,24 +2356,7 @@ impl Repository {
                         .try_get(9)
                         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-                    let status: PipelineStatus = if status_str.trim().is_empty() {
-                        PipelineStatus::Pending
-                    } else if status_str.starts_with('"') {
-                        serde_json::from_str(&status_str).map_err(|e| {
-                            DomainError::DatabaseError(format!(
-                                "Failed to parse status '{}': {}",
-                                status_str, e
-                            ))
-                        })?
-                    } else {
-                        let quoted = format!("\"{}\"", status_str);
-                        serde_json::from_str(&quoted).map_err(|e| {
-                            DomainError::DatabaseError(format!(
-                                "Failed to parse status '{}': {}",
-                                status_str, e
-                            ))
-                        })?
-                    };
+                    let status = PipelineStatus::from_stored(&status_str);
                     let last_run = last_run_str
                         .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
                         .map(|dt| dt.with_timezone(&Utc));
@@ -1299,280 +2364,897 @@ impl Repository {
                         .map_err(|e| DomainError::DatabaseError(e.to_string()))?
                         .with_timezone(&Utc);
 
-                    existing.insert(
-                        id.clone(),
-                        Pipeline {
-                            id,
-                            provider_id: provider_id_val,
-                            provider_type,
-                            name,
-                            status,
-                            last_run,
-                            last_updated,
-                            repository,
-                            branch,
-                            workflow_file,
-                            metadata: std::collections::HashMap::new(),
-                        },
-                    );
-                }
+                    existing.insert(
+                        id.clone(),
+                        Pipeline {
+                            id,
+                            provider_id: provider_id_val,
+                            provider_type,
+                            name,
+                            status,
+                            last_run,
+                            last_updated,
+                            repository,
+                            branch,
+                            workflow_file,
+                            metadata: std::collections::HashMap::new(),
+                            labels: Vec::new(),
+                        },
+                    );
+                }
+
+                let new_ids: HashMap<String, &Pipeline> =
+                    new_pipelines.iter().map(|p| (p.id.clone(), p)).collect();
+
+                for pipeline in new_pipelines {
+                    let metadata_json = self.encode_pipeline_metadata(pipeline)?;
+                    if let Some(old) = existing.get(&pipeline.id) {
+                        if old.status != pipeline.status
+                            || old.last_run != pipeline.last_run
+                            || old.name != pipeline.name
+                            || old.provider_id != provider_id
+                            || old.provider_type != pipeline.provider_type
+                        {
+                            changed = true;
+                            sqlx::query(&update_sql)
+                                .bind(provider_id)
+                                .bind(&pipeline.name)
+                                .bind(pipeline.status.as_str())
+                                .bind(&pipeline.repository)
+                                .bind(&pipeline.branch)
+                                .bind(&pipeline.workflow_file)
+                                .bind(pipeline.last_run.as_ref().map(|dt| dt.to_rfc3339()))
+                                .bind(Utc::now().to_rfc3339())
+                                .bind(&metadata_json)
+                                .bind(&pipeline.provider_type)
+                                .bind(&pipeline.id)
+                                .execute(&mut *tx)
+                                .await
+                                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                        }
+                    } else {
+                        changed = true;
+                        sqlx::query(&insert_sql)
+                            .bind(&pipeline.id)
+                            .bind(provider_id)
+                            .bind(&pipeline.name)
+                            .bind(pipeline.status.as_str())
+                            .bind(&pipeline.repository)
+                            .bind(&pipeline.branch)
+                            .bind(&pipeline.workflow_file)
+                            .bind(pipeline.last_run.as_ref().map(|dt| dt.to_rfc3339()))
+                            .bind(Utc::now().to_rfc3339())
+                            .bind(&metadata_json)
+                            .bind(&pipeline.provider_type)
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    }
+                }
+
+                for old_id in existing.keys() {
+                    if !new_ids.contains_key(old_id) {
+                        changed = true;
+                        sqlx::query(&delete_sql)
+                            .bind(old_id)
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    }
+                }
+
+                tx.commit()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            }
+            DatabasePool::Postgres(p) => {
+                let mut tx = p
+                    .begin()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                let existing_rows = sqlx::query(&select_sql)
+                    .bind(provider_id)
+                    .fetch_all(&mut *tx)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                let mut existing: HashMap<String, Pipeline> = HashMap::new();
+                for row in existing_rows.iter() {
+                    let id: String = row
+                        .try_get(0)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    let provider_id_val: i64 = row
+                        .try_get(1)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    let name: String = row
+                        .try_get(2)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    let status_str: String = row
+                        .try_get(3)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    let repository: String = row
+                        .try_get(4)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    let branch: Option<String> = row
+                        .try_get(5)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    let workflow_file: Option<String> = row
+                        .try_get(6)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    let last_run: Option<DateTime<Utc>> = row
+                        .try_get(7)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    let last_updated: DateTime<Utc> = row
+                        .try_get(8)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    let provider_type: String = row
+                        .try_get(9)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+                    let status = PipelineStatus::from_stored(&status_str);
+
+                    existing.insert(
+                        id.clone(),
+                        Pipeline {
+                            id,
+                            provider_id: provider_id_val,
+                            provider_type,
+                            name,
+                            status,
+                            last_run,
+                            last_updated,
+                            repository,
+                            branch,
+                            workflow_file,
+                            metadata: std::collections::HashMap::new(),
+                            labels: Vec::new(),
+                        },
+                    );
+                }
+
+                let new_ids: HashMap<String, &Pipeline> =
+                    new_pipelines.iter().map(|p| (p.id.clone(), p)).collect();
+
+                for pipeline in new_pipelines {
+                    let now = Utc::now();
+                    let metadata_json = self.encode_pipeline_metadata(pipeline)?;
+                    if let Some(old) = existing.get(&pipeline.id) {
+                        if old.status != pipeline.status
+                            || old.last_run != pipeline.last_run
+                            || old.name != pipeline.name
+                            || old.provider_id != provider_id
+                            || old.provider_type != pipeline.provider_type
+                        {
+                            changed = true;
+                            sqlx::query(&update_sql)
+                                .bind(provider_id)
+                                .bind(&pipeline.name)
+                                .bind(pipeline.status.as_str())
+                                .bind(&pipeline.repository)
+                                .bind(&pipeline.branch)
+                                .bind(&pipeline.workflow_file)
+                                .bind(pipeline.last_run)
+                                .bind(now)
+                                .bind(&metadata_json)
+                                .bind(&pipeline.provider_type)
+                                .bind(&pipeline.id)
+                                .execute(&mut *tx)
+                                .await
+                                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                        }
+                    } else {
+                        changed = true;
+                        sqlx::query(&insert_sql)
+                            .bind(&pipeline.id)
+                            .bind(provider_id)
+                            .bind(&pipeline.name)
+                            .bind(pipeline.status.as_str())
+                            .bind(&pipeline.repository)
+                            .bind(&pipeline.branch)
+                            .bind(&pipeline.workflow_file)
+                            .bind(pipeline.last_run)
+                            .bind(now)
+                            .bind(&metadata_json)
+                            .bind(&pipeline.provider_type)
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    }
+                }
+
+                for old_id in existing.keys() {
+                    if !new_ids.contains_key(old_id) {
+                        changed = true;
+                        sqlx::query(&delete_sql)
+                            .bind(old_id)
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    }
+                }
+
+                tx.commit()
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            }
+        }
+
+        if changed {
+            self.publish_invalidation(CacheInvalidation::Pipelines {
+                provider_id: Some(provider_id),
+            })
+            .await;
+        }
+
+        Ok(changed)
+    }
+
+    pub async fn schema_status(&self) -> DomainResult<SchemaStatus> {
+        const QUERY: &str =
+            "SELECT version, description, success FROM _sqlx_migrations ORDER BY version";
+
+        let applied = with_pool!(self, |p| sqlx::query_as::<_, (i64, String, bool)>(QUERY)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+
+        Ok(SchemaStatus {
+            backend: if self.cache_pool.is_postgres() {
+                "postgres"
+            } else {
+                "sqlite"
+            }
+            .to_string(),
+            latest_known: latest_migration(self.cache_pool.is_postgres()),
+            applied: applied
+                .into_iter()
+                .map(|(version, description, success)| SchemaMigration {
+                    version,
+                    description,
+                    success,
+                })
+                .collect(),
+        })
+    }
+
+    pub async fn get_pipelines_cache_count(&self) -> DomainResult<i64> {
+        let count = with_pool!(self, |p| {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM pipelines_cache")
+                .fetch_one(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        });
+        Ok(count)
+    }
+
+    pub async fn get_run_history_cache_count(&self) -> DomainResult<i64> {
+        let count = with_pool!(self, |p| {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM run_history_cache")
+                .fetch_one(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        });
+        Ok(count)
+    }
+
+    pub async fn get_workflow_params_cache_count(&self) -> DomainResult<i64> {
+        let count = with_pool!(self, |p| {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM workflow_parameters_cache")
+                .fetch_one(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        });
+        Ok(count)
+    }
+
+    pub async fn clear_pipelines_cache(&self) -> DomainResult<usize> {
+        let deleted = with_write_connection!(self, |conn| {
+            let result = sqlx::query("DELETE FROM pipelines_cache")
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            result.rows_affected() as usize
+        });
+
+        self.publish_invalidation(CacheInvalidation::Pipelines { provider_id: None })
+            .await;
+
+        Ok(deleted)
+    }
+
+    pub async fn clear_all_caches_atomic(&self) -> DomainResult<()> {
+        with_write_connection!(self, |conn| {
+            let mut tx = conn
+                .begin()
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+            sqlx::query("DELETE FROM run_history_cache")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+            sqlx::query("DELETE FROM run_annotations_cache")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+            sqlx::query("DELETE FROM branch_status_cache")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+            sqlx::query("DELETE FROM pipelines_cache")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+            sqlx::query("DELETE FROM workflow_parameters_cache")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+            tx.commit()
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+
+        self.publish_invalidation(CacheInvalidation::Pipelines { provider_id: None })
+            .await;
+        self.publish_invalidation(CacheInvalidation::RunHistory { pipeline_id: None })
+            .await;
+
+        Ok(())
+    }
+
+    pub async fn get_cached_agents(
+        &self, provider_id: Option<i64>,
+    ) -> DomainResult<Vec<ProviderAgent>> {
+        let sql = match provider_id {
+            Some(_) => self.sql("SELECT provider_id, agent_data FROM agents_cache WHERE provider_id = ? ORDER BY agent_id"),
+            None => {
+                "SELECT provider_id, agent_data FROM agents_cache ORDER BY provider_id, agent_id"
+                    .to_string()
+            }
+        };
+
+        let rows: Vec<(i64, String)> = with_pool!(self, |p| {
+            let mut query = sqlx::query_as(&sql);
+            if let Some(pid) = provider_id {
+                query = query.bind(pid);
+            }
+            query
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        });
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(provider_id, json)| {
+                serde_json::from_str(&json)
+                    .ok()
+                    .map(|agent| ProviderAgent { provider_id, agent })
+            })
+            .collect())
+    }
+
+    pub async fn replace_cached_agents(
+        &self, provider_id: i64, agents: &[BuildAgent],
+    ) -> DomainResult<()> {
+        let delete_sql = self.sql("DELETE FROM agents_cache WHERE provider_id = ?");
+        let insert_sql = self.sql("INSERT INTO agents_cache (provider_id, agent_id, status, agent_data) VALUES (?, ?, ?, ?)");
+
+        let rows: Vec<(&BuildAgent, String)> = agents
+            .iter()
+            .map(|agent| {
+                serde_json::to_string(agent)
+                    .map(|json| (agent, json))
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))
+            })
+            .collect::<DomainResult<_>>()?;
+
+        with_write_connection!(self, |conn| {
+            let mut tx = conn
+                .begin()
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+            sqlx::query(&delete_sql)
+                .bind(provider_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+            for (agent, json) in &rows {
+                sqlx::query(&insert_sql)
+                    .bind(provider_id)
+                    .bind(&agent.id)
+                    .bind(&agent.status)
+                    .bind(json)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            }
+
+            tx.commit()
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+
+        Ok(())
+    }
+
+    // Keeps the newest MAX_NOTIFICATION_DELIVERIES attempts across all
+    // channels.
+    pub async fn record_notification_delivery(
+        &self, delivery: &NotificationDelivery,
+    ) -> DomainResult<()> {
+        let insert_sql = self
+            .sql("INSERT INTO notification_deliveries (channel_id, delivery_data) VALUES (?, ?)");
+        let trim_sql = format!(
+            "DELETE FROM notification_deliveries WHERE id NOT IN (SELECT id FROM notification_deliveries ORDER BY id DESC LIMIT {})",
+            MAX_NOTIFICATION_DELIVERIES
+        );
+        let json = serde_json::to_string(delivery)
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        with_write_connection!(self, |conn| {
+            sqlx::query(&insert_sql)
+                .bind(delivery.channel_id)
+                .bind(&json)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            sqlx::query(&trim_sql)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+
+        Ok(())
+    }
+
+    pub async fn get_notification_deliveries(
+        &self, channel_id: Option<i64>, limit: usize,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        let sql = match channel_id {
+            Some(_) => format!(
+                "SELECT delivery_data FROM notification_deliveries WHERE channel_id = {} ORDER BY id DESC LIMIT {}",
+                self.placeholder(1),
+                limit
+            ),
+            None => format!(
+                "SELECT delivery_data FROM notification_deliveries ORDER BY id DESC LIMIT {}",
+                limit
+            ),
+        };
+
+        let rows: Vec<(String,)> = with_pool!(self, |p| {
+            let mut query = sqlx::query_as(&sql);
+            if let Some(id) = channel_id {
+                query = query.bind(id);
+            }
+            query
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        });
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(json,)| serde_json::from_str(&json).ok())
+            .collect())
+    }
+
+    pub async fn delete_notification_deliveries(&self, channel_id: i64) -> DomainResult<()> {
+        let sql = self.sql("DELETE FROM notification_deliveries WHERE channel_id = ?");
+
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(channel_id)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+
+        Ok(())
+    }
+
+    // Keeps the newest MAX_NOTIFICATIONS entries, read or not.
+    pub async fn insert_notification(&self, notification: &AppNotification) -> DomainResult<i64> {
+        let insert_sql = self.sql(
+            "INSERT INTO notifications (kind, notification_data, is_read) VALUES (?, ?, ?) \
+             RETURNING id",
+        );
+        let trim_sql = format!(
+            "DELETE FROM notifications WHERE id NOT IN (SELECT id FROM notifications ORDER BY id DESC LIMIT {})",
+            MAX_NOTIFICATIONS
+        );
+        let json = serde_json::to_string(notification)
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let id = with_write_connection!(self, |conn| {
+            let id: i64 = sqlx::query_scalar(&insert_sql)
+                .bind(notification.kind.as_str())
+                .bind(&json)
+                .bind(notification.read)
+                .fetch_one(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            sqlx::query(&trim_sql)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            id
+        });
+
+        Ok(id)
+    }
+
+    pub async fn list_notifications(
+        &self, unread_only: bool, limit: usize,
+    ) -> DomainResult<Vec<AppNotification>> {
+        let filter = if unread_only {
+            self.sql("WHERE is_read = ?")
+        } else {
+            String::new()
+        };
+        let sql = format!(
+            "SELECT id, notification_data, is_read FROM notifications {} ORDER BY id DESC LIMIT {}",
+            filter, limit
+        );
+
+        let rows: Vec<(i64, String, bool)> = with_pool!(self, |p| {
+            let mut query = sqlx::query_as(&sql);
+            if unread_only {
+                query = query.bind(false);
+            }
+            query
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        });
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, json, read)| {
+                let mut notification: AppNotification = serde_json::from_str(&json).ok()?;
+                notification.id = id;
+                notification.read = read;
+                Some(notification)
+            })
+            .collect())
+    }
+
+    pub async fn count_unread_notifications(&self) -> DomainResult<usize> {
+        let sql = self.sql("SELECT COUNT(*) FROM notifications WHERE is_read = ?");
+
+        let count: i64 = with_pool!(self, |p| sqlx::query_scalar(&sql)
+            .bind(false)
+            .fetch_one(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+
+        Ok(count as usize)
+    }
+
+    // Marks one notification as read, or all of them when `id` is None.
+    pub async fn mark_notification_read(&self, id: Option<i64>) -> DomainResult<()> {
+        let sql = match id {
+            Some(_) => self.sql("UPDATE notifications SET is_read = ? WHERE id = ?"),
+            None => self.sql("UPDATE notifications SET is_read = ?"),
+        };
+
+        let affected = with_write_connection!(self, |conn| {
+            let mut query = sqlx::query(&sql).bind(true);
+            if let Some(id) = id {
+                query = query.bind(id);
+            }
+            query
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+                .rows_affected()
+        });
+
+        if let Some(id) = id.filter(|_| affected == 0) {
+            return Err(DomainError::NotFound(format!(
+                "Notification {} not found",
+                id
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn clear_notifications(&self) -> DomainResult<()> {
+        let sql = "DELETE FROM notifications";
+
+        with_write_connection!(self, |conn| {
+            sqlx::query(sql)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
+
+        Ok(())
+    }
+
+    // Keeps the newest MAX_AUDIT_ENTRIES entries.
+    pub async fn insert_audit_entry(&self, entry: &AuditEntry) -> DomainResult<i64> {
+        let insert_sql =
+            self.sql("INSERT INTO audit_log (action, entry_data) VALUES (?, ?) RETURNING id");
+        let trim_sql = format!(
+            "DELETE FROM audit_log WHERE id NOT IN (SELECT id FROM audit_log ORDER BY id DESC LIMIT {})",
+            MAX_AUDIT_ENTRIES
+        );
+        let json =
+            serde_json::to_string(entry).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let id = with_write_connection!(self, |conn| {
+            let id: i64 = sqlx::query_scalar(&insert_sql)
+                .bind(entry.action.as_str())
+                .bind(&json)
+                .fetch_one(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            sqlx::query(&trim_sql)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            id
+        });
+
+        Ok(id)
+    }
+
+    pub async fn list_audit_entries(&self, limit: usize) -> DomainResult<Vec<AuditEntry>> {
+        let sql = format!(
+            "SELECT id, entry_data FROM audit_log ORDER BY id DESC LIMIT {}",
+            limit
+        );
+
+        let rows: Vec<(i64, String)> = with_pool!(self, |p| sqlx::query_as(&sql)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, json)| {
+                let mut entry: AuditEntry = serde_json::from_str(&json).ok()?;
+                entry.id = id;
+                Some(entry)
+            })
+            .collect())
+    }
+
+    pub async fn insert_trigger_approval(&self, approval: &TriggerApproval) -> DomainResult<i64> {
+        let sql = self.sql(
+            "INSERT INTO trigger_approvals (pipeline_id, status, approval_data) VALUES (?, ?, ?) \
+             RETURNING id",
+        );
+        let json = serde_json::to_string(approval)
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let id = with_write_connection!(self, |conn| {
+            sqlx::query_scalar(&sql)
+                .bind(&approval.pipeline_id)
+                .bind(approval.status.as_str())
+                .bind(&json)
+                .fetch_one(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        });
+
+        Ok(id)
+    }
+
+    pub async fn get_trigger_approval(&self, id: i64) -> DomainResult<TriggerApproval> {
+        let sql = self.sql("SELECT approval_data FROM trigger_approvals WHERE id = ?");
+
+        let json: Option<String> = with_pool!(self, |p| sqlx::query_scalar(&sql)
+            .bind(id)
+            .fetch_optional(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+
+        let json =
+            json.ok_or_else(|| DomainError::NotFound(format!("Trigger request {} not found", id)))?;
+        let mut approval: TriggerApproval =
+            serde_json::from_str(&json).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        approval.id = id;
+        Ok(approval)
+    }
+
+    pub async fn list_trigger_approvals(
+        &self, pending_only: bool, limit: usize,
+    ) -> DomainResult<Vec<TriggerApproval>> {
+        let filter = if pending_only {
+            self.sql("WHERE status = ?")
+        } else {
+            String::new()
+        };
+        let sql = format!(
+            "SELECT id, approval_data FROM trigger_approvals {} ORDER BY id DESC LIMIT {}",
+            filter, limit
+        );
+
+        let rows: Vec<(i64, String)> = with_pool!(self, |p| {
+            let mut query = sqlx::query_as(&sql);
+            if pending_only {
+                query = query.bind(ApprovalStatus::Pending.as_str());
+            }
+            query
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        });
 
-                        let new_ids: HashMap<String, &Pipeline> =
-                            new_pipelines_clone.iter().map(|p| (p.id.clone(), p)).collect();
-
-                        for pipeline in &new_pipelines_clone {
-                            if let Some(old) = existing.get(&pipeline.id) {
-                                if old.status != pipeline.status
-                                    || old.last_run != pipeline.last_run
-                                    || old.name != pipeline.name
-                                    || old.provider_id != provider_id
-                                    || old.provider_type != pipeline.provider_type
-                                {
-                                    sqlx::query(update_sql)
-                                        .bind(provider_id)
-                                        .bind(&pipeline.name)
-                                        .bind(pipeline.status.as_str())
-                                        .bind(&pipeline.repository)
-                                        .bind(&pipeline.branch)
-                                        .bind(&pipeline.workflow_file)
-                                        .bind(pipeline.last_run.as_ref().map(|dt| dt.to_rfc3339()))
-                                        .bind(Utc::now().to_rfc3339())
-                                        .bind("{}")
-                                        .bind(&pipeline.provider_type)
-                                        .bind(&pipeline.id)
-                                        .execute(&mut *tx)
-                                        .await
-                                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                                }
-                            } else {
-                                sqlx::query(insert_sql)
-                                    .bind(&pipeline.id)
-                                    .bind(provider_id)
-                                    .bind(&pipeline.name)
-                                    .bind(pipeline.status.as_str())
-                                    .bind(&pipeline.repository)
-                                    .bind(&pipeline.branch)
-                                    .bind(&pipeline.workflow_file)
-                                    .bind(pipeline.last_run.as_ref().map(|dt| dt.to_rfc3339()))
-                                    .bind(Utc::now().to_rfc3339())
-                                    .bind("{}")
-                                    .bind(&pipeline.provider_type)
-                                    .execute(&mut *tx)
-                                    .await
-                                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            }
-                        }
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, json)| {
+                let mut approval: TriggerApproval = serde_json::from_str(&json).ok()?;
+                approval.id = id;
+                Some(approval)
+            })
+            .collect())
+    }
 
-                        for (old_id, _) in existing.iter() {
-                            if !new_ids.contains_key(old_id) {
-                                sqlx::query(delete_sql)
-                                    .bind(old_id)
-                                    .execute(&mut *tx)
-                                    .await
-                                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            }
-                        }
+    // Saves the request, but only if its stored status is still `from`. Returns
+    // false when someone else decided on it first.
+    pub async fn update_trigger_approval(
+        &self, approval: &TriggerApproval, from: ApprovalStatus,
+    ) -> DomainResult<bool> {
+        let sql = self.sql("UPDATE trigger_approvals SET status = ?, approval_data = ? WHERE id = ? AND status = ?");
+        let json = serde_json::to_string(approval)
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-                        tx.commit().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    }
-                    DatabasePool::Postgres(p) => {
-                        let mut tx = p.begin().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let affected = with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(approval.status.as_str())
+                .bind(&json)
+                .bind(approval.id)
+                .bind(from.as_str())
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+                .rows_affected()
+        });
 
-                        let existing_rows = sqlx::query(select_sql)
-                            .bind(provider_id)
-                            .fetch_all(&mut *tx)
-                            .await
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        Ok(affected > 0)
+    }
 
-                        let mut existing: HashMap<String, Pipeline> = HashMap::new();
-                        for row in existing_rows.iter() {
-                            let id: String = row.try_get(0).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            let provider_id_val: i64 = row.try_get(1).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            let name: String = row.try_get(2).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            let status_str: String = row.try_get(3).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            let repository: String = row.try_get(4).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            let branch: Option<String> = row.try_get(5).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            let workflow_file: Option<String> = row.try_get(6).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            let last_run: Option<DateTime<Utc>> = row.try_get(7).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            let last_updated: DateTime<Utc> = row.try_get(8).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            let provider_type: String = row.try_get(9).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-
-                            let status: PipelineStatus = if status_str.trim().is_empty() {
-                                PipelineStatus::Pending
-                            } else if status_str.starts_with('"') {
-                                serde_json::from_str(&status_str).map_err(|e| DomainError::DatabaseError(format!("Failed to parse status '{}': {}", status_str, e)))?
-                            } else {
-                                let quoted = format!("\"{}\"", status_str);
-                                serde_json::from_str(&quoted).map_err(|e| DomainError::DatabaseError(format!("Failed to parse status '{}': {}", status_str, e)))?
-                            };
-
-                            existing.insert(id.clone(), Pipeline {
-                                id, provider_id: provider_id_val, provider_type, name, status,
-                                last_run, last_updated, repository, branch, workflow_file,
-                                metadata: std::collections::HashMap::new(),
-                            });
-                        }
+    pub async fn save_run_backfill_job(&self, job: &RunBackfillJob) -> DomainResult<()> {
+        let sql = self.sql(
+            "INSERT INTO run_backfill_jobs (provider_id, status, job_data, updated_at) VALUES (?, ?, ?, NOW()) ON CONFLICT (provider_id) DO UPDATE SET status = EXCLUDED.status, job_data = EXCLUDED.job_data, updated_at = EXCLUDED.updated_at",
+        );
+        let json =
+            serde_json::to_string(job).map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        with_write_connection!(self, |conn| {
+            sqlx::query(&sql)
+                .bind(job.provider_id)
+                .bind(job.status.as_str())
+                .bind(&json)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
 
-                        let new_ids: HashMap<String, &Pipeline> = new_pipelines_clone.iter().map(|p| (p.id.clone(), p)).collect();
-
-                        for pipeline in &new_pipelines_clone {
-                            let now = Utc::now();
-                            if let Some(old) = existing.get(&pipeline.id) {
-                                if old.status != pipeline.status || old.last_run != pipeline.last_run || old.name != pipeline.name || old.provider_id != provider_id || old.provider_type != pipeline.provider_type {
-                                    sqlx::query(update_sql)
-                                        .bind(provider_id).bind(&pipeline.name).bind(pipeline.status.as_str()).bind(&pipeline.repository).bind(&pipeline.branch)
-                                        .bind(&pipeline.workflow_file).bind(pipeline.last_run).bind(now).bind("{}").bind(&pipeline.provider_type).bind(&pipeline.id)
-                                        .execute(&mut *tx).await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                                }
-                            } else {
-                                sqlx::query(insert_sql)
-                                    .bind(&pipeline.id).bind(provider_id).bind(&pipeline.name).bind(pipeline.status.as_str()).bind(&pipeline.repository).bind(&pipeline.branch)
-                                    .bind(&pipeline.workflow_file).bind(pipeline.last_run).bind(now).bind("{}").bind(&pipeline.provider_type)
-                                    .execute(&mut *tx).await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            }
-                        }
+        Ok(())
+    }
 
-                        for (old_id, _) in existing.iter() {
-                            if !new_ids.contains_key(old_id) {
-                                sqlx::query(delete_sql).bind(old_id).execute(&mut *tx).await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                            }
-                        }
+    pub async fn get_run_backfill_job(
+        &self, provider_id: i64,
+    ) -> DomainResult<Option<RunBackfillJob>> {
+        let sql = self.sql("SELECT job_data FROM run_backfill_jobs WHERE provider_id = ?");
 
-                        tx.commit().await.map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                    }
-                }
-                Ok(())
-            }
+        let json: Option<String> = with_pool!(self, |p| sqlx::query_scalar(&sql)
+            .bind(provider_id)
+            .fetch_optional(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
+
+        json.map(|json| {
+            serde_json::from_str(&json).map_err(|e| DomainError::DatabaseError(e.to_string()))
         })
-        .await
+        .transpose()
     }
 
-    pub async fn get_pipelines_cache_count(&self) -> DomainResult<i64> {
-        let count = match &self.cache_pool {
-            DatabasePool::Sqlite(p) => {
-                sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM pipelines_cache")
-                    .fetch_one(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?
-            }
-            DatabasePool::Postgres(p) => {
-                sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM pipelines_cache")
-                    .fetch_one(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?
-            }
+    pub async fn list_run_backfill_jobs(
+        &self, status: Option<BackfillStatus>,
+    ) -> DomainResult<Vec<RunBackfillJob>> {
+        let filter = if status.is_some() {
+            self.sql("WHERE status = ?")
+        } else {
+            String::new()
         };
-        Ok(count)
-    }
+        let sql = format!(
+            "SELECT job_data FROM run_backfill_jobs {} ORDER BY provider_id",
+            filter
+        );
 
-    pub async fn get_run_history_cache_count(&self) -> DomainResult<i64> {
-        let count = match &self.cache_pool {
-            DatabasePool::Sqlite(p) => {
-                sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM run_history_cache")
-                    .fetch_one(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?
-            }
-            DatabasePool::Postgres(p) => {
-                sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM run_history_cache")
-                    .fetch_one(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        let rows: Vec<String> = with_pool!(self, |p| {
+            let mut query = sqlx::query_scalar(&sql);
+            if let Some(status) = status {
+                query = query.bind(status.as_str());
             }
-        };
-        Ok(count)
-    }
+            query
+                .fetch_all(p)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        });
 
-    pub async fn get_workflow_params_cache_count(&self) -> DomainResult<i64> {
-        let count = match &self.cache_pool {
-            DatabasePool::Sqlite(p) => {
-                sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM workflow_parameters_cache")
-                    .fetch_one(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?
-            }
-            DatabasePool::Postgres(p) => {
-                sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM workflow_parameters_cache")
-                    .fetch_one(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?
-            }
-        };
-        Ok(count)
+        Ok(rows
+            .iter()
+            .filter_map(|json| serde_json::from_str(json).ok())
+            .collect())
     }
 
-    pub async fn clear_pipelines_cache(&self) -> DomainResult<usize> {
-        match &self.cache_pool {
-            DatabasePool::Sqlite(p) => {
-                let result = sqlx::query("DELETE FROM pipelines_cache")
-                    .execute(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                Ok(result.rows_affected() as usize)
-            }
-            DatabasePool::Postgres(p) => {
-                let result = sqlx::query("DELETE FROM pipelines_cache")
-                    .execute(p)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                Ok(result.rows_affected() as usize)
-            }
-        }
+    pub async fn list_notification_channels(&self) -> DomainResult<Vec<NotificationChannel>> {
+        self.config_backend.list_notification_channels().await
     }
 
-    pub async fn clear_all_caches_atomic(&self) -> DomainResult<()> {
-        match &self.cache_pool {
-            DatabasePool::Sqlite(p) => {
-                let mut tx = p
-                    .begin()
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+    pub async fn save_notification_channel(
+        &self, name: &str, kind: &str, url: &str, enabled: bool, rules_json: &str,
+        suppression_json: &str,
+    ) -> DomainResult<i64> {
+        self.config_backend
+            .save_notification_channel(name, kind, url, enabled, rules_json, suppression_json)
+            .await
+    }
 
-                sqlx::query("DELETE FROM run_history_cache")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+    pub async fn delete_notification_channel(&self, id: i64) -> DomainResult<()> {
+        self.config_backend.delete_notification_channel(id).await
+    }
 
-                sqlx::query("DELETE FROM pipelines_cache")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+    pub async fn get_pipeline_labels(&self) -> DomainResult<HashMap<String, Vec<String>>> {
+        let sql = "SELECT pipeline_id, label FROM pipeline_labels ORDER BY pipeline_id, label";
+        let rows: Vec<(String, String)> = with_pool!(self, |p| sqlx::query_as(sql)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
 
-                sqlx::query("DELETE FROM workflow_parameters_cache")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let mut labels: HashMap<String, Vec<String>> = HashMap::new();
+        for (pipeline_id, label) in rows {
+            labels.entry(pipeline_id).or_default().push(label);
+        }
+        Ok(labels)
+    }
 
-                tx.commit()
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-            }
-            DatabasePool::Postgres(p) => {
-                let mut tx = p
-                    .begin()
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+    pub async fn set_pipeline_labels(
+        &self, pipeline_id: &str, labels: &[String],
+    ) -> DomainResult<()> {
+        let delete_sql = self.sql("DELETE FROM pipeline_labels WHERE pipeline_id = ?");
+        let insert_sql = self.sql("INSERT INTO pipeline_labels (pipeline_id, label) VALUES (?, ?)");
 
-                sqlx::query("DELETE FROM run_history_cache")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        with_write_connection!(self, |conn| {
+            let mut tx = conn
+                .begin()
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-                sqlx::query("DELETE FROM pipelines_cache")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            sqlx::query(&delete_sql)
+                .bind(pipeline_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-                sqlx::query("DELETE FROM workflow_parameters_cache")
+            for label in labels {
+                sqlx::query(&insert_sql)
+                    .bind(pipeline_id)
+                    .bind(label)
                     .execute(&mut *tx)
                     .await
                     .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-
-                tx.commit()
-                    .await
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
             }
-        }
+
+            tx.commit()
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        });
 
         Ok(())
     }
@@ -1593,6 +3275,141 @@ impl Repository {
             .await
     }
 
+    pub async fn list_pipeline_groups(&self) -> DomainResult<Vec<PipelineGroup>> {
+        self.config_backend.list_pipeline_groups().await
+    }
+
+    pub async fn create_pipeline_group(&self, name: &str) -> DomainResult<i64> {
+        self.config_backend.create_pipeline_group(name).await
+    }
+
+    pub async fn delete_pipeline_group(&self, id: i64) -> DomainResult<()> {
+        self.config_backend.delete_pipeline_group(id).await
+    }
+
+    pub async fn add_pipeline_to_group(
+        &self, group_id: i64, pipeline_id: &str,
+    ) -> DomainResult<()> {
+        self.config_backend
+            .add_pipeline_to_group(group_id, pipeline_id)
+            .await
+    }
+
+    pub async fn remove_pipeline_from_group(
+        &self, group_id: i64, pipeline_id: &str,
+    ) -> DomainResult<()> {
+        self.config_backend
+            .remove_pipeline_from_group(group_id, pipeline_id)
+            .await
+    }
+
+    pub async fn list_saved_views(&self) -> DomainResult<Vec<SavedView>> {
+        self.config_backend.list_saved_views().await
+    }
+
+    pub async fn save_view(&self, name: &str, query_json: &str) -> DomainResult<i64> {
+        self.config_backend.save_view(name, query_json).await
+    }
+
+    pub async fn delete_saved_view(&self, id: i64) -> DomainResult<()> {
+        self.config_backend.delete_saved_view(id).await
+    }
+
+    pub async fn list_trigger_presets(
+        &self, pipeline_id: &str,
+    ) -> DomainResult<Vec<TriggerPreset>> {
+        self.config_backend.list_trigger_presets(pipeline_id).await
+    }
+
+    pub async fn save_trigger_preset(
+        &self, pipeline_id: &str, name: &str, branch: Option<&str>, parameters_json: &str,
+    ) -> DomainResult<i64> {
+        self.config_backend
+            .save_trigger_preset(pipeline_id, name, branch, parameters_json)
+            .await
+    }
+
+    pub async fn delete_trigger_preset(&self, id: i64) -> DomainResult<()> {
+        self.config_backend.delete_trigger_preset(id).await
+    }
+
+    pub async fn list_pipeline_dependencies(&self) -> DomainResult<Vec<PipelineDependency>> {
+        self.config_backend.list_pipeline_dependencies().await
+    }
+
+    pub async fn save_pipeline_dependency(
+        &self, upstream_id: &str, downstream_id: &str, trigger_on: &str, inputs_json: &str,
+        created_by_role: &str,
+    ) -> DomainResult<i64> {
+        self.config_backend
+            .save_pipeline_dependency(
+                upstream_id,
+                downstream_id,
+                trigger_on,
+                inputs_json,
+                created_by_role,
+            )
+            .await
+    }
+
+    pub async fn delete_pipeline_dependency(&self, id: i64) -> DomainResult<()> {
+        self.config_backend.delete_pipeline_dependency(id).await
+    }
+
+    pub async fn list_pipeline_refresh_settings(
+        &self,
+    ) -> DomainResult<Vec<PipelineRefreshSetting>> {
+        self.config_backend.list_pipeline_refresh_settings().await
+    }
+
+    pub async fn save_pipeline_refresh_setting(
+        &self, pipeline_id: &str, refresh_interval_secs: Option<i64>, snoozed: bool,
+    ) -> DomainResult<()> {
+        self.config_backend
+            .save_pipeline_refresh_setting(pipeline_id, refresh_interval_secs, snoozed)
+            .await
+    }
+
+    pub async fn delete_pipeline_refresh_setting(&self, pipeline_id: &str) -> DomainResult<()> {
+        self.config_backend
+            .delete_pipeline_refresh_setting(pipeline_id)
+            .await
+    }
+
+    pub async fn list_approval_rules(&self) -> DomainResult<Vec<String>> {
+        self.config_backend.list_approval_rules().await
+    }
+
+    pub async fn set_approval_rule(&self, pipeline_id: &str, required: bool) -> DomainResult<()> {
+        self.config_backend
+            .set_approval_rule(pipeline_id, required)
+            .await
+    }
+
+    pub async fn list_maintenance_windows(&self) -> DomainResult<Vec<MaintenanceWindow>> {
+        self.config_backend.list_maintenance_windows().await
+    }
+
+    pub async fn save_maintenance_window(
+        &self, name: &str, window_json: &str,
+    ) -> DomainResult<i64> {
+        self.config_backend
+            .save_maintenance_window(name, window_json)
+            .await
+    }
+
+    pub async fn delete_maintenance_window(&self, id: i64) -> DomainResult<()> {
+        self.config_backend.delete_maintenance_window(id).await
+    }
+
+    pub async fn list_pinned_pipelines(&self) -> DomainResult<Vec<PinnedPipeline>> {
+        self.config_backend.list_pinned_pipelines().await
+    }
+
+    pub async fn save_pinned_pipeline(&self, pin: &PinnedPipeline) -> DomainResult<()> {
+        self.config_backend.save_pinned_pipeline(pin).await
+    }
+
     pub async fn store_provider_permissions(
         &self, provider_id: i64, status: &pipedash_plugin_api::PermissionStatus,
     ) -> DomainResult<()> {
@@ -1650,22 +3467,12 @@ impl Repository {
     }
 
     pub async fn get_cached_run_count(&self, pipeline_id: &str) -> DomainResult<usize> {
-        let sql = format!(
-            "SELECT COUNT(*) FROM run_history_cache WHERE pipeline_id = {}",
-            self.placeholder(1)
-        );
-        let count = match &self.cache_pool {
-            DatabasePool::Sqlite(p) => sqlx::query_scalar::<_, i64>(&sql)
-                .bind(pipeline_id)
-                .fetch_one(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-            DatabasePool::Postgres(p) => sqlx::query_scalar::<_, i64>(&sql)
-                .bind(pipeline_id)
-                .fetch_one(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-        };
+        let sql = self.sql("SELECT COUNT(*) FROM run_history_cache WHERE pipeline_id = ?");
+        let count = with_pool!(self, |p| sqlx::query_scalar::<_, i64>(&sql)
+            .bind(pipeline_id)
+            .fetch_one(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
 
         Ok(count as usize)
     }
@@ -1675,35 +3482,23 @@ impl Repository {
     ) -> DomainResult<Vec<PipelineRun>> {
         let offset = (page - 1) * page_size;
 
-        let sql = format!(
+        let sql = self.sql(
             "SELECT run_data FROM run_history_cache
-             WHERE pipeline_id = {}
+             WHERE pipeline_id = ?
              ORDER BY run_number DESC
-             LIMIT {} OFFSET {}",
-            self.placeholder(1),
-            self.placeholder(2),
-            self.placeholder(3)
+             LIMIT ? OFFSET ?",
         );
-        let runs_json = match &self.cache_pool {
-            DatabasePool::Sqlite(p) => sqlx::query_scalar::<_, String>(&sql)
-                .bind(pipeline_id)
-                .bind(page_size as i64)
-                .bind(offset as i64)
-                .fetch_all(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-            DatabasePool::Postgres(p) => sqlx::query_scalar::<_, String>(&sql)
-                .bind(pipeline_id)
-                .bind(page_size as i64)
-                .bind(offset as i64)
-                .fetch_all(p)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-        };
+        let runs_json = with_pool!(self, |p| sqlx::query_scalar::<_, String>(&sql)
+            .bind(pipeline_id)
+            .bind(page_size as i64)
+            .bind(offset as i64)
+            .fetch_all(p)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?);
 
         let runs: Vec<PipelineRun> = runs_json
             .into_iter()
-            .filter_map(|json| serde_json::from_str::<PipelineRun>(&json).ok())
+            .filter_map(|json| self.decode_run(&json))
             .collect();
 
         Ok(runs)
@@ -1741,24 +3536,7 @@ impl Repository {
             .try_get(6)
             .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-        let status: PipelineStatus = if status_str.trim().is_empty() {
-            PipelineStatus::Pending
-        } else if status_str.starts_with('"') {
-            serde_json::from_str(&status_str).map_err(|e| {
-                DomainError::DatabaseError(format!(
-                    "Failed to parse status '{}': {}",
-                    status_str, e
-                ))
-            })?
-        } else {
-            let quoted = format!("\"{}\"", status_str);
-            serde_json::from_str(&quoted).map_err(|e| {
-                DomainError::DatabaseError(format!(
-                    "Failed to parse status '{}': {}",
-                    status_str, e
-                ))
-            })?
-        };
+        let status = PipelineStatus::from_stored(&status_str);
 
         let (last_run, last_updated) = match &self.cache_pool {
             DatabasePool::Sqlite(_) => {
@@ -1793,6 +3571,13 @@ impl Repository {
         let provider_type: String = row
             .try_get(9)
             .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let metadata_json: String = row
+            .try_get(10)
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let metadata = self
+            .open_payload(&metadata_json)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
 
         Ok(Pipeline {
             id,
@@ -1805,7 +3590,8 @@ impl Repository {
             repository,
             branch,
             workflow_file,
-            metadata: std::collections::HashMap::new(),
+            metadata,
+            labels: Vec::new(),
         })
     }
 }
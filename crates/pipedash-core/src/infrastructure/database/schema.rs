@@ -1,26 +1,61 @@
 use std::path::PathBuf;
+use std::sync::LazyLock;
 use std::time::Duration;
 
+use sqlx::migrate::Migrator;
 use sqlx::postgres::{
     PgPool,
     PgPoolOptions,
 };
 use sqlx::sqlite::{
+    SqliteAutoVacuum,
     SqliteConnectOptions,
+    SqliteJournalMode,
     SqlitePoolOptions,
+    SqliteSynchronous,
 };
-use sqlx::{
-    Executor,
-    SqlitePool,
-};
+use sqlx::SqlitePool;
+
+// Migrations the database has but this build doesn't are ignored, so an older
+// build can still open a database a newer one has migrated.
+static SQLITE_MIGRATOR: LazyLock<Migrator> = LazyLock::new(|| {
+    let mut migrator = sqlx::migrate!("./migrations/sqlite");
+    migrator.set_ignore_missing(true);
+    migrator
+});
+
+static POSTGRES_MIGRATOR: LazyLock<Migrator> = LazyLock::new(|| {
+    let mut migrator = sqlx::migrate!("./migrations/postgres");
+    migrator.set_ignore_missing(true);
+    migrator
+});
+
+// The newest migration this build knows about for the given backend.
+pub(crate) fn latest_migration(postgres: bool) -> Option<i64> {
+    let migrator = if postgres {
+        &POSTGRES_MIGRATOR
+    } else {
+        &SQLITE_MIGRATOR
+    };
+    migrator.iter().map(|migration| migration.version).max()
+}
 
 pub async fn init_database(path: PathBuf) -> anyhow::Result<SqlitePool> {
     let options = SqliteConnectOptions::new()
         .filename(&path)
         .create_if_missing(true)
-        .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
-        .synchronous(sqlx::sqlite::SqliteSynchronous::Normal)
-        .busy_timeout(Duration::from_secs(5)); // Reduced from 30s to 5s to prevent compounding with retries
+        .journal_mode(SqliteJournalMode::Wal)
+        // NORMAL is durable enough under WAL and skips an fsync per commit.
+        .synchronous(SqliteSynchronous::Normal)
+        // Writes are queued through `SqliteWriter`, so this only covers a
+        // checkpoint or another process holding the lock.
+        .busy_timeout(Duration::from_secs(10))
+        .auto_vacuum(SqliteAutoVacuum::Incremental)
+        // Set on every connection, not just the first one the pool hands out.
+        .pragma("cache_size", "-64000") // 64MB cache
+        .pragma("temp_store", "MEMORY")
+        .pragma("mmap_size", "268435456") // 256MB mmap
+        .pragma("wal_autocheckpoint", "1000"); // Checkpoint every 1000 pages
 
     let pool = SqlitePoolOptions::new()
         .max_connections(50)
@@ -31,16 +66,7 @@ pub async fn init_database(path: PathBuf) -> anyhow::Result<SqlitePool> {
         .connect_with(options)
         .await?;
 
-    sqlx::migrate!("./migrations/sqlite")
-        .set_ignore_missing(true)
-        .run(&pool)
-        .await?;
-
-    pool.execute("PRAGMA cache_size = -64000").await?; // 64MB cache
-    pool.execute("PRAGMA temp_store = MEMORY").await?;
-    pool.execute("PRAGMA mmap_size = 268435456").await?; // 256MB mmap
-    pool.execute("PRAGMA auto_vacuum = INCREMENTAL").await?; // Enables incremental auto-vacuum
-    pool.execute("PRAGMA wal_autocheckpoint = 1000").await?; // Checkpoint every 1000 pages
+    SQLITE_MIGRATOR.run(&pool).await?;
 
     Ok(pool)
 }
@@ -63,10 +89,7 @@ pub async fn init_postgres_database(connection_string: &str) -> anyhow::Result<P
         .connect(connection_string)
         .await?;
 
-    sqlx::migrate!("./migrations/postgres")
-        .set_ignore_missing(true)
-        .run(&pool)
-        .await?;
+    POSTGRES_MIGRATOR.run(&pool).await?;
 
     Ok(pool)
 }
@@ -0,0 +1,553 @@
+use std::collections::{
+    BTreeMap,
+    HashMap,
+    HashSet,
+};
+use std::path::PathBuf;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use sqlx::postgres::PgPool;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{
+    ConnectOptions,
+    Connection,
+    Row as SqlxRow,
+    SqlitePool,
+};
+
+use super::DatabasePool;
+use crate::domain::{
+    BackupFormat,
+    DomainError,
+    DomainResult,
+};
+
+// Bookkeeping tables that describe the schema rather than hold data; the
+// running binary owns them, not the backup.
+const EXCLUDED_TABLES: &[&str] = &["_sqlx_migrations", "cache_objects"];
+
+// Every backup must contain this table, which rules out restoring some
+// unrelated database file.
+const REQUIRED_TABLE: &str = "providers";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PostgresSnapshot {
+    tables: BTreeMap<String, TableSnapshot>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TableSnapshot {
+    columns: Vec<String>,
+    rows: serde_json::Value,
+}
+
+fn db_err(e: impl std::fmt::Display) -> DomainError {
+    DomainError::DatabaseError(e.to_string())
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// Scratch file for SQLite, which can only vacuum into and attach files.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(extension: &str) -> Self {
+        Self(std::env::temp_dir().join(format!(
+            "pipedash-backup-{:016x}.{}",
+            rand::random::<u64>(),
+            extension
+        )))
+    }
+
+    fn path_str(&self) -> String {
+        self.0.to_string_lossy().into_owned()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+pub fn snapshot_format(pool: &DatabasePool) -> BackupFormat {
+    match pool {
+        DatabasePool::Sqlite(_) => BackupFormat::Sqlite,
+        DatabasePool::Postgres(_) => BackupFormat::Postgres,
+    }
+}
+
+pub async fn export_snapshot(pool: &DatabasePool) -> DomainResult<Vec<u8>> {
+    match pool {
+        DatabasePool::Sqlite(p) => export_sqlite(p).await,
+        DatabasePool::Postgres(p) => export_postgres(p).await,
+    }
+}
+
+pub async fn verify_snapshot(format: BackupFormat, data: &[u8]) -> DomainResult<()> {
+    match format {
+        BackupFormat::Sqlite => verify_sqlite(data).await,
+        BackupFormat::Postgres => parse_postgres(data).map(|_| ()),
+    }
+}
+
+// Replaces the rows of every table with the snapshot's, keeping the current
+// schema. Columns missing from an older snapshot fall back to their defaults.
+pub async fn import_snapshot(pool: &DatabasePool, data: &[u8]) -> DomainResult<()> {
+    match pool {
+        DatabasePool::Sqlite(p) => import_sqlite(p, data).await,
+        DatabasePool::Postgres(p) => import_postgres(p, data).await,
+    }
+}
+
+async fn export_sqlite(pool: &SqlitePool) -> DomainResult<Vec<u8>> {
+    let file = TempFile::new("db");
+
+    sqlx::query("VACUUM INTO ?")
+        .bind(file.path_str())
+        .execute(pool)
+        .await
+        .map_err(db_err)?;
+
+    tokio::fs::read(&file.0).await.map_err(db_err)
+}
+
+async fn verify_sqlite(data: &[u8]) -> DomainResult<()> {
+    let file = TempFile::new("db");
+    tokio::fs::write(&file.0, data).await.map_err(db_err)?;
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(&file.0)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(db_err)?;
+
+    let result = async {
+        let check: String = sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_one(&mut conn)
+            .await
+            .map_err(db_err)?;
+        if check != "ok" {
+            return Err(DomainError::DatabaseError(format!(
+                "Integrity check failed: {}",
+                check
+            )));
+        }
+
+        let has_required: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+        )
+        .bind(REQUIRED_TABLE)
+        .fetch_one(&mut conn)
+        .await
+        .map_err(db_err)?;
+        if has_required == 0 {
+            return Err(DomainError::DatabaseError(
+                "Backup is not a Pipedash database".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+    .await;
+
+    let _ = conn.close().await;
+    result
+}
+
+// Plain tables only: FTS tables and their shadow tables are rebuilt by the
+// triggers on the tables they index.
+async fn sqlite_tables(
+    conn: &mut sqlx::SqliteConnection, schema: &str,
+) -> DomainResult<Vec<String>> {
+    let rows = sqlx::query(&format!(
+        "SELECT name, sql FROM {}.sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+        schema
+    ))
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(db_err)?;
+
+    let entries: Vec<(String, String)> = rows
+        .iter()
+        .map(|row| {
+            (
+                row.get::<String, _>("name"),
+                row.get::<Option<String>, _>("sql").unwrap_or_default(),
+            )
+        })
+        .collect();
+    let virtual_tables: Vec<&String> = entries
+        .iter()
+        .filter(|(_, sql)| sql.to_uppercase().starts_with("CREATE VIRTUAL TABLE"))
+        .map(|(name, _)| name)
+        .collect();
+
+    Ok(entries
+        .iter()
+        .filter(|(name, sql)| {
+            !sql.to_uppercase().starts_with("CREATE VIRTUAL TABLE")
+                && !EXCLUDED_TABLES.contains(&name.as_str())
+                && !virtual_tables
+                    .iter()
+                    .any(|vt| name.starts_with(&format!("{}_", vt)))
+        })
+        .map(|(name, _)| name.clone())
+        .collect())
+}
+
+async fn sqlite_columns(
+    conn: &mut sqlx::SqliteConnection, schema: &str, table: &str,
+) -> DomainResult<Vec<String>> {
+    let rows = sqlx::query(&format!(
+        "PRAGMA {}.table_info({})",
+        schema,
+        quote_ident(table)
+    ))
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(db_err)?;
+
+    Ok(rows
+        .iter()
+        .map(|row| row.get::<String, _>("name"))
+        .collect())
+}
+
+async fn import_sqlite(pool: &SqlitePool, data: &[u8]) -> DomainResult<()> {
+    let file = TempFile::new("db");
+    tokio::fs::write(&file.0, data).await.map_err(db_err)?;
+
+    let mut conn = pool.acquire().await.map_err(db_err)?;
+    sqlx::query("ATTACH DATABASE ? AS backup")
+        .bind(file.path_str())
+        .execute(&mut *conn)
+        .await
+        .map_err(db_err)?;
+
+    let result = async {
+        let tables = sqlite_tables(&mut conn, "main").await?;
+        let backup_tables: HashSet<String> = sqlite_tables(&mut conn, "backup")
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut tx = conn.begin().await.map_err(db_err)?;
+        sqlx::query("PRAGMA defer_foreign_keys = ON")
+            .execute(&mut *tx)
+            .await
+            .map_err(db_err)?;
+
+        // Clear everything before inserting so that cascading deletes cannot
+        // remove rows that were already restored.
+        for table in &tables {
+            sqlx::query(&format!("DELETE FROM main.{}", quote_ident(table)))
+                .execute(&mut *tx)
+                .await
+                .map_err(db_err)?;
+        }
+
+        for table in &tables {
+            if !backup_tables.contains(table) {
+                continue;
+            }
+
+            let current = sqlite_columns(&mut tx, "main", table).await?;
+            let saved: HashSet<String> = sqlite_columns(&mut tx, "backup", table)
+                .await?
+                .into_iter()
+                .collect();
+            let columns: Vec<String> = current
+                .into_iter()
+                .filter(|c| saved.contains(c))
+                .map(|c| quote_ident(&c))
+                .collect();
+            if columns.is_empty() {
+                continue;
+            }
+
+            let columns = columns.join(", ");
+            sqlx::query(&format!(
+                "INSERT INTO main.{table} ({columns}) SELECT {columns} FROM backup.{table}",
+                table = quote_ident(table),
+                columns = columns
+            ))
+            .execute(&mut *tx)
+            .await
+            .map_err(db_err)?;
+        }
+
+        tx.commit().await.map_err(db_err)
+    }
+    .await;
+
+    let _ = sqlx::query("DETACH DATABASE backup")
+        .execute(&mut *conn)
+        .await;
+    result
+}
+
+async fn postgres_tables(conn: &mut sqlx::PgConnection) -> DomainResult<Vec<String>> {
+    let tables: Vec<String> = sqlx::query_scalar(
+        "SELECT table_name::text FROM information_schema.tables
+         WHERE table_schema = current_schema() AND table_type = 'BASE TABLE'
+         ORDER BY table_name",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(db_err)?;
+
+    Ok(tables
+        .into_iter()
+        .filter(|t| !EXCLUDED_TABLES.contains(&t.as_str()))
+        .collect())
+}
+
+async fn postgres_columns(conn: &mut sqlx::PgConnection, table: &str) -> DomainResult<Vec<String>> {
+    sqlx::query_scalar(
+        "SELECT column_name::text FROM information_schema.columns
+         WHERE table_schema = current_schema() AND table_name = $1
+         ORDER BY ordinal_position",
+    )
+    .bind(table)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(db_err)
+}
+
+async fn export_postgres(pool: &PgPool) -> DomainResult<Vec<u8>> {
+    let mut tx = pool.begin().await.map_err(db_err)?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+        .execute(&mut *tx)
+        .await
+        .map_err(db_err)?;
+
+    let mut snapshot = PostgresSnapshot {
+        tables: BTreeMap::new(),
+    };
+    for table in postgres_tables(&mut tx).await? {
+        let columns = postgres_columns(&mut tx, &table).await?;
+        let rows: String = sqlx::query_scalar(&format!(
+            "SELECT COALESCE(json_agg(t), '[]'::json)::text FROM {} t",
+            quote_ident(&table)
+        ))
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_err)?;
+        let rows = serde_json::from_str(&rows).map_err(db_err)?;
+
+        snapshot
+            .tables
+            .insert(table, TableSnapshot { columns, rows });
+    }
+
+    tx.commit().await.map_err(db_err)?;
+    serde_json::to_vec(&snapshot).map_err(db_err)
+}
+
+fn parse_postgres(data: &[u8]) -> DomainResult<PostgresSnapshot> {
+    let snapshot: PostgresSnapshot = serde_json::from_slice(data)
+        .map_err(|e| DomainError::DatabaseError(format!("Backup is not readable: {}", e)))?;
+
+    if !snapshot.tables.contains_key(REQUIRED_TABLE) {
+        return Err(DomainError::DatabaseError(
+            "Backup is not a Pipedash database".to_string(),
+        ));
+    }
+    if let Some((table, _)) = snapshot.tables.iter().find(|(_, t)| !t.rows.is_array()) {
+        return Err(DomainError::DatabaseError(format!(
+            "Backup rows for table {} are malformed",
+            table
+        )));
+    }
+
+    Ok(snapshot)
+}
+
+// Orders tables so that every table comes after the tables it references.
+fn dependency_order(tables: &[String], references: &[(String, String)]) -> Vec<String> {
+    let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (child, parent) in references {
+        if child != parent {
+            parents
+                .entry(child.as_str())
+                .or_default()
+                .push(parent.as_str());
+        }
+    }
+
+    fn visit<'a>(
+        table: &'a str, parents: &HashMap<&'a str, Vec<&'a str>>, done: &mut HashSet<&'a str>,
+        order: &mut Vec<String>,
+    ) {
+        if !done.insert(table) {
+            return;
+        }
+        for parent in parents.get(table).into_iter().flatten() {
+            visit(parent, parents, done, order);
+        }
+        order.push(table.to_string());
+    }
+
+    let known: HashSet<&str> = tables.iter().map(String::as_str).collect();
+    let mut done = HashSet::new();
+    let mut order = Vec::new();
+    for table in tables {
+        visit(table, &parents, &mut done, &mut order);
+    }
+    order.retain(|t| known.contains(t.as_str()));
+    order
+}
+
+async fn import_postgres(pool: &PgPool, data: &[u8]) -> DomainResult<()> {
+    let snapshot = parse_postgres(data)?;
+    let mut tx = pool.begin().await.map_err(db_err)?;
+
+    let tables = postgres_tables(&mut tx).await?;
+    if tables.is_empty() {
+        return Ok(());
+    }
+
+    let references: Vec<(String, String)> = sqlx::query_as(
+        "SELECT conrelid::regclass::text, confrelid::regclass::text FROM pg_constraint
+         WHERE contype = 'f' AND connamespace = current_schema()::regnamespace",
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_err)?;
+
+    let all = tables
+        .iter()
+        .map(|t| quote_ident(t))
+        .collect::<Vec<_>>()
+        .join(", ");
+    sqlx::query(&format!("TRUNCATE {}", all))
+        .execute(&mut *tx)
+        .await
+        .map_err(db_err)?;
+
+    for table in dependency_order(&tables, &references) {
+        let Some(saved) = snapshot.tables.get(&table) else {
+            continue;
+        };
+
+        let saved_columns: HashSet<&String> = saved.columns.iter().collect();
+        let columns: Vec<String> = postgres_columns(&mut tx, &table)
+            .await?
+            .into_iter()
+            .filter(|c| saved_columns.contains(c))
+            .map(|c| quote_ident(&c))
+            .collect();
+        if columns.is_empty() {
+            continue;
+        }
+
+        let columns = columns.join(", ");
+        sqlx::query(&format!(
+            "INSERT INTO {table} ({columns}) SELECT {columns} FROM json_populate_recordset(NULL::{table}, $1::json)",
+            table = quote_ident(&table),
+            columns = columns
+        ))
+        .bind(saved.rows.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(db_err)?;
+    }
+
+    // Restored rows keep their ids, so serial sequences must move past them.
+    let serial_columns: Vec<(String, String)> = sqlx::query_as(
+        "SELECT table_name::text, column_name::text FROM information_schema.columns
+         WHERE table_schema = current_schema() AND column_default LIKE 'nextval%'",
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_err)?;
+
+    for (table, column) in serial_columns {
+        if !tables.contains(&table) {
+            continue;
+        }
+        sqlx::query(&format!(
+            "SELECT setval(pg_get_serial_sequence($1, $2), COALESCE((SELECT MAX({column}) FROM {table}), 0) + 1, false)",
+            column = quote_ident(&column),
+            table = quote_ident(&table)
+        ))
+        .bind(quote_ident(&table))
+        .bind(&column)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_err)?;
+    }
+
+    tx.commit().await.map_err(db_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_database;
+
+    #[test]
+    fn test_dependency_order() {
+        let tables: Vec<String> = ["group_members", "pipeline_groups", "providers"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let references = vec![("group_members".to_string(), "pipeline_groups".to_string())];
+
+        let order = dependency_order(&tables, &references);
+        let position = |t: &str| order.iter().position(|o| o == t).unwrap();
+        assert_eq!(order.len(), 3);
+        assert!(position("pipeline_groups") < position("group_members"));
+    }
+
+    #[test]
+    fn test_parse_postgres_requires_providers() {
+        let data = br#"{"tables": {"providers": {"columns": ["id"], "rows": []}}}"#;
+        assert!(parse_postgres(data).is_ok());
+
+        let data = br#"{"tables": {"other": {"columns": ["id"], "rows": []}}}"#;
+        assert!(parse_postgres(data).is_err());
+        assert!(parse_postgres(b"not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let sqlite = init_database(dir.path().join("test.db")).await.unwrap();
+        sqlx::query("INSERT INTO pipeline_groups (id, name) VALUES (1, 'deploys')")
+            .execute(&sqlite)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO pipeline_group_members (group_id, pipeline_id) VALUES (1, 'p')")
+            .execute(&sqlite)
+            .await
+            .unwrap();
+        let pool = DatabasePool::Sqlite(sqlite.clone());
+
+        let data = export_snapshot(&pool).await.unwrap();
+        verify_snapshot(BackupFormat::Sqlite, &data).await.unwrap();
+
+        sqlx::query("DELETE FROM pipeline_groups")
+            .execute(&sqlite)
+            .await
+            .unwrap();
+
+        import_snapshot(&pool, &data).await.unwrap();
+        let members: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pipeline_group_members")
+            .fetch_one(&sqlite)
+            .await
+            .unwrap();
+        assert_eq!(members, 1);
+
+        assert!(verify_snapshot(BackupFormat::Sqlite, b"garbage")
+            .await
+            .is_err());
+    }
+}
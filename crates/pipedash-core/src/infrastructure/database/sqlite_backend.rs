@@ -1,118 +1,125 @@
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::OnceLock;
 
 use async_trait::async_trait;
 use sqlx::{
+    Connection,
     Row as SqlxRow,
     SqlitePool,
 };
-use tokio::time::sleep;
 
+use super::sqlite_writer::SqliteWriter;
 use crate::domain::{
     DomainError,
     DomainResult,
+    MaintenanceWindow,
+    NotificationChannel,
+    PinnedPipeline,
+    PipelineDependency,
+    PipelineGroup,
+    PipelineRefreshSetting,
     ProviderConfig,
+    SavedView,
+    TriggerPreset,
 };
+use crate::infrastructure::config::bundle::remap_pipeline_id;
 use crate::infrastructure::config_backend::{
     ConfigBackend,
     ConfigExport,
     StoredPermissions,
 };
 
-async fn retry_on_busy<F, Fut, T>(operation: F) -> DomainResult<T>
-where
-    F: Fn() -> Fut,
-    Fut: std::future::Future<Output = DomainResult<T>>,
-{
-    const MAX_RETRIES: u32 = 3; // Reduced from 10 to 3 to prevent 5-minute delays
-    const INITIAL_DELAY_MS: u64 = 10;
-
-    let mut attempt = 0;
-    loop {
-        match operation().await {
-            Ok(result) => {
-                if attempt > 0 {
-                    tracing::warn!(
-                        "SQLite operation succeeded after {} retries ({}ms total)",
-                        attempt,
-                        INITIAL_DELAY_MS * (2_u64.pow(attempt) - 1)
-                    );
-                }
-                return Ok(result);
-            }
-            Err(DomainError::DatabaseError(ref msg))
-                if (msg.contains("database is locked")
-                    || msg.contains("SQLITE_BUSY")
-                    || msg.contains("locked"))
-                    && attempt < MAX_RETRIES =>
-            {
-                attempt += 1;
-                let delay = INITIAL_DELAY_MS * 2_u64.pow(attempt - 1);
-
-                if attempt > 3 {
-                    tracing::warn!(
-                        "SQLite busy, retry {}/{} (waiting {}ms)",
-                        attempt,
-                        MAX_RETRIES,
-                        delay
-                    );
-                }
-
-                sleep(Duration::from_millis(delay)).await;
-            }
-            Err(e) => {
-                if attempt > 0 {
-                    tracing::error!("SQLite operation failed after {} retries: {}", attempt, e);
-                }
-                return Err(e);
-            }
-        }
-    }
-}
-
 pub struct SqliteConfigBackend {
     pool: SqlitePool,
+    // Shared with the `Repository` on the same database, so config and cache
+    // writes queue on one connection. Started on the first write.
+    writer: OnceLock<SqliteWriter>,
 }
 
 impl SqliteConfigBackend {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            writer: OnceLock::new(),
+        }
     }
 
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
+
+    fn writer(&self) -> &SqliteWriter {
+        self.writer
+            .get_or_init(|| SqliteWriter::spawn(self.pool.clone()))
+    }
 }
 
 #[async_trait]
 impl ConfigBackend for SqliteConfigBackend {
+    fn sqlite_writer(&self) -> Option<SqliteWriter> {
+        Some(self.writer().clone())
+    }
+
     async fn list_providers(&self) -> DomainResult<Vec<ProviderConfig>> {
-        retry_on_busy(|| async {
-            let rows = sqlx::query(
-                r#"SELECT id, name, provider_type, token_encrypted, config_json, refresh_interval, version FROM providers"#,
-            )
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| DomainError::DatabaseError(format!("Failed to list providers: {}", e)))?;
+        let rows = sqlx::query(
+            r#"SELECT id, name, provider_type, token_encrypted, config_json, refresh_interval, version, enabled FROM providers"#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to list providers: {}", e)))?;
+
+        let mut providers = Vec::new();
+        for row in rows {
+            let id: i64 = row.get("id");
+            let config_json: String = row.get("config_json");
+            let config: HashMap<String, String> = if config_json.trim().is_empty() {
+                HashMap::new()
+            } else {
+                serde_json::from_str(&config_json).map_err(|e| {
+                    DomainError::DatabaseError(format!("Failed to parse config for {}: {}", id, e))
+                })?
+            };
 
-            let mut providers = Vec::new();
-            for row in rows {
-                let id: i64 = row.get("id");
+            let token_ref: String = row.get("token_encrypted");
+
+            providers.push(ProviderConfig {
+                id: Some(id),
+                name: row.get("name"),
+                provider_type: row.get("provider_type"),
+                config,
+                token: token_ref,
+                refresh_interval: row.get("refresh_interval"),
+                version: Some(row.get("version")),
+                enabled: row.get("enabled"),
+            });
+        }
+
+        Ok(providers)
+    }
+
+    async fn get_provider(&self, id: i64) -> DomainResult<Option<ProviderConfig>> {
+        let row = sqlx::query(
+            r#"SELECT id, name, provider_type, token_encrypted, config_json, refresh_interval, version, enabled FROM providers WHERE id = ?"#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to get provider: {}", e)))?;
+
+        match row {
+            Some(row) => {
                 let config_json: String = row.get("config_json");
                 let config: HashMap<String, String> = if config_json.trim().is_empty() {
                     HashMap::new()
                 } else {
                     serde_json::from_str(&config_json).map_err(|e| {
-                        DomainError::DatabaseError(format!(
-                            "Failed to parse config for {}: {}",
-                            id, e
-                        ))
+                        DomainError::DatabaseError(format!("Failed to parse config: {}", e))
                     })?
                 };
 
                 let token_ref: String = row.get("token_encrypted");
 
-                providers.push(ProviderConfig {
+                Ok(Some(ProviderConfig {
                     id: Some(id),
                     name: row.get("name"),
                     provider_type: row.get("provider_type"),
@@ -120,196 +127,857 @@ impl ConfigBackend for SqliteConfigBackend {
                     token: token_ref,
                     refresh_interval: row.get("refresh_interval"),
                     version: Some(row.get("version")),
-                });
+                    enabled: row.get("enabled"),
+                }))
             }
+            None => Ok(None),
+        }
+    }
 
-            Ok(providers)
-        })
+    async fn create_provider(&self, config: &ProviderConfig) -> DomainResult<i64> {
+        let mut conn = self.writer().acquire().await?;
+
+        let existing = sqlx::query_scalar::<_, i64>("SELECT id FROM providers WHERE name = ?")
+            .bind(&config.name)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to check existing: {}", e)))?;
+
+        if existing.is_some() {
+            return Err(DomainError::InvalidConfig(format!(
+                "Provider '{}' already exists",
+                config.name
+            )));
+        }
+
+        let config_json = serde_json::to_string(&config.config).map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to serialize config: {}", e))
+        })?;
+
+        let result = sqlx::query(
+            r#"INSERT INTO providers (name, provider_type, token_encrypted, config_json, refresh_interval, enabled)
+               VALUES (?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(&config.name)
+        .bind(&config.provider_type)
+        .bind(&config.token)
+        .bind(&config_json)
+        .bind(config.refresh_interval)
+        .bind(config.enabled)
+        .execute(&mut *conn)
         .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to insert provider: {}", e)))?;
+
+        Ok(result.last_insert_rowid())
     }
 
-    async fn get_provider(&self, id: i64) -> DomainResult<Option<ProviderConfig>> {
-        retry_on_busy(|| async {
-            let row = sqlx::query(
-                r#"SELECT id, name, provider_type, token_encrypted, config_json, refresh_interval, version FROM providers WHERE id = ?"#,
-            )
+    async fn update_provider(&self, id: i64, config: &ProviderConfig) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        let config_json = serde_json::to_string(&config.config).map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to serialize config: {}", e))
+        })?;
+
+        let result = sqlx::query(
+            r#"UPDATE providers
+               SET name = ?, provider_type = ?, token_encrypted = ?, config_json = ?, refresh_interval = ?, updated_at = datetime('now')
+               WHERE id = ?"#,
+        )
+        .bind(&config.name)
+        .bind(&config.provider_type)
+        .bind(&config.token)
+        .bind(&config_json)
+        .bind(config.refresh_interval)
+        .bind(id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to update provider: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::ProviderNotFound(format!(
+                "Provider {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_provider(&self, id: i64) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        let _ = sqlx::query("DELETE FROM pipelines_cache WHERE provider_id = ?")
             .bind(id)
-            .fetch_optional(&self.pool)
-            .await
-            .map_err(|e| DomainError::DatabaseError(format!("Failed to get provider: {}", e)))?;
-
-            match row {
-                Some(row) => {
-                    let config_json: String = row.get("config_json");
-                    let config: HashMap<String, String> = if config_json.trim().is_empty() {
-                        HashMap::new()
-                    } else {
-                        serde_json::from_str(&config_json).map_err(|e| {
-                            DomainError::DatabaseError(format!("Failed to parse config: {}", e))
-                        })?
-                    };
-
-                    let token_ref: String = row.get("token_encrypted");
-
-                    Ok(Some(ProviderConfig {
-                        id: Some(id),
-                        name: row.get("name"),
-                        provider_type: row.get("provider_type"),
-                        config,
-                        token: token_ref,
-                        refresh_interval: row.get("refresh_interval"),
-                        version: Some(row.get("version")),
-                    }))
-                }
-                None => Ok(None),
-            }
-        })
+            .execute(&mut *conn)
+            .await;
+
+        let _ = sqlx::query("DELETE FROM provider_permissions WHERE provider_id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await;
+
+        let _ = sqlx::query("DELETE FROM table_preferences WHERE provider_id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await;
+
+        let result = sqlx::query("DELETE FROM providers WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to delete provider: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::ProviderNotFound(format!(
+                "Provider {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn get_table_preferences(
+        &self, provider_id: i64, table_id: &str,
+    ) -> DomainResult<Option<String>> {
+        let result = sqlx::query_scalar::<_, String>(
+            "SELECT preferences_json FROM table_preferences WHERE provider_id = ? AND table_id = ?",
+        )
+        .bind(provider_id)
+        .bind(table_id)
+        .fetch_optional(&self.pool)
         .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to get preferences: {}", e)))?;
+
+        Ok(result)
     }
 
-    async fn create_provider(&self, config: &ProviderConfig) -> DomainResult<i64> {
-        retry_on_busy(|| async {
-            let existing = sqlx::query_scalar::<_, i64>("SELECT id FROM providers WHERE name = ?")
-                .bind(&config.name)
-                .fetch_optional(&self.pool)
+    async fn set_table_preferences(
+        &self, provider_id: i64, table_id: &str, preferences_json: &str,
+    ) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        sqlx::query(
+            r#"INSERT INTO table_preferences (provider_id, table_id, preferences_json, created_at, updated_at)
+               VALUES (?, ?, ?, datetime('now'), datetime('now'))
+               ON CONFLICT(provider_id, table_id) DO UPDATE SET
+                   preferences_json = excluded.preferences_json,
+                   updated_at = datetime('now')"#,
+        )
+        .bind(provider_id)
+        .bind(table_id)
+        .bind(preferences_json)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to save preferences: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn list_pipeline_groups(&self) -> DomainResult<Vec<PipelineGroup>> {
+        let group_rows =
+            sqlx::query("SELECT id, name, created_at FROM pipeline_groups ORDER BY name")
+                .fetch_all(&self.pool)
                 .await
-                .map_err(|e| {
-                    DomainError::DatabaseError(format!("Failed to check existing: {}", e))
-                })?;
+                .map_err(|e| DomainError::DatabaseError(format!("Failed to list groups: {}", e)))?;
 
-            if existing.is_some() {
-                return Err(DomainError::InvalidConfig(format!(
-                    "Provider '{}' already exists",
-                    config.name
-                )));
-            }
+        let member_rows = sqlx::query(
+            "SELECT group_id, pipeline_id FROM pipeline_group_members ORDER BY pipeline_id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to list group members: {}", e)))?;
+
+        let mut members: HashMap<i64, Vec<String>> = HashMap::new();
+        for row in member_rows {
+            members
+                .entry(row.get("group_id"))
+                .or_default()
+                .push(row.get("pipeline_id"));
+        }
+
+        Ok(group_rows
+            .into_iter()
+            .map(|row| {
+                let id: i64 = row.get("id");
+                PipelineGroup {
+                    id,
+                    name: row.get("name"),
+                    pipeline_ids: members.remove(&id).unwrap_or_default(),
+                    created_at: row
+                        .try_get("created_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                }
+            })
+            .collect())
+    }
 
-            let config_json = serde_json::to_string(&config.config)
-                .map_err(|e| DomainError::DatabaseError(format!("Failed to serialize config: {}", e)))?;
+    async fn create_pipeline_group(&self, name: &str) -> DomainResult<i64> {
+        let mut conn = self.writer().acquire().await?;
 
-            let result = sqlx::query(
-                r#"INSERT INTO providers (name, provider_type, token_encrypted, config_json, refresh_interval)
-                   VALUES (?, ?, ?, ?, ?)"#,
-            )
-            .bind(&config.name)
-            .bind(&config.provider_type)
-            .bind(&config.token)
-            .bind(&config_json)
-            .bind(config.refresh_interval)
-            .execute(&self.pool)
+        let result = sqlx::query("INSERT INTO pipeline_groups (name) VALUES (?)")
+            .bind(name)
+            .execute(&mut *conn)
             .await
-            .map_err(|e| DomainError::DatabaseError(format!("Failed to insert provider: {}", e)))?;
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to create group: {}", e)))?;
 
-            Ok(result.last_insert_rowid())
-        })
+        Ok(result.last_insert_rowid())
+    }
+
+    async fn delete_pipeline_group(&self, id: i64) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        sqlx::query("DELETE FROM pipeline_group_members WHERE group_id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete group members: {}", e))
+            })?;
+
+        let result = sqlx::query("DELETE FROM pipeline_groups WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to delete group: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!("Group {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    async fn add_pipeline_to_group(&self, group_id: i64, pipeline_id: &str) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        sqlx::query(
+            "INSERT OR IGNORE INTO pipeline_group_members (group_id, pipeline_id) VALUES (?, ?)",
+        )
+        .bind(group_id)
+        .bind(pipeline_id)
+        .execute(&mut *conn)
         .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to assign pipeline to group: {}", e))
+        })?;
+
+        Ok(())
     }
 
-    async fn update_provider(&self, id: i64, config: &ProviderConfig) -> DomainResult<()> {
-        retry_on_busy(|| async {
-            let config_json = serde_json::to_string(&config.config)
-                .map_err(|e| DomainError::DatabaseError(format!("Failed to serialize config: {}", e)))?;
-
-            let result = sqlx::query(
-                r#"UPDATE providers
-                   SET name = ?, provider_type = ?, token_encrypted = ?, config_json = ?, refresh_interval = ?, updated_at = datetime('now')
-                   WHERE id = ?"#,
-            )
-            .bind(&config.name)
-            .bind(&config.provider_type)
-            .bind(&config.token)
-            .bind(&config_json)
-            .bind(config.refresh_interval)
+    async fn remove_pipeline_from_group(
+        &self, group_id: i64, pipeline_id: &str,
+    ) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        sqlx::query("DELETE FROM pipeline_group_members WHERE group_id = ? AND pipeline_id = ?")
+            .bind(group_id)
+            .bind(pipeline_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to remove pipeline from group: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    async fn list_saved_views(&self) -> DomainResult<Vec<SavedView>> {
+        let rows = sqlx::query(
+            "SELECT id, name, query_json, created_at, updated_at FROM saved_views ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to list views: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.get("id");
+                let query_json: String = row.get("query_json");
+                let query = serde_json::from_str(&query_json)
+                    .map_err(|e| {
+                        tracing::warn!(view_id = id, error = %e, "Skipping unreadable saved view");
+                    })
+                    .ok()?;
+
+                Some(SavedView {
+                    id,
+                    name: row.get("name"),
+                    query,
+                    created_at: row
+                        .try_get("created_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                    updated_at: row
+                        .try_get("updated_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                })
+            })
+            .collect())
+    }
+
+    async fn save_view(&self, name: &str, query_json: &str) -> DomainResult<i64> {
+        let mut conn = self.writer().acquire().await?;
+
+        let id: i64 = sqlx::query_scalar(
+            r#"INSERT INTO saved_views (name, query_json, created_at, updated_at)
+               VALUES (?, ?, datetime('now'), datetime('now'))
+               ON CONFLICT(name) DO UPDATE SET
+                   query_json = excluded.query_json,
+                   updated_at = datetime('now')
+               RETURNING id"#,
+        )
+        .bind(name)
+        .bind(query_json)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to save view: {}", e)))?;
+
+        Ok(id)
+    }
+
+    async fn delete_saved_view(&self, id: i64) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        let result = sqlx::query("DELETE FROM saved_views WHERE id = ?")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
-            .map_err(|e| DomainError::DatabaseError(format!("Failed to update provider: {}", e)))?;
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to delete view: {}", e)))?;
 
-            if result.rows_affected() == 0 {
-                return Err(DomainError::ProviderNotFound(format!("Provider {} not found", id)));
-            }
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!("View {} not found", id)));
+        }
 
-            Ok(())
-        })
+        Ok(())
+    }
+
+    async fn list_trigger_presets(&self, pipeline_id: &str) -> DomainResult<Vec<TriggerPreset>> {
+        let rows = sqlx::query(
+            r#"SELECT id, pipeline_id, name, branch, parameters_json, created_at, updated_at
+               FROM trigger_presets WHERE pipeline_id = ? ORDER BY name"#,
+        )
+        .bind(pipeline_id)
+        .fetch_all(&self.pool)
         .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list trigger presets: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.get("id");
+                let parameters_json: String = row.get("parameters_json");
+                let parameters = serde_json::from_str(&parameters_json)
+                    .map_err(|e| {
+                        tracing::warn!(preset_id = id, error = %e, "Skipping unreadable preset");
+                    })
+                    .ok()?;
+
+                Some(TriggerPreset {
+                    id,
+                    pipeline_id: row.get("pipeline_id"),
+                    name: row.get("name"),
+                    branch: row.get("branch"),
+                    parameters,
+                    created_at: row
+                        .try_get("created_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                    updated_at: row
+                        .try_get("updated_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                })
+            })
+            .collect())
     }
 
-    async fn delete_provider(&self, id: i64) -> DomainResult<()> {
-        retry_on_busy(|| async {
-            let _ = sqlx::query("DELETE FROM pipelines_cache WHERE provider_id = ?")
-                .bind(id)
-                .execute(&self.pool)
-                .await;
-
-            let _ = sqlx::query("DELETE FROM provider_permissions WHERE provider_id = ?")
-                .bind(id)
-                .execute(&self.pool)
-                .await;
-
-            let _ = sqlx::query("DELETE FROM table_preferences WHERE provider_id = ?")
-                .bind(id)
-                .execute(&self.pool)
-                .await;
-
-            let result = sqlx::query("DELETE FROM providers WHERE id = ?")
-                .bind(id)
-                .execute(&self.pool)
-                .await
-                .map_err(|e| {
-                    DomainError::DatabaseError(format!("Failed to delete provider: {}", e))
-                })?;
+    async fn save_trigger_preset(
+        &self, pipeline_id: &str, name: &str, branch: Option<&str>, parameters_json: &str,
+    ) -> DomainResult<i64> {
+        let mut conn = self.writer().acquire().await?;
+
+        let id: i64 = sqlx::query_scalar(
+            r#"INSERT INTO trigger_presets (pipeline_id, name, branch, parameters_json, created_at, updated_at)
+               VALUES (?, ?, ?, ?, datetime('now'), datetime('now'))
+               ON CONFLICT(pipeline_id, name) DO UPDATE SET
+                   branch = excluded.branch,
+                   parameters_json = excluded.parameters_json,
+                   updated_at = datetime('now')
+               RETURNING id"#,
+        )
+        .bind(pipeline_id)
+        .bind(name)
+        .bind(branch)
+        .bind(parameters_json)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save trigger preset: {}", e))
+        })?;
 
-            if result.rows_affected() == 0 {
-                return Err(DomainError::ProviderNotFound(format!(
-                    "Provider {} not found",
-                    id
-                )));
-            }
+        Ok(id)
+    }
 
-            Ok(())
-        })
+    async fn delete_trigger_preset(&self, id: i64) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        let result = sqlx::query("DELETE FROM trigger_presets WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete trigger preset: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Trigger preset {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn list_pipeline_dependencies(&self) -> DomainResult<Vec<PipelineDependency>> {
+        let rows = sqlx::query(
+            r#"SELECT id, upstream_id, downstream_id, trigger_on, inputs_json,
+                      created_by_role, created_at
+               FROM pipeline_dependencies ORDER BY id"#,
+        )
+        .fetch_all(&self.pool)
         .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list pipeline dependencies: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.get("id");
+                let trigger_on: String = row.get("trigger_on");
+                let inputs_json: String = row.get("inputs_json");
+                let parsed = serde_json::from_value(serde_json::Value::String(trigger_on))
+                    .and_then(|trigger_on| {
+                        serde_json::from_str(&inputs_json).map(|inputs| (trigger_on, inputs))
+                    });
+                let (trigger_on, inputs) = parsed
+                    .map_err(|e| {
+                        tracing::warn!(
+                            dependency_id = id,
+                            error = %e,
+                            "Skipping unreadable dependency"
+                        );
+                    })
+                    .ok()?;
+
+                Some(PipelineDependency {
+                    id,
+                    upstream_id: row.get("upstream_id"),
+                    downstream_id: row.get("downstream_id"),
+                    trigger_on,
+                    inputs,
+                    created_by_role: row
+                        .get::<Option<String>, _>("created_by_role")
+                        .and_then(|role| role.parse().ok()),
+                    created_at: row
+                        .try_get("created_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                })
+            })
+            .collect())
     }
 
-    async fn get_table_preferences(
-        &self, provider_id: i64, table_id: &str,
-    ) -> DomainResult<Option<String>> {
-        retry_on_busy(|| async {
-            let result = sqlx::query_scalar::<_, String>(
-                "SELECT preferences_json FROM table_preferences WHERE provider_id = ? AND table_id = ?",
-            )
-            .bind(provider_id)
-            .bind(table_id)
-            .fetch_optional(&self.pool)
+    async fn save_pipeline_dependency(
+        &self, upstream_id: &str, downstream_id: &str, trigger_on: &str, inputs_json: &str,
+        created_by_role: &str,
+    ) -> DomainResult<i64> {
+        let mut conn = self.writer().acquire().await?;
+
+        let id: i64 = sqlx::query_scalar(
+            r#"INSERT INTO pipeline_dependencies
+                   (upstream_id, downstream_id, trigger_on, inputs_json, created_by_role,
+                    created_at)
+               VALUES (?, ?, ?, ?, ?, datetime('now'))
+               ON CONFLICT(upstream_id, downstream_id) DO UPDATE SET
+                   trigger_on = excluded.trigger_on,
+                   inputs_json = excluded.inputs_json,
+                   created_by_role = excluded.created_by_role
+               RETURNING id"#,
+        )
+        .bind(upstream_id)
+        .bind(downstream_id)
+        .bind(trigger_on)
+        .bind(inputs_json)
+        .bind(created_by_role)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save pipeline dependency: {}", e))
+        })?;
+
+        Ok(id)
+    }
+
+    async fn delete_pipeline_dependency(&self, id: i64) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        let result = sqlx::query("DELETE FROM pipeline_dependencies WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
             .await
-            .map_err(|e| DomainError::DatabaseError(format!("Failed to get preferences: {}", e)))?;
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete pipeline dependency: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Dependency {} not found",
+                id
+            )));
+        }
 
-            Ok(result)
-        })
+        Ok(())
+    }
+
+    async fn list_notification_channels(&self) -> DomainResult<Vec<NotificationChannel>> {
+        let rows = sqlx::query(
+            r#"SELECT id, name, kind, url, enabled, rules_json, suppression_json, created_at,
+                      updated_at
+               FROM notification_channels ORDER BY name"#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list notification channels: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.get("id");
+                let kind: String = row.get("kind");
+                let rules_json: String = row.get("rules_json");
+                let suppression_json: String = row.get("suppression_json");
+                let parsed = kind.parse().and_then(|kind| {
+                    serde_json::from_str(&rules_json)
+                        .and_then(|rules| {
+                            serde_json::from_str(&suppression_json)
+                                .map(|suppression| (kind, rules, suppression))
+                        })
+                        .map_err(|e| DomainError::InvalidConfig(e.to_string()))
+                });
+                let (kind, rules, suppression) = parsed
+                    .map_err(|e| {
+                        tracing::warn!(
+                            channel_id = id,
+                            error = %e,
+                            "Skipping unreadable notification channel"
+                        );
+                    })
+                    .ok()?;
+
+                Some(NotificationChannel {
+                    id,
+                    name: row.get("name"),
+                    kind,
+                    url: row.get("url"),
+                    enabled: row.get("enabled"),
+                    rules,
+                    suppression,
+                    created_at: row
+                        .try_get("created_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                    updated_at: row
+                        .try_get("updated_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                })
+            })
+            .collect())
+    }
+
+    async fn save_notification_channel(
+        &self, name: &str, kind: &str, url: &str, enabled: bool, rules_json: &str,
+        suppression_json: &str,
+    ) -> DomainResult<i64> {
+        let mut conn = self.writer().acquire().await?;
+
+        let id: i64 = sqlx::query_scalar(
+            r#"INSERT INTO notification_channels
+                   (name, kind, url, enabled, rules_json, suppression_json, created_at,
+                    updated_at)
+               VALUES (?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))
+               ON CONFLICT(name) DO UPDATE SET
+                   kind = excluded.kind,
+                   url = excluded.url,
+                   enabled = excluded.enabled,
+                   rules_json = excluded.rules_json,
+                   suppression_json = excluded.suppression_json,
+                   updated_at = datetime('now')
+               RETURNING id"#,
+        )
+        .bind(name)
+        .bind(kind)
+        .bind(url)
+        .bind(enabled)
+        .bind(rules_json)
+        .bind(suppression_json)
+        .fetch_one(&mut *conn)
         .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save notification channel: {}", e))
+        })?;
+
+        Ok(id)
     }
 
-    async fn set_table_preferences(
-        &self, provider_id: i64, table_id: &str, preferences_json: &str,
+    async fn delete_notification_channel(&self, id: i64) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        let result = sqlx::query("DELETE FROM notification_channels WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete notification channel: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Notification channel {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn list_pipeline_refresh_settings(&self) -> DomainResult<Vec<PipelineRefreshSetting>> {
+        let rows = sqlx::query(
+            r#"SELECT pipeline_id, refresh_interval_secs, snoozed, updated_at
+               FROM pipeline_refresh_settings ORDER BY pipeline_id"#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list refresh settings: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PipelineRefreshSetting {
+                pipeline_id: row.get("pipeline_id"),
+                refresh_interval_secs: row
+                    .get::<Option<i64>, _>("refresh_interval_secs")
+                    .map(|secs| secs.max(0) as u64),
+                snoozed: row.get("snoozed"),
+                updated_at: row
+                    .try_get("updated_at")
+                    .unwrap_or_else(|_| chrono::Utc::now()),
+            })
+            .collect())
+    }
+
+    async fn save_pipeline_refresh_setting(
+        &self, pipeline_id: &str, refresh_interval_secs: Option<i64>, snoozed: bool,
     ) -> DomainResult<()> {
-        retry_on_busy(|| async {
-            sqlx::query(
-                r#"INSERT INTO table_preferences (provider_id, table_id, preferences_json, created_at, updated_at)
-                   VALUES (?, ?, ?, datetime('now'), datetime('now'))
-                   ON CONFLICT(provider_id, table_id) DO UPDATE SET
-                       preferences_json = excluded.preferences_json,
-                       updated_at = datetime('now')"#,
-            )
-            .bind(provider_id)
-            .bind(table_id)
-            .bind(preferences_json)
-            .execute(&self.pool)
+        let mut conn = self.writer().acquire().await?;
+
+        sqlx::query(
+            r#"INSERT INTO pipeline_refresh_settings
+                   (pipeline_id, refresh_interval_secs, snoozed, updated_at)
+               VALUES (?, ?, ?, datetime('now'))
+               ON CONFLICT(pipeline_id) DO UPDATE SET
+                   refresh_interval_secs = excluded.refresh_interval_secs,
+                   snoozed = excluded.snoozed,
+                   updated_at = datetime('now')"#,
+        )
+        .bind(pipeline_id)
+        .bind(refresh_interval_secs)
+        .bind(snoozed)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save refresh setting: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    async fn delete_pipeline_refresh_setting(&self, pipeline_id: &str) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        sqlx::query("DELETE FROM pipeline_refresh_settings WHERE pipeline_id = ?")
+            .bind(pipeline_id)
+            .execute(&mut *conn)
             .await
-            .map_err(|e| DomainError::DatabaseError(format!("Failed to save preferences: {}", e)))?;
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete refresh setting: {}", e))
+            })?;
 
-            Ok(())
-        })
+        Ok(())
+    }
+
+    async fn list_approval_rules(&self) -> DomainResult<Vec<String>> {
+        sqlx::query_scalar("SELECT pipeline_id FROM pipeline_approval_rules ORDER BY pipeline_id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to list approval rules: {}", e))
+            })
+    }
+
+    async fn set_approval_rule(&self, pipeline_id: &str, required: bool) -> DomainResult<()> {
+        let sql = if required {
+            "INSERT INTO pipeline_approval_rules (pipeline_id) VALUES (?) \
+             ON CONFLICT(pipeline_id) DO NOTHING"
+        } else {
+            "DELETE FROM pipeline_approval_rules WHERE pipeline_id = ?"
+        };
+
+        let mut conn = self.writer().acquire().await?;
+        sqlx::query(sql)
+            .bind(pipeline_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to save approval rule: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    async fn list_maintenance_windows(&self) -> DomainResult<Vec<MaintenanceWindow>> {
+        let rows = sqlx::query(
+            r#"SELECT id, name, window_data, created_at, updated_at
+               FROM maintenance_windows ORDER BY name"#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list maintenance windows: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.get("id");
+                let window_data: String = row.get("window_data");
+                let window: MaintenanceWindow = serde_json::from_str(&window_data)
+                    .map_err(|e| {
+                        tracing::warn!(
+                            window_id = id,
+                            error = %e,
+                            "Skipping unreadable maintenance window"
+                        );
+                    })
+                    .ok()?;
+
+                Some(MaintenanceWindow {
+                    id,
+                    name: row.get("name"),
+                    created_at: row
+                        .try_get("created_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                    updated_at: row
+                        .try_get("updated_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                    ..window
+                })
+            })
+            .collect())
+    }
+
+    async fn save_maintenance_window(&self, name: &str, window_json: &str) -> DomainResult<i64> {
+        let mut conn = self.writer().acquire().await?;
+
+        let id: i64 = sqlx::query_scalar(
+            r#"INSERT INTO maintenance_windows (name, window_data, created_at, updated_at)
+               VALUES (?, ?, datetime('now'), datetime('now'))
+               ON CONFLICT(name) DO UPDATE SET
+                   window_data = excluded.window_data,
+                   updated_at = datetime('now')
+               RETURNING id"#,
+        )
+        .bind(name)
+        .bind(window_json)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save maintenance window: {}", e))
+        })?;
+
+        Ok(id)
+    }
+
+    async fn delete_maintenance_window(&self, id: i64) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        let result = sqlx::query("DELETE FROM maintenance_windows WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete maintenance window: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Maintenance window {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn list_pinned_pipelines(&self) -> DomainResult<Vec<PinnedPipeline>> {
+        let rows = sqlx::query(
+            r#"SELECT pipeline_id, position, unpinned, updated_at
+               FROM pinned_pipelines ORDER BY position, pipeline_id"#,
+        )
+        .fetch_all(&self.pool)
         .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to list pinned pipelines: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PinnedPipeline {
+                pipeline_id: row.get("pipeline_id"),
+                position: row.get("position"),
+                unpinned: row.get("unpinned"),
+                updated_at: row
+                    .try_get("updated_at")
+                    .unwrap_or_else(|_| chrono::Utc::now()),
+            })
+            .collect())
+    }
+
+    async fn save_pinned_pipeline(&self, pin: &PinnedPipeline) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+
+        sqlx::query(
+            r#"INSERT INTO pinned_pipelines (pipeline_id, position, unpinned, updated_at)
+               VALUES (?, ?, ?, ?)
+               ON CONFLICT(pipeline_id) DO UPDATE SET
+                   position = excluded.position,
+                   unpinned = excluded.unpinned,
+                   updated_at = excluded.updated_at"#,
+        )
+        .bind(&pin.pipeline_id)
+        .bind(pin.position)
+        .bind(pin.unpinned)
+        .bind(pin.updated_at)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to save pinned pipeline: {}", e))
+        })?;
+
+        Ok(())
     }
 
     async fn store_permissions(
@@ -318,58 +986,55 @@ impl ConfigBackend for SqliteConfigBackend {
         let start = std::time::Instant::now();
         let permissions_count = permissions.permissions.len();
 
-        retry_on_busy(|| async {
-            let checked_at_str = permissions.last_checked.to_rfc3339();
-
-            sqlx::query("DELETE FROM provider_permissions WHERE provider_id = ?")
-                .bind(provider_id)
-                .execute(&self.pool)
-                .await
-                .map_err(|e| {
-                    DomainError::DatabaseError(format!("Failed to clear permissions: {}", e))
-                })?;
+        let checked_at_str = permissions.last_checked.to_rfc3339();
+        let mut conn = self.writer().acquire().await?;
+        let mut tx = conn
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-            if permissions.permissions.is_empty() {
-                return Ok(());
+        sqlx::query("DELETE FROM provider_permissions WHERE provider_id = ?")
+            .bind(provider_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to clear permissions: {}", e))
+            })?;
+
+        const BATCH_SIZE: usize = 100;
+
+        let perms_vec: Vec<_> = permissions.permissions.iter().collect();
+
+        for chunk in perms_vec.chunks(BATCH_SIZE) {
+            let values_clause = chunk
+                .iter()
+                .map(|_| "(?, ?, ?, ?)")
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let sql = format!(
+                r#"INSERT INTO provider_permissions (provider_id, permission_name, granted, checked_at)
+                   VALUES {}"#,
+                values_clause
+            );
+
+            let mut query = sqlx::query(&sql);
+            for (permission_name, granted) in chunk {
+                query = query
+                    .bind(provider_id)
+                    .bind(permission_name)
+                    .bind(**granted)
+                    .bind(&checked_at_str);
             }
 
-            const BATCH_SIZE: usize = 100;
-
-            let perms_vec: Vec<_> = permissions.permissions.iter().collect();
-
-            for chunk in perms_vec.chunks(BATCH_SIZE) {
-                let values_clause = chunk
-                    .iter()
-                    .map(|_| "(?, ?, ?, ?)")
-                    .collect::<Vec<_>>()
-                    .join(", ");
-
-                let sql = format!(
-                    r#"INSERT INTO provider_permissions (provider_id, permission_name, granted, checked_at)
-                       VALUES {}"#,
-                    values_clause
-                );
-
-                let mut query = sqlx::query(&sql);
-                for (permission_name, granted) in chunk {
-                    query = query
-                        .bind(provider_id)
-                        .bind(permission_name)
-                        .bind(**granted)
-                        .bind(&checked_at_str);
-                }
-
-                query
-                    .execute(&self.pool)
-                    .await
-                    .map_err(|e| {
-                        DomainError::DatabaseError(format!("Failed to store permission: {}", e))
-                    })?;
-            }
+            query.execute(&mut *tx).await.map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to store permission: {}", e))
+            })?;
+        }
 
-            Ok(())
-        })
-        .await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
         let elapsed = start.elapsed();
         tracing::debug!(
@@ -383,41 +1048,36 @@ impl ConfigBackend for SqliteConfigBackend {
     }
 
     async fn get_permissions(&self, provider_id: i64) -> DomainResult<Option<StoredPermissions>> {
-        retry_on_busy(|| async {
-            let rows = sqlx::query(
-                r#"SELECT permission_name, granted, checked_at
-                   FROM provider_permissions
-                   WHERE provider_id = ?"#,
-            )
-            .bind(provider_id)
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| DomainError::DatabaseError(format!("Failed to get permissions: {}", e)))?;
+        let rows = sqlx::query(
+            r#"SELECT permission_name, granted, checked_at
+               FROM provider_permissions
+               WHERE provider_id = ?"#,
+        )
+        .bind(provider_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to get permissions: {}", e)))?;
 
-            if rows.is_empty() {
-                return Ok(None);
-            }
+        if rows.is_empty() {
+            return Ok(None);
+        }
 
-            let checked_at_str: String = rows[0].get("checked_at");
-            let last_checked = chrono::DateTime::parse_from_rfc3339(&checked_at_str)
-                .map_err(|e| {
-                    DomainError::DatabaseError(format!("Failed to parse timestamp: {}", e))
-                })?
-                .with_timezone(&chrono::Utc);
+        let checked_at_str: String = rows[0].get("checked_at");
+        let last_checked = chrono::DateTime::parse_from_rfc3339(&checked_at_str)
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to parse timestamp: {}", e)))?
+            .with_timezone(&chrono::Utc);
 
-            let mut permissions = HashMap::new();
-            for row in rows {
-                let permission_name: String = row.get("permission_name");
-                let granted: bool = row.get("granted");
-                permissions.insert(permission_name, granted);
-            }
+        let mut permissions = HashMap::new();
+        for row in rows {
+            let permission_name: String = row.get("permission_name");
+            let granted: bool = row.get("granted");
+            permissions.insert(permission_name, granted);
+        }
 
-            Ok(Some(StoredPermissions {
-                permissions,
-                last_checked,
-            }))
-        })
-        .await
+        Ok(Some(StoredPermissions {
+            permissions,
+            last_checked,
+        }))
     }
 
     async fn export_all(&self) -> DomainResult<ConfigExport> {
@@ -467,11 +1127,14 @@ impl ConfigBackend for SqliteConfigBackend {
             entry.permissions.insert(permission_name, granted);
         }
 
+        let pinned_pipelines = self.list_pinned_pipelines().await?;
+
         Ok(ConfigExport {
             version: "1.0".to_string(),
             providers,
             table_preferences,
             permissions,
+            pinned_pipelines,
         })
     }
 
@@ -527,6 +1190,14 @@ impl ConfigBackend for SqliteConfigBackend {
             }
         }
 
+        for pin in &data.pinned_pipelines {
+            let mut pin = pin.clone();
+            if let Some(pipeline_id) = remap_pipeline_id(&pin.pipeline_id, &id_mapping) {
+                pin.pipeline_id = pipeline_id;
+            }
+            self.save_pinned_pipeline(&pin).await?;
+        }
+
         Ok(id_mapping)
     }
 }
@@ -551,6 +1222,7 @@ mod tests {
             token: String::new(),
             refresh_interval: 30,
             version: None,
+            enabled: true,
         };
 
         let id = backend.create_provider(&config).await.unwrap();
@@ -559,6 +1231,7 @@ mod tests {
         let retrieved = backend.get_provider(id).await.unwrap().unwrap();
         assert_eq!(retrieved.name, "Test Provider");
         assert_eq!(retrieved.provider_type, "github");
+        assert!(retrieved.enabled);
 
         let all = backend.list_providers().await.unwrap();
         assert_eq!(all.len(), 1);
@@ -571,6 +1244,7 @@ mod tests {
             token: String::new(),
             refresh_interval: 60,
             version: None,
+            enabled: true,
         };
         backend.update_provider(id, &updated_config).await.unwrap();
 
@@ -597,6 +1271,7 @@ mod tests {
             token: String::new(),
             refresh_interval: 30,
             version: None,
+            enabled: true,
         };
         let provider_id = backend.create_provider(&config).await.unwrap();
 
@@ -619,6 +1294,183 @@ mod tests {
         assert!(missing.is_none());
     }
 
+    #[tokio::test]
+    async fn test_sqlite_backend_writes_queue_on_writer() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = init_database(dir.path().join("test.db")).await.unwrap();
+        let backend = std::sync::Arc::new(SqliteConfigBackend::new(pool));
+
+        let lease = backend.sqlite_writer().unwrap().acquire().await.unwrap();
+        let write = tokio::spawn({
+            let backend = backend.clone();
+            async move { backend.create_pipeline_group("Backend").await }
+        });
+
+        // Reads don't wait for the writer.
+        assert!(backend.list_pipeline_groups().await.unwrap().is_empty());
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!write.is_finished());
+
+        drop(lease);
+        write.await.unwrap().unwrap();
+        assert_eq!(backend.list_pipeline_groups().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_backend_pipeline_groups() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let pool = init_database(db_path).await.unwrap();
+        let backend = SqliteConfigBackend::new(pool);
+
+        let group_id = backend.create_pipeline_group("Backend").await.unwrap();
+        backend
+            .add_pipeline_to_group(group_id, "github__1__org__api__ci")
+            .await
+            .unwrap();
+        backend
+            .add_pipeline_to_group(group_id, "github__1__org__api__ci")
+            .await
+            .unwrap();
+        backend
+            .add_pipeline_to_group(group_id, "gitlab__2__team__worker")
+            .await
+            .unwrap();
+
+        let groups = backend.list_pipeline_groups().await.unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "Backend");
+        assert_eq!(
+            groups[0].pipeline_ids,
+            vec!["github__1__org__api__ci", "gitlab__2__team__worker"]
+        );
+
+        backend
+            .remove_pipeline_from_group(group_id, "github__1__org__api__ci")
+            .await
+            .unwrap();
+        let groups = backend.list_pipeline_groups().await.unwrap();
+        assert_eq!(groups[0].pipeline_ids, vec!["gitlab__2__team__worker"]);
+
+        backend.delete_pipeline_group(group_id).await.unwrap();
+        assert!(backend.list_pipeline_groups().await.unwrap().is_empty());
+        assert!(backend.delete_pipeline_group(group_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_backend_refresh_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let pool = init_database(db_path).await.unwrap();
+        let backend = SqliteConfigBackend::new(pool);
+
+        backend
+            .save_pipeline_refresh_setting("github__1__org__api__deploy", Some(15), false)
+            .await
+            .unwrap();
+        backend
+            .save_pipeline_refresh_setting("github__1__org__api__deploy", Some(30), true)
+            .await
+            .unwrap();
+
+        let settings = backend.list_pipeline_refresh_settings().await.unwrap();
+        assert_eq!(settings.len(), 1);
+        assert_eq!(settings[0].refresh_interval_secs, Some(30));
+        assert!(settings[0].snoozed);
+
+        backend
+            .delete_pipeline_refresh_setting("github__1__org__api__deploy")
+            .await
+            .unwrap();
+        assert!(backend
+            .list_pipeline_refresh_settings()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_backend_approval_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let pool = init_database(db_path).await.unwrap();
+        let backend = SqliteConfigBackend::new(pool);
+
+        backend
+            .set_approval_rule("github__1__org__api__deploy", true)
+            .await
+            .unwrap();
+        backend
+            .set_approval_rule("github__1__org__api__deploy", true)
+            .await
+            .unwrap();
+        assert_eq!(
+            backend.list_approval_rules().await.unwrap(),
+            vec!["github__1__org__api__deploy"]
+        );
+
+        backend
+            .set_approval_rule("github__1__org__api__deploy", false)
+            .await
+            .unwrap();
+        assert!(backend.list_approval_rules().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_backend_maintenance_windows() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let pool = init_database(db_path).await.unwrap();
+        let backend = SqliteConfigBackend::new(pool);
+
+        let window_json = r#"{"name":"Migration","pipeline_ids":["github__1__org__api__deploy"],
+            "schedule":{"kind":"weekly","days":["Sat"],"start":"22:00:00","end":"02:00:00"}}"#;
+        let id = backend
+            .save_maintenance_window("Migration", window_json)
+            .await
+            .unwrap();
+        assert_eq!(
+            backend
+                .save_maintenance_window("Migration", window_json)
+                .await
+                .unwrap(),
+            id
+        );
+
+        let windows = backend.list_maintenance_windows().await.unwrap();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].id, id);
+        assert_eq!(windows[0].pipeline_ids, vec!["github__1__org__api__deploy"]);
+
+        backend.delete_maintenance_window(id).await.unwrap();
+        assert!(backend.list_maintenance_windows().await.unwrap().is_empty());
+        assert!(backend.delete_maintenance_window(id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_backend_pinned_pipelines() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let pool = init_database(db_path).await.unwrap();
+        let backend = SqliteConfigBackend::new(pool);
+
+        let mut pin = PinnedPipeline {
+            pipeline_id: "github__1__org__api__deploy".to_string(),
+            position: 0,
+            unpinned: false,
+            updated_at: chrono::DateTime::from_timestamp(1_000, 0).unwrap(),
+        };
+        backend.save_pinned_pipeline(&pin).await.unwrap();
+        pin.unpinned = true;
+        backend.save_pinned_pipeline(&pin).await.unwrap();
+
+        let pins = backend.list_pinned_pipelines().await.unwrap();
+        assert_eq!(pins, vec![pin.clone()]);
+
+        let export = backend.export_all().await.unwrap();
+        assert_eq!(export.pinned_pipelines, vec![pin]);
+    }
+
     #[tokio::test]
     async fn test_sqlite_backend_permissions() {
         let dir = tempfile::tempdir().unwrap();
@@ -634,6 +1486,7 @@ mod tests {
             token: String::new(),
             refresh_interval: 30,
             version: None,
+            enabled: true,
         };
         let provider_id = backend.create_provider(&config).await.unwrap();
 
@@ -669,6 +1522,7 @@ mod tests {
             token: String::new(),
             refresh_interval: 45,
             version: None,
+            enabled: true,
         };
         backend.create_provider(&config).await.unwrap();
 
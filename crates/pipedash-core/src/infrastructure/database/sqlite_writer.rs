@@ -0,0 +1,148 @@
+use std::ops::{
+    Deref,
+    DerefMut,
+};
+
+use sqlx::pool::PoolConnection;
+use sqlx::{
+    Sqlite,
+    SqliteConnection,
+    SqlitePool,
+};
+use tokio::sync::{
+    mpsc,
+    oneshot,
+};
+
+use crate::domain::{
+    DomainError,
+    DomainResult,
+};
+
+// SQLite allows one writer at a time, so writes from the repository queue
+// here for a single connection held by a writer task instead of racing for
+// the database lock. Reads keep using the rest of the pool and, with WAL,
+// never wait behind a bulk cache write.
+#[derive(Clone)]
+pub struct SqliteWriter {
+    requests: mpsc::UnboundedSender<oneshot::Sender<WriteConnection>>,
+}
+
+impl SqliteWriter {
+    // Must be called inside a Tokio runtime.
+    pub fn spawn(pool: SqlitePool) -> Self {
+        let (requests, mut queue) = mpsc::unbounded_channel::<oneshot::Sender<WriteConnection>>();
+
+        tokio::spawn(async move {
+            let mut idle: Option<PoolConnection<Sqlite>> = None;
+
+            while let Some(reply) = queue.recv().await {
+                let conn = match idle.take() {
+                    Some(conn) => conn,
+                    None => match pool.acquire().await {
+                        Ok(conn) => conn,
+                        Err(e) => {
+                            // Dropping `reply` fails this write; the next one
+                            // tries again.
+                            tracing::warn!(error = %e, "SQLite writer could not get a connection");
+                            continue;
+                        }
+                    },
+                };
+
+                let (release, released) = oneshot::channel();
+                let lease = WriteConnection {
+                    conn: Some(conn),
+                    release: Some(release),
+                };
+                if let Err(mut lease) = reply.send(lease) {
+                    // The caller gave up while queued.
+                    idle = lease.conn.take();
+                    continue;
+                }
+
+                // Only the next writer waits here, once the lease is dropped.
+                idle = released.await.ok();
+            }
+        });
+
+        Self { requests }
+    }
+
+    // Waits for every write queued before this one to finish.
+    pub async fn acquire(&self) -> DomainResult<WriteConnection> {
+        let (reply, lease) = oneshot::channel();
+        self.requests
+            .send(reply)
+            .map_err(|_| DomainError::DatabaseError("SQLite writer has stopped".to_string()))?;
+        lease.await.map_err(|_| {
+            DomainError::DatabaseError("SQLite writer could not get a connection".to_string())
+        })
+    }
+}
+
+// The writer's connection, handed back to it on drop. Don't hold one across
+// another repository write: that write queues behind this one.
+pub struct WriteConnection {
+    conn: Option<PoolConnection<Sqlite>>,
+    release: Option<oneshot::Sender<PoolConnection<Sqlite>>>,
+}
+
+impl Deref for WriteConnection {
+    type Target = SqliteConnection;
+
+    fn deref(&self) -> &Self::Target {
+        self.conn
+            .as_ref()
+            .expect("write connection already released")
+    }
+}
+
+impl DerefMut for WriteConnection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.conn
+            .as_mut()
+            .expect("write connection already released")
+    }
+}
+
+impl Drop for WriteConnection {
+    fn drop(&mut self) {
+        if let (Some(conn), Some(release)) = (self.conn.take(), self.release.take()) {
+            let _ = release.send(conn);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::infrastructure::database::init_database;
+
+    #[tokio::test]
+    async fn test_writes_are_serialized() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = init_database(dir.path().join("test.db")).await.unwrap();
+        let writer = SqliteWriter::spawn(pool.clone());
+
+        let first = writer.acquire().await.unwrap();
+        let queued = tokio::spawn({
+            let writer = writer.clone();
+            async move { writer.acquire().await.map(|_| ()) }
+        });
+
+        // Reads go through the pool while a write is in progress.
+        sqlx::query("SELECT 1").execute(&pool).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!queued.is_finished());
+
+        drop(first);
+        tokio::time::timeout(Duration::from_secs(5), queued)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+    }
+}
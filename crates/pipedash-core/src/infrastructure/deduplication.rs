@@ -4,68 +4,84 @@ use std::hash::{
     Hash,
     Hasher,
 };
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    Mutex,
+};
 
+use futures::future::{
+    BoxFuture,
+    FutureExt,
+    Shared,
+};
 use sha2::{
     Digest,
     Sha256,
 };
-use tokio::sync::{
-    oneshot,
-    Mutex,
-};
+
+use crate::domain::DomainError;
 
 type RequestId = u64;
 
-pub struct RequestDeduplicator<T: Clone> {
-    #[allow(clippy::type_complexity)]
-    in_flight: Arc<Mutex<HashMap<RequestId, Vec<oneshot::Sender<Arc<T>>>>>>,
+type InFlight<T, E> = Shared<BoxFuture<'static, Result<T, E>>>;
+
+// Coalesces identical in-flight requests: every caller with the same request id
+// awaits one shared future, errors included. The future removes itself from the
+// map once it resolves, so later calls start a fresh request. Dropping one
+// caller does not cancel the request for the others.
+pub struct RequestDeduplicator<T: Clone, E: Clone = DomainError> {
+    in_flight: Arc<Mutex<HashMap<RequestId, InFlight<T, E>>>>,
 }
 
-impl<T: Clone> RequestDeduplicator<T> {
+impl<T, E> RequestDeduplicator<T, E>
+where
+    T: Clone + Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+{
     pub fn new() -> Self {
         Self {
             in_flight: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
-    pub async fn deduplicate<F, Fut, E>(&self, request_id: RequestId, operation: F) -> Result<T, E>
+    pub async fn deduplicate<F, Fut>(&self, request_id: RequestId, operation: F) -> Result<T, E>
     where
         F: FnOnce() -> Fut,
-        Fut: std::future::Future<Output = Result<T, E>>,
+        Fut: std::future::Future<Output = Result<T, E>> + Send + 'static,
     {
-        let (tx, rx) = oneshot::channel();
-
-        let mut in_flight = self.in_flight.lock().await;
-
-        if let Some(waiters) = in_flight.get_mut(&request_id) {
-            waiters.push(tx);
-            drop(in_flight);
-
-            match rx.await {
-                Ok(result) => Ok((*result).clone()),
-                Err(_) => operation().await, // Fallback if sender dropped
-            }
-        } else {
-            in_flight.insert(request_id, vec![tx]);
-            drop(in_flight);
-
-            let result = operation().await?;
-            let shared_result = Arc::new(result.clone());
-
-            let mut in_flight = self.in_flight.lock().await;
-            if let Some(waiters) = in_flight.remove(&request_id) {
-                for waiter in waiters {
-                    let _ = waiter.send(shared_result.clone());
+        let shared = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&request_id) {
+                Some(shared) => shared.clone(),
+                None => {
+                    let map = Arc::clone(&self.in_flight);
+                    let operation = operation();
+                    let shared = async move {
+                        let result = operation.await;
+                        map.lock().unwrap().remove(&request_id);
+                        result
+                    }
+                    .boxed()
+                    .shared();
+                    in_flight.insert(request_id, shared.clone());
+                    shared
                 }
             }
+        };
 
-            Ok(result)
-        }
+        shared.await
+    }
+
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
     }
 }
 
-impl<T: Clone> Default for RequestDeduplicator<T> {
+impl<T, E> Default for RequestDeduplicator<T, E>
+where
+    T: Clone + Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+{
     fn default() -> Self {
         Self::new()
     }
@@ -96,7 +112,7 @@ pub fn hash_pipeline_run(
     format!("{:x}", result)
 }
 
-impl<T: Clone> Clone for RequestDeduplicator<T> {
+impl<T: Clone, E: Clone> Clone for RequestDeduplicator<T, E> {
     fn clone(&self) -> Self {
         Self {
             in_flight: self.in_flight.clone(),
@@ -115,7 +131,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_request_deduplication() {
-        let dedup = RequestDeduplicator::<i32>::new();
+        let dedup = RequestDeduplicator::<i32, ()>::new();
         let call_count = Arc::new(AtomicUsize::new(0));
 
         let request_id = hash_request(1, "test");
@@ -124,7 +140,7 @@ mod tests {
         let count1 = call_count.clone();
         let handle1 = tokio::spawn(async move {
             dedup1
-                .deduplicate(request_id, || async {
+                .deduplicate(request_id, || async move {
                     count1.fetch_add(1, Ordering::SeqCst);
                     tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
                     Ok::<_, ()>(42)
@@ -136,7 +152,7 @@ mod tests {
         let count2 = call_count.clone();
         let handle2 = tokio::spawn(async move {
             dedup2
-                .deduplicate(request_id, || async {
+                .deduplicate(request_id, || async move {
                     count2.fetch_add(1, Ordering::SeqCst);
                     tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
                     Ok::<_, ()>(42)
@@ -149,6 +165,31 @@ mod tests {
         assert_eq!(r2.unwrap().unwrap(), 42);
 
         assert_eq!(call_count.load(Ordering::SeqCst), 1);
+        assert_eq!(dedup.in_flight_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_request_deduplication_shares_errors() {
+        let dedup = RequestDeduplicator::<i32, String>::new();
+        let request_id = hash_request(1, "failing");
+
+        let operation = || async {
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            Err::<i32, _>("boom".to_string())
+        };
+        let (r1, r2) = tokio::join!(
+            dedup.deduplicate(request_id, operation),
+            dedup.deduplicate(request_id, operation)
+        );
+
+        assert_eq!(r1, Err("boom".to_string()));
+        assert_eq!(r2, Err("boom".to_string()));
+        assert_eq!(dedup.in_flight_count(), 0);
+
+        let retried = dedup
+            .deduplicate(request_id, || async { Ok::<_, String>(7) })
+            .await;
+        assert_eq!(retried, Ok(7));
     }
 
     #[test]
@@ -1,28 +1,202 @@
+use std::collections::HashMap;
+use std::path::{
+    Path,
+    PathBuf,
+};
 use std::sync::Arc;
 use std::time::Duration;
 
 use dashmap::DashMap;
-use reqwest::Client;
+use pipedash_plugin_api::{
+    ConditionalRequestCache,
+    CA_CERTIFICATE_KEY,
+    CLIENT_CERTIFICATE_KEY,
+    CLIENT_KEY_KEY,
+    PROXY_URL_KEY,
+    TLS_SKIP_VERIFY_KEY,
+};
+use pipedash_plugin_testing::FixtureServer;
+use reqwest::{
+    Certificate,
+    Client,
+    Identity,
+    Proxy,
+};
+use tokio::io::AsyncWriteExt;
 
 use crate::domain::{
+    ArtifactDownload,
     DomainError,
     DomainResult,
 };
 
+const RECORD_DIR_ENV: &str = "PIPEDASH_HTTP_RECORD_DIR";
+
+// Per-provider network settings, read from the provider config.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct HttpClientOptions {
+    pub proxy_url: Option<String>,
+    // A path to a PEM file, or the PEM contents inline.
+    pub ca_certificate: Option<String>,
+    pub tls_skip_verify: bool,
+    // Client certificate and key for mTLS, each a path or inline PEM.
+    pub client_certificate: Option<String>,
+    pub client_key: Option<String>,
+}
+
+impl HttpClientOptions {
+    pub fn from_config(config: &HashMap<String, String>) -> Self {
+        let non_empty = |key: &str| {
+            config
+                .get(key)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        Self {
+            proxy_url: non_empty(PROXY_URL_KEY),
+            ca_certificate: non_empty(CA_CERTIFICATE_KEY),
+            tls_skip_verify: config
+                .get(TLS_SKIP_VERIFY_KEY)
+                .is_some_and(|value| value.trim().eq_ignore_ascii_case("true")),
+            client_certificate: non_empty(CLIENT_CERTIFICATE_KEY),
+            client_key: non_empty(CLIENT_KEY_KEY),
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn root_certificates(&self) -> DomainResult<Vec<Certificate>> {
+        let Some(ca) = &self.ca_certificate else {
+            return Ok(Vec::new());
+        };
+
+        let pem = read_pem(ca, "CA certificate")?;
+        let certificates = Certificate::from_pem_bundle(&pem)
+            .map_err(|e| DomainError::InvalidConfig(format!("Invalid CA certificate: {}", e)))?;
+        if certificates.is_empty() {
+            return Err(DomainError::InvalidConfig(
+                "CA certificate contains no PEM certificates".to_string(),
+            ));
+        }
+        Ok(certificates)
+    }
+
+    fn identity(&self) -> DomainResult<Option<Identity>> {
+        let (certificate, key) = match (&self.client_certificate, &self.client_key) {
+            (None, None) => return Ok(None),
+            (Some(certificate), Some(key)) => (certificate, key),
+            _ => {
+                return Err(DomainError::InvalidConfig(
+                    "Client certificate and client key must be set together".to_string(),
+                ))
+            }
+        };
+
+        // reqwest expects the key and certificate chain in a single PEM buffer.
+        let mut pem = read_pem(key, "client key")?;
+        pem.push(b'\n');
+        pem.extend(read_pem(certificate, "client certificate")?);
+
+        Identity::from_pem(&pem)
+            .map(Some)
+            .map_err(|e| DomainError::InvalidConfig(format!("Invalid client certificate: {}", e)))
+    }
+}
+
+fn read_pem(value: &str, what: &str) -> DomainResult<Vec<u8>> {
+    if value.starts_with("-----BEGIN") {
+        return Ok(value.as_bytes().to_vec());
+    }
+
+    std::fs::read(value).map_err(|e| {
+        DomainError::InvalidConfig(format!("Failed to read {} {}: {}", what, value, e))
+    })
+}
+
 pub struct HttpClientManager {
     default_client: Arc<Client>,
+    download_client: Arc<Client>,
     custom_clients: DashMap<String, Arc<Client>>,
+    configured_clients: DashMap<(Option<String>, HttpClientOptions), Arc<Client>>,
+    conditional_caches: DashMap<i64, Arc<ConditionalRequestCache>>,
+    // Set from PIPEDASH_HTTP_RECORD_DIR: provider traffic is then recorded
+    // into plugin test fixtures, one cassette per provider.
+    record_dir: Option<PathBuf>,
+    recordings: DashMap<i64, FixtureServer>,
 }
 
 impl HttpClientManager {
     pub fn new() -> DomainResult<Self> {
-        let default_client = Self::create_optimized_client()?;
+        let default_client = Self::create_optimized_client(&HttpClientOptions::default())?;
+        let download_client = Self::create_download_client()?;
         Ok(Self {
             default_client: Arc::new(default_client),
+            download_client: Arc::new(download_client),
             custom_clients: DashMap::new(),
+            configured_clients: DashMap::new(),
+            conditional_caches: DashMap::new(),
+            record_dir: std::env::var(RECORD_DIR_ENV)
+                .ok()
+                .filter(|dir| !dir.trim().is_empty())
+                .map(PathBuf::from),
+            recordings: DashMap::new(),
         })
     }
 
+    pub fn with_record_dir(mut self, dir: PathBuf) -> Self {
+        self.record_dir = Some(dir);
+        self
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.record_dir.is_some()
+    }
+
+    // Starts a proxy that forwards `{prefix}/...` to `{upstream}/...` and
+    // appends every exchange to `<dir>/<provider_type>-<id>.json`, returning
+    // the URL the provider should be pointed at. A running proxy for the same
+    // target is reused, so reloading a provider keeps appending to the same
+    // cassette.
+    pub fn start_recording(
+        &self, provider_id: i64, provider_type: &str, upstream: &str, prefix: &str, client: &Client,
+    ) -> DomainResult<String> {
+        let dir = self
+            .record_dir
+            .as_ref()
+            .ok_or_else(|| DomainError::InvalidConfig("HTTP recording is off".to_string()))?;
+
+        if let Some(server) = self.recordings.get(&provider_id) {
+            let target = (upstream.trim_end_matches('/'), prefix.trim_end_matches('/'));
+            if server.recording_target() == Some(target) {
+                return Ok(server.base_url().to_string());
+            }
+        }
+
+        let path = dir.join(format!("{}-{}.json", provider_type, provider_id));
+        let server =
+            FixtureServer::record(&path, upstream, prefix, client.clone()).map_err(|e| {
+                DomainError::InternalError(format!("Failed to start HTTP recording: {}", e))
+            })?;
+        let url = server.base_url().to_string();
+        tracing::info!(
+            provider_id,
+            upstream,
+            cassette = %path.display(),
+            "Recording provider HTTP traffic"
+        );
+
+        self.recordings.insert(provider_id, server);
+        Ok(url)
+    }
+
+    pub fn stop_recording(&self, provider_id: i64) {
+        self.recordings.remove(&provider_id);
+    }
+
     pub fn default_client(&self) -> Arc<Client> {
         Arc::clone(&self.default_client)
     }
@@ -32,26 +206,170 @@ impl HttpClientManager {
             return Ok(Arc::clone(client.value()));
         }
 
-        let client = Self::create_optimized_client()?;
+        let client = Self::create_optimized_client(&HttpClientOptions::default())?;
         let client = Arc::new(client);
         self.custom_clients
             .insert(base_url.to_string(), Arc::clone(&client));
         Ok(client)
     }
 
-    fn create_optimized_client() -> DomainResult<Client> {
+    // The client a plugin should be initialized with, given its provider
+    // config. Providers without proxy or TLS settings share the plain
+    // clients.
+    pub fn client_for_provider(
+        &self, config: &HashMap<String, String>,
+    ) -> DomainResult<Arc<Client>> {
+        let base_url = config.get("base_url");
+        let options = HttpClientOptions::from_config(config);
+
+        if options.is_default() {
+            return match base_url {
+                Some(base_url) => self.client_for_url(base_url),
+                None => Ok(self.default_client()),
+            };
+        }
+
+        let key = (base_url.cloned(), options);
+        if let Some(client) = self.configured_clients.get(&key) {
+            return Ok(Arc::clone(client.value()));
+        }
+
+        let client = Arc::new(Self::create_optimized_client(&key.1)?);
+        self.configured_clients.insert(key, Arc::clone(&client));
+        Ok(client)
+    }
+
+    // The size limit only applies when the cache is created; providers drop
+    // theirs on update.
+    pub fn conditional_cache(
+        &self, provider_id: i64, max_response_bytes: usize,
+    ) -> Arc<ConditionalRequestCache> {
+        Arc::clone(
+            self.conditional_caches
+                .entry(provider_id)
+                .or_insert_with(|| {
+                    Arc::new(
+                        ConditionalRequestCache::new().with_max_response_bytes(max_response_bytes),
+                    )
+                })
+                .value(),
+        )
+    }
+
+    pub fn remove_conditional_cache(&self, provider_id: i64) {
+        self.conditional_caches.remove(&provider_id);
+    }
+
+    pub async fn open_download(
+        &self, download: &ArtifactDownload,
+    ) -> DomainResult<reqwest::Response> {
+        let mut request = self.download_client.get(&download.url);
+        for (name, value) in &download.headers {
+            request = request.header(name, value);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| DomainError::NetworkError(format!("Download failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(DomainError::ApiError(format!(
+                "Download failed: HTTP {}",
+                response.status()
+            )));
+        }
+
+        Ok(response)
+    }
+
+    pub async fn download_to_file(
+        &self, download: &ArtifactDownload, path: &Path,
+    ) -> DomainResult<u64> {
+        let mut response = self.open_download(download).await?;
+
+        // Write to a sibling temp file so a failed download never leaves a
+        // truncated file at the destination.
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".part");
+        let tmp_path = PathBuf::from(tmp_path);
+        let mut file = tokio::fs::File::create(&tmp_path)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Failed to create file: {}", e)))?;
+
+        let mut written = 0u64;
+        let result: DomainResult<()> = async {
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| DomainError::NetworkError(format!("Download failed: {}", e)))?
+            {
+                file.write_all(&chunk).await.map_err(|e| {
+                    DomainError::InternalError(format!("Failed to write file: {}", e))
+                })?;
+                written += chunk.len() as u64;
+            }
+            file.flush()
+                .await
+                .map_err(|e| DomainError::InternalError(format!("Failed to write file: {}", e)))
+        }
+        .await;
+
+        if let Err(e) = result {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(e);
+        }
+
+        tokio::fs::rename(&tmp_path, path)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Failed to save file: {}", e)))?;
+
+        Ok(written)
+    }
+
+    fn create_download_client() -> DomainResult<Client> {
+        // No overall timeout: artifacts can be large, so only stalls are
+        // treated as failures.
+        Client::builder()
+            .use_rustls_tls()
+            .connect_timeout(Duration::from_secs(10))
+            .read_timeout(Duration::from_secs(60))
+            .build()
+            .map_err(|e| DomainError::InternalError(format!("Failed to create HTTP client: {}", e)))
+    }
+
+    fn create_optimized_client(options: &HttpClientOptions) -> DomainResult<Client> {
         let pool_size = std::env::var("PIPEDASH_HTTP_POOL_SIZE")
             .ok()
             .and_then(|s| s.parse().ok())
             .unwrap_or(10);
 
-        Client::builder()
+        let mut builder = Client::builder()
             .use_rustls_tls()
             .pool_max_idle_per_host(pool_size)
             .pool_idle_timeout(Duration::from_secs(90))
             .tcp_keepalive(Duration::from_secs(60))
             .timeout(Duration::from_secs(30))
-            .connect_timeout(Duration::from_secs(10))
+            .connect_timeout(Duration::from_secs(10));
+
+        if let Some(proxy_url) = &options.proxy_url {
+            let proxy = Proxy::all(proxy_url).map_err(|e| {
+                DomainError::InvalidConfig(format!("Invalid proxy URL {}: {}", proxy_url, e))
+            })?;
+            builder = builder.proxy(proxy);
+        }
+        for certificate in options.root_certificates()? {
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(identity) = options.identity()? {
+            builder = builder.identity(identity);
+        }
+        if options.tls_skip_verify {
+            tracing::warn!("TLS certificate verification disabled for a provider client");
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        builder
             .build()
             .map_err(|e| DomainError::InternalError(format!("Failed to create HTTP client: {}", e)))
     }
@@ -61,16 +379,23 @@ impl HttpClientManager {
 mod tests {
     use super::*;
 
+    // The workspace builds reqwest without a default rustls provider; the
+    // binaries install one at startup, so tests have to do the same.
+    fn manager() -> HttpClientManager {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        HttpClientManager::new().unwrap()
+    }
+
     #[test]
     fn test_http_client_manager_creation() {
-        let manager = HttpClientManager::new().unwrap();
+        let manager = manager();
         let client = manager.default_client();
         assert!(Arc::strong_count(&client) >= 1);
     }
 
     #[test]
     fn test_client_caching() {
-        let manager = HttpClientManager::new().unwrap();
+        let manager = manager();
 
         let client1 = manager.client_for_url("https://api.github.com").unwrap();
         let client2 = manager.client_for_url("https://api.github.com").unwrap();
@@ -80,7 +405,7 @@ mod tests {
 
     #[test]
     fn test_different_urls_different_clients() {
-        let manager = HttpClientManager::new().unwrap();
+        let manager = manager();
 
         let client1 = manager.client_for_url("https://api.github.com").unwrap();
         let client2 = manager.client_for_url("https://gitlab.com").unwrap();
@@ -90,11 +415,114 @@ mod tests {
 
     #[test]
     fn test_default_client_separate_from_custom() {
-        let manager = HttpClientManager::new().unwrap();
+        let manager = manager();
 
         let default_client = manager.default_client();
         let custom_client = manager.client_for_url("https://api.github.com").unwrap();
 
         assert!(!Arc::ptr_eq(&default_client, &custom_client));
     }
+
+    #[test]
+    fn test_client_options_from_config() {
+        let mut config = HashMap::new();
+        assert!(HttpClientOptions::from_config(&config).is_default());
+
+        config.insert(PROXY_URL_KEY.to_string(), " http://proxy:3128 ".to_string());
+        config.insert(CA_CERTIFICATE_KEY.to_string(), String::new());
+        config.insert(TLS_SKIP_VERIFY_KEY.to_string(), "true".to_string());
+        let options = HttpClientOptions::from_config(&config);
+
+        assert_eq!(options.proxy_url.as_deref(), Some("http://proxy:3128"));
+        assert_eq!(options.ca_certificate, None);
+        assert!(options.tls_skip_verify);
+    }
+
+    #[test]
+    fn test_invalid_ca_certificate_rejected() {
+        let options = HttpClientOptions {
+            ca_certificate: Some("-----BEGIN CERTIFICATE-----\nnot base64\n".to_string()),
+            ..Default::default()
+        };
+        assert!(options.root_certificates().is_err());
+
+        let options = HttpClientOptions {
+            ca_certificate: Some("/nonexistent/ca.pem".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            options.root_certificates(),
+            Err(DomainError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_client_certificate_requires_key() {
+        let mut config = HashMap::new();
+        config.insert(
+            CLIENT_CERTIFICATE_KEY.to_string(),
+            "/etc/pipedash/client.pem".to_string(),
+        );
+        let options = HttpClientOptions::from_config(&config);
+
+        assert!(!options.is_default());
+        assert!(matches!(
+            options.identity(),
+            Err(DomainError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_recording_proxy_reused_per_target() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = manager().with_record_dir(dir.path().to_path_buf());
+        let client = manager.default_client();
+
+        let url = manager
+            .start_recording(
+                1,
+                "gitlab",
+                "https://gitlab.example.com/api/v4",
+                "/api/v4",
+                &client,
+            )
+            .unwrap();
+        let same = manager
+            .start_recording(
+                1,
+                "gitlab",
+                "https://gitlab.example.com/api/v4/",
+                "/api/v4",
+                &client,
+            )
+            .unwrap();
+        let moved = manager
+            .start_recording(
+                1,
+                "gitlab",
+                "https://gitlab.internal/api/v4",
+                "/api/v4",
+                &client,
+            )
+            .unwrap();
+
+        assert!(url.starts_with("http://127.0.0.1:"));
+        assert_eq!(url, same);
+        assert_ne!(url, moved);
+
+        manager.stop_recording(1);
+        assert!(manager.recordings.is_empty());
+    }
+
+    #[test]
+    fn test_conditional_cache_per_provider() {
+        let manager = manager();
+
+        let cache1 = manager.conditional_cache(1, 1024);
+        assert!(Arc::ptr_eq(&cache1, &manager.conditional_cache(1, 1024)));
+        assert!(!Arc::ptr_eq(&cache1, &manager.conditional_cache(2, 1024)));
+
+        manager.remove_conditional_cache(1);
+        assert!(!Arc::ptr_eq(&cache1, &manager.conditional_cache(1, 1024)));
+    }
 }
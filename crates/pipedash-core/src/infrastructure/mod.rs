@@ -1,14 +1,18 @@
 pub mod config;
 pub mod config_backend;
+pub mod crypto;
 pub mod database;
 pub mod deduplication;
 pub mod http_client;
 pub mod migration;
+pub mod oauth;
 pub mod providers;
 pub mod secrets;
+pub mod smtp;
 pub mod storage;
 pub mod sync;
 pub mod token_store;
+pub mod webhooks;
 
 pub use config::{
     ConfigChangeEvent,
@@ -22,6 +26,7 @@ pub use config::{
     ProviderFileConfig,
     ProviderSyncService,
     ServerConfig,
+    SetupDocument,
     SetupStatus,
     StorageBackend as StorageBackendType,
     StorageConfig,
@@ -45,7 +50,10 @@ pub use deduplication::{
     hash_request,
     RequestDeduplicator,
 };
-pub use http_client::HttpClientManager;
+pub use http_client::{
+    HttpClientManager,
+    HttpClientOptions,
+};
 pub use migration::{
     MigrationOptions,
     MigrationOrchestrator,
@@ -56,6 +64,7 @@ pub use migration::{
     MigrationStep,
     ValidationReport,
 };
+pub use smtp::SmtpMailer;
 pub use storage::{
     LocalStorage,
     ObjectMetadata,
@@ -67,6 +76,7 @@ pub use sync::{
     SyncDirection,
     SyncManager,
     SyncResult,
+    PINNED_PIPELINES_KEY,
 };
 pub use token_store::{
     EnvTokenStore,
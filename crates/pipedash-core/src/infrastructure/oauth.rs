@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{
+    DateTime,
+    Duration,
+    Utc,
+};
+use serde::Deserialize;
+
+use crate::domain::{
+    DeviceAuthorization,
+    DomainError,
+    DomainResult,
+    OAuthCredentials,
+    OAuthPollStatus,
+};
+
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+// RFC 8628: back off by this much whenever the server answers `slow_down`.
+const SLOW_DOWN_SECS: u64 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceFlowEndpoints {
+    pub device_code_url: String,
+    pub token_url: String,
+    pub scope: &'static str,
+}
+
+impl DeviceFlowEndpoints {
+    pub fn for_provider(provider_type: &str, base_url: Option<&str>) -> DomainResult<Self> {
+        let base_url = base_url
+            .map(|url| url.trim().trim_end_matches('/'))
+            .filter(|url| !url.is_empty());
+
+        match provider_type {
+            "github" => {
+                let base = base_url.unwrap_or("https://github.com");
+                Ok(Self {
+                    device_code_url: format!("{}/login/device/code", base),
+                    token_url: format!("{}/login/oauth/access_token", base),
+                    scope: "repo workflow read:org",
+                })
+            }
+            "gitlab" => {
+                let base = base_url.unwrap_or("https://gitlab.com");
+                Ok(Self {
+                    device_code_url: format!("{}/oauth/authorize_device", base),
+                    token_url: format!("{}/oauth/token", base),
+                    scope: "api",
+                })
+            }
+            other => Err(DomainError::NotSupported(format!(
+                "OAuth sign-in is not available for {} providers",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    verification_uri_complete: Option<String>,
+    expires_in: i64,
+    interval: Option<u64>,
+}
+
+// GitHub answers 200 with an `error` field while GitLab uses 400, so both
+// shapes share one struct and the status code is ignored.
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    expires_in: Option<i64>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+struct PendingAuthorization {
+    device_code: String,
+    token_url: String,
+    client_id: String,
+    expires_at: DateTime<Utc>,
+    interval_secs: u64,
+    next_poll_at: DateTime<Utc>,
+}
+
+// OAuth 2.0 device authorization grant (RFC 8628). Pending authorizations only
+// live in memory; an app restart means starting the sign-in again.
+#[derive(Default)]
+pub struct DeviceFlowClient {
+    pending: Mutex<HashMap<String, PendingAuthorization>>,
+}
+
+impl DeviceFlowClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn start(
+        &self, http: &reqwest::Client, endpoints: &DeviceFlowEndpoints, client_id: &str,
+    ) -> DomainResult<DeviceAuthorization> {
+        let response: DeviceCodeResponse = http
+            .post(&endpoints.device_code_url)
+            .header("Accept", "application/json")
+            .form(&[("client_id", client_id), ("scope", endpoints.scope)])
+            .send()
+            .await
+            .map_err(|e| DomainError::NetworkError(format!("Device authorization failed: {}", e)))?
+            .error_for_status()
+            .map_err(|e| {
+                DomainError::AuthenticationFailed(format!("Device authorization rejected: {}", e))
+            })?
+            .json()
+            .await
+            .map_err(|e| {
+                DomainError::NetworkError(format!("Invalid device authorization response: {}", e))
+            })?;
+
+        let now = Utc::now();
+        let session_id = format!("{:032x}", rand::random::<u128>());
+        let interval_secs = response.interval.unwrap_or(SLOW_DOWN_SECS);
+        let expires_at = now + Duration::seconds(response.expires_in);
+
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, p| p.expires_at > now);
+        pending.insert(
+            session_id.clone(),
+            PendingAuthorization {
+                device_code: response.device_code,
+                token_url: endpoints.token_url.clone(),
+                client_id: client_id.to_string(),
+                expires_at,
+                interval_secs,
+                next_poll_at: now,
+            },
+        );
+
+        Ok(DeviceAuthorization {
+            session_id,
+            user_code: response.user_code,
+            verification_uri: response.verification_uri,
+            verification_uri_complete: response.verification_uri_complete,
+            expires_at,
+            interval_secs,
+        })
+    }
+
+    // Callers poll at `interval_secs`; polling early just returns `Pending`
+    // without hitting the provider.
+    pub async fn poll(
+        &self, http: &reqwest::Client, session_id: &str,
+    ) -> DomainResult<OAuthPollStatus> {
+        let now = Utc::now();
+        let (device_code, token_url, client_id) = {
+            let mut pending = self.pending.lock().unwrap();
+            let Some(authorization) = pending.get(session_id) else {
+                return Err(DomainError::NotFound(format!(
+                    "OAuth session {} not found",
+                    session_id
+                )));
+            };
+            if authorization.expires_at <= now {
+                pending.remove(session_id);
+                return Ok(OAuthPollStatus::Expired);
+            }
+            if authorization.next_poll_at > now {
+                return Ok(OAuthPollStatus::Pending {
+                    interval_secs: authorization.interval_secs,
+                });
+            }
+            (
+                authorization.device_code.clone(),
+                authorization.token_url.clone(),
+                authorization.client_id.clone(),
+            )
+        };
+
+        let response = request_token(
+            http,
+            &token_url,
+            &[
+                ("client_id", client_id.as_str()),
+                ("device_code", device_code.as_str()),
+                ("grant_type", DEVICE_CODE_GRANT),
+            ],
+        )
+        .await?;
+
+        let error = response.error.clone();
+        let mut pending = self.pending.lock().unwrap();
+        match error.as_deref() {
+            None => {
+                pending.remove(session_id);
+                let credentials = credentials_from(response, token_url, client_id, None)?;
+                // Tokens that never expire are stored as-is, like a pasted
+                // token.
+                let token = if credentials.is_refreshable() {
+                    credentials.to_token_string()
+                } else {
+                    credentials.access_token
+                };
+                Ok(OAuthPollStatus::Authorized { token })
+            }
+            Some(error @ ("authorization_pending" | "slow_down")) => {
+                let Some(authorization) = pending.get_mut(session_id) else {
+                    return Ok(OAuthPollStatus::Expired);
+                };
+                if error == "slow_down" {
+                    authorization.interval_secs += SLOW_DOWN_SECS;
+                }
+                authorization.next_poll_at =
+                    Utc::now() + Duration::seconds(authorization.interval_secs as i64);
+                Ok(OAuthPollStatus::Pending {
+                    interval_secs: authorization.interval_secs,
+                })
+            }
+            Some("expired_token") => {
+                pending.remove(session_id);
+                Ok(OAuthPollStatus::Expired)
+            }
+            Some("access_denied") => {
+                pending.remove(session_id);
+                Ok(OAuthPollStatus::Denied)
+            }
+            Some(error) => {
+                pending.remove(session_id);
+                Err(DomainError::AuthenticationFailed(
+                    response
+                        .error_description
+                        .unwrap_or_else(|| error.to_string()),
+                ))
+            }
+        }
+    }
+}
+
+// Providers may rotate the refresh token on every use; the old one is kept
+// only when the response doesn't include a new one.
+pub async fn refresh_credentials(
+    http: &reqwest::Client, credentials: &OAuthCredentials,
+) -> DomainResult<OAuthCredentials> {
+    let refresh_token = credentials.refresh_token.as_deref().ok_or_else(|| {
+        DomainError::AuthenticationFailed("OAuth credentials have no refresh token".into())
+    })?;
+
+    let response = request_token(
+        http,
+        &credentials.token_url,
+        &[
+            ("client_id", credentials.client_id.as_str()),
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ],
+    )
+    .await?;
+
+    if let Some(error) = response.error.as_deref() {
+        return Err(DomainError::AuthenticationFailed(format!(
+            "Token refresh failed: {}",
+            response.error_description.as_deref().unwrap_or(error)
+        )));
+    }
+
+    credentials_from(
+        response,
+        credentials.token_url.clone(),
+        credentials.client_id.clone(),
+        credentials.refresh_token.clone(),
+    )
+}
+
+async fn request_token(
+    http: &reqwest::Client, token_url: &str, form: &[(&str, &str)],
+) -> DomainResult<TokenResponse> {
+    http.post(token_url)
+        .header("Accept", "application/json")
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| DomainError::NetworkError(format!("Token request failed: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| DomainError::NetworkError(format!("Invalid token response: {}", e)))
+}
+
+fn credentials_from(
+    response: TokenResponse, token_url: String, client_id: String,
+    previous_refresh_token: Option<String>,
+) -> DomainResult<OAuthCredentials> {
+    let access_token = response.access_token.ok_or_else(|| {
+        DomainError::AuthenticationFailed("Token response did not include an access token".into())
+    })?;
+
+    Ok(OAuthCredentials {
+        access_token,
+        refresh_token: response.refresh_token.or(previous_refresh_token),
+        expires_at: response
+            .expires_in
+            .map(|secs| Utc::now() + Duration::seconds(secs)),
+        token_url,
+        client_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoints_for_provider() {
+        let github = DeviceFlowEndpoints::for_provider("github", None).unwrap();
+        assert_eq!(
+            github.token_url,
+            "https://github.com/login/oauth/access_token"
+        );
+
+        let gitlab =
+            DeviceFlowEndpoints::for_provider("gitlab", Some("https://gitlab.example.com/"))
+                .unwrap();
+        assert_eq!(
+            gitlab.device_code_url,
+            "https://gitlab.example.com/oauth/authorize_device"
+        );
+
+        assert!(DeviceFlowEndpoints::for_provider("jenkins", None).is_err());
+    }
+}
@@ -1,14 +1,26 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use pipedash_plugin_api::Plugin as PluginTrait;
 
 use crate::domain::{
+    ArtifactDownload,
+    BranchStatus,
+    BuildAgent,
+    BuildArtifact,
+    BuildQueue,
+    CommitInfo,
     DomainError,
     DomainResult,
+    LogQuery,
+    PendingApproval,
     Pipeline,
     PipelineRun,
     Provider,
+    RunAnnotation,
+    RunLogs,
+    RunMetric,
     TriggerParams,
 };
 
@@ -33,6 +45,9 @@ impl PluginAdapter {
                 DomainError::AuthenticationFailed(msg)
             }
             pipedash_plugin_api::PluginError::ApiError(msg) => DomainError::ApiError(msg),
+            pipedash_plugin_api::PluginError::PermissionDenied(msg) => {
+                DomainError::PermissionDenied(msg)
+            }
             pipedash_plugin_api::PluginError::InvalidConfig(msg) => DomainError::InvalidConfig(msg),
             pipedash_plugin_api::PluginError::PipelineNotFound(msg) => {
                 DomainError::PipelineNotFound(msg)
@@ -41,6 +56,16 @@ impl PluginAdapter {
                 DomainError::InvalidProviderType(msg)
             }
             pipedash_plugin_api::PluginError::NetworkError(msg) => DomainError::NetworkError(msg),
+            pipedash_plugin_api::PluginError::RateLimited {
+                message,
+                retry_after,
+            } => DomainError::RateLimited {
+                message,
+                retry_after,
+            },
+            e @ pipedash_plugin_api::PluginError::ResponseTooLarge { .. } => {
+                DomainError::ProviderError(e.to_string())
+            }
             pipedash_plugin_api::PluginError::SerializationError(msg) => {
                 DomainError::ApiError(format!("Serialization error: {}", msg))
             }
@@ -65,6 +90,7 @@ impl PluginAdapter {
             branch: plugin_pipeline.branch,
             workflow_file: plugin_pipeline.workflow_file,
             metadata: plugin_pipeline.metadata,
+            labels: Vec::new(),
         }
     }
 
@@ -76,15 +102,20 @@ impl PluginAdapter {
             pipedash_plugin_api::PipelineStatus::Failed => crate::domain::PipelineStatus::Failed,
             pipedash_plugin_api::PipelineStatus::Running => crate::domain::PipelineStatus::Running,
             pipedash_plugin_api::PipelineStatus::Pending => crate::domain::PipelineStatus::Pending,
+            pipedash_plugin_api::PipelineStatus::Queued => crate::domain::PipelineStatus::Queued,
             pipedash_plugin_api::PipelineStatus::Cancelled => {
                 crate::domain::PipelineStatus::Cancelled
             }
             pipedash_plugin_api::PipelineStatus::Skipped => crate::domain::PipelineStatus::Skipped,
+            pipedash_plugin_api::PipelineStatus::Waiting => crate::domain::PipelineStatus::Waiting,
+            pipedash_plugin_api::PipelineStatus::Neutral => crate::domain::PipelineStatus::Neutral,
         }
     }
 
+    // Runs are scrubbed here, before they reach the cache or any API
+    // response.
     fn convert_run(plugin_run: pipedash_plugin_api::PipelineRun) -> PipelineRun {
-        PipelineRun {
+        let mut run = PipelineRun {
             id: plugin_run.id,
             pipeline_id: plugin_run.pipeline_id,
             run_number: plugin_run.run_number,
@@ -97,8 +128,55 @@ impl PluginAdapter {
             commit_message: plugin_run.commit_message,
             branch: plugin_run.branch,
             actor: plugin_run.actor,
+            commit_author: plugin_run.commit_author,
+            author_avatar_url: plugin_run.author_avatar_url,
+            pull_request: plugin_run.pull_request,
             inputs: plugin_run.inputs,
             metadata: plugin_run.metadata,
+            annotations: plugin_run.annotations,
+        };
+        crate::domain::redactor().redact_run(&mut run);
+        run
+    }
+
+    fn to_plugin_status(
+        status: &crate::domain::PipelineStatus,
+    ) -> pipedash_plugin_api::PipelineStatus {
+        match status {
+            crate::domain::PipelineStatus::Success => pipedash_plugin_api::PipelineStatus::Success,
+            crate::domain::PipelineStatus::Failed => pipedash_plugin_api::PipelineStatus::Failed,
+            crate::domain::PipelineStatus::Running => pipedash_plugin_api::PipelineStatus::Running,
+            crate::domain::PipelineStatus::Pending => pipedash_plugin_api::PipelineStatus::Pending,
+            crate::domain::PipelineStatus::Queued => pipedash_plugin_api::PipelineStatus::Queued,
+            crate::domain::PipelineStatus::Cancelled => {
+                pipedash_plugin_api::PipelineStatus::Cancelled
+            }
+            crate::domain::PipelineStatus::Skipped => pipedash_plugin_api::PipelineStatus::Skipped,
+            crate::domain::PipelineStatus::Waiting => pipedash_plugin_api::PipelineStatus::Waiting,
+            crate::domain::PipelineStatus::Neutral => pipedash_plugin_api::PipelineStatus::Neutral,
+        }
+    }
+
+    fn to_plugin_run(run: &PipelineRun) -> pipedash_plugin_api::PipelineRun {
+        pipedash_plugin_api::PipelineRun {
+            id: run.id.clone(),
+            pipeline_id: run.pipeline_id.clone(),
+            run_number: run.run_number,
+            status: Self::to_plugin_status(&run.status),
+            started_at: run.started_at,
+            concluded_at: run.concluded_at,
+            duration_seconds: run.duration_seconds,
+            logs_url: run.logs_url.clone(),
+            commit_sha: run.commit_sha.clone(),
+            commit_message: run.commit_message.clone(),
+            branch: run.branch.clone(),
+            actor: run.actor.clone(),
+            commit_author: run.commit_author.clone(),
+            author_avatar_url: run.author_avatar_url.clone(),
+            pull_request: run.pull_request.clone(),
+            inputs: run.inputs.clone(),
+            metadata: run.metadata.clone(),
+            annotations: run.annotations.clone(),
         }
     }
 }
@@ -118,6 +196,21 @@ impl Provider for PluginAdapter {
             .collect())
     }
 
+    async fn fetch_pipelines_if_modified(&self) -> DomainResult<Option<Vec<Pipeline>>> {
+        let pipelines = self
+            .plugin
+            .fetch_pipelines_if_modified()
+            .await
+            .map_err(Self::map_error)?;
+
+        Ok(pipelines.map(|pipelines| {
+            pipelines
+                .into_iter()
+                .map(|p| Self::convert_pipeline(p, self.provider_id, &self.provider_type))
+                .collect()
+        }))
+    }
+
     async fn fetch_run_history(
         &self, pipeline_id: &str, limit: usize,
     ) -> DomainResult<Vec<PipelineRun>> {
@@ -142,6 +235,68 @@ impl Provider for PluginAdapter {
         Ok(Self::convert_run(run))
     }
 
+    async fn fetch_run_logs(
+        &self, pipeline_id: &str, run_number: i64, query: LogQuery,
+    ) -> DomainResult<RunLogs> {
+        self.plugin
+            .fetch_run_logs(pipeline_id, run_number, query)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn fetch_artifacts(&self, run_id: &str) -> DomainResult<Vec<BuildArtifact>> {
+        self.plugin
+            .fetch_artifacts(run_id)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn fetch_agents(&self) -> DomainResult<Vec<BuildAgent>> {
+        self.plugin.fetch_agents().await.map_err(Self::map_error)
+    }
+
+    async fn fetch_queues(&self) -> DomainResult<Vec<BuildQueue>> {
+        self.plugin.fetch_queues().await.map_err(Self::map_error)
+    }
+
+    async fn artifact_download(&self, artifact: &BuildArtifact) -> DomainResult<ArtifactDownload> {
+        self.plugin
+            .artifact_download(artifact)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn fetch_commits_between(
+        &self, pipeline_id: &str, base_sha: &str, head_sha: &str,
+    ) -> DomainResult<Vec<CommitInfo>> {
+        self.plugin
+            .fetch_commits_between(pipeline_id, base_sha, head_sha)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn list_branch_statuses(&self, pipeline_id: &str) -> DomainResult<Vec<BranchStatus>> {
+        let statuses = self
+            .plugin
+            .list_branch_statuses(pipeline_id)
+            .await
+            .map_err(Self::map_error)?;
+
+        Ok(statuses
+            .into_iter()
+            .map(|s| BranchStatus {
+                branch: s.branch,
+                status: Self::convert_status(s.status),
+                run_number: s.run_number,
+                started_at: s.started_at,
+                logs_url: s.logs_url,
+                commit_sha: s.commit_sha,
+                actor: s.actor,
+                pull_request: s.pull_request,
+            })
+            .collect())
+    }
+
     async fn trigger_pipeline(&self, params: TriggerParams) -> DomainResult<String> {
         let trigger_params = pipedash_plugin_api::TriggerParams {
             workflow_id: params.workflow_id,
@@ -161,6 +316,33 @@ impl Provider for PluginAdapter {
             .map_err(Self::map_error)
     }
 
+    async fn retry_run(
+        &self, pipeline_id: &str, run_number: i64, failed_only: bool,
+    ) -> DomainResult<String> {
+        self.plugin
+            .retry_run(pipeline_id, run_number, failed_only)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn fetch_pending_approvals(
+        &self, pipeline_id: &str, run_number: i64,
+    ) -> DomainResult<Vec<PendingApproval>> {
+        self.plugin
+            .fetch_pending_approvals(pipeline_id, run_number)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn unblock_job(
+        &self, pipeline_id: &str, run_number: i64, job_id: &str, fields: HashMap<String, String>,
+    ) -> DomainResult<()> {
+        self.plugin
+            .unblock_job(pipeline_id, run_number, job_id, fields)
+            .await
+            .map_err(Self::map_error)
+    }
+
     async fn get_workflow_parameters(
         &self, workflow_id: &str,
     ) -> DomainResult<Vec<pipedash_plugin_api::WorkflowParameter>> {
@@ -170,6 +352,20 @@ impl Provider for PluginAdapter {
             .map_err(Self::map_error)
     }
 
+    async fn extract_metrics(&self, run: &PipelineRun) -> DomainResult<Vec<RunMetric>> {
+        self.plugin
+            .extract_metrics(&Self::to_plugin_run(run))
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn fetch_run_annotations(&self, run: &PipelineRun) -> DomainResult<Vec<RunAnnotation>> {
+        self.plugin
+            .fetch_run_annotations(&Self::to_plugin_run(run))
+            .await
+            .map_err(Self::map_error)
+    }
+
     async fn validate_credentials(&self) -> DomainResult<bool> {
         self.plugin
             .validate_credentials()
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::domain::DomainResult;
+use crate::infrastructure::database::SqliteWriter;
+use crate::infrastructure::TokenStore;
+
+const TOKEN_TTL: Duration = Duration::from_secs(60);
+
+// Keeps tokens read through it in memory for a short while, so reloading or
+// listing providers doesn't go back to the keychain or vault each time.
+// Writes through this store drop the provider's entry right away; the TTL
+// only bounds how stale a change made behind its back can get.
+pub struct CachedTokenStore {
+    inner: Arc<dyn TokenStore>,
+    ttl: Duration,
+    tokens: RwLock<HashMap<i64, (String, Instant)>>,
+}
+
+impl CachedTokenStore {
+    pub fn new(inner: Arc<dyn TokenStore>) -> Self {
+        Self {
+            inner,
+            ttl: TOKEN_TTL,
+            tokens: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    async fn cached(&self, provider_id: i64) -> Option<String> {
+        self.tokens
+            .read()
+            .await
+            .get(&provider_id)
+            .filter(|(_, at)| at.elapsed() < self.ttl)
+            .map(|(token, _)| token.clone())
+    }
+
+    async fn invalidate(&self, provider_id: i64) {
+        self.tokens.write().await.remove(&provider_id);
+    }
+}
+
+#[async_trait]
+impl TokenStore for CachedTokenStore {
+    async fn store_token(&self, provider_id: i64, token: &str) -> DomainResult<()> {
+        let result = self.inner.store_token(provider_id, token).await;
+        self.invalidate(provider_id).await;
+        result
+    }
+
+    async fn get_token(&self, provider_id: i64) -> DomainResult<String> {
+        if let Some(token) = self.cached(provider_id).await {
+            return Ok(token);
+        }
+
+        let token = self.inner.get_token(provider_id).await?;
+        self.tokens
+            .write()
+            .await
+            .insert(provider_id, (token.clone(), Instant::now()));
+        Ok(token)
+    }
+
+    async fn delete_token(&self, provider_id: i64) -> DomainResult<()> {
+        let result = self.inner.delete_token(provider_id).await;
+        self.invalidate(provider_id).await;
+        result
+    }
+
+    async fn get_all_tokens(&self) -> DomainResult<HashMap<i64, String>> {
+        self.inner.get_all_tokens().await
+    }
+
+    async fn get_tokens_batch(&self, provider_ids: &[i64]) -> DomainResult<HashMap<i64, String>> {
+        let mut tokens = HashMap::with_capacity(provider_ids.len());
+        let mut missing = Vec::new();
+        {
+            let cache = self.tokens.read().await;
+            for &provider_id in provider_ids {
+                match cache.get(&provider_id) {
+                    Some((token, at)) if at.elapsed() < self.ttl => {
+                        tokens.insert(provider_id, token.clone());
+                    }
+                    _ => missing.push(provider_id),
+                }
+            }
+        }
+        if missing.is_empty() {
+            return Ok(tokens);
+        }
+
+        let fetched = self.inner.get_tokens_batch(&missing).await?;
+        let now = Instant::now();
+        let mut cache = self.tokens.write().await;
+        for (provider_id, token) in fetched {
+            cache.insert(provider_id, (token.clone(), now));
+            tokens.insert(provider_id, token);
+        }
+        Ok(tokens)
+    }
+
+    async fn get_token_by_name(&self, name: &str) -> DomainResult<String> {
+        self.inner.get_token_by_name(name).await
+    }
+
+    async fn store_secret(&self, provider_id: i64, name: &str, value: &str) -> DomainResult<()> {
+        self.inner.store_secret(provider_id, name, value).await
+    }
+
+    async fn get_secret(&self, provider_id: i64, name: &str) -> DomainResult<Option<String>> {
+        self.inner.get_secret(provider_id, name).await
+    }
+
+    async fn delete_secret(&self, provider_id: i64, name: &str) -> DomainResult<()> {
+        self.inner.delete_secret(provider_id, name).await
+    }
+
+    async fn export_encrypted(&self, password: &str) -> DomainResult<Vec<u8>> {
+        self.inner.export_encrypted(password).await
+    }
+
+    async fn import_encrypted(&self, data: &[u8], password: &str) -> DomainResult<()> {
+        let result = self.inner.import_encrypted(data, password).await;
+        self.tokens.write().await.clear();
+        result
+    }
+
+    async fn rotate_password(
+        &self, current_password: &str, new_password: &str,
+        progress: &(dyn Fn(usize, usize) + Send + Sync),
+    ) -> DomainResult<usize> {
+        self.inner
+            .rotate_password(current_password, new_password, progress)
+            .await
+    }
+
+    async fn warmup(&self) -> DomainResult<()> {
+        self.inner.warmup().await
+    }
+
+    fn use_sqlite_writer(&self, writer: SqliteWriter) {
+        self.inner.use_sqlite_writer(writer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::MemoryTokenStore;
+
+    #[tokio::test]
+    async fn test_cached_token_store() {
+        let inner = Arc::new(MemoryTokenStore::new());
+        inner.store_token(1, "one").await.unwrap();
+        inner.store_token(2, "two").await.unwrap();
+        let store = CachedTokenStore::new(inner.clone());
+
+        let tokens = store.get_tokens_batch(&[1, 2, 3]).await.unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[&2], "two");
+
+        // Served from the cache until it expires or is written through it.
+        inner.store_token(1, "changed").await.unwrap();
+        assert_eq!(store.get_token(1).await.unwrap(), "one");
+        store.store_token(1, "updated").await.unwrap();
+        assert_eq!(store.get_token(1).await.unwrap(), "updated");
+
+        let store = store.with_ttl(Duration::ZERO);
+        inner.store_token(2, "rotated").await.unwrap();
+        assert_eq!(store.get_tokens_batch(&[2]).await.unwrap()[&2], "rotated");
+    }
+}
@@ -0,0 +1,538 @@
+use std::collections::HashMap;
+use std::sync::{
+    Arc,
+    OnceLock,
+};
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use sha2::{
+    Digest,
+    Sha256,
+};
+use tokio::sync::RwLock;
+
+use crate::domain::{
+    DomainError,
+    DomainResult,
+};
+use crate::infrastructure::config::token_ref::TokenReference;
+use crate::infrastructure::crypto::hmac_sha256;
+use crate::infrastructure::database::SqliteWriter;
+use crate::infrastructure::TokenStore;
+
+// Resolved secrets are kept briefly so listing providers doesn't hit the
+// secrets manager once per provider, while rotations upstream still land soon.
+const RESOLVED_TTL: Duration = Duration::from_secs(300);
+
+// Wraps the regular token store. Tokens that are references into HashiCorp
+// Vault, AWS Secrets Manager or 1Password Connect are stored as the reference
+// and resolved on read, so only the reference ever reaches pipedash's database.
+// Everything else passes through to `inner` untouched.
+pub struct ExternalSecretsTokenStore {
+    inner: Arc<dyn TokenStore>,
+    resolver: ExternalSecretResolver,
+    resolved: RwLock<HashMap<String, (String, Instant)>>,
+}
+
+impl ExternalSecretsTokenStore {
+    pub fn new(inner: Arc<dyn TokenStore>) -> Self {
+        Self {
+            inner,
+            resolver: ExternalSecretResolver::new(),
+            resolved: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn resolve(&self, reference: &TokenReference) -> DomainResult<String> {
+        let key = reference.to_toml_string();
+        if let Some((token, at)) = self.resolved.read().await.get(&key) {
+            if at.elapsed() < RESOLVED_TTL {
+                return Ok(token.clone());
+            }
+        }
+
+        let token = self.resolver.resolve(reference).await?;
+        self.resolved
+            .write()
+            .await
+            .insert(key, (token.clone(), Instant::now()));
+        Ok(token)
+    }
+}
+
+fn external_reference(value: &str) -> Option<TokenReference> {
+    TokenReference::parse(value)
+        .ok()
+        .filter(TokenReference::is_external)
+}
+
+#[async_trait]
+impl TokenStore for ExternalSecretsTokenStore {
+    async fn store_token(&self, provider_id: i64, token: &str) -> DomainResult<()> {
+        self.inner.store_token(provider_id, token).await
+    }
+
+    async fn get_token(&self, provider_id: i64) -> DomainResult<String> {
+        let stored = self.inner.get_token(provider_id).await?;
+        match external_reference(&stored) {
+            Some(reference) => self.resolve(&reference).await,
+            None => Ok(stored),
+        }
+    }
+
+    // A reference that fails to resolve leaves its provider out, like a
+    // missing token would.
+    async fn get_tokens_batch(&self, provider_ids: &[i64]) -> DomainResult<HashMap<i64, String>> {
+        let mut tokens = self.inner.get_tokens_batch(provider_ids).await?;
+        let references: Vec<(i64, TokenReference)> = tokens
+            .iter()
+            .filter_map(|(id, stored)| Some((*id, external_reference(stored)?)))
+            .collect();
+        for (provider_id, reference) in references {
+            match self.resolve(&reference).await {
+                Ok(token) => {
+                    tokens.insert(provider_id, token);
+                }
+                Err(e) => {
+                    tracing::warn!(provider_id, error = %e, "Failed to resolve token reference");
+                    tokens.remove(&provider_id);
+                }
+            }
+        }
+        Ok(tokens)
+    }
+
+    async fn delete_token(&self, provider_id: i64) -> DomainResult<()> {
+        self.inner.delete_token(provider_id).await
+    }
+
+    // Returns what is stored, i.e. references stay references. Callers use this
+    // for migrations and exports, which should carry the reference along.
+    async fn get_all_tokens(&self) -> DomainResult<HashMap<i64, String>> {
+        self.inner.get_all_tokens().await
+    }
+
+    async fn get_token_by_name(&self, name: &str) -> DomainResult<String> {
+        match external_reference(name) {
+            Some(reference) => self.resolve(&reference).await,
+            None => self.inner.get_token_by_name(name).await,
+        }
+    }
+
+    async fn store_secret(&self, provider_id: i64, name: &str, value: &str) -> DomainResult<()> {
+        self.inner.store_secret(provider_id, name, value).await
+    }
+
+    async fn get_secret(&self, provider_id: i64, name: &str) -> DomainResult<Option<String>> {
+        self.inner.get_secret(provider_id, name).await
+    }
+
+    async fn delete_secret(&self, provider_id: i64, name: &str) -> DomainResult<()> {
+        self.inner.delete_secret(provider_id, name).await
+    }
+
+    async fn export_encrypted(&self, password: &str) -> DomainResult<Vec<u8>> {
+        self.inner.export_encrypted(password).await
+    }
+
+    async fn import_encrypted(&self, data: &[u8], password: &str) -> DomainResult<()> {
+        self.inner.import_encrypted(data, password).await
+    }
+
+    async fn rotate_password(
+        &self, current_password: &str, new_password: &str,
+        progress: &(dyn Fn(usize, usize) + Send + Sync),
+    ) -> DomainResult<usize> {
+        self.inner
+            .rotate_password(current_password, new_password, progress)
+            .await
+    }
+
+    async fn warmup(&self) -> DomainResult<()> {
+        self.inner.warmup().await
+    }
+
+    fn use_sqlite_writer(&self, writer: SqliteWriter) {
+        self.inner.use_sqlite_writer(writer);
+    }
+}
+
+// Talks to the secrets managers using each tool's standard environment
+// variables, so the same setup that works for their CLIs works here. The
+// client is built on first use, as most deployments never need one.
+pub struct ExternalSecretResolver {
+    client: OnceLock<Client>,
+}
+
+impl ExternalSecretResolver {
+    pub fn new() -> Self {
+        Self {
+            client: OnceLock::new(),
+        }
+    }
+
+    fn client(&self) -> &Client {
+        self.client.get_or_init(|| {
+            Client::builder()
+                .use_rustls_tls()
+                .connect_timeout(Duration::from_secs(10))
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default()
+        })
+    }
+
+    pub async fn resolve(&self, reference: &TokenReference) -> DomainResult<String> {
+        match reference {
+            TokenReference::Vault { path, key } => self.resolve_vault(path, key).await,
+            TokenReference::AwsSecret { secret_id, key } => {
+                self.resolve_aws(secret_id, key.as_deref()).await
+            }
+            TokenReference::OnePassword { vault, item, field } => {
+                self.resolve_one_password(vault, item, field).await
+            }
+            other => Err(DomainError::InvalidConfig(format!(
+                "{} is not an external secret reference",
+                other
+            ))),
+        }
+    }
+
+    // KV v2: `secret/ci/github` reads `{addr}/v1/secret/data/ci/github`.
+    async fn resolve_vault(&self, path: &str, key: &str) -> DomainResult<String> {
+        let addr = required_env("VAULT_ADDR")?;
+        let token = required_env("VAULT_TOKEN")?;
+        let (mount, secret_path) = path.split_once('/').ok_or_else(|| {
+            DomainError::InvalidConfig(format!("Vault path '{}' has no mount", path))
+        })?;
+
+        let mut request = self
+            .client()
+            .get(format!(
+                "{}/v1/{}/data/{}",
+                addr.trim_end_matches('/'),
+                mount,
+                secret_path
+            ))
+            .header("X-Vault-Token", token);
+        if let Ok(namespace) = std::env::var("VAULT_NAMESPACE") {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+
+        #[derive(Deserialize)]
+        struct KvResponse {
+            data: KvData,
+        }
+        #[derive(Deserialize)]
+        struct KvData {
+            data: HashMap<String, serde_json::Value>,
+        }
+
+        let response: KvResponse = send_json(request, "Vault").await?;
+        response
+            .data
+            .data
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                DomainError::InvalidConfig(format!("Vault secret '{}' has no key '{}'", path, key))
+            })
+    }
+
+    async fn resolve_aws(&self, secret_id: &str, key: Option<&str>) -> DomainResult<String> {
+        let credentials = AwsCredentials::from_env()?;
+        let endpoint = std::env::var("AWS_ENDPOINT_URL_SECRETS_MANAGER")
+            .or_else(|_| std::env::var("AWS_ENDPOINT_URL"))
+            .unwrap_or_else(|_| {
+                format!(
+                    "https://secretsmanager.{}.amazonaws.com",
+                    credentials.region
+                )
+            });
+        let endpoint = endpoint.trim_end_matches('/');
+        let host = endpoint
+            .split_once("://")
+            .map(|(_, rest)| rest)
+            .unwrap_or(endpoint);
+
+        let body = serde_json::json!({ "SecretId": secret_id }).to_string();
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+        let mut request = self
+            .client()
+            .post(format!("{}/", endpoint))
+            .header("Content-Type", "application/x-amz-json-1.1")
+            .header("X-Amz-Target", "secretsmanager.GetSecretValue")
+            .header("X-Amz-Date", &amz_date)
+            .header(
+                "Authorization",
+                credentials.authorization(host, &amz_date, &body),
+            );
+        if let Some(session_token) = &credentials.session_token {
+            request = request.header("X-Amz-Security-Token", session_token);
+        }
+
+        #[derive(Deserialize)]
+        struct SecretValue {
+            #[serde(rename = "SecretString")]
+            secret_string: Option<String>,
+        }
+
+        let response: SecretValue = send_json(request.body(body), "AWS Secrets Manager").await?;
+        let secret = response.secret_string.ok_or_else(|| {
+            DomainError::InvalidConfig(format!("AWS secret '{}' is not a string secret", secret_id))
+        })?;
+
+        match key {
+            None => Ok(secret),
+            Some(key) => serde_json::from_str::<HashMap<String, serde_json::Value>>(&secret)
+                .ok()
+                .and_then(|values| values.get(key)?.as_str().map(str::to_string))
+                .ok_or_else(|| {
+                    DomainError::InvalidConfig(format!(
+                        "AWS secret '{}' has no JSON key '{}'",
+                        secret_id, key
+                    ))
+                }),
+        }
+    }
+
+    // Connect addresses vaults and items by ID; names are looked up first, the
+    // same way `op://` references work in the 1Password CLI.
+    async fn resolve_one_password(
+        &self, vault: &str, item: &str, field: &str,
+    ) -> DomainResult<String> {
+        let host = required_env("OP_CONNECT_HOST")?;
+        let token = required_env("OP_CONNECT_TOKEN")?;
+        let base = format!("{}/v1", host.trim_end_matches('/'));
+
+        #[derive(Deserialize)]
+        struct Summary {
+            id: String,
+        }
+        #[derive(Deserialize)]
+        struct Item {
+            #[serde(default)]
+            fields: Vec<Field>,
+        }
+        #[derive(Deserialize)]
+        struct Field {
+            id: String,
+            label: Option<String>,
+            value: Option<String>,
+        }
+
+        let lookup = |url: String, filter: String| {
+            self.client()
+                .get(url)
+                .query(&[("filter", filter)])
+                .bearer_auth(&token)
+        };
+
+        let vault_id = if is_one_password_id(vault) {
+            vault.to_string()
+        } else {
+            let found: Vec<Summary> = send_json(
+                lookup(format!("{}/vaults", base), format!("name eq \"{}\"", vault)),
+                "1Password Connect",
+            )
+            .await?;
+            found.into_iter().next().map(|v| v.id).ok_or_else(|| {
+                DomainError::InvalidConfig(format!("1Password vault '{}' not found", vault))
+            })?
+        };
+
+        let item_id = if is_one_password_id(item) {
+            item.to_string()
+        } else {
+            let found: Vec<Summary> = send_json(
+                lookup(
+                    format!("{}/vaults/{}/items", base, vault_id),
+                    format!("title eq \"{}\"", item),
+                ),
+                "1Password Connect",
+            )
+            .await?;
+            found.into_iter().next().map(|i| i.id).ok_or_else(|| {
+                DomainError::InvalidConfig(format!("1Password item '{}' not found", item))
+            })?
+        };
+
+        let found: Item = send_json(
+            self.client()
+                .get(format!("{}/vaults/{}/items/{}", base, vault_id, item_id))
+                .bearer_auth(&token),
+            "1Password Connect",
+        )
+        .await?;
+
+        found
+            .fields
+            .into_iter()
+            .find(|f| f.id == field || f.label.as_deref() == Some(field))
+            .and_then(|f| f.value)
+            .ok_or_else(|| {
+                DomainError::InvalidConfig(format!(
+                    "1Password item '{}' has no field '{}'",
+                    item, field
+                ))
+            })
+    }
+}
+
+fn required_env(name: &str) -> DomainResult<String> {
+    std::env::var(name)
+        .ok()
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| DomainError::InvalidConfig(format!("{} is not set", name)))
+}
+
+fn is_one_password_id(value: &str) -> bool {
+    value.len() == 26
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+async fn send_json<T: serde::de::DeserializeOwned>(
+    request: reqwest::RequestBuilder, backend: &str,
+) -> DomainResult<T> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| DomainError::NetworkError(format!("{} request failed: {}", backend, e)))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(DomainError::AuthenticationFailed(format!(
+            "{} rejected the credentials (HTTP {})",
+            backend, status
+        )));
+    }
+    if !status.is_success() {
+        return Err(DomainError::NetworkError(format!(
+            "{} returned HTTP {}",
+            backend, status
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| DomainError::NetworkError(format!("Invalid {} response: {}", backend, e)))
+}
+
+impl Default for ExternalSecretResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Static credentials only (env vars); instance and SSO credentials need the
+// AWS SDK and are out of scope here.
+struct AwsCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    region: String,
+}
+
+impl AwsCredentials {
+    fn from_env() -> DomainResult<Self> {
+        Ok(Self {
+            access_key_id: required_env("AWS_ACCESS_KEY_ID")?,
+            secret_access_key: required_env("AWS_SECRET_ACCESS_KEY")?,
+            session_token: std::env::var("AWS_SESSION_TOKEN")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            region: required_env("AWS_REGION").or_else(|_| required_env("AWS_DEFAULT_REGION"))?,
+        })
+    }
+
+    // SigV4 for a `POST /` with the headers set in `resolve_aws`.
+    fn authorization(&self, host: &str, amz_date: &str, body: &str) -> String {
+        let date = &amz_date[..8];
+        let scope = format!("{}/{}/secretsmanager/aws4_request", date, self.region);
+
+        let mut headers = vec![
+            ("content-type", "application/x-amz-json-1.1".to_string()),
+            ("host", host.to_string()),
+            ("x-amz-date", amz_date.to_string()),
+            ("x-amz-target", "secretsmanager.GetSecretValue".to_string()),
+        ];
+        if let Some(session_token) = &self.session_token {
+            headers.push(("x-amz-security-token", session_token.clone()));
+        }
+        headers.sort_by_key(|(name, _)| *name);
+
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value))
+            .collect();
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+
+        let canonical_request = format!(
+            "POST\n/\n\n{}\n{}\n{}",
+            canonical_headers,
+            signed_headers,
+            hex(&Sha256::digest(body.as_bytes()))
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let mut key = hmac_sha256(
+            format!("AWS4{}", self.secret_access_key).as_bytes(),
+            date.as_bytes(),
+        );
+        for part in [self.region.as_str(), "secretsmanager", "aws4_request"] {
+            key = hmac_sha256(&key, part.as_bytes());
+        }
+        let signature = hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, signature
+        )
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::MemoryTokenStore;
+
+    #[tokio::test]
+    async fn test_plain_tokens_pass_through() {
+        let store = ExternalSecretsTokenStore::new(Arc::new(MemoryTokenStore::new()));
+        store.store_token(1, "plain-token").await.unwrap();
+        store
+            .store_token(2, "vault:secret/ci/github#token")
+            .await
+            .unwrap();
+
+        assert_eq!(store.get_token(1).await.unwrap(), "plain-token");
+
+        // The reference is what gets stored, not a resolved token.
+        let all = store.get_all_tokens().await.unwrap();
+        assert_eq!(all.get(&2).unwrap(), "vault:secret/ci/github#token");
+    }
+}
@@ -2,11 +2,13 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use pipedash_core::domain::{
+
+use crate::domain::{
     DomainError,
     DomainResult,
 };
-use pipedash_core::infrastructure::TokenStore;
+use crate::infrastructure::database::SqliteWriter;
+use crate::infrastructure::TokenStore;
 
 pub struct FallbackTokenStore {
     primary: Arc<dyn TokenStore>,
@@ -89,6 +91,25 @@ impl TokenStore for FallbackTokenStore {
         )))
     }
 
+    // Only providers missing from the primary store go through `get_token`,
+    // which checks the fallback and migrates what it finds there.
+    async fn get_tokens_batch(&self, provider_ids: &[i64]) -> DomainResult<HashMap<i64, String>> {
+        let mut tokens = self
+            .primary
+            .get_tokens_batch(provider_ids)
+            .await
+            .unwrap_or_default();
+        for &provider_id in provider_ids {
+            if tokens.contains_key(&provider_id) {
+                continue;
+            }
+            if let Ok(token) = self.get_token(provider_id).await {
+                tokens.insert(provider_id, token);
+            }
+        }
+        Ok(tokens)
+    }
+
     async fn delete_token(&self, provider_id: i64) -> DomainResult<()> {
         let primary_result = self.primary.delete_token(provider_id).await;
         let fallback_result = self.fallback.delete_token(provider_id).await;
@@ -110,6 +131,27 @@ impl TokenStore for FallbackTokenStore {
         Ok(all_tokens)
     }
 
+    async fn store_secret(&self, provider_id: i64, name: &str, value: &str) -> DomainResult<()> {
+        self.primary.store_secret(provider_id, name, value).await?;
+
+        let _ = self.fallback.delete_secret(provider_id, name).await;
+
+        Ok(())
+    }
+
+    async fn get_secret(&self, provider_id: i64, name: &str) -> DomainResult<Option<String>> {
+        if let Some(value) = self.primary.get_secret(provider_id, name).await? {
+            return Ok(Some(value));
+        }
+        self.fallback.get_secret(provider_id, name).await
+    }
+
+    async fn delete_secret(&self, provider_id: i64, name: &str) -> DomainResult<()> {
+        let primary_result = self.primary.delete_secret(provider_id, name).await;
+        let _ = self.fallback.delete_secret(provider_id, name).await;
+        primary_result
+    }
+
     async fn export_encrypted(&self, password: &str) -> DomainResult<Vec<u8>> {
         let _all_tokens = self.get_all_tokens().await?;
 
@@ -120,16 +162,29 @@ impl TokenStore for FallbackTokenStore {
         self.primary.import_encrypted(data, password).await
     }
 
+    async fn rotate_password(
+        &self, current_password: &str, new_password: &str,
+        progress: &(dyn Fn(usize, usize) + Send + Sync),
+    ) -> DomainResult<usize> {
+        self.primary
+            .rotate_password(current_password, new_password, progress)
+            .await
+    }
+
     async fn warmup(&self) -> DomainResult<()> {
         self.primary.warmup().await
     }
+
+    fn use_sqlite_writer(&self, writer: SqliteWriter) {
+        self.primary.use_sqlite_writer(writer.clone());
+        self.fallback.use_sqlite_writer(writer);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use pipedash_core::infrastructure::MemoryTokenStore;
-
     use super::*;
+    use crate::infrastructure::MemoryTokenStore;
 
     #[tokio::test]
     async fn test_fallback_primary_first() {
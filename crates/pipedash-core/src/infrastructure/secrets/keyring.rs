@@ -0,0 +1,655 @@
+use std::collections::{
+    BTreeSet,
+    HashMap,
+    HashSet,
+};
+use std::sync::Arc;
+
+use aes_gcm::{
+    aead::{
+        Aead,
+        KeyInit,
+    },
+    Aes256Gcm,
+    Nonce,
+};
+use async_trait::async_trait;
+use keyring::Entry;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::domain::{
+    DomainError,
+    DomainResult,
+};
+use crate::infrastructure::config::KeyringLayout;
+use crate::infrastructure::token_store::TokenExport;
+use crate::infrastructure::TokenStore;
+
+const BACKUP_KDF_SALT: &[u8] = b"pipedash-backup-salt-v1";
+
+// The keyring can't list entries, so per-provider ones are found by probing
+// ids up to this one.
+const MAX_SCANNED_PROVIDER_ID: i64 = 1000;
+
+pub struct KeyringTokenStore {
+    keyring_lock: Arc<Mutex<()>>,
+    token_cache: Arc<Mutex<Option<HashMap<String, String>>>>,
+    layout: KeyringLayout,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct KeyringMigrationReport {
+    pub layout: KeyringLayout,
+    pub dry_run: bool,
+    // Providers whose token was moved into the requested layout.
+    pub moved: Vec<i64>,
+    // Tokens left behind by deleted providers, removed from the keyring.
+    pub orphaned: Vec<i64>,
+    // Providers stored both ways; only the copy in the new layout is kept.
+    pub duplicates: Vec<i64>,
+}
+
+impl Default for KeyringTokenStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyringTokenStore {
+    pub fn new() -> Self {
+        Self {
+            keyring_lock: Arc::new(Mutex::new(())),
+            token_cache: Arc::new(Mutex::new(None)),
+            layout: KeyringLayout::default(),
+        }
+    }
+
+    pub fn with_layout(mut self, layout: KeyringLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    fn keyring_entry(&self) -> DomainResult<Entry> {
+        Entry::new("pipedash", "tokens")
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to create keyring entry: {e}")))
+    }
+
+    fn provider_entry(provider_id: i64) -> DomainResult<Entry> {
+        Entry::new("pipedash", &format!("provider_{}", provider_id)).map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to create provider keyring entry: {e}"))
+        })
+    }
+
+    fn read_provider_entry(provider_id: i64) -> DomainResult<Option<String>> {
+        match Self::provider_entry(provider_id)?.get_password() {
+            Ok(token) => Ok(Some(token)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(DomainError::DatabaseError(format!(
+                "Failed to get token from keyring for provider {provider_id}: {e}"
+            ))),
+        }
+    }
+
+    fn write_provider_entry(provider_id: i64, token: &str) -> DomainResult<()> {
+        Self::provider_entry(provider_id)?
+            .set_password(token)
+            .map_err(|e| {
+                DomainError::DatabaseError(format!(
+                    "Failed to store token in keyring for provider {provider_id}: {e}"
+                ))
+            })
+    }
+
+    fn delete_provider_entry(provider_id: i64) -> DomainResult<()> {
+        match Self::provider_entry(provider_id)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(DomainError::DatabaseError(format!(
+                "Failed to delete keyring entry for provider {provider_id}: {e}"
+            ))),
+        }
+    }
+
+    // A provider's other credentials share one entry, as a JSON map by name.
+    fn secrets_entry(provider_id: i64) -> DomainResult<Entry> {
+        Entry::new("pipedash", &format!("provider_{}_secrets", provider_id)).map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to create secrets keyring entry: {e}"))
+        })
+    }
+
+    fn read_secrets(provider_id: i64) -> DomainResult<HashMap<String, String>> {
+        match Self::secrets_entry(provider_id)?.get_password() {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to parse secrets JSON: {e}"))
+            }),
+            Err(keyring::Error::NoEntry) => Ok(HashMap::new()),
+            Err(e) => Err(DomainError::DatabaseError(format!(
+                "Failed to get secrets from keyring for provider {provider_id}: {e}"
+            ))),
+        }
+    }
+
+    fn write_secrets(provider_id: i64, secrets: &HashMap<String, String>) -> DomainResult<()> {
+        if secrets.is_empty() {
+            return Self::delete_secrets(provider_id);
+        }
+
+        let entry = Self::secrets_entry(provider_id)?;
+        let json = serde_json::to_string(secrets)
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to serialize secrets: {e}")))?;
+        entry.set_password(&json).map_err(|e| {
+            DomainError::DatabaseError(format!(
+                "Failed to store secrets in keyring for provider {provider_id}: {e}"
+            ))
+        })
+    }
+
+    fn delete_secrets(provider_id: i64) -> DomainResult<()> {
+        match Self::secrets_entry(provider_id)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(DomainError::DatabaseError(format!(
+                "Failed to delete secrets keyring entry for provider {provider_id}: {e}"
+            ))),
+        }
+    }
+
+    // Keyring calls block, on some platforms for a while, so longer runs of
+    // them go to the blocking pool instead of stalling a runtime worker.
+    async fn blocking<T, F>(f: F) -> DomainResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> DomainResult<T> + Send + 'static,
+    {
+        tokio::task::spawn_blocking(f)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Keyring task failed: {e}")))?
+    }
+
+    async fn get_all_tokens_cached(&self) -> DomainResult<HashMap<String, String>> {
+        let mut cache = self.token_cache.lock().await;
+
+        if let Some(ref cached_tokens) = *cache {
+            return Ok(cached_tokens.clone());
+        }
+
+        let entry = self.keyring_entry()?;
+        let tokens = match entry.get_password() {
+            Ok(json) => {
+                if json.trim().is_empty() {
+                    HashMap::new()
+                } else {
+                    serde_json::from_str(&json).map_err(|e| {
+                        DomainError::DatabaseError(format!("Failed to parse tokens JSON: {e}"))
+                    })?
+                }
+            }
+            Err(keyring::Error::NoEntry) => HashMap::new(),
+            Err(e) => {
+                return Err(DomainError::DatabaseError(format!(
+                    "Failed to get tokens from keyring: {e}"
+                )))
+            }
+        };
+
+        *cache = Some(tokens.clone());
+        Ok(tokens)
+    }
+
+    async fn save_all_tokens(&self, tokens: &HashMap<String, String>) -> DomainResult<()> {
+        let entry = self.keyring_entry()?;
+
+        if tokens.is_empty() {
+            match entry.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => {}
+                Err(e) => {
+                    return Err(DomainError::DatabaseError(format!(
+                        "Failed to delete keyring entry: {e}"
+                    )))
+                }
+            }
+            *self.token_cache.lock().await = Some(HashMap::new());
+            return Ok(());
+        }
+
+        let json = serde_json::to_string(tokens)
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to serialize tokens: {e}")))?;
+
+        entry.set_password(&json).map_err(|e| {
+            DomainError::DatabaseError(format!(
+                "Failed to store tokens in system keyring: {}\n\
+                 \nThe tokens will not be saved securely. Please ensure:\n\
+                 - macOS: Grant Keychain Access permission to Pipedash\n\
+                 - Linux: Install libsecret (sudo apt install libsecret-1-dev)\n\
+                 - Windows: Ensure Credential Manager is accessible",
+                e
+            ))
+        })?;
+
+        let mut cache = self.token_cache.lock().await;
+        *cache = Some(tokens.clone());
+
+        Ok(())
+    }
+
+    async fn remove_shared_token(&self, provider_id: i64) -> DomainResult<()> {
+        let mut tokens = self.get_all_tokens_cached().await?;
+        if tokens.remove(&provider_id.to_string()).is_none() {
+            return Ok(());
+        }
+        self.save_all_tokens(&tokens).await
+    }
+
+    // Looks in the provider's own entry first, then in the shared one for
+    // tokens not moved yet.
+    async fn get_per_provider_token(&self, provider_id: i64) -> DomainResult<Option<String>> {
+        if let Some(token) = Self::read_provider_entry(provider_id)? {
+            return Ok(Some(token));
+        }
+        Ok(self
+            .get_all_tokens_cached()
+            .await?
+            .get(&provider_id.to_string())
+            .cloned())
+    }
+
+    async fn migrate_legacy_token(&self, provider_id: i64) -> DomainResult<Option<String>> {
+        let old_entry = Self::provider_entry(provider_id)?;
+
+        if let Ok(token) = old_entry.get_password() {
+            let mut tokens = self.get_all_tokens_cached().await?;
+            tokens.insert(provider_id.to_string(), token.clone());
+            self.save_all_tokens(&tokens).await?;
+
+            let _ = old_entry.delete_credential();
+
+            return Ok(Some(token));
+        }
+
+        Ok(None)
+    }
+
+    pub async fn cleanup_legacy_entries(&self) -> DomainResult<usize> {
+        // Per-provider entries are where tokens live in that layout.
+        if self.layout == KeyringLayout::PerProvider {
+            return Ok(0);
+        }
+
+        let _lock = self.keyring_lock.lock().await;
+        let mut cleaned = 0;
+
+        for provider_id in 1..=MAX_SCANNED_PROVIDER_ID {
+            let old_entry = match Self::provider_entry(provider_id) {
+                Ok(entry) => entry,
+                Err(_) => continue,
+            };
+
+            if old_entry.delete_credential().is_ok() {
+                cleaned += 1;
+                tracing::debug!(
+                    "[KeyringTokenStore] Cleaned up legacy entry for provider_{}",
+                    provider_id
+                );
+            }
+        }
+
+        if cleaned > 0 {
+            tracing::info!(
+                "[KeyringTokenStore] Cleaned up {} legacy keyring entries",
+                cleaned
+            );
+        }
+
+        Ok(cleaned)
+    }
+
+    // Moves every token into `target`, dropping those whose provider isn't in
+    // `provider_ids` along with the provider's other secrets. Where a token
+    // was stored both ways, the copy in this store's current layout wins,
+    // since that's the one kept up to date. The keyring can't be listed, so
+    // only the given providers and those in the shared entry are looked at.
+    pub async fn migrate_layout(
+        &self, target: KeyringLayout, provider_ids: &[i64], dry_run: bool,
+    ) -> DomainResult<KeyringMigrationReport> {
+        let _lock = self.keyring_lock.lock().await;
+
+        let live: HashSet<i64> = provider_ids.iter().copied().collect();
+        let mut shared = self.get_all_tokens_cached().await?;
+        let candidates: BTreeSet<i64> = live
+            .iter()
+            .copied()
+            .chain(shared.keys().filter_map(|key| key.parse().ok()))
+            .collect();
+        let stored = Self::blocking(move || {
+            candidates
+                .into_iter()
+                .map(|provider_id| Ok((provider_id, Self::read_provider_entry(provider_id)?)))
+                .collect::<DomainResult<Vec<_>>>()
+        })
+        .await?;
+
+        let mut report = KeyringMigrationReport {
+            layout: target,
+            dry_run,
+            ..Default::default()
+        };
+        let mut to_write = Vec::new();
+        let mut to_delete = Vec::new();
+
+        for (provider_id, separate) in stored {
+            let key = provider_id.to_string();
+            let in_shared = shared.get(&key).cloned();
+
+            let token = match (separate.clone(), in_shared.clone()) {
+                (None, None) => continue,
+                (Some(separate), Some(in_shared)) => match self.layout {
+                    KeyringLayout::Single => in_shared,
+                    KeyringLayout::PerProvider => separate,
+                },
+                (Some(token), None) | (None, Some(token)) => token,
+            };
+
+            if !live.contains(&provider_id) {
+                report.orphaned.push(provider_id);
+                shared.remove(&key);
+                if separate.is_some() {
+                    to_delete.push(provider_id);
+                }
+                continue;
+            }
+
+            let in_target = match target {
+                KeyringLayout::Single => in_shared.is_some(),
+                KeyringLayout::PerProvider => separate.is_some(),
+            };
+            if !in_target {
+                report.moved.push(provider_id);
+            } else if separate.is_some() && in_shared.is_some() {
+                report.duplicates.push(provider_id);
+            }
+
+            match target {
+                KeyringLayout::Single => {
+                    shared.insert(key, token);
+                    if separate.is_some() {
+                        to_delete.push(provider_id);
+                    }
+                }
+                KeyringLayout::PerProvider => {
+                    if separate.as_ref() != Some(&token) {
+                        to_write.push((provider_id, token));
+                    }
+                    shared.remove(&key);
+                }
+            }
+        }
+
+        if dry_run {
+            return Ok(report);
+        }
+
+        // Copies are written before the old ones are removed, so a failure
+        // part way leaves a token stored twice rather than not at all.
+        Self::blocking(move || {
+            for (provider_id, token) in &to_write {
+                Self::write_provider_entry(*provider_id, token)?;
+            }
+            Ok(())
+        })
+        .await?;
+        if report.orphaned.len() + report.moved.len() + report.duplicates.len() > 0 {
+            self.save_all_tokens(&shared).await?;
+        }
+        let orphaned = report.orphaned.clone();
+        Self::blocking(move || {
+            for provider_id in to_delete {
+                Self::delete_provider_entry(provider_id)?;
+            }
+            for provider_id in orphaned {
+                Self::delete_secrets(provider_id)?;
+            }
+            Ok(())
+        })
+        .await?;
+
+        tracing::info!(
+            layout = %target,
+            moved = report.moved.len(),
+            orphaned = report.orphaned.len(),
+            duplicates = report.duplicates.len(),
+            "[KeyringTokenStore] Migrated keyring layout"
+        );
+
+        Ok(report)
+    }
+
+    fn derive_backup_key(password: &str) -> [u8; 32] {
+        use argon2::{
+            Argon2,
+            ParamsBuilder,
+        };
+
+        let mut output = [0u8; 32];
+
+        let params = ParamsBuilder::new()
+            .m_cost(4096) // 4 MiB memory
+            .t_cost(1) // 1 iteration
+            .p_cost(1) // 1 thread
+            .output_len(32) // 256-bit key
+            .build()
+            .expect("Invalid Argon2 parameters");
+
+        let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+        argon2
+            .hash_password_into(password.as_bytes(), BACKUP_KDF_SALT, &mut output)
+            .expect("Failed to derive backup encryption key");
+
+        output
+    }
+}
+
+#[async_trait]
+impl TokenStore for KeyringTokenStore {
+    async fn store_token(&self, provider_id: i64, token: &str) -> DomainResult<()> {
+        let _lock = self.keyring_lock.lock().await;
+
+        if self.layout == KeyringLayout::PerProvider {
+            Self::write_provider_entry(provider_id, token)?;
+            // A stale copy in the shared entry would outlive a later delete.
+            return self.remove_shared_token(provider_id).await;
+        }
+
+        let mut tokens = self.get_all_tokens_cached().await?;
+        tokens.insert(provider_id.to_string(), token.to_string());
+        self.save_all_tokens(&tokens).await
+    }
+
+    async fn get_token(&self, provider_id: i64) -> DomainResult<String> {
+        let _lock = self.keyring_lock.lock().await;
+
+        if self.layout == KeyringLayout::PerProvider {
+            return self
+                .get_per_provider_token(provider_id)
+                .await?
+                .ok_or_else(|| {
+                    DomainError::DatabaseError(format!(
+                        "Token not found in keyring for provider {}",
+                        provider_id
+                    ))
+                });
+        }
+
+        let tokens = self.get_all_tokens_cached().await?;
+
+        if let Some(token) = tokens.get(&provider_id.to_string()) {
+            return Ok(token.clone());
+        }
+
+        drop(tokens);
+        if let Some(token) = self.migrate_legacy_token(provider_id).await? {
+            return Ok(token);
+        }
+
+        Err(DomainError::DatabaseError(format!(
+            "Token not found in keyring for provider {}",
+            provider_id
+        )))
+    }
+
+    // One keychain read for the whole batch instead of one per provider.
+    async fn get_tokens_batch(&self, provider_ids: &[i64]) -> DomainResult<HashMap<i64, String>> {
+        let _lock = self.keyring_lock.lock().await;
+
+        let mut tokens = HashMap::with_capacity(provider_ids.len());
+        if self.layout == KeyringLayout::PerProvider {
+            for &provider_id in provider_ids {
+                if let Some(token) = self.get_per_provider_token(provider_id).await? {
+                    tokens.insert(provider_id, token);
+                }
+            }
+            return Ok(tokens);
+        }
+
+        let stored = self.get_all_tokens_cached().await?;
+        for &provider_id in provider_ids {
+            let token = match stored.get(&provider_id.to_string()) {
+                Some(token) => Some(token.clone()),
+                None => self.migrate_legacy_token(provider_id).await?,
+            };
+            if let Some(token) = token {
+                tokens.insert(provider_id, token);
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    async fn delete_token(&self, provider_id: i64) -> DomainResult<()> {
+        let _lock = self.keyring_lock.lock().await;
+
+        if self.layout == KeyringLayout::PerProvider {
+            Self::delete_provider_entry(provider_id)?;
+        }
+        self.remove_shared_token(provider_id).await
+    }
+
+    async fn get_all_tokens(&self) -> DomainResult<HashMap<i64, String>> {
+        let _lock = self.keyring_lock.lock().await;
+
+        let string_tokens = self.get_all_tokens_cached().await?;
+        let mut result = HashMap::new();
+
+        for (key, value) in string_tokens {
+            if let Ok(id) = key.parse::<i64>() {
+                result.insert(id, value);
+            }
+        }
+
+        if self.layout == KeyringLayout::PerProvider {
+            for provider_id in 1..=MAX_SCANNED_PROVIDER_ID {
+                if let Some(token) = Self::read_provider_entry(provider_id)? {
+                    result.insert(provider_id, token);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn store_secret(&self, provider_id: i64, name: &str, value: &str) -> DomainResult<()> {
+        let _lock = self.keyring_lock.lock().await;
+
+        let mut secrets = Self::read_secrets(provider_id)?;
+        secrets.insert(name.to_string(), value.to_string());
+        Self::write_secrets(provider_id, &secrets)
+    }
+
+    async fn get_secret(&self, provider_id: i64, name: &str) -> DomainResult<Option<String>> {
+        let _lock = self.keyring_lock.lock().await;
+
+        Ok(Self::read_secrets(provider_id)?.remove(name))
+    }
+
+    async fn delete_secret(&self, provider_id: i64, name: &str) -> DomainResult<()> {
+        let _lock = self.keyring_lock.lock().await;
+
+        let mut secrets = Self::read_secrets(provider_id)?;
+        if secrets.remove(name).is_some() {
+            Self::write_secrets(provider_id, &secrets)?;
+        }
+        Ok(())
+    }
+
+    // Secrets are exported for providers that have a token; the keyring
+    // can't be listed, and a provider without a token isn't usable anyway.
+    async fn export_encrypted(&self, password: &str) -> DomainResult<Vec<u8>> {
+        let tokens = self.get_all_tokens().await?;
+        let mut secrets = HashMap::new();
+        {
+            let _lock = self.keyring_lock.lock().await;
+            for &provider_id in tokens.keys() {
+                let values = Self::read_secrets(provider_id)?;
+                if !values.is_empty() {
+                    secrets.insert(provider_id, values);
+                }
+            }
+        }
+
+        let json = serde_json::to_vec(&TokenExport { tokens, secrets }).map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to serialize tokens: {}", e))
+        })?;
+
+        let backup_key = Self::derive_backup_key(password);
+        let cipher = Aes256Gcm::new_from_slice(&backup_key)
+            .map_err(|e| DomainError::InternalError(format!("Failed to create cipher: {}", e)))?;
+
+        let nonce_bytes: [u8; 12] = rand::random();
+        let nonce = Nonce::from(nonce_bytes);
+
+        let ciphertext = cipher
+            .encrypt(&nonce, json.as_ref())
+            .map_err(|e| DomainError::InternalError(format!("Encryption failed: {}", e)))?;
+
+        let mut result = nonce_bytes.to_vec();
+        result.extend(ciphertext);
+        Ok(result)
+    }
+
+    async fn import_encrypted(&self, data: &[u8], password: &str) -> DomainResult<()> {
+        if data.len() < 12 {
+            return Err(DomainError::InvalidConfig("Invalid backup data".into()));
+        }
+
+        let nonce_bytes: [u8; 12] = data[..12]
+            .try_into()
+            .map_err(|_| DomainError::InvalidConfig("Invalid nonce".into()))?;
+        let ciphertext = &data[12..];
+
+        let backup_key = Self::derive_backup_key(password);
+        let cipher = Aes256Gcm::new_from_slice(&backup_key)
+            .map_err(|e| DomainError::InternalError(format!("Failed to create cipher: {}", e)))?;
+
+        let nonce = Nonce::from(nonce_bytes);
+
+        let plaintext = cipher.decrypt(&nonce, ciphertext).map_err(|_| {
+            DomainError::AuthenticationFailed("Decryption failed (wrong password?)".into())
+        })?;
+
+        let TokenExport { tokens, secrets } = TokenExport::parse(&plaintext)?;
+
+        for (provider_id, token) in tokens {
+            self.store_token(provider_id, &token).await?;
+        }
+        for (provider_id, values) in secrets {
+            for (name, value) in values {
+                self.store_secret(provider_id, &name, &value).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn create_keyring_token_store() -> Arc<dyn TokenStore> {
+    Arc::new(KeyringTokenStore::new())
+}
@@ -7,6 +7,37 @@ pub use postgres::PostgresTokenStore;
 mod sqlite;
 pub use sqlite::SqliteTokenStore;
 
+mod fallback;
+pub use fallback::FallbackTokenStore;
+
+mod cached;
+pub use cached::CachedTokenStore;
+
+mod provider_secrets;
+pub use provider_secrets::{
+    delete_config_secrets,
+    is_secret_reference,
+    redact_config_secrets,
+    resolve_config_secrets,
+    store_config_secrets,
+    PROVIDER_SECRET_KEYS,
+};
+
+mod external;
+pub use external::{
+    ExternalSecretResolver,
+    ExternalSecretsTokenStore,
+};
+
+#[cfg(feature = "keyring")]
+mod keyring;
+
+#[cfg(feature = "keyring")]
+pub use self::keyring::{
+    create_keyring_token_store,
+    KeyringMigrationReport,
+    KeyringTokenStore,
+};
 pub use super::token_store::{
     EnvTokenStore,
     MemoryTokenStore,
@@ -20,13 +20,17 @@ use crate::domain::{
     DomainError,
     DomainResult,
 };
+use crate::infrastructure::token_store::{
+    validate_new_vault_password,
+    TokenExport,
+};
 use crate::infrastructure::TokenStore;
 
 const BACKUP_KDF_SALT: &[u8] = b"pipedash-backup-salt-v1";
 
 pub struct PostgresTokenStore {
     pool: Pool<Postgres>,
-    encryption_key: [u8; 32],
+    encryption_key: RwLock<[u8; 32]>,
     cache: Arc<RwLock<HashMap<i64, String>>>,
 }
 
@@ -62,7 +66,7 @@ impl PostgresTokenStore {
 
         let store = Self {
             pool,
-            encryption_key,
+            encryption_key: RwLock::new(encryption_key),
             cache: Arc::new(RwLock::new(HashMap::new())),
         };
 
@@ -112,7 +116,17 @@ impl PostgresTokenStore {
     }
 
     async fn encrypt_token(&self, plaintext: &str) -> DomainResult<(Vec<u8>, Vec<u8>)> {
-        let cipher = Aes256Gcm::new_from_slice(&self.encryption_key)
+        let key = *self.encryption_key.read().await;
+        Self::encrypt_with_key(&key, plaintext)
+    }
+
+    async fn decrypt_token(&self, nonce: &[u8], ciphertext: &[u8]) -> DomainResult<String> {
+        let key = *self.encryption_key.read().await;
+        Self::decrypt_with_key(&key, nonce, ciphertext)
+    }
+
+    fn encrypt_with_key(key: &[u8; 32], plaintext: &str) -> DomainResult<(Vec<u8>, Vec<u8>)> {
+        let cipher = Aes256Gcm::new_from_slice(key)
             .map_err(|e| DomainError::InternalError(format!("Failed to create cipher: {}", e)))?;
 
         let nonce_bytes: [u8; 12] = rand::random();
@@ -125,12 +139,12 @@ impl PostgresTokenStore {
         Ok((nonce_bytes.to_vec(), ciphertext))
     }
 
-    async fn decrypt_token(&self, nonce: &[u8], ciphertext: &[u8]) -> DomainResult<String> {
+    fn decrypt_with_key(key: &[u8; 32], nonce: &[u8], ciphertext: &[u8]) -> DomainResult<String> {
         if nonce.len() != 12 {
             return Err(DomainError::InvalidConfig("Invalid nonce length".into()));
         }
 
-        let cipher = Aes256Gcm::new_from_slice(&self.encryption_key)
+        let cipher = Aes256Gcm::new_from_slice(key)
             .map_err(|e| DomainError::InternalError(format!("Failed to create cipher: {}", e)))?;
 
         let nonce_array: [u8; 12] = nonce
@@ -146,6 +160,22 @@ impl PostgresTokenStore {
             .map_err(|e| DomainError::DatabaseError(format!("Invalid UTF-8: {}", e)))
     }
 
+    async fn all_secrets(&self) -> DomainResult<HashMap<i64, HashMap<String, String>>> {
+        let rows = sqlx::query_as::<_, (i64, String, Vec<u8>, Vec<u8>)>(
+            "SELECT provider_id, name, value_nonce, encrypted_value FROM provider_secrets",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to load secrets: {}", e)))?;
+
+        let mut secrets: HashMap<i64, HashMap<String, String>> = HashMap::new();
+        for (provider_id, name, nonce, ciphertext) in rows {
+            let value = self.decrypt_token(&nonce, &ciphertext).await?;
+            secrets.entry(provider_id).or_default().insert(name, value);
+        }
+        Ok(secrets)
+    }
+
     async fn load_to_cache(&self) -> DomainResult<()> {
         let rows = sqlx::query_as::<_, (i64, Vec<u8>, Vec<u8>)>(
             "SELECT provider_id, nonce, ciphertext FROM encrypted_tokens",
@@ -241,6 +271,42 @@ impl TokenStore for PostgresTokenStore {
         Ok(token)
     }
 
+    async fn get_tokens_batch(&self, provider_ids: &[i64]) -> DomainResult<HashMap<i64, String>> {
+        let mut tokens = HashMap::with_capacity(provider_ids.len());
+        let mut missing = Vec::new();
+        {
+            let cache = self.cache.read().await;
+            for &provider_id in provider_ids {
+                match cache.get(&provider_id) {
+                    Some(token) => {
+                        tokens.insert(provider_id, token.clone());
+                    }
+                    None => missing.push(provider_id),
+                }
+            }
+        }
+        if missing.is_empty() {
+            return Ok(tokens);
+        }
+
+        let rows = sqlx::query_as::<_, (i64, Vec<u8>, Vec<u8>)>(
+            "SELECT provider_id, nonce, ciphertext FROM encrypted_tokens WHERE provider_id = ANY($1)",
+        )
+        .bind(&missing)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to get tokens: {}", e)))?;
+
+        let mut cache = self.cache.write().await;
+        for (provider_id, nonce, ciphertext) in rows {
+            let token = self.decrypt_token(&nonce, &ciphertext).await?;
+            cache.insert(provider_id, token.clone());
+            tokens.insert(provider_id, token);
+        }
+
+        Ok(tokens)
+    }
+
     async fn delete_token(&self, provider_id: i64) -> DomainResult<()> {
         sqlx::query("DELETE FROM encrypted_tokens WHERE provider_id = $1")
             .bind(provider_id)
@@ -259,9 +325,57 @@ impl TokenStore for PostgresTokenStore {
         Ok(cache.clone())
     }
 
+    async fn store_secret(&self, provider_id: i64, name: &str, value: &str) -> DomainResult<()> {
+        let (nonce, ciphertext) = self.encrypt_token(value).await?;
+        sqlx::query(
+            "INSERT INTO provider_secrets (provider_id, name, value_nonce, encrypted_value, updated_at)
+             VALUES ($1, $2, $3, $4, NOW())
+             ON CONFLICT (provider_id, name)
+             DO UPDATE SET value_nonce = $3, encrypted_value = $4, updated_at = NOW()",
+        )
+        .bind(provider_id)
+        .bind(name)
+        .bind(&nonce)
+        .bind(&ciphertext)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to store secret: {}", e)))?;
+        Ok(())
+    }
+
+    async fn get_secret(&self, provider_id: i64, name: &str) -> DomainResult<Option<String>> {
+        let row = sqlx::query_as::<_, (Vec<u8>, Vec<u8>)>(
+            "SELECT value_nonce, encrypted_value FROM provider_secrets
+             WHERE provider_id = $1 AND name = $2",
+        )
+        .bind(provider_id)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to get secret: {}", e)))?;
+
+        match row {
+            Some((nonce, ciphertext)) => Ok(Some(self.decrypt_token(&nonce, &ciphertext).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn delete_secret(&self, provider_id: i64, name: &str) -> DomainResult<()> {
+        sqlx::query("DELETE FROM provider_secrets WHERE provider_id = $1 AND name = $2")
+            .bind(provider_id)
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to delete secret: {}", e)))?;
+        Ok(())
+    }
+
     async fn export_encrypted(&self, password: &str) -> DomainResult<Vec<u8>> {
-        let tokens = self.get_all_tokens().await?;
-        let json = serde_json::to_vec(&tokens).map_err(|e| {
+        let export = TokenExport {
+            tokens: self.get_all_tokens().await?,
+            secrets: self.all_secrets().await?,
+        };
+        let json = serde_json::to_vec(&export).map_err(|e| {
             DomainError::DatabaseError(format!("Failed to serialize tokens: {}", e))
         })?;
 
@@ -302,15 +416,100 @@ impl TokenStore for PostgresTokenStore {
             DomainError::AuthenticationFailed("Decryption failed (wrong password?)".into())
         })?;
 
-        let tokens: HashMap<i64, String> = serde_json::from_slice(&plaintext)
-            .map_err(|e| DomainError::DatabaseError(format!("Failed to parse tokens: {}", e)))?;
+        let TokenExport { tokens, secrets } = TokenExport::parse(&plaintext)?;
 
         for (provider_id, token) in tokens {
             self.store_token(provider_id, &token).await?;
         }
+        for (provider_id, values) in secrets {
+            for (name, value) in values {
+                self.store_secret(provider_id, &name, &value).await?;
+            }
+        }
 
         Ok(())
     }
+
+    async fn rotate_password(
+        &self, current_password: &str, new_password: &str,
+        progress: &(dyn Fn(usize, usize) + Send + Sync),
+    ) -> DomainResult<usize> {
+        validate_new_vault_password(current_password, new_password)?;
+
+        let mut key = self.encryption_key.write().await;
+        if Self::derive_encryption_key(current_password) != *key {
+            return Err(DomainError::AuthenticationFailed(
+                "Current vault password is incorrect".into(),
+            ));
+        }
+        let new_key = Self::derive_encryption_key(new_password);
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        // Row locks keep other instances sharing the database from writing
+        // tokens under the old key mid-rotation.
+        let rows = sqlx::query_as::<_, (i64, Vec<u8>, Vec<u8>)>(
+            "SELECT provider_id, nonce, ciphertext FROM encrypted_tokens FOR UPDATE",
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to load tokens: {}", e)))?;
+
+        let total = rows.len();
+        for (index, (provider_id, nonce, ciphertext)) in rows.into_iter().enumerate() {
+            let token = Self::decrypt_with_key(&key, &nonce, &ciphertext)?;
+            let (nonce, ciphertext) = Self::encrypt_with_key(&new_key, &token)?;
+
+            sqlx::query(
+                "UPDATE encrypted_tokens SET nonce = $1, ciphertext = $2, updated_at = NOW()
+                 WHERE provider_id = $3",
+            )
+            .bind(&nonce)
+            .bind(&ciphertext)
+            .bind(provider_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to store token: {}", e)))?;
+
+            progress(index + 1, total);
+        }
+
+        let secret_rows = sqlx::query_as::<_, (i64, String, Vec<u8>, Vec<u8>)>(
+            "SELECT provider_id, name, value_nonce, encrypted_value FROM provider_secrets FOR UPDATE",
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to load secrets: {}", e)))?;
+
+        for (provider_id, name, nonce, ciphertext) in secret_rows {
+            let value = Self::decrypt_with_key(&key, &nonce, &ciphertext)?;
+            let (nonce, ciphertext) = Self::encrypt_with_key(&new_key, &value)?;
+
+            sqlx::query(
+                "UPDATE provider_secrets SET value_nonce = $1, encrypted_value = $2, updated_at = NOW()
+                 WHERE provider_id = $3 AND name = $4",
+            )
+            .bind(&nonce)
+            .bind(&ciphertext)
+            .bind(provider_id)
+            .bind(&name)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to store secret: {}", e)))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        *key = new_key;
+
+        tracing::info!(count = total, "PostgreSQL vault password rotated");
+        Ok(total)
+    }
 }
 
 impl PostgresTokenStore {
@@ -366,6 +565,20 @@ mod tests {
         .await
         .expect("Failed to create test table");
 
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS provider_secrets (
+                provider_id BIGINT NOT NULL,
+                name TEXT NOT NULL,
+                encrypted_value BYTEA NOT NULL,
+                value_nonce BYTEA NOT NULL,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                PRIMARY KEY (provider_id, name)
+            )",
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create secrets table");
+
         sqlx::query("TRUNCATE TABLE encrypted_tokens")
             .execute(&pool)
             .await
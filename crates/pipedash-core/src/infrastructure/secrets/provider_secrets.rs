@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use crate::domain::DomainResult;
+use crate::infrastructure::webhooks::WEBHOOK_SECRET_KEY;
+use crate::infrastructure::TokenStore;
+
+// Config keys holding credentials. Their values live in the token store next
+// to the provider's token; the saved config only keeps `storage:<key>`.
+pub const PROVIDER_SECRET_KEYS: &[&str] = &[WEBHOOK_SECRET_KEY];
+
+const SECRET_REFERENCE_PREFIX: &str = "storage:";
+
+fn secret_reference(key: &str) -> String {
+    format!("{}{}", SECRET_REFERENCE_PREFIX, key)
+}
+
+pub fn is_secret_reference(key: &str, value: &str) -> bool {
+    value
+        .strip_prefix(SECRET_REFERENCE_PREFIX)
+        .is_some_and(|name| name == key)
+}
+
+// Moves secret values out of `config` into the token store. A reference is
+// left as is (the client sent back what it was given), and an empty value
+// clears the secret.
+pub async fn store_config_secrets(
+    token_store: &dyn TokenStore, provider_id: i64, config: &mut HashMap<String, String>,
+) -> DomainResult<()> {
+    for &key in PROVIDER_SECRET_KEYS {
+        let Some(value) = config.get(key) else {
+            continue;
+        };
+        if is_secret_reference(key, value) {
+            continue;
+        }
+
+        if value.is_empty() {
+            token_store.delete_secret(provider_id, key).await?;
+            config.remove(key);
+        } else {
+            token_store.store_secret(provider_id, key, value).await?;
+            config.insert(key.to_string(), secret_reference(key));
+        }
+    }
+    Ok(())
+}
+
+// Replaces references with the stored values. A reference with nothing behind
+// it is dropped, the same as a secret that was never set.
+pub async fn resolve_config_secrets(
+    token_store: &dyn TokenStore, provider_id: i64, config: &mut HashMap<String, String>,
+) -> DomainResult<()> {
+    for &key in PROVIDER_SECRET_KEYS {
+        if !config.get(key).is_some_and(|v| is_secret_reference(key, v)) {
+            continue;
+        }
+
+        match token_store.get_secret(provider_id, key).await? {
+            Some(value) => {
+                config.insert(key.to_string(), value);
+            }
+            None => {
+                config.remove(key);
+            }
+        }
+    }
+    Ok(())
+}
+
+// What API responses and exports show: whether a secret is set, not its value.
+pub fn redact_config_secrets(config: &mut HashMap<String, String>) {
+    for &key in PROVIDER_SECRET_KEYS {
+        match config.get(key) {
+            Some(value) if value.is_empty() => {
+                config.remove(key);
+            }
+            Some(_) => {
+                config.insert(key.to_string(), secret_reference(key));
+            }
+            None => {}
+        }
+    }
+}
+
+pub async fn delete_config_secrets(
+    token_store: &dyn TokenStore, provider_id: i64,
+) -> DomainResult<()> {
+    for &key in PROVIDER_SECRET_KEYS {
+        token_store.delete_secret(provider_id, key).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::MemoryTokenStore;
+
+    fn config(value: &str) -> HashMap<String, String> {
+        HashMap::from([
+            (WEBHOOK_SECRET_KEY.to_string(), value.to_string()),
+            ("owner".to_string(), "acme".to_string()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_secrets_round_trip_through_store() {
+        let store = MemoryTokenStore::new();
+
+        let mut saved = config("s3cret");
+        store_config_secrets(&store, 1, &mut saved).await.unwrap();
+        assert_eq!(saved[WEBHOOK_SECRET_KEY], "storage:webhook_secret");
+        assert_eq!(saved["owner"], "acme");
+
+        // Sending the reference back keeps the stored value.
+        let mut resent = saved.clone();
+        store_config_secrets(&store, 1, &mut resent).await.unwrap();
+        resolve_config_secrets(&store, 1, &mut resent)
+            .await
+            .unwrap();
+        assert_eq!(resent[WEBHOOK_SECRET_KEY], "s3cret");
+
+        let mut cleared = config("");
+        store_config_secrets(&store, 1, &mut cleared).await.unwrap();
+        assert!(!cleared.contains_key(WEBHOOK_SECRET_KEY));
+
+        let mut stale = saved;
+        resolve_config_secrets(&store, 1, &mut stale).await.unwrap();
+        assert!(!stale.contains_key(WEBHOOK_SECRET_KEY));
+    }
+
+    #[test]
+    fn test_redact_config_secrets() {
+        let mut set = config("s3cret");
+        redact_config_secrets(&mut set);
+        assert_eq!(set[WEBHOOK_SECRET_KEY], "storage:webhook_secret");
+
+        let mut empty = config("");
+        redact_config_secrets(&mut empty);
+        assert!(!empty.contains_key(WEBHOOK_SECRET_KEY));
+    }
+}
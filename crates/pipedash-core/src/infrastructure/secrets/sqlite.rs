@@ -1,5 +1,8 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    OnceLock,
+};
 
 use aes_gcm::{
     aead::{
@@ -11,6 +14,7 @@ use aes_gcm::{
 };
 use async_trait::async_trait;
 use sqlx::{
+    Connection,
     Pool,
     Sqlite,
 };
@@ -20,6 +24,11 @@ use crate::domain::{
     DomainError,
     DomainResult,
 };
+use crate::infrastructure::database::SqliteWriter;
+use crate::infrastructure::token_store::{
+    validate_new_vault_password,
+    TokenExport,
+};
 use crate::infrastructure::TokenStore;
 
 const SQLITE_KDF_SALT: &[u8] = b"pipedash-sqlite-vault-v1";
@@ -27,7 +36,10 @@ const BACKUP_KDF_SALT: &[u8] = b"pipedash-backup-salt-v1";
 
 pub struct SqliteTokenStore {
     pool: Pool<Sqlite>,
-    encryption_key: [u8; 32],
+    // The config backend's writer once `use_sqlite_writer` hands it over, so
+    // token writes queue with the other writes to this database.
+    writer: OnceLock<SqliteWriter>,
+    encryption_key: RwLock<[u8; 32]>,
     cache: Arc<RwLock<HashMap<i64, String>>>,
 }
 
@@ -45,7 +57,8 @@ impl SqliteTokenStore {
 
         let store = Self {
             pool,
-            encryption_key,
+            writer: OnceLock::new(),
+            encryption_key: RwLock::new(encryption_key),
             cache: Arc::new(RwLock::new(HashMap::new())),
         };
 
@@ -61,6 +74,11 @@ impl SqliteTokenStore {
         Self::new(pool, vault_password).await
     }
 
+    fn writer(&self) -> &SqliteWriter {
+        self.writer
+            .get_or_init(|| SqliteWriter::spawn(self.pool.clone()))
+    }
+
     fn derive_encryption_key(password: &str) -> [u8; 32] {
         use argon2::{
             Argon2,
@@ -112,7 +130,17 @@ impl SqliteTokenStore {
     }
 
     async fn encrypt_token(&self, plaintext: &str) -> DomainResult<(Vec<u8>, Vec<u8>)> {
-        let cipher = Aes256Gcm::new_from_slice(&self.encryption_key)
+        let key = *self.encryption_key.read().await;
+        Self::encrypt_with_key(&key, plaintext)
+    }
+
+    async fn decrypt_token(&self, nonce: &[u8], ciphertext: &[u8]) -> DomainResult<String> {
+        let key = *self.encryption_key.read().await;
+        Self::decrypt_with_key(&key, nonce, ciphertext)
+    }
+
+    fn encrypt_with_key(key: &[u8; 32], plaintext: &str) -> DomainResult<(Vec<u8>, Vec<u8>)> {
+        let cipher = Aes256Gcm::new_from_slice(key)
             .map_err(|e| DomainError::InternalError(format!("Failed to create cipher: {}", e)))?;
 
         let nonce_bytes: [u8; 12] = rand::random();
@@ -125,12 +153,12 @@ impl SqliteTokenStore {
         Ok((nonce_bytes.to_vec(), ciphertext))
     }
 
-    async fn decrypt_token(&self, nonce: &[u8], ciphertext: &[u8]) -> DomainResult<String> {
+    fn decrypt_with_key(key: &[u8; 32], nonce: &[u8], ciphertext: &[u8]) -> DomainResult<String> {
         if nonce.len() != 12 {
             return Err(DomainError::InvalidConfig("Invalid nonce length".into()));
         }
 
-        let cipher = Aes256Gcm::new_from_slice(&self.encryption_key)
+        let cipher = Aes256Gcm::new_from_slice(key)
             .map_err(|e| DomainError::InternalError(format!("Failed to create cipher: {}", e)))?;
 
         let nonce_array: [u8; 12] = nonce
@@ -146,6 +174,22 @@ impl SqliteTokenStore {
             .map_err(|e| DomainError::DatabaseError(format!("Invalid UTF-8 in token: {}", e)))
     }
 
+    async fn all_secrets(&self) -> DomainResult<HashMap<i64, HashMap<String, String>>> {
+        let rows = sqlx::query_as::<_, (i64, String, Vec<u8>, Vec<u8>)>(
+            "SELECT provider_id, name, encrypted_value, value_nonce FROM provider_secrets",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to load secrets: {}", e)))?;
+
+        let mut secrets: HashMap<i64, HashMap<String, String>> = HashMap::new();
+        for (provider_id, name, ciphertext, nonce) in rows {
+            let value = self.decrypt_token(&nonce, &ciphertext).await?;
+            secrets.entry(provider_id).or_default().insert(name, value);
+        }
+        Ok(secrets)
+    }
+
     async fn load_to_cache(&self) -> DomainResult<()> {
         let rows = sqlx::query_as::<_, (i64, Vec<u8>, Vec<u8>)>(
             "SELECT id, encrypted_token, token_nonce FROM providers
@@ -205,6 +249,7 @@ impl TokenStore for SqliteTokenStore {
             ciphertext.len()
         );
 
+        let mut conn = self.writer().acquire().await?;
         let result = sqlx::query(
             "UPDATE providers SET encrypted_token = ?1, token_nonce = ?2, updated_at = datetime('now')
              WHERE id = ?3",
@@ -212,7 +257,7 @@ impl TokenStore for SqliteTokenStore {
         .bind(&ciphertext)
         .bind(&nonce)
         .bind(provider_id)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await
         .map_err(|e| DomainError::DatabaseError(format!("Failed to store token: {}", e)))?;
 
@@ -266,13 +311,56 @@ impl TokenStore for SqliteTokenStore {
         Ok(token)
     }
 
+    async fn get_tokens_batch(&self, provider_ids: &[i64]) -> DomainResult<HashMap<i64, String>> {
+        let mut tokens = HashMap::with_capacity(provider_ids.len());
+        let mut missing = Vec::new();
+        {
+            let cache = self.cache.read().await;
+            for &provider_id in provider_ids {
+                match cache.get(&provider_id) {
+                    Some(token) => {
+                        tokens.insert(provider_id, token.clone());
+                    }
+                    None => missing.push(provider_id),
+                }
+            }
+        }
+        if missing.is_empty() {
+            return Ok(tokens);
+        }
+
+        let sql = format!(
+            "SELECT id, encrypted_token, token_nonce FROM providers
+             WHERE id IN ({}) AND encrypted_token IS NOT NULL",
+            vec!["?"; missing.len()].join(", ")
+        );
+        let mut query = sqlx::query_as::<_, (i64, Vec<u8>, Vec<u8>)>(&sql);
+        for provider_id in &missing {
+            query = query.bind(provider_id);
+        }
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to get tokens: {}", e)))?;
+
+        let mut cache = self.cache.write().await;
+        for (provider_id, ciphertext, nonce) in rows {
+            let token = self.decrypt_token(&nonce, &ciphertext).await?;
+            cache.insert(provider_id, token.clone());
+            tokens.insert(provider_id, token);
+        }
+
+        Ok(tokens)
+    }
+
     async fn delete_token(&self, provider_id: i64) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
         sqlx::query(
             "UPDATE providers SET encrypted_token = NULL, token_nonce = NULL, updated_at = datetime('now')
              WHERE id = ?1",
         )
         .bind(provider_id)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await
         .map_err(|e| DomainError::DatabaseError(format!("Failed to delete token: {}", e)))?;
 
@@ -288,9 +376,61 @@ impl TokenStore for SqliteTokenStore {
         Ok(cache.clone())
     }
 
+    async fn store_secret(&self, provider_id: i64, name: &str, value: &str) -> DomainResult<()> {
+        let (nonce, ciphertext) = self.encrypt_token(value).await?;
+        let mut conn = self.writer().acquire().await?;
+        sqlx::query(
+            "INSERT INTO provider_secrets (provider_id, name, encrypted_value, value_nonce)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (provider_id, name) DO UPDATE SET
+                encrypted_value = excluded.encrypted_value,
+                value_nonce = excluded.value_nonce,
+                updated_at = datetime('now')",
+        )
+        .bind(provider_id)
+        .bind(name)
+        .bind(&ciphertext)
+        .bind(&nonce)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to store secret: {}", e)))?;
+        Ok(())
+    }
+
+    async fn get_secret(&self, provider_id: i64, name: &str) -> DomainResult<Option<String>> {
+        let row = sqlx::query_as::<_, (Vec<u8>, Vec<u8>)>(
+            "SELECT encrypted_value, value_nonce FROM provider_secrets
+             WHERE provider_id = ?1 AND name = ?2",
+        )
+        .bind(provider_id)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to get secret: {}", e)))?;
+
+        match row {
+            Some((ciphertext, nonce)) => Ok(Some(self.decrypt_token(&nonce, &ciphertext).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn delete_secret(&self, provider_id: i64, name: &str) -> DomainResult<()> {
+        let mut conn = self.writer().acquire().await?;
+        sqlx::query("DELETE FROM provider_secrets WHERE provider_id = ?1 AND name = ?2")
+            .bind(provider_id)
+            .bind(name)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to delete secret: {}", e)))?;
+        Ok(())
+    }
+
     async fn export_encrypted(&self, password: &str) -> DomainResult<Vec<u8>> {
-        let tokens = self.get_all_tokens().await?;
-        let json = serde_json::to_vec(&tokens).map_err(|e| {
+        let export = TokenExport {
+            tokens: self.get_all_tokens().await?,
+            secrets: self.all_secrets().await?,
+        };
+        let json = serde_json::to_vec(&export).map_err(|e| {
             DomainError::DatabaseError(format!("Failed to serialize tokens: {}", e))
         })?;
 
@@ -347,8 +487,7 @@ impl TokenStore for SqliteTokenStore {
         );
 
         tracing::debug!("[SqliteTokenStore] Parsing JSON tokens...");
-        let tokens: HashMap<i64, String> = serde_json::from_slice(&plaintext)
-            .map_err(|e| DomainError::DatabaseError(format!("Failed to parse tokens: {}", e)))?;
+        let TokenExport { tokens, secrets } = TokenExport::parse(&plaintext)?;
 
         tracing::info!(
             "[SqliteTokenStore] Parsed {} tokens from encrypted blob",
@@ -366,6 +505,12 @@ impl TokenStore for SqliteTokenStore {
             stored_count += 1;
         }
 
+        for (provider_id, values) in secrets {
+            for (name, value) in values {
+                self.store_secret(provider_id, &name, &value).await?;
+            }
+        }
+
         tracing::info!(
             "[SqliteTokenStore] Import completed: {} tokens stored",
             stored_count
@@ -374,10 +519,97 @@ impl TokenStore for SqliteTokenStore {
         Ok(())
     }
 
+    async fn rotate_password(
+        &self, current_password: &str, new_password: &str,
+        progress: &(dyn Fn(usize, usize) + Send + Sync),
+    ) -> DomainResult<usize> {
+        validate_new_vault_password(current_password, new_password)?;
+
+        // Held for the whole rotation so no token is written under the old key
+        // while rows are being re-encrypted.
+        let mut key = self.encryption_key.write().await;
+        if Self::derive_encryption_key(current_password) != *key {
+            return Err(DomainError::AuthenticationFailed(
+                "Current vault password is incorrect".into(),
+            ));
+        }
+        let new_key = Self::derive_encryption_key(new_password);
+
+        let rows = sqlx::query_as::<_, (i64, Vec<u8>, Vec<u8>)>(
+            "SELECT id, encrypted_token, token_nonce FROM providers
+             WHERE encrypted_token IS NOT NULL AND token_nonce IS NOT NULL",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to load tokens: {}", e)))?;
+
+        let secret_rows = sqlx::query_as::<_, (i64, String, Vec<u8>, Vec<u8>)>(
+            "SELECT provider_id, name, encrypted_value, value_nonce FROM provider_secrets",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to load secrets: {}", e)))?;
+
+        let total = rows.len();
+        let mut conn = self.writer().acquire().await?;
+        let mut tx = conn
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        for (index, (provider_id, ciphertext, nonce)) in rows.into_iter().enumerate() {
+            let token = Self::decrypt_with_key(&key, &nonce, &ciphertext)?;
+            let (nonce, ciphertext) = Self::encrypt_with_key(&new_key, &token)?;
+
+            sqlx::query(
+                "UPDATE providers SET encrypted_token = ?1, token_nonce = ?2, updated_at = datetime('now')
+                 WHERE id = ?3",
+            )
+            .bind(&ciphertext)
+            .bind(&nonce)
+            .bind(provider_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to store token: {}", e)))?;
+
+            progress(index + 1, total);
+        }
+
+        for (provider_id, name, ciphertext, nonce) in secret_rows {
+            let value = Self::decrypt_with_key(&key, &nonce, &ciphertext)?;
+            let (nonce, ciphertext) = Self::encrypt_with_key(&new_key, &value)?;
+
+            sqlx::query(
+                "UPDATE provider_secrets SET encrypted_value = ?1, value_nonce = ?2, updated_at = datetime('now')
+                 WHERE provider_id = ?3 AND name = ?4",
+            )
+            .bind(&ciphertext)
+            .bind(&nonce)
+            .bind(provider_id)
+            .bind(&name)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to store secret: {}", e)))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        *key = new_key;
+
+        tracing::info!(count = total, "SQLite vault password rotated");
+        Ok(total)
+    }
+
     async fn warmup(&self) -> DomainResult<()> {
         tracing::info!("SQLite token store warmup complete (instant)");
         Ok(())
     }
+
+    fn use_sqlite_writer(&self, writer: SqliteWriter) {
+        // Too late once this store has started its own writer.
+        let _ = self.writer.set(writer);
+    }
 }
 
 #[cfg(test)]
@@ -385,11 +617,22 @@ mod tests {
     use super::*;
 
     async fn create_test_pool() -> Pool<Sqlite> {
+        use std::sync::atomic::{
+            AtomicUsize,
+            Ordering,
+        };
+
         use sqlx::sqlite::SqlitePoolOptions;
 
+        // Writes hold on to a connection of their own, so the pool needs a
+        // second one onto the same in-memory database for reads.
+        static DATABASES: AtomicUsize = AtomicUsize::new(0);
         let pool = SqlitePoolOptions::new()
-            .max_connections(1)
-            .connect(":memory:")
+            .max_connections(2)
+            .connect(&format!(
+                "sqlite:file:token-store-{}?mode=memory&cache=shared",
+                DATABASES.fetch_add(1, Ordering::Relaxed)
+            ))
             .await
             .expect("Failed to create in-memory pool");
 
@@ -410,6 +653,20 @@ mod tests {
         .await
         .expect("Failed to create table");
 
+        sqlx::query(
+            "CREATE TABLE provider_secrets (
+                provider_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                encrypted_value BLOB NOT NULL,
+                value_nonce BLOB NOT NULL,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (provider_id, name)
+            )",
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create secrets table");
+
         sqlx::query(
             "INSERT INTO providers (name, provider_type) VALUES ('test-provider', 'github')",
         )
@@ -466,10 +723,15 @@ mod tests {
             .unwrap();
 
         store.store_token(1, "export-token").await.unwrap();
+        store
+            .store_secret(1, "webhook_secret", "export-secret")
+            .await
+            .unwrap();
 
         let encrypted = store.export_encrypted("backup-password").await.unwrap();
 
         store.delete_token(1).await.unwrap();
+        store.delete_secret(1, "webhook_secret").await.unwrap();
 
         store
             .import_encrypted(&encrypted, "backup-password")
@@ -478,6 +740,71 @@ mod tests {
 
         let token = store.get_token(1).await.unwrap();
         assert_eq!(token, "export-token");
+        assert_eq!(
+            store.get_secret(1, "webhook_secret").await.unwrap(),
+            Some("export-secret".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_secrets() {
+        let pool = create_test_pool().await;
+        let store = SqliteTokenStore::new(pool.clone(), Some("test-password".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(store.get_secret(1, "webhook_secret").await.unwrap(), None);
+
+        store
+            .store_secret(1, "webhook_secret", "first")
+            .await
+            .unwrap();
+        store
+            .store_secret(1, "webhook_secret", "second")
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_secret(1, "webhook_secret").await.unwrap(),
+            Some("second".to_string())
+        );
+
+        let stored: Vec<u8> = sqlx::query_scalar(
+            "SELECT encrypted_value FROM provider_secrets WHERE provider_id = 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_ne!(stored, b"second");
+
+        store.delete_secret(1, "webhook_secret").await.unwrap();
+        assert_eq!(store.get_secret(1, "webhook_secret").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_writes_queue_on_shared_writer() {
+        let pool = create_test_pool().await;
+        let store = SqliteTokenStore::new(pool.clone(), Some("test-password".to_string()))
+            .await
+            .unwrap();
+        let writer = SqliteWriter::spawn(pool);
+        store.use_sqlite_writer(writer.clone());
+
+        let lease = writer.acquire().await.unwrap();
+        let write = store.store_secret(1, "webhook_secret", "s3cret");
+        tokio::pin!(write);
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(100), &mut write)
+                .await
+                .is_err(),
+            "write didn't wait for the shared writer"
+        );
+
+        drop(lease);
+        write.await.unwrap();
+        assert_eq!(
+            store.get_secret(1, "webhook_secret").await.unwrap(),
+            Some("s3cret".to_string())
+        );
     }
 
     #[tokio::test]
@@ -493,4 +820,46 @@ mod tests {
         let result = store.import_encrypted(&encrypted, "wrong-password").await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_sqlite_rotate_password() {
+        let pool = create_test_pool().await;
+        let store = SqliteTokenStore::new(pool.clone(), Some("old-password".to_string()))
+            .await
+            .unwrap();
+        store.store_token(1, "rotated-token").await.unwrap();
+        store
+            .store_secret(1, "webhook_secret", "rotated-secret")
+            .await
+            .unwrap();
+
+        assert!(store
+            .rotate_password("wrong-password", "new-password", &|_, _| {})
+            .await
+            .is_err());
+
+        let progress = std::sync::Mutex::new(Vec::new());
+        let rotated = store
+            .rotate_password("old-password", "new-password", &|done, total| {
+                progress.lock().unwrap().push((done, total))
+            })
+            .await
+            .unwrap();
+        assert_eq!(rotated, 1);
+        assert_eq!(*progress.lock().unwrap(), vec![(1, 1)]);
+
+        assert!(
+            SqliteTokenStore::new(pool.clone(), Some("old-password".to_string()))
+                .await
+                .is_err()
+        );
+        let reopened = SqliteTokenStore::new(pool, Some("new-password".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(reopened.get_token(1).await.unwrap(), "rotated-token");
+        assert_eq!(
+            reopened.get_secret(1, "webhook_secret").await.unwrap(),
+            Some("rotated-secret".to_string())
+        );
+    }
 }
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{
+    AsyncSmtpTransport,
+    AsyncTransport,
+    Message,
+    Tokio1Executor,
+};
+
+use super::config::{
+    SmtpConfig,
+    SmtpSecurity,
+};
+use crate::domain::{
+    DomainError,
+    DomainResult,
+    NotificationMessage,
+    DEFAULT_EMAIL_BODY,
+    DEFAULT_EMAIL_SUBJECT,
+};
+
+const SMTP_TIMEOUT: Duration = Duration::from_secs(15);
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    subject_template: String,
+    body_template: String,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &SmtpConfig) -> DomainResult<Self> {
+        let host = config.host.trim();
+        let builder = match config.security {
+            SmtpSecurity::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host),
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host),
+            SmtpSecurity::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                host,
+            )),
+        }
+        .map_err(|e| DomainError::InvalidConfig(format!("Invalid SMTP host '{}': {}", host, e)))?;
+
+        let mut builder = builder.port(config.port).timeout(Some(SMTP_TIMEOUT));
+        if let Some(username) = &config.username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                config.password.clone().unwrap_or_default(),
+            ));
+        }
+
+        let from = config.from.parse::<Mailbox>().map_err(|e| {
+            DomainError::InvalidConfig(format!("Invalid SMTP sender '{}': {}", config.from, e))
+        })?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+            subject_template: config
+                .subject_template
+                .clone()
+                .unwrap_or_else(|| DEFAULT_EMAIL_SUBJECT.to_string()),
+            body_template: config
+                .body_template
+                .clone()
+                .unwrap_or_else(|| DEFAULT_EMAIL_BODY.to_string()),
+        })
+    }
+
+    pub async fn send(
+        &self, recipients: &[&str], notification: &NotificationMessage,
+    ) -> DomainResult<()> {
+        // Header values cannot span lines, so a multi-line template is
+        // flattened.
+        let subject = notification
+            .render(&self.subject_template)
+            .lines()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut message = Message::builder()
+            .from(self.from.clone())
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN);
+        for recipient in recipients {
+            let mailbox = recipient.parse::<Mailbox>().map_err(|e| {
+                DomainError::InvalidConfig(format!("Invalid recipient '{}': {}", recipient, e))
+            })?;
+            message = message.to(mailbox);
+        }
+
+        let message = message
+            .body(notification.render(&self.body_template))
+            .map_err(|e| DomainError::InternalError(format!("Failed to build email: {}", e)))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| DomainError::NetworkError(format!("SMTP delivery failed: {}", e)))?;
+        Ok(())
+    }
+}
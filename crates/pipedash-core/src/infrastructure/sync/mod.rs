@@ -16,14 +16,20 @@ use tracing::{
 };
 
 use crate::domain::{
+    merge_pins,
     DomainError,
     DomainResult,
+    PinnedPipeline,
 };
 use crate::infrastructure::storage::{
     ObjectMetadata,
     StorageBackend,
 };
 
+// Pins live in the config database, which is never synced as a file, so they
+// travel as a JSON snapshot that is merged instead of overwritten.
+pub const PINNED_PIPELINES_KEY: &str = "pinned_pipelines.json";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SyncDirection {
     Push,
@@ -431,6 +437,38 @@ impl SyncManager {
         self.sync(Some(config)).await
     }
 
+    // Merges the local pins with the remote snapshot and writes the result
+    // back to both sides. Without a remote the local pins are returned as-is.
+    pub async fn sync_pinned_pipelines(
+        &self, local: Vec<PinnedPipeline>,
+    ) -> DomainResult<Vec<PinnedPipeline>> {
+        let Some(remote) = &self.remote else {
+            return Ok(local);
+        };
+
+        let remote_pins: Vec<PinnedPipeline> = if remote.exists(PINNED_PIPELINES_KEY).await? {
+            let data = remote.get(PINNED_PIPELINES_KEY).await?;
+            serde_json::from_slice(&data).map_err(|e| {
+                DomainError::DataConsistency(format!("Invalid pinned pipelines snapshot: {}", e))
+            })?
+        } else {
+            Vec::new()
+        };
+
+        let merged = merge_pins(local, remote_pins);
+        let data =
+            serde_json::to_vec(&merged).map_err(|e| DomainError::InternalError(e.to_string()))?;
+        remote
+            .put(PINNED_PIPELINES_KEY, &data, Some("application/json"))
+            .await?;
+        self.local
+            .put(PINNED_PIPELINES_KEY, &data, Some("application/json"))
+            .await?;
+
+        debug!("Synced {} pinned pipelines", merged.len());
+        Ok(merged)
+    }
+
     pub async fn last_sync_time(&self) -> Option<DateTime<Utc>> {
         self.state.read().await.last_sync
     }
@@ -513,4 +551,33 @@ mod tests {
         let remote_data = remote.get("test.txt").await.unwrap();
         assert_eq!(remote_data, b"hello world");
     }
+
+    #[tokio::test]
+    async fn test_sync_pinned_pipelines() {
+        let local_dir = TempDir::new().unwrap();
+        let remote_dir = TempDir::new().unwrap();
+        let local = Arc::new(LocalStorage::new(local_dir.path().to_path_buf()));
+        let remote: Arc<dyn StorageBackend> =
+            Arc::new(LocalStorage::new(remote_dir.path().to_path_buf()));
+        let manager = SyncManager::with_remote(local, remote);
+
+        let pin = |id: &str, unpinned: bool, at: i64| PinnedPipeline {
+            pipeline_id: id.to_string(),
+            position: 0,
+            unpinned,
+            updated_at: DateTime::from_timestamp(at, 0).unwrap(),
+        };
+
+        let first = manager
+            .sync_pinned_pipelines(vec![pin("deploy", false, 10)])
+            .await
+            .unwrap();
+        assert_eq!(first, vec![pin("deploy", false, 10)]);
+
+        let second = manager
+            .sync_pinned_pipelines(vec![pin("deploy", true, 20), pin("lint", false, 5)])
+            .await
+            .unwrap();
+        assert_eq!(second, vec![pin("deploy", true, 20), pin("lint", false, 5)]);
+    }
 }
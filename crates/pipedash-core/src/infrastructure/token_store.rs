@@ -2,11 +2,16 @@ use std::collections::HashMap;
 use std::sync::RwLock;
 
 use async_trait::async_trait;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
 use crate::domain::{
     DomainError,
     DomainResult,
 };
+use crate::infrastructure::database::SqliteWriter;
 
 #[async_trait]
 pub trait TokenStore: Send + Sync {
@@ -18,12 +23,41 @@ pub trait TokenStore: Send + Sync {
 
     async fn get_all_tokens(&self) -> DomainResult<HashMap<i64, String>>;
 
+    // Tokens for several providers at once. Providers without a token are left
+    // out instead of failing the whole batch. Stores that pay per lookup (a
+    // keychain prompt, a round trip) override this to fetch them together.
+    async fn get_tokens_batch(&self, provider_ids: &[i64]) -> DomainResult<HashMap<i64, String>> {
+        let mut tokens = HashMap::with_capacity(provider_ids.len());
+        for &provider_id in provider_ids {
+            if let Ok(token) = self.get_token(provider_id).await {
+                tokens.insert(provider_id, token);
+            }
+        }
+        Ok(tokens)
+    }
+
     async fn get_token_by_name(&self, _name: &str) -> DomainResult<String> {
         Err(DomainError::InternalError(
             "This token store does not support name-based lookups".into(),
         ))
     }
 
+    // Credentials from a provider's config besides its token, such as a
+    // webhook secret, kept under `name`.
+    async fn store_secret(&self, _provider_id: i64, _name: &str, _value: &str) -> DomainResult<()> {
+        Err(DomainError::NotSupported(
+            "This token store does not support provider secrets".into(),
+        ))
+    }
+
+    async fn get_secret(&self, _provider_id: i64, _name: &str) -> DomainResult<Option<String>> {
+        Ok(None)
+    }
+
+    async fn delete_secret(&self, _provider_id: i64, _name: &str) -> DomainResult<()> {
+        Ok(())
+    }
+
     async fn export_encrypted(&self, _password: &str) -> DomainResult<Vec<u8>> {
         Err(DomainError::InternalError(
             "This token store does not support export".into(),
@@ -36,25 +70,76 @@ pub trait TokenStore: Send + Sync {
         ))
     }
 
+    // Re-encrypts every stored token under `new_password` and makes it the only
+    // key that unlocks the store. `progress` receives (done, total).
+    async fn rotate_password(
+        &self, _current_password: &str, _new_password: &str,
+        _progress: &(dyn Fn(usize, usize) + Send + Sync),
+    ) -> DomainResult<usize> {
+        Err(DomainError::NotSupported(
+            "This token store does not support password rotation".into(),
+        ))
+    }
+
     async fn warmup(&self) -> DomainResult<()> {
         Ok(())
     }
+
+    // The config backend's writer, for a store that writes to the same SQLite
+    // database, so there is still one writer per database.
+    fn use_sqlite_writer(&self, _writer: SqliteWriter) {}
+}
+
+// The JSON sealed by `export_encrypted`. Exports from before provider secrets
+// were kept in the token store are a bare token map.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct TokenExport {
+    pub tokens: HashMap<i64, String>,
+    #[serde(default)]
+    pub secrets: HashMap<i64, HashMap<String, String>>,
+}
+
+impl TokenExport {
+    pub(crate) fn parse(json: &[u8]) -> DomainResult<Self> {
+        serde_json::from_slice(json)
+            .or_else(|_| {
+                serde_json::from_slice(json).map(|tokens| Self {
+                    tokens,
+                    secrets: HashMap::new(),
+                })
+            })
+            .map_err(|e| DomainError::DatabaseError(format!("Failed to parse tokens: {}", e)))
+    }
+}
+
+pub(crate) fn validate_new_vault_password(current: &str, new: &str) -> DomainResult<()> {
+    if new.trim().is_empty() {
+        return Err(DomainError::InvalidConfig(
+            "New vault password cannot be empty".into(),
+        ));
+    }
+    if new == current {
+        return Err(DomainError::InvalidConfig(
+            "New vault password must differ from the current one".into(),
+        ));
+    }
+    Ok(())
 }
 
 pub struct MemoryTokenStore {
     tokens: RwLock<HashMap<i64, String>>,
+    secrets: RwLock<HashMap<(i64, String), String>>,
 }
 
 impl MemoryTokenStore {
     pub fn new() -> Self {
-        Self {
-            tokens: RwLock::new(HashMap::new()),
-        }
+        Self::with_tokens(HashMap::new())
     }
 
     pub fn with_tokens(tokens: HashMap<i64, String>) -> Self {
         Self {
             tokens: RwLock::new(tokens),
+            secrets: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -102,11 +187,38 @@ impl TokenStore for MemoryTokenStore {
             .map_err(|e| DomainError::InternalError(format!("Lock poisoned: {}", e)))?;
         Ok(tokens.clone())
     }
+
+    async fn store_secret(&self, provider_id: i64, name: &str, value: &str) -> DomainResult<()> {
+        let mut secrets = self
+            .secrets
+            .write()
+            .map_err(|e| DomainError::InternalError(format!("Lock poisoned: {}", e)))?;
+        secrets.insert((provider_id, name.to_string()), value.to_string());
+        Ok(())
+    }
+
+    async fn get_secret(&self, provider_id: i64, name: &str) -> DomainResult<Option<String>> {
+        let secrets = self
+            .secrets
+            .read()
+            .map_err(|e| DomainError::InternalError(format!("Lock poisoned: {}", e)))?;
+        Ok(secrets.get(&(provider_id, name.to_string())).cloned())
+    }
+
+    async fn delete_secret(&self, provider_id: i64, name: &str) -> DomainResult<()> {
+        let mut secrets = self
+            .secrets
+            .write()
+            .map_err(|e| DomainError::InternalError(format!("Lock poisoned: {}", e)))?;
+        secrets.remove(&(provider_id, name.to_string()));
+        Ok(())
+    }
 }
 
 pub struct EnvTokenStore {
     prefix: String,
     cache: RwLock<HashMap<i64, String>>,
+    secrets: RwLock<HashMap<(i64, String), String>>,
 }
 
 impl EnvTokenStore {
@@ -118,12 +230,22 @@ impl EnvTokenStore {
         Self {
             prefix: prefix.to_string(),
             cache: RwLock::new(HashMap::new()),
+            secrets: RwLock::new(HashMap::new()),
         }
     }
 
     fn env_var_name(&self, provider_id: i64) -> String {
         format!("{}{}", self.prefix, provider_id)
     }
+
+    // `PIPEDASH_TOKEN_3_WEBHOOK_SECRET` for provider 3's `webhook_secret`.
+    fn secret_var_name(&self, provider_id: i64, name: &str) -> String {
+        format!(
+            "{}_{}",
+            self.env_var_name(provider_id),
+            name.to_ascii_uppercase()
+        )
+    }
 }
 
 impl Default for EnvTokenStore {
@@ -179,6 +301,38 @@ impl TokenStore for EnvTokenStore {
             .map_err(|e| DomainError::InternalError(format!("Lock poisoned: {}", e)))?;
         Ok(cache.clone())
     }
+
+    async fn store_secret(&self, provider_id: i64, name: &str, value: &str) -> DomainResult<()> {
+        let mut secrets = self
+            .secrets
+            .write()
+            .map_err(|e| DomainError::InternalError(format!("Lock poisoned: {}", e)))?;
+        secrets.insert((provider_id, name.to_string()), value.to_string());
+        Ok(())
+    }
+
+    async fn get_secret(&self, provider_id: i64, name: &str) -> DomainResult<Option<String>> {
+        {
+            let secrets = self
+                .secrets
+                .read()
+                .map_err(|e| DomainError::InternalError(format!("Lock poisoned: {}", e)))?;
+            if let Some(value) = secrets.get(&(provider_id, name.to_string())) {
+                return Ok(Some(value.clone()));
+            }
+        }
+
+        Ok(std::env::var(self.secret_var_name(provider_id, name)).ok())
+    }
+
+    async fn delete_secret(&self, provider_id: i64, name: &str) -> DomainResult<()> {
+        let mut secrets = self
+            .secrets
+            .write()
+            .map_err(|e| DomainError::InternalError(format!("Lock poisoned: {}", e)))?;
+        secrets.remove(&(provider_id, name.to_string()));
+        Ok(())
+    }
 }
 
 #[cfg(test)]
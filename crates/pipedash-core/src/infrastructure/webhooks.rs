@@ -0,0 +1,144 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::domain::{
+    DomainError,
+    DomainResult,
+};
+use crate::infrastructure::crypto::{
+    constant_time_eq,
+    verify_hmac_sha256,
+};
+
+pub const WEBHOOK_SECRET_KEY: &str = "webhook_secret";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub provider_id: i64,
+    pub event_type: String,
+    pub pipeline_id: Option<String>,
+}
+
+fn decode_hex(input: &str) -> Option<Vec<u8>> {
+    if !input.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..input.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(input.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+pub fn verify_github_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(expected) = signature.strip_prefix("sha256=").and_then(decode_hex) else {
+        return false;
+    };
+
+    verify_hmac_sha256(secret.as_bytes(), body, &expected)
+}
+
+pub fn verify_gitlab_token(secret: &str, token: &str) -> bool {
+    constant_time_eq(secret.as_bytes(), token.as_bytes())
+}
+
+pub fn parse_github_event(
+    provider_id: i64, event_type: &str, body: &[u8],
+) -> DomainResult<WebhookEvent> {
+    let payload: serde_json::Value = serde_json::from_slice(body)
+        .map_err(|e| DomainError::InvalidConfig(format!("Invalid webhook payload: {}", e)))?;
+
+    let pipeline_id = match event_type {
+        "workflow_run" | "workflow_job" => {
+            let owner = payload["repository"]["owner"]["login"].as_str();
+            let repo = payload["repository"]["name"].as_str();
+            let workflow_id = payload["workflow_run"]["workflow_id"]
+                .as_u64()
+                .or_else(|| payload["workflow_job"]["workflow_id"].as_u64());
+
+            match (owner, repo, workflow_id) {
+                (Some(owner), Some(repo), Some(workflow_id)) => Some(format!(
+                    "github__{}__{}__{}__{}",
+                    provider_id, owner, repo, workflow_id
+                )),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    Ok(WebhookEvent {
+        provider_id,
+        event_type: event_type.to_string(),
+        pipeline_id,
+    })
+}
+
+pub fn parse_gitlab_event(
+    provider_id: i64, event_type: &str, body: &[u8],
+) -> DomainResult<WebhookEvent> {
+    let payload: serde_json::Value = serde_json::from_slice(body)
+        .map_err(|e| DomainError::InvalidConfig(format!("Invalid webhook payload: {}", e)))?;
+
+    let pipeline_id = match payload["object_kind"].as_str() {
+        Some("pipeline") | Some("build") => payload["project"]["id"]
+            .as_i64()
+            .or_else(|| payload["project_id"].as_i64())
+            .map(|project_id| format!("gitlab__{}__{}", provider_id, project_id)),
+        _ => None,
+    };
+
+    Ok(WebhookEvent {
+        provider_id,
+        event_type: event_type.to_string(),
+        pipeline_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::crypto::hmac_sha256;
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_verify_github_signature() {
+        let body = br#"{"action":"completed"}"#;
+        let signature = format!("sha256={}", to_hex(&hmac_sha256(b"secret", body)));
+
+        assert!(verify_github_signature("secret", body, &signature));
+        assert!(!verify_github_signature("wrong", body, &signature));
+        assert!(!verify_github_signature("secret", body, "sha256=zz"));
+        assert!(!verify_github_signature("secret", body, "sha1=abcd"));
+    }
+
+    #[test]
+    fn test_parse_github_workflow_run() {
+        let body = br#"{
+            "workflow_run": {"workflow_id": 42},
+            "repository": {"name": "repo", "owner": {"login": "octo"}}
+        }"#;
+
+        let event = parse_github_event(7, "workflow_run", body).unwrap();
+        assert_eq!(
+            event.pipeline_id.as_deref(),
+            Some("github__7__octo__repo__42")
+        );
+
+        let event = parse_github_event(7, "push", body).unwrap();
+        assert!(event.pipeline_id.is_none());
+    }
+
+    #[test]
+    fn test_parse_gitlab_pipeline() {
+        let body = br#"{"object_kind": "pipeline", "project": {"id": 99}}"#;
+        let event = parse_gitlab_event(3, "Pipeline Hook", body).unwrap();
+        assert_eq!(event.pipeline_id.as_deref(), Some("gitlab__3__99"));
+        assert!(verify_gitlab_token("token", "token"));
+        assert!(!verify_gitlab_token("token", "other"));
+    }
+}
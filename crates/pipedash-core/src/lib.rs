@@ -6,6 +6,7 @@ pub mod logging;
 pub mod plugins;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 pub use domain::{
     DomainError,
@@ -26,6 +27,7 @@ pub use event::{
 };
 use infrastructure::database::{
     init_database,
+    CacheCipher,
     MetricsRepository,
     Repository,
 };
@@ -42,13 +44,77 @@ pub use infrastructure::{
     TokenStore,
 };
 
+// How often config.toml is checked for edits made outside the app.
+const CONFIG_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+// Top-level config.toml sections whose edits are provisioned to the database.
+const PROVISIONED_SECTIONS: [&str; 5] = [
+    "providers.",
+    "groups.",
+    "schedules.",
+    "notifications.channels",
+    "provisioning.",
+];
+
+// Cached run data is only encrypted when `storage.encrypt_cache` is set, with
+// a key derived from the vault password. While the vault is locked the cache
+// stays as it is; unlocking rebuilds the context with the password available,
+// which installs the key and seals what was written in the meantime.
+async fn install_cache_cipher(
+    repository: &Repository, storage: &infrastructure::StorageConfig,
+) -> anyhow::Result<()> {
+    if !storage.encrypt_cache || repository.cache_encrypted() {
+        return Ok(());
+    }
+    let Some(password) = storage
+        .vault_password
+        .clone()
+        .or_else(|| std::env::var("PIPEDASH_VAULT_PASSWORD").ok())
+    else {
+        tracing::warn!("Vault is locked - cached run data is not encrypted until it is unlocked");
+        return Ok(());
+    };
+    repository.set_cache_cipher(CacheCipher::derive(&password).await?);
+    seal_cache(repository).await;
+    Ok(())
+}
+
+// Encrypts cache rows written before encryption was turned on.
+async fn seal_cache(repository: &Repository) {
+    if !repository.cache_encrypted() {
+        return;
+    }
+    match repository.seal_cached_payloads(None).await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!(count, "Encrypted cached run data"),
+        Err(e) => tracing::warn!(error = %e, "Failed to encrypt cached run data"),
+    }
+}
+
+// Applies the `[redaction]` config on top of the built-in patterns. A bad
+// pattern keeps the previous redactor rather than logging secrets.
+fn apply_redaction(redaction: &domain::RedactionConfig) {
+    match domain::Redactor::new(redaction) {
+        Ok(redactor) => domain::set_redactor(redactor),
+        Err(e) => tracing::warn!(error = %e, "Ignoring redaction config"),
+    }
+}
+
 pub struct CoreContext {
     pub event_bus: Arc<dyn EventBus>,
 
+    pub config_state: Arc<infrastructure::ConfigState>,
+    config_watch: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
+    // Applies of config.toml run one at a time, so a reload can't create the
+    // same provider twice.
+    provisioning_lock: Arc<tokio::sync::Mutex<()>>,
+
     pub token_store: Arc<dyn TokenStore>,
 
     pub http_client_manager: Arc<infrastructure::HttpClientManager>,
 
+    pub access_control: Arc<application::AccessControl>,
+
     pub provider_service: Arc<application::ProviderService>,
 
     pub pipeline_service: Arc<application::PipelineService>,
@@ -56,6 +122,38 @@ pub struct CoreContext {
     pub metrics_service: Option<Arc<application::MetricsService>>,
 
     pub refresh_manager: Arc<application::RefreshManager>,
+
+    pub log_tailer: Arc<application::LogTailer>,
+
+    pub pipeline_previewer: Arc<application::PipelinePreviewer>,
+
+    pub discovery_service: Arc<application::DiscoveryService>,
+
+    pub config_bundle_service: Arc<application::ConfigBundleService>,
+
+    pub provisioning_service: Arc<application::ProvisioningService>,
+
+    pub retention_service: Arc<application::RunRetentionService>,
+
+    pub backfill_service: Arc<application::BackfillService>,
+
+    pub agent_service: Arc<application::AgentService>,
+
+    pub queue_service: Arc<application::QueueService>,
+
+    pub orchestrator_service: Arc<application::OrchestratorService>,
+
+    pub notification_service: Arc<application::NotificationService>,
+    pub notification_center: Arc<application::NotificationCenter>,
+
+    pub audit_log: Arc<application::AuditLog>,
+
+    pub backup_service: Arc<application::BackupService>,
+    backup_schedule: Option<std::time::Duration>,
+
+    // Only set on Postgres, where several instances can share one cache.
+    pub cache_invalidation_listener:
+        Option<Arc<infrastructure::database::CacheInvalidationListener>>,
 }
 
 impl CoreContext {
@@ -64,12 +162,23 @@ impl CoreContext {
     ) -> anyhow::Result<Self> {
         std::fs::create_dir_all(data_dir)?;
 
+        plugins::load_dynamic_plugins(data_dir);
+
         let config_state =
             infrastructure::ConfigState::initialize(data_dir, token_store.clone()).await?;
+        plugins::set_demo_enabled(
+            config_state
+                .get_bool(infrastructure::ConfigKey::DemoMode)
+                .await,
+        );
 
         let config = config_state.get().await;
+        apply_redaction(&config.redaction);
         let db_path = config.db_path();
         let metrics_enabled = config.general.metrics_enabled;
+        let default_role = config.access.default_role;
+        let allow_self_approval = config.access.allow_self_approval;
+        let oauth_config = config.oauth.clone();
 
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent)?;
@@ -96,9 +205,20 @@ impl CoreContext {
             );
         }
 
+        let repository = Arc::new(Repository::new(
+            config_backend,
+            infrastructure::database::DatabasePool::Sqlite(cache_pool),
+            token_store.clone(),
+        ));
+        install_cache_cipher(&repository, &config.storage).await?;
+
         let metrics_service = if metrics_enabled {
             let metrics_repository = Arc::new(MetricsRepository::new(pool.clone()));
-            let service = Arc::new(application::MetricsService::new(metrics_repository));
+            let service = Arc::new(application::MetricsService::new(
+                metrics_repository,
+                Arc::clone(&repository),
+                config.costs.clone(),
+            ));
 
             match service.check_and_repair_corruption().await {
                 Ok(repaired) => {
@@ -125,39 +245,139 @@ impl CoreContext {
             None
         };
 
-        let repository = Arc::new(Repository::new(
-            config_backend,
-            infrastructure::database::DatabasePool::Sqlite(cache_pool),
-            token_store.clone(),
-        ));
-
         let http_client_manager = Arc::new(infrastructure::HttpClientManager::new()?);
-
-        let provider_service = Arc::new(application::ProviderService::new(
+        let access_control = Arc::new(
+            application::AccessControl::new(default_role).with_self_approval(allow_self_approval),
+        );
+        let notification_center = Arc::new(application::NotificationCenter::new(
             Arc::clone(&repository),
-            Arc::clone(&http_client_manager),
             Arc::clone(&event_bus),
         ));
+        let audit_log = Arc::new(application::AuditLog::new(
+            Arc::clone(&repository),
+            Arc::clone(&access_control),
+        ));
+
+        let provider_service = Arc::new(
+            application::ProviderService::new(
+                Arc::clone(&repository),
+                Arc::clone(&http_client_manager),
+                Arc::clone(&event_bus),
+                Arc::clone(&access_control),
+                oauth_config,
+                Arc::clone(&notification_center),
+            )
+            .with_resilience(config.resilience.clone()),
+        );
         let pipeline_service = Arc::new(application::PipelineService::new(
             Arc::clone(&repository),
             Arc::clone(&provider_service),
             metrics_service.clone(),
             Arc::clone(&event_bus),
+            Arc::clone(&notification_center),
+            Arc::clone(&audit_log),
+        ));
+        let retention_service = Arc::new(application::RunRetentionService::new(Arc::clone(
+            &repository,
+        )));
+        let backfill_service = Arc::new(application::BackfillService::new(
+            Arc::clone(&repository),
+            Arc::clone(&provider_service),
+            Arc::clone(&pipeline_service),
+            metrics_service.clone(),
+            Arc::clone(&event_bus),
+        ));
+        let agent_service = Arc::new(application::AgentService::new(
+            Arc::clone(&repository),
+            Arc::clone(&provider_service),
+            Arc::clone(&event_bus),
+        ));
+        let queue_service = Arc::new(application::QueueService::new(
+            Arc::clone(&provider_service),
+            metrics_service.clone(),
+        ));
+        let orchestrator_service = Arc::new(application::OrchestratorService::new(
+            Arc::clone(&repository),
+            Arc::clone(&pipeline_service),
+            Arc::clone(&event_bus),
+        ));
+        let notification_service = Arc::new(application::NotificationService::new(
+            Arc::clone(&repository),
+            Arc::clone(&http_client_manager),
+            Arc::clone(&access_control),
+            &config.notifications,
+            config.server.public_url.clone(),
         ));
         let refresh_manager = Arc::new(application::RefreshManager::new(
             Arc::clone(&pipeline_service),
             metrics_service.clone(),
+            Arc::clone(&retention_service),
+            Arc::clone(&agent_service),
+            Arc::clone(&queue_service),
+            Arc::clone(&orchestrator_service),
+            Arc::clone(&notification_service),
+            Arc::clone(&notification_center),
             Arc::clone(&event_bus),
+            None,
         ));
+        let log_tailer = Arc::new(application::LogTailer::new(
+            Arc::clone(&pipeline_service),
+            Arc::clone(&event_bus),
+        ));
+        let pipeline_previewer = Arc::new(application::PipelinePreviewer::new(
+            Arc::clone(&provider_service),
+            Arc::clone(&http_client_manager),
+            Arc::clone(&event_bus),
+        ));
+        let discovery_service = Arc::new(application::DiscoveryService::new(
+            Arc::clone(&provider_service),
+            Arc::clone(&refresh_manager),
+            Arc::clone(&event_bus),
+        ));
+        let config_bundle_service = Arc::new(application::ConfigBundleService::new(
+            Arc::clone(&provider_service),
+            metrics_service.clone(),
+        ));
+        let provisioning_service = Arc::new(application::ProvisioningService::new(
+            Arc::clone(&repository),
+            token_store.clone(),
+        ));
+        let (backup_service, backup_schedule) = Self::build_backup_service(
+            &config,
+            &repository,
+            &provider_service,
+            &access_control,
+            &event_bus,
+        );
 
         Ok(Self {
             event_bus,
+            config_state,
+            config_watch: std::sync::Mutex::new(None),
+            provisioning_lock: Arc::new(tokio::sync::Mutex::new(())),
             token_store,
             http_client_manager,
+            access_control,
             provider_service,
             pipeline_service,
             metrics_service,
             refresh_manager,
+            log_tailer,
+            pipeline_previewer,
+            discovery_service,
+            config_bundle_service,
+            provisioning_service,
+            retention_service,
+            backfill_service,
+            agent_service,
+            queue_service,
+            orchestrator_service,
+            notification_service,
+            notification_center,
+            audit_log,
+            backup_service,
+            backup_schedule,
+            cache_invalidation_listener: None,
         })
     }
 
@@ -167,10 +387,17 @@ impl CoreContext {
         let token_store = storage_manager.token_store().await;
         let config_backend = storage_manager.config_backend();
         let data_dir = storage_manager.config().data_dir();
-        let _storage_config = storage_manager.config();
+        let storage_config = storage_manager.config();
+
+        plugins::load_dynamic_plugins(&data_dir);
 
         let config_state =
             infrastructure::ConfigState::initialize(&data_dir, token_store.clone()).await?;
+        plugins::set_demo_enabled(
+            config_state
+                .get_bool(infrastructure::ConfigKey::DemoMode)
+                .await,
+        );
 
         let sync_result = config_state
             .sync_providers_from_toml(config_backend.as_ref(), false)
@@ -194,15 +421,21 @@ impl CoreContext {
             cache_pool.clone(),
             token_store.clone(),
         ));
+        install_cache_cipher(&repository, &storage_config.storage).await?;
 
         let http_client_manager = Arc::new(infrastructure::HttpClientManager::new()?);
 
         let config = config_state.get().await;
+        apply_redaction(&config.redaction);
         let metrics_service = if config.general.metrics_enabled {
             let metrics_repository = Arc::new(
                 infrastructure::database::MetricsRepository::new_from_pool(cache_pool),
             );
-            let service = Arc::new(application::MetricsService::new(metrics_repository));
+            let service = Arc::new(application::MetricsService::new(
+                metrics_repository,
+                Arc::clone(&repository),
+                config.costs.clone(),
+            ));
 
             match service.check_and_repair_corruption().await {
                 Ok(repaired) => {
@@ -227,49 +460,390 @@ impl CoreContext {
             None
         };
 
-        let provider_service = Arc::new(application::ProviderService::new(
+        let access_control = Arc::new(
+            application::AccessControl::new(config.access.default_role)
+                .with_self_approval(config.access.allow_self_approval),
+        );
+        let notification_center = Arc::new(application::NotificationCenter::new(
             repository.clone(),
-            Arc::clone(&http_client_manager),
             Arc::clone(&event_bus),
         ));
+        let audit_log = Arc::new(application::AuditLog::new(
+            repository.clone(),
+            Arc::clone(&access_control),
+        ));
+        let provider_service = Arc::new(
+            application::ProviderService::new(
+                repository.clone(),
+                Arc::clone(&http_client_manager),
+                Arc::clone(&event_bus),
+                Arc::clone(&access_control),
+                config.oauth.clone(),
+                Arc::clone(&notification_center),
+            )
+            .with_resilience(config.resilience.clone()),
+        );
         let pipeline_service = Arc::new(application::PipelineService::new(
             repository.clone(),
             Arc::clone(&provider_service),
             metrics_service.clone(),
             Arc::clone(&event_bus),
+            Arc::clone(&notification_center),
+            Arc::clone(&audit_log),
         ));
+        let retention_service = Arc::new(application::RunRetentionService::new(repository.clone()));
+        let backfill_service = Arc::new(application::BackfillService::new(
+            Arc::clone(&repository),
+            Arc::clone(&provider_service),
+            Arc::clone(&pipeline_service),
+            metrics_service.clone(),
+            Arc::clone(&event_bus),
+        ));
+        let agent_service = Arc::new(application::AgentService::new(
+            Arc::clone(&repository),
+            Arc::clone(&provider_service),
+            Arc::clone(&event_bus),
+        ));
+        let queue_service = Arc::new(application::QueueService::new(
+            Arc::clone(&provider_service),
+            metrics_service.clone(),
+        ));
+        let orchestrator_service = Arc::new(application::OrchestratorService::new(
+            Arc::clone(&repository),
+            Arc::clone(&pipeline_service),
+            Arc::clone(&event_bus),
+        ));
+        let notification_service = Arc::new(application::NotificationService::new(
+            Arc::clone(&repository),
+            Arc::clone(&http_client_manager),
+            Arc::clone(&access_control),
+            &config.notifications,
+            config.server.public_url.clone(),
+        ));
+        let refresh_lock = repository
+            .cache_pool()
+            .as_postgres()
+            .filter(|_| config.storage.postgres.refresh_lock)
+            .map(|pool| Arc::new(infrastructure::database::RefreshLock::new(pool.clone())));
         let refresh_manager = Arc::new(application::RefreshManager::new(
             Arc::clone(&pipeline_service),
             metrics_service.clone(),
+            Arc::clone(&retention_service),
+            Arc::clone(&agent_service),
+            Arc::clone(&queue_service),
+            Arc::clone(&orchestrator_service),
+            Arc::clone(&notification_service),
+            Arc::clone(&notification_center),
             Arc::clone(&event_bus),
+            refresh_lock,
         ));
+        let log_tailer = Arc::new(application::LogTailer::new(
+            Arc::clone(&pipeline_service),
+            Arc::clone(&event_bus),
+        ));
+        let pipeline_previewer = Arc::new(application::PipelinePreviewer::new(
+            Arc::clone(&provider_service),
+            Arc::clone(&http_client_manager),
+            Arc::clone(&event_bus),
+        ));
+        let discovery_service = Arc::new(application::DiscoveryService::new(
+            Arc::clone(&provider_service),
+            Arc::clone(&refresh_manager),
+            Arc::clone(&event_bus),
+        ));
+        let config_bundle_service = Arc::new(application::ConfigBundleService::new(
+            Arc::clone(&provider_service),
+            metrics_service.clone(),
+        ));
+        let provisioning_service = Arc::new(application::ProvisioningService::new(
+            Arc::clone(&repository),
+            token_store.clone(),
+        ));
+        let (backup_service, backup_schedule) = Self::build_backup_service(
+            &config,
+            &repository,
+            &provider_service,
+            &access_control,
+            &event_bus,
+        );
+        let cache_invalidation_listener = repository.cache_pool().as_postgres().map(|pool| {
+            Arc::new(infrastructure::database::CacheInvalidationListener::new(
+                pool.clone(),
+                repository.instance_id().to_string(),
+                Arc::clone(&event_bus),
+            ))
+        });
 
         Ok(Self {
             event_bus,
+            config_state,
+            config_watch: std::sync::Mutex::new(None),
+            provisioning_lock: Arc::new(tokio::sync::Mutex::new(())),
             token_store,
             http_client_manager,
+            access_control,
             provider_service,
             pipeline_service,
             metrics_service,
             refresh_manager,
+            log_tailer,
+            pipeline_previewer,
+            discovery_service,
+            config_bundle_service,
+            provisioning_service,
+            retention_service,
+            backfill_service,
+            agent_service,
+            queue_service,
+            orchestrator_service,
+            notification_service,
+            notification_center,
+            audit_log,
+            backup_service,
+            backup_schedule,
+            cache_invalidation_listener,
         })
     }
 
+    fn build_backup_service(
+        config: &infrastructure::PipedashConfig, repository: &Arc<Repository>,
+        provider_service: &Arc<application::ProviderService>,
+        access_control: &Arc<application::AccessControl>, event_bus: &Arc<dyn EventBus>,
+    ) -> (Arc<application::BackupService>, Option<std::time::Duration>) {
+        let storage = Arc::new(LocalStorage::new(config.backup_dir())) as Arc<dyn StorageBackend>;
+        let service = Arc::new(application::BackupService::new(
+            Arc::clone(repository),
+            Arc::clone(provider_service),
+            storage,
+            Arc::clone(access_control),
+            Arc::clone(event_bus),
+            config.backup.retention,
+        ));
+        let schedule = config.backup.enabled.then(|| {
+            std::time::Duration::from_secs(u64::from(config.backup.interval_hours.max(1)) * 3600)
+        });
+
+        (service, schedule)
+    }
+
     pub async fn start_background_tasks(&self) {
+        if let Some(listener) = &self.cache_invalidation_listener {
+            listener.start().await;
+        }
+        if let Some(interval) = self.backup_schedule {
+            self.backup_service.start_schedule(interval).await;
+        }
+        if let Some(metrics_service) = &self.metrics_service {
+            metrics_service.start_compaction().await;
+        }
+
         let provider_service = Arc::clone(&self.provider_service);
+        let provisioning_service = Arc::clone(&self.provisioning_service);
+        let provisioning_lock = Arc::clone(&self.provisioning_lock);
         let refresh_manager = Arc::clone(&self.refresh_manager);
+        let backfill_service = Arc::clone(&self.backfill_service);
+        let discovery_service = Arc::clone(&self.discovery_service);
+        let config_state = Arc::clone(&self.config_state);
+        let event_bus = Arc::clone(&self.event_bus);
 
         tokio::spawn(async move {
             if let Err(e) = provider_service.load_all_providers().await {
                 tracing::warn!("Failed to load providers during startup: {}", e);
             }
+            Self::apply_provisioning(
+                &provisioning_lock,
+                &config_state,
+                &provisioning_service,
+                &provider_service,
+                &event_bus,
+            )
+            .await;
 
             refresh_manager.start().await;
+            provider_service.start_diagnostics().await;
+            discovery_service.start().await;
+            backfill_service.resume_interrupted().await;
+        });
+
+        self.start_config_watch();
+    }
+
+    // Reloads config.toml when it changes on disk. Edits to provisioned
+    // resources (providers, groups, schedules and notification channels) are
+    // applied to the database the same way as on startup, and the plugins
+    // rebuilt when providers changed. Every change is forwarded to the event
+    // bus.
+    fn start_config_watch(&self) {
+        let mut config_watch = self.config_watch.lock().unwrap();
+        if config_watch.is_some() {
+            return;
+        }
+
+        let mut changes = self.config_state.subscribe();
+        let config_state = Arc::clone(&self.config_state);
+        let provider_service = Arc::clone(&self.provider_service);
+        let provisioning_service = Arc::clone(&self.provisioning_service);
+        let provisioning_lock = Arc::clone(&self.provisioning_lock);
+        let event_bus = Arc::clone(&self.event_bus);
+
+        *config_watch = Some(tokio::spawn(async move {
+            loop {
+                let change = match changes.recv().await {
+                    Ok(change) => change,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+
+                if let infrastructure::ConfigChangeEvent::Reloaded { changed_keys, .. } = &change {
+                    if changed_keys.iter().any(|key| key.starts_with("redaction.")) {
+                        apply_redaction(&config_state.get().await.redaction);
+                    }
+                    if changed_keys.iter().any(|key| {
+                        PROVISIONED_SECTIONS
+                            .iter()
+                            .any(|section| key.starts_with(section))
+                    }) {
+                        Self::apply_provisioning(
+                            &provisioning_lock,
+                            &config_state,
+                            &provisioning_service,
+                            &provider_service,
+                            &event_bus,
+                        )
+                        .await;
+                    }
+                }
+
+                event_bus.emit(CoreEvent::ConfigChanged { change }).await;
+            }
+        }));
+        self.config_state.start_watching(CONFIG_WATCH_INTERVAL);
+    }
+
+    // Reloads config.toml and applies the resources it declares right away,
+    // for callers that need the outcome instead of waiting for the watcher.
+    pub async fn apply_config(&self) -> DomainResult<domain::ProvisioningPlan> {
+        let _guard = self.provisioning_lock.lock().await;
+        if let Some(infrastructure::ConfigChangeEvent::Rejected { errors }) =
+            self.config_state.check_for_changes().await
+        {
+            return Err(DomainError::InvalidConfig(errors.join(", ")));
+        }
+        Self::provision(
+            &self.config_state,
+            &self.provisioning_service,
+            &self.provider_service,
+            &self.event_bus,
+        )
+        .await
+    }
+
+    async fn apply_provisioning(
+        provisioning_lock: &tokio::sync::Mutex<()>, config_state: &infrastructure::ConfigState,
+        provisioning_service: &application::ProvisioningService,
+        provider_service: &application::ProviderService, event_bus: &Arc<dyn EventBus>,
+    ) {
+        let _guard = provisioning_lock.lock().await;
+        if let Err(e) = Self::provision(
+            config_state,
+            provisioning_service,
+            provider_service,
+            event_bus,
+        )
+        .await
+        {
+            tracing::error!(error = %e, "Failed to apply provisioning from config.toml");
+        }
+    }
+
+    async fn provision(
+        config_state: &infrastructure::ConfigState,
+        provisioning_service: &application::ProvisioningService,
+        provider_service: &application::ProviderService, event_bus: &Arc<dyn EventBus>,
+    ) -> DomainResult<domain::ProvisioningPlan> {
+        let config = config_state.get().await;
+        let plan = provisioning_service.apply(&config).await?;
+        for warning in &plan.warnings {
+            tracing::warn!("{}", warning);
+        }
+
+        let providers_changed = plan.changes.iter().any(|change| {
+            change.resource == domain::ProvisionedResource::Provider
+                && change.action != domain::PlanAction::Unmanaged
         });
+        if providers_changed {
+            if let Err(e) = provider_service.load_all_providers().await {
+                tracing::warn!(error = %e, "Failed to reload providers after provisioning");
+            }
+            event_bus.emit(CoreEvent::ProvidersChanged).await;
+        }
+        Ok(plan)
     }
 
     pub async fn shutdown(&self) {
+        self.config_state.stop_watching();
+        if let Some(handle) = self.config_watch.lock().unwrap().take() {
+            handle.abort();
+        }
         self.refresh_manager.stop().await;
+        self.provider_service.stop_diagnostics().await;
+        self.discovery_service.stop().await;
+        self.log_tailer.stop_all();
+        self.pipeline_previewer.stop_all();
+        self.backfill_service.stop_all();
+        self.backup_service.stop_schedule().await;
+        if let Some(metrics_service) = &self.metrics_service {
+            metrics_service.stop_compaction().await;
+        }
+        if let Some(listener) = &self.cache_invalidation_listener {
+            listener.stop().await;
+        }
+    }
+
+    // Re-encrypts all provider tokens under a new vault password. The session
+    // password is replaced so later unlocks and store re-creation use the new
+    // one; the old password stops decrypting anything once this returns.
+    pub async fn rotate_vault_password(
+        &self, current_password: &str, new_password: &str,
+    ) -> DomainResult<usize> {
+        self.access_control
+            .ensure(domain::Action::ManageProviders)?;
+
+        let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        let event_bus = Arc::clone(&self.event_bus);
+        let forwarder = tokio::spawn(async move {
+            while let Some((rotated, total)) = progress_rx.recv().await {
+                event_bus
+                    .emit(CoreEvent::VaultRotationProgress { rotated, total })
+                    .await;
+            }
+        });
+
+        let result = self
+            .token_store
+            .rotate_password(current_password, new_password, &move |rotated, total| {
+                let _ = progress_tx.send((rotated, total));
+            })
+            .await;
+        let _ = forwarder.await;
+        let rotated = result?;
+
+        std::env::set_var("PIPEDASH_VAULT_PASSWORD", new_password);
+        tracing::info!(count = rotated, "Vault password rotated");
+
+        // The tokens are already under the new password, so a cache that
+        // fails to follow is only logged; its rows get refetched.
+        match self
+            .provider_service
+            .repository()
+            .rotate_cache_key(new_password)
+            .await
+        {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(count, "Re-encrypted cached run data"),
+            Err(e) => tracing::warn!(error = %e, "Failed to re-encrypt cached run data"),
+        }
+        Ok(rotated)
     }
 
     pub async fn warmup_token_store(&self) -> anyhow::Result<()> {
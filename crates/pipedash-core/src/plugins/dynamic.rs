@@ -0,0 +1,228 @@
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::sync::RwLock;
+
+use libloading::Library;
+use pipedash_plugin_api::dynamic::{
+    PluginDeclaration,
+    PLUGIN_API_VERSION,
+    PLUGIN_DECLARATION_SYMBOL,
+    SDK_VERSION,
+};
+use pipedash_plugin_api::Plugin as PluginTrait;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::domain::{
+    DomainError,
+    DomainResult,
+};
+
+const PLUGINS_DIR_ENV: &str = "PIPEDASH_PLUGINS_DIR";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DynamicPluginInfo {
+    pub provider_type: String,
+    pub name: String,
+    pub version: String,
+    pub path: PathBuf,
+}
+
+struct DynamicPlugin {
+    info: DynamicPluginInfo,
+    create: fn() -> Box<dyn PluginTrait>,
+    // Libraries are never unloaded: plugin instances and the `create` function
+    // pointer borrow code from them for the lifetime of the process.
+    _library: Library,
+}
+
+static DYNAMIC_PLUGINS: RwLock<Vec<DynamicPlugin>> = RwLock::new(Vec::new());
+
+pub fn plugins_dir(data_dir: &Path) -> PathBuf {
+    std::env::var(PLUGINS_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| data_dir.join("plugins"))
+}
+
+pub fn load_plugins_from_dir(dir: &Path) -> Vec<DynamicPluginInfo> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => {
+            tracing::debug!(dir = %dir.display(), "No plugins directory found");
+            return Vec::new();
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path.extension().and_then(|ext| ext.to_str())
+                    == Some(std::env::consts::DLL_EXTENSION)
+        })
+        .collect();
+    paths.sort();
+
+    let mut loaded = Vec::new();
+    for path in paths {
+        match load_plugin(&path) {
+            Ok(Some(info)) => {
+                tracing::info!(
+                    provider_type = %info.provider_type,
+                    path = %path.display(),
+                    "Loaded dynamic plugin"
+                );
+                loaded.push(info);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to load dynamic plugin");
+            }
+        }
+    }
+
+    loaded
+}
+
+fn load_plugin(path: &Path) -> DomainResult<Option<DynamicPluginInfo>> {
+    {
+        let plugins = DYNAMIC_PLUGINS
+            .read()
+            .map_err(|e| DomainError::LockError(e.to_string()))?;
+        if plugins.iter().any(|p| p.info.path == path) {
+            return Ok(None);
+        }
+    }
+
+    // SAFETY: loading a library runs its initialisers; plugins in the plugins
+    // directory are trusted the same way as the application binary itself.
+    let library = unsafe { Library::new(path) }
+        .map_err(|e| DomainError::InternalError(format!("Failed to open library: {}", e)))?;
+
+    // SAFETY: the symbol is a `static PluginDeclaration` emitted by
+    // `pipedash_plugin_api::export_plugin!`.
+    let (api_version, sdk_version, create) = unsafe {
+        let symbol = library
+            .get::<*const PluginDeclaration>(PLUGIN_DECLARATION_SYMBOL)
+            .map_err(|e| {
+                DomainError::InvalidConfig(format!("Missing plugin declaration symbol: {}", e))
+            })?;
+        let declaration = &**symbol;
+        (
+            declaration.api_version,
+            declaration.sdk_version.to_string(),
+            declaration.create,
+        )
+    };
+
+    if api_version != PLUGIN_API_VERSION || sdk_version != SDK_VERSION {
+        return Err(DomainError::InvalidConfig(format!(
+            "Incompatible plugin: built for API v{} / SDK {}, host expects API v{} / SDK {}",
+            api_version, sdk_version, PLUGIN_API_VERSION, SDK_VERSION
+        )));
+    }
+
+    let plugin = create();
+    let metadata = plugin.metadata();
+    let provider_type = metadata.provider_type.clone();
+
+    if super::is_builtin_provider(&provider_type) {
+        return Err(DomainError::InvalidProviderType(format!(
+            "Plugin provider type '{}' conflicts with a built-in provider",
+            provider_type
+        )));
+    }
+
+    let info = DynamicPluginInfo {
+        provider_type: provider_type.clone(),
+        name: metadata.name.clone(),
+        version: metadata.version.clone(),
+        path: path.to_path_buf(),
+    };
+    drop(plugin);
+
+    let mut plugins = DYNAMIC_PLUGINS
+        .write()
+        .map_err(|e| DomainError::LockError(e.to_string()))?;
+    if plugins
+        .iter()
+        .any(|p| p.info.provider_type == provider_type)
+    {
+        return Err(DomainError::InvalidProviderType(format!(
+            "Plugin provider type '{}' is already registered",
+            provider_type
+        )));
+    }
+
+    plugins.push(DynamicPlugin {
+        info: info.clone(),
+        create,
+        _library: library,
+    });
+
+    Ok(Some(info))
+}
+
+pub fn loaded_plugins() -> Vec<DynamicPluginInfo> {
+    DYNAMIC_PLUGINS
+        .read()
+        .map(|plugins| plugins.iter().map(|p| p.info.clone()).collect())
+        .unwrap_or_default()
+}
+
+pub(crate) fn create_plugin(provider_type: &str) -> Option<Box<dyn PluginTrait>> {
+    let plugins = DYNAMIC_PLUGINS.read().ok()?;
+    plugins
+        .iter()
+        .find(|p| p.info.provider_type == provider_type)
+        .map(|p| (p.create)())
+}
+
+pub(crate) fn provider_types() -> Vec<String> {
+    DYNAMIC_PLUGINS
+        .read()
+        .map(|plugins| {
+            plugins
+                .iter()
+                .map(|p| p.info.provider_type.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_dir_loads_nothing() {
+        let loaded = load_plugins_from_dir(Path::new("/nonexistent/pipedash/plugins"));
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn test_non_library_files_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("README.txt"), "not a plugin").unwrap();
+
+        let loaded = load_plugins_from_dir(dir.path());
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_library_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = dir
+            .path()
+            .join(format!("broken.{}", std::env::consts::DLL_EXTENSION));
+        std::fs::write(&fake, b"garbage").unwrap();
+
+        let loaded = load_plugins_from_dir(dir.path());
+        assert!(loaded.is_empty());
+        assert!(create_plugin("broken").is_none());
+    }
+}
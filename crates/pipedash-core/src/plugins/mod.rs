@@ -1,9 +1,62 @@
+mod dynamic;
+
+use std::path::Path;
+use std::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
+
+pub use dynamic::{
+    load_plugins_from_dir,
+    loaded_plugins,
+    plugins_dir,
+    DynamicPluginInfo,
+};
 use pipedash_plugin_api::{
     Plugin as PluginTrait,
     PluginMetadata,
     PluginRegistry,
 };
 
+const BUILTIN_PROVIDER_TYPES: &[&str] = &[
+    "github",
+    "gitlab",
+    "bitbucket",
+    "buildkite",
+    "jenkins",
+    "tekton",
+    "argocd",
+    "spinnaker",
+];
+
+const DEMO_PROVIDER_TYPE: &str = "demo";
+
+// The demo plugin is only offered when `general.demo_mode` is on, so real
+// installs never list a provider that serves made-up data.
+static DEMO_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_demo_enabled(enabled: bool) {
+    DEMO_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn demo_enabled() -> bool {
+    DEMO_ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn is_builtin_provider(provider_type: &str) -> bool {
+    BUILTIN_PROVIDER_TYPES.contains(&provider_type)
+        || (provider_type == DEMO_PROVIDER_TYPE && demo_enabled())
+}
+
+pub fn is_known_provider(provider_type: &str) -> bool {
+    is_builtin_provider(provider_type)
+        || dynamic::provider_types().iter().any(|t| t == provider_type)
+}
+
+pub fn load_dynamic_plugins(data_dir: &Path) -> Vec<DynamicPluginInfo> {
+    load_plugins_from_dir(&plugins_dir(data_dir))
+}
+
 pub fn create_plugin_registry() -> PluginRegistry {
     let mut registry = PluginRegistry::new();
 
@@ -14,6 +67,16 @@ pub fn create_plugin_registry() -> PluginRegistry {
     registry.register(Box::new(pipedash_plugin_jenkins::JenkinsPlugin::new()));
     registry.register(Box::new(pipedash_plugin_tekton::TektonPlugin::new()));
     registry.register(Box::new(pipedash_plugin_argocd::ArgocdPlugin::new()));
+    registry.register(Box::new(pipedash_plugin_spinnaker::SpinnakerPlugin::new()));
+    if demo_enabled() {
+        registry.register(Box::new(pipedash_plugin_demo::DemoPlugin::new()));
+    }
+
+    for provider_type in dynamic::provider_types() {
+        if let Some(plugin) = dynamic::create_plugin(&provider_type) {
+            registry.register(plugin);
+        }
+    }
 
     registry
 }
@@ -37,6 +100,10 @@ pub fn create_plugin(provider_type: &str) -> Option<Box<dyn PluginTrait>> {
         "jenkins" => Some(Box::new(pipedash_plugin_jenkins::JenkinsPlugin::new())),
         "tekton" => Some(Box::new(pipedash_plugin_tekton::TektonPlugin::new())),
         "argocd" => Some(Box::new(pipedash_plugin_argocd::ArgocdPlugin::new())),
-        _ => None,
+        "spinnaker" => Some(Box::new(pipedash_plugin_spinnaker::SpinnakerPlugin::new())),
+        DEMO_PROVIDER_TYPE if demo_enabled() => {
+            Some(Box::new(pipedash_plugin_demo::DemoPlugin::new()))
+        }
+        _ => dynamic::create_plugin(provider_type),
     }
 }
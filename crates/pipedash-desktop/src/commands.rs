@@ -2,24 +2,79 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use pipedash_core::{
-    application::RefreshMode,
+    application::{
+        ActivityHint,
+        PipelinePreviewRequest,
+        RefreshMode,
+    },
     domain::{
         AggregatedMetrics,
         AggregationPeriod,
         AggregationType,
+        AppNotification,
+        BackupInfo,
+        BackupVerification,
+        BranchBoard,
+        BuildArtifact,
+        BulkItemResult,
+        ChannelKind,
+        CommitStatus,
+        CostGroupBy,
+        CostReport,
+        DeviceAuthorization,
+        DomainResult,
+        ExportFilter,
+        ExportFormat,
+        FlakyPipeline,
         GlobalMetricsConfig,
+        GlobalRunRetentionConfig,
+        LogQuery,
+        MaintenanceWindow,
+        MetricAnomaly,
         MetricType,
         MetricsConfig,
         MetricsQuery,
+        MetricsRebuild,
         MetricsStats,
+        NotificationChannel,
+        NotificationDelivery,
+        NotificationRule,
+        NotificationSuppression,
+        OAuthPollStatus,
         PaginatedAvailablePipelines,
         PaginatedRunHistory,
-        PaginationParams,
+        PendingApproval,
+        PinnedPipeline,
         Pipeline,
+        PipelineDependency,
+        PipelineGraph,
+        PipelineGroup,
+        PipelineGroupStatus,
+        PipelineListQuery,
+        PipelineRefreshSetting,
         PipelineRun,
+        PipelineRunRetention,
+        PipelineStatus,
+        ProviderAgent,
         ProviderConfig,
+        ProviderDiagnosis,
+        ProviderHealth,
+        ProviderQueue,
         ProviderSummary,
+        QuickAction,
+        ReleaseReport,
+        RunBackfillJob,
+        RunComparison,
+        RunLogs,
+        RunRef,
+        RunRetentionPolicy,
+        SavedView,
+        SearchResult,
+        StatusSummary,
+        TriggerApproval,
         TriggerParams,
+        TriggerPreset,
+        ViewQuery,
     },
     CoreContext,
 };
@@ -39,7 +94,9 @@ pub struct ErrorResponse {
 impl From<pipedash_core::DomainError> for ErrorResponse {
     fn from(err: pipedash_core::DomainError) -> Self {
         ErrorResponse {
-            error: err.to_string(),
+            error: pipedash_core::domain::redactor()
+                .redact(&err.to_string())
+                .into_owned(),
             details: None,
         }
     }
@@ -49,6 +106,8 @@ impl From<pipedash_core::DomainError> for ErrorResponse {
 pub struct ValidationResult {
     pub valid: bool,
     pub error: Option<String>,
+    // Reported by self-hosted providers, with a warning when unsupported.
+    pub server_version: Option<pipedash_plugin_api::ServerVersion>,
 }
 
 #[derive(Debug, Serialize)]
@@ -63,6 +122,7 @@ pub struct CacheStats {
     pub run_history_count: i64,
     pub workflow_params_count: i64,
     pub metrics_count: i64,
+    pub run_history_retention: Option<GlobalRunRetentionConfig>,
 }
 
 #[tauri::command]
@@ -156,6 +216,69 @@ pub async fn update_provider_refresh_interval(
         .map_err(Into::into)
 }
 
+#[tauri::command]
+pub async fn enable_provider(
+    maybe_core: State<'_, crate::MaybeCoreContext>, id: i64,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.provider_service.enable_provider(id).await?;
+    core.refresh_manager.prioritize_provider(id).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn disable_provider(
+    maybe_core: State<'_, crate::MaybeCoreContext>, id: i64,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.provider_service
+        .disable_provider(id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn start_run_backfill(
+    maybe_core: State<'_, crate::MaybeCoreContext>, id: i64, depth: Option<usize>,
+) -> Result<RunBackfillJob, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.backfill_service
+        .start(id, depth)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn cancel_run_backfill(
+    maybe_core: State<'_, crate::MaybeCoreContext>, id: i64,
+) -> Result<RunBackfillJob, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.backfill_service.cancel(id).await.map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_run_backfills(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<RunBackfillJob>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.backfill_service.list_jobs().await.map_err(Into::into)
+}
+
 #[tauri::command]
 pub async fn remove_provider(
     maybe_core: State<'_, crate::MaybeCoreContext>, id: i64,
@@ -210,6 +333,12 @@ pub async fn list_plugin_metadata(
     }
 }
 
+#[tauri::command]
+pub async fn list_loaded_plugins(
+) -> Result<Vec<pipedash_core::plugins::DynamicPluginInfo>, ErrorResponse> {
+    Ok(pipedash_core::plugins::loaded_plugins())
+}
+
 #[tauri::command]
 pub async fn get_provider_field_options(
     maybe_core: State<'_, crate::MaybeCoreContext>, provider_type: String, field_key: String,
@@ -245,8 +374,9 @@ pub async fn fetch_provider_organizations(
         .provider_service
         .create_uninitialized_plugin(&provider_type)?;
 
+    let http_client = core.http_client_manager.client_for_provider(&config)?;
     plugin
-        .initialize(0, config, None)
+        .initialize(0, config, Some(http_client))
         .map_err(|e| ErrorResponse {
             error: format!("Failed to initialize plugin: {e}"),
             details: None,
@@ -279,44 +409,52 @@ pub async fn preview_provider_pipelines(
         error: e,
         details: None,
     })?;
-    let mut plugin = core
-        .provider_service
-        .create_uninitialized_plugin(&provider_type)?;
-
-    plugin
-        .initialize(0, config, None)
-        .map_err(|e| ErrorResponse {
-            error: format!("Failed to initialize plugin: {e}"),
-            details: None,
-        })?;
-
-    plugin
-        .validate_credentials()
-        .await
-        .map_err(|e| ErrorResponse {
-            error: format!("Failed to validate credentials: {e}"),
-            details: None,
-        })?;
-
-    let params = PaginationParams {
-        page: page.unwrap_or(1).max(1),
-        page_size: page_size.unwrap_or(100).clamp(10, 200),
+    let request = PipelinePreviewRequest {
+        preview_id: None,
+        provider_type,
+        config,
+        org,
+        search,
+        page_size,
     };
+    Ok(core
+        .pipeline_previewer
+        .fetch_page(request, page.unwrap_or(1))
+        .await?)
+}
 
-    if let Err(validation_error) = params.validate() {
-        return Err(ErrorResponse {
-            error: validation_error,
-            details: None,
-        });
-    }
+// Pages arrive as `preview-page` events, then `preview-finished`.
+#[tauri::command]
+pub async fn start_pipeline_preview(
+    maybe_core: State<'_, crate::MaybeCoreContext>, request: PipelinePreviewRequest,
+) -> Result<String, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    Ok(core.pipeline_previewer.start(request).await?)
+}
 
-    plugin
-        .fetch_available_pipelines_filtered(org, search, Some(params))
-        .await
-        .map_err(|e| ErrorResponse {
-            error: format!("Failed to fetch available pipelines: {e}"),
-            details: None,
-        })
+#[tauri::command]
+pub async fn cancel_pipeline_preview(
+    maybe_core: State<'_, crate::MaybeCoreContext>, preview_id: String,
+) -> Result<bool, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    Ok(core.pipeline_previewer.cancel(&preview_id).await)
+}
+
+#[tauri::command]
+pub async fn discover_provider_repositories(
+    maybe_core: State<'_, crate::MaybeCoreContext>, provider_id: i64,
+) -> Result<Vec<String>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    Ok(core.discovery_service.discover(provider_id).await?)
 }
 
 #[tauri::command]
@@ -332,8 +470,9 @@ pub async fn validate_provider_credentials(
         .provider_service
         .create_uninitialized_plugin(&provider_type)?;
 
+    let http_client = core.http_client_manager.client_for_provider(&config)?;
     plugin
-        .initialize(0, config, None)
+        .initialize(0, config, Some(http_client))
         .map_err(|e| ErrorResponse {
             error: format!("Failed to initialize plugin: {}", e),
             details: None,
@@ -343,14 +482,20 @@ pub async fn validate_provider_credentials(
         Ok(valid) if valid => Ok(ValidationResult {
             valid: true,
             error: None,
+            server_version: core
+                .provider_service
+                .detect_server_version(plugin.as_ref())
+                .await,
         }),
         Ok(_) => Ok(ValidationResult {
             valid: false,
             error: Some("Invalid credentials".to_string()),
+            server_version: None,
         }),
         Err(e) => Ok(ValidationResult {
             valid: false,
             error: Some(e.to_string()),
+            server_version: None,
         }),
     }
 }
@@ -368,8 +513,9 @@ pub async fn check_provider_permissions(
         .provider_service
         .create_uninitialized_plugin(&provider_type)?;
 
+    let http_client = core.http_client_manager.client_for_provider(&config)?;
     plugin
-        .initialize(0, config, None)
+        .initialize(0, config, Some(http_client))
         .map_err(|e| ErrorResponse {
             error: format!("Failed to initialize plugin: {}", e),
             details: None,
@@ -404,77 +550,84 @@ pub async fn get_provider_permissions(
 }
 
 #[tauri::command]
-pub async fn get_provider_features(
-    maybe_core: State<'_, crate::MaybeCoreContext>, provider_id: i64,
-) -> Result<Vec<pipedash_plugin_api::FeatureAvailability>, ErrorResponse> {
+pub async fn get_provider_health(
+    maybe_core: State<'_, crate::MaybeCoreContext>, provider_id: Option<i64>,
+) -> Result<Vec<ProviderHealth>, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    let provider_config = core
-        .provider_service
-        .get_provider_config(provider_id)
-        .await?;
-
-    let metadata_list = core.provider_service.list_available_plugins();
-    let plugin_metadata = metadata_list
-        .iter()
-        .find(|m| m.provider_type == provider_config.provider_type)
-        .ok_or_else(|| ErrorResponse {
-            error: format!(
-                "Plugin not found for provider type: {}",
-                provider_config.provider_type
-            ),
-            details: None,
-        })?;
+    core.provider_service
+        .get_provider_health(provider_id)
+        .await
+        .map_err(Into::into)
+}
 
-    let permission_status = core
-        .provider_service
-        .get_provider_permissions(provider_id)
-        .await?;
+#[tauri::command]
+pub async fn diagnose_provider(
+    maybe_core: State<'_, crate::MaybeCoreContext>, provider_id: i64,
+) -> Result<ProviderDiagnosis, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.provider_service
+        .diagnose_provider(provider_id)
+        .await
+        .map_err(Into::into)
+}
 
-    let permission_status = match permission_status {
-        Some(status) => status,
-        None => {
-            return Ok(plugin_metadata
-                .features
-                .iter()
-                .map(|f| pipedash_plugin_api::FeatureAvailability {
-                    feature: f.clone(),
-                    available: false,
-                    missing_permissions: f.required_permissions.clone(),
-                })
-                .collect());
-        }
-    };
+#[tauri::command]
+pub async fn get_provider_diagnoses(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<ProviderDiagnosis>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    Ok(core.provider_service.get_provider_diagnoses().await)
+}
 
-    let granted_perms: std::collections::HashSet<String> = permission_status
-        .permissions
-        .iter()
-        .filter(|p| p.granted)
-        .map(|p| p.permission.name.clone())
-        .collect();
+#[tauri::command]
+pub async fn start_provider_oauth(
+    maybe_core: State<'_, crate::MaybeCoreContext>, provider_type: String, base_url: Option<String>,
+) -> Result<DeviceAuthorization, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.provider_service
+        .start_oauth(&provider_type, base_url.as_deref())
+        .await
+        .map_err(Into::into)
+}
 
-    let features = plugin_metadata
-        .features
-        .iter()
-        .map(|feature| {
-            let missing: Vec<String> = feature
-                .required_permissions
-                .iter()
-                .filter(|p| !granted_perms.contains(*p))
-                .cloned()
-                .collect();
-
-            pipedash_plugin_api::FeatureAvailability {
-                feature: feature.clone(),
-                available: missing.is_empty(),
-                missing_permissions: missing,
-            }
-        })
-        .collect();
+#[tauri::command]
+pub async fn poll_provider_oauth(
+    maybe_core: State<'_, crate::MaybeCoreContext>, session_id: String,
+) -> Result<OAuthPollStatus, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.provider_service
+        .poll_oauth(&session_id)
+        .await
+        .map_err(Into::into)
+}
 
-    Ok(features)
+#[tauri::command]
+pub async fn get_provider_features(
+    maybe_core: State<'_, crate::MaybeCoreContext>, provider_id: i64,
+) -> Result<Vec<pipedash_plugin_api::FeatureAvailability>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.provider_service
+        .get_provider_features(provider_id)
+        .await
+        .map_err(Into::into)
 }
 
 #[tauri::command]
@@ -485,24 +638,10 @@ pub async fn get_provider_table_schema(
         error: e,
         details: None,
     })?;
-    let provider_config = core
-        .provider_service
-        .get_provider_config(provider_id)
-        .await?;
-
-    let metadata_list = core.provider_service.list_available_plugins();
-    let plugin_metadata = metadata_list
-        .iter()
-        .find(|m| m.provider_type == provider_config.provider_type)
-        .ok_or_else(|| ErrorResponse {
-            error: format!(
-                "Plugin not found for provider type: {}",
-                provider_config.provider_type
-            ),
-            details: None,
-        })?;
-
-    Ok(plugin_metadata.table_schema.clone())
+    core.provider_service
+        .get_provider_table_schema(provider_id)
+        .await
+        .map_err(Into::into)
 }
 
 #[tauri::command]
@@ -529,24 +668,23 @@ pub async fn fetch_pipelines(
 
 #[tauri::command]
 pub async fn get_cached_pipelines(
-    maybe_core: State<'_, crate::MaybeCoreContext>, provider_id: Option<i64>,
-) -> Result<Vec<Pipeline>, ErrorResponse> {
+    maybe_core: State<'_, crate::MaybeCoreContext>, query: Option<PipelineListQuery>,
+) -> Result<pipedash_plugin_api::PaginatedResponse<Pipeline>, ErrorResponse> {
+    let query = query.unwrap_or_default();
     tracing::debug!(
-        "[get_cached_pipelines] Command invoked with provider_id: {:?}",
-        provider_id
+        "[get_cached_pipelines] Command invoked with query: {:?}",
+        query
     );
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    let result = core
-        .pipeline_service
-        .get_cached_pipelines(provider_id)
-        .await;
+    let result = core.pipeline_service.list_cached_pipelines(&query).await;
     match &result {
-        Ok(pipelines) => tracing::debug!(
-            "[get_cached_pipelines] Returning {} pipelines",
-            pipelines.len()
+        Ok(page) => tracing::debug!(
+            "[get_cached_pipelines] Returning {} of {} pipelines",
+            page.items.len(),
+            page.total_count
         ),
         Err(e) => tracing::error!("[get_cached_pipelines] Error: {:?}", e),
     }
@@ -586,69 +724,805 @@ pub async fn get_workflow_run_details(
 }
 
 #[tauri::command]
-pub async fn trigger_pipeline(
-    maybe_core: State<'_, crate::MaybeCoreContext>, params: TriggerParams,
-) -> Result<String, ErrorResponse> {
+pub async fn fetch_branch_statuses(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, refresh: Option<bool>,
+) -> Result<BranchBoard, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    let workflow_id = params.workflow_id.clone();
-    let result = core.pipeline_service.trigger_pipeline(params).await?;
-
     core.pipeline_service
-        .invalidate_run_cache(&workflow_id)
-        .await;
-
-    Ok(result)
+        .fetch_branch_statuses(&pipeline_id, refresh.unwrap_or(false))
+        .await
+        .map_err(Into::into)
 }
 
 #[tauri::command]
-pub async fn cancel_pipeline_run(
+pub async fn get_run_logs(
     maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, run_number: i64,
-) -> Result<(), ErrorResponse> {
+    job_id: Option<String>, offset: Option<usize>,
+) -> Result<RunLogs, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
+    let query = LogQuery {
+        job_id,
+        offset: offset.unwrap_or(0),
+    };
     core.pipeline_service
-        .cancel_run(&pipeline_id, run_number)
-        .await?;
-    core.pipeline_service
-        .invalidate_run_cache(&pipeline_id)
-        .await;
-    Ok(())
+        .fetch_run_logs(&pipeline_id, run_number, query)
+        .await
+        .map_err(Into::into)
 }
 
 #[tauri::command]
-pub async fn get_workflow_parameters(
-    maybe_core: State<'_, crate::MaybeCoreContext>, workflow_id: String,
-) -> Result<Vec<pipedash_plugin_api::WorkflowParameter>, ErrorResponse> {
-    let parts: Vec<&str> = workflow_id.split("__").collect();
-    if parts.len() < 2 {
-        return Err(ErrorResponse {
-            error: format!(
-                "Invalid workflow ID format '{}'. Expected format: 'provider__id__...'",
-                workflow_id
-            ),
-            details: None,
-        });
-    }
-
-    let provider_id: i64 = parts[1].parse().map_err(|_| ErrorResponse {
-        error: format!(
-            "Invalid provider ID '{}' in workflow ID. Expected numeric ID.",
-            parts[1]
-        ),
-        details: None,
-    })?;
-
+pub async fn compare_runs(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, run_a: i64, run_b: i64,
+) -> Result<RunComparison, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    core.provider_service
-        .get_workflow_parameters(provider_id, &workflow_id)
+    core.pipeline_service
+        .compare_runs(&pipeline_id, run_a, run_b)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn generate_release_report(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, from_run: i64, to_run: i64,
+) -> Result<ReleaseReport, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .generate_release_report(&pipeline_id, from_run, to_run)
+        .await
+        .map_err(Into::into)
+}
+
+// Saves the report as Markdown; None when the save dialog was cancelled.
+#[tauri::command]
+pub async fn export_release_report(
+    app: tauri::AppHandle, maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String,
+    from_run: i64, to_run: i64,
+) -> Result<Option<String>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let report = core
+        .pipeline_service
+        .generate_release_report(&pipeline_id, from_run, to_run)
+        .await?;
+
+    let body = Box::pin(futures::stream::once(async move {
+        DomainResult::Ok(report.to_markdown())
+    }));
+    save_file(&app, format!("release-{}-{}.md", from_run, to_run), body).await
+}
+
+#[tauri::command]
+pub async fn list_groups(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<PipelineGroupStatus>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .list_groups()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn create_group(
+    maybe_core: State<'_, crate::MaybeCoreContext>, name: String,
+) -> Result<PipelineGroup, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .create_group(&name)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn delete_group(
+    maybe_core: State<'_, crate::MaybeCoreContext>, group_id: i64,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .delete_group(group_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn assign_pipeline_to_group(
+    maybe_core: State<'_, crate::MaybeCoreContext>, group_id: i64, pipeline_id: String,
+) -> Result<PipelineGroup, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .assign_pipeline_to_group(group_id, &pipeline_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn remove_pipeline_from_group(
+    maybe_core: State<'_, crate::MaybeCoreContext>, group_id: i64, pipeline_id: String,
+) -> Result<PipelineGroup, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .remove_pipeline_from_group(group_id, &pipeline_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_views(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<SavedView>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service.list_views().await.map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn save_view(
+    maybe_core: State<'_, crate::MaybeCoreContext>, name: String, query: ViewQuery,
+) -> Result<SavedView, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .save_view(&name, query)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn delete_view(
+    maybe_core: State<'_, crate::MaybeCoreContext>, view_id: i64,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .delete_view(view_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn query_view(
+    maybe_core: State<'_, crate::MaybeCoreContext>, view_id: i64,
+) -> Result<Vec<Pipeline>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .query_view(view_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn set_pipeline_labels(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, labels: Vec<String>,
+) -> Result<Vec<String>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .set_pipeline_labels(&pipeline_id, labels)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_refresh_settings(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<PipelineRefreshSetting>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .list_refresh_settings()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn set_pipeline_refresh(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String,
+    refresh_interval_secs: Option<u64>, snoozed: bool,
+) -> Result<Option<PipelineRefreshSetting>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .set_pipeline_refresh(&pipeline_id, refresh_interval_secs, snoozed)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_pinned_pipelines(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<PinnedPipeline>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .list_pinned_pipelines()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn pin_pipeline(
+    app: tauri::AppHandle, maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String,
+) -> Result<PinnedPipeline, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let pin = core.pipeline_service.pin_pipeline(&pipeline_id).await?;
+    crate::tray::schedule_update(&app);
+    Ok(pin)
+}
+
+#[tauri::command]
+pub async fn unpin_pipeline(
+    app: tauri::AppHandle, maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service.unpin_pipeline(&pipeline_id).await?;
+    crate::tray::schedule_update(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_tray_summary(app: tauri::AppHandle) -> Result<StatusSummary, ErrorResponse> {
+    crate::tray::summary(&app).await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })
+}
+
+// An empty selection is kept as-is; `None` goes back to the pinned pipelines.
+#[tauri::command]
+pub async fn set_tray_pipelines(
+    app: tauri::AppHandle, tray_state: State<'_, crate::tray::TrayState>,
+    pipeline_ids: Option<Vec<String>>,
+) -> Result<StatusSummary, ErrorResponse> {
+    tray_state
+        .set_pipeline_ids(pipeline_ids)
+        .map_err(|e| ErrorResponse {
+            error: e,
+            details: None,
+        })?;
+    crate::tray::schedule_update(&app);
+    get_tray_summary(app).await
+}
+
+#[tauri::command]
+pub async fn search_everything(
+    maybe_core: State<'_, crate::MaybeCoreContext>, query: String, limit: Option<usize>,
+) -> Result<Vec<SearchResult>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .search_everything(&query, limit.unwrap_or(20))
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_commit_status(
+    maybe_core: State<'_, crate::MaybeCoreContext>, sha: String,
+) -> Result<CommitStatus, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .get_commit_status(&sha)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn quick_actions(
+    maybe_core: State<'_, crate::MaybeCoreContext>, query: Option<String>, limit: Option<usize>,
+) -> Result<Vec<QuickAction>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .quick_actions(query.as_deref(), limit.unwrap_or(20))
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn start_log_tail(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, run_number: i64,
+) -> Result<bool, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    Ok(core.log_tailer.start(&pipeline_id, run_number))
+}
+
+#[tauri::command]
+pub async fn stop_log_tail(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, run_number: i64,
+) -> Result<bool, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    Ok(core.log_tailer.stop(&pipeline_id, run_number).await)
+}
+
+#[tauri::command]
+pub async fn list_run_artifacts(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, run_number: i64,
+) -> Result<Vec<BuildArtifact>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .list_run_artifacts(&pipeline_id, run_number)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn download_artifact(
+    app: tauri::AppHandle, maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String,
+    run_number: i64, artifact_id: String,
+) -> Result<Option<String>, ErrorResponse> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let (artifact, download) = core
+        .pipeline_service
+        .resolve_artifact_download(&pipeline_id, run_number, &artifact_id)
+        .await?;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .set_file_name(&artifact.filename)
+        .save_file(move |path| {
+            let _ = tx.send(path);
+        });
+
+    let Some(path) = rx.await.ok().flatten() else {
+        return Ok(None);
+    };
+    let path = path.into_path().map_err(|e| ErrorResponse {
+        error: format!("Invalid save location: {}", e),
+        details: None,
+    })?;
+
+    core.http_client_manager
+        .download_to_file(&download, &path)
+        .await?;
+
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+// Asks where to save, then writes the export chunk by chunk. Returns None when
+// the dialog is cancelled.
+async fn save_export(
+    app: &tauri::AppHandle, name: &str, format: ExportFormat,
+    stream: futures::stream::BoxStream<'static, DomainResult<String>>,
+) -> Result<Option<String>, ErrorResponse> {
+    save_file(
+        app,
+        format!("pipedash-{}.{}", name, format.extension()),
+        stream,
+    )
+    .await
+}
+
+// Asks where to save, `None` when the dialog was cancelled.
+async fn pick_save_path(
+    app: &tauri::AppHandle, file_name: String,
+) -> Result<Option<std::path::PathBuf>, ErrorResponse> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .set_file_name(file_name)
+        .save_file(move |path| {
+            let _ = tx.send(path);
+        });
+
+    let Some(path) = rx.await.ok().flatten() else {
+        return Ok(None);
+    };
+    path.into_path().map(Some).map_err(|e| ErrorResponse {
+        error: format!("Invalid save location: {}", e),
+        details: None,
+    })
+}
+
+async fn save_file(
+    app: &tauri::AppHandle, file_name: String,
+    mut stream: futures::stream::BoxStream<'static, DomainResult<String>>,
+) -> Result<Option<String>, ErrorResponse> {
+    use futures::StreamExt;
+    use tokio::io::AsyncWriteExt;
+
+    let Some(path) = pick_save_path(app, file_name).await? else {
+        return Ok(None);
+    };
+
+    let io_error = |e: std::io::Error| ErrorResponse {
+        error: format!("Failed to write export: {}", e),
+        details: None,
+    };
+    let mut file = tokio::fs::File::create(&path).await.map_err(io_error)?;
+    while let Some(chunk) = stream.next().await {
+        file.write_all(chunk?.as_bytes()).await.map_err(io_error)?;
+    }
+    file.flush().await.map_err(io_error)?;
+
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+#[tauri::command]
+pub async fn export_metrics(
+    app: tauri::AppHandle, maybe_core: State<'_, crate::MaybeCoreContext>, format: ExportFormat,
+    filter: ExportFilter, metric_type: Option<MetricType>,
+) -> Result<Option<String>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let metrics_service = core.metrics_service.as_ref().ok_or_else(|| ErrorResponse {
+        error: "Metrics service not available".to_string(),
+        details: None,
+    })?;
+
+    let stream = metrics_service.export_metrics(filter, metric_type, format);
+    save_export(&app, "metrics", format, stream).await
+}
+
+#[tauri::command]
+pub async fn export_run_history(
+    app: tauri::AppHandle, maybe_core: State<'_, crate::MaybeCoreContext>, format: ExportFormat,
+    filter: ExportFilter,
+) -> Result<Option<String>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+
+    let stream = core.pipeline_service.export_run_history(filter, format);
+    save_export(&app, "runs", format, stream).await
+}
+
+#[tauri::command]
+pub async fn trigger_pipeline(
+    maybe_core: State<'_, crate::MaybeCoreContext>, params: TriggerParams,
+) -> Result<String, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let workflow_id = params.workflow_id.clone();
+    let result = core.pipeline_service.trigger_pipeline(params).await?;
+
+    core.pipeline_service
+        .invalidate_run_cache(&workflow_id)
+        .await;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn cancel_pipeline_run(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, run_number: i64,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .cancel_run(&pipeline_id, run_number)
+        .await?;
+    core.pipeline_service
+        .invalidate_run_cache(&pipeline_id)
+        .await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn retry_pipeline_run(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, run_number: i64,
+    failed_only: bool,
+) -> Result<String, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let result = core
+        .pipeline_service
+        .retry_run(&pipeline_id, run_number, failed_only)
+        .await?;
+    core.pipeline_service
+        .invalidate_run_cache(&pipeline_id)
+        .await;
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_pending_approvals(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, run_number: i64,
+) -> Result<Vec<PendingApproval>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .list_pending_approvals(&pipeline_id, run_number)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn unblock_job(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, run_number: i64,
+    job_id: String, fields: Option<HashMap<String, String>>,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .unblock_job(
+            &pipeline_id,
+            run_number,
+            &job_id,
+            fields.unwrap_or_default(),
+        )
+        .await?;
+    core.pipeline_service
+        .invalidate_run_cache(&pipeline_id)
+        .await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn trigger_pipelines_bulk(
+    maybe_core: State<'_, crate::MaybeCoreContext>, items: Vec<TriggerParams>,
+) -> Result<Vec<BulkItemResult>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .trigger_pipelines_bulk(items)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_trigger_approvals(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pending_only: Option<bool>,
+    limit: Option<usize>,
+) -> Result<Vec<TriggerApproval>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .list_trigger_approvals(pending_only.unwrap_or(false), limit)
+        .await
+        .map_err(Into::into)
+}
+
+// The desktop app has a single user, so approving always goes through the
+// explicit confirmation in the UI.
+#[tauri::command]
+pub async fn approve_trigger(
+    maybe_core: State<'_, crate::MaybeCoreContext>, id: i64, confirmed: Option<bool>,
+    comment: Option<String>,
+) -> Result<TriggerApproval, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .decide_trigger_approval(id, true, confirmed.unwrap_or(false), comment)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn reject_trigger(
+    maybe_core: State<'_, crate::MaybeCoreContext>, id: i64, comment: Option<String>,
+) -> Result<TriggerApproval, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .decide_trigger_approval(id, false, false, comment)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_approval_rules(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<String>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .list_approval_rules()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn set_pipeline_approval(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, required: bool,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .set_approval_required(&pipeline_id, required)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_maintenance_windows(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<MaintenanceWindow>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .list_maintenance_windows()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn save_maintenance_window(
+    maybe_core: State<'_, crate::MaybeCoreContext>, window: MaintenanceWindow,
+) -> Result<MaintenanceWindow, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .save_maintenance_window(window)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn delete_maintenance_window(
+    maybe_core: State<'_, crate::MaybeCoreContext>, id: i64,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .delete_maintenance_window(id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn cancel_runs_bulk(
+    maybe_core: State<'_, crate::MaybeCoreContext>, runs: Vec<RunRef>,
+) -> Result<Vec<BulkItemResult>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .cancel_runs_bulk(runs)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn retry_runs_bulk(
+    maybe_core: State<'_, crate::MaybeCoreContext>, runs: Vec<RunRef>, failed_only: bool,
+) -> Result<Vec<BulkItemResult>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .retry_runs_bulk(runs, failed_only)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_workflow_parameters(
+    maybe_core: State<'_, crate::MaybeCoreContext>, workflow_id: String,
+) -> Result<Vec<pipedash_plugin_api::WorkflowParameter>, ErrorResponse> {
+    let parts: Vec<&str> = workflow_id.split("__").collect();
+    if parts.len() < 2 {
+        return Err(ErrorResponse {
+            error: format!(
+                "Invalid workflow ID format '{}'. Expected format: 'provider__id__...'",
+                workflow_id
+            ),
+            details: None,
+        });
+    }
+
+    let provider_id: i64 = parts[1].parse().map_err(|_| ErrorResponse {
+        error: format!(
+            "Invalid provider ID '{}' in workflow ID. Expected numeric ID.",
+            parts[1]
+        ),
+        details: None,
+    })?;
+
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.provider_service
+        .get_workflow_parameters(provider_id, &workflow_id)
         .await
         .map_err(|e| ErrorResponse {
             error: format!("Failed to fetch workflow parameters: {e}"),
@@ -656,6 +1530,264 @@ pub async fn get_workflow_parameters(
         })
 }
 
+#[tauri::command]
+pub async fn list_trigger_presets(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String,
+) -> Result<Vec<TriggerPreset>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .list_trigger_presets(&pipeline_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn save_trigger_preset(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, name: String,
+    branch: Option<String>, parameters: HashMap<String, serde_json::Value>,
+) -> Result<TriggerPreset, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .save_trigger_preset(&pipeline_id, &name, branch, parameters)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn delete_trigger_preset(
+    maybe_core: State<'_, crate::MaybeCoreContext>, preset_id: i64,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .delete_trigger_preset(preset_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn trigger_with_preset(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, preset_id: i64,
+    overrides: Option<HashMap<String, serde_json::Value>>,
+) -> Result<String, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let result = core
+        .pipeline_service
+        .trigger_with_preset(&pipeline_id, preset_id, overrides.unwrap_or_default())
+        .await?;
+
+    core.pipeline_service
+        .invalidate_run_cache(&pipeline_id)
+        .await;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_pipeline_dependencies(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<PipelineDependency>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.orchestrator_service
+        .list_dependencies()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn save_pipeline_dependency(
+    maybe_core: State<'_, crate::MaybeCoreContext>, upstream_id: String, downstream_id: String,
+    trigger_on: Option<PipelineStatus>, inputs: Option<HashMap<String, serde_json::Value>>,
+) -> Result<PipelineDependency, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.orchestrator_service
+        .save_dependency(
+            &upstream_id,
+            &downstream_id,
+            trigger_on.unwrap_or(PipelineStatus::Success),
+            inputs.unwrap_or_default(),
+        )
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn delete_pipeline_dependency(
+    maybe_core: State<'_, crate::MaybeCoreContext>, dependency_id: i64,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.orchestrator_service
+        .delete_dependency(dependency_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_pipeline_graph(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<PipelineGraph, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.orchestrator_service
+        .get_pipeline_graph()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_notification_channels(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<NotificationChannel>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.notification_service
+        .list_channels()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn save_notification_channel(
+    maybe_core: State<'_, crate::MaybeCoreContext>, name: String, kind: ChannelKind, url: String,
+    enabled: Option<bool>, rules: Vec<NotificationRule>,
+    suppression: Option<NotificationSuppression>,
+) -> Result<NotificationChannel, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.notification_service
+        .save_channel(
+            &name,
+            kind,
+            &url,
+            enabled.unwrap_or(true),
+            rules,
+            suppression.unwrap_or_default(),
+        )
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn delete_notification_channel(
+    maybe_core: State<'_, crate::MaybeCoreContext>, channel_id: i64,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.notification_service
+        .delete_channel(channel_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn test_notification_channel(
+    maybe_core: State<'_, crate::MaybeCoreContext>, channel_id: i64,
+) -> Result<NotificationDelivery, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.notification_service
+        .test_channel(channel_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_notification_deliveries(
+    maybe_core: State<'_, crate::MaybeCoreContext>, channel_id: Option<i64>, limit: Option<usize>,
+) -> Result<Vec<NotificationDelivery>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.notification_service
+        .get_deliveries(channel_id, limit)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_notifications(
+    maybe_core: State<'_, crate::MaybeCoreContext>, unread_only: Option<bool>, limit: Option<usize>,
+) -> Result<Vec<AppNotification>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.notification_center
+        .list_notifications(unread_only.unwrap_or(false), limit)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_unread_notification_count(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<usize, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.notification_center
+        .unread_count()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn mark_notification_read(
+    maybe_core: State<'_, crate::MaybeCoreContext>, id: Option<i64>,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.notification_center
+        .mark_read(id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn clear_notifications(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.notification_center.clear().await.map_err(Into::into)
+}
+
 #[tauri::command]
 pub async fn refresh_all(
     maybe_core: State<'_, crate::MaybeCoreContext>,
@@ -672,136 +1804,327 @@ pub async fn refresh_all(
 }
 
 #[tauri::command]
-pub async fn set_refresh_mode(
-    maybe_core: State<'_, crate::MaybeCoreContext>, mode: String,
-) -> Result<(), ErrorResponse> {
+pub async fn set_refresh_mode(
+    maybe_core: State<'_, crate::MaybeCoreContext>, mode: String,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let refresh_mode = mode.parse::<RefreshMode>().unwrap_or(RefreshMode::Idle);
+    core.refresh_manager.set_mode(refresh_mode).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_refresh_mode(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<String, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let mode = core.refresh_manager.get_mode().await;
+    Ok(mode.as_str().to_string())
+}
+
+// Sent by the pipeline detail view so the open pipeline refreshes faster.
+#[tauri::command]
+pub async fn set_refresh_activity_hint(
+    maybe_core: State<'_, crate::MaybeCoreContext>, hint: ActivityHint,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.refresh_manager.set_activity_hint(hint).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn clear_run_history_cache(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .clear_run_history_cache(&pipeline_id)
+        .await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_cache_stats(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<CacheStats, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let pipelines_count = core
+        .pipeline_service
+        .get_pipelines_cache_count()
+        .await
+        .unwrap_or(0);
+    let run_history_count = core
+        .pipeline_service
+        .get_run_history_cache_count()
+        .await
+        .unwrap_or(0);
+    let workflow_params_count = core
+        .pipeline_service
+        .get_workflow_params_cache_count()
+        .await
+        .unwrap_or(0);
+
+    let metrics_count = if let Some(metrics_service) = &core.metrics_service {
+        metrics_service
+            .get_storage_stats()
+            .await
+            .map(|stats| stats.total_metrics_count)
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    let run_history_retention = core.retention_service.get_config().await.ok();
+
+    Ok(CacheStats {
+        pipelines_count,
+        run_history_count,
+        workflow_params_count,
+        metrics_count,
+        run_history_retention,
+    })
+}
+
+#[tauri::command]
+pub async fn clear_pipelines_cache(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<usize, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .clear_pipelines_cache()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn clear_all_run_history_caches(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service.clear_all_run_history_caches().await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn clear_workflow_params_cache(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .clear_workflow_params_cache()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn clear_all_caches(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.pipeline_service
+        .clear_all_caches_atomic()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_run_retention_config(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<GlobalRunRetentionConfig, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.retention_service
+        .get_config()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn update_run_retention_config(
+    maybe_core: State<'_, crate::MaybeCoreContext>, enabled: bool, max_age_days: Option<i64>,
+    max_runs_per_pipeline: Option<i64>,
+) -> Result<GlobalRunRetentionConfig, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let policy = RunRetentionPolicy {
+        max_age_days,
+        max_runs: max_runs_per_pipeline,
+    };
+    core.retention_service
+        .update_config(enabled, policy)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_pipeline_run_retention(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<Vec<PipelineRunRetention>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.retention_service
+        .list_pipeline_overrides()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn set_pipeline_run_retention(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String, max_age_days: Option<i64>,
+    max_runs: Option<i64>,
+) -> Result<(), ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let policy = RunRetentionPolicy {
+        max_age_days,
+        max_runs,
+    };
+    core.retention_service
+        .set_pipeline_override(&pipeline_id, policy)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn prune_run_history(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<usize, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    let refresh_mode = mode.parse::<RefreshMode>().unwrap_or(RefreshMode::Idle);
-    core.refresh_manager.set_mode(refresh_mode).await;
-    Ok(())
+    core.retention_service
+        .prune_run_history()
+        .await
+        .map_err(Into::into)
 }
 
 #[tauri::command]
-pub async fn get_refresh_mode(
+pub async fn list_backups(
     maybe_core: State<'_, crate::MaybeCoreContext>,
-) -> Result<String, ErrorResponse> {
+) -> Result<Vec<BackupInfo>, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    let mode = core.refresh_manager.get_mode().await;
-    Ok(mode.as_str().to_string())
+    core.backup_service.list_backups().await.map_err(Into::into)
 }
 
 #[tauri::command]
-pub async fn clear_run_history_cache(
-    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String,
-) -> Result<(), ErrorResponse> {
+pub async fn create_backup(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<BackupInfo, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    core.pipeline_service
-        .clear_run_history_cache(&pipeline_id)
-        .await;
-    Ok(())
+    core.backup_service
+        .create_backup()
+        .await
+        .map_err(Into::into)
 }
 
 #[tauri::command]
-pub async fn get_cache_stats(
-    maybe_core: State<'_, crate::MaybeCoreContext>,
-) -> Result<CacheStats, ErrorResponse> {
+pub async fn verify_backup(
+    maybe_core: State<'_, crate::MaybeCoreContext>, id: String,
+) -> Result<BackupVerification, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    let pipelines_count = core
-        .pipeline_service
-        .get_pipelines_cache_count()
-        .await
-        .unwrap_or(0);
-    let run_history_count = core
-        .pipeline_service
-        .get_run_history_cache_count()
-        .await
-        .unwrap_or(0);
-    let workflow_params_count = core
-        .pipeline_service
-        .get_workflow_params_cache_count()
+    core.backup_service
+        .verify_backup(&id)
         .await
-        .unwrap_or(0);
-
-    let metrics_count = if let Some(metrics_service) = &core.metrics_service {
-        metrics_service
-            .get_storage_stats()
-            .await
-            .map(|stats| stats.total_metrics_count)
-            .unwrap_or(0)
-    } else {
-        0
-    };
-
-    Ok(CacheStats {
-        pipelines_count,
-        run_history_count,
-        workflow_params_count,
-        metrics_count,
-    })
+        .map_err(Into::into)
 }
 
 #[tauri::command]
-pub async fn clear_pipelines_cache(
-    maybe_core: State<'_, crate::MaybeCoreContext>,
-) -> Result<usize, ErrorResponse> {
+pub async fn restore_backup(
+    maybe_core: State<'_, crate::MaybeCoreContext>, id: String,
+) -> Result<BackupInfo, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    core.pipeline_service
-        .clear_pipelines_cache()
+    core.backup_service
+        .restore_backup(&id)
         .await
         .map_err(Into::into)
 }
 
 #[tauri::command]
-pub async fn clear_all_run_history_caches(
-    maybe_core: State<'_, crate::MaybeCoreContext>,
-) -> Result<(), ErrorResponse> {
+pub async fn fetch_agents(
+    maybe_core: State<'_, crate::MaybeCoreContext>, provider_id: Option<i64>,
+) -> Result<Vec<ProviderAgent>, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    core.pipeline_service.clear_all_run_history_caches().await;
-    Ok(())
+    core.agent_service
+        .fetch_agents(provider_id)
+        .await
+        .map_err(Into::into)
 }
 
 #[tauri::command]
-pub async fn clear_workflow_params_cache(
-    maybe_core: State<'_, crate::MaybeCoreContext>,
-) -> Result<(), ErrorResponse> {
+pub async fn get_cached_agents(
+    maybe_core: State<'_, crate::MaybeCoreContext>, provider_id: Option<i64>,
+) -> Result<Vec<ProviderAgent>, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    core.pipeline_service
-        .clear_workflow_params_cache()
+    core.agent_service
+        .get_cached_agents(provider_id)
         .await
         .map_err(Into::into)
 }
 
 #[tauri::command]
-pub async fn clear_all_caches(
-    maybe_core: State<'_, crate::MaybeCoreContext>,
-) -> Result<(), ErrorResponse> {
+pub async fn fetch_queues(
+    maybe_core: State<'_, crate::MaybeCoreContext>, provider_id: Option<i64>,
+) -> Result<Vec<ProviderQueue>, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
         details: None,
     })?;
-    core.pipeline_service
-        .clear_all_caches_atomic()
+    core.queue_service
+        .fetch_queues(provider_id)
         .await
         .map_err(Into::into)
 }
@@ -908,6 +2231,8 @@ pub async fn query_pipeline_metrics(
 
     let query = MetricsQuery {
         pipeline_id,
+        pipeline_ids: None,
+        labels: Vec::new(),
         metric_type: parsed_metric_type,
         start_date: parsed_start_date,
         end_date: parsed_end_date,
@@ -928,6 +2253,7 @@ pub async fn query_aggregated_metrics(
     maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: Option<String>,
     metric_type: String, aggregation_period: String, aggregation_type: Option<String>,
     start_date: Option<String>, end_date: Option<String>, limit: Option<usize>,
+    labels: Option<Vec<String>>,
 ) -> Result<AggregatedMetrics, ErrorResponse> {
     let core = maybe_core.get().await.map_err(|e| ErrorResponse {
         error: e,
@@ -983,6 +2309,8 @@ pub async fn query_aggregated_metrics(
 
     let query = MetricsQuery {
         pipeline_id,
+        pipeline_ids: None,
+        labels: labels.unwrap_or_default(),
         metric_type: Some(parsed_metric_type),
         start_date: parsed_start_date,
         end_date: parsed_end_date,
@@ -997,6 +2325,76 @@ pub async fn query_aggregated_metrics(
         .map_err(Into::into)
 }
 
+#[tauri::command]
+pub async fn query_flaky_pipelines(
+    maybe_core: State<'_, crate::MaybeCoreContext>, min_score: Option<f64>, limit: Option<usize>,
+) -> Result<Vec<FlakyPipeline>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let metrics_service = core.metrics_service.as_ref().ok_or_else(|| ErrorResponse {
+        error: "Metrics service not available".to_string(),
+        details: None,
+    })?;
+
+    metrics_service
+        .query_flaky_pipelines(min_score, limit)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_metric_anomalies(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<MetricAnomaly>, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let metrics_service = core.metrics_service.as_ref().ok_or_else(|| ErrorResponse {
+        error: "Metrics service not available".to_string(),
+        details: None,
+    })?;
+
+    metrics_service
+        .list_metric_anomalies(pipeline_id.as_deref(), limit)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn estimate_costs(
+    maybe_core: State<'_, crate::MaybeCoreContext>, group_by: Option<CostGroupBy>,
+    start_date: Option<String>, end_date: Option<String>,
+) -> Result<CostReport, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    let metrics_service = core.metrics_service.as_ref().ok_or_else(|| ErrorResponse {
+        error: "Metrics service not available".to_string(),
+        details: None,
+    })?;
+
+    let parsed_start_date = start_date
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&chrono::Utc));
+    let parsed_end_date = end_date
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&chrono::Utc));
+
+    metrics_service
+        .estimate_costs(
+            group_by.unwrap_or_default(),
+            parsed_start_date,
+            parsed_end_date,
+        )
+        .await
+        .map_err(Into::into)
+}
+
 #[tauri::command]
 pub async fn get_metrics_storage_stats(
     maybe_core: State<'_, crate::MaybeCoreContext>,
@@ -1035,6 +2433,21 @@ pub async fn flush_pipeline_metrics(
         .map_err(Into::into)
 }
 
+#[tauri::command]
+pub async fn rebuild_metrics(
+    maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: Option<String>,
+) -> Result<MetricsRebuild, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+
+    core.pipeline_service
+        .rebuild_metrics(pipeline_id.as_deref())
+        .await
+        .map_err(Into::into)
+}
+
 #[tauri::command]
 pub async fn reset_metrics_processing_state(
     maybe_core: State<'_, crate::MaybeCoreContext>, pipeline_id: String,
@@ -1150,6 +2563,14 @@ pub async fn get_default_table_preferences(
     })
 }
 
+use pipedash_core::infrastructure::config::{
+    versions,
+    write_config,
+    ConfigBundle,
+    ConfigVersion,
+    ImportOptions,
+    ImportReport,
+};
 use pipedash_core::infrastructure::{
     ConfigLoader,
     MigrationOptions,
@@ -1179,16 +2600,14 @@ pub async fn bootstrap_app(
 ) -> Result<(), ErrorResponse> {
     use std::sync::Arc;
 
+    use pipedash_core::infrastructure::secrets::KeyringTokenStore;
     use pipedash_core::infrastructure::{
         config::ConfigLoader,
         StorageManager,
     };
     use tauri::Manager;
 
-    use crate::{
-        keyring_store::KeyringTokenStore,
-        tauri_event_bus::create_tauri_event_bus,
-    };
+    use crate::tauri_event_bus::create_tauri_event_bus;
 
     tracing::info!("Bootstrapping application after initial setup");
 
@@ -1203,7 +2622,8 @@ pub async fn bootstrap_app(
 
     let use_keyring = config.storage.backend.is_sqlite();
     let storage_manager = if use_keyring {
-        let token_store = Arc::new(KeyringTokenStore::new());
+        let token_store =
+            Arc::new(KeyringTokenStore::new().with_layout(config.storage.keyring_layout));
         StorageManager::with_token_store(config.clone(), token_store, true)
             .await
             .map_err(|e| ErrorResponse {
@@ -1529,6 +2949,28 @@ pub async fn lock_vault() -> Result<UnlockVaultResponse, ErrorResponse> {
     })
 }
 
+#[tauri::command]
+pub async fn rotate_vault_password(
+    app_data_dir: State<'_, AppDataDir>, maybe_core: State<'_, crate::MaybeCoreContext>,
+    current_password: String, new_password: String,
+) -> Result<usize, ErrorResponse> {
+    let config = load_config_from_dir(&app_data_dir.0)?;
+    if config.storage.vault_password.is_some() {
+        return Err(ErrorResponse {
+            error: "Vault password is set in the config file; change it there instead".to_string(),
+            details: None,
+        });
+    }
+
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.rotate_vault_password(&current_password, &new_password)
+        .await
+        .map_err(Into::into)
+}
+
 #[tauri::command]
 pub async fn save_storage_config(
     _core: State<'_, Arc<CoreContext>>, app_data_dir: State<'_, AppDataDir>,
@@ -1603,7 +3045,7 @@ pub async fn save_config_content(
     }
 
     let config_path = app_data_dir.0.join("config.toml");
-    std::fs::write(&config_path, &content).map_err(|e| ErrorResponse {
+    write_config(&config_path, &content).map_err(|e| ErrorResponse {
         error: format!("Failed to save config file: {}", e),
         details: None,
     })?;
@@ -1612,6 +3054,105 @@ pub async fn save_config_content(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn list_config_versions(
+    app_data_dir: State<'_, AppDataDir>,
+) -> Result<Vec<ConfigVersion>, ErrorResponse> {
+    Ok(versions::list_config_versions(
+        &app_data_dir.0.join("config.toml"),
+    )?)
+}
+
+// A running core picks the restored file up through its config watcher.
+#[tauri::command]
+pub async fn restore_config_version(
+    app_data_dir: State<'_, AppDataDir>, version_id: String,
+) -> Result<(), ErrorResponse> {
+    Ok(versions::restore_config_version(
+        &app_data_dir.0.join("config.toml"),
+        &version_id,
+    )?)
+}
+
+#[tauri::command]
+pub async fn get_run_in_background(
+    background_mode: State<'_, crate::BackgroundMode>,
+) -> Result<bool, ErrorResponse> {
+    Ok(background_mode.0.load(std::sync::atomic::Ordering::Relaxed))
+}
+
+#[tauri::command]
+pub async fn set_run_in_background(
+    background_mode: State<'_, crate::BackgroundMode>, app_data_dir: State<'_, AppDataDir>,
+    enabled: bool,
+) -> Result<(), ErrorResponse> {
+    let config_path = app_data_dir.0.join("config.toml");
+
+    // Parsed raw so `${VAR}` references are written back untouched.
+    let content = std::fs::read_to_string(&config_path).map_err(|e| ErrorResponse {
+        error: format!("Failed to read config file: {}", e),
+        details: None,
+    })?;
+    let mut config = ConfigLoader::parse_raw(&content).map_err(|e| ErrorResponse {
+        error: format!("Failed to parse config file: {}", e),
+        details: None,
+    })?;
+    config.desktop.run_in_background = enabled;
+
+    ConfigLoader::save(&config, &config_path).map_err(|e| ErrorResponse {
+        error: format!("Failed to save config file: {}", e),
+        details: None,
+    })?;
+
+    background_mode
+        .0
+        .store(enabled, std::sync::atomic::Ordering::Relaxed);
+    tracing::info!("Run in background set to {}", enabled);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn take_pending_deep_link(
+    pending: State<'_, crate::deep_link::PendingDeepLink>,
+) -> Result<Option<String>, ErrorResponse> {
+    Ok(pending.take())
+}
+
+#[tauri::command]
+pub async fn export_configuration(
+    maybe_core: State<'_, crate::MaybeCoreContext>,
+) -> Result<ConfigBundle, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.config_bundle_service
+        .export_bundle()
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn import_configuration(
+    maybe_core: State<'_, crate::MaybeCoreContext>, bundle: ConfigBundle, dry_run: bool,
+    tokens: Option<HashMap<String, String>>,
+) -> Result<ImportReport, ErrorResponse> {
+    let core = maybe_core.get().await.map_err(|e| ErrorResponse {
+        error: e,
+        details: None,
+    })?;
+    core.config_bundle_service
+        .import_bundle(
+            bundle,
+            ImportOptions {
+                dry_run,
+                tokens: tokens.unwrap_or_default(),
+            },
+        )
+        .await
+        .map_err(Into::into)
+}
+
 #[derive(Debug, Serialize)]
 pub struct StoragePathsResponse {
     pub config_file: String,
@@ -1999,3 +3540,66 @@ pub async fn restart_app(app: tauri::AppHandle) -> Result<(), ErrorResponse> {
     #[allow(unreachable_code)]
     Ok(())
 }
+
+// Works before setup finishes or after the core failed to start, so the
+// frontend passes along the error it got from `bootstrap_app`.
+#[tauri::command]
+pub async fn create_diagnostic_bundle(
+    app: tauri::AppHandle, app_data_dir: State<'_, AppDataDir>,
+    maybe_core: State<'_, crate::MaybeCoreContext>, startup_error: Option<String>,
+) -> Result<Option<String>, ErrorResponse> {
+    use pipedash_core::application::DiagnosticBundle;
+
+    let mut bundle = DiagnosticBundle::new();
+    if let Some(error) = &startup_error {
+        bundle = bundle.with_startup_error(error);
+    }
+    bundle = match maybe_core.get().await {
+        Ok(core) => bundle.with_core(&core).await,
+        Err(_) => bundle.with_config_file(&app_data_dir.0.join("config.toml")),
+    };
+    let zip = bundle.into_zip()?;
+
+    let Some(path) = pick_save_path(&app, DiagnosticBundle::file_name()).await? else {
+        return Ok(None);
+    };
+    tokio::fs::write(&path, zip)
+        .await
+        .map_err(|e| ErrorResponse {
+            error: format!("Failed to write diagnostic bundle: {}", e),
+            details: None,
+        })?;
+
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+#[tauri::command]
+pub async fn get_app_logs(
+    level: Option<pipedash_core::logging::LogLevel>, since: Option<String>, until: Option<String>,
+    limit: Option<usize>,
+) -> Vec<pipedash_core::logging::AppLogEntry> {
+    let parse_date = |date: Option<String>| {
+        date.and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+    };
+
+    pipedash_core::logging::app_logs(&pipedash_core::logging::AppLogQuery {
+        level,
+        since: parse_date(since),
+        until: parse_date(until),
+        limit,
+    })
+}
+
+#[tauri::command]
+pub async fn get_log_level() -> Result<String, ErrorResponse> {
+    pipedash_core::logging::log_level().ok_or_else(|| ErrorResponse {
+        error: "Logging is not initialized".to_string(),
+        details: None,
+    })
+}
+
+#[tauri::command]
+pub async fn set_log_level(filter: String) -> Result<(), ErrorResponse> {
+    Ok(pipedash_core::logging::set_log_level(&filter)?)
+}
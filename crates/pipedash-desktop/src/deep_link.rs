@@ -0,0 +1,51 @@
+use std::sync::Mutex;
+
+use pipedash_core::domain::{
+    PipelineLink,
+    DEEP_LINK_SCHEME,
+};
+use tauri::{
+    AppHandle,
+    Emitter,
+    Manager,
+    Runtime,
+};
+
+pub const DEEP_LINK_EVENT: &str = "deep-link-opened";
+
+// Frontend route of the last opened link, kept until the webview asks for it
+// since a freshly created window is not listening yet.
+#[derive(Default)]
+pub struct PendingDeepLink(Mutex<Option<String>>);
+
+impl PendingDeepLink {
+    pub fn take(&self) -> Option<String> {
+        self.0.lock().ok().and_then(|mut path| path.take())
+    }
+}
+
+// Windows and Linux launch the app with the URL as an argument.
+pub fn from_args() -> Option<String> {
+    let prefix = format!("{}://", DEEP_LINK_SCHEME);
+    std::env::args()
+        .skip(1)
+        .find(|arg| arg.starts_with(&prefix))
+}
+
+pub fn open<R: Runtime>(app: &AppHandle<R>, url: &str) {
+    let link = match PipelineLink::parse(url) {
+        Ok(link) => link,
+        Err(e) => {
+            tracing::warn!("Ignoring deep link: {}", e);
+            return;
+        }
+    };
+
+    if let Some(pending) = app.try_state::<PendingDeepLink>() {
+        if let Ok(mut path) = pending.0.lock() {
+            *path = Some(link.app_path());
+        }
+    }
+    crate::tray::show_main_window(app);
+    let _ = app.emit(DEEP_LINK_EVENT, ());
+}
@@ -1,14 +1,19 @@
 mod commands;
-pub mod fallback_store;
-pub mod keyring_store;
+mod deep_link;
 pub mod tauri_event_bus;
+mod tray;
 
 use std::path::{
     Path,
     PathBuf,
 };
+use std::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
 use std::sync::Arc;
 
+use pipedash_core::application::ActivityHint;
 use pipedash_core::CoreContext;
 use tauri::Manager;
 
@@ -82,7 +87,7 @@ backend = "sqlite"
         )
     };
 
-    std::fs::write(config_path, &config_content)
+    pipedash_core::infrastructure::config::write_config(config_path, &config_content)
         .map_err(|e| format!("Failed to write config: {}", e))?;
 
     if is_legacy_migration {
@@ -197,6 +202,57 @@ fn restore_database_backups(backup_info: &BackupInfo) -> Result<(), String> {
 
 pub struct AppDataDir(pub PathBuf);
 
+// `[desktop] run_in_background`: closing the window frees the webview but
+// keeps the core (refresh loop, notifications, tray) running until Quit.
+pub struct BackgroundMode(pub AtomicBool);
+
+fn runs_in_background<R: tauri::Runtime, M: Manager<R>>(manager: &M) -> bool {
+    manager
+        .try_state::<BackgroundMode>()
+        .is_some_and(|mode| mode.0.load(Ordering::Relaxed))
+}
+
+fn shutdown_core<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(maybe_core) = app.try_state::<MaybeCoreContext>() else {
+        return;
+    };
+    let maybe_core = Arc::clone(&maybe_core.0);
+
+    tauri::async_runtime::block_on(async move {
+        if let Some(core) = maybe_core.read().await.clone() {
+            core.shutdown().await;
+            tracing::info!("Core services stopped");
+        }
+    });
+}
+
+// Minimized or hidden windows drop the refresh loop to its background cadence.
+fn handle_window_event<R: tauri::Runtime>(window: &tauri::Window<R>, event: &tauri::WindowEvent) {
+    let hidden = match event {
+        tauri::WindowEvent::Destroyed => true,
+        tauri::WindowEvent::Focused(true) => false,
+        tauri::WindowEvent::Focused(false) | tauri::WindowEvent::Resized(_) => {
+            window.is_minimized().unwrap_or(false) || !window.is_visible().unwrap_or(true)
+        }
+        _ => return,
+    };
+    let Some(maybe_core) = window.try_state::<MaybeCoreContext>() else {
+        return;
+    };
+    let maybe_core = Arc::clone(&maybe_core.0);
+    let hint = if hidden {
+        ActivityHint::WindowHidden
+    } else {
+        ActivityHint::WindowVisible
+    };
+
+    tauri::async_runtime::spawn(async move {
+        if let Some(core) = maybe_core.read().await.clone() {
+            core.refresh_manager.set_activity_hint(hint).await;
+        }
+    });
+}
+
 pub struct MaybeCoreContext(pub Arc<tokio::sync::RwLock<Option<Arc<CoreContext>>>>);
 
 impl MaybeCoreContext {
@@ -211,73 +267,180 @@ impl MaybeCoreContext {
 
 use commands::{
     add_provider,
+    approve_trigger,
+    assign_pipeline_to_group,
     bootstrap_app,
+    cancel_pipeline_preview,
     cancel_pipeline_run,
+    cancel_run_backfill,
+    cancel_runs_bulk,
     check_database_exists,
     check_provider_permissions,
     check_setup_status,
     clear_all_caches,
     clear_all_run_history_caches,
+    clear_notifications,
     clear_pipelines_cache,
     clear_run_history_cache,
     clear_workflow_params_cache,
+    compare_runs,
+    create_backup,
+    create_diagnostic_bundle,
+    create_group,
     create_initial_config,
+    delete_group,
+    delete_maintenance_window,
+    delete_notification_channel,
+    delete_pipeline_dependency,
+    delete_trigger_preset,
+    delete_view,
+    diagnose_provider,
+    disable_provider,
+    discover_provider_repositories,
+    download_artifact,
+    enable_provider,
+    estimate_costs,
     execute_storage_migration,
+    export_configuration,
+    export_metrics,
+    export_release_report,
+    export_run_history,
     factory_reset,
+    fetch_agents,
+    fetch_branch_statuses,
     fetch_pipelines,
     fetch_provider_organizations,
+    fetch_queues,
     fetch_run_history,
     flush_pipeline_metrics,
+    generate_release_report,
+    get_app_logs,
     get_available_plugins,
     get_cache_stats,
+    get_cached_agents,
     get_cached_pipelines,
+    get_commit_status,
     get_config_content,
     get_default_data_dir,
     get_default_table_preferences,
     get_effective_data_dir,
     get_global_metrics_config,
+    get_log_level,
     get_metrics_storage_stats,
+    get_notification_deliveries,
+    get_pipeline_graph,
     get_pipeline_metrics_config,
     get_provider,
+    get_provider_diagnoses,
     get_provider_features,
     get_provider_field_options,
+    get_provider_health,
     get_provider_permissions,
     get_provider_table_schema,
     get_refresh_mode,
+    get_run_in_background,
+    get_run_logs,
+    get_run_retention_config,
     get_storage_config,
     get_storage_paths,
     get_table_preferences,
+    get_tray_summary,
+    get_unread_notification_count,
     get_vault_password_status,
     get_vault_status,
     get_workflow_parameters,
     get_workflow_run_details,
+    import_configuration,
+    list_approval_rules,
+    list_backups,
+    list_config_versions,
+    list_groups,
+    list_loaded_plugins,
+    list_maintenance_windows,
+    list_metric_anomalies,
+    list_notification_channels,
+    list_notifications,
+    list_pending_approvals,
+    list_pinned_pipelines,
+    list_pipeline_dependencies,
+    list_pipeline_run_retention,
     list_plugin_metadata,
     list_providers,
+    list_refresh_settings,
+    list_run_artifacts,
+    list_run_backfills,
+    list_trigger_approvals,
+    list_trigger_presets,
+    list_views,
     lock_vault,
+    mark_notification_read,
+    pin_pipeline,
     plan_storage_migration,
+    poll_provider_oauth,
     preview_provider_pipelines,
+    prune_run_history,
     query_aggregated_metrics,
+    query_flaky_pipelines,
     query_pipeline_metrics,
+    query_view,
+    quick_actions,
+    rebuild_metrics,
     refresh_all,
+    reject_trigger,
+    remove_pipeline_from_group,
     remove_provider,
     reset_metrics_processing_state,
     restart_app,
+    restore_backup,
+    restore_config_version,
+    retry_pipeline_run,
+    retry_runs_bulk,
+    rotate_vault_password,
     save_config_content,
+    save_maintenance_window,
+    save_notification_channel,
+    save_pipeline_dependency,
     save_storage_config,
     save_table_preferences,
+    save_trigger_preset,
+    save_view,
+    search_everything,
+    set_log_level,
+    set_pipeline_approval,
+    set_pipeline_labels,
+    set_pipeline_refresh,
+    set_pipeline_run_retention,
+    set_refresh_activity_hint,
     set_refresh_mode,
+    set_run_in_background,
+    set_tray_pipelines,
+    start_log_tail,
+    start_pipeline_preview,
+    start_provider_oauth,
+    start_run_backfill,
+    stop_log_tail,
+    take_pending_deep_link,
+    test_notification_channel,
     test_storage_connection,
     trigger_pipeline,
+    trigger_pipelines_bulk,
+    trigger_with_preset,
+    unblock_job,
     unlock_vault,
+    unpin_pipeline,
     update_global_metrics_config,
     update_pipeline_metrics_config,
     update_provider,
     update_provider_refresh_interval,
+    update_run_retention_config,
     validate_provider_credentials,
     validate_storage_config,
+    verify_backup,
+};
+use pipedash_core::infrastructure::secrets::{
+    FallbackTokenStore,
+    KeyringTokenStore,
 };
-use fallback_store::FallbackTokenStore;
-use keyring_store::KeyringTokenStore;
 use tauri_event_bus::create_tauri_event_bus;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -384,7 +547,7 @@ pub fn run() {
                     let create_storage_manager = || async {
                         if use_keyring {
                             let token_store: Arc<dyn pipedash_core::infrastructure::TokenStore> =
-                                Arc::new(KeyringTokenStore::new());
+                                Arc::new(KeyringTokenStore::new().with_layout(config.storage.keyring_layout));
                             pipedash_core::infrastructure::StorageManager::with_token_store(
                                 config.clone(),
                                 token_store,
@@ -420,7 +583,7 @@ pub fn run() {
                                 Arc::new(SqliteTokenStore::new(pool, None).await?);
 
                             let fallback_store: Arc<dyn pipedash_core::infrastructure::TokenStore> =
-                                Arc::new(KeyringTokenStore::new());
+                                Arc::new(KeyringTokenStore::new().with_layout(config.storage.keyring_layout));
 
                             let token_store: Arc<dyn pipedash_core::infrastructure::TokenStore> =
                                 Arc::new(FallbackTokenStore::new(primary_store, fallback_store));
@@ -555,10 +718,24 @@ pub fn run() {
 
             app.manage(MaybeCoreContext(maybe_core_context));
 
+            let run_in_background = ConfigLoader::load(&config_path)
+                .map(|config| config.desktop.run_in_background)
+                .unwrap_or(false);
+            app.manage(BackgroundMode(AtomicBool::new(run_in_background)));
+            app.manage(tray::TrayState::load(&app_data_dir));
+            app.manage(deep_link::PendingDeepLink::default());
             app.manage(AppDataDir(app_data_dir));
 
+            if let Err(e) = tray::create_tray(app.handle()) {
+                tracing::warn!("Failed to create tray icon: {}", e);
+            }
+            if let Some(url) = deep_link::from_args() {
+                deep_link::open(app.handle(), &url);
+            }
+
             Ok(())
         })
+        .on_window_event(handle_window_event)
         .invoke_handler(tauri::generate_handler![
             check_setup_status,
             create_initial_config,
@@ -568,41 +745,137 @@ pub fn run() {
             get_provider,
             update_provider,
             update_provider_refresh_interval,
+            enable_provider,
+            disable_provider,
+            start_run_backfill,
+            cancel_run_backfill,
+            list_run_backfills,
             remove_provider,
             get_available_plugins,
             list_plugin_metadata,
+            list_loaded_plugins,
             get_provider_field_options,
             fetch_provider_organizations,
             preview_provider_pipelines,
+            start_pipeline_preview,
+            cancel_pipeline_preview,
+            discover_provider_repositories,
             validate_provider_credentials,
             check_provider_permissions,
             get_provider_permissions,
+            get_provider_health,
+            diagnose_provider,
+            get_provider_diagnoses,
+            start_provider_oauth,
+            poll_provider_oauth,
             get_provider_features,
             get_provider_table_schema,
             fetch_pipelines,
             get_cached_pipelines,
             fetch_run_history,
             get_workflow_run_details,
+            fetch_branch_statuses,
+            get_run_logs,
+            compare_runs,
+            generate_release_report,
+            export_release_report,
+            list_groups,
+            create_group,
+            delete_group,
+            assign_pipeline_to_group,
+            remove_pipeline_from_group,
+            list_views,
+            save_view,
+            delete_view,
+            query_view,
+            set_pipeline_labels,
+            list_refresh_settings,
+            set_pipeline_refresh,
+            list_pinned_pipelines,
+            pin_pipeline,
+            unpin_pipeline,
+            get_tray_summary,
+            set_tray_pipelines,
+            get_run_in_background,
+            set_run_in_background,
+            take_pending_deep_link,
+            search_everything,
+            get_commit_status,
+            quick_actions,
+            start_log_tail,
+            stop_log_tail,
+            list_run_artifacts,
+            download_artifact,
             trigger_pipeline,
             cancel_pipeline_run,
+            retry_pipeline_run,
+            list_pending_approvals,
+            unblock_job,
+            trigger_pipelines_bulk,
+            cancel_runs_bulk,
+            retry_runs_bulk,
+            list_trigger_approvals,
+            approve_trigger,
+            reject_trigger,
+            list_approval_rules,
+            set_pipeline_approval,
             get_workflow_parameters,
+            list_trigger_presets,
+            save_trigger_preset,
+            delete_trigger_preset,
+            trigger_with_preset,
+            list_pipeline_dependencies,
+            save_pipeline_dependency,
+            delete_pipeline_dependency,
+            get_pipeline_graph,
+            list_maintenance_windows,
+            save_maintenance_window,
+            delete_maintenance_window,
+            list_notification_channels,
+            save_notification_channel,
+            delete_notification_channel,
+            test_notification_channel,
+            get_notification_deliveries,
+            list_notifications,
+            get_unread_notification_count,
+            mark_notification_read,
+            clear_notifications,
             refresh_all,
             set_refresh_mode,
             get_refresh_mode,
+            set_refresh_activity_hint,
             clear_run_history_cache,
             get_cache_stats,
             clear_pipelines_cache,
             clear_all_run_history_caches,
             clear_workflow_params_cache,
             clear_all_caches,
+            get_run_retention_config,
+            update_run_retention_config,
+            list_pipeline_run_retention,
+            set_pipeline_run_retention,
+            prune_run_history,
+            list_backups,
+            create_backup,
+            verify_backup,
+            restore_backup,
+            fetch_agents,
+            get_cached_agents,
+            fetch_queues,
             get_global_metrics_config,
             update_global_metrics_config,
             get_pipeline_metrics_config,
             update_pipeline_metrics_config,
             query_pipeline_metrics,
             query_aggregated_metrics,
+            query_flaky_pipelines,
+            list_metric_anomalies,
+            estimate_costs,
+            export_metrics,
+            export_run_history,
             get_metrics_storage_stats,
             flush_pipeline_metrics,
+            rebuild_metrics,
             reset_metrics_processing_state,
             get_table_preferences,
             save_table_preferences,
@@ -612,9 +885,14 @@ pub fn run() {
             get_vault_status,
             unlock_vault,
             lock_vault,
+            rotate_vault_password,
             save_storage_config,
             get_config_content,
             save_config_content,
+            list_config_versions,
+            restore_config_version,
+            export_configuration,
+            import_configuration,
             get_storage_paths,
             get_default_data_dir,
             get_effective_data_dir,
@@ -624,8 +902,26 @@ pub fn run() {
             plan_storage_migration,
             execute_storage_migration,
             factory_reset,
+            create_diagnostic_bundle,
+            get_app_logs,
+            get_log_level,
+            set_log_level,
             restart_app,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            // No exit code means the last window was closed.
+            tauri::RunEvent::ExitRequested {
+                code: None, api, ..
+            } if runs_in_background(app) => api.prevent_exit(),
+            #[cfg(any(target_os = "macos", target_os = "ios"))]
+            tauri::RunEvent::Opened { urls } => {
+                for url in urls {
+                    deep_link::open(app, url.as_str());
+                }
+            }
+            tauri::RunEvent::Exit => shutdown_core(app),
+            _ => {}
+        });
 }
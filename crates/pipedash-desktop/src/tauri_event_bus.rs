@@ -31,6 +31,13 @@ impl<R: Runtime + 'static> EventBus for TauriEventBus<R> {
         if let Err(e) = self.app_handle.emit(event_name, event.to_json_payload()) {
             tracing::error!("Failed to emit event '{}': {}", event_name, e);
         }
+
+        if matches!(
+            event,
+            CoreEvent::PipelineStatusChanged { .. } | CoreEvent::PipelinesUpdated { .. }
+        ) {
+            crate::tray::schedule_update(&self.app_handle);
+        }
     }
 
     async fn emit_to(&self, target: &str, event: CoreEvent) {
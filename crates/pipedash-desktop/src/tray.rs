@@ -0,0 +1,194 @@
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::sync::{
+    Arc,
+    RwLock,
+};
+
+use pipedash_core::domain::StatusSummary;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use tauri::menu::{
+    Menu,
+    MenuItem,
+};
+use tauri::tray::{
+    MouseButton,
+    MouseButtonState,
+    TrayIconBuilder,
+    TrayIconEvent,
+};
+use tauri::{
+    AppHandle,
+    Manager,
+    Runtime,
+    WebviewWindowBuilder,
+};
+
+use crate::MaybeCoreContext;
+
+const TRAY_ID: &str = "pipedash";
+const SETTINGS_FILE: &str = "tray.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TraySettings {
+    // Unset follows the pinned pipelines.
+    #[serde(default)]
+    pipeline_ids: Option<Vec<String>>,
+}
+
+// Which pipelines the tray summarizes, persisted next to the config.
+pub struct TrayState {
+    path: PathBuf,
+    settings: RwLock<TraySettings>,
+}
+
+impl TrayState {
+    pub fn load(app_data_dir: &Path) -> Self {
+        let path = app_data_dir.join(SETTINGS_FILE);
+        let settings = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            path,
+            settings: RwLock::new(settings),
+        }
+    }
+
+    pub fn pipeline_ids(&self) -> Option<Vec<String>> {
+        self.settings
+            .read()
+            .ok()
+            .and_then(|settings| settings.pipeline_ids.clone())
+    }
+
+    pub fn set_pipeline_ids(&self, pipeline_ids: Option<Vec<String>>) -> Result<(), String> {
+        let mut settings = self
+            .settings
+            .write()
+            .map_err(|_| "Tray settings lock poisoned".to_string())?;
+        settings.pipeline_ids = pipeline_ids;
+
+        let content = serde_json::to_string_pretty(&*settings)
+            .map_err(|e| format!("Failed to serialize tray settings: {}", e))?;
+        std::fs::write(&self.path, content)
+            .map_err(|e| format!("Failed to save tray settings: {}", e))
+    }
+}
+
+// Recreates the webview when it was closed in background mode.
+pub fn show_main_window<R: Runtime>(app: &AppHandle<R>) {
+    let window = match app.get_webview_window("main") {
+        Some(window) => window,
+        None => {
+            let Some(config) = app.config().app.windows.first() else {
+                return;
+            };
+            match WebviewWindowBuilder::from_config(app, config).and_then(|b| b.build()) {
+                Ok(window) => window,
+                Err(e) => {
+                    tracing::warn!("Failed to reopen the main window: {}", e);
+                    return;
+                }
+            }
+        }
+    };
+
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
+}
+
+pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    let show = MenuItem::with_id(app, "show", "Show Pipedash", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&show, &quit])?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Pipedash")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "show" => show_main_window(app),
+            // Core services are stopped on `RunEvent::Exit`.
+            "quit" => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    schedule_update(app);
+    Ok(())
+}
+
+pub async fn summary<R: Runtime>(app: &AppHandle<R>) -> Result<StatusSummary, String> {
+    let maybe_core = app
+        .try_state::<MaybeCoreContext>()
+        .map(|state| Arc::clone(&state.0))
+        .ok_or_else(|| "Application not initialized".to_string())?;
+    let core = maybe_core
+        .read()
+        .await
+        .clone()
+        .ok_or_else(|| "Application not initialized".to_string())?;
+    let pipeline_ids = app
+        .try_state::<TrayState>()
+        .and_then(|state| state.pipeline_ids());
+
+    core.pipeline_service
+        .status_summary(pipeline_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn label(summary: &StatusSummary) -> String {
+    if summary.total == 0 {
+        "No pinned pipelines".to_string()
+    } else if summary.failed > 0 {
+        format!("{} of {} failing", summary.failed, summary.total)
+    } else if summary.running > 0 {
+        format!("{} of {} running", summary.running, summary.total)
+    } else {
+        format!("{} passing", summary.total)
+    }
+}
+
+async fn update<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let summary = summary(app).await?;
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Ok(());
+    };
+
+    tray.set_tooltip(Some(format!("Pipedash: {}", label(&summary))))
+        .map_err(|e| e.to_string())?;
+    // Only shown next to the icon on macOS.
+    let title = (summary.failed > 0 || summary.running > 0).then(|| label(&summary));
+    tray.set_title(title).map_err(|e| e.to_string())
+}
+
+pub fn schedule_update<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = update(&app).await {
+            tracing::debug!("Failed to update tray: {}", e);
+        }
+    });
+}
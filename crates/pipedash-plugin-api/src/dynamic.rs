@@ -0,0 +1,38 @@
+use crate::plugin::Plugin;
+
+/// Bumped whenever the `Plugin` trait or the declaration layout changes in a
+/// way that breaks previously compiled out-of-tree plugins.
+pub const PLUGIN_API_VERSION: u32 = 14;
+
+pub const SDK_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const PLUGIN_DECLARATION_SYMBOL: &[u8] = b"PIPEDASH_PLUGIN_DECLARATION\0";
+
+/// Entry point exported by a dynamically loaded provider plugin.
+///
+/// Rust trait objects have no stable ABI, so plugins must be built against the
+/// same SDK version and toolchain as the host application.
+pub struct PluginDeclaration {
+    pub api_version: u32,
+    pub sdk_version: &'static str,
+    pub create: fn() -> Box<dyn Plugin>,
+}
+
+impl PluginDeclaration {
+    pub fn is_compatible(&self) -> bool {
+        self.api_version == PLUGIN_API_VERSION && self.sdk_version == SDK_VERSION
+    }
+}
+
+#[macro_export]
+macro_rules! export_plugin {
+    ($plugin_type:ty) => {
+        #[no_mangle]
+        pub static PIPEDASH_PLUGIN_DECLARATION: $crate::dynamic::PluginDeclaration =
+            $crate::dynamic::PluginDeclaration {
+                api_version: $crate::dynamic::PLUGIN_API_VERSION,
+                sdk_version: $crate::dynamic::SDK_VERSION,
+                create: || Box::new(<$plugin_type>::default()),
+            };
+    };
+}
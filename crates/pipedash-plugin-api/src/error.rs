@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,6 +10,10 @@ pub enum PluginError {
     #[error("API error: {0}")]
     ApiError(String),
 
+    // The credentials are valid but lack a permission the request needs.
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 
@@ -20,6 +26,19 @@ pub enum PluginError {
     #[error("Network error: {0}")]
     NetworkError(String),
 
+    // The provider asked us to slow down, usually with a 429. `retry_after`
+    // comes from the Retry-After header when it was sent.
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        retry_after: Option<Duration>,
+    },
+
+    // The response body was bigger than the provider's `max_response_mb`.
+    // Not retried: asking again would return the same body.
+    #[error("Response from {url} exceeded the {limit} byte limit")]
+    ResponseTooLarge { url: String, limit: usize },
+
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
@@ -35,6 +54,25 @@ pub enum PluginError {
 
 pub type PluginResult<T> = Result<T, PluginError>;
 
+impl PluginError {
+    // Failures that may go away on their own, so the request is worth retrying.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            PluginError::NetworkError(_)
+                | PluginError::ApiError(_)
+                | PluginError::RateLimited { .. }
+        )
+    }
+
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            PluginError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
 impl From<serde_json::Error> for PluginError {
     fn from(err: serde_json::Error) -> Self {
         PluginError::SerializationError(err.to_string())
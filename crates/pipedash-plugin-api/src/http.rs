@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::time::Duration;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use reqwest::header::{
+    HeaderMap,
+    HeaderValue,
+    ETAG,
+    IF_MODIFIED_SINCE,
+    IF_NONE_MATCH,
+    LAST_MODIFIED,
+    RETRY_AFTER,
+};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+
+use crate::schema::MAX_RESPONSE_SIZE_KEY;
+use crate::{
+    PluginError,
+    PluginResult,
+};
+
+const MAX_ENTRIES: usize = 1024;
+
+// Used when the provider config doesn't set `max_response_mb`.
+pub const DEFAULT_MAX_RESPONSE_MB: usize = 32;
+
+// The response size limit from a provider config, in bytes.
+pub fn max_response_bytes(config: &HashMap<String, String>) -> usize {
+    config
+        .get(MAX_RESPONSE_SIZE_KEY)
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|mb| *mb > 0)
+        .unwrap_or(DEFAULT_MAX_RESPONSE_MB)
+        .saturating_mul(1024 * 1024)
+}
+
+// Reads the body a chunk at a time and gives up as soon as it grows past
+// `limit`, so a pathological response fails the request instead of being
+// buffered whole. A Content-Length over the limit fails before reading.
+pub async fn read_body(mut response: reqwest::Response, limit: usize) -> PluginResult<Vec<u8>> {
+    let too_large = |response: &reqwest::Response| PluginError::ResponseTooLarge {
+        url: response.url().to_string(),
+        limit,
+    };
+
+    if response
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        return Err(too_large(&response));
+    }
+
+    let mut body = Vec::with_capacity(
+        response
+            .content_length()
+            .map_or(0, |length| length as usize),
+    );
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| PluginError::NetworkError(format!("Failed to read response: {e}")))?
+    {
+        if body.len() + chunk.len() > limit {
+            return Err(too_large(&response));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+pub async fn read_text(response: reqwest::Response, limit: usize) -> PluginResult<String> {
+    let body = read_body(response, limit).await?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+// `Response::json` with a size limit. Parses from the raw bytes, without the
+// intermediate String that `text()` + `from_str` would allocate.
+pub async fn read_json<T: DeserializeOwned>(
+    response: reqwest::Response, limit: usize,
+) -> PluginResult<T> {
+    let body = read_body(response, limit).await?;
+    serde_json::from_slice(&body).map_err(Into::into)
+}
+
+// Retry-After is either a number of seconds or an HTTP date. Dates in the
+// past mean the request can be retried right away.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (at.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+// A `RateLimited` error for 429s, and for 503s that say when to come back.
+// Other statuses are left to the caller.
+pub fn rate_limit_error(response: &reqwest::Response) -> Option<PluginError> {
+    let status = response.status();
+    let retry_after = retry_after(response.headers());
+    let limited = status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::SERVICE_UNAVAILABLE && retry_after.is_some());
+
+    limited.then(|| PluginError::RateLimited {
+        message: format!("HTTP {status} for {}", response.url()),
+        retry_after,
+    })
+}
+
+#[derive(Clone)]
+struct Entry {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    body: Arc<[u8]>,
+}
+
+// Body of a conditional GET. `modified` is false when the server answered 304
+// and the body is the one stored from the previous response, so callers can
+// reuse whatever they derived from it last time.
+pub struct ConditionalResponse {
+    pub body: Arc<[u8]>,
+    pub modified: bool,
+}
+
+impl ConditionalResponse {
+    pub fn json<T: DeserializeOwned>(&self) -> PluginResult<T> {
+        serde_json::from_slice(&self.body).map_err(Into::into)
+    }
+}
+
+// Remembers ETag / Last-Modified validators per URL and replays them as
+// If-None-Match / If-Modified-Since. Providers like GitHub don't count 304s
+// against the rate limit. Entries are per provider, since the stored body is
+// what the provider's credentials were allowed to see.
+pub struct ConditionalRequestCache {
+    entries: Mutex<HashMap<String, Entry>>,
+    max_response_bytes: usize,
+}
+
+impl Default for ConditionalRequestCache {
+    fn default() -> Self {
+        Self {
+            entries: Mutex::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_MB * 1024 * 1024,
+        }
+    }
+}
+
+impl ConditionalRequestCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    pub async fn send(
+        &self, client: &reqwest::Client, request: reqwest::RequestBuilder,
+    ) -> PluginResult<ConditionalResponse> {
+        let mut request = request
+            .build()
+            .map_err(|e| PluginError::Internal(format!("Failed to build request: {e}")))?;
+        let key = request.url().to_string();
+
+        let cached = self.entries.lock().unwrap().get(&key).cloned();
+        if let Some(entry) = &cached {
+            let headers = request.headers_mut();
+            if let Some(etag) = &entry.etag {
+                headers.insert(IF_NONE_MATCH, etag.clone());
+            } else if let Some(last_modified) = &entry.last_modified {
+                headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+            }
+        }
+
+        let response = client
+            .execute(request)
+            .await
+            .map_err(|e| PluginError::NetworkError(format!("Request failed: {e}")))?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some(entry) = cached {
+                return Ok(ConditionalResponse {
+                    body: entry.body,
+                    modified: false,
+                });
+            }
+        }
+        if let Some(error) = rate_limit_error(&response) {
+            return Err(error);
+        }
+        if !status.is_success() {
+            return Err(PluginError::ApiError(format!("HTTP {status} for {key}")));
+        }
+
+        let etag = response.headers().get(ETAG).cloned();
+        let last_modified = response.headers().get(LAST_MODIFIED).cloned();
+        let body: Arc<[u8]> = read_body(response, self.max_response_bytes).await?.into();
+
+        if etag.is_some() || last_modified.is_some() {
+            let mut entries = self.entries.lock().unwrap();
+            if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+                entries.clear();
+            }
+            entries.insert(
+                key,
+                Entry {
+                    etag,
+                    last_modified,
+                    body: Arc::clone(&body),
+                },
+            );
+        }
+
+        Ok(ConditionalResponse {
+            body,
+            modified: true,
+        })
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_response_bytes() {
+        let mut config = HashMap::new();
+        assert_eq!(max_response_bytes(&config), 32 * 1024 * 1024);
+
+        config.insert(MAX_RESPONSE_SIZE_KEY.to_string(), " 8 ".to_string());
+        assert_eq!(max_response_bytes(&config), 8 * 1024 * 1024);
+
+        for invalid in ["0", "-1", "lots"] {
+            config.insert(MAX_RESPONSE_SIZE_KEY.to_string(), invalid.to_string());
+            assert_eq!(max_response_bytes(&config), 32 * 1024 * 1024);
+        }
+    }
+}
@@ -1,16 +1,34 @@
 pub mod defaults;
+pub mod dynamic;
 pub mod error;
+pub mod http;
 pub mod plugin;
 pub mod registry;
 pub mod schema;
 pub mod types;
 pub mod utils;
 
+pub use dynamic::{
+    PluginDeclaration,
+    PLUGIN_API_VERSION,
+};
 pub use error::{
     PluginError,
     PluginResult,
 };
+pub use http::{
+    max_response_bytes,
+    rate_limit_error,
+    read_body,
+    read_json,
+    read_text,
+    retry_after,
+    ConditionalRequestCache,
+    ConditionalResponse,
+};
 pub use plugin::{
+    Capability,
+    PaginationStyle,
     Plugin,
     PluginCapabilities,
     PluginMetadata,
@@ -20,29 +38,55 @@ pub use schema::{
     ConfigField,
     ConfigFieldType,
     ConfigSchema,
+    CA_CERTIFICATE_KEY,
+    CLIENT_CERTIFICATE_KEY,
+    CLIENT_KEY_KEY,
+    MAX_RESPONSE_SIZE_KEY,
+    PROXY_URL_KEY,
+    TLS_SKIP_VERIFY_KEY,
 };
 pub use types::{
+    AnnotationLevel,
+    ArtifactDownload,
     AvailablePipeline,
+    BranchStatus,
     BuildAgent,
     BuildArtifact,
     BuildQueue,
+    CommitInfo,
     Feature,
     FeatureAvailability,
+    JobLog,
+    LogQuery,
+    LogStep,
     Organization,
     PaginatedAvailablePipelines,
     PaginatedResponse,
     PaginationParams,
+    PendingApproval,
     Permission,
     PermissionCheck,
     PermissionStatus,
     Pipeline,
     PipelineRun,
     PipelineStatus,
+    PullRequestRef,
+    RateLimitStatus,
+    RunAnnotation,
+    RunLogs,
+    RunMetric,
+    RunMetricType,
+    ServerVersion,
     TriggerParams,
     WorkflowParameter,
     WorkflowParameterType,
+    BLOCKED_STEP_METADATA_KEY,
+};
+pub use utils::{
+    latest_run_per_branch,
+    parse_version,
+    RetryPolicy,
 };
-pub use utils::RetryPolicy;
 
 #[macro_export]
 macro_rules! register_plugin {
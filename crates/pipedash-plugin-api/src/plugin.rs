@@ -30,6 +30,9 @@ pub struct PluginMetadata {
     pub features: Vec<Feature>,
 }
 
+// What a plugin supports. Core routes optional features on these flags, so
+// plugins should build them with `..PluginCapabilities::default()` and only
+// set what they support; new capabilities then default to unsupported.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PluginCapabilities {
     pub pipelines: bool,
@@ -39,6 +42,102 @@ pub struct PluginCapabilities {
     pub artifacts: bool,
     pub queues: bool,
     pub custom_tables: bool,
+    #[serde(default)]
+    pub logs: bool,
+    #[serde(default)]
+    pub retry_failed_jobs: bool,
+    #[serde(default)]
+    pub branch_statuses: bool,
+    #[serde(default)]
+    pub cancel: bool,
+    #[serde(default)]
+    pub workflow_parameters: bool,
+    #[serde(default)]
+    pub unblock_jobs: bool,
+    // `fetch_available_pipelines_filtered` can list a whole organization,
+    // so new repositories can be picked up automatically.
+    #[serde(default)]
+    pub repository_discovery: bool,
+    #[serde(default)]
+    pub pagination: PaginationStyle,
+}
+
+impl PluginCapabilities {
+    pub fn supports(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Trigger => self.trigger,
+            Capability::Cancel => self.cancel,
+            Capability::RetryFailedJobs => self.retry_failed_jobs,
+            Capability::Logs => self.logs,
+            Capability::Artifacts => self.artifacts,
+            Capability::Agents => self.agents,
+            Capability::Queues => self.queues,
+            Capability::WorkflowParameters => self.workflow_parameters,
+            Capability::BranchStatuses => self.branch_statuses,
+            Capability::UnblockJobs => self.unblock_jobs,
+            Capability::RepositoryDiscovery => self.repository_discovery,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    Trigger,
+    Cancel,
+    RetryFailedJobs,
+    Logs,
+    Artifacts,
+    Agents,
+    Queues,
+    WorkflowParameters,
+    BranchStatuses,
+    UnblockJobs,
+    RepositoryDiscovery,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 11] = [
+        Capability::Trigger,
+        Capability::Cancel,
+        Capability::RetryFailedJobs,
+        Capability::Logs,
+        Capability::Artifacts,
+        Capability::Agents,
+        Capability::Queues,
+        Capability::WorkflowParameters,
+        Capability::BranchStatuses,
+        Capability::UnblockJobs,
+        Capability::RepositoryDiscovery,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Capability::Trigger => "triggering runs",
+            Capability::Cancel => "cancelling runs",
+            Capability::RetryFailedJobs => "retrying failed jobs",
+            Capability::Logs => "run logs",
+            Capability::Artifacts => "build artifacts",
+            Capability::Agents => "build agents",
+            Capability::Queues => "build queues",
+            Capability::WorkflowParameters => "workflow parameters",
+            Capability::BranchStatuses => "branch statuses",
+            Capability::UnblockJobs => "unblocking jobs",
+            Capability::RepositoryDiscovery => "discovering new repositories",
+        }
+    }
+}
+
+// How `fetch_available_pipelines_filtered` pages its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PaginationStyle {
+    // Everything is fetched and sliced locally, so each page costs a full
+    // listing.
+    #[default]
+    Client,
+    // Pages are requested from the provider.
+    Server,
 }
 
 #[async_trait]
@@ -50,6 +149,21 @@ pub trait Plugin: Send + Sync {
         http_client: Option<std::sync::Arc<reqwest::Client>>,
     ) -> PluginResult<()>;
 
+    // Called before `initialize` with a cache that outlives re-initialization,
+    // for plugins that issue conditional requests.
+    fn set_conditional_cache(
+        &mut self, cache: std::sync::Arc<crate::http::ConditionalRequestCache>,
+    ) {
+        let _ = cache;
+    }
+
+    // Called before `initialize` with the version detected when the provider
+    // was last validated, so clients can pick compatibility shims for older
+    // self-hosted servers.
+    fn set_server_version(&mut self, version: &ServerVersion) {
+        let _ = version;
+    }
+
     async fn validate_credentials(&self) -> PluginResult<bool>;
 
     async fn fetch_available_pipelines(
@@ -99,6 +213,13 @@ pub trait Plugin: Send + Sync {
 
     async fn fetch_pipelines(&self) -> PluginResult<Vec<Pipeline>>;
 
+    // `None` when the provider reported that nothing changed since the
+    // previous fetch (every conditional request came back 304), so the host
+    // keeps its cached pipelines instead of rewriting them.
+    async fn fetch_pipelines_if_modified(&self) -> PluginResult<Option<Vec<Pipeline>>> {
+        self.fetch_pipelines().await.map(Some)
+    }
+
     async fn fetch_pipelines_paginated(
         &self, page: usize, page_size: usize,
     ) -> PluginResult<crate::types::PaginatedResponse<Pipeline>> {
@@ -137,6 +258,49 @@ pub trait Plugin: Send + Sync {
         ))
     }
 
+    async fn fetch_run_logs(
+        &self, _pipeline_id: &str, _run_number: i64, _query: LogQuery,
+    ) -> PluginResult<RunLogs> {
+        Err(crate::error::PluginError::NotSupported(
+            "Log streaming not supported by this provider".to_string(),
+        ))
+    }
+
+    async fn retry_run(
+        &self, _pipeline_id: &str, _run_number: i64, _failed_only: bool,
+    ) -> PluginResult<String> {
+        Err(crate::error::PluginError::NotSupported(
+            "Run retry not supported by this provider".to_string(),
+        ))
+    }
+
+    // Steps of a run waiting to be unblocked; empty when nothing is waiting.
+    async fn fetch_pending_approvals(
+        &self, _pipeline_id: &str, _run_number: i64,
+    ) -> PluginResult<Vec<PendingApproval>> {
+        Err(crate::error::PluginError::NotSupported(
+            "Unblocking jobs not supported by this provider".to_string(),
+        ))
+    }
+
+    // Lets a run continue past a waiting step, filling in the step's fields.
+    async fn unblock_job(
+        &self, _pipeline_id: &str, _run_number: i64, _job_id: &str,
+        _fields: HashMap<String, String>,
+    ) -> PluginResult<()> {
+        Err(crate::error::PluginError::NotSupported(
+            "Unblocking jobs not supported by this provider".to_string(),
+        ))
+    }
+
+    async fn fetch_commits_between(
+        &self, _pipeline_id: &str, _base_sha: &str, _head_sha: &str,
+    ) -> PluginResult<Vec<CommitInfo>> {
+        Err(crate::error::PluginError::NotSupported(
+            "Commit comparison not supported by this provider".to_string(),
+        ))
+    }
+
     async fn fetch_workflow_parameters(
         &self, _workflow_id: &str,
     ) -> PluginResult<Vec<WorkflowParameter>> {
@@ -155,12 +319,39 @@ pub trait Plugin: Send + Sync {
         ))
     }
 
+    async fn artifact_download(&self, _artifact: &BuildArtifact) -> PluginResult<ArtifactDownload> {
+        Err(crate::error::PluginError::NotSupported(
+            "Artifact download not supported by this provider".to_string(),
+        ))
+    }
+
     async fn fetch_queues(&self) -> PluginResult<Vec<BuildQueue>> {
         Err(crate::error::PluginError::NotSupported(
             "Queue monitoring not supported by this provider".to_string(),
         ))
     }
 
+    // Latest run per branch of a pipeline, most recent first. Branches without
+    // runs are left out.
+    async fn list_branch_statuses(&self, _pipeline_id: &str) -> PluginResult<Vec<BranchStatus>> {
+        Err(crate::error::PluginError::NotSupported(
+            "Branch statuses not supported by this provider".to_string(),
+        ))
+    }
+
+    // Extra metrics for a finished run, stored next to the built-in duration
+    // and success metrics. Runs may come from the cache, so anything needed
+    // here should either be kept in `run.metadata` or fetched on demand.
+    async fn extract_metrics(&self, _run: &PipelineRun) -> PluginResult<Vec<RunMetric>> {
+        Ok(Vec::new())
+    }
+
+    // Failure context for a single run, shown next to its details. Only called
+    // for runs opened in the UI; results for finished runs are cached.
+    async fn fetch_run_annotations(&self, _run: &PipelineRun) -> PluginResult<Vec<RunAnnotation>> {
+        Ok(Vec::new())
+    }
+
     fn get_migrations(&self) -> Vec<String> {
         Vec::new()
     }
@@ -175,6 +366,21 @@ pub trait Plugin: Send + Sync {
         Ok(Vec::new())
     }
 
+    // Base URL of the provider API, probed by provider diagnostics.
+    fn api_endpoint(&self) -> Option<String> {
+        None
+    }
+
+    async fn rate_limit_status(&self) -> PluginResult<Option<RateLimitStatus>> {
+        Ok(None)
+    }
+
+    // Version of a self-hosted server, for plugins whose API shape differs
+    // between releases. Asked for when credentials are validated.
+    async fn detect_server_version(&self) -> PluginResult<Option<ServerVersion>> {
+        Ok(None)
+    }
+
     async fn check_permissions(&self) -> PluginResult<PermissionStatus> {
         let required_permissions = &self.metadata().required_permissions;
         let permissions = required_permissions
@@ -221,3 +427,28 @@ pub trait Plugin: Send + Sync {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_default_to_unsupported() {
+        // Metadata written before a capability existed still deserializes.
+        let capabilities: PluginCapabilities = serde_json::from_str(
+            r#"{"pipelines":true,"pipeline_runs":true,"trigger":true,"agents":false,
+                "artifacts":false,"queues":false,"custom_tables":false}"#,
+        )
+        .unwrap();
+
+        assert!(capabilities.supports(Capability::Trigger));
+        assert!(!capabilities.supports(Capability::Cancel));
+        assert_eq!(capabilities.pagination, PaginationStyle::Client);
+
+        let supported = Capability::ALL
+            .iter()
+            .filter(|capability| capabilities.supports(**capability))
+            .count();
+        assert_eq!(supported, 1);
+    }
+}
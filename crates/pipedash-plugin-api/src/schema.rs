@@ -3,6 +3,8 @@ use serde::{
     Serialize,
 };
 
+use crate::plugin::PluginCapabilities;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ConfigFieldType {
     Text,
@@ -27,6 +29,13 @@ pub struct ConfigField {
     pub validation_message: Option<String>,
 }
 
+pub const PROXY_URL_KEY: &str = "proxy_url";
+pub const CA_CERTIFICATE_KEY: &str = "ca_certificate";
+pub const TLS_SKIP_VERIFY_KEY: &str = "tls_skip_verify";
+pub const CLIENT_CERTIFICATE_KEY: &str = "client_certificate";
+pub const CLIENT_KEY_KEY: &str = "client_key";
+pub const MAX_RESPONSE_SIZE_KEY: &str = "max_response_mb";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigSchema {
     pub fields: Vec<ConfigField>,
@@ -41,6 +50,98 @@ impl ConfigSchema {
         self.fields.push(field);
         self
     }
+
+    // Proxy and TLS settings. These are applied by the host to the HTTP client
+    // passed to `Plugin::initialize`, so only plugins that use that client
+    // should offer them.
+    pub fn with_network_fields(self) -> Self {
+        self.add_field(ConfigField {
+            key: PROXY_URL_KEY.to_string(),
+            label: "Proxy URL".to_string(),
+            description: Some(
+                "Proxy for requests to this provider (e.g., http://proxy.internal:3128)"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Text,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: Some(r"^https?://.+".to_string()),
+            validation_message: Some("Must start with http:// or https://".to_string()),
+        })
+        .add_field(ConfigField {
+            key: CA_CERTIFICATE_KEY.to_string(),
+            label: "CA Certificate".to_string(),
+            description: Some(
+                "Extra root certificates to trust: a path to a PEM file or the PEM contents"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::TextArea,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+        .add_field(ConfigField {
+            key: TLS_SKIP_VERIFY_KEY.to_string(),
+            label: "Skip TLS Verification".to_string(),
+            description: Some(
+                "Skip TLS certificate verification (not recommended; prefer a CA certificate)"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Boolean,
+            required: false,
+            default_value: Some(serde_json::Value::Bool(false)),
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+        .add_field(ConfigField {
+            key: MAX_RESPONSE_SIZE_KEY.to_string(),
+            label: "Max Response Size (MB)".to_string(),
+            description: Some(
+                "Responses larger than this fail instead of being loaded into memory".to_string(),
+            ),
+            field_type: ConfigFieldType::Number,
+            required: false,
+            default_value: Some(serde_json::json!(crate::http::DEFAULT_MAX_RESPONSE_MB)),
+            options: None,
+            validation_regex: Some(r"^[1-9][0-9]*$".to_string()),
+            validation_message: Some("Must be a whole number of megabytes".to_string()),
+        })
+    }
+    // Client certificate for mTLS, as a path to a PEM file or the PEM contents.
+    pub fn with_client_certificate_fields(self) -> Self {
+        self.add_field(ConfigField {
+            key: CLIENT_CERTIFICATE_KEY.to_string(),
+            label: "Client Certificate".to_string(),
+            description: Some(
+                "Client certificate for mutual TLS: a path to a PEM file or the PEM contents"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::TextArea,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+        .add_field(ConfigField {
+            key: CLIENT_KEY_KEY.to_string(),
+            label: "Client Key".to_string(),
+            description: Some(
+                "Private key for the client certificate: a path to a PEM file or the PEM contents"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Password,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+    }
 }
 
 impl Default for ConfigSchema {
@@ -123,11 +224,18 @@ pub struct TableDefinition {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableSchema {
     pub tables: Vec<TableDefinition>,
+    // Filled in when served for a provider, so clients can hide columns and
+    // actions the plugin does not support.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<PluginCapabilities>,
 }
 
 impl TableSchema {
     pub fn new() -> Self {
-        Self { tables: Vec::new() }
+        Self {
+            tables: Vec::new(),
+            capabilities: None,
+        }
     }
 
     pub fn add_table(mut self, table: TableDefinition) -> Self {
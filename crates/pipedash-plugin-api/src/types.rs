@@ -16,8 +16,16 @@ pub enum PipelineStatus {
     Failed,
     Running,
     Pending,
+    // Accepted by the provider but still waiting for a runner or agent.
+    Queued,
     Cancelled,
     Skipped,
+    // Held by an approval gate, manual step or protection rule until someone
+    // lets it through.
+    #[serde(alias = "blocked", alias = "waiting_approval")]
+    Waiting,
+    // Finished without passing or failing, e.g. a GitHub neutral conclusion.
+    Neutral,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,10 +67,85 @@ pub struct PipelineRun {
     pub commit_message: Option<String>,
     pub branch: Option<String>,
     pub actor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_avatar_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<PullRequestRef>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inputs: Option<serde_json::Value>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, serde_json::Value>,
+    // Only filled in by `fetch_run_details`; run listings leave it empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<RunAnnotation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+// Failure context a provider attaches to a run, such as a Buildkite
+// annotation or a GitHub check-run summary. `summary` is plain text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunAnnotation {
+    pub level: AnnotationLevel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+// Run metadata key naming the step a run is waiting on. Plugins that support
+// unblocking set it so clients know when to ask for pending approvals.
+pub const BLOCKED_STEP_METADATA_KEY: &str = "blocked_step";
+
+// A step holding a run until someone lets it continue, such as a Buildkite
+// block step. `fields` lists the inputs the step asks for when the provider
+// reports them; their values are passed back to `Plugin::unblock_job`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingApproval {
+    pub job_id: String,
+    pub label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<WorkflowParameter>,
+}
+
+// The pull or merge request a run was built for. `title` and `url` are only
+// set when the provider returns them with the run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PullRequestRef {
+    pub number: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+// Latest run on a branch of a pipeline, for the branch board. `pull_request`
+// is set when the branch has an open pull or merge request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchStatus {
+    pub branch: String,
+    pub status: PipelineStatus,
+    pub run_number: i64,
+    pub started_at: DateTime<Utc>,
+    pub logs_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<PullRequestRef>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +154,7 @@ pub struct TriggerParams {
     pub inputs: Option<serde_json::Value>,
 }
 
+// `status` is one of `busy`, `idle`, `paused` or `offline` across providers.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildAgent {
     pub id: String,
@@ -82,6 +166,15 @@ pub struct BuildAgent {
     pub metadata: HashMap<String, String>,
 }
 
+impl BuildAgent {
+    pub fn is_offline(&self) -> bool {
+        self.status == "offline"
+    }
+}
+
+// A snapshot of work waiting for build capacity. `avg_wait_time` is the average
+// number of seconds the currently waiting items have been queued, `None` when
+// nothing is waiting.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildQueue {
     pub id: String,
@@ -90,6 +183,45 @@ pub struct BuildQueue {
     pub avg_wait_time: Option<i64>,
 }
 
+impl BuildQueue {
+    pub fn from_queued_at(
+        id: String, queued_at: &[DateTime<Utc>], running: usize, now: DateTime<Utc>,
+    ) -> Self {
+        let avg_wait_time = if queued_at.is_empty() {
+            None
+        } else {
+            let total: i64 = queued_at
+                .iter()
+                .map(|at| (now - *at).num_seconds().max(0))
+                .sum();
+            Some(total / queued_at.len() as i64)
+        };
+
+        Self {
+            id,
+            waiting: queued_at.len(),
+            running,
+            avg_wait_time,
+        }
+    }
+}
+
+// Provider-specific metrics a plugin can record for a finished run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunMetricType {
+    // Seconds the run's jobs spent waiting for an agent to pick them up.
+    AgentWaitTime,
+    // Minutes billed for the run's jobs, after rounding and OS multipliers.
+    BillableMinutes,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunMetric {
+    pub metric_type: RunMetricType,
+    pub value: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildArtifact {
     pub id: String,
@@ -101,6 +233,61 @@ pub struct BuildArtifact {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactDownload {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogQuery {
+    pub job_id: Option<String>,
+    #[serde(default)]
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogStep {
+    pub number: i64,
+    pub name: String,
+    pub status: PipelineStatus,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobLog {
+    pub id: String,
+    pub name: String,
+    pub status: PipelineStatus,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub steps: Vec<LogStep>,
+    pub content: String,
+    pub offset: usize,
+    pub next_offset: usize,
+    pub is_complete: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunLogs {
+    pub pipeline_id: String,
+    pub run_number: i64,
+    pub status: PipelineStatus,
+    pub jobs: Vec<JobLog>,
+    pub is_complete: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitInfo {
+    pub sha: String,
+    pub message: String,
+    pub author: Option<String>,
+    pub committed_at: Option<DateTime<Utc>>,
+    pub url: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Organization {
     pub id: String,
@@ -258,3 +445,64 @@ pub struct FeatureAvailability {
     pub available: bool,
     pub missing_permissions: Vec<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitStatus {
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerVersion {
+    // As the server reports it, e.g. `16.4.1-ee` or `v2.9.3+6eba5be`.
+    pub version: String,
+    pub supported: bool,
+    // Why the version is unsupported, or what works differently on it.
+    pub warning: Option<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl ServerVersion {
+    // Versions older than `minimum` (major, minor), or that don't parse, are
+    // unsupported.
+    pub fn new(product: &str, version: &str, minimum: (u64, u64)) -> Self {
+        let version = version.trim();
+        let (supported, warning) = match crate::utils::parse_version(version) {
+            None => (
+                false,
+                Some(format!(
+                    "Unrecognized {} version `{}`, some features may not work",
+                    product, version
+                )),
+            ),
+            Some((major, minor, _)) if (major, minor) < minimum => (
+                false,
+                Some(format!(
+                    "{} {} is older than {}.{}, the oldest supported version. Some features may \
+                     not work.",
+                    product, version, minimum.0, minimum.1
+                )),
+            ),
+            Some(_) => (true, None),
+        };
+
+        Self {
+            version: version.to_string(),
+            supported,
+            warning,
+            detected_at: Utc::now(),
+        }
+    }
+
+    // For supported versions that still need a shim worth mentioning.
+    pub fn with_warning(mut self, warning: impl Into<String>) -> Self {
+        self.warning.get_or_insert_with(|| warning.into());
+        self
+    }
+
+    // True when the version parses and is below `major.minor`.
+    pub fn is_older_than(&self, major: u64, minor: u64) -> bool {
+        crate::utils::parse_version(&self.version).is_some_and(|(a, b, _)| (a, b) < (major, minor))
+    }
+}
@@ -1,14 +1,29 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 use std::time::Duration;
 
 use crate::{
-    PluginError,
+    BranchStatus,
+    PipelineRun,
     PluginResult,
+    PullRequestRef,
 };
 
+// Retries transient failures with exponential backoff. Only wrap requests
+// that are safe to repeat, such as GETs.
 pub struct RetryPolicy {
+    // Attempts in total, the first one included.
     pub max_retries: usize,
     pub initial_delay: Duration,
     pub exponential_backoff: bool,
+    // Longest wait between attempts. A Retry-After beyond it ends the retries
+    // instead of stalling the caller.
+    pub max_delay: Duration,
+    // Waits a random 50-100% of each delay, so clients that failed together
+    // don't all come back at the same moment.
+    pub jitter: bool,
 }
 
 impl Default for RetryPolicy {
@@ -17,6 +32,8 @@ impl Default for RetryPolicy {
             max_retries: 3,
             initial_delay: Duration::from_millis(100),
             exponential_backoff: true,
+            max_delay: Duration::from_secs(10),
+            jitter: true,
         }
     }
 }
@@ -27,45 +44,217 @@ impl RetryPolicy {
             max_retries,
             initial_delay,
             exponential_backoff,
+            ..Self::default()
         }
     }
 
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    // How long to wait after failed attempt `attempt` (0 for the first), or
+    // None once the attempts are used up or the server asked for a longer
+    // pause than `max_delay`.
+    pub fn delay(&self, attempt: usize, retry_after: Option<Duration>) -> Option<Duration> {
+        if attempt + 1 >= self.max_retries {
+            return None;
+        }
+        if let Some(wait) = retry_after {
+            return (wait <= self.max_delay).then_some(wait);
+        }
+
+        let factor = if self.exponential_backoff {
+            2u32.saturating_pow(attempt.min(31) as u32)
+        } else {
+            1
+        };
+        let delay = self
+            .initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay);
+
+        Some(if self.jitter { jitter(delay) } else { delay })
+    }
+
     pub async fn retry<F, Fut, T>(&self, operation: F) -> PluginResult<T>
     where
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = PluginResult<T>>,
     {
-        let mut delay = self.initial_delay;
-        let mut last_error = None;
+        let mut attempt = 0;
 
-        for attempt in 0..self.max_retries {
+        loop {
             match operation().await {
                 Ok(result) => return Ok(result),
-                Err(e) if attempt < self.max_retries - 1 => match &e {
-                    PluginError::NetworkError(_) | PluginError::ApiError(_) => {
-                        last_error = Some(e);
+                Err(e) if e.is_transient() => match self.delay(attempt, e.retry_after()) {
+                    Some(delay) => {
                         tokio::time::sleep(delay).await;
-                        if self.exponential_backoff {
-                            delay *= 2;
-                        }
-                        continue;
+                        attempt += 1;
                     }
-                    _ => return Err(e),
+                    None => return Err(e),
                 },
-                Err(e) => {
-                    last_error = Some(e);
-                }
+                Err(e) => return Err(e),
             }
         }
-
-        Err(last_error
-            .unwrap_or_else(|| PluginError::NetworkError("Max retries exceeded".to_string())))
     }
 }
 
+// Somewhere between half of `delay` and all of it. `RandomState` is seeded
+// randomly, which is all the randomness this needs.
+fn jitter(delay: Duration) -> Duration {
+    use std::hash::{
+        BuildHasher,
+        Hasher,
+    };
+
+    let random = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    let half = delay / 2;
+    let spread = half.as_nanos() as u64 + 1;
+    half + Duration::from_nanos(random % spread)
+}
+
+// Most recent run of each branch, newest first. `pull_requests` maps branch
+// names to their open pull request, which takes precedence over the one the
+// run carries. Runs without a branch are skipped, and so are branches that are
+// neither in `open_branches` (when given) nor have an open pull request.
+pub fn latest_run_per_branch(
+    runs: impl IntoIterator<Item = PipelineRun>, open_branches: Option<&HashSet<String>>,
+    pull_requests: &HashMap<String, PullRequestRef>,
+) -> Vec<BranchStatus> {
+    let mut runs: Vec<PipelineRun> = runs.into_iter().collect();
+    runs.sort_by_key(|run| std::cmp::Reverse(run.started_at));
+
+    let mut seen = HashSet::new();
+    runs.into_iter()
+        .filter_map(|run| {
+            let branch = run.branch.clone()?;
+            let open = open_branches.is_none_or(|branches| branches.contains(&branch))
+                || pull_requests.contains_key(&branch);
+            if !open || !seen.insert(branch.clone()) {
+                return None;
+            }
+
+            let pull_request = pull_requests.get(&branch).cloned().or(run.pull_request);
+            Some(BranchStatus {
+                branch,
+                status: run.status,
+                run_number: run.run_number,
+                started_at: run.started_at,
+                logs_url: run.logs_url,
+                commit_sha: run.commit_sha,
+                actor: run.actor,
+                pull_request,
+            })
+        })
+        .collect()
+}
+
+// Leading `major.minor.patch` of a version string, ignoring a `v` prefix and
+// anything after the numbers (`-ee`, `+6eba5be`). Missing parts are zero.
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim();
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    let end = version
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(version.len());
+    let mut parts = version[..end]
+        .split('.')
+        .map(|part| part.parse::<u64>().ok());
+
+    let major = parts.next().flatten()?;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
 #[cfg(test)]
 mod tests {
+    use chrono::{
+        DateTime,
+        Utc,
+    };
+
     use super::*;
+    use crate::{
+        PipelineStatus,
+        PluginError,
+    };
+
+    fn run(run_number: i64, branch: &str, started_at: i64) -> PipelineRun {
+        PipelineRun {
+            id: run_number.to_string(),
+            pipeline_id: "github__1__o__r__1".to_string(),
+            run_number,
+            status: PipelineStatus::Success,
+            started_at: DateTime::<Utc>::from_timestamp(started_at, 0).unwrap(),
+            concluded_at: None,
+            duration_seconds: None,
+            logs_url: String::new(),
+            commit_sha: None,
+            commit_message: None,
+            branch: Some(branch.to_string()),
+            actor: None,
+            commit_author: None,
+            author_avatar_url: None,
+            pull_request: None,
+            inputs: None,
+            metadata: HashMap::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_latest_run_per_branch() {
+        let runs = vec![
+            run(1, "main", 100),
+            run(3, "main", 300),
+            run(2, "feature", 200),
+            run(4, "deleted", 400),
+        ];
+        let open_branches: HashSet<String> = ["main".to_string()].into();
+        let pull_requests = HashMap::from([(
+            "feature".to_string(),
+            PullRequestRef {
+                number: 7,
+                title: None,
+                url: None,
+            },
+        )]);
+
+        let statuses = latest_run_per_branch(runs, Some(&open_branches), &pull_requests);
+
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].branch, "main");
+        assert_eq!(statuses[0].run_number, 3);
+        assert_eq!(statuses[1].branch, "feature");
+        assert_eq!(statuses[1].pull_request.as_ref().unwrap().number, 7);
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("16.4.1-ee"), Some((16, 4, 1)));
+        assert_eq!(parse_version("v2.9.3+6eba5be"), Some((2, 9, 3)));
+        assert_eq!(parse_version("2.426.3"), Some((2, 426, 3)));
+        assert_eq!(parse_version("2.0"), Some((2, 0, 0)));
+        assert_eq!(parse_version("12-rc1.4"), Some((12, 0, 0)));
+        assert_eq!(parse_version("unknown"), None);
+
+        let old = crate::ServerVersion::new("GitLab", "10.8.7", (12, 0));
+        assert!(!old.supported);
+        assert!(old.warning.unwrap().contains("older than 12.0"));
+        let current = crate::ServerVersion::new("GitLab", "16.4.1-ee", (12, 0));
+        assert!(current.supported && current.warning.is_none());
+        assert!(current.is_older_than(17, 0));
+        assert!(!current.is_older_than(16, 4));
+    }
 
     #[tokio::test]
     async fn test_retry_success() {
@@ -93,4 +282,42 @@ mod tests {
 
         assert_eq!(result.unwrap(), 42);
     }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_on_permanent_errors() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(10), false);
+        let attempts = std::cell::Cell::new(0);
+
+        let result = policy
+            .retry(|| async {
+                attempts.set(attempts.get() + 1);
+                Err::<(), _>(PluginError::AuthenticationFailed("bad token".to_string()))
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy::new(4, Duration::from_millis(100), true)
+            .with_max_delay(Duration::from_millis(300))
+            .with_jitter(false);
+
+        assert_eq!(policy.delay(0, None), Some(Duration::from_millis(100)));
+        assert_eq!(policy.delay(1, None), Some(Duration::from_millis(200)));
+        assert_eq!(policy.delay(2, None), Some(Duration::from_millis(300)));
+        assert_eq!(policy.delay(3, None), None);
+
+        // Retry-After wins over the backoff, unless it's longer than allowed.
+        assert_eq!(
+            policy.delay(0, Some(Duration::from_millis(250))),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(policy.delay(0, Some(Duration::from_secs(60))), None);
+
+        let jittered = policy.with_jitter(true).delay(1, None).unwrap();
+        assert!(jittered >= Duration::from_millis(100) && jittered <= Duration::from_millis(200));
+    }
 }
@@ -1,17 +1,25 @@
 use std::time::Duration;
 
+use pipedash_plugin_api::http::DEFAULT_MAX_RESPONSE_MB;
 use pipedash_plugin_api::{
+    read_json,
+    read_text,
     PluginError,
     PluginResult,
     RetryPolicy,
 };
 use reqwest::StatusCode;
-use tracing::debug;
+use tracing::{
+    debug,
+    warn,
+};
 
 use crate::types::{
     Application,
     ApplicationList,
+    RollbackRequest,
     SyncRequest,
+    VersionMessage,
 };
 
 const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
@@ -23,6 +31,10 @@ pub struct ArgocdClient {
     api_url: String,
     auth_header: String,
     retry_policy: RetryPolicy,
+    max_response_bytes: usize,
+    // Set when applications live outside the control plane namespace and the
+    // token can't list them all at once.
+    app_namespaces: Vec<String>,
 }
 
 impl ArgocdClient {
@@ -52,22 +64,93 @@ impl ArgocdClient {
             api_url,
             auth_header,
             retry_policy: RetryPolicy::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_MB * 1024 * 1024,
+            app_namespaces: Vec::new(),
         })
     }
 
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    pub fn with_app_namespaces(mut self, app_namespaces: Vec<String>) -> Self {
+        self.app_namespaces = app_namespaces;
+        self
+    }
+
+    pub fn app_namespaces(&self) -> &[String] {
+        &self.app_namespaces
+    }
+
     fn build_api_url(server_url: &str) -> String {
         format!("{}/api/v1", server_url.trim_end_matches('/'))
     }
 
+    // Served next to, not under, the versioned API.
+    pub async fn get_version(&self) -> PluginResult<VersionMessage> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("{}/version", self.api_url.trim_end_matches("/v1"));
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .header(reqwest::header::AUTHORIZATION, &self.auth_header)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!("Failed to get Argo CD version: {}", e))
+                    })?;
+
+                self.handle_response(response).await
+            })
+            .await
+    }
+
+    // Namespaces that can't be read are skipped, unless none of them can.
     pub async fn list_applications(
         &self, projects_filter: Option<&Vec<String>>,
+    ) -> PluginResult<Vec<Application>> {
+        if self.app_namespaces.is_empty() {
+            return self.list_applications_in(None, projects_filter).await;
+        }
+
+        let results = futures::future::join_all(
+            self.app_namespaces
+                .iter()
+                .map(|namespace| self.list_applications_in(Some(namespace), projects_filter)),
+        )
+        .await;
+
+        let mut apps = Vec::new();
+        let mut last_error = None;
+        for (namespace, result) in self.app_namespaces.iter().zip(results) {
+            match result {
+                Ok(namespace_apps) => apps.extend(namespace_apps),
+                Err(e) => {
+                    warn!(namespace, error = %e, "Failed to list applications in namespace");
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match last_error {
+            Some(e) if apps.is_empty() => Err(e),
+            _ => Ok(apps),
+        }
+    }
+
+    pub async fn list_applications_in(
+        &self, app_namespace: Option<&str>, projects_filter: Option<&Vec<String>>,
     ) -> PluginResult<Vec<Application>> {
         self.retry_policy
             .retry(|| async {
                 let url = format!("{}/applications", self.api_url);
-                let response = self
-                    .http_client
-                    .get(&url)
+                let mut request = self.http_client.get(&url);
+                if let Some(namespace) = app_namespace {
+                    request = request.query(&[("appNamespace", namespace)]);
+                }
+                let response = request
                     .header(reqwest::header::AUTHORIZATION, &self.auth_header)
                     .send()
                     .await
@@ -168,6 +251,68 @@ impl ArgocdClient {
             .await
     }
 
+    // Makes Argo CD compare the app against Git now instead of on its next
+    // poll. A hard refresh also drops the cached manifests.
+    pub async fn refresh_application(
+        &self, app_name: &str, hard: bool,
+    ) -> PluginResult<Application> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("{}/applications/{}", self.api_url, app_name);
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .query(&[("refresh", if hard { "hard" } else { "normal" })])
+                    .header(reqwest::header::AUTHORIZATION, &self.auth_header)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!(
+                            "Failed to refresh application '{}': {}",
+                            app_name, e
+                        ))
+                    })?;
+
+                self.handle_response(response).await
+            })
+            .await
+    }
+
+    pub async fn rollback_application(
+        &self, app_name: &str, history_id: i64, prune: bool, dry_run: bool,
+    ) -> PluginResult<()> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("{}/applications/{}/rollback", self.api_url, app_name);
+
+                let rollback_request = RollbackRequest {
+                    id: history_id,
+                    prune: Some(prune),
+                    dry_run: Some(dry_run),
+                };
+
+                debug!(?rollback_request, "Sending rollback request to ArgoCD API");
+
+                let response = self
+                    .http_client
+                    .post(&url)
+                    .header(reqwest::header::AUTHORIZATION, &self.auth_header)
+                    .json(&rollback_request)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!(
+                            "Failed to roll back application '{}': {}",
+                            app_name, e
+                        ))
+                    })?;
+
+                self.handle_response::<serde_json::Value>(response).await?;
+                Ok(())
+            })
+            .await
+    }
+
     pub async fn terminate_operation(&self, app_name: &str) -> PluginResult<()> {
         self.retry_policy
             .retry(|| async {
@@ -187,8 +332,7 @@ impl ArgocdClient {
 
                 let status = response.status();
                 if !status.is_success() {
-                    let error_text = response
-                        .text()
+                    let error_text = read_text(response, self.max_response_bytes)
                         .await
                         .unwrap_or_else(|_| "Unknown error".to_string());
                     return Err(PluginError::ApiError(format!(
@@ -209,10 +353,15 @@ impl ArgocdClient {
         let url = response.url().clone();
 
         if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
-            let error_text = response
-                .text()
+            let error_text = read_text(response, self.max_response_bytes)
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
+            if status == StatusCode::FORBIDDEN {
+                return Err(PluginError::PermissionDenied(format!(
+                    "Permission denied for {}: {}",
+                    url, error_text
+                )));
+            }
             return Err(PluginError::AuthenticationFailed(format!(
                 "Authentication failed for {}: {}",
                 url, error_text
@@ -226,9 +375,12 @@ impl ArgocdClient {
             )));
         }
 
+        if let Some(error) = pipedash_plugin_api::rate_limit_error(&response) {
+            return Err(error);
+        }
+
         if !status.is_success() {
-            let error_text = response
-                .text()
+            let error_text = read_text(response, self.max_response_bytes)
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
             return Err(PluginError::ApiError(format!(
@@ -237,11 +389,14 @@ impl ArgocdClient {
             )));
         }
 
-        response.json::<T>().await.map_err(|e| {
-            PluginError::ApiError(format!(
-                "Failed to parse ArgoCD API response from {}: {}",
-                url, e
-            ))
-        })
+        read_json(response, self.max_response_bytes)
+            .await
+            .map_err(|e| match e {
+                PluginError::SerializationError(e) => PluginError::ApiError(format!(
+                    "Failed to parse ArgoCD API response from {}: {}",
+                    url, e
+                )),
+                e => e,
+            })
     }
 }
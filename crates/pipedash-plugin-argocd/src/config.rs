@@ -5,6 +5,9 @@ use pipedash_plugin_api::{
     PluginResult,
 };
 
+// Oldest Argo CD release the plugin is known to work with.
+pub(crate) const MINIMUM_VERSION: (u64, u64) = (2, 0);
+
 pub(crate) fn parse_pipeline_id(id: &str) -> PluginResult<(i64, String, String)> {
     let parts: Vec<&str> = id.split("__").collect();
 
@@ -58,6 +61,19 @@ pub(crate) fn parse_organizations_filter(config: &HashMap<String, String>) -> Op
     })
 }
 
+pub(crate) fn parse_app_namespaces(config: &HashMap<String, String>) -> Vec<String> {
+    config
+        .get("namespaces")
+        .map(|namespaces| {
+            namespaces
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 pub(crate) fn build_pipeline_id(provider_id: i64, namespace: &str, app_name: &str) -> String {
     format!("argocd__{}__{}__{}", provider_id, namespace, app_name)
 }
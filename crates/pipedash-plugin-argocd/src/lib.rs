@@ -2,6 +2,7 @@ mod client;
 mod config;
 mod mapper;
 mod metadata;
+mod operation;
 mod plugin;
 mod schema;
 mod types;
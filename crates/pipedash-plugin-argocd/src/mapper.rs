@@ -286,8 +286,12 @@ pub(crate) fn map_history_to_run(
         commit_message: None,
         branch: Some(app.spec.source.target_revision.clone()),
         actor: None,
+        commit_author: None,
+        author_avatar_url: None,
+        pull_request: None,
         inputs: None,
         metadata,
+        annotations: Vec::new(),
     }
 }
 
@@ -376,8 +380,12 @@ pub(crate) fn map_operation_to_run(
         commit_message: None,
         branch: Some(app.spec.source.target_revision.clone()),
         actor: None,
+        commit_author: None,
+        author_avatar_url: None,
+        pull_request: None,
         inputs: None,
         metadata,
+        annotations: Vec::new(),
     })
 }
 
@@ -7,7 +7,7 @@ pub fn create_metadata() -> PluginMetadata {
         name: "ArgoCD".to_string(),
         provider_type: "argocd".to_string(),
         version: "0.1.0".to_string(),
-        description: "Monitor and manage ArgoCD applications and deployments".to_string(),
+        description: "Monitor, sync, refresh and roll back ArgoCD applications".to_string(),
         author: Some("Pipedash Team".to_string()),
         icon: Some("https://cdn.simpleicons.org/argo/EF7B4D".to_string()),
         config_schema: create_config_schema(),
@@ -74,6 +74,21 @@ fn create_config_schema() -> ConfigSchema {
             validation_regex: None,
             validation_message: None,
         })
+        .add_field(ConfigField {
+            key: "namespaces".to_string(),
+            label: "Application Namespaces (optional)".to_string(),
+            description: Some(
+                "Comma-separated namespaces to read applications from (e.g. 'argocd,team-a'), each checked for access. Use this when applications live in other namespaces and the token can't list them all at once; leave empty to list every application the token can see."
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Text,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+        .with_client_certificate_fields()
 }
 
 fn create_capabilities() -> PluginCapabilities {
@@ -81,9 +96,8 @@ fn create_capabilities() -> PluginCapabilities {
         pipelines: true,
         pipeline_runs: true,
         trigger: true,
-        agents: false,
-        artifacts: false,
-        queues: false,
-        custom_tables: false,
+        cancel: true,
+        workflow_parameters: true,
+        ..PluginCapabilities::default()
     }
 }
@@ -0,0 +1,138 @@
+use pipedash_plugin_api::{
+    PluginError,
+    PluginResult,
+};
+
+pub(crate) const SYNC: &str = "sync";
+pub(crate) const REFRESH: &str = "refresh";
+pub(crate) const HARD_REFRESH: &str = "hard_refresh";
+pub(crate) const ROLLBACK: &str = "rollback";
+
+pub(crate) const OPERATIONS: [&str; 4] = [SYNC, REFRESH, HARD_REFRESH, ROLLBACK];
+
+// What a trigger asks Argo CD to do, picked with the `operation` parameter.
+// Triggers without it stay plain syncs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Operation {
+    Sync {
+        revision: Option<String>,
+        prune: bool,
+        dry_run: bool,
+        force: bool,
+        apply_only: bool,
+    },
+    Refresh {
+        hard: bool,
+    },
+    Rollback {
+        history_id: i64,
+        prune: bool,
+        dry_run: bool,
+    },
+}
+
+fn flag(inputs: Option<&serde_json::Value>, key: &str) -> bool {
+    inputs
+        .and_then(|i| i.get(key))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+fn text(inputs: Option<&serde_json::Value>, key: &str) -> Option<String> {
+    inputs
+        .and_then(|i| i.get(key))
+        .and_then(|v| match v {
+            serde_json::Value::String(s) => Some(s.trim().to_string()),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+        .filter(|s| !s.is_empty())
+}
+
+pub(crate) fn parse(inputs: Option<&serde_json::Value>) -> PluginResult<Operation> {
+    let operation = text(inputs, "operation").unwrap_or_else(|| SYNC.to_string());
+
+    match operation.as_str() {
+        SYNC => Ok(Operation::Sync {
+            revision: text(inputs, "revision"),
+            prune: flag(inputs, "prune"),
+            dry_run: flag(inputs, "dry_run"),
+            force: flag(inputs, "force"),
+            apply_only: flag(inputs, "apply_only"),
+        }),
+        REFRESH => Ok(Operation::Refresh { hard: false }),
+        HARD_REFRESH => Ok(Operation::Refresh { hard: true }),
+        ROLLBACK => {
+            let raw = text(inputs, "rollback_id").ok_or_else(|| {
+                PluginError::InvalidConfig("Rollback requires a rollback_id".to_string())
+            })?;
+            let history_id = raw.parse::<i64>().map_err(|_| {
+                PluginError::InvalidConfig(format!(
+                    "Invalid rollback_id '{}': expected a sync history ID",
+                    raw
+                ))
+            })?;
+            Ok(Operation::Rollback {
+                history_id,
+                prune: flag(inputs, "prune"),
+                dry_run: flag(inputs, "dry_run"),
+            })
+        }
+        other => Err(PluginError::InvalidConfig(format!(
+            "Unknown operation '{}'. Expected one of: {}",
+            other,
+            OPERATIONS.join(", ")
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_operation() {
+        assert_eq!(
+            parse(None).unwrap(),
+            Operation::Sync {
+                revision: None,
+                prune: false,
+                dry_run: false,
+                force: false,
+                apply_only: false,
+            }
+        );
+        assert_eq!(
+            parse(Some(&json!({"operation": "hard_refresh"}))).unwrap(),
+            Operation::Refresh { hard: true }
+        );
+        assert_eq!(
+            parse(Some(
+                &json!({"operation": "rollback", "rollback_id": "3", "prune": true})
+            ))
+            .unwrap(),
+            Operation::Rollback {
+                history_id: 3,
+                prune: true,
+                dry_run: false,
+            }
+        );
+        assert_eq!(
+            parse(Some(&json!({"operation": "rollback", "rollback_id": 4}))).unwrap(),
+            Operation::Rollback {
+                history_id: 4,
+                prune: false,
+                dry_run: false,
+            }
+        );
+
+        assert!(parse(Some(&json!({"operation": "rollback"}))).is_err());
+        assert!(parse(Some(
+            &json!({"operation": "rollback", "rollback_id": "abc"})
+        ))
+        .is_err());
+        assert!(parse(Some(&json!({"operation": "delete"}))).is_err());
+    }
+}
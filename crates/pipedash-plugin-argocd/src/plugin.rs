@@ -13,6 +13,7 @@ use crate::{
     config,
     mapper,
     metadata,
+    operation,
 };
 
 const DEFAULT_PAGE_SIZE: usize = 1000;
@@ -88,7 +89,12 @@ impl Plugin for ArgocdPlugin {
         let organizations_filter = config::parse_organizations_filter(&config);
         debug!(?organizations_filter, "Organizations filter configured");
 
-        let client = client::ArgocdClient::new(http_client, server_url.clone(), token, insecure)?;
+        let app_namespaces = config::parse_app_namespaces(&config);
+        debug!(?app_namespaces, "Application namespaces configured");
+
+        let client = client::ArgocdClient::new(http_client, server_url.clone(), token, insecure)?
+            .with_app_namespaces(app_namespaces)
+            .with_max_response_bytes(max_response_bytes(&config));
         debug!("ArgoCD client created successfully");
 
         self.client = Some(client);
@@ -101,9 +107,44 @@ impl Plugin for ArgocdPlugin {
         Ok(())
     }
 
+    fn api_endpoint(&self) -> Option<String> {
+        self.server_url.clone()
+    }
+
     async fn validate_credentials(&self) -> PluginResult<bool> {
         debug!("Validating ArgoCD credentials");
         let client = self.client()?;
+
+        // Each configured namespace has to be readable on its own, so a typo
+        // or a missing RBAC grant shows up here rather than as missing apps.
+        if !client.app_namespaces().is_empty() {
+            let results = futures::future::join_all(
+                client
+                    .app_namespaces()
+                    .iter()
+                    .map(|namespace| client.list_applications_in(Some(namespace), None)),
+            )
+            .await;
+
+            let errors: Vec<String> = client
+                .app_namespaces()
+                .iter()
+                .zip(results)
+                .filter_map(|(namespace, result)| {
+                    result
+                        .err()
+                        .map(|e| format!("Failed to access namespace '{}': {}", namespace, e))
+                })
+                .collect();
+
+            if !errors.is_empty() {
+                warn!(?errors, "Application namespace validation failed");
+                return Err(PluginError::InvalidConfig(format!(
+                    "Failed to validate application namespaces. Errors: {}",
+                    errors.join("; ")
+                )));
+            }
+        }
         match client.list_applications(None).await {
             Ok(apps) => {
                 info!(app_count = apps.len(), "Credentials validated successfully");
@@ -116,6 +157,16 @@ impl Plugin for ArgocdPlugin {
         }
     }
 
+    async fn detect_server_version(&self) -> PluginResult<Option<ServerVersion>> {
+        let version = self.client()?.get_version().await?;
+        debug!(version = %version.version, "Detected Argo CD version");
+        Ok(Some(ServerVersion::new(
+            "Argo CD",
+            &version.version,
+            config::MINIMUM_VERSION,
+        )))
+    }
+
     async fn fetch_available_pipelines(
         &self, params: Option<PaginationParams>,
     ) -> PluginResult<PaginatedResponse<AvailablePipeline>> {
@@ -424,52 +475,103 @@ impl Plugin for ArgocdPlugin {
         let (_provider_id, _namespace, app_name) = config::parse_pipeline_id(&params.workflow_id)?;
         let client = self.client()?;
 
-        let inputs = params.inputs.as_ref();
-
-        let revision = inputs
-            .and_then(|i| i.get("revision"))
-            .and_then(|v| v.as_str())
-            .map(String::from);
-
-        let prune = inputs
-            .and_then(|i| i.get("prune"))
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
-
-        let dry_run = inputs
-            .and_then(|i| i.get("dry_run"))
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
-
-        let force = inputs
-            .and_then(|i| i.get("force"))
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
-
-        let apply_only = inputs
-            .and_then(|i| i.get("apply_only"))
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
-
-        info!(
-            app_name,
-            ?revision,
-            prune,
-            dry_run,
-            force,
-            apply_only,
-            "Triggering ArgoCD sync operation"
-        );
+        match operation::parse(params.inputs.as_ref())? {
+            operation::Operation::Sync {
+                revision,
+                prune,
+                dry_run,
+                force,
+                apply_only,
+            } => {
+                info!(
+                    app_name,
+                    ?revision,
+                    prune,
+                    dry_run,
+                    force,
+                    apply_only,
+                    "Triggering ArgoCD sync operation"
+                );
 
-        client
-            .sync_application(&app_name, revision, prune, dry_run, force, apply_only)
-            .await?;
+                client
+                    .sync_application(&app_name, revision, prune, dry_run, force, apply_only)
+                    .await?;
 
-        let sync_type = if dry_run { "Dry run" } else { "Sync" };
-        Ok(format!(
-            "{} triggered for application: {}",
-            sync_type, app_name
-        ))
+                let sync_type = if dry_run { "Dry run" } else { "Sync" };
+                Ok(format!(
+                    "{} triggered for application: {}",
+                    sync_type, app_name
+                ))
+            }
+            operation::Operation::Refresh { hard } => {
+                info!(app_name, hard, "Refreshing ArgoCD application");
+
+                let app = client.refresh_application(&app_name, hard).await?;
+
+                Ok(format!(
+                    "Refreshed application: {} ({}, {})",
+                    app_name, app.status.sync.status, app.status.health.status
+                ))
+            }
+            operation::Operation::Rollback {
+                history_id,
+                prune,
+                dry_run,
+            } => {
+                let app = client.get_application(&app_name).await?;
+
+                // Argo CD refuses the rollback anyway, but with a less useful
+                // message.
+                if app
+                    .spec
+                    .sync_policy
+                    .as_ref()
+                    .is_some_and(|sp| sp.automated.is_some())
+                {
+                    return Err(PluginError::InvalidConfig(format!(
+                        "Cannot roll back '{}' while auto-sync is enabled; disable it first",
+                        app_name
+                    )));
+                }
+
+                let target = app
+                    .status
+                    .history
+                    .as_deref()
+                    .unwrap_or(&[])
+                    .iter()
+                    .find(|h| h.id == history_id)
+                    .ok_or_else(|| {
+                        PluginError::InvalidConfig(format!(
+                            "Sync history ID {} not found for application '{}'",
+                            history_id, app_name
+                        ))
+                    })?;
+
+                info!(
+                    app_name,
+                    history_id,
+                    revision = %target.revision,
+                    prune,
+                    dry_run,
+                    "Triggering ArgoCD rollback"
+                );
+
+                client
+                    .rollback_application(&app_name, history_id, prune, dry_run)
+                    .await?;
+
+                let rollback_type = if dry_run {
+                    "Dry run rollback"
+                } else {
+                    "Rollback"
+                };
+                Ok(format!(
+                    "{} to revision {} triggered for application: {}",
+                    rollback_type, target.revision, app_name
+                ))
+            }
+        }
     }
 
     async fn cancel_run(&self, pipeline_id: &str, _run_number: i64) -> PluginResult<()> {
@@ -482,27 +584,55 @@ impl Plugin for ArgocdPlugin {
     }
 
     async fn fetch_workflow_parameters(
-        &self, _workflow_id: &str,
+        &self, workflow_id: &str,
     ) -> PluginResult<Vec<WorkflowParameter>> {
+        let (_provider_id, _namespace, app_name) = config::parse_pipeline_id(workflow_id)?;
+
+        // Newest first, so the latest deployment is at the top of the list.
+        let history_ids: Vec<String> = match self.client()?.get_application(&app_name).await {
+            Ok(app) => app
+                .status
+                .history
+                .unwrap_or_default()
+                .iter()
+                .rev()
+                .map(|h| h.id.to_string())
+                .collect(),
+            Err(e) => {
+                warn!(app_name, error = ?e, "Could not load sync history for rollback options");
+                Vec::new()
+            }
+        };
+
         Ok(vec![
+            WorkflowParameter {
+                name: "operation".to_string(),
+                label: Some("Operation".to_string()),
+                description: Some("Sync to Git, refresh the application state, or roll back to an earlier sync".to_string()),
+                param_type: WorkflowParameterType::Choice {
+                    options: operation::OPERATIONS.iter().map(|o| o.to_string()).collect(),
+                    default: Some(operation::SYNC.to_string()),
+                },
+                required: false,
+            },
             WorkflowParameter {
                 name: "revision".to_string(),
                 label: Some("Revision".to_string()),
-                description: Some("Git revision (branch, tag, or commit SHA) to sync to. Leave empty to use target revision.".to_string()),
+                description: Some("Git revision (branch, tag, or commit SHA) to sync to (sync only). Leave empty to use target revision.".to_string()),
                 param_type: WorkflowParameterType::String { default: None },
                 required: false,
             },
             WorkflowParameter {
                 name: "prune".to_string(),
                 label: Some("Prune Resources".to_string()),
-                description: Some("Delete resources that are no longer defined in Git".to_string()),
+                description: Some("Delete resources that are no longer defined in Git (sync and rollback)".to_string()),
                 param_type: WorkflowParameterType::Boolean { default: false },
                 required: false,
             },
             WorkflowParameter {
                 name: "dry_run".to_string(),
                 label: Some("Dry Run".to_string()),
-                description: Some("Preview sync without applying changes".to_string()),
+                description: Some("Preview the sync or rollback without applying changes".to_string()),
                 param_type: WorkflowParameterType::Boolean { default: false },
                 required: false,
             },
@@ -520,6 +650,16 @@ impl Plugin for ArgocdPlugin {
                 param_type: WorkflowParameterType::Boolean { default: false },
                 required: false,
             },
+            WorkflowParameter {
+                name: "rollback_id".to_string(),
+                label: Some("Rollback To".to_string()),
+                description: Some("Sync history ID to roll back to (rollback only). Shown as History ID in the run details.".to_string()),
+                param_type: WorkflowParameterType::Choice {
+                    options: history_ids,
+                    default: None,
+                },
+                required: false,
+            },
         ])
     }
 
@@ -547,7 +687,8 @@ impl Plugin for ArgocdPlugin {
             let token = config::get_token(config)?;
             let insecure = config::is_insecure(config);
 
-            let temp_client = client::ArgocdClient::new(None, server_url, token, insecure)?;
+            let temp_client = client::ArgocdClient::new(None, server_url, token, insecure)?
+                .with_app_namespaces(config::parse_app_namespaces(config));
 
             let apps = temp_client.list_applications(None).await?;
 
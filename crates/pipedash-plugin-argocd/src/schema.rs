@@ -77,6 +77,7 @@ fn create_pipeline_runs_table() -> schema::TableDefinition {
         .insert(status_index + 3, create_run_source_path_column());
 
     table.columns.push(create_sync_revision_column());
+    table.columns.push(create_history_id_column());
     table.columns.push(create_operation_message_column());
     table.columns.push(create_run_app_sync_status_column());
     table.columns.push(create_run_app_health_status_column());
@@ -189,6 +190,23 @@ fn create_sync_revision_column() -> schema::ColumnDefinition {
     }
 }
 
+fn create_history_id_column() -> schema::ColumnDefinition {
+    schema::ColumnDefinition {
+        id: "history_id".to_string(),
+        label: "History ID".to_string(),
+        description: Some("Sync history ID, used to pick a rollback target".to_string()),
+        field_path: "metadata.history_id".to_string(),
+        data_type: schema::ColumnDataType::Number,
+        renderer: schema::CellRenderer::Text,
+        visibility: schema::ColumnVisibility::Always,
+        default_visible: false,
+        width: Some(100),
+        sortable: true,
+        filterable: false,
+        align: Some("center".to_string()),
+    }
+}
+
 fn create_current_revision_column() -> schema::ColumnDefinition {
     schema::ColumnDefinition {
         id: "current_revision".to_string(),
@@ -468,9 +486,9 @@ fn create_run_app_sync_status_column() -> schema::ColumnDefinition {
         description: Some("Current application sync status".to_string()),
         field_path: "metadata.app_sync_status".to_string(),
         data_type: schema::ColumnDataType::String,
-        renderer: schema::CellRenderer::Badge,
+        renderer: schema::CellRenderer::StatusBadge,
         visibility: schema::ColumnVisibility::Always,
-        default_visible: false,
+        default_visible: true,
         width: Some(120),
         sortable: false,
         filterable: false,
@@ -485,9 +503,9 @@ fn create_run_app_health_status_column() -> schema::ColumnDefinition {
         description: Some("Current application health status".to_string()),
         field_path: "metadata.app_health_status".to_string(),
         data_type: schema::ColumnDataType::String,
-        renderer: schema::CellRenderer::Badge,
+        renderer: schema::CellRenderer::StatusBadge,
         visibility: schema::ColumnVisibility::Always,
-        default_visible: false,
+        default_visible: true,
         width: Some(120),
         sortable: false,
         filterable: false,
@@ -7,6 +7,12 @@ use serde::{
     Serialize,
 };
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionMessage {
+    #[serde(rename = "Version")]
+    pub version: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApplicationList {
     pub items: Vec<Application>,
@@ -134,6 +140,14 @@ pub struct SyncRequest {
     pub strategy: Option<SyncStrategy>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackRequest {
+    pub id: i64,
+    pub prune: Option<bool>,
+    #[serde(rename = "dryRun")]
+    pub dry_run: Option<bool>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncStrategy {
     pub hook: Option<SyncStrategyHook>,
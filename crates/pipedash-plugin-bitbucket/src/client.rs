@@ -1,6 +1,10 @@
 use std::sync::OnceLock;
 
+use futures::future::join_all;
+use pipedash_plugin_api::http::DEFAULT_MAX_RESPONSE_MB;
 use pipedash_plugin_api::{
+    read_json,
+    read_text,
     PaginatedResponse,
     PaginationParams,
     PluginError,
@@ -9,37 +13,199 @@ use pipedash_plugin_api::{
 };
 use reqwest::StatusCode;
 
+use crate::config::DeploymentType;
+use crate::mapper;
 use crate::types::{
+    Branch,
     PaginatedResponse as BitbucketPaginatedResponse,
     Pipeline,
     PipelineStep,
     Repository,
+    ServerBuildStatus,
+    ServerCommit,
+    ServerPage,
+    ServerProject,
+    ServerRepository,
     TriggerPipelineRequest,
     User,
     Workspace,
 };
 
+// Build statuses hang off commits on Server, so this many recent commits are
+// scanned to find a repository's latest builds.
+const SERVER_MIN_COMMITS: usize = 5;
+const SERVER_MAX_COMMITS: usize = 25;
+
 pub struct BitbucketClient {
     http_client: std::sync::Arc<reqwest::Client>,
     api_url: String,
     auth_value: String,
+    deployment: DeploymentType,
     retry_policy: RetryPolicy,
+    max_response_bytes: usize,
     user_cache: OnceLock<User>,
 }
 
 impl BitbucketClient {
+    // `api_url` is the Cloud API root, or the Server/Data Center base URL.
     pub fn new(
         http_client: std::sync::Arc<reqwest::Client>, api_url: String, auth_value: String,
+        deployment: DeploymentType,
     ) -> Self {
         Self {
             http_client,
             api_url: api_url.trim_end_matches('/').to_string(),
             auth_value,
+            deployment,
             retry_policy: RetryPolicy::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_MB * 1024 * 1024,
             user_cache: OnceLock::new(),
         }
     }
 
+    pub fn deployment(&self) -> DeploymentType {
+        self.deployment
+    }
+
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    async fn server_get<T: serde::de::DeserializeOwned>(
+        &self, path: &str, action: &str,
+    ) -> PluginResult<T> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("{}{}", self.api_url, path);
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .header(reqwest::header::AUTHORIZATION, &self.auth_value)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!("Failed to {}: {}", action, e))
+                    })?;
+
+                self.handle_response(response).await
+            })
+            .await
+    }
+
+    pub async fn validate_server_access(&self) -> PluginResult<()> {
+        self.server_get::<ServerPage<ServerProject>>(
+            "/rest/api/1.0/projects?limit=1",
+            "list projects",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn list_server_projects(&self) -> PluginResult<Vec<Workspace>> {
+        let mut projects = Vec::new();
+        let mut start = Some(0);
+        const MAX_PAGES: usize = 10;
+
+        for _ in 0..MAX_PAGES {
+            let Some(page_start) = start else {
+                break;
+            };
+            let page: ServerPage<ServerProject> = self
+                .server_get(
+                    &format!("/rest/api/1.0/projects?start={}&limit=100", page_start),
+                    "list projects",
+                )
+                .await?;
+
+            projects.extend(page.values.into_iter().map(mapper::map_server_project));
+            start = if page.is_last_page {
+                None
+            } else {
+                page.next_page_start
+            };
+        }
+
+        Ok(projects)
+    }
+
+    async fn list_server_repositories(
+        &self, project: Option<&str>, params: &PaginationParams,
+    ) -> PluginResult<PaginatedResponse<Repository>> {
+        let start = params.page.saturating_sub(1) * params.page_size;
+        let path = match project {
+            Some(key) => format!(
+                "/rest/api/1.0/projects/{}/repos?start={}&limit={}",
+                urlencoding::encode(key),
+                start,
+                params.page_size
+            ),
+            None => format!(
+                "/rest/api/1.0/repos?start={}&limit={}",
+                start, params.page_size
+            ),
+        };
+        let page: ServerPage<ServerRepository> =
+            self.server_get(&path, "list repositories").await?;
+
+        // Server doesn't report a total, so count one extra item while more
+        // pages remain.
+        let seen = page.start + page.values.len();
+        let total_count = if page.is_last_page { seen } else { seen + 1 };
+        let repositories = page
+            .values
+            .iter()
+            .map(|repo| mapper::map_server_repository(repo, &self.api_url))
+            .collect();
+
+        Ok(PaginatedResponse::new(
+            repositories,
+            params.page,
+            params.page_size,
+            total_count,
+        ))
+    }
+
+    async fn list_server_builds(
+        &self, project: &str, repo_slug: &str, limit: usize,
+    ) -> PluginResult<Vec<Pipeline>> {
+        let commits: ServerPage<ServerCommit> = self
+            .server_get(
+                &format!(
+                    "/rest/api/1.0/projects/{}/repos/{}/commits?limit={}",
+                    urlencoding::encode(project),
+                    urlencoding::encode(repo_slug),
+                    limit.clamp(SERVER_MIN_COMMITS, SERVER_MAX_COMMITS)
+                ),
+                "list commits",
+            )
+            .await?;
+
+        let build_futures = commits.values.iter().map(|commit| async move {
+            let builds: ServerPage<ServerBuildStatus> = self
+                .server_get(
+                    &format!("/rest/build-status/1.0/commits/{}", commit.id),
+                    "list build statuses",
+                )
+                .await?;
+            Ok::<_, PluginError>(
+                builds
+                    .values
+                    .iter()
+                    .map(|build| mapper::map_server_build(build, commit))
+                    .collect::<Vec<_>>(),
+            )
+        });
+
+        let mut pipelines = Vec::new();
+        for result in join_all(build_futures).await {
+            pipelines.extend(result?);
+        }
+        pipelines.sort_by_key(|p| std::cmp::Reverse(p.created_on));
+        pipelines.truncate(limit);
+        Ok(pipelines)
+    }
+
     pub async fn get_user(&self) -> PluginResult<User> {
         if let Some(user) = self.user_cache.get() {
             return Ok(user.clone());
@@ -66,6 +232,10 @@ impl BitbucketClient {
     }
 
     pub async fn list_workspaces(&self) -> PluginResult<Vec<Workspace>> {
+        if self.deployment == DeploymentType::Server {
+            return self.list_server_projects().await;
+        }
+
         let mut all_workspaces = Vec::new();
         let mut next_url = Some(format!("{}/workspaces?pagelen=100", self.api_url));
         const MAX_PAGES: usize = 10;
@@ -103,6 +273,10 @@ impl BitbucketClient {
     pub async fn list_repositories(
         &self, workspace: &str, params: &PaginationParams,
     ) -> PluginResult<PaginatedResponse<Repository>> {
+        if self.deployment == DeploymentType::Server {
+            return self.list_server_repositories(Some(workspace), params).await;
+        }
+
         self.retry_policy
             .retry(|| async {
                 let url = format!(
@@ -136,6 +310,10 @@ impl BitbucketClient {
     pub async fn list_all_repositories(
         &self, params: &PaginationParams,
     ) -> PluginResult<PaginatedResponse<Repository>> {
+        if self.deployment == DeploymentType::Server {
+            return self.list_server_repositories(None, params).await;
+        }
+
         self.retry_policy
             .retry(|| async {
                 let url = format!(
@@ -169,6 +347,10 @@ impl BitbucketClient {
     pub async fn list_pipelines(
         &self, workspace: &str, repo_slug: &str, limit: usize,
     ) -> PluginResult<Vec<Pipeline>> {
+        if self.deployment == DeploymentType::Server {
+            return self.list_server_builds(workspace, repo_slug, limit).await;
+        }
+
         let pagelen = limit.min(100);
         self.retry_policy
             .retry(|| async {
@@ -193,6 +375,33 @@ impl BitbucketClient {
             .await
     }
 
+    // Cloud only; Server builds are not tied to the repository's branch list.
+    pub async fn list_branches(
+        &self, workspace: &str, repo_slug: &str,
+    ) -> PluginResult<Vec<Branch>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "{}/repositories/{}/{}/refs/branches?pagelen=100",
+                    self.api_url, workspace, repo_slug
+                );
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .header(reqwest::header::AUTHORIZATION, &self.auth_value)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!("Failed to list branches: {}", e))
+                    })?;
+
+                let paginated: BitbucketPaginatedResponse<Branch> =
+                    self.handle_response(response).await?;
+                Ok(paginated.values)
+            })
+            .await
+    }
+
     pub async fn list_steps(
         &self, workspace: &str, repo_slug: &str, pipeline_uuid: &str,
     ) -> PluginResult<Vec<PipelineStep>> {
@@ -281,7 +490,9 @@ impl BitbucketClient {
                 if status.is_success() {
                     Ok(())
                 } else {
-                    let error_text = response.text().await.unwrap_or_default();
+                    let error_text = read_text(response, self.max_response_bytes)
+                        .await
+                        .unwrap_or_default();
                     Err(PluginError::ApiError(format!(
                         "Failed to stop pipeline ({}): {}",
                         status, error_text
@@ -298,10 +509,15 @@ impl BitbucketClient {
         let url = response.url().clone();
 
         if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
-            let error_text = response
-                .text()
+            let error_text = read_text(response, self.max_response_bytes)
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
+            if status == StatusCode::FORBIDDEN {
+                return Err(PluginError::PermissionDenied(format!(
+                    "Permission denied for {}: {}",
+                    url, error_text
+                )));
+            }
             return Err(PluginError::AuthenticationFailed(format!(
                 "Authentication failed for {}: {}",
                 url, error_text
@@ -315,9 +531,12 @@ impl BitbucketClient {
             )));
         }
 
+        if let Some(error) = pipedash_plugin_api::rate_limit_error(&response) {
+            return Err(error);
+        }
+
         if !status.is_success() {
-            let error_text = response
-                .text()
+            let error_text = read_text(response, self.max_response_bytes)
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
             return Err(PluginError::ApiError(format!(
@@ -326,11 +545,14 @@ impl BitbucketClient {
             )));
         }
 
-        response.json::<T>().await.map_err(|e| {
-            PluginError::ApiError(format!(
-                "Failed to parse Bitbucket API response from {}: {}",
-                url, e
-            ))
-        })
+        read_json(response, self.max_response_bytes)
+            .await
+            .map_err(|e| match e {
+                PluginError::SerializationError(e) => PluginError::ApiError(format!(
+                    "Failed to parse Bitbucket API response from {}: {}",
+                    url, e
+                )),
+                e => e,
+            })
     }
 }
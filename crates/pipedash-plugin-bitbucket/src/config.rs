@@ -35,20 +35,102 @@ pub(crate) fn parse_selected_items(config: &HashMap<String, String>) -> Option<V
     })
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DeploymentType {
+    Cloud,
+    Server,
+}
+
+pub(crate) fn get_deployment_type(config: &HashMap<String, String>) -> DeploymentType {
+    match config.get("deployment_type").map(|s| s.trim()) {
+        Some("server") => DeploymentType::Server,
+        _ => DeploymentType::Cloud,
+    }
+}
+
 pub(crate) fn get_api_url() -> String {
     "https://api.bitbucket.org/2.0".to_string()
 }
 
+pub(crate) fn get_server_url(config: &HashMap<String, String>) -> PluginResult<String> {
+    config
+        .get("server_url")
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            PluginError::InvalidConfig(
+                "Missing server URL for Bitbucket Server/Data Center".to_string(),
+            )
+        })
+}
+
 pub(crate) fn get_auth(config: &HashMap<String, String>) -> PluginResult<(String, String)> {
     let email = config
         .get("email")
         .ok_or_else(|| PluginError::InvalidConfig("Missing Atlassian account email".to_string()))?
         .clone();
 
-    let api_token = config
-        .get("api_token")
-        .ok_or_else(|| PluginError::InvalidConfig("Missing Bitbucket API token".to_string()))?
-        .clone();
+    let api_token = get_api_token(config)?;
 
     Ok((email, api_token))
 }
+
+pub(crate) fn get_api_token(config: &HashMap<String, String>) -> PluginResult<String> {
+    config
+        .get("api_token")
+        .cloned()
+        .ok_or_else(|| PluginError::InvalidConfig("Missing Bitbucket API token".to_string()))
+}
+
+// Cloud uses Basic auth with the account email; Server/Data Center takes an
+// HTTP access token as a bearer token.
+pub(crate) fn get_auth_header(
+    config: &HashMap<String, String>, deployment: DeploymentType,
+) -> PluginResult<String> {
+    match deployment {
+        DeploymentType::Cloud => {
+            let (email, api_token) = get_auth(config)?;
+            let credentials = format!("{}:{}", email, api_token);
+            let encoded = base64::Engine::encode(
+                &base64::engine::general_purpose::STANDARD,
+                credentials.as_bytes(),
+            );
+            Ok(format!("Basic {}", encoded))
+        }
+        DeploymentType::Server => Ok(format!("Bearer {}", get_api_token(config)?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_auth_header() {
+        let mut config = HashMap::new();
+        config.insert("deployment_type".to_string(), "server".to_string());
+        config.insert("api_token".to_string(), "secret".to_string());
+
+        let deployment = get_deployment_type(&config);
+        assert_eq!(deployment, DeploymentType::Server);
+        assert_eq!(
+            get_auth_header(&config, deployment).unwrap(),
+            "Bearer secret"
+        );
+        assert!(get_server_url(&config).is_err());
+    }
+
+    #[test]
+    fn test_cloud_is_default() {
+        let mut config = HashMap::new();
+        config.insert("email".to_string(), "me@example.com".to_string());
+        config.insert("api_token".to_string(), "secret".to_string());
+
+        let deployment = get_deployment_type(&config);
+        assert_eq!(deployment, DeploymentType::Cloud);
+        assert!(get_auth_header(&config, deployment)
+            .unwrap()
+            .starts_with("Basic "));
+    }
+}
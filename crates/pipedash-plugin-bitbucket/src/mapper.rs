@@ -1,18 +1,22 @@
 use std::collections::HashMap;
 
-use chrono::Utc;
+use chrono::{
+    TimeZone,
+    Utc,
+};
 use pipedash_plugin_api::{
     AvailablePipeline,
     Pipeline,
     PipelineRun,
     PipelineStatus,
+    PullRequestRef,
 };
 
 use crate::types;
 
 pub(crate) fn map_status(state: &types::PipelineState) -> PipelineStatus {
     match state.name.as_str() {
-        "PENDING" => PipelineStatus::Pending,
+        "PENDING" => PipelineStatus::Queued,
         "PARSING" => PipelineStatus::Running,
         "IN_PROGRESS" => {
             let is_paused = state
@@ -21,12 +25,12 @@ pub(crate) fn map_status(state: &types::PipelineState) -> PipelineStatus {
                 .map(|s| s.name == "PAUSED")
                 .unwrap_or(false);
             if is_paused {
-                PipelineStatus::Pending
+                PipelineStatus::Waiting
             } else {
                 PipelineStatus::Running
             }
         }
-        "PAUSED" | "HALTED" => PipelineStatus::Pending,
+        "PAUSED" | "HALTED" => PipelineStatus::Waiting,
         "COMPLETED" => match state.result.as_ref().map(|r| r.name.as_str()) {
             Some("SUCCESSFUL") => PipelineStatus::Success,
             Some("FAILED") | Some("ERROR") => PipelineStatus::Failed,
@@ -72,6 +76,44 @@ pub(crate) fn map_pipeline(
     }
 }
 
+fn commit_author(commit: &types::PipelineCommit) -> Option<String> {
+    let author = commit.author.as_ref()?;
+    author
+        .user
+        .as_ref()
+        .map(|user| user.display_name.clone())
+        .or_else(|| {
+            author
+                .raw
+                .as_deref()
+                .map(|raw| raw.split('<').next().unwrap_or(raw).trim().to_string())
+        })
+        .filter(|name| !name.is_empty())
+}
+
+fn pull_request(
+    pipeline: &types::Pipeline, workspace: &str, repo_slug: &str,
+) -> Option<PullRequestRef> {
+    let pr = pipeline.target.pullrequest.as_ref()?;
+    let url = pr
+        .links
+        .as_ref()
+        .and_then(|links| links.html.as_ref())
+        .map(|link| link.href.clone())
+        .unwrap_or_else(|| {
+            format!(
+                "https://bitbucket.org/{}/{}/pull-requests/{}",
+                workspace, repo_slug, pr.id
+            )
+        });
+
+    Some(PullRequestRef {
+        number: pr.id,
+        title: pr.title.clone(),
+        url: Some(url),
+    })
+}
+
 pub(crate) fn map_pipeline_run(
     pipeline: &types::Pipeline, workspace: &str, repo_slug: &str, provider_id: i64,
 ) -> PipelineRun {
@@ -120,8 +162,17 @@ pub(crate) fn map_pipeline_run(
             .and_then(|c| c.message.clone()),
         branch: pipeline.target.ref_name.clone(),
         actor: pipeline.creator.as_ref().map(|u| u.display_name.clone()),
+        commit_author: pipeline.target.commit.as_ref().and_then(commit_author),
+        author_avatar_url: pipeline
+            .creator
+            .as_ref()
+            .and_then(|u| u.links.as_ref())
+            .and_then(|links| links.avatar.as_ref())
+            .map(|avatar| avatar.href.clone()),
+        pull_request: pull_request(pipeline, workspace, repo_slug),
         inputs: None,
         metadata,
+        annotations: Vec::new(),
     }
 }
 
@@ -134,3 +185,190 @@ pub(crate) fn map_available_pipeline(repo: &types::Repository) -> AvailablePipel
         repository: Some(repo.slug.clone()),
     }
 }
+
+pub(crate) fn map_server_repository(
+    repo: &types::ServerRepository, server_url: &str,
+) -> types::Repository {
+    let html = repo
+        .links
+        .self_links
+        .first()
+        .map(|link| link.href.clone())
+        .unwrap_or_else(|| {
+            format!(
+                "{}/projects/{}/repos/{}/browse",
+                server_url, repo.project.key, repo.slug
+            )
+        });
+
+    types::Repository {
+        uuid: repo.id.to_string(),
+        name: repo.name.clone(),
+        full_name: format!("{}/{}", repo.project.key, repo.slug),
+        slug: repo.slug.clone(),
+        description: repo.description.clone(),
+        workspace: types::WorkspaceRef {
+            uuid: repo.project.id.to_string(),
+            slug: repo.project.key.clone(),
+            name: repo.project.name.clone(),
+        },
+        links: types::RepositoryLinks {
+            html: types::Link { href: html },
+        },
+    }
+}
+
+pub(crate) fn map_server_project(project: types::ServerProject) -> types::Workspace {
+    types::Workspace {
+        uuid: project.key.clone(),
+        slug: project.key,
+        name: project.name,
+    }
+}
+
+// Server has no pipelines of its own, only build statuses reported by Bamboo,
+// Jenkins and friends. Each status becomes a run, numbered by the time it was
+// reported since statuses carry no build number.
+pub(crate) fn map_server_build(
+    build: &types::ServerBuildStatus, commit: &types::ServerCommit,
+) -> types::Pipeline {
+    let (name, result) = match build.state.as_str() {
+        "INPROGRESS" => ("IN_PROGRESS", None),
+        "SUCCESSFUL" => ("COMPLETED", Some("SUCCESSFUL")),
+        "FAILED" => ("COMPLETED", Some("FAILED")),
+        "CANCELLED" => ("COMPLETED", Some("STOPPED")),
+        _ => ("PENDING", None),
+    };
+    let created_on = Utc
+        .timestamp_millis_opt(build.date_added)
+        .single()
+        .unwrap_or_else(Utc::now);
+
+    types::Pipeline {
+        uuid: format!("{}-{}", build.key, build.date_added),
+        build_number: build.date_added,
+        state: types::PipelineState {
+            name: name.to_string(),
+            result: result.map(|name| types::PipelineResult {
+                name: name.to_string(),
+            }),
+            stage: None,
+        },
+        target: types::PipelineTarget {
+            target_type: "build_status".to_string(),
+            ref_name: None,
+            ref_type: None,
+            commit: Some(types::PipelineCommit {
+                hash: commit.id.clone(),
+                message: commit.message.clone(),
+                author: commit.author.as_ref().map(|author| types::CommitAuthor {
+                    raw: Some(
+                        author
+                            .display_name
+                            .clone()
+                            .unwrap_or_else(|| author.name.clone()),
+                    ),
+                    user: None,
+                }),
+            }),
+            selector: Some(types::PipelineSelector {
+                selector_type: "build".to_string(),
+                pattern: Some(build.name.clone().unwrap_or_else(|| build.key.clone())),
+            }),
+            pullrequest: None,
+        },
+        created_on,
+        completed_on: result.map(|_| created_on),
+        duration_in_seconds: None,
+        creator: commit.author.as_ref().map(|author| types::User {
+            uuid: author.name.clone(),
+            display_name: author
+                .display_name
+                .clone()
+                .unwrap_or_else(|| author.name.clone()),
+            nickname: Some(author.name.clone()),
+            account_id: None,
+            links: None,
+        }),
+        links: types::PipelineLinks {
+            self_link: None,
+            html: Some(types::Link {
+                href: build.url.clone(),
+            }),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_server_build() {
+        let build: types::ServerBuildStatus = serde_json::from_value(serde_json::json!({
+            "state": "FAILED",
+            "key": "PROJ-PLAN",
+            "name": "PROJ-PLAN #42",
+            "url": "https://bamboo.example.com/browse/PROJ-PLAN-42",
+            "dateAdded": 1_700_000_000_000i64,
+        }))
+        .unwrap();
+        let commit: types::ServerCommit = serde_json::from_value(serde_json::json!({
+            "id": "abc123",
+            "message": "Fix build",
+            "author": { "name": "jdoe", "displayName": "J. Doe" },
+        }))
+        .unwrap();
+
+        let pipeline = map_server_build(&build, &commit);
+        assert_eq!(map_status(&pipeline.state), PipelineStatus::Failed);
+        assert_eq!(pipeline.build_number, 1_700_000_000_000);
+
+        let run = map_pipeline_run(&pipeline, "PROJ", "repo", 1);
+        assert_eq!(run.commit_sha.as_deref(), Some("abc123"));
+        assert_eq!(run.actor.as_deref(), Some("J. Doe"));
+        assert_eq!(
+            run.logs_url,
+            "https://bamboo.example.com/browse/PROJ-PLAN-42"
+        );
+    }
+
+    #[test]
+    fn test_map_pipeline_run_pull_request() {
+        let pipeline: types::Pipeline = serde_json::from_value(serde_json::json!({
+            "uuid": "{1}",
+            "build_number": 7,
+            "state": { "name": "COMPLETED", "result": { "name": "FAILED" } },
+            "target": {
+                "type": "pipeline_pullrequest_target",
+                "commit": {
+                    "hash": "abc123",
+                    "author": { "raw": "Jane Doe <jane@example.com>" },
+                },
+                "pullrequest": { "id": 12, "title": "Add caching" },
+            },
+            "created_on": "2024-01-01T00:00:00Z",
+            "creator": {
+                "uuid": "{u}",
+                "display_name": "Jane",
+                "links": { "avatar": { "href": "https://avatars.example.com/jane" } },
+            },
+            "links": {},
+        }))
+        .unwrap();
+
+        let run = map_pipeline_run(&pipeline, "ws", "repo", 1);
+        assert_eq!(run.commit_author.as_deref(), Some("Jane Doe"));
+        assert_eq!(
+            run.author_avatar_url.as_deref(),
+            Some("https://avatars.example.com/jane")
+        );
+        let pr = run.pull_request.unwrap();
+        assert_eq!(pr.number, 12);
+        assert_eq!(pr.title.as_deref(), Some("Add caching"));
+        assert_eq!(
+            pr.url.as_deref(),
+            Some("https://bitbucket.org/ws/repo/pull-requests/12")
+        );
+    }
+}
@@ -7,7 +7,9 @@ pub fn create_metadata() -> PluginMetadata {
         name: "Bitbucket Pipelines".to_string(),
         provider_type: "bitbucket".to_string(),
         version: "0.1.0".to_string(),
-        description: "Monitor and trigger Bitbucket Cloud Pipelines CI/CD".to_string(),
+        description:
+            "Monitor and trigger Bitbucket Cloud Pipelines, or track build statuses on Bitbucket Server/Data Center"
+                .to_string(),
         author: Some("Pipedash Team".to_string()),
         icon: Some("https://cdn.simpleicons.org/bitbucket/0052CC".to_string()),
         config_schema: create_config_schema(),
@@ -20,15 +22,43 @@ pub fn create_metadata() -> PluginMetadata {
 
 fn create_config_schema() -> ConfigSchema {
     ConfigSchema::new()
+        .add_field(ConfigField {
+            key: "deployment_type".to_string(),
+            label: "Deployment Type".to_string(),
+            description: Some(
+                "'cloud' for bitbucket.org, 'server' for self-hosted Bitbucket Server/Data Center"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Select,
+            required: false,
+            default_value: Some(serde_json::Value::String("cloud".to_string())),
+            options: Some(vec!["cloud".to_string(), "server".to_string()]),
+            validation_regex: None,
+            validation_message: None,
+        })
+        .add_field(ConfigField {
+            key: "server_url".to_string(),
+            label: "Server URL".to_string(),
+            description: Some(
+                "Bitbucket Server/Data Center URL (e.g., https://bitbucket.example.com). Server only."
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Text,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: Some(r"^https?://.+".to_string()),
+            validation_message: Some("Must start with http:// or https://".to_string()),
+        })
         .add_field(ConfigField {
             key: "email".to_string(),
             label: "Email".to_string(),
             description: Some(
-                "Your Atlassian account email (found in Bitbucket Personal settings > Email)"
+                "Your Atlassian account email (found in Bitbucket Personal settings > Email). Cloud only."
                     .to_string(),
             ),
             field_type: ConfigFieldType::Text,
-            required: true,
+            required: false,
             default_value: None,
             options: None,
             validation_regex: None,
@@ -38,7 +68,7 @@ fn create_config_schema() -> ConfigSchema {
             key: "api_token".to_string(),
             label: "API Token".to_string(),
             description: Some(
-                "Bitbucket API token with Repository:Read, Workspace:Read, and Pipelines:Read/Write scopes"
+                "Cloud: API token with Repository:Read, Workspace:Read, and Pipelines:Read/Write scopes. Server/Data Center: HTTP access token with repository read permission."
                     .to_string(),
             ),
             field_type: ConfigFieldType::Password,
@@ -48,6 +78,7 @@ fn create_config_schema() -> ConfigSchema {
             validation_regex: None,
             validation_message: None,
         })
+        .with_network_fields()
 }
 
 fn create_capabilities() -> PluginCapabilities {
@@ -55,10 +86,11 @@ fn create_capabilities() -> PluginCapabilities {
         pipelines: true,
         pipeline_runs: true,
         trigger: true,
-        agents: false,
-        artifacts: false,
-        queues: false,
-        custom_tables: false,
+        branch_statuses: true,
+        cancel: true,
+        workflow_parameters: true,
+        pagination: PaginationStyle::Server,
+        ..PluginCapabilities::default()
     }
 }
 
@@ -78,6 +78,18 @@ impl BitbucketPlugin {
         }
     }
 
+    // Server/Data Center only reports statuses from external CI, so anything
+    // that acts on a run has to happen in that CI.
+    fn ensure_cloud(&self, action: &str) -> PluginResult<()> {
+        match self.client()?.deployment() {
+            config::DeploymentType::Cloud => Ok(()),
+            config::DeploymentType::Server => Err(PluginError::NotSupported(format!(
+                "{} is not supported for Bitbucket Server/Data Center; use the CI server that reports the build",
+                action
+            ))),
+        }
+    }
+
     async fn find_pipeline_by_build_number(
         &self, workspace: &str, repo_slug: &str, build_number: i64,
     ) -> PluginResult<types::Pipeline> {
@@ -110,15 +122,12 @@ impl Plugin for BitbucketPlugin {
         &mut self, provider_id: i64, config: HashMap<String, String>,
         http_client: Option<std::sync::Arc<reqwest::Client>>,
     ) -> PluginResult<()> {
-        let (email, api_token) = config::get_auth(&config)?;
-        let api_url = config::get_api_url();
-
-        let credentials = format!("{}:{}", email, api_token);
-        let encoded = base64::Engine::encode(
-            &base64::engine::general_purpose::STANDARD,
-            credentials.as_bytes(),
-        );
-        let auth_value = format!("Basic {}", encoded);
+        let deployment = config::get_deployment_type(&config);
+        let api_url = match deployment {
+            config::DeploymentType::Cloud => config::get_api_url(),
+            config::DeploymentType::Server => config::get_server_url(&config)?,
+        };
+        let auth_value = config::get_auth_header(&config, deployment)?;
 
         let client = http_client.unwrap_or_else(|| {
             std::sync::Arc::new(
@@ -133,16 +142,31 @@ impl Plugin for BitbucketPlugin {
             )
         });
 
-        self.client = Some(client::BitbucketClient::new(client, api_url, auth_value));
+        self.client = Some(
+            client::BitbucketClient::new(client, api_url, auth_value, deployment)
+                .with_max_response_bytes(max_response_bytes(&config)),
+        );
         self.provider_id = Some(provider_id);
         self.config = config;
 
         Ok(())
     }
 
+    fn api_endpoint(&self) -> Option<String> {
+        match config::get_deployment_type(&self.config) {
+            config::DeploymentType::Cloud => Some(config::get_api_url()),
+            config::DeploymentType::Server => config::get_server_url(&self.config).ok(),
+        }
+    }
+
     async fn validate_credentials(&self) -> PluginResult<bool> {
         let client = self.client()?;
-        client.get_user().await?;
+        match client.deployment() {
+            config::DeploymentType::Cloud => {
+                client.get_user().await?;
+            }
+            config::DeploymentType::Server => client.validate_server_access().await?,
+        }
         Ok(true)
     }
 
@@ -251,6 +275,34 @@ impl Plugin for BitbucketPlugin {
             .collect())
     }
 
+    // Pull request pipelines already carry their pull request, so no separate
+    // lookup is needed. On Server every branch with a build is listed.
+    async fn list_branch_statuses(&self, pipeline_id: &str) -> PluginResult<Vec<BranchStatus>> {
+        let (provider_id, workspace, repo_slug) = config::parse_pipeline_id(pipeline_id)?;
+        let client = self.client()?;
+
+        let pipelines = client.list_pipelines(&workspace, &repo_slug, 100).await?;
+        let open_branches: Option<std::collections::HashSet<String>> = match client.deployment() {
+            config::DeploymentType::Cloud => Some(
+                client
+                    .list_branches(&workspace, &repo_slug)
+                    .await?
+                    .into_iter()
+                    .map(|b| b.name)
+                    .collect(),
+            ),
+            config::DeploymentType::Server => None,
+        };
+
+        Ok(latest_run_per_branch(
+            pipelines
+                .iter()
+                .map(|p| mapper::map_pipeline_run(p, &workspace, &repo_slug, provider_id)),
+            open_branches.as_ref(),
+            &HashMap::new(),
+        ))
+    }
+
     async fn fetch_run_details(
         &self, pipeline_id: &str, run_number: i64,
     ) -> PluginResult<PipelineRun> {
@@ -294,6 +346,7 @@ impl Plugin for BitbucketPlugin {
     async fn trigger_pipeline(&self, params: TriggerParams) -> PluginResult<String> {
         let (_, workspace, repo_slug) = config::parse_pipeline_id(&params.workflow_id)?;
         let client = self.client()?;
+        self.ensure_cloud("Triggering builds")?;
 
         let ref_name = params
             .inputs
@@ -339,6 +392,7 @@ impl Plugin for BitbucketPlugin {
     async fn cancel_run(&self, pipeline_id: &str, run_number: i64) -> PluginResult<()> {
         let (_, workspace, repo_slug) = config::parse_pipeline_id(pipeline_id)?;
         let client = self.client()?;
+        self.ensure_cloud("Cancelling builds")?;
 
         let pipeline = self
             .find_pipeline_by_build_number(&workspace, &repo_slug, run_number)
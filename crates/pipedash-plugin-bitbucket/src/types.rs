@@ -13,6 +13,14 @@ pub struct User {
     pub display_name: String,
     pub nickname: Option<String>,
     pub account_id: Option<String>,
+    #[serde(default)]
+    pub links: Option<UserLinks>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserLinks {
+    #[serde(default)]
+    pub avatar: Option<Link>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +59,11 @@ pub struct Link {
     pub href: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Branch {
+    pub name: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pipeline {
     pub uuid: String,
@@ -98,6 +111,8 @@ pub struct PipelineTarget {
     pub commit: Option<PipelineCommit>,
     #[serde(default)]
     pub selector: Option<PipelineSelector>,
+    #[serde(default)]
+    pub pullrequest: Option<PipelinePullRequest>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,6 +120,27 @@ pub struct PipelineCommit {
     pub hash: String,
     #[serde(default)]
     pub message: Option<String>,
+    #[serde(default)]
+    pub author: Option<CommitAuthor>,
+}
+
+// `raw` is the git author line ("Name <email>"); `user` is only set when
+// Bitbucket could match the email to an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitAuthor {
+    #[serde(default)]
+    pub raw: Option<String>,
+    #[serde(default)]
+    pub user: Option<User>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelinePullRequest {
+    pub id: i64,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub links: Option<PipelineLinks>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -179,3 +215,77 @@ pub struct TriggerSelector {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
 }
+
+// Bitbucket Server / Data Center (REST API 1.0). Repositories and build
+// statuses are normalized into the Cloud types above by the mapper.
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerPage<T> {
+    pub values: Vec<T>,
+    #[serde(default)]
+    pub start: usize,
+    #[serde(default)]
+    pub is_last_page: bool,
+    #[serde(default)]
+    pub next_page_start: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerProject {
+    pub id: i64,
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerRepository {
+    pub id: i64,
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub project: ServerProject,
+    #[serde(default)]
+    pub links: ServerLinks,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerLinks {
+    #[serde(default, rename = "self")]
+    pub self_links: Vec<Link>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerCommit {
+    pub id: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub author: Option<ServerUser>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerUser {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerBuildStatus {
+    pub state: String,
+    pub key: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub url: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    // Milliseconds since the epoch.
+    pub date_added: i64,
+}
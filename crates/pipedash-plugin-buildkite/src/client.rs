@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 
 use chrono::Utc;
+use pipedash_plugin_api::http::DEFAULT_MAX_RESPONSE_MB;
 use pipedash_plugin_api::{
+    read_json,
+    read_text,
+    ArtifactDownload,
     AvailablePipeline,
     BuildArtifact,
     PaginatedResponse,
@@ -10,9 +14,12 @@ use pipedash_plugin_api::{
     PipelineRun,
     PluginError,
     PluginResult,
+    PullRequestRef,
     RetryPolicy,
+    BLOCKED_STEP_METADATA_KEY,
 };
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 
 use crate::{
     config,
@@ -20,12 +27,17 @@ use crate::{
     types,
 };
 
-const BASE_URL: &str = "https://api.buildkite.com/v2";
+pub(crate) const BASE_URL: &str = "https://api.buildkite.com/v2";
+
+// Run metadata key holding `mapper::agent_wait_seconds`, read back by
+// `extract_metrics` for cached runs.
+pub(crate) const AGENT_WAIT_SECONDS_KEY: &str = "agent_wait_seconds";
 
 pub(crate) struct BuildkiteClient {
     http_client: std::sync::Arc<Client>,
     token: String,
     retry_policy: RetryPolicy,
+    max_response_bytes: usize,
 }
 
 impl BuildkiteClient {
@@ -34,15 +46,34 @@ impl BuildkiteClient {
             http_client,
             token,
             retry_policy: RetryPolicy::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_MB * 1024 * 1024,
         }
     }
 
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    async fn parse_json<T: DeserializeOwned>(
+        &self, response: reqwest::Response, what: &str,
+    ) -> PluginResult<T> {
+        read_json(response, self.max_response_bytes)
+            .await
+            .map_err(|e| match e {
+                PluginError::SerializationError(e) => {
+                    PluginError::ApiError(format!("Failed to parse {what}: {e}"))
+                }
+                e => e,
+            })
+    }
+
     pub async fn fetch_organizations(&self) -> PluginResult<Vec<types::Organization>> {
         self.retry_policy
             .retry(|| async {
                 let url = format!("{BASE_URL}/organizations");
 
-                let orgs = self
+                let response = self
                     .http_client
                     .get(&url)
                     .header(
@@ -53,13 +84,10 @@ impl BuildkiteClient {
                     .await
                     .map_err(|e| {
                         PluginError::ApiError(format!("Failed to fetch organizations: {e}"))
-                    })?
-                    .json()
-                    .await
-                    .map_err(|e| {
-                        PluginError::ApiError(format!("Failed to parse organizations: {e}"))
                     })?;
 
+                let orgs = self.parse_json(response, "organizations").await?;
+
                 Ok(orgs)
             })
             .await
@@ -74,7 +102,7 @@ impl BuildkiteClient {
                     "{BASE_URL}/organizations/{org_slug}/pipelines?per_page={per_page}&page={page}"
                 );
 
-                let pipelines: Vec<types::Pipeline> = self
+                let response = self
                     .http_client
                     .get(&url)
                     .header(
@@ -87,15 +115,12 @@ impl BuildkiteClient {
                         PluginError::ApiError(format!(
                             "Failed to fetch pipelines for {org_slug}: {e}"
                         ))
-                    })?
-                    .json()
-                    .await
-                    .map_err(|e| {
-                        PluginError::ApiError(format!(
-                            "Failed to parse pipelines for {org_slug}: {e}"
-                        ))
                     })?;
 
+                let pipelines: Vec<types::Pipeline> = self
+                    .parse_json(response, &format!("pipelines for {org_slug}"))
+                    .await?;
+
                 Ok(pipelines
                     .into_iter()
                     .map(|pipeline| {
@@ -120,7 +145,7 @@ impl BuildkiteClient {
             .retry(|| async {
                 let pipeline_url = format!("{BASE_URL}/organizations/{org}/pipelines/{slug}");
 
-                let pipeline: types::Pipeline = self
+                let response = self
                     .http_client
                     .get(&pipeline_url)
                     .header(
@@ -129,15 +154,14 @@ impl BuildkiteClient {
                     )
                     .send()
                     .await
-                    .map_err(|e| PluginError::ApiError(format!("Failed to fetch pipeline: {e}")))?
-                    .json()
-                    .await
-                    .map_err(|e| PluginError::ApiError(format!("Failed to parse pipeline: {e}")))?;
+                    .map_err(|e| PluginError::ApiError(format!("Failed to fetch pipeline: {e}")))?;
+
+                let pipeline: types::Pipeline = self.parse_json(response, "pipeline").await?;
 
                 let builds_url =
                     format!("{BASE_URL}/organizations/{org}/pipelines/{slug}/builds?per_page=1");
 
-                let builds: Vec<types::Build> = self
+                let response = self
                     .http_client
                     .get(&builds_url)
                     .header(
@@ -146,10 +170,9 @@ impl BuildkiteClient {
                     )
                     .send()
                     .await
-                    .map_err(|e| PluginError::ApiError(format!("Failed to fetch builds: {e}")))?
-                    .json()
-                    .await
-                    .map_err(|e| PluginError::ApiError(format!("Failed to parse builds: {e}")))?;
+                    .map_err(|e| PluginError::ApiError(format!("Failed to fetch builds: {e}")))?;
+
+                let builds: Vec<types::Build> = self.parse_json(response, "builds").await?;
 
                 let latest_build = builds.first();
                 let status = latest_build
@@ -204,7 +227,7 @@ impl BuildkiteClient {
                 per_page, page
             );
 
-            let builds: Vec<types::Build> = self
+            let response = self
                 .http_client
                 .get(&url)
                 .header(
@@ -213,10 +236,9 @@ impl BuildkiteClient {
                 )
                 .send()
                 .await
-                .map_err(|e| PluginError::ApiError(format!("Failed to fetch builds: {e}")))?
-                .json()
-                .await
-                .map_err(|e| PluginError::ApiError(format!("Failed to parse builds: {e}")))?;
+                .map_err(|e| PluginError::ApiError(format!("Failed to fetch builds: {e}")))?;
+
+            let builds: Vec<types::Build> = self.parse_json(response, "builds").await?;
 
             if builds.is_empty() {
                 break;
@@ -233,6 +255,31 @@ impl BuildkiteClient {
         Ok(all_builds)
     }
 
+    pub async fn fetch_build(
+        &self, org: &str, slug: &str, build_number: i64,
+    ) -> PluginResult<types::Build> {
+        let url = format!("{BASE_URL}/organizations/{org}/pipelines/{slug}/builds/{build_number}");
+
+        let response = self
+            .http_client
+            .get(&url)
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {}", self.token),
+            )
+            .send()
+            .await
+            .map_err(|e| PluginError::ApiError(format!("Failed to fetch build: {e}")))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(PluginError::PipelineNotFound(format!(
+                "Build #{build_number} not found for {org}/{slug}"
+            )));
+        }
+
+        self.parse_json(response, "build").await
+    }
+
     pub async fn trigger_build(
         &self, org: &str, slug: &str, branch: String, inputs: Option<serde_json::Value>,
     ) -> PluginResult<types::Build> {
@@ -272,8 +319,7 @@ impl BuildkiteClient {
                     .map_err(|e| PluginError::ApiError(format!("Failed to trigger build: {e}")))?;
 
                 if !response.status().is_success() {
-                    let error_text = response
-                        .text()
+                    let error_text = read_text(response, self.max_response_bytes)
                         .await
                         .unwrap_or_else(|_| "Unknown error".to_string());
                     return Err(PluginError::ApiError(format!(
@@ -281,12 +327,7 @@ impl BuildkiteClient {
                     )));
                 }
 
-                let build = response
-                    .json()
-                    .await
-                    .map_err(|e| PluginError::ApiError(format!("Failed to parse response: {e}")))?;
-
-                Ok(build)
+                self.parse_json(response, "response").await
             })
             .await
     }
@@ -294,7 +335,7 @@ impl BuildkiteClient {
     pub async fn fetch_agents(&self, org: &str) -> PluginResult<Vec<types::Agent>> {
         let url = format!("{BASE_URL}/organizations/{org}/agents");
 
-        let agents = self
+        let response = self
             .http_client
             .get(&url)
             .header(
@@ -303,20 +344,40 @@ impl BuildkiteClient {
             )
             .send()
             .await
-            .map_err(|e| PluginError::ApiError(format!("Failed to fetch agents: {e}")))?
-            .json()
-            .await
-            .map_err(|e| PluginError::ApiError(format!("Failed to parse agents: {e}")))?;
+            .map_err(|e| PluginError::ApiError(format!("Failed to fetch agents: {e}")))?;
+
+        let agents = self.parse_json(response, "agents").await?;
 
         Ok(agents)
     }
 
+    pub async fn fetch_active_builds(&self, org: &str) -> PluginResult<Vec<types::QueuedBuild>> {
+        let url = format!(
+            "{BASE_URL}/organizations/{org}/builds?state[]=scheduled&state[]=running&per_page=100"
+        );
+
+        let response = self
+            .http_client
+            .get(&url)
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {}", self.token),
+            )
+            .send()
+            .await
+            .map_err(|e| PluginError::ApiError(format!("Failed to fetch active builds: {e}")))?;
+
+        let builds = self.parse_json(response, "active builds").await?;
+
+        Ok(builds)
+    }
+
     pub async fn fetch_artifacts(
         &self, org: &str, build_id: &str,
     ) -> PluginResult<Vec<types::Artifact>> {
         let url = format!("{BASE_URL}/organizations/{org}/builds/{build_id}/artifacts");
 
-        let artifacts = self
+        let response = self
             .http_client
             .get(&url)
             .header(
@@ -325,14 +386,92 @@ impl BuildkiteClient {
             )
             .send()
             .await
-            .map_err(|e| PluginError::ApiError(format!("Failed to fetch artifacts: {e}")))?
-            .json()
-            .await
-            .map_err(|e| PluginError::ApiError(format!("Failed to parse artifacts: {e}")))?;
+            .map_err(|e| PluginError::ApiError(format!("Failed to fetch artifacts: {e}")))?;
+
+        let artifacts = self.parse_json(response, "artifacts").await?;
 
         Ok(artifacts)
     }
 
+    pub async fn fetch_annotations(
+        &self, org: &str, pipeline_slug: &str, build_number: i64,
+    ) -> PluginResult<Vec<types::Annotation>> {
+        let url = format!(
+            "{BASE_URL}/organizations/{org}/pipelines/{pipeline_slug}/builds/{build_number}/annotations"
+        );
+
+        let response = self
+            .http_client
+            .get(&url)
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {}", self.token),
+            )
+            .send()
+            .await
+            .map_err(|e| PluginError::ApiError(format!("Failed to fetch annotations: {e}")))?;
+
+        let annotations = self.parse_json(response, "annotations").await?;
+
+        Ok(annotations)
+    }
+
+    pub fn artifact_download(&self, artifact: &BuildArtifact) -> ArtifactDownload {
+        // The download URL redirects to a pre-signed S3 URL; the token is only
+        // sent to Buildkite since the redirect crosses origins.
+        let mut headers = HashMap::new();
+        headers.insert(
+            reqwest::header::AUTHORIZATION.to_string(),
+            format!("Bearer {}", self.token),
+        );
+
+        ArtifactDownload {
+            url: artifact.download_url.clone(),
+            headers,
+        }
+    }
+
+    // Not retried: a second attempt after a timed-out success would be
+    // rejected because the step is no longer blocked.
+    pub async fn unblock_job(
+        &self, org: &str, pipeline_slug: &str, build_number: i64, job_id: &str,
+        fields: HashMap<String, String>,
+    ) -> PluginResult<()> {
+        let url = format!(
+            "{BASE_URL}/organizations/{org}/pipelines/{pipeline_slug}/builds/{build_number}/jobs/{job_id}/unblock"
+        );
+
+        tracing::info!(
+            build_number = build_number,
+            job_id = %job_id,
+            pipeline = %pipeline_slug,
+            "Unblocking Buildkite job"
+        );
+
+        let response = self
+            .http_client
+            .put(&url)
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {}", self.token),
+            )
+            .json(&serde_json::json!({ "fields": fields }))
+            .send()
+            .await
+            .map_err(|e| PluginError::ApiError(format!("Failed to unblock job: {e}")))?;
+
+        if !response.status().is_success() {
+            let error_text = read_text(response, self.max_response_bytes)
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(PluginError::ApiError(format!(
+                "Failed to unblock job: {error_text}"
+            )));
+        }
+
+        Ok(())
+    }
+
     pub async fn cancel_build(
         &self, org: &str, pipeline_slug: &str, build_number: i64,
     ) -> PluginResult<()> {
@@ -360,8 +499,7 @@ impl BuildkiteClient {
                 .map_err(|e| PluginError::ApiError(format!("Failed to cancel build: {e}")))?;
 
             if !response.status().is_success() {
-                let error_text = response
-                    .text()
+                let error_text = read_text(response, self.max_response_bytes)
                     .await
                     .unwrap_or_else(|_| "Unknown error".to_string());
                 return Err(PluginError::ApiError(format!(
@@ -432,6 +570,17 @@ pub(crate) fn build_to_pipeline_run(build: types::Build, pipeline_id: &str) -> P
 
     let inputs = Some(serde_json::Value::Object(inputs_map));
 
+    let mut metadata = HashMap::new();
+    if let Some(wait) = mapper::agent_wait_seconds(&build.jobs) {
+        metadata.insert(AGENT_WAIT_SECONDS_KEY.to_string(), serde_json::json!(wait));
+    }
+    if let Some(step) = mapper::pending_approvals(&build.jobs).into_iter().next() {
+        metadata.insert(
+            BLOCKED_STEP_METADATA_KEY.to_string(),
+            serde_json::json!(step.label),
+        );
+    }
+
     PipelineRun {
         id: format!("buildkite-build-{}", build.id),
         pipeline_id: pipeline_id.to_string(),
@@ -449,8 +598,24 @@ pub(crate) fn build_to_pipeline_run(build: types::Build, pipeline_id: &str) -> P
         commit_message: build.message.clone(),
         branch: Some(branch_value),
         actor: build.author.as_ref().map(|a| a.name.clone()),
+        commit_author: build
+            .author
+            .as_ref()
+            .map(|a| a.name.clone())
+            .filter(|name| !name.is_empty()),
+        author_avatar_url: build.creator.as_ref().and_then(|c| c.avatar_url.clone()),
+        pull_request: build
+            .pull_request
+            .as_ref()
+            .and_then(|pr| pr.id.parse::<i64>().ok())
+            .map(|number| PullRequestRef {
+                number,
+                title: None,
+                url: None,
+            }),
         inputs,
-        metadata: HashMap::new(), // No additional metadata for runs yet
+        metadata,
+        annotations: Vec::new(),
     }
 }
 
@@ -461,7 +626,7 @@ pub(crate) fn artifact_to_build_artifact(artifact: types::Artifact, run_id: &str
         filename: artifact.filename,
         size_bytes: artifact.size,
         download_url: artifact.download_url,
-        content_type: None,
+        content_type: artifact.mime_type,
         created_at: Utc::now(),
     }
 }
@@ -1,9 +1,16 @@
 use std::collections::HashMap;
 
-use chrono::Utc;
+use chrono::{
+    DateTime,
+    Utc,
+};
 use pipedash_plugin_api::{
+    AnnotationLevel,
     BuildAgent,
+    BuildQueue,
+    PendingApproval,
     PipelineStatus,
+    RunAnnotation,
 };
 
 use crate::types;
@@ -13,10 +20,10 @@ pub(crate) fn map_build_state(state: &str) -> PipelineStatus {
         "passed" => PipelineStatus::Success,
         "failed" => PipelineStatus::Failed,
         "running" | "started" => PipelineStatus::Running,
-        "scheduled" | "creating" | "waiting" => PipelineStatus::Pending,
+        "scheduled" | "creating" | "waiting" => PipelineStatus::Queued,
         "canceled" | "canceling" => PipelineStatus::Cancelled,
         "skipped" | "not_run" => PipelineStatus::Skipped,
-        "blocked" => PipelineStatus::Pending,
+        "blocked" => PipelineStatus::Waiting,
         _ => PipelineStatus::Pending,
     }
 }
@@ -29,7 +36,7 @@ pub(crate) fn map_agent(agent: types::Agent) -> BuildAgent {
             "idle".to_string()
         }
     } else {
-        "disconnected".to_string()
+        "offline".to_string()
     };
 
     let mut metadata = HashMap::new();
@@ -47,6 +54,151 @@ pub(crate) fn map_agent(agent: types::Agent) -> BuildAgent {
     }
 }
 
+fn agent_queue(job: &types::QueuedJob) -> String {
+    job.agent_query_rules
+        .iter()
+        .find_map(|rule| rule.strip_prefix("queue="))
+        .unwrap_or("default")
+        .to_string()
+}
+
+// Jobs that are scheduled, assigned or accepted are waiting for an agent.
+// Jobs blocked on dependencies or manual steps aren't counted.
+pub(crate) fn map_queues(builds: &[types::QueuedBuild], now: DateTime<Utc>) -> Vec<BuildQueue> {
+    let mut queues: HashMap<String, (Vec<DateTime<Utc>>, usize)> = HashMap::new();
+
+    for job in builds.iter().flat_map(|build| &build.jobs) {
+        match job.state.as_deref() {
+            Some("scheduled" | "assigned" | "accepted") => {
+                let queued_at = job
+                    .scheduled_at
+                    .as_deref()
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or(now);
+                queues
+                    .entry(agent_queue(job))
+                    .or_default()
+                    .0
+                    .push(queued_at);
+            }
+            Some("running") => {
+                queues.entry(agent_queue(job)).or_default().1 += 1;
+            }
+            _ => {}
+        }
+    }
+
+    let mut queues: Vec<BuildQueue> = queues
+        .into_iter()
+        .map(|(id, (queued_at, running))| BuildQueue::from_queued_at(id, &queued_at, running, now))
+        .collect();
+    queues.sort_by(|a, b| a.id.cmp(&b.id));
+    queues
+}
+
+// Total seconds the build's command jobs waited between becoming runnable and
+// an agent starting them. `None` when no job has started yet.
+pub(crate) fn agent_wait_seconds(jobs: &[types::BuildJob]) -> Option<i64> {
+    let parse = |s: &Option<String>| {
+        s.as_deref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+    };
+
+    jobs.iter()
+        .filter(|job| job.job_type.as_deref() == Some("script"))
+        .filter_map(|job| Some((parse(&job.runnable_at)?, parse(&job.started_at)?)))
+        .map(|(runnable_at, started_at)| (started_at - runnable_at).num_seconds().max(0))
+        .reduce(|total, wait| total + wait)
+}
+
+// Block steps the build is waiting on. Buildkite doesn't return a block
+// step's fields with the build, so the approver supplies them by key.
+pub(crate) fn pending_approvals(jobs: &[types::BuildJob]) -> Vec<PendingApproval> {
+    jobs.iter()
+        .filter(|job| {
+            job.job_type.as_deref() == Some("manual")
+                && job.state.as_deref() == Some("blocked")
+                && job.unblockable != Some(false)
+        })
+        .filter_map(|job| {
+            Some(PendingApproval {
+                job_id: job.id.clone()?,
+                label: job
+                    .label
+                    .clone()
+                    .filter(|label| !label.is_empty())
+                    .unwrap_or_else(|| "Block".to_string()),
+                prompt: None,
+                fields: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn map_annotation(annotation: types::Annotation) -> RunAnnotation {
+    let level = match annotation.style.as_deref() {
+        Some("error") => AnnotationLevel::Error,
+        Some("warning") => AnnotationLevel::Warning,
+        Some("success") => AnnotationLevel::Success,
+        _ => AnnotationLevel::Info,
+    };
+
+    RunAnnotation {
+        level,
+        // Annotations added without `--context` all share "default".
+        title: annotation.context.filter(|c| c != "default"),
+        summary: html_to_text(&annotation.body_html),
+        url: None,
+    }
+}
+
+// Annotation bodies are rendered Markdown. Block-level tags become line
+// breaks and everything else is dropped, which is enough for a one-line
+// summary such as "3 tests failed".
+fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+
+        let tag = rest[start + 1..start + end]
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if matches!(
+            tag.as_str(),
+            "br" | "p" | "div" | "li" | "tr" | "h1" | "h2" | "h3" | "h4" | "pre"
+        ) {
+            text.push('\n');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,6 +210,87 @@ mod tests {
         assert_eq!(map_build_state("running"), PipelineStatus::Running);
         assert_eq!(map_build_state("canceled"), PipelineStatus::Cancelled);
         assert_eq!(map_build_state("skipped"), PipelineStatus::Skipped);
+        assert_eq!(map_build_state("scheduled"), PipelineStatus::Queued);
+        assert_eq!(map_build_state("blocked"), PipelineStatus::Waiting);
         assert_eq!(map_build_state("unknown"), PipelineStatus::Pending);
     }
+
+    #[test]
+    fn test_map_queues() {
+        let now = DateTime::parse_from_rfc3339("2025-01-01T12:10:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let builds: Vec<types::QueuedBuild> = serde_json::from_value(serde_json::json!([
+            { "jobs": [
+                { "state": "scheduled", "scheduled_at": "2025-01-01T12:00:00Z",
+                  "agent_query_rules": ["queue=linux"] },
+                { "state": "running", "agent_query_rules": ["queue=linux"] },
+                { "state": "waiting", "agent_query_rules": ["queue=linux"] },
+            ] },
+            { "jobs": [
+                { "state": "assigned", "scheduled_at": "2025-01-01T12:08:00Z",
+                  "agent_query_rules": ["queue=linux"] },
+                { "state": "scheduled", "scheduled_at": "2025-01-01T12:09:00Z" },
+            ] },
+        ]))
+        .unwrap();
+
+        let queues = map_queues(&builds, now);
+
+        assert_eq!(queues.len(), 2);
+        assert_eq!(queues[0].id, "default");
+        assert_eq!(queues[0].avg_wait_time, Some(60));
+        assert_eq!(queues[1].id, "linux");
+        assert_eq!(queues[1].waiting, 2);
+        assert_eq!(queues[1].running, 1);
+        assert_eq!(queues[1].avg_wait_time, Some(360));
+    }
+
+    #[test]
+    fn test_agent_wait_seconds() {
+        let jobs: Vec<types::BuildJob> = serde_json::from_value(serde_json::json!([
+            { "type": "script", "runnable_at": "2025-01-01T12:00:00Z",
+              "started_at": "2025-01-01T12:01:30Z" },
+            { "type": "script", "runnable_at": "2025-01-01T12:05:00Z",
+              "started_at": "2025-01-01T12:05:10Z" },
+            { "type": "script", "runnable_at": "2025-01-01T12:06:00Z" },
+            { "type": "waiter" },
+        ]))
+        .unwrap();
+
+        assert_eq!(agent_wait_seconds(&jobs), Some(100));
+        assert_eq!(agent_wait_seconds(&jobs[2..]), None);
+    }
+
+    #[test]
+    fn test_pending_approvals() {
+        let jobs: Vec<types::BuildJob> = serde_json::from_value(serde_json::json!([
+            { "id": "j1", "type": "script", "state": "passed" },
+            { "id": "j2", "type": "manual", "label": ":rocket: Deploy?", "state": "blocked",
+              "unblockable": true },
+            { "id": "j3", "type": "manual", "label": "Release", "state": "unblocked" },
+            { "id": "j4", "type": "manual", "state": "blocked", "unblockable": false },
+        ]))
+        .unwrap();
+
+        let approvals = pending_approvals(&jobs);
+        assert_eq!(approvals.len(), 1);
+        assert_eq!(approvals[0].job_id, "j2");
+        assert_eq!(approvals[0].label, ":rocket: Deploy?");
+    }
+
+    #[test]
+    fn test_map_annotation() {
+        let annotation: types::Annotation = serde_json::from_value(serde_json::json!({
+            "context": "junit",
+            "style": "error",
+            "body_html": "<p><strong>3 tests failed</strong></p>\n<ul><li>auth &amp; login</li><li>checkout</li></ul>",
+        }))
+        .unwrap();
+
+        let mapped = map_annotation(annotation);
+        assert_eq!(mapped.level, AnnotationLevel::Error);
+        assert_eq!(mapped.title.as_deref(), Some("junit"));
+        assert_eq!(mapped.summary, "3 tests failed\nauth & login\ncheckout");
+    }
 }
@@ -19,20 +19,22 @@ pub fn create_metadata() -> PluginMetadata {
 }
 
 fn create_config_schema() -> ConfigSchema {
-    ConfigSchema::new().add_field(ConfigField {
-        key: "token".to_string(),
-        label: "API Token".to_string(),
-        description: Some(
-            "Buildkite API Access Token with read_builds, read_pipelines, and read_organizations scopes"
-                .to_string(),
-        ),
-        field_type: ConfigFieldType::Password,
-        required: true,
-        default_value: None,
-        options: None,
-        validation_regex: None,
-        validation_message: None,
-    })
+    ConfigSchema::new()
+        .add_field(ConfigField {
+            key: "token".to_string(),
+            label: "API Token".to_string(),
+            description: Some(
+                "Buildkite API Access Token with read_builds, read_pipelines, and read_organizations scopes"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Password,
+            required: true,
+            default_value: None,
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+        .with_network_fields()
 }
 
 fn create_capabilities() -> PluginCapabilities {
@@ -42,7 +44,11 @@ fn create_capabilities() -> PluginCapabilities {
         trigger: true,
         agents: true,
         artifacts: true,
-        queues: false,
-        custom_tables: false,
+        queues: true,
+        cancel: true,
+        workflow_parameters: true,
+        unblock_jobs: true,
+        pagination: PaginationStyle::Server,
+        ..PluginCapabilities::default()
     }
 }
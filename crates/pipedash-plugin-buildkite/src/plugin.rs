@@ -70,13 +70,20 @@ impl Plugin for BuildkitePlugin {
             )
         });
 
-        self.client = Some(client::BuildkiteClient::new(client, token));
+        self.client = Some(
+            client::BuildkiteClient::new(client, token)
+                .with_max_response_bytes(max_response_bytes(&config)),
+        );
         self.provider_id = Some(provider_id);
         self.config = config;
 
         Ok(())
     }
 
+    fn api_endpoint(&self) -> Option<String> {
+        Some(client::BASE_URL.to_string())
+    }
+
     async fn validate_credentials(&self) -> PluginResult<bool> {
         let client = self.client()?;
 
@@ -258,6 +265,15 @@ impl Plugin for BuildkitePlugin {
         Ok(agents.into_iter().map(mapper::map_agent).collect())
     }
 
+    async fn fetch_queues(&self) -> PluginResult<Vec<BuildQueue>> {
+        let (org, _) = config::parse_selected_items(&self.config)?;
+
+        let client = self.client()?;
+        let builds = client.fetch_active_builds(&org).await?;
+
+        Ok(mapper::map_queues(&builds, chrono::Utc::now()))
+    }
+
     async fn fetch_artifacts(&self, run_id: &str) -> PluginResult<Vec<BuildArtifact>> {
         let (org, _) = config::parse_selected_items(&self.config)?;
 
@@ -274,6 +290,17 @@ impl Plugin for BuildkitePlugin {
             .collect())
     }
 
+    async fn artifact_download(&self, artifact: &BuildArtifact) -> PluginResult<ArtifactDownload> {
+        if artifact.download_url.is_empty() {
+            return Err(PluginError::ApiError(format!(
+                "Artifact {} has no download URL",
+                artifact.filename
+            )));
+        }
+
+        Ok(self.client()?.artifact_download(artifact))
+    }
+
     async fn cancel_run(&self, pipeline_id: &str, run_number: i64) -> PluginResult<()> {
         let parts: Vec<&str> = pipeline_id.split("__").collect();
         if parts.len() != 4 {
@@ -288,4 +315,70 @@ impl Plugin for BuildkitePlugin {
         let client = self.client()?;
         client.cancel_build(org, slug, run_number).await
     }
+
+    async fn fetch_pending_approvals(
+        &self, pipeline_id: &str, run_number: i64,
+    ) -> PluginResult<Vec<PendingApproval>> {
+        let parts: Vec<&str> = pipeline_id.split("__").collect();
+        if parts.len() != 4 {
+            return Err(PluginError::InvalidConfig(format!(
+                "Invalid pipeline ID format: {pipeline_id}"
+            )));
+        }
+
+        let build = self
+            .client()?
+            .fetch_build(parts[2], parts[3], run_number)
+            .await?;
+
+        Ok(mapper::pending_approvals(&build.jobs))
+    }
+
+    async fn unblock_job(
+        &self, pipeline_id: &str, run_number: i64, job_id: &str, fields: HashMap<String, String>,
+    ) -> PluginResult<()> {
+        let parts: Vec<&str> = pipeline_id.split("__").collect();
+        if parts.len() != 4 {
+            return Err(PluginError::InvalidConfig(format!(
+                "Invalid pipeline ID format: {pipeline_id}"
+            )));
+        }
+
+        self.client()?
+            .unblock_job(parts[2], parts[3], run_number, job_id, fields)
+            .await
+    }
+
+    async fn fetch_run_annotations(&self, run: &PipelineRun) -> PluginResult<Vec<RunAnnotation>> {
+        let parts: Vec<&str> = run.pipeline_id.split("__").collect();
+        if parts.len() != 4 {
+            return Err(PluginError::InvalidConfig(format!(
+                "Invalid pipeline ID format: {}",
+                run.pipeline_id
+            )));
+        }
+
+        let annotations = self
+            .client()?
+            .fetch_annotations(parts[2], parts[3], run.run_number)
+            .await?;
+
+        Ok(annotations
+            .into_iter()
+            .map(mapper::map_annotation)
+            .collect())
+    }
+
+    async fn extract_metrics(&self, run: &PipelineRun) -> PluginResult<Vec<RunMetric>> {
+        Ok(run
+            .metadata
+            .get(client::AGENT_WAIT_SECONDS_KEY)
+            .and_then(|wait| wait.as_f64())
+            .map(|value| RunMetric {
+                metric_type: RunMetricType::AgentWaitTime,
+                value,
+            })
+            .into_iter()
+            .collect())
+    }
 }
@@ -30,6 +30,44 @@ pub(crate) struct Build {
     pub started_at: Option<String>,
     pub finished_at: Option<String>,
     pub author: Option<Author>,
+    #[serde(default)]
+    pub creator: Option<Creator>,
+    #[serde(default)]
+    pub pull_request: Option<BuildPullRequest>,
+    #[serde(default)]
+    pub jobs: Vec<BuildJob>,
+}
+
+// Only what's needed to work out how long jobs waited for an agent and which
+// block steps are waiting to be unblocked.
+#[derive(Debug, Deserialize)]
+pub(crate) struct BuildJob {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default, rename = "type")]
+    pub job_type: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub unblockable: Option<bool>,
+    #[serde(default)]
+    pub runnable_at: Option<String>,
+    #[serde(default)]
+    pub started_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Creator {
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+}
+
+// Buildkite reports the PR number as a string and doesn't include the title.
+#[derive(Debug, Deserialize)]
+pub(crate) struct BuildPullRequest {
+    pub id: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,6 +79,23 @@ pub(crate) struct Author {
     pub email: String,
 }
 
+// Only the job fields needed to work out which agent queue is waiting.
+#[derive(Debug, Deserialize)]
+pub(crate) struct QueuedBuild {
+    #[serde(default)]
+    pub jobs: Vec<QueuedJob>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct QueuedJob {
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub scheduled_at: Option<String>,
+    #[serde(default)]
+    pub agent_query_rules: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct Agent {
     pub id: String,
@@ -69,6 +124,18 @@ pub(crate) struct Artifact {
     pub url: String,
     #[serde(default)]
     pub download_url: String,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Annotation {
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub style: Option<String>,
+    #[serde(default)]
+    pub body_html: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -0,0 +1,7 @@
+mod metadata;
+mod plugin;
+mod simulation;
+
+pub use plugin::DemoPlugin;
+
+pipedash_plugin_api::register_plugin!(DemoPlugin);
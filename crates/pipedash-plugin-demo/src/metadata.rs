@@ -0,0 +1,60 @@
+use pipedash_plugin_api::*;
+
+pub fn create_metadata() -> PluginMetadata {
+    PluginMetadata {
+        name: "Demo".to_string(),
+        provider_type: "demo".to_string(),
+        version: "0.1.0".to_string(),
+        description: "Simulated pipelines, runs, agents and logs for demos and testing".to_string(),
+        author: Some("Pipedash Team".to_string()),
+        icon: None,
+        config_schema: create_config_schema(),
+        table_schema: pipedash_plugin_api::defaults::default_table_schema(),
+        capabilities: create_capabilities(),
+        required_permissions: Vec::new(),
+        features: Vec::new(),
+    }
+}
+
+fn create_config_schema() -> ConfigSchema {
+    ConfigSchema::new()
+        .add_field(ConfigField {
+            key: "token".to_string(),
+            label: "Token".to_string(),
+            description: Some("Any value; the demo provider does not authenticate".to_string()),
+            field_type: ConfigFieldType::Password,
+            required: true,
+            default_value: Some(serde_json::json!("demo")),
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+        .add_field(ConfigField {
+            key: "seed".to_string(),
+            label: "Seed".to_string(),
+            description: Some(
+                "Providers with the same seed show the same pipeline history".to_string(),
+            ),
+            field_type: ConfigFieldType::Text,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+}
+
+fn create_capabilities() -> PluginCapabilities {
+    PluginCapabilities {
+        pipelines: true,
+        pipeline_runs: true,
+        trigger: true,
+        agents: true,
+        queues: true,
+        logs: true,
+        branch_statuses: true,
+        cancel: true,
+        workflow_parameters: true,
+        ..PluginCapabilities::default()
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::sync::{
+    Mutex,
+    MutexGuard,
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use pipedash_plugin_api::*;
+
+use crate::metadata;
+use crate::simulation::{
+    self,
+    DemoPipeline,
+    Simulation,
+};
+
+pub struct DemoPlugin {
+    metadata: PluginMetadata,
+    provider_id: Option<i64>,
+    config: HashMap<String, String>,
+    simulation: Mutex<Simulation>,
+}
+
+impl Default for DemoPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DemoPlugin {
+    pub fn new() -> Self {
+        Self {
+            metadata: metadata::create_metadata(),
+            provider_id: None,
+            config: HashMap::new(),
+            simulation: Mutex::new(Simulation::default()),
+        }
+    }
+
+    fn simulation(&self) -> PluginResult<MutexGuard<'_, Simulation>> {
+        self.simulation
+            .lock()
+            .map_err(|e| PluginError::Internal(format!("Demo simulation lock poisoned: {e}")))
+    }
+
+    fn pipeline_id(&self, pipeline: &DemoPipeline) -> String {
+        format!("demo__{}__{}", self.provider_id.unwrap_or(0), pipeline.slug)
+    }
+
+    fn parse_pipeline_id(&self, pipeline_id: &str) -> PluginResult<&'static DemoPipeline> {
+        let parts: Vec<&str> = pipeline_id.split("__").collect();
+        if parts.len() != 3 || parts[0] != "demo" {
+            return Err(PluginError::InvalidConfig(format!(
+                "Invalid pipeline ID format: {pipeline_id}"
+            )));
+        }
+
+        simulation::find_pipeline(parts[2])
+            .ok_or_else(|| PluginError::PipelineNotFound(pipeline_id.to_string()))
+    }
+
+    // Every demo pipeline unless the provider was set up with a selection.
+    fn selected_pipelines(&self) -> Vec<&'static DemoPipeline> {
+        let selected: Vec<&str> = self
+            .config
+            .get("selected_items")
+            .map(|items| {
+                items
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        simulation::PIPELINES
+            .iter()
+            .filter(|p| selected.is_empty() || selected.contains(&p.slug))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Plugin for DemoPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    fn initialize(
+        &mut self, provider_id: i64, config: HashMap<String, String>,
+        _http_client: Option<std::sync::Arc<reqwest::Client>>,
+    ) -> PluginResult<()> {
+        self.simulation = Mutex::new(Simulation::new(simulation::parse_seed(config.get("seed"))));
+        self.provider_id = Some(provider_id);
+        self.config = config;
+
+        Ok(())
+    }
+
+    async fn validate_credentials(&self) -> PluginResult<bool> {
+        Ok(true)
+    }
+
+    async fn fetch_available_pipelines(
+        &self, params: Option<PaginationParams>,
+    ) -> PluginResult<PaginatedResponse<AvailablePipeline>> {
+        let params = params.unwrap_or_default();
+        let pipelines: Vec<AvailablePipeline> = simulation::PIPELINES
+            .iter()
+            .map(|p| AvailablePipeline {
+                id: p.slug.to_string(),
+                name: p.name.to_string(),
+                description: Some(format!("Simulated pipeline for {}", p.repository)),
+                organization: Some(simulation::ORGANIZATION.to_string()),
+                repository: Some(p.repository.to_string()),
+            })
+            .collect();
+        let total_count = pipelines.len();
+
+        Ok(PaginatedResponse::new(
+            pipelines,
+            params.page,
+            params.page_size,
+            total_count,
+        ))
+    }
+
+    async fn fetch_organizations(&self) -> PluginResult<Vec<Organization>> {
+        Ok(vec![Organization {
+            id: simulation::ORGANIZATION.to_string(),
+            name: "Acme".to_string(),
+            description: Some("Demo organization".to_string()),
+        }])
+    }
+
+    async fn fetch_pipelines(&self) -> PluginResult<Vec<Pipeline>> {
+        let provider_id = self
+            .provider_id
+            .ok_or_else(|| PluginError::Internal("Provider ID not set".to_string()))?;
+        let now = Utc::now();
+        let sim = self.simulation()?;
+
+        Ok(self
+            .selected_pipelines()
+            .into_iter()
+            .map(|p| {
+                let latest = sim.runs(p, now, 1).into_iter().next();
+                let status = latest
+                    .as_ref()
+                    .map(|run| sim.status_at(p, run, now).0)
+                    .unwrap_or(PipelineStatus::Pending);
+
+                Pipeline {
+                    id: self.pipeline_id(p),
+                    provider_id,
+                    provider_type: "demo".to_string(),
+                    name: p.name.to_string(),
+                    status,
+                    last_run: latest.map(|run| run.started_at),
+                    last_updated: now,
+                    repository: p.repository.to_string(),
+                    branch: Some(simulation::DEFAULT_BRANCH.to_string()),
+                    workflow_file: Some(p.workflow_file.to_string()),
+                    metadata: HashMap::new(),
+                }
+            })
+            .collect())
+    }
+
+    async fn fetch_run_history(
+        &self, pipeline_id: &str, limit: usize,
+    ) -> PluginResult<Vec<PipelineRun>> {
+        let pipeline = self.parse_pipeline_id(pipeline_id)?;
+        let now = Utc::now();
+        let sim = self.simulation()?;
+
+        Ok(sim
+            .runs(pipeline, now, limit)
+            .iter()
+            .map(|run| sim.pipeline_run(pipeline_id, pipeline, run, now))
+            .collect())
+    }
+
+    async fn fetch_run_details(
+        &self, pipeline_id: &str, run_number: i64,
+    ) -> PluginResult<PipelineRun> {
+        let pipeline = self.parse_pipeline_id(pipeline_id)?;
+        let now = Utc::now();
+        let sim = self.simulation()?;
+
+        let run = sim.run(pipeline, run_number, now).ok_or_else(|| {
+            PluginError::PipelineNotFound(format!(
+                "Run #{run_number} not found for pipeline {pipeline_id}"
+            ))
+        })?;
+
+        Ok(sim.pipeline_run(pipeline_id, pipeline, &run, now))
+    }
+
+    async fn fetch_workflow_parameters(
+        &self, _workflow_id: &str,
+    ) -> PluginResult<Vec<WorkflowParameter>> {
+        Ok(vec![WorkflowParameter {
+            name: "branch".to_string(),
+            label: Some("Branch".to_string()),
+            description: Some("Branch to run".to_string()),
+            param_type: WorkflowParameterType::String {
+                default: Some(simulation::DEFAULT_BRANCH.to_string()),
+            },
+            required: true,
+        }])
+    }
+
+    async fn trigger_pipeline(&self, params: TriggerParams) -> PluginResult<String> {
+        let pipeline = self.parse_pipeline_id(&params.workflow_id)?;
+        let branch = params
+            .inputs
+            .as_ref()
+            .and_then(|inputs| inputs.get("branch"))
+            .and_then(|v| v.as_str())
+            .unwrap_or(simulation::DEFAULT_BRANCH)
+            .to_string();
+
+        let run_number =
+            self.simulation()?
+                .trigger(pipeline, branch.clone(), params.inputs, Utc::now());
+
+        Ok(serde_json::json!({
+            "message": format!("Triggered run #{} on branch {}", run_number, branch),
+            "run_number": run_number,
+        })
+        .to_string())
+    }
+
+    async fn cancel_run(&self, pipeline_id: &str, run_number: i64) -> PluginResult<()> {
+        let pipeline = self.parse_pipeline_id(pipeline_id)?;
+        self.simulation()?.cancel(pipeline, run_number, Utc::now())
+    }
+
+    async fn fetch_run_logs(
+        &self, pipeline_id: &str, run_number: i64, query: LogQuery,
+    ) -> PluginResult<RunLogs> {
+        let pipeline = self.parse_pipeline_id(pipeline_id)?;
+        let now = Utc::now();
+        let sim = self.simulation()?;
+
+        let run = sim.run(pipeline, run_number, now).ok_or_else(|| {
+            PluginError::PipelineNotFound(format!(
+                "Run #{run_number} not found for pipeline {pipeline_id}"
+            ))
+        })?;
+
+        Ok(sim.logs(pipeline_id, pipeline, &run, now, &query))
+    }
+
+    async fn fetch_agents(&self) -> PluginResult<Vec<BuildAgent>> {
+        Ok(self.simulation()?.agents(Utc::now()))
+    }
+
+    async fn fetch_queues(&self) -> PluginResult<Vec<BuildQueue>> {
+        Ok(self.simulation()?.queues(Utc::now()))
+    }
+
+    async fn list_branch_statuses(&self, pipeline_id: &str) -> PluginResult<Vec<BranchStatus>> {
+        let runs = self.fetch_run_history(pipeline_id, 100).await?;
+
+        Ok(pipedash_plugin_api::utils::latest_run_per_branch(
+            runs,
+            None,
+            &HashMap::new(),
+        ))
+    }
+}
@@ -0,0 +1,788 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use chrono::{
+    DateTime,
+    Duration,
+    Utc,
+};
+use pipedash_plugin_api::*;
+
+// Schedules are anchored here so run numbers stay small and come out the same
+// on every machine.
+const EPOCH_SECS: i64 = 1_735_689_600; // 2025-01-01T00:00:00Z
+
+// Part of a run spent queued before an agent picks it up.
+const QUEUED_PERCENT: i64 = 5;
+// Scheduled runs cancelled on their own, on top of each pipeline's failures.
+const CANCELLED_PERCENT: u64 = 3;
+// How far into a run a self-cancelled run stops.
+const CANCELLED_AT_PERCENT: i64 = 60;
+const LINES_PER_STEP: i64 = 4;
+const MAX_RUNS: usize = 500;
+const MANUAL_SALT: u64 = 0x6d61_6e75_616c;
+
+pub(crate) const ORGANIZATION: &str = "acme";
+pub(crate) const DEFAULT_BRANCH: &str = "main";
+pub(crate) const QUEUE_ID: &str = "default";
+
+pub(crate) struct DemoPipeline {
+    pub slug: &'static str,
+    pub name: &'static str,
+    pub repository: &'static str,
+    pub workflow_file: &'static str,
+    // A run starts about every `interval_secs` and takes about
+    // `duration_secs`.
+    pub interval_secs: i64,
+    pub duration_secs: i64,
+    pub failure_percent: u64,
+}
+
+pub(crate) const PIPELINES: &[DemoPipeline] = &[
+    DemoPipeline {
+        slug: "api-ci",
+        name: "API CI",
+        repository: "acme/api",
+        workflow_file: ".github/workflows/ci.yml",
+        interval_secs: 600,
+        duration_secs: 240,
+        failure_percent: 15,
+    },
+    DemoPipeline {
+        slug: "web-ci",
+        name: "Web CI",
+        repository: "acme/web",
+        workflow_file: ".github/workflows/ci.yml",
+        interval_secs: 900,
+        duration_secs: 420,
+        failure_percent: 10,
+    },
+    DemoPipeline {
+        slug: "deploy-staging",
+        name: "Deploy Staging",
+        repository: "acme/api",
+        workflow_file: ".github/workflows/deploy-staging.yml",
+        interval_secs: 3600,
+        duration_secs: 480,
+        failure_percent: 5,
+    },
+    DemoPipeline {
+        slug: "deploy-production",
+        name: "Deploy Production",
+        repository: "acme/api",
+        workflow_file: ".github/workflows/deploy-production.yml",
+        interval_secs: 14_400,
+        duration_secs: 720,
+        failure_percent: 5,
+    },
+    DemoPipeline {
+        slug: "nightly-e2e",
+        name: "Nightly E2E",
+        repository: "acme/web",
+        workflow_file: ".github/workflows/e2e.yml",
+        interval_secs: 86_400,
+        duration_secs: 2700,
+        failure_percent: 30,
+    },
+    DemoPipeline {
+        slug: "docs",
+        name: "Docs",
+        repository: "acme/docs",
+        workflow_file: ".github/workflows/publish.yml",
+        interval_secs: 1800,
+        duration_secs: 90,
+        failure_percent: 2,
+    },
+];
+
+const FEATURE_BRANCHES: &[&str] = &["feature/checkout-v2", "fix/flaky-login-test", "chore/deps"];
+const ACTORS: &[&str] = &["alice", "bob", "carol", "dave", "erin"];
+const COMMIT_MESSAGES: &[&str] = &[
+    "Fix race in session refresh",
+    "Bump dependencies",
+    "Add retry to payment webhook",
+    "Refactor order service",
+    "Update onboarding copy",
+    "Speed up integration tests",
+    "Handle empty cart on checkout",
+];
+const JOBS: &[(&str, &[&str])] = &[
+    ("build", &["Checkout", "Install dependencies", "Compile"]),
+    ("test", &["Checkout", "Run tests", "Publish report"]),
+];
+const AGENTS: &[&str] = &[
+    "demo-agent-1",
+    "demo-agent-2",
+    "demo-agent-3",
+    "demo-agent-4",
+];
+
+pub(crate) fn find_pipeline(slug: &str) -> Option<&'static DemoPipeline> {
+    PIPELINES.iter().find(|p| p.slug == slug)
+}
+
+pub(crate) fn parse_seed(value: Option<&String>) -> u64 {
+    value
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse().unwrap_or_else(|_| mix(0, v, 0)))
+        .unwrap_or(0)
+}
+
+// FNV-1a over the inputs, finished with a splitmix64 round so neighbouring run
+// numbers get unrelated values.
+fn mix(seed: u64, key: &str, n: i64) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325 ^ seed;
+    for byte in key.bytes().chain(n.to_le_bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
+
+fn pick(items: &[&'static str], hash: u64) -> &'static str {
+    items[(hash % items.len() as u64) as usize]
+}
+
+fn timestamp(secs: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(secs, 0).unwrap_or_default()
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RunPlan {
+    pub number: i64,
+    pub started_at: DateTime<Utc>,
+    pub duration_secs: i64,
+    pub outcome: PipelineStatus,
+    pub branch: String,
+    pub commit_sha: String,
+    pub commit_message: String,
+    pub actor: String,
+    pub inputs: Option<serde_json::Value>,
+}
+
+impl RunPlan {
+    fn running_at(&self) -> DateTime<Utc> {
+        self.started_at + Duration::seconds(self.duration_secs * QUEUED_PERCENT / 100)
+    }
+
+    fn finishes_at(&self) -> DateTime<Utc> {
+        self.started_at + Duration::seconds(self.duration_secs)
+    }
+}
+
+struct ManualRun {
+    started_at: DateTime<Utc>,
+    branch: String,
+    inputs: Option<serde_json::Value>,
+}
+
+// Runs are a pure function of the seed and the clock: each pipeline starts a
+// run on a fixed schedule and its outcome is hashed from the run's index, so
+// two instances with the same seed show the same history. Triggered and
+// cancelled runs are the only state, and live as long as the plugin.
+#[derive(Default)]
+pub(crate) struct Simulation {
+    seed: u64,
+    manual: HashMap<&'static str, Vec<ManualRun>>,
+    cancelled: HashMap<(&'static str, i64), DateTime<Utc>>,
+}
+
+impl Simulation {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Self::default()
+        }
+    }
+
+    fn scheduled_start(&self, pipeline: &DemoPipeline, n: i64) -> DateTime<Utc> {
+        let max_jitter = (pipeline.interval_secs / 4).max(1) as u64;
+        let jitter = mix(self.seed, pipeline.slug, n) % max_jitter;
+        timestamp(EPOCH_SECS + n * pipeline.interval_secs + jitter as i64)
+    }
+
+    // Index of the last scheduled run started at or before `at`.
+    fn last_scheduled(&self, pipeline: &DemoPipeline, at: DateTime<Utc>) -> Option<i64> {
+        let n = (at.timestamp() - EPOCH_SECS).div_euclid(pipeline.interval_secs);
+        let n = if self.scheduled_start(pipeline, n) <= at {
+            n
+        } else {
+            n - 1
+        };
+        (n >= 0).then_some(n)
+    }
+
+    fn manual_runs(&self, pipeline: &DemoPipeline) -> &[ManualRun] {
+        self.manual
+            .get(pipeline.slug)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    // Run numbers count scheduled and triggered runs together in start order.
+    fn scheduled_run(&self, pipeline: &DemoPipeline, n: i64) -> RunPlan {
+        let started_at = self.scheduled_start(pipeline, n);
+        let triggered_before = self
+            .manual_runs(pipeline)
+            .iter()
+            .filter(|run| run.started_at < started_at)
+            .count() as i64;
+        let hash = mix(self.seed, pipeline.slug, n);
+        let branch = if hash.is_multiple_of(5) {
+            pick(FEATURE_BRANCHES, hash >> 40)
+        } else {
+            DEFAULT_BRANCH
+        };
+
+        self.plan(
+            pipeline,
+            hash,
+            n + 1 + triggered_before,
+            started_at,
+            branch.to_string(),
+            None,
+        )
+    }
+
+    fn manual_run(&self, pipeline: &DemoPipeline, index: usize) -> RunPlan {
+        let run = &self.manual_runs(pipeline)[index];
+        let scheduled_before = self
+            .last_scheduled(pipeline, run.started_at)
+            .map_or(0, |n| n + 1);
+        let hash = mix(self.seed ^ MANUAL_SALT, pipeline.slug, index as i64);
+
+        self.plan(
+            pipeline,
+            hash,
+            scheduled_before + index as i64 + 1,
+            run.started_at,
+            run.branch.clone(),
+            run.inputs.clone(),
+        )
+    }
+
+    fn plan(
+        &self, pipeline: &DemoPipeline, hash: u64, number: i64, started_at: DateTime<Utc>,
+        branch: String, inputs: Option<serde_json::Value>,
+    ) -> RunPlan {
+        let duration_secs = pipeline.duration_secs * (80 + ((hash >> 8) % 41) as i64) / 100;
+        let roll = (hash >> 16) % 100;
+        let outcome = if roll < pipeline.failure_percent {
+            PipelineStatus::Failed
+        } else if roll >= 100 - CANCELLED_PERCENT {
+            PipelineStatus::Cancelled
+        } else {
+            PipelineStatus::Success
+        };
+
+        RunPlan {
+            number,
+            started_at,
+            duration_secs,
+            outcome,
+            branch,
+            commit_sha: format!(
+                "{:016x}{:016x}{:08x}",
+                hash,
+                mix(hash, pipeline.slug, 1),
+                mix(hash, pipeline.slug, 2) as u32
+            ),
+            commit_message: pick(COMMIT_MESSAGES, hash >> 24).to_string(),
+            actor: pick(ACTORS, hash >> 32).to_string(),
+            inputs,
+        }
+    }
+
+    // Runs started at or before `now`, newest first.
+    pub fn runs(&self, pipeline: &DemoPipeline, now: DateTime<Utc>, limit: usize) -> Vec<RunPlan> {
+        let limit = limit.min(MAX_RUNS);
+        let mut runs: Vec<RunPlan> = match self.last_scheduled(pipeline, now) {
+            Some(last) => ((last - limit as i64 + 1).max(0)..=last)
+                .map(|n| self.scheduled_run(pipeline, n))
+                .collect(),
+            None => Vec::new(),
+        };
+        runs.extend(
+            self.manual_runs(pipeline)
+                .iter()
+                .enumerate()
+                .filter(|(_, run)| run.started_at <= now)
+                .map(|(index, _)| self.manual_run(pipeline, index)),
+        );
+
+        runs.sort_by_key(|run| Reverse(run.number));
+        runs.truncate(limit);
+        runs
+    }
+
+    pub fn run(&self, pipeline: &DemoPipeline, number: i64, now: DateTime<Utc>) -> Option<RunPlan> {
+        let latest = self.runs(pipeline, now, 1).first()?.number;
+        if number < 1 || number > latest {
+            return None;
+        }
+
+        // Numbers are contiguous, so the newest `latest - number + 1` runs
+        // always include the one asked for.
+        let lookback = (latest - number + 1) as usize;
+        self.runs(pipeline, now, lookback)
+            .into_iter()
+            .find(|run| run.number == number)
+    }
+
+    // Final status of a run and when it is reached.
+    fn conclusion(
+        &self, pipeline: &DemoPipeline, run: &RunPlan,
+    ) -> (PipelineStatus, DateTime<Utc>) {
+        match self.cancelled.get(&(pipeline.slug, run.number)) {
+            Some(at) if *at < run.finishes_at() => {
+                (PipelineStatus::Cancelled, (*at).max(run.started_at))
+            }
+            _ if run.outcome == PipelineStatus::Cancelled => (
+                PipelineStatus::Cancelled,
+                run.started_at + Duration::seconds(run.duration_secs * CANCELLED_AT_PERCENT / 100),
+            ),
+            _ => (run.outcome, run.finishes_at()),
+        }
+    }
+
+    pub fn status_at(
+        &self, pipeline: &DemoPipeline, run: &RunPlan, now: DateTime<Utc>,
+    ) -> (PipelineStatus, Option<DateTime<Utc>>) {
+        let (status, concluded_at) = self.conclusion(pipeline, run);
+        if now >= concluded_at {
+            (status, Some(concluded_at))
+        } else if now < run.running_at() {
+            (PipelineStatus::Pending, None)
+        } else {
+            (PipelineStatus::Running, None)
+        }
+    }
+
+    pub fn trigger(
+        &mut self, pipeline: &'static DemoPipeline, branch: String,
+        inputs: Option<serde_json::Value>, now: DateTime<Utc>,
+    ) -> i64 {
+        let runs = self.manual.entry(pipeline.slug).or_default();
+        runs.push(ManualRun {
+            started_at: now,
+            branch,
+            inputs,
+        });
+        let index = runs.len() - 1;
+
+        self.manual_run(pipeline, index).number
+    }
+
+    pub fn cancel(
+        &mut self, pipeline: &'static DemoPipeline, number: i64, now: DateTime<Utc>,
+    ) -> PluginResult<()> {
+        let run = self.run(pipeline, number, now).ok_or_else(|| {
+            PluginError::PipelineNotFound(format!("Run #{number} not found for {}", pipeline.slug))
+        })?;
+        if self.status_at(pipeline, &run, now).1.is_some() {
+            return Err(PluginError::ApiError(format!(
+                "Run #{number} has already finished"
+            )));
+        }
+
+        self.cancelled.insert((pipeline.slug, number), now);
+        Ok(())
+    }
+
+    pub fn pipeline_run(
+        &self, pipeline_id: &str, pipeline: &DemoPipeline, run: &RunPlan, now: DateTime<Utc>,
+    ) -> PipelineRun {
+        let (status, concluded_at) = self.status_at(pipeline, run, now);
+        let duration_seconds = concluded_at.map(|at| (at - run.started_at).num_seconds());
+
+        PipelineRun {
+            id: format!("demo-{}-{}", pipeline.slug, run.number),
+            pipeline_id: pipeline_id.to_string(),
+            run_number: run.number,
+            status,
+            started_at: run.started_at,
+            concluded_at,
+            duration_seconds,
+            logs_url: format!(
+                "https://ci.example.com/{}/{}/runs/{}",
+                pipeline.repository, pipeline.slug, run.number
+            ),
+            commit_sha: Some(run.commit_sha.clone()),
+            commit_message: Some(run.commit_message.clone()),
+            branch: Some(run.branch.clone()),
+            actor: Some(run.actor.clone()),
+            commit_author: Some(run.actor.clone()),
+            author_avatar_url: None,
+            pull_request: None,
+            inputs: run.inputs.clone(),
+            metadata: HashMap::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    // Jobs run back to back and their steps split each job evenly. Every step
+    // prints a few lines over its lifetime, so a running job's log grows with
+    // the clock.
+    pub fn logs(
+        &self, pipeline_id: &str, pipeline: &DemoPipeline, run: &RunPlan, now: DateTime<Utc>,
+        query: &LogQuery,
+    ) -> RunLogs {
+        let (status, concluded_at) = self.status_at(pipeline, run, now);
+        let (final_status, conclusion_at) = self.conclusion(pipeline, run);
+        let cutoff = now.min(conclusion_at);
+        let running_at = run.running_at();
+        let job_secs = (run.finishes_at() - running_at).num_seconds() / JOBS.len() as i64;
+
+        let jobs = JOBS
+            .iter()
+            .enumerate()
+            .map(|(job_index, (job_name, steps))| {
+                let job_start = running_at + Duration::seconds(job_secs * job_index as i64);
+                let step_secs = job_secs / steps.len() as i64;
+                let last_job = job_index == JOBS.len() - 1;
+
+                let mut content = String::new();
+                let log_steps: Vec<LogStep> = steps
+                    .iter()
+                    .enumerate()
+                    .map(|(step_index, step_name)| {
+                        let start = job_start + Duration::seconds(step_secs * step_index as i64);
+                        let end = start + Duration::seconds(step_secs);
+                        let failing = last_job
+                            && step_index == steps.len() - 1
+                            && final_status == PipelineStatus::Failed;
+
+                        for line in 0..LINES_PER_STEP {
+                            let at = start + Duration::seconds(step_secs * line / LINES_PER_STEP);
+                            if at > cutoff {
+                                break;
+                            }
+                            let text = match line {
+                                0 => format!("Starting {step_name}"),
+                                _ if line == LINES_PER_STEP - 1 && failing => {
+                                    format!("Error: {step_name} failed with exit code 1")
+                                }
+                                _ if line == LINES_PER_STEP - 1 => format!("Finished {step_name}"),
+                                _ => format!("{step_name}: {}% done", line * 100 / LINES_PER_STEP),
+                            };
+                            content.push_str(&format!("{} {text}\n", at.format("%H:%M:%S")));
+                        }
+
+                        let step_status = if start > cutoff {
+                            if concluded_at.is_some() {
+                                PipelineStatus::Skipped
+                            } else {
+                                PipelineStatus::Pending
+                            }
+                        } else if end > cutoff {
+                            if concluded_at.is_some() {
+                                content.push_str(&format!(
+                                    "{} The operation was cancelled.\n",
+                                    cutoff.format("%H:%M:%S")
+                                ));
+                                PipelineStatus::Cancelled
+                            } else {
+                                PipelineStatus::Running
+                            }
+                        } else if failing {
+                            PipelineStatus::Failed
+                        } else {
+                            PipelineStatus::Success
+                        };
+
+                        LogStep {
+                            number: step_index as i64 + 1,
+                            name: step_name.to_string(),
+                            status: step_status,
+                            started_at: (start <= cutoff).then_some(start),
+                            completed_at: (end <= cutoff).then_some(end),
+                        }
+                    })
+                    .collect();
+
+                let job_status = job_status(&log_steps);
+                let is_complete = !matches!(
+                    job_status,
+                    PipelineStatus::Running | PipelineStatus::Pending
+                );
+                let id = format!("{}-{}-{job_name}", pipeline.slug, run.number);
+                let requested = query.job_id.as_ref().is_none_or(|job_id| *job_id == id);
+                let offset = if query.job_id.is_some() {
+                    query.offset
+                } else {
+                    0
+                };
+                let (content, next_offset) = if requested {
+                    let start = offset.min(content.len());
+                    (content[start..].to_string(), content.len())
+                } else {
+                    (String::new(), offset)
+                };
+
+                JobLog {
+                    id,
+                    name: job_name.to_string(),
+                    status: job_status,
+                    started_at: log_steps.first().and_then(|step| step.started_at),
+                    completed_at: if is_complete {
+                        log_steps.iter().filter_map(|step| step.completed_at).max()
+                    } else {
+                        None
+                    },
+                    steps: log_steps,
+                    content,
+                    offset,
+                    next_offset,
+                    is_complete,
+                }
+            })
+            .collect();
+
+        RunLogs {
+            pipeline_id: pipeline_id.to_string(),
+            run_number: run.number,
+            status,
+            jobs,
+            is_complete: concluded_at.is_some(),
+        }
+    }
+
+    // Runs still queued or running across all pipelines.
+    fn active_runs(&self, now: DateTime<Utc>) -> Vec<(&'static DemoPipeline, RunPlan)> {
+        PIPELINES
+            .iter()
+            .flat_map(|pipeline| {
+                self.runs(pipeline, now, 5)
+                    .into_iter()
+                    .map(move |run| (pipeline, run))
+            })
+            .filter(|(pipeline, run)| self.status_at(pipeline, run, now).1.is_none())
+            .collect()
+    }
+
+    // Running runs are handed to agents in order; the last agent is always
+    // offline.
+    pub fn agents(&self, now: DateTime<Utc>) -> Vec<BuildAgent> {
+        let mut running = self
+            .active_runs(now)
+            .into_iter()
+            .filter(|(pipeline, run)| {
+                self.status_at(pipeline, run, now).0 == PipelineStatus::Running
+            })
+            .map(|(pipeline, run)| format!("demo-{}-{}", pipeline.slug, run.number));
+
+        AGENTS
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let offline = index == AGENTS.len() - 1;
+                let job_id = if offline { None } else { running.next() };
+                let status = match (&job_id, offline) {
+                    (_, true) => "offline",
+                    (Some(_), _) => "busy",
+                    (None, _) => "idle",
+                };
+
+                BuildAgent {
+                    id: name.to_string(),
+                    name: name.to_string(),
+                    hostname: format!("ip-10-0-0-{}", index + 10),
+                    status: status.to_string(),
+                    job_id,
+                    last_seen: if offline {
+                        now - Duration::hours(3)
+                    } else {
+                        now
+                    },
+                    metadata: HashMap::from([
+                        ("queue".to_string(), QUEUE_ID.to_string()),
+                        ("os".to_string(), "linux".to_string()),
+                    ]),
+                }
+            })
+            .collect()
+    }
+
+    pub fn queues(&self, now: DateTime<Utc>) -> Vec<BuildQueue> {
+        let active = self.active_runs(now);
+        let queued_at: Vec<DateTime<Utc>> = active
+            .iter()
+            .filter(|(pipeline, run)| {
+                self.status_at(pipeline, run, now).0 == PipelineStatus::Pending
+            })
+            .map(|(_, run)| run.started_at)
+            .collect();
+        let running = active.len() - queued_at.len();
+
+        vec![BuildQueue::from_queued_at(
+            QUEUE_ID.to_string(),
+            &queued_at,
+            running,
+            now,
+        )]
+    }
+}
+
+fn job_status(steps: &[LogStep]) -> PipelineStatus {
+    let any = |status: PipelineStatus| steps.iter().any(|step| step.status == status);
+
+    if any(PipelineStatus::Failed) {
+        PipelineStatus::Failed
+    } else if any(PipelineStatus::Cancelled) {
+        PipelineStatus::Cancelled
+    } else if any(PipelineStatus::Running) {
+        PipelineStatus::Running
+    } else if steps
+        .iter()
+        .all(|step| step.status == PipelineStatus::Skipped)
+    {
+        PipelineStatus::Skipped
+    } else if any(PipelineStatus::Pending) {
+        if any(PipelineStatus::Success) {
+            PipelineStatus::Running
+        } else {
+            PipelineStatus::Pending
+        }
+    } else {
+        PipelineStatus::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        timestamp(EPOCH_SECS + secs)
+    }
+
+    #[test]
+    fn test_runs_are_deterministic() {
+        let pipeline = find_pipeline("api-ci").unwrap();
+        let now = at(30 * 86_400);
+
+        let first = Simulation::new(7).runs(pipeline, now, 20);
+        let second = Simulation::new(7).runs(pipeline, now, 20);
+        let other_seed = Simulation::new(8).runs(pipeline, now, 20);
+
+        assert_eq!(first.len(), 20);
+        let summary = |runs: &[RunPlan]| {
+            runs.iter()
+                .map(|run| {
+                    (
+                        run.number,
+                        run.started_at,
+                        run.outcome,
+                        run.commit_sha.clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summary(&first), summary(&second));
+        assert_ne!(summary(&first), summary(&other_seed));
+
+        let numbers: Vec<i64> = first.iter().map(|run| run.number).collect();
+        let expected: Vec<i64> = (0..20).map(|i| first[0].number - i).collect();
+        assert_eq!(numbers, expected);
+        assert!(first.iter().all(|run| run.started_at <= now));
+    }
+
+    #[test]
+    fn test_status_moves_through_a_run() {
+        let pipeline = find_pipeline("web-ci").unwrap();
+        let sim = Simulation::new(1);
+        let run = sim
+            .runs(pipeline, at(86_400), 20)
+            .into_iter()
+            .find(|run| run.outcome == PipelineStatus::Success)
+            .unwrap();
+        let status = |offset: i64| {
+            sim.status_at(pipeline, &run, run.started_at + Duration::seconds(offset))
+                .0
+        };
+
+        assert_eq!(status(0), PipelineStatus::Pending);
+        assert_eq!(status(run.duration_secs / 2), PipelineStatus::Running);
+        assert_eq!(status(run.duration_secs), PipelineStatus::Success);
+    }
+
+    #[test]
+    fn test_trigger_and_cancel() {
+        let pipeline = find_pipeline("docs").unwrap();
+        let mut sim = Simulation::new(0);
+        let now = at(86_400 + 60);
+        let latest = sim.runs(pipeline, now, 1)[0].number;
+
+        let number = sim.trigger(pipeline, "feature/x".to_string(), None, now);
+        assert_eq!(number, latest + 1);
+
+        let later = now + Duration::seconds(10);
+        let run = sim.run(pipeline, number, later).unwrap();
+        assert_eq!(run.branch, "feature/x");
+        sim.cancel(pipeline, number, later).unwrap();
+
+        let (status, concluded_at) = sim.status_at(pipeline, &run, later);
+        assert_eq!(status, PipelineStatus::Cancelled);
+        assert_eq!(concluded_at, Some(later));
+        assert!(sim.cancel(pipeline, number, later).is_err());
+
+        // Scheduled runs after the trigger are numbered past it.
+        let next = sim.runs(pipeline, now + Duration::hours(1), 1)[0].number;
+        assert!(next > number);
+        assert!(sim
+            .run(pipeline, number, now + Duration::hours(1))
+            .is_some());
+    }
+
+    #[test]
+    fn test_logs_grow_with_the_clock() {
+        let pipeline = find_pipeline("api-ci").unwrap();
+        let sim = Simulation::new(3);
+        let run = sim
+            .runs(pipeline, at(86_400), 20)
+            .into_iter()
+            .find(|run| run.outcome == PipelineStatus::Failed)
+            .unwrap();
+        let logs_at = |offset: i64| {
+            sim.logs(
+                "demo__1__api-ci",
+                pipeline,
+                &run,
+                run.started_at + Duration::seconds(offset),
+                &LogQuery::default(),
+            )
+        };
+
+        let early = logs_at(run.duration_secs / 4);
+        assert!(!early.is_complete);
+        assert_eq!(early.jobs[0].status, PipelineStatus::Running);
+        assert_eq!(early.jobs[1].status, PipelineStatus::Pending);
+
+        let done = logs_at(run.duration_secs);
+        assert!(done.is_complete);
+        assert_eq!(done.status, PipelineStatus::Failed);
+        assert_eq!(done.jobs[0].status, PipelineStatus::Success);
+        assert_eq!(done.jobs[1].status, PipelineStatus::Failed);
+        assert!(done.jobs[1].content.contains("failed with exit code 1"));
+        assert!(done.jobs[0].content.starts_with(&early.jobs[0].content));
+
+        let tail = sim.logs(
+            "demo__1__api-ci",
+            pipeline,
+            &run,
+            run.finishes_at(),
+            &LogQuery {
+                job_id: Some(done.jobs[0].id.clone()),
+                offset: early.jobs[0].next_offset,
+            },
+        );
+        assert_eq!(
+            format!("{}{}", early.jobs[0].content, tail.jobs[0].content),
+            done.jobs[0].content
+        );
+        assert!(tail.jobs[1].content.is_empty());
+    }
+}
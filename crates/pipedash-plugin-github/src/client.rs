@@ -1,10 +1,25 @@
 use std::collections::HashMap;
+use std::sync::atomic::{
+    AtomicU8,
+    Ordering,
+};
+use std::sync::Arc;
+use std::time::Duration;
 
-use chrono::Utc;
+use chrono::{
+    DateTime,
+    Utc,
+};
 use futures::future::join_all;
 use octocrab::Octocrab;
+use pipedash_plugin_api::http::DEFAULT_MAX_RESPONSE_MB;
 use pipedash_plugin_api::{
+    read_json,
+    read_text,
     AvailablePipeline,
+    BuildQueue,
+    ConditionalRequestCache,
+    ConditionalResponse,
     PaginatedResponse,
     PaginationParams,
     PermissionStatus,
@@ -12,35 +27,240 @@ use pipedash_plugin_api::{
     PipelineRun,
     PluginError,
     PluginResult,
+    PullRequestRef,
+    RateLimitStatus,
     RetryPolicy,
 };
+use secrecy::{
+    ExposeSecret,
+    SecretString,
+};
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
 use tracing::debug;
 
 use crate::{
     config,
+    graphql,
     mapper,
     permissions::PermissionChecker,
     types,
 };
 
+// Run metadata key for the run's check suite, read by `fetch_run_annotations`.
+pub(crate) const CHECK_SUITE_ID_KEY: &str = "check_suite_id";
+
+// Run metadata key for the protection rules a waiting run is held by.
+pub(crate) const ENVIRONMENT_PROTECTION_KEY: &str = "environment_protection";
+
+pub(crate) enum Auth {
+    Token(SecretString),
+    App(Box<AppInstallation>),
+}
+
+// Authentication as a GitHub App installation. `octocrab` refreshes its own
+// installation token; requests made with reqwest (logs, GraphQL, conditional
+// GETs) get theirs from here, minted with the app's JWT and renewed shortly
+// before it expires.
+pub(crate) struct AppInstallation {
+    app: Octocrab,
+    installation_id: u64,
+    token: Mutex<Option<types::InstallationToken>>,
+}
+
+impl AppInstallation {
+    // Installation tokens live for an hour.
+    const REFRESH_MARGIN_SECS: i64 = 300;
+
+    pub fn new(app: Octocrab, installation_id: u64) -> Self {
+        Self {
+            app,
+            installation_id,
+            token: Mutex::new(None),
+        }
+    }
+
+    async fn installation_token(&self) -> PluginResult<types::InstallationToken> {
+        let mut cached = self.token.lock().await;
+        if let Some(token) = cached.as_ref() {
+            let remaining = token.expires_at - Utc::now();
+            if remaining.num_seconds() > Self::REFRESH_MARGIN_SECS {
+                return Ok(token.clone());
+            }
+        }
+
+        debug!(
+            installation_id = self.installation_id,
+            "Refreshing GitHub App installation token"
+        );
+        let token: types::InstallationToken = self
+            .app
+            .post(
+                format!("/app/installations/{}/access_tokens", self.installation_id),
+                None::<&()>,
+            )
+            .await
+            .map_err(|e| {
+                PluginError::AuthenticationFailed(format!(
+                    "Failed to create GitHub App installation token: {e}"
+                ))
+            })?;
+
+        *cached = Some(token.clone());
+        Ok(token)
+    }
+
+    async fn account(&self) -> PluginResult<String> {
+        let installation: types::Installation = self
+            .app
+            .get(
+                format!("/app/installations/{}", self.installation_id),
+                None::<&()>,
+            )
+            .await
+            .map_err(|e| {
+                PluginError::ApiError(format!("Failed to fetch GitHub App installation: {e}"))
+            })?;
+        Ok(installation.account.login)
+    }
+}
+
+// The workflows of a repository and the pipelines last built from them.
+#[derive(Clone)]
+struct RepoSnapshot {
+    workflows: Vec<types::Workflow>,
+    pipelines: HashMap<u64, Pipeline>,
+}
+
 pub(crate) struct GitHubClient {
     pub(crate) octocrab: Octocrab,
     pub(crate) retry_policy: RetryPolicy,
-    permission_checker: PermissionChecker,
+    permission_checker: Option<PermissionChecker>,
+    api_url: String,
+    graphql_url: String,
+    graphql_support: AtomicU8,
+    auth: Auth,
+    log_client: reqwest::Client,
+    http_client: Arc<reqwest::Client>,
+    conditional_cache: Arc<ConditionalRequestCache>,
+    workflow_snapshots: Mutex<HashMap<String, RepoSnapshot>>,
+    max_response_bytes: usize,
 }
 
 impl GitHubClient {
-    pub fn new(octocrab: Octocrab, token: String) -> PluginResult<Self> {
-        let token_secret = token.into();
-        let permission_checker = PermissionChecker::new(octocrab.clone(), token_secret)?;
+    const LOG_TIMEOUT_SECS: u64 = 30;
+
+    const GRAPHQL_UNKNOWN: u8 = 0;
+    const GRAPHQL_AVAILABLE: u8 = 1;
+    const GRAPHQL_UNAVAILABLE: u8 = 2;
+
+    pub fn new(
+        octocrab: Octocrab, auth: Auth, api_url: String, http_client: Arc<reqwest::Client>,
+        conditional_cache: Arc<ConditionalRequestCache>,
+    ) -> PluginResult<Self> {
+        let permission_checker = match &auth {
+            Auth::Token(token) => Some(PermissionChecker::new(
+                octocrab.clone(),
+                token.expose_secret().to_string().into(),
+            )?),
+            Auth::App(_) => None,
+        };
+
+        // Log downloads redirect to blob storage, which octocrab does not
+        // follow.
+        let log_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(Self::LOG_TIMEOUT_SECS))
+            .user_agent("pipedash")
+            .build()
+            .map_err(|e| PluginError::Internal(format!("Failed to build HTTP client: {e}")))?;
 
         Ok(Self {
             octocrab,
             retry_policy: RetryPolicy::default(),
             permission_checker,
+            graphql_url: config::build_graphql_url(&api_url),
+            graphql_support: AtomicU8::new(Self::GRAPHQL_UNKNOWN),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            auth,
+            log_client,
+            http_client,
+            conditional_cache,
+            workflow_snapshots: Mutex::new(HashMap::new()),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_MB * 1024 * 1024,
         })
     }
 
+    // Applies to GraphQL responses and job logs; REST calls go through
+    // octocrab.
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    pub fn is_app(&self) -> bool {
+        matches!(self.auth, Auth::App(_))
+    }
+
+    pub async fn bearer_token(&self) -> PluginResult<SecretString> {
+        match &self.auth {
+            Auth::Token(token) => Ok(token.expose_secret().to_string().into()),
+            Auth::App(app) => Ok(app.installation_token().await?.token.into()),
+        }
+    }
+
+    // Installations can't use the /user endpoints, so repositories come from
+    // the installation itself. `org` and `search` filter the returned page.
+    async fn fetch_installation_repositories(
+        &self, org: Option<String>, search: Option<String>, params: PaginationParams,
+    ) -> PluginResult<PaginatedResponse<AvailablePipeline>> {
+        let route = format!(
+            "/installation/repositories?per_page={}&page={}",
+            params.page_size.min(100),
+            params.page.max(1)
+        );
+        let response: types::InstallationRepositories = self
+            .octocrab
+            .get(route, None::<&()>)
+            .await
+            .map_err(|e| PluginError::ApiError(format!("Failed to fetch repositories: {e}")))?;
+
+        let search = search.map(|s| s.to_lowercase());
+        let repos: Vec<AvailablePipeline> = response
+            .repositories
+            .into_iter()
+            .filter_map(|repo| {
+                let (owner, name) = config::parse_repo(&repo.full_name)?;
+                if org.as_ref().is_some_and(|org| *org != owner) {
+                    return None;
+                }
+                if let Some(search) = &search {
+                    let matches = repo.full_name.to_lowercase().contains(search)
+                        || repo
+                            .description
+                            .as_ref()
+                            .is_some_and(|d| d.to_lowercase().contains(search));
+                    if !matches {
+                        return None;
+                    }
+                }
+                Some(AvailablePipeline {
+                    id: repo.full_name.clone(),
+                    name: repo.full_name,
+                    description: repo.description,
+                    organization: Some(owner),
+                    repository: Some(name),
+                })
+            })
+            .collect();
+
+        Ok(PaginatedResponse::new(
+            repos,
+            params.page,
+            params.page_size,
+            response.total_count as usize,
+        ))
+    }
+
     async fn detect_organizations_from_repos(
         &self,
     ) -> PluginResult<Vec<pipedash_plugin_api::Organization>> {
@@ -88,6 +308,16 @@ impl GitHubClient {
     pub async fn fetch_organizations(
         &self,
     ) -> PluginResult<Vec<pipedash_plugin_api::Organization>> {
+        // An installation belongs to exactly one user or organization.
+        if let Auth::App(app) = &self.auth {
+            let login = app.account().await?;
+            return Ok(vec![pipedash_plugin_api::Organization {
+                id: login.clone(),
+                name: login,
+                description: None,
+            }]);
+        }
+
         self.retry_policy
             .retry(|| async {
                 let mut orgs = Vec::new();
@@ -156,6 +386,11 @@ impl GitHubClient {
         &self, params: Option<PaginationParams>,
     ) -> PluginResult<PaginatedResponse<AvailablePipeline>> {
         let params = params.unwrap_or_default();
+        if self.is_app() {
+            return self
+                .fetch_installation_repositories(None, None, params)
+                .await;
+        }
         let github_page = params.page.min(255) as u8;
 
         let repos = self
@@ -202,110 +437,67 @@ impl GitHubClient {
         ))
     }
 
+    // Repository pages go through the conditional cache, so re-running a
+    // preview over a large organization costs 304s rather than rate limit.
     pub async fn fetch_available_pipelines_filtered(
         &self, org: Option<String>, search: Option<String>, params: Option<PaginationParams>,
     ) -> PluginResult<PaginatedResponse<AvailablePipeline>> {
         let params = params.unwrap_or_default();
-        let github_page = params.page.min(255) as u8;
-
-        let mut all_repos = Vec::new();
+        if self.is_app() {
+            return self
+                .fetch_installation_repositories(org, search, params)
+                .await;
+        }
+        let per_page = params.page_size.min(100);
+        let query = format!("per_page={}&page={}", per_page, params.page.max(1));
 
-        if let Some(org_name) = org {
-            let user =
-                self.octocrab.current().user().await.map_err(|e| {
+        let (repos, owner_filter) = match org {
+            Some(org_name) => {
+                let user = self.octocrab.current().user().await.map_err(|e| {
                     PluginError::ApiError(format!("Failed to fetch current user: {e}"))
                 })?;
 
-            let is_personal_account = org_name == user.login;
-
-            let repos = if is_personal_account {
-                self.octocrab
-                    .current()
-                    .list_repos_for_authenticated_user()
-                    .per_page(params.page_size.min(100) as u8)
-                    .page(github_page)
-                    .send()
-                    .await
-                    .map_err(|e| {
-                        PluginError::ApiError(format!("Failed to fetch user repositories: {e}"))
-                    })?
-            } else {
-                self.octocrab
-                    .orgs(&org_name)
-                    .list_repos()
-                    .per_page(params.page_size.min(100) as u8)
-                    .page(github_page)
-                    .send()
-                    .await
-                    .map_err(|e| {
-                        PluginError::ApiError(format!("Failed to fetch org repositories: {e}"))
-                    })?
-            };
-
-            for repo in repos.items {
-                let full_name = repo.full_name.as_ref().ok_or_else(|| {
-                    PluginError::ApiError("Repository missing full_name".to_string())
-                })?;
-
-                let (owner, repo_name) = if let Some((o, r)) = config::parse_repo(full_name) {
-                    (Some(o), Some(r))
+                if org_name == user.login {
+                    let repos = self
+                        .conditional_get::<Vec<octocrab::models::Repository>>(&format!(
+                            "{}/user/repos?{query}",
+                            self.api_url
+                        ))
+                        .await
+                        .map_err(|e| {
+                            PluginError::ApiError(format!("Failed to fetch user repositories: {e}"))
+                        })?;
+                    (repos, Some(user.login))
                 } else {
-                    (None, None)
-                };
-
-                if is_personal_account {
-                    if let Some(ref owner_name) = owner {
-                        if owner_name != &user.login {
-                            continue;
-                        }
-                    } else {
-                        continue;
-                    }
+                    let repos = self
+                        .conditional_get::<Vec<octocrab::models::Repository>>(&format!(
+                            "{}/orgs/{org_name}/repos?{query}",
+                            self.api_url
+                        ))
+                        .await
+                        .map_err(|e| {
+                            PluginError::ApiError(format!("Failed to fetch org repositories: {e}"))
+                        })?;
+                    (repos, None)
                 }
-
-                all_repos.push(AvailablePipeline {
-                    id: full_name.clone(),
-                    name: full_name.clone(),
-                    description: repo.description.clone(),
-                    organization: owner,
-                    repository: repo_name,
-                });
             }
-
-            if let Some(search_term) = search {
-                let search_lower = search_term.to_lowercase();
-                all_repos.retain(|p| {
-                    p.name.to_lowercase().contains(&search_lower)
-                        || p.id.to_lowercase().contains(&search_lower)
-                        || p.description
-                            .as_ref()
-                            .is_some_and(|d| d.to_lowercase().contains(&search_lower))
-                });
+            None => {
+                let repos = self
+                    .conditional_get::<Vec<octocrab::models::Repository>>(&format!(
+                        "{}/user/repos?{query}",
+                        self.api_url
+                    ))
+                    .await
+                    .map_err(|e| {
+                        PluginError::ApiError(format!("Failed to fetch repositories: {e}"))
+                    })?;
+                (repos, None)
             }
+        };
+        let fetched = repos.len();
 
-            let total_count = repos
-                .total_count
-                .map(|t| t as usize)
-                .unwrap_or(all_repos.len());
-            return Ok(PaginatedResponse::new(
-                all_repos,
-                params.page,
-                params.page_size,
-                total_count,
-            ));
-        }
-
-        let repos = self
-            .octocrab
-            .current()
-            .list_repos_for_authenticated_user()
-            .per_page(params.page_size.min(100) as u8)
-            .page(github_page)
-            .send()
-            .await
-            .map_err(|e| PluginError::ApiError(format!("Failed to fetch repositories: {e}")))?;
-
-        for repo in repos.items {
+        let mut all_repos = Vec::new();
+        for repo in repos {
             let full_name = repo
                 .full_name
                 .as_ref()
@@ -317,6 +509,12 @@ impl GitHubClient {
                 (None, None)
             };
 
+            if let Some(login) = &owner_filter {
+                if owner.as_ref() != Some(login) {
+                    continue;
+                }
+            }
+
             all_repos.push(AvailablePipeline {
                 id: full_name.clone(),
                 name: full_name.clone(),
@@ -337,21 +535,43 @@ impl GitHubClient {
             });
         }
 
-        let total_count = repos
-            .total_count
-            .map(|t| t as usize)
-            .unwrap_or(all_repos.len());
-        Ok(PaginatedResponse::new(
-            all_repos,
-            params.page,
-            params.page_size,
-            total_count,
-        ))
+        let total_count = all_repos.len();
+        let mut response =
+            PaginatedResponse::new(all_repos, params.page, params.page_size, total_count);
+        // Filtering shrinks the page, so whether GitHub has more is decided
+        // by what it returned.
+        response.has_more = fetched == per_page;
+        Ok(response)
     }
 
+    // Workflow lists and latest runs go through the conditional cache: on a
+    // quiet repository every request after the first is a 304, which GitHub
+    // does not count against the rate limit. Sent with the host's client so
+    // its proxy and TLS settings apply.
+    async fn conditional_send(&self, url: &str) -> PluginResult<ConditionalResponse> {
+        let token = self.bearer_token().await?;
+        let request = self
+            .http_client
+            .get(url)
+            .bearer_auth(token.expose_secret())
+            .header(reqwest::header::USER_AGENT, "pipedash")
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json");
+
+        self.conditional_cache
+            .send(&self.http_client, request)
+            .await
+    }
+
+    async fn conditional_get<T: DeserializeOwned>(&self, url: &str) -> PluginResult<T> {
+        self.conditional_send(url).await?.json()
+    }
+
+    // Also returns whether anything changed since the previous call. While
+    // GitHub answers 304, the pipelines built last time are reused rather
+    // than parsed again from the cached bodies.
     pub async fn fetch_repo_workflows(
         &self, provider_id: i64, repo_full_name: String,
-    ) -> PluginResult<Vec<Pipeline>> {
+    ) -> PluginResult<(Vec<Pipeline>, bool)> {
         self.retry_policy
             .retry(|| async {
                 let (owner, repo) = config::parse_repo(&repo_full_name).ok_or_else(|| {
@@ -360,42 +580,56 @@ impl GitHubClient {
                     ))
                 })?;
 
-                let workflows = self
-                    .octocrab
-                    .workflows(&owner, &repo)
-                    .list()
-                    .per_page(100)
-                    .send()
+                let listing = self
+                    .conditional_send(&format!(
+                        "{}/repos/{owner}/{repo}/actions/workflows?per_page=100",
+                        self.api_url
+                    ))
                     .await
                     .map_err(|e| {
                         PluginError::ApiError(format!("Failed to fetch workflows: {e}"))
                     })?;
 
-                let fetch_runs_futures = workflows.items.iter().map(|workflow| {
-                    let octocrab = self.octocrab.clone();
-                    let owner = owner.clone();
-                    let repo = repo.clone();
-                    let workflow_id = workflow.id;
-                    async move {
-                        let runs = octocrab
-                            .workflows(&owner, &repo)
-                            .list_runs(workflow_id.to_string())
-                            .per_page(1)
-                            .send()
-                            .await
-                            .ok();
-                        (workflow_id, runs)
-                    }
+                let previous = self
+                    .workflow_snapshots
+                    .lock()
+                    .await
+                    .get(&repo_full_name)
+                    .cloned()
+                    .filter(|_| !listing.modified);
+                let workflows = match &previous {
+                    Some(snapshot) => snapshot.workflows.clone(),
+                    None => listing.json::<types::WorkflowsResponse>()?.workflows,
+                };
+
+                let fetch_runs_futures = workflows.iter().map(|workflow| {
+                    let url = format!(
+                        "{}/repos/{owner}/{repo}/actions/workflows/{}/runs?per_page=1",
+                        self.api_url, workflow.id
+                    );
+                    async move { (workflow.id, self.conditional_send(&url).await.ok()) }
                 });
 
                 let runs_results: Vec<_> = join_all(fetch_runs_futures).await;
-                let runs_map: std::collections::HashMap<_, _> = runs_results.into_iter().collect();
+                let runs_map: HashMap<_, _> = runs_results.into_iter().collect();
 
+                let mut modified = previous.is_none();
                 let mut pipelines = Vec::new();
 
-                for workflow in workflows.items {
-                    let runs = runs_map.get(&workflow.id);
-                    let latest_run = runs.and_then(|r| r.as_ref()).and_then(|r| r.items.first());
+                for workflow in &workflows {
+                    let runs = runs_map.get(&workflow.id).and_then(Option::as_ref);
+                    let unchanged = previous
+                        .as_ref()
+                        .filter(|_| runs.is_some_and(|r| !r.modified))
+                        .and_then(|snapshot| snapshot.pipelines.get(&workflow.id));
+                    if let Some(pipeline) = unchanged {
+                        pipelines.push(pipeline.clone());
+                        continue;
+                    }
+                    modified = true;
+
+                    let runs = runs.and_then(|r| r.json::<types::RunsResponse>().ok());
+                    let latest_run = runs.as_ref().and_then(|r| r.workflow_runs.first());
 
                     let status = latest_run
                         .map(|run| {
@@ -408,34 +642,199 @@ impl GitHubClient {
                     let mut metadata = HashMap::new();
                     metadata.insert("workflow_id".to_string(), serde_json::json!(workflow.id));
 
-                    let pipeline = Pipeline {
+                    pipelines.push(Pipeline {
                         id: format!(
                             "github__{}__{}__{}__{}",
                             provider_id, owner, repo, workflow.id
                         ),
                         provider_id,
                         provider_type: "github".to_string(),
-                        name: workflow.name,
+                        name: workflow.name.clone(),
                         status,
                         last_run,
                         last_updated: Utc::now(),
                         repository: repo_full_name.clone(),
                         branch: latest_run.map(|run| run.head_branch.clone()),
-                        workflow_file: Some(workflow.path),
+                        workflow_file: Some(workflow.path.clone()),
                         metadata,
-                    };
+                    });
+                }
 
-                    pipelines.push(pipeline);
+                if modified {
+                    let snapshot = RepoSnapshot {
+                        pipelines: workflows
+                            .iter()
+                            .map(|workflow| workflow.id)
+                            .zip(pipelines.iter().cloned())
+                            .collect(),
+                        workflows,
+                    };
+                    self.workflow_snapshots
+                        .lock()
+                        .await
+                        .insert(repo_full_name.clone(), snapshot);
                 }
 
-                Ok(pipelines)
+                Ok((pipelines, modified))
             })
             .await
     }
 
+    // Tokens without GraphQL access (some fine-grained tokens, GHES instances
+    // with the API disabled) fail the probe and stay on REST for the lifetime
+    // of the client.
+    pub async fn graphql_available(&self) -> bool {
+        match self.graphql_support.load(Ordering::Relaxed) {
+            Self::GRAPHQL_AVAILABLE => return true,
+            Self::GRAPHQL_UNAVAILABLE => return false,
+            _ => {}
+        }
+
+        let body = serde_json::json!({ "query": graphql::PROBE_QUERY });
+        let available = match self.graphql::<serde_json::Value>(&body).await {
+            Ok(_) => true,
+            Err(e) => {
+                debug!("GitHub GraphQL API unavailable, using REST: {e}");
+                false
+            }
+        };
+
+        self.graphql_support.store(
+            if available {
+                Self::GRAPHQL_AVAILABLE
+            } else {
+                Self::GRAPHQL_UNAVAILABLE
+            },
+            Ordering::Relaxed,
+        );
+        available
+    }
+
+    async fn graphql<T: DeserializeOwned>(&self, body: &serde_json::Value) -> PluginResult<T> {
+        let token = self.bearer_token().await?;
+        let response = self
+            .log_client
+            .post(&self.graphql_url)
+            .bearer_auth(token.expose_secret())
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| PluginError::NetworkError(format!("GraphQL request failed: {e}")))?;
+
+        if let Some(error) = pipedash_plugin_api::rate_limit_error(&response) {
+            return Err(error);
+        }
+        let status = response.status();
+        if !status.is_success() {
+            return Err(PluginError::ApiError(format!(
+                "GraphQL request failed: HTTP {status}"
+            )));
+        }
+
+        let response: graphql::Response<T> = read_json(response, self.max_response_bytes)
+            .await
+            .map_err(|e| match e {
+                PluginError::SerializationError(e) => {
+                    PluginError::ApiError(format!("Failed to parse GraphQL response: {e}"))
+                }
+                e => e,
+            })?;
+
+        // Partial errors (e.g. one repository not found) still come with data.
+        for error in &response.errors {
+            debug!("GitHub GraphQL error: {}", error.message);
+        }
+
+        response.data.ok_or_else(|| {
+            PluginError::ApiError(format!(
+                "GraphQL request failed: {}",
+                response
+                    .errors
+                    .first()
+                    .map(|e| e.message.as_str())
+                    .unwrap_or("no data returned")
+            ))
+        })
+    }
+
+    // Fetches workflows and their latest runs for many repositories with one
+    // GraphQL request per batch. Returns the pipelines plus the repositories
+    // that could not be fully resolved and need the REST path.
+    pub async fn fetch_repos_workflows_graphql(
+        &self, provider_id: i64, repositories: Vec<String>,
+    ) -> (Vec<Pipeline>, Vec<String>) {
+        let mut fallback = Vec::new();
+        let mut parsed = Vec::new();
+
+        for repo_full_name in repositories {
+            match config::parse_repo(&repo_full_name) {
+                Some((owner, repo)) => parsed.push((owner, repo, repo_full_name)),
+                None => fallback.push(repo_full_name),
+            }
+        }
+
+        let batch_futures = parsed.chunks(graphql::BATCH_SIZE).map(|batch| async move {
+            let names: Vec<(String, String)> = batch
+                .iter()
+                .map(|(owner, repo, _)| (owner.clone(), repo.clone()))
+                .collect();
+            let body = graphql::build_workflows_query(&names);
+
+            let result = self
+                .retry_policy
+                .retry(|| async {
+                    self.graphql::<HashMap<String, Option<graphql::RepositoryNode>>>(&body)
+                        .await
+                })
+                .await;
+
+            let mut pipelines = Vec::new();
+            let mut fallback = Vec::new();
+
+            let mut data = match result {
+                Ok(data) => data,
+                Err(e) => {
+                    tracing::warn!("GitHub GraphQL batch failed, falling back to REST: {e}");
+                    return (
+                        pipelines,
+                        batch.iter().map(|(_, _, name)| name.clone()).collect(),
+                    );
+                }
+            };
+
+            for (i, (owner, repo, repo_full_name)) in batch.iter().enumerate() {
+                let mapped = data
+                    .remove(&graphql::repository_alias(i))
+                    .flatten()
+                    .and_then(|node| graphql::map_repository(provider_id, owner, repo, &node));
+
+                match mapped {
+                    Some(mut repo_pipelines) => pipelines.append(&mut repo_pipelines),
+                    None => fallback.push(repo_full_name.clone()),
+                }
+            }
+
+            (pipelines, fallback)
+        });
+
+        let mut pipelines = Vec::new();
+        for (mut batch_pipelines, mut batch_fallback) in join_all(batch_futures).await {
+            pipelines.append(&mut batch_pipelines);
+            fallback.append(&mut batch_fallback);
+        }
+
+        debug!(
+            pipelines = pipelines.len(),
+            rest_fallback = fallback.len(),
+            "GitHub GraphQL bulk fetch complete"
+        );
+
+        (pipelines, fallback)
+    }
+
     pub async fn fetch_run_history(
         &self, owner: &str, repo: &str, workflow_id: u64, limit: usize,
-    ) -> PluginResult<Vec<types::Run>> {
+    ) -> PluginResult<Vec<types::WorkflowRun>> {
         use std::sync::Arc;
 
         use tokio::sync::Semaphore;
@@ -458,13 +857,14 @@ impl GitHubClient {
                     let _permit = semaphore.acquire().await.unwrap();
 
                     octocrab
-                        .workflows(&owner, &repo)
-                        .list_runs(workflow_id_str)
-                        .per_page(per_page)
-                        .page(page as u8)
-                        .send()
+                        .get::<types::RunsResponse, _, _>(
+                            format!(
+                                "/repos/{owner}/{repo}/actions/workflows/{workflow_id_str}/runs"
+                            ),
+                            Some(&[("per_page", per_page as usize), ("page", page)]),
+                        )
                         .await
-                        .map(|response| response.items)
+                        .map(|response| response.workflow_runs)
                         .map_err(|e| {
                             PluginError::ApiError(format!(
                                 "Failed to fetch run history page {page}: {e}"
@@ -487,21 +887,24 @@ impl GitHubClient {
 
     pub async fn fetch_run_by_number(
         &self, owner: &str, repo: &str, workflow_id: u64, run_number: i64,
-    ) -> PluginResult<types::Run> {
+    ) -> PluginResult<types::WorkflowRun> {
         let mut page = 1u32;
 
         loop {
-            let runs = self
+            let runs: types::RunsResponse = self
                 .octocrab
-                .workflows(owner, repo)
-                .list_runs(workflow_id.to_string())
-                .per_page(100)
-                .page(page.min(255) as u8)
-                .send()
+                .get(
+                    format!("/repos/{owner}/{repo}/actions/workflows/{workflow_id}/runs"),
+                    Some(&[("per_page", 100), ("page", page)]),
+                )
                 .await
                 .map_err(|e| PluginError::ApiError(format!("Failed to fetch runs: {e}")))?;
 
-            if let Some(run) = runs.items.into_iter().find(|r| r.run_number == run_number) {
+            if let Some(run) = runs
+                .workflow_runs
+                .into_iter()
+                .find(|r| r.run_number == run_number)
+            {
                 return Ok(run);
             }
 
@@ -537,19 +940,269 @@ impl GitHubClient {
                     }
                     Err(e) => {
                         debug!("Cancel failed: {e}");
-                        Err(PluginError::ApiError(format!("Failed to cancel run: {e}")))
+                        Err(api_error("Failed to cancel run", e))
                     }
                 }
             })
             .await
     }
 
+    pub async fn rerun(
+        &self, owner: &str, repo: &str, run_id: u64, failed_only: bool,
+    ) -> PluginResult<()> {
+        let endpoint = if failed_only {
+            "rerun-failed-jobs"
+        } else {
+            "rerun"
+        };
+
+        self.retry_policy
+            .retry(|| async {
+                debug!("Re-running run {run_id} ({endpoint}) for {owner}/{repo}");
+
+                let url = format!("/repos/{owner}/{repo}/actions/runs/{run_id}/{endpoint}");
+
+                let response: Result<serde_json::Value, octocrab::Error> =
+                    self.octocrab.post(url, None::<&()>).await;
+
+                response
+                    .map(|_| ())
+                    .map_err(|e| api_error("Failed to re-run workflow", e))
+            })
+            .await
+    }
+
+    pub async fn fetch_run_jobs(
+        &self, owner: &str, repo: &str, run_id: u64,
+    ) -> PluginResult<Vec<types::Job>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("/repos/{owner}/{repo}/actions/runs/{run_id}/jobs?per_page=100");
+
+                let response: Result<types::JobsResponse, octocrab::Error> =
+                    self.octocrab.get(url, None::<&()>).await;
+
+                response
+                    .map(|r| r.jobs)
+                    .map_err(|e| PluginError::ApiError(format!("Failed to fetch jobs: {e}")))
+            })
+            .await
+    }
+
+    // Deployments of a waiting run held by environment protection rules.
+    pub async fn fetch_pending_deployments(
+        &self, owner: &str, repo: &str, run_id: u64,
+    ) -> PluginResult<Vec<types::PendingDeployment>> {
+        self.retry_policy
+            .retry(|| async {
+                let url =
+                    format!("/repos/{owner}/{repo}/actions/runs/{run_id}/pending_deployments");
+
+                self.octocrab
+                    .get(url, None::<&()>)
+                    .await
+                    .map_err(|e| api_error("Failed to fetch pending deployments", e))
+            })
+            .await
+    }
+
+    pub async fn fetch_check_suite_runs(
+        &self, owner: &str, repo: &str, check_suite_id: u64,
+    ) -> PluginResult<Vec<types::CheckRun>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "/repos/{owner}/{repo}/check-suites/{check_suite_id}/check-runs?per_page=100"
+                );
+
+                let response: Result<types::CheckRunsResponse, octocrab::Error> =
+                    self.octocrab.get(url, None::<&()>).await;
+
+                response
+                    .map(|r| r.check_runs)
+                    .map_err(|e| PluginError::ApiError(format!("Failed to fetch check runs: {e}")))
+            })
+            .await
+    }
+
+    pub async fn fetch_check_run_annotations(
+        &self, owner: &str, repo: &str, check_run_id: u64,
+    ) -> PluginResult<Vec<types::CheckAnnotation>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "/repos/{owner}/{repo}/check-runs/{check_run_id}/annotations?per_page=50"
+                );
+
+                self.octocrab.get(url, None::<&()>).await.map_err(|e| {
+                    PluginError::ApiError(format!("Failed to fetch check run annotations: {e}"))
+                })
+            })
+            .await
+    }
+
+    pub async fn fetch_branch_names(&self, owner: &str, repo: &str) -> PluginResult<Vec<String>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("/repos/{owner}/{repo}/branches?per_page=100");
+
+                let response: Result<Vec<types::Branch>, octocrab::Error> =
+                    self.octocrab.get(url, None::<&()>).await;
+
+                response
+                    .map(|branches| branches.into_iter().map(|b| b.name).collect())
+                    .map_err(|e| PluginError::ApiError(format!("Failed to fetch branches: {e}")))
+            })
+            .await
+    }
+
+    pub async fn fetch_open_pull_requests(
+        &self, owner: &str, repo: &str,
+    ) -> PluginResult<Vec<types::PullRequest>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("/repos/{owner}/{repo}/pulls?state=open&per_page=100");
+
+                self.octocrab.get(url, None::<&()>).await.map_err(|e| {
+                    PluginError::ApiError(format!("Failed to fetch pull requests: {e}"))
+                })
+            })
+            .await
+    }
+
+    // Queued runs are waiting for a runner; only the count of in-progress runs
+    // is needed.
+    pub async fn fetch_repo_queue(&self, owner: &str, repo: &str) -> PluginResult<BuildQueue> {
+        self.retry_policy
+            .retry(|| async {
+                let queued_url =
+                    format!("/repos/{owner}/{repo}/actions/runs?status=queued&per_page=100");
+                let running_url =
+                    format!("/repos/{owner}/{repo}/actions/runs?status=in_progress&per_page=1");
+
+                let (queued, running): (types::RunsResponse, types::RunCountResponse) =
+                    futures::try_join!(
+                        self.octocrab.get(queued_url, None::<&()>),
+                        self.octocrab.get(running_url, None::<&()>),
+                    )
+                    .map_err(|e| {
+                        PluginError::ApiError(format!("Failed to fetch queued runs: {e}"))
+                    })?;
+
+                let queued_at: Vec<_> = queued
+                    .workflow_runs
+                    .iter()
+                    .map(|run| run.created_at)
+                    .collect();
+
+                Ok(BuildQueue::from_queued_at(
+                    format!("{owner}/{repo}"),
+                    &queued_at,
+                    running.total_count,
+                    Utc::now(),
+                ))
+            })
+            .await
+    }
+
+    // `scope` is either `repos/{owner}/{repo}` or `orgs/{org}`. Not retried:
+    // listing runners needs admin access, so 403/404 answers are expected.
+    pub async fn fetch_runners(&self, scope: &str) -> PluginResult<Vec<types::Runner>> {
+        let url = format!("/{scope}/actions/runners?per_page=100");
+
+        let response: Result<types::RunnersResponse, octocrab::Error> =
+            self.octocrab.get(url, None::<&()>).await;
+
+        response
+            .map(|r| r.runners)
+            .map_err(|e| PluginError::ApiError(format!("Failed to fetch runners: {e}")))
+    }
+
+    pub async fn compare_commits(
+        &self, owner: &str, repo: &str, base: &str, head: &str,
+    ) -> PluginResult<Vec<types::CompareCommit>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("/repos/{owner}/{repo}/compare/{base}...{head}");
+
+                let response: Result<types::CompareResponse, octocrab::Error> =
+                    self.octocrab.get(url, None::<&()>).await;
+
+                response
+                    .map(|r| r.commits)
+                    .map_err(|e| PluginError::ApiError(format!("Failed to compare commits: {e}")))
+            })
+            .await
+    }
+
+    pub async fn fetch_job_log(
+        &self, owner: &str, repo: &str, job_id: u64,
+    ) -> PluginResult<Option<String>> {
+        let url = format!(
+            "{}/repos/{owner}/{repo}/actions/jobs/{job_id}/logs",
+            self.api_url
+        );
+
+        let token = self.bearer_token().await?;
+        let response = self
+            .log_client
+            .get(&url)
+            .bearer_auth(token.expose_secret())
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await
+            .map_err(|e| PluginError::NetworkError(format!("Failed to fetch job log: {e}")))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::GONE {
+            debug!("Log for job {job_id} not available yet ({status})");
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(PluginError::ApiError(format!(
+                "Failed to fetch job log: HTTP {status}"
+            )));
+        }
+
+        read_text(response, self.max_response_bytes).await.map(Some)
+    }
+
+    // Checking the rate limit does not count against it.
+    pub async fn fetch_rate_limit(&self) -> PluginResult<RateLimitStatus> {
+        let response: types::RateLimitResponse = self
+            .octocrab
+            .get("/rate_limit", None::<&()>)
+            .await
+            .map_err(|e| PluginError::ApiError(format!("Failed to fetch rate limit: {e}")))?;
+
+        let core = response.resources.core;
+        Ok(RateLimitStatus {
+            limit: core.limit,
+            remaining: core.remaining,
+            reset_at: DateTime::from_timestamp(core.reset, 0),
+        })
+    }
+
     pub async fn check_token_permissions(&self) -> PluginResult<PermissionStatus> {
-        self.permission_checker.check_token_permissions().await
+        match (&self.auth, &self.permission_checker) {
+            (Auth::App(app), _) => {
+                let token = app.installation_token().await?;
+                Ok(PermissionChecker::build_app_status(&token.permissions))
+            }
+            (Auth::Token(_), Some(checker)) => checker.check_token_permissions().await,
+            (Auth::Token(_), None) => Err(PluginError::Internal(
+                "Permission checker not initialized".to_string(),
+            )),
+        }
     }
 }
 
-pub(crate) fn run_to_pipeline_run(run: types::Run, pipeline_id: &str) -> PipelineRun {
+pub(crate) fn run_to_pipeline_run(run: types::WorkflowRun, pipeline_id: &str) -> PipelineRun {
+    let types::WorkflowRun {
+        run,
+        pull_requests,
+        actor,
+    } = run;
     let status = mapper::map_status(run.status.as_str(), run.conclusion.as_deref());
 
     let duration_seconds = {
@@ -575,11 +1228,32 @@ pub(crate) fn run_to_pipeline_run(run: types::Run, pipeline_id: &str) -> Pipelin
     let mut metadata = HashMap::new();
     metadata.insert("event".to_string(), serde_json::json!(&run.event));
     metadata.insert("run_id".to_string(), serde_json::json!(run.id.0));
+    if let Some(check_suite_id) = run
+        .check_suite_url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|id| id.parse::<u64>().ok())
+    {
+        metadata.insert(
+            CHECK_SUITE_ID_KEY.to_string(),
+            serde_json::json!(check_suite_id),
+        );
+    }
 
     if let Some(owner) = run.repository.owner.as_ref() {
         metadata.insert("owner".to_string(), serde_json::json!(&owner.login));
     }
 
+    let pull_request = pull_requests.first().map(|pr| PullRequestRef {
+        number: pr.number as i64,
+        title: None,
+        url: run
+            .repository
+            .html_url
+            .as_ref()
+            .map(|url| format!("{}/pull/{}", url.as_str().trim_end_matches('/'), pr.number)),
+    });
+
     PipelineRun {
         id: format!("github-run-{}", run.id),
         pipeline_id: pipeline_id.to_string(),
@@ -593,7 +1267,94 @@ pub(crate) fn run_to_pipeline_run(run: types::Run, pipeline_id: &str) -> Pipelin
         commit_message: Some(run.head_commit.message.clone()),
         branch: Some(run.head_branch.clone()),
         actor: Some(run.head_commit.author.name.clone()),
+        commit_author: Some(run.head_commit.author.name.clone()),
+        author_avatar_url: actor.and_then(|actor| actor.avatar_url),
+        pull_request,
         inputs,
         metadata,
+        annotations: Vec::new(),
+    }
+}
+
+// A 403 means the token lacks a permission for the endpoint, which callers
+// surface differently from other API failures.
+pub(crate) fn api_error(context: &str, error: octocrab::Error) -> PluginError {
+    match &error {
+        octocrab::Error::GitHub { source, .. } if source.status_code.as_u16() == 403 => {
+            PluginError::PermissionDenied(format!("{context}: {error}"))
+        }
+        _ => PluginError::ApiError(format!("{context}: {error}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_to_pipeline_run_reads_pull_request_and_avatar() {
+        let run: types::WorkflowRun = serde_json::from_value(serde_json::json!({
+            "id": 30433642,
+            "workflow_id": 159038,
+            "node_id": "MDEyOldvcmtmbG93IFJ1bjI2OTI4OQ==",
+            "name": "Build",
+            "head_branch": "feature",
+            "head_sha": "acb5820ced9479c074f688cc328bf03f341a511d",
+            "run_number": 562,
+            "event": "pull_request",
+            "status": "completed",
+            "conclusion": "success",
+            "created_at": "2024-01-10T09:00:00Z",
+            "updated_at": "2024-01-10T09:05:00Z",
+            "url": "https://api.github.com/repos/octo/hello/actions/runs/30433642",
+            "html_url": "https://github.com/octo/hello/actions/runs/30433642",
+            "jobs_url": "https://api.github.com/repos/octo/hello/actions/runs/30433642/jobs",
+            "logs_url": "https://api.github.com/repos/octo/hello/actions/runs/30433642/logs",
+            "check_suite_url": "https://api.github.com/repos/octo/hello/check-suites/414944374",
+            "artifacts_url": "https://api.github.com/repos/octo/hello/actions/runs/30433642/artifacts",
+            "cancel_url": "https://api.github.com/repos/octo/hello/actions/runs/30433642/cancel",
+            "rerun_url": "https://api.github.com/repos/octo/hello/actions/runs/30433642/rerun",
+            "workflow_url": "https://api.github.com/repos/octo/hello/actions/workflows/159038",
+            "pull_requests": [{
+                "url": "https://api.github.com/repos/octo/hello/pulls/42",
+                "id": 717179206,
+                "number": 42,
+                "head": {"ref": "feature", "sha": "acb5820ced9479c074f688cc328bf03f341a511d"},
+                "base": {"ref": "main", "sha": "abbb7cf0c75ab51b84309ac547c3c3c089dd36eb"}
+            }],
+            "actor": {
+                "login": "octocat",
+                "avatar_url": "https://avatars.githubusercontent.com/u/583231?v=4"
+            },
+            "head_commit": {
+                "id": "acb5820ced9479c074f688cc328bf03f341a511d",
+                "tree_id": "d23f6eedb1e1b9610bbc754ddb5197bfe7271223",
+                "message": "Add feature",
+                "timestamp": "2024-01-10T08:59:00Z",
+                "author": {"name": "Octo Cat", "email": "octocat@github.com"},
+                "committer": {"name": "GitHub", "email": "noreply@github.com"}
+            },
+            "repository": {
+                "id": 1296269,
+                "name": "hello",
+                "full_name": "octo/hello",
+                "html_url": "https://github.com/octo/hello",
+                "url": "https://api.github.com/repos/octo/hello"
+            }
+        }))
+        .unwrap();
+
+        let pipeline_run = run_to_pipeline_run(run, "github__1__octo/hello__159038");
+
+        assert_eq!(
+            pipeline_run.author_avatar_url.as_deref(),
+            Some("https://avatars.githubusercontent.com/u/583231?v=4")
+        );
+        let pull_request = pipeline_run.pull_request.unwrap();
+        assert_eq!(pull_request.number, 42);
+        assert_eq!(
+            pull_request.url.as_deref(),
+            Some("https://github.com/octo/hello/pull/42")
+        );
     }
 }
@@ -0,0 +1,329 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use pipedash_plugin_api::Pipeline;
+use serde::Deserialize;
+
+use crate::mapper;
+
+pub(crate) const BATCH_SIZE: usize = 25;
+
+// Commits on the default branch (and check suites per commit) scanned for the
+// latest run of each workflow.
+const HISTORY_DEPTH: usize = 10;
+const CHECK_SUITES_PER_COMMIT: usize = 20;
+
+pub(crate) const PROBE_QUERY: &str = "query { rateLimit { remaining } }";
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Response<T> {
+    pub data: Option<T>,
+    #[serde(default)]
+    pub errors: Vec<Error>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Error {
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RepositoryNode {
+    workflow_files: Option<Tree>,
+    default_branch_ref: Option<BranchRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tree {
+    #[serde(default)]
+    entries: Vec<TreeEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TreeEntry {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchRef {
+    target: Option<CommitTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitTarget {
+    history: Option<Connection<HistoryCommit>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Connection<T> {
+    #[serde(default = "Vec::new")]
+    nodes: Vec<Option<T>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryCommit {
+    check_suites: Option<Connection<CheckSuite>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CheckSuite {
+    status: String,
+    conclusion: Option<String>,
+    branch: Option<NamedRef>,
+    workflow_run: Option<WorkflowRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedRef {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkflowRun {
+    created_at: DateTime<Utc>,
+    workflow: Workflow,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Workflow {
+    database_id: u64,
+    name: String,
+    url: String,
+}
+
+// One aliased `repository` field per repo (r0, r1, ...) so a whole batch costs
+// a single request.
+pub(crate) fn build_workflows_query(repos: &[(String, String)]) -> serde_json::Value {
+    let mut params = Vec::with_capacity(repos.len() * 2);
+    let mut fields = String::new();
+    let mut variables = serde_json::Map::new();
+
+    for (i, (owner, name)) in repos.iter().enumerate() {
+        params.push(format!("$o{i}: String!, $n{i}: String!"));
+        variables.insert(format!("o{i}"), serde_json::json!(owner));
+        variables.insert(format!("n{i}"), serde_json::json!(name));
+        fields.push_str(&format!(
+            r#"
+  r{i}: repository(owner: $o{i}, name: $n{i}) {{
+    workflowFiles: object(expression: "HEAD:.github/workflows") {{ ... on Tree {{ entries {{ name }} }} }}
+    defaultBranchRef {{
+      target {{
+        ... on Commit {{
+          history(first: {HISTORY_DEPTH}) {{
+            nodes {{
+              checkSuites(first: {CHECK_SUITES_PER_COMMIT}) {{
+                nodes {{
+                  status
+                  conclusion
+                  branch {{ name }}
+                  workflowRun {{ createdAt workflow {{ databaseId name url }} }}
+                }}
+              }}
+            }}
+          }}
+        }}
+      }}
+    }}
+  }}"#
+        ));
+    }
+
+    serde_json::json!({
+        "query": format!("query({}) {{{}\n}}", params.join(", "), fields),
+        "variables": variables,
+    })
+}
+
+pub(crate) fn repository_alias(index: usize) -> String {
+    format!("r{index}")
+}
+
+// Maps a repository to pipelines, or `None` when some workflow file had no run
+// in the scanned history; the caller then falls back to the REST API so no
+// workflow goes missing.
+pub(crate) fn map_repository(
+    provider_id: i64, owner: &str, repo: &str, node: &RepositoryNode,
+) -> Option<Vec<Pipeline>> {
+    let mut latest: HashMap<u64, (&CheckSuite, &WorkflowRun)> = HashMap::new();
+
+    let commits = node
+        .default_branch_ref
+        .as_ref()
+        .and_then(|r| r.target.as_ref())
+        .and_then(|t| t.history.as_ref())
+        .map(|h| h.nodes.iter().flatten())
+        .into_iter()
+        .flatten();
+
+    for commit in commits {
+        let suites = commit
+            .check_suites
+            .iter()
+            .flat_map(|c| c.nodes.iter().flatten());
+        for suite in suites {
+            let Some(run) = &suite.workflow_run else {
+                continue;
+            };
+            let entry = latest
+                .entry(run.workflow.database_id)
+                .or_insert((suite, run));
+            if run.created_at > entry.1.created_at {
+                *entry = (suite, run);
+            }
+        }
+    }
+
+    let seen_files: HashSet<String> = latest
+        .values()
+        .filter_map(|(_, run)| workflow_path(&run.workflow.url))
+        .collect();
+
+    let all_files_seen = node
+        .workflow_files
+        .iter()
+        .flat_map(|tree| tree.entries.iter())
+        .filter(|entry| entry.name.ends_with(".yml") || entry.name.ends_with(".yaml"))
+        .all(|entry| seen_files.contains(&format!(".github/workflows/{}", entry.name)));
+
+    if !all_files_seen {
+        return None;
+    }
+
+    let mut pipelines: Vec<Pipeline> = latest
+        .into_values()
+        .map(|(suite, run)| {
+            let mut metadata = HashMap::new();
+            metadata.insert(
+                "workflow_id".to_string(),
+                serde_json::json!(run.workflow.database_id),
+            );
+
+            Pipeline {
+                id: format!(
+                    "github__{}__{}__{}__{}",
+                    provider_id, owner, repo, run.workflow.database_id
+                ),
+                provider_id,
+                provider_type: "github".to_string(),
+                name: run.workflow.name.clone(),
+                status: mapper::map_status(
+                    &suite.status.to_lowercase(),
+                    suite
+                        .conclusion
+                        .as_deref()
+                        .map(str::to_lowercase)
+                        .as_deref(),
+                ),
+                last_run: Some(run.created_at),
+                last_updated: Utc::now(),
+                repository: format!("{owner}/{repo}"),
+                branch: suite.branch.as_ref().map(|b| b.name.clone()),
+                workflow_file: workflow_path(&run.workflow.url),
+                metadata,
+            }
+        })
+        .collect();
+    pipelines.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Some(pipelines)
+}
+
+// Workflow URLs look like `https://github.com/o/r/actions/workflows/ci.yml`.
+fn workflow_path(url: &str) -> Option<String> {
+    url.split_once("/actions/workflows/")
+        .map(|(_, file)| format!(".github/workflows/{file}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(files: &[&str]) -> RepositoryNode {
+        let entries: Vec<_> = files
+            .iter()
+            .map(|name| serde_json::json!({ "name": name }))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "workflowFiles": { "entries": entries },
+            "defaultBranchRef": { "target": { "history": { "nodes": [
+                { "checkSuites": { "nodes": [
+                    {
+                        "status": "COMPLETED",
+                        "conclusion": "FAILURE",
+                        "branch": { "name": "main" },
+                        "workflowRun": {
+                            "createdAt": "2025-01-02T00:00:00Z",
+                            "workflow": {
+                                "databaseId": 7,
+                                "name": "CI",
+                                "url": "https://github.com/o/r/actions/workflows/ci.yml"
+                            }
+                        }
+                    },
+                    { "status": "COMPLETED", "conclusion": "SUCCESS", "branch": null, "workflowRun": null }
+                ]}},
+                { "checkSuites": { "nodes": [
+                    {
+                        "status": "COMPLETED",
+                        "conclusion": "SUCCESS",
+                        "branch": { "name": "main" },
+                        "workflowRun": {
+                            "createdAt": "2025-01-01T00:00:00Z",
+                            "workflow": {
+                                "databaseId": 7,
+                                "name": "CI",
+                                "url": "https://github.com/o/r/actions/workflows/ci.yml"
+                            }
+                        }
+                    }
+                ]}}
+            ]}}}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_map_repository_uses_latest_run() {
+        let pipelines = map_repository(1, "o", "r", &node(&["ci.yml", "README.md"])).unwrap();
+
+        assert_eq!(pipelines.len(), 1);
+        assert_eq!(pipelines[0].id, "github__1__o__r__7");
+        assert_eq!(
+            pipelines[0].status,
+            pipedash_plugin_api::PipelineStatus::Failed
+        );
+        assert_eq!(
+            pipelines[0].workflow_file.as_deref(),
+            Some(".github/workflows/ci.yml")
+        );
+    }
+
+    #[test]
+    fn test_map_repository_falls_back_on_unseen_workflow() {
+        assert!(map_repository(1, "o", "r", &node(&["ci.yml", "release.yml"])).is_none());
+    }
+
+    #[test]
+    fn test_build_workflows_query_uses_variables() {
+        let query = build_workflows_query(&[
+            ("o".to_string(), "a".to_string()),
+            ("o".to_string(), "b\"".to_string()),
+        ]);
+
+        let text = query["query"].as_str().unwrap();
+        assert!(text.starts_with("query($o0: String!, $n0: String!, $o1: String!, $n1: String!)"));
+        assert!(text.contains("r1: repository(owner: $o1, name: $n1)"));
+        assert_eq!(query["variables"]["n1"], "b\"");
+    }
+}
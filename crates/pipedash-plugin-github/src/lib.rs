@@ -1,5 +1,6 @@
 mod client;
 mod config;
+mod graphql;
 mod mapper;
 mod metadata;
 mod permissions;
@@ -1,4 +1,17 @@
-use pipedash_plugin_api::PipelineStatus;
+use std::collections::HashMap;
+
+use chrono::Utc;
+use pipedash_plugin_api::{
+    AnnotationLevel,
+    BuildAgent,
+    CommitInfo,
+    LogStep,
+    PipelineStatus,
+    PullRequestRef,
+    RunAnnotation,
+};
+
+use crate::types;
 
 pub(crate) fn map_status(status: &str, conclusion: Option<&str>) -> PipelineStatus {
     match (status, conclusion) {
@@ -6,11 +19,179 @@ pub(crate) fn map_status(status: &str, conclusion: Option<&str>) -> PipelineStat
         ("completed", Some("failure")) => PipelineStatus::Failed,
         ("completed", Some("cancelled")) => PipelineStatus::Cancelled,
         ("completed", Some("skipped")) => PipelineStatus::Skipped,
-        ("in_progress", _) | ("queued", _) => PipelineStatus::Running,
+        ("completed", Some("neutral")) => PipelineStatus::Neutral,
+        // Runs from first-time contributors wait for a maintainer to approve them.
+        ("completed", Some("action_required")) | ("waiting", _) => PipelineStatus::Waiting,
+        ("in_progress", _) => PipelineStatus::Running,
+        ("queued", _) | ("requested", _) | ("pending", _) => PipelineStatus::Queued,
         _ => PipelineStatus::Pending,
     }
 }
 
+// One entry per environment the run is waiting on, shown in the run details.
+pub(crate) fn map_pending_deployments(
+    deployments: &[types::PendingDeployment],
+) -> serde_json::Value {
+    let entries: Vec<serde_json::Value> = deployments
+        .iter()
+        .map(|deployment| {
+            let reviewers: Vec<String> = deployment
+                .reviewers
+                .iter()
+                .filter_map(|r| match r.reviewer_type.as_str() {
+                    "Team" => r
+                        .reviewer
+                        .slug
+                        .as_ref()
+                        .or(r.reviewer.name.as_ref())
+                        .map(|team| format!("team:{team}")),
+                    _ => r.reviewer.login.as_ref().map(|login| format!("@{login}")),
+                })
+                .collect();
+
+            let wait_timer_ends_at = deployment
+                .wait_timer_started_at
+                .filter(|_| deployment.wait_timer > 0)
+                .map(|started| started + chrono::Duration::minutes(deployment.wait_timer));
+
+            serde_json::json!({
+                "environment": deployment.environment.name,
+                "environment_url": deployment.environment.html_url,
+                "reviewers": reviewers,
+                "wait_timer_minutes": deployment.wait_timer,
+                "wait_timer_ends_at": wait_timer_ends_at,
+                "can_approve": deployment.current_user_can_approve,
+            })
+        })
+        .collect();
+
+    serde_json::Value::Array(entries)
+}
+
+pub(crate) fn map_step(step: &types::JobStep) -> LogStep {
+    LogStep {
+        number: step.number,
+        name: step.name.clone(),
+        status: map_status(&step.status, step.conclusion.as_deref()),
+        started_at: step.started_at,
+        completed_at: step.completed_at,
+    }
+}
+
+pub(crate) fn map_commit(commit: types::CompareCommit) -> CommitInfo {
+    let signature = commit.commit.author;
+    CommitInfo {
+        sha: commit.sha,
+        message: commit.commit.message,
+        author: commit
+            .author
+            .map(|user| user.login)
+            .or_else(|| signature.as_ref().and_then(|s| s.name.clone())),
+        committed_at: signature.and_then(|s| s.date),
+        url: commit.html_url,
+    }
+}
+
+pub(crate) fn map_runner(runner: types::Runner, scope: &str) -> BuildAgent {
+    let status = match (runner.status.as_str(), runner.busy) {
+        ("online", true) => "busy",
+        ("online", false) => "idle",
+        _ => "offline",
+    };
+
+    let labels: Vec<String> = runner.labels.into_iter().map(|label| label.name).collect();
+
+    let mut metadata = HashMap::new();
+    metadata.insert("os".to_string(), runner.os);
+    metadata.insert("labels".to_string(), labels.join(","));
+    metadata.insert("scope".to_string(), scope.to_string());
+
+    BuildAgent {
+        id: runner.id.to_string(),
+        hostname: runner.name.clone(),
+        name: runner.name,
+        status: status.to_string(),
+        job_id: None,
+        last_seen: Utc::now(),
+        metadata,
+    }
+}
+
+pub(crate) fn slice_log(content: &str, offset: usize) -> &str {
+    let mut start = offset.min(content.len());
+    while !content.is_char_boundary(start) {
+        start += 1;
+    }
+    &content[start..]
+}
+
+// GitHub bills each hosted job rounded up to the minute, with Windows and
+// macOS runners counting double and ten times. Self-hosted jobs are free.
+pub(crate) fn billable_minutes(jobs: &[types::Job]) -> f64 {
+    jobs.iter()
+        .filter(|job| !job.labels.iter().any(|label| label == "self-hosted"))
+        .filter_map(|job| {
+            let seconds = (job.completed_at? - job.started_at?).num_seconds().max(0);
+            let multiplier = if job.labels.iter().any(|l| l.starts_with("macos")) {
+                10
+            } else if job.labels.iter().any(|l| l.starts_with("windows")) {
+                2
+            } else {
+                1
+            };
+            Some((seconds as u64).div_ceil(60) * multiplier)
+        })
+        .sum::<u64>() as f64
+}
+
+// Keyed by head branch, for matching against the runs' `head_branch`.
+pub(crate) fn map_pull_request(pull_request: types::PullRequest) -> (String, PullRequestRef) {
+    (
+        pull_request.head.ref_name,
+        PullRequestRef {
+            number: pull_request.number,
+            title: Some(pull_request.title),
+            url: Some(pull_request.html_url),
+        },
+    )
+}
+
+// Line annotations are listed under the check run's own summary, e.g.
+// "src/app.rs:12: assertion failed". `None` for check runs with nothing to say.
+pub(crate) fn map_check_run(
+    check_run: types::CheckRun, annotations: &[types::CheckAnnotation],
+) -> Option<RunAnnotation> {
+    let level = match check_run.conclusion.as_deref() {
+        Some("failure") | Some("timed_out") | Some("startup_failure") => AnnotationLevel::Error,
+        Some("action_required") => AnnotationLevel::Warning,
+        Some("success") => AnnotationLevel::Success,
+        _ => AnnotationLevel::Info,
+    };
+
+    let lines: Vec<String> = check_run
+        .output
+        .summary
+        .iter()
+        .filter(|summary| !summary.trim().is_empty())
+        .map(|summary| summary.trim().to_string())
+        .chain(annotations.iter().map(|a| match a.start_line {
+            Some(line) => format!("{}:{}: {}", a.path, line, a.message),
+            None => format!("{}: {}", a.path, a.message),
+        }))
+        .collect();
+
+    if lines.is_empty() && check_run.output.title.is_none() {
+        return None;
+    }
+
+    Some(RunAnnotation {
+        level,
+        title: Some(check_run.output.title.unwrap_or(check_run.name)),
+        summary: lines.join("\n"),
+        url: check_run.html_url,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -30,6 +211,117 @@ mod tests {
             PipelineStatus::Cancelled
         );
         assert_eq!(map_status("in_progress", None), PipelineStatus::Running);
-        assert_eq!(map_status("queued", None), PipelineStatus::Running);
+        assert_eq!(map_status("queued", None), PipelineStatus::Queued);
+        assert_eq!(map_status("waiting", None), PipelineStatus::Waiting);
+        assert_eq!(
+            map_status("completed", Some("neutral")),
+            PipelineStatus::Neutral
+        );
+        assert_eq!(
+            map_status("completed", Some("action_required")),
+            PipelineStatus::Waiting
+        );
+    }
+
+    #[test]
+    fn test_map_pending_deployments() {
+        let deployments: Vec<types::PendingDeployment> =
+            serde_json::from_value(serde_json::json!([{
+                "environment": {"id": 1, "name": "production", "html_url": null},
+                "wait_timer": 30,
+                "wait_timer_started_at": "2024-05-01T10:00:00Z",
+                "current_user_can_approve": true,
+                "reviewers": [
+                    {"type": "User", "reviewer": {"login": "octocat", "id": 1}},
+                    {"type": "Team", "reviewer": {"slug": "platform", "name": "Platform", "id": 2}}
+                ]
+            }]))
+            .unwrap();
+
+        let value = map_pending_deployments(&deployments);
+        assert_eq!(value[0]["environment"], "production");
+        assert_eq!(
+            value[0]["reviewers"],
+            serde_json::json!(["@octocat", "team:platform"])
+        );
+        assert_eq!(value[0]["wait_timer_ends_at"], "2024-05-01T10:30:00Z");
+        assert_eq!(value[0]["can_approve"], true);
+    }
+
+    #[test]
+    fn test_slice_log() {
+        let log = "step 1\nstep 2\n";
+        assert_eq!(slice_log(log, 0), log);
+        assert_eq!(slice_log(log, 7), "step 2\n");
+        assert_eq!(slice_log(log, 100), "");
+        assert_eq!(slice_log("héllo", 2), "llo");
+    }
+
+    #[test]
+    fn test_map_runner() {
+        let runner: types::Runner = serde_json::from_value(serde_json::json!({
+            "id": 42,
+            "name": "build-01",
+            "os": "linux",
+            "status": "online",
+            "busy": true,
+            "labels": [{ "name": "self-hosted" }, { "name": "x64" }],
+        }))
+        .unwrap();
+
+        let agent = map_runner(runner, "orgs/acme");
+
+        assert_eq!(agent.id, "42");
+        assert_eq!(agent.status, "busy");
+        assert_eq!(agent.metadata["labels"], "self-hosted,x64");
+        assert_eq!(agent.metadata["scope"], "orgs/acme");
+    }
+
+    #[test]
+    fn test_billable_minutes() {
+        let jobs: Vec<types::Job> = serde_json::from_value(serde_json::json!([
+            { "id": 1, "name": "linux", "status": "completed", "conclusion": "success",
+              "started_at": "2025-01-01T12:00:00Z", "completed_at": "2025-01-01T12:01:30Z",
+              "labels": ["ubuntu-latest"] },
+            { "id": 2, "name": "mac", "status": "completed", "conclusion": "success",
+              "started_at": "2025-01-01T12:00:00Z", "completed_at": "2025-01-01T12:00:20Z",
+              "labels": ["macos-14"] },
+            { "id": 3, "name": "own", "status": "completed", "conclusion": "success",
+              "started_at": "2025-01-01T12:00:00Z", "completed_at": "2025-01-01T13:00:00Z",
+              "labels": ["self-hosted", "linux"] },
+            { "id": 4, "name": "skipped", "status": "completed", "conclusion": "skipped",
+              "started_at": null, "completed_at": null },
+        ]))
+        .unwrap();
+
+        assert_eq!(billable_minutes(&jobs), 12.0);
+    }
+
+    #[test]
+    fn test_map_check_run() {
+        let check_runs: types::CheckRunsResponse = serde_json::from_value(serde_json::json!({
+            "check_runs": [
+                { "id": 1, "name": "test", "conclusion": "failure",
+                  "html_url": "https://github.com/o/r/runs/1",
+                  "output": { "title": "2 tests failed", "summary": null,
+                              "annotations_count": 1 } },
+                { "id": 2, "name": "lint", "conclusion": "success",
+                  "html_url": null,
+                  "output": { "title": null, "summary": null } },
+            ]
+        }))
+        .unwrap();
+        let annotations: Vec<types::CheckAnnotation> = serde_json::from_value(serde_json::json!([
+            { "path": "src/app.rs", "start_line": 12, "message": "assertion failed" },
+        ]))
+        .unwrap();
+
+        let mut check_runs = check_runs.check_runs.into_iter();
+        let failed = map_check_run(check_runs.next().unwrap(), &annotations).unwrap();
+        assert_eq!(failed.level, AnnotationLevel::Error);
+        assert_eq!(failed.title.as_deref(), Some("2 tests failed"));
+        assert_eq!(failed.summary, "src/app.rs:12: assertion failed");
+
+        assert!(map_check_run(check_runs.next().unwrap(), &[]).is_none());
     }
 }
@@ -19,20 +19,49 @@ pub fn create_metadata() -> PluginMetadata {
 }
 
 fn create_config_schema() -> ConfigSchema {
-    ConfigSchema::new().add_field(ConfigField {
-        key: "token".to_string(),
-        label: "API Token".to_string(),
-        description: Some(
-            "GitHub Personal Access Token or Fine-grained token with repo and workflow permissions"
-                .to_string(),
-        ),
-        field_type: ConfigFieldType::Password,
-        required: true,
-        default_value: None,
-        options: None,
-        validation_regex: None,
-        validation_message: None,
-    })
+    ConfigSchema::new()
+        .add_field(ConfigField {
+            key: "token".to_string(),
+            label: "API Token".to_string(),
+            description: Some(
+                "GitHub Personal Access Token or Fine-grained token with repo and workflow permissions. When using a GitHub App, paste the app's private key (PEM) instead"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Password,
+            required: true,
+            default_value: None,
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+        .add_field(ConfigField {
+            key: "app_id".to_string(),
+            label: "GitHub App ID".to_string(),
+            description: Some(
+                "Authenticate as a GitHub App installation instead of a personal token (higher rate limits, org-scoped permissions)"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Text,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: Some(r"^\d*$".to_string()),
+            validation_message: Some("App ID must be a number".to_string()),
+        })
+        .add_field(ConfigField {
+            key: "installation_id".to_string(),
+            label: "Installation ID".to_string(),
+            description: Some(
+                "ID of the app's installation on your user or organization (required with App ID)"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Text,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: Some(r"^\d*$".to_string()),
+            validation_message: Some("Installation ID must be a number".to_string()),
+        })
 }
 
 fn create_capabilities() -> PluginCapabilities {
@@ -40,10 +69,16 @@ fn create_capabilities() -> PluginCapabilities {
         pipelines: true,
         pipeline_runs: true,
         trigger: true,
-        agents: false,
-        artifacts: false,
-        queues: false,
-        custom_tables: false,
+        agents: true,
+        queues: true,
+        logs: true,
+        retry_failed_jobs: true,
+        branch_statuses: true,
+        cancel: true,
+        workflow_parameters: true,
+        repository_discovery: true,
+        pagination: PaginationStyle::Server,
+        ..PluginCapabilities::default()
     }
 }
 
@@ -99,6 +134,12 @@ fn create_features() -> Vec<Feature> {
             description: "POST /repos/{owner}/{repo}/actions/runs/{id}/cancel - Stop queued or running workflows".to_string(),
             required_permissions: vec!["workflow".to_string()],
         },
+        Feature {
+            id: "list_agents".to_string(),
+            name: "Monitor self-hosted runners".to_string(),
+            description: "GET /repos/{owner}/{repo}/actions/runners - Track runner status and capacity (requires admin access)".to_string(),
+            required_permissions: vec!["repo".to_string()],
+        },
         Feature {
             id: "filter_by_org".to_string(),
             name: "Filter repos by organization".to_string(),
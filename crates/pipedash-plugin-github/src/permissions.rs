@@ -176,6 +176,38 @@ impl PermissionChecker {
     }
 
     fn build_fine_grained_status(capabilities: &ScopeSet) -> PermissionStatus {
+        Self::build_capability_status(capabilities, "fine_grained")
+    }
+
+    // App installations report their permissions in the installation token
+    // response, using the same categories as fine-grained tokens.
+    pub(crate) fn build_app_status(
+        permissions: &std::collections::HashMap<String, String>,
+    ) -> PermissionStatus {
+        let level = |name: &str| permissions.get(name).map(String::as_str);
+        let mut capabilities = ScopeSet::new();
+
+        if level("metadata").is_some() {
+            capabilities.insert("Repository Metadata".to_string());
+        }
+        if level("members").is_some() {
+            capabilities.insert("Organization members and teams (Read)".to_string());
+        }
+        match level("actions") {
+            Some("write") => {
+                capabilities.insert("Actions (Read)".to_string());
+                capabilities.insert("Actions (Write)".to_string());
+            }
+            Some(_) => {
+                capabilities.insert("Actions (Read)".to_string());
+            }
+            None => {}
+        }
+
+        Self::build_capability_status(&capabilities, "github_app")
+    }
+
+    fn build_capability_status(capabilities: &ScopeSet, token_type: &str) -> PermissionStatus {
         let required_perms = Self::get_fine_grained_permissions();
         let permissions: Vec<PermissionCheck> = required_perms
             .iter()
@@ -199,7 +231,7 @@ impl PermissionChecker {
             .all(|p| p.granted);
 
         let mut metadata = std::collections::HashMap::new();
-        metadata.insert("token_type".to_string(), "fine_grained".to_string());
+        metadata.insert("token_type".to_string(), token_type.to_string());
 
         PermissionStatus {
             permissions,
@@ -1,13 +1,19 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use futures::future::join_all;
+use octocrab::models::{
+    AppId,
+    InstallationId,
+};
 use octocrab::Octocrab;
 use pipedash_plugin_api::*;
 
 use crate::{
     client,
     config,
+    mapper,
     metadata,
 };
 
@@ -16,6 +22,7 @@ pub struct GitHubPlugin {
     client: Option<client::GitHubClient>,
     provider_id: Option<i64>,
     config: HashMap<String, String>,
+    conditional_cache: Option<Arc<ConditionalRequestCache>>,
 }
 
 impl Default for GitHubPlugin {
@@ -39,7 +46,7 @@ mod permission_mapping {
         token_type: &str, feature_id: &str, classic_permissions: &[String],
     ) -> Vec<String> {
         match token_type {
-            "fine_grained" => FINE_GRAINED_FEATURE_MAPPINGS
+            "fine_grained" | "github_app" => FINE_GRAINED_FEATURE_MAPPINGS
                 .iter()
                 .find(|(id, _)| *id == feature_id)
                 .map(|(_, perm)| vec![perm.to_string()])
@@ -56,6 +63,7 @@ impl GitHubPlugin {
             client: None,
             provider_id: None,
             config: HashMap::new(),
+            conditional_cache: None,
         }
     }
 
@@ -64,6 +72,77 @@ impl GitHubPlugin {
             .as_ref()
             .ok_or_else(|| PluginError::Internal("Plugin not initialized".to_string()))
     }
+
+    // Also returns whether anything changed since the previous fetch. GraphQL
+    // has no conditional requests, so only a fetch served entirely by 304s
+    // counts as unchanged.
+    async fn fetch_all_pipelines(&self) -> PluginResult<(Vec<Pipeline>, bool)> {
+        let provider_id = self
+            .provider_id
+            .ok_or_else(|| PluginError::Internal("Provider ID not set".to_string()))?;
+
+        let repositories = config::get_repositories(&self.config);
+        tracing::debug!(repositories = ?repositories, "Configured GitHub repositories");
+
+        if repositories.is_empty() {
+            if config::auto_discover(&self.config) {
+                return Ok((Vec::new(), true));
+            }
+            return Err(PluginError::InvalidConfig(
+                "No repositories configured".to_string(),
+            ));
+        }
+
+        let client = self.client()?;
+        let requested = repositories.len();
+        let (mut all_pipelines, repositories) = if client.graphql_available().await {
+            client
+                .fetch_repos_workflows_graphql(provider_id, repositories)
+                .await
+        } else {
+            (Vec::new(), repositories)
+        };
+        let mut modified = repositories.len() < requested;
+
+        let futures = repositories
+            .into_iter()
+            .map(|repo_full_name| client.fetch_repo_workflows(provider_id, repo_full_name));
+
+        let results = join_all(futures).await;
+
+        let mut errors = Vec::new();
+
+        for result in results {
+            match result {
+                Ok((mut pipelines, repo_modified)) => {
+                    tracing::debug!(count = pipelines.len(), "GitHub repo returned workflows");
+                    all_pipelines.append(&mut pipelines);
+                    modified |= repo_modified;
+                }
+                Err(e) => {
+                    modified = true;
+                    errors.push(e);
+                }
+            }
+        }
+
+        let unique_count = all_pipelines
+            .iter()
+            .map(|p| &p.id)
+            .collect::<std::collections::HashSet<_>>()
+            .len();
+        tracing::debug!(
+            unique_pipelines = unique_count,
+            total_pipelines = all_pipelines.len(),
+            "GitHub pipeline fetch complete"
+        );
+
+        if !errors.is_empty() && all_pipelines.is_empty() {
+            return Err(errors.into_iter().next().unwrap());
+        }
+
+        Ok((all_pipelines, modified))
+    }
 }
 
 #[async_trait]
@@ -72,9 +151,13 @@ impl Plugin for GitHubPlugin {
         &self.metadata
     }
 
+    fn set_conditional_cache(&mut self, cache: Arc<ConditionalRequestCache>) {
+        self.conditional_cache = Some(cache);
+    }
+
     fn initialize(
         &mut self, provider_id: i64, config: HashMap<String, String>,
-        _http_client: Option<std::sync::Arc<reqwest::Client>>,
+        http_client: Option<std::sync::Arc<reqwest::Client>>,
     ) -> PluginResult<()> {
         let token = config
             .get("token")
@@ -94,16 +177,70 @@ impl Plugin for GitHubPlugin {
 
         tracing::debug!(api_url = %api_url, "Using GitHub API URL");
 
-        let octocrab = Octocrab::builder()
-            .personal_token(token.clone())
-            .base_uri(&api_url)
-            .map_err(|e| PluginError::InvalidConfig(format!("Failed to set base URI: {e}")))?
-            .build()
-            .map_err(|e| {
-                PluginError::InvalidConfig(format!("Failed to build GitHub client: {e}"))
-            })?;
+        let (octocrab, auth) = match config::get_app_installation(&config)? {
+            Some((app_id, installation_id)) => {
+                tracing::debug!(app_id, installation_id, "Using GitHub App authentication");
+
+                let key = jsonwebtoken::EncodingKey::from_rsa_pem(
+                    config::normalize_private_key(token).as_bytes(),
+                )
+                .map_err(|e| {
+                    PluginError::InvalidConfig(format!("Invalid GitHub App private key: {e}"))
+                })?;
+                let app = Octocrab::builder()
+                    .app(AppId(app_id), key)
+                    .base_uri(&api_url)
+                    .map_err(|e| {
+                        PluginError::InvalidConfig(format!("Failed to set base URI: {e}"))
+                    })?
+                    .build()
+                    .map_err(|e| {
+                        PluginError::InvalidConfig(format!("Failed to build GitHub client: {e}"))
+                    })?;
+                let octocrab = app
+                    .installation(InstallationId(installation_id))
+                    .map_err(|e| {
+                        PluginError::InvalidConfig(format!("Failed to build GitHub client: {e}"))
+                    })?;
+
+                (
+                    octocrab,
+                    client::Auth::App(Box::new(client::AppInstallation::new(app, installation_id))),
+                )
+            }
+            None => {
+                let octocrab = Octocrab::builder()
+                    .personal_token(token.clone())
+                    .base_uri(&api_url)
+                    .map_err(|e| {
+                        PluginError::InvalidConfig(format!("Failed to set base URI: {e}"))
+                    })?
+                    .build()
+                    .map_err(|e| {
+                        PluginError::InvalidConfig(format!("Failed to build GitHub client: {e}"))
+                    })?;
+
+                (octocrab, client::Auth::Token(token.clone().into()))
+            }
+        };
 
-        let github_client = client::GitHubClient::new(octocrab, token.clone())?;
+        let conditional_cache = self
+            .conditional_cache
+            .clone()
+            .unwrap_or_else(|| Arc::new(ConditionalRequestCache::new()));
+        let http_client = http_client.unwrap_or_else(|| {
+            Arc::new(
+                reqwest::Client::builder()
+                    .use_rustls_tls()
+                    .timeout(std::time::Duration::from_secs(30))
+                    .connect_timeout(std::time::Duration::from_secs(10))
+                    .build()
+                    .expect("Failed to build HTTP client"),
+            )
+        });
+        let github_client =
+            client::GitHubClient::new(octocrab, auth, api_url, http_client, conditional_cache)?
+                .with_max_response_bytes(max_response_bytes(&config));
         self.client = Some(github_client);
         self.provider_id = Some(provider_id);
         self.config = config;
@@ -111,9 +248,19 @@ impl Plugin for GitHubPlugin {
         Ok(())
     }
 
+    fn api_endpoint(&self) -> Option<String> {
+        Some(config::build_api_url(&config::get_base_url(&self.config)))
+    }
+
     async fn validate_credentials(&self) -> PluginResult<bool> {
         let client = self.client()?;
 
+        // Installation tokens can't read /user; minting one already proves the
+        // app ID, private key and installation are valid.
+        if client.is_app() {
+            return client.bearer_token().await.map(|_| true);
+        }
+
         client
             .retry_policy
             .retry(|| async {
@@ -159,55 +306,12 @@ impl Plugin for GitHubPlugin {
     }
 
     async fn fetch_pipelines(&self) -> PluginResult<Vec<Pipeline>> {
-        let provider_id = self
-            .provider_id
-            .ok_or_else(|| PluginError::Internal("Provider ID not set".to_string()))?;
-
-        let repositories = config::get_repositories(&self.config);
-        tracing::debug!(repositories = ?repositories, "Configured GitHub repositories");
-
-        if repositories.is_empty() {
-            return Err(PluginError::InvalidConfig(
-                "No repositories configured".to_string(),
-            ));
-        }
-
-        let client = self.client()?;
-        let futures = repositories
-            .into_iter()
-            .map(|repo_full_name| client.fetch_repo_workflows(provider_id, repo_full_name));
-
-        let results = join_all(futures).await;
-
-        let mut all_pipelines = Vec::new();
-        let mut errors = Vec::new();
-
-        for result in results {
-            match result {
-                Ok(mut pipelines) => {
-                    tracing::debug!(count = pipelines.len(), "GitHub repo returned workflows");
-                    all_pipelines.append(&mut pipelines);
-                }
-                Err(e) => errors.push(e),
-            }
-        }
-
-        let unique_count = all_pipelines
-            .iter()
-            .map(|p| &p.id)
-            .collect::<std::collections::HashSet<_>>()
-            .len();
-        tracing::debug!(
-            unique_pipelines = unique_count,
-            total_pipelines = all_pipelines.len(),
-            "GitHub pipeline fetch complete"
-        );
-
-        if !errors.is_empty() && all_pipelines.is_empty() {
-            return Err(errors.into_iter().next().unwrap());
-        }
+        Ok(self.fetch_all_pipelines().await?.0)
+    }
 
-        Ok(all_pipelines)
+    async fn fetch_pipelines_if_modified(&self) -> PluginResult<Option<Vec<Pipeline>>> {
+        let (pipelines, modified) = self.fetch_all_pipelines().await?;
+        Ok(modified.then_some(pipelines))
     }
 
     async fn fetch_run_history(
@@ -265,8 +369,27 @@ impl Plugin for GitHubPlugin {
         let run = client
             .fetch_run_by_number(owner, repo, workflow_id, run_number)
             .await?;
+        let run_id = run.id.0;
 
-        Ok(client::run_to_pipeline_run(run, pipeline_id))
+        let mut pipeline_run = client::run_to_pipeline_run(run, pipeline_id);
+
+        // Best effort: the run details still load without the protection rules.
+        if pipeline_run.status == PipelineStatus::Waiting {
+            match client.fetch_pending_deployments(owner, repo, run_id).await {
+                Ok(deployments) if !deployments.is_empty() => {
+                    pipeline_run.metadata.insert(
+                        client::ENVIRONMENT_PROTECTION_KEY.to_string(),
+                        mapper::map_pending_deployments(&deployments),
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(run_id, error = %e, "Failed to fetch pending deployments");
+                }
+            }
+        }
+
+        Ok(pipeline_run)
     }
 
     async fn fetch_workflow_parameters(
@@ -283,6 +406,73 @@ impl Plugin for GitHubPlugin {
         }])
     }
 
+    // Self-hosted runners can be registered on the repository or on the owning
+    // organization. Organization listing fails for personal accounts and for
+    // tokens without admin:org, so scopes fail independently.
+    async fn fetch_agents(&self) -> PluginResult<Vec<BuildAgent>> {
+        let repositories = config::get_repositories(&self.config);
+        let mut scopes: Vec<String> = Vec::new();
+        for repo in &repositories {
+            if let Some((owner, name)) = config::parse_repo(repo) {
+                let org_scope = format!("orgs/{owner}");
+                if !scopes.contains(&org_scope) {
+                    scopes.push(org_scope);
+                }
+                scopes.push(format!("repos/{owner}/{name}"));
+            }
+        }
+
+        let client = self.client()?;
+        let results = join_all(scopes.iter().map(|scope| client.fetch_runners(scope))).await;
+
+        let mut agents: HashMap<String, BuildAgent> = HashMap::new();
+        let mut last_error = None;
+        let mut any_succeeded = false;
+
+        for (scope, result) in scopes.iter().zip(results) {
+            match result {
+                Ok(runners) => {
+                    any_succeeded = true;
+                    for runner in runners {
+                        let agent = mapper::map_runner(runner, scope);
+                        agents.entry(agent.id.clone()).or_insert(agent);
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!(scope = %scope, error = %e, "Skipping GitHub runner scope");
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        if !any_succeeded {
+            if let Some(e) = last_error {
+                return Err(e);
+            }
+        }
+
+        let mut agents: Vec<BuildAgent> = agents.into_values().collect();
+        agents.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(agents)
+    }
+
+    async fn fetch_queues(&self) -> PluginResult<Vec<BuildQueue>> {
+        let repositories: Vec<(String, String)> = config::get_repositories(&self.config)
+            .iter()
+            .filter_map(|repo| config::parse_repo(repo))
+            .collect();
+
+        let client = self.client()?;
+        let results = join_all(
+            repositories
+                .iter()
+                .map(|(owner, repo)| client.fetch_repo_queue(owner, repo)),
+        )
+        .await;
+
+        results.into_iter().collect()
+    }
+
     async fn trigger_pipeline(&self, params: TriggerParams) -> PluginResult<String> {
         let parts: Vec<&str> = params.workflow_id.split("__").collect();
         if parts.len() != 5 {
@@ -330,9 +520,7 @@ impl Plugin for GitHubPlugin {
             client.octocrab.post(url, Some(&body)).await;
 
         if let Err(e) = response {
-            return Err(PluginError::ApiError(format!(
-                "Failed to trigger workflow: {e}"
-            )));
+            return Err(client::api_error("Failed to trigger workflow", e));
         }
 
         let workflow_id_u64: u64 = workflow_id.parse().map_err(|_| {
@@ -427,6 +615,256 @@ impl Plugin for GitHubPlugin {
         let run_id_u64: u64 = run.id.0;
         client.cancel_run(owner, repo, run_id_u64).await
     }
+
+    async fn retry_run(
+        &self, pipeline_id: &str, run_number: i64, failed_only: bool,
+    ) -> PluginResult<String> {
+        let parts: Vec<&str> = pipeline_id.split("__").collect();
+        if parts.len() != 5 {
+            return Err(PluginError::InvalidConfig(format!(
+                "Invalid pipeline ID format: {} (expected 5 parts, got {})",
+                pipeline_id,
+                parts.len()
+            )));
+        }
+
+        let owner = parts[2];
+        let repo = parts[3];
+        let workflow_id_str = parts[4];
+        let workflow_id: u64 = workflow_id_str.parse().map_err(|_| {
+            PluginError::InvalidConfig(format!("Invalid workflow ID: {workflow_id_str}"))
+        })?;
+
+        let client = self.client()?;
+        let run = client
+            .fetch_run_by_number(owner, repo, workflow_id, run_number)
+            .await?;
+
+        client.rerun(owner, repo, run.id.0, failed_only).await?;
+
+        // Re-runs reuse the same run number, so the original run URL stays
+        // valid.
+        Ok(run.html_url.to_string())
+    }
+
+    async fn fetch_commits_between(
+        &self, pipeline_id: &str, base_sha: &str, head_sha: &str,
+    ) -> PluginResult<Vec<CommitInfo>> {
+        let parts: Vec<&str> = pipeline_id.split("__").collect();
+        if parts.len() != 5 {
+            return Err(PluginError::InvalidConfig(format!(
+                "Invalid pipeline ID format: {} (expected 5 parts, got {})",
+                pipeline_id,
+                parts.len()
+            )));
+        }
+
+        let owner = parts[2];
+        let repo = parts[3];
+
+        let client = self.client()?;
+        let commits = client
+            .compare_commits(owner, repo, base_sha, head_sha)
+            .await?;
+
+        Ok(commits.into_iter().map(mapper::map_commit).collect())
+    }
+
+    async fn fetch_run_logs(
+        &self, pipeline_id: &str, run_number: i64, query: LogQuery,
+    ) -> PluginResult<RunLogs> {
+        let parts: Vec<&str> = pipeline_id.split("__").collect();
+        if parts.len() != 5 {
+            return Err(PluginError::InvalidConfig(format!(
+                "Invalid pipeline ID format: {} (expected 5 parts, got {})",
+                pipeline_id,
+                parts.len()
+            )));
+        }
+
+        let owner = parts[2];
+        let repo = parts[3];
+        let workflow_id_str = parts[4];
+        let workflow_id: u64 = workflow_id_str.parse().map_err(|_| {
+            PluginError::InvalidConfig(format!("Invalid workflow ID: {workflow_id_str}"))
+        })?;
+
+        let client = self.client()?;
+        let run = client
+            .fetch_run_by_number(owner, repo, workflow_id, run_number)
+            .await?;
+        let jobs = client.fetch_run_jobs(owner, repo, run.id.0).await?;
+
+        let log_futures = jobs.into_iter().map(|job| {
+            let requested = query
+                .job_id
+                .as_ref()
+                .is_none_or(|id| *id == job.id.to_string());
+            let offset = if query.job_id.is_some() {
+                query.offset
+            } else {
+                0
+            };
+
+            async move {
+                // GitHub only serves job logs once the job has finished;
+                // in-progress jobs report step progress and are polled again
+                // by the caller.
+                let is_complete = job.status == "completed";
+                let log = if requested && is_complete {
+                    client.fetch_job_log(owner, repo, job.id).await?
+                } else {
+                    None
+                };
+
+                let (content, next_offset) = match log {
+                    Some(log) => (mapper::slice_log(&log, offset).to_string(), log.len()),
+                    None => (String::new(), offset),
+                };
+
+                Ok::<_, PluginError>(JobLog {
+                    id: job.id.to_string(),
+                    name: job.name,
+                    status: mapper::map_status(&job.status, job.conclusion.as_deref()),
+                    started_at: job.started_at,
+                    completed_at: job.completed_at,
+                    steps: job.steps.iter().map(mapper::map_step).collect(),
+                    content,
+                    offset,
+                    next_offset,
+                    is_complete,
+                })
+            }
+        });
+
+        let jobs = join_all(log_futures)
+            .await
+            .into_iter()
+            .collect::<PluginResult<Vec<_>>>()?;
+
+        Ok(RunLogs {
+            pipeline_id: pipeline_id.to_string(),
+            run_number,
+            status: mapper::map_status(run.status.as_str(), run.conclusion.as_deref()),
+            is_complete: run.status == "completed",
+            jobs,
+        })
+    }
+
+    // Costs one jobs request per finished run, so only runs that actually
+    // executed are looked up.
+    async fn list_branch_statuses(&self, pipeline_id: &str) -> PluginResult<Vec<BranchStatus>> {
+        let parts: Vec<&str> = pipeline_id.split("__").collect();
+        if parts.len() != 5 {
+            return Err(PluginError::InvalidConfig(format!(
+                "Invalid pipeline ID format: {} (expected 5 parts, got {})",
+                pipeline_id,
+                parts.len()
+            )));
+        }
+
+        let owner = parts[2];
+        let repo = parts[3];
+        let workflow_id_str = parts[4];
+        let workflow_id: u64 = workflow_id_str.parse().map_err(|_| {
+            PluginError::InvalidConfig(format!("Invalid workflow ID: {workflow_id_str}"))
+        })?;
+
+        let client = self.client()?;
+        let (runs, branches, pull_requests) = futures::try_join!(
+            client.fetch_run_history(owner, repo, workflow_id, 100),
+            client.fetch_branch_names(owner, repo),
+            client.fetch_open_pull_requests(owner, repo),
+        )?;
+
+        let open_branches: std::collections::HashSet<String> = branches.into_iter().collect();
+        let pull_requests: HashMap<String, PullRequestRef> = pull_requests
+            .into_iter()
+            .map(mapper::map_pull_request)
+            .collect();
+
+        Ok(latest_run_per_branch(
+            runs.into_iter()
+                .map(|run| client::run_to_pipeline_run(run, pipeline_id)),
+            Some(&open_branches),
+            &pull_requests,
+        ))
+    }
+
+    async fn fetch_run_annotations(&self, run: &PipelineRun) -> PluginResult<Vec<RunAnnotation>> {
+        let parts: Vec<&str> = run.pipeline_id.split("__").collect();
+        let (Some(owner), Some(repo)) = (parts.get(2), parts.get(3)) else {
+            return Ok(Vec::new());
+        };
+        let Some(check_suite_id) = run
+            .metadata
+            .get(client::CHECK_SUITE_ID_KEY)
+            .and_then(|id| id.as_u64())
+        else {
+            return Ok(Vec::new());
+        };
+
+        let client = self.client()?;
+        let check_runs = client
+            .fetch_check_suite_runs(owner, repo, check_suite_id)
+            .await?;
+
+        // Line annotations cost a request per check run, so they are only
+        // loaded for the ones that failed.
+        let annotated = join_all(check_runs.iter().map(|check_run| async move {
+            let failed = matches!(
+                check_run.conclusion.as_deref(),
+                Some("failure") | Some("timed_out")
+            );
+            if failed && check_run.output.annotations_count > 0 {
+                client
+                    .fetch_check_run_annotations(owner, repo, check_run.id)
+                    .await
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            }
+        }))
+        .await;
+
+        Ok(check_runs
+            .into_iter()
+            .zip(annotated)
+            .filter_map(|(check_run, annotations)| mapper::map_check_run(check_run, &annotations))
+            .collect())
+    }
+
+    async fn extract_metrics(&self, run: &PipelineRun) -> PluginResult<Vec<RunMetric>> {
+        if !matches!(
+            run.status,
+            PipelineStatus::Success | PipelineStatus::Failed | PipelineStatus::Cancelled
+        ) {
+            return Ok(Vec::new());
+        }
+
+        let parts: Vec<&str> = run.pipeline_id.split("__").collect();
+        let (Some(owner), Some(repo)) = (parts.get(2), parts.get(3)) else {
+            return Ok(Vec::new());
+        };
+        let Some(run_id) = run.metadata.get("run_id").and_then(|id| id.as_u64()) else {
+            return Ok(Vec::new());
+        };
+
+        let jobs = self.client()?.fetch_run_jobs(owner, repo, run_id).await?;
+        if jobs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![RunMetric {
+            metric_type: RunMetricType::BillableMinutes,
+            value: mapper::billable_minutes(&jobs),
+        }])
+    }
+
+    async fn rate_limit_status(&self) -> PluginResult<Option<RateLimitStatus>> {
+        self.client()?.fetch_rate_limit().await.map(Some)
+    }
+
     async fn check_permissions(&self) -> PluginResult<PermissionStatus> {
         let client = self.client()?;
         client.check_token_permissions().await
@@ -1 +1,273 @@
+use std::collections::HashMap;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
 pub(crate) use octocrab::models::workflows::Run;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct WorkflowsResponse {
+    pub workflows: Vec<Workflow>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Workflow {
+    pub id: u64,
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RunsResponse {
+    pub workflow_runs: Vec<WorkflowRun>,
+}
+
+// octocrab's `Run` leaves out `pull_requests` and `actor`, so runs are read
+// with those two fields alongside it.
+#[derive(Debug, Deserialize)]
+pub(crate) struct WorkflowRun {
+    #[serde(flatten)]
+    pub run: Run,
+    #[serde(default)]
+    pub pull_requests: Vec<RunPullRequest>,
+    pub actor: Option<RunActor>,
+}
+
+impl std::ops::Deref for WorkflowRun {
+    type Target = Run;
+
+    fn deref(&self) -> &Run {
+        &self.run
+    }
+}
+
+// Workflow runs only carry the PR number; the title needs a separate call.
+#[derive(Debug, Deserialize)]
+pub(crate) struct RunPullRequest {
+    pub number: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RunActor {
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RunCountResponse {
+    pub total_count: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct JobsResponse {
+    pub jobs: Vec<Job>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Job {
+    pub id: u64,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub steps: Vec<JobStep>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct JobStep {
+    pub number: i64,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CheckRunsResponse {
+    pub check_runs: Vec<CheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CheckRun {
+    pub id: u64,
+    pub name: String,
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+    pub output: CheckRunOutput,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CheckRunOutput {
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub annotations_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CheckAnnotation {
+    pub path: String,
+    pub start_line: Option<u64>,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CompareResponse {
+    #[serde(default)]
+    pub commits: Vec<CompareCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CompareCommit {
+    pub sha: String,
+    pub html_url: Option<String>,
+    pub commit: CommitDetail,
+    pub author: Option<CommitUser>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CommitDetail {
+    pub message: String,
+    pub author: Option<CommitSignature>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CommitSignature {
+    pub name: Option<String>,
+    pub date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CommitUser {
+    pub login: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct InstallationToken {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default)]
+    pub permissions: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Installation {
+    pub account: Account,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Account {
+    pub login: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct InstallationRepositories {
+    pub total_count: u64,
+    #[serde(default)]
+    pub repositories: Vec<InstallationRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct InstallationRepository {
+    pub full_name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Branch {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PullRequest {
+    pub number: i64,
+    pub title: String,
+    pub html_url: String,
+    pub head: PullRequestHead,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PullRequestHead {
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RunnersResponse {
+    #[serde(default)]
+    pub runners: Vec<Runner>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Runner {
+    pub id: u64,
+    pub name: String,
+    pub os: String,
+    pub status: String,
+    pub busy: bool,
+    #[serde(default)]
+    pub labels: Vec<RunnerLabel>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RunnerLabel {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RateLimitResponse {
+    pub resources: RateLimitResources,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RateLimitResources {
+    pub core: RateLimitWindow,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RateLimitWindow {
+    pub limit: u64,
+    pub remaining: u64,
+    // Unix timestamp, in seconds.
+    pub reset: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PendingDeployment {
+    pub environment: DeploymentEnvironment,
+    // Minutes, counted from `wait_timer_started_at`.
+    #[serde(default)]
+    pub wait_timer: i64,
+    pub wait_timer_started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub current_user_can_approve: bool,
+    #[serde(default)]
+    pub reviewers: Vec<DeploymentReviewer>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct DeploymentEnvironment {
+    pub name: String,
+    pub html_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct DeploymentReviewer {
+    #[serde(rename = "type")]
+    pub reviewer_type: String,
+    pub reviewer: ReviewerAccount,
+}
+
+// A user has a `login`, a team a `slug`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ReviewerAccount {
+    pub login: Option<String>,
+    pub slug: Option<String>,
+    pub name: Option<String>,
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use pipedash_plugin_api::Plugin;
+use pipedash_plugin_github::GitHubPlugin;
+use pipedash_plugin_testing::{
+    install_crypto_provider,
+    Cassette,
+    FixtureServer,
+    RecordedRequest,
+    RecordedResponse,
+};
+use serde_json::json;
+
+const WORKFLOWS: &str = "/api/v3/repos/acme/api/actions/workflows?per_page=100";
+const RUNS: &str = "/api/v3/repos/acme/api/actions/workflows/7/runs?per_page=1";
+
+fn plugin(server: &FixtureServer) -> GitHubPlugin {
+    install_crypto_provider();
+    let mut plugin = GitHubPlugin::new();
+    let config = HashMap::from([
+        ("token".to_string(), "ghp_test".to_string()),
+        ("base_url".to_string(), server.base_url().to_string()),
+        ("selected_items".to_string(), "acme/api".to_string()),
+    ]);
+    plugin.initialize(1, config, None).unwrap();
+    plugin
+}
+
+#[tokio::test]
+async fn test_unchanged_workflows_are_not_modified() {
+    let server = FixtureServer::replay(
+        Cassette::new()
+            // No GraphQL, so every repository goes through REST.
+            .with(
+                RecordedRequest::new("POST", "/api/graphql"),
+                RecordedResponse::text(404, ""),
+            )
+            .with(
+                RecordedRequest::new("GET", WORKFLOWS),
+                RecordedResponse::json(
+                    200,
+                    json!({
+                        "total_count": 1,
+                        "workflows": [{"id": 7, "name": "CI", "path": ".github/workflows/ci.yml"}],
+                    }),
+                )
+                .with_header("etag", "\"workflows\""),
+            )
+            .with(
+                RecordedRequest::new("GET", WORKFLOWS),
+                RecordedResponse::text(304, ""),
+            )
+            .with(
+                RecordedRequest::new("GET", RUNS),
+                RecordedResponse::json(200, json!({"total_count": 0, "workflow_runs": []}))
+                    .with_header("etag", "\"runs\""),
+            )
+            .with(
+                RecordedRequest::new("GET", RUNS),
+                RecordedResponse::text(304, ""),
+            ),
+    )
+    .unwrap();
+    let plugin = plugin(&server);
+
+    let first = plugin
+        .fetch_pipelines_if_modified()
+        .await
+        .unwrap()
+        .expect("the first fetch has nothing to compare against");
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].id, "github__1__acme__api__7");
+    assert_eq!(first[0].name, "CI");
+
+    // Both requests come back 304 now.
+    assert!(plugin
+        .fetch_pipelines_if_modified()
+        .await
+        .unwrap()
+        .is_none());
+
+    let pipelines = plugin.fetch_pipelines().await.unwrap();
+    assert_eq!(pipelines.len(), 1);
+    assert_eq!(pipelines[0].id, first[0].id);
+    assert!(server.unmatched().is_empty());
+}
@@ -1,6 +1,9 @@
 use std::sync::OnceLock;
 
+use pipedash_plugin_api::http::DEFAULT_MAX_RESPONSE_MB;
 use pipedash_plugin_api::{
+    read_json,
+    read_text,
     PaginatedResponse,
     PaginationParams,
     PluginError,
@@ -10,11 +13,17 @@ use pipedash_plugin_api::{
 use reqwest::StatusCode;
 
 use crate::types::{
+    Branch,
+    CompareResult,
+    MergeRequest,
     Pipeline,
+    PipelineRef,
     PipelineVariable,
     Project,
+    Runner,
     TriggerPipelineRequest,
     User,
+    Version,
 };
 
 pub struct GitLabClient {
@@ -22,7 +31,9 @@ pub struct GitLabClient {
     api_url: String,
     token: String,
     retry_policy: RetryPolicy,
+    max_response_bytes: usize,
     user_cache: OnceLock<User>,
+    legacy_pipelines: bool,
 }
 
 impl GitLabClient {
@@ -34,10 +45,42 @@ impl GitLabClient {
             api_url: api_url.trim_end_matches('/').to_string(),
             token,
             retry_policy: RetryPolicy::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_MB * 1024 * 1024,
             user_cache: OnceLock::new(),
+            legacy_pipelines: false,
         }
     }
 
+    // For servers older than `config::LEGACY_PIPELINES_BEFORE`.
+    pub fn with_legacy_pipelines(mut self, legacy_pipelines: bool) -> Self {
+        self.legacy_pipelines = legacy_pipelines;
+        self
+    }
+
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    pub async fn get_version(&self) -> PluginResult<Version> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("{}/version", self.api_url);
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .header("PRIVATE-TOKEN", &self.token)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!("Failed to get GitLab version: {}", e))
+                    })?;
+
+                self.handle_response(response).await
+            })
+            .await
+    }
+
     pub async fn get_user(&self) -> PluginResult<User> {
         if let Some(user) = self.user_cache.get() {
             return Ok(user.clone());
@@ -229,6 +272,12 @@ impl GitLabClient {
     pub async fn get_project_pipelines(
         &self, project_id: i64, per_page: usize,
     ) -> PluginResult<Vec<Pipeline>> {
+        if self.legacy_pipelines {
+            return self
+                .get_project_pipelines_legacy(project_id, per_page)
+                .await;
+        }
+
         self.retry_policy
             .retry(|| async {
                 let url = format!(
@@ -250,6 +299,39 @@ impl GitLabClient {
             .await
     }
 
+    // Older servers list pipelines newest first by id with only a few fields
+    // each, so the details are fetched one by one.
+    async fn get_project_pipelines_legacy(
+        &self, project_id: i64, per_page: usize,
+    ) -> PluginResult<Vec<Pipeline>> {
+        let refs: Vec<PipelineRef> = self
+            .retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "{}/projects/{}/pipelines?per_page={}",
+                    self.api_url, project_id, per_page
+                );
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .header("PRIVATE-TOKEN", &self.token)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!("Failed to get project pipelines: {}", e))
+                    })?;
+
+                self.handle_response(response).await
+            })
+            .await?;
+
+        futures::future::try_join_all(
+            refs.iter()
+                .map(|pipeline| self.get_pipeline(project_id, pipeline.id)),
+        )
+        .await
+    }
+
     pub async fn get_pipeline(&self, project_id: i64, pipeline_id: i64) -> PluginResult<Pipeline> {
         self.retry_policy
             .retry(|| async {
@@ -299,6 +381,54 @@ impl GitLabClient {
             .await
     }
 
+    pub async fn retry_pipeline(
+        &self, project_id: i64, pipeline_id: i64,
+    ) -> PluginResult<Pipeline> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "{}/projects/{}/pipelines/{}/retry",
+                    self.api_url, project_id, pipeline_id
+                );
+                let response = self
+                    .http_client
+                    .post(&url)
+                    .header("PRIVATE-TOKEN", &self.token)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!("Failed to retry pipeline: {}", e))
+                    })?;
+
+                self.handle_response(response).await
+            })
+            .await
+    }
+
+    pub async fn compare_commits(
+        &self, project_id: i64, from: &str, to: &str,
+    ) -> PluginResult<CompareResult> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "{}/projects/{}/repository/compare?from={}&to={}",
+                    self.api_url, project_id, from, to
+                );
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .header("PRIVATE-TOKEN", &self.token)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!("Failed to compare commits: {}", e))
+                    })?;
+
+                self.handle_response(response).await
+            })
+            .await
+    }
+
     pub async fn cancel_pipeline(
         &self, project_id: i64, pipeline_id: i64,
     ) -> PluginResult<Pipeline> {
@@ -323,6 +453,73 @@ impl GitLabClient {
             .await
     }
 
+    pub async fn list_branches(&self, project_id: i64) -> PluginResult<Vec<Branch>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "{}/projects/{}/repository/branches?per_page=100",
+                    self.api_url, project_id
+                );
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .header("PRIVATE-TOKEN", &self.token)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!("Failed to list branches: {}", e))
+                    })?;
+
+                self.handle_response(response).await
+            })
+            .await
+    }
+
+    pub async fn list_open_merge_requests(
+        &self, project_id: i64,
+    ) -> PluginResult<Vec<MergeRequest>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "{}/projects/{}/merge_requests?state=opened&per_page=100",
+                    self.api_url, project_id
+                );
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .header("PRIVATE-TOKEN", &self.token)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!("Failed to list merge requests: {}", e))
+                    })?;
+
+                self.handle_response(response).await
+            })
+            .await
+    }
+
+    // Runners the token's user can manage, including project and group
+    // runners. Instance-wide runners need an admin token.
+    pub async fn list_runners(&self) -> PluginResult<Vec<Runner>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("{}/runners?per_page=100", self.api_url);
+                let response = self
+                    .http_client
+                    .get(&url)
+                    .header("PRIVATE-TOKEN", &self.token)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        PluginError::NetworkError(format!("Failed to list runners: {}", e))
+                    })?;
+
+                self.handle_response(response).await
+            })
+            .await
+    }
+
     async fn handle_response<T: serde::de::DeserializeOwned>(
         &self, response: reqwest::Response,
     ) -> PluginResult<T> {
@@ -338,10 +535,15 @@ impl GitLabClient {
         );
 
         if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
-            let error_text = response
-                .text()
+            let error_text = read_text(response, self.max_response_bytes)
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
+            if status == StatusCode::FORBIDDEN {
+                return Err(PluginError::PermissionDenied(format!(
+                    "Permission denied for {}: {}",
+                    url, error_text
+                )));
+            }
             return Err(PluginError::AuthenticationFailed(format!(
                 "Authentication failed for {}: {}",
                 url, error_text
@@ -355,9 +557,12 @@ impl GitLabClient {
             )));
         }
 
+        if let Some(error) = pipedash_plugin_api::rate_limit_error(&response) {
+            return Err(error);
+        }
+
         if !status.is_success() {
-            let error_text = response
-                .text()
+            let error_text = read_text(response, self.max_response_bytes)
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
             return Err(PluginError::ApiError(format!(
@@ -366,12 +571,17 @@ impl GitLabClient {
             )));
         }
 
-        response.json::<T>().await.map_err(|e| {
-            tracing::error!(url = %url, error = %e, "Failed to deserialize GitLab response");
-            PluginError::ApiError(format!(
-                "Failed to parse GitLab API response from {}: {}. This may indicate a network issue or incompatible response format.",
-                url, e
-            ))
-        })
+        read_json(response, self.max_response_bytes)
+            .await
+            .map_err(|e| match e {
+                PluginError::SerializationError(e) => {
+                    tracing::error!(url = %url, error = %e, "Failed to deserialize GitLab response");
+                    PluginError::ApiError(format!(
+                        "Failed to parse GitLab API response from {}: {}. This may indicate a network issue or incompatible response format.",
+                        url, e
+                    ))
+                }
+                e => e,
+            })
     }
 }
@@ -5,6 +5,12 @@ use pipedash_plugin_api::{
     PluginResult,
 };
 
+// Oldest GitLab release the plugin is known to work with.
+pub(crate) const MINIMUM_VERSION: (u64, u64) = (11, 0);
+// Releases before this return bare pipeline list entries (no timestamps or
+// URLs) and can't sort them by `updated_at`.
+pub(crate) const LEGACY_PIPELINES_BEFORE: (u64, u64) = (12, 0);
+
 pub(crate) fn parse_pipeline_id(id: &str) -> PluginResult<(i64, i64)> {
     let parts: Vec<&str> = id.split("__").collect();
 
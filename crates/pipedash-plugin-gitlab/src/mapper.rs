@@ -3,9 +3,12 @@ use std::collections::HashMap;
 use chrono::Utc;
 use pipedash_plugin_api::{
     AvailablePipeline,
+    BuildAgent,
+    CommitInfo,
     Pipeline,
     PipelineRun,
     PipelineStatus,
+    PullRequestRef,
 };
 
 use crate::types;
@@ -14,7 +17,11 @@ pub(crate) fn map_status(gitlab_status: &str) -> PipelineStatus {
     match gitlab_status {
         "success" => PipelineStatus::Success,
         "failed" => PipelineStatus::Failed,
-        "running" | "pending" => PipelineStatus::Running,
+        "running" => PipelineStatus::Running,
+        "created" | "pending" | "waiting_for_resource" | "preparing" | "scheduled" => {
+            PipelineStatus::Queued
+        }
+        "manual" => PipelineStatus::Waiting,
         "canceled" => PipelineStatus::Cancelled,
         "skipped" => PipelineStatus::Skipped,
         _ => PipelineStatus::Pending,
@@ -60,6 +67,29 @@ pub(crate) fn map_pipeline(
     }
 }
 
+// Merge request pipelines run on `refs/merge-requests/<iid>/head` (or `/merge`
+// for merged results), which is the only MR reference the pipeline list has.
+fn merge_request_from_ref(pipeline: &types::Pipeline) -> Option<PullRequestRef> {
+    let iid = pipeline
+        .ref_name
+        .strip_prefix("refs/merge-requests/")?
+        .split('/')
+        .next()?
+        .parse::<i64>()
+        .ok()?;
+
+    let url = pipeline
+        .web_url
+        .split_once("/-/pipelines/")
+        .map(|(project_url, _)| format!("{}/-/merge_requests/{}", project_url, iid));
+
+    Some(PullRequestRef {
+        number: iid,
+        title: None,
+        url,
+    })
+}
+
 pub(crate) fn map_pipeline_run(
     pipeline: &types::Pipeline, project_id: i64, provider_id: i64, namespace: Option<&str>,
 ) -> PipelineRun {
@@ -92,8 +122,22 @@ pub(crate) fn map_pipeline_run(
         commit_message: None,
         branch: Some(pipeline.ref_name.clone()),
         actor: pipeline.user.as_ref().map(|u| u.username.clone()),
+        commit_author: None,
+        author_avatar_url: pipeline.user.as_ref().and_then(|u| u.avatar_url.clone()),
+        pull_request: merge_request_from_ref(pipeline),
         inputs: None,
         metadata,
+        annotations: Vec::new(),
+    }
+}
+
+pub(crate) fn map_commit(commit: types::Commit) -> CommitInfo {
+    CommitInfo {
+        sha: commit.id,
+        message: commit.message,
+        author: commit.author_name,
+        committed_at: commit.committed_date,
+        url: commit.web_url,
     }
 }
 
@@ -115,3 +159,38 @@ pub(crate) fn map_available_pipeline(project: &types::Project) -> AvailablePipel
         repository,
     }
 }
+
+pub(crate) fn map_runner(runner: types::Runner) -> BuildAgent {
+    let status = match (runner.status.as_deref(), runner.paused) {
+        (Some("online"), true) => "paused",
+        (Some("online"), false) => "idle",
+        _ => "offline",
+    };
+
+    let mut metadata = HashMap::new();
+    if let Some(runner_type) = runner.runner_type {
+        metadata.insert("runner_type".to_string(), runner_type);
+    }
+    if let Some(ip_address) = &runner.ip_address {
+        metadata.insert("ip_address".to_string(), ip_address.clone());
+    }
+
+    let name = runner
+        .description
+        .filter(|d| !d.is_empty())
+        .or(runner.name.clone())
+        .unwrap_or_else(|| format!("runner-{}", runner.id));
+
+    BuildAgent {
+        id: runner.id.to_string(),
+        hostname: runner
+            .ip_address
+            .or(runner.name)
+            .unwrap_or_else(|| name.clone()),
+        name,
+        status: status.to_string(),
+        job_id: None,
+        last_seen: Utc::now(),
+        metadata,
+    }
+}
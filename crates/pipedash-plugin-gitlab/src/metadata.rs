@@ -45,6 +45,7 @@ fn create_config_schema() -> ConfigSchema {
             validation_regex: None,
             validation_message: None,
         })
+        .with_network_fields()
 }
 
 fn create_capabilities() -> PluginCapabilities {
@@ -52,9 +53,13 @@ fn create_capabilities() -> PluginCapabilities {
         pipelines: true,
         pipeline_runs: true,
         trigger: true,
-        agents: false,
-        artifacts: false,
-        queues: false,
-        custom_tables: false,
+        agents: true,
+        retry_failed_jobs: true,
+        branch_statuses: true,
+        cancel: true,
+        workflow_parameters: true,
+        repository_discovery: true,
+        pagination: PaginationStyle::Server,
+        ..PluginCapabilities::default()
     }
 }
@@ -18,6 +18,7 @@ pub struct GitLabPlugin {
     client: Option<client::GitLabClient>,
     provider_id: Option<i64>,
     config: HashMap<String, String>,
+    server_version: Option<ServerVersion>,
 }
 
 impl Default for GitLabPlugin {
@@ -33,6 +34,7 @@ impl GitLabPlugin {
             client: None,
             provider_id: None,
             config: HashMap::new(),
+            server_version: None,
         }
     }
 
@@ -115,6 +117,10 @@ impl Plugin for GitLabPlugin {
         "gitlab"
     }
 
+    fn set_server_version(&mut self, version: &ServerVersion) {
+        self.server_version = Some(version.clone());
+    }
+
     fn initialize(
         &mut self, provider_id: i64, config: HashMap<String, String>,
         http_client: Option<std::sync::Arc<reqwest::Client>>,
@@ -140,19 +146,50 @@ impl Plugin for GitLabPlugin {
             )
         });
 
-        self.client = Some(client::GitLabClient::new(client, api_url, token));
+        let (major, minor) = config::LEGACY_PIPELINES_BEFORE;
+        let legacy_pipelines = self
+            .server_version
+            .as_ref()
+            .is_some_and(|version| version.is_older_than(major, minor));
+
+        self.client = Some(
+            client::GitLabClient::new(client, api_url, token)
+                .with_legacy_pipelines(legacy_pipelines)
+                .with_max_response_bytes(max_response_bytes(&config)),
+        );
         self.provider_id = Some(provider_id);
         self.config = config;
 
         Ok(())
     }
 
+    fn api_endpoint(&self) -> Option<String> {
+        Some(config::build_api_url(&config::get_base_url(&self.config)))
+    }
+
     async fn validate_credentials(&self) -> PluginResult<bool> {
         let client = self.client()?;
         client.get_user().await?;
         Ok(true)
     }
 
+    async fn detect_server_version(&self) -> PluginResult<Option<ServerVersion>> {
+        let version = self.client()?.get_version().await?;
+        let server_version =
+            ServerVersion::new("GitLab", &version.version, config::MINIMUM_VERSION);
+
+        let (major, minor) = config::LEGACY_PIPELINES_BEFORE;
+        Ok(Some(if server_version.is_older_than(major, minor) {
+            server_version.with_warning(format!(
+                "GitLab {} lists pipelines without details, so each one is fetched separately \
+                 and refreshes are slower. Upgrade to {}.{} or later for faster refreshes.",
+                version.version, major, minor
+            ))
+        } else {
+            server_version
+        }))
+    }
+
     async fn fetch_available_pipelines(
         &self, params: Option<PaginationParams>,
     ) -> PluginResult<PaginatedResponse<AvailablePipeline>> {
@@ -247,6 +284,57 @@ impl Plugin for GitLabPlugin {
             .collect())
     }
 
+    // Merge request pipelines run on `refs/merge-requests/<iid>/head`, so they
+    // are filed under the merge request's source branch.
+    async fn list_branch_statuses(&self, pipeline_id: &str) -> PluginResult<Vec<BranchStatus>> {
+        let (provider_id, project_id) = config::parse_pipeline_id(pipeline_id)?;
+        let client = self.client()?;
+
+        let (pipeline_list, branches, merge_requests) = futures::try_join!(
+            client.get_project_pipelines(project_id, 100),
+            client.list_branches(project_id),
+            client.list_open_merge_requests(project_id),
+        )?;
+
+        let source_branches: HashMap<i64, String> = merge_requests
+            .iter()
+            .map(|mr| (mr.iid, mr.source_branch.clone()))
+            .collect();
+        let pull_requests: HashMap<String, PullRequestRef> = merge_requests
+            .into_iter()
+            .map(|mr| {
+                (
+                    mr.source_branch,
+                    PullRequestRef {
+                        number: mr.iid,
+                        title: Some(mr.title),
+                        url: Some(mr.web_url),
+                    },
+                )
+            })
+            .collect();
+        let open_branches: std::collections::HashSet<String> =
+            branches.into_iter().map(|b| b.name).collect();
+
+        let runs = pipeline_list.iter().map(|p| {
+            let mut run = mapper::map_pipeline_run(p, project_id, provider_id, None);
+            if let Some(source_branch) = run
+                .pull_request
+                .as_ref()
+                .and_then(|pr| source_branches.get(&pr.number))
+            {
+                run.branch = Some(source_branch.clone());
+            }
+            run
+        });
+
+        Ok(latest_run_per_branch(
+            runs,
+            Some(&open_branches),
+            &pull_requests,
+        ))
+    }
+
     async fn fetch_run_details(
         &self, pipeline_id: &str, run_number: i64,
     ) -> PluginResult<PipelineRun> {
@@ -318,6 +406,38 @@ impl Plugin for GitLabPlugin {
         Ok(pipeline.web_url)
     }
 
+    async fn retry_run(
+        &self, pipeline_id: &str, run_number: i64, failed_only: bool,
+    ) -> PluginResult<String> {
+        let (_, project_id) = config::parse_pipeline_id(pipeline_id)?;
+        let client = self.client()?;
+
+        // GitLab's retry endpoint only re-runs failed and cancelled jobs; a
+        // full re-run is a new pipeline on the same ref.
+        let pipeline = if failed_only {
+            client.retry_pipeline(project_id, run_number).await?
+        } else {
+            let original = client.get_pipeline(project_id, run_number).await?;
+            client
+                .trigger_pipeline(project_id, original.ref_name, None)
+                .await?
+        };
+
+        Ok(pipeline.web_url)
+    }
+
+    async fn fetch_commits_between(
+        &self, pipeline_id: &str, base_sha: &str, head_sha: &str,
+    ) -> PluginResult<Vec<CommitInfo>> {
+        let (_, project_id) = config::parse_pipeline_id(pipeline_id)?;
+        let client = self.client()?;
+        let result = client
+            .compare_commits(project_id, base_sha, head_sha)
+            .await?;
+
+        Ok(result.commits.into_iter().map(mapper::map_commit).collect())
+    }
+
     async fn cancel_run(&self, pipeline_id: &str, run_number: i64) -> PluginResult<()> {
         let (_, project_id) = config::parse_pipeline_id(pipeline_id)?;
         let client = self.client()?;
@@ -326,9 +446,9 @@ impl Plugin for GitLabPlugin {
     }
 
     async fn fetch_agents(&self) -> PluginResult<Vec<BuildAgent>> {
-        Err(PluginError::NotSupported(
-            "GitLab runners monitoring not implemented".to_string(),
-        ))
+        let client = self.client()?;
+        let runners = client.list_runners().await?;
+        Ok(runners.into_iter().map(mapper::map_runner).collect())
     }
 
     async fn fetch_artifacts(&self, _run_id: &str) -> PluginResult<Vec<BuildArtifact>> {
@@ -14,6 +14,13 @@ pub struct User {
     pub name: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Version {
+    pub version: String,
+    #[serde(default)]
+    pub revision: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
     pub id: i64,
@@ -43,10 +50,18 @@ pub struct Pipeline {
     pub source: Option<String>,
 }
 
+// A pipeline list entry as older GitLab releases return it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineRef {
+    pub id: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PipelineUser {
     pub username: String,
     pub name: String,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,3 +79,48 @@ pub struct PipelineVariable {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variable_type: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompareResult {
+    #[serde(default)]
+    pub commits: Vec<Commit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Commit {
+    pub id: String,
+    pub message: String,
+    pub author_name: Option<String>,
+    pub committed_date: Option<DateTime<Utc>>,
+    pub web_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Branch {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequest {
+    pub iid: i64,
+    pub title: String,
+    pub web_url: String,
+    pub source_branch: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Runner {
+    pub id: i64,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub runner_type: Option<String>,
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub status: Option<String>,
+}
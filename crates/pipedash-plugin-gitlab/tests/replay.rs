@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use pipedash_plugin_api::{
+    PaginationParams,
+    PipelineStatus,
+    Plugin,
+    PluginError,
+};
+use pipedash_plugin_gitlab::GitLabPlugin;
+use pipedash_plugin_testing::{
+    fixture_path,
+    Cassette,
+    FixtureServer,
+    RecordedRequest,
+    RecordedResponse,
+};
+use serde_json::json;
+
+fn plugin(server: &FixtureServer) -> GitLabPlugin {
+    let mut plugin = GitLabPlugin::new();
+    let config = HashMap::from([
+        ("token".to_string(), "glpat-test".to_string()),
+        ("base_url".to_string(), server.base_url().to_string()),
+    ]);
+    plugin.initialize(1, config, None).unwrap();
+    plugin
+}
+
+fn project(id: i64, name: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": name,
+        "name_with_namespace": format!("acme / {}", name),
+        "description": null,
+        "web_url": format!("{{{{base_url}}}}/acme/{}", name),
+    })
+}
+
+#[tokio::test]
+async fn test_fetch_pipelines_maps_latest_run() {
+    let server =
+        FixtureServer::replay_file(&fixture_path(env!("CARGO_MANIFEST_DIR"), "gitlab")).unwrap();
+    let pipelines = plugin(&server).fetch_pipelines().await.unwrap();
+
+    // The project whose pipelines 404 is left out rather than failing the
+    // whole provider.
+    assert_eq!(pipelines.len(), 1);
+    let pipeline = &pipelines[0];
+    assert_eq!(pipeline.id, "gitlab__1__101");
+    assert_eq!(pipeline.name, "api");
+    assert_eq!(pipeline.repository, "acme/backend/api");
+    assert_eq!(pipeline.status, PipelineStatus::Failed);
+    assert_eq!(pipeline.branch.as_deref(), Some("main"));
+    assert_eq!(pipeline.metadata.get("project_id"), Some(&json!(101)));
+    assert!(server.unmatched().is_empty());
+}
+
+#[tokio::test]
+async fn test_fetch_run_history_maps_runs() {
+    let server =
+        FixtureServer::replay_file(&fixture_path(env!("CARGO_MANIFEST_DIR"), "gitlab")).unwrap();
+    let runs = plugin(&server)
+        .fetch_run_history("gitlab__1__101", 2)
+        .await
+        .unwrap();
+
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].run_number, 5002);
+    assert_eq!(runs[0].status, PipelineStatus::Failed);
+    assert_eq!(runs[0].duration_seconds, Some(355));
+    assert_eq!(runs[0].actor.as_deref(), Some("jdoe"));
+    assert_eq!(
+        runs[0].logs_url,
+        format!("{}/acme/backend/api/-/pipelines/5002", server.base_url())
+    );
+
+    assert_eq!(runs[1].status, PipelineStatus::Running);
+    let pull_request = runs[1].pull_request.as_ref().unwrap();
+    assert_eq!(pull_request.number, 42);
+    assert_eq!(
+        pull_request.url.as_deref(),
+        Some(format!("{}/acme/backend/api/-/merge_requests/42", server.base_url()).as_str())
+    );
+    assert!(server.unmatched().is_empty());
+}
+
+#[tokio::test]
+async fn test_available_pipelines_pagination() {
+    let cassette = Cassette::new()
+        .with(
+            RecordedRequest::new("GET", "/api/v4/projects?membership=true&per_page=2&page=1"),
+            RecordedResponse::json(200, json!([project(1, "web"), project(2, "api")]))
+                .with_header("x-total", "3"),
+        )
+        .with(
+            RecordedRequest::new("GET", "/api/v4/projects?membership=true&per_page=2&page=2"),
+            RecordedResponse::json(200, json!([project(3, "docs")])).with_header("x-total", "3"),
+        );
+    let server = FixtureServer::replay(cassette).unwrap();
+    let plugin = plugin(&server);
+
+    let first = plugin
+        .fetch_available_pipelines(Some(PaginationParams {
+            page: 1,
+            page_size: 2,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(first.total_count, 3);
+    assert_eq!(first.total_pages, 2);
+    assert!(first.has_more);
+    assert_eq!(
+        first
+            .items
+            .iter()
+            .map(|p| p.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["acme/web", "acme/api"]
+    );
+
+    let second = plugin
+        .fetch_available_pipelines(Some(PaginationParams {
+            page: 2,
+            page_size: 2,
+        }))
+        .await
+        .unwrap();
+    assert!(!second.has_more);
+    assert_eq!(second.items.len(), 1);
+    assert_eq!(second.items[0].name, "docs");
+}
+
+#[tokio::test]
+async fn test_error_statuses() {
+    let cassette = Cassette::new()
+        .with(
+            RecordedRequest::new("GET", "/api/v4/user"),
+            RecordedResponse::json(401, json!({"message": "401 Unauthorized"})),
+        )
+        .with(
+            RecordedRequest::new("GET", "/api/v4/projects/5"),
+            RecordedResponse::json(403, json!({"message": "403 Forbidden"})),
+        )
+        .with(
+            RecordedRequest::new("GET", "/api/v4/projects/6"),
+            RecordedResponse::json(404, json!({"message": "404 Project Not Found"})),
+        )
+        .with(
+            RecordedRequest::new("GET", "/api/v4/projects/7"),
+            RecordedResponse::text(502, "Bad Gateway"),
+        );
+    let server = FixtureServer::replay(cassette).unwrap();
+    let plugin = plugin(&server);
+
+    assert!(matches!(
+        plugin.validate_credentials().await,
+        Err(PluginError::AuthenticationFailed(_))
+    ));
+    assert!(matches!(
+        plugin.fetch_run_history("gitlab__1__5", 5).await,
+        Err(PluginError::PermissionDenied(_))
+    ));
+    assert!(matches!(
+        plugin.fetch_run_history("gitlab__1__6", 5).await,
+        Err(PluginError::PipelineNotFound(_))
+    ));
+    assert!(matches!(
+        plugin.fetch_run_history("gitlab__1__7", 5).await,
+        Err(PluginError::ApiError(_))
+    ));
+
+    // Server errors are retried, auth errors are not.
+    let served = server.served();
+    assert_eq!(served.iter().filter(|r| r.ends_with("/user")).count(), 1);
+    assert_eq!(
+        served.iter().filter(|r| r.ends_with("/projects/7")).count(),
+        3
+    );
+}
+
+#[tokio::test]
+async fn test_legacy_server_version_shim() {
+    let pipeline = json!({
+        "id": 900,
+        "status": "success",
+        "ref": "master",
+        "sha": "0a1b2c3d",
+        "web_url": "{{base_url}}/acme/legacy/pipelines/900",
+        "created_at": "2019-05-01T10:00:00Z",
+        "updated_at": "2019-05-01T10:04:00Z",
+        "started_at": "2019-05-01T10:00:02Z",
+        "finished_at": "2019-05-01T10:04:00Z",
+        "duration": 238,
+        "user": null
+    });
+    let cassette = Cassette::new()
+        .with(
+            RecordedRequest::new("GET", "/api/v4/version"),
+            RecordedResponse::json(200, json!({"version": "11.11.8", "revision": "e0c2a7b"})),
+        )
+        .with(
+            RecordedRequest::new(
+                "GET",
+                "/api/v4/projects?membership=true&per_page=100&page=1",
+            ),
+            RecordedResponse::json(200, json!([project(3, "legacy")])),
+        )
+        .with(
+            RecordedRequest::new("GET", "/api/v4/projects/3/pipelines?per_page=1"),
+            RecordedResponse::json(
+                200,
+                json!([{"id": 900, "sha": "0a1b2c3d", "ref": "master", "status": "success"}]),
+            ),
+        )
+        .with(
+            RecordedRequest::new("GET", "/api/v4/projects/3/pipelines/900"),
+            RecordedResponse::json(200, pipeline),
+        );
+    let server = FixtureServer::replay(cassette).unwrap();
+
+    let version = plugin(&server)
+        .detect_server_version()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(version.version, "11.11.8");
+    assert!(version.supported);
+    assert!(version.warning.as_deref().unwrap().contains("12.0"));
+
+    let mut legacy = GitLabPlugin::new();
+    legacy.set_server_version(&version);
+    legacy
+        .initialize(
+            1,
+            HashMap::from([
+                ("token".to_string(), "glpat-test".to_string()),
+                ("base_url".to_string(), server.base_url().to_string()),
+            ]),
+            None,
+        )
+        .unwrap();
+
+    let pipelines = legacy.fetch_pipelines().await.unwrap();
+    assert_eq!(pipelines.len(), 1);
+    assert_eq!(pipelines[0].status, PipelineStatus::Success);
+    assert_eq!(pipelines[0].branch.as_deref(), Some("master"));
+    assert!(server.unmatched().is_empty());
+}
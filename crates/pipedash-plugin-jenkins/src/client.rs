@@ -1,8 +1,18 @@
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{
+    Duration,
+    Instant,
+};
 
 use chrono::Utc;
+use futures::stream::{
+    self,
+    StreamExt,
+};
+use pipedash_plugin_api::http::DEFAULT_MAX_RESPONSE_MB;
 use pipedash_plugin_api::{
+    read_json,
+    read_text,
     AvailablePipeline,
     Pipeline,
     PluginError,
@@ -10,18 +20,29 @@ use pipedash_plugin_api::{
     RetryPolicy,
 };
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 
 use crate::{
     config,
     mapper,
+    tree,
     types,
 };
 
+// Folder listings fetched at once while crawling.
+const MAX_CONCURRENT_FOLDER_FETCHES: usize = 8;
+// How long one discovery call crawls before returning what it has found.
+const DISCOVERY_BUDGET: Duration = Duration::from_secs(20);
+// Builds per request when fetching run history.
+const BUILD_PAGE_SIZE: usize = 100;
+
 pub(crate) struct JenkinsClient {
     http_client: std::sync::Arc<Client>,
     server_url: String,
     auth_header: String,
     pub(crate) retry_policy: RetryPolicy,
+    max_response_bytes: usize,
+    folder_tree: tokio::sync::Mutex<tree::FolderTree>,
 }
 
 impl JenkinsClient {
@@ -33,66 +54,143 @@ impl JenkinsClient {
             server_url,
             auth_header,
             retry_policy: RetryPolicy::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_MB * 1024 * 1024,
+            folder_tree: tokio::sync::Mutex::new(tree::FolderTree::default()),
         }
     }
 
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
     pub fn server_url(&self) -> &str {
         &self.server_url
     }
 
-    pub async fn discover_all_jobs(&self) -> PluginResult<Vec<types::DiscoveredJob>> {
-        let mut all_jobs = Vec::new();
-        let mut queue = vec![String::new()];
+    async fn send_json<T: DeserializeOwned>(
+        &self, request: reqwest::RequestBuilder, what: &str,
+    ) -> PluginResult<T> {
+        let response = request
+            .header(reqwest::header::AUTHORIZATION, &self.auth_header)
+            .send()
+            .await
+            .map_err(|e| PluginError::ApiError(format!("Failed to fetch {what}: {e}")))?;
 
-        while let Some(path) = queue.pop() {
-            let jobs = self.fetch_jobs_in_folder(&path).await?;
+        read_json(response, self.max_response_bytes)
+            .await
+            .map_err(|e| match e {
+                PluginError::SerializationError(e) => {
+                    PluginError::ApiError(format!("Failed to parse {what}: {e}"))
+                }
+                e => e,
+            })
+    }
 
-            for job in jobs {
-                let full_path = if path.is_empty() {
-                    job.name.clone()
-                } else {
-                    format!("{}/{}", path, job.name)
-                };
+    // Jenkins reports its version in the `X-Jenkins` header of every response.
+    pub async fn fetch_version(&self) -> PluginResult<String> {
+        let url = format!("{}/api/json?tree=mode", self.server_url);
+        let response = self
+            .http_client
+            .get(&url)
+            .header(reqwest::header::AUTHORIZATION, &self.auth_header)
+            .send()
+            .await
+            .map_err(|e| PluginError::NetworkError(format!("Failed to reach Jenkins: {e}")))?;
 
-                if job._class.contains("Folder") {
-                    queue.push(full_path);
-                } else if job._class.contains("WorkflowJob")
-                    || job._class.contains("FreeStyleProject")
-                    || job._class.contains("WorkflowMultiBranchProject")
-                {
-                    all_jobs.push(types::DiscoveredJob {
-                        name: job.name,
-                        full_path,
-                        _class: job._class,
-                    });
+        let status = response.status();
+        if !status.is_success() {
+            return Err(PluginError::ApiError(format!(
+                "Jenkins responded with {status} to a version check"
+            )));
+        }
+
+        response
+            .headers()
+            .get("x-jenkins")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                PluginError::ApiError(format!(
+                    "{} did not report a Jenkins version",
+                    self.server_url
+                ))
+            })
+    }
+
+    // Crawls folders, organization folders and multibranch projects level by
+    // level. A folder that fails to load is skipped, except the root. When the
+    // budget runs out the jobs found so far are returned and the next call
+    // carries on from the cached listings.
+    pub async fn discover_jobs(&self) -> PluginResult<tree::Discovery> {
+        let mut folder_tree = self.folder_tree.lock().await;
+        let deadline = Instant::now() + DISCOVERY_BUDGET;
+
+        loop {
+            let walk = folder_tree.walk(Instant::now());
+            if walk.pending.is_empty() {
+                return Ok(tree::Discovery {
+                    jobs: walk.jobs,
+                    complete: true,
+                });
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                tracing::info!(
+                    pending = walk.pending.len(),
+                    jobs = walk.jobs.len(),
+                    "Jenkins folder crawl paused, resuming on the next request"
+                );
+                return Ok(tree::Discovery {
+                    jobs: walk.jobs,
+                    complete: false,
+                });
+            }
+
+            let listings: Vec<_> = stream::iter(walk.pending)
+                .map(|path| async move {
+                    let result =
+                        tokio::time::timeout(remaining, self.fetch_jobs_in_folder(&path)).await;
+                    (path, result)
+                })
+                .buffer_unordered(MAX_CONCURRENT_FOLDER_FETCHES)
+                .collect()
+                .await;
+
+            for (path, result) in listings {
+                match result {
+                    Ok(Ok(items)) => folder_tree.insert(path, items),
+                    Ok(Err(e)) if path.is_empty() => return Err(e),
+                    Ok(Err(e)) => {
+                        tracing::warn!(folder = %path, error = %e, "Skipping Jenkins folder that failed to load");
+                        folder_tree.insert(path, Vec::new());
+                    }
+                    // Out of budget; the folder stays pending.
+                    Err(_) => {}
                 }
             }
         }
-
-        Ok(all_jobs)
     }
 
-    async fn fetch_jobs_in_folder(&self, folder_path: &str) -> PluginResult<Vec<types::JobItem>> {
+    pub async fn fetch_jobs_in_folder(
+        &self, folder_path: &str,
+    ) -> PluginResult<Vec<types::JobItem>> {
         let url = if folder_path.is_empty() {
-            format!("{}/api/json?tree=jobs[name,url,_class]", self.server_url)
+            format!(
+                "{}/api/json?tree=jobs[name,url,_class,displayName]",
+                self.server_url
+            )
         } else {
             let encoded_path = config::encode_job_name(folder_path);
             format!(
-                "{}/job/{}/api/json?tree=jobs[name,url,_class]",
+                "{}/job/{}/api/json?tree=jobs[name,url,_class,displayName]",
                 self.server_url, encoded_path
             )
         };
 
-        let response: types::JobsResponse = self
-            .http_client
-            .get(&url)
-            .header(reqwest::header::AUTHORIZATION, &self.auth_header)
-            .send()
-            .await
-            .map_err(|e| PluginError::ApiError(format!("Failed to fetch jobs: {e}")))?
-            .json()
-            .await
-            .map_err(|e| PluginError::ApiError(format!("Failed to parse jobs: {e}")))?;
+        let response: types::JobsResponse =
+            self.send_json(self.http_client.get(&url), "jobs").await?;
 
         Ok(response.jobs)
     }
@@ -103,27 +201,15 @@ impl JenkinsClient {
             .retry(|| async {
                 let encoded_path = config::encode_job_name(&job_path);
                 let url = format!(
-                    "{}/job/{}/api/json?tree=name,lastBuild[number]",
+                    "{}/job/{}/api/json?tree=name,displayName,property[_class],lastBuild[number]",
                     self.server_url, encoded_path
                 );
 
-                let job: types::Job = self
-                    .http_client
-                    .get(&url)
-                    .header(reqwest::header::AUTHORIZATION, &self.auth_header)
-                    .timeout(Duration::from_secs(10))
-                    .send()
-                    .await
-                    .map_err(|e| {
-                        PluginError::ApiError(format!("Failed to fetch job {job_path}: {e}"))
-                    })?
-                    .json()
-                    .await
-                    .map_err(|e| {
-                        PluginError::ApiError(format!("Failed to parse job {job_path}: {e}"))
-                    })?;
-
-                Ok(job)
+                self.send_json(
+                    self.http_client.get(&url).timeout(Duration::from_secs(10)),
+                    &format!("job {job_path}"),
+                )
+                .await
             })
             .await
     }
@@ -135,28 +221,18 @@ impl JenkinsClient {
         self.retry_policy.retry(|| async {
             let encoded_path = config::encode_job_name(&job_path);
             let url = format!(
-                "{}/job/{}/{}/api/json?tree=number,result,building,timestamp,duration,url,fullDisplayName,actions[_class,causes[userName,shortDescription],lastBuiltRevision[SHA1,branch[SHA1,name]],parameters[name,value]]",
+                "{}/job/{}/{}/api/json?tree=number,result,building,timestamp,duration,url,fullDisplayName,actions[_class,causes[userName,shortDescription],lastBuiltRevision[SHA1,branch[SHA1,name]],parameters[name,value]],changeSets[items[msg,author[fullName]]]",
                 self.server_url, encoded_path, build_number
             );
 
             tracing::debug!(url = %url, "Fetching Jenkins build details");
 
-            let response = self
-                .http_client
-                .get(&url)
-                .header(reqwest::header::AUTHORIZATION, &self.auth_header)
-                .timeout(Duration::from_secs(10))
-                .send()
-                .await
-                .map_err(|e| PluginError::ApiError(format!("Failed to fetch build: {e}")))?;
-
-            let response_text = response.text().await
-                .map_err(|e| PluginError::ApiError(format!("Failed to read response: {e}")))?;
-
-            tracing::trace!(response = %response_text, "Jenkins raw response");
-
-            let build: types::Build = serde_json::from_str(&response_text)
-                .map_err(|e| PluginError::ApiError(format!("Failed to parse build: {e}")))?;
+            let build: types::Build = self
+                .send_json(
+                    self.http_client.get(&url).timeout(Duration::from_secs(10)),
+                    "build",
+                )
+                .await?;
 
             tracing::debug!(action_count = build.actions.len(), "Parsed Jenkins build");
 
@@ -165,27 +241,32 @@ impl JenkinsClient {
         .await
     }
 
+    // Fetched a page at a time: every build carries its actions and change
+    // sets, so one request for a long history can run to hundreds of MB.
     pub async fn fetch_build_history(
         &self, job_path: &str, limit: usize,
     ) -> PluginResult<Vec<types::Build>> {
         let encoded_path = config::encode_job_name(job_path);
-        let url = format!(
-            "{}/job/{}/api/json?tree=builds[number,url,result,building,timestamp,duration,actions[_class,causes[userName,shortDescription],lastBuiltRevision[SHA1,branch[SHA1,name]],parameters[name,value]]]{{0,{limit}}}",
-            self.server_url, encoded_path
-        );
+        let mut builds = Vec::with_capacity(limit.min(BUILD_PAGE_SIZE));
 
-        let response: types::JobBuildsResponse = self
-            .http_client
-            .get(&url)
-            .header(reqwest::header::AUTHORIZATION, &self.auth_header)
-            .send()
-            .await
-            .map_err(|e| PluginError::ApiError(format!("Failed to fetch builds: {e}")))?
-            .json()
-            .await
-            .map_err(|e| PluginError::ApiError(format!("Failed to parse builds: {e}")))?;
+        while builds.len() < limit {
+            let start = builds.len();
+            let end = limit.min(start + BUILD_PAGE_SIZE);
+            let url = format!(
+                "{}/job/{}/api/json?tree=builds[number,url,result,building,timestamp,duration,actions[_class,causes[userName,shortDescription],lastBuiltRevision[SHA1,branch[SHA1,name]],parameters[name,value]],changeSets[items[msg,author[fullName]]]]{{{start},{end}}}",
+                self.server_url, encoded_path
+            );
 
-        Ok(response.builds)
+            let page: types::JobBuildsResponse =
+                self.send_json(self.http_client.get(&url), "builds").await?;
+            let complete = page.builds.len() < end - start;
+            builds.extend(page.builds);
+            if complete {
+                break;
+            }
+        }
+
+        Ok(builds)
     }
 
     pub async fn fetch_job_parameters(
@@ -200,19 +281,11 @@ impl JenkinsClient {
 
             tracing::debug!(url = %url, "Fetching Jenkins job parameters");
 
-            let response: types::JobWithParameters = self
-                .http_client
-                .get(&url)
-                .header(reqwest::header::AUTHORIZATION, &self.auth_header)
-                .timeout(Duration::from_secs(30))
-                .send()
-                .await
-                .map_err(|e| PluginError::ApiError(format!("Failed to fetch job parameters: {e}")))?
-                .json()
-                .await
-                .map_err(|e| PluginError::ApiError(format!("Failed to parse job parameters: {e}")))?;
-
-            Ok(response)
+            self.send_json(
+                self.http_client.get(&url).timeout(Duration::from_secs(30)),
+                "job parameters",
+            )
+            .await
         })
         .await
     }
@@ -258,8 +331,7 @@ impl JenkinsClient {
                 tracing::info!("Jenkins build triggered successfully");
                 Ok(())
             } else {
-                let error_text = response
-                    .text()
+                let error_text = read_text(response, self.max_response_bytes)
                     .await
                     .unwrap_or_else(|_| "Unknown error".to_string());
 
@@ -333,11 +405,32 @@ impl JenkinsClient {
             (pipedash_plugin_api::PipelineStatus::Pending, None)
         };
 
-        let (org, repo) = config::split_job_path(&job_path);
-        let repository_field = if job_path.contains('/') {
-            job_path.clone()
-        } else {
-            format!("{org}/{repo}")
+        // Branch jobs are named after the branch and live under their
+        // multibranch project, which stands in for the repository.
+        let branch = job
+            .property
+            .iter()
+            .any(|p| {
+                p._class
+                    .as_deref()
+                    .is_some_and(|c| c.contains("BranchJobProperty"))
+            })
+            .then(|| job.display_name.clone().unwrap_or(job.name.clone()));
+
+        let (name, repository_field) = match (&branch, job_path.rsplit_once('/')) {
+            (Some(branch), Some((parent_path, _))) => {
+                let project = parent_path.rsplit('/').next().unwrap_or(parent_path);
+                (format!("{project} » {branch}"), parent_path.to_string())
+            }
+            _ => {
+                let (org, repo) = config::split_job_path(&job_path);
+                let repository_field = if job_path.contains('/') {
+                    job_path.clone()
+                } else {
+                    format!("{org}/{repo}")
+                };
+                (job.name, repository_field)
+            }
         };
 
         tracing::debug!(elapsed = ?pipeline_start.elapsed(), "Total Jenkins pipeline fetch time");
@@ -346,12 +439,12 @@ impl JenkinsClient {
             id: format!("jenkins__{provider_id}__{job_path}"),
             provider_id,
             provider_type: "jenkins".to_string(),
-            name: job.name,
+            name,
             status,
             last_run,
             last_updated: Utc::now(),
             repository: repository_field,
-            branch: None,
+            branch,
             workflow_file: None,
             metadata: HashMap::new(),
         })
@@ -386,8 +479,7 @@ impl JenkinsClient {
                     tracing::info!(build_number = build_number, "Jenkins build cancelled successfully");
                     Ok(())
                 } else {
-                    let error_text = response
-                        .text()
+                    let error_text = read_text(response, self.max_response_bytes)
                         .await
                         .unwrap_or_else(|_| "Unknown error".to_string());
                     tracing::error!(status = %status, error = %error_text, "Jenkins cancel failed");
@@ -399,15 +491,80 @@ impl JenkinsClient {
             .await
     }
 
+    pub async fn fetch_computers(&self) -> PluginResult<Vec<types::Computer>> {
+        self.retry_policy.retry(|| async {
+            let url = format!(
+                "{}/computer/api/json?tree=computer[displayName,offline,temporarilyOffline,idle,numExecutors,offlineCauseReason,executors[currentExecutable[url]],assignedLabels[name]]",
+                self.server_url
+            );
+
+            let response: types::ComputerSet = self
+                .send_json(
+                    self.http_client.get(&url).timeout(Duration::from_secs(10)),
+                    "nodes",
+                )
+                .await?;
+
+            Ok(response.computer)
+        })
+        .await
+    }
+
+    pub async fn fetch_queue_items(&self) -> PluginResult<Vec<types::QueueItem>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "{}/queue/api/json?tree=items[inQueueSince,blocked]",
+                    self.server_url
+                );
+
+                let response: types::BuildQueueResponse = self
+                    .send_json(
+                        self.http_client.get(&url).timeout(Duration::from_secs(10)),
+                        "queue",
+                    )
+                    .await?;
+
+                Ok(response.items)
+            })
+            .await
+    }
+
+    pub async fn fetch_busy_executors(&self) -> PluginResult<usize> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("{}/computer/api/json?tree=busyExecutors", self.server_url);
+
+                let response: types::ComputerSet = self
+                    .send_json(
+                        self.http_client.get(&url).timeout(Duration::from_secs(10)),
+                        "executors",
+                    )
+                    .await?;
+
+                Ok(response.busy_executors)
+            })
+            .await
+    }
+
     pub fn discovered_jobs_to_available_pipelines(
         &self, all_jobs: Vec<types::DiscoveredJob>,
     ) -> Vec<AvailablePipeline> {
         all_jobs
             .into_iter()
             .map(|job| {
-                let job_type = if job._class.contains("WorkflowMultiBranch") {
-                    "Multibranch Pipeline"
-                } else if job._class.contains("WorkflowJob") {
+                if let Some(branch) = job.branch {
+                    let (organization, repository) = config::split_job_path(&job.parent_path);
+                    return AvailablePipeline {
+                        id: job.full_path,
+                        name: format!("{repository} » {branch}"),
+                        description: Some(format!("Branch of {}", job.parent_path)),
+                        organization: Some(organization),
+                        repository: Some(repository),
+                    };
+                }
+
+                let job_type = if job._class.contains("WorkflowJob") {
                     "Pipeline"
                 } else if job._class.contains("FreeStyleProject") {
                     "Freestyle"
@@ -5,6 +5,9 @@ use pipedash_plugin_api::{
     PluginResult,
 };
 
+// Oldest Jenkins release the plugin is known to work with.
+pub(crate) const MINIMUM_VERSION: (u64, u64) = (2, 0);
+
 pub(crate) fn parse_selected_items(config: &HashMap<String, String>) -> PluginResult<Vec<String>> {
     let selected_items = config
         .get("selected_items")
@@ -17,8 +20,25 @@ pub(crate) fn parse_selected_items(config: &HashMap<String, String>) -> PluginRe
         .collect())
 }
 
+// Each path segment is percent-encoded, since multibranch jobs are named
+// after the URL-encoded branch (`feature%2Flogin`).
 pub(crate) fn encode_job_name(name: &str) -> String {
-    name.replace('/', "/job/")
+    name.split('/')
+        .map(encode_segment)
+        .collect::<Vec<_>>()
+        .join("/job/")
+}
+
+fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
 }
 
 pub(crate) fn split_job_path(job_path: &str) -> (String, String) {
@@ -39,6 +59,11 @@ mod tests {
     fn test_encode_job_name() {
         assert_eq!(encode_job_name("folder/job"), "folder/job/job");
         assert_eq!(encode_job_name("simple"), "simple");
+        assert_eq!(
+            encode_job_name("platform/web/feature%2Flogin"),
+            "platform/job/web/job/feature%252Flogin"
+        );
+        assert_eq!(encode_job_name("nightly build"), "nightly%20build");
     }
 
     #[test]
@@ -4,6 +4,7 @@ mod mapper;
 mod metadata;
 mod plugin;
 mod schema;
+mod tree;
 mod types;
 
 pub use plugin::JenkinsPlugin;
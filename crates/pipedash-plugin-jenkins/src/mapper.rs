@@ -5,6 +5,8 @@ use std::collections::{
 
 use chrono::Utc;
 use pipedash_plugin_api::{
+    BuildAgent,
+    BuildQueue,
     PipelineRun,
     PipelineStatus,
     WorkflowParameter,
@@ -107,6 +109,17 @@ pub(crate) fn build_to_pipeline_run(
 
     tracing::trace!(inputs = ?inputs, "Final Jenkins inputs");
 
+    // Change sets list commits oldest first, so the last one is what was built.
+    let head_change = build
+        .change_sets
+        .iter()
+        .flat_map(|change_set| change_set.items.iter())
+        .last();
+    let commit_message = head_change.and_then(|item| item.msg.clone());
+    let commit_author = head_change
+        .and_then(|item| item.author.as_ref())
+        .and_then(|author| author.full_name.clone());
+
     let mut metadata = HashMap::new();
     if let Some(cause) = trigger_cause {
         metadata.insert("trigger_cause".to_string(), serde_json::json!(cause));
@@ -125,11 +138,15 @@ pub(crate) fn build_to_pipeline_run(
             .clone()
             .unwrap_or_else(|| format!("{}/job/{}/{}", server_url, encoded_path, build.number)),
         commit_sha,
-        commit_message: None,
+        commit_message,
         branch,
         actor,
+        commit_author,
+        author_avatar_url: None,
+        pull_request: None,
         inputs,
         metadata,
+        annotations: Vec::new(),
     }
 }
 
@@ -222,6 +239,63 @@ pub(crate) fn parameter_definitions_to_workflow_parameters(
     parameters
 }
 
+// Nodes taken offline by hand are reported as paused rather than offline, so
+// planned maintenance doesn't look like lost capacity.
+pub(crate) fn map_computer(computer: types::Computer) -> BuildAgent {
+    let status = if computer.temporarily_offline {
+        "paused"
+    } else if computer.offline {
+        "offline"
+    } else if computer.idle {
+        "idle"
+    } else {
+        "busy"
+    };
+
+    let job_id = computer
+        .executors
+        .iter()
+        .filter_map(|executor| executor.current_executable.as_ref())
+        .find_map(|executable| executable.url.clone());
+
+    let labels: Vec<String> = computer
+        .assigned_labels
+        .into_iter()
+        .map(|label| label.name)
+        .filter(|name| *name != computer.display_name)
+        .collect();
+
+    let mut metadata = HashMap::new();
+    metadata.insert("executors".to_string(), computer.num_executors.to_string());
+    metadata.insert("labels".to_string(), labels.join(","));
+    if let Some(reason) = computer.offline_cause_reason.filter(|r| !r.is_empty()) {
+        metadata.insert("offline_reason".to_string(), reason);
+    }
+
+    BuildAgent {
+        id: computer.display_name.clone(),
+        name: computer.display_name.clone(),
+        hostname: computer.display_name,
+        status: status.to_string(),
+        job_id,
+        last_seen: Utc::now(),
+        metadata,
+    }
+}
+
+// Jenkins has a single build queue. Blocked items wait on another build or a
+// throttle rather than on an executor, so they don't count towards the queue.
+pub(crate) fn map_queue(items: &[types::QueueItem], running: usize) -> BuildQueue {
+    let now = Utc::now();
+    let queued_at: Vec<_> = items
+        .iter()
+        .filter(|item| !item.blocked)
+        .filter_map(|item| chrono::DateTime::from_timestamp_millis(item.in_queue_since))
+        .collect();
+
+    BuildQueue::from_queued_at("default".to_string(), &queued_at, running, now)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,4 +310,29 @@ mod tests {
         );
         assert_eq!(map_jenkins_result(None), PipelineStatus::Running);
     }
+
+    #[test]
+    fn test_map_computer() {
+        let computer: types::Computer = serde_json::from_value(serde_json::json!({
+            "displayName": "agent-1",
+            "offline": false,
+            "idle": false,
+            "numExecutors": 2,
+            "executors": [
+                { "currentExecutable": null },
+                { "currentExecutable": { "url": "https://ci.example.com/job/app/12/" } },
+            ],
+            "assignedLabels": [{ "name": "agent-1" }, { "name": "linux" }],
+        }))
+        .unwrap();
+
+        let agent = map_computer(computer);
+
+        assert_eq!(agent.status, "busy");
+        assert_eq!(
+            agent.job_id.as_deref(),
+            Some("https://ci.example.com/job/app/12/")
+        );
+        assert_eq!(agent.metadata["labels"], "linux");
+    }
 }
@@ -55,6 +55,7 @@ fn create_config_schema() -> ConfigSchema {
             validation_regex: None,
             validation_message: None,
         })
+        .with_network_fields()
 }
 
 fn create_capabilities() -> PluginCapabilities {
@@ -62,9 +63,10 @@ fn create_capabilities() -> PluginCapabilities {
         pipelines: true,
         pipeline_runs: true,
         trigger: true,
-        agents: false,
-        artifacts: false,
-        queues: false,
-        custom_tables: false,
+        agents: true,
+        queues: true,
+        cancel: true,
+        workflow_parameters: true,
+        ..PluginCapabilities::default()
     }
 }
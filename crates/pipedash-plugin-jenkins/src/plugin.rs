@@ -88,39 +88,41 @@ impl Plugin for JenkinsPlugin {
             )
         });
 
-        self.client = Some(client::JenkinsClient::new(client, server_url, auth_header));
+        self.client = Some(
+            client::JenkinsClient::new(client, server_url, auth_header)
+                .with_max_response_bytes(max_response_bytes(&config)),
+        );
         self.provider_id = Some(provider_id);
         self.config = config;
 
         Ok(())
     }
 
-    async fn validate_credentials(&self) -> PluginResult<bool> {
-        let client = self.client()?;
+    fn api_endpoint(&self) -> Option<String> {
+        self.client.as_ref().map(|c| c.server_url().to_string())
+    }
 
-        client.discover_all_jobs().await?;
+    async fn validate_credentials(&self) -> PluginResult<bool> {
+        // Listing the root is enough; a full crawl can take minutes on big
+        // instances.
+        self.client()?.fetch_jobs_in_folder("").await?;
         Ok(true)
     }
 
+    async fn detect_server_version(&self) -> PluginResult<Option<ServerVersion>> {
+        let version = self.client()?.fetch_version().await?;
+        Ok(Some(ServerVersion::new(
+            "Jenkins",
+            &version,
+            config::MINIMUM_VERSION,
+        )))
+    }
+
     async fn fetch_available_pipelines(
         &self, params: Option<PaginationParams>,
     ) -> PluginResult<PaginatedResponse<AvailablePipeline>> {
-        let params = params.unwrap_or_default();
-        let client = self.client()?;
-        let all_jobs = client.discover_all_jobs().await?;
-        let all_pipelines = client.discovered_jobs_to_available_pipelines(all_jobs);
-
-        let total_count = all_pipelines.len();
-        let start = ((params.page - 1) * params.page_size).min(total_count);
-        let end = (start + params.page_size).min(total_count);
-        let items = all_pipelines[start..end].to_vec();
-
-        Ok(PaginatedResponse::new(
-            items,
-            params.page,
-            params.page_size,
-            total_count,
-        ))
+        self.fetch_available_pipelines_filtered(None, None, params)
+            .await
     }
 
     async fn fetch_pipelines(&self) -> PluginResult<Vec<Pipeline>> {
@@ -328,8 +330,8 @@ impl Plugin for JenkinsPlugin {
     ) -> PluginResult<PaginatedResponse<AvailablePipeline>> {
         let params = params.unwrap_or_default();
         let client = self.client()?;
-        let all_jobs = client.discover_all_jobs().await?;
-        let mut all_pipelines = client.discovered_jobs_to_available_pipelines(all_jobs);
+        let discovery = client.discover_jobs().await?;
+        let mut all_pipelines = client.discovered_jobs_to_available_pipelines(discovery.jobs);
 
         if let Some(search_term) = search {
             let search_lower = search_term.to_lowercase();
@@ -347,12 +349,25 @@ impl Plugin for JenkinsPlugin {
         let end = (start + params.page_size).min(total_count);
         let items = all_pipelines[start..end].to_vec();
 
-        Ok(PaginatedResponse::new(
-            items,
-            params.page,
-            params.page_size,
-            total_count,
-        ))
+        let mut response =
+            PaginatedResponse::new(items, params.page, params.page_size, total_count);
+        // The crawl hasn't reached every folder yet, so later pages may fill
+        // in.
+        response.has_more |= !discovery.complete;
+        Ok(response)
+    }
+
+    async fn fetch_agents(&self) -> PluginResult<Vec<BuildAgent>> {
+        let client = self.client()?;
+        let computers = client.fetch_computers().await?;
+        Ok(computers.into_iter().map(mapper::map_computer).collect())
+    }
+
+    async fn fetch_queues(&self) -> PluginResult<Vec<BuildQueue>> {
+        let client = self.client()?;
+        let (items, running) =
+            futures::try_join!(client.fetch_queue_items(), client.fetch_busy_executors())?;
+        Ok(vec![mapper::map_queue(&items, running)])
     }
 
     async fn cancel_run(&self, pipeline_id: &str, run_number: i64) -> PluginResult<()> {
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use crate::types;
+
+// How long a folder listing is reused before the crawl fetches it again.
+pub(crate) const LISTING_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ItemKind {
+    // Plain and organization folders.
+    Folder,
+    // Its children are the per-branch jobs.
+    MultiBranch,
+    Job,
+    Other,
+}
+
+pub(crate) fn classify(class: &str) -> ItemKind {
+    if class.contains("WorkflowMultiBranchProject") {
+        ItemKind::MultiBranch
+    } else if class.contains("Folder") {
+        ItemKind::Folder
+    } else if class.contains("WorkflowJob") || class.contains("FreeStyleProject") {
+        ItemKind::Job
+    } else {
+        ItemKind::Other
+    }
+}
+
+struct Listing {
+    fetched_at: Instant,
+    items: Vec<types::JobItem>,
+}
+
+pub(crate) struct Walk {
+    pub jobs: Vec<types::DiscoveredJob>,
+    // Folders that were never listed or whose listing has expired.
+    pub pending: Vec<String>,
+}
+
+pub(crate) struct Discovery {
+    pub jobs: Vec<types::DiscoveredJob>,
+    // False when the crawl stopped early and more jobs may turn up.
+    pub complete: bool,
+}
+
+// The folder listings fetched so far, keyed by folder path ("" is the root).
+// Crawls resume from here, so a large instance is loaded over several calls
+// and pages of the same crawl don't refetch anything.
+#[derive(Default)]
+pub(crate) struct FolderTree {
+    listings: HashMap<String, Listing>,
+}
+
+impl FolderTree {
+    pub fn insert(&mut self, path: String, items: Vec<types::JobItem>) {
+        self.listings.insert(
+            path,
+            Listing {
+                fetched_at: Instant::now(),
+                items,
+            },
+        );
+    }
+
+    // Goes through the cached listings breadth first, so jobs found by a later
+    // call are appended after the ones already returned. Expired listings are
+    // still walked while they wait to be refetched.
+    pub fn walk(&self, now: Instant) -> Walk {
+        let mut jobs = Vec::new();
+        let mut pending = Vec::new();
+        let mut level = vec![(String::new(), false)];
+
+        while !level.is_empty() {
+            let mut next = Vec::new();
+
+            for (path, multibranch) in level {
+                let Some(listing) = self.listings.get(&path) else {
+                    pending.push(path);
+                    continue;
+                };
+                if now.duration_since(listing.fetched_at) >= LISTING_TTL {
+                    pending.push(path.clone());
+                }
+
+                for item in &listing.items {
+                    let full_path = if path.is_empty() {
+                        item.name.clone()
+                    } else {
+                        format!("{}/{}", path, item.name)
+                    };
+
+                    match classify(&item._class) {
+                        ItemKind::Folder => next.push((full_path, false)),
+                        ItemKind::MultiBranch => next.push((full_path, true)),
+                        ItemKind::Job => jobs.push(types::DiscoveredJob {
+                            name: item.name.clone(),
+                            full_path,
+                            _class: item._class.clone(),
+                            parent_path: path.clone(),
+                            branch: multibranch
+                                .then(|| item.display_name.clone().unwrap_or(item.name.clone())),
+                        }),
+                        ItemKind::Other => {}
+                    }
+                }
+            }
+
+            level = next;
+        }
+
+        Walk { jobs, pending }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, class: &str, display_name: Option<&str>) -> types::JobItem {
+        types::JobItem {
+            name: name.to_string(),
+            _class: class.to_string(),
+            display_name: display_name.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_walk_resumes_from_cached_listings() {
+        let mut tree = FolderTree::default();
+        assert_eq!(tree.walk(Instant::now()).pending, vec![String::new()]);
+
+        tree.insert(
+            String::new(),
+            vec![
+                item(
+                    "platform",
+                    "com.cloudbees.hudson.plugins.folder.Folder",
+                    None,
+                ),
+                item("deploy", "hudson.model.FreeStyleProject", None),
+            ],
+        );
+        let walk = tree.walk(Instant::now());
+        assert_eq!(walk.jobs.len(), 1);
+        assert_eq!(walk.pending, vec!["platform".to_string()]);
+
+        tree.insert(
+            "platform".to_string(),
+            vec![item(
+                "web",
+                "org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject",
+                None,
+            )],
+        );
+        tree.insert(
+            "platform/web".to_string(),
+            vec![item(
+                "feature%2Flogin",
+                "org.jenkinsci.plugins.workflow.job.WorkflowJob",
+                Some("feature/login"),
+            )],
+        );
+        let walk = tree.walk(Instant::now());
+        assert!(walk.pending.is_empty());
+        assert_eq!(walk.jobs[0].full_path, "deploy");
+        assert_eq!(walk.jobs[0].branch, None);
+        assert_eq!(walk.jobs[1].full_path, "platform/web/feature%2Flogin");
+        assert_eq!(walk.jobs[1].parent_path, "platform/web");
+        assert_eq!(walk.jobs[1].branch.as_deref(), Some("feature/login"));
+
+        // Expired listings are refetched but keep their jobs in the meantime.
+        let walk = tree.walk(Instant::now() + LISTING_TTL);
+        assert_eq!(walk.pending.len(), 3);
+        assert_eq!(walk.jobs.len(), 2);
+    }
+}
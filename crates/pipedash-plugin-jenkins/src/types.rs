@@ -5,12 +5,18 @@ pub(crate) struct JobItem {
     pub name: String,
     #[serde(rename = "_class")]
     pub _class: String,
+    #[serde(rename = "displayName")]
+    #[serde(default)]
+    pub display_name: Option<String>,
 }
 
 pub(crate) struct DiscoveredJob {
     pub name: String,
     pub full_path: String,
     pub _class: String,
+    pub parent_path: String,
+    // Set for the branch jobs of a multibranch project.
+    pub branch: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -19,6 +25,11 @@ pub(crate) struct Job {
     #[serde(rename = "_class")]
     #[serde(default)]
     pub _class: Option<String>,
+    #[serde(rename = "displayName")]
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub property: Vec<JobProperty>,
     #[serde(rename = "lastBuild")]
     #[serde(default)]
     pub last_build: Option<BuildRef>,
@@ -51,6 +62,30 @@ pub(crate) struct Build {
     pub full_display_name: Option<String>,
     #[serde(default)]
     pub actions: Vec<BuildAction>,
+    #[serde(rename = "changeSets")]
+    #[serde(default)]
+    pub change_sets: Vec<ChangeSet>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ChangeSet {
+    #[serde(default)]
+    pub items: Vec<ChangeSetItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ChangeSetItem {
+    #[serde(default)]
+    pub msg: Option<String>,
+    #[serde(default)]
+    pub author: Option<ChangeSetAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ChangeSetAuthor {
+    #[serde(rename = "fullName")]
+    #[serde(default)]
+    pub full_name: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -154,3 +189,64 @@ pub(crate) struct JobsResponse {
 pub(crate) struct JobBuildsResponse {
     pub builds: Vec<Build>,
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ComputerSet {
+    #[serde(default)]
+    pub computer: Vec<Computer>,
+    #[serde(default)]
+    pub busy_executors: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Computer {
+    pub display_name: String,
+    #[serde(default)]
+    pub offline: bool,
+    #[serde(default)]
+    pub temporarily_offline: bool,
+    #[serde(default)]
+    pub idle: bool,
+    #[serde(default)]
+    pub num_executors: i64,
+    #[serde(default)]
+    pub offline_cause_reason: Option<String>,
+    #[serde(default)]
+    pub executors: Vec<Executor>,
+    #[serde(default)]
+    pub assigned_labels: Vec<AssignedLabel>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Executor {
+    #[serde(default)]
+    pub current_executable: Option<CurrentExecutable>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CurrentExecutable {
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AssignedLabel {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BuildQueueResponse {
+    #[serde(default)]
+    pub items: Vec<QueueItem>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QueueItem {
+    pub in_queue_since: i64,
+    #[serde(default)]
+    pub blocked: bool,
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use pipedash_plugin_api::{
+    PaginationParams,
+    PipelineStatus,
+    Plugin,
+    PluginError,
+};
+use pipedash_plugin_jenkins::JenkinsPlugin;
+use pipedash_plugin_testing::{
+    Cassette,
+    FixtureServer,
+    RecordedRequest,
+    RecordedResponse,
+};
+use serde_json::json;
+
+const JOBS_TREE: &str = "api/json?tree=jobs[name,url,_class,displayName]";
+const BUILDS_TREE: &str = "api/json?tree=builds[number,url,result,building,timestamp,duration,actions[_class,causes[userName,shortDescription],lastBuiltRevision[SHA1,branch[SHA1,name]],parameters[name,value]],changeSets[items[msg,author[fullName]]]]{0,2}";
+
+fn plugin(server: &FixtureServer) -> JenkinsPlugin {
+    let mut plugin = JenkinsPlugin::new();
+    let config = HashMap::from([
+        ("username".to_string(), "ci".to_string()),
+        ("token".to_string(), "secret".to_string()),
+        ("server_url".to_string(), server.base_url().to_string()),
+    ]);
+    plugin.initialize(1, config, None).unwrap();
+    plugin
+}
+
+fn job(name: &str, class: &str) -> serde_json::Value {
+    json!({"_class": class, "name": name, "url": format!("{{{{base_url}}}}/job/{}/", name)})
+}
+
+fn jobs_cassette() -> Cassette {
+    Cassette::new()
+        .with(
+            RecordedRequest::new("GET", &format!("/{}", JOBS_TREE)),
+            RecordedResponse::json(
+                200,
+                json!({"jobs": [
+                    job("platform", "com.cloudbees.hudson.plugins.folder.Folder"),
+                    job("deploy", "hudson.model.FreeStyleProject"),
+                    job("notes", "hudson.model.ExternalJob"),
+                ]}),
+            ),
+        )
+        .with(
+            RecordedRequest::new("GET", &format!("/job/platform/{}", JOBS_TREE)),
+            RecordedResponse::json(
+                200,
+                json!({"jobs": [
+                    job("api", "org.jenkinsci.plugins.workflow.job.WorkflowJob"),
+                    job("web", "org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject"),
+                    job("legacy", "com.cloudbees.hudson.plugins.folder.Folder"),
+                ]}),
+            ),
+        )
+        .with(
+            RecordedRequest::new("GET", &format!("/job/platform/job/web/{}", JOBS_TREE)),
+            RecordedResponse::json(
+                200,
+                json!({"jobs": [
+                    {
+                        "_class": "org.jenkinsci.plugins.workflow.job.WorkflowJob",
+                        "name": "feature%2Flogin",
+                        "displayName": "feature/login"
+                    },
+                ]}),
+            ),
+        )
+        // A folder the user can't read is skipped rather than failing the crawl.
+        .with(
+            RecordedRequest::new("GET", &format!("/job/platform/job/legacy/{}", JOBS_TREE)),
+            RecordedResponse::text(403, "Forbidden"),
+        )
+}
+
+#[tokio::test]
+async fn test_discovers_jobs_in_folders() {
+    let server = FixtureServer::replay(jobs_cassette()).unwrap();
+    let plugin = plugin(&server);
+
+    let page = plugin
+        .fetch_available_pipelines(Some(PaginationParams {
+            page: 1,
+            page_size: 2,
+        }))
+        .await
+        .unwrap();
+
+    assert_eq!(page.total_count, 3);
+    assert!(page.has_more);
+    assert_eq!(page.items[0].id, "deploy");
+    assert_eq!(page.items[0].organization.as_deref(), Some("(root)"));
+    assert_eq!(
+        page.items[0].description.as_deref(),
+        Some("Type: Freestyle")
+    );
+    assert_eq!(page.items[1].id, "platform/api");
+    assert_eq!(page.items[1].organization.as_deref(), Some("platform"));
+    assert_eq!(page.items[1].description.as_deref(), Some("Type: Pipeline"));
+
+    let last = plugin
+        .fetch_available_pipelines(Some(PaginationParams {
+            page: 2,
+            page_size: 2,
+        }))
+        .await
+        .unwrap();
+    assert!(!last.has_more);
+    assert_eq!(last.items.len(), 1);
+    assert_eq!(last.items[0].id, "platform/web/feature%2Flogin");
+    assert_eq!(last.items[0].name, "web » feature/login");
+    assert_eq!(last.items[0].repository.as_deref(), Some("web"));
+    assert_eq!(
+        last.items[0].description.as_deref(),
+        Some("Branch of platform/web")
+    );
+    // The second page reuses the cached folder tree.
+    assert_eq!(server.served().len(), 4);
+    assert!(server.unmatched().is_empty());
+}
+
+#[tokio::test]
+async fn test_fetch_run_history_maps_builds() {
+    let cassette = Cassette::new().with(
+        RecordedRequest::new("GET", &format!("/job/platform/job/api/{}", BUILDS_TREE)),
+        RecordedResponse::json(
+            200,
+            json!({"builds": [
+                {
+                    "number": 12,
+                    "url": "{{base_url}}/job/platform/job/api/12/",
+                    "result": null,
+                    "building": true,
+                    "timestamp": 1740823200000_i64,
+                    "duration": 0,
+                    "actions": [
+                        {"_class": "hudson.model.CauseAction", "causes": [
+                            {"shortDescription": "Started by user Jane", "userName": "jane"}
+                        ]},
+                        {"_class": "hudson.model.ParametersAction", "parameters": [
+                            {"name": "ENVIRONMENT", "value": "staging"}
+                        ]}
+                    ],
+                    "changeSets": []
+                },
+                {
+                    "number": 11,
+                    "result": "UNSTABLE",
+                    "building": false,
+                    "timestamp": 1740819600000_i64,
+                    "duration": 95000,
+                    "actions": [
+                        {"_class": "hudson.plugins.git.util.BuildData", "lastBuiltRevision": {
+                            "SHA1": "a1b2c3d4",
+                            "branch": [{"SHA1": "a1b2c3d4", "name": "origin/main"}]
+                        }}
+                    ],
+                    "changeSets": [{"items": [
+                        {"msg": "Older change", "author": {"fullName": "Sam"}},
+                        {"msg": "Fix flaky test", "author": {"fullName": "Jane Doe"}}
+                    ]}]
+                }
+            ]}),
+        ),
+    );
+    let server = FixtureServer::replay(cassette).unwrap();
+    let runs = plugin(&server)
+        .fetch_run_history("jenkins__1__platform/api", 2)
+        .await
+        .unwrap();
+
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].status, PipelineStatus::Running);
+    assert_eq!(runs[0].actor.as_deref(), Some("jane"));
+    assert_eq!(runs[0].inputs, Some(json!({"ENVIRONMENT": "staging"})));
+    assert_eq!(
+        runs[0].logs_url,
+        format!("{}/job/platform/job/api/12/", server.base_url())
+    );
+    assert_eq!(runs[0].duration_seconds, None);
+
+    assert_eq!(runs[1].status, PipelineStatus::Failed);
+    assert_eq!(runs[1].duration_seconds, Some(95));
+    assert_eq!(runs[1].commit_sha.as_deref(), Some("a1b2c3d4"));
+    assert_eq!(runs[1].commit_message.as_deref(), Some("Fix flaky test"));
+    assert_eq!(runs[1].commit_author.as_deref(), Some("Jane Doe"));
+    // Without a build URL the link is built from the server URL.
+    assert_eq!(
+        runs[1].logs_url,
+        format!("{}/job/platform/job/api/11", server.base_url())
+    );
+}
+
+#[tokio::test]
+async fn test_error_responses() {
+    let cassette = Cassette::new().with(
+        RecordedRequest::new("GET", &format!("/{}", JOBS_TREE)),
+        RecordedResponse::text(401, "<html><body>Unauthorized</body></html>")
+            .with_header("content-type", "text/html"),
+    );
+    let server = FixtureServer::replay(cassette).unwrap();
+    let plugin = plugin(&server);
+
+    assert!(matches!(
+        plugin.validate_credentials().await,
+        Err(PluginError::ApiError(_))
+    ));
+    // Jobs that aren't in the fixture get a 501 from the server, which the
+    // plugin reports rather than panicking on.
+    assert!(plugin
+        .fetch_run_history("jenkins__1__missing", 2)
+        .await
+        .is_err());
+    assert_eq!(server.unmatched().len(), 1);
+}
+
+#[tokio::test]
+async fn test_detects_server_version() {
+    let cassette = Cassette::new().with(
+        RecordedRequest::new("GET", "/api/json?tree=mode"),
+        RecordedResponse::json(
+            200,
+            json!({"_class": "hudson.model.Hudson", "mode": "NORMAL"}),
+        )
+        .with_header("x-jenkins", "1.651.3"),
+    );
+    let server = FixtureServer::replay(cassette).unwrap();
+
+    let version = plugin(&server)
+        .detect_server_version()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(version.version, "1.651.3");
+    assert!(!version.supported);
+    assert!(version.warning.unwrap().contains("older than 2.0"));
+}
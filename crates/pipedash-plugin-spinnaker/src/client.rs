@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use pipedash_plugin_api::http::DEFAULT_MAX_RESPONSE_MB;
+use pipedash_plugin_api::{
+    read_json,
+    read_text,
+    PluginError,
+    PluginResult,
+    RetryPolicy,
+};
+use reqwest::StatusCode;
+use tracing::debug;
+
+use crate::config::GateAuth;
+use crate::types::{
+    Application,
+    Execution,
+    JudgmentRequest,
+    PipelineConfig,
+    TriggerRequest,
+    TriggerResponse,
+};
+
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct SpinnakerClient {
+    http_client: Arc<reqwest::Client>,
+    gate_url: String,
+    auth: GateAuth,
+    retry_policy: RetryPolicy,
+    max_response_bytes: usize,
+}
+
+impl SpinnakerClient {
+    pub fn new(
+        http_client: Option<Arc<reqwest::Client>>, gate_url: String, auth: GateAuth,
+    ) -> PluginResult<Self> {
+        let client = match http_client {
+            Some(client) => client,
+            None => Arc::new(
+                reqwest::Client::builder()
+                    .use_rustls_tls()
+                    .pool_max_idle_per_host(10)
+                    .timeout(DEFAULT_REQUEST_TIMEOUT)
+                    .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+                    .tcp_keepalive(Duration::from_secs(60))
+                    .build()
+                    .map_err(|e| {
+                        PluginError::Internal(format!("Failed to build HTTP client: {}", e))
+                    })?,
+            ),
+        };
+
+        Ok(Self {
+            http_client: client,
+            gate_url,
+            auth,
+            retry_policy: RetryPolicy::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_MB * 1024 * 1024,
+        })
+    }
+
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    pub async fn list_applications(&self) -> PluginResult<Vec<Application>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!("{}/applications", self.gate_url);
+                self.get(&url, "list applications").await
+            })
+            .await
+    }
+
+    pub async fn list_pipeline_configs(
+        &self, application: &str,
+    ) -> PluginResult<Vec<PipelineConfig>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "{}/applications/{}/pipelineConfigs",
+                    self.gate_url,
+                    urlencoding::encode(application)
+                );
+                self.get(&url, "list pipeline configs").await
+            })
+            .await
+    }
+
+    pub async fn get_pipeline_config(
+        &self, application: &str, pipeline: &str,
+    ) -> PluginResult<PipelineConfig> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "{}/applications/{}/pipelineConfigs/{}",
+                    self.gate_url,
+                    urlencoding::encode(application),
+                    urlencoding::encode(pipeline)
+                );
+                self.get(&url, "get pipeline config").await
+            })
+            .await
+    }
+
+    // Gate applies `limit` per pipeline config, so the result holds up to
+    // `limit` executions for every pipeline in the application, newest first.
+    pub async fn list_executions(
+        &self, application: &str, limit: usize,
+    ) -> PluginResult<Vec<Execution>> {
+        self.retry_policy
+            .retry(|| async {
+                let url = format!(
+                    "{}/applications/{}/pipelines?limit={}&expand=true",
+                    self.gate_url,
+                    urlencoding::encode(application),
+                    limit
+                );
+                self.get(&url, "list executions").await
+            })
+            .await
+    }
+
+    // Returns the new execution ID. Not retried: a retry after a timeout could
+    // start the pipeline twice.
+    pub async fn trigger_pipeline(
+        &self, application: &str, pipeline: &str, parameters: HashMap<String, String>,
+    ) -> PluginResult<String> {
+        let url = format!(
+            "{}/pipelines/{}/{}",
+            self.gate_url,
+            urlencoding::encode(application),
+            urlencoding::encode(pipeline)
+        );
+        let request = TriggerRequest {
+            trigger_type: "manual",
+            parameters,
+        };
+        debug!(?request, "Triggering Spinnaker pipeline");
+
+        let response = self
+            .auth
+            .apply(self.http_client.post(&url))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| PluginError::NetworkError(format!("Failed to trigger pipeline: {}", e)))?;
+
+        let trigger: TriggerResponse = self.handle_response(response).await?;
+        Ok(trigger
+            .reference
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    pub async fn cancel_execution(&self, execution_id: &str) -> PluginResult<()> {
+        let url = format!(
+            "{}/pipelines/{}/cancel",
+            self.gate_url,
+            urlencoding::encode(execution_id)
+        );
+        let response = self
+            .auth
+            .apply(self.http_client.put(&url))
+            .send()
+            .await
+            .map_err(|e| PluginError::NetworkError(format!("Failed to cancel execution: {}", e)))?;
+
+        self.handle_empty_response(response).await
+    }
+
+    pub async fn submit_judgment(
+        &self, execution_id: &str, stage_id: &str, judgment: JudgmentRequest,
+    ) -> PluginResult<()> {
+        let url = format!(
+            "{}/pipelines/{}/stages/{}",
+            self.gate_url,
+            urlencoding::encode(execution_id),
+            urlencoding::encode(stage_id)
+        );
+        let response = self
+            .auth
+            .apply(self.http_client.patch(&url))
+            .json(&judgment)
+            .send()
+            .await
+            .map_err(|e| {
+                PluginError::NetworkError(format!("Failed to submit manual judgment: {}", e))
+            })?;
+
+        self.handle_empty_response(response).await
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(
+        &self, url: &str, action: &str,
+    ) -> PluginResult<T> {
+        let response = self
+            .auth
+            .apply(self.http_client.get(url))
+            .send()
+            .await
+            .map_err(|e| PluginError::NetworkError(format!("Failed to {}: {}", action, e)))?;
+
+        self.handle_response(response).await
+    }
+
+    async fn handle_empty_response(&self, response: reqwest::Response) -> PluginResult<()> {
+        self.handle_response::<serde_json::Value>(response)
+            .await
+            .map(|_| ())
+            .or_else(|e| match e {
+                // Some Gate versions answer these calls with an empty body.
+                PluginError::SerializationError(_) => Ok(()),
+                other => Err(other),
+            })
+    }
+
+    async fn handle_response<T: serde::de::DeserializeOwned>(
+        &self, response: reqwest::Response,
+    ) -> PluginResult<T> {
+        let status = response.status();
+        let url = response.url().clone();
+
+        if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+            let error_text = read_text(response, self.max_response_bytes)
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            if status == StatusCode::FORBIDDEN {
+                return Err(PluginError::PermissionDenied(format!(
+                    "Permission denied for {}: {}",
+                    url, error_text
+                )));
+            }
+            return Err(PluginError::AuthenticationFailed(format!(
+                "Authentication failed for {}: {}",
+                url, error_text
+            )));
+        }
+
+        if status == StatusCode::NOT_FOUND {
+            return Err(PluginError::PipelineNotFound(format!(
+                "Resource not found: {}",
+                url
+            )));
+        }
+
+        if let Some(error) = pipedash_plugin_api::rate_limit_error(&response) {
+            return Err(error);
+        }
+
+        if !status.is_success() {
+            let error_text = read_text(response, self.max_response_bytes)
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(PluginError::ApiError(format!(
+                "Spinnaker API error ({}) for {}: {}",
+                status, url, error_text
+            )));
+        }
+
+        read_json(response, self.max_response_bytes)
+            .await
+            .map_err(|e| match e {
+                PluginError::SerializationError(e) => PluginError::SerializationError(format!(
+                    "Failed to parse Spinnaker API response from {}: {}",
+                    url, e
+                )),
+                e => e,
+            })
+    }
+}
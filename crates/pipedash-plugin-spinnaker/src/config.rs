@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use pipedash_plugin_api::{
+    PluginError,
+    PluginResult,
+};
+
+// Trigger inputs that answer a waiting manual judgment stage instead of
+// starting a new execution.
+pub(crate) const JUDGMENT_PARAM: &str = "manual_judgment";
+pub(crate) const JUDGMENT_INPUT_PARAM: &str = "judgment_input";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum GateAuth {
+    None,
+    Bearer(String),
+    Basic { username: String, password: String },
+}
+
+impl GateAuth {
+    pub(crate) fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            GateAuth::None => request,
+            GateAuth::Bearer(token) => request.bearer_auth(token),
+            GateAuth::Basic { username, password } => request.basic_auth(username, Some(password)),
+        }
+    }
+}
+
+pub(crate) fn parse_pipeline_id(id: &str) -> PluginResult<(i64, String, String)> {
+    let parts: Vec<&str> = id.splitn(4, "__").collect();
+
+    if parts.len() != 4 || parts[0] != "spinnaker" {
+        return Err(PluginError::InvalidConfig(format!(
+            "Invalid pipeline ID format: '{}'. Expected format: 'spinnaker__{{provider_id}}__{{application}}__{{pipeline}}'",
+            id
+        )));
+    }
+
+    let provider_id = parts[1].parse::<i64>().map_err(|_| {
+        PluginError::InvalidConfig(format!(
+            "Invalid provider ID '{}' in pipeline ID '{}'. Provider ID must be a valid integer",
+            parts[1], id
+        ))
+    })?;
+
+    Ok((provider_id, parts[2].to_string(), parts[3].to_string()))
+}
+
+pub(crate) fn build_pipeline_id(provider_id: i64, application: &str, pipeline: &str) -> String {
+    format!("spinnaker__{}__{}__{}", provider_id, application, pipeline)
+}
+
+pub(crate) fn get_gate_url(config: &HashMap<String, String>) -> PluginResult<String> {
+    config
+        .get("gate_url")
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+        .ok_or_else(|| PluginError::InvalidConfig("Missing gate_url in config".to_string()))
+}
+
+// Execution links point at Deck, which usually lives on a different host than
+// Gate. Without it the links fall back to the Gate URL.
+pub(crate) fn get_deck_url(config: &HashMap<String, String>) -> Option<String> {
+    config
+        .get("deck_url")
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+}
+
+pub(crate) fn get_auth(config: &HashMap<String, String>) -> PluginResult<GateAuth> {
+    let non_empty = |key: &str| {
+        config
+            .get(key)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    if let Some(token) = non_empty("token") {
+        return Ok(GateAuth::Bearer(token));
+    }
+
+    match (non_empty("username"), non_empty("password")) {
+        (Some(username), Some(password)) => Ok(GateAuth::Basic { username, password }),
+        (None, None) => Ok(GateAuth::None),
+        _ => Err(PluginError::InvalidConfig(
+            "Both username and password are required for basic authentication".to_string(),
+        )),
+    }
+}
+
+// Selected items are stored as `application/pipeline`. Application names can't
+// contain a slash, pipeline names can.
+pub(crate) fn parse_selected_items(
+    config: &HashMap<String, String>,
+) -> Option<Vec<(String, String)>> {
+    config.get("selected_items").map(|items| {
+        items
+            .split(',')
+            .filter_map(|item| {
+                let (application, pipeline) = item.trim().split_once('/')?;
+                if application.is_empty() || pipeline.is_empty() {
+                    return None;
+                }
+                Some((application.to_string(), pipeline.to_string()))
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_id_round_trip() {
+        let id = build_pipeline_id(3, "checkout", "deploy__prod");
+        assert_eq!(
+            parse_pipeline_id(&id).unwrap(),
+            (3, "checkout".to_string(), "deploy__prod".to_string())
+        );
+        assert!(parse_pipeline_id("argocd__3__ns__app").is_err());
+    }
+
+    #[test]
+    fn test_get_auth() {
+        let mut config = HashMap::new();
+        assert_eq!(get_auth(&config).unwrap(), GateAuth::None);
+
+        config.insert("username".to_string(), "admin".to_string());
+        assert!(get_auth(&config).is_err());
+
+        config.insert("password".to_string(), "secret".to_string());
+        assert!(matches!(get_auth(&config).unwrap(), GateAuth::Basic { .. }));
+
+        config.insert("token".to_string(), "abc".to_string());
+        assert_eq!(
+            get_auth(&config).unwrap(),
+            GateAuth::Bearer("abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_selected_items() {
+        let mut config = HashMap::new();
+        config.insert(
+            "selected_items".to_string(),
+            "checkout/Deploy to prod, checkout/canary/eu,invalid".to_string(),
+        );
+
+        assert_eq!(
+            parse_selected_items(&config).unwrap(),
+            vec![
+                ("checkout".to_string(), "Deploy to prod".to_string()),
+                ("checkout".to_string(), "canary/eu".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+mod client;
+mod config;
+mod mapper;
+mod metadata;
+mod plugin;
+mod schema;
+mod types;
+
+pub use plugin::SpinnakerPlugin;
+
+pipedash_plugin_api::register_plugin!(SpinnakerPlugin);
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use pipedash_plugin_api::{
+    AvailablePipeline,
+    Pipeline,
+    PipelineRun,
+    PipelineStatus,
+    WorkflowParameter,
+    WorkflowParameterType,
+};
+
+use crate::{
+    config,
+    types,
+};
+
+const MANUAL_JUDGMENT_STAGE: &str = "manualJudgment";
+
+pub(crate) fn map_status(status: &str) -> PipelineStatus {
+    match status {
+        "SUCCEEDED" => PipelineStatus::Success,
+        "TERMINAL" | "FAILED_CONTINUE" => PipelineStatus::Failed,
+        "RUNNING" => PipelineStatus::Running,
+        "CANCELED" | "STOPPED" => PipelineStatus::Cancelled,
+        "SKIPPED" => PipelineStatus::Skipped,
+        "NOT_STARTED" | "BUFFERED" => PipelineStatus::Queued,
+        "PAUSED" | "SUSPENDED" => PipelineStatus::Waiting,
+        _ => PipelineStatus::Pending,
+    }
+}
+
+fn from_millis(millis: Option<i64>) -> Option<DateTime<Utc>> {
+    millis.and_then(DateTime::from_timestamp_millis)
+}
+
+// Execution IDs are ULIDs, so the build time in milliseconds stands in for a
+// run number.
+pub(crate) fn run_number(execution: &types::Execution) -> i64 {
+    execution
+        .build_time
+        .or(execution.start_time)
+        .unwrap_or_default()
+}
+
+pub(crate) fn awaiting_judgment(execution: &types::Execution) -> Option<&types::Stage> {
+    execution
+        .stages
+        .iter()
+        .find(|stage| stage.stage_type == MANUAL_JUDGMENT_STAGE && stage.status == "RUNNING")
+}
+
+pub(crate) fn execution_url(ui_url: &str, execution: &types::Execution) -> String {
+    format!(
+        "{}/#/applications/{}/executions/details/{}",
+        ui_url, execution.application, execution.id
+    )
+}
+
+fn map_stages(execution: &types::Execution) -> serde_json::Value {
+    execution
+        .stages
+        .iter()
+        .map(|stage| {
+            let duration_seconds = stage
+                .start_time
+                .zip(stage.end_time)
+                .map(|(start, end)| (end - start) / 1000);
+            serde_json::json!({
+                "id": stage.id,
+                "name": stage.name,
+                "type": stage.stage_type,
+                "status": map_status(&stage.status),
+                "started_at": from_millis(stage.start_time),
+                "duration_seconds": duration_seconds,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn map_execution_to_run(
+    execution: &types::Execution, provider_id: i64, ui_url: &str,
+) -> PipelineRun {
+    let started_at =
+        from_millis(execution.start_time.or(execution.build_time)).unwrap_or_else(Utc::now);
+    let concluded_at = from_millis(execution.end_time);
+    let duration_seconds = concluded_at.map(|end| (end - started_at).num_seconds());
+
+    let mut metadata = HashMap::new();
+    metadata.insert("execution_id".to_string(), serde_json::json!(execution.id));
+    metadata.insert("stages".to_string(), map_stages(execution));
+    metadata.insert(
+        "stage_count".to_string(),
+        serde_json::json!(execution.stages.len()),
+    );
+    if let Some(trigger_type) = &execution.trigger.trigger_type {
+        metadata.insert("trigger_type".to_string(), serde_json::json!(trigger_type));
+    }
+    if let Some(stage) = awaiting_judgment(execution) {
+        metadata.insert(
+            "awaiting_judgment".to_string(),
+            serde_json::json!(stage.name),
+        );
+    }
+
+    PipelineRun {
+        id: execution.id.clone(),
+        pipeline_id: config::build_pipeline_id(
+            provider_id,
+            &execution.application,
+            &execution.name,
+        ),
+        run_number: run_number(execution),
+        status: map_status(&execution.status),
+        started_at,
+        concluded_at,
+        duration_seconds,
+        logs_url: execution_url(ui_url, execution),
+        commit_sha: execution.trigger.hash.clone(),
+        commit_message: None,
+        branch: execution.trigger.branch.clone(),
+        actor: execution.trigger.user.clone(),
+        commit_author: None,
+        author_avatar_url: None,
+        pull_request: None,
+        inputs: execution
+            .trigger
+            .parameters
+            .as_ref()
+            .filter(|parameters| !parameters.is_empty())
+            .map(|parameters| serde_json::json!(parameters)),
+        metadata,
+        annotations: Vec::new(),
+    }
+}
+
+pub(crate) fn map_pipeline(
+    pipeline_config: &types::PipelineConfig, latest: Option<&types::Execution>, provider_id: i64,
+) -> Pipeline {
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "application".to_string(),
+        serde_json::json!(pipeline_config.application),
+    );
+    metadata.insert(
+        "pipeline_config_id".to_string(),
+        serde_json::json!(pipeline_config.id),
+    );
+    metadata.insert(
+        "disabled".to_string(),
+        serde_json::json!(pipeline_config.disabled),
+    );
+    if let Some(stage) = latest.and_then(awaiting_judgment) {
+        metadata.insert(
+            "awaiting_judgment".to_string(),
+            serde_json::json!(stage.name),
+        );
+    }
+
+    let last_run = latest.and_then(|execution| from_millis(execution.start_time));
+
+    Pipeline {
+        id: config::build_pipeline_id(
+            provider_id,
+            &pipeline_config.application,
+            &pipeline_config.name,
+        ),
+        provider_id,
+        provider_type: "spinnaker".to_string(),
+        name: pipeline_config.name.clone(),
+        status: latest
+            .map(|execution| map_status(&execution.status))
+            .unwrap_or(PipelineStatus::Pending),
+        last_run,
+        last_updated: last_run.unwrap_or_else(Utc::now),
+        repository: pipeline_config.application.clone(),
+        branch: latest.and_then(|execution| execution.trigger.branch.clone()),
+        workflow_file: None,
+        metadata,
+    }
+}
+
+pub(crate) fn map_available_pipeline(pipeline_config: &types::PipelineConfig) -> AvailablePipeline {
+    AvailablePipeline {
+        id: format!("{}/{}", pipeline_config.application, pipeline_config.name),
+        name: pipeline_config.name.clone(),
+        description: pipeline_config.description.clone(),
+        organization: Some(pipeline_config.application.clone()),
+        repository: Some(pipeline_config.application.clone()),
+    }
+}
+
+pub(crate) fn map_parameters(pipeline_config: &types::PipelineConfig) -> Vec<WorkflowParameter> {
+    let mut parameters: Vec<WorkflowParameter> = pipeline_config
+        .parameter_config
+        .iter()
+        .map(|parameter| {
+            let param_type = if parameter.has_options && !parameter.options.is_empty() {
+                WorkflowParameterType::Choice {
+                    options: parameter
+                        .options
+                        .iter()
+                        .map(|option| option.value.clone())
+                        .collect(),
+                    default: parameter.default.clone(),
+                }
+            } else {
+                WorkflowParameterType::String {
+                    default: parameter.default.clone(),
+                }
+            };
+
+            WorkflowParameter {
+                name: parameter.name.clone(),
+                label: parameter.label.clone(),
+                description: parameter.description.clone(),
+                param_type,
+                required: parameter.required,
+            }
+        })
+        .collect();
+
+    let has_judgment = pipeline_config
+        .stages
+        .iter()
+        .any(|stage| stage.stage_type == MANUAL_JUDGMENT_STAGE);
+
+    if has_judgment {
+        parameters.push(WorkflowParameter {
+            name: config::JUDGMENT_PARAM.to_string(),
+            label: Some("Manual Judgment".to_string()),
+            description: Some(
+                "Answer the waiting manual judgment instead of starting a new run".to_string(),
+            ),
+            param_type: WorkflowParameterType::Choice {
+                options: vec!["continue".to_string(), "stop".to_string()],
+                default: None,
+            },
+            required: false,
+        });
+        parameters.push(WorkflowParameter {
+            name: config::JUDGMENT_INPUT_PARAM.to_string(),
+            label: Some("Judgment Input".to_string()),
+            description: Some("Optional input passed along with the judgment".to_string()),
+            param_type: WorkflowParameterType::String { default: None },
+            required: false,
+        });
+    }
+
+    parameters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execution(stages: serde_json::Value) -> types::Execution {
+        serde_json::from_value(serde_json::json!({
+            "id": "01HXYZ",
+            "name": "Deploy",
+            "application": "checkout",
+            "status": "RUNNING",
+            "buildTime": 1_700_000_000_000i64,
+            "startTime": 1_700_000_001_000i64,
+            "trigger": { "type": "manual", "user": "alice", "parameters": { "env": "prod" } },
+            "stages": stages,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_map_status() {
+        assert_eq!(map_status("SUCCEEDED"), PipelineStatus::Success);
+        assert_eq!(map_status("TERMINAL"), PipelineStatus::Failed);
+        assert_eq!(map_status("RUNNING"), PipelineStatus::Running);
+        assert_eq!(map_status("CANCELED"), PipelineStatus::Cancelled);
+        assert_eq!(map_status("SKIPPED"), PipelineStatus::Skipped);
+        assert_eq!(map_status("BUFFERED"), PipelineStatus::Queued);
+        assert_eq!(map_status("PAUSED"), PipelineStatus::Waiting);
+        assert_eq!(map_status("unknown"), PipelineStatus::Pending);
+    }
+
+    #[test]
+    fn test_map_execution_to_run() {
+        let execution = execution(serde_json::json!([
+            { "id": "s1", "name": "Bake", "type": "bake", "status": "SUCCEEDED",
+              "startTime": 1_700_000_001_000i64, "endTime": 1_700_000_061_000i64 },
+            { "id": "s2", "name": "Approve", "type": "manualJudgment", "status": "RUNNING",
+              "startTime": 1_700_000_061_000i64 },
+        ]));
+
+        let run = map_execution_to_run(&execution, 7, "https://deck.example.com");
+
+        assert_eq!(run.pipeline_id, "spinnaker__7__checkout__Deploy");
+        assert_eq!(run.run_number, 1_700_000_000_000);
+        assert_eq!(run.status, PipelineStatus::Running);
+        assert_eq!(run.actor.as_deref(), Some("alice"));
+        assert_eq!(
+            run.logs_url,
+            "https://deck.example.com/#/applications/checkout/executions/details/01HXYZ"
+        );
+        assert_eq!(run.metadata["stages"][0]["duration_seconds"], 60);
+        assert_eq!(run.metadata["awaiting_judgment"], "Approve");
+        assert_eq!(awaiting_judgment(&execution).unwrap().id, "s2");
+    }
+}
@@ -0,0 +1,98 @@
+use pipedash_plugin_api::*;
+
+use crate::schema;
+
+pub fn create_metadata() -> PluginMetadata {
+    PluginMetadata {
+        name: "Spinnaker".to_string(),
+        provider_type: "spinnaker".to_string(),
+        version: "0.1.0".to_string(),
+        description: "Monitor and trigger Spinnaker pipelines through Gate".to_string(),
+        author: Some("Pipedash Team".to_string()),
+        icon: Some("https://cdn.simpleicons.org/spinnaker/139BB4".to_string()),
+        config_schema: create_config_schema(),
+        table_schema: schema::create_table_schema(),
+        capabilities: create_capabilities(),
+        required_permissions: Vec::new(),
+        features: Vec::new(),
+    }
+}
+
+fn create_config_schema() -> ConfigSchema {
+    ConfigSchema::new()
+        .add_field(ConfigField {
+            key: "gate_url".to_string(),
+            label: "Gate URL".to_string(),
+            description: Some(
+                "Spinnaker Gate API URL (e.g., https://gate.spinnaker.example.com)".to_string(),
+            ),
+            field_type: ConfigFieldType::Text,
+            required: true,
+            default_value: None,
+            options: None,
+            validation_regex: Some(r"^https?://.*".to_string()),
+            validation_message: Some("Must be a valid HTTP or HTTPS URL".to_string()),
+        })
+        .add_field(ConfigField {
+            key: "deck_url".to_string(),
+            label: "Deck URL (optional)".to_string(),
+            description: Some(
+                "Spinnaker UI URL used for execution links. Defaults to the Gate URL".to_string(),
+            ),
+            field_type: ConfigFieldType::Text,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: Some(r"^https?://.*".to_string()),
+            validation_message: Some("Must be a valid HTTP or HTTPS URL".to_string()),
+        })
+        .add_field(ConfigField {
+            key: "token".to_string(),
+            label: "Bearer Token (optional)".to_string(),
+            description: Some(
+                "Token sent as a bearer credential, e.g. from an OAuth2 proxy in front of Gate"
+                    .to_string(),
+            ),
+            field_type: ConfigFieldType::Password,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+        .add_field(ConfigField {
+            key: "username".to_string(),
+            label: "Username (optional)".to_string(),
+            description: Some("Username for Gate basic authentication".to_string()),
+            field_type: ConfigFieldType::Text,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+        .add_field(ConfigField {
+            key: "password".to_string(),
+            label: "Password (optional)".to_string(),
+            description: Some("Password for Gate basic authentication".to_string()),
+            field_type: ConfigFieldType::Password,
+            required: false,
+            default_value: None,
+            options: None,
+            validation_regex: None,
+            validation_message: None,
+        })
+        .with_network_fields()
+        .with_client_certificate_fields()
+}
+
+fn create_capabilities() -> PluginCapabilities {
+    PluginCapabilities {
+        pipelines: true,
+        pipeline_runs: true,
+        trigger: true,
+        cancel: true,
+        workflow_parameters: true,
+        ..PluginCapabilities::default()
+    }
+}
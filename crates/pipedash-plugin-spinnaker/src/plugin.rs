@@ -0,0 +1,472 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use futures::stream::{
+    self,
+    StreamExt,
+};
+use pipedash_plugin_api::*;
+use tracing::{
+    debug,
+    info,
+    warn,
+};
+
+use crate::{
+    client,
+    config,
+    mapper,
+    metadata,
+    types,
+};
+
+const DEFAULT_PAGE_SIZE: usize = 1000;
+
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+// How many recent executions per pipeline are searched when looking up a run
+// by number or a stage waiting for judgment.
+const EXECUTION_SCAN_LIMIT: usize = 50;
+
+pub struct SpinnakerPlugin {
+    metadata: PluginMetadata,
+    client: Option<client::SpinnakerClient>,
+    provider_id: Option<i64>,
+    config: HashMap<String, String>,
+    ui_url: Option<String>,
+}
+
+impl Default for SpinnakerPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpinnakerPlugin {
+    pub fn new() -> Self {
+        Self {
+            metadata: metadata::create_metadata(),
+            client: None,
+            provider_id: None,
+            config: HashMap::new(),
+            ui_url: None,
+        }
+    }
+
+    fn client(&self) -> PluginResult<&client::SpinnakerClient> {
+        self.client
+            .as_ref()
+            .ok_or_else(|| PluginError::Internal("Plugin not initialized".to_string()))
+    }
+
+    fn provider_id(&self) -> PluginResult<i64> {
+        self.provider_id
+            .ok_or_else(|| PluginError::Internal("Provider ID not set".to_string()))
+    }
+
+    fn ui_url(&self) -> PluginResult<&str> {
+        self.ui_url
+            .as_deref()
+            .ok_or_else(|| PluginError::Internal("UI URL not set".to_string()))
+    }
+
+    // Applications the token can't read are skipped rather than failing the
+    // whole listing.
+    async fn list_pipeline_configs(
+        &self, applications: Vec<String>,
+    ) -> PluginResult<Vec<types::PipelineConfig>> {
+        let client = self.client()?;
+
+        let results: Vec<_> = stream::iter(applications)
+            .map(|application| async move {
+                let configs = client.list_pipeline_configs(&application).await;
+                (application, configs)
+            })
+            .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+
+        let mut pipeline_configs = Vec::new();
+        for (application, result) in results {
+            match result {
+                Ok(configs) => pipeline_configs.extend(configs),
+                Err(e) => warn!(application, error = %e, "Failed to list pipeline configs"),
+            }
+        }
+
+        pipeline_configs.sort_by(|a, b| {
+            a.application
+                .cmp(&b.application)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(pipeline_configs)
+    }
+
+    async fn list_application_names(&self) -> PluginResult<Vec<String>> {
+        let applications = self.client()?.list_applications().await?;
+        Ok(applications.into_iter().map(|app| app.name).collect())
+    }
+
+    async fn pipeline_executions(
+        &self, application: &str, pipeline: &str, limit: usize,
+    ) -> PluginResult<Vec<types::Execution>> {
+        let mut executions: Vec<types::Execution> = self
+            .client()?
+            .list_executions(application, limit)
+            .await?
+            .into_iter()
+            .filter(|execution| execution.name == pipeline)
+            .collect();
+
+        executions.sort_by_key(|execution| std::cmp::Reverse(mapper::run_number(execution)));
+        executions.truncate(limit);
+        Ok(executions)
+    }
+
+    async fn find_execution(
+        &self, pipeline_id: &str, run_number: i64,
+    ) -> PluginResult<types::Execution> {
+        let (_provider_id, application, pipeline) = config::parse_pipeline_id(pipeline_id)?;
+
+        self.pipeline_executions(&application, &pipeline, EXECUTION_SCAN_LIMIT)
+            .await?
+            .into_iter()
+            .find(|execution| mapper::run_number(execution) == run_number)
+            .ok_or_else(|| PluginError::PipelineNotFound(format!("Run {} not found", run_number)))
+    }
+
+    async fn submit_judgment(
+        &self, application: &str, pipeline: &str, judgment: String, input: Option<String>,
+    ) -> PluginResult<String> {
+        if judgment != "continue" && judgment != "stop" {
+            return Err(PluginError::InvalidConfig(format!(
+                "Invalid manual judgment '{}'. Expected 'continue' or 'stop'",
+                judgment
+            )));
+        }
+
+        let executions = self
+            .pipeline_executions(application, pipeline, EXECUTION_SCAN_LIMIT)
+            .await?;
+        let (execution, stage) = executions
+            .iter()
+            .find_map(|execution| {
+                mapper::awaiting_judgment(execution).map(|stage| (execution, stage))
+            })
+            .ok_or_else(|| {
+                PluginError::InvalidConfig(format!(
+                    "No execution of '{}' is waiting for a manual judgment",
+                    pipeline
+                ))
+            })?;
+
+        info!(
+            application,
+            pipeline,
+            execution_id = %execution.id,
+            stage = %stage.name,
+            judgment,
+            "Submitting Spinnaker manual judgment"
+        );
+
+        self.client()?
+            .submit_judgment(
+                &execution.id,
+                &stage.id,
+                types::JudgmentRequest {
+                    judgment_status: judgment.clone(),
+                    judgment_input: input,
+                },
+            )
+            .await?;
+
+        Ok(format!(
+            "Manual judgment '{}' submitted for stage '{}' of execution {}",
+            judgment, stage.name, execution.id
+        ))
+    }
+
+    fn paginate(
+        items: Vec<AvailablePipeline>, params: Option<PaginationParams>,
+    ) -> PaginatedResponse<AvailablePipeline> {
+        let total_count = items.len();
+        let (page, page_size) = match params {
+            Some(p) => (
+                p.page.max(1),
+                if p.page_size == 0 {
+                    DEFAULT_PAGE_SIZE
+                } else {
+                    p.page_size
+                },
+            ),
+            None => (1, DEFAULT_PAGE_SIZE),
+        };
+
+        let items = items
+            .into_iter()
+            .skip((page - 1) * page_size)
+            .take(page_size)
+            .collect();
+
+        PaginatedResponse::new(items, page, page_size, total_count)
+    }
+}
+
+fn input_to_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) if s.is_empty() => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[async_trait]
+impl Plugin for SpinnakerPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    fn provider_type(&self) -> &str {
+        "spinnaker"
+    }
+
+    fn initialize(
+        &mut self, provider_id: i64, config: HashMap<String, String>,
+        http_client: Option<std::sync::Arc<reqwest::Client>>,
+    ) -> PluginResult<()> {
+        info!(provider_id, "Initializing Spinnaker plugin");
+
+        let gate_url = config::get_gate_url(&config)?;
+        let auth = config::get_auth(&config)?;
+        let ui_url = config::get_deck_url(&config).unwrap_or_else(|| gate_url.clone());
+        debug!(gate_url, ui_url, "Configured Spinnaker URLs");
+
+        self.client = Some(
+            client::SpinnakerClient::new(http_client, gate_url, auth)?
+                .with_max_response_bytes(max_response_bytes(&config)),
+        );
+        self.provider_id = Some(provider_id);
+        self.ui_url = Some(ui_url);
+        self.config = config;
+
+        Ok(())
+    }
+
+    fn api_endpoint(&self) -> Option<String> {
+        config::get_gate_url(&self.config).ok()
+    }
+
+    async fn validate_credentials(&self) -> PluginResult<bool> {
+        let applications = self.client()?.list_applications().await?;
+        info!(
+            application_count = applications.len(),
+            "Spinnaker credentials validated"
+        );
+        Ok(true)
+    }
+
+    async fn fetch_organizations(&self) -> PluginResult<Vec<Organization>> {
+        let mut applications = self.client()?.list_applications().await?;
+        applications.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(applications
+            .into_iter()
+            .map(|app| Organization {
+                id: app.name.clone(),
+                name: app.name,
+                description: app.description,
+            })
+            .collect())
+    }
+
+    async fn fetch_available_pipelines(
+        &self, params: Option<PaginationParams>,
+    ) -> PluginResult<PaginatedResponse<AvailablePipeline>> {
+        let applications = self.list_application_names().await?;
+        let pipelines = self
+            .list_pipeline_configs(applications)
+            .await?
+            .iter()
+            .map(mapper::map_available_pipeline)
+            .collect();
+
+        Ok(Self::paginate(pipelines, params))
+    }
+
+    async fn fetch_available_pipelines_filtered(
+        &self, org: Option<String>, search: Option<String>, params: Option<PaginationParams>,
+    ) -> PluginResult<PaginatedResponse<AvailablePipeline>> {
+        let applications = match org {
+            Some(application) => vec![application],
+            None => self.list_application_names().await?,
+        };
+
+        let search = search.map(|s| s.to_lowercase());
+        let pipelines = self
+            .list_pipeline_configs(applications)
+            .await?
+            .iter()
+            .filter(|pipeline_config| {
+                search.as_ref().is_none_or(|s| {
+                    pipeline_config.name.to_lowercase().contains(s)
+                        || pipeline_config.application.to_lowercase().contains(s)
+                })
+            })
+            .map(mapper::map_available_pipeline)
+            .collect();
+
+        Ok(Self::paginate(pipelines, params))
+    }
+
+    async fn fetch_pipelines(&self) -> PluginResult<Vec<Pipeline>> {
+        let provider_id = self.provider_id()?;
+        let client = self.client()?;
+
+        let selected = config::parse_selected_items(&self.config);
+        let mut applications: Vec<String> = match &selected {
+            Some(items) => items.iter().map(|(app, _)| app.clone()).collect(),
+            None => self.list_application_names().await?,
+        };
+        applications.sort();
+        applications.dedup();
+
+        let mut pipeline_configs = self.list_pipeline_configs(applications.clone()).await?;
+        if let Some(items) = &selected {
+            pipeline_configs.retain(|pipeline_config| {
+                items.iter().any(|(app, name)| {
+                    *app == pipeline_config.application && *name == pipeline_config.name
+                })
+            });
+        }
+
+        let latest_results: Vec<_> = stream::iter(applications)
+            .map(|application| async move {
+                let executions = client.list_executions(&application, 1).await;
+                (application, executions)
+            })
+            .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+
+        let mut latest: HashMap<(String, String), types::Execution> = HashMap::new();
+        for (application, result) in latest_results {
+            let executions = match result {
+                Ok(executions) => executions,
+                Err(e) => {
+                    warn!(application, error = %e, "Failed to fetch latest executions");
+                    continue;
+                }
+            };
+            for execution in executions {
+                let key = (execution.application.clone(), execution.name.clone());
+                let newer = latest.get(&key).is_none_or(|existing| {
+                    mapper::run_number(existing) < mapper::run_number(&execution)
+                });
+                if newer {
+                    latest.insert(key, execution);
+                }
+            }
+        }
+
+        let pipelines: Vec<Pipeline> = pipeline_configs
+            .iter()
+            .map(|pipeline_config| {
+                let key = (
+                    pipeline_config.application.clone(),
+                    pipeline_config.name.clone(),
+                );
+                mapper::map_pipeline(pipeline_config, latest.get(&key), provider_id)
+            })
+            .collect();
+
+        debug!(
+            pipeline_count = pipelines.len(),
+            "Fetched Spinnaker pipelines"
+        );
+        Ok(pipelines)
+    }
+
+    async fn fetch_run_history(
+        &self, pipeline_id: &str, limit: usize,
+    ) -> PluginResult<Vec<PipelineRun>> {
+        let (provider_id, application, pipeline) = config::parse_pipeline_id(pipeline_id)?;
+        let ui_url = self.ui_url()?;
+
+        let executions = self
+            .pipeline_executions(&application, &pipeline, limit)
+            .await?;
+
+        Ok(executions
+            .iter()
+            .map(|execution| mapper::map_execution_to_run(execution, provider_id, ui_url))
+            .collect())
+    }
+
+    async fn fetch_run_details(
+        &self, pipeline_id: &str, run_number: i64,
+    ) -> PluginResult<PipelineRun> {
+        let (provider_id, _application, _pipeline) = config::parse_pipeline_id(pipeline_id)?;
+        let execution = self.find_execution(pipeline_id, run_number).await?;
+
+        Ok(mapper::map_execution_to_run(
+            &execution,
+            provider_id,
+            self.ui_url()?,
+        ))
+    }
+
+    async fn trigger_pipeline(&self, params: TriggerParams) -> PluginResult<String> {
+        let (_provider_id, application, pipeline) = config::parse_pipeline_id(&params.workflow_id)?;
+
+        let mut inputs: HashMap<String, String> = params
+            .inputs
+            .as_ref()
+            .and_then(|inputs| inputs.as_object())
+            .map(|inputs| {
+                inputs
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), input_to_string(value)?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let judgment_input = inputs.remove(config::JUDGMENT_INPUT_PARAM);
+        if let Some(judgment) = inputs.remove(config::JUDGMENT_PARAM) {
+            return self
+                .submit_judgment(&application, &pipeline, judgment, judgment_input)
+                .await;
+        }
+
+        info!(application, pipeline, parameters = ?inputs, "Triggering Spinnaker pipeline");
+
+        let execution_id = self
+            .client()?
+            .trigger_pipeline(&application, &pipeline, inputs)
+            .await?;
+
+        Ok(format!(
+            "Triggered pipeline '{}' in application '{}' (execution {})",
+            pipeline, application, execution_id
+        ))
+    }
+
+    async fn cancel_run(&self, pipeline_id: &str, run_number: i64) -> PluginResult<()> {
+        let execution = self.find_execution(pipeline_id, run_number).await?;
+
+        info!(execution_id = %execution.id, "Cancelling Spinnaker execution");
+        self.client()?.cancel_execution(&execution.id).await
+    }
+
+    async fn fetch_workflow_parameters(
+        &self, workflow_id: &str,
+    ) -> PluginResult<Vec<WorkflowParameter>> {
+        let (_provider_id, application, pipeline) = config::parse_pipeline_id(workflow_id)?;
+        let pipeline_config = self
+            .client()?
+            .get_pipeline_config(&application, &pipeline)
+            .await?;
+
+        Ok(mapper::map_parameters(&pipeline_config))
+    }
+}
@@ -0,0 +1,133 @@
+use pipedash_plugin_api::*;
+
+pub fn create_table_schema() -> schema::TableSchema {
+    schema::TableSchema::new()
+        .add_table(create_pipeline_runs_table())
+        .add_table(create_pipelines_table())
+}
+
+fn create_pipelines_table() -> schema::TableDefinition {
+    let mut table = pipedash_plugin_api::defaults::default_pipelines_table();
+
+    let status_index = table
+        .columns
+        .iter()
+        .position(|c| c.id == "status")
+        .unwrap_or(2);
+
+    table
+        .columns
+        .insert(status_index + 1, create_application_column());
+    table
+        .columns
+        .insert(status_index + 2, create_awaiting_judgment_column());
+
+    table
+}
+
+fn create_pipeline_runs_table() -> schema::TableDefinition {
+    let mut table = pipedash_plugin_api::defaults::default_pipeline_runs_table();
+
+    let status_index = table
+        .columns
+        .iter()
+        .position(|c| c.id == "status")
+        .unwrap_or(1);
+
+    table
+        .columns
+        .insert(status_index + 1, create_trigger_type_column());
+    table
+        .columns
+        .insert(status_index + 2, create_awaiting_judgment_column());
+
+    table.columns.push(create_stage_count_column());
+    table.columns.push(create_stages_column());
+
+    table
+}
+
+fn create_application_column() -> schema::ColumnDefinition {
+    schema::ColumnDefinition {
+        id: "application".to_string(),
+        label: "Application".to_string(),
+        description: Some("Spinnaker application the pipeline belongs to".to_string()),
+        field_path: "metadata.application".to_string(),
+        data_type: schema::ColumnDataType::String,
+        renderer: schema::CellRenderer::Badge,
+        visibility: schema::ColumnVisibility::WhenPresent,
+        default_visible: true,
+        width: Some(140),
+        sortable: true,
+        filterable: true,
+        align: None,
+    }
+}
+
+fn create_awaiting_judgment_column() -> schema::ColumnDefinition {
+    schema::ColumnDefinition {
+        id: "awaiting_judgment".to_string(),
+        label: "Awaiting Judgment".to_string(),
+        description: Some("Manual judgment stage waiting for a response".to_string()),
+        field_path: "metadata.awaiting_judgment".to_string(),
+        data_type: schema::ColumnDataType::String,
+        renderer: schema::CellRenderer::Badge,
+        visibility: schema::ColumnVisibility::WhenPresent,
+        default_visible: true,
+        width: Some(160),
+        sortable: false,
+        filterable: true,
+        align: None,
+    }
+}
+
+fn create_trigger_type_column() -> schema::ColumnDefinition {
+    schema::ColumnDefinition {
+        id: "trigger_type".to_string(),
+        label: "Trigger".to_string(),
+        description: Some("What started the execution".to_string()),
+        field_path: "metadata.trigger_type".to_string(),
+        data_type: schema::ColumnDataType::String,
+        renderer: schema::CellRenderer::Badge,
+        visibility: schema::ColumnVisibility::WhenPresent,
+        default_visible: true,
+        width: Some(110),
+        sortable: true,
+        filterable: true,
+        align: None,
+    }
+}
+
+fn create_stage_count_column() -> schema::ColumnDefinition {
+    schema::ColumnDefinition {
+        id: "stage_count".to_string(),
+        label: "Stages".to_string(),
+        description: Some("Number of stages in the execution".to_string()),
+        field_path: "metadata.stage_count".to_string(),
+        data_type: schema::ColumnDataType::Number,
+        renderer: schema::CellRenderer::Text,
+        visibility: schema::ColumnVisibility::Always,
+        default_visible: false,
+        width: Some(90),
+        sortable: true,
+        filterable: false,
+        align: Some("right".to_string()),
+    }
+}
+
+fn create_stages_column() -> schema::ColumnDefinition {
+    schema::ColumnDefinition {
+        id: "stages".to_string(),
+        label: "Stage Breakdown".to_string(),
+        description: Some("Status and duration of every stage".to_string()),
+        field_path: "metadata.stages".to_string(),
+        data_type: schema::ColumnDataType::Json,
+        renderer: schema::CellRenderer::JsonViewer,
+        visibility: schema::ColumnVisibility::Always,
+        default_visible: false,
+        width: Some(300),
+        sortable: false,
+        filterable: false,
+        align: None,
+    }
+}
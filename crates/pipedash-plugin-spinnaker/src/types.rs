@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Application {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineConfig {
+    pub id: String,
+    pub name: String,
+    pub application: String,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parameter_config: Vec<ParameterConfig>,
+    #[serde(default)]
+    pub stages: Vec<StageConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParameterConfig {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub has_options: bool,
+    #[serde(default)]
+    pub options: Vec<ParameterOption>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterOption {
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StageConfig {
+    #[serde(rename = "type")]
+    pub stage_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Execution {
+    pub id: String,
+    pub name: String,
+    pub application: String,
+    pub status: String,
+    #[serde(default)]
+    pub build_time: Option<i64>,
+    #[serde(default)]
+    pub start_time: Option<i64>,
+    #[serde(default)]
+    pub end_time: Option<i64>,
+    #[serde(default)]
+    pub trigger: Trigger,
+    #[serde(default)]
+    pub stages: Vec<Stage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trigger {
+    #[serde(default, rename = "type")]
+    pub trigger_type: Option<String>,
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub hash: Option<String>,
+    #[serde(default)]
+    pub parameters: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stage {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub stage_type: String,
+    pub status: String,
+    #[serde(default)]
+    pub start_time: Option<i64>,
+    #[serde(default)]
+    pub end_time: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TriggerRequest {
+    #[serde(rename = "type")]
+    pub trigger_type: &'static str,
+    pub parameters: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JudgmentRequest {
+    pub judgment_status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub judgment_input: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TriggerResponse {
+    #[serde(rename = "ref")]
+    pub reference: String,
+}
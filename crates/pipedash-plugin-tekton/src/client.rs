@@ -12,13 +12,17 @@ use crate::{
     types::{
         PipelineList,
         PipelineRunList,
+        ProjectList,
+        TaskRunList,
         TektonPipeline,
         TektonPipelineRun,
+        TektonTaskRun,
     },
 };
 
 pub struct TektonClient {
     client: Client,
+    default_namespace: String,
     retry_policy: RetryPolicy,
 }
 
@@ -68,15 +72,19 @@ impl TektonClient {
 
     pub async fn from_kubeconfig(
         kubeconfig_path: Option<&str>, context: Option<&str>,
+        client_certificate: Option<&config::ClientCertificate>,
     ) -> PluginResult<Self> {
-        let kubeconfig = if let Some(path_str) = kubeconfig_path {
-            let paths = config::split_kubeconfig_paths(path_str);
-            Self::merge_kubeconfigs(paths)?
-        } else {
-            let default_path = config::get_default_kubeconfig_path();
-            let paths = config::split_kubeconfig_paths(&default_path);
-            Self::merge_kubeconfigs(paths)?
-        };
+        let mut kubeconfig =
+            Self::merge_kubeconfigs(config::resolve_kubeconfig_paths(kubeconfig_path))?;
+
+        for exec in kubeconfig
+            .auth_infos
+            .iter_mut()
+            .filter_map(|named| named.auth_info.as_mut())
+            .filter_map(|auth_info| auth_info.exec.as_mut())
+        {
+            config::prepare_exec(exec);
+        }
 
         let options = if let Some(ctx) = context {
             kube::config::KubeConfigOptions {
@@ -87,49 +95,56 @@ impl TektonClient {
             kube::config::KubeConfigOptions::default()
         };
 
-        let config = kube::Config::from_custom_kubeconfig(kubeconfig, &options)
+        let mut kube_config = kube::Config::from_custom_kubeconfig(kubeconfig, &options)
             .await
             .map_err(|e| PluginError::InvalidConfig(format!("Failed to load kubeconfig: {}", e)))?;
 
-        let client = Client::try_from(config).map_err(|e| {
+        if let Some(client_certificate) = client_certificate {
+            client_certificate.apply(&mut kube_config.auth_info);
+        }
+
+        let default_namespace = kube_config.default_namespace.clone();
+        let client = Client::try_from(kube_config).map_err(|e| {
             PluginError::InvalidConfig(format!("Failed to create Kubernetes client: {}", e))
         })?;
 
         Ok(Self {
             client,
+            default_namespace,
             retry_policy: RetryPolicy::default(),
         })
     }
 
-    pub async fn list_namespaces(&self) -> PluginResult<Vec<String>> {
-        self.retry_policy
-            .retry(|| async {
-                use kube::api::{
-                    Api,
-                    ListParams,
-                };
-
-                let namespaces_api: Api<k8s_openapi::api::core::v1::Namespace> =
-                    Api::all(self.client.clone());
-
-                let namespaces =
-                    namespaces_api
-                        .list(&ListParams::default())
-                        .await
-                        .map_err(|e| {
-                            PluginError::ApiError(format!("Failed to list namespaces: {}", e))
-                        })?;
-
-                Ok(namespaces
-                    .items
-                    .into_iter()
-                    .filter_map(|ns| ns.metadata.name)
-                    .collect())
-            })
-            .await
+    // The namespace set on the kubeconfig context, or "default".
+    pub fn default_namespace(&self) -> &str {
+        &self.default_namespace
     }
 
-    pub async fn try_list_namespaces_cluster_wide(&self) -> PluginResult<Vec<String>> {
+    pub async fn try_list_accessible_namespaces(&self) -> PluginResult<Vec<String>> {
+        self.list_accessible_namespaces().await?.ok_or_else(|| {
+            PluginError::ApiError(
+                "Missing permissions to list namespaces or OpenShift projects. Please use 'custom' mode and specify namespaces manually in the configuration.".to_string()
+            )
+        })
+    }
+
+    // Every namespace when the credentials may list them, otherwise the
+    // OpenShift projects the user can see. `None` when neither is allowed.
+    pub async fn list_accessible_namespaces(&self) -> PluginResult<Option<Vec<String>>> {
+        if let Some(namespaces) = self.list_namespaces_if_permitted().await? {
+            return Ok(Some(namespaces));
+        }
+
+        let projects = self.list_projects_if_permitted().await?;
+        if projects.is_some() {
+            tracing::debug!("Namespace listing forbidden, using OpenShift projects");
+        }
+        Ok(projects)
+    }
+
+    // `None` when the credentials aren't allowed to list namespaces, which is
+    // common for namespace-scoped service accounts and on OpenShift.
+    async fn list_namespaces_if_permitted(&self) -> PluginResult<Option<Vec<String>>> {
         use kube::api::{
             Api,
             ListParams,
@@ -139,28 +154,64 @@ impl TektonClient {
             Api::all(self.client.clone());
 
         match namespaces_api.list(&ListParams::default()).await {
-            Ok(namespaces) => Ok(namespaces
-                .items
-                .into_iter()
-                .filter_map(|ns| ns.metadata.name)
-                .collect()),
-            Err(e) => {
-                if let kube::Error::Api(api_error) = &e {
-                    if api_error.code == 403 {
-                        return Err(PluginError::ApiError(
-                            "Missing cluster-wide namespace permissions. Please use 'custom' mode and specify namespaces manually in the configuration.".to_string()
-                        ));
-                    }
-                }
-                Err(PluginError::ApiError(format!(
-                    "Failed to list namespaces: {}",
-                    e
-                )))
+            Ok(namespaces) => Ok(Some(
+                namespaces
+                    .items
+                    .into_iter()
+                    .filter_map(|ns| ns.metadata.name)
+                    .collect(),
+            )),
+            Err(kube::Error::Api(api_error)) if api_error.code == 403 => Ok(None),
+            Err(e) => Err(map_kube_error(e, "Failed to list namespaces")),
+        }
+    }
+
+    // OpenShift lets any user list the projects they're a member of, even
+    // when listing namespaces is forbidden. `None` elsewhere, where the API
+    // doesn't exist.
+    async fn list_projects_if_permitted(&self) -> PluginResult<Option<Vec<String>>> {
+        let request = http::Request::builder()
+            .uri("/apis/project.openshift.io/v1/projects")
+            .method(http::Method::GET)
+            .body(Vec::new())
+            .map_err(|e| PluginError::Internal(format!("Failed to build request: {}", e)))?;
+
+        match self.client.request_text(request).await {
+            Ok(body) => {
+                let projects: ProjectList = serde_json::from_str(&body).map_err(|e| {
+                    PluginError::SerializationError(format!("Failed to parse projects: {}", e))
+                })?;
+                Ok(Some(
+                    projects
+                        .items
+                        .into_iter()
+                        .map(|project| project.metadata.name)
+                        .collect(),
+                ))
             }
+            Err(kube::Error::Api(api_error)) if matches!(api_error.code, 403 | 404) => Ok(None),
+            Err(e) => Err(map_kube_error(e, "Failed to list projects")),
         }
     }
 
-    async fn filter_namespaces_with_pipelines(&self, namespaces: &[String]) -> Vec<String> {
+    // Namespaces with pipelines. Without permission to list namespaces or
+    // projects, only `fallback` is checked.
+    pub async fn discover_namespaces_with_pipelines(
+        &self, fallback: &[String],
+    ) -> PluginResult<Vec<String>> {
+        match self.list_accessible_namespaces().await? {
+            Some(namespaces) => Ok(self.filter_namespaces_with_pipelines(&namespaces).await),
+            None => {
+                tracing::debug!(
+                    ?fallback,
+                    "Namespace and project listing forbidden, checking fallback namespaces"
+                );
+                Ok(self.filter_namespaces_with_pipelines(fallback).await)
+            }
+        }
+    }
+
+    pub async fn filter_namespaces_with_pipelines(&self, namespaces: &[String]) -> Vec<String> {
         use futures::future::join_all;
 
         let check_futures = namespaces.iter().map(|namespace| {
@@ -182,11 +233,8 @@ impl TektonClient {
             .collect()
     }
 
-    pub async fn list_namespaces_with_pipelines(&self) -> PluginResult<Vec<String>> {
-        let all_namespaces = self.list_namespaces().await?;
-        Ok(self.filter_namespaces_with_pipelines(&all_namespaces).await)
-    }
-
+    // Checks each namespace on its own, so no cluster-scoped permission is
+    // needed. Fails when any of them can't be read, naming it.
     pub async fn validate_namespaces_have_pipelines(
         &self, namespaces: &[String],
     ) -> PluginResult<Vec<String>> {
@@ -227,17 +275,17 @@ impl TektonClient {
             }
         }
 
+        if !errors.is_empty() {
+            return Err(PluginError::InvalidConfig(format!(
+                "Failed to validate namespaces. Errors: {}",
+                errors.join("; ")
+            )));
+        }
+
         if valid_namespaces.is_empty() {
-            if errors.is_empty() {
-                return Err(PluginError::InvalidConfig(
-                    format!("No Tekton pipelines found in any of the specified namespaces: {:?}. Verify that Tekton is installed and pipelines exist in these namespaces.", namespaces)
-                ));
-            } else {
-                return Err(PluginError::InvalidConfig(format!(
-                    "Failed to validate namespaces. Errors: {}",
-                    errors.join("; ")
-                )));
-            }
+            return Err(PluginError::InvalidConfig(
+                format!("No Tekton pipelines found in any of the specified namespaces: {:?}. Verify that Tekton is installed and pipelines exist in these namespaces.", namespaces)
+            ));
         }
 
         Ok(valid_namespaces)
@@ -260,10 +308,11 @@ impl TektonClient {
                         PluginError::Internal(format!("Failed to build request: {}", e))
                     })?;
 
-                let response_body =
-                    self.client.request_text(request).await.map_err(|e| {
-                        PluginError::ApiError(format!("Failed to make request: {}", e))
-                    })?;
+                let response_body = self
+                    .client
+                    .request_text(request)
+                    .await
+                    .map_err(|e| map_kube_error(e, "Failed to make request"))?;
 
                 serde_json::from_str(&response_body).map_err(|e| {
                     PluginError::SerializationError(format!("Failed to parse response: {}", e))
@@ -293,10 +342,11 @@ impl TektonClient {
                         PluginError::Internal(format!("Failed to build request: {}", e))
                     })?;
 
-                let response_body =
-                    self.client.request_text(request).await.map_err(|e| {
-                        PluginError::ApiError(format!("Failed to make request: {}", e))
-                    })?;
+                let response_body = self
+                    .client
+                    .request_text(request)
+                    .await
+                    .map_err(|e| map_kube_error(e, "Failed to make request"))?;
 
                 serde_json::from_str(&response_body).map_err(|e| {
                     PluginError::SerializationError(format!("Failed to parse response: {}", e))
@@ -335,6 +385,50 @@ impl TektonClient {
         Ok(pipelinerun_list.items)
     }
 
+    pub async fn list_taskruns(
+        &self, namespace: &str, pipelinerun_name: &str,
+    ) -> PluginResult<Vec<TektonTaskRun>> {
+        let url = format!(
+            "/apis/tekton.dev/v1/namespaces/{}/taskruns?labelSelector=tekton.dev/pipelineRun={}",
+            namespace, pipelinerun_name
+        );
+        let taskrun_list: TaskRunList = self.request_json(&url).await?;
+        Ok(taskrun_list.items)
+    }
+
+    // `None` once the pod is gone, e.g. after the run was pruned.
+    pub async fn fetch_container_log(
+        &self, namespace: &str, pod: &str, container: &str,
+    ) -> PluginResult<Option<String>> {
+        let url = format!(
+            "/api/v1/namespaces/{}/pods/{}/log?container={}",
+            namespace, pod, container
+        );
+
+        self.retry_policy
+            .retry(|| async {
+                let request = http::Request::builder()
+                    .uri(&url)
+                    .method(http::Method::GET)
+                    .body(Vec::new())
+                    .map_err(|e| {
+                        PluginError::Internal(format!("Failed to build request: {}", e))
+                    })?;
+
+                match self.client.request_text(request).await {
+                    Ok(log) => Ok(Some(log)),
+                    // 400 while the container is still waiting to start.
+                    Err(kube::Error::Api(api_error))
+                        if api_error.code == 404 || api_error.code == 400 =>
+                    {
+                        Ok(None)
+                    }
+                    Err(e) => Err(map_kube_error(e, "Failed to fetch container log")),
+                }
+            })
+            .await
+    }
+
     pub async fn create_pipelinerun(
         &self, namespace: &str, pipelinerun: &TektonPipelineRun,
     ) -> PluginResult<TektonPipelineRun> {
@@ -362,12 +456,30 @@ impl TektonClient {
                         PluginError::Internal(format!("Failed to build request: {}", e))
                     })?;
 
-                self.client.request_text(request).await.map_err(|e| {
-                    PluginError::ApiError(format!("Failed to delete pipelinerun: {}", e))
-                })?;
+                self.client
+                    .request_text(request)
+                    .await
+                    .map_err(|e| map_kube_error(e, "Failed to delete pipelinerun"))?;
 
                 Ok(())
             })
             .await
     }
 }
+
+// 401s usually mean an expired token in the kubeconfig; surfacing them as auth
+// failures lets the caller reload it.
+fn map_kube_error(error: kube::Error, context: &str) -> PluginError {
+    match &error {
+        kube::Error::Api(api_error) if api_error.code == 401 => {
+            PluginError::AuthenticationFailed(format!(
+                "{}: {}. Check that the kubeconfig credentials are still valid.",
+                context, error
+            ))
+        }
+        kube::Error::Auth(_) => {
+            PluginError::AuthenticationFailed(format!("{}: {}", context, error))
+        }
+        _ => PluginError::ApiError(format!("{}: {}", context, error)),
+    }
+}
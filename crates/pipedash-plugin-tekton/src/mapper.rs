@@ -3,9 +3,11 @@ use std::collections::HashMap;
 use chrono::Utc;
 use pipedash_plugin_api::{
     AvailablePipeline,
+    LogStep,
     Pipeline,
     PipelineRun,
     PipelineStatus,
+    PullRequestRef,
     WorkflowParameter,
     WorkflowParameterType,
 };
@@ -37,7 +39,9 @@ pub(crate) fn map_status(conditions: &[types::Condition]) -> PipelineStatus {
         match succeeded_condition.status.as_str() {
             "True" => PipelineStatus::Success,
             "False" => {
-                if succeeded_condition.reason == "PipelineRunCancelled" {
+                if succeeded_condition.reason == "PipelineRunCancelled"
+                    || succeeded_condition.reason == "TaskRunCancelled"
+                {
                     PipelineStatus::Cancelled
                 } else {
                     PipelineStatus::Failed
@@ -166,6 +170,24 @@ pub(crate) fn map_pipeline_run(run: &TektonPipelineRun, provider_id: i64) -> Pip
             }
         });
 
+    // Set by Pipelines-as-Code on runs it starts from a git event.
+    let pac_value = |key: &str| {
+        run.metadata
+            .annotations
+            .get(key)
+            .or_else(|| run.metadata.labels.get(key))
+            .cloned()
+    };
+    let commit_message = pac_value("pipelinesascode.tekton.dev/sha-title");
+    let commit_author = pac_value("pipelinesascode.tekton.dev/sender");
+    let pull_request = pac_value("pipelinesascode.tekton.dev/pull-request")
+        .and_then(|number| number.parse::<i64>().ok())
+        .map(|number| PullRequestRef {
+            number,
+            title: None,
+            url: None,
+        });
+
     let logs_url = run
         .metadata
         .annotations
@@ -347,14 +369,93 @@ pub(crate) fn map_pipeline_run(run: &TektonPipelineRun, provider_id: i64) -> Pip
         duration_seconds,
         logs_url,
         commit_sha,
-        commit_message: None,
+        commit_message,
         branch,
         actor,
+        commit_author,
+        author_avatar_url: None,
+        pull_request,
         inputs,
         metadata,
+        annotations: Vec::new(),
     }
 }
 
+pub(crate) fn is_finished(status: PipelineStatus) -> bool {
+    !matches!(status, PipelineStatus::Running | PipelineStatus::Pending)
+}
+
+// The pipeline task the TaskRun was created for, falling back to its own name
+// for runs started outside a pipeline.
+pub(crate) fn task_name(taskrun: &types::TektonTaskRun) -> String {
+    taskrun
+        .metadata
+        .labels
+        .get("tekton.dev/pipelineTask")
+        .cloned()
+        .unwrap_or_else(|| taskrun.metadata.name.clone())
+}
+
+pub(crate) fn step_container(step: &types::StepState) -> String {
+    if step.container.is_empty() {
+        format!("step-{}", step.name)
+    } else {
+        step.container.clone()
+    }
+}
+
+pub(crate) fn map_step(index: usize, step: &types::StepState) -> LogStep {
+    let (status, started_at, completed_at) = match (&step.terminated, &step.running) {
+        (Some(terminated), _) => (
+            if terminated.exit_code == 0 {
+                PipelineStatus::Success
+            } else {
+                PipelineStatus::Failed
+            },
+            types::parse_timestamp(&terminated.started_at),
+            types::parse_timestamp(&terminated.finished_at),
+        ),
+        (None, Some(running)) => (
+            PipelineStatus::Running,
+            types::parse_timestamp(&running.started_at),
+            None,
+        ),
+        (None, None) => (PipelineStatus::Pending, None, None),
+    };
+
+    LogStep {
+        number: index as i64 + 1,
+        name: step.name.clone(),
+        status,
+        started_at,
+        completed_at,
+    }
+}
+
+// Each step runs in its own container, so the TaskRun log is their logs one
+// after the other. Steps run in order, which keeps the result append-only
+// while the task is running.
+pub(crate) fn combine_step_logs(logs: &[(String, Option<String>)]) -> String {
+    let mut content = String::new();
+    for (step, log) in logs {
+        let Some(log) = log else { continue };
+        content.push_str(&format!("==> {} <==\n", step));
+        content.push_str(log);
+        if !log.is_empty() && !log.ends_with('\n') {
+            content.push('\n');
+        }
+    }
+    content
+}
+
+pub(crate) fn slice_log(content: &str, offset: usize) -> &str {
+    let mut start = offset.min(content.len());
+    while !content.is_char_boundary(start) {
+        start += 1;
+    }
+    &content[start..]
+}
+
 pub(crate) fn map_available_pipeline(pipeline: &TektonPipeline) -> AvailablePipeline {
     let namespace = &pipeline.metadata.namespace;
     let pipeline_name = &pipeline.metadata.name;
@@ -404,3 +505,43 @@ pub(crate) fn map_workflow_parameters(pipeline: &TektonPipeline) -> Vec<Workflow
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_step() {
+        let step: types::StepState = serde_json::from_value(serde_json::json!({
+            "name": "build",
+            "container": "step-build",
+            "terminated": {
+                "exitCode": 1,
+                "reason": "Error",
+                "startedAt": "2024-01-01T10:00:00Z",
+                "finishedAt": "2024-01-01T10:01:00Z"
+            }
+        }))
+        .unwrap();
+        let log_step = map_step(1, &step);
+        assert_eq!(log_step.number, 2);
+        assert_eq!(log_step.status, PipelineStatus::Failed);
+        assert!(log_step.completed_at.is_some());
+
+        let waiting: types::StepState =
+            serde_json::from_value(serde_json::json!({"name": "test"})).unwrap();
+        assert_eq!(map_step(0, &waiting).status, PipelineStatus::Pending);
+        assert_eq!(step_container(&waiting), "step-test");
+    }
+
+    #[test]
+    fn test_combine_step_logs() {
+        let content = combine_step_logs(&[
+            ("clone".to_string(), Some("cloned".to_string())),
+            ("build".to_string(), Some("built\n".to_string())),
+            ("test".to_string(), None),
+        ]);
+        assert_eq!(content, "==> clone <==\ncloned\n==> build <==\nbuilt\n");
+        assert_eq!(slice_log(&content, content.len() - 6), "built\n");
+    }
+}
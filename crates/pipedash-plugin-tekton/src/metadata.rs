@@ -60,7 +60,7 @@ fn create_config_schema() -> ConfigSchema {
             key: "namespace_mode".to_string(),
             label: "Namespace Discovery Mode".to_string(),
             description: Some(
-                "How to discover namespaces containing Tekton pipelines:\n• 'all' - Automatically discover all namespaces (requires cluster-wide namespace list permissions)\n• 'custom' - Manually specify namespaces (recommended for users without admin permissions or on OpenShift/RHOS)\n\nIf you see a '403 Forbidden' error during validation, switch to 'custom' mode and specify namespaces manually."
+                "How to discover namespaces containing Tekton pipelines:\n• 'all' - Automatically discover all namespaces, or the OpenShift projects you can access when namespaces can't be listed\n• 'custom' - Manually specify namespaces (recommended for users without admin permissions or on OpenShift/RHOS)\n\nIf neither namespaces nor projects can be listed, the namespaces entered below (or the context's namespace) are used instead, and each one is checked for access."
                     .to_string(),
             ),
             field_type: ConfigFieldType::Select,
@@ -89,6 +89,7 @@ fn create_config_schema() -> ConfigSchema {
                 "Invalid namespace format. Each namespace must: start and end with alphanumeric, contain only lowercase letters, numbers, and hyphens, be max 63 characters. Separate multiple namespaces with commas.".to_string(),
             ),
         })
+        .with_client_certificate_fields()
 }
 
 fn create_capabilities() -> PluginCapabilities {
@@ -96,9 +97,9 @@ fn create_capabilities() -> PluginCapabilities {
         pipelines: true,
         pipeline_runs: true,
         trigger: true,
-        agents: false,
-        artifacts: false,
-        queues: false,
-        custom_tables: false,
+        cancel: true,
+        workflow_parameters: true,
+        logs: true,
+        ..PluginCapabilities::default()
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use std::sync::OnceLock;
+use std::sync::Arc;
+use std::time::SystemTime;
 
 use async_trait::async_trait;
 use futures::future::join_all;
@@ -13,9 +14,17 @@ use crate::{
     types,
 };
 
+// Rebuilt whenever the kubeconfig files change, so rewritten credentials
+// (e.g. after `aws eks update-kubeconfig` or `gcloud` refreshing an access
+// token) are picked up. Exec plugin tokens are refreshed by kube itself.
+struct CachedClient {
+    client: Arc<client::TektonClient>,
+    kubeconfig_modified: Option<SystemTime>,
+}
+
 pub struct TektonPlugin {
     metadata: PluginMetadata,
-    client: OnceLock<client::TektonClient>,
+    client: tokio::sync::Mutex<Option<CachedClient>>,
     provider_id: Option<i64>,
     config: HashMap<String, String>,
 }
@@ -30,29 +39,59 @@ impl TektonPlugin {
     pub fn new() -> Self {
         Self {
             metadata: metadata::create_metadata(),
-            client: OnceLock::new(),
+            client: tokio::sync::Mutex::new(None),
             provider_id: None,
             config: HashMap::new(),
         }
     }
 
-    async fn client(&self) -> PluginResult<&client::TektonClient> {
-        if let Some(client) = self.client.get() {
-            return Ok(client);
+    async fn client(&self) -> PluginResult<Arc<client::TektonClient>> {
+        let kubeconfig_path = config::get_kubeconfig_path(&self.config);
+        let kubeconfig_modified = config::kubeconfig_modified(&config::resolve_kubeconfig_paths(
+            kubeconfig_path.as_deref(),
+        ));
+
+        let mut cached = self.client.lock().await;
+        if let Some(existing) = cached
+            .as_ref()
+            .filter(|existing| existing.kubeconfig_modified == kubeconfig_modified)
+        {
+            return Ok(Arc::clone(&existing.client));
         }
 
-        let kubeconfig_path = config::get_kubeconfig_path(&self.config);
         let context = config::get_context(&self.config);
+        let client_certificate = config::get_client_certificate(&self.config)?;
 
-        let new_client =
-            client::TektonClient::from_kubeconfig(kubeconfig_path.as_deref(), context.as_deref())
-                .await?;
+        let new_client = Arc::new(
+            client::TektonClient::from_kubeconfig(
+                kubeconfig_path.as_deref(),
+                context.as_deref(),
+                client_certificate.as_ref(),
+            )
+            .await?,
+        );
 
-        Ok(self.client.get_or_init(|| new_client))
+        *cached = Some(CachedClient {
+            client: Arc::clone(&new_client),
+            kubeconfig_modified,
+        });
+        Ok(new_client)
+    }
+
+    // Namespaces checked when the credentials can't list namespaces: the
+    // configured ones, or the kubeconfig context's namespace.
+    fn fallback_namespaces(&self, client: &client::TektonClient) -> Vec<String> {
+        let namespaces = config::get_namespaces(&self.config);
+        if namespaces.is_empty() {
+            vec![client.default_namespace().to_string()]
+        } else {
+            namespaces
+        }
     }
 
     async fn fetch_all_pipelines_in_namespaces(&self) -> PluginResult<Vec<types::TektonPipeline>> {
         let client = self.client().await?;
+        let client = client.as_ref();
 
         let selected_ids = config::get_selected_pipelines(&self.config);
 
@@ -61,7 +100,11 @@ impl TektonPlugin {
 
             match namespace_mode {
                 config::NamespaceMode::Custom => config::get_namespaces(&self.config),
-                config::NamespaceMode::All => client.list_namespaces_with_pipelines().await?,
+                config::NamespaceMode::All => {
+                    client
+                        .discover_namespaces_with_pipelines(&self.fallback_namespaces(client))
+                        .await?
+                }
             }
         } else {
             let unique_namespaces: std::collections::HashSet<String> = selected_ids
@@ -119,12 +162,7 @@ impl TektonPlugin {
         use std::collections::HashSet;
         use std::path::PathBuf;
 
-        let paths = if let Some(path_str) = kubeconfig_path {
-            config::split_kubeconfig_paths(path_str)
-        } else {
-            let default_path = config::get_default_kubeconfig_path();
-            config::split_kubeconfig_paths(&default_path)
-        };
+        let paths = config::resolve_kubeconfig_paths(kubeconfig_path);
 
         let mut all_contexts = HashSet::new();
 
@@ -193,16 +231,13 @@ impl Plugin for TektonPlugin {
                     .validate_namespaces_have_pipelines(&manual_namespaces)
                     .await?
             }
-            config::NamespaceMode::All => match client.try_list_namespaces_cluster_wide().await {
-                Ok(all_namespaces) => {
-                    if all_namespaces.is_empty() {
-                        return Err(PluginError::InvalidConfig(
-                                "No namespaces found in the cluster. Please verify your cluster connection and permissions.".to_string(),
-                            ));
-                    }
-                    client.list_namespaces_with_pipelines().await?
+            config::NamespaceMode::All => match client.list_accessible_namespaces().await? {
+                Some(namespaces) => client.filter_namespaces_with_pipelines(&namespaces).await,
+                None => {
+                    client
+                        .validate_namespaces_have_pipelines(&self.fallback_namespaces(&client))
+                        .await?
                 }
-                Err(e) => return Err(e),
             },
         };
 
@@ -447,6 +482,115 @@ impl Plugin for TektonPlugin {
         Ok(())
     }
 
+    // One job per TaskRun with its steps; logs come from the step containers
+    // of the TaskRun's pod while it still exists.
+    async fn fetch_run_logs(
+        &self, pipeline_id: &str, run_number: i64, query: LogQuery,
+    ) -> PluginResult<RunLogs> {
+        let (_provider_id, namespace, pipeline_name) = config::parse_pipeline_id(pipeline_id)?;
+        let client = self.client().await?;
+
+        let run = client
+            .list_pipelineruns(&namespace, Some(&pipeline_name))
+            .await?
+            .into_iter()
+            .find(|r| {
+                types::parse_timestamp(&r.metadata.creation_timestamp).map(|dt| dt.timestamp())
+                    == Some(run_number)
+            })
+            .ok_or_else(|| {
+                PluginError::PipelineNotFound(format!(
+                    "PipelineRun with timestamp {} not found",
+                    run_number
+                ))
+            })?;
+
+        let mut taskruns = client.list_taskruns(&namespace, &run.metadata.name).await?;
+        // Tasks that haven't started yet go last.
+        taskruns.sort_by_key(|tr| {
+            let started = types::parse_timestamp(&tr.status.start_time);
+            (started.is_none(), started)
+        });
+
+        let log_futures = taskruns.into_iter().map(|taskrun| {
+            let requested = query
+                .job_id
+                .as_ref()
+                .is_none_or(|id| *id == taskrun.metadata.name);
+            let offset = if query.job_id.is_some() {
+                query.offset
+            } else {
+                0
+            };
+            let client = Arc::clone(&client);
+            let namespace = namespace.clone();
+
+            async move {
+                let status = mapper::map_status(&taskrun.status.conditions);
+                let pod = &taskrun.status.pod_name;
+
+                let step_logs = if requested && !pod.is_empty() {
+                    let step_futures = taskrun.status.steps.iter().map(|step| {
+                        let client = &client;
+                        let namespace = &namespace;
+                        async move {
+                            let log = client
+                                .fetch_container_log(namespace, pod, &mapper::step_container(step))
+                                .await?;
+                            Ok::<_, PluginError>((step.name.clone(), log))
+                        }
+                    });
+                    join_all(step_futures)
+                        .await
+                        .into_iter()
+                        .collect::<PluginResult<Vec<_>>>()?
+                } else {
+                    Vec::new()
+                };
+
+                let (content, next_offset) = if step_logs.is_empty() {
+                    (String::new(), offset)
+                } else {
+                    let log = mapper::combine_step_logs(&step_logs);
+                    (mapper::slice_log(&log, offset).to_string(), log.len())
+                };
+
+                Ok::<_, PluginError>(JobLog {
+                    id: taskrun.metadata.name.clone(),
+                    name: mapper::task_name(&taskrun),
+                    status,
+                    started_at: types::parse_timestamp(&taskrun.status.start_time),
+                    completed_at: types::parse_timestamp(&taskrun.status.completion_time),
+                    steps: taskrun
+                        .status
+                        .steps
+                        .iter()
+                        .enumerate()
+                        .map(|(index, step)| mapper::map_step(index, step))
+                        .collect(),
+                    content,
+                    offset,
+                    next_offset,
+                    is_complete: mapper::is_finished(status),
+                })
+            }
+        });
+
+        let jobs = join_all(log_futures)
+            .await
+            .into_iter()
+            .collect::<PluginResult<Vec<_>>>()?;
+
+        let status = mapper::map_status(&run.status.conditions);
+        Ok(RunLogs {
+            pipeline_id: pipeline_id.to_string(),
+            run_number,
+            status,
+            is_complete: mapper::is_finished(status),
+            jobs,
+        })
+    }
+
     async fn fetch_organizations(&self) -> PluginResult<Vec<Organization>> {
         Ok(vec![Organization {
             id: "default".to_string(),
@@ -521,14 +665,16 @@ impl Plugin for TektonPlugin {
         } else if field_key == "namespaces" {
             let kubeconfig_path = config::get_kubeconfig_path(config);
             let context = config::get_context(config);
+            let client_certificate = config::get_client_certificate(config)?;
 
             match client::TektonClient::from_kubeconfig(
                 kubeconfig_path.as_deref(),
                 context.as_deref(),
+                client_certificate.as_ref(),
             )
             .await
             {
-                Ok(temp_client) => match temp_client.try_list_namespaces_cluster_wide().await {
+                Ok(temp_client) => match temp_client.try_list_accessible_namespaces().await {
                     Ok(namespaces) => Ok(namespaces),
                     Err(e) => {
                         tracing::warn!(error = %e, "Failed to fetch namespaces for Tekton autocomplete");
@@ -190,6 +190,57 @@ pub struct TektonPipelineRun {
     pub status: PipelineRunStatus,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct StepRunning {
+    #[serde(rename = "startedAt", skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct StepTerminated {
+    #[serde(rename = "exitCode", default)]
+    pub exit_code: i32,
+    #[serde(default)]
+    pub reason: String,
+    #[serde(rename = "startedAt", skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(rename = "finishedAt", skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StepState {
+    pub name: String,
+    // Pod container running the step, usually `step-<name>`.
+    #[serde(default)]
+    pub container: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub running: Option<StepRunning>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terminated: Option<StepTerminated>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct TaskRunDetailStatus {
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    #[serde(rename = "startTime", skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<String>,
+    #[serde(rename = "completionTime", skip_serializing_if = "Option::is_none")]
+    pub completion_time: Option<String>,
+    #[serde(rename = "podName", default)]
+    pub pod_name: String,
+    #[serde(default)]
+    pub steps: Vec<StepState>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TektonTaskRun {
+    pub metadata: ObjectMeta,
+    #[serde(default)]
+    pub status: TaskRunDetailStatus,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PipelineList {
     pub items: Vec<TektonPipeline>,
@@ -200,6 +251,26 @@ pub struct PipelineRunList {
     pub items: Vec<TektonPipelineRun>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ProjectMeta {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Project {
+    pub metadata: ProjectMeta,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectList {
+    pub items: Vec<Project>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TaskRunList {
+    pub items: Vec<TektonTaskRun>,
+}
+
 pub fn parse_timestamp(timestamp: &Option<String>) -> Option<DateTime<Utc>> {
     timestamp.as_ref()?.parse::<DateTime<Utc>>().ok()
 }
@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+// Stands in for the fixture server's URL in recorded bodies and headers, so a
+// cassette replays on whatever port the server gets.
+pub const BASE_URL_PLACEHOLDER: &str = "{{base_url}}";
+
+// Response headers worth keeping: content type, caching, pagination and rate
+// limits. Everything else, cookies included, is dropped when recording.
+const KEPT_HEADERS: &[&str] = &[
+    "content-type",
+    "etag",
+    "last-modified",
+    "link",
+    "location",
+    "retry-after",
+];
+const KEPT_HEADER_PREFIXES: &[&str] = &[
+    "x-next-page",
+    "x-page",
+    "x-per-page",
+    "x-prev-page",
+    "x-ratelimit-",
+    "x-total",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    // Path and query, relative to the server root.
+    pub path: String,
+    // Only compared when set, so hand-written fixtures can leave it out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+impl RecordedRequest {
+    pub fn new(method: &str, path: &str) -> Self {
+        Self {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            body: None,
+        }
+    }
+
+    pub(crate) fn matches(
+        &self, method: &str, path: &str, body: Option<&serde_json::Value>,
+    ) -> bool {
+        self.method.eq_ignore_ascii_case(method)
+            && normalize_path(&self.path) == normalize_path(path)
+            && self
+                .body
+                .as_ref()
+                .is_none_or(|expected| Some(expected) == body)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    // JSON bodies are kept as JSON so fixtures stay readable and editable,
+    // anything else as text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl RecordedResponse {
+    pub fn json(status: u16, body: serde_json::Value) -> Self {
+        Self {
+            status,
+            headers: BTreeMap::new(),
+            json: Some(body),
+            text: None,
+        }
+    }
+
+    pub fn text(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: BTreeMap::new(),
+            json: None,
+            text: Some(body.to_string()),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers
+            .insert(name.to_ascii_lowercase(), value.to_string());
+        self
+    }
+
+    pub(crate) fn from_upstream(
+        status: u16, headers: &reqwest::header::HeaderMap, body: &[u8],
+        rewrite: impl Fn(&str) -> String,
+    ) -> Self {
+        let headers = headers
+            .iter()
+            .filter(|(name, _)| keep_header(name.as_str()))
+            .filter_map(|(name, value)| {
+                Some((name.as_str().to_string(), rewrite(value.to_str().ok()?)))
+            })
+            .collect::<BTreeMap<_, _>>();
+        let text = rewrite(&String::from_utf8_lossy(body));
+        let is_json = headers
+            .get("content-type")
+            .is_some_and(|content_type| content_type.contains("json"));
+
+        match serde_json::from_str(&text) {
+            Ok(json) if is_json => Self {
+                status,
+                headers,
+                json: Some(json),
+                text: None,
+            },
+            _ => Self {
+                status,
+                headers,
+                json: None,
+                text: (!text.is_empty()).then_some(text),
+            },
+        }
+    }
+
+    pub(crate) fn body(&self, base_url: &str) -> String {
+        let body = match (&self.json, &self.text) {
+            (Some(json), _) => json.to_string(),
+            (None, Some(text)) => text.clone(),
+            (None, None) => String::new(),
+        };
+        body.replace(BASE_URL_PLACEHOLDER, base_url)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+// The recorded exchanges with one provider, in the order they happened.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, request: RecordedRequest, response: RecordedResponse) -> Self {
+        self.interactions.push(Interaction { request, response });
+        self
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid cassette {}: {}", path.display(), e),
+            )
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        content.push('\n');
+        std::fs::write(path, content)
+    }
+}
+
+// Query parameters are sorted so fixtures don't depend on the order a client
+// happens to add them in.
+pub fn normalize_path(path: &str) -> String {
+    match path.split_once('?') {
+        Some((path, query)) => {
+            let mut params: Vec<&str> = query.split('&').filter(|p| !p.is_empty()).collect();
+            params.sort_unstable();
+            if params.is_empty() {
+                path.to_string()
+            } else {
+                format!("{}?{}", path, params.join("&"))
+            }
+        }
+        None => path.to_string(),
+    }
+}
+
+fn keep_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    KEPT_HEADERS.contains(&name.as_str())
+        || KEPT_HEADER_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path_sorts_query() {
+        assert_eq!(
+            normalize_path("/api/v4/projects?per_page=20&page=2"),
+            "/api/v4/projects?page=2&per_page=20"
+        );
+        assert_eq!(normalize_path("/api/json?"), "/api/json");
+        assert_eq!(normalize_path("/api/json"), "/api/json");
+    }
+
+    #[test]
+    fn test_request_matching() {
+        let mut request = RecordedRequest::new("post", "/graphql?b=2&a=1");
+        assert!(request.matches("POST", "/graphql?a=1&b=2", None));
+        assert!(!request.matches("GET", "/graphql?a=1&b=2", None));
+
+        request.body = Some(serde_json::json!({"query": "a"}));
+        assert!(request.matches(
+            "POST",
+            "/graphql?a=1&b=2",
+            Some(&serde_json::json!({"query": "a"}))
+        ));
+        assert!(!request.matches(
+            "POST",
+            "/graphql?a=1&b=2",
+            Some(&serde_json::json!({"query": "b"}))
+        ));
+    }
+
+    #[test]
+    fn test_upstream_headers_filtered() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("content-type", "application/json".parse().unwrap());
+        headers.insert("set-cookie", "session=secret".parse().unwrap());
+        headers.insert("x-total-pages", "3".parse().unwrap());
+        headers.insert(
+            "link",
+            "<https://gitlab.example.com/api/v4/projects?page=2>; rel=\"next\""
+                .parse()
+                .unwrap(),
+        );
+
+        let response = RecordedResponse::from_upstream(
+            200,
+            &headers,
+            br#"{"web_url":"https://gitlab.example.com/group/project"}"#,
+            |text| text.replace("https://gitlab.example.com", BASE_URL_PLACEHOLDER),
+        );
+
+        assert_eq!(
+            response.headers.keys().collect::<Vec<_>>(),
+            vec!["content-type", "link", "x-total-pages"]
+        );
+        assert_eq!(
+            response.json,
+            Some(serde_json::json!({"web_url": "{{base_url}}/group/project"}))
+        );
+        assert_eq!(
+            response.body("http://127.0.0.1:1"),
+            r#"{"web_url":"http://127.0.0.1:1/group/project"}"#
+        );
+    }
+}
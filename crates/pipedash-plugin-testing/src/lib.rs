@@ -0,0 +1,33 @@
+mod cassette;
+mod server;
+
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+pub use cassette::{
+    normalize_path,
+    Cassette,
+    Interaction,
+    RecordedRequest,
+    RecordedResponse,
+    BASE_URL_PLACEHOLDER,
+};
+pub use server::FixtureServer;
+
+// The workspace builds reqwest without a default rustls provider; the
+// binaries install one at startup, so tests have to do the same before a
+// plugin builds its client.
+pub fn install_crypto_provider() {
+    let _ = rustls::crypto::ring::default_provider().install_default();
+}
+
+// `tests/fixtures/{name}.json` under a crate, for use with
+// `fixture_path(env!("CARGO_MANIFEST_DIR"), "...")`.
+pub fn fixture_path(manifest_dir: &str, name: &str) -> PathBuf {
+    Path::new(manifest_dir)
+        .join("tests")
+        .join("fixtures")
+        .join(format!("{}.json", name))
+}
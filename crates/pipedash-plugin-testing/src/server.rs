@@ -0,0 +1,446 @@
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::sync::{
+    Arc,
+    Mutex,
+};
+
+use axum::body::Body;
+use axum::extract::{
+    Request,
+    State,
+};
+use axum::http::{
+    HeaderValue,
+    StatusCode,
+};
+use axum::response::{
+    IntoResponse,
+    Response,
+};
+use axum::Router;
+
+use crate::cassette::{
+    Cassette,
+    Interaction,
+    RecordedRequest,
+    RecordedResponse,
+    BASE_URL_PLACEHOLDER,
+};
+
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+// Request headers that describe the hop to this server rather than the
+// request itself.
+const HOP_HEADERS: &[&str] = &[
+    "accept-encoding",
+    "connection",
+    "content-length",
+    "host",
+    "transfer-encoding",
+];
+
+enum Mode {
+    Replay,
+    Record {
+        path: PathBuf,
+        upstream: String,
+        prefix: String,
+        client: reqwest::Client,
+    },
+}
+
+struct ServerState {
+    mode: Mode,
+    base_url: String,
+    cassette: Mutex<Cassette>,
+    used: Mutex<Vec<bool>>,
+    served: Mutex<Vec<String>>,
+    unmatched: Mutex<Vec<String>>,
+}
+
+// A local HTTP server that plugins are pointed at through their URL setting.
+//
+// Replaying, it answers from a cassette: each request gets the first unused
+// interaction with the same method and path (and body, when the fixture has
+// one), and the last matching one again once they are used up, so polling
+// loops keep working. Unknown requests get a 501 and are listed by
+// `unmatched`.
+//
+// Recording, it forwards `{prefix}/rest` to `{upstream}/rest` and appends
+// every exchange to the cassette file, with upstream URLs swapped for
+// `{{base_url}}` so pagination links lead back through the server.
+//
+// Must be started from within a Tokio runtime; the server stops when dropped.
+pub struct FixtureServer {
+    state: Arc<ServerState>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl FixtureServer {
+    pub fn replay(cassette: Cassette) -> std::io::Result<Self> {
+        Self::start(Mode::Replay, cassette)
+    }
+
+    pub fn replay_file(path: &Path) -> std::io::Result<Self> {
+        Self::replay(Cassette::load(path)?)
+    }
+
+    // Interactions already in the file are kept and new ones appended.
+    pub fn record(
+        path: &Path, upstream: &str, prefix: &str, client: reqwest::Client,
+    ) -> std::io::Result<Self> {
+        let cassette = if path.exists() {
+            Cassette::load(path)?
+        } else {
+            Cassette::new()
+        };
+
+        Self::start(
+            Mode::Record {
+                path: path.to_path_buf(),
+                upstream: upstream.trim_end_matches('/').to_string(),
+                prefix: prefix.trim_end_matches('/').to_string(),
+                client,
+            },
+            cassette,
+        )
+    }
+
+    fn start(mode: Mode, cassette: Cassette) -> std::io::Result<Self> {
+        crate::install_crypto_provider();
+
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0))?;
+        listener.set_nonblocking(true)?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let listener = tokio::net::TcpListener::from_std(listener)?;
+
+        let used = vec![false; cassette.interactions.len()];
+        let state = Arc::new(ServerState {
+            mode,
+            base_url,
+            cassette: Mutex::new(cassette),
+            used: Mutex::new(used),
+            served: Mutex::new(Vec::new()),
+            unmatched: Mutex::new(Vec::new()),
+        });
+
+        let router = Router::new()
+            .fallback(handle)
+            .with_state(Arc::clone(&state));
+        let task = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, router).await {
+                tracing::warn!(error = %e, "Fixture server stopped");
+            }
+        });
+
+        Ok(Self { state, task })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.state.base_url
+    }
+
+    // `upstream` and `prefix` of a recording server.
+    pub fn recording_target(&self) -> Option<(&str, &str)> {
+        match &self.state.mode {
+            Mode::Record {
+                upstream, prefix, ..
+            } => Some((upstream, prefix)),
+            Mode::Replay => None,
+        }
+    }
+
+    // Every request served so far, as `METHOD /path?query`.
+    pub fn served(&self) -> Vec<String> {
+        lock(&self.state.served).clone()
+    }
+
+    pub fn unmatched(&self) -> Vec<String> {
+        lock(&self.state.unmatched).clone()
+    }
+
+    pub fn cassette(&self) -> Cassette {
+        lock(&self.state.cassette).clone()
+    }
+}
+
+impl Drop for FixtureServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+async fn handle(State(state): State<Arc<ServerState>>, request: Request) -> Response {
+    let method = request.method().clone();
+    let path = request
+        .uri()
+        .path_and_query()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "/".to_string());
+    let headers = request.headers().clone();
+    let body = match axum::body::to_bytes(request.into_body(), MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let json_body = (!body.is_empty()).then(|| {
+        serde_json::from_slice(&body)
+            .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(&body).into()))
+    });
+
+    let label = format!("{} {}", method, path);
+    lock(&state.served).push(label.clone());
+
+    let response = match &state.mode {
+        Mode::Replay => {
+            let response = find_response(&state, method.as_str(), &path, json_body.as_ref());
+            match response {
+                Some(response) => response,
+                None => {
+                    lock(&state.unmatched).push(label.clone());
+                    return (
+                        StatusCode::NOT_IMPLEMENTED,
+                        format!("No fixture for {}", label),
+                    )
+                        .into_response();
+                }
+            }
+        }
+        Mode::Record {
+            path: cassette_path,
+            upstream,
+            prefix,
+            client,
+        } => {
+            let Some(rest) = path.strip_prefix(prefix.as_str()) else {
+                return (
+                    StatusCode::BAD_GATEWAY,
+                    format!("{} is outside the recorded API {}", path, prefix),
+                )
+                    .into_response();
+            };
+
+            let mut forwarded = client.request(method.clone(), format!("{}{}", upstream, rest));
+            for (name, value) in headers.iter() {
+                if !HOP_HEADERS.contains(&name.as_str()) {
+                    forwarded = forwarded.header(name, value);
+                }
+            }
+            if !body.is_empty() {
+                forwarded = forwarded.body(body.to_vec());
+            }
+
+            let upstream_response = match forwarded.send().await {
+                Ok(response) => response,
+                Err(e) => return (StatusCode::BAD_GATEWAY, e.to_string()).into_response(),
+            };
+            let status = upstream_response.status().as_u16();
+            let upstream_headers = upstream_response.headers().clone();
+            let upstream_body = match upstream_response.bytes().await {
+                Ok(body) => body,
+                Err(e) => return (StatusCode::BAD_GATEWAY, e.to_string()).into_response(),
+            };
+
+            let origin = origin(upstream);
+            let response = RecordedResponse::from_upstream(
+                status,
+                &upstream_headers,
+                &upstream_body,
+                |text| {
+                    let text = text.replace(
+                        upstream.as_str(),
+                        &format!("{}{}", BASE_URL_PLACEHOLDER, prefix),
+                    );
+                    match origin {
+                        Some(origin) => text.replace(origin, BASE_URL_PLACEHOLDER),
+                        None => text,
+                    }
+                },
+            );
+
+            let mut cassette = lock(&state.cassette);
+            cassette.interactions.push(Interaction {
+                request: RecordedRequest {
+                    method: method.to_string(),
+                    path: path.clone(),
+                    body: json_body,
+                },
+                response: response.clone(),
+            });
+            if let Err(e) = cassette.save(cassette_path) {
+                tracing::warn!(path = %cassette_path.display(), error = %e, "Failed to save cassette");
+            }
+            response
+        }
+    };
+
+    into_response(&response, &state.base_url)
+}
+
+fn find_response(
+    state: &ServerState, method: &str, path: &str, body: Option<&serde_json::Value>,
+) -> Option<RecordedResponse> {
+    let cassette = lock(&state.cassette);
+    let mut used = lock(&state.used);
+
+    let matching: Vec<usize> = cassette
+        .interactions
+        .iter()
+        .enumerate()
+        .filter(|(_, interaction)| interaction.request.matches(method, path, body))
+        .map(|(index, _)| index)
+        .collect();
+    let index = matching
+        .iter()
+        .copied()
+        .find(|index| !used[*index])
+        .or_else(|| matching.last().copied())?;
+
+    used[index] = true;
+    Some(cassette.interactions[index].response.clone())
+}
+
+fn into_response(recorded: &RecordedResponse, base_url: &str) -> Response {
+    let mut response = Response::new(Body::from(recorded.body(base_url)));
+    *response.status_mut() = StatusCode::from_u16(recorded.status).unwrap_or(StatusCode::OK);
+
+    let headers = response.headers_mut();
+    for (name, value) in &recorded.headers {
+        let value = value.replace(BASE_URL_PLACEHOLDER, base_url);
+        if let (Ok(name), Ok(value)) = (
+            axum::http::HeaderName::try_from(name.as_str()),
+            HeaderValue::try_from(value),
+        ) {
+            headers.insert(name, value);
+        }
+    }
+    if recorded.json.is_some() && !headers.contains_key("content-type") {
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+    }
+
+    response
+}
+
+// `scheme://host[:port]` of a URL.
+fn origin(url: &str) -> Option<&str> {
+    let scheme_end = url.find("://")? + 3;
+    let end = url[scheme_end..]
+        .find('/')
+        .map_or(url.len(), |i| scheme_end + i);
+    Some(&url[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin() {
+        assert_eq!(
+            origin("https://gitlab.example.com/api/v4"),
+            Some("https://gitlab.example.com")
+        );
+        assert_eq!(
+            origin("http://127.0.0.1:8080"),
+            Some("http://127.0.0.1:8080")
+        );
+        assert_eq!(origin("not a url"), None);
+    }
+
+    #[tokio::test]
+    async fn test_replay_in_order_then_repeat() {
+        let server = FixtureServer::replay(
+            Cassette::new()
+                .with(
+                    RecordedRequest::new("GET", "/status"),
+                    RecordedResponse::json(200, serde_json::json!({"state": "running"})),
+                )
+                .with(
+                    RecordedRequest::new("GET", "/status"),
+                    RecordedResponse::json(200, serde_json::json!({"state": "done"})),
+                ),
+        )
+        .unwrap();
+        let client = reqwest::Client::new();
+        let get = |path: &str| {
+            let request = client.get(format!("{}{}", server.base_url(), path));
+            async move {
+                let response = request.send().await.unwrap();
+                (response.status().as_u16(), response.text().await.unwrap())
+            }
+        };
+
+        assert_eq!(
+            get("/status").await,
+            (200, r#"{"state":"running"}"#.to_string())
+        );
+        assert_eq!(
+            get("/status").await,
+            (200, r#"{"state":"done"}"#.to_string())
+        );
+        assert_eq!(
+            get("/status").await,
+            (200, r#"{"state":"done"}"#.to_string())
+        );
+        assert_eq!(get("/missing").await.0, 501);
+        assert_eq!(server.unmatched(), vec!["GET /missing".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_record_through_proxy() {
+        let upstream = FixtureServer::replay(Cassette::new().with(
+            RecordedRequest::new("GET", "/api/v4/projects?page=1"),
+            RecordedResponse::json(200, serde_json::json!([{"id": 1}])).with_header(
+                "link",
+                "<{{base_url}}/api/v4/projects?page=2>; rel=\"next\"",
+            ),
+        ))
+        .unwrap();
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("fixtures/gitlab-1.json");
+
+        let recorder = FixtureServer::record(
+            &path,
+            &format!("{}/api/v4", upstream.base_url()),
+            "/api/v4",
+            reqwest::Client::new(),
+        )
+        .unwrap();
+        let response = reqwest::get(format!("{}/api/v4/projects?page=1", recorder.base_url()))
+            .await
+            .unwrap();
+
+        // Links point back through the recorder, not at the upstream.
+        let link = response.headers()["link"].to_str().unwrap().to_string();
+        assert_eq!(
+            link,
+            format!(
+                "<{}/api/v4/projects?page=2>; rel=\"next\"",
+                recorder.base_url()
+            )
+        );
+
+        let saved = Cassette::load(&path).unwrap();
+        assert_eq!(saved.interactions.len(), 1);
+        assert_eq!(
+            saved.interactions[0].request.path,
+            "/api/v4/projects?page=1"
+        );
+        assert_eq!(
+            saved.interactions[0].response.headers["link"],
+            "<{{base_url}}/api/v4/projects?page=2>; rel=\"next\""
+        );
+        assert_eq!(
+            saved.interactions[0].response.json,
+            Some(serde_json::json!([{"id": 1}]))
+        );
+    }
+}
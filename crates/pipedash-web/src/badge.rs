@@ -0,0 +1,135 @@
+use axum::{
+    http::{
+        header,
+        StatusCode,
+    },
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use pipedash_core::domain::PipelineStatus;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+// Rough average glyph width of 11px Verdana, the font shields.io badges use.
+const CHAR_WIDTH: usize = 7;
+const PADDING: usize = 10;
+
+const LABEL_COLOR: &str = "#555";
+const UNKNOWN_COLOR: &str = "#9f9f9f";
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BadgeQuery {
+    // Overrides the left-hand text, which defaults to the pipeline or group name.
+    pub label: Option<String>,
+}
+
+fn status_text(status: &PipelineStatus) -> (&'static str, &'static str) {
+    match status {
+        PipelineStatus::Success => ("passing", "#4c1"),
+        PipelineStatus::Failed => ("failing", "#e05d44"),
+        PipelineStatus::Running => ("running", "#007ec6"),
+        PipelineStatus::Pending => ("pending", "#dfb317"),
+        PipelineStatus::Queued => ("queued", "#dfb317"),
+        PipelineStatus::Cancelled => ("cancelled", UNKNOWN_COLOR),
+        PipelineStatus::Skipped => ("skipped", UNKNOWN_COLOR),
+        PipelineStatus::Waiting => ("waiting", "#fe7d37"),
+        PipelineStatus::Neutral => ("neutral", UNKNOWN_COLOR),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn text_width(text: &str) -> usize {
+    text.chars().count() * CHAR_WIDTH + PADDING
+}
+
+pub fn render(label: &str, message: &str, color: &str) -> String {
+    let label_width = text_width(label);
+    let message_width = text_width(message);
+    let width = label_width + message_width;
+    let label = escape(label);
+    let message = escape(message);
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
+  <linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+  <clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="{LABEL_COLOR}"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+    <rect width="{width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text>
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{message_x}" y="15" fill="#010101" fill-opacity=".3">{message}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>"##,
+        label_x = label_width / 2,
+        message_x = label_width + message_width / 2,
+    )
+}
+
+pub fn status_badge(label: &str, status: &PipelineStatus) -> Response {
+    let (message, color) = status_text(status);
+    svg_response(StatusCode::OK, render(label, message, color))
+}
+
+// Served in place of an error so embedded images still show something.
+pub fn unknown_badge(label: &str) -> Response {
+    svg_response(
+        StatusCode::NOT_FOUND,
+        render(label, "unknown", UNKNOWN_COLOR),
+    )
+}
+
+fn svg_response(status: StatusCode, svg: String) -> Response {
+    (
+        status,
+        [
+            (header::CONTENT_TYPE, "image/svg+xml"),
+            // Image proxies (GitHub camo, wiki renderers) otherwise keep stale
+            // statuses around.
+            (header::CACHE_CONTROL, "no-cache, max-age=0"),
+        ],
+        svg,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_escapes_label() {
+        let svg = render("deploy <prod> & \"qa\"", "passing", "#4c1");
+        assert!(svg.contains("deploy &lt;prod&gt; &amp; &quot;qa&quot;: passing"));
+        assert!(!svg.contains("<prod>"));
+    }
+
+    #[test]
+    fn test_badge_width_grows_with_text() {
+        let short = render("ci", "passing", "#4c1");
+        let long = render("release-pipeline", "passing", "#4c1");
+        assert!(short.contains(&format!(
+            "width=\"{}\"",
+            text_width("ci") + text_width("passing")
+        )));
+        assert!(long.contains(&format!(
+            "width=\"{}\"",
+            text_width("release-pipeline") + text_width("passing")
+        )));
+    }
+}
@@ -1,81 +1,130 @@
 use axum::{
-    http::StatusCode,
+    http::{
+        header,
+        StatusCode,
+    },
     response::{
         IntoResponse,
         Response,
     },
     Json,
 };
-use pipedash_core::domain::DomainError;
+use pipedash_core::domain::{
+    redactor,
+    DomainError,
+};
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize)]
-pub struct ApiError {
-    pub code: String,
-    pub message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub details: Option<String>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    NotFound,
+    BadRequest,
+    InternalError,
+    Unauthorized,
+    Forbidden,
+    FeatureUnavailable,
+    NotInitialized,
+    NotSupported,
+    TooManyRequests,
+    ProviderUnavailable,
 }
 
-impl ApiError {
-    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
-        Self {
-            code: code.into(),
-            message: message.into(),
-            details: None,
-        }
-    }
+// RFC 9457 problem details. `code` is the stable, machine-readable part;
+// `detail` is meant for humans and may change between releases.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub code: ErrorCode,
 }
 
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+#[derive(Debug)]
 pub struct AppError {
     pub status: StatusCode,
-    pub error: ApiError,
+    pub code: ErrorCode,
+    pub message: String,
 }
 
 impl AppError {
-    pub fn new(status: StatusCode, error: ApiError) -> Self {
-        Self { status, error }
+    pub fn new(status: StatusCode, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
     }
 
     pub fn not_found(message: impl Into<String>) -> Self {
-        Self::new(StatusCode::NOT_FOUND, ApiError::new("NOT_FOUND", message))
+        Self::new(StatusCode::NOT_FOUND, ErrorCode::NotFound, message)
     }
 
     pub fn bad_request(message: impl Into<String>) -> Self {
-        Self::new(
-            StatusCode::BAD_REQUEST,
-            ApiError::new("BAD_REQUEST", message),
-        )
+        Self::new(StatusCode::BAD_REQUEST, ErrorCode::BadRequest, message)
     }
 
     pub fn internal(message: impl Into<String>) -> Self {
         Self::new(
             StatusCode::INTERNAL_SERVER_ERROR,
-            ApiError::new("INTERNAL_ERROR", message),
+            ErrorCode::InternalError,
+            message,
         )
     }
 
     pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, ErrorCode::Unauthorized, message)
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, ErrorCode::Forbidden, message)
+    }
+
+    pub fn too_many_requests(message: impl Into<String>) -> Self {
         Self::new(
-            StatusCode::UNAUTHORIZED,
-            ApiError::new("UNAUTHORIZED", message),
+            StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::TooManyRequests,
+            message,
         )
     }
 
     pub fn not_initialized() -> Self {
         Self::new(
             StatusCode::SERVICE_UNAVAILABLE,
-            ApiError::new(
-                "NOT_INITIALIZED",
-                "Application not initialized - setup required",
-            ),
+            ErrorCode::NotInitialized,
+            "Application not initialized - setup required",
         )
     }
+
+    pub fn to_problem(&self) -> Problem {
+        Problem {
+            problem_type: "about:blank".to_string(),
+            title: self
+                .status
+                .canonical_reason()
+                .unwrap_or("Error")
+                .to_string(),
+            status: self.status.as_u16(),
+            // Provider errors can echo request headers back.
+            detail: redactor().redact(&self.message).into_owned(),
+            code: self.code,
+        }
+    }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        (self.status, Json(self.error)).into_response()
+        (
+            self.status,
+            [(header::CONTENT_TYPE, PROBLEM_CONTENT_TYPE)],
+            Json(self.to_problem()),
+        )
+            .into_response()
     }
 }
 
@@ -84,12 +133,26 @@ impl From<DomainError> for AppError {
         match &err {
             DomainError::ProviderNotFound(_) => AppError::not_found(err.to_string()),
             DomainError::PipelineNotFound(_) => AppError::not_found(err.to_string()),
+            DomainError::NotFound(_) => AppError::not_found(err.to_string()),
             DomainError::InvalidConfig(_) => AppError::bad_request(err.to_string()),
             DomainError::AuthenticationFailed(_) => AppError::unauthorized(err.to_string()),
+            DomainError::PermissionDenied(_) => AppError::forbidden(err.to_string()),
+            DomainError::FeatureUnavailable { .. } => AppError::new(
+                StatusCode::FORBIDDEN,
+                ErrorCode::FeatureUnavailable,
+                err.to_string(),
+            ),
             DomainError::InvalidProviderType(_) => AppError::bad_request(err.to_string()),
             DomainError::NotSupported(_) => AppError::new(
                 StatusCode::NOT_IMPLEMENTED,
-                ApiError::new("NOT_SUPPORTED", err.to_string()),
+                ErrorCode::NotSupported,
+                err.to_string(),
+            ),
+            DomainError::RateLimited { .. } => AppError::too_many_requests(err.to_string()),
+            DomainError::CircuitOpen { .. } => AppError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                ErrorCode::ProviderUnavailable,
+                err.to_string(),
             ),
             _ => AppError::internal(err.to_string()),
         }
@@ -103,3 +166,64 @@ impl From<anyhow::Error> for AppError {
 }
 
 pub type ApiResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_problem_from_domain_error() {
+        let err = AppError::from(DomainError::PipelineNotFound("gh__1__ci".to_string()));
+        let problem = serde_json::to_value(err.to_problem()).unwrap();
+        assert_eq!(problem["type"], "about:blank");
+        assert_eq!(problem["title"], "Not Found");
+        assert_eq!(problem["status"], 404);
+        assert_eq!(problem["code"], "NOT_FOUND");
+        assert!(problem["detail"].as_str().unwrap().contains("gh__1__ci"));
+    }
+
+    #[test]
+    fn test_problem_from_feature_unavailable() {
+        let err = AppError::from(DomainError::FeatureUnavailable {
+            feature: "Trigger workflow dispatch".to_string(),
+            missing_permission: "workflow".to_string(),
+        });
+        let problem = serde_json::to_value(err.to_problem()).unwrap();
+        assert_eq!(problem["status"], 403);
+        assert_eq!(problem["code"], "FEATURE_UNAVAILABLE");
+        assert!(problem["detail"].as_str().unwrap().contains("`workflow`"));
+    }
+
+    #[test]
+    fn test_problem_from_open_circuit() {
+        let err = AppError::from(DomainError::CircuitOpen {
+            provider_id: 3,
+            retry_at: chrono::Utc::now(),
+        });
+        let problem = serde_json::to_value(err.to_problem()).unwrap();
+        assert_eq!(problem["status"], 503);
+        assert_eq!(problem["code"], "PROVIDER_UNAVAILABLE");
+    }
+
+    #[test]
+    fn test_problem_detail_is_redacted() {
+        let err = AppError::from(DomainError::ApiError(
+            "401 for request with Authorization: token ghp_secret".to_string(),
+        ));
+        let problem = serde_json::to_value(err.to_problem()).unwrap();
+        assert_eq!(
+            problem["detail"],
+            "API error: 401 for request with Authorization: token ***"
+        );
+    }
+
+    #[test]
+    fn test_problem_content_type() {
+        let response = AppError::not_initialized().into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            PROBLEM_CONTENT_TYPE
+        );
+    }
+}
@@ -0,0 +1,674 @@
+use std::sync::OnceLock;
+
+use async_graphql::{
+    ComplexObject,
+    Context,
+    EmptyMutation,
+    Enum,
+    InputObject,
+    Json,
+    Object,
+    Result,
+    Schema,
+    SimpleObject,
+    Subscription,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use futures_util::{
+    Stream,
+    StreamExt,
+};
+use pipedash_core::domain::{
+    self,
+    MetricsQuery,
+};
+use pipedash_core::CoreContext;
+use tokio::sync::broadcast;
+
+use crate::state::{
+    AppState,
+    AppStateInner,
+};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+// Deep enough for the standard introspection query, which nests `ofType`
+// several levels down.
+const MAX_QUERY_DEPTH: usize = 15;
+const MAX_QUERY_COMPLEXITY: usize = 1000;
+
+pub type PipedashSchema = Schema<QueryRoot, EmptyMutation, SubscriptionRoot>;
+
+// Resolvers read `AppState` from the request data, so the schema itself is
+// stateless and built once.
+pub fn schema() -> PipedashSchema {
+    static SCHEMA: OnceLock<PipedashSchema> = OnceLock::new();
+    SCHEMA
+        .get_or_init(|| {
+            Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+                .limit_depth(MAX_QUERY_DEPTH)
+                .limit_complexity(MAX_QUERY_COMPLEXITY)
+                .finish()
+        })
+        .clone()
+}
+
+fn core(inner: &AppStateInner) -> Result<&CoreContext> {
+    inner
+        .core
+        .as_ref()
+        .ok_or_else(|| "Pipedash is not initialized".into())
+}
+
+fn page_size(limit: Option<usize>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "PipelineStatus", remote = "domain::PipelineStatus")]
+enum GqlPipelineStatus {
+    Success,
+    Failed,
+    Running,
+    Pending,
+    Queued,
+    Cancelled,
+    Skipped,
+    Waiting,
+    Neutral,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "FetchStatus", remote = "pipedash_core::FetchStatus")]
+enum GqlFetchStatus {
+    Success,
+    Error,
+    Never,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "MetricType", remote = "domain::MetricType")]
+enum GqlMetricType {
+    RunDuration,
+    SuccessRate,
+    RunFrequency,
+    QueueWaitTime,
+    QueueDepth,
+    EstimatedCost,
+    AgentWaitTime,
+    BillableMinutes,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "AggregationPeriod", remote = "domain::AggregationPeriod")]
+enum GqlAggregationPeriod {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "AggregationType", remote = "domain::AggregationType")]
+enum GqlAggregationType {
+    Avg,
+    Sum,
+    Min,
+    Max,
+    P95,
+    P99,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Provider", complex)]
+struct GqlProvider {
+    id: i64,
+    name: String,
+    provider_type: String,
+    pipeline_count: usize,
+    last_updated: Option<DateTime<Utc>>,
+    refresh_interval: i64,
+    configured_repositories: Vec<String>,
+    last_fetch_status: GqlFetchStatus,
+    last_fetch_error: Option<String>,
+    last_fetch_at: Option<DateTime<Utc>>,
+}
+
+impl From<pipedash_core::ProviderSummary> for GqlProvider {
+    fn from(provider: pipedash_core::ProviderSummary) -> Self {
+        Self {
+            id: provider.id,
+            name: provider.name,
+            provider_type: provider.provider_type,
+            pipeline_count: provider.pipeline_count,
+            last_updated: provider.last_updated,
+            refresh_interval: provider.refresh_interval,
+            configured_repositories: provider.configured_repositories,
+            last_fetch_status: provider.last_fetch_status.into(),
+            last_fetch_error: provider.last_fetch_error,
+            last_fetch_at: provider.last_fetch_at,
+        }
+    }
+}
+
+#[ComplexObject]
+impl GqlProvider {
+    async fn pipelines(&self, ctx: &Context<'_>) -> Result<Vec<GqlPipeline>> {
+        let state = ctx.data::<AppState>()?;
+        let inner = state.inner.read().await;
+        let pipelines = core(&inner)?
+            .pipeline_service
+            .get_cached_pipelines(Some(self.id))
+            .await?;
+        Ok(pipelines.into_iter().map(Into::into).collect())
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Pipeline", complex)]
+struct GqlPipeline {
+    id: String,
+    provider_id: i64,
+    provider_type: String,
+    name: String,
+    status: GqlPipelineStatus,
+    last_run: Option<DateTime<Utc>>,
+    last_updated: DateTime<Utc>,
+    repository: String,
+    branch: Option<String>,
+    workflow_file: Option<String>,
+    labels: Vec<String>,
+    metadata: Json<serde_json::Value>,
+}
+
+impl From<domain::Pipeline> for GqlPipeline {
+    fn from(pipeline: domain::Pipeline) -> Self {
+        Self {
+            id: pipeline.id,
+            provider_id: pipeline.provider_id,
+            provider_type: pipeline.provider_type,
+            name: pipeline.name,
+            status: pipeline.status.into(),
+            last_run: pipeline.last_run,
+            last_updated: pipeline.last_updated,
+            repository: pipeline.repository,
+            branch: pipeline.branch,
+            workflow_file: pipeline.workflow_file,
+            labels: pipeline.labels,
+            metadata: Json(serde_json::json!(pipeline.metadata)),
+        }
+    }
+}
+
+#[ComplexObject]
+impl GqlPipeline {
+    async fn runs(
+        &self, ctx: &Context<'_>, #[graphql(default = 1)] page: usize,
+        #[graphql(default = 20)] page_size: usize,
+    ) -> Result<GqlRunConnection> {
+        run_history(ctx, &self.id, page, page_size).await
+    }
+
+    async fn metrics(
+        &self, ctx: &Context<'_>, metric_type: Option<GqlMetricType>,
+        start_date: Option<DateTime<Utc>>, end_date: Option<DateTime<Utc>>, limit: Option<usize>,
+    ) -> Result<Vec<GqlMetricEntry>> {
+        let filter = MetricsFilter {
+            pipeline_ids: vec![self.id.clone()],
+            labels: Vec::new(),
+            metric_type,
+            start_date,
+            end_date,
+            limit,
+        };
+        query_metrics(ctx, filter).await
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "PullRequest")]
+struct GqlPullRequest {
+    number: i64,
+    title: Option<String>,
+    url: Option<String>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Run")]
+struct GqlRun {
+    id: String,
+    pipeline_id: String,
+    run_number: i64,
+    status: GqlPipelineStatus,
+    started_at: DateTime<Utc>,
+    concluded_at: Option<DateTime<Utc>>,
+    duration_seconds: Option<i64>,
+    logs_url: String,
+    commit_sha: Option<String>,
+    commit_message: Option<String>,
+    branch: Option<String>,
+    actor: Option<String>,
+    commit_author: Option<String>,
+    pull_request: Option<GqlPullRequest>,
+    inputs: Option<Json<serde_json::Value>>,
+}
+
+impl From<domain::PipelineRun> for GqlRun {
+    fn from(run: domain::PipelineRun) -> Self {
+        Self {
+            id: run.id,
+            pipeline_id: run.pipeline_id,
+            run_number: run.run_number,
+            status: run.status.into(),
+            started_at: run.started_at,
+            concluded_at: run.concluded_at,
+            duration_seconds: run.duration_seconds,
+            logs_url: run.logs_url,
+            commit_sha: run.commit_sha,
+            commit_message: run.commit_message,
+            branch: run.branch,
+            actor: run.actor,
+            commit_author: run.commit_author,
+            pull_request: run.pull_request.map(|pr| GqlPullRequest {
+                number: pr.number,
+                title: pr.title,
+                url: pr.url,
+            }),
+            inputs: run.inputs.map(Json),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "RunConnection")]
+struct GqlRunConnection {
+    nodes: Vec<GqlRun>,
+    total_count: usize,
+    has_more: bool,
+    page: usize,
+    page_size: usize,
+    total_pages: usize,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "PipelineConnection")]
+struct GqlPipelineConnection {
+    nodes: Vec<GqlPipeline>,
+    total_count: usize,
+    has_next_page: bool,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "MetricEntry")]
+struct GqlMetricEntry {
+    pipeline_id: String,
+    run_number: i64,
+    timestamp: DateTime<Utc>,
+    metric_type: GqlMetricType,
+    value: f64,
+}
+
+impl From<domain::MetricEntry> for GqlMetricEntry {
+    fn from(entry: domain::MetricEntry) -> Self {
+        Self {
+            pipeline_id: entry.pipeline_id,
+            run_number: entry.run_number,
+            timestamp: entry.timestamp,
+            metric_type: entry.metric_type.into(),
+            value: entry.value,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "AggregatedMetric")]
+struct GqlAggregatedMetric {
+    timestamp: DateTime<Utc>,
+    value: f64,
+    count: i64,
+    min: Option<f64>,
+    max: Option<f64>,
+    avg: f64,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "AggregatedMetrics")]
+struct GqlAggregatedMetrics {
+    metrics: Vec<GqlAggregatedMetric>,
+    total_count: usize,
+    metric_type: GqlMetricType,
+    aggregation_period: GqlAggregationPeriod,
+}
+
+#[derive(InputObject, Default)]
+struct PipelineFilter {
+    provider_id: Option<i64>,
+    #[graphql(default)]
+    statuses: Vec<GqlPipelineStatus>,
+    // Same syntax as the REST `labels` filter: `team:payments` or `team`.
+    #[graphql(default)]
+    labels: Vec<String>,
+    // Case-insensitive match on name or repository.
+    search: Option<String>,
+}
+
+impl PipelineFilter {
+    fn matches(&self, pipeline: &domain::Pipeline) -> bool {
+        if !self.statuses.is_empty()
+            && !self
+                .statuses
+                .contains(&GqlPipelineStatus::from(pipeline.status.clone()))
+        {
+            return false;
+        }
+
+        if !pipeline.has_labels(&self.labels) {
+            return false;
+        }
+
+        self.search.as_deref().is_none_or(|search| {
+            let search = search.to_lowercase();
+            pipeline.name.to_lowercase().contains(&search)
+                || pipeline.repository.to_lowercase().contains(&search)
+        })
+    }
+}
+
+#[derive(InputObject)]
+struct MetricsFilter {
+    #[graphql(default)]
+    pipeline_ids: Vec<String>,
+    #[graphql(default)]
+    labels: Vec<String>,
+    metric_type: Option<GqlMetricType>,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    limit: Option<usize>,
+}
+
+impl From<MetricsFilter> for MetricsQuery {
+    fn from(filter: MetricsFilter) -> Self {
+        MetricsQuery {
+            pipeline_id: None,
+            pipeline_ids: (!filter.pipeline_ids.is_empty()).then_some(filter.pipeline_ids),
+            labels: filter.labels,
+            metric_type: filter.metric_type.map(Into::into),
+            start_date: filter.start_date,
+            end_date: filter.end_date,
+            aggregation_period: None,
+            aggregation_type: None,
+            limit: filter.limit,
+        }
+    }
+}
+
+async fn run_history(
+    ctx: &Context<'_>, pipeline_id: &str, page: usize, page_size: usize,
+) -> Result<GqlRunConnection> {
+    let state = ctx.data::<AppState>()?;
+    let inner = state.inner.read().await;
+    let history = core(&inner)?
+        .pipeline_service
+        .fetch_run_history_paginated(pipeline_id, page.max(1), page_size.clamp(1, 100))
+        .await?;
+
+    Ok(GqlRunConnection {
+        nodes: history.runs.into_iter().map(Into::into).collect(),
+        total_count: history.total_count,
+        has_more: history.has_more,
+        page: history.page,
+        page_size: history.page_size,
+        total_pages: history.total_pages,
+    })
+}
+
+async fn query_metrics(ctx: &Context<'_>, filter: MetricsFilter) -> Result<Vec<GqlMetricEntry>> {
+    let state = ctx.data::<AppState>()?;
+    let inner = state.inner.read().await;
+    let metrics_service = core(&inner)?
+        .metrics_service
+        .as_ref()
+        .ok_or("Metrics service not available")?;
+    let metrics = metrics_service.query_metrics(filter.into()).await?;
+    Ok(metrics.into_iter().map(Into::into).collect())
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn providers(&self, ctx: &Context<'_>) -> Result<Vec<GqlProvider>> {
+        let state = ctx.data::<AppState>()?;
+        let inner = state.inner.read().await;
+        let providers = core(&inner)?.provider_service.list_providers().await?;
+        Ok(providers.into_iter().map(Into::into).collect())
+    }
+
+    async fn provider(&self, ctx: &Context<'_>, id: i64) -> Result<Option<GqlProvider>> {
+        let state = ctx.data::<AppState>()?;
+        let inner = state.inner.read().await;
+        let providers = core(&inner)?.provider_service.list_providers().await?;
+        Ok(providers
+            .into_iter()
+            .find(|provider| provider.id == id)
+            .map(Into::into))
+    }
+
+    // Served from the pipeline cache; `offset` and `limit` page through the
+    // filtered result.
+    async fn pipelines(
+        &self, ctx: &Context<'_>, filter: Option<PipelineFilter>,
+        #[graphql(default)] offset: usize, limit: Option<usize>,
+    ) -> Result<GqlPipelineConnection> {
+        let filter = filter.unwrap_or_default();
+        let state = ctx.data::<AppState>()?;
+        let inner = state.inner.read().await;
+        let pipelines: Vec<domain::Pipeline> = core(&inner)?
+            .pipeline_service
+            .get_cached_pipelines(filter.provider_id)
+            .await?
+            .into_iter()
+            .filter(|pipeline| filter.matches(pipeline))
+            .collect();
+
+        let total_count = pipelines.len();
+        let limit = page_size(limit);
+        Ok(GqlPipelineConnection {
+            nodes: pipelines
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .map(Into::into)
+                .collect(),
+            total_count,
+            has_next_page: offset + limit < total_count,
+        })
+    }
+
+    async fn pipeline(&self, ctx: &Context<'_>, id: String) -> Result<Option<GqlPipeline>> {
+        let state = ctx.data::<AppState>()?;
+        let inner = state.inner.read().await;
+        let pipeline = core(&inner)?
+            .pipeline_service
+            .get_cached_pipelines(None)
+            .await?
+            .into_iter()
+            .find(|pipeline| pipeline.id == id);
+        Ok(pipeline.map(Into::into))
+    }
+
+    async fn runs(
+        &self, ctx: &Context<'_>, pipeline_id: String, #[graphql(default = 1)] page: usize,
+        #[graphql(default = 20)] page_size: usize,
+    ) -> Result<GqlRunConnection> {
+        run_history(ctx, &pipeline_id, page, page_size).await
+    }
+
+    async fn metrics(
+        &self, ctx: &Context<'_>, filter: MetricsFilter,
+    ) -> Result<Vec<GqlMetricEntry>> {
+        query_metrics(ctx, filter).await
+    }
+
+    async fn aggregated_metrics(
+        &self, ctx: &Context<'_>, filter: MetricsFilter, period: GqlAggregationPeriod,
+        #[graphql(default_with = "GqlAggregationType::Avg")] aggregation: GqlAggregationType,
+    ) -> Result<GqlAggregatedMetrics> {
+        let state = ctx.data::<AppState>()?;
+        let inner = state.inner.read().await;
+        let metrics_service = core(&inner)?
+            .metrics_service
+            .as_ref()
+            .ok_or("Metrics service not available")?;
+
+        let mut query = MetricsQuery::from(filter);
+        query.aggregation_period = Some(period.into());
+        query.aggregation_type = Some(aggregation.into());
+        let aggregated = metrics_service.query_aggregated_metrics(query).await?;
+
+        Ok(GqlAggregatedMetrics {
+            metrics: aggregated
+                .metrics
+                .into_iter()
+                .map(|metric| GqlAggregatedMetric {
+                    timestamp: metric.timestamp,
+                    value: metric.value,
+                    count: metric.count,
+                    min: metric.min,
+                    max: metric.max,
+                    avg: metric.avg,
+                })
+                .collect(),
+            total_count: aggregated.total_count,
+            metric_type: aggregated.metric_type.into(),
+            aggregation_period: aggregated.aggregation_period.into(),
+        })
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+struct Event {
+    // Same names as the `/ws` feed, e.g. `pipeline-status-changed`.
+    name: String,
+    payload: Json<serde_json::Value>,
+}
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    // Core events as they are broadcast to `WebSocketEventBus`, optionally
+    // limited to the given event names.
+    async fn events(
+        &self, ctx: &Context<'_>, names: Option<Vec<String>>,
+    ) -> Result<impl Stream<Item = Event>> {
+        let state = ctx.data::<AppState>()?;
+        let receiver = state.ws_event_bus.subscribe();
+
+        let events = futures_util::stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "GraphQL subscriber lagged behind events");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        Ok(events.filter_map(move |event| {
+            let wanted = names
+                .as_ref()
+                .is_none_or(|names| names.iter().any(|name| name == event.event_name()));
+            let event = wanted.then(|| Event {
+                name: event.event_name().to_string(),
+                payload: Json(event.to_json_payload()),
+            });
+            async move { event }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn pipeline(name: &str, status: domain::PipelineStatus) -> domain::Pipeline {
+        domain::Pipeline {
+            id: format!("github__1__org__{}", name),
+            provider_id: 1,
+            provider_type: "github".to_string(),
+            name: name.to_string(),
+            status,
+            last_run: None,
+            last_updated: Utc::now(),
+            repository: "org/app".to_string(),
+            branch: Some("main".to_string()),
+            workflow_file: None,
+            metadata: HashMap::new(),
+            labels: vec!["team:payments".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_pipeline_filter() {
+        let deploy = pipeline("Deploy", domain::PipelineStatus::Failed);
+
+        assert!(PipelineFilter::default().matches(&deploy));
+
+        let failed = PipelineFilter {
+            statuses: vec![GqlPipelineStatus::Failed],
+            search: Some("deploy".to_string()),
+            labels: vec!["team".to_string()],
+            ..Default::default()
+        };
+        assert!(failed.matches(&deploy));
+
+        let running = PipelineFilter {
+            statuses: vec![GqlPipelineStatus::Running],
+            ..Default::default()
+        };
+        assert!(!running.matches(&deploy));
+
+        let other_team = PipelineFilter {
+            labels: vec!["team:search".to_string()],
+            ..Default::default()
+        };
+        assert!(!other_team.matches(&deploy));
+    }
+
+    #[test]
+    fn test_schema_exposes_root_fields() {
+        let sdl = schema().sdl();
+        for field in [
+            "providers:",
+            "pipelines(",
+            "runs(",
+            "aggregatedMetrics(",
+            "events(",
+        ] {
+            assert!(sdl.contains(field), "missing {}", field);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schema_rejects_deep_queries() {
+        let nested = (0..MAX_QUERY_DEPTH).fold("name".to_string(), |inner, _| {
+            format!("ofType {{ {} }}", inner)
+        });
+        let query = format!("{{ __schema {{ types {{ {} }} }} }}", nested);
+
+        let response = schema().execute(query.as_str()).await;
+        assert!(
+            response.errors[0].message.contains("nested too deep"),
+            "{:?}",
+            response.errors
+        );
+    }
+}
@@ -1,4 +1,7 @@
+mod badge;
 mod error;
+mod graphql;
+mod rate_limit;
 mod routes;
 mod state;
 mod static_files;
@@ -6,10 +9,14 @@ mod ws;
 
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context;
 use axum::{
-    extract::Request,
+    extract::{
+        DefaultBodyLimit,
+        Request,
+    },
     http::{
         header::AUTHORIZATION,
         StatusCode,
@@ -18,6 +25,8 @@ use axum::{
     response::Response,
     Router,
 };
+use pipedash_core::application::with_role;
+use pipedash_core::domain::Role;
 use pipedash_core::infrastructure::{
     ConfigLoader,
     Platform,
@@ -29,8 +38,15 @@ use tower_http::cors::{
     Any,
     CorsLayer,
 };
+use tower_http::timeout::TimeoutLayer;
 use tower_http::trace::TraceLayer;
 
+use crate::error::AppError;
+use crate::rate_limit::{
+    rate_limit_middleware,
+    RateLimitConfig,
+    RateLimiter,
+};
 use crate::state::AppState;
 use crate::ws::WebSocketEventBus;
 
@@ -38,6 +54,11 @@ struct ApiServerConfig {
     bind_addr: SocketAddr,
     cors_allow_all: bool,
     enable_embedded_frontend: bool,
+    enable_webhooks: bool,
+    request_body_limit: usize,
+    request_timeout: Option<Duration>,
+    shutdown_timeout: Duration,
+    rate_limit: RateLimitConfig,
 }
 
 /// Get the current API auth token from environment variable.
@@ -46,15 +67,51 @@ fn get_api_auth_token() -> Option<String> {
     std::env::var("PIPEDASH_VAULT_PASSWORD").ok()
 }
 
-async fn auth_middleware(req: Request, next: Next) -> Result<Response, StatusCode> {
+/// Status badges are embedded as plain images, which cannot send a bearer
+/// token, so they skip auth unless `PIPEDASH_PUBLIC_BADGES` is turned off.
+fn public_badges() -> bool {
+    std::env::var("PIPEDASH_PUBLIC_BADGES")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(true)
+}
+
+/// Resolve the role for a bearer token. The vault password grants admin access;
+/// optional per-role tokens let teammates in with reduced permissions.
+fn role_for_token(token: &str, admin_token: &str) -> Option<Role> {
+    if token == admin_token {
+        return Some(Role::Admin);
+    }
+
+    [
+        ("PIPEDASH_OPERATOR_TOKEN", Role::Operator),
+        ("PIPEDASH_VIEWER_TOKEN", Role::Viewer),
+    ]
+    .into_iter()
+    .find(|(var, _)| {
+        std::env::var(var)
+            .map(|expected| !expected.is_empty() && expected == token)
+            .unwrap_or(false)
+    })
+    .map(|(_, role)| role)
+}
+
+async fn auth_middleware(req: Request, next: Next) -> Result<Response, AppError> {
     let path = req.uri().path();
 
     if !path.starts_with("/api/v1/")
         || path.starts_with("/api/v1/health")
-        || path.starts_with("/api/v1/setup")
+        // Applying a setup document can reconfigure a running server, so it
+        // needs the admin token once a vault password is set.
+        || (path.starts_with("/api/v1/setup") && path != "/api/v1/setup/apply")
         || path.starts_with("/api/v1/vault")
         || path == "/api/v1/ws"
+        // GraphQL subscriptions authenticate with their `connection_init` payload.
+        || path == "/api/v1/graphql/ws"
         || path == "/api/v1/plugins"
+        || path == "/api/v1/openapi.json"
+        || path.starts_with("/api/v1/docs")
+        || path.starts_with("/api/v1/webhooks/")
+        || (path.ends_with("/badge.svg") && public_badges())
     {
         return Ok(next.run(req).await);
     }
@@ -64,20 +121,16 @@ async fn auth_middleware(req: Request, next: Next) -> Result<Response, StatusCod
         None => return Ok(next.run(req).await),
     };
 
-    let auth_header = req
+    let role = req
         .headers()
         .get(AUTHORIZATION)
-        .and_then(|v| v.to_str().ok());
-
-    match auth_header {
-        Some(h)
-            if h.strip_prefix("Bearer ")
-                .map(|t| t == token)
-                .unwrap_or(false) =>
-        {
-            Ok(next.run(req).await)
-        }
-        _ => Err(StatusCode::UNAUTHORIZED),
+        .and_then(|v| v.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .and_then(|t| role_for_token(t, &token));
+
+    match role {
+        Some(role) => Ok(with_role(role, next.run(req)).await),
+        None => Err(AppError::unauthorized("Missing or invalid bearer token")),
     }
 }
 
@@ -96,14 +149,88 @@ impl ApiServerConfig {
             .map(|v| v == "true" || v == "1")
             .unwrap_or(true); // Default to true for production
 
+        let enable_webhooks = std::env::var("PIPEDASH_WEBHOOKS_ENABLED")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        let request_body_limit = env_number("PIPEDASH_REQUEST_BODY_LIMIT_MB", 10) * 1024 * 1024;
+
+        // 0 turns the timeout off.
+        let request_timeout = Some(env_number("PIPEDASH_REQUEST_TIMEOUT_SECS", 60))
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs);
+
+        // Below Kubernetes' default 30s termination grace period, so the
+        // server exits on its own before it gets killed.
+        let shutdown_timeout =
+            Duration::from_secs(env_number("PIPEDASH_SHUTDOWN_TIMEOUT_SECS", 25));
+
         Self {
             bind_addr,
             cors_allow_all,
             enable_embedded_frontend,
+            enable_webhooks,
+            request_body_limit,
+            request_timeout,
+            shutdown_timeout,
+            rate_limit: RateLimitConfig::from_env(),
         }
     }
 }
 
+fn env_number<T>(name: &str, default: T) -> T
+where
+    T: std::str::FromStr,
+{
+    match std::env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid {}: {}", name, value)),
+        Err(_) => default,
+    }
+}
+
+// Resolves on SIGTERM or Ctrl+C. Readiness fails from then on and WebSocket
+// clients are told to go away, while in-flight requests get to finish.
+async fn shutdown_signal(state: AppState, timeout: Duration) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to listen for Ctrl+C");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+
+    tracing::info!("Shutdown signal received - draining connections");
+    state.inner.write().await.shutting_down = true;
+    state.ws_event_bus.close_all();
+
+    // Requests that are still running when the grace period ends are cut off
+    // rather than left for the orchestrator to kill.
+    tokio::spawn(async move {
+        tokio::time::sleep(timeout).await;
+        tracing::warn!(
+            "Connections still open after {}s - exiting",
+            timeout.as_secs()
+        );
+        std::process::exit(1);
+    });
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     rustls::crypto::ring::default_provider()
@@ -220,15 +347,37 @@ async fn main() -> anyhow::Result<()> {
         app_state
     };
 
+    let mut api_router = routes::api_router();
+    if let Some(timeout) = api_config.request_timeout {
+        api_router = api_router.layer(TimeoutLayer::with_status_code(
+            StatusCode::REQUEST_TIMEOUT,
+            timeout,
+        ));
+    }
+    if api_config.enable_webhooks {
+        tracing::info!("Webhook receiver enabled at /api/v1/webhooks");
+        api_router = api_router.nest("/webhooks", routes::webhooks::router());
+    }
+
+    let rate_limiter = Arc::new(RateLimiter::new(api_config.rate_limit));
+
     let app = Router::new()
-        .nest("/api/v1", routes::api_router())
+        .nest("/api/v1", api_router)
         .route("/api/v1/ws", axum::routing::get(ws::ws_handler))
+        .merge(routes::openapi::swagger_ui())
+        .merge(routes::links::router())
         .fallback(if api_config.enable_embedded_frontend {
             axum::routing::get(static_files::serve_static)
         } else {
             axum::routing::get(|| async { axum::http::StatusCode::NOT_FOUND })
         })
         .layer(axum::middleware::from_fn(auth_middleware))
+        // Outside auth, so rejected bearer tokens count as failures.
+        .layer(axum::middleware::from_fn_with_state(
+            (rate_limiter, app_state.clone()),
+            rate_limit_middleware,
+        ))
+        .layer(DefaultBodyLimit::max(api_config.request_body_limit))
         .layer(TraceLayer::new_for_http())
         .layer(if api_config.cors_allow_all {
             CorsLayer::new()
@@ -238,11 +387,32 @@ async fn main() -> anyhow::Result<()> {
         } else {
             CorsLayer::new()
         })
-        .with_state(app_state);
+        .with_state(app_state.clone());
 
     tracing::info!("Listening on {}", api_config.bind_addr);
     let listener = tokio::net::TcpListener::bind(api_config.bind_addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal(
+        app_state.clone(),
+        api_config.shutdown_timeout,
+    ))
+    .await?;
+
+    // Upgraded WebSocket connections aren't tracked by the server itself.
+    if !app_state
+        .ws_event_bus
+        .drain(api_config.shutdown_timeout)
+        .await
+    {
+        tracing::warn!("Some WebSocket connections did not close in time");
+    }
+    if let Some(core) = app_state.inner.read().await.core.as_ref() {
+        core.shutdown().await;
+    }
+    tracing::info!("Shutdown complete");
 
     Ok(())
 }
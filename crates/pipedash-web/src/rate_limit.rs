@@ -0,0 +1,458 @@
+use std::collections::HashMap;
+use std::net::{
+    IpAddr,
+    SocketAddr,
+};
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use axum::{
+    extract::{
+        ConnectInfo,
+        Request,
+        State,
+    },
+    http::{
+        header::{
+            AUTHORIZATION,
+            RETRY_AFTER,
+        },
+        HeaderMap,
+        HeaderValue,
+        StatusCode,
+    },
+    middleware::Next,
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use pipedash_core::domain::{
+    AuditAction,
+    AuditEntry,
+};
+
+use crate::error::AppError;
+use crate::state::AppState;
+
+// Endpoints that check a password or token. They get the stricter auth limit,
+// and their failures count towards a lockout. Login endpoints belong here too.
+pub const AUTH_PATHS: &[&str] = &["/api/v1/vault/unlock", "/api/v1/vault/rotate"];
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+// Past this many tracked clients, idle ones are dropped on the next request.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+// Set as a response extension by handlers that report a failed authentication
+// in the body rather than with a 401.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthFailed;
+
+pub struct RateLimitConfig {
+    // Requests per minute and client across the API; 0 turns the limit off.
+    pub requests_per_minute: u32,
+    pub auth_requests_per_minute: u32,
+    pub max_auth_failures: u32,
+    pub lockout: Duration,
+    // Take the client address from X-Forwarded-For, for deployments behind a
+    // reverse proxy. Only safe when clients can't reach the server directly.
+    pub trust_proxy: bool,
+}
+
+impl RateLimitConfig {
+    pub fn from_env() -> Self {
+        Self {
+            requests_per_minute: crate::env_number("PIPEDASH_RATE_LIMIT_PER_MINUTE", 600),
+            auth_requests_per_minute: crate::env_number("PIPEDASH_AUTH_RATE_LIMIT_PER_MINUTE", 10),
+            max_auth_failures: crate::env_number("PIPEDASH_AUTH_MAX_FAILURES", 5),
+            lockout: Duration::from_secs(crate::env_number("PIPEDASH_AUTH_LOCKOUT_SECS", 900)),
+            trust_proxy: std::env::var("PIPEDASH_TRUST_PROXY")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Decision {
+    Allow,
+    // `first` is set for the first rejection in a window, so a client that
+    // keeps retrying produces one audit entry rather than one per request.
+    Limited { retry_after: Duration, first: bool },
+    LockedOut { retry_after: Duration },
+}
+
+struct Window {
+    started: Instant,
+    count: u32,
+    rejected: bool,
+}
+
+impl Window {
+    fn new(now: Instant) -> Self {
+        Self {
+            started: now,
+            count: 0,
+            rejected: false,
+        }
+    }
+
+    fn hit(&mut self, limit: u32, now: Instant) -> Decision {
+        if now.duration_since(self.started) >= WINDOW {
+            *self = Window::new(now);
+        }
+        if limit == 0 || self.count < limit {
+            self.count += 1;
+            return Decision::Allow;
+        }
+
+        let first = !self.rejected;
+        self.rejected = true;
+        Decision::Limited {
+            retry_after: WINDOW.saturating_sub(now.duration_since(self.started)),
+            first,
+        }
+    }
+}
+
+struct ClientState {
+    requests: Window,
+    auth_requests: Window,
+    failures: u32,
+    locked_until: Option<Instant>,
+    last_seen: Instant,
+}
+
+impl ClientState {
+    fn new(now: Instant) -> Self {
+        Self {
+            requests: Window::new(now),
+            auth_requests: Window::new(now),
+            failures: 0,
+            locked_until: None,
+            last_seen: now,
+        }
+    }
+}
+
+// Fixed one-minute windows per client address, plus a failure counter that
+// locks the client out once it reaches `max_auth_failures`.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    clients: Mutex<HashMap<IpAddr, ClientState>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn check(&self, client: IpAddr, auth: bool, now: Instant) -> Decision {
+        let mut clients = self.clients.lock().unwrap();
+        if clients.len() >= MAX_TRACKED_CLIENTS {
+            clients.retain(|_, state| {
+                now.duration_since(state.last_seen) < WINDOW
+                    || state.locked_until.is_some_and(|until| until > now)
+            });
+        }
+
+        let state = clients
+            .entry(client)
+            .or_insert_with(|| ClientState::new(now));
+        state.last_seen = now;
+
+        if let Some(until) = state.locked_until {
+            if until > now {
+                return Decision::LockedOut {
+                    retry_after: until - now,
+                };
+            }
+            state.locked_until = None;
+            state.failures = 0;
+        }
+
+        if auth {
+            let decision = state
+                .auth_requests
+                .hit(self.config.auth_requests_per_minute, now);
+            if decision != Decision::Allow {
+                return decision;
+            }
+        }
+        state.requests.hit(self.config.requests_per_minute, now)
+    }
+
+    // Returns true when this failure locked the client out.
+    pub fn record_failure(&self, client: IpAddr, now: Instant) -> bool {
+        if self.config.max_auth_failures == 0 {
+            return false;
+        }
+
+        let mut clients = self.clients.lock().unwrap();
+        let state = clients
+            .entry(client)
+            .or_insert_with(|| ClientState::new(now));
+        state.failures += 1;
+        if state.failures < self.config.max_auth_failures {
+            return false;
+        }
+
+        state.locked_until = Some(now + self.config.lockout);
+        true
+    }
+
+    pub fn record_success(&self, client: IpAddr) {
+        if let Some(state) = self.clients.lock().unwrap().get_mut(&client) {
+            state.failures = 0;
+        }
+    }
+
+    fn client_addr(&self, headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<IpAddr> {
+        if self.config.trust_proxy {
+            // The last entry is the one added by the proxy itself; anything
+            // before it came from the client and can be forged.
+            let forwarded = headers
+                .get_all("x-forwarded-for")
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .flat_map(|v| v.split(','))
+                .filter_map(|addr| addr.trim().parse().ok())
+                .next_back();
+            if forwarded.is_some() {
+                return forwarded;
+            }
+        }
+        peer.map(|addr| addr.ip())
+    }
+}
+
+pub async fn rate_limit_middleware(
+    State((limiter, state)): State<(Arc<RateLimiter>, AppState)>, req: Request, next: Next,
+) -> Response {
+    let path = req.uri().path().to_string();
+    if !path.starts_with("/api/v1/") || path.starts_with("/api/v1/health") {
+        return next.run(req).await;
+    }
+
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| info.0);
+    let Some(client) = limiter.client_addr(req.headers(), peer) else {
+        return next.run(req).await;
+    };
+
+    let auth_path = AUTH_PATHS.contains(&path.as_str());
+    match limiter.check(client, auth_path, Instant::now()) {
+        Decision::Allow => {}
+        Decision::LockedOut { retry_after } => {
+            return too_many_requests(
+                "Too many failed authentication attempts; try again later",
+                retry_after,
+            );
+        }
+        Decision::Limited { retry_after, first } => {
+            if auth_path && first {
+                audit(
+                    &state,
+                    AuditEntry::new(
+                        AuditAction::AuthRateLimited,
+                        &client.to_string(),
+                        &path,
+                        None,
+                    ),
+                )
+                .await;
+            }
+            return too_many_requests("Rate limit exceeded", retry_after);
+        }
+    }
+
+    let presented_credentials = auth_path || req.headers().contains_key(AUTHORIZATION);
+    let response = next.run(req).await;
+
+    let failed = response.status() == StatusCode::UNAUTHORIZED
+        || response.extensions().get::<AuthFailed>().is_some();
+    if failed {
+        let locked = limiter.record_failure(client, Instant::now());
+        audit(
+            &state,
+            AuditEntry::new(AuditAction::AuthFailed, &client.to_string(), &path, None),
+        )
+        .await;
+        if locked {
+            audit(
+                &state,
+                AuditEntry::new(
+                    AuditAction::AuthLockout,
+                    &client.to_string(),
+                    &path,
+                    Some(format!(
+                        "Locked out for {}s",
+                        limiter.config.lockout.as_secs()
+                    )),
+                ),
+            )
+            .await;
+        }
+    } else if presented_credentials && response.status().is_success() {
+        limiter.record_success(client);
+    }
+
+    response
+}
+
+fn too_many_requests(message: &str, retry_after: Duration) -> Response {
+    let mut response = AppError::too_many_requests(message).into_response();
+    // Round up, so clients don't retry a moment too early.
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(secs.max(1)));
+    response
+}
+
+// Before setup there is no database, so the entry only goes to the log.
+async fn audit(state: &AppState, entry: AuditEntry) {
+    let audit_log = state
+        .inner
+        .read()
+        .await
+        .core
+        .as_ref()
+        .map(|core| core.audit_log.clone());
+
+    match audit_log {
+        Some(audit_log) => audit_log.record(entry).await,
+        None => tracing::warn!(
+            target: "pipedash::audit",
+            action = entry.action.as_str(),
+            actor = %entry.actor,
+            target = %entry.target,
+            "Audit event"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(requests_per_minute: u32, auth_requests_per_minute: u32) -> RateLimiter {
+        RateLimiter::new(RateLimitConfig {
+            requests_per_minute,
+            auth_requests_per_minute,
+            max_auth_failures: 3,
+            lockout: Duration::from_secs(900),
+            trust_proxy: false,
+        })
+    }
+
+    fn client(last: u8) -> IpAddr {
+        IpAddr::from([10, 0, 0, last])
+    }
+
+    #[test]
+    fn test_window_limits_per_client() {
+        let limiter = limiter(2, 1);
+        let now = Instant::now();
+
+        assert_eq!(limiter.check(client(1), false, now), Decision::Allow);
+        assert_eq!(limiter.check(client(1), false, now), Decision::Allow);
+        assert_eq!(
+            limiter.check(client(1), false, now + Duration::from_secs(20)),
+            Decision::Limited {
+                retry_after: Duration::from_secs(40),
+                first: true
+            }
+        );
+        assert!(matches!(
+            limiter.check(client(1), false, now + Duration::from_secs(21)),
+            Decision::Limited { first: false, .. }
+        ));
+        assert_eq!(limiter.check(client(2), false, now), Decision::Allow);
+
+        // A new window starts a minute later.
+        assert_eq!(
+            limiter.check(client(1), false, now + WINDOW),
+            Decision::Allow
+        );
+    }
+
+    #[test]
+    fn test_auth_paths_get_the_stricter_limit() {
+        let limiter = limiter(100, 1);
+        let now = Instant::now();
+
+        assert_eq!(limiter.check(client(1), true, now), Decision::Allow);
+        assert!(matches!(
+            limiter.check(client(1), true, now),
+            Decision::Limited { .. }
+        ));
+        assert_eq!(limiter.check(client(1), false, now), Decision::Allow);
+    }
+
+    #[test]
+    fn test_lockout_after_repeated_failures() {
+        let limiter = limiter(0, 0);
+        let now = Instant::now();
+
+        assert!(!limiter.record_failure(client(1), now));
+        assert!(!limiter.record_failure(client(1), now));
+        limiter.record_success(client(1));
+        assert!(!limiter.record_failure(client(1), now));
+        assert!(!limiter.record_failure(client(1), now));
+        assert!(limiter.record_failure(client(1), now));
+
+        assert_eq!(
+            limiter.check(client(1), false, now + Duration::from_secs(100)),
+            Decision::LockedOut {
+                retry_after: Duration::from_secs(800)
+            }
+        );
+        assert_eq!(limiter.check(client(2), true, now), Decision::Allow);
+
+        // The lockout expires and the failure count starts over.
+        let later = now + Duration::from_secs(900);
+        assert_eq!(limiter.check(client(1), true, later), Decision::Allow);
+        assert!(!limiter.record_failure(client(1), later));
+    }
+
+    #[test]
+    fn test_client_addr_behind_proxy() {
+        let peer = Some(SocketAddr::from(([127, 0, 0, 1], 4000)));
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.9, 10.0.0.7"),
+        );
+
+        let direct = limiter(0, 0);
+        assert_eq!(
+            direct.client_addr(&headers, peer),
+            Some(IpAddr::from([127, 0, 0, 1]))
+        );
+
+        let proxied = RateLimiter::new(RateLimitConfig {
+            requests_per_minute: 0,
+            auth_requests_per_minute: 0,
+            max_auth_failures: 0,
+            lockout: Duration::ZERO,
+            trust_proxy: true,
+        });
+        assert_eq!(
+            proxied.client_addr(&headers, peer),
+            Some(IpAddr::from([10, 0, 0, 7]))
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use axum::{
+    extract::{
+        Query,
+        State,
+    },
+    routing::{
+        get,
+        post,
+    },
+    Json,
+    Router,
+};
+use pipedash_core::domain::ProviderAgent;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AgentsQuery {
+    pub provider_id: Option<i64>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_cached_agents))
+        .route("/fetch", post(fetch_agents))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/agents",
+    tag = "agents",
+    params(AgentsQuery),
+    responses((status = 200, description = "Get cached agents", body = [Object]))
+)]
+async fn get_cached_agents(
+    State(state): State<AppState>, Query(query): Query<AgentsQuery>,
+) -> ApiResult<Json<Vec<ProviderAgent>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let agents = core
+        .agent_service
+        .get_cached_agents(query.provider_id)
+        .await?;
+    Ok(Json(agents))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/agents/fetch",
+    tag = "agents",
+    params(AgentsQuery),
+    responses((status = 200, description = "Fetch agents", body = [Object]))
+)]
+async fn fetch_agents(
+    State(state): State<AppState>, Query(query): Query<AgentsQuery>,
+) -> ApiResult<Json<Vec<ProviderAgent>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let agents = core.agent_service.fetch_agents(query.provider_id).await?;
+    Ok(Json(agents))
+}
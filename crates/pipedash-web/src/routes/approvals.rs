@@ -0,0 +1,146 @@
+use axum::{
+    extract::{
+        Path,
+        Query,
+        State,
+    },
+    routing::{
+        get,
+        post,
+    },
+    Json,
+    Router,
+};
+use pipedash_core::domain::TriggerApproval;
+use serde::Deserialize;
+use utoipa::{
+    IntoParams,
+    ToSchema,
+};
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListApprovalsQuery {
+    #[serde(default)]
+    pub pending_only: bool,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct DecideApprovalRequest {
+    // Needed to approve your own request, when self-approval is allowed.
+    #[serde(default)]
+    pub confirmed: bool,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ApprovalRuleRequest {
+    pub pipeline_id: String,
+    pub required: bool,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_approvals))
+        .route("/{id}/approve", post(approve_trigger))
+        .route("/{id}/reject", post(reject_trigger))
+        .route("/rules", get(list_rules).post(set_rule))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/approvals",
+    tag = "approvals",
+    params(ListApprovalsQuery),
+    responses((status = 200, description = "List trigger requests, newest first", body = [Object]))
+)]
+async fn list_approvals(
+    State(state): State<AppState>, Query(query): Query<ListApprovalsQuery>,
+) -> ApiResult<Json<Vec<TriggerApproval>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let approvals = core
+        .pipeline_service
+        .list_trigger_approvals(query.pending_only, query.limit)
+        .await?;
+    Ok(Json(approvals))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/approvals/{id}/approve",
+    tag = "approvals",
+    params(("id" = i64, Path)),
+    request_body = DecideApprovalRequest,
+    responses((status = 200, description = "Approve a trigger request and run it", body = Object))
+)]
+async fn approve_trigger(
+    State(state): State<AppState>, Path(id): Path<i64>, body: Option<Json<DecideApprovalRequest>>,
+) -> ApiResult<Json<TriggerApproval>> {
+    let req = body.map(|Json(req)| req).unwrap_or_default();
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let approval = core
+        .pipeline_service
+        .decide_trigger_approval(id, true, req.confirmed, req.comment)
+        .await?;
+    Ok(Json(approval))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/approvals/{id}/reject",
+    tag = "approvals",
+    params(("id" = i64, Path)),
+    request_body = DecideApprovalRequest,
+    responses((status = 200, description = "Reject a trigger request", body = Object))
+)]
+async fn reject_trigger(
+    State(state): State<AppState>, Path(id): Path<i64>, body: Option<Json<DecideApprovalRequest>>,
+) -> ApiResult<Json<TriggerApproval>> {
+    let req = body.map(|Json(req)| req).unwrap_or_default();
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let approval = core
+        .pipeline_service
+        .decide_trigger_approval(id, false, false, req.comment)
+        .await?;
+    Ok(Json(approval))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/approvals/rules",
+    tag = "approvals",
+    responses((status = 200, description = "Pipelines whose triggers need approval", body = [String]))
+)]
+async fn list_rules(State(state): State<AppState>) -> ApiResult<Json<Vec<String>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    Ok(Json(core.pipeline_service.list_approval_rules().await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/approvals/rules",
+    tag = "approvals",
+    request_body = ApprovalRuleRequest,
+    responses((status = 200, description = "Mark a pipeline as needing approval, or not"))
+)]
+async fn set_rule(
+    State(state): State<AppState>, Json(req): Json<ApprovalRuleRequest>,
+) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.pipeline_service
+        .set_approval_required(&req.pipeline_id, req.required)
+        .await?;
+    Ok(())
+}
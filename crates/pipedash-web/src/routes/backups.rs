@@ -0,0 +1,87 @@
+use axum::{
+    extract::{
+        Path,
+        State,
+    },
+    routing::{
+        get,
+        post,
+    },
+    Json,
+    Router,
+};
+use pipedash_core::domain::{
+    BackupInfo,
+    BackupVerification,
+};
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_backups).post(create_backup))
+        .route("/{id}/verify", post(verify_backup))
+        .route("/{id}/restore", post(restore_backup))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/backups",
+    tag = "backups",
+    responses((status = 200, description = "List backups", body = [Object]))
+)]
+async fn list_backups(State(state): State<AppState>) -> ApiResult<Json<Vec<BackupInfo>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let backups = core.backup_service.list_backups().await?;
+    Ok(Json(backups))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/backups",
+    tag = "backups",
+    responses((status = 200, description = "Create backup", body = Object))
+)]
+async fn create_backup(State(state): State<AppState>) -> ApiResult<Json<BackupInfo>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let info = core.backup_service.create_backup().await?;
+    Ok(Json(info))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/backups/{id}/verify",
+    tag = "backups",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Verify backup", body = Object))
+)]
+async fn verify_backup(
+    State(state): State<AppState>, Path(id): Path<String>,
+) -> ApiResult<Json<BackupVerification>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let verification = core.backup_service.verify_backup(&id).await?;
+    Ok(Json(verification))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/backups/{id}/restore",
+    tag = "backups",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Restore backup", body = Object))
+)]
+async fn restore_backup(
+    State(state): State<AppState>, Path(id): Path<String>,
+) -> ApiResult<Json<BackupInfo>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let info = core.backup_service.restore_backup(&id).await?;
+    Ok(Json(info))
+}
@@ -6,11 +6,22 @@ use axum::{
     routing::{
         delete,
         get,
+        post,
+        put,
     },
     Json,
     Router,
 };
-use serde::Serialize;
+use pipedash_core::domain::{
+    GlobalRunRetentionConfig,
+    PipelineRunRetention,
+    RunRetentionPolicy,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use utoipa::ToSchema;
 
 use crate::error::{
     ApiResult,
@@ -18,19 +29,28 @@ use crate::error::{
 };
 use crate::state::AppState;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CacheStats {
     pub pipelines_count: i64,
     pub run_history_count: i64,
     pub workflow_params_count: i64,
     pub metrics_count: i64,
+    #[schema(value_type = Option<Object>)]
+    pub run_history_retention: Option<GlobalRunRetentionConfig>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CacheClearResponse {
     pub cleared: usize,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRetentionRequest {
+    pub enabled: bool,
+    pub max_age_days: Option<i64>,
+    pub max_runs_per_pipeline: Option<i64>,
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/stats", get(get_cache_stats))
@@ -42,8 +62,24 @@ pub fn router() -> Router<AppState> {
         .route("/pipelines", delete(clear_pipelines_cache))
         .route("/workflow-params", delete(clear_workflow_params_cache))
         .route("/", delete(clear_all_caches))
+        .route(
+            "/retention",
+            get(get_run_retention_config).put(update_run_retention_config),
+        )
+        .route("/retention/pipelines", get(list_pipeline_run_retention))
+        .route(
+            "/retention/pipelines/{pipeline_id}",
+            put(set_pipeline_run_retention).delete(remove_pipeline_run_retention),
+        )
+        .route("/retention/prune", post(prune_run_history))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/cache/stats",
+    tag = "cache",
+    responses((status = 200, description = "Get cache stats", body = CacheStats))
+)]
 async fn get_cache_stats(State(state): State<AppState>) -> ApiResult<Json<CacheStats>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
@@ -75,14 +111,24 @@ async fn get_cache_stats(State(state): State<AppState>) -> ApiResult<Json<CacheS
         0
     };
 
+    let run_history_retention = core.retention_service.get_config().await.ok();
+
     Ok(Json(CacheStats {
         pipelines_count,
         run_history_count,
         workflow_params_count,
         metrics_count,
+        run_history_retention,
     }))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/cache/run-history/{pipeline_id}",
+    tag = "cache",
+    params(("pipeline_id" = String, Path)),
+    responses((status = 200, description = "Clear run history cache"))
+)]
 async fn clear_run_history_cache(
     State(state): State<AppState>, Path(pipeline_id): Path<String>,
 ) -> ApiResult<()> {
@@ -94,6 +140,12 @@ async fn clear_run_history_cache(
     Ok(())
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/cache/run-history",
+    tag = "cache",
+    responses((status = 200, description = "Clear all run history caches"))
+)]
 async fn clear_all_run_history_caches(State(state): State<AppState>) -> ApiResult<()> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
@@ -101,6 +153,12 @@ async fn clear_all_run_history_caches(State(state): State<AppState>) -> ApiResul
     Ok(())
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/cache/pipelines",
+    tag = "cache",
+    responses((status = 200, description = "Clear pipelines cache", body = CacheClearResponse))
+)]
 async fn clear_pipelines_cache(
     State(state): State<AppState>,
 ) -> ApiResult<Json<CacheClearResponse>> {
@@ -110,6 +168,12 @@ async fn clear_pipelines_cache(
     Ok(Json(CacheClearResponse { cleared }))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/cache/workflow-params",
+    tag = "cache",
+    responses((status = 200, description = "Clear workflow params cache"))
+)]
 async fn clear_workflow_params_cache(State(state): State<AppState>) -> ApiResult<()> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
@@ -117,6 +181,12 @@ async fn clear_workflow_params_cache(State(state): State<AppState>) -> ApiResult
     Ok(())
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/cache",
+    tag = "cache",
+    responses((status = 200, description = "Clear all caches"))
+)]
 async fn clear_all_caches(State(state): State<AppState>) -> ApiResult<()> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
@@ -125,3 +195,107 @@ async fn clear_all_caches(State(state): State<AppState>) -> ApiResult<()> {
     core.pipeline_service.clear_workflow_params_cache().await?;
     Ok(())
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/cache/retention",
+    tag = "cache",
+    responses((status = 200, description = "Get run retention config", body = Object))
+)]
+async fn get_run_retention_config(
+    State(state): State<AppState>,
+) -> ApiResult<Json<GlobalRunRetentionConfig>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let config = core.retention_service.get_config().await?;
+    Ok(Json(config))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/cache/retention",
+    tag = "cache",
+    request_body = UpdateRetentionRequest,
+    responses((status = 200, description = "Update run retention config", body = Object))
+)]
+async fn update_run_retention_config(
+    State(state): State<AppState>, Json(req): Json<UpdateRetentionRequest>,
+) -> ApiResult<Json<GlobalRunRetentionConfig>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let policy = RunRetentionPolicy {
+        max_age_days: req.max_age_days,
+        max_runs: req.max_runs_per_pipeline,
+    };
+    let config = core
+        .retention_service
+        .update_config(req.enabled, policy)
+        .await?;
+    Ok(Json(config))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/cache/retention/pipelines",
+    tag = "cache",
+    responses((status = 200, description = "List pipeline run retention", body = [Object]))
+)]
+async fn list_pipeline_run_retention(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<PipelineRunRetention>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let overrides = core.retention_service.list_pipeline_overrides().await?;
+    Ok(Json(overrides))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/cache/retention/pipelines/{pipeline_id}",
+    tag = "cache",
+    params(("pipeline_id" = String, Path)),
+    request_body = Object,
+    responses((status = 200, description = "Set pipeline run retention"))
+)]
+async fn set_pipeline_run_retention(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+    Json(policy): Json<RunRetentionPolicy>,
+) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.retention_service
+        .set_pipeline_override(&pipeline_id, policy)
+        .await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/cache/retention/pipelines/{pipeline_id}",
+    tag = "cache",
+    params(("pipeline_id" = String, Path)),
+    responses((status = 200, description = "Remove pipeline run retention"))
+)]
+async fn remove_pipeline_run_retention(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.retention_service
+        .remove_pipeline_override(&pipeline_id)
+        .await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/cache/retention/prune",
+    tag = "cache",
+    responses((status = 200, description = "Prune run history", body = CacheClearResponse))
+)]
+async fn prune_run_history(State(state): State<AppState>) -> ApiResult<Json<CacheClearResponse>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let cleared = core.retention_service.prune_run_history().await?;
+    Ok(Json(CacheClearResponse { cleared }))
+}
@@ -0,0 +1,39 @@
+use axum::{
+    extract::{
+        Path,
+        State,
+    },
+    routing::get,
+    Json,
+    Router,
+};
+use pipedash_core::domain::CommitStatus;
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/{sha}/status", get(get_commit_status))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/commits/{sha}/status",
+    tag = "commits",
+    params(("sha" = String, Path, description = "Full or abbreviated (7+ characters) commit SHA")),
+    responses(
+        (status = 200, description = "Latest run of the commit on every pipeline that built it", body = Object),
+        (status = 400, description = "Invalid or ambiguous SHA")
+    )
+)]
+async fn get_commit_status(
+    State(state): State<AppState>, Path(sha): Path<String>,
+) -> ApiResult<Json<CommitStatus>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let status = core.pipeline_service.get_commit_status(&sha).await?;
+    Ok(Json(status))
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{
+        Path,
+        State,
+    },
+    routing::{
+        delete,
+        get,
+    },
+    Json,
+    Router,
+};
+use pipedash_core::domain::{
+    PipelineDependency,
+    PipelineGraph,
+    PipelineStatus,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SaveDependencyRequest {
+    pub upstream_id: String,
+    pub downstream_id: String,
+    #[serde(default = "default_trigger_on")]
+    #[schema(value_type = Object)]
+    pub trigger_on: PipelineStatus,
+    #[serde(default)]
+    pub inputs: HashMap<String, serde_json::Value>,
+}
+
+fn default_trigger_on() -> PipelineStatus {
+    PipelineStatus::Success
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_dependencies).post(save_dependency))
+        .route("/graph", get(get_pipeline_graph))
+        .route("/{id}", delete(delete_dependency))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/dependencies",
+    tag = "dependencies",
+    responses((status = 200, description = "List dependencies", body = [Object]))
+)]
+async fn list_dependencies(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<PipelineDependency>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let dependencies = core.orchestrator_service.list_dependencies().await?;
+    Ok(Json(dependencies))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/dependencies",
+    tag = "dependencies",
+    request_body = SaveDependencyRequest,
+    responses((status = 200, description = "Save dependency", body = Object))
+)]
+async fn save_dependency(
+    State(state): State<AppState>, Json(req): Json<SaveDependencyRequest>,
+) -> ApiResult<Json<PipelineDependency>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let dependency = core
+        .orchestrator_service
+        .save_dependency(
+            &req.upstream_id,
+            &req.downstream_id,
+            req.trigger_on,
+            req.inputs,
+        )
+        .await?;
+    Ok(Json(dependency))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/dependencies/{id}",
+    tag = "dependencies",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Delete dependency"))
+)]
+async fn delete_dependency(State(state): State<AppState>, Path(id): Path<i64>) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.orchestrator_service.delete_dependency(id).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/dependencies/graph",
+    tag = "dependencies",
+    responses((status = 200, description = "Get pipeline graph", body = Object))
+)]
+async fn get_pipeline_graph(State(state): State<AppState>) -> ApiResult<Json<PipelineGraph>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let graph = core.orchestrator_service.get_pipeline_graph().await?;
+    Ok(Json(graph))
+}
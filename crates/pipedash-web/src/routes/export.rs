@@ -0,0 +1,152 @@
+use axum::{
+    body::Body,
+    extract::{
+        Query,
+        State,
+    },
+    http::header,
+    response::{
+        IntoResponse,
+        Response,
+    },
+    routing::get,
+    Router,
+};
+use futures_util::stream::BoxStream;
+use pipedash_core::domain::{
+    DomainResult,
+    ExportFilter,
+    ExportFormat,
+    MetricType,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQueryParams {
+    // csv (default) or jsonl
+    pub format: Option<String>,
+    // Comma-separated pipeline ids; all pipelines when omitted.
+    pub pipeline_ids: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    // Metrics export only.
+    pub metric_type: Option<String>,
+}
+
+impl ExportQueryParams {
+    fn format(&self) -> ApiResult<ExportFormat> {
+        match self.format.as_deref() {
+            Some(format) => format.parse().map_err(AppError::bad_request),
+            None => Ok(ExportFormat::default()),
+        }
+    }
+
+    fn filter(&self) -> ApiResult<ExportFilter> {
+        let parse_date = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|s| {
+                    chrono::DateTime::parse_from_rfc3339(s)
+                        .map(|dt| dt.with_timezone(&chrono::Utc))
+                        .map_err(|e| AppError::bad_request(format!("Invalid date '{}': {}", s, e)))
+                })
+                .transpose()
+        };
+
+        Ok(ExportFilter {
+            pipeline_ids: self
+                .pipeline_ids
+                .as_deref()
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(String::from)
+                .collect(),
+            start_date: parse_date(&self.start_date)?,
+            end_date: parse_date(&self.end_date)?,
+        })
+    }
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/metrics", get(export_metrics))
+        .route("/runs", get(export_run_history))
+}
+
+fn attachment(
+    name: &str, format: ExportFormat, body: BoxStream<'static, DomainResult<String>>,
+) -> Response {
+    let disposition = format!(
+        "attachment; filename=\"pipedash-{}.{}\"",
+        name,
+        format.extension()
+    );
+
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/export/metrics",
+    tag = "export",
+    params(ExportQueryParams),
+    responses((status = 200, description = "Stream raw metrics as CSV or JSON Lines", content_type = "text/csv", body = String))
+)]
+async fn export_metrics(
+    State(state): State<AppState>, Query(params): Query<ExportQueryParams>,
+) -> ApiResult<Response> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let metrics_service = core
+        .metrics_service
+        .as_ref()
+        .ok_or_else(|| AppError::bad_request("Metrics service not available"))?;
+
+    let format = params.format()?;
+    let metric_type = params
+        .metric_type
+        .as_deref()
+        .map(|t| t.parse::<MetricType>().map_err(AppError::bad_request))
+        .transpose()?;
+    let stream = metrics_service.export_metrics(params.filter()?, metric_type, format);
+
+    Ok(attachment("metrics", format, stream))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/export/runs",
+    tag = "export",
+    params(ExportQueryParams),
+    responses((status = 200, description = "Stream cached run history as CSV or JSON Lines", content_type = "text/csv", body = String))
+)]
+async fn export_run_history(
+    State(state): State<AppState>, Query(params): Query<ExportQueryParams>,
+) -> ApiResult<Response> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+
+    let format = params.format()?;
+    let stream = core
+        .pipeline_service
+        .export_run_history(params.filter()?, format);
+
+    Ok(attachment("runs", format, stream))
+}
@@ -0,0 +1,71 @@
+use async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
+use async_graphql::Data;
+use async_graphql_axum::{
+    GraphQLProtocol,
+    GraphQLRequest,
+    GraphQLResponse,
+    GraphQLWebSocket,
+};
+use axum::{
+    extract::{
+        State,
+        WebSocketUpgrade,
+    },
+    response::Response,
+    routing::{
+        get,
+        post,
+    },
+    Router,
+};
+
+use crate::graphql;
+use crate::state::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", post(graphql_handler))
+        .route("/ws", get(graphql_ws_handler))
+}
+
+async fn graphql_handler(State(state): State<AppState>, req: GraphQLRequest) -> GraphQLResponse {
+    graphql::schema()
+        .execute(req.into_inner().data(state))
+        .await
+        .into()
+}
+
+async fn graphql_ws_handler(
+    State(state): State<AppState>, protocol: GraphQLProtocol, upgrade: WebSocketUpgrade,
+) -> Response {
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            let mut data = Data::default();
+            data.insert(state);
+            GraphQLWebSocket::new(stream, graphql::schema(), protocol)
+                .with_data(data)
+                .on_connection_init(authenticate)
+                .serve()
+        })
+}
+
+// Browsers can't set headers on a WebSocket, so subscriptions send the token
+// in the `connection_init` payload, as `{"token": "..."}` or
+// `{"Authorization": "Bearer ..."}`.
+async fn authenticate(payload: serde_json::Value) -> async_graphql::Result<Data> {
+    let Some(admin_token) = crate::get_api_auth_token() else {
+        return Ok(Data::default());
+    };
+
+    let token = payload.get("token").and_then(|t| t.as_str()).or_else(|| {
+        payload
+            .get("Authorization")
+            .and_then(|h| h.as_str())
+            .and_then(|h| h.strip_prefix("Bearer "))
+    });
+    match token.and_then(|t| crate::role_for_token(t, &admin_token)) {
+        Some(_) => Ok(Data::default()),
+        None => Err("Missing or invalid bearer token".into()),
+    }
+}
@@ -0,0 +1,157 @@
+use axum::{
+    extract::{
+        Path,
+        Query,
+        State,
+    },
+    response::Response,
+    routing::{
+        delete,
+        get,
+        put,
+    },
+    Json,
+    Router,
+};
+use pipedash_core::domain::{
+    PipelineGroup,
+    PipelineGroupStatus,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::badge::{
+    self,
+    BadgeQuery,
+};
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateGroupRequest {
+    pub name: String,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_groups).post(create_group))
+        .route("/{id}", delete(delete_group))
+        .route("/{id}/badge.svg", get(get_group_badge))
+        .route(
+            "/{id}/pipelines/{pipeline_id}",
+            put(assign_pipeline_to_group).delete(remove_pipeline_from_group),
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/groups",
+    tag = "groups",
+    responses((status = 200, description = "List groups", body = [Object]))
+)]
+async fn list_groups(State(state): State<AppState>) -> ApiResult<Json<Vec<PipelineGroupStatus>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let groups = core.pipeline_service.list_groups().await?;
+    Ok(Json(groups))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/groups",
+    tag = "groups",
+    request_body = CreateGroupRequest,
+    responses((status = 200, description = "Create group", body = Object))
+)]
+async fn create_group(
+    State(state): State<AppState>, Json(req): Json<CreateGroupRequest>,
+) -> ApiResult<Json<PipelineGroup>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let group = core.pipeline_service.create_group(&req.name).await?;
+    Ok(Json(group))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/groups/{id}",
+    tag = "groups",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Delete group"))
+)]
+async fn delete_group(State(state): State<AppState>, Path(id): Path<i64>) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.pipeline_service.delete_group(id).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/groups/{id}/pipelines/{pipeline_id}",
+    tag = "groups",
+    params(("id" = i64, Path), ("pipeline_id" = String, Path)),
+    responses((status = 200, description = "Assign pipeline to group", body = Object))
+)]
+async fn assign_pipeline_to_group(
+    State(state): State<AppState>, Path((id, pipeline_id)): Path<(i64, String)>,
+) -> ApiResult<Json<PipelineGroup>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let group = core
+        .pipeline_service
+        .assign_pipeline_to_group(id, &pipeline_id)
+        .await?;
+    Ok(Json(group))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/groups/{id}/pipelines/{pipeline_id}",
+    tag = "groups",
+    params(("id" = i64, Path), ("pipeline_id" = String, Path)),
+    responses((status = 200, description = "Remove pipeline from group", body = Object))
+)]
+async fn remove_pipeline_from_group(
+    State(state): State<AppState>, Path((id, pipeline_id)): Path<(i64, String)>,
+) -> ApiResult<Json<PipelineGroup>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let group = core
+        .pipeline_service
+        .remove_pipeline_from_group(id, &pipeline_id)
+        .await?;
+    Ok(Json(group))
+}
+
+// Aggregate status of the group's members, worst first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/groups/{id}/badge.svg",
+    tag = "groups",
+    params(("id" = i64, Path), BadgeQuery),
+    responses((status = 200, description = "Get group badge", content_type = "image/svg+xml", body = String))
+)]
+async fn get_group_badge(
+    State(state): State<AppState>, Path(id): Path<i64>, Query(query): Query<BadgeQuery>,
+) -> ApiResult<Response> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let group = core
+        .pipeline_service
+        .list_groups()
+        .await?
+        .into_iter()
+        .find(|status| status.group.id == id);
+
+    Ok(match group {
+        Some(status) => badge::status_badge(
+            query.label.as_deref().unwrap_or(&status.group.name),
+            &status.status,
+        ),
+        None => badge::unknown_badge(query.label.as_deref().unwrap_or("group")),
+    })
+}
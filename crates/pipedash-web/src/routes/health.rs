@@ -1,15 +1,17 @@
 use axum::{
     extract::State,
+    http::StatusCode,
     Json,
 };
 use serde::{
     Deserialize,
     Serialize,
 };
+use utoipa::ToSchema;
 
 use crate::state::AppState;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct HealthResponse {
     pub status: String,
     pub version: String,
@@ -20,12 +22,18 @@ pub struct HealthResponse {
     pub config_error: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DatabaseHealth {
     pub connected: bool,
     pub provider_count: usize,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/health",
+    tag = "health",
+    responses((status = 200, description = "Health check", body = HealthResponse))
+)]
 pub async fn health_check(State(state): State<AppState>) -> Json<HealthResponse> {
     let inner = state.inner.read().await;
 
@@ -79,3 +87,90 @@ pub async fn health_check(State(state): State<AppState>) -> Json<HealthResponse>
         config_error: None,
     })
 }
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProbeResponse {
+    pub status: String,
+    // Why the server isn't ready; empty when it is.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reasons: Vec<String>,
+}
+
+// Only says the process is up and serving requests: restarting it would not
+// help with a locked vault or an unreachable database, so those are left to
+// the readiness probe.
+#[utoipa::path(
+    get,
+    path = "/api/v1/health/live",
+    tag = "health",
+    responses((status = 200, description = "The server is running", body = ProbeResponse))
+)]
+pub async fn liveness() -> Json<ProbeResponse> {
+    Json(ProbeResponse {
+        status: "ok".to_string(),
+        reasons: Vec::new(),
+    })
+}
+
+// Ready once setup is done, the vault is unlocked, provider tokens are loaded
+// and the database answers. Fails as soon as shutdown starts so traffic moves
+// elsewhere while connections drain.
+#[utoipa::path(
+    get,
+    path = "/api/v1/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "The server can take traffic", body = ProbeResponse),
+        (status = 503, description = "The server can't take traffic yet", body = ProbeResponse)
+    )
+)]
+pub async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<ProbeResponse>) {
+    let mut reasons = Vec::new();
+    let database_pool = {
+        let inner = state.inner.read().await;
+        if inner.shutting_down {
+            reasons.push("shutting down".to_string());
+        }
+        if inner.setup_required {
+            reasons.push("setup required".to_string());
+        }
+        if let Some(error) = &inner.config_error {
+            reasons.push(format!("config error: {}", error));
+        }
+
+        match &inner.storage_manager {
+            Some(storage_manager) => {
+                if storage_manager.is_vault_locked().await {
+                    reasons.push("vault locked".to_string());
+                } else if !inner.token_store_ready {
+                    reasons.push("loading provider tokens".to_string());
+                }
+                Some(storage_manager.database_pool())
+            }
+            None => None,
+        }
+    };
+
+    if let Some(pool) = database_pool {
+        if let Err(e) = pool.ping().await {
+            reasons.push(format!("database unreachable: {}", e));
+        }
+    }
+
+    let status = if reasons.is_empty() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(ProbeResponse {
+            status: if reasons.is_empty() {
+                "ok".to_string()
+            } else {
+                "unavailable".to_string()
+            },
+            reasons,
+        }),
+    )
+}
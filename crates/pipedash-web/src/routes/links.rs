@@ -0,0 +1,26 @@
+use axum::{
+    http::Uri,
+    response::Redirect,
+    routing::get,
+    Router,
+};
+use pipedash_core::domain::PipelineLink;
+
+use crate::error::ApiResult;
+use crate::state::AppState;
+
+// Canonical `/p/{provider}/{pipeline}[/{run}]` links, as sent in notifications,
+// redirect to the matching frontend route.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/p/{provider_id}/{pipeline_id}", get(open_link))
+        .route(
+            "/p/{provider_id}/{pipeline_id}/{run_number}",
+            get(open_link),
+        )
+}
+
+async fn open_link(uri: Uri) -> ApiResult<Redirect> {
+    let link = PipelineLink::parse(uri.path())?;
+    Ok(Redirect::to(&link.app_path()))
+}
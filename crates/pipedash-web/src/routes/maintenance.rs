@@ -0,0 +1,99 @@
+use axum::{
+    extract::{
+        Path,
+        State,
+    },
+    routing::{
+        delete,
+        get,
+    },
+    Json,
+    Router,
+};
+use chrono::Utc;
+use pipedash_core::domain::{
+    MaintenanceSchedule,
+    MaintenanceWindow,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SaveMaintenanceWindowRequest {
+    pub name: String,
+    // Leave both empty to cover every pipeline.
+    #[serde(default)]
+    pub pipeline_ids: Vec<String>,
+    #[serde(default)]
+    pub group_ids: Vec<i64>,
+    #[schema(value_type = Object)]
+    pub schedule: MaintenanceSchedule,
+    pub reason: Option<String>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_windows).post(save_window))
+        .route("/{id}", delete(delete_window))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/maintenance",
+    tag = "maintenance",
+    responses((status = 200, description = "List maintenance windows", body = [Object]))
+)]
+async fn list_windows(State(state): State<AppState>) -> ApiResult<Json<Vec<MaintenanceWindow>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let windows = core.pipeline_service.list_maintenance_windows().await?;
+    Ok(Json(windows))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/maintenance",
+    tag = "maintenance",
+    request_body = SaveMaintenanceWindowRequest,
+    responses((status = 200, description = "Save a maintenance window, replacing one with the same name", body = Object))
+)]
+async fn save_window(
+    State(state): State<AppState>, Json(req): Json<SaveMaintenanceWindowRequest>,
+) -> ApiResult<Json<MaintenanceWindow>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let window = core
+        .pipeline_service
+        .save_maintenance_window(MaintenanceWindow {
+            id: 0,
+            name: req.name,
+            pipeline_ids: req.pipeline_ids,
+            group_ids: req.group_ids,
+            schedule: req.schedule,
+            reason: req.reason,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+        .await?;
+    Ok(Json(window))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/maintenance/{id}",
+    tag = "maintenance",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Delete a maintenance window"))
+)]
+async fn delete_window(State(state): State<AppState>, Path(id): Path<i64>) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.pipeline_service.delete_maintenance_window(id).await?;
+    Ok(())
+}
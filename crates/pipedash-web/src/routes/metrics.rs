@@ -1,6 +1,7 @@
 use axum::{
     extract::{
         Path,
+        Query,
         State,
     },
     routing::{
@@ -15,17 +16,26 @@ use pipedash_core::domain::{
     AggregatedMetrics,
     AggregationPeriod,
     AggregationType,
+    CostGroupBy,
+    CostReport,
+    FlakyPipeline,
     GlobalMetricsConfig,
+    MetricAnomaly,
     MetricEntry,
     MetricType,
     MetricsConfig,
     MetricsQuery,
+    MetricsRebuild,
     MetricsStats,
 };
 use serde::{
     Deserialize,
     Serialize,
 };
+use utoipa::{
+    IntoParams,
+    ToSchema,
+};
 
 use crate::error::{
     ApiResult,
@@ -33,19 +43,19 @@ use crate::error::{
 };
 use crate::state::AppState;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateGlobalMetricsConfigRequest {
     pub enabled: bool,
     pub default_retention_days: i64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdatePipelineMetricsConfigRequest {
     pub enabled: bool,
     pub retention_days: i64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct MetricsQueryParams {
     pub metric_type: Option<String>,
     pub start_date: Option<String>,
@@ -53,7 +63,7 @@ pub struct MetricsQueryParams {
     pub limit: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AggregatedMetricsQueryParams {
     pub pipeline_id: Option<String>,
     pub metric_type: String,
@@ -62,18 +72,50 @@ pub struct AggregatedMetricsQueryParams {
     pub start_date: Option<String>,
     pub end_date: Option<String>,
     pub limit: Option<usize>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FlakyPipelinesQueryParams {
+    pub min_score: Option<f64>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MetricAnomaliesQueryParams {
+    pub pipeline_id: Option<String>,
+    pub limit: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CostReportQueryParams {
+    // pipeline, group or provider
+    pub group_by: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct FlushMetricsRequest {
     pub pipeline_id: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct FlushMetricsResponse {
     pub flushed: usize,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RebuildMetricsRequest {
+    // Every pipeline with cached run history when omitted.
+    #[serde(default)]
+    pub pipeline_id: Option<String>,
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/config", get(get_global_metrics_config))
@@ -85,10 +127,20 @@ pub fn router() -> Router<AppState> {
         )
         .route("/pipelines/{id}/query", post(query_pipeline_metrics))
         .route("/aggregated", post(query_aggregated_metrics))
+        .route("/flaky", get(query_flaky_pipelines))
+        .route("/anomalies", get(list_metric_anomalies))
+        .route("/costs", get(estimate_costs))
         .route("/storage/stats", get(get_metrics_storage_stats))
         .route("/flush", post(flush_pipeline_metrics))
+        .route("/rebuild", post(rebuild_metrics))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/metrics/config",
+    tag = "metrics",
+    responses((status = 200, description = "Get global metrics config", body = Object))
+)]
 async fn get_global_metrics_config(
     State(state): State<AppState>,
 ) -> ApiResult<Json<GlobalMetricsConfig>> {
@@ -102,6 +154,13 @@ async fn get_global_metrics_config(
     Ok(Json(config))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/metrics/config",
+    tag = "metrics",
+    request_body = UpdateGlobalMetricsConfigRequest,
+    responses((status = 200, description = "Update global metrics config"))
+)]
 async fn update_global_metrics_config(
     State(state): State<AppState>, Json(req): Json<UpdateGlobalMetricsConfigRequest>,
 ) -> ApiResult<()> {
@@ -117,6 +176,13 @@ async fn update_global_metrics_config(
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/metrics/pipelines/{id}/config",
+    tag = "metrics",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Get pipeline metrics config", body = Object))
+)]
 async fn get_pipeline_metrics_config(
     State(state): State<AppState>, Path(pipeline_id): Path<String>,
 ) -> ApiResult<Json<MetricsConfig>> {
@@ -132,6 +198,14 @@ async fn get_pipeline_metrics_config(
     Ok(Json(config))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/metrics/pipelines/{id}/config",
+    tag = "metrics",
+    params(("id" = String, Path)),
+    request_body = UpdatePipelineMetricsConfigRequest,
+    responses((status = 200, description = "Update pipeline metrics config"))
+)]
 async fn update_pipeline_metrics_config(
     State(state): State<AppState>, Path(pipeline_id): Path<String>,
     Json(req): Json<UpdatePipelineMetricsConfigRequest>,
@@ -148,6 +222,14 @@ async fn update_pipeline_metrics_config(
     Ok(())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/metrics/pipelines/{id}/query",
+    tag = "metrics",
+    params(("id" = String, Path)),
+    request_body = MetricsQueryParams,
+    responses((status = 200, description = "Query pipeline metrics", body = [Object]))
+)]
 async fn query_pipeline_metrics(
     State(state): State<AppState>, Path(pipeline_id): Path<String>,
     Json(params): Json<MetricsQueryParams>,
@@ -173,6 +255,8 @@ async fn query_pipeline_metrics(
 
     let query = MetricsQuery {
         pipeline_id: Some(pipeline_id),
+        pipeline_ids: None,
+        labels: Vec::new(),
         metric_type: parsed_metric_type,
         start_date: parsed_start_date,
         end_date: parsed_end_date,
@@ -185,6 +269,13 @@ async fn query_pipeline_metrics(
     Ok(Json(metrics))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/metrics/aggregated",
+    tag = "metrics",
+    request_body = AggregatedMetricsQueryParams,
+    responses((status = 200, description = "Query aggregated metrics", body = Object))
+)]
 async fn query_aggregated_metrics(
     State(state): State<AppState>, Json(params): Json<AggregatedMetricsQueryParams>,
 ) -> ApiResult<Json<AggregatedMetrics>> {
@@ -240,6 +331,8 @@ async fn query_aggregated_metrics(
 
     let query = MetricsQuery {
         pipeline_id: params.pipeline_id,
+        pipeline_ids: None,
+        labels: params.labels,
         metric_type: Some(parsed_metric_type),
         start_date: parsed_start_date,
         end_date: parsed_end_date,
@@ -252,6 +345,94 @@ async fn query_aggregated_metrics(
     Ok(Json(aggregated))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/metrics/flaky",
+    tag = "metrics",
+    params(FlakyPipelinesQueryParams),
+    responses((status = 200, description = "Query flaky pipelines", body = [Object]))
+)]
+async fn query_flaky_pipelines(
+    State(state): State<AppState>, Query(params): Query<FlakyPipelinesQueryParams>,
+) -> ApiResult<Json<Vec<FlakyPipeline>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let metrics_service = core
+        .metrics_service
+        .as_ref()
+        .ok_or_else(|| AppError::bad_request("Metrics service not available"))?;
+    let flaky = metrics_service
+        .query_flaky_pipelines(params.min_score, params.limit)
+        .await?;
+    Ok(Json(flaky))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/metrics/anomalies",
+    tag = "metrics",
+    params(MetricAnomaliesQueryParams),
+    responses((status = 200, description = "List detected metric anomalies", body = [Object]))
+)]
+async fn list_metric_anomalies(
+    State(state): State<AppState>, Query(params): Query<MetricAnomaliesQueryParams>,
+) -> ApiResult<Json<Vec<MetricAnomaly>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let metrics_service = core
+        .metrics_service
+        .as_ref()
+        .ok_or_else(|| AppError::bad_request("Metrics service not available"))?;
+    let anomalies = metrics_service
+        .list_metric_anomalies(params.pipeline_id.as_deref(), params.limit)
+        .await?;
+    Ok(Json(anomalies))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/metrics/costs",
+    tag = "metrics",
+    params(CostReportQueryParams),
+    responses((status = 200, description = "Estimated CI cost by pipeline, group or provider", body = Object))
+)]
+async fn estimate_costs(
+    State(state): State<AppState>, Query(params): Query<CostReportQueryParams>,
+) -> ApiResult<Json<CostReport>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let metrics_service = core
+        .metrics_service
+        .as_ref()
+        .ok_or_else(|| AppError::bad_request("Metrics service not available"))?;
+
+    let group_by = match params.group_by.as_deref() {
+        Some(group_by) => group_by
+            .parse::<CostGroupBy>()
+            .map_err(AppError::bad_request)?,
+        None => CostGroupBy::default(),
+    };
+    let parsed_start_date = params
+        .start_date
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&chrono::Utc));
+    let parsed_end_date = params
+        .end_date
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&chrono::Utc));
+
+    let report = metrics_service
+        .estimate_costs(group_by, parsed_start_date, parsed_end_date)
+        .await?;
+    Ok(Json(report))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/metrics/storage/stats",
+    tag = "metrics",
+    responses((status = 200, description = "Get metrics storage stats", body = Object))
+)]
 async fn get_metrics_storage_stats(State(state): State<AppState>) -> ApiResult<Json<MetricsStats>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
@@ -263,6 +444,13 @@ async fn get_metrics_storage_stats(State(state): State<AppState>) -> ApiResult<J
     Ok(Json(stats))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/metrics/flush",
+    tag = "metrics",
+    request_body = FlushMetricsRequest,
+    responses((status = 200, description = "Flush pipeline metrics", body = FlushMetricsResponse))
+)]
 async fn flush_pipeline_metrics(
     State(state): State<AppState>, Json(req): Json<FlushMetricsRequest>,
 ) -> ApiResult<Json<FlushMetricsResponse>> {
@@ -277,3 +465,25 @@ async fn flush_pipeline_metrics(
         .await?;
     Ok(Json(FlushMetricsResponse { flushed }))
 }
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/metrics/rebuild",
+    tag = "metrics",
+    request_body = RebuildMetricsRequest,
+    responses((status = 200, description = "Recompute metrics from cached run history", body = Object))
+)]
+async fn rebuild_metrics(
+    State(state): State<AppState>, Json(req): Json<RebuildMetricsRequest>,
+) -> ApiResult<Json<MetricsRebuild>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    if core.metrics_service.is_none() {
+        return Err(AppError::bad_request("Metrics service not available"));
+    }
+    let rebuild = core
+        .pipeline_service
+        .rebuild_metrics(req.pipeline_id.as_deref())
+        .await?;
+    Ok(Json(rebuild))
+}
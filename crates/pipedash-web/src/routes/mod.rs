@@ -1,15 +1,31 @@
+mod agents;
+mod approvals;
+mod backups;
 mod cache;
+mod commits;
+mod dependencies;
+mod export;
+mod graphql;
+mod groups;
 pub mod health;
+pub mod links;
+mod maintenance;
 mod metrics;
+mod notifications;
+pub mod openapi;
 mod pipelines;
 mod plugins;
 mod preferences;
 mod providers;
+mod queues;
 mod refresh;
+mod search;
 mod setup;
 mod storage;
 mod system;
 mod vault;
+mod views;
+pub mod webhooks;
 
 use axum::{
     routing::get,
@@ -21,9 +37,22 @@ use crate::state::AppState;
 pub fn api_router() -> Router<AppState> {
     Router::new()
         .route("/health", get(health::health_check))
+        .route("/health/live", get(health::liveness))
+        .route("/health/ready", get(health::readiness))
         .nest("/setup", setup::router())
         .nest("/providers", providers::router())
         .nest("/pipelines", pipelines::router())
+        .nest("/approvals", approvals::router())
+        .nest("/agents", agents::router())
+        .nest("/queues", queues::router())
+        .nest("/groups", groups::router())
+        .nest("/views", views::router())
+        .nest("/dependencies", dependencies::router())
+        .nest("/notifications", notifications::router())
+        .nest("/maintenance", maintenance::router())
+        .nest("/search", search::router())
+        .nest("/commits", commits::router())
+        .nest("/graphql", graphql::router())
         .nest("/plugins", plugins::router())
         .nest("/cache", cache::router())
         .nest("/metrics", metrics::router())
@@ -31,5 +60,7 @@ pub fn api_router() -> Router<AppState> {
         .nest("/refresh", refresh::router())
         .nest("/storage", storage::router())
         .nest("/vault", vault::router())
+        .nest("/backups", backups::router())
+        .nest("/export", export::router())
         .merge(system::router())
 }
@@ -0,0 +1,250 @@
+use axum::{
+    extract::{
+        Path,
+        Query,
+        State,
+    },
+    routing::{
+        delete,
+        get,
+        post,
+    },
+    Json,
+    Router,
+};
+use pipedash_core::domain::{
+    AppNotification,
+    ChannelKind,
+    NotificationChannel,
+    NotificationDelivery,
+    NotificationRule,
+    NotificationSuppression,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use utoipa::{
+    IntoParams,
+    ToSchema,
+};
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SaveChannelRequest {
+    pub name: String,
+    #[schema(value_type = Object)]
+    pub kind: ChannelKind,
+    pub url: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub rules: Vec<NotificationRule>,
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub suppression: NotificationSuppression,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeliveriesQuery {
+    pub channel_id: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListNotificationsQuery {
+    #[serde(default)]
+    pub unread_only: bool,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnreadCountResponse {
+    pub unread: usize,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_notifications).delete(clear_notifications))
+        .route("/unread-count", get(get_unread_count))
+        .route("/read", post(mark_all_read))
+        .route("/{id}/read", post(mark_notification_read))
+        .route("/channels", get(list_channels).post(save_channel))
+        .route("/channels/{id}", delete(delete_channel))
+        .route("/channels/{id}/test", post(test_channel))
+        .route("/deliveries", get(get_deliveries))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications",
+    tag = "notifications",
+    params(ListNotificationsQuery),
+    responses((status = 200, description = "List notifications", body = [Object]))
+)]
+async fn list_notifications(
+    State(state): State<AppState>, Query(query): Query<ListNotificationsQuery>,
+) -> ApiResult<Json<Vec<AppNotification>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let notifications = core
+        .notification_center
+        .list_notifications(query.unread_only, query.limit)
+        .await?;
+    Ok(Json(notifications))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications/unread-count",
+    tag = "notifications",
+    responses((status = 200, description = "Get unread count", body = UnreadCountResponse))
+)]
+async fn get_unread_count(State(state): State<AppState>) -> ApiResult<Json<UnreadCountResponse>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let unread = core.notification_center.unread_count().await?;
+    Ok(Json(UnreadCountResponse { unread }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/{id}/read",
+    tag = "notifications",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Mark notification read"))
+)]
+async fn mark_notification_read(
+    State(state): State<AppState>, Path(id): Path<i64>,
+) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.notification_center.mark_read(Some(id)).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/read",
+    tag = "notifications",
+    responses((status = 200, description = "Mark all read"))
+)]
+async fn mark_all_read(State(state): State<AppState>) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.notification_center.mark_read(None).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/notifications",
+    tag = "notifications",
+    responses((status = 200, description = "Clear notifications"))
+)]
+async fn clear_notifications(State(state): State<AppState>) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.notification_center.clear().await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications/channels",
+    tag = "notifications",
+    responses((status = 200, description = "List channels", body = [Object]))
+)]
+async fn list_channels(State(state): State<AppState>) -> ApiResult<Json<Vec<NotificationChannel>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let channels = core.notification_service.list_channels().await?;
+    Ok(Json(channels))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/channels",
+    tag = "notifications",
+    request_body = SaveChannelRequest,
+    responses((status = 200, description = "Save channel", body = Object))
+)]
+async fn save_channel(
+    State(state): State<AppState>, Json(req): Json<SaveChannelRequest>,
+) -> ApiResult<Json<NotificationChannel>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let channel = core
+        .notification_service
+        .save_channel(
+            &req.name,
+            req.kind,
+            &req.url,
+            req.enabled,
+            req.rules,
+            req.suppression,
+        )
+        .await?;
+    Ok(Json(channel))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/notifications/channels/{id}",
+    tag = "notifications",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Delete channel"))
+)]
+async fn delete_channel(State(state): State<AppState>, Path(id): Path<i64>) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.notification_service.delete_channel(id).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/channels/{id}/test",
+    tag = "notifications",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Test channel", body = Object))
+)]
+async fn test_channel(
+    State(state): State<AppState>, Path(id): Path<i64>,
+) -> ApiResult<Json<NotificationDelivery>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let delivery = core.notification_service.test_channel(id).await?;
+    Ok(Json(delivery))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications/deliveries",
+    tag = "notifications",
+    params(DeliveriesQuery),
+    responses((status = 200, description = "Get deliveries", body = [Object]))
+)]
+async fn get_deliveries(
+    State(state): State<AppState>, Query(query): Query<DeliveriesQuery>,
+) -> ApiResult<Json<Vec<NotificationDelivery>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let deliveries = core
+        .notification_service
+        .get_deliveries(query.channel_id, query.limit)
+        .await?;
+    Ok(Json(deliveries))
+}
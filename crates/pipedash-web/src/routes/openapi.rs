@@ -0,0 +1,323 @@
+use utoipa::openapi::{
+    ContentBuilder,
+    RefOr,
+    ResponseBuilder,
+};
+use utoipa::{
+    Modify,
+    OpenApi,
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use super::{
+    agents,
+    approvals,
+    backups,
+    cache,
+    commits,
+    dependencies,
+    export,
+    groups,
+    health,
+    maintenance,
+    metrics,
+    notifications,
+    pipelines,
+    plugins,
+    preferences,
+    providers,
+    queues,
+    refresh,
+    search,
+    setup,
+    storage,
+    system,
+    vault,
+    views,
+    webhooks,
+};
+use crate::error::{
+    ErrorCode,
+    Problem,
+    PROBLEM_CONTENT_TYPE,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Pipedash API", description = "REST API served by pipedash-web"),
+    paths(
+        health::health_check,
+        health::liveness,
+        health::readiness,
+        setup::get_setup_status,
+        setup::create_initial_config,
+        setup::apply_setup,
+        providers::list_providers,
+        providers::create_provider,
+        providers::get_provider,
+        providers::update_provider,
+        providers::remove_provider,
+        providers::enable_provider,
+        providers::disable_provider,
+        providers::get_run_backfill,
+        providers::start_run_backfill,
+        providers::cancel_run_backfill,
+        providers::list_run_backfills,
+        providers::validate_existing_credentials,
+        providers::update_refresh_interval,
+        providers::get_provider_organizations,
+        providers::get_provider_permissions,
+        providers::get_provider_health,
+        providers::list_provider_health,
+        providers::diagnose_provider,
+        providers::discover_repositories,
+        providers::list_provider_diagnoses,
+        providers::get_provider_features,
+        providers::get_provider_table_schema,
+        providers::validate_credentials,
+        providers::fetch_organizations,
+        providers::check_permissions,
+        providers::preview_pipelines,
+        providers::start_pipeline_preview,
+        providers::cancel_pipeline_preview,
+        providers::get_field_options,
+        providers::start_provider_oauth,
+        providers::poll_provider_oauth,
+        pipelines::list_pipelines,
+        pipelines::get_cached_pipelines,
+        pipelines::fetch_fresh_pipelines,
+        pipelines::list_pipelines_lazy,
+        pipelines::trigger_pipelines_bulk,
+        pipelines::cancel_runs_bulk,
+        pipelines::retry_runs_bulk,
+        pipelines::get_run_history,
+        pipelines::get_run_details,
+        pipelines::get_branch_statuses,
+        pipelines::trigger_pipeline,
+        approvals::list_approvals,
+        approvals::approve_trigger,
+        approvals::reject_trigger,
+        approvals::list_rules,
+        approvals::set_rule,
+        pipelines::cancel_run,
+        pipelines::retry_run,
+        pipelines::list_pending_approvals,
+        pipelines::unblock_job,
+        pipelines::get_run_logs,
+        pipelines::compare_runs,
+        pipelines::get_release_report,
+        pipelines::start_log_tail,
+        pipelines::stop_log_tail,
+        pipelines::list_log_tails,
+        pipelines::list_run_artifacts,
+        pipelines::download_artifact,
+        pipelines::get_workflow_parameters,
+        pipelines::list_trigger_presets,
+        pipelines::save_trigger_preset,
+        pipelines::delete_trigger_preset,
+        pipelines::trigger_with_preset,
+        pipelines::set_pipeline_labels,
+        pipelines::list_refresh_settings,
+        pipelines::set_pipeline_refresh,
+        pipelines::list_pinned_pipelines,
+        pipelines::get_pinned_summary,
+        pipelines::pin_pipeline,
+        pipelines::unpin_pipeline,
+        pipelines::get_pipeline_badge,
+        agents::get_cached_agents,
+        agents::fetch_agents,
+        queues::fetch_queues,
+        groups::list_groups,
+        groups::create_group,
+        groups::delete_group,
+        groups::get_group_badge,
+        groups::assign_pipeline_to_group,
+        groups::remove_pipeline_from_group,
+        views::list_views,
+        views::save_view,
+        views::delete_view,
+        views::query_view,
+        dependencies::list_dependencies,
+        dependencies::save_dependency,
+        dependencies::get_pipeline_graph,
+        dependencies::delete_dependency,
+        notifications::list_notifications,
+        notifications::clear_notifications,
+        notifications::get_unread_count,
+        notifications::mark_all_read,
+        notifications::mark_notification_read,
+        notifications::list_channels,
+        notifications::save_channel,
+        notifications::delete_channel,
+        notifications::test_channel,
+        notifications::get_deliveries,
+        maintenance::list_windows,
+        maintenance::save_window,
+        maintenance::delete_window,
+        search::search_everything,
+        search::quick_actions,
+        commits::get_commit_status,
+        plugins::list_plugins,
+        plugins::list_plugin_metadata,
+        plugins::list_loaded_plugins,
+        cache::get_cache_stats,
+        cache::clear_run_history_cache,
+        cache::clear_all_run_history_caches,
+        cache::clear_pipelines_cache,
+        cache::clear_workflow_params_cache,
+        cache::clear_all_caches,
+        cache::get_run_retention_config,
+        cache::update_run_retention_config,
+        cache::list_pipeline_run_retention,
+        cache::set_pipeline_run_retention,
+        cache::remove_pipeline_run_retention,
+        cache::prune_run_history,
+        metrics::get_global_metrics_config,
+        metrics::update_global_metrics_config,
+        metrics::get_pipeline_metrics_config,
+        metrics::update_pipeline_metrics_config,
+        metrics::query_pipeline_metrics,
+        metrics::query_aggregated_metrics,
+        metrics::query_flaky_pipelines,
+        metrics::list_metric_anomalies,
+        metrics::estimate_costs,
+        metrics::get_metrics_storage_stats,
+        metrics::flush_pipeline_metrics,
+        metrics::rebuild_metrics,
+        preferences::get_table_preferences,
+        preferences::save_table_preferences,
+        preferences::get_default_table_preferences,
+        refresh::get_refresh_mode,
+        refresh::set_refresh_mode,
+        refresh::refresh_all,
+        storage::get_storage_config,
+        storage::update_storage_config,
+        storage::get_config_content,
+        storage::save_config_content,
+        storage::list_config_versions,
+        storage::restore_config_version,
+        storage::analyze_config,
+        storage::export_configuration,
+        storage::import_configuration,
+        storage::get_storage_paths,
+        storage::validate_storage_config,
+        storage::test_storage_connection,
+        storage::get_vault_password_status,
+        storage::plan_migration,
+        storage::execute_migration,
+        vault::get_vault_status,
+        vault::unlock_vault,
+        vault::lock_vault,
+        vault::rotate_vault_password,
+        backups::list_backups,
+        backups::create_backup,
+        backups::verify_backup,
+        backups::restore_backup,
+        export::export_metrics,
+        export::export_run_history,
+        system::factory_reset,
+        system::list_audit_entries,
+        system::get_app_logs,
+        system::get_log_level,
+        system::set_log_level,
+        system::create_diagnostic_bundle,
+        webhooks::receive_webhook,
+    ),
+    components(schemas(Problem, ErrorCode)),
+    tags(
+        (name = "health"),
+        (name = "setup"),
+        (name = "providers"),
+        (name = "pipelines"),
+        (name = "approvals"),
+        (name = "agents"),
+        (name = "queues"),
+        (name = "groups"),
+        (name = "views"),
+        (name = "dependencies"),
+        (name = "notifications"),
+        (name = "maintenance"),
+        (name = "search"),
+        (name = "commits"),
+        (name = "plugins"),
+        (name = "cache"),
+        (name = "metrics"),
+        (name = "preferences"),
+        (name = "refresh"),
+        (name = "storage"),
+        (name = "vault"),
+        (name = "backups"),
+        (name = "export"),
+        (name = "system"),
+        (name = "webhooks"),
+    ),
+    modifiers(&ProblemResponses)
+)]
+pub struct ApiDoc;
+
+/// Every handler reports failures through `AppError`, so rather than
+/// repeating the error shape on each route the spec gets a shared `default`
+/// response pointing at the problem-details schema.
+struct ProblemResponses;
+
+impl Modify for ProblemResponses {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let problem = ResponseBuilder::new()
+            .description("Problem details (RFC 9457)")
+            .content(
+                PROBLEM_CONTENT_TYPE,
+                ContentBuilder::new()
+                    .schema(Some(RefOr::Ref(utoipa::openapi::Ref::from_schema_name(
+                        "Problem",
+                    ))))
+                    .build(),
+            )
+            .build();
+
+        for item in openapi.paths.paths.values_mut() {
+            for operation in [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                operation
+                    .responses
+                    .responses
+                    .entry("default".to_string())
+                    .or_insert_with(|| RefOr::T(problem.clone()));
+            }
+        }
+    }
+}
+
+/// Swagger UI at `/api/v1/docs`, backed by the generated spec at
+/// `/api/v1/openapi.json`.
+pub fn swagger_ui() -> SwaggerUi {
+    SwaggerUi::new("/api/v1/docs").url("/api/v1/openapi.json", ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_covers_routes_with_problem_responses() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let paths = spec["paths"].as_object().unwrap();
+
+        assert!(paths.contains_key("/api/v1/health"));
+        assert!(paths.contains_key("/api/v1/pipelines/{id}/runs/{run_number}/logs"));
+        assert!(paths.contains_key("/api/v1/providers/{id}"));
+
+        let get_provider = &paths["/api/v1/providers/{id}"]["get"];
+        assert_eq!(
+            get_provider["responses"]["default"]["content"][PROBLEM_CONTENT_TYPE]["schema"]["$ref"],
+            "#/components/schemas/Problem"
+        );
+        assert!(spec["components"]["schemas"]["Problem"].is_object());
+    }
+}
@@ -1,40 +1,150 @@
+use std::collections::HashMap;
+
 use axum::{
+    body::Body,
     extract::{
         Path,
         Query,
         State,
     },
+    http::header,
+    response::{
+        IntoResponse,
+        Response,
+    },
     routing::{
+        delete,
         get,
         post,
+        put,
     },
     Json,
     Router,
 };
+use pipedash_core::application::LogTailInfo;
 use pipedash_core::domain::{
+    BranchBoard,
+    BuildArtifact,
+    BulkItemResult,
+    LogQuery,
     PaginatedRunHistory,
+    PendingApproval,
+    PinnedPipeline,
     Pipeline,
+    PipelineListQuery,
+    PipelineRefreshSetting,
     PipelineRun,
+    PipelineSortField,
+    PipelineStatus,
+    RunComparison,
+    RunLogs,
+    RunRef,
+    SortOrder,
+    StatusSummary,
     TriggerParams,
+    TriggerPreset,
+};
+use pipedash_plugin_api::{
+    PaginatedResponse,
+    WorkflowParameter,
 };
-use pipedash_plugin_api::WorkflowParameter;
 use serde::{
     Deserialize,
     Serialize,
 };
+use utoipa::{
+    IntoParams,
+    ToSchema,
+};
 
+use crate::badge::{
+    self,
+    BadgeQuery,
+};
 use crate::error::{
     ApiResult,
     AppError,
 };
 use crate::state::AppState;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListPipelinesQuery {
     pub provider_id: Option<i64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CachedPipelinesQuery {
+    pub provider_id: Option<i64>,
+    // Comma-separated, e.g. `failed,running`
+    pub status: Option<String>,
+    pub branch: Option<String>,
+    // Comma-separated, e.g. `team:payments,tier`
+    pub labels: Option<String>,
+    #[serde(default)]
+    #[param(value_type = Option<String>)]
+    pub sort_by: PipelineSortField,
+    #[serde(default)]
+    #[param(value_type = Option<String>)]
+    pub sort_order: SortOrder,
+    #[serde(default = "default_page")]
+    pub page: usize,
+    #[serde(default = "default_cached_page_size")]
+    pub page_size: usize,
+}
+
+fn default_cached_page_size() -> usize {
+    100
+}
+
+fn split_csv(value: Option<&str>) -> impl Iterator<Item = &str> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+impl CachedPipelinesQuery {
+    fn into_list_query(self) -> ApiResult<PipelineListQuery> {
+        let statuses = split_csv(self.status.as_deref())
+            .map(|status| {
+                serde_json::from_value(serde_json::Value::String(status.to_lowercase()))
+                    .map_err(|_| AppError::bad_request(format!("Unknown status '{}'", status)))
+            })
+            .collect::<ApiResult<Vec<PipelineStatus>>>()?;
+
+        Ok(PipelineListQuery {
+            provider_id: self.provider_id,
+            statuses,
+            branch: self.branch,
+            labels: split_csv(self.labels.as_deref())
+                .map(String::from)
+                .collect(),
+            sort_by: self.sort_by,
+            sort_order: self.sort_order,
+            page: self.page,
+            page_size: self.page_size,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetPipelineLabelsRequest {
+    pub labels: Vec<String>,
+}
+
+// Clearing both fields returns the pipeline to the provider's schedule.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetPipelineRefreshRequest {
+    pub refresh_interval_secs: Option<u64>,
+    #[serde(default)]
+    pub snoozed: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct LazyPipelinesQuery {
     pub provider_id: Option<i64>,
     #[serde(default = "default_page")]
@@ -47,7 +157,8 @@ fn default_lazy_page_size() -> usize {
     20
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RunHistoryQuery {
     #[serde(default = "default_page")]
     pub page: usize,
@@ -55,6 +166,14 @@ pub struct RunHistoryQuery {
     pub page_size: usize,
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BranchStatusQuery {
+    // Bypass the cached board and fetch from the provider.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
 fn default_page() -> usize {
     1
 }
@@ -63,31 +182,143 @@ fn default_page_size() -> usize {
     20
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct TriggerPipelineRequest {
     pub workflow_id: String,
     #[serde(default)]
     pub inputs: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkTriggerRequest {
+    #[schema(value_type = Vec<Object>)]
+    pub items: Vec<TriggerParams>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkRunsRequest {
+    #[schema(value_type = Vec<Object>)]
+    pub runs: Vec<RunRef>,
+    #[serde(default)]
+    pub failed_only: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SaveTriggerPresetRequest {
+    pub name: String,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub parameters: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct TriggerWithPresetRequest {
+    #[serde(default)]
+    pub overrides: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct RetryRunRequest {
+    #[serde(default)]
+    pub failed_only: bool,
+}
+
+// Values for the block step's fields, by field key.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct UnblockJobRequest {
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CompareRunsQuery {
+    pub run_a: i64,
+    pub run_b: i64,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReleaseReportQuery {
+    pub from_run: i64,
+    pub to_run: i64,
+    // json (default) or markdown
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TriggerResponse {
     pub run_id: String,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LogTailResponse {
+    pub active: bool,
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_pipelines))
         .route("/cached", get(get_cached_pipelines))
         .route("/fresh", get(fetch_fresh_pipelines))
         .route("/lazy", get(list_pipelines_lazy))
+        .route("/bulk/trigger", post(trigger_pipelines_bulk))
+        .route("/bulk/cancel", post(cancel_runs_bulk))
+        .route("/bulk/retry", post(retry_runs_bulk))
         .route("/{id}/runs", get(get_run_history))
         .route("/{id}/runs/{run_number}", get(get_run_details))
+        .route("/{id}/branches", get(get_branch_statuses))
         .route("/{id}/trigger", post(trigger_pipeline))
         .route("/{id}/runs/{run_number}/cancel", post(cancel_run))
+        .route("/{id}/runs/{run_number}/retry", post(retry_run))
+        .route(
+            "/{id}/runs/{run_number}/approvals",
+            get(list_pending_approvals),
+        )
+        .route(
+            "/{id}/runs/{run_number}/jobs/{job_id}/unblock",
+            post(unblock_job),
+        )
+        .route("/{id}/runs/{run_number}/logs", get(get_run_logs))
+        .route("/{id}/compare", get(compare_runs))
+        .route("/{id}/release-report", get(get_release_report))
+        .route(
+            "/{id}/runs/{run_number}/logs/tail",
+            post(start_log_tail).delete(stop_log_tail),
+        )
+        .route("/log-tails", get(list_log_tails))
+        .route("/refresh-settings", get(list_refresh_settings))
+        .route("/pinned", get(list_pinned_pipelines))
+        .route("/pinned/summary", get(get_pinned_summary))
+        .route("/{id}/runs/{run_number}/artifacts", get(list_run_artifacts))
+        .route(
+            "/{id}/runs/{run_number}/artifacts/{artifact_id}/download",
+            get(download_artifact),
+        )
         .route("/{id}/workflow-params", get(get_workflow_parameters))
+        .route(
+            "/{id}/presets",
+            get(list_trigger_presets).post(save_trigger_preset),
+        )
+        .route("/{id}/presets/{preset_id}", delete(delete_trigger_preset))
+        .route(
+            "/{id}/presets/{preset_id}/trigger",
+            post(trigger_with_preset),
+        )
+        .route("/{id}/labels", put(set_pipeline_labels))
+        .route("/{id}/refresh-settings", put(set_pipeline_refresh))
+        .route("/{id}/pin", put(pin_pipeline).delete(unpin_pipeline))
+        .route("/{id}/badge.svg", get(get_pipeline_badge))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines",
+    tag = "pipelines",
+    params(ListPipelinesQuery),
+    responses((status = 200, description = "List pipelines", body = [Object]))
+)]
 async fn list_pipelines(
     State(state): State<AppState>, Query(query): Query<ListPipelinesQuery>,
 ) -> ApiResult<Json<Vec<Pipeline>>> {
@@ -97,6 +328,7 @@ async fn list_pipelines(
         .pipeline_service
         .get_cached_pipelines(query.provider_id)
         .await?;
+    let pipelines = core.pipeline_service.annotate_maintenance(pipelines).await;
 
     let pipeline_service = core.pipeline_service.clone();
     let provider_id = query.provider_id;
@@ -107,6 +339,13 @@ async fn list_pipelines(
     Ok(Json(pipelines))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/runs",
+    tag = "pipelines",
+    params(("id" = String, Path), RunHistoryQuery),
+    responses((status = 200, description = "Get run history", body = Object))
+)]
 async fn get_run_history(
     State(state): State<AppState>, Path(pipeline_id): Path<String>,
     Query(query): Query<RunHistoryQuery>,
@@ -121,29 +360,260 @@ async fn get_run_history(
     Ok(Json(result))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/runs/{run_number}",
+    tag = "pipelines",
+    params(("id" = String, Path), ("run_number" = i64, Path)),
+    responses((status = 200, description = "Get run details", body = Object))
+)]
 async fn get_run_details(
     State(state): State<AppState>, Path((pipeline_id, run_number)): Path<(String, i64)>,
 ) -> ApiResult<Json<PipelineRun>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
-    let runs = core
+    let run = core
         .pipeline_service
-        .fetch_run_history(&pipeline_id, 100)
+        .fetch_run_details(&pipeline_id, run_number)
         .await?;
 
-    let run = runs
-        .into_iter()
-        .find(|r| r.run_number == run_number)
-        .ok_or_else(|| {
-            pipedash_core::domain::DomainError::PipelineNotFound(format!(
-                "Run {} not found for pipeline {}",
-                run_number, pipeline_id
-            ))
-        })?;
-
     Ok(Json(run))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/branches",
+    tag = "pipelines",
+    params(("id" = String, Path), BranchStatusQuery),
+    responses((status = 200, description = "Latest run per branch", body = Object))
+)]
+async fn get_branch_statuses(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+    Query(query): Query<BranchStatusQuery>,
+) -> ApiResult<Json<BranchBoard>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let board = core
+        .pipeline_service
+        .fetch_branch_statuses(&pipeline_id, query.refresh)
+        .await?;
+
+    Ok(Json(board))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/runs/{run_number}/logs",
+    tag = "pipelines",
+    params(("id" = String, Path), ("run_number" = i64, Path), ("job_id" = Option<String>, Query), ("offset" = Option<usize>, Query)),
+    responses((status = 200, description = "Get run logs", body = Object))
+)]
+async fn get_run_logs(
+    State(state): State<AppState>, Path((pipeline_id, run_number)): Path<(String, i64)>,
+    Query(query): Query<LogQuery>,
+) -> ApiResult<Json<RunLogs>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let logs = core
+        .pipeline_service
+        .fetch_run_logs(&pipeline_id, run_number, query)
+        .await?;
+    Ok(Json(logs))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/compare",
+    tag = "pipelines",
+    params(("id" = String, Path), CompareRunsQuery),
+    responses((status = 200, description = "Compare runs", body = Object))
+)]
+async fn compare_runs(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+    Query(query): Query<CompareRunsQuery>,
+) -> ApiResult<Json<RunComparison>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let comparison = core
+        .pipeline_service
+        .compare_runs(&pipeline_id, query.run_a, query.run_b)
+        .await?;
+    Ok(Json(comparison))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/release-report",
+    tag = "pipelines",
+    params(("id" = String, Path), ReleaseReportQuery),
+    responses(
+        (status = 200, description = "What shipped between two runs, as JSON or a Markdown download", body = Object),
+        (status = 400, description = "Invalid run range or format")
+    )
+)]
+async fn get_release_report(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+    Query(query): Query<ReleaseReportQuery>,
+) -> ApiResult<Response> {
+    let markdown = match query.format.as_deref() {
+        None | Some("json") => false,
+        Some("markdown") | Some("md") => true,
+        Some(other) => {
+            return Err(AppError::bad_request(format!(
+                "Unknown report format '{}': expected json or markdown",
+                other
+            )))
+        }
+    };
+
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let report = core
+        .pipeline_service
+        .generate_release_report(&pipeline_id, query.from_run, query.to_run)
+        .await?;
+
+    if !markdown {
+        return Ok(Json(report).into_response());
+    }
+
+    let disposition = format!(
+        "attachment; filename=\"release-{}-{}.md\"",
+        query.from_run, query.to_run
+    );
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/markdown; charset=utf-8".to_string(),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        report.to_markdown(),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pipelines/{id}/runs/{run_number}/logs/tail",
+    tag = "pipelines",
+    params(("id" = String, Path), ("run_number" = i64, Path)),
+    responses((status = 200, description = "Start log tail", body = LogTailResponse))
+)]
+async fn start_log_tail(
+    State(state): State<AppState>, Path((pipeline_id, run_number)): Path<(String, i64)>,
+) -> ApiResult<Json<LogTailResponse>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.log_tailer.start(&pipeline_id, run_number);
+    Ok(Json(LogTailResponse { active: true }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/pipelines/{id}/runs/{run_number}/logs/tail",
+    tag = "pipelines",
+    params(("id" = String, Path), ("run_number" = i64, Path)),
+    responses((status = 200, description = "Stop log tail", body = LogTailResponse))
+)]
+async fn stop_log_tail(
+    State(state): State<AppState>, Path((pipeline_id, run_number)): Path<(String, i64)>,
+) -> ApiResult<Json<LogTailResponse>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.log_tailer.stop(&pipeline_id, run_number).await;
+    Ok(Json(LogTailResponse { active: false }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/log-tails",
+    tag = "pipelines",
+    responses((status = 200, description = "List log tails", body = [Object]))
+)]
+async fn list_log_tails(State(state): State<AppState>) -> ApiResult<Json<Vec<LogTailInfo>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    Ok(Json(core.log_tailer.active_tails()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/runs/{run_number}/artifacts",
+    tag = "pipelines",
+    params(("id" = String, Path), ("run_number" = i64, Path)),
+    responses((status = 200, description = "List run artifacts", body = [Object]))
+)]
+async fn list_run_artifacts(
+    State(state): State<AppState>, Path((pipeline_id, run_number)): Path<(String, i64)>,
+) -> ApiResult<Json<Vec<BuildArtifact>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let artifacts = core
+        .pipeline_service
+        .list_run_artifacts(&pipeline_id, run_number)
+        .await?;
+    Ok(Json(artifacts))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/runs/{run_number}/artifacts/{artifact_id}/download",
+    tag = "pipelines",
+    params(("id" = String, Path), ("run_number" = i64, Path), ("artifact_id" = String, Path)),
+    responses((status = 200, description = "Download artifact", content_type = "application/octet-stream", body = Vec<u8>))
+)]
+async fn download_artifact(
+    State(state): State<AppState>,
+    Path((pipeline_id, run_number, artifact_id)): Path<(String, i64, String)>,
+) -> ApiResult<Response> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let (artifact, download) = core
+        .pipeline_service
+        .resolve_artifact_download(&pipeline_id, run_number, &artifact_id)
+        .await?;
+    let response = core.http_client_manager.open_download(&download).await?;
+
+    let body = Body::from_stream(futures_util::stream::unfold(
+        Some(response),
+        |response| async move {
+            let mut response = response?;
+            match response.chunk().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(response))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        },
+    ));
+
+    let content_type = artifact
+        .content_type
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        artifact.filename.replace('"', "")
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pipelines/{id}/trigger",
+    tag = "pipelines",
+    params(("id" = String, Path)),
+    request_body = TriggerPipelineRequest,
+    responses((status = 200, description = "Trigger pipeline", body = TriggerResponse))
+)]
 async fn trigger_pipeline(
     State(state): State<AppState>, Path(_pipeline_id): Path<String>,
     Json(req): Json<TriggerPipelineRequest>,
@@ -160,6 +630,67 @@ async fn trigger_pipeline(
     Ok(Json(TriggerResponse { run_id }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/pipelines/bulk/trigger",
+    tag = "pipelines",
+    request_body = BulkTriggerRequest,
+    responses((status = 200, description = "Trigger pipelines bulk", body = [Object]))
+)]
+async fn trigger_pipelines_bulk(
+    State(state): State<AppState>, Json(req): Json<BulkTriggerRequest>,
+) -> ApiResult<Json<Vec<BulkItemResult>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let results = core
+        .pipeline_service
+        .trigger_pipelines_bulk(req.items)
+        .await?;
+    Ok(Json(results))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pipelines/bulk/cancel",
+    tag = "pipelines",
+    request_body = BulkRunsRequest,
+    responses((status = 200, description = "Cancel runs bulk", body = [Object]))
+)]
+async fn cancel_runs_bulk(
+    State(state): State<AppState>, Json(req): Json<BulkRunsRequest>,
+) -> ApiResult<Json<Vec<BulkItemResult>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let results = core.pipeline_service.cancel_runs_bulk(req.runs).await?;
+    Ok(Json(results))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pipelines/bulk/retry",
+    tag = "pipelines",
+    request_body = BulkRunsRequest,
+    responses((status = 200, description = "Retry runs bulk", body = [Object]))
+)]
+async fn retry_runs_bulk(
+    State(state): State<AppState>, Json(req): Json<BulkRunsRequest>,
+) -> ApiResult<Json<Vec<BulkItemResult>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let results = core
+        .pipeline_service
+        .retry_runs_bulk(req.runs, req.failed_only)
+        .await?;
+    Ok(Json(results))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pipelines/{id}/runs/{run_number}/cancel",
+    tag = "pipelines",
+    params(("id" = String, Path), ("run_number" = i64, Path)),
+    responses((status = 200, description = "Cancel run"))
+)]
 async fn cancel_run(
     State(state): State<AppState>, Path((pipeline_id, run_number)): Path<(String, i64)>,
 ) -> ApiResult<()> {
@@ -171,18 +702,297 @@ async fn cancel_run(
     Ok(())
 }
 
-async fn get_cached_pipelines(
-    State(state): State<AppState>, Query(query): Query<ListPipelinesQuery>,
-) -> ApiResult<Json<Vec<Pipeline>>> {
+#[utoipa::path(
+    post,
+    path = "/api/v1/pipelines/{id}/runs/{run_number}/retry",
+    tag = "pipelines",
+    params(("id" = String, Path), ("run_number" = i64, Path)),
+    request_body = RetryRunRequest,
+    responses((status = 200, description = "Retry run", body = TriggerResponse))
+)]
+async fn retry_run(
+    State(state): State<AppState>, Path((pipeline_id, run_number)): Path<(String, i64)>,
+    Json(req): Json<RetryRunRequest>,
+) -> ApiResult<Json<TriggerResponse>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
-    let pipelines = core
+    let run_id = core
         .pipeline_service
-        .get_cached_pipelines(query.provider_id)
+        .retry_run(&pipeline_id, run_number, req.failed_only)
+        .await?;
+    core.pipeline_service
+        .invalidate_run_cache(&pipeline_id)
+        .await;
+    Ok(Json(TriggerResponse { run_id }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/runs/{run_number}/approvals",
+    tag = "pipelines",
+    params(("id" = String, Path), ("run_number" = i64, Path)),
+    responses((status = 200, description = "Steps of the run waiting to be unblocked", body = [Object]))
+)]
+async fn list_pending_approvals(
+    State(state): State<AppState>, Path((pipeline_id, run_number)): Path<(String, i64)>,
+) -> ApiResult<Json<Vec<PendingApproval>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let approvals = core
+        .pipeline_service
+        .list_pending_approvals(&pipeline_id, run_number)
+        .await?;
+    Ok(Json(approvals))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pipelines/{id}/runs/{run_number}/jobs/{job_id}/unblock",
+    tag = "pipelines",
+    params(
+        ("id" = String, Path),
+        ("run_number" = i64, Path),
+        ("job_id" = String, Path)
+    ),
+    request_body = UnblockJobRequest,
+    responses((status = 200, description = "Unblock job"))
+)]
+async fn unblock_job(
+    State(state): State<AppState>,
+    Path((pipeline_id, run_number, job_id)): Path<(String, i64, String)>,
+    Json(req): Json<UnblockJobRequest>,
+) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.pipeline_service
+        .unblock_job(&pipeline_id, run_number, &job_id, req.fields)
         .await?;
+    core.pipeline_service
+        .invalidate_run_cache(&pipeline_id)
+        .await;
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/cached",
+    tag = "pipelines",
+    params(CachedPipelinesQuery),
+    responses((status = 200, description = "Get cached pipelines", body = Object))
+)]
+async fn get_cached_pipelines(
+    State(state): State<AppState>, Query(query): Query<CachedPipelinesQuery>,
+) -> ApiResult<Json<PaginatedResponse<Pipeline>>> {
+    let query = query.into_list_query()?;
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let pipelines = core.pipeline_service.list_cached_pipelines(&query).await?;
     Ok(Json(pipelines))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/pipelines/{id}/labels",
+    tag = "pipelines",
+    params(("id" = String, Path)),
+    request_body = SetPipelineLabelsRequest,
+    responses((status = 200, description = "Set pipeline labels", body = [String]))
+)]
+async fn set_pipeline_labels(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+    Json(req): Json<SetPipelineLabelsRequest>,
+) -> ApiResult<Json<Vec<String>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let labels = core
+        .pipeline_service
+        .set_pipeline_labels(&pipeline_id, req.labels)
+        .await?;
+    Ok(Json(labels))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/refresh-settings",
+    tag = "pipelines",
+    responses((status = 200, description = "List per-pipeline refresh overrides", body = [Object]))
+)]
+async fn list_refresh_settings(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<PipelineRefreshSetting>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let settings = core.pipeline_service.list_refresh_settings().await?;
+    Ok(Json(settings))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/pipelines/{id}/refresh-settings",
+    tag = "pipelines",
+    params(("id" = String, Path)),
+    request_body = SetPipelineRefreshRequest,
+    responses((status = 200, description = "Set the pipeline's refresh interval and snooze state", body = Object))
+)]
+async fn set_pipeline_refresh(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+    Json(req): Json<SetPipelineRefreshRequest>,
+) -> ApiResult<Json<Option<PipelineRefreshSetting>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let setting = core
+        .pipeline_service
+        .set_pipeline_refresh(&pipeline_id, req.refresh_interval_secs, req.snoozed)
+        .await?;
+    Ok(Json(setting))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/pinned",
+    tag = "pipelines",
+    responses((status = 200, description = "List pinned pipelines in pin order", body = [Object]))
+)]
+async fn list_pinned_pipelines(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<PinnedPipeline>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let pins = core.pipeline_service.list_pinned_pipelines().await?;
+    Ok(Json(pins))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/pinned/summary",
+    tag = "pipelines",
+    responses((status = 200, description = "Aggregate status of the pinned pipelines", body = Object))
+)]
+async fn get_pinned_summary(State(state): State<AppState>) -> ApiResult<Json<StatusSummary>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let summary = core.pipeline_service.status_summary(None).await?;
+    Ok(Json(summary))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/pipelines/{id}/pin",
+    tag = "pipelines",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Pin the pipeline", body = Object))
+)]
+async fn pin_pipeline(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+) -> ApiResult<Json<PinnedPipeline>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let pin = core.pipeline_service.pin_pipeline(&pipeline_id).await?;
+    Ok(Json(pin))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/pipelines/{id}/pin",
+    tag = "pipelines",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Unpin the pipeline"))
+)]
+async fn unpin_pipeline(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.pipeline_service.unpin_pipeline(&pipeline_id).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/presets",
+    tag = "pipelines",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "List trigger presets", body = [Object]))
+)]
+async fn list_trigger_presets(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+) -> ApiResult<Json<Vec<TriggerPreset>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let presets = core
+        .pipeline_service
+        .list_trigger_presets(&pipeline_id)
+        .await?;
+    Ok(Json(presets))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pipelines/{id}/presets",
+    tag = "pipelines",
+    params(("id" = String, Path)),
+    request_body = SaveTriggerPresetRequest,
+    responses((status = 200, description = "Save trigger preset", body = Object))
+)]
+async fn save_trigger_preset(
+    State(state): State<AppState>, Path(pipeline_id): Path<String>,
+    Json(req): Json<SaveTriggerPresetRequest>,
+) -> ApiResult<Json<TriggerPreset>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let preset = core
+        .pipeline_service
+        .save_trigger_preset(&pipeline_id, &req.name, req.branch, req.parameters)
+        .await?;
+    Ok(Json(preset))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/pipelines/{id}/presets/{preset_id}",
+    tag = "pipelines",
+    params(("id" = String, Path), ("preset_id" = i64, Path)),
+    responses((status = 200, description = "Delete trigger preset"))
+)]
+async fn delete_trigger_preset(
+    State(state): State<AppState>, Path((_pipeline_id, preset_id)): Path<(String, i64)>,
+) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.pipeline_service
+        .delete_trigger_preset(preset_id)
+        .await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pipelines/{id}/presets/{preset_id}/trigger",
+    tag = "pipelines",
+    params(("id" = String, Path), ("preset_id" = i64, Path)),
+    request_body = TriggerWithPresetRequest,
+    responses((status = 200, description = "Trigger with preset", body = TriggerResponse))
+)]
+async fn trigger_with_preset(
+    State(state): State<AppState>, Path((pipeline_id, preset_id)): Path<(String, i64)>,
+    Json(req): Json<TriggerWithPresetRequest>,
+) -> ApiResult<Json<TriggerResponse>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let run_id = core
+        .pipeline_service
+        .trigger_with_preset(&pipeline_id, preset_id, req.overrides)
+        .await?;
+    Ok(Json(TriggerResponse { run_id }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/fresh",
+    tag = "pipelines",
+    params(ListPipelinesQuery),
+    responses((status = 200, description = "Fetch fresh pipelines", body = [Object]))
+)]
 async fn fetch_fresh_pipelines(
     State(state): State<AppState>, Query(query): Query<ListPipelinesQuery>,
 ) -> ApiResult<Json<Vec<Pipeline>>> {
@@ -195,9 +1005,16 @@ async fn fetch_fresh_pipelines(
     Ok(Json(pipelines))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/lazy",
+    tag = "pipelines",
+    params(LazyPipelinesQuery),
+    responses((status = 200, description = "List pipelines lazy", body = Object))
+)]
 async fn list_pipelines_lazy(
     State(state): State<AppState>, Query(query): Query<LazyPipelinesQuery>,
-) -> ApiResult<Json<pipedash_plugin_api::PaginatedResponse<Pipeline>>> {
+) -> ApiResult<Json<PaginatedResponse<Pipeline>>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
     let result = core
@@ -207,6 +1024,13 @@ async fn list_pipelines_lazy(
     Ok(Json(result))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/workflow-params",
+    tag = "pipelines",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Get workflow parameters", body = [Object]))
+)]
 async fn get_workflow_parameters(
     State(state): State<AppState>, Path(workflow_id): Path<String>,
 ) -> ApiResult<Json<Vec<WorkflowParameter>>> {
@@ -235,3 +1059,31 @@ async fn get_workflow_parameters(
 
     Ok(Json(params))
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pipelines/{id}/badge.svg",
+    tag = "pipelines",
+    params(("id" = String, Path), BadgeQuery),
+    responses((status = 200, description = "Get pipeline badge", content_type = "image/svg+xml", body = String))
+)]
+async fn get_pipeline_badge(
+    State(state): State<AppState>, Path(id): Path<String>, Query(query): Query<BadgeQuery>,
+) -> ApiResult<Response> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let pipeline = core
+        .pipeline_service
+        .get_cached_pipelines(None)
+        .await?
+        .into_iter()
+        .find(|pipeline| pipeline.id == id);
+
+    Ok(match pipeline {
+        Some(pipeline) => badge::status_badge(
+            query.label.as_deref().unwrap_or(&pipeline.name),
+            &pipeline.status,
+        ),
+        None => badge::unknown_badge(query.label.as_deref().unwrap_or("pipeline")),
+    })
+}
@@ -4,6 +4,7 @@ use axum::{
     Json,
     Router,
 };
+use pipedash_core::plugins::DynamicPluginInfo;
 use pipedash_plugin_api::PluginMetadata;
 
 use crate::error::{
@@ -16,8 +17,15 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_plugins))
         .route("/metadata", get(list_plugin_metadata))
+        .route("/loaded", get(list_loaded_plugins))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/plugins",
+    tag = "plugins",
+    responses((status = 200, description = "List plugins", body = [Object]))
+)]
 async fn list_plugins(State(state): State<AppState>) -> ApiResult<Json<Vec<PluginMetadata>>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
@@ -25,6 +33,12 @@ async fn list_plugins(State(state): State<AppState>) -> ApiResult<Json<Vec<Plugi
     Ok(Json(metadata))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/plugins/metadata",
+    tag = "plugins",
+    responses((status = 200, description = "List plugin metadata", body = [Object]))
+)]
 async fn list_plugin_metadata(
     State(state): State<AppState>,
 ) -> ApiResult<Json<Vec<PluginMetadata>>> {
@@ -33,3 +47,13 @@ async fn list_plugin_metadata(
     let metadata = core.provider_service.list_available_plugins();
     Ok(Json(metadata))
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/plugins/loaded",
+    tag = "plugins",
+    responses((status = 200, description = "List loaded plugins", body = [Object]))
+)]
+async fn list_loaded_plugins() -> ApiResult<Json<Vec<DynamicPluginInfo>>> {
+    Ok(Json(pipedash_core::plugins::loaded_plugins()))
+}
@@ -13,6 +13,7 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use utoipa::ToSchema;
 
 use crate::error::{
     ApiResult,
@@ -20,12 +21,12 @@ use crate::error::{
 };
 use crate::state::AppState;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SavePreferencesRequest {
     pub preferences_json: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct DefaultPreferences {
     #[serde(rename = "columnOrder")]
     pub column_order: Vec<String>,
@@ -45,6 +46,13 @@ pub fn router() -> Router<AppState> {
         )
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/preferences/table/{provider_id}/{table_id}",
+    tag = "preferences",
+    params(("provider_id" = i64, Path), ("table_id" = String, Path)),
+    responses((status = 200, description = "Get table preferences", body = Object))
+)]
 async fn get_table_preferences(
     State(state): State<AppState>, Path((provider_id, table_id)): Path<(i64, String)>,
 ) -> ApiResult<Json<Option<String>>> {
@@ -58,6 +66,14 @@ async fn get_table_preferences(
     Ok(Json(preferences))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/preferences/table/{provider_id}/{table_id}",
+    tag = "preferences",
+    params(("provider_id" = i64, Path), ("table_id" = String, Path)),
+    request_body = SavePreferencesRequest,
+    responses((status = 200, description = "Save table preferences"))
+)]
 async fn save_table_preferences(
     State(state): State<AppState>, Path((provider_id, table_id)): Path<(i64, String)>,
     Json(req): Json<SavePreferencesRequest>,
@@ -71,6 +87,13 @@ async fn save_table_preferences(
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/preferences/table/{provider_id}/{table_id}/default",
+    tag = "preferences",
+    params(("provider_id" = i64, Path), ("table_id" = String, Path)),
+    responses((status = 200, description = "Get default table preferences", body = DefaultPreferences))
+)]
 async fn get_default_table_preferences(
     State(state): State<AppState>, Path((provider_id, table_id)): Path<(i64, String)>,
 ) -> ApiResult<Json<DefaultPreferences>> {
@@ -14,11 +14,18 @@ use axum::{
     Json,
     Router,
 };
+use pipedash_core::application::PipelinePreviewRequest;
 use pipedash_core::domain::{
+    plugin_token,
+    DeviceAuthorization,
+    OAuthPollStatus,
     PaginatedAvailablePipelines,
-    PaginationParams,
     ProviderConfig,
+    ProviderDiagnosis,
+    ProviderHealth,
+    RunBackfillJob,
 };
+use pipedash_core::infrastructure::secrets::redact_config_secrets;
 use pipedash_plugin_api::{
     FeatureAvailability,
     Organization,
@@ -28,6 +35,7 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use utoipa::ToSchema;
 
 use crate::error::{
     ApiResult,
@@ -35,7 +43,7 @@ use crate::error::{
 };
 use crate::state::AppState;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateProviderRequest {
     pub name: String,
     pub provider_type: String,
@@ -50,7 +58,7 @@ fn default_refresh_interval() -> i64 {
     60
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateProviderRequest {
     pub name: String,
     pub token: String,
@@ -60,7 +68,25 @@ pub struct UpdateProviderRequest {
     pub refresh_interval: i64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct StartBackfillRequest {
+    // Runs to import per pipeline; the server default when omitted.
+    #[serde(default)]
+    pub depth: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StartOAuthRequest {
+    pub provider_type: String,
+    pub base_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PollOAuthRequest {
+    pub session_id: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ProviderResponse {
     pub id: i64,
     pub name: String,
@@ -68,56 +94,65 @@ pub struct ProviderResponse {
     #[serde(default)]
     pub config: HashMap<String, String>,
     pub refresh_interval: i64,
+    pub enabled: bool,
 }
 
+// Secret config values come back as their `storage:` reference.
 impl From<ProviderConfig> for ProviderResponse {
-    fn from(config: ProviderConfig) -> Self {
+    fn from(mut config: ProviderConfig) -> Self {
+        redact_config_secrets(&mut config.config);
         Self {
             id: config.id.unwrap_or(0),
             name: config.name,
             provider_type: config.provider_type,
             config: config.config,
             refresh_interval: config.refresh_interval,
+            enabled: config.enabled,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ValidationResult {
     pub valid: bool,
     pub error: Option<String>,
+    // Reported by self-hosted providers, with a warning when unsupported.
+    #[schema(value_type = Option<Object>)]
+    pub server_version: Option<pipedash_plugin_api::ServerVersion>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PermissionCheckResult {
+    #[schema(value_type = Option<Object>)]
     pub permission_status: Option<PermissionStatus>,
+    #[schema(value_type = Vec<Object>)]
     pub features: Vec<FeatureAvailability>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateRefreshIntervalRequest {
     pub refresh_interval: i64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct FetchOrganizationsRequest {
     pub provider_type: String,
     pub config: HashMap<String, String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ValidateCredentialsRequest {
     pub provider_type: String,
     pub config: HashMap<String, String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CheckPermissionsRequest {
     pub provider_type: String,
     pub config: HashMap<String, String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct PreviewPipelinesRequest {
     pub provider_type: String,
     pub config: HashMap<String, String>,
@@ -134,19 +169,43 @@ pub fn router() -> Router<AppState> {
         .route("/{id}", get(get_provider))
         .route("/{id}", put(update_provider))
         .route("/{id}", delete(remove_provider))
+        .route("/{id}/enable", post(enable_provider))
+        .route("/{id}/disable", post(disable_provider))
+        .route("/{id}/backfill", get(get_run_backfill))
+        .route("/{id}/backfill", post(start_run_backfill))
+        .route("/{id}/backfill", delete(cancel_run_backfill))
         .route("/{id}/validate", post(validate_existing_credentials))
         .route("/{id}/refresh-interval", put(update_refresh_interval))
         .route("/{id}/organizations", get(get_provider_organizations))
         .route("/{id}/permissions", get(get_provider_permissions))
+        .route("/{id}/health", get(get_provider_health))
+        .route("/{id}/diagnose", post(diagnose_provider))
+        .route("/{id}/discover", post(discover_repositories))
         .route("/{id}/features", get(get_provider_features))
         .route("/{id}/table-schema", get(get_provider_table_schema))
+        .route("/backfills", get(list_run_backfills))
+        .route("/health", get(list_provider_health))
+        .route("/diagnostics", get(list_provider_diagnoses))
         .route("/validate", post(validate_credentials))
         .route("/organizations", post(fetch_organizations))
         .route("/permissions/check", post(check_permissions))
         .route("/preview", post(preview_pipelines))
+        .route("/preview/stream", post(start_pipeline_preview))
+        .route(
+            "/preview/stream/{preview_id}",
+            delete(cancel_pipeline_preview),
+        )
         .route("/field-options", post(get_field_options))
+        .route("/oauth/start", post(start_provider_oauth))
+        .route("/oauth/poll", post(poll_provider_oauth))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers",
+    tag = "providers",
+    responses((status = 200, description = "List providers", body = [Object]))
+)]
 async fn list_providers(
     State(state): State<AppState>,
 ) -> ApiResult<Json<Vec<pipedash_core::ProviderSummary>>> {
@@ -157,6 +216,13 @@ async fn list_providers(
     Ok(Json(providers))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers",
+    tag = "providers",
+    request_body = CreateProviderRequest,
+    responses((status = 200, description = "Create provider", body = ProviderResponse))
+)]
 async fn create_provider(
     State(state): State<AppState>, Json(req): Json<CreateProviderRequest>,
 ) -> ApiResult<Json<ProviderResponse>> {
@@ -168,6 +234,7 @@ async fn create_provider(
         config: req.config,
         refresh_interval: req.refresh_interval,
         version: None,
+        enabled: true,
     };
 
     let inner = state.inner.read().await;
@@ -182,15 +249,19 @@ async fn create_provider(
 
     core.refresh_manager.prioritize_provider(id).await;
 
-    Ok(Json(ProviderResponse {
-        id,
-        name: config.name,
-        provider_type: config.provider_type,
-        config: config.config,
-        refresh_interval: config.refresh_interval,
-    }))
+    Ok(Json(ProviderResponse::from(ProviderConfig {
+        id: Some(id),
+        ..config
+    })))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/{id}",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Get provider", body = ProviderResponse))
+)]
 async fn get_provider(
     State(state): State<AppState>, Path(id): Path<i64>,
 ) -> ApiResult<Json<ProviderResponse>> {
@@ -200,6 +271,14 @@ async fn get_provider(
     Ok(Json(provider.into()))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/providers/{id}",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    request_body = UpdateProviderRequest,
+    responses((status = 200, description = "Update provider", body = ProviderResponse))
+)]
 async fn update_provider(
     State(state): State<AppState>, Path(id): Path<i64>, Json(req): Json<UpdateProviderRequest>,
 ) -> ApiResult<Json<ProviderResponse>> {
@@ -215,21 +294,23 @@ async fn update_provider(
         config: req.config,
         refresh_interval: req.refresh_interval,
         version: existing.version,
+        enabled: existing.enabled,
     };
 
     core.provider_service
         .update_provider(id, config.clone())
         .await?;
 
-    Ok(Json(ProviderResponse {
-        id,
-        name: config.name,
-        provider_type: existing.provider_type,
-        config: config.config,
-        refresh_interval: config.refresh_interval,
-    }))
+    Ok(Json(config.into()))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/providers/{id}",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Remove provider"))
+)]
 async fn remove_provider(State(state): State<AppState>, Path(id): Path<i64>) -> ApiResult<()> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
@@ -237,6 +318,104 @@ async fn remove_provider(State(state): State<AppState>, Path(id): Path<i64>) ->
     Ok(())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/{id}/enable",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Resume fetching a disabled provider"))
+)]
+async fn enable_provider(State(state): State<AppState>, Path(id): Path<i64>) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.provider_service.enable_provider(id).await?;
+    core.refresh_manager.prioritize_provider(id).await;
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/{id}/disable",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Stop fetching a provider, keeping its data"))
+)]
+async fn disable_provider(State(state): State<AppState>, Path(id): Path<i64>) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.provider_service.disable_provider(id).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/{id}/backfill",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Run history backfill of a provider, if any", body = Object))
+)]
+async fn get_run_backfill(
+    State(state): State<AppState>, Path(id): Path<i64>,
+) -> ApiResult<Json<Option<RunBackfillJob>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let job = core.backfill_service.get_job(id).await?;
+    Ok(Json(job))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/{id}/backfill",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    request_body = StartBackfillRequest,
+    responses((status = 200, description = "Start or resume importing run history", body = Object))
+)]
+async fn start_run_backfill(
+    State(state): State<AppState>, Path(id): Path<i64>, Json(req): Json<StartBackfillRequest>,
+) -> ApiResult<Json<RunBackfillJob>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let job = core.backfill_service.start(id, req.depth).await?;
+    Ok(Json(job))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/providers/{id}/backfill",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Cancel a running backfill", body = Object))
+)]
+async fn cancel_run_backfill(
+    State(state): State<AppState>, Path(id): Path<i64>,
+) -> ApiResult<Json<RunBackfillJob>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let job = core.backfill_service.cancel(id).await?;
+    Ok(Json(job))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/backfills",
+    tag = "providers",
+    responses((status = 200, description = "List run history backfills", body = [Object]))
+)]
+async fn list_run_backfills(State(state): State<AppState>) -> ApiResult<Json<Vec<RunBackfillJob>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let jobs = core.backfill_service.list_jobs().await?;
+    Ok(Json(jobs))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/{id}/validate",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Validate existing credentials", body = bool))
+)]
 async fn validate_existing_credentials(
     State(state): State<AppState>, Path(id): Path<i64>,
 ) -> ApiResult<Json<bool>> {
@@ -247,6 +426,14 @@ async fn validate_existing_credentials(
     Ok(Json(valid))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/providers/{id}/refresh-interval",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    request_body = UpdateRefreshIntervalRequest,
+    responses((status = 200, description = "Update refresh interval"))
+)]
 async fn update_refresh_interval(
     State(state): State<AppState>, Path(id): Path<i64>,
     Json(req): Json<UpdateRefreshIntervalRequest>,
@@ -265,6 +452,13 @@ async fn update_refresh_interval(
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/{id}/organizations",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Get provider organizations", body = [Object]))
+)]
 async fn get_provider_organizations(
     State(state): State<AppState>, Path(id): Path<i64>,
 ) -> ApiResult<Json<Vec<Organization>>> {
@@ -276,13 +470,11 @@ async fn get_provider_organizations(
         .create_uninitialized_plugin(&config.provider_type)?;
 
     let mut plugin_config = config.config.clone();
-    plugin_config.insert("token".to_string(), config.token.clone());
+    plugin_config.insert("token".to_string(), plugin_token(&config.token));
 
-    let http_client = if let Some(base_url) = plugin_config.get("base_url") {
-        core.http_client_manager.client_for_url(base_url)?
-    } else {
-        core.http_client_manager.default_client()
-    };
+    let http_client = core
+        .http_client_manager
+        .client_for_provider(&plugin_config)?;
 
     plugin
         .initialize(id, plugin_config, Some(http_client))
@@ -295,6 +487,13 @@ async fn get_provider_organizations(
     Ok(Json(orgs))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/{id}/permissions",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Get provider permissions", body = Object))
+)]
 async fn get_provider_permissions(
     State(state): State<AppState>, Path(id): Path<i64>,
 ) -> ApiResult<Json<Option<PermissionStatus>>> {
@@ -304,93 +503,165 @@ async fn get_provider_permissions(
     Ok(Json(permissions))
 }
 
-async fn get_provider_features(
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/health",
+    tag = "providers",
+    responses((status = 200, description = "Fetch health of every provider", body = [Object]))
+)]
+async fn list_provider_health(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<ProviderHealth>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let health = core.provider_service.get_provider_health(None).await?;
+    Ok(Json(health))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/{id}/health",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Fetch health, score and outage state of a provider", body = Object))
+)]
+async fn get_provider_health(
     State(state): State<AppState>, Path(id): Path<i64>,
-) -> ApiResult<Json<Vec<FeatureAvailability>>> {
+) -> ApiResult<Json<ProviderHealth>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
-    let provider_config = core.provider_service.get_provider_config(id).await?;
-
-    let metadata_list = core.provider_service.list_available_plugins();
-    let plugin_metadata = metadata_list
-        .iter()
-        .find(|m| m.provider_type == provider_config.provider_type)
-        .ok_or_else(|| {
-            AppError::not_found(format!(
-                "Plugin not found for provider type: {}",
-                provider_config.provider_type
-            ))
-        })?;
-
-    let permission_status = core.provider_service.get_provider_permissions(id).await?;
-
-    let permission_status = match permission_status {
-        Some(status) => status,
-        None => {
-            return Ok(Json(
-                plugin_metadata
-                    .features
-                    .iter()
-                    .map(|f| FeatureAvailability {
-                        feature: f.clone(),
-                        available: false,
-                        missing_permissions: f.required_permissions.clone(),
-                    })
-                    .collect(),
-            ));
-        }
-    };
+    let health = core
+        .provider_service
+        .get_provider_health(Some(id))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::not_found(format!("Provider {} not found", id)))?;
+    Ok(Json(health))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/{id}/diagnose",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Check auth, permissions, reachability, clock skew and rate limit of a provider", body = Object))
+)]
+async fn diagnose_provider(
+    State(state): State<AppState>, Path(id): Path<i64>,
+) -> ApiResult<Json<ProviderDiagnosis>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let diagnosis = core.provider_service.diagnose_provider(id).await?;
+    Ok(Json(diagnosis))
+}
+
+// Runs auto-discovery now instead of waiting for the hourly pass. Returns the
+// repositories added to the provider's selection.
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/{id}/discover",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Discover new repositories in the provider's organizations", body = [String]))
+)]
+async fn discover_repositories(
+    State(state): State<AppState>, Path(id): Path<i64>,
+) -> ApiResult<Json<Vec<String>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let added = core.discovery_service.discover(id).await?;
+    Ok(Json(added))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/diagnostics",
+    tag = "providers",
+    responses((status = 200, description = "Latest diagnosis of each provider", body = [Object]))
+)]
+async fn list_provider_diagnoses(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<ProviderDiagnosis>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    Ok(Json(core.provider_service.get_provider_diagnoses().await))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/oauth/start",
+    tag = "providers",
+    request_body = StartOAuthRequest,
+    responses((status = 200, description = "Start provider oauth", body = Object))
+)]
+async fn start_provider_oauth(
+    State(state): State<AppState>, Json(req): Json<StartOAuthRequest>,
+) -> ApiResult<Json<DeviceAuthorization>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let authorization = core
+        .provider_service
+        .start_oauth(&req.provider_type, req.base_url.as_deref())
+        .await?;
+    Ok(Json(authorization))
+}
 
-    let granted_perms: std::collections::HashSet<String> = permission_status
-        .permissions
-        .iter()
-        .filter(|p| p.granted)
-        .map(|p| p.permission.name.clone())
-        .collect();
-
-    let features = plugin_metadata
-        .features
-        .iter()
-        .map(|feature| {
-            let missing: Vec<String> = feature
-                .required_permissions
-                .iter()
-                .filter(|p| !granted_perms.contains(*p))
-                .cloned()
-                .collect();
-
-            FeatureAvailability {
-                feature: feature.clone(),
-                available: missing.is_empty(),
-                missing_permissions: missing,
-            }
-        })
-        .collect();
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/oauth/poll",
+    tag = "providers",
+    request_body = PollOAuthRequest,
+    responses((status = 200, description = "Poll provider oauth", body = Object))
+)]
+async fn poll_provider_oauth(
+    State(state): State<AppState>, Json(req): Json<PollOAuthRequest>,
+) -> ApiResult<Json<OAuthPollStatus>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let status = core.provider_service.poll_oauth(&req.session_id).await?;
+    Ok(Json(status))
+}
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/{id}/features",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Get provider features", body = [Object]))
+)]
+async fn get_provider_features(
+    State(state): State<AppState>, Path(id): Path<i64>,
+) -> ApiResult<Json<Vec<FeatureAvailability>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let features = core.provider_service.get_provider_features(id).await?;
     Ok(Json(features))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/{id}/table-schema",
+    tag = "providers",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Get provider table schema", body = Object))
+)]
 async fn get_provider_table_schema(
     State(state): State<AppState>, Path(id): Path<i64>,
 ) -> ApiResult<Json<pipedash_plugin_api::schema::TableSchema>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
-    let provider_config = core.provider_service.get_provider_config(id).await?;
-
-    let metadata_list = core.provider_service.list_available_plugins();
-    let plugin_metadata = metadata_list
-        .iter()
-        .find(|m| m.provider_type == provider_config.provider_type)
-        .ok_or_else(|| {
-            AppError::not_found(format!(
-                "Plugin not found for provider type: {}",
-                provider_config.provider_type
-            ))
-        })?;
-
-    Ok(Json(plugin_metadata.table_schema.clone()))
+    let schema = core.provider_service.get_provider_table_schema(id).await?;
+    Ok(Json(schema))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/validate",
+    tag = "providers",
+    request_body = ValidateCredentialsRequest,
+    responses((status = 200, description = "Validate credentials", body = ValidationResult))
+)]
 async fn validate_credentials(
     State(state): State<AppState>, Json(req): Json<ValidateCredentialsRequest>,
 ) -> ApiResult<Json<ValidationResult>> {
@@ -400,11 +671,7 @@ async fn validate_credentials(
         .provider_service
         .create_uninitialized_plugin(&req.provider_type)?;
 
-    let http_client = if let Some(base_url) = req.config.get("base_url") {
-        core.http_client_manager.client_for_url(base_url)?
-    } else {
-        core.http_client_manager.default_client()
-    };
+    let http_client = core.http_client_manager.client_for_provider(&req.config)?;
 
     plugin
         .initialize(0, req.config, Some(http_client))
@@ -414,18 +681,31 @@ async fn validate_credentials(
         Ok(valid) if valid => Ok(Json(ValidationResult {
             valid: true,
             error: None,
+            server_version: core
+                .provider_service
+                .detect_server_version(plugin.as_ref())
+                .await,
         })),
         Ok(_) => Ok(Json(ValidationResult {
             valid: false,
             error: Some("Invalid credentials".to_string()),
+            server_version: None,
         })),
         Err(e) => Ok(Json(ValidationResult {
             valid: false,
             error: Some(e.to_string()),
+            server_version: None,
         })),
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/organizations",
+    tag = "providers",
+    request_body = FetchOrganizationsRequest,
+    responses((status = 200, description = "Fetch organizations", body = [Object]))
+)]
 async fn fetch_organizations(
     State(state): State<AppState>, Json(req): Json<FetchOrganizationsRequest>,
 ) -> ApiResult<Json<Vec<Organization>>> {
@@ -435,11 +715,7 @@ async fn fetch_organizations(
         .provider_service
         .create_uninitialized_plugin(&req.provider_type)?;
 
-    let http_client = if let Some(base_url) = req.config.get("base_url") {
-        core.http_client_manager.client_for_url(base_url)?
-    } else {
-        core.http_client_manager.default_client()
-    };
+    let http_client = core.http_client_manager.client_for_provider(&req.config)?;
 
     plugin
         .initialize(0, req.config, Some(http_client))
@@ -458,6 +734,13 @@ async fn fetch_organizations(
     Ok(Json(orgs))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/permissions/check",
+    tag = "providers",
+    request_body = CheckPermissionsRequest,
+    responses((status = 200, description = "Check permissions", body = PermissionCheckResult))
+)]
 async fn check_permissions(
     State(state): State<AppState>, Json(req): Json<CheckPermissionsRequest>,
 ) -> ApiResult<Json<PermissionCheckResult>> {
@@ -467,11 +750,7 @@ async fn check_permissions(
         .provider_service
         .create_uninitialized_plugin(&req.provider_type)?;
 
-    let http_client = if let Some(base_url) = req.config.get("base_url") {
-        core.http_client_manager.client_for_url(base_url)?
-    } else {
-        core.http_client_manager.default_client()
-    };
+    let http_client = core.http_client_manager.client_for_provider(&req.config)?;
 
     plugin
         .initialize(0, req.config, Some(http_client))
@@ -491,48 +770,78 @@ async fn check_permissions(
     }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/preview",
+    tag = "providers",
+    request_body = PreviewPipelinesRequest,
+    responses((status = 200, description = "Preview pipelines", body = Object))
+)]
 async fn preview_pipelines(
     State(state): State<AppState>, Json(req): Json<PreviewPipelinesRequest>,
 ) -> ApiResult<Json<PaginatedAvailablePipelines>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
-    let mut plugin = core
-        .provider_service
-        .create_uninitialized_plugin(&req.provider_type)?;
-
-    let http_client = if let Some(base_url) = req.config.get("base_url") {
-        core.http_client_manager.client_for_url(base_url)?
-    } else {
-        core.http_client_manager.default_client()
+    let request = PipelinePreviewRequest {
+        preview_id: None,
+        provider_type: req.provider_type,
+        config: req.config,
+        org: req.org,
+        search: req.search,
+        page_size: req.page_size,
     };
+    let page = core
+        .pipeline_previewer
+        .fetch_page(request, req.page.unwrap_or(1))
+        .await?;
+    Ok(Json(page))
+}
 
-    plugin
-        .initialize(0, req.config, Some(http_client))
-        .map_err(|e| AppError::internal(format!("Failed to initialize plugin: {}", e)))?;
-
-    plugin
-        .validate_credentials()
-        .await
-        .map_err(|e| AppError::internal(format!("Failed to validate credentials: {e}")))?;
-
-    let params = PaginationParams {
-        page: req.page.unwrap_or(1).max(1),
-        page_size: req.page_size.unwrap_or(100).clamp(10, 200),
-    };
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PipelinePreviewResponse {
+    pub preview_id: String,
+}
 
-    if let Err(validation_error) = params.validate() {
-        return Err(AppError::bad_request(validation_error));
-    }
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CancelPipelinePreviewResponse {
+    pub cancelled: bool,
+}
 
-    let result = plugin
-        .fetch_available_pipelines_filtered(req.org, req.search, Some(params))
-        .await
-        .map_err(|e| AppError::internal(format!("Failed to fetch available pipelines: {e}")))?;
+// Pages arrive over the WebSocket as `preview-page` events, then
+// `preview-finished`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/preview/stream",
+    tag = "providers",
+    request_body = Object,
+    responses((status = 200, description = "Start a streamed pipelines preview", body = PipelinePreviewResponse))
+)]
+async fn start_pipeline_preview(
+    State(state): State<AppState>, Json(req): Json<PipelinePreviewRequest>,
+) -> ApiResult<Json<PipelinePreviewResponse>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let preview_id = core.pipeline_previewer.start(req).await?;
+    Ok(Json(PipelinePreviewResponse { preview_id }))
+}
 
-    Ok(Json(result))
+#[utoipa::path(
+    delete,
+    path = "/api/v1/providers/preview/stream/{preview_id}",
+    tag = "providers",
+    params(("preview_id" = String, Path)),
+    responses((status = 200, description = "Cancel a streamed pipelines preview", body = CancelPipelinePreviewResponse))
+)]
+async fn cancel_pipeline_preview(
+    State(state): State<AppState>, Path(preview_id): Path<String>,
+) -> ApiResult<Json<CancelPipelinePreviewResponse>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let cancelled = core.pipeline_previewer.cancel(&preview_id).await;
+    Ok(Json(CancelPipelinePreviewResponse { cancelled }))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct GetFieldOptionsRequest {
     pub provider_type: String,
     pub field_key: String,
@@ -540,6 +849,13 @@ pub struct GetFieldOptionsRequest {
     pub config: HashMap<String, String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/providers/field-options",
+    tag = "providers",
+    request_body = GetFieldOptionsRequest,
+    responses((status = 200, description = "Get field options", body = [String]))
+)]
 async fn get_field_options(
     State(state): State<AppState>, Json(req): Json<GetFieldOptionsRequest>,
 ) -> ApiResult<Json<Vec<String>>> {
@@ -0,0 +1,44 @@
+use axum::{
+    extract::{
+        Query,
+        State,
+    },
+    routing::get,
+    Json,
+    Router,
+};
+use pipedash_core::domain::ProviderQueue;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QueuesQuery {
+    pub provider_id: Option<i64>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/", get(fetch_queues))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/queues",
+    tag = "queues",
+    params(QueuesQuery),
+    responses((status = 200, description = "Fetch queues", body = [Object]))
+)]
+async fn fetch_queues(
+    State(state): State<AppState>, Query(query): Query<QueuesQuery>,
+) -> ApiResult<Json<Vec<ProviderQueue>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let queues = core.queue_service.fetch_queues(query.provider_id).await?;
+    Ok(Json(queues))
+}
@@ -12,6 +12,7 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use utoipa::ToSchema;
 
 use crate::error::{
     ApiResult,
@@ -19,12 +20,12 @@ use crate::error::{
 };
 use crate::state::AppState;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RefreshModeResponse {
     pub mode: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SetRefreshModeRequest {
     pub mode: String,
 }
@@ -35,6 +36,12 @@ pub fn router() -> Router<AppState> {
         .route("/all", post(refresh_all))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/refresh/mode",
+    tag = "refresh",
+    responses((status = 200, description = "Get refresh mode", body = RefreshModeResponse))
+)]
 async fn get_refresh_mode(State(state): State<AppState>) -> ApiResult<Json<RefreshModeResponse>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
@@ -44,6 +51,13 @@ async fn get_refresh_mode(State(state): State<AppState>) -> ApiResult<Json<Refre
     }))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/refresh/mode",
+    tag = "refresh",
+    request_body = SetRefreshModeRequest,
+    responses((status = 200, description = "Set refresh mode"))
+)]
 async fn set_refresh_mode(
     State(state): State<AppState>, Json(req): Json<SetRefreshModeRequest>,
 ) -> ApiResult<()> {
@@ -54,6 +68,12 @@ async fn set_refresh_mode(
     Ok(())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/refresh/all",
+    tag = "refresh",
+    responses((status = 200, description = "Refresh all"))
+)]
 async fn refresh_all(State(state): State<AppState>) -> ApiResult<()> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
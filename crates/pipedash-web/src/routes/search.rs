@@ -0,0 +1,87 @@
+use axum::{
+    extract::{
+        Query,
+        State,
+    },
+    routing::get,
+    Json,
+    Router,
+};
+use pipedash_core::domain::{
+    QuickAction,
+    SearchResult,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchParams {
+    pub q: String,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QuickActionParams {
+    // Without a query the recently used pipelines are returned.
+    #[serde(default)]
+    pub q: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    20
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(search_everything))
+        .route("/quick-actions", get(quick_actions))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/search",
+    tag = "search",
+    params(SearchParams),
+    responses((status = 200, description = "Search everything", body = [Object]))
+)]
+async fn search_everything(
+    State(state): State<AppState>, Query(params): Query<SearchParams>,
+) -> ApiResult<Json<Vec<SearchResult>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let results = core
+        .pipeline_service
+        .search_everything(&params.q, params.limit)
+        .await?;
+    Ok(Json(results))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/search/quick-actions",
+    tag = "search",
+    params(QuickActionParams),
+    responses((status = 200, description = "Command palette entries", body = [Object]))
+)]
+async fn quick_actions(
+    State(state): State<AppState>, Query(params): Query<QuickActionParams>,
+) -> ApiResult<Json<Vec<QuickAction>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let entries = core
+        .pipeline_service
+        .quick_actions(params.q.as_deref(), params.limit)
+        .await?;
+    Ok(Json(entries))
+}
@@ -7,9 +7,14 @@ use axum::{
     Json,
     Router,
 };
+use pipedash_core::domain::{
+    Action,
+    ProvisioningPlan,
+};
 use pipedash_core::infrastructure::{
     ConfigLoader,
     PipedashConfig,
+    SetupDocument,
     SetupStatus,
     StorageBackendType,
     StorageManager,
@@ -19,6 +24,7 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use utoipa::ToSchema;
 
 use crate::error::{
     ApiResult,
@@ -26,7 +32,7 @@ use crate::error::{
 };
 use crate::state::AppState;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SetupStatusResponse {
     pub config_exists: bool,
     pub config_valid: bool,
@@ -47,19 +53,40 @@ impl From<SetupStatus> for SetupStatusResponse {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateInitialConfigRequest {
+    #[schema(value_type = Object)]
     pub config: PipedashConfig,
     #[serde(default)]
     pub vault_password: Option<String>,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApplySetupResponse {
+    // Whether anything was done; `false` when the document was applied
+    // before.
+    pub changed: bool,
+    pub config_path: String,
+    pub config_changed: bool,
+    // Whether the server left setup mode with this call.
+    pub initialized: bool,
+    #[schema(value_type = Object)]
+    pub plan: ProvisioningPlan,
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/status", get(get_setup_status))
         .route("/config", post(create_initial_config))
+        .route("/apply", post(apply_setup))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/setup/status",
+    tag = "setup",
+    responses((status = 200, description = "Get setup status", body = SetupStatusResponse))
+)]
 async fn get_setup_status() -> Json<SetupStatusResponse> {
     let config_path = ConfigLoader::discover_config_path();
     let data_dir = config_path.parent().unwrap_or(std::path::Path::new("."));
@@ -67,6 +94,13 @@ async fn get_setup_status() -> Json<SetupStatusResponse> {
     Json(status.into())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/setup/config",
+    tag = "setup",
+    request_body = CreateInitialConfigRequest,
+    responses((status = 200, description = "Create initial config", body = Object))
+)]
 async fn create_initial_config(
     State(state): State<AppState>, Json(req): Json<CreateInitialConfigRequest>,
 ) -> ApiResult<Json<serde_json::Value>> {
@@ -88,6 +122,27 @@ async fn create_initial_config(
 
     tracing::info!("Initial configuration created at {:?}", config_path);
 
+    if initialize(&state, config).await? {
+        let inner = state.inner.read().await;
+        if let Some(core) = &inner.core {
+            core.start_background_tasks().await;
+            tracing::info!("Core context initialized and background tasks started");
+        }
+    }
+
+    tracing::info!("Application state updated - setup complete!");
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Configuration created and system initialized successfully",
+        "config_path": config_path.to_string_lossy()
+    })))
+}
+
+// Brings up storage and the core for a newly written config and leaves setup
+// mode. Returns whether the vault is unlocked; background tasks are left to
+// the caller.
+async fn initialize(state: &AppState, config: PipedashConfig) -> ApiResult<bool> {
     tracing::info!(
         "Initializing with storage backend: {}",
         config.storage.backend,
@@ -106,7 +161,7 @@ async fn create_initial_config(
         tracing::info!("PostgreSQL migrations completed");
     }
 
-    let storage_manager = StorageManager::from_config_allow_locked(config.clone(), false)
+    let storage_manager = StorageManager::from_config_allow_locked(config, false)
         .await
         .map_err(|e| AppError::internal(format!("Failed to initialize storage manager: {}", e)))?;
 
@@ -125,37 +180,101 @@ async fn create_initial_config(
         .await
         .map_err(|e| AppError::internal(format!("Failed to initialize core context: {}", e)))?;
 
-    let token_store_ready = if !vault_locked {
+    if !vault_locked {
         tracing::info!("Warming up token store after setup completion...");
         core_context
             .warmup_token_store()
             .await
             .map_err(|e| AppError::internal(format!("Failed to warm up token store: {}", e)))?;
-
-        core_context.start_background_tasks().await;
-        tracing::info!("Core context initialized and background tasks started");
-        true
     } else {
         tracing::info!(
             "Vault locked - skipping token store warmup. Unlock vault to enable providers."
         );
-        false
+    }
+
+    let mut inner = state.inner.write().await;
+    inner.core = Some(core_context);
+    inner.storage_manager = Some(storage_manager);
+    inner.setup_required = false;
+    inner.config_error = None;
+    inner.token_store_ready = !vault_locked;
+    Ok(!vault_locked)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/setup/apply",
+    tag = "setup",
+    request_body(
+        content = String,
+        description = "Setup document: config.toml contents plus `vault_password`, as JSON or TOML"
+    ),
+    responses((status = 200, description = "Apply a setup document", body = ApplySetupResponse))
+)]
+async fn apply_setup(
+    State(state): State<AppState>, body: String,
+) -> ApiResult<Json<ApplySetupResponse>> {
+    let document = SetupDocument::parse(&body)?;
+    document.validate()?;
+
+    let needs_core = {
+        let inner = state.inner.read().await;
+        match &inner.core {
+            Some(core) => {
+                core.access_control.ensure(Action::ManageProviders)?;
+                if !inner.token_store_ready {
+                    return Err(AppError::bad_request(
+                        "Vault is locked: unlock it with /api/v1/vault/unlock before applying setup",
+                    ));
+                }
+                false
+            }
+            None => true,
+        }
     };
 
-    {
-        let mut inner = state.inner.write().await;
-        inner.core = Some(core_context);
-        inner.storage_manager = Some(storage_manager);
-        inner.setup_required = false;
-        inner.config_error = None;
-        inner.token_store_ready = token_store_ready;
+    // Validated above, so there is one.
+    let password = document.vault_password().unwrap_or_default();
+    match std::env::var("PIPEDASH_VAULT_PASSWORD") {
+        Ok(current) if current != password => {
+            return Err(AppError::bad_request(
+                "The vault password differs from the one in use; change it with /api/v1/vault/rotate",
+            ));
+        }
+        Ok(_) => {}
+        Err(_) => std::env::set_var("PIPEDASH_VAULT_PASSWORD", &password),
     }
 
-    tracing::info!("Application state updated - setup complete!");
+    let config_path = ConfigLoader::discover_config_path();
+    let config_changed = document.install(&config_path)?;
 
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "message": "Configuration created and system initialized successfully",
-        "config_path": config_path.to_string_lossy()
-    })))
+    if needs_core {
+        let config = ConfigLoader::load(&config_path)
+            .map_err(|e| AppError::bad_request(format!("Failed to load configuration: {}", e)))?;
+        if !initialize(&state, config).await? {
+            return Err(AppError::internal("Vault is still locked after setup"));
+        }
+    }
+
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let plan = core.apply_config().await?;
+    if needs_core {
+        core.start_background_tasks().await;
+    }
+
+    tracing::info!(
+        config_changed,
+        initialized = needs_core,
+        changes = plan.changes.len(),
+        "Setup document applied"
+    );
+
+    Ok(Json(ApplySetupResponse {
+        changed: config_changed || needs_core || plan.has_changes(),
+        config_path: config_path.to_string_lossy().into_owned(),
+        config_changed,
+        initialized: needs_core,
+        plan,
+    }))
 }
@@ -1,5 +1,8 @@
 use axum::{
-    extract::State,
+    extract::{
+        Path,
+        State,
+    },
     routing::{
         get,
         post,
@@ -8,6 +11,14 @@ use axum::{
     Json,
     Router,
 };
+use pipedash_core::infrastructure::config::{
+    versions,
+    write_config,
+    ConfigBundle,
+    ConfigVersion,
+    ImportOptions,
+    ImportReport,
+};
 use pipedash_core::infrastructure::{
     ConfigLoader,
     MigrationOptions,
@@ -25,6 +36,7 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use utoipa::ToSchema;
 
 use crate::error::{
     ApiResult,
@@ -32,57 +44,72 @@ use crate::error::{
 };
 use crate::state::AppState;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct StorageConfigResponse {
+    #[schema(value_type = Object)]
     pub config: PipedashConfig,
     pub summary: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateStorageConfigRequest {
     #[serde(default)]
+    #[schema(value_type = Option<Object>)]
     pub backend: Option<StorageBackendType>,
     #[serde(default)]
     pub postgres_connection_string: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct PlanMigrationRequest {
+    #[schema(value_type = Object)]
     pub target_config: PipedashConfig,
     #[serde(default)]
+    #[schema(value_type = Object)]
     pub options: MigrationOptions,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ExecuteMigrationRequest {
+    #[schema(value_type = Object)]
     pub plan: MigrationPlan,
+    #[schema(value_type = Object)]
     pub options: MigrationOptions,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportConfigurationRequest {
+    #[schema(value_type = Object)]
+    pub bundle: ConfigBundle,
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub options: ImportOptions,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ConfigContentResponse {
     pub content: String,
     pub path: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SaveConfigContentRequest {
     pub content: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AnalyzeConfigRequest {
     pub new_content: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ConfigIssue {
     pub field: String,
     pub message: String,
     pub code: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ConnectionTestResult {
     pub success: bool,
     pub message: String,
@@ -90,19 +117,20 @@ pub struct ConnectionTestResult {
     pub latency_ms: Option<u64>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct MigrationStats {
     pub providers_count: usize,
     pub tokens_count: usize,
     pub cache_entries_count: usize,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ConfigAnalysisResponse {
     pub valid: bool,
     pub errors: Vec<ConfigIssue>,
     pub warnings: Vec<ConfigIssue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
     pub migration_plan: Option<MigrationPlan>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub postgres_connection: Option<ConnectionTestResult>,
@@ -110,7 +138,7 @@ pub struct ConfigAnalysisResponse {
     pub stats: Option<MigrationStats>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct StoragePathsResponse {
     pub config_file: String,
     pub pipedash_db: String,
@@ -126,7 +154,14 @@ pub fn router() -> Router<AppState> {
         .route("/config", put(update_storage_config))
         .route("/config/content", get(get_config_content))
         .route("/config/content", put(save_config_content))
+        .route("/config/versions", get(list_config_versions))
+        .route(
+            "/config/versions/{id}/restore",
+            post(restore_config_version),
+        )
         .route("/config/analyze", post(analyze_config))
+        .route("/config/export", get(export_configuration))
+        .route("/config/import", post(import_configuration))
         .route("/paths", get(get_storage_paths))
         .route("/validate", post(validate_storage_config))
         .route("/test-connection", post(test_storage_connection))
@@ -135,12 +170,18 @@ pub fn router() -> Router<AppState> {
         .route("/migration/execute", post(execute_migration))
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct VaultPasswordStatus {
     pub is_set: bool,
     pub env_var_name: &'static str,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/storage/vault-password-status",
+    tag = "storage",
+    responses((status = 200, description = "Get vault password status", body = VaultPasswordStatus))
+)]
 async fn get_vault_password_status() -> Json<VaultPasswordStatus> {
     let is_set = std::env::var("PIPEDASH_VAULT_PASSWORD").is_ok();
     Json(VaultPasswordStatus {
@@ -149,6 +190,12 @@ async fn get_vault_password_status() -> Json<VaultPasswordStatus> {
     })
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/storage/config",
+    tag = "storage",
+    responses((status = 200, description = "Get storage config", body = StorageConfigResponse))
+)]
 async fn get_storage_config(
     State(state): State<AppState>,
 ) -> ApiResult<Json<StorageConfigResponse>> {
@@ -163,6 +210,12 @@ async fn get_storage_config(
     Ok(Json(StorageConfigResponse { config, summary }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/storage/config/content",
+    tag = "storage",
+    responses((status = 200, description = "Get config content", body = ConfigContentResponse))
+)]
 async fn get_config_content(
     State(_state): State<AppState>,
 ) -> ApiResult<Json<ConfigContentResponse>> {
@@ -193,6 +246,13 @@ async fn get_config_content(
     }))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/storage/config/content",
+    tag = "storage",
+    request_body = SaveConfigContentRequest,
+    responses((status = 200, description = "Save config content", body = Object))
+)]
 async fn save_config_content(
     State(_state): State<AppState>, Json(req): Json<SaveConfigContentRequest>,
 ) -> ApiResult<Json<serde_json::Value>> {
@@ -216,7 +276,7 @@ async fn save_config_content(
 
     let config_path = ConfigLoader::discover_config_path();
 
-    std::fs::write(&config_path, &req.content).map_err(|e| {
+    write_config(&config_path, &req.content).map_err(|e| {
         AppError::internal(format!(
             "Failed to save config file at {}: {}",
             config_path.display(),
@@ -229,6 +289,41 @@ async fn save_config_content(
     Ok(Json(serde_json::json!({"success": true})))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/storage/config/versions",
+    tag = "storage",
+    responses((status = 200, description = "Previous versions of config.toml, newest first", body = [Object]))
+)]
+async fn list_config_versions(
+    State(_state): State<AppState>,
+) -> ApiResult<Json<Vec<ConfigVersion>>> {
+    let config_path = ConfigLoader::discover_config_path();
+    Ok(Json(versions::list_config_versions(&config_path)?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/storage/config/versions/{id}/restore",
+    tag = "storage",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Restore a previous version of config.toml", body = Object))
+)]
+async fn restore_config_version(
+    State(_state): State<AppState>, Path(id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let config_path = ConfigLoader::discover_config_path();
+    versions::restore_config_version(&config_path, &id)?;
+    Ok(Json(serde_json::json!({"success": true})))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/storage/config/analyze",
+    tag = "storage",
+    request_body = AnalyzeConfigRequest,
+    responses((status = 200, description = "Analyze config", body = ConfigAnalysisResponse))
+)]
 async fn analyze_config(
     State(state): State<AppState>, Json(req): Json<AnalyzeConfigRequest>,
 ) -> ApiResult<Json<ConfigAnalysisResponse>> {
@@ -409,6 +504,12 @@ async fn test_postgres_connection(connection_string: &str) -> ConnectionTestResu
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/storage/paths",
+    tag = "storage",
+    responses((status = 200, description = "Get storage paths", body = StoragePathsResponse))
+)]
 async fn get_storage_paths(State(state): State<AppState>) -> ApiResult<Json<StoragePathsResponse>> {
     use pipedash_core::infrastructure::config::ConfigLoader;
 
@@ -432,6 +533,13 @@ async fn get_storage_paths(State(state): State<AppState>) -> ApiResult<Json<Stor
     }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/storage/validate",
+    tag = "storage",
+    request_body = Object,
+    responses((status = 200, description = "Validate storage config", body = Object))
+)]
 async fn validate_storage_config(
     State(state): State<AppState>, Json(config): Json<StorageConfig>,
 ) -> ApiResult<Json<ValidationReport>> {
@@ -457,6 +565,13 @@ async fn validate_storage_config(
     Ok(Json(report))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/storage/test-connection",
+    tag = "storage",
+    request_body = Object,
+    responses((status = 200, description = "Test storage connection", body = Object))
+)]
 async fn test_storage_connection(
     State(_state): State<AppState>, Json(config): Json<PipedashConfig>,
 ) -> ApiResult<Json<serde_json::Value>> {
@@ -472,6 +587,13 @@ async fn test_storage_connection(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/storage/config",
+    tag = "storage",
+    request_body = UpdateStorageConfigRequest,
+    responses((status = 200, description = "Update storage config", body = Object))
+)]
 async fn update_storage_config(
     State(state): State<AppState>, Json(req): Json<UpdateStorageConfigRequest>,
 ) -> ApiResult<Json<MigrationPlan>> {
@@ -506,6 +628,13 @@ async fn update_storage_config(
     Ok(Json(plan))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/storage/migration/plan",
+    tag = "storage",
+    request_body = PlanMigrationRequest,
+    responses((status = 200, description = "Plan migration", body = Object))
+)]
 async fn plan_migration(
     State(state): State<AppState>, Json(req): Json<PlanMigrationRequest>,
 ) -> ApiResult<Json<MigrationPlan>> {
@@ -530,6 +659,13 @@ async fn plan_migration(
     Ok(Json(plan))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/storage/migration/execute",
+    tag = "storage",
+    request_body = ExecuteMigrationRequest,
+    responses((status = 200, description = "Execute migration", body = Object))
+)]
 async fn execute_migration(
     State(state): State<AppState>, Json(req): Json<ExecuteMigrationRequest>,
 ) -> ApiResult<Json<MigrationResult>> {
@@ -652,3 +788,35 @@ async fn execute_migration(
 
     Ok(Json(result))
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/storage/config/export",
+    tag = "storage",
+    responses((status = 200, description = "Export configuration", body = Object))
+)]
+async fn export_configuration(State(state): State<AppState>) -> ApiResult<Json<ConfigBundle>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let bundle = core.config_bundle_service.export_bundle().await?;
+    Ok(Json(bundle))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/storage/config/import",
+    tag = "storage",
+    request_body = ImportConfigurationRequest,
+    responses((status = 200, description = "Import configuration", body = Object))
+)]
+async fn import_configuration(
+    State(state): State<AppState>, Json(request): Json<ImportConfigurationRequest>,
+) -> ApiResult<Json<ImportReport>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let report = core
+        .config_bundle_service
+        .import_bundle(request.bundle, request.options)
+        .await?;
+    Ok(Json(report))
+}
@@ -1,10 +1,36 @@
 use axum::{
-    extract::State,
-    routing::post,
+    extract::{
+        Query,
+        State,
+    },
+    http::header,
+    response::{
+        IntoResponse,
+        Response,
+    },
+    routing::{
+        get,
+        post,
+    },
     Json,
     Router,
 };
-use serde::Serialize;
+use pipedash_core::application::DiagnosticBundle;
+use pipedash_core::domain::AuditEntry;
+use pipedash_core::logging::{
+    self,
+    AppLogEntry,
+    AppLogQuery,
+    LogLevel,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use utoipa::{
+    IntoParams,
+    ToSchema,
+};
 
 use crate::error::{
     ApiResult,
@@ -12,7 +38,7 @@ use crate::error::{
 };
 use crate::state::AppState;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct FactoryResetResponse {
     pub providers_removed: i64,
     pub caches_cleared: bool,
@@ -20,10 +46,44 @@ pub struct FactoryResetResponse {
     pub metrics_cleared: bool,
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditQuery {
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AppLogsQuery {
+    // Least severe level to include: error, warn, info, debug or trace
+    #[param(value_type = Option<String>)]
+    pub level: Option<LogLevel>,
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LogLevelBody {
+    // A level or `RUST_LOG`-style directives, e.g. `pipedash_core=debug`
+    pub filter: String,
+}
+
 pub fn router() -> Router<AppState> {
-    Router::new().route("/factory-reset", post(factory_reset))
+    Router::new()
+        .route("/factory-reset", post(factory_reset))
+        .route("/audit", get(list_audit_entries))
+        .route("/logs", get(get_app_logs))
+        .route("/logs/level", get(get_log_level).put(set_log_level))
+        .route("/diagnostics/bundle", get(create_diagnostic_bundle))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/factory-reset",
+    tag = "system",
+    responses((status = 200, description = "Factory reset", body = FactoryResetResponse))
+)]
 async fn factory_reset(State(state): State<AppState>) -> ApiResult<Json<FactoryResetResponse>> {
     let inner = state.inner.read().await;
     let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
@@ -45,3 +105,87 @@ async fn factory_reset(State(state): State<AppState>) -> ApiResult<Json<FactoryR
         metrics_cleared: core.metrics_service.is_some(),
     }))
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit",
+    tag = "system",
+    params(AuditQuery),
+    responses((status = 200, description = "List audit log entries, newest first", body = [Object]))
+)]
+async fn list_audit_entries(
+    State(state): State<AppState>, Query(query): Query<AuditQuery>,
+) -> ApiResult<Json<Vec<AuditEntry>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    Ok(Json(core.audit_log.list(query.limit).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/logs",
+    tag = "system",
+    params(AppLogsQuery),
+    responses((status = 200, description = "Recent pipedash log entries, oldest first", body = [Object]))
+)]
+async fn get_app_logs(Query(query): Query<AppLogsQuery>) -> Json<Vec<AppLogEntry>> {
+    Json(logging::app_logs(&AppLogQuery {
+        level: query.level,
+        since: query.since,
+        until: query.until,
+        limit: query.limit,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/logs/level",
+    tag = "system",
+    responses((status = 200, description = "Active log filter", body = LogLevelBody))
+)]
+async fn get_log_level() -> ApiResult<Json<LogLevelBody>> {
+    let filter =
+        logging::log_level().ok_or_else(|| AppError::internal("Logging is not initialized"))?;
+    Ok(Json(LogLevelBody { filter }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/logs/level",
+    tag = "system",
+    request_body = LogLevelBody,
+    responses((status = 200, description = "Log filter changed until restart", body = LogLevelBody))
+)]
+async fn set_log_level(Json(body): Json<LogLevelBody>) -> ApiResult<Json<LogLevelBody>> {
+    logging::set_log_level(&body.filter)?;
+    Ok(Json(body))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/diagnostics/bundle",
+    tag = "system",
+    responses((status = 200, description = "Zip of redacted config, recent logs, provider health, cache stats, schema versions and platform info", content_type = "application/zip", body = Vec<u8>))
+)]
+async fn create_diagnostic_bundle(State(state): State<AppState>) -> ApiResult<Response> {
+    let inner = state.inner.read().await;
+
+    let mut bundle = DiagnosticBundle::new();
+    if let Some(error) = &inner.config_error {
+        bundle = bundle.with_startup_error(error);
+    }
+    if let Some(core) = &inner.core {
+        bundle = bundle.with_core(core).await;
+    }
+    let zip = bundle.into_zip()?;
+
+    let disposition = format!("attachment; filename=\"{}\"", DiagnosticBundle::file_name());
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        zip,
+    )
+        .into_response())
+}
@@ -1,5 +1,9 @@
 use axum::{
     extract::State,
+    response::{
+        IntoResponse,
+        Response,
+    },
     routing::{
         get,
         post,
@@ -12,16 +16,19 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use utoipa::ToSchema;
 
 use crate::error::{
     ApiResult,
     AppError,
 };
+use crate::rate_limit::AuthFailed;
 use crate::state::AppState;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct VaultStatusResponse {
     pub is_unlocked: bool,
+    #[schema(value_type = Object)]
     pub password_source: PasswordSource,
     pub backend: String,
     pub requires_password: bool,
@@ -36,24 +43,42 @@ pub enum PasswordSource {
     None,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UnlockVaultRequest {
     pub password: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UnlockVaultResponse {
     pub success: bool,
     pub message: String,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RotateVaultPasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RotateVaultPasswordResponse {
+    pub rotated: usize,
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/status", get(get_vault_status))
         .route("/unlock", post(unlock_vault))
         .route("/lock", post(lock_vault))
+        .route("/rotate", post(rotate_vault_password))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/vault/status",
+    tag = "vault",
+    responses((status = 200, description = "Get vault status", body = VaultStatusResponse))
+)]
 async fn get_vault_status(State(state): State<AppState>) -> Json<VaultStatusResponse> {
     let inner = state.inner.read().await;
 
@@ -109,9 +134,16 @@ fn determine_password_source(inner: &crate::state::AppStateInner) -> PasswordSou
     PasswordSource::None
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/vault/unlock",
+    tag = "vault",
+    request_body = UnlockVaultRequest,
+    responses((status = 200, description = "Unlock vault", body = UnlockVaultResponse))
+)]
 async fn unlock_vault(
     State(state): State<AppState>, Json(req): Json<UnlockVaultRequest>,
-) -> ApiResult<Json<UnlockVaultResponse>> {
+) -> ApiResult<Response> {
     // Check if already unlocked BEFORE setting env var
     {
         let inner = state.inner.read().await;
@@ -119,7 +151,8 @@ async fn unlock_vault(
             return Ok(Json(UnlockVaultResponse {
                 success: true,
                 message: "Vault is already unlocked".to_string(),
-            }));
+            })
+            .into_response());
         }
     }
 
@@ -188,7 +221,8 @@ async fn unlock_vault(
                     Ok(Json(UnlockVaultResponse {
                         success: true,
                         message: "Vault unlocked successfully".to_string(),
-                    }))
+                    })
+                    .into_response())
                 }
                 Err(e) => {
                     std::env::remove_var("PIPEDASH_VAULT_PASSWORD");
@@ -198,7 +232,8 @@ async fn unlock_vault(
                     Ok(Json(UnlockVaultResponse {
                         success: false,
                         message: format!("Failed to unlock vault: {}", e),
-                    }))
+                    })
+                    .into_response())
                 }
             }
         }
@@ -210,14 +245,24 @@ async fn unlock_vault(
                 e
             );
 
-            Ok(Json(UnlockVaultResponse {
+            // Still a 200 for the frontend, but counted towards the lockout.
+            let mut response = Json(UnlockVaultResponse {
                 success: false,
                 message: format!("Invalid password or storage error: {}", e),
-            }))
+            })
+            .into_response();
+            response.extensions_mut().insert(AuthFailed);
+            Ok(response)
         }
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/vault/lock",
+    tag = "vault",
+    responses((status = 200, description = "Lock vault", body = UnlockVaultResponse))
+)]
 async fn lock_vault(State(state): State<AppState>) -> Json<UnlockVaultResponse> {
     std::env::remove_var("PIPEDASH_VAULT_PASSWORD");
 
@@ -233,3 +278,31 @@ async fn lock_vault(State(state): State<AppState>) -> Json<UnlockVaultResponse>
         message: "Vault locked. Restart required to fully clear token cache.".to_string(),
     })
 }
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/vault/rotate",
+    tag = "vault",
+    request_body = RotateVaultPasswordRequest,
+    responses((status = 200, description = "Rotate vault password", body = RotateVaultPasswordResponse))
+)]
+async fn rotate_vault_password(
+    State(state): State<AppState>, Json(req): Json<RotateVaultPasswordRequest>,
+) -> ApiResult<Json<RotateVaultPasswordResponse>> {
+    let inner = state.inner.read().await;
+
+    if let Some(storage_manager) = &inner.storage_manager {
+        if storage_manager.config().storage.vault_password.is_some() {
+            return Err(AppError::bad_request(
+                "Vault password is set in the config file; change it there instead",
+            ));
+        }
+    }
+
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let rotated = core
+        .rotate_vault_password(&req.current_password, &req.new_password)
+        .await?;
+
+    Ok(Json(RotateVaultPasswordResponse { rotated }))
+}
@@ -0,0 +1,102 @@
+use axum::{
+    extract::{
+        Path,
+        State,
+    },
+    routing::{
+        delete,
+        get,
+    },
+    Json,
+    Router,
+};
+use pipedash_core::domain::{
+    Pipeline,
+    SavedView,
+    ViewQuery,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SaveViewRequest {
+    pub name: String,
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub query: ViewQuery,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_views).post(save_view))
+        .route("/{id}", delete(delete_view))
+        .route("/{id}/pipelines", get(query_view))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/views",
+    tag = "views",
+    responses((status = 200, description = "List views", body = [Object]))
+)]
+async fn list_views(State(state): State<AppState>) -> ApiResult<Json<Vec<SavedView>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let views = core.pipeline_service.list_views().await?;
+    Ok(Json(views))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/views",
+    tag = "views",
+    request_body = SaveViewRequest,
+    responses((status = 200, description = "Save view", body = Object))
+)]
+async fn save_view(
+    State(state): State<AppState>, Json(req): Json<SaveViewRequest>,
+) -> ApiResult<Json<SavedView>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let view = core
+        .pipeline_service
+        .save_view(&req.name, req.query)
+        .await?;
+    Ok(Json(view))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/views/{id}",
+    tag = "views",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Delete view"))
+)]
+async fn delete_view(State(state): State<AppState>, Path(id): Path<i64>) -> ApiResult<()> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    core.pipeline_service.delete_view(id).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/views/{id}/pipelines",
+    tag = "views",
+    params(("id" = i64, Path)),
+    responses((status = 200, description = "Query view", body = [Object]))
+)]
+async fn query_view(
+    State(state): State<AppState>, Path(id): Path<i64>,
+) -> ApiResult<Json<Vec<Pipeline>>> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+    let pipelines = core.pipeline_service.query_view(id).await?;
+    Ok(Json(pipelines))
+}
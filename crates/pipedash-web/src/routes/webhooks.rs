@@ -0,0 +1,114 @@
+use axum::{
+    body::Bytes,
+    extract::{
+        Path,
+        State,
+    },
+    http::{
+        HeaderMap,
+        StatusCode,
+    },
+    routing::post,
+    Router,
+};
+use pipedash_core::infrastructure::webhooks::{
+    self,
+    WebhookEvent,
+    WEBHOOK_SECRET_KEY,
+};
+
+use crate::error::{
+    ApiResult,
+    AppError,
+};
+use crate::state::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/{provider_type}/{provider_id}", post(receive_webhook))
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks/{provider_type}/{provider_id}",
+    tag = "webhooks",
+    params(("provider_type" = String, Path), ("provider_id" = i64, Path)),
+    request_body(content = Object, description = "Provider webhook payload"),
+    responses((status = 200, description = "Receive webhook"))
+)]
+async fn receive_webhook(
+    State(state): State<AppState>, Path((provider_type, provider_id)): Path<(String, i64)>,
+    headers: HeaderMap, body: Bytes,
+) -> ApiResult<StatusCode> {
+    let inner = state.inner.read().await;
+    let core = inner.core.as_ref().ok_or_else(AppError::not_initialized)?;
+
+    let config = core
+        .provider_service
+        .get_provider_config(provider_id)
+        .await?;
+    if config.provider_type != provider_type {
+        return Err(AppError::not_found(format!(
+            "No {} provider with ID {}",
+            provider_type, provider_id
+        )));
+    }
+
+    let secret = config
+        .config
+        .get(WEBHOOK_SECRET_KEY)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| AppError::unauthorized("Webhook secret not configured for provider"))?;
+
+    let event: WebhookEvent = match provider_type.as_str() {
+        "github" => {
+            let signature = header(&headers, "x-hub-signature-256").unwrap_or_default();
+            if !webhooks::verify_github_signature(secret, &body, signature) {
+                return Err(AppError::unauthorized("Invalid webhook signature"));
+            }
+            let event_type = header(&headers, "x-github-event").unwrap_or_default();
+            webhooks::parse_github_event(provider_id, event_type, &body)?
+        }
+        "gitlab" => {
+            let token = header(&headers, "x-gitlab-token").unwrap_or_default();
+            if !webhooks::verify_gitlab_token(secret, token) {
+                return Err(AppError::unauthorized("Invalid webhook token"));
+            }
+            let event_type = header(&headers, "x-gitlab-event").unwrap_or_default();
+            webhooks::parse_gitlab_event(provider_id, event_type, &body)?
+        }
+        other => {
+            return Err(AppError::bad_request(format!(
+                "Webhooks are not supported for provider type '{}'",
+                other
+            )));
+        }
+    };
+
+    tracing::debug!(
+        provider_id = provider_id,
+        event_type = %event.event_type,
+        pipeline_id = ?event.pipeline_id,
+        "Received webhook"
+    );
+
+    core.refresh_manager.record_webhook(provider_id).await;
+
+    // Providers expect a quick acknowledgement, so the refresh runs in the
+    // background.
+    let pipeline_service = core.pipeline_service.clone();
+    tokio::spawn(async move {
+        if let Err(e) = pipeline_service.apply_webhook_event(&event).await {
+            tracing::warn!(
+                provider_id = event.provider_id,
+                error = %e,
+                "Failed to apply webhook event"
+            );
+        }
+    });
+
+    Ok(StatusCode::ACCEPTED)
+}
@@ -12,6 +12,8 @@ pub struct AppStateInner {
     pub setup_required: bool,
     pub config_error: Option<String>,
     pub token_store_ready: bool,
+    // Set once a shutdown signal arrives, so readiness fails while draining.
+    pub shutting_down: bool,
 }
 
 #[derive(Clone)]
@@ -29,6 +31,7 @@ impl AppState {
                 setup_required: true,
                 config_error: None,
                 token_store_ready: false,
+                shutting_down: false,
             })),
             ws_event_bus,
         }
@@ -42,6 +45,7 @@ impl AppState {
                 setup_required: false,
                 config_error: Some(error),
                 token_store_ready: false,
+                shutting_down: false,
             })),
             ws_event_bus,
         }
@@ -57,6 +61,7 @@ impl AppState {
                 setup_required: false,
                 config_error: None,
                 token_store_ready: false,
+                shutting_down: false,
             })),
             ws_event_bus,
         }
@@ -1,7 +1,19 @@
+use std::sync::atomic::{
+    AtomicUsize,
+    Ordering,
+};
+use std::sync::Arc;
+use std::time::{
+    Duration,
+    Instant,
+};
+
 use async_trait::async_trait;
 use axum::{
     extract::{
         ws::{
+            close_code,
+            CloseFrame,
             Message,
             WebSocket,
             WebSocketUpgrade,
@@ -19,7 +31,10 @@ use pipedash_core::event::{
     EventBus,
 };
 use serde::Deserialize;
-use tokio::sync::broadcast;
+use tokio::sync::{
+    broadcast,
+    watch,
+};
 
 use crate::state::AppState;
 
@@ -36,19 +51,62 @@ struct AuthMessage {
     token: String,
 }
 
+// How long a client gets to answer our close frame on shutdown.
+const CLOSE_GRACE: Duration = Duration::from_secs(2);
+
 pub struct WebSocketEventBus {
     tx: broadcast::Sender<CoreEvent>,
+    closing: watch::Sender<bool>,
+    open_sockets: Arc<AtomicUsize>,
 }
 
 impl WebSocketEventBus {
     pub fn new() -> Self {
         let (tx, _) = broadcast::channel(1024);
-        Self { tx }
+        let (closing, _) = watch::channel(false);
+        Self {
+            tx,
+            closing,
+            open_sockets: Arc::new(AtomicUsize::new(0)),
+        }
     }
 
     pub fn subscribe(&self) -> broadcast::Receiver<CoreEvent> {
         self.tx.subscribe()
     }
+
+    // Sends every open socket a close frame and refuses new ones.
+    pub fn close_all(&self) {
+        self.closing.send_replace(true);
+    }
+
+    // Waits for the sockets closed by `close_all` to go away. Returns false if
+    // some were still open when the timeout ran out.
+    pub async fn drain(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while self.open_sockets.load(Ordering::SeqCst) > 0 {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        true
+    }
+}
+
+struct OpenSocket(Arc<AtomicUsize>);
+
+impl OpenSocket {
+    fn new(count: &Arc<AtomicUsize>) -> Self {
+        count.fetch_add(1, Ordering::SeqCst);
+        Self(Arc::clone(count))
+    }
+}
+
+impl Drop for OpenSocket {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl Default for WebSocketEventBus {
@@ -73,7 +131,13 @@ pub async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) ->
 }
 
 async fn handle_socket(socket: WebSocket, state: AppState) {
+    let _open = OpenSocket::new(&state.ws_event_bus.open_sockets);
+    let mut closing = state.ws_event_bus.closing.subscribe();
     let (mut sender, mut receiver) = socket.split();
+    if *closing.borrow() {
+        let _ = sender.send(shutdown_frame()).await;
+        return;
+    }
 
     if let Some(expected_token) = get_ws_auth_token() {
         match receiver.next().await {
@@ -96,41 +160,91 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     }
 
     let mut rx = state.ws_event_bus.subscribe();
+    let mut send_closing = closing.clone();
 
     let send_task = tokio::spawn(async move {
-        while let Ok(event) = rx.recv().await {
-            let event_data = serde_json::json!({
-                "type": event.event_name(),
-                "payload": event.to_json_payload(),
-            });
-
-            if let Ok(json) = serde_json::to_string(&event_data) {
-                if sender.send(Message::Text(json.into())).await.is_err() {
+        loop {
+            tokio::select! {
+                event = rx.recv() => {
+                    let Ok(event) = event else {
+                        break;
+                    };
+                    let event_data = serde_json::json!({
+                        "type": event.event_name(),
+                        "payload": event.to_json_payload(),
+                    });
+
+                    if let Ok(json) = serde_json::to_string(&event_data) {
+                        if sender.send(Message::Text(json.into())).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                _ = async { let _ = send_closing.wait_for(|closing| *closing).await; } => {
+                    let _ = sender.send(shutdown_frame()).await;
                     break;
                 }
             }
         }
     });
 
-    while let Some(msg) = receiver.next().await {
-        match msg {
-            Ok(Message::Ping(data)) => {
-                tracing::trace!("Received ping: {:?}", data);
-            }
-            Ok(Message::Close(_)) => {
-                tracing::debug!("WebSocket client disconnected");
-                break;
-            }
-            Ok(Message::Text(text)) => {
-                tracing::trace!("Received text: {}", text);
-            }
-            Err(e) => {
-                tracing::warn!("WebSocket error: {}", e);
-                break;
+    let receive = async {
+        while let Some(msg) = receiver.next().await {
+            match msg {
+                Ok(Message::Ping(data)) => {
+                    tracing::trace!("Received ping: {:?}", data);
+                }
+                Ok(Message::Close(_)) => {
+                    tracing::debug!("WebSocket client disconnected");
+                    break;
+                }
+                Ok(Message::Text(text)) => {
+                    tracing::trace!("Received text: {}", text);
+                }
+                Err(e) => {
+                    tracing::warn!("WebSocket error: {}", e);
+                    break;
+                }
+                _ => {}
             }
-            _ => {}
+        }
+    };
+    let shutdown = async {
+        let _ = closing.wait_for(|closing| *closing).await;
+        tokio::time::sleep(CLOSE_GRACE).await;
+    };
+
+    tokio::select! {
+        _ = receive => {}
+        _ = shutdown => {
+            tracing::debug!("WebSocket client did not answer the close frame");
         }
     }
 
     send_task.abort();
 }
+
+fn shutdown_frame() -> Message {
+    Message::Close(Some(CloseFrame {
+        code: close_code::AWAY,
+        reason: "Server shutting down".into(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_waits_for_open_sockets() {
+        let bus = WebSocketEventBus::new();
+        let open = OpenSocket::new(&bus.open_sockets);
+
+        bus.close_all();
+        assert!(*bus.closing.subscribe().borrow());
+        assert!(!bus.drain(Duration::from_millis(100)).await);
+
+        drop(open);
+        assert!(bus.drain(Duration::from_millis(100)).await);
+    }
+}
@@ -24,7 +24,7 @@ import { useFilterStore } from './stores/filterStore'
 import { useModalStore } from './stores/modalStore'
 import { displayErrorNotification } from './utils/errorDisplay'
 import { PipelineDetailRoute, PipelinesRoute, SettingsRoute, UnlockRoute } from './routes'
-import { service } from './services'
+import { events, isTauri, service } from './services'
 import type { Pipeline, PipelineRun } from './types'
 
 function AppContent() {
@@ -91,6 +91,40 @@ function AppContent() {
     }
   }, [providers, selectedProviderId, selectedProviderName, setSelectedProviderId])
 
+  useEffect(() => {
+    if (!isTauri()) {
+      return
+    }
+
+    let mounted = true
+    let unlisten: (() => void) | undefined
+
+    const openPendingLink = () => {
+      service
+        .takePendingDeepLink()
+        .then((path) => {
+          if (mounted && path) {
+            navigate(path)
+          }
+        })
+        .catch(() => {})
+    }
+
+    openPendingLink()
+    events.listen('deep-link-opened', openPendingLink).then((fn) => {
+      if (mounted) {
+        unlisten = fn
+      } else {
+        fn()
+      }
+    })
+
+    return () => {
+      mounted = false
+      unlisten?.()
+    }
+  }, [navigate])
+
   const handleRefreshAll = useCallback(async () => {
     await Promise.all([
       queryClient.invalidateQueries({ queryKey: queryKeys.providers.all }),
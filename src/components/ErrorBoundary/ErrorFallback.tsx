@@ -1,7 +1,8 @@
 import { Alert, Button, Card, Code, Collapse, Stack, Text } from '@mantine/core'
 import { useDisclosure } from '@mantine/hooks'
-import { IconAlertTriangle, IconRefresh } from '@tabler/icons-react'
+import { IconAlertTriangle, IconFileZip, IconRefresh } from '@tabler/icons-react'
 
+import { useCreateDiagnosticBundle } from '../../queries/usePlatformQueries'
 import { formatErrorMessage } from '../../types/errors'
 
 interface ErrorFallbackProps {
@@ -18,6 +19,7 @@ export function ErrorFallback({
   showDetails = true,
 }: ErrorFallbackProps) {
   const [detailsOpened, { toggle }] = useDisclosure(false)
+  const createBundle = useCreateDiagnosticBundle()
 
   return (
     <Card withBorder padding="lg" radius="md" style={{ maxWidth: 600, margin: '0 auto' }}>
@@ -60,6 +62,16 @@ export function ErrorFallback({
         >
           Try again
         </Button>
+
+        <Button
+          leftSection={<IconFileZip size={16} />}
+          onClick={() => createBundle.mutate(error.stack || error.message)}
+          loading={createBundle.isPending}
+          variant="subtle"
+          color="gray"
+        >
+          Save diagnostic bundle
+        </Button>
       </Stack>
     </Card>
   )
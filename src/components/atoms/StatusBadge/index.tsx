@@ -1,10 +1,13 @@
 import { Badge, Box } from '@mantine/core'
 import {
   IconCheck,
+  IconCircle,
   IconCircleOff,
   IconClock,
+  IconHourglass,
   IconLoader,
   IconMinus,
+  IconStack2,
   IconX,
 } from '@tabler/icons-react'
 
@@ -37,6 +40,10 @@ const statusConfig: Record<PipelineStatus, {
     label: 'Pending',
     icon: IconClock,
   },
+  queued: {
+    label: 'Queued',
+    icon: IconStack2,
+  },
   cancelled: {
     label: 'Cancelled',
     icon: IconCircleOff,
@@ -45,6 +52,14 @@ const statusConfig: Record<PipelineStatus, {
     label: 'Skipped',
     icon: IconMinus,
   },
+  waiting: {
+    label: 'Waiting',
+    icon: IconHourglass,
+  },
+  neutral: {
+    label: 'Neutral',
+    icon: IconCircle,
+  },
 }
 
 export const StatusBadge = ({
@@ -63,8 +63,11 @@ const statusOptions = [
   { value: 'failed', label: '✗ Failed' },
   { value: 'running', label: '⟳ Running' },
   { value: 'pending', label: '⋯ Pending' },
+  { value: 'queued', label: '⋯ Queued' },
   { value: 'cancelled', label: '⊘ Cancelled' },
   { value: 'skipped', label: '⊗ Skipped' },
+  { value: 'waiting', label: '⏸ Waiting' },
+  { value: 'neutral', label: '○ Neutral' },
 ]
 
 const dateRangeOptions = [
@@ -15,6 +15,8 @@ import { platform } from '../../utils/platform'
 import { ConnectionStatus } from '../common/ConnectionStatus'
 import { WindowControls } from '../common/WindowControls'
 
+import { NotificationCenter } from './NotificationCenter'
+
 interface HeaderProps {
   onRefreshAll?: () => void;
   onToggleNavbar?: () => void;
@@ -235,6 +237,8 @@ setIsMacOS(false)
             </Tooltip>
           )}
 
+          <NotificationCenter />
+
           <Tooltip label="Refresh all" position="bottom">
             <ActionIcon
               variant="subtle"
@@ -133,8 +133,8 @@ export const Navbar = ({
   )
 
   const handleAddProvider = useCallback(
-    async (config: import('../../types').ProviderConfig) => {
-      await addProviderMutation(config)
+    async (config: import('../../types').ProviderConfig, backfillDepth?: number) => {
+      await addProviderMutation({ config, backfillDepth })
     },
     [addProviderMutation]
   )
@@ -0,0 +1,148 @@
+import { useCallback } from 'react'
+import { useNavigate } from 'react-router-dom'
+
+import {
+  ActionIcon,
+  Box,
+  Button,
+  Group,
+  Indicator,
+  Popover,
+  ScrollArea,
+  Stack,
+  Text,
+  Tooltip,
+  UnstyledButton,
+} from '@mantine/core'
+import { notifications as toasts } from '@mantine/notifications'
+import { IconBell } from '@tabler/icons-react'
+
+import { useApprovalEvents, usePendingApprovals } from '../../queries/useApprovalQueries'
+import {
+  useClearNotifications,
+  useMarkNotificationRead,
+  useNotificationEvents,
+  useNotifications,
+  useUnreadNotificationCount,
+} from '../../queries/useNotificationQueries'
+import type { AppNotification, AppNotificationKind } from '../../types'
+
+import { PendingApprovals } from './PendingApprovals'
+
+const KIND_COLORS: Record<AppNotificationKind, string> = {
+  failure: 'red',
+  recovery: 'green',
+  token_expiry: 'orange',
+  sync_conflict: 'yellow',
+  provider_outage: 'red',
+  provider_diagnostics: 'orange',
+}
+
+export function NotificationCenter() {
+  const navigate = useNavigate()
+  const { data: unread = 0 } = useUnreadNotificationCount()
+  const { data: items = [] } = useNotifications()
+  const { data: approvals = [] } = usePendingApprovals()
+  const markReadMutation = useMarkNotificationRead()
+  const clearMutation = useClearNotifications()
+
+  const handleCreated = useCallback((notification: AppNotification) => {
+    toasts.show({
+      title: notification.title,
+      message: notification.message,
+      color: KIND_COLORS[notification.kind],
+    })
+  }, [])
+
+  useNotificationEvents(handleCreated)
+  useApprovalEvents()
+
+  const badge = unread + approvals.length
+
+  const handleOpen = (notification: AppNotification) => {
+    if (!notification.read) {
+      markReadMutation.mutate(notification.id)
+    }
+    if (notification.pipeline_id) {
+      navigate(`/pipelines/${notification.pipeline_id}`)
+    }
+  }
+
+  return (
+    <Popover width={360} position="bottom-end" shadow="md" withArrow>
+      <Popover.Target>
+        <Tooltip label="Notifications" position="bottom">
+          <Indicator label={badge} size={16} disabled={badge === 0} offset={6}>
+            <ActionIcon variant="subtle" size="xl" color="gray">
+              <IconBell size={22} />
+            </ActionIcon>
+          </Indicator>
+        </Tooltip>
+      </Popover.Target>
+
+      <Popover.Dropdown p="xs">
+        <Group justify="space-between" mb="xs">
+          <Text size="sm" fw={600}>Notifications</Text>
+          <Group gap={4}>
+            <Button
+              size="compact-xs"
+              variant="subtle"
+              disabled={unread === 0}
+              onClick={() => markReadMutation.mutate(undefined)}
+            >
+              Mark all read
+            </Button>
+            <Button
+              size="compact-xs"
+              variant="subtle"
+              color="gray"
+              disabled={items.length === 0}
+              onClick={() => clearMutation.mutate()}
+            >
+              Clear
+            </Button>
+          </Group>
+        </Group>
+
+        <PendingApprovals />
+
+        {items.length === 0 ? (
+          <Text size="sm" c="dimmed" ta="center" py="md">
+            No notifications
+          </Text>
+        ) : (
+          <ScrollArea.Autosize mah={400}>
+            <Stack gap={4}>
+              {items.map((notification) => (
+                <UnstyledButton
+                  key={notification.id}
+                  onClick={() => handleOpen(notification)}
+                  p="xs"
+                  style={{
+                    borderRadius: 'var(--mantine-radius-sm)',
+                    borderLeft: `3px solid var(--mantine-color-${KIND_COLORS[notification.kind]}-6)`,
+                    opacity: notification.read ? 0.6 : 1,
+                  }}
+                >
+                  <Group justify="space-between" wrap="nowrap" gap="xs">
+                    <Text size="sm" fw={notification.read ? 400 : 600} lineClamp={1}>
+                      {notification.title}
+                    </Text>
+                    <Text size="xs" c="dimmed" style={{ flexShrink: 0 }}>
+                      {new Date(notification.created_at).toLocaleString()}
+                    </Text>
+                  </Group>
+                  <Box>
+                    <Text size="xs" c="dimmed" lineClamp={2}>
+                      {notification.message}
+                    </Text>
+                  </Box>
+                </UnstyledButton>
+              ))}
+            </Stack>
+          </ScrollArea.Autosize>
+        )}
+      </Popover.Dropdown>
+    </Popover>
+  )
+}
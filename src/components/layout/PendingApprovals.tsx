@@ -0,0 +1,82 @@
+import { Box, Button, Group, Stack, Text } from '@mantine/core'
+import { modals } from '@mantine/modals'
+
+import { useDecideTrigger, usePendingApprovals } from '../../queries/useApprovalQueries'
+import type { TriggerApproval } from '../../types'
+
+export function PendingApprovals() {
+  const { data: approvals = [] } = usePendingApprovals()
+  const decideMutation = useDecideTrigger()
+
+  if (approvals.length === 0) {
+    return null
+  }
+
+  // Approving runs the pipeline, so it always asks first. The confirmation
+  // also covers approving your own request where that's allowed.
+  const handleApprove = (approval: TriggerApproval) => {
+    modals.openConfirmModal({
+      title: 'Approve Trigger',
+      children: (
+        <Text size="sm">
+          Run {approval.pipeline_name} now, as requested by {approval.requested_by}?
+        </Text>
+      ),
+      labels: { confirm: 'Approve and Run', cancel: 'Cancel' },
+      onConfirm: () => decideMutation.mutate({ id: approval.id, approve: true, confirmed: true }),
+    })
+  }
+
+  return (
+    <Box mb="xs">
+      <Text size="xs" fw={600} c="dimmed" mb={4}>
+        Waiting for approval
+      </Text>
+      <Stack gap={4}>
+        {approvals.map((approval) => (
+          <Box
+            key={approval.id}
+            p="xs"
+            style={{
+              borderRadius: 'var(--mantine-radius-sm)',
+              borderLeft: '3px solid var(--mantine-color-blue-6)',
+            }}
+          >
+            <Group justify="space-between" wrap="nowrap" gap="xs">
+              <Text size="sm" fw={600} lineClamp={1}>
+                {approval.pipeline_name}
+              </Text>
+              <Text size="xs" c="dimmed" style={{ flexShrink: 0 }}>
+                {new Date(approval.requested_at).toLocaleString()}
+              </Text>
+            </Group>
+            <Group justify="space-between" wrap="nowrap" gap="xs" mt={4}>
+              <Text size="xs" c="dimmed" lineClamp={1}>
+                Requested by {approval.requested_by}
+              </Text>
+              <Group gap={4} style={{ flexShrink: 0 }}>
+                <Button
+                  size="compact-xs"
+                  variant="subtle"
+                  color="red"
+                  disabled={decideMutation.isPending}
+                  onClick={() => decideMutation.mutate({ id: approval.id, approve: false })}
+                >
+                  Reject
+                </Button>
+                <Button
+                  size="compact-xs"
+                  variant="light"
+                  disabled={decideMutation.isPending}
+                  onClick={() => handleApprove(approval)}
+                >
+                  Approve
+                </Button>
+              </Group>
+            </Group>
+          </Box>
+        ))}
+      </Stack>
+    </Box>
+  )
+}
@@ -34,6 +34,36 @@ export const CompactMetricsCard = ({ metricType, data, loading, onExpand }: Comp
           icon: IconActivity,
           color: 'violet',
         }
+      case 'queue_wait_time':
+        return {
+          title: 'Queue Wait Time',
+          icon: IconClock,
+          color: 'orange',
+        }
+      case 'queue_depth':
+        return {
+          title: 'Queue Depth',
+          icon: IconActivity,
+          color: 'orange',
+        }
+      case 'estimated_cost':
+        return {
+          title: 'Estimated Cost',
+          icon: IconActivity,
+          color: 'teal',
+        }
+      case 'agent_wait_time':
+        return {
+          title: 'Agent Wait Time',
+          icon: IconClock,
+          color: 'orange',
+        }
+      case 'billable_minutes':
+        return {
+          title: 'Billable Minutes',
+          icon: IconClock,
+          color: 'teal',
+        }
     }
   }
 
@@ -237,6 +237,41 @@ return
           color: 'violet',
           icon: IconActivity,
         }
+      case 'queue_wait_time':
+        return {
+          title: 'Queue Wait Time',
+          valueLabel: 'Wait Time (seconds)',
+          color: 'orange',
+          icon: IconClock,
+        }
+      case 'queue_depth':
+        return {
+          title: 'Queue Depth',
+          valueLabel: 'Queued Builds',
+          color: 'orange',
+          icon: IconActivity,
+        }
+      case 'estimated_cost':
+        return {
+          title: 'Estimated Cost',
+          valueLabel: 'Cost',
+          color: 'teal',
+          icon: IconActivity,
+        }
+      case 'agent_wait_time':
+        return {
+          title: 'Agent Wait Time',
+          valueLabel: 'Wait Time (seconds)',
+          color: 'orange',
+          icon: IconClock,
+        }
+      case 'billable_minutes':
+        return {
+          title: 'Billable Minutes',
+          valueLabel: 'Minutes',
+          color: 'teal',
+          icon: IconClock,
+        }
     }
   }
 
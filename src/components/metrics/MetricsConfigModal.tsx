@@ -3,13 +3,14 @@ import { useEffect, useState } from 'react'
 import { Button, Card, Group, NumberInput, Progress, Select, Stack, Switch, Text } from '@mantine/core'
 import { modals } from '@mantine/modals'
 import { notifications } from '@mantine/notifications'
-import { IconCheck, IconTrash } from '@tabler/icons-react'
+import { IconCheck, IconRefresh, IconTrash } from '@tabler/icons-react'
 
 import {
   useFlushMetrics,
   useGlobalMetricsConfig,
   useMetricsStorageStats,
   usePipelineMetricsConfig,
+  useRebuildMetrics,
   useUpdateGlobalMetricsConfig,
   useUpdatePipelineMetricsConfig,
 } from '../../queries/useMetricsQueries'
@@ -45,6 +46,7 @@ export const MetricsConfigModal = ({
   const updateGlobal = useUpdateGlobalMetricsConfig()
   const updatePipeline = useUpdatePipelineMetricsConfig()
   const flushMutation = useFlushMetrics()
+  const rebuildMutation = useRebuildMetrics()
 
   const [enabled, setEnabled] = useState(false)
   const [initialEnabled, setInitialEnabled] = useState(false)
@@ -89,6 +91,24 @@ export const MetricsConfigModal = ({
     })
   }
 
+  const handleRebuild = () => {
+    modals.openConfirmModal({
+      title: 'Rebuild Metrics',
+      children: (
+        <Text size="sm">
+          Recompute {pipelineId ? 'metrics for this pipeline' : 'all metrics'} from the cached run history?
+          Existing metrics are replaced.
+        </Text>
+      ),
+      labels: { confirm: 'Rebuild', cancel: 'Cancel' },
+      onConfirm: async () => {
+        await rebuildMutation.mutateAsync(pipelineId)
+        stats.refetch()
+        onConfigChange?.()
+      },
+    })
+  }
+
   const handleSave = async () => {
     const isDisabling = initialEnabled && !enabled
 
@@ -254,17 +274,29 @@ export const MetricsConfigModal = ({
                     {metricsCount.toLocaleString()} metric{metricsCount !== 1 ? 's' : ''} • {sizeMB.toFixed(2)} MB
                   </Text>
                 </div>
-                <Button
-                  size="xs"
-                  variant="subtle"
-                  color="red"
-                  leftSection={<IconTrash size={14} />}
-                  onClick={handleFlush}
-                  loading={flushMutation.isPending}
-                  disabled={loadingInitial}
-                >
-                  Flush
-                </Button>
+                <Group gap="xs">
+                  <Button
+                    size="xs"
+                    variant="subtle"
+                    leftSection={<IconRefresh size={14} />}
+                    onClick={handleRebuild}
+                    loading={rebuildMutation.isPending}
+                    disabled={loadingInitial || !initialEnabled || flushMutation.isPending}
+                  >
+                    Rebuild
+                  </Button>
+                  <Button
+                    size="xs"
+                    variant="subtle"
+                    color="red"
+                    leftSection={<IconTrash size={14} />}
+                    onClick={handleFlush}
+                    loading={flushMutation.isPending}
+                    disabled={loadingInitial || rebuildMutation.isPending}
+                  >
+                    Flush
+                  </Button>
+                </Group>
               </Group>
 
               <Progress value={Math.min((sizeMB / 100) * 100, 100)} size="sm" />
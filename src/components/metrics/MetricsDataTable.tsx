@@ -115,6 +115,16 @@ return value.toFixed(0)
         return 'Success Rate'
       case 'run_frequency':
         return 'Run Count'
+      case 'queue_wait_time':
+        return 'Avg Wait Time'
+      case 'queue_depth':
+        return 'Queued Builds'
+      case 'estimated_cost':
+        return 'Estimated Cost'
+      case 'agent_wait_time':
+        return 'Avg Agent Wait'
+      case 'billable_minutes':
+        return 'Billable Minutes'
     }
   }
 
@@ -0,0 +1,46 @@
+import { Alert, Anchor, Badge, Group, Stack, Text } from '@mantine/core'
+import { IconHourglass } from '@tabler/icons-react'
+
+import type { EnvironmentProtection } from '../../types'
+
+interface EnvironmentProtectionPanelProps {
+  rules: EnvironmentProtection[];
+  logsUrl: string;
+}
+
+export const EnvironmentProtectionPanel = ({ rules, logsUrl }: EnvironmentProtectionPanelProps) => {
+  if (rules.length === 0) {
+    return null
+  }
+
+  return (
+    <Alert color="orange" icon={<IconHourglass size={16} />} title="Waiting for approval">
+      <Stack gap="sm">
+        {rules.map((rule) => (
+          <Stack key={rule.environment} gap={4}>
+            <Group gap="xs">
+              <Text size="sm" fw={600}>{rule.environment}</Text>
+              {rule.can_approve && (
+                <Badge size="xs" variant="light" color="teal">You can approve</Badge>
+              )}
+            </Group>
+            {rule.reviewers.length > 0 && (
+              <Text size="xs" c="dimmed">
+                Required reviewers: {rule.reviewers.join(', ')}
+              </Text>
+            )}
+            {rule.wait_timer_minutes > 0 && (
+              <Text size="xs" c="dimmed">
+                Wait timer: {rule.wait_timer_minutes} min
+                {rule.wait_timer_ends_at && ` (until ${new Date(rule.wait_timer_ends_at).toLocaleString()})`}
+              </Text>
+            )}
+          </Stack>
+        ))}
+        <Anchor href={logsUrl} target="_blank" size="xs">
+          Review deployments on GitHub
+        </Anchor>
+      </Stack>
+    </Alert>
+  )
+}
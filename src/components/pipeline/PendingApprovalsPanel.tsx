@@ -0,0 +1,114 @@
+import { useState } from 'react'
+
+import { Alert, Button, Group, Stack, Text, Textarea, TextInput } from '@mantine/core'
+import { IconLockOpen } from '@tabler/icons-react'
+
+import { usePendingApprovals, useUnblockJob } from '../../queries/useRunDetailsQuery'
+import type { PendingApproval } from '../../types'
+
+interface PendingApprovalsPanelProps {
+  pipelineId: string;
+  runNumber: number;
+}
+
+// Block steps don't always report their fields, so those are entered as
+// `key=value` lines.
+const parseFields = (text: string): Record<string, string> => {
+  const fields: Record<string, string> = {}
+
+  for (const line of text.split('\n')) {
+    const separator = line.indexOf('=')
+
+    if (separator > 0) {
+      fields[line.slice(0, separator).trim()] = line.slice(separator + 1).trim()
+    }
+  }
+
+  return fields
+}
+
+const ApprovalItem = ({
+  pipelineId,
+  runNumber,
+  approval,
+}: PendingApprovalsPanelProps & { approval: PendingApproval }) => {
+  const unblockMutation = useUnblockJob()
+  const [values, setValues] = useState<Record<string, string>>({})
+  const [rawFields, setRawFields] = useState('')
+
+  const handleUnblock = () => {
+    unblockMutation.mutate({
+      pipelineId,
+      runNumber,
+      jobId: approval.job_id,
+      fields: approval.fields.length > 0 ? values : parseFields(rawFields),
+    })
+  }
+
+  return (
+    <Stack gap="xs">
+      <Text size="sm" fw={600}>{approval.label}</Text>
+      {approval.prompt && <Text size="xs" c="dimmed">{approval.prompt}</Text>}
+      {approval.fields.length > 0 ? (
+        approval.fields.map((field) => (
+          <TextInput
+            key={field.name}
+            label={field.label ?? field.name}
+            description={field.description ?? undefined}
+            required={field.required}
+            value={values[field.name] ?? String(field.default ?? '')}
+            onChange={(e) => {
+              const value = e.currentTarget.value
+
+              setValues((prev) => ({ ...prev, [field.name]: value }))
+            }}
+          />
+        ))
+      ) : (
+        <Textarea
+          label="Fields"
+          description="Optional, one key=value per line"
+          autosize
+          minRows={2}
+          value={rawFields}
+          onChange={(e) => setRawFields(e.currentTarget.value)}
+        />
+      )}
+      <Group justify="flex-end">
+        <Button
+          size="sm"
+          variant="light"
+          color="teal"
+          leftSection={<IconLockOpen size={14} />}
+          onClick={handleUnblock}
+          loading={unblockMutation.isPending}
+        >
+          Unblock
+        </Button>
+      </Group>
+    </Stack>
+  )
+}
+
+export const PendingApprovalsPanel = ({ pipelineId, runNumber }: PendingApprovalsPanelProps) => {
+  const { data: approvals = [] } = usePendingApprovals(pipelineId, runNumber)
+
+  if (approvals.length === 0) {
+    return null
+  }
+
+  return (
+    <Alert color="yellow" icon={<IconLockOpen size={16} />} title="Waiting for approval">
+      <Stack gap="md">
+        {approvals.map((approval) => (
+          <ApprovalItem
+            key={approval.job_id}
+            pipelineId={pipelineId}
+            runNumber={runNumber}
+            approval={approval}
+          />
+        ))}
+      </Stack>
+    </Alert>
+  )
+}
@@ -46,6 +46,16 @@ const getDefaultAggregationType = (metricType: MetricType): AggregationType => {
       return 'avg'
     case 'run_frequency':
       return 'sum'
+    case 'queue_wait_time':
+      return 'avg'
+    case 'queue_depth':
+      return 'avg'
+    case 'estimated_cost':
+      return 'sum'
+    case 'agent_wait_time':
+      return 'avg'
+    case 'billable_minutes':
+      return 'sum'
   }
 }
 
@@ -57,6 +67,16 @@ const getMetricTitle = (type: MetricType): string => {
       return 'Success Rate'
     case 'run_frequency':
       return 'Run Frequency'
+    case 'queue_wait_time':
+      return 'Queue Wait Time'
+    case 'queue_depth':
+      return 'Queue Depth'
+    case 'estimated_cost':
+      return 'Estimated Cost'
+    case 'agent_wait_time':
+      return 'Agent Wait Time'
+    case 'billable_minutes':
+      return 'Billable Minutes'
   }
 }
 
@@ -5,7 +5,7 @@ import { notifications } from '@mantine/notifications'
 import { IconAlertCircle } from '@tabler/icons-react'
 
 import { useIsMobile } from '../../hooks/useIsMobile'
-import { useTriggerWorkflow, useWorkflowParameters } from '../../queries/useWorkflowQueries'
+import { pendingApprovalId, useTriggerWorkflow, useWorkflowParameters } from '../../queries/useWorkflowQueries'
 import type { Pipeline, WorkflowParameter } from '../../types'
 import { displayErrorNotification } from '../../utils/errorDisplay'
 import { StandardModal } from '../common/StandardModal'
@@ -108,6 +108,12 @@ return
       {
         onSuccess: (result) => {
           isSubmittingRef.current = false
+          if (pendingApprovalId(result) !== null) {
+            onClose()
+
+            return
+          }
+
           let runNumber = 0
           let shouldOpenLogs = false
 
@@ -8,7 +8,7 @@ import { useIsMobile } from '../../hooks/useIsMobile'
 import { useRerunWorkflow, useRunDetails } from '../../queries/useRunDetailsQuery'
 import { useTableDefinition } from '../../queries/useTableSchemaQueries'
 import { service } from '../../services'
-import type { PipelineStatus } from '../../types'
+import type { EnvironmentProtection, PipelineStatus } from '../../types'
 import { filterVisibleColumns } from '../../utils/columnBuilder'
 import { DynamicRenderers, THEME_COLORS, THEME_TYPOGRAPHY } from '../../utils/dynamicRenderers'
 import { formatDuration } from '../../utils/formatDuration'
@@ -17,6 +17,9 @@ import { CopyButton } from '../atoms/CopyButton'
 import { StandardModal } from '../common/StandardModal'
 import { StatusBadge } from '../common/StatusBadge'
 
+import { EnvironmentProtectionPanel } from './EnvironmentProtectionPanel'
+import { PendingApprovalsPanel } from './PendingApprovalsPanel'
+
 interface WorkflowLogsModalProps {
   opened: boolean;
   onClose: () => void;
@@ -70,7 +73,7 @@ export const WorkflowLogsModal = ({
 
   const isPipelineStatus = (value: unknown): value is PipelineStatus => {
     return typeof value === 'string' &&
-      ['success', 'failed', 'running', 'pending', 'cancelled', 'skipped'].includes(value)
+      ['success', 'failed', 'running', 'pending', 'queued', 'cancelled', 'skipped', 'waiting', 'neutral'].includes(value)
   }
 
   const isValidDateValue = (value: unknown): value is string | number | Date => {
@@ -81,7 +84,7 @@ export const WorkflowLogsModal = ({
 return false
   }
 
-  const isRunning = runDetails?.status === 'running' || runDetails?.status === 'pending'
+  const isRunning = runDetails?.status === 'running' || runDetails?.status === 'pending' || runDetails?.status === 'queued' || runDetails?.status === 'waiting'
   const isPending = runDetails?.status === 'pending'
 
   const handleRerun = async () => {
@@ -258,6 +261,17 @@ return
             </Alert>
           )}
 
+          {isRunning && runDetails.metadata?.blocked_step && (
+            <PendingApprovalsPanel pipelineId={pipelineId} runNumber={runNumber} />
+          )}
+
+          {runDetails.status === 'waiting' && Array.isArray(runDetails.metadata?.environment_protection) && (
+            <EnvironmentProtectionPanel
+              rules={runDetails.metadata.environment_protection as EnvironmentProtection[]}
+              logsUrl={runDetails.logs_url}
+            />
+          )}
+
           <Paper
             p={isMobile ? 'md' : 'lg'}
             withBorder
@@ -7,6 +7,8 @@ import {
   Checkbox as MantineCheckbox,
   Group,
   Loader,
+  MultiSelect,
+  NumberInput,
   Paper,
   ScrollArea,
   Select,
@@ -27,6 +29,12 @@ import { LoadingState } from '../../common/LoadingState'
 
 import type { FormAction, FormState } from './types'
 
+const DEFAULT_BACKFILL_DEPTH = 1000
+const MAX_BACKFILL_DEPTH = 10000
+
+const AUTO_DISCOVER_KEY = 'auto_discover'
+const DISCOVERY_ORGS_KEY = 'discovery_orgs'
+
 interface PipelinesStepProps {
   state: FormState
   dispatch: Dispatch<FormAction>
@@ -49,8 +57,32 @@ export function PipelinesStep({
     selectedOrganization,
     selectedPipelines,
     repositorySearch,
+    backfillDepth,
+    configValues,
   } = state
 
+  const supportsDiscovery = !!selectedPlugin?.capabilities.repository_discovery
+  const autoDiscover = configValues[AUTO_DISCOVER_KEY] === 'true'
+  const discoveryOrgs = useMemo(
+    () =>
+      (configValues[DISCOVERY_ORGS_KEY] || '')
+        .split(',')
+        .map((org) => org.trim())
+        .filter(Boolean),
+    [configValues]
+  )
+
+  // Discovery settings don't change what the provider lists, so toggling them
+  // shouldn't refetch organizations or restart the preview.
+  const previewConfig = useMemo<ProviderConfig>(() => {
+    const config = { ...(providerConfig.config || {}) }
+
+    delete config[AUTO_DISCOVER_KEY]
+    delete config[DISCOVERY_ORGS_KEY]
+
+    return { ...providerConfig, config }
+  }, [providerConfig])
+
   const debouncedSearch = useDebounce(repositorySearch, DEBOUNCE_DELAYS.FILTER)
 
   const {
@@ -58,34 +90,25 @@ export function PipelinesStep({
     isLoading: loadingOrganizations,
   } = useProviderOrganizations(
     selectedPlugin?.provider_type || '',
-    providerConfig,
+    previewConfig,
     true
   )
 
   const {
-    data: pipelinesData,
+    items: availablePipelines,
     isLoading: loadingPipelines,
-    isFetching: searchingPipelines,
-    fetchNextPage,
-    hasNextPage,
-    isFetchingNextPage: loadingMore,
+    isStreaming: streamingPipelines,
+    error: pipelinesError,
+    cancel: cancelPreview,
   } = usePipelinePreview(
     selectedPlugin?.provider_type || '',
-    providerConfig,
+    previewConfig,
     selectedOrganization || undefined,
     debouncedSearch || undefined,
     true
   )
 
   const availableOrganizations = useMemo(() => organizationsData || [], [organizationsData])
-  const availablePipelines = useMemo(() =>
-    pipelinesData?.pages.flatMap((page) => page.items) || [],
-  [pipelinesData]
-  )
-  const allPipelines = useMemo(() =>
-    pipelinesData?.pages[pipelinesData.pages.length - 1] || null,
-  [pipelinesData]
-  )
 
   const filteredPipelines = useMemo(() => {
     if (!repositorySearch.trim()) {
@@ -130,6 +153,17 @@ export function PipelinesStep({
     dispatch({ type: 'SET_REPOSITORY_SEARCH', search: value })
   }
 
+  const handleAutoDiscoverChange = (checked: boolean) => {
+    dispatch({ type: 'UPDATE_CONFIG', key: AUTO_DISCOVER_KEY, value: checked ? 'true' : 'false' })
+    if (checked && discoveryOrgs.length === 0 && selectedOrganization) {
+      dispatch({ type: 'UPDATE_CONFIG', key: DISCOVERY_ORGS_KEY, value: selectedOrganization })
+    }
+  }
+
+  const handleDiscoveryOrgsChange = (orgs: string[]) => {
+    dispatch({ type: 'UPDATE_CONFIG', key: DISCOVERY_ORGS_KEY, value: orgs.join(',') })
+  }
+
   const handlePipelineToggle = (pipelineId: string) => {
     dispatch({ type: 'TOGGLE_PIPELINE', pipelineId })
   }
@@ -142,12 +176,6 @@ export function PipelinesStep({
     }
   }
 
-  const handleLoadMore = () => {
-    if (hasNextPage && !loadingMore) {
-      fetchNextPage()
-    }
-  }
-
   const renderMobilePipelineCards = () => {
     return (
       <Stack gap="xs">
@@ -197,7 +225,7 @@ export function PipelinesStep({
                 placeholder="Search repositories..."
                 value={repositorySearch}
                 onChange={(e) => handleSearchChange(e.currentTarget.value)}
-                leftSection={searchingPipelines ? <Loader size={16} /> : <IconSearch size={16} />}
+                leftSection={streamingPipelines ? <Loader size={16} /> : <IconSearch size={16} />}
                 disabled={!selectedOrganization}
                 style={{ flex: 1 }}
                 styles={{
@@ -236,22 +264,91 @@ export function PipelinesStep({
               />
             </ScrollArea>
 
-            {allPipelines && selectedOrganization && allPipelines.has_more && (
-              <Group justify="center" pt={4}>
-                <Button
-                  size="xs"
-                  variant="filled"
-                  color="dark.5"
-                  onClick={handleLoadMore}
-                  loading={loadingMore}
-                  disabled={loadingMore}
-                >
-                  Load More
-                </Button>
+            {(streamingPipelines || pipelinesError) && (
+              <Group justify="center" gap="xs" pt={4}>
+                {pipelinesError ? (
+                  <Text size={THEME_TYPOGRAPHY.HELPER_TEXT.size} c="red">
+                    {pipelinesError}
+                  </Text>
+                ) : (
+                  <>
+                    <Loader size="xs" />
+                    <Text size={THEME_TYPOGRAPHY.HELPER_TEXT.size} c={THEME_COLORS.DIMMED}>
+                      Loaded {availablePipelines.length} so far...
+                    </Text>
+                    <Button size="xs" variant="subtle" color="gray" onClick={cancelPreview}>
+                      Stop
+                    </Button>
+                  </>
+                )}
               </Group>
             )}
           </>
         )}
+
+        {supportsDiscovery && (
+          <Paper p="sm" withBorder style={{ flexShrink: 0 }}>
+            <Stack gap="xs">
+              <MantineCheckbox
+                label="Auto-discover new repositories"
+                description="Repositories created later in these organizations are added automatically"
+                checked={autoDiscover}
+                onChange={(e) => handleAutoDiscoverChange(e.currentTarget.checked)}
+              />
+              {autoDiscover && (
+                <MultiSelect
+                  size="xs"
+                  placeholder="Organizations to watch"
+                  value={discoveryOrgs}
+                  onChange={handleDiscoveryOrgsChange}
+                  data={Array.from(
+                    new Set([...availableOrganizations.map((org) => org.id), ...discoveryOrgs])
+                  ).map((id) => ({
+                    value: id,
+                    label: availableOrganizations.find((org) => org.id === id)?.name || id,
+                  }))}
+                  searchable
+                  error={discoveryOrgs.length === 0 ? 'Select at least one organization' : undefined}
+                />
+              )}
+            </Stack>
+          </Paper>
+        )}
+
+        {!editMode && (
+          <Paper p="sm" withBorder style={{ flexShrink: 0, marginBottom: 'var(--mantine-spacing-md)' }}>
+            <Group gap="sm" justify="space-between" wrap="nowrap">
+              <MantineCheckbox
+                label="Import run history"
+                description="Backfill older runs so metrics don't start from zero"
+                checked={backfillDepth !== null}
+                onChange={(e) =>
+                  dispatch({
+                    type: 'SET_BACKFILL_DEPTH',
+                    depth: e.currentTarget.checked ? DEFAULT_BACKFILL_DEPTH : null,
+                  })
+                }
+              />
+              {backfillDepth !== null && (
+                <NumberInput
+                  size="xs"
+                  w={isMobile ? 100 : 140}
+                  min={1}
+                  max={MAX_BACKFILL_DEPTH}
+                  step={100}
+                  suffix=" runs"
+                  value={backfillDepth}
+                  onChange={(value) =>
+                    dispatch({
+                      type: 'SET_BACKFILL_DEPTH',
+                      depth: typeof value === 'number' ? value : DEFAULT_BACKFILL_DEPTH,
+                    })
+                  }
+                />
+              )}
+            </Group>
+          </Paper>
+        )}
       </Stack>
     </Box>
   )
@@ -38,6 +38,7 @@ export function AddProviderModal({
     providerName,
     configValues,
     selectedPipelines,
+    backfillDepth,
     submitting,
     error,
     fieldErrors,
@@ -51,6 +52,12 @@ export function AddProviderModal({
     refresh_interval: 300,
   }), [providerName, selectedPlugin?.provider_type, configValues])
 
+  // With auto-discovery on, the provider can start from an empty selection.
+  const discoveryEnabled =
+    !!selectedPlugin?.capabilities.repository_discovery &&
+    configValues.auto_discover === 'true' &&
+    (configValues.discovery_orgs || '').split(',').some((org) => org.trim())
+
   const permission = usePermissionCheck({
     selectedPlugin,
     providerConfig,
@@ -133,7 +140,7 @@ export function AddProviderModal({
   }, [])
 
   const handleSubmit = useCallback(async () => {
-    if (!selectedPlugin || selectedPipelines.size === 0) {
+    if (!selectedPlugin || (selectedPipelines.size === 0 && !discoveryEnabled)) {
       dispatch({ type: 'SET_ERROR', error: 'Please select at least one pipeline' })
 
       return
@@ -162,7 +169,7 @@ export function AddProviderModal({
       if (editMode && existingProvider && onUpdate) {
         await onUpdate(existingProvider.id, providerConfig)
       } else if (onAdd) {
-        await onAdd(providerConfig)
+        await onAdd(providerConfig, backfillDepth ?? undefined)
       }
 
       success = true
@@ -184,7 +191,7 @@ export function AddProviderModal({
         onClose()
       }
     }
-  }, [selectedPlugin, selectedPipelines, configValues, providerName, editMode, existingProvider, onUpdate, onAdd, onClose])
+  }, [selectedPlugin, selectedPipelines, discoveryEnabled, configValues, providerName, backfillDepth, editMode, existingProvider, onUpdate, onAdd, onClose])
 
   const handleSaveCredentialsOnly = useCallback(async () => {
     if (!validateCredentials() || !selectedPlugin) {
@@ -286,14 +293,14 @@ export function AddProviderModal({
             size="sm"
             onClick={handleSubmit}
             loading={submitting}
-            disabled={selectedPipelines.size === 0}
+            disabled={selectedPipelines.size === 0 && !discoveryEnabled}
           >
             {editMode ? (isMobile ? 'Update' : 'Update Provider') : (isMobile ? 'Add' : 'Add Provider')}
           </Button>
         </Group>
       </Group>
     )
-  }, [step, selectedPlugin, configValues.token, submitting, permission, editMode, providerName, isMobile, selectedPipelines.size, handleNext, handleBack, handleSubmit, handleSaveCredentialsOnly, onClose])
+  }, [step, selectedPlugin, configValues.token, submitting, permission, editMode, providerName, isMobile, selectedPipelines.size, discoveryEnabled, handleNext, handleBack, handleSubmit, handleSaveCredentialsOnly, onClose])
 
   const activeStepIndex = step === 'credentials' ? 0 : 1
 
@@ -92,6 +92,9 @@ export function formReducer(state: FormState, action: FormAction): FormState {
     case 'SET_REPOSITORY_SEARCH':
       return { ...state, repositorySearch: action.search }
 
+    case 'SET_BACKFILL_DEPTH':
+      return { ...state, backfillDepth: action.depth }
+
     case 'SET_SUBMITTING':
       return { ...state, submitting: action.submitting }
 
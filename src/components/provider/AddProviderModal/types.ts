@@ -14,6 +14,8 @@ export interface FormState {
   selectedOrganization: string
   selectedPipelines: Set<string>
   repositorySearch: string
+  // Runs per pipeline to import once the provider is added; off when null.
+  backfillDepth: number | null
 
   submitting: boolean
   error: string | null
@@ -42,6 +44,7 @@ export type FormAction =
   | { type: 'SELECT_ALL_PIPELINES'; pipelineIds: string[] }
   | { type: 'CLEAR_SELECTED_PIPELINES' }
   | { type: 'SET_REPOSITORY_SEARCH'; search: string }
+  | { type: 'SET_BACKFILL_DEPTH'; depth: number | null }
   | { type: 'SET_SUBMITTING'; submitting: boolean }
   | { type: 'SET_ERROR'; error: string | null }
   | { type: 'SET_FIELD_ERROR'; key: string; error: string }
@@ -53,7 +56,7 @@ export type FormAction =
 export interface AddProviderModalProps {
   opened: boolean
   onClose: () => void
-  onAdd?: (config: ProviderConfig) => Promise<void>
+  onAdd?: (config: ProviderConfig, backfillDepth?: number) => Promise<void>
   onUpdate?: (id: number, config: ProviderConfig) => Promise<void>
   editMode?: boolean
   existingProvider?: ProviderConfig & { id: number }
@@ -68,6 +71,7 @@ export const initialFormState: FormState = {
   selectedOrganization: '',
   selectedPipelines: new Set(),
   repositorySearch: '',
+  backfillDepth: null,
   submitting: false,
   error: null,
   fieldErrors: {},
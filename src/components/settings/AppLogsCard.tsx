@@ -0,0 +1,128 @@
+import { useEffect, useState } from 'react'
+
+import { Badge, Box, Button, Card, Code, Divider, Group, ScrollArea, Select, Stack, Text, TextInput } from '@mantine/core'
+import { IconFileZip, IconRefresh } from '@tabler/icons-react'
+
+import { useAppLogs, useCreateDiagnosticBundle, useLogLevel, useSetLogLevel } from '@/queries/usePlatformQueries'
+import type { AppLogEntry, LogLevel } from '@/types'
+
+import { CopyButton } from '../atoms'
+
+const LEVEL_COLORS: Record<LogLevel, string> = {
+  error: 'red',
+  warn: 'yellow',
+  info: 'blue',
+  debug: 'gray',
+  trace: 'gray',
+}
+
+const formatEntry = (entry: AppLogEntry) => {
+  const fields = entry.fields ? ` ${JSON.stringify(entry.fields)}` : ''
+
+  return `${entry.timestamp} ${entry.level.toUpperCase()} ${entry.target}: ${entry.message}${fields}`
+}
+
+export function AppLogsCard() {
+  const [level, setLevel] = useState<LogLevel | undefined>(undefined)
+  const [filter, setFilter] = useState('')
+  const { data: logs = [], refetch, isFetching } = useAppLogs(level)
+  const { data: currentFilter } = useLogLevel()
+  const setLogLevel = useSetLogLevel()
+  const createBundle = useCreateDiagnosticBundle()
+
+  useEffect(() => {
+    if (currentFilter !== undefined) {
+      setFilter(currentFilter)
+    }
+  }, [currentFilter])
+
+  return (
+    <Card withBorder padding="md" radius="md">
+      <Stack gap="md">
+        <Group justify="space-between" align="flex-start">
+          <Box>
+            <Text size="md" fw={600}>Logs</Text>
+            <Text size="xs" c="dimmed">Recent application logs, with secrets masked</Text>
+          </Box>
+          <Group gap={4}>
+            <CopyButton value={logs.map(formatEntry).join('\n')} label="Copy logs" />
+            <Button
+              variant="subtle"
+              size="xs"
+              leftSection={<IconFileZip size={14} />}
+              loading={createBundle.isPending}
+              onClick={() => createBundle.mutate(undefined)}
+            >
+              Diagnostic bundle
+            </Button>
+            <Button
+              variant="subtle"
+              size="xs"
+              leftSection={<IconRefresh size={14} />}
+              loading={isFetching}
+              onClick={() => refetch()}
+            >
+              Refresh
+            </Button>
+          </Group>
+        </Group>
+
+        <Divider />
+
+        <Group align="flex-end" grow>
+          <Select
+            label="Show"
+            placeholder="All levels"
+            clearable
+            data={[
+              { value: 'error', label: 'Errors' },
+              { value: 'warn', label: 'Warnings and above' },
+              { value: 'info', label: 'Info and above' },
+              { value: 'debug', label: 'Debug and above' },
+            ]}
+            value={level ?? null}
+            onChange={(value) => setLevel((value as LogLevel) || undefined)}
+          />
+          <TextInput
+            label="Log filter"
+            description="A level or directives like pipedash_core=debug, until restart"
+            value={filter}
+            onChange={(event) => setFilter(event.currentTarget.value)}
+            rightSectionWidth={70}
+            rightSection={
+              <Button
+                size="compact-xs"
+                variant="light"
+                disabled={!filter.trim() || filter === currentFilter}
+                loading={setLogLevel.isPending}
+                onClick={() => setLogLevel.mutate(filter.trim())}
+              >
+                Apply
+              </Button>
+            }
+          />
+        </Group>
+
+        <ScrollArea h={280} type="auto">
+          {logs.length === 0 ? (
+            <Text size="xs" c="dimmed">No log entries</Text>
+          ) : (
+            <Stack gap={2}>
+              {logs.map((entry, index) => (
+                <Group key={`${entry.timestamp}-${index}`} gap="xs" wrap="nowrap" align="flex-start">
+                  <Badge size="xs" variant="light" color={LEVEL_COLORS[entry.level]} style={{ flexShrink: 0 }}>
+                    {entry.level}
+                  </Badge>
+                  <Code style={{ whiteSpace: 'pre-wrap', wordBreak: 'break-word', fontSize: 11 }}>
+                    {new Date(entry.timestamp).toLocaleTimeString()} {entry.target}: {entry.message}
+                    {entry.fields ? ` ${JSON.stringify(entry.fields)}` : ''}
+                  </Code>
+                </Group>
+              ))}
+            </Stack>
+          )}
+        </ScrollArea>
+      </Stack>
+    </Card>
+  )
+}
@@ -1,14 +1,14 @@
 import { useCallback, useEffect, useState } from 'react'
 
 import { parse } from '@iarna/toml'
-import { Alert, Badge, Button, Group, Loader, Stack, Text } from '@mantine/core'
+import { Alert, Badge, Button, Group, Loader, Select, Stack, Text } from '@mantine/core'
 import { modals } from '@mantine/modals'
 import { notifications } from '@mantine/notifications'
 import Editor from '@monaco-editor/react'
 import { IconAlertCircle, IconCheck, IconX } from '@tabler/icons-react'
 
 import { service } from '../../services'
-import type { ConfigAnalysisResponse, MigrationOptions } from '../../types'
+import type { ConfigAnalysisResponse, ConfigVersion, MigrationOptions } from '../../types'
 import { StandardModal } from '../common/StandardModal'
 
 import { MigrationConfirmModal } from './MigrationConfirmModal'
@@ -34,6 +34,7 @@ export const ConfigEditorModal = ({
   const [analysis, setAnalysis] = useState<ConfigAnalysisResponse | null>(null)
   const [analyzing, setAnalyzing] = useState(false)
   const [showMigrationModal, setShowMigrationModal] = useState(false)
+  const [versions, setVersions] = useState<ConfigVersion[]>([])
 
   useEffect(() => {
     if (opened) {
@@ -42,6 +43,10 @@ export const ConfigEditorModal = ({
       setHasChanges(false)
       setAnalysis(null)
       setAnalyzing(false)
+      service
+        .listConfigVersions()
+        .then(setVersions)
+        .catch(() => setVersions([]))
     }
   }, [opened, initialContent])
 
@@ -220,6 +225,43 @@ return
     }
   }
 
+  const handleRestore = (version: ConfigVersion) => {
+    modals.openConfirmModal({
+      title: 'Restore Previous Version',
+      centered: true,
+      zIndex: 400,
+      children: (
+        <Text size="sm">
+          Replace config.toml with the version saved {new Date(version.created_at).toLocaleString()}?
+          The current file is kept as a version, so this can be undone.
+        </Text>
+      ),
+      labels: { confirm: 'Restore', cancel: 'Cancel' },
+      confirmProps: { color: 'orange' },
+      onConfirm: async () => {
+        setLoading(true)
+        try {
+          await service.restoreConfigVersion(version.id)
+          notifications.show({
+            title: 'Success',
+            message: 'Previous configuration restored',
+            color: 'green',
+          })
+          onSaved?.()
+          onClose()
+        } catch (err) {
+          notifications.show({
+            title: 'Error',
+            message: err instanceof Error ? err.message : 'Failed to restore configuration',
+            color: 'red',
+          })
+        } finally {
+          setLoading(false)
+        }
+      },
+    })
+  }
+
   const handleClose = () => {
     if (hasChanges) {
       modals.openConfirmModal({
@@ -243,9 +285,32 @@ return
 
   const footerContent = (
     <Group justify="space-between" w="100%">
-      <Text size="xs" c="dimmed">
-        {hasChanges ? 'Unsaved changes' : 'No changes'}
-      </Text>
+      <Group gap="sm">
+        <Text size="xs" c="dimmed">
+          {hasChanges ? 'Unsaved changes' : 'No changes'}
+        </Text>
+        {versions.length > 0 && (
+          <Select
+            size="xs"
+            w={220}
+            placeholder="Restore previous version"
+            value={null}
+            disabled={loading}
+            comboboxProps={{ zIndex: 400 }}
+            data={versions.map((version) => ({
+              value: version.id,
+              label: new Date(version.created_at).toLocaleString(),
+            }))}
+            onChange={(id) => {
+              const version = versions.find((v) => v.id === id)
+
+              if (version) {
+                handleRestore(version)
+              }
+            }}
+          />
+        )}
+      </Group>
       <Group gap="xs">
         <Button variant="subtle" onClick={handleClose} disabled={loading}>
           Cancel
@@ -10,6 +10,7 @@ import {
   IconFolder,
   IconPlug,
   IconSettings,
+  IconTool,
   IconTrash,
 } from '@tabler/icons-react'
 
@@ -33,6 +34,7 @@ const TABS: Array<{
   { id: 'metrics', label: 'Metrics', icon: <IconChartBar size={18} /> },
   { id: 'cache', label: 'Cache', icon: <IconDatabase size={18} /> },
   { id: 'storage', label: 'Storage', icon: <IconFolder size={18} /> },
+  { id: 'maintenance', label: 'Maintenance', icon: <IconTool size={18} /> },
 ]
 
 export const SettingsMobileTabs = ({
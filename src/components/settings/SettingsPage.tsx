@@ -4,6 +4,7 @@ import { useIsMobile } from '../../hooks/useIsMobile'
 
 import { CacheSection } from './sections/CacheSection'
 import { GeneralSection } from './sections/GeneralSection'
+import { MaintenanceSection } from './sections/MaintenanceSection'
 import { MetricsSection } from './sections/MetricsSection'
 import { ProvidersSection } from './sections/ProvidersSection'
 import { StorageSection } from './sections/StorageSection'
@@ -35,6 +36,8 @@ export const SettingsPage = ({
         return <CacheSection onRefresh={onRefresh} />
       case 'storage':
         return <StorageSection />
+      case 'maintenance':
+        return <MaintenanceSection />
       default:
         return null
     }
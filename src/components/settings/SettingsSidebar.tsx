@@ -5,6 +5,7 @@ import {
   IconFolder,
   IconPlug,
   IconSettings,
+  IconTool,
 } from '@tabler/icons-react'
 
 export type SettingsSection =
@@ -13,6 +14,7 @@ export type SettingsSection =
   | 'metrics'
   | 'cache'
   | 'storage'
+  | 'maintenance'
 
 interface SettingsSidebarProps {
   activeSection: SettingsSection;
@@ -29,6 +31,7 @@ const SECTIONS: Array<{
   { id: 'metrics', label: 'Metrics', icon: <IconChartBar size={16} /> },
   { id: 'cache', label: 'Cache', icon: <IconDatabase size={16} /> },
   { id: 'storage', label: 'Storage', icon: <IconFolder size={16} /> },
+  { id: 'maintenance', label: 'Maintenance', icon: <IconTool size={16} /> },
 ]
 
 export const SettingsSidebar = ({
@@ -8,6 +8,8 @@ import { IconAlertTriangle, IconTrash } from '@tabler/icons-react'
 import { useFactoryReset } from '@/queries/usePlatformQueries'
 import { getPlatformOverride, platform, setPlatformOverride } from '@/utils/platform'
 
+import { AppLogsCard } from '../AppLogsCard'
+
 interface GeneralSectionProps {
   onRefresh?: () => Promise<void>;
 }
@@ -115,6 +117,8 @@ export function GeneralSection({ onRefresh }: GeneralSectionProps) {
         </Stack>
       </Card>
 
+      <AppLogsCard />
+
       <Card withBorder padding="md" radius="md">
         <Stack gap="md">
           <Box>
@@ -0,0 +1,232 @@
+import { useMemo, useState } from 'react'
+
+import {
+  ActionIcon,
+  Badge,
+  Box,
+  Button,
+  Card,
+  Chip,
+  Group,
+  MultiSelect,
+  SegmentedControl,
+  Stack,
+  Text,
+  TextInput,
+} from '@mantine/core'
+import { modals } from '@mantine/modals'
+import { IconTrash } from '@tabler/icons-react'
+
+import {
+  useDeleteMaintenanceWindow,
+  useMaintenanceWindows,
+  useSaveMaintenanceWindow,
+} from '../../../queries/useMaintenanceQueries'
+import { usePipelines } from '../../../queries/usePipelinesQueries'
+import { useProviders } from '../../../queries/useProvidersQueries'
+import type { MaintenanceSchedule, MaintenanceWindow } from '../../../types'
+
+const DAYS = ['Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat', 'Sun']
+
+const describeSchedule = (schedule: MaintenanceSchedule) => {
+  if (schedule.kind === 'once') {
+    return `${new Date(schedule.start).toLocaleString()} – ${new Date(schedule.end).toLocaleString()}`
+  }
+
+  const days = schedule.days.length > 0 ? schedule.days.join(', ') : 'Every day'
+
+  return `${days}, ${schedule.start.slice(0, 5)} – ${schedule.end.slice(0, 5)}`
+}
+
+export const MaintenanceSection = () => {
+  const { data: windows = [], isLoading } = useMaintenanceWindows()
+  const { data: providers = [] } = useProviders()
+  const { data: pipelines = [] } = usePipelines(undefined, { providers })
+  const saveMutation = useSaveMaintenanceWindow()
+  const deleteMutation = useDeleteMaintenanceWindow()
+
+  const [name, setName] = useState('')
+  const [pipelineIds, setPipelineIds] = useState<string[]>([])
+  const [kind, setKind] = useState<'once' | 'weekly'>('weekly')
+  const [start, setStart] = useState('')
+  const [end, setEnd] = useState('')
+  const [days, setDays] = useState<string[]>([])
+  const [reason, setReason] = useState('')
+
+  const pipelineNames = useMemo(
+    () => new Map(pipelines.map((p) => [p.id, p.name])),
+    [pipelines]
+  )
+
+  const resetForm = () => {
+    setName('')
+    setPipelineIds([])
+    setStart('')
+    setEnd('')
+    setDays([])
+    setReason('')
+  }
+
+  // Weekly windows are entered in local time, so the browser's offset is
+  // stored with them.
+  const handleSave = async () => {
+    const schedule: MaintenanceSchedule =
+      kind === 'once'
+        ? { kind, start: new Date(start).toISOString(), end: new Date(end).toISOString() }
+        : {
+            kind,
+            days,
+            start: `${start}:00`,
+            end: `${end}:00`,
+            utc_offset_minutes: -new Date().getTimezoneOffset(),
+          }
+
+    await saveMutation.mutateAsync({
+      name: name.trim(),
+      pipeline_ids: pipelineIds,
+      schedule,
+      reason: reason.trim() || undefined,
+    })
+    resetForm()
+  }
+
+  const handleDelete = (window: MaintenanceWindow) => {
+    modals.openConfirmModal({
+      title: 'Delete Maintenance Window',
+      children: <Text size="sm">Delete the window "{window.name}"?</Text>,
+      labels: { confirm: 'Delete', cancel: 'Cancel' },
+      confirmProps: { color: 'red' },
+      onConfirm: () => deleteMutation.mutate(window.id),
+    })
+  }
+
+  const describeScope = (window: MaintenanceWindow) => {
+    const ids = window.pipeline_ids ?? []
+    const groups = window.group_ids?.length ?? 0
+
+    if (ids.length === 0 && groups === 0) {
+      return 'All pipelines'
+    }
+
+    const parts = ids.map((id) => pipelineNames.get(id) ?? id)
+
+    if (groups > 0) {
+      parts.push(`${groups} group${groups === 1 ? '' : 's'}`)
+    }
+
+    return parts.join(', ')
+  }
+
+  const canSave = name.trim() !== '' && start !== '' && end !== '' && !saveMutation.isPending
+
+  return (
+    <Box>
+      <Text size="lg" fw={600} mb="xs">Maintenance Windows</Text>
+      <Text size="sm" c="dimmed" mb="lg">
+        While a window is active, dependency triggers into its pipelines are skipped and their
+        status changes don't send notifications.
+      </Text>
+
+      <Stack gap="md">
+        {!isLoading && windows.length === 0 && (
+          <Text size="sm" c="dimmed">No maintenance windows yet.</Text>
+        )}
+
+        {windows.map((window) => (
+          <Card key={window.id} withBorder padding="md" radius="md">
+            <Group justify="space-between" wrap="nowrap">
+              <Stack gap={2}>
+                <Group gap="xs">
+                  <Text size="sm" fw={600}>{window.name}</Text>
+                  <Badge size="xs" variant="light">
+                    {window.schedule.kind === 'once' ? 'One-off' : 'Weekly'}
+                  </Badge>
+                </Group>
+                <Text size="xs" c="dimmed">{describeSchedule(window.schedule)}</Text>
+                <Text size="xs" c="dimmed">{describeScope(window)}</Text>
+                {window.reason && <Text size="xs">{window.reason}</Text>}
+              </Stack>
+              <ActionIcon
+                variant="subtle"
+                color="red"
+                onClick={() => handleDelete(window)}
+                disabled={deleteMutation.isPending}
+                aria-label="Delete maintenance window"
+              >
+                <IconTrash size={16} />
+              </ActionIcon>
+            </Group>
+          </Card>
+        ))}
+
+        <Card withBorder padding="md" radius="md">
+          <Stack gap="md">
+            <Text size="sm" fw={600}>Add window</Text>
+            <TextInput
+              label="Name"
+              description="Saving with an existing name replaces that window"
+              value={name}
+              onChange={(e) => setName(e.currentTarget.value)}
+            />
+            <MultiSelect
+              label="Pipelines"
+              placeholder={pipelineIds.length === 0 ? 'All pipelines' : undefined}
+              data={pipelines.map((p) => ({ value: p.id, label: p.name }))}
+              value={pipelineIds}
+              onChange={setPipelineIds}
+              searchable
+              clearable
+            />
+            <SegmentedControl
+              value={kind}
+              onChange={(value) => {
+                setKind(value as 'once' | 'weekly')
+                setStart('')
+                setEnd('')
+              }}
+              data={[
+                { value: 'weekly', label: 'Weekly' },
+                { value: 'once', label: 'One-off' },
+              ]}
+            />
+            {kind === 'weekly' && (
+              <Chip.Group multiple value={days} onChange={setDays}>
+                <Group gap={4}>
+                  {DAYS.map((day) => (
+                    <Chip key={day} value={day} size="xs">
+                      {day}
+                    </Chip>
+                  ))}
+                </Group>
+              </Chip.Group>
+            )}
+            <Group grow>
+              <TextInput
+                label="Start"
+                type={kind === 'once' ? 'datetime-local' : 'time'}
+                value={start}
+                onChange={(e) => setStart(e.currentTarget.value)}
+              />
+              <TextInput
+                label="End"
+                type={kind === 'once' ? 'datetime-local' : 'time'}
+                value={end}
+                onChange={(e) => setEnd(e.currentTarget.value)}
+              />
+            </Group>
+            <TextInput
+              label="Reason"
+              value={reason}
+              onChange={(e) => setReason(e.currentTarget.value)}
+            />
+            <Group justify="flex-end">
+              <Button onClick={handleSave} disabled={!canSave} loading={saveMutation.isPending}>
+                Save Window
+              </Button>
+            </Group>
+          </Stack>
+        </Card>
+      </Stack>
+    </Box>
+  )
+}
@@ -9,6 +9,7 @@ import {
   Group,
   Loader,
   NumberInput,
+  Progress,
   SimpleGrid,
   Stack,
   Text,
@@ -16,13 +17,24 @@ import {
 } from '@mantine/core'
 import { modals } from '@mantine/modals'
 import { notifications } from '@mantine/notifications'
-import { IconEdit, IconSettings, IconTrash } from '@tabler/icons-react'
+import {
+  IconEdit,
+  IconHistory,
+  IconPlayerPause,
+  IconPlayerPlay,
+  IconSettings,
+  IconTrash,
+} from '@tabler/icons-react'
 
 import { usePlugins } from '../../../contexts/PluginContext'
 import { useProviderDetails } from '../../../queries/useProviderDetailsQuery'
 import {
+  useCancelRunBackfill,
   useProviders,
   useRemoveProvider,
+  useRunBackfills,
+  useSetProviderEnabled,
+  useStartRunBackfill,
   useUpdateProvider,
   useUpdateProviderRefreshInterval,
 } from '../../../queries/useProvidersQueries'
@@ -41,6 +53,10 @@ export const ProvidersSection = () => {
   const { mutateAsync: updateProviderMutation } = useUpdateProvider()
   const { mutateAsync: removeProviderMutation } = useRemoveProvider()
   const updateRefreshIntervalMutation = useUpdateProviderRefreshInterval()
+  const setProviderEnabledMutation = useSetProviderEnabled()
+  const { data: backfills = [] } = useRunBackfills()
+  const startBackfillMutation = useStartRunBackfill()
+  const cancelBackfillMutation = useCancelRunBackfill()
 
   const error = providersError ? (providersError as Error).message : null
 
@@ -178,9 +194,17 @@ return
             {providers.map((provider) => {
               const isEditing = editingId === provider.id
               const currentValue = refreshValues[provider.id] ?? provider.refresh_interval
+              const backfill = backfills.find((job) => job.provider_id === provider.id)
+              const backfilling = backfill?.status === 'running'
 
               return (
-                <Card key={provider.id} withBorder padding="md" radius="md">
+                <Card
+                  key={provider.id}
+                  withBorder
+                  padding="md"
+                  radius="md"
+                  style={provider.enabled ? undefined : { opacity: 0.6 }}
+                >
                   <Stack gap="md">
                     <SimpleGrid cols={{ base: 1, sm: 2 }} spacing="md">
                       <Stack gap={4}>
@@ -189,7 +213,12 @@ return
                         </Text>
                         <Group gap="xs">
                           <Text size="sm">{provider.name}</Text>
-                          {provider.last_fetch_status === 'error' && (
+                          {!provider.enabled && (
+                            <Text size="xs" c="dimmed">
+                              Disabled
+                            </Text>
+                          )}
+                          {provider.enabled && provider.last_fetch_status === 'error' && (
                             <Tooltip
                               label={provider.last_fetch_error || 'Failed to fetch'}
                               multiline
@@ -208,7 +237,31 @@ return
                         <Text size="xs" c="dimmed">
                           Type
                         </Text>
-                        <Text size="sm">{getPluginDisplayName(provider.provider_type)}</Text>
+                        <Group gap="xs">
+                          <Text size="sm">{getPluginDisplayName(provider.provider_type)}</Text>
+                          {provider.server_version && (
+                            <Tooltip
+                              label={provider.server_version.warning}
+                              disabled={!provider.server_version.warning}
+                              multiline
+                              w={300}
+                              withArrow
+                            >
+                              <Text
+                                size="xs"
+                                c={
+                                  !provider.server_version.supported
+                                    ? 'orange'
+                                    : provider.server_version.warning
+                                      ? 'yellow'
+                                      : 'dimmed'
+                                }
+                              >
+                                {provider.server_version.version}
+                              </Text>
+                            </Tooltip>
+                          )}
+                        </Group>
                       </Stack>
 
                       <Stack gap={4}>
@@ -260,6 +313,27 @@ return
                       </Stack>
                     </SimpleGrid>
 
+                    {backfill && (backfilling || backfill.status === 'failed') && (
+                      <Stack gap={4}>
+                        <Text size="xs" c={backfill.status === 'failed' ? 'red' : 'dimmed'}>
+                          {backfilling
+                            ? `Backfilling ${backfill.runs_fetched.toLocaleString()}/${backfill.runs_target.toLocaleString()} runs`
+                            : `Run history import failed: ${backfill.error || 'Unknown error'}`}
+                        </Text>
+                        {backfilling && (
+                          <Progress
+                            size="sm"
+                            value={
+                              backfill.runs_target > 0
+                                ? (backfill.runs_fetched / backfill.runs_target) * 100
+                                : 0
+                            }
+                            animated
+                          />
+                        )}
+                      </Stack>
+                    )}
+
                     <Divider />
 
                     <Group gap="xs" justify="flex-end">
@@ -297,6 +371,54 @@ return
                           >
                             Edit
                           </Button>
+                          <Button
+                            size="compact-xs"
+                            variant="subtle"
+                            color="gray"
+                            onClick={() =>
+                              setProviderEnabledMutation.mutate({
+                                id: provider.id,
+                                enabled: !provider.enabled,
+                              })
+                            }
+                            loading={
+                              setProviderEnabledMutation.isPending &&
+                              setProviderEnabledMutation.variables?.id === provider.id
+                            }
+                            leftSection={
+                              provider.enabled ? (
+                                <IconPlayerPause size={14} />
+                              ) : (
+                                <IconPlayerPlay size={14} />
+                              )
+                            }
+                          >
+                            {provider.enabled ? 'Disable' : 'Enable'}
+                          </Button>
+                          {provider.enabled && (
+                            <Button
+                              size="compact-xs"
+                              variant="subtle"
+                              color="gray"
+                              onClick={() =>
+                                backfilling
+                                  ? cancelBackfillMutation.mutate(provider.id)
+                                  : startBackfillMutation.mutate({
+                                      id: provider.id,
+                                      depth: backfill?.depth,
+                                    })
+                              }
+                              loading={
+                                (startBackfillMutation.isPending &&
+                                  startBackfillMutation.variables?.id === provider.id) ||
+                                (cancelBackfillMutation.isPending &&
+                                  cancelBackfillMutation.variables === provider.id)
+                              }
+                              leftSection={<IconHistory size={14} />}
+                            >
+                              {backfilling ? 'Stop Import' : 'Import History'}
+                            </Button>
+                          )}
                           <Button
                             size="compact-xs"
                             variant="subtle"
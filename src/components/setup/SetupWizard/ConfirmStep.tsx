@@ -56,6 +56,15 @@ export function ConfirmStep({
               </Text>
             </Group>
 
+            <Group justify="space-between" wrap="nowrap" gap="xs">
+              <Text size="sm" c="dimmed" style={{ minWidth: LABEL_WIDTH, flexShrink: 0 }}>
+                Email
+              </Text>
+              <Text size="sm" fw={500}>
+                {state.smtp ? `${state.smtp.host}:${state.smtp.port}` : 'Disabled'}
+              </Text>
+            </Group>
+
             {state.backend === 'postgres' && (
               <Group justify="space-between" wrap="nowrap" gap="xs">
                 <Text size="sm" c="dimmed" style={{ minWidth: LABEL_WIDTH, flexShrink: 0 }}>
@@ -6,18 +6,28 @@ import {
   Code,
   Group,
   Loader,
+  NumberInput,
   PasswordInput,
   SegmentedControl,
   Stack,
+  Switch,
   Text,
   TextInput,
 } from '@mantine/core'
 import { IconAlertCircle, IconAlertTriangle, IconInfoCircle, IconKey } from '@tabler/icons-react'
 
 import { isTauri } from '../../../services'
+import type { SmtpConfig, SmtpSecurity } from '../../../types'
 
 import type { StorageBackend, StorageStepProps } from './types'
 
+const DEFAULT_SMTP: SmtpConfig = {
+  host: '',
+  port: 587,
+  security: 'starttls',
+  from: '',
+}
+
 function analyzePasswordStrength(password: string): string[] {
   const warnings: string[] = []
 
@@ -55,6 +65,12 @@ export function StorageStep({
 
   const isFromKeyring = isTauri() && currentBackend === 'sqlite' && !vaultPasswordFromEnv
 
+  const updateSmtp = (changes: Partial<SmtpConfig>) => {
+    if (state.smtp) {
+      setState({ ...state, smtp: { ...state.smtp, ...changes } })
+    }
+  }
+
   const passwordWarnings = useMemo(() => {
     if (state.vaultPassword.length < 12) {
 return []
@@ -159,6 +175,73 @@ return analyzePasswordStrength(state.vaultPassword)
         </Card>
       )}
 
+      <Card p="sm" withBorder>
+        <Stack gap="sm">
+          <Group justify="space-between">
+            <Text size="sm" fw={600}>Email Notifications</Text>
+            <Switch
+              checked={state.smtp !== null}
+              onChange={(e) => setState({ ...state, smtp: e.currentTarget.checked ? DEFAULT_SMTP : null })}
+              size="sm"
+            />
+          </Group>
+          <Text size="xs" c="dimmed">
+            Optional SMTP server for email notification channels. Use <Code>{'${SMTP_PASSWORD}'}</Code> to keep the password out of the config file.
+          </Text>
+
+          {state.smtp && (
+            <>
+              <Group grow align="flex-start">
+                <TextInput
+                  label="SMTP Host"
+                  placeholder="smtp.example.com"
+                  value={state.smtp.host}
+                  onChange={(e) => updateSmtp({ host: e.target.value })}
+                  required
+                />
+                <NumberInput
+                  label="Port"
+                  value={state.smtp.port}
+                  onChange={(value) => updateSmtp({ port: Number(value) || 587 })}
+                  min={1}
+                  max={65535}
+                  maw={110}
+                />
+              </Group>
+              <SegmentedControl
+                value={state.smtp.security}
+                onChange={(value) => updateSmtp({ security: value as SmtpSecurity })}
+                data={[
+                  { label: 'STARTTLS', value: 'starttls' },
+                  { label: 'TLS', value: 'tls' },
+                  { label: 'None', value: 'none' },
+                ]}
+                fullWidth
+              />
+              <TextInput
+                label="Sender Address"
+                placeholder="pipedash@example.com"
+                value={state.smtp.from}
+                onChange={(e) => updateSmtp({ from: e.target.value })}
+                required
+              />
+              <Group grow align="flex-start">
+                <TextInput
+                  label="Username"
+                  value={state.smtp.username ?? ''}
+                  onChange={(e) => updateSmtp({ username: e.target.value || undefined })}
+                />
+                <PasswordInput
+                  label="Password"
+                  value={state.smtp.password ?? ''}
+                  onChange={(e) => updateSmtp({ password: e.target.value || undefined })}
+                />
+              </Group>
+            </>
+          )}
+        </Stack>
+      </Card>
+
       {vaultPasswordFromEnv && (
         <Alert icon={<IconInfoCircle size={16} />} color="blue" variant="light" p="sm">
           <Text size="sm">
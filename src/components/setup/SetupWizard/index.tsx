@@ -62,6 +62,7 @@ export const SetupWizard = ({ opened, onComplete, onClose }: SetupWizardProps) =
     vaultPassword: '',
     vaultPasswordConfirm: '',
     transferData: true,
+    smtp: null,
   })
 
   const { isMobile } = useIsMobile()
@@ -150,6 +151,7 @@ return false
         vaultPassword: '',
         vaultPasswordConfirm: '',
         transferData: true,
+        smtp: null,
       })
       setError(null)
       setTransferResult(null)
@@ -171,6 +173,7 @@ return false
         backend: currentBackend as StorageBackend,
         dataDir: effectiveDataDir,
         postgresUrl: currentConfig.config.storage.postgres?.connection_string || '',
+        smtp: currentConfig.config.notifications?.smtp ?? null,
       }))
     } else {
       setState((prev) => ({
@@ -189,10 +192,15 @@ return false
       return false
     }
 
+    if (state.smtp && (!state.smtp.host.trim() || !state.smtp.from.includes('@'))) {
+      return false
+    }
+
     return true
   }, [
     state.backend,
     state.postgresUrl,
+    state.smtp,
     vaultPasswordFromEnv,
     passwordValid,
     needsVaultPassword,
@@ -227,6 +235,11 @@ return false
             },
           }),
         },
+        ...(state.smtp && {
+          notifications: {
+            smtp: state.smtp,
+          },
+        }),
       }
 
       if (!currentConfig) {
@@ -322,6 +335,7 @@ return false
         vaultPassword: '',
         vaultPasswordConfirm: '',
         transferData: true,
+        smtp: null,
       })
     }, 100)
   }
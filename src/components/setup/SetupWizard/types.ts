@@ -1,4 +1,4 @@
-import type { StorageConfigResponse } from '../../../types'
+import type { SmtpConfig, StorageConfigResponse } from '../../../types'
 
 export type StorageBackend = 'sqlite' | 'postgres'
 
@@ -9,6 +9,7 @@ export interface SetupState {
   vaultPassword: string;
   vaultPasswordConfirm: string;
   transferData: boolean;
+  smtp: SmtpConfig | null;
 }
 
 export interface SetupWizardProps {
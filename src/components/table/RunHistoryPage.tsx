@@ -4,12 +4,13 @@ import { DataTableSortStatus } from 'mantine-datatable'
 import { ActionIcon, Box, Button, Card, Center, Group, Loader, Skeleton, Stack, Tabs, Text } from '@mantine/core'
 import { useDisclosure, useIntersection } from '@mantine/hooks'
 import { modals } from '@mantine/modals'
-import { IconAdjustments, IconCalendar, IconChartLine, IconClock, IconFileText, IconGitBranch, IconHistory, IconRefresh, IconSquare, IconUser } from '@tabler/icons-react'
+import { IconAdjustments, IconCalendar, IconChartLine, IconClock, IconFileText, IconGitBranch, IconHistory, IconRefresh, IconShieldCheck, IconSquare, IconUser } from '@tabler/icons-react'
 
 import { PAGE_SIZES } from '../../constants/pagination'
 import { useIsMobile } from '../../hooks/useIsMobile'
 import { useTableColumns } from '../../hooks/useTableColumns'
 import { useRunHistoryFilters } from '../../hooks/useUrlState'
+import { useApprovalRules, useSetPipelineApproval } from '../../queries/useApprovalQueries'
 import {
   useClearRunHistoryCache,
   useRunHistory,
@@ -217,7 +218,7 @@ return
     toggleable: false,
     draggable: false,
     render: (run: PipelineRun) => {
-      const isRunning = run.status === 'running' || run.status === 'pending'
+      const isRunning = run.status === 'running' || run.status === 'pending' || run.status === 'queued' || run.status === 'waiting'
       const isCancelling = cancellingRunNumber === run.run_number
       const isRerunning = rerunLoading?.pipelineId === pipeline?.id && rerunLoading?.runNumber === run.run_number
 
@@ -294,6 +295,10 @@ return
 
   const additionalColumns = useMemo(() => [actionsColumn], [actionsColumn])
 
+  const { data: approvalRules = [] } = useApprovalRules()
+  const setApprovalMutation = useSetPipelineApproval()
+  const requiresApproval = !!pipeline && approvalRules.includes(pipeline.id)
+
   const headerActions = useMemo(() => {
     const shouldShow = !isMobile && activeTab === 'history'
     const isLoading = isLoadingPipeline || !pipeline
@@ -307,12 +312,27 @@ return
     }
 
     return (
-      <Box
+      <Group
+        gap="xs"
+        wrap="nowrap"
         style={{
           visibility: shouldShow ? 'visible' : 'hidden',
           minWidth: 'fit-content',
         }}
       >
+        <Button
+          variant={requiresApproval ? 'filled' : 'light'}
+          size="xs"
+          leftSection={<IconShieldCheck size={14} />}
+          loading={setApprovalMutation.isPending}
+          onClick={() =>
+            pipeline && setApprovalMutation.mutate({ pipelineId: pipeline.id, required: !requiresApproval })
+          }
+          disabled={!shouldShow}
+          tabIndex={shouldShow ? 0 : -1}
+        >
+          {requiresApproval ? 'Approval Required' : 'Require Approval'}
+        </Button>
         <Button
           variant="light"
           size="xs"
@@ -323,9 +343,9 @@ return
         >
           Customize Columns
         </Button>
-      </Box>
+      </Group>
     )
-  }, [isMobile, activeTab, openCustomizeModal, isLoadingPipeline, pipeline])
+  }, [isMobile, activeTab, openCustomizeModal, isLoadingPipeline, pipeline, requiresApproval, setApprovalMutation])
 
   const { columns, allColumns } = useTableColumns(
     pipeline?.provider_id,
@@ -598,7 +618,7 @@ result = result.filter(run => run.actor === actorFilter)
     return (
       <Stack gap="md">
         {mobileRuns.map((run) => {
-          const isRunning = run.status === 'running' || run.status === 'pending'
+          const isRunning = run.status === 'running' || run.status === 'pending' || run.status === 'queued' || run.status === 'waiting'
           const isCancelling = cancellingRunNumber === run.run_number
 
           return (
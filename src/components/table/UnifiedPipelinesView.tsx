@@ -9,7 +9,7 @@ import { IconAlertCircle, IconAlertTriangle, IconChartLine, IconChevronRight, Ic
 import { useIsMobile } from '../../hooks/useIsMobile'
 import { usePipelineFilters } from '../../hooks/useUrlState'
 import { logger } from '../../lib/logger'
-import type { Pipeline, ProviderSummary } from '../../types'
+import type { ActiveMaintenance, Pipeline, ProviderSummary } from '../../types'
 import { THEME_COLORS, THEME_TYPOGRAPHY } from '../../utils/dynamicRenderers'
 import { TableCells } from '../../utils/tableCells'
 import { TableHeader } from '../atoms'
@@ -653,12 +653,16 @@ return null
                 const lastActivity = row.pipeline.last_run
                   ? new Date(row.pipeline.last_run).toLocaleString()
                   : 'Never'
+                const maintenance = row.pipeline.metadata?.maintenance as ActiveMaintenance | undefined
+                const label = maintenance
+                  ? `In maintenance (${maintenance.name}) until ${new Date(maintenance.until).toLocaleString()}`
+                  : `Last activity: ${lastActivity}`
 
 
 
 return (
                   <Box style={{ display: 'flex', justifyContent: 'center' }}>
-                    <Tooltip label={`Last activity: ${lastActivity}`} withArrow>
+                    <Tooltip label={label} withArrow>
                       <div>{TableCells.status(row.pipeline.status)}</div>
                     </Tooltip>
                   </Box>
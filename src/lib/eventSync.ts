@@ -1,5 +1,6 @@
 import { useCallback, useEffect, useRef } from 'react'
 
+import { notifications } from '@mantine/notifications'
 import { useQueryClient } from '@tanstack/react-query'
 
 import { events, wsClient } from '../services'
@@ -247,6 +248,68 @@ return
           }
         )
 
+        const unlistenConfigChanged = await events.listen<
+          EventPayloadMap['config-changed']
+        >('config-changed', (payload) => {
+          if (!payload) {
+            return
+          }
+          logger.info('EventSync', 'config-changed', { summary: payload.summary })
+
+          if (payload.kind === 'rejected') {
+            notifications.show({
+              title: 'Config change rejected',
+              message: `config.toml was not applied: ${payload.errors.join('; ')}`,
+              color: 'red',
+              autoClose: false,
+            })
+
+            return
+          }
+
+          if (payload.kind !== 'reloaded') {
+            return
+          }
+
+          if (payload.changed_keys.some((key) => key.startsWith('providers.'))) {
+            queryClient.invalidateQueries({ queryKey: queryKeys.providers.all })
+            scheduleBatchedInvalidation()
+          } else if (
+            payload.changed_keys.some((key) => /^(groups|schedules|provisioning)\./.test(key))
+          ) {
+            scheduleBatchedInvalidation()
+          }
+          if (payload.restart_required.length > 0) {
+            notifications.show({
+              title: 'Restart required',
+              message: `Changes to ${payload.restart_required.join(', ')} take effect after a restart`,
+              color: 'yellow',
+            })
+          }
+        })
+
+        const unlistenPipelinesDiscovered = await events.listen<
+          EventPayloadMap['pipelines-discovered']
+        >('pipelines-discovered', (payload) => {
+          if (!payload?.repositories?.length) {
+            return
+          }
+          logger.info('EventSync', 'pipelines-discovered', {
+            providerId: payload.provider_id,
+            count: payload.repositories.length,
+          })
+          queryClient.invalidateQueries({ queryKey: queryKeys.providers.all })
+          scheduleBatchedInvalidation(payload.provider_id)
+
+          const [first, ...rest] = payload.repositories
+
+          notifications.show({
+            title: 'New repositories discovered',
+            message: rest.length > 0 ? `${first} and ${rest.length} more` : first,
+            color: 'blue',
+          })
+        })
+
         cleanupFns.push(
           unlistenProviderAdded,
           unlistenProviderUpdated,
@@ -256,7 +319,9 @@ return
           unlistenStorageUpdated,
           unlistenCacheCleared,
           unlistenSchemaUpdated,
-          unlistenVaultUnlocked
+          unlistenVaultUnlocked,
+          unlistenConfigChanged,
+          unlistenPipelinesDiscovered
         )
 
         if (mounted) {
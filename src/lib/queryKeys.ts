@@ -8,6 +8,7 @@ export const queryKeys = {
     schema: (id: number) => [...queryKeys.providers.all, 'schema', id] as const,
     refreshInterval: (id: number) =>
       [...queryKeys.providers.all, 'refreshInterval', id] as const,
+    backfills: () => [...queryKeys.providers.all, 'backfills'] as const,
   },
 
   pipelines: {
@@ -25,6 +26,8 @@ export const queryKeys = {
       [...queryKeys.runs.all, 'detail', pipelineId, runNumber] as const,
     active: (pipelineId: string) =>
       [...queryKeys.runs.all, 'active', pipelineId] as const,
+    approvals: (pipelineId: string, runNumber: number) =>
+      [...queryKeys.runs.all, 'approvals', pipelineId, runNumber] as const,
   },
 
   metrics: {
@@ -75,6 +78,23 @@ export const queryKeys = {
       [...queryKeys.workflows.all, 'parameters', providerId, pipelineId] as const,
   },
 
+  notifications: {
+    all: ['notifications'] as const,
+    list: () => [...queryKeys.notifications.all, 'list'] as const,
+    unreadCount: () => [...queryKeys.notifications.all, 'unreadCount'] as const,
+  },
+
+  approvals: {
+    all: ['approvals'] as const,
+    list: (pendingOnly: boolean) => [...queryKeys.approvals.all, 'list', pendingOnly] as const,
+    rules: () => [...queryKeys.approvals.all, 'rules'] as const,
+  },
+
+  maintenance: {
+    all: ['maintenance'] as const,
+    list: () => [...queryKeys.maintenance.all, 'list'] as const,
+  },
+
   vault: {
     all: ['vault'] as const,
     passwordStatus: () => [...queryKeys.vault.all, 'passwordStatus'] as const,
@@ -90,5 +110,7 @@ export const queryKeys = {
   platform: {
     all: ['platform'] as const,
     current: () => [...queryKeys.platform.all, 'current'] as const,
+    logs: (level?: string) => [...queryKeys.platform.all, 'logs', level ?? 'all'] as const,
+    logLevel: () => [...queryKeys.platform.all, 'logLevel'] as const,
   },
 } as const
@@ -0,0 +1,109 @@
+import { useEffect } from 'react'
+
+import { notifications } from '@mantine/notifications'
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
+
+import { GC_TIMES, STALE_TIMES } from '../lib/cacheConfig'
+import { queryKeys } from '../lib/queryKeys'
+import { events, service } from '../services'
+import type { TriggerApproval } from '../types'
+
+export function usePendingApprovals() {
+  return useQuery<TriggerApproval[]>({
+    queryKey: queryKeys.approvals.list(true),
+    queryFn: () => service.listTriggerApprovals(true),
+    staleTime: STALE_TIMES.MODERATE,
+    gcTime: GC_TIMES.MEDIUM,
+  })
+}
+
+export function useApprovalRules() {
+  return useQuery<string[]>({
+    queryKey: queryKeys.approvals.rules(),
+    queryFn: () => service.listApprovalRules(),
+    staleTime: STALE_TIMES.SLOW_CHANGING,
+    gcTime: GC_TIMES.MEDIUM,
+  })
+}
+
+export function useDecideTrigger() {
+  const queryClient = useQueryClient()
+
+  return useMutation<
+    TriggerApproval,
+    Error,
+    { id: number; approve: boolean; confirmed?: boolean; comment?: string }
+  >({
+    mutationFn: ({ id, approve, confirmed, comment }) =>
+      approve
+        ? service.approveTrigger(id, confirmed ?? false, comment)
+        : service.rejectTrigger(id, comment),
+    onSuccess: (approval) => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.approvals.all })
+      queryClient.invalidateQueries({ queryKey: queryKeys.runs.list(approval.pipeline_id) })
+
+      if (approval.status === 'failed') {
+        notifications.show({
+          title: 'Trigger Failed',
+          message: approval.comment || 'The provider refused the trigger',
+          color: 'red',
+        })
+      }
+    },
+    onError: (error) => {
+      notifications.show({
+        title: 'Failed to Decide Trigger Request',
+        message: error.message || 'Unknown error',
+        color: 'red',
+      })
+    },
+  })
+}
+
+export function useSetPipelineApproval() {
+  const queryClient = useQueryClient()
+
+  return useMutation<void, Error, { pipelineId: string; required: boolean }>({
+    mutationFn: ({ pipelineId, required }) => service.setPipelineApproval(pipelineId, required),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.approvals.rules() })
+    },
+    onError: (error) => {
+      notifications.show({
+        title: 'Failed to Update Approval Rule',
+        message: error.message || 'Unknown error',
+        color: 'red',
+      })
+    },
+  })
+}
+
+// Requests and decisions can come from another window or client.
+export function useApprovalEvents() {
+  const queryClient = useQueryClient()
+
+  useEffect(() => {
+    let isActive = true
+    let unlisten: (() => void) | null = null
+
+    events
+      .listen<TriggerApproval>('trigger-approvals-changed', () => {
+        queryClient.invalidateQueries({ queryKey: queryKeys.approvals.all })
+      })
+      .then((fn) => {
+        if (isActive) {
+          unlisten = fn
+        } else {
+          fn()
+        }
+      })
+      .catch((error) => {
+        console.error('Failed to setup approval listener:', error)
+      })
+
+    return () => {
+      isActive = false
+      unlisten?.()
+    }
+  }, [queryClient])
+}
@@ -0,0 +1,55 @@
+import { notifications } from '@mantine/notifications'
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
+
+import { GC_TIMES, STALE_TIMES } from '../lib/cacheConfig'
+import { queryKeys } from '../lib/queryKeys'
+import { service } from '../services'
+import type { MaintenanceWindow, MaintenanceWindowInput } from '../types'
+
+export function useMaintenanceWindows() {
+  return useQuery<MaintenanceWindow[]>({
+    queryKey: queryKeys.maintenance.list(),
+    queryFn: () => service.listMaintenanceWindows(),
+    staleTime: STALE_TIMES.SLOW_CHANGING,
+    gcTime: GC_TIMES.MEDIUM,
+  })
+}
+
+// Windows change which pipelines are annotated, so pipelines refetch too.
+export function useSaveMaintenanceWindow() {
+  const queryClient = useQueryClient()
+
+  return useMutation<MaintenanceWindow, Error, MaintenanceWindowInput>({
+    mutationFn: (window) => service.saveMaintenanceWindow(window),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.maintenance.all })
+      queryClient.invalidateQueries({ queryKey: queryKeys.pipelines.all })
+    },
+    onError: (error) => {
+      notifications.show({
+        title: 'Failed to Save Maintenance Window',
+        message: error.message || 'Unknown error',
+        color: 'red',
+      })
+    },
+  })
+}
+
+export function useDeleteMaintenanceWindow() {
+  const queryClient = useQueryClient()
+
+  return useMutation<void, Error, number>({
+    mutationFn: (id) => service.deleteMaintenanceWindow(id),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.maintenance.all })
+      queryClient.invalidateQueries({ queryKey: queryKeys.pipelines.all })
+    },
+    onError: (error) => {
+      notifications.show({
+        title: 'Failed to Delete Maintenance Window',
+        message: error.message || 'Unknown error',
+        color: 'red',
+      })
+    },
+  })
+}
@@ -187,3 +187,29 @@ export function useFlushMetrics() {
     },
   })
 }
+
+export function useRebuildMetrics() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: (pipelineId?: string) => service.rebuildMetrics(pipelineId),
+
+    onSuccess: (result) => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.metrics.all })
+
+      notifications.show({
+        title: 'Metrics Rebuilt',
+        message: `Recomputed ${result.metrics} metric${result.metrics !== 1 ? 's' : ''} from ${result.runs} cached run${result.runs !== 1 ? 's' : ''}`,
+        color: 'blue',
+      })
+    },
+
+    onError: (error: Error) => {
+      notifications.show({
+        title: 'Failed to Rebuild Metrics',
+        message: error.message || 'Unknown error occurred',
+        color: 'red',
+      })
+    },
+  })
+}
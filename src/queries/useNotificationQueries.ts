@@ -0,0 +1,93 @@
+import { useEffect } from 'react'
+
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
+
+import { GC_TIMES, STALE_TIMES } from '../lib/cacheConfig'
+import { queryKeys } from '../lib/queryKeys'
+import { events, service } from '../services'
+import type { AppNotification } from '../types'
+
+const NOTIFICATION_EVENTS = ['notification-created', 'notifications-changed'] as const
+
+export function useNotifications(options?: { enabled?: boolean }) {
+  return useQuery<AppNotification[]>({
+    queryKey: queryKeys.notifications.list(),
+    queryFn: () => service.listNotifications(),
+    staleTime: STALE_TIMES.MODERATE,
+    gcTime: GC_TIMES.MEDIUM,
+    ...options,
+  })
+}
+
+export function useUnreadNotificationCount() {
+  return useQuery<number>({
+    queryKey: queryKeys.notifications.unreadCount(),
+    queryFn: () => service.getUnreadNotificationCount(),
+    staleTime: STALE_TIMES.MODERATE,
+    gcTime: GC_TIMES.MEDIUM,
+  })
+}
+
+export function useMarkNotificationRead() {
+  const queryClient = useQueryClient()
+
+  return useMutation<void, Error, number | undefined>({
+    mutationFn: (id) => service.markNotificationRead(id),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.notifications.all })
+    },
+  })
+}
+
+export function useClearNotifications() {
+  const queryClient = useQueryClient()
+
+  return useMutation<void, Error, void>({
+    mutationFn: () => service.clearNotifications(),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.notifications.all })
+    },
+  })
+}
+
+// Keeps the badge and history current when the backend pushes a change,
+// including changes made from another window or client.
+export function useNotificationEvents(onCreated?: (notification: AppNotification) => void) {
+  const queryClient = useQueryClient()
+
+  useEffect(() => {
+    let isActive = true
+    const unlisteners: Array<() => void> = []
+
+    const setupListeners = async () => {
+      for (const eventName of NOTIFICATION_EVENTS) {
+        const unlisten = await events.listen<{ unread: number; notification?: AppNotification }>(
+          eventName,
+          (payload) => {
+            queryClient.setQueryData(queryKeys.notifications.unreadCount(), payload.unread)
+            queryClient.invalidateQueries({ queryKey: queryKeys.notifications.list() })
+            if (payload.notification && onCreated) {
+              onCreated(payload.notification)
+            }
+          }
+        )
+
+        if (!isActive) {
+          unlisten()
+
+          return
+        }
+        unlisteners.push(unlisten)
+      }
+    }
+
+    setupListeners().catch((error) => {
+      console.error('Failed to setup notification listeners:', error)
+    })
+
+    return () => {
+      isActive = false
+      unlisteners.forEach((unlisten) => unlisten())
+    }
+  }, [queryClient, onCreated])
+}
@@ -1,7 +1,10 @@
 import { notifications } from '@mantine/notifications'
-import { useMutation, useQueryClient } from '@tanstack/react-query'
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
 
+import { GC_TIMES, STALE_TIMES } from '../lib/cacheConfig'
+import { queryKeys } from '../lib/queryKeys'
 import { service } from '../services'
+import type { LogLevel } from '../types'
 
 export function useFactoryReset() {
   const queryClient = useQueryClient()
@@ -28,3 +31,75 @@ export function useFactoryReset() {
     },
   })
 }
+
+export function useAppLogs(level?: LogLevel, enabled = true) {
+  return useQuery({
+    queryKey: queryKeys.platform.logs(level),
+    queryFn: () => service.getAppLogs({ level }),
+    staleTime: STALE_TIMES.FAST_CHANGING,
+    gcTime: GC_TIMES.SHORT,
+    enabled,
+  })
+}
+
+export function useLogLevel() {
+  return useQuery({
+    queryKey: queryKeys.platform.logLevel(),
+    queryFn: () => service.getLogLevel(),
+    staleTime: STALE_TIMES.SLOW_CHANGING,
+    gcTime: GC_TIMES.SHORT,
+  })
+}
+
+export function useSetLogLevel() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: (filter: string) => service.setLogLevel(filter),
+
+    onSuccess: (_, filter) => {
+      queryClient.setQueryData(queryKeys.platform.logLevel(), filter)
+      queryClient.invalidateQueries({ queryKey: [...queryKeys.platform.all, 'logs'] })
+
+      notifications.show({
+        title: 'Log Level Changed',
+        message: `Logging with "${filter}" until the next restart`,
+        color: 'green',
+      })
+    },
+
+    onError: (error: Error) => {
+      notifications.show({
+        title: 'Failed to Change Log Level',
+        message: error.message || 'Invalid log filter',
+        color: 'red',
+      })
+    },
+  })
+}
+
+export function useCreateDiagnosticBundle() {
+  return useMutation({
+    mutationFn: (startupError?: string) => service.createDiagnosticBundle(startupError),
+
+    onSuccess: (savedAs) => {
+      if (!savedAs) {
+        return
+      }
+
+      notifications.show({
+        title: 'Diagnostic Bundle Saved',
+        message: `Saved to ${savedAs}. Secrets are masked, but check it before attaching it to an issue.`,
+        color: 'green',
+      })
+    },
+
+    onError: (error: Error) => {
+      notifications.show({
+        title: 'Diagnostic Bundle Failed',
+        message: error.message || 'Failed to create diagnostic bundle',
+        color: 'red',
+      })
+    },
+  })
+}
@@ -1,9 +1,12 @@
-import { useInfiniteQuery, useQuery } from '@tanstack/react-query'
+import { useCallback, useEffect, useState } from 'react'
+
+import { useQuery } from '@tanstack/react-query'
 
 import { PAGE_SIZES } from '../constants/pagination'
 import { GC_TIMES, STALE_TIMES } from '../lib/cacheConfig'
-import { service } from '../services'
-import type { PaginatedAvailablePipelines, ProviderConfig } from '../types'
+import { events, service } from '../services'
+import type { AvailablePipeline, ProviderConfig } from '../types'
+import type { PreviewFinishedPayload, PreviewPagePayload } from '../types/events'
 
 export function useProviderOrganizations(
   providerType: string,
@@ -19,6 +22,25 @@ export function useProviderOrganizations(
   })
 }
 
+interface PipelinePreviewState {
+  previewId: string | null
+  items: AvailablePipeline[]
+  received: boolean
+  streaming: boolean
+  error: string | null
+}
+
+const IDLE_PREVIEW: PipelinePreviewState = {
+  previewId: null,
+  items: [],
+  received: false,
+  streaming: false,
+  error: null,
+}
+
+// Pages are streamed as events while the backend walks the provider's
+// listing, so large organizations show up progressively. Changing the inputs
+// or unmounting cancels the running preview.
 export function usePipelinePreview(
   providerType: string,
   config: ProviderConfig,
@@ -26,29 +48,94 @@ export function usePipelinePreview(
   search?: string,
   enableQuery = true
 ) {
-  return useInfiniteQuery({
-    queryKey: [
-      'pipelinePreview',
-      providerType,
-      config.config,
-      organization,
-      search,
-    ],
-    queryFn: ({ pageParam = 1 }) =>
-      service.previewProviderPipelines(
-        providerType,
-        config.config || {},
-        organization,
-        search,
-        pageParam,
-        PAGE_SIZES.PIPELINE_PREVIEW
-      ),
-    getNextPageParam: (lastPage: PaginatedAvailablePipelines) =>
-      lastPage.has_more ? (lastPage.page ?? 0) + 1 : undefined,
-    initialPageParam: 1,
-    staleTime: STALE_TIMES.MODERATE,
-    gcTime: GC_TIMES.SHORT,
-    enabled: enableQuery && !!providerType && !!config.token && Object.keys(config.config || {}).length > 0 && !!organization,
-  })
-}
+  const [state, setState] = useState<PipelinePreviewState>(IDLE_PREVIEW)
+  const configKey = JSON.stringify(config.config || {})
+  const enabled = enableQuery && !!providerType && !!config.token && configKey !== '{}' && !!organization
+
+  useEffect(() => {
+    if (!enabled) {
+      setState(IDLE_PREVIEW)
 
+      return
+    }
+
+    const previewId = `${Date.now()}-${Math.random().toString(36).slice(2)}`
+    const unlisteners: Array<() => void> = []
+    let disposed = false
+
+    const update = (fn: (prev: PipelinePreviewState) => PipelinePreviewState) =>
+      setState((prev) => (prev.previewId === previewId ? fn(prev) : prev))
+
+    setState({ ...IDLE_PREVIEW, previewId, streaming: true })
+
+    Promise.all([
+      events.listen<PreviewPagePayload>('preview-page', ({ preview_id, page }) => {
+        if (preview_id !== previewId) {
+          return
+        }
+        update((prev) => {
+          const seen = new Set(prev.items.map((item) => item.id))
+
+          return {
+            ...prev,
+            received: true,
+            items: [...prev.items, ...page.items.filter((item) => !seen.has(item.id))],
+          }
+        })
+      }),
+      events.listen<PreviewFinishedPayload>('preview-finished', ({ preview_id, error }) => {
+        if (preview_id !== previewId) {
+          return
+        }
+        update((prev) => ({ ...prev, received: true, streaming: false, error }))
+      }),
+    ])
+      .then(async (fns) => {
+        if (disposed) {
+          fns.forEach((unlisten) => unlisten())
+
+          return
+        }
+        unlisteners.push(...fns)
+        await service.startPipelinePreview({
+          preview_id: previewId,
+          provider_type: providerType,
+          config: JSON.parse(configKey),
+          org: organization ?? null,
+          search: search ?? null,
+          page_size: PAGE_SIZES.PIPELINE_PREVIEW,
+        })
+        if (disposed) {
+          service.cancelPipelinePreview(previewId).catch(() => {})
+        }
+      })
+      .catch((error) => {
+        update((prev) => ({
+          ...prev,
+          received: true,
+          streaming: false,
+          error: error instanceof Error ? error.message : String(error),
+        }))
+      })
+
+    return () => {
+      disposed = true
+      unlisteners.forEach((unlisten) => unlisten())
+      service.cancelPipelinePreview(previewId).catch(() => {})
+    }
+  }, [enabled, providerType, configKey, organization, search])
+
+  const cancel = useCallback(() => {
+    if (state.previewId && state.streaming) {
+      service.cancelPipelinePreview(state.previewId).catch(() => {})
+    }
+  }, [state.previewId, state.streaming])
+
+  return {
+    items: state.items,
+    isLoading: state.streaming && !state.received,
+    isStreaming: state.streaming,
+    error: state.error,
+    cancel,
+  }
+}
@@ -1,11 +1,13 @@
+import { useEffect } from 'react'
+
 import { notifications } from '@mantine/notifications'
 import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
 
 import { GC_TIMES, STALE_TIMES } from '../lib/cacheConfig'
 import { logger } from '../lib/logger'
 import { queryKeys } from '../lib/queryKeys'
-import { service } from '../services'
-import type { ProviderConfig, ProviderSummary } from '../types'
+import { events, service } from '../services'
+import type { ProviderConfig, ProviderSummary, RunBackfillJob } from '../types'
 
 export function useProviders(options?: { enabled?: boolean }) {
   return useQuery({
@@ -29,9 +31,10 @@ export function useAddProvider() {
   const queryClient = useQueryClient()
 
   return useMutation({
-    mutationFn: (config: ProviderConfig) => service.addProvider(config),
+    mutationFn: ({ config }: { config: ProviderConfig; backfillDepth?: number }) =>
+      service.addProvider(config),
 
-    onSuccess: async (newProviderId, config) => {
+    onSuccess: async (newProviderId, { config, backfillDepth }) => {
       queryClient.setQueryData<ProviderSummary[]>(queryKeys.providers.list(), (old = []) => [
         ...old,
         {
@@ -46,6 +49,8 @@ export function useAddProvider() {
           last_fetch_error: null,
           last_fetch_at: null,
           configured_repositories: [],
+          server_version: null,
+          enabled: true,
         },
       ])
 
@@ -57,9 +62,22 @@ export function useAddProvider() {
 
       queryClient.invalidateQueries({ queryKey: queryKeys.providers.list() })
 
+      if (backfillDepth) {
+        service
+          .startRunBackfill(newProviderId, backfillDepth)
+          .then(() => queryClient.invalidateQueries({ queryKey: queryKeys.providers.backfills() }))
+          .catch((error: Error) => {
+            notifications.show({
+              title: 'Failed to Import Run History',
+              message: error.message || 'Unknown error',
+              color: 'red',
+            })
+          })
+      }
+
       notifications.show({
         title: 'Provider Added',
-        message: 'Fetching pipelines...',
+        message: backfillDepth ? 'Fetching pipelines and importing run history...' : 'Fetching pipelines...',
         color: 'green',
       })
     },
@@ -223,6 +241,118 @@ export function useUpdateProviderRefreshInterval() {
   })
 }
 
+export function useSetProviderEnabled() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: ({ id, enabled }: { id: number; enabled: boolean }) =>
+      enabled ? service.enableProvider(id) : service.disableProvider(id),
+
+    onSuccess: (_data, { enabled }) => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.providers.list() })
+      queryClient.invalidateQueries({ queryKey: queryKeys.pipelines.list({}) })
+
+      notifications.show({
+        title: enabled ? 'Provider Enabled' : 'Provider Disabled',
+        message: enabled
+          ? 'Refreshing pipelines...'
+          : 'Pipelines and history are kept but no longer refreshed',
+        color: enabled ? 'green' : 'blue',
+      })
+    },
+
+    onError: (error: Error, { enabled }) => {
+      notifications.show({
+        title: enabled ? 'Failed to Enable Provider' : 'Failed to Disable Provider',
+        message: error.message || 'Unknown error',
+        color: 'red',
+      })
+    },
+  })
+}
+
+// Progress arrives as events, so the list is patched in place rather than
+// refetched for every pipeline a job finishes.
+export function useRunBackfills() {
+  const queryClient = useQueryClient()
+
+  useEffect(() => {
+    let isActive = true
+    let unlisten: (() => void) | null = null
+
+    events
+      .listen<RunBackfillJob>('run-backfill-progress', (job) => {
+        queryClient.setQueryData<RunBackfillJob[]>(queryKeys.providers.backfills(), (old = []) => [
+          ...old.filter((existing) => existing.provider_id !== job.provider_id),
+          job,
+        ])
+      })
+      .then((fn) => {
+        if (isActive) {
+          unlisten = fn
+        } else {
+          fn()
+        }
+      })
+      .catch((error) => {
+        console.error('Failed to setup backfill listener:', error)
+      })
+
+    return () => {
+      isActive = false
+      unlisten?.()
+    }
+  }, [queryClient])
+
+  return useQuery<RunBackfillJob[]>({
+    queryKey: queryKeys.providers.backfills(),
+    queryFn: () => service.listRunBackfills(),
+    staleTime: STALE_TIMES.MODERATE,
+    gcTime: GC_TIMES.MEDIUM,
+  })
+}
+
+export function useStartRunBackfill() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: ({ id, depth }: { id: number; depth?: number }) =>
+      service.startRunBackfill(id, depth),
+
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.providers.backfills() })
+    },
+
+    onError: (error: Error) => {
+      notifications.show({
+        title: 'Failed to Import Run History',
+        message: error.message || 'Unknown error',
+        color: 'red',
+      })
+    },
+  })
+}
+
+export function useCancelRunBackfill() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: (id: number) => service.cancelRunBackfill(id),
+
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.providers.backfills() })
+    },
+
+    onError: (error: Error) => {
+      notifications.show({
+        title: 'Failed to Cancel Run History Import',
+        message: error.message || 'Unknown error',
+        color: 'red',
+      })
+    },
+  })
+}
+
 export function useProviderPermissions(providerType: string, config: ProviderConfig) {
   return useQuery({
     queryKey: ['checkProviderPermissions', providerType, config.config],
@@ -19,7 +19,7 @@ export function useRunDetails(
     enabled: enabled && !!pipelineId && runNumber > 0,
     refetchInterval: (query) => {
       const data = query.state.data as PipelineRun | undefined
-      const isRunning = data?.status === 'running' || data?.status === 'pending'
+      const isRunning = data?.status === 'running' || data?.status === 'pending' || data?.status === 'queued' || data?.status === 'waiting'
 
 
 
@@ -111,3 +111,53 @@ export function useRerunWorkflow() {
     },
   })
 }
+
+export function usePendingApprovals(
+  pipelineId: string,
+  runNumber: number,
+  enabled: boolean = true
+) {
+  return useQuery({
+    queryKey: queryKeys.runs.approvals(pipelineId, runNumber),
+    queryFn: () => service.listPendingApprovals(pipelineId, runNumber),
+    staleTime: STALE_TIMES.FAST_CHANGING,
+    gcTime: GC_TIMES.SHORT,
+    enabled: enabled && !!pipelineId && runNumber > 0,
+  })
+}
+
+export function useUnblockJob() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: ({
+      pipelineId,
+      runNumber,
+      jobId,
+      fields,
+    }: {
+      pipelineId: string
+      runNumber: number
+      jobId: string
+      fields: Record<string, string>
+    }) => service.unblockJob(pipelineId, runNumber, jobId, fields),
+
+    onSuccess: (_data, { pipelineId, runNumber }) => {
+      queryClient.invalidateQueries({
+        queryKey: queryKeys.runs.approvals(pipelineId, runNumber),
+      })
+      queryClient.invalidateQueries({
+        queryKey: queryKeys.runs.detail(pipelineId, runNumber),
+      })
+      queryClient.invalidateQueries({
+        queryKey: queryKeys.runs.list(pipelineId),
+      })
+
+      displaySuccessNotification(`Run #${runNumber} unblocked`, 'Job Unblocked')
+    },
+
+    onError: (error: Error) => {
+      displayErrorNotification(error, 'Failed to Unblock Job')
+    },
+  })
+}
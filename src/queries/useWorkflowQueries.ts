@@ -16,13 +16,36 @@ export function useWorkflowParameters(providerId: number, pipelineId: string) {
   })
 }
 
+// A trigger on a pipeline that needs approval comes back as a pending
+// approval rather than a run.
+export function pendingApprovalId(result: string): number | null {
+  try {
+    const parsed = JSON.parse(result)
+
+    return typeof parsed?.approval_id === 'number' ? parsed.approval_id : null
+  } catch {
+    return null
+  }
+}
+
 export function useTriggerWorkflow() {
   const queryClient = useQueryClient()
 
   return useMutation({
     mutationFn: (params: TriggerParams) => service.triggerPipeline(params),
 
-    onSuccess: (_data, variables) => {
+    onSuccess: (data, variables) => {
+      if (pendingApprovalId(data) !== null) {
+        queryClient.invalidateQueries({ queryKey: queryKeys.approvals.all })
+        notifications.show({
+          title: 'Approval Requested',
+          message: 'This pipeline needs approval before it runs',
+          color: 'blue',
+        })
+
+        return
+      }
+
       queryClient.invalidateQueries({
         queryKey: queryKeys.runs.list(variables.workflow_id),
       })
@@ -3,6 +3,7 @@ import { useNavigate, useParams, useSearchParams } from 'react-router-dom'
 
 import { FeatureErrorBoundary } from '../components/ErrorBoundary/FeatureErrorBoundary'
 import { RunHistoryPage } from '../components/table/RunHistoryPage'
+import { service } from '../services'
 import type { Pipeline, PipelineComponentProps } from '../types'
 
 interface PipelineDetailRouteProps extends Omit<PipelineComponentProps, 'pipeline'> {
@@ -18,10 +19,12 @@ export function PipelineDetailRoute({
   onCancel,
 }: PipelineDetailRouteProps) {
   const { pipelineId } = useParams()
-  const [searchParams] = useSearchParams()
+  const [searchParams, setSearchParams] = useSearchParams()
   const navigate = useNavigate()
 
   const tab = (searchParams.get('tab') || 'history') as 'history' | 'metrics'
+  // Set by pipeline links that point at a specific run.
+  const runParam = searchParams.get('run')
 
   const actualPipeline = pipelines.find((p) => p.id === pipelineId) || null
 
@@ -46,6 +49,42 @@ export function PipelineDetailRoute({
     }
   }
 
+  useEffect(() => {
+    if (!pipelineId) {
+      return
+    }
+
+    service
+      .setRefreshActivityHint({ kind: 'pipeline_opened', pipeline_id: pipelineId })
+      .catch(() => {})
+
+    return () => {
+      service.setRefreshActivityHint({ kind: 'pipeline_closed' }).catch(() => {})
+    }
+  }, [pipelineId])
+
+  useEffect(() => {
+    if (!runParam || !actualPipeline) {
+      return
+    }
+
+    const runNumber = Number(runParam)
+
+    if (Number.isInteger(runNumber) && onViewRun) {
+      onViewRun(actualPipeline.id, runNumber)
+    }
+    setSearchParams(
+      (prev) => {
+        const next = new URLSearchParams(prev)
+
+        next.delete('run')
+
+        return next
+      },
+      { replace: true }
+    )
+  }, [runParam, actualPipeline, onViewRun, setSearchParams])
+
   useEffect(() => {
     if (!loading && !actualPipeline) {
       navigate('/pipelines', { replace: true })
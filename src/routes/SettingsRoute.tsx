@@ -4,7 +4,7 @@ import { useNavigate, useParams } from 'react-router-dom'
 import { SettingsPage } from '../components/settings/SettingsPage'
 import type { SettingsSection } from '../components/settings/SettingsSidebar'
 
-const VALID_SECTIONS: SettingsSection[] = ['general', 'providers', 'metrics', 'cache', 'storage']
+const VALID_SECTIONS: SettingsSection[] = ['general', 'providers', 'metrics', 'cache', 'storage', 'maintenance']
 
 function isValidSection(section: string | undefined): section is SettingsSection {
   return section !== undefined && VALID_SECTIONS.includes(section as SettingsSection)
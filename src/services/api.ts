@@ -2,15 +2,25 @@
 import { API_TIMEOUTS } from '../constants/timeouts'
 import { getToken, useAuthStore } from '../stores/authStore'
 import type {
+  ActivityHint,
   AggregatedMetrics,
+  AppLogEntry,
+  AppLogQuery,
+  AppNotification,
   AggregationPeriod,
   AggregationType,
+  BranchBoard,
+  CommitStatus,
   ConfigAnalysisResponse,
   ConfigContentResponse,
+  ConfigVersion,
   FeatureAvailability,
   GlobalMetricsConfig,
+  MaintenanceWindow,
+  MaintenanceWindowInput,
   MetricEntry,
   MetricsConfig,
+  MetricsRebuild,
   MetricsStats,
   MetricType,
   MigrationOptions,
@@ -18,18 +28,28 @@ import type {
   MigrationResult,
   Organization,
   PaginatedAvailablePipelines,
+  PaginatedResponse,
   PaginatedRunHistory,
+  PendingApproval,
+  PinnedPipeline,
   PermissionCheckResult,
   PermissionStatus,
   PipedashConfig,
   Pipeline,
+  PipelinePreviewRequest,
   PipelineRun,
   PluginMetadata,
   ProviderConfig,
+  ProviderDiagnosis,
   ProviderSummary,
+  QuickAction,
+  ReleaseReport,
+  RunBackfillJob,
   SetupStatus,
+  StatusSummary,
   StorageConfigResponse,
   StoragePathsResponse,
+  TriggerApproval,
   TriggerParams,
   UnlockVaultResponse,
   ValidationResult,
@@ -215,6 +235,26 @@ return { ...config, id }
     })
   }
 
+  async enableProvider(id: number): Promise<void> {
+    await this.post(`/providers/${id}/enable`)
+  }
+
+  async disableProvider(id: number): Promise<void> {
+    await this.post(`/providers/${id}/disable`)
+  }
+
+  async startRunBackfill(id: number, depth?: number): Promise<RunBackfillJob> {
+    return this.post<RunBackfillJob>(`/providers/${id}/backfill`, { depth })
+  }
+
+  async cancelRunBackfill(id: number): Promise<RunBackfillJob> {
+    return this.delete<RunBackfillJob>(`/providers/${id}/backfill`)
+  }
+
+  async listRunBackfills(): Promise<RunBackfillJob[]> {
+    return this.get<RunBackfillJob[]>('/providers/backfills')
+  }
+
   async removeProvider(id: number): Promise<void> {
     await this.delete(`/providers/${id}`)
   }
@@ -247,6 +287,20 @@ return { ...config, id }
     })
   }
 
+  async startPipelinePreview(request: PipelinePreviewRequest): Promise<string> {
+    const result = await this.post<{ preview_id: string }>('/providers/preview/stream', request)
+
+    return result.preview_id
+  }
+
+  async cancelPipelinePreview(previewId: string): Promise<boolean> {
+    const result = await this.delete<{ cancelled: boolean }>(
+      `/providers/preview/stream/${encodeURIComponent(previewId)}`
+    )
+
+    return result.cancelled
+  }
+
   async getProviderFieldOptions(
     providerType: string,
     fieldKey: string,
@@ -273,6 +327,18 @@ return { ...config, id }
     return this.get<FeatureAvailability[]>(`/providers/${providerId}/features`)
   }
 
+  async discoverProviderRepositories(providerId: number): Promise<string[]> {
+    return this.post<string[]>(`/providers/${providerId}/discover`)
+  }
+
+  async diagnoseProvider(providerId: number): Promise<ProviderDiagnosis> {
+    return this.post<ProviderDiagnosis>(`/providers/${providerId}/diagnose`)
+  }
+
+  async getProviderDiagnoses(): Promise<ProviderDiagnosis[]> {
+    return this.get<ProviderDiagnosis[]>('/providers/diagnostics')
+  }
+
   async getProviderTableSchema(providerId: number): Promise<any> {
     return this.get<any>(`/providers/${providerId}/table-schema`)
   }
@@ -306,11 +372,26 @@ return result || []
   }
 
   async getCachedPipelines(providerId?: number): Promise<Pipeline[]> {
-    const params = providerId ? `?provider_id=${providerId}` : ''
-    const result = await this.get<Pipeline[]>(`/pipelines/cached${params}`)
+    const result: Pipeline[] = []
+    let page = 1
 
+    for (;;) {
+      const params = new URLSearchParams({ page: String(page), page_size: '1000' })
 
-return result || []
+      if (providerId) {
+        params.set('provider_id', String(providerId))
+      }
+      const response = await this.get<PaginatedResponse<Pipeline>>(`/pipelines/cached?${params}`)
+
+      result.push(...(response?.items ?? []))
+
+      if (!response?.has_more) {
+        break
+      }
+      page += 1
+    }
+
+    return result
   }
 
   async fetchFreshPipelines(providerId?: number): Promise<Pipeline[]> {
@@ -421,6 +502,10 @@ return result.run_id
 return result.mode as 'active' | 'idle'
   }
 
+  // The server refreshes for every client at once, so focus hints from a
+  // single browser tab are ignored.
+  async setRefreshActivityHint(_hint: ActivityHint): Promise<void> {}
+
   async getWorkflowRunDetails(
     pipelineId: string,
     runNumber: number
@@ -430,6 +515,80 @@ return result.mode as 'active' | 'idle'
     )
   }
 
+  async fetchBranchStatuses(
+    pipelineId: string,
+    refresh = false
+  ): Promise<BranchBoard> {
+    return this.get<BranchBoard>(
+      `/pipelines/${encodeURIComponent(pipelineId)}/branches?refresh=${refresh}`
+    )
+  }
+
+  async getCommitStatus(sha: string): Promise<CommitStatus> {
+    return this.get<CommitStatus>(`/commits/${encodeURIComponent(sha)}/status`)
+  }
+
+  async generateReleaseReport(
+    pipelineId: string,
+    fromRun: number,
+    toRun: number
+  ): Promise<ReleaseReport> {
+    return this.get<ReleaseReport>(
+      `/pipelines/${encodeURIComponent(pipelineId)}/release-report?from_run=${fromRun}&to_run=${toRun}`
+    )
+  }
+
+  async listPinnedPipelines(): Promise<PinnedPipeline[]> {
+    return this.get<PinnedPipeline[]>('/pipelines/pinned')
+  }
+
+  async pinPipeline(pipelineId: string): Promise<PinnedPipeline> {
+    return this.put<PinnedPipeline>(
+      `/pipelines/${encodeURIComponent(pipelineId)}/pin`
+    )
+  }
+
+  async unpinPipeline(pipelineId: string): Promise<void> {
+    await this.delete(`/pipelines/${encodeURIComponent(pipelineId)}/pin`)
+  }
+
+  async quickActions(query?: string, limit?: number): Promise<QuickAction[]> {
+    const params = new URLSearchParams()
+
+    if (query) {
+      params.set('q', query)
+    }
+    if (limit !== undefined) {
+      params.set('limit', String(limit))
+    }
+
+    return this.get<QuickAction[]>(`/search/quick-actions?${params}`)
+  }
+
+  async getTraySummary(): Promise<StatusSummary> {
+    return this.get<StatusSummary>('/pipelines/pinned/summary')
+  }
+
+  // The tray only exists in the desktop app, so the web summary always
+  // covers the pinned pipelines.
+  async setTrayPipelines(_pipelineIds: string[] | null): Promise<StatusSummary> {
+    return this.getTraySummary()
+  }
+
+  // The server has no window to close, so background mode never applies.
+  async getRunInBackground(): Promise<boolean> {
+    return false
+  }
+
+  setRunInBackground(_enabled: boolean): Promise<void> {
+    return Promise.resolve()
+  }
+
+  // Web links are plain /p/ URLs that the server redirects.
+  async takePendingDeepLink(): Promise<string | null> {
+    return null
+  }
+
   async cancelPipelineRun(
     pipelineId: string,
     runNumber: number
@@ -439,6 +598,27 @@ return result.mode as 'active' | 'idle'
     )
   }
 
+  async listPendingApprovals(
+    pipelineId: string,
+    runNumber: number
+  ): Promise<PendingApproval[]> {
+    return this.get<PendingApproval[]>(
+      `/pipelines/${encodeURIComponent(pipelineId)}/runs/${runNumber}/approvals`
+    )
+  }
+
+  async unblockJob(
+    pipelineId: string,
+    runNumber: number,
+    jobId: string,
+    fields: Record<string, string>
+  ): Promise<void> {
+    await this.post(
+      `/pipelines/${encodeURIComponent(pipelineId)}/runs/${runNumber}/jobs/${encodeURIComponent(jobId)}/unblock`,
+      { fields }
+    )
+  }
+
   async openUrl(url: string): Promise<void> {
     window.open(url, '_blank')
   }
@@ -569,6 +749,12 @@ return result.cleared
 return result.flushed
   }
 
+  async rebuildMetrics(pipelineId?: string): Promise<MetricsRebuild> {
+    return this.post<MetricsRebuild>('/metrics/rebuild', {
+      pipeline_id: pipelineId ?? null,
+    })
+  }
+
   async getTablePreferences(
     providerId: number,
     tableId: string
@@ -646,6 +832,14 @@ return result.flushed
     await this.put('/storage/config/content', { content })
   }
 
+  async listConfigVersions(): Promise<ConfigVersion[]> {
+    return this.get<ConfigVersion[]>('/storage/config/versions')
+  }
+
+  async restoreConfigVersion(versionId: string): Promise<void> {
+    await this.post(`/storage/config/versions/${encodeURIComponent(versionId)}/restore`)
+  }
+
   async analyzeConfig(content: string): Promise<ConfigAnalysisResponse> {
     return this.post<ConfigAnalysisResponse>('/storage/config/analyze', {
       new_content: content,
@@ -725,6 +919,56 @@ return result.flushed
     return this.executeStorageMigration(plan, options)
   }
 
+  async listNotifications(unreadOnly = false): Promise<AppNotification[]> {
+    return this.get<AppNotification[]>(`/notifications?unread_only=${unreadOnly}`)
+  }
+
+  async getUnreadNotificationCount(): Promise<number> {
+    const response = await this.get<{ unread: number }>('/notifications/unread-count')
+
+    return response.unread
+  }
+
+  async markNotificationRead(id?: number): Promise<void> {
+    return this.post<void>(id === undefined ? '/notifications/read' : `/notifications/${id}/read`)
+  }
+
+  async clearNotifications(): Promise<void> {
+    return this.delete<void>('/notifications')
+  }
+
+  async listTriggerApprovals(pendingOnly = false): Promise<TriggerApproval[]> {
+    return this.get<TriggerApproval[]>(`/approvals?pending_only=${pendingOnly}`)
+  }
+
+  async approveTrigger(id: number, confirmed = false, comment?: string): Promise<TriggerApproval> {
+    return this.post<TriggerApproval>(`/approvals/${id}/approve`, { confirmed, comment })
+  }
+
+  async rejectTrigger(id: number, comment?: string): Promise<TriggerApproval> {
+    return this.post<TriggerApproval>(`/approvals/${id}/reject`, { comment })
+  }
+
+  async listApprovalRules(): Promise<string[]> {
+    return this.get<string[]>('/approvals/rules')
+  }
+
+  async setPipelineApproval(pipelineId: string, required: boolean): Promise<void> {
+    return this.post<void>('/approvals/rules', { pipeline_id: pipelineId, required })
+  }
+
+  async listMaintenanceWindows(): Promise<MaintenanceWindow[]> {
+    return this.get<MaintenanceWindow[]>('/maintenance')
+  }
+
+  async saveMaintenanceWindow(window: MaintenanceWindowInput): Promise<MaintenanceWindow> {
+    return this.post<MaintenanceWindow>('/maintenance', window)
+  }
+
+  async deleteMaintenanceWindow(id: number): Promise<void> {
+    return this.delete<void>(`/maintenance/${id}`)
+  }
+
   async factoryReset(): Promise<{
     providers_removed: number
     caches_cleared: boolean
@@ -734,6 +978,67 @@ return result.flushed
     return this.post('/factory-reset')
   }
 
+  // The server includes its own startup error, so `startupError` is unused
+  // here. Resolves to the downloaded file name.
+  async createDiagnosticBundle(_startupError?: string): Promise<string | null> {
+    const token = getToken()
+    const headers: Record<string, string> = {}
+
+    if (token) {
+      headers.Authorization = `Bearer ${token}`
+    }
+    const res = await this.fetchWithTimeout(
+      `${API_BASE}/diagnostics/bundle`,
+      { method: 'GET', headers },
+      API_TIMEOUTS.EXTENDED
+    )
+
+    if (!res.ok) {
+      throw new Error((await res.text()) || `HTTP ${res.status}`)
+    }
+
+    const disposition = res.headers.get('Content-Disposition') ?? ''
+    const fileName = disposition.match(/filename="([^"]+)"/)?.[1] ?? 'pipedash-diagnostics.zip'
+    const url = URL.createObjectURL(await res.blob())
+    const link = document.createElement('a')
+
+    link.href = url
+    link.download = fileName
+    link.click()
+    URL.revokeObjectURL(url)
+
+    return fileName
+  }
+
+  async getAppLogs(query: AppLogQuery = {}): Promise<AppLogEntry[]> {
+    const params = new URLSearchParams()
+
+    if (query.level) {
+      params.set('level', query.level)
+    }
+    if (query.since) {
+      params.set('since', query.since)
+    }
+    if (query.until) {
+      params.set('until', query.until)
+    }
+    if (query.limit !== undefined) {
+      params.set('limit', String(query.limit))
+    }
+
+    return this.get<AppLogEntry[]>(`/logs?${params}`)
+  }
+
+  async getLogLevel(): Promise<string> {
+    const response = await this.get<{ filter: string }>('/logs/level')
+
+    return response.filter
+  }
+
+  async setLogLevel(filter: string): Promise<void> {
+    await this.put('/logs/level', { filter })
+  }
+
   async restartApp(): Promise<void> {
     window.location.reload()
   }
@@ -2,16 +2,26 @@ import { invoke } from '@tauri-apps/api/core'
 import { openUrl } from '@tauri-apps/plugin-opener'
 
 import {
+  type ActivityHint,
   type AggregatedMetrics,
+  type AppLogEntry,
+  type AppLogQuery,
+  type AppNotification,
   type AggregationPeriod,
   type AggregationType,
+  type BranchBoard,
+  type CommitStatus,
   type ConfigAnalysisResponse,
   type ConfigContentResponse,
+  type ConfigVersion,
   createError,
   type FeatureAvailability,
   type GlobalMetricsConfig,
+  type MaintenanceWindow,
+  type MaintenanceWindowInput,
   type MetricEntry,
   type MetricsConfig,
+  type MetricsRebuild,
   type MetricsStats,
   type MetricType,
   type MigrationOptions,
@@ -19,19 +29,29 @@ import {
   type MigrationResult,
   type Organization,
   type PaginatedAvailablePipelines,
+  type PaginatedResponse,
   type PaginatedRunHistory,
+  type PendingApproval,
+  type PinnedPipeline,
   type PermissionCheckResult,
   type PermissionStatus,
   type PipedashConfig,
   type Pipeline,
+  type PipelinePreviewRequest,
   type PipelineRun,
   type PluginMetadata,
   type ProviderConfig,
+  type ProviderDiagnosis,
   type ProviderSummary,
+  type QuickAction,
+  type ReleaseReport,
+  type RunBackfillJob,
   type SetupStatus,
+  type StatusSummary,
   type StorageConfigResponse,
   type StoragePathsResponse,
   toPipedashError,
+  type TriggerApproval,
   type TriggerParams,
   type UnlockVaultResponse,
   type ValidationResult,
@@ -46,6 +66,7 @@ const TAURI_COMMAND_TIMEOUTS: Record<string, number> = {
   get_cached_pipelines: 90000,
   fetch_pipelines: 90000,
   preview_provider_pipelines: 60000,
+  start_pipeline_preview: 60000,
   fetch_provider_organizations: 60000,
   add_provider: 120000,
   default: 45000,
@@ -138,6 +159,46 @@ return { ...config, id }
     }
   },
 
+  enableProvider: async (id: number): Promise<void> => {
+    try {
+      return await invoke<void>('enable_provider', { id })
+    } catch (error) {
+      throw toPipedashError(error)
+    }
+  },
+
+  disableProvider: async (id: number): Promise<void> => {
+    try {
+      return await invoke<void>('disable_provider', { id })
+    } catch (error) {
+      throw toPipedashError(error)
+    }
+  },
+
+  startRunBackfill: async (id: number, depth?: number): Promise<RunBackfillJob> => {
+    try {
+      return await invoke<RunBackfillJob>('start_run_backfill', { id, depth })
+    } catch (error) {
+      throw toPipedashError(error)
+    }
+  },
+
+  cancelRunBackfill: async (id: number): Promise<RunBackfillJob> => {
+    try {
+      return await invoke<RunBackfillJob>('cancel_run_backfill', { id })
+    } catch (error) {
+      throw toPipedashError(error)
+    }
+  },
+
+  listRunBackfills: async (): Promise<RunBackfillJob[]> => {
+    try {
+      return await invoke<RunBackfillJob[]>('list_run_backfills')
+    } catch (error) {
+      throw toPipedashError(error)
+    }
+  },
+
   removeProvider: async (id: number): Promise<void> => {
     try {
       return await invoke<void>('remove_provider', { id })
@@ -174,6 +235,14 @@ return { ...config, id }
     })
   },
 
+  startPipelinePreview: async (request: PipelinePreviewRequest): Promise<string> => {
+    return invokeWithTimeout<string>('start_pipeline_preview', { request })
+  },
+
+  cancelPipelinePreview: async (previewId: string): Promise<boolean> => {
+    return invokeWithTimeout<boolean>('cancel_pipeline_preview', { previewId })
+  },
+
   getProviderFieldOptions: async (
     providerType: string,
     fieldKey: string,
@@ -206,6 +275,26 @@ return { ...config, id }
     }
   },
 
+  discoverProviderRepositories: async (providerId: number): Promise<string[]> => {
+    return invokeWithTimeout<string[]>('discover_provider_repositories', { providerId }, 120000)
+  },
+
+  diagnoseProvider: async (providerId: number): Promise<ProviderDiagnosis> => {
+    try {
+      return await invoke<ProviderDiagnosis>('diagnose_provider', { providerId })
+    } catch (error) {
+      throw toPipedashError(error)
+    }
+  },
+
+  getProviderDiagnoses: async (): Promise<ProviderDiagnosis[]> => {
+    try {
+      return await invoke<ProviderDiagnosis[]>('get_provider_diagnoses')
+    } catch (error) {
+      throw toPipedashError(error)
+    }
+  },
+
   getProviderTableSchema: async (providerId: number): Promise<any> => {
     try {
       return await invoke('get_provider_table_schema', { providerId })
@@ -270,11 +359,23 @@ return { ...config, id }
     return withRetry(
       async () => {
         try {
-          const result = await invokeWithTimeout<Pipeline[]>('get_cached_pipelines', {
-            providerId: providerId ?? null,
-          })
+          const result: Pipeline[] = []
+          let page = 1
+
+          for (;;) {
+            const response = await invokeWithTimeout<PaginatedResponse<Pipeline>>('get_cached_pipelines', {
+              query: { provider_id: providerId ?? null, page, page_size: 1000 },
+            })
+
+            result.push(...response.items)
 
-          console.debug('[tauriService] get_cached_pipelines returned:', result?.length ?? 0, 'items')
+            if (!response.has_more) {
+              break
+            }
+            page += 1
+          }
+
+          console.debug('[tauriService] get_cached_pipelines returned:', result.length, 'items')
 
           return result
         } catch (err) {
@@ -318,6 +419,10 @@ return { ...config, id }
     return invoke<'active' | 'idle'>('get_refresh_mode')
   },
 
+  setRefreshActivityHint: async (hint: ActivityHint): Promise<void> => {
+    return invoke<void>('set_refresh_activity_hint', { hint })
+  },
+
   getWorkflowRunDetails: async (
     pipelineId: string,
     runNumber: number
@@ -328,6 +433,66 @@ return { ...config, id }
     })
   },
 
+  fetchBranchStatuses: async (
+    pipelineId: string,
+    refresh = false
+  ): Promise<BranchBoard> => {
+    return invoke<BranchBoard>('fetch_branch_statuses', { pipelineId, refresh })
+  },
+
+  getCommitStatus: async (sha: string): Promise<CommitStatus> => {
+    return invoke<CommitStatus>('get_commit_status', { sha })
+  },
+
+  generateReleaseReport: async (
+    pipelineId: string,
+    fromRun: number,
+    toRun: number
+  ): Promise<ReleaseReport> => {
+    return invoke<ReleaseReport>('generate_release_report', { pipelineId, fromRun, toRun })
+  },
+
+  listPinnedPipelines: async (): Promise<PinnedPipeline[]> => {
+    return invoke<PinnedPipeline[]>('list_pinned_pipelines')
+  },
+
+  pinPipeline: async (pipelineId: string): Promise<PinnedPipeline> => {
+    return invoke<PinnedPipeline>('pin_pipeline', { pipelineId })
+  },
+
+  unpinPipeline: async (pipelineId: string): Promise<void> => {
+    return invoke<void>('unpin_pipeline', { pipelineId })
+  },
+
+  // Recently used pipelines without a query.
+  quickActions: async (query?: string, limit?: number): Promise<QuickAction[]> => {
+    return invoke<QuickAction[]>('quick_actions', { query, limit })
+  },
+
+  getTraySummary: async (): Promise<StatusSummary> => {
+    return invoke<StatusSummary>('get_tray_summary')
+  },
+
+  // Pass null to follow the pinned pipelines again.
+  setTrayPipelines: async (
+    pipelineIds: string[] | null
+  ): Promise<StatusSummary> => {
+    return invoke<StatusSummary>('set_tray_pipelines', { pipelineIds })
+  },
+
+  getRunInBackground: async (): Promise<boolean> => {
+    return invoke<boolean>('get_run_in_background')
+  },
+
+  setRunInBackground: async (enabled: boolean): Promise<void> => {
+    return invoke<void>('set_run_in_background', { enabled })
+  },
+
+  // Route of the last pipedash:// link opened, if not yet handled.
+  takePendingDeepLink: async (): Promise<string | null> => {
+    return invoke<string | null>('take_pending_deep_link')
+  },
+
   cancelPipelineRun: async (
     pipelineId: string,
     runNumber: number
@@ -338,6 +503,30 @@ return { ...config, id }
     })
   },
 
+  listPendingApprovals: async (
+    pipelineId: string,
+    runNumber: number
+  ): Promise<PendingApproval[]> => {
+    return invoke<PendingApproval[]>('list_pending_approvals', {
+      pipelineId,
+      runNumber,
+    })
+  },
+
+  unblockJob: async (
+    pipelineId: string,
+    runNumber: number,
+    jobId: string,
+    fields: Record<string, string>
+  ): Promise<void> => {
+    return invoke<void>('unblock_job', {
+      pipelineId,
+      runNumber,
+      jobId,
+      fields,
+    })
+  },
+
   openUrl: async (url: string): Promise<void> => {
     await openUrl(url)
   },
@@ -426,6 +615,12 @@ return { ...config, id }
     })
   },
 
+  rebuildMetrics: async (pipelineId?: string): Promise<MetricsRebuild> => {
+    return invoke<MetricsRebuild>('rebuild_metrics', {
+      pipelineId: pipelineId ?? null,
+    })
+  },
+
   getCacheStats: async (): Promise<{
     pipelines_count: number
     run_history_count: number
@@ -514,6 +709,14 @@ return { ...config, id }
     return invoke<void>('save_config_content', { content })
   },
 
+  listConfigVersions: async (): Promise<ConfigVersion[]> => {
+    return invoke<ConfigVersion[]>('list_config_versions')
+  },
+
+  restoreConfigVersion: async (versionId: string): Promise<void> => {
+    return invoke<void>('restore_config_version', { versionId })
+  },
+
   analyzeConfig: async (content: string): Promise<ConfigAnalysisResponse> => {
     return invoke<ConfigAnalysisResponse>('analyze_config', { content })
   },
@@ -565,6 +768,54 @@ return { ...config, id }
     })
   },
 
+  listNotifications: async (unreadOnly = false): Promise<AppNotification[]> => {
+    return invoke<AppNotification[]>('list_notifications', { unreadOnly })
+  },
+
+  getUnreadNotificationCount: async (): Promise<number> => {
+    return invoke<number>('get_unread_notification_count')
+  },
+
+  markNotificationRead: async (id?: number): Promise<void> => {
+    return invoke<void>('mark_notification_read', { id: id ?? null })
+  },
+
+  clearNotifications: async (): Promise<void> => {
+    return invoke<void>('clear_notifications')
+  },
+
+  listTriggerApprovals: async (pendingOnly = false): Promise<TriggerApproval[]> => {
+    return invoke<TriggerApproval[]>('list_trigger_approvals', { pendingOnly, limit: null })
+  },
+
+  approveTrigger: async (id: number, confirmed = false, comment?: string): Promise<TriggerApproval> => {
+    return invoke<TriggerApproval>('approve_trigger', { id, confirmed, comment: comment ?? null })
+  },
+
+  rejectTrigger: async (id: number, comment?: string): Promise<TriggerApproval> => {
+    return invoke<TriggerApproval>('reject_trigger', { id, comment: comment ?? null })
+  },
+
+  listApprovalRules: async (): Promise<string[]> => {
+    return invoke<string[]>('list_approval_rules')
+  },
+
+  setPipelineApproval: async (pipelineId: string, required: boolean): Promise<void> => {
+    return invoke<void>('set_pipeline_approval', { pipelineId, required })
+  },
+
+  listMaintenanceWindows: async (): Promise<MaintenanceWindow[]> => {
+    return invoke<MaintenanceWindow[]>('list_maintenance_windows')
+  },
+
+  saveMaintenanceWindow: async (window: MaintenanceWindowInput): Promise<MaintenanceWindow> => {
+    return invoke<MaintenanceWindow>('save_maintenance_window', { window })
+  },
+
+  deleteMaintenanceWindow: async (id: number): Promise<void> => {
+    return invoke<void>('delete_maintenance_window', { id })
+  },
+
   factoryReset: async (): Promise<{
     providers_removed: number
     caches_cleared: boolean
@@ -574,6 +825,30 @@ return { ...config, id }
     return invokeWithTimeout('factory_reset')
   },
 
+  // Resolves to the saved path, or null when the save dialog was cancelled.
+  createDiagnosticBundle: async (startupError?: string): Promise<string | null> => {
+    return invoke<string | null>('create_diagnostic_bundle', {
+      startupError: startupError ?? null,
+    })
+  },
+
+  getAppLogs: async (query: AppLogQuery = {}): Promise<AppLogEntry[]> => {
+    return invoke<AppLogEntry[]>('get_app_logs', {
+      level: query.level ?? null,
+      since: query.since ?? null,
+      until: query.until ?? null,
+      limit: query.limit ?? null,
+    })
+  },
+
+  getLogLevel: async (): Promise<string> => {
+    return invoke<string>('get_log_level')
+  },
+
+  setLogLevel: async (filter: string): Promise<void> => {
+    return invoke<void>('set_log_level', { filter })
+  },
+
   restartApp: async (): Promise<void> => {
     return invoke<void>('restart_app')
   },
@@ -7,7 +7,10 @@ export const STATUS_COLORS: Record<PipelineStatus, MantineColor> = {
   failed: 'red',
   running: 'cyan',
   pending: 'yellow',
+  queued: 'yellow',
   cancelled: 'gray',
   skipped: 'gray',
+  waiting: 'orange',
+  neutral: 'gray',
 } as const
 
@@ -1,4 +1,10 @@
-import type { Pipeline, ProviderSummary } from './index'
+import type {
+  PaginatedAvailablePipelines,
+  Pipeline,
+  ProviderSummary,
+  RunBackfillJob,
+  TriggerApproval,
+} from './index'
 
 export interface PipelinesUpdatedPayload {
   pipelines: Pipeline[]
@@ -14,7 +20,7 @@ export interface ProviderChangedPayload {
 
 export interface CacheInvalidatedPayload {
   providerId?: number
-  reason: 'fetch' | 'provider-change' | 'manual-refresh'
+  reason: 'fetch' | 'provider-change' | 'manual-refresh' | 'remote'
 }
 
 export interface RefreshStatusPayload {
@@ -22,6 +28,29 @@ export interface RefreshStatusPayload {
   providerId?: number
 }
 
+export type ConfigChangedPayload =
+  | { kind: 'reloaded'; changed_keys: string[]; restart_required: string[]; summary: string }
+  | { kind: 'rejected'; errors: string[]; summary: string }
+  | { kind: 'value_changed' | 'provider_added' | 'provider_updated' | 'provider_removed'; summary: string }
+
+export interface PreviewPagePayload {
+  preview_id: string
+  page: PaginatedAvailablePipelines
+}
+
+export interface PreviewFinishedPayload {
+  preview_id: string
+  total: number
+  pages: number
+  cancelled: boolean
+  error: string | null
+}
+
+export interface PipelinesDiscoveredPayload {
+  provider_id: number
+  repositories: string[]
+}
+
 export type EventPayloadMap = {
   'pipelines-updated': PipelinesUpdatedPayload
   'provider-added': ProviderChangedPayload
@@ -29,4 +58,10 @@ export type EventPayloadMap = {
   'provider-removed': ProviderChangedPayload
   'pipeline-cache-invalidated': CacheInvalidatedPayload
   'refresh-status': RefreshStatusPayload
+  'config-changed': ConfigChangedPayload
+  'trigger-approvals-changed': TriggerApproval
+  'run-backfill-progress': RunBackfillJob
+  'preview-page': PreviewPagePayload
+  'preview-finished': PreviewFinishedPayload
+  'pipelines-discovered': PipelinesDiscoveredPayload
 }
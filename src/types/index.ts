@@ -3,8 +3,11 @@ export type PipelineStatus =
   | 'failed'
   | 'running'
   | 'pending'
+  | 'queued'
   | 'cancelled'
-  | 'skipped';
+  | 'skipped'
+  | 'waiting'
+  | 'neutral';
 
 export interface AvailablePipeline {
   id: string;
@@ -47,11 +50,110 @@ export interface PipelineRun {
   commit_message: string | null;
   branch: string | null;
   actor: string | null;
+  commit_author?: string;
+  author_avatar_url?: string;
+  pull_request?: PullRequestRef;
   inputs?: Record<string, any>;
   metadata?: Record<string, any>;
+  annotations?: RunAnnotation[];
   [key: string]: unknown;
 }
 
+export type AnnotationLevel = 'info' | 'success' | 'warning' | 'error';
+
+export interface RunAnnotation {
+  level: AnnotationLevel;
+  title?: string;
+  summary: string;
+  url?: string;
+}
+
+// A step holding a run until someone unblocks it, such as a Buildkite block
+// step. Runs waiting on one carry its label in `metadata.blocked_step`.
+export interface PendingApproval {
+  job_id: string;
+  label: string;
+  prompt?: string;
+  fields: WorkflowParameter[];
+}
+
+// A GitHub environment a waiting run is held by, from run metadata.
+export interface EnvironmentProtection {
+  environment: string;
+  environment_url: string | null;
+  reviewers: string[];
+  wait_timer_minutes: number;
+  wait_timer_ends_at: string | null;
+  can_approve: boolean;
+}
+
+export interface BranchStatus {
+  branch: string;
+  status: PipelineStatus;
+  run_number: number;
+  started_at: string;
+  logs_url: string;
+  commit_sha?: string;
+  actor?: string;
+  pull_request?: PullRequestRef;
+}
+
+export interface BranchBoard {
+  pipeline_id: string;
+  branches: BranchStatus[];
+  fetched_at: string;
+}
+
+export interface CommitRun {
+  pipeline_id: string;
+  pipeline_name: string;
+  provider_id: number;
+  provider_type: string;
+  run: PipelineRun;
+}
+
+// Latest run of a commit on every cached pipeline that built it.
+export interface CommitStatus {
+  sha: string;
+  status: PipelineStatus;
+  green: boolean;
+  total: number;
+  failed: number;
+  running: number;
+  runs: CommitRun[];
+}
+
+export interface ReleaseCommit {
+  sha: string;
+  title: string;
+  author?: string;
+  committed_at?: string;
+  url?: string;
+  pull_request?: number;
+  issues?: string[];
+}
+
+// What shipped between two deployment runs.
+export interface ReleaseReport {
+  pipeline_id: string;
+  pipeline_name: string;
+  repository: string;
+  from_run: PipelineRun;
+  to_run: PipelineRun;
+  commits_available: boolean;
+  commits: ReleaseCommit[];
+  pull_requests: Array<{ number: number; title: string; url?: string }>;
+  contributors: Array<{ name: string; commits: number }>;
+  issues: string[];
+  generated_at: string;
+}
+
+export interface PullRequestRef {
+  number: number;
+  title?: string;
+  url?: string;
+}
+
 export interface PaginatedResponse<T> {
   items: T[];
   page: number;
@@ -63,6 +165,16 @@ export interface PaginatedResponse<T> {
 
 export type PaginatedAvailablePipelines = PaginatedResponse<AvailablePipeline>;
 
+// Starts a streamed preview; pages arrive as `preview-page` events.
+export interface PipelinePreviewRequest {
+  preview_id?: string;
+  provider_type: string;
+  config: Record<string, string>;
+  org?: string | null;
+  search?: string | null;
+  page_size?: number;
+}
+
 export interface PaginatedRunHistory {
   runs: PipelineRun[];
   total_count: number;
@@ -96,6 +208,70 @@ export interface ProviderSummary {
   last_fetch_status: FetchStatus;
   last_fetch_error: string | null;
   last_fetch_at: string | null;
+  server_version: ServerVersion | null;
+  enabled: boolean;
+}
+
+export interface ServerVersion {
+  version: string;
+  supported: boolean;
+  warning: string | null;
+  detected_at: string;
+}
+
+export interface ProviderHealthCheck {
+  success: boolean;
+  error: string | null;
+  checked_at: string;
+}
+
+export interface ProviderHealth {
+  provider_id: number;
+  name: string;
+  provider_type: string;
+  score: number | null;
+  consecutive_failures: number;
+  outage: boolean;
+  down_since: string | null;
+  last_success_at: string | null;
+  last_error: string | null;
+  checks: ProviderHealthCheck[];
+  circuit_breaker: CircuitBreakerStatus;
+}
+
+export type CircuitState = 'closed' | 'open' | 'half_open';
+
+export interface CircuitBreakerStatus {
+  state: CircuitState;
+  consecutive_failures: number;
+  opened_at: string | null;
+  retry_at: string | null;
+}
+
+export type DiagnosticCheck =
+  | 'auth'
+  | 'permissions'
+  | 'reachability'
+  | 'clock_skew'
+  | 'rate_limit'
+  | 'server_version';
+
+export type FindingSeverity = 'ok' | 'info' | 'warning' | 'error';
+
+export interface DiagnosticFinding {
+  check: DiagnosticCheck;
+  severity: FindingSeverity;
+  message: string;
+  suggestion?: string;
+}
+
+export interface ProviderDiagnosis {
+  provider_id: number;
+  provider_name: string;
+  provider_type: string;
+  severity: FindingSeverity;
+  findings: DiagnosticFinding[];
+  checked_at: string;
 }
 
 export interface TriggerParams {
@@ -103,6 +279,267 @@ export interface TriggerParams {
   inputs?: Record<string, any>;
 }
 
+export type ApprovalStatus = 'pending' | 'approved' | 'rejected' | 'failed';
+
+export interface TriggerApproval {
+  id: number;
+  pipeline_id: string;
+  pipeline_name: string;
+  params: TriggerParams;
+  status: ApprovalStatus;
+  requested_by: string;
+  requested_at: string;
+  decided_by?: string;
+  decided_at?: string;
+  comment?: string;
+  result?: string;
+}
+
+export type BackfillStatus = 'running' | 'completed' | 'cancelled' | 'failed';
+
+export interface RunBackfillJob {
+  provider_id: number;
+  depth: number;
+  status: BackfillStatus;
+  pending_pipelines: string[];
+  pipelines_total: number;
+  runs_fetched: number;
+  runs_target: number;
+  started_at: string;
+  updated_at: string;
+  error?: string;
+}
+
+export type MaintenanceSchedule =
+  | { kind: 'once'; start: string; end: string }
+  | {
+      kind: 'weekly';
+      // 'Mon'..'Sun'; every day when empty.
+      days: string[];
+      start: string;
+      end: string;
+      utc_offset_minutes: number;
+    };
+
+export interface MaintenanceWindow {
+  id: number;
+  name: string;
+  pipeline_ids?: string[];
+  group_ids?: number[];
+  schedule: MaintenanceSchedule;
+  reason?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export type MaintenanceWindowInput = Pick<
+  MaintenanceWindow,
+  'name' | 'pipeline_ids' | 'group_ids' | 'schedule' | 'reason'
+>;
+
+// Set on pipeline.metadata.maintenance while a window is active.
+export interface ActiveMaintenance {
+  window_id: number;
+  name: string;
+  until: string;
+  reason?: string;
+}
+
+export interface RunRef {
+  pipeline_id: string;
+  run_number: number;
+}
+
+export type BulkOperation = 'trigger' | 'cancel' | 'retry';
+
+export interface BulkItemResult {
+  pipeline_id: string;
+  run_number: number | null;
+  success: boolean;
+  run_id: string | null;
+  error: string | null;
+}
+
+export interface BulkProgressEvent {
+  operation: BulkOperation;
+  completed: number;
+  total: number;
+  item: BulkItemResult;
+}
+
+export interface PipelineDependency {
+  id: number;
+  upstream_id: string;
+  downstream_id: string;
+  trigger_on: PipelineStatus;
+  inputs: Record<string, any>;
+  created_by_role?: 'viewer' | 'operator' | 'admin' | null;
+  created_at: string;
+}
+
+export interface PipelineGraphNode {
+  pipeline_id: string;
+  name: string | null;
+  status: PipelineStatus | null;
+}
+
+export interface PipelineGraph {
+  nodes: PipelineGraphNode[];
+  edges: PipelineDependency[];
+}
+
+export type AppNotificationKind =
+  | 'failure'
+  | 'recovery'
+  | 'token_expiry'
+  | 'sync_conflict'
+  | 'provider_outage'
+  | 'provider_diagnostics';
+
+export interface AppNotification {
+  id: number;
+  kind: AppNotificationKind;
+  title: string;
+  message: string;
+  pipeline_id: string | null;
+  provider_id: number | null;
+  read: boolean;
+  created_at: string;
+}
+
+export type ChannelKind = 'slack' | 'teams' | 'discord' | 'webhook' | 'email';
+
+export interface NotificationRule {
+  pipeline_ids?: string[];
+  group_ids?: number[];
+  statuses?: PipelineStatus[];
+  anomalies?: boolean;
+}
+
+export interface QuietHours {
+  start: string;
+  end: string;
+  utc_offset_minutes: number;
+}
+
+export interface NotificationSuppression {
+  quiet_hours?: QuietHours;
+  dedup_window_minutes: number;
+  transitions_only: boolean;
+}
+
+export interface NotificationChannel {
+  id: number;
+  name: string;
+  kind: ChannelKind;
+  url: string;
+  enabled: boolean;
+  rules: NotificationRule[];
+  suppression: NotificationSuppression;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface NotificationDelivery {
+  channel_id: number;
+  pipeline_id: string | null;
+  status: PipelineStatus | null;
+  success: boolean;
+  error: string | null;
+  delivered_at: string;
+}
+
+export interface TriggerPreset {
+  id: number;
+  pipeline_id: string;
+  name: string;
+  branch: string | null;
+  parameters: Record<string, any>;
+  created_at: string;
+  updated_at: string;
+}
+
+export type ActivityHint =
+  | { kind: 'window_visible' }
+  | { kind: 'window_hidden' }
+  | { kind: 'pipeline_opened'; pipeline_id: string }
+  | { kind: 'pipeline_closed' }
+
+export interface PipelineRefreshSetting {
+  pipeline_id: string;
+  refresh_interval_secs: number | null;
+  snoozed: boolean;
+  updated_at: string;
+}
+
+export interface PinnedPipeline {
+  pipeline_id: string;
+  position: number;
+  unpinned: boolean;
+  updated_at: string;
+}
+
+export interface StatusSummary {
+  status: PipelineStatus;
+  total: number;
+  failed: number;
+  running: number;
+  missing: number;
+  maintenance: number;
+  last_run: string | null;
+  pipelines: Pipeline[];
+}
+
+export type PaletteAction =
+  | 'open'
+  | 'view_metrics'
+  | 'view_branches'
+  | 'trigger'
+  | 'cancel'
+  | 'retry_failed_jobs'
+  | 'pin'
+  | 'unpin';
+
+export interface ActionAvailability {
+  action: PaletteAction;
+  available: boolean;
+  missing_permissions?: string[];
+}
+
+export interface QuickAction {
+  pipeline_id: string;
+  pipeline_name: string;
+  repository: string;
+  provider_id: number;
+  provider_type: string;
+  status: PipelineStatus;
+  score?: number;
+  last_used_at?: string;
+  actions: ActionAvailability[];
+}
+
+export interface ProviderQueue {
+  provider_id: number;
+  metrics_id: string;
+  id: string;
+  waiting: number;
+  running: number;
+  avg_wait_time: number | null;
+}
+
+export type AgentStatus = 'busy' | 'idle' | 'paused' | 'offline';
+
+export interface ProviderAgent {
+  provider_id: number;
+  id: string;
+  name: string;
+  hostname: string;
+  status: AgentStatus;
+  job_id: string | null;
+  last_seen: string;
+  metadata: Record<string, string>;
+}
+
 interface PluginCapabilities {
   pipelines: boolean;
   pipeline_runs: boolean;
@@ -111,8 +548,18 @@ interface PluginCapabilities {
   artifacts: boolean;
   queues: boolean;
   custom_tables: boolean;
+  logs: boolean;
+  retry_failed_jobs: boolean;
+  branch_statuses: boolean;
+  cancel: boolean;
+  workflow_parameters: boolean;
+  unblock_jobs: boolean;
+  repository_discovery: boolean;
+  pagination: PaginationStyle;
 }
 
+type PaginationStyle = 'client' | 'server';
+
 type ConfigFieldType = 'Text' | 'TextArea' | 'Password' | 'Number' | 'Select' | 'Checkbox';
 
 export interface ConfigField {
@@ -188,6 +635,7 @@ export interface TableDefinition {
 
 export interface TableSchema {
   tables: TableDefinition[];
+  capabilities?: PluginCapabilities;
 }
 
 export interface PluginMetadata {
@@ -238,6 +686,7 @@ export interface FeatureAvailability {
 export interface ValidationResult {
   valid: boolean;
   error: string | null;
+  server_version: ServerVersion | null;
 }
 
 export interface PermissionCheckResult {
@@ -255,7 +704,15 @@ export interface WorkflowParameter {
   required: boolean;
 }
 
-export type MetricType = 'run_duration' | 'success_rate' | 'run_frequency';
+export type MetricType =
+  | 'run_duration'
+  | 'success_rate'
+  | 'run_frequency'
+  | 'queue_wait_time'
+  | 'queue_depth'
+  | 'estimated_cost'
+  | 'agent_wait_time'
+  | 'billable_minutes';
 
 export type AggregationPeriod = 'hourly' | 'daily' | 'weekly' | 'monthly';
 
@@ -325,6 +782,29 @@ export interface MetricsStats {
   by_pipeline: PipelineMetricsStats[];
 }
 
+export interface MetricsRebuild {
+  pipelines: number;
+  runs: number;
+  metrics: number;
+}
+
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+export interface AppLogEntry {
+  timestamp: string;
+  level: LogLevel;
+  target: string;
+  message: string;
+  fields?: Record<string, unknown>;
+}
+
+export interface AppLogQuery {
+  level?: LogLevel;
+  since?: string;
+  until?: string;
+  limit?: number;
+}
+
 export type StorageBackendType = 'sqlite' | 'postgres';
 
 export interface PostgresSettings {
@@ -347,10 +827,33 @@ export interface ServerConfig {
   cors_allow_all: boolean;
 }
 
+export type SmtpSecurity = 'starttls' | 'tls' | 'none';
+
+export interface SmtpConfig {
+  host: string;
+  port: number;
+  security: SmtpSecurity;
+  username?: string;
+  password?: string;
+  from: string;
+  subject_template?: string;
+  body_template?: string;
+}
+
+export interface NotificationsConfig {
+  smtp?: SmtpConfig;
+}
+
+export interface DesktopConfig {
+  run_in_background: boolean;
+}
+
 export interface PipedashConfig {
   general: GeneralConfig;
   server: ServerConfig;
   storage: StorageConfig;
+  notifications?: NotificationsConfig;
+  desktop?: DesktopConfig;
   providers?: ProviderFileConfig[];
 }
 
@@ -473,6 +976,12 @@ export interface ConfigContentResponse {
   path: string;
 }
 
+export interface ConfigVersion {
+  id: string;
+  created_at: string;
+  size_bytes: number;
+}
+
 export interface StoragePathsResponse {
   config_file: string;
   pipedash_db: string;